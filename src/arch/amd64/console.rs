@@ -1,6 +1,7 @@
 use crate::platform::PlatformManager;
 use core::fmt::{self, Write};
 use core::sync::atomic::{AtomicBool, Ordering};
+use crate::uart::{self, FlowControl, TxQueue, UartTx};
 
 // Seri Port (COM1) I/O Port Adresi. x86/AMD64'te standarttır.
 const COM1_PORT: u16 = 0x3F8;
@@ -10,6 +11,29 @@ const DATA_PORT: u16 = 0; // Veri yazma/okuma
 const FIFO_CTRL_PORT: u16 = 2; // FIFO Kontrol Yazmacı
 const LINE_CTRL_PORT: u16 = 3; // Hat Kontrol Yazmacı
 const LINE_STATUS_PORT: u16 = 5; // Hat Durumu Yazmacı
+const INT_ENABLE_PORT: u16 = 1; // Kesme Etkinleştirme Yazmacı (IER, DLAB=0)
+const INT_ID_PORT: u16 = 2; // Kesme Kimlik Yazmacı (IIR, okuma)
+const MODEM_CTRL_PORT: u16 = 4; // Modem Kontrol Yazmacı (MCR)
+const MODEM_STATUS_PORT: u16 = 6; // Modem Durum Yazmacı (MSR)
+
+// IER bitleri
+const IER_THRE: u8 = 1 << 1; // Gönderim tutucu boş kesmesi
+const IER_MODEM_STATUS: u8 = 1 << 3; // CTS değişim kesmesi
+
+// MCR bitleri
+const MCR_DTR: u8 = 1 << 0;
+const MCR_RTS: u8 = 1 << 1;
+const MCR_OUT2: u8 = 1 << 3; // PC uyumlu kartlarda IRQ hattını açar
+const MCR_AUTO_FLOW: u8 = 1 << 5; // 16750+ otomatik RTS/CTS
+
+/// 16550 gönderim FIFO derinliği.
+const TX_FIFO_DEPTH: usize = 16;
+
+/// COM1'in PIC hattı (vektör 32 + 4).
+pub const COM1_IRQ: u8 = 4;
+
+/// Kesme kipinde kullanılan gönderim kuyruğu.
+static TX_QUEUE: TxQueue = TxQueue::new();
 
 /// AMD64 Seri Port I/O'yu yöneten yapı.
 ///
@@ -20,6 +44,12 @@ pub struct SerialPort;
 static IS_INITIALIZED: AtomicBool = AtomicBool::new(false);
 
 impl SerialPort {
+    /// Konsolun G/Ç port tabanı: önyükleyicinin bildirdiği 16550 (SPCR,
+    /// `earlycon=uart,io,...`), yoksa COM1.
+    fn port() -> usize {
+        crate::earlycon::io_base(crate::earlycon::UartKind::Ns16550).unwrap_or(COM1_PORT as usize)
+    }
+
     /// Seri Portu 115200 baud hızında başlatır.
     ///
     /// # Güvenlik Notu
@@ -35,15 +65,16 @@ impl SerialPort {
         let platform_write = |port: u16, data: u8| unsafe {
             // usize'a çevirme platformmod.rs içinde yapılacaktır,
             // ancak I/O portu olduğu için u16'yı hedefliyoruz.
-            PlatformManager::write_byte_to_address(COM1_PORT as usize + port as usize, data)
+            PlatformManager::write_byte_to_address(Self::port() + port as usize, data)
         };
         
         // 1. Kesmeleri Kapat (Değişken Bölücüye Erişim için DLAB'ı ayarla)
         platform_write(LINE_CTRL_PORT, 0x80);
 
-        // 2. Baud Hızını Ayarla (115200 baud) -> Bölücü 1
-        platform_write(DATA_PORT + 0, 0x01); // Bölücü Alt Bayt (LSB)
-        platform_write(DATA_PORT + 1, 0x00); // Bölücü Üst Bayt (MSB)
+        // 2. Baud Hızını Ayarla (varsayılan 115200 baud -> Bölücü 1)
+        let [divisor_lo, divisor_hi] = crate::quirks::uart_divisor(crate::earlycon::baud()).to_le_bytes();
+        platform_write(DATA_PORT + 0, divisor_lo); // Bölücü Alt Bayt (LSB)
+        platform_write(DATA_PORT + 1, divisor_hi); // Bölücü Üst Bayt (MSB)
 
         // 3. Hat Kontrol Yazmacını Ayarla (8 Veri Biti, 1 Stop Biti, Parite Yok)
         // DLAB'ı sıfırla (0x03 = 8N1 konfigürasyonu)
@@ -64,21 +95,93 @@ impl SerialPort {
     /// I/O Port okuma işlemi olduğu için `unsafe` gerektirir.
     fn is_transmit_empty() -> bool {
         let platform_read = |port: u16| unsafe {
-            PlatformManager::read_byte_from_address(COM1_PORT as usize + port as usize)
+            PlatformManager::read_byte_from_address(Self::port() + port as usize)
         };
         
         // Hat Durumu Yazmacı'nın 5. bitini kontrol et (Veri İletim Kaydı Boş)
         (platform_read(LINE_STATUS_PORT) & 0x20) == 0x20
     }
 
-    /// Seri Port'a bir bayt yazar.
+    /// Seri Port'a bir bayt yazar. Kesme kipi açıksa bayt gönderim kuyruğuna
+    /// alınır; değilse port boşalana kadar beklenir.
     pub fn write_byte(byte: u8) {
-        // Portun boş olmasını bekle (Busy-Waiting)
-        while !Self::is_transmit_empty() {}
+        uart::write::<Self>(&TX_QUEUE, byte);
+    }
+
+    /// Kuyruğu boşaltıp baytı yoklamalı yazar (panik ve GDB yolu).
+    pub fn write_byte_polled(byte: u8) {
+        uart::write_polled::<Self>(&TX_QUEUE, byte);
+    }
+
+    /// Kesme güdümlü gönderimi açar.
+    ///
+    /// # Parametreler
+    /// * `flow`: `RtsCts` seçilirse RTS sürülür, destekleyen yongalarda otomatik
+    ///   akış denetimi açılır ve CTS düşükken FIFO doldurulmaz.
+    pub fn enable_tx_interrupts(flow: FlowControl) {
+        let mut mcr = MCR_DTR | MCR_RTS | MCR_OUT2;
+        let mut ier = IER_THRE;
+        if flow == FlowControl::RtsCts {
+            mcr |= MCR_AUTO_FLOW;
+            ier |= IER_MODEM_STATUS;
+        }
+
+        TX_QUEUE.set_irq_mode(true, flow);
+        unsafe {
+            PlatformManager::write_byte_to_address(Self::port() + MODEM_CTRL_PORT as usize, mcr);
+            PlatformManager::write_byte_to_address(Self::port() + INT_ENABLE_PORT as usize, ier);
+            crate::arch::amd64::interrupt::unmask_irq(COM1_IRQ);
+        }
+    }
 
-        // Veri Portuna baytı yaz
+    /// Kesme güdümlü gönderimi kapatır; kuyruktaki baytlar önce gönderilir.
+    pub fn disable_tx_interrupts() {
+        uart::flush::<Self>(&TX_QUEUE);
+        TX_QUEUE.set_irq_mode(false, FlowControl::None);
         unsafe {
-            PlatformManager::write_byte_from_address(COM1_PORT as usize + DATA_PORT as usize, byte)
+            PlatformManager::write_byte_to_address(Self::port() + INT_ENABLE_PORT as usize, 0);
+        }
+    }
+
+    /// COM1 kesme işleyicisi (vektör 36).
+    pub fn handle_interrupt() {
+        unsafe {
+            // IIR okuması THRE kesmesini, MSR okuması modem durum kesmesini temizler
+            PlatformManager::read_byte_from_address(Self::port() + INT_ID_PORT as usize);
+            PlatformManager::read_byte_from_address(Self::port() + MODEM_STATUS_PORT as usize);
+        }
+        uart::handle_tx_interrupt::<Self>(&TX_QUEUE);
+    }
+}
+
+impl UartTx for SerialPort {
+    fn tx_room() -> usize {
+        if TX_QUEUE.flow_control() == FlowControl::RtsCts {
+            // MSR bit 4: CTS
+            let msr = unsafe {
+                PlatformManager::read_byte_from_address(Self::port() + MODEM_STATUS_PORT as usize)
+            };
+            if msr & 0x10 == 0 {
+                return 0;
+            }
+        }
+        // THRE yalnızca FIFO tamamen boşaldığında ayarlanır
+        if Self::is_transmit_empty() { TX_FIFO_DEPTH } else { 0 }
+    }
+
+    fn put(byte: u8) {
+        unsafe {
+            PlatformManager::write_byte_from_address(Self::port() + DATA_PORT as usize, byte)
+        }
+    }
+
+    fn set_tx_interrupt(enable: bool) {
+        let mut ier = if enable { IER_THRE } else { 0 };
+        if TX_QUEUE.flow_control() == FlowControl::RtsCts {
+            ier |= IER_MODEM_STATUS;
+        }
+        unsafe {
+            PlatformManager::write_byte_to_address(Self::port() + INT_ENABLE_PORT as usize, ier);
         }
     }
 }
@@ -102,6 +205,76 @@ impl fmt::Write for SerialPort {
     }
 }
 
+// -----------------------------------------------------------------------------
+// İKİNCİL SERİ PORT (COM2)
+// -----------------------------------------------------------------------------
+
+const COM2_PORT: u16 = 0x2F8;
+
+/// İkincil seri port (COM2): `console` çoğullayıcısının `serial1` hedefi.
+/// Yalnızca yoklamalı yazar; kesme ve alım yolu yoktur.
+pub struct AuxSerialPort;
+
+impl AuxSerialPort {
+    /// COM2'yi COM1 ile aynı ayarlarla (115200 baud, 8N1, FIFO açık, kesmeler kapalı) başlatır.
+    pub fn init() {
+        let registers = [
+            (LINE_CTRL_PORT, 0x80),
+            (DATA_PORT, 0x01),
+            (DATA_PORT + 1, 0x00),
+            (LINE_CTRL_PORT, 0x03),
+            (FIFO_CTRL_PORT, 0xC7),
+            (INT_ENABLE_PORT, 0x00),
+        ];
+        for (port, value) in registers {
+            unsafe { PlatformManager::write_byte_to_address(COM2_PORT as usize + port as usize, value) };
+        }
+    }
+
+    fn write_byte(byte: u8) {
+        // Hat Durumu Yazmacı bit 5: veri iletim yazmacı boş
+        while unsafe { PlatformManager::read_byte_from_address(COM2_PORT as usize + LINE_STATUS_PORT as usize) } & 0x20 == 0 {
+            core::hint::spin_loop();
+        }
+        unsafe { PlatformManager::write_byte_to_address(COM2_PORT as usize + DATA_PORT as usize, byte) };
+    }
+}
+
+impl fmt::Write for AuxSerialPort {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for byte in s.bytes() {
+            if byte == b'\n' {
+                Self::write_byte(b'\r');
+            }
+            Self::write_byte(byte);
+        }
+        Ok(())
+    }
+}
+
+// -----------------------------------------------------------------------------
+// PANİK VE İKİNCİL KONSOL
+// -----------------------------------------------------------------------------
+
+/// Panik yolunun kilitsiz yazıcısı (bkz. `crate::panic`): TX kuyruğu deneme
+/// kilidiyle boşaltılır, bayt yoklamalı gönderilir.
+pub fn panic_write_byte(byte: u8) {
+    SerialPort::write_byte_polled(byte);
+}
+
+/// Kesme güdümlü gönderimi kapatır; sonraki yazmalar yoklamalıdır.
+pub fn panic_quiesce() {
+    SerialPort::disable_tx_interrupts();
+}
+
+/// COM2'yi başlatır ve `serial1` hedefinin yazıcısını döndürür (bkz. `crate::console`).
+pub fn init_aux_serial() -> Option<fn(fmt::Arguments)> {
+    AuxSerialPort::init();
+    Some(|args| {
+        let _ = AuxSerialPort.write_fmt(args);
+    })
+}
+
 // -----------------------------------------------------------------------------
 // GENEL KONSOL ÇIKTI FONKSİYONLARI
 // -----------------------------------------------------------------------------
@@ -121,8 +294,11 @@ macro_rules! serial_print {
         // G/Ç işlemleri sırasında yarış koşullarını önlemek için kilit (spinlock)
         // kullanılmalıdır. Şimdilik sadece `unsafe` kullanarak doğrudan erişimi sağlıyoruz.
         // Gerçek bir çekirdekte burada bir Mutex/Spinlock çağrısı olur.
-        unsafe {
-             let _ = write!(SerialPort, $($arg)*);
+        // Yönlendirilmiş görev çıktısı (örn. uzak kabuk) seri porta yazılmaz
+        if !$crate::shell::redirect(format_args!($($arg)*)) {
+            unsafe {
+                 let _ = write!(SerialPort, $($arg)*);
+            }
         }
     });
 }
@@ -0,0 +1,50 @@
+// src/arch/amd64/cpu.rs
+// AMD64 (x86_64) işlemci özelliği algılama (CPUID).
+
+#![allow(dead_code)]
+
+use core::arch::x86_64::{__cpuid, __cpuid_count};
+use crate::cpu::CpuFeatures;
+
+/// CPUID.1:EDX[0] - x87 FPU.
+const CPUID_1_EDX_FPU: u32 = 1 << 0;
+/// CPUID.1:EDX[26] - SSE2.
+const CPUID_1_EDX_SSE2: u32 = 1 << 26;
+/// CPUID.1:ECX[13] - CMPXCHG16B.
+const CPUID_1_ECX_CX16: u32 = 1 << 13;
+/// CPUID.1:ECX[20] - SSE4.2 (CRC32 talimatı).
+const CPUID_1_ECX_SSE42: u32 = 1 << 20;
+/// CPUID.1:ECX[25] - AES-NI.
+const CPUID_1_ECX_AES: u32 = 1 << 25;
+/// CPUID.1:ECX[30] - RDRAND.
+const CPUID_1_ECX_RDRAND: u32 = 1 << 30;
+/// CPUID.7.0:EBX[29] - SHA uzantıları.
+const CPUID_7_EBX_SHA: u32 = 1 << 29;
+/// CPUID.8000_0001:EDX[26] - 1 GiB sayfalar.
+const CPUID_EXT1_EDX_PAGE1GB: u32 = 1 << 26;
+
+pub fn detect() -> CpuFeatures {
+    // SAFETY: CPUID her x86_64 işlemcide mevcuttur.
+    unsafe {
+        let max_leaf = __cpuid(0).eax;
+        let max_ext_leaf = __cpuid(0x8000_0000).eax;
+        let leaf1 = __cpuid(1);
+        let sha = max_leaf >= 7 && __cpuid_count(7, 0).ebx & CPUID_7_EBX_SHA != 0;
+        let page1gb = max_ext_leaf >= 0x8000_0001 && __cpuid(0x8000_0001).edx & CPUID_EXT1_EDX_PAGE1GB != 0;
+
+        CpuFeatures {
+            // Aile/model/adım imzası
+            id: leaf1.eax as u64,
+            fpu: leaf1.edx & CPUID_1_EDX_FPU != 0,
+            simd: leaf1.edx & CPUID_1_EDX_SSE2 != 0,
+            aes: leaf1.ecx & CPUID_1_ECX_AES != 0,
+            sha2: sha,
+            crc32: leaf1.ecx & CPUID_1_ECX_SSE42 != 0,
+            atomics: leaf1.ecx & CPUID_1_ECX_CX16 != 0,
+            // Uzun kipte 2 MiB sayfalar her zaman vardır
+            large_pages: true,
+            huge_pages: page1gb,
+            rng: leaf1.ecx & CPUID_1_ECX_RDRAND != 0,
+        }
+    }
+}
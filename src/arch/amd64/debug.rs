@@ -0,0 +1,118 @@
+// src/arch/amd64/debug.rs
+// AMD64 (x86_64) mimarisine özgü kesme noktası, tek adım ve gözlem noktası desteği.
+
+#![allow(dead_code)]
+
+use core::arch::asm;
+use crate::debug::{WatchKind, ERR_WATCH_KIND_UNSUPPORTED};
+use crate::platformgeneric::KernelError;
+
+/// `int3` talimatı (tek bayt).
+pub const BREAKPOINT_INSN: u32 = 0xCC;
+
+/// Kesme noktası talimatının uzunluğu (bayt).
+pub const BREAKPOINT_INSN_LEN: usize = 1;
+
+/// `int3` bir tuzak (trap) olduğundan, kaydedilen RIP talimatın sonrasını gösterir.
+pub const BREAKPOINT_PC_ADJUST: usize = 1;
+
+/// RFLAGS.TF (Trap Flag) biti.
+const RFLAGS_TF: u64 = 1 << 8;
+
+/// Talimat önbelleğini senkronize eder.
+///
+/// x86'da talimat ve veri önbellekleri donanım tarafından tutarlı tutulur;
+/// ek bir işlem gerekmez.
+#[inline(always)]
+pub unsafe fn sync_icache(_addr: usize, _len: usize) {}
+
+/// Kaydedilmiş RFLAGS içinde TF bitini ayarlar. Bir sonraki talimattan sonra
+/// Vektör 1 (#DB) tetiklenir.
+pub fn set_single_step(rflags: &mut u64, enable: bool) -> Result<(), KernelError> {
+    if enable {
+        *rflags |= RFLAGS_TF;
+    } else {
+        *rflags &= !RFLAGS_TF;
+    }
+    Ok(())
+}
+
+// -----------------------------------------------------------------------------
+// DONANIM GÖZLEM NOKTALARI (DR0-DR3 / DR7)
+// -----------------------------------------------------------------------------
+
+/// Adres yazmaçları DR0-DR3.
+pub const MAX_WATCHPOINTS: usize = 4;
+
+/// Veri kesme noktaları erişim tamamlandıktan sonra #DB tuzağı üretir.
+pub const WATCH_BEFORE_ACCESS: bool = false;
+
+/// DR6.B0-B3: hangi adres yazmacının eşleştiğini gösterir.
+const DR6_HIT_MASK: u64 = 0xF;
+
+unsafe fn read_dr6() -> u64 {
+    let value: u64;
+    asm!("mov {}, dr6", out(reg) value, options(nomem, nostack, preserves_flags));
+    value
+}
+
+unsafe fn read_dr7() -> u64 {
+    let value: u64;
+    asm!("mov {}, dr7", out(reg) value, options(nomem, nostack, preserves_flags));
+    value
+}
+
+unsafe fn write_dr7(value: u64) {
+    asm!("mov dr7, {}", in(reg) value, options(nomem, nostack, preserves_flags));
+}
+
+unsafe fn write_addr_reg(slot: usize, addr: usize) {
+    match slot {
+        0 => asm!("mov dr0, {}", in(reg) addr, options(nomem, nostack, preserves_flags)),
+        1 => asm!("mov dr1, {}", in(reg) addr, options(nomem, nostack, preserves_flags)),
+        2 => asm!("mov dr2, {}", in(reg) addr, options(nomem, nostack, preserves_flags)),
+        _ => asm!("mov dr3, {}", in(reg) addr, options(nomem, nostack, preserves_flags)),
+    }
+}
+
+/// DR<slot>'u ayarlar ve DR7'de yerel etkinleştirme (L), R/W ve LEN alanlarını yazar.
+///
+/// x86 salt okuma gözlemini desteklemez (R/W=10 G/Ç portları içindir).
+pub unsafe fn set_watchpoint(slot: usize, addr: usize, len: usize, kind: WatchKind) -> Result<(), KernelError> {
+    let rw: u64 = match kind {
+        WatchKind::Write => 0b01,
+        WatchKind::ReadWrite => 0b11,
+        WatchKind::Read => return Err(KernelError::PlatformSpecificError(ERR_WATCH_KIND_UNSUPPORTED)),
+    };
+    let len_bits: u64 = match len {
+        1 => 0b00,
+        2 => 0b01,
+        8 => 0b10,
+        _ => 0b11,
+    };
+
+    write_addr_reg(slot, addr);
+
+    let mut dr7 = read_dr7();
+    dr7 &= !((0b11 << (slot * 2)) | (0xF << (16 + slot * 4)));
+    dr7 |= (1 << (slot * 2)) | (rw << (16 + slot * 4)) | (len_bits << (18 + slot * 4));
+    write_dr7(dr7);
+    Ok(())
+}
+
+/// DR7'de yuvanın L/G bitlerini temizler.
+pub unsafe fn clear_watchpoint(slot: usize) {
+    write_dr7(read_dr7() & !(0b11 << (slot * 2)));
+}
+
+/// DR6'dan eşleşen yuvayı okur ve durum bitlerini temizler (DR6 yapışkandır).
+pub fn watchpoint_hit() -> Option<usize> {
+    unsafe {
+        let dr6 = read_dr6();
+        if dr6 & DR6_HIT_MASK == 0 {
+            return None;
+        }
+        asm!("mov dr6, {}", in(reg) dr6 & !DR6_HIT_MASK, options(nomem, nostack, preserves_flags));
+        Some((dr6 & DR6_HIT_MASK).trailing_zeros() as usize)
+    }
+}
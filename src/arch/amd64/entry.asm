@@ -62,7 +62,10 @@ pml4: .skip 4096
 # Sayfa Dizin İşaretçisi Tablosu (PDPT)
 pdpt: .skip 4096
 
-# Yığın (Stack)
+# Yığın (Stack): linker betiğindeki `.boot_stack` bölümüne, koruma sayfasının
+# hemen üstüne yerleşir.
+.section .boot_stack, "aw", @nobits
+.align 16
 .equ STACK_SIZE, 0x4000 # 16KB geçici yığın
 stack_top: .skip STACK_SIZE
 stack_bottom:
@@ -1,5 +1,9 @@
 use core::arch::asm;
+use core::cell::UnsafeCell;
 use core::fmt;
+use crate::memory::frame::{self, FRAME_SIZE};
+use crate::platformgeneric::KernelError;
+use crate::trap::{handle_fatal, Access, Cause};
 
 // -----------------------------------------------------------------------------
 // HARİCİ MONTAJ DİLİ İŞLEYİCİLERİ
@@ -12,6 +16,10 @@ use core::fmt;
 extern "C" {
     // Vektör 0: Bölme Hatası
     fn exception_handler_divide_by_zero();
+    // Vektör 1: Hata Ayıklama İstisnası (Tek Adım / Donanım Kesme Noktası)
+    fn exception_handler_debug();
+    // Vektör 3: Kesme Noktası (int3)
+    fn exception_handler_breakpoint();
     // Vektör 6: Geçersiz İşlem Kodu (Invalid Opcode)
     fn exception_handler_invalid_opcode();
     // Vektör 8: Çift Hata (Double Fault) - Hata kodu ile
@@ -75,12 +83,17 @@ impl IdtEntry {
     fn set_handler(&mut self, handler: usize, segment_selector: u16, attributes: u8) {
         self.offset_low = handler as u16;
         self.segment_selector = segment_selector;
-        self.ist = 0; // Şimdilik IST (Interrupt Stack Table) kullanmıyoruz.
+        self.ist = 0; // IST, `install_irq_stack` ile sonradan atanır.
         self.attributes = attributes;
         self.offset_middle = (handler >> 16) as u16;
         self.offset_high = (handler >> 32) as u32;
         self.reserved = 0;
     }
+
+    /// Girişin kullanacağı IST yığınını seçer (0: kesilen kodun yığını).
+    fn set_stack(&mut self, ist: u8) {
+        self.ist = ist;
+    }
 }
 
 /// Tüm istisnaları ve kesmeleri kapsayan statik IDT. (256 Giriş)
@@ -106,9 +119,12 @@ impl Idt {
         
         // --- 0-31: CPU İstisnaları ---
         self.entries[0].set_handler(exception_handler_divide_by_zero as usize, KERNEL_CODE_SEGMENT, INTERRUPT_GATE_ATTR);
+        self.entries[1].set_handler(exception_handler_debug as usize, KERNEL_CODE_SEGMENT, INTERRUPT_GATE_ATTR);
+        // int3'ün kullanıcı modundan da çağrılabilmesi için DPL=3 (0xEE)
+        self.entries[3].set_handler(exception_handler_breakpoint as usize, KERNEL_CODE_SEGMENT, 0xEE);
         self.entries[6].set_handler(exception_handler_invalid_opcode as usize, KERNEL_CODE_SEGMENT, INTERRUPT_GATE_ATTR);
-        // Çift Hata için Task Gate kullanmak daha iyidir, ancak Interrupt Gate ile başlıyoruz.
-        // Double Fault'ta IST kullanmak HAYATİDİR, ancak şimdilik atlıyoruz.
+        // Çift Hata bozuk bir yığından gelebilir; `install_irq_stack` onu
+        // acil durum yığınına (IST2) alır.
         self.entries[8].set_handler(exception_handler_double_fault as usize, KERNEL_CODE_SEGMENT, 0x8E);
         self.entries[13].set_handler(exception_handler_general_protection_fault as usize, KERNEL_CODE_SEGMENT, INTERRUPT_GATE_ATTR);
         self.entries[14].set_handler(exception_handler_page_fault as usize, KERNEL_CODE_SEGMENT, INTERRUPT_GATE_ATTR);
@@ -142,52 +158,155 @@ pub struct ExceptionContext {
     // Hata kodlu istisnalarda (örn. Sayfa Hatası, GPF) bu kayıtlardan önce hata kodu bulunur.
 }
 
+// İşlemcinin kesme girişinde yığına ittiği çerçeve (Intel SDM 3A, 6.14.1);
+// `exception_handlers.S` taslakları bu düzene göre `iretq` yapar.
+crate::asm_layout!(ExceptionContext {
+    instruction_pointer: 0,
+    code_segment: 8,
+    cpu_flags: 16,
+    stack_pointer: 24,
+    stack_segment: 32,
+}, size == 40);
+
+/// Vektör numarasını ve hata kodunu ortak `trap::Cause` tipine çevirir.
+///
+/// # Parametreler
+/// * `vector`: IDT vektör numarası.
+/// * `error_code`: CPU'nun yığına attığı hata kodu (yoksa 0).
+/// * `fault_addr`: Sayfa hatası için CR2 değeri (diğerleri için yok sayılır).
+pub fn decode_cause(vector: u64, error_code: u64, fault_addr: u64) -> Cause {
+    match vector {
+        // Sayfa Hatası: hata kodunun 1. biti yazma, 4. biti talimat getirme
+        14 => {
+            let access = if error_code & (1 << 4) != 0 {
+                Access::Execute
+            } else if error_code & (1 << 1) != 0 {
+                Access::Write
+            } else {
+                Access::Read
+            };
+            Cause::PageFault { access, addr: fault_addr as usize }
+        }
+        // #DB: DR6.B0-B3 ayarlıysa veri gözlem noktası, aksi halde tek adım (DR6.BS)
+        1 => match crate::debug::watchpoint_hit() {
+            Some(addr) => Cause::Watchpoint { addr },
+            None => Cause::SingleStep,
+        },
+        3 => Cause::Breakpoint,
+        6 => Cause::IllegalInstruction,
+        17 => Cause::Misaligned { addr: fault_addr as usize }, // Hizalama Denetimi (#AC)
+        18 => Cause::MachineError, // Makine Denetimi (#MC)
+        // PIC ofsetinden (32) sonrası donanım kesmeleridir
+        32..=255 => Cause::Interrupt((vector - 32) as u32),
+        _ => Cause::Unknown(vector),
+    }
+}
+
 /// Tüm hata kodsuz istisnalar için genel işleyici.
 #[no_mangle]
-pub extern "C" fn generic_exception_handler(vector: u64, context: &ExceptionContext) {
+pub extern "C" fn generic_exception_handler(vector: u64, context: &mut ExceptionContext) {
+    // Kesme noktası, tek adım ve gözlem noktası tuzakları önce hata ayıklayıcıya sunulur.
+    let cause = decode_cause(vector, 0, 0);
+    if crate::debug::handle_trap(cause, &mut context.instruction_pointer, &mut context.cpu_flags) {
+        return;
+    }
+
+    // Geçersiz talimat vb. bir `fixup::catch` gövdesindeyse oraya dönülür
+    if crate::fixup::fixup_exception(cause, &mut context.instruction_pointer) {
+        return;
+    }
+
     serial_println!("\n--- CPU İSTİSNASI ---");
     serial_println!("Vektör Numarası: {}", vector);
     serial_println!("RIP: {:#x}", context.instruction_pointer);
     // Gerekirse yığın dökümü yapılabilir
     
     // Çift Hata (Double Fault) hariç tüm istisnalarda kernel paniklenmelidir.
-    panic!("Kritik İstisna: Vektör {}", vector); 
+    handle_fatal(cause, context.instruction_pointer as usize);
 }
 
 /// Hata kodu olan istisnalar için genel işleyici.
 #[no_mangle]
-pub extern "C" fn generic_exception_handler_with_error(vector: u64, error_code: u64, context: &ExceptionContext) {
-    serial_println!("\n--- CPU İSTİSNASI (Hata Kodu ile) ---");
-    serial_println!("Vektör Numarası: {}", vector);
-    serial_println!("Hata Kodu: {:#x}", error_code);
-    serial_println!("RIP: {:#x}", context.instruction_pointer);
-    
+pub extern "C" fn generic_exception_handler_with_error(vector: u64, error_code: u64, context: &mut ExceptionContext) {
     // Sayfa Hatası için CR2 Kaydını okumak gerekir:
+    let mut cr2: u64 = 0;
     if vector == 14 {
-        let cr2: u64;
         unsafe {
             asm!("mov {}, cr2", out(reg) cr2);
         }
+    }
+
+    // Kullanıcı belleği erişimi veya bellek yoklaması ise düzeltme tablosuna bak
+    let cause = decode_cause(vector, error_code, cr2);
+    if crate::fixup::fixup_exception(cause, &mut context.instruction_pointer) {
+        return;
+    }
+
+    serial_println!("\n--- CPU İSTİSNASI (Hata Kodu ile) ---");
+    serial_println!("Vektör Numarası: {}", vector);
+    serial_println!("Hata Kodu: {:#x}", error_code);
+    serial_println!("RIP: {:#x}", context.instruction_pointer);
+    if vector == 14 {
         serial_println!("CR2 (Hata Adresi): {:#x}", cr2);
     }
     
-    panic!("Kritik İstisna: Vektör {}", vector);
+    handle_fatal(cause, context.instruction_pointer as usize);
 }
 
 /// Donanım Kesmeleri için genel işleyici.
 #[no_mangle]
-pub extern "C" fn generic_interrupt_handler(vector: u64, _context: &ExceptionContext) {
+pub extern "C" fn generic_interrupt_handler(vector: u64, context: &ExceptionContext) {
+    let entry = crate::latency::irq_enter();
+    // IST1 her girişte yığının üstünden başlar: iç içe kesme çerçeveyi ezer.
+    let _irq = crate::irqstack::enter(context as *const _ as usize);
+    // Sahte kesmeler sayılır ama kesme istatistiğine ve sürücülere gitmez
+    if unsafe { crate::arch::amd64::interrupt::check_spurious(vector as u8) } {
+        SPURIOUS_IRQS.inc();
+        return;
+    }
+    crate::stats::record_irq(vector as usize);
+    crate::replay::irq(vector as u32);
+    crate::latency::irq_dispatch(vector as u32, entry);
+    if !crate::irqstorm::irq_fired(vector as u32, irq_handler_name) {
+        return;
+    }
+
     // Gelen kesmeyi işlemek için (Örn: Zamanlayıcı)
     match vector {
         32 => { // Zamanlayıcı Kesmesi (Timer)
-            // Zamanlayıcı mantığını çalıştır
+            // Zamanlayıcı mantığını çalıştır (tik ve yüksek çözünürlüklü zamanlayıcılar)
+            crate::hrtimer::timer_interrupt();
             // serial_print!("."); // Çok sık loglamayı engelle
         }
         33 => { // Klavye Kesmesi (Keyboard)
             // Klavye sürücüsünü çağır
         }
+        36 => { // COM1 (IRQ 4): gönderim kuyruğunu FIFO'ya aktar
+            crate::arch::amd64::console::SerialPort::handle_interrupt();
+        }
+        // Paylaşılan PCI INTx hattı: tüm sürücülere sorulur (kısa devresiz `|`)
+        v if crate::drivers::usb::xhci::handle_irq(v as u32) | crate::drivers::block::ahci::handle_irq(v as u32)
+            | crate::drivers::block::nvme::handle_irq(v as u32) => {}
         _ => {
-            serial_println!("Bilinmeyen IRQ: {}", vector);
+            // Sürücüsü olmayan vektör: bir kez raporlanır, hattı maskelenir ve
+            // EOI gönderilir; aksi halde PIC daha düşük öncelikli hatları tutar
+            let (irr, isr, imr) = unsafe { crate::arch::amd64::interrupt::pic_state() };
+            crate::irqquarantine::quarantine(
+                vector as u32,
+                format_args!("8259 IRR {:#06x} ISR {:#06x} IMR {:#06x}", irr, isr, imr),
+                format_args!(
+                    "RIP {:#x} CS {:#x} RFLAGS {:#x} RSP {:#x}",
+                    context.instruction_pointer,
+                    context.code_segment,
+                    context.cpu_flags,
+                    context.stack_pointer
+                ),
+            );
+            // APIC vektörlerinin (48+) EOI'si yerel APIC'e yazılır; burada
+            // yalnızca 8259 hatları onaylanır
+            if (32..48).contains(&vector) {
+                unsafe { crate::arch::amd64::interrupt::send_eoi(vector as u8) };
+            }
         }
     }
 
@@ -199,6 +318,18 @@ pub extern "C" fn generic_interrupt_handler(vector: u64, _context: &ExceptionCon
 }
 
 
+crate::kernel_counter!(SPURIOUS_IRQS, "irq.spurious");
+
+/// Kesme fırtınası uyarısında gösterilen işleyici adı.
+fn irq_handler_name(vector: u32) -> &'static str {
+    match vector {
+        32 => "timer",
+        33 => "keyboard",
+        36 => "com1",
+        _ => "pci-intx (xhci/ahci/nvme)",
+    }
+}
+
 // -----------------------------------------------------------------------------
 // 3. KESME YÖNETİMİ API'SI
 // -----------------------------------------------------------------------------
@@ -237,4 +368,102 @@ unsafe fn load_idt(idt: &Idt) {
     };
 
     asm!("lidt ({})", in(reg) &ptr, options(nostack, preserves_flags));
-}
\ No newline at end of file
+}
+// -----------------------------------------------------------------------------
+// 4. GÖREV DURUM SEGMENTİ (TSS) VE KESME YIĞINLARI
+// -----------------------------------------------------------------------------
+
+/// Donanım kesmelerinin (32 ve üstü) yığını.
+pub const IST_IRQ: u8 = 1;
+/// Çift hatanın acil durum yığını.
+pub const IST_EMERGENCY: u8 = 2;
+
+/// 64-bit TSS; yalnızca IST girdileri kullanılır.
+#[repr(C, packed(4))]
+struct Tss {
+    reserved0: u32,
+    /// Ayrıcalık değişiminde yüklenen yığınlar (RSP0-2).
+    rsp: [u64; 3],
+    reserved1: u64,
+    /// IST1-IST7.
+    ist: [u64; 7],
+    reserved2: u64,
+    reserved3: u16,
+    iomap_base: u16,
+}
+
+/// İşlemci başına GDT ve TSS. Önyükleme GDT'sinin kod (0x08) ve veri (0x10)
+/// seçicileri korunur; TSS tanımlayıcısı 0x18'dedir (16 bayt).
+struct CpuTables {
+    gdt: UnsafeCell<[u64; 5]>,
+    tss: UnsafeCell<Tss>,
+}
+
+// Her işlemci yalnızca kendi kopyasını yazar.
+unsafe impl Sync for CpuTables {}
+
+const GDT_CODE_64: u64 = 0x0020_9a00_0000_0000;
+const GDT_DATA: u64 = 0x00c0_9200_0000_0000;
+const TSS_SELECTOR: u16 = 0x18;
+
+crate::percpu!(CPU_TABLES: CpuTables = CpuTables {
+    gdt: UnsafeCell::new([0; 5]),
+    tss: UnsafeCell::new(Tss {
+        reserved0: 0,
+        rsp: [0; 3],
+        reserved1: 0,
+        ist: [0; 7],
+        reserved2: 0,
+        reserved3: 0,
+        iomap_base: 0,
+    }),
+});
+
+/// TSS'yi gösteren 16 baytlık sistem tanımlayıcısı (tip 0x9: uygun 64-bit TSS).
+fn tss_descriptor(base: u64) -> [u64; 2] {
+    let limit = core::mem::size_of::<Tss>() as u64 - 1;
+    let low = (limit & 0xFFFF)
+        | (base & 0xFF_FFFF) << 16
+        | 0x89 << 40
+        | ((limit >> 16) & 0xF) << 48
+        | ((base >> 24) & 0xFF) << 56;
+    [low, base >> 32]
+}
+
+/// Kesme girişi ayrı kesme yığınına geçer (bkz. `crate::irqstack`).
+pub const SWITCHES_IRQ_STACK: bool = true;
+
+/// Çalışılan işlemcinin TSS'sini kurar: IST1 kesme yığını (`top`), IST2 bir
+/// sayfalık acil durum yığını. GDT yeniden yüklenir, TSS `ltr` ile etkinleşir
+/// ve IDT girdileri bu yığınlara yönlendirilir.
+///
+/// # Safety
+/// Kesmeler kapalıyken ve `top` bu işlemciye ait bir yığının üstüyken çağrılmalıdır.
+pub unsafe fn install_irq_stack(top: usize) -> Result<(), KernelError> {
+    let emergency = frame::alloc()? + FRAME_SIZE;
+    let tables = CPU_TABLES.get();
+    let tss = &mut *tables.tss.get();
+    tss.ist[IST_IRQ as usize - 1] = top as u64;
+    tss.ist[IST_EMERGENCY as usize - 1] = emergency as u64;
+    tss.iomap_base = core::mem::size_of::<Tss>() as u16; // G/Ç izin haritası yok
+
+    let [tss_low, tss_high] = tss_descriptor(tables.tss.get() as u64);
+    let gdt = &mut *tables.gdt.get();
+    *gdt = [0, GDT_CODE_64, GDT_DATA, tss_low, tss_high];
+    // GDTR, IDTR ile aynı biçimdedir.
+    let ptr = IdtPointer {
+        limit: (core::mem::size_of::<[u64; 5]>() - 1) as u16,
+        base: gdt.as_ptr() as u64,
+    };
+    // Seçiciler değişmediğinden segment yazmaçlarını yeniden yüklemek gerekmez.
+    asm!("lgdt [{}]", in(reg) &ptr, options(nostack, preserves_flags));
+    asm!("ltr {0:x}", in(reg) TSS_SELECTOR, options(nostack, preserves_flags));
+
+    // IDT tüm işlemcilerde ortaktır; IST numarası her işlemcinin kendi TSS'sinden okunur.
+    let idt = &mut *core::ptr::addr_of_mut!(IDT);
+    for entry in &mut idt.entries[32..] {
+        entry.set_stack(IST_IRQ);
+    }
+    idt.entries[8].set_stack(IST_EMERGENCY);
+    Ok(())
+}
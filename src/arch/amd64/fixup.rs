@@ -0,0 +1,79 @@
+// src/arch/amd64/fixup.rs
+// AMD64 (x86_64) hata güvenli bellek yoklama talimatları.
+
+#![allow(dead_code)]
+
+use core::arch::asm;
+
+/// Tek bayt okur; sayfa hatasında `None` döner.
+pub unsafe fn probe_read_u8(addr: usize) -> Option<u8> {
+    let value: u32;
+    let err: u32;
+    asm!(
+        "xor {err:e}, {err:e}",
+        "2: movzx {val:e}, byte ptr [{addr}]",
+        "3:",
+        ".pushsection .text.fixup, \"ax\"",
+        "4: mov {err:e}, 1",
+        "xor {val:e}, {val:e}",
+        "jmp 3b",
+        ".popsection",
+        ".pushsection .ex_table, \"a\"",
+        ".balign 8",
+        ".quad 2b, 4b",
+        ".popsection",
+        addr = in(reg) addr,
+        val = out(reg) value,
+        err = out(reg) err,
+        options(nostack, readonly)
+    );
+    if err == 0 { Some(value as u8) } else { None }
+}
+
+/// Tek bayt yazar; erişim hatasında `false` döner.
+pub unsafe fn probe_write_u8(addr: usize, value: u8) -> bool {
+    let err: u32;
+    asm!(
+        "xor {err:e}, {err:e}",
+        "2: mov byte ptr [{addr}], {val}",
+        "3:",
+        ".pushsection .text.fixup, \"ax\"",
+        "4: mov {err:e}, 1",
+        "jmp 3b",
+        ".popsection",
+        ".pushsection .ex_table, \"a\"",
+        ".balign 8",
+        ".quad 2b, 4b",
+        ".popsection",
+        addr = in(reg) addr,
+        val = in(reg_byte) value,
+        err = out(reg) err,
+        options(nostack)
+    );
+    err == 0
+}
+
+/// 32 bit okur; sayfa hatası veya genel koruma hatasında `None` döner.
+pub unsafe fn probe_read_u32(addr: usize) -> Option<u32> {
+    let value: u32;
+    let err: u32;
+    asm!(
+        "xor {err:e}, {err:e}",
+        "2: mov {val:e}, dword ptr [{addr}]",
+        "3:",
+        ".pushsection .text.fixup, \"ax\"",
+        "4: mov {err:e}, 1",
+        "xor {val:e}, {val:e}",
+        "jmp 3b",
+        ".popsection",
+        ".pushsection .ex_table, \"a\"",
+        ".balign 8",
+        ".quad 2b, 4b",
+        ".popsection",
+        addr = in(reg) addr,
+        val = out(reg) value,
+        err = out(reg) err,
+        options(nostack, readonly)
+    );
+    if err == 0 { Some(value) } else { None }
+}
@@ -0,0 +1,144 @@
+// src/arch/amd64/hypervisor.rs
+// AMD64 (x86_64) hypervisor algılama (CPUID) ve kvmclock paravirt saati.
+
+#![allow(dead_code)]
+
+use core::arch::asm;
+use core::arch::x86_64::__cpuid;
+use crate::hypervisor::{HypervisorKind, Paravirt};
+use crate::arch::amd64::time::read_tsc;
+
+// -----------------------------------------------------------------------------
+// CPUID ALGILAMA
+// -----------------------------------------------------------------------------
+
+/// CPUID.1:ECX[31] - "hypervisor present" biti.
+const CPUID_1_ECX_HYPERVISOR: u32 = 1 << 31;
+/// CPUID.1:ECX[24] - TSC deadline zamanlayıcı modu.
+const CPUID_1_ECX_TSC_DEADLINE: u32 = 1 << 24;
+
+/// Hypervisor CPUID yaprakları 0x4000_0000'dan başlar; EBX:ECX:EDX 12 baytlık imzadır.
+const CPUID_HV_BASE: u32 = 0x4000_0000;
+/// KVM özellik yaprağı; EAX[3] = KVM_FEATURE_CLOCKSOURCE2.
+const CPUID_KVM_FEATURES: u32 = 0x4000_0001;
+const KVM_FEATURE_CLOCKSOURCE2: u32 = 1 << 3;
+
+/// CPUID hypervisor bitini ve imza yaprağını okur.
+pub fn detect() -> HypervisorKind {
+    let leaf1 = unsafe { __cpuid(1) };
+    if leaf1.ecx & CPUID_1_ECX_HYPERVISOR == 0 {
+        return HypervisorKind::None;
+    }
+
+    let hv = unsafe { __cpuid(CPUID_HV_BASE) };
+    let mut sig = [0u8; 12];
+    sig[0..4].copy_from_slice(&hv.ebx.to_le_bytes());
+    sig[4..8].copy_from_slice(&hv.ecx.to_le_bytes());
+    sig[8..12].copy_from_slice(&hv.edx.to_le_bytes());
+
+    match &sig {
+        b"KVMKVMKVM\0\0\0" => HypervisorKind::Kvm,
+        b"TCGTCGTCGTCG" => HypervisorKind::Qemu,
+        b"XenVMMXenVMM" => HypervisorKind::Xen,
+        b"Microsoft Hv" => HypervisorKind::HyperV,
+        _ => HypervisorKind::Other,
+    }
+}
+
+// -----------------------------------------------------------------------------
+// KVMCLOCK (pvclock)
+// -----------------------------------------------------------------------------
+
+/// MSR_KVM_SYSTEM_TIME_NEW: pvclock yapısının fiziksel adresi | etkin biti.
+const MSR_KVM_SYSTEM_TIME_NEW: u32 = 0x4b56_4d01;
+
+/// Hypervisor'ın güncellediği zaman bilgisi (pvclock_vcpu_time_info).
+#[repr(C, align(32))]
+struct PvClockTimeInfo {
+    version: u32,
+    pad0: u32,
+    tsc_timestamp: u64,
+    system_time: u64,
+    tsc_to_system_mul: u32,
+    tsc_shift: i8,
+    flags: u8,
+    pad: [u8; 2],
+}
+
+static mut PVCLOCK: PvClockTimeInfo = PvClockTimeInfo {
+    version: 0,
+    pad0: 0,
+    tsc_timestamp: 0,
+    system_time: 0,
+    tsc_to_system_mul: 0,
+    tsc_shift: 0,
+    flags: 0,
+    pad: [0; 2],
+};
+
+unsafe fn wrmsr(msr: u32, value: u64) {
+    asm!(
+        "wrmsr",
+        in("ecx") msr,
+        in("eax") value as u32,
+        in("edx") (value >> 32) as u32,
+        options(nostack, preserves_flags)
+    );
+}
+
+/// KVM altında kvmclock'u kaydeder ve TSC deadline desteğini bildirir.
+///
+/// Konsol için paravirt bir arayüz kullanılmaz; COM1 KVM'de de hızlı yoldan öykünülür.
+pub unsafe fn enable_paravirt(kind: HypervisorKind) -> Paravirt {
+    let leaf1 = __cpuid(1);
+    let mut pv = Paravirt {
+        timer_deadline: leaf1.ecx & CPUID_1_ECX_TSC_DEADLINE != 0,
+        ..Paravirt::default()
+    };
+
+    if kind == HypervisorKind::Kvm && __cpuid(CPUID_KVM_FEATURES).eax & KVM_FEATURE_CLOCKSOURCE2 != 0 {
+        // Çekirdek verisi birebir eşli olduğundan sanal adres fiziksel adrestir.
+        let phys = core::ptr::addr_of!(PVCLOCK) as u64;
+        wrmsr(MSR_KVM_SYSTEM_TIME_NEW, phys | 1);
+        pv.clock = true;
+    }
+    pv
+}
+
+/// pvclock yapısından nanosaniye cinsinden sistem zamanını hesaplar.
+///
+/// Sürüm numarası tek ise hypervisor yapıyı güncelliyordur; çift ve değişmemiş
+/// bir sürüm okunana kadar tekrarlanır.
+pub fn pv_clock_ns() -> u64 {
+    let info = core::ptr::addr_of!(PVCLOCK);
+    loop {
+        unsafe {
+            let version = core::ptr::read_volatile(&(*info).version);
+            if version & 1 != 0 {
+                continue;
+            }
+            core::sync::atomic::fence(core::sync::atomic::Ordering::Acquire);
+
+            let tsc_timestamp = core::ptr::read_volatile(&(*info).tsc_timestamp);
+            let system_time = core::ptr::read_volatile(&(*info).system_time);
+            let mul = core::ptr::read_volatile(&(*info).tsc_to_system_mul);
+            let shift = core::ptr::read_volatile(&(*info).tsc_shift);
+
+            let mut delta = read_tsc().0.wrapping_sub(tsc_timestamp);
+            if shift < 0 {
+                delta >>= -shift as u32;
+            } else {
+                delta <<= shift as u32;
+            }
+            let ns = system_time.wrapping_add(((delta as u128 * mul as u128) >> 32) as u64);
+
+            core::sync::atomic::fence(core::sync::atomic::Ordering::Acquire);
+            if core::ptr::read_volatile(&(*info).version) == version {
+                return ns;
+            }
+        }
+    }
+}
+
+/// x86'da paravirt konsol kullanılmaz; `enable_paravirt` bu olanağı açmaz.
+pub fn pv_console_write_byte(_byte: u8) {}
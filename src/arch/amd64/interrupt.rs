@@ -116,6 +116,61 @@ pub unsafe fn send_eoi(interrupt_vector: u8) {
 }
 
 
+/// Yerel APIC sahte kesme vektörü (SVR'de programlanan geleneksel değer).
+/// Bu vektör için EOI gönderilmez.
+pub const APIC_SPURIOUS_VECTOR: u8 = 0xFF;
+
+/// OCW3: bir sonraki komut portu okuması ISR (hizmetteki kesmeler) döndürür.
+const OCW3_READ_ISR: u8 = 0x0B;
+
+/// Vektörün sahte PIC kesmesi olup olmadığını denetler ve gerekiyorsa
+/// onaylar. 8259A, istek hattı INTA döngüsünden önce düşerse IRQ 7 (master)
+/// veya IRQ 15 (slave) bildirir; bu durumda ISR'de ilgili bit ayarlı değildir.
+///
+/// * Sahte IRQ 7: EOI gönderilmez (master ISR'de bit yok).
+/// * Sahte IRQ 15: yalnızca master'a EOI gönderilir (master, IRQ 2 kademe
+///   hattını gerçek sanmıştır).
+///
+/// # Dönüş Değeri
+/// Kesme sahteyse `true`; çağıran işleyiciyi çalıştırmamalı ve EOI
+/// göndermemelidir.
+pub unsafe fn check_spurious(interrupt_vector: u8) -> bool {
+    const EOI: u8 = 0x20;
+    if interrupt_vector == APIC_SPURIOUS_VECTOR {
+        return true;
+    }
+    let (command, bit) = match interrupt_vector.wrapping_sub(PIC_OFFSET) {
+        7 => (PIC1_COMMAND, 7),
+        15 => (PIC2_COMMAND, 7),
+        _ => return false,
+    };
+    port_out(command, OCW3_READ_ISR);
+    if port_in(command) & (1 << bit) != 0 {
+        return false;
+    }
+    if command == PIC2_COMMAND {
+        port_out(PIC1_COMMAND, EOI);
+    }
+    true
+}
+
+/// OCW3: bir sonraki komut portu okuması IRR (bekleyen istekler) döndürür.
+const OCW3_READ_IRR: u8 = 0x0A;
+
+/// İki PIC'in bekleyen (IRR), hizmetteki (ISR) ve maske (IMR) yazmaçları;
+/// her değerde slave üst bayttadır. Teşhis içindir (bkz. `irqquarantine`).
+pub unsafe fn pic_state() -> (u16, u16, u16) {
+    let read = |ocw3: u8| {
+        port_out(PIC1_COMMAND, ocw3);
+        port_out(PIC2_COMMAND, ocw3);
+        u16::from_le_bytes([port_in(PIC1_COMMAND), port_in(PIC2_COMMAND)])
+    };
+    let irr = read(OCW3_READ_IRR);
+    let isr = read(OCW3_READ_ISR);
+    let imr = u16::from_le_bytes([port_in(PIC1_DATA), port_in(PIC2_DATA)]);
+    (irr, isr, imr)
+}
+
 /// Belirtilen IRQ hattını maskeler (devre dışı bırakır).
 pub unsafe fn mask_irq(irq_line: u8) {
     let port;
@@ -148,4 +203,80 @@ pub unsafe fn unmask_irq(irq_line: u8) {
         mask = port_in(port) & !(1 << (irq_line - 8));
     }
     port_out(port, mask);
-}
\ No newline at end of file
+}
+
+// -----------------------------------------------------------------------------
+// YEREL KESME MASKESİ
+// -----------------------------------------------------------------------------
+
+// Genel kod bunları `crate::irqoff` üzerinden kullanır. `asm!` bloklarında
+// `nomem` yoktur: maske değişikliği derleyici için bir bellek engelidir,
+// kilitli bölgenin erişimleri dışarı taşınmaz.
+
+/// Yerel kesmeleri kapatır; önceki RFLAGS değerini döndürür.
+#[inline(always)]
+pub fn local_irq_save() -> usize {
+    let flags: usize;
+    // SAFETY: Yalnızca RFLAGS okunur ve IF temizlenir.
+    unsafe { asm!("pushfq", "pop {}", "cli", out(reg) flags) };
+    flags
+}
+
+/// `local_irq_save` öncesindeki durumu geri yükler.
+#[inline(always)]
+pub fn local_irq_restore(flags: usize) {
+    if irqs_were_enabled(flags) {
+        // SAFETY: Kesmeler kaydedildiğinde açıktı.
+        unsafe { asm!("sti", options(nostack)) };
+    }
+}
+
+/// Kaydedilen durumda kesmeler açık mıydı (RFLAGS.IF)?
+#[inline(always)]
+pub fn irqs_were_enabled(flags: usize) -> bool {
+    flags & (1 << 9) != 0
+}
+
+/// Yerel kesmeleri koşulsuz açar.
+#[inline(always)]
+pub fn local_irq_enable() {
+    // SAFETY: Çağıran kesme almaya hazırdır.
+    unsafe { asm!("sti", options(nostack)) };
+}
+
+// -----------------------------------------------------------------------------
+// HAT MASKESİ VE PCI INTx
+// -----------------------------------------------------------------------------
+
+/// Kesme hattını denetleyicide maskeler veya açar (bkz. `crate::irqstorm`);
+/// `irq` dağıtıcının gördüğü numaradır. Desteklenmiyorsa `false`.
+pub fn set_irq_masked(irq: u32, masked: bool) -> bool {
+    // Vektör 32-47: 8259 PIC hatları
+    let Some(line) = irq.checked_sub(32).filter(|&l| l < 16) else { return false };
+    // SAFETY: PIC maske yazmaçlarına tek bayt okuma-değiştirme-yazma.
+    unsafe {
+        if masked {
+            mask_irq(line as u8);
+        } else {
+            unmask_irq(line as u8);
+        }
+    }
+    true
+}
+
+/// PCI aygıtının INTx hattını açar ve dağıtıcının göreceği kesme numarasını
+/// döndürür. INTx eşlemesi yoksa `None`; sürücü yoklamayla çalışır.
+pub fn route_pci_intx(line: u8) -> Option<u32> {
+    // PIC hatları 32. vektörden başlar
+    if line < 16 {
+        // SAFETY: PIC maske yazmacında yalnızca bu hattın biti değişir.
+        unsafe { unmask_irq(line) };
+    }
+    Some(32 + u32::from(line))
+}
+
+/// Aygıt ağacı düğümünün ilk kesmesini denetleyicinin göreceği numaraya
+/// çevirir. Bu portun denetleyicisi tek hücreli kesme belirteci kullanır.
+pub fn decode_fdt_irq(node: &crate::fdt::Node) -> Option<u32> {
+    node.interrupt_cell(0)
+}
@@ -85,6 +85,18 @@ pub unsafe fn mmio_write_u8(addr: usize, value: u8) {
     write_volatile(addr as *mut u8, value)
 }
 
+/// Belirtilen bellek adresinden (MMIO) bir u16 okur.
+#[inline(always)]
+pub unsafe fn mmio_read_u16(addr: usize) -> u16 {
+    read_volatile(addr as *const u16)
+}
+
+/// Belirtilen bellek adresine (MMIO) bir u16 yazar.
+#[inline(always)]
+pub unsafe fn mmio_write_u16(addr: usize, value: u16) {
+    write_volatile(addr as *mut u16, value)
+}
+
 /// Belirtilen bellek adresinden (MMIO) bir u32 okur.
 #[inline(always)]
 pub unsafe fn mmio_read_u32(addr: usize) -> u32 {
@@ -121,4 +133,41 @@ pub fn io_wait() {
     // Port 0x80 genellikle bir 'checkpoint' portu olarak kullanılır.
     // Sadece yazma işlemi bir miktar gecikme sağlar.
     unsafe { port_outb(0x80, 0) };
-}
\ No newline at end of file
+}
+
+// -----------------------------------------------------------------------------
+// 4. PCI YAPILANDIRMA ALANI (0xCF8/0xCFC)
+// -----------------------------------------------------------------------------
+
+/// Yapılandırma alanına G/Ç portlarıyla erişilir (bkz. `crate::drivers::pci`).
+pub const PCI_CONFIG_PORTS: bool = true;
+
+const PCI_CONFIG_ADDRESS: u16 = 0xCF8;
+const PCI_CONFIG_DATA: u16 = 0xCFC;
+
+/// Adres/veri çifti bölünmez kullanılmalıdır.
+static PCI_CONFIG_LOCK: crate::platformgeneric::spinlock::Spinlock = crate::platformgeneric::spinlock::Spinlock::new();
+
+/// `address` (etkin bit, veri yolu, aygıt, fonksiyon, ofset) ile seçilen
+/// 32 bitlik yapılandırma kelimesini okur.
+pub fn pci_config_read32(address: u32) -> u32 {
+    PCI_CONFIG_LOCK.lock();
+    // SAFETY: 0xCF8/0xCFC PCI yapılandırma mekanizması #1'e aittir; kilit çifti korur.
+    let value = unsafe {
+        port_outl(PCI_CONFIG_ADDRESS, address);
+        port_inl(PCI_CONFIG_DATA)
+    };
+    PCI_CONFIG_LOCK.unlock();
+    value
+}
+
+/// `address` ile seçilen 32 bitlik yapılandırma kelimesini yazar.
+pub fn pci_config_write32(address: u32, value: u32) {
+    PCI_CONFIG_LOCK.lock();
+    // SAFETY: Bkz. `pci_config_read32`.
+    unsafe {
+        port_outl(PCI_CONFIG_ADDRESS, address);
+        port_outl(PCI_CONFIG_DATA, value);
+    }
+    PCI_CONFIG_LOCK.unlock();
+}
@@ -3,7 +3,10 @@
 
 use core::arch::asm;
 use core::ptr::NonNull;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use crate::platformgeneric::KernelError;
 use crate::serial_println;
+use crate::memory::vmaudit::{validate_mapping, CacheAttr, MapError, Mapping, Perms};
 
 // -----------------------------------------------------------------------------
 // SAYFALAMA SABİTLERİ VE TİPLERİ
@@ -27,6 +30,9 @@ pub enum PageFlags {
     NO_EXEC   = 1 << 63, // Yürütülemez (Execute Disable - XD)
 }
 
+/// PTE'deki fiziksel adres alanı (bit 12..51).
+const ADDR_MASK: u64 = 0x000F_FFFF_FFFF_F000;
+
 /// Sayfa Tablosu Girişi (PTE)
 #[repr(transparent)]
 #[derive(Clone, Copy)]
@@ -56,8 +62,8 @@ impl PageTableEntry {
 
     /// Girişten fiziksel sayfa çerçevesi adresini döndürür.
     pub fn addr(&self) -> usize {
-        // Alt 12 bit (bayraklar) ve üst 4 bit (bayraklar) temizlenir
-        (self.0 & 0x000F_FFFF_FFFF_F000) as usize
+        // Alt 12 bit (bayraklar) ve üst 12 bit (bayraklar) temizlenir
+        (self.0 & ADDR_MASK) as usize
     }
 
     /// Yeni bir girdi oluşturur.
@@ -102,25 +108,80 @@ fn get_indices(virtual_addr: usize) -> (usize, usize, usize, usize) {
     (pml4, pdpt, pd, pt)
 }
 
-/// Yeni bir sayfa tablosu tahsis etmeyi simüle eder.
-/// (Gerçekte bir fiziksel bellek yöneticisi gereklidir.)
-/// Şimdilik sadece bir statik alanda sıfırlanmış bir tablo döndürür.
-fn alloc_page_table() -> NonNull<PageTable> {
-    // statik değişkeni kullanmak yerine, gerçek bir bellek tahsisini simüle ederiz.
-    // Lütfen unutmayın: Bu, gerçek bellek yöneticisi olmadan tehlikelidir ve sadece bir mock'tur.
-    static mut PAGE_TABLE_MOCK: [u8; PAGE_SIZE] = [0; PAGE_SIZE];
-    
-    let ptr = unsafe { PAGE_TABLE_MOCK.as_mut_ptr() as *mut PageTable };
-    let table = unsafe { &mut *ptr };
-    table.zero();
-    
-    // Güvenli olmayan bir şekilde NonNull oluşturma.
-    unsafe { NonNull::new_unchecked(ptr) }
+/// Sıfırlanmış yeni bir sayfa tablosu ayırır (bkz. `physmap::alloc_table`).
+fn alloc_page_table() -> Result<NonNull<PageTable>, MapError> {
+    let addr = crate::physmap::alloc_table().ok_or(MapError::OutOfMemory)?;
+    // SAFETY: `alloc_table` sıfırdan farklı, sayfa hizalı bir adres döndürür.
+    Ok(unsafe { NonNull::new_unchecked(addr as *mut PageTable) })
+}
+
+/// Donanımın güncellediği bitler; eşlemelerin karşılaştırılmasında yok sayılır.
+const HW_BITS: u64 = PageFlags::ACCESSED as u64 | PageFlags::DIRTY as u64;
+
+/// Tablo girişi geçerliyse gösterdiği tabloyu, değilse yeni ayrılan tabloyu
+/// döndürür; yeni tablo üst tablonun giriş sayısına eklenir (`physmap::table_ref`).
+/// Giriş büyük sayfaysa (PS=1) adres zaten eşlidir.
+unsafe fn next_table(table: &mut PageTable, index: usize) -> Result<&'static mut PageTable, MapError> {
+    let table_addr = table as *mut PageTable as usize;
+    let entry = table.get_entry(index);
+    if !entry.is_present() {
+        let new_addr = alloc_page_table()?.as_ptr() as usize;
+        *entry = PageTableEntry::new(new_addr, PageFlags::PRESENT as u64 | PageFlags::WRITABLE as u64);
+        crate::physmap::table_ref(table_addr);
+    } else if entry.0 & PageFlags::HUGE_PAGE as u64 != 0 {
+        return Err(MapError::AlreadyMapped);
+    }
+    Ok(&mut *(entry.addr() as *mut PageTable))
+}
+
+/// Yaprak girişini yazar. Aynı eşlemenin tekrarı (donanım bitleri hariç)
+/// etkisizdir; farklı bir eşleme yalnızca `replace` ile değiştirilir.
+unsafe fn set_leaf(table: &mut PageTable, index: usize, new: PageTableEntry, replace: bool) -> Result<(), MapError> {
+    let table_addr = table as *mut PageTable as usize;
+    let entry = table.get_entry(index);
+    if entry.is_present() {
+        if (entry.0 ^ new.0) & !HW_BITS == 0 {
+            return Ok(());
+        }
+        if !replace {
+            return Err(MapError::AlreadyMapped);
+        }
+    } else {
+        crate::physmap::table_ref(table_addr);
+    }
+    *entry = new;
+    Ok(())
 }
 
+/// Adres ve bayrakların ortak denetimi: 48 bit kanonik sanal adres, 52 bit
+/// fiziksel adres; bayraklar adres alanına (bit 12..51) taşamaz.
+fn validate(virtual_addr: usize, physical_addr: usize, size: usize, flags: u64) -> Result<(), MapError> {
+    validate_mapping(virtual_addr, physical_addr, size, 48, 52, flags, ADDR_MASK)
+}
+
+/// 4 KiB eşlemenin ortak yolu (`map_page` / `remap_page`).
+unsafe fn map_small(pml4_addr: usize, virtual_addr: usize, physical_addr: usize, flags: u64, replace: bool) -> Result<(), MapError> {
+    validate(virtual_addr, physical_addr, PAGE_SIZE, flags)?;
+    let (pml4i, pdpti, pdi, pti) = get_indices(virtual_addr);
+
+    let pml4 = &mut *(pml4_addr as *mut PageTable);
+    // PML4 -> PDPT -> PD -> PT; eksik tablolar oluşturulur
+    let pdpt = next_table(pml4, pml4i)?;
+    let pd = next_table(pdpt, pdpti)?;
+    let pt = next_table(pd, pdi)?;
+
+    // PT Girişini ayarla (Son eşleme)
+    set_leaf(pt, pti, PageTableEntry::new(physical_addr, flags), replace)
+}
 
 /// Sanal adresi fiziksel adrese eşler (4KiB sayfa).
 ///
+/// # Hatalar
+/// * `Unaligned` / `OutOfRange` / `InvalidFlags`: bkz. `validate_mapping`.
+/// * `AlreadyMapped`: Adres başka bir adrese veya bayraklarla eşli ya da bir
+///   büyük sayfanın içinde.
+/// * `OutOfMemory`: Ara tablo ayrılamadı.
+///
 /// # Güvenlik Notu
 /// Bu fonksiyon doğrudan bellek adreslerini değiştirir ve bellek yöneticisinden
 /// bağımsız olarak tahsis ve haritalama yapar.
@@ -129,53 +190,81 @@ pub unsafe fn map_page(
     virtual_addr: usize,
     physical_addr: usize,
     flags: u64,
-) {
-    let (pml4i, pdpti, pdi, pti) = get_indices(virtual_addr);
-    
+) -> Result<(), MapError> {
+    map_small(pml4_addr, virtual_addr, physical_addr, flags, false)
+}
+
+/// `map_page` gibi, ancak mevcut 4 KiB eşlemeyi (adres veya bayraklar)
+/// değiştirir. Çağıran eski çeviri için TLB'yi boşaltmalıdır.
+pub unsafe fn remap_page(pml4_addr: usize, virtual_addr: usize, physical_addr: usize, flags: u64) -> Result<(), MapError> {
+    map_small(pml4_addr, virtual_addr, physical_addr, flags, true)
+}
+
+/// Sanal adresi 2 MiB'lık büyük sayfa (PD girişi, PS=1) olarak eşler.
+/// Adresler `physmap::LARGE_PAGE_SIZE` hizalı olmalıdır.
+///
+/// # Hatalar
+/// `map_page` ile aynı; ayrıca PD girişi zaten bir PT gösteriyorsa `AlreadyMapped`.
+pub unsafe fn map_large_page(pml4_addr: usize, virtual_addr: usize, physical_addr: usize, flags: u64) -> Result<(), MapError> {
+    validate(virtual_addr, physical_addr, crate::physmap::LARGE_PAGE_SIZE, flags)?;
+    let (pml4i, pdpti, pdi, _) = get_indices(virtual_addr);
     let pml4 = &mut *(pml4_addr as *mut PageTable);
-    
-    // 1. PDPT'yi al veya oluştur
-    let pdpt_entry = pml4.get_entry(pml4i);
-    let pdpt_addr = if pdpt_entry.is_present() {
-        pdpt_entry.addr()
-    } else {
-        let new_pdpt = alloc_page_table();
-        let new_addr = new_pdpt.as_ptr() as usize;
-        *pdpt_entry = PageTableEntry::new(new_addr, PageFlags::PRESENT as u64 | PageFlags::WRITABLE as u64);
-        new_addr
-    };
-    
-    let pdpt = &mut *(pdpt_addr as *mut PageTable);
-    
-    // 2. PD'yi al veya oluştur
-    let pd_entry = pdpt.get_entry(pdpti);
-    let pd_addr = if pd_entry.is_present() {
-        pd_entry.addr()
-    } else {
-        let new_pd = alloc_page_table();
-        let new_addr = new_pd.as_ptr() as usize;
-        *pd_entry = PageTableEntry::new(new_addr, PageFlags::PRESENT as u64 | PageFlags::WRITABLE as u64);
-        new_addr
-    };
+    let pdpt = next_table(pml4, pml4i)?;
+    let pd = next_table(pdpt, pdpti)?;
+    let entry = pd.get_entry(pdi);
+    if entry.is_present() && entry.0 & PageFlags::HUGE_PAGE as u64 == 0 {
+        return Err(MapError::AlreadyMapped);
+    }
+    set_leaf(pd, pdi, PageTableEntry::new(physical_addr, flags | PageFlags::HUGE_PAGE as u64), false)
+}
 
-    let pd = &mut *(pd_addr as *mut PageTable);
-    
-    // 3. PT'yi al veya oluştur
-    let pt_entry = pd.get_entry(pdi);
-    let pt_addr = if pt_entry.is_present() {
-        pt_entry.addr()
-    } else {
-        let new_pt = alloc_page_table();
-        let new_addr = new_pt.as_ptr() as usize;
-        *pt_entry = PageTableEntry::new(new_addr, PageFlags::PRESENT as u64 | PageFlags::WRITABLE as u64);
-        new_addr
+/// Sanal adresi içeren yaprak eşlemeyi (4 KiB, 2 MiB veya 1 GiB) kaldırır,
+/// TLB girişini geçersiz kılar ve boşalan ara tabloları serbest bırakır.
+/// Eşlenmiş fiziksel adresi döndürür.
+///
+/// # Hatalar
+/// * `NotMapped`: Adres eşli değil.
+/// * `Unaligned`: Adres, onu içeren büyük sayfanın başlangıcı değil.
+pub unsafe fn unmap_page(pml4_addr: usize, virtual_addr: usize) -> Result<usize, MapError> {
+    let (pml4i, pdpti, pdi, pti) = get_indices(virtual_addr);
+    let indices = [pml4i, pdpti, pdi, pti];
+    let mut path = [pml4_addr, 0, 0, 0];
+    let mut level = 0;
+    let physical_addr = loop {
+        let entry = (*(path[level] as *mut PageTable)).get_entry(indices[level]);
+        if !entry.is_present() {
+            return Err(MapError::NotMapped);
+        }
+        if level == 3 || (level > 0 && entry.0 & PageFlags::HUGE_PAGE as u64 != 0) {
+            let size = 1usize << (12 + 9 * (3 - level));
+            if !virtual_addr.is_multiple_of(size) {
+                return Err(MapError::Unaligned);
+            }
+            // Büyük sayfalarda bit 12 PAT bitidir
+            let physical_addr = entry.addr() & !(size - 1);
+            *entry = PageTableEntry::zero();
+            break physical_addr;
+        }
+        path[level + 1] = entry.addr();
+        level += 1;
     };
-    
-    let pt = &mut *(pt_addr as *mut PageTable);
-    
-    // 4. PT Girişini ayarla (Son eşleme)
-    let final_entry = pt.get_entry(pti);
-    *final_entry = PageTableEntry::new(physical_addr, flags);
+    flush_page(virtual_addr);
+
+    // Boşalan tablolar yukarı doğru serbest bırakılır; kök izlenmez (`None`).
+    while crate::physmap::table_unref(path[level]) == Some(0) && level > 0 {
+        *(*(path[level - 1] as *mut PageTable)).get_entry(indices[level - 1]) = PageTableEntry::zero();
+        // INVLPG, adrese ait ara tablo önbelleklerini de geçersiz kılar
+        flush_page(virtual_addr);
+        crate::physmap::free_table(path[level]);
+        level -= 1;
+    }
+    Ok(physical_addr)
+}
+
+/// Tek bir sayfanın TLB girişini geçersiz kılar.
+#[inline(always)]
+unsafe fn flush_page(virtual_addr: usize) {
+    asm!("invlpg [{0}]", in(reg) virtual_addr, options(nostack, preserves_flags));
 }
 
 // -----------------------------------------------------------------------------
@@ -185,8 +274,9 @@ pub unsafe fn map_page(
 /// Sayfalama için yeni PML4 tablosunu hazırlar ve sanal adresleri eşler.
 ///
 /// # Geri Dönüş
-/// Yeni sayfa tablosunun fiziksel adresi (CR3'e yazılacak adres).
-pub fn setup_initial_paging() -> usize {
+/// Yeni sayfa tablosunun fiziksel adresi (CR3'e yazılacak adres); RAM
+/// eşlenemezse eşleme hatası.
+pub fn setup_initial_paging() -> Result<usize, MapError> {
     serial_println!("[x86_64] İlk Sayfa Tablosu Hazırlanıyor...");
     
     // Statik olarak hizalanmış bir bellek bloğunu PML4 olarak kullan.
@@ -202,18 +292,14 @@ pub fn setup_initial_paging() -> usize {
                      | PageFlags::WRITABLE as u64 
                      | PageFlags::NO_EXEC as u64; 
 
-    // Örnek eşleme: İlk 16MB'ı birebir (identity) eşle.
-    // Çekirdek genellikle bu bölgede çalışır.
-    let identity_mapping_size = 16 * 1024 * 1024; // 16 MB
-
-    for addr in (0..identity_mapping_size).step_by(PAGE_SIZE) {
-        unsafe {
-            map_page(pml4_addr, addr, addr, kernel_flags);
-        }
-    }
-
-    serial_println!("[x86_64] İlk 16MB birebir eşlendi (Identity Mapped).");
-    pml4_addr
+    // Tüm RAM birebir eşlenir (sanal = fiziksel); aygıtlar `physmap::map_mmio` ile.
+    crate::physmap::map_ram(
+        0,
+        &mut |virt, phys| unsafe { map_large_page(pml4_addr, virt, phys, kernel_flags) },
+        &mut |virt, phys| unsafe { map_page(pml4_addr, virt, phys, kernel_flags) },
+    )?;
+    ROOT_TABLE.store(pml4_addr, Ordering::Relaxed);
+    Ok(pml4_addr)
 }
 
 /// Sayfalama mekanizmasını etkinleştirir.
@@ -242,11 +328,172 @@ pub unsafe fn enable_paging(pml4_phys_addr: usize) {
 /// Sayfalama sonrası çekirdek başlatma işlevi.
 /// `main.rs` içinden çağrılmalıdır.
 pub fn init_mmu() {
-    // İlk sayfa tablosunu hazırla
-    let pml4_addr = setup_initial_paging();
+    // İlk sayfa tablosunu hazırla; eksik tabloyla CR3 yüklenmez
+    let pml4_addr = match setup_initial_paging() {
+        Ok(addr) => addr,
+        Err(e) => {
+            serial_println!("[x86_64] İlk sayfa tablosu kurulamadı: {:?}", e);
+            return;
+        }
+    };
     
     // Sayfalamayı etkinleştir
     unsafe {
         enable_paging(pml4_addr);
     }
-}
\ No newline at end of file
+}
+
+// -----------------------------------------------------------------------------
+// AYGIT EŞLEMELERİ (physmap)
+// -----------------------------------------------------------------------------
+
+/// `setup_initial_paging` tarafından kurulan PML4 (0: henüz yok).
+static ROOT_TABLE: AtomicUsize = AtomicUsize::new(0);
+
+/// Aygıt bölgesini çekirdek için birebir, önbelleksiz eşler
+/// (`physmap::map_mmio`) ve sanal adresini döndürür.
+///
+/// # Hatalar
+/// * `Map(NotReady)`: Sayfalama henüz kurulmadı.
+/// * Eşleme hataları `MapError` dönüşümüyle (bkz. `vmaudit`).
+pub fn map_device(phys: usize, len: usize) -> Result<usize, KernelError> {
+    let root = ROOT_TABLE.load(Ordering::Relaxed);
+    if root == 0 {
+        return Err(MapError::NotReady.into());
+    }
+    let flags = PageFlags::PRESENT as u64
+        | PageFlags::WRITABLE as u64
+        | PageFlags::NO_CACHE as u64
+        | PageFlags::WRITE_THR as u64
+        | PageFlags::NO_EXEC as u64;
+    crate::physmap::map_range(
+        phys,
+        phys,
+        len,
+        &mut |virt, phys| unsafe {
+            map_large_page(root, virt, phys, flags)?;
+            asm!("invlpg [{0}]", in(reg) virt, options(nostack, preserves_flags));
+            Ok(())
+        },
+        &mut |virt, phys| unsafe {
+            map_page(root, virt, phys, flags)?;
+            asm!("invlpg [{0}]", in(reg) virt, options(nostack, preserves_flags));
+            Ok(())
+        },
+    )?;
+    Ok(phys)
+}
+
+// -----------------------------------------------------------------------------
+// ÇEKİRDEK SANAL ALANI (vmalloc)
+// -----------------------------------------------------------------------------
+
+/// `memory::vmalloc` penceresinin başlangıcı (üst yarı, RAM eşlemesinin dışında).
+pub const VMALLOC_BASE: usize = 0xFFFF_C900_0000_0000;
+/// `memory::vmalloc` penceresinin boyutu.
+pub const VMALLOC_SIZE: usize = 1 << 30;
+
+/// Çekirdek verisi için 4 KiB sayfa eşler (yazılabilir, yürütülemez, global).
+///
+/// # Hatalar
+/// * `Map(NotReady)`: Sayfalama henüz kurulmadı.
+/// * Eşleme hataları `MapError` dönüşümüyle (bkz. `vmaudit`).
+pub fn map_kernel_page(virt: usize, phys: usize) -> Result<(), KernelError> {
+    let root = ROOT_TABLE.load(Ordering::Relaxed);
+    if root == 0 {
+        return Err(MapError::NotReady.into());
+    }
+    let flags = PageFlags::PRESENT as u64 | PageFlags::WRITABLE as u64 | PageFlags::GLOBAL as u64 | PageFlags::NO_EXEC as u64;
+    // SAFETY: Pencere yalnızca `vmalloc` tarafından yönetilir; geçersiz girişler TLB'de tutulmaz.
+    unsafe { map_page(root, virt, phys, flags)? };
+    Ok(())
+}
+
+/// `map_kernel_page` eşlemesini kaldırır ve fiziksel adresini döndürür.
+pub unsafe fn unmap_kernel_page(virt: usize) -> Option<usize> {
+    match ROOT_TABLE.load(Ordering::Relaxed) {
+        0 => None,
+        root => unmap_page(root, virt).ok(),
+    }
+}
+
+// -----------------------------------------------------------------------------
+// SAYFA TABLOSU DENETİMİ (vm dump)
+// -----------------------------------------------------------------------------
+/// 48 bitlik sanal adresi kanonik biçime (bit 47'nin işaret genişletmesi) getirir.
+fn canonical(addr: usize) -> usize {
+    (((addr << 16) as isize) >> 16) as usize
+}
+
+/// Bir tablo seviyesini özyinelemeli olarak dolaşır.
+///
+/// `inherited`, üst seviyelerden gelen (write, user, exec) izinleridir; x86'da
+/// etkin izin tüm seviyelerin kesişimidir.
+unsafe fn walk_level(table_addr: usize, level: usize, base: usize, inherited: (bool, bool, bool), visit: &mut dyn FnMut(Mapping)) {
+    let table = &*(table_addr as *const PageTable);
+    // Seviye 3: PML4 (512G), 2: PDPT (1G), 1: PD (2M), 0: PT (4K)
+    let shift = 12 + 9 * level;
+
+    for (index, entry) in table.entries.iter().enumerate() {
+        if !entry.is_present() {
+            continue;
+        }
+        let raw = entry.0;
+        let vaddr = canonical(base | (index << shift));
+        let perms = (
+            inherited.0 && raw & PageFlags::WRITABLE as u64 != 0,
+            inherited.1 && raw & PageFlags::USER_ACC as u64 != 0,
+            inherited.2 && raw & PageFlags::NO_EXEC as u64 == 0,
+        );
+
+        let is_leaf = level == 0 || ((level == 1 || level == 2) && raw & PageFlags::HUGE_PAGE as u64 != 0);
+        if !is_leaf {
+            walk_level(entry.addr(), level - 1, vaddr, perms, visit);
+            continue;
+        }
+
+        let cache = if raw & PageFlags::NO_CACHE as u64 != 0 {
+            CacheAttr::Uncached
+        } else if raw & PageFlags::WRITE_THR as u64 != 0 {
+            CacheAttr::WriteThrough
+        } else {
+            CacheAttr::WriteBack
+        };
+
+        // Büyük sayfalarda bit 12 PAT bitidir; adres hizalamaya göre maskelenir.
+        let size = 1usize << shift;
+        visit(Mapping {
+            vaddr,
+            paddr: entry.addr() & !(size - 1),
+            size,
+            perms: Perms { read: true, write: perms.0, exec: perms.2, user: perms.1 },
+            cache,
+        });
+    }
+}
+
+/// CR3'teki etkin PML4'ten başlayarak tüm yaprak eşlemeleri artan sanal adres sırasıyla bildirir.
+pub fn walk_mappings(visit: &mut dyn FnMut(Mapping)) {
+    let cr3: usize;
+    unsafe {
+        asm!("mov {}, cr3", out(reg) cr3, options(nomem, nostack, preserves_flags));
+        // Çekirdek sayfa tabloları birebir eşlidir; fiziksel adres doğrudan erişilebilir.
+        walk_level(cr3 & ADDR_MASK as usize, 3, 0, (true, true, true), visit);
+    }
+}
+
+// -----------------------------------------------------------------------------
+// KULLANICI SAYFALARI (mmap)
+// -----------------------------------------------------------------------------
+
+/// Kullanıcı sayfası eşlemesi bu portta henüz yok; `mmap` dosya eşlemeleri
+/// `PlatformSpecificError` ile reddedilir.
+///
+/// # Safety
+/// Diğer portlarla aynı sözleşme (bkz. `rv64i::mmu::map_user_page`).
+pub unsafe fn map_user_page(_virtual_addr: usize, _physical_addr: usize, _write: bool, _exec: bool) -> Result<(), crate::platformgeneric::KernelError> {
+    Err(crate::platformgeneric::KernelError::PlatformSpecificError(0))
+}
+
+/// Kullanıcı sayfası eşlenemediğinden etkisizdir.
+pub unsafe fn unmap_user_page(_virtual_addr: usize) {}
@@ -0,0 +1,114 @@
+// src/arch/amd64/multiboot.rs
+// AMD64 (x86_64) Multiboot2 bilgi yapısının (MBI) `BootInfo`'ya çözülmesi.
+//
+// MBI, 8 baytlık başlıktan (toplam boyut, ayrılmış) sonra 8 bayt hizalı
+// etiketlerden oluşur; her etiket `tür` ve `boyut` ile başlar, tür 0 ile
+// biter. Yalnızca çekirdeğin kullandığı etiketler okunur; diğerleri atlanır.
+// Biçim: Multiboot2 Specification 1.0, bölüm 3.6.
+
+#![allow(dead_code)]
+
+use crate::boot::{BootInfo, Firmware, MemoryKind};
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// ETİKETLER
+// -----------------------------------------------------------------------------
+
+const TAG_END: u32 = 0;
+const TAG_CMDLINE: u32 = 1;
+const TAG_MODULE: u32 = 3;
+const TAG_BASIC_MEMINFO: u32 = 4;
+const TAG_MMAP: u32 = 6;
+const TAG_ACPI_OLD: u32 = 14;
+const TAG_ACPI_NEW: u32 = 15;
+
+// Bellek haritası girdi türleri
+const MMAP_AVAILABLE: u32 = 1;
+const MMAP_ACPI_RECLAIMABLE: u32 = 3;
+const MMAP_ACPI_NVS: u32 = 4;
+const MMAP_BAD: u32 = 5;
+
+unsafe fn read_u32(addr: usize) -> u32 {
+    core::ptr::read_unaligned(addr as *const u32)
+}
+
+unsafe fn read_u64(addr: usize) -> u64 {
+    core::ptr::read_unaligned(addr as *const u64)
+}
+
+fn memory_kind(kind: u32) -> MemoryKind {
+    match kind {
+        MMAP_AVAILABLE => MemoryKind::Usable,
+        MMAP_ACPI_RECLAIMABLE => MemoryKind::AcpiReclaimable,
+        MMAP_ACPI_NVS => MemoryKind::AcpiNvs,
+        MMAP_BAD => MemoryKind::Bad,
+        _ => MemoryKind::Reserved,
+    }
+}
+
+// -----------------------------------------------------------------------------
+// ÇÖZÜMLEME
+// -----------------------------------------------------------------------------
+
+/// `addr`'deki MBI'yı okur ve `info`'yu doldurur.
+///
+/// * Komut satırı (1) kopyalanır.
+/// * İlk modül (3) initramfs kabul edilir.
+/// * Bellek haritası (6) olduğu gibi aktarılır; yoksa temel bellek bilgisi
+///   (4) iki kullanılabilir bölgeye çevrilir.
+/// * ACPI 2.0 RSDP (15), 1.0 RSDP'ye (14) tercih edilir. RSDP etiketin
+///   içinde kopya olarak gelir; adresi etiket verisidir.
+///
+/// # Safety
+/// `addr` geçerli, eşlenmiş bir Multiboot2 bilgi yapısını göstermelidir.
+pub unsafe fn parse(addr: usize, info: &mut BootInfo) {
+    info.firmware = Firmware::Multiboot2;
+    let total = read_u32(addr) as usize;
+    let mut basic = None;
+    let mut off = 8;
+    while off + 8 <= total {
+        let tag = addr + off;
+        let kind = read_u32(tag);
+        let size = read_u32(tag + 4) as usize;
+        if kind == TAG_END || size < 8 {
+            break;
+        }
+        match kind {
+            TAG_CMDLINE => {
+                info.set_cmdline(core::slice::from_raw_parts((tag + 8) as *const u8, size - 8));
+            }
+            TAG_MODULE if info.initramfs.is_none() => {
+                let (start, end) = (read_u32(tag + 8) as usize, read_u32(tag + 12) as usize);
+                if end > start {
+                    info.initramfs = Some((start, end - start));
+                }
+            }
+            TAG_BASIC_MEMINFO => basic = Some((read_u32(tag + 8), read_u32(tag + 12))),
+            TAG_MMAP => {
+                let entry_size = read_u32(tag + 8) as usize;
+                let mut entry = tag + 16;
+                while entry_size >= 24 && entry + entry_size <= tag + size {
+                    let (base, len) = (read_u64(entry), read_u64(entry + 8));
+                    if info.add_memory(base, len, memory_kind(read_u32(entry + 16))).is_err() {
+                        serial_println!("[MULTIBOOT] UYARI: bellek haritası kesildi");
+                        break;
+                    }
+                    entry += entry_size;
+                }
+            }
+            TAG_ACPI_NEW => info.acpi_rsdp = Some(tag + 8),
+            TAG_ACPI_OLD if info.acpi_rsdp.is_none() => info.acpi_rsdp = Some(tag + 8),
+            _ => {}
+        }
+        off += (size + 7) & !7;
+    }
+
+    // Temel bellek bilgisi KiB cinsindendir: alt bellek 0'dan, üst bellek 1 MiB'den
+    if info.memory_map().is_empty() {
+        if let Some((lower, upper)) = basic {
+            let _ = info.add_memory(0, u64::from(lower) * 1024, MemoryKind::Usable);
+            let _ = info.add_memory(0x10_0000, u64::from(upper) * 1024, MemoryKind::Usable);
+        }
+    }
+}
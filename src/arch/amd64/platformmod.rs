@@ -82,7 +82,9 @@ pub fn platform_init() {
     // 1. Seri G/Ç doğrulama (Zaten `main.rs` veya `lib.rs` tarafından yapılmış olmalı).
 
     // 2. Temel işlemci durumunu kontrol etme ve ayarlama (Örn: MSR'lar).
-    // Gelişmiş kodda burada GDT/IDT/TSS/CR0/CR4 ayarları yapılacaktır.
+    // Gelişmiş kodda burada GDT/IDT/TSS/CR0 ayarları yapılacaktır.
+    // CR4.SMEP/SMAP/UMIP ve EFER.NXE, CPUID destekliyorsa açılır.
+    crate::arch::amd64::security::enable_cpu_protections();
     
     // 3. Kesmeleri devre dışı bırak (Güvenlik için)
     unsafe {
@@ -90,8 +92,28 @@ pub fn platform_init() {
     }
     serial_println!("[AMD64] Kesmeler devre dışı bırakıldı (CLI).");
 
+    // Errata ve kart tuhaflıkları (bkz. `quirks`) alt sistemlerden önce uygulanır.
+    crate::quirks::apply();
+
     // 4. Diğer alt sistemleri başlat (MMU, Zamanlayıcı, Kesme Denetleyicisi, vb.)
-    // Burası sadece platformmod.rs'nin görevi değil, ancak bir başlangıç noktasıdır.
+    // Alt sistemler kendilerini `initcall!` ile kaydeder; sıra tüm mimarilerde aynıdır.
+    crate::initcall::run_initcalls();
     
     serial_println!("[AMD64] Temel Platform Hazır.");
-}
\ No newline at end of file
+}
+
+// -----------------------------------------------------------------------------
+// ÖNYÜKLEME DEVRİ
+// -----------------------------------------------------------------------------
+
+/// Önyükleyicinin porta özgü devir yapısını okur (bkz. `crate::boot`).
+/// AMD64 aygıt ağacıyla açılmaz: Multiboot2 yoksa bilgi boş kalır ve her
+/// durumda `true` döner.
+pub fn parse_boot_handoff(regs: &crate::boot::BootRegs, info: &mut crate::boot::BootInfo) -> bool {
+    if regs.magic == crate::boot::MULTIBOOT2_MAGIC && regs.firmware != 0 {
+        // SAFETY: Sihirli sayı yapının Multiboot2 yükleyicisinden geldiğini
+        // gösterir; ilk 1 GiB kimlik eşlemelidir.
+        unsafe { super::multiboot::parse(regs.firmware as usize, info) };
+    }
+    true
+}
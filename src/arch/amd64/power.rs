@@ -54,6 +54,14 @@ pub fn system_reboot() -> ! {
     halt_loop();
 }
 
+/// İşlemciyi geçici olarak düşük güç moduna alır (Rölantide).
+/// Kesme gelene kadar işlemciyi duraklatır (HLT).
+pub fn system_idle() {
+    unsafe {
+        io::hlt();
+    }
+}
+
 /// Sistemi tamamen kapatmaya çalışır (Halt).
 ///
 /// **Yöntemler:**
@@ -2,6 +2,9 @@
 // AMD64 (x86_64) mimarisine özgü temel güvenlik işlevleri.
 
 use core::arch::asm;
+use core::arch::x86_64::{__cpuid, __cpuid_count};
+use core::sync::atomic::{AtomicBool, Ordering};
+use crate::platformgeneric::KernelError;
 use crate::serial_println;
 // arch/amd64/platformmod.rs dosyasından temel G/Ç (MSR, CR) işlevlerini içe aktarır.
 use super::platformmod::io; 
@@ -99,6 +102,248 @@ pub fn enable_nx_bit() {
     }
 }
 
+// -----------------------------------------------------------------------------
+// İşlemci Korumaları (SMEP / SMAP / UMIP / NX)
+// -----------------------------------------------------------------------------
+
+/// CR4.UMIP: Kullanıcı modunda SGDT/SIDT/SLDT/SMSW/STR talimatlarını engeller.
+const CR4_UMIP: u64 = 1 << 11;
+/// CR4.SMEP: Çekirdeğin kullanıcı sayfalarındaki kodu yürütmesini engeller.
+const CR4_SMEP: u64 = 1 << 20;
+/// CR4.SMAP: Çekirdeğin kullanıcı sayfalarına (RFLAGS.AC=0 iken) erişmesini engeller.
+const CR4_SMAP: u64 = 1 << 21;
+
+/// CPUID ile bildirilen koruma özellikleri.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CpuProtections {
+    pub smep: bool,
+    pub smap: bool,
+    pub umip: bool,
+    pub nx: bool,
+}
+
+/// CPUID 7.0 ve 0x8000_0001 yapraklarından koruma desteğini okur.
+pub fn detect_protections() -> CpuProtections {
+    // SAFETY: CPUID her x86_64 işlemcide mevcuttur.
+    unsafe {
+        let max_leaf = __cpuid(0).eax;
+        let max_ext_leaf = __cpuid(0x8000_0000).eax;
+
+        let mut prot = CpuProtections::default();
+        if max_leaf >= 7 {
+            let leaf7 = __cpuid_count(7, 0);
+            prot.smep = leaf7.ebx & (1 << 7) != 0;
+            prot.smap = leaf7.ebx & (1 << 20) != 0;
+            prot.umip = leaf7.ecx & (1 << 2) != 0;
+        }
+        if max_ext_leaf >= 0x8000_0001 {
+            prot.nx = __cpuid(0x8000_0001).edx & (1 << 20) != 0;
+        }
+        prot
+    }
+}
+
+/// SMAP etkin mi? `stac`/`clac` SMAP'sız işlemcide tanımsız talimattır.
+static SMAP_ENABLED: AtomicBool = AtomicBool::new(false);
+
+#[inline(always)]
+unsafe fn read_cr4() -> u64 {
+    let value: u64;
+    asm!("mov {}, cr4", out(reg) value, options(nomem, nostack, preserves_flags));
+    value
+}
+
+#[inline(always)]
+unsafe fn write_cr4(value: u64) {
+    asm!("mov cr4, {}", in(reg) value, options(nostack, preserves_flags));
+}
+
+/// CPUID'nin desteklediği SMEP/SMAP/UMIP ve EFER.NXE korumalarını etkinleştirir.
+/// `platform_init` tarafından, kullanıcı sayfaları oluşturulmadan önce çağrılır.
+pub fn enable_cpu_protections() -> CpuProtections {
+    let prot = detect_protections();
+
+    if prot.nx {
+        enable_nx_bit();
+    }
+
+    unsafe {
+        let mut cr4 = read_cr4();
+        if prot.smep {
+            cr4 |= CR4_SMEP;
+        }
+        if prot.smap {
+            cr4 |= CR4_SMAP;
+        }
+        if prot.umip {
+            cr4 |= CR4_UMIP;
+        }
+        write_cr4(cr4);
+    }
+    SMAP_ENABLED.store(prot.smap, Ordering::Release);
+
+    serial_println!(
+        "[SECURITY] SMEP: {} | SMAP: {} | UMIP: {} | NX: {}",
+        prot.smep, prot.smap, prot.umip, prot.nx
+    );
+    prot
+}
+
+// -----------------------------------------------------------------------------
+// Kullanıcı Belleği Erişimi
+// -----------------------------------------------------------------------------
+
+/// Kullanıcı alanının üst sınırı (4 seviyeli sayfalama, kanonik alt yarı).
+pub const USER_SPACE_END: usize = 0x0000_8000_0000_0000;
+
+/// `[addr, addr + len)` aralığının tamamen kullanıcı alanında olup olmadığını denetler.
+#[inline]
+fn user_range_ok(addr: usize, len: usize) -> bool {
+    match addr.checked_add(len) {
+        Some(end) => end <= USER_SPACE_END,
+        None => false,
+    }
+}
+
+/// `rep movsb` ile kopyalar. Kopyalama sırasında sayfa hatası olursa düzeltme
+/// tablosu (bkz. `crate::fixup`) sayesinde panik yerine kopyalanamayan bayt
+/// sayısı döndürülür.
+///
+/// SMAP etkinse erişim `stac`/`clac` ile sarılır; hata yolunda da `clac`
+/// çalıştırılır, çünkü istisna dönüşü RFLAGS.AC'yi geri yükler.
+#[inline(always)]
+unsafe fn user_copy(dst: *mut u8, src: *const u8, len: usize) -> usize {
+    let remaining: usize;
+    if SMAP_ENABLED.load(Ordering::Relaxed) {
+        asm!(
+            "stac",
+            "2: rep movsb",
+            "3: clac",
+            ".pushsection .ex_table, \"a\"",
+            ".balign 8",
+            ".quad 2b, 3b",
+            ".popsection",
+            inout("rcx") len => remaining,
+            inout("rdi") dst => _,
+            inout("rsi") src => _,
+            options(nostack)
+        );
+    } else {
+        asm!(
+            "2: rep movsb",
+            "3:",
+            ".pushsection .ex_table, \"a\"",
+            ".balign 8",
+            ".quad 2b, 3b",
+            ".popsection",
+            inout("rcx") len => remaining,
+            inout("rdi") dst => _,
+            inout("rsi") src => _,
+            options(nostack, preserves_flags)
+        );
+    }
+    remaining
+}
+
+/// Kullanıcı alanındaki `src` adresinden çekirdek arabelleğine `dst.len()` bayt kopyalar.
+///
+/// # Hatalar
+/// * `InvalidArgument`: Aralık kullanıcı alanının dışına taşıyor veya kopyalama
+///   sırasında eşlenmemiş bir kullanıcı sayfasına rastlandı.
+pub fn copy_from_user(dst: &mut [u8], src: usize) -> Result<(), KernelError> {
+    if !user_range_ok(src, dst.len()) {
+        return Err(KernelError::InvalidArgument);
+    }
+    match unsafe { user_copy(dst.as_mut_ptr(), src as *const u8, dst.len()) } {
+        0 => Ok(()),
+        _ => Err(KernelError::InvalidArgument),
+    }
+}
+
+/// Çekirdek arabelleği `src`'yi kullanıcı alanındaki `dst` adresine kopyalar.
+///
+/// # Hatalar
+/// Bkz. [`copy_from_user`].
+pub fn copy_to_user(dst: usize, src: &[u8]) -> Result<(), KernelError> {
+    if !user_range_ok(dst, src.len()) {
+        return Err(KernelError::InvalidArgument);
+    }
+    match unsafe { user_copy(dst as *mut u8, src.as_ptr(), src.len()) } {
+        0 => Ok(()),
+        _ => Err(KernelError::InvalidArgument),
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Donanım Şifreleme Hızlandırması (AES-NI)
+// -----------------------------------------------------------------------------
+
+/// CR4.OSFXSR: SSE talimatları etkin (aksi halde XMM erişimi #UD üretir).
+const CR4_OSFXSR: u64 = 1 << 9;
+
+/// AES-NI blok şifreleyicisi. Görev geçişleri SIMD durumunu kaydetmediği
+/// için kullanılan XMM yazmaçları kesmeler kapalıyken saklanır ve geri
+/// yüklenir.
+struct AesNi;
+
+impl crate::crypto::crypto::AesAccel for AesNi {
+    fn name(&self) -> &'static str {
+        "aes-ni"
+    }
+
+    fn encrypt_block(&self, round_keys: &[[u8; 16]; 11], block: &mut [u8; 16]) {
+        let mut save = [0u8; 32];
+        // SAFETY: Yalnızca CPUID AES desteği ve CR4.OSFXSR doğrulandıktan sonra kaydedilir.
+        unsafe {
+            asm!(
+                "pushfq",
+                "cli",
+                "movdqu [{save}], xmm0",
+                "movdqu [{save} + 16], xmm1",
+                "movdqu xmm0, [{block}]",
+                "movdqu xmm1, [{rk}]",
+                "pxor xmm0, xmm1",
+                "movdqu xmm1, [{rk} + 16]",
+                "aesenc xmm0, xmm1",
+                "movdqu xmm1, [{rk} + 32]",
+                "aesenc xmm0, xmm1",
+                "movdqu xmm1, [{rk} + 48]",
+                "aesenc xmm0, xmm1",
+                "movdqu xmm1, [{rk} + 64]",
+                "aesenc xmm0, xmm1",
+                "movdqu xmm1, [{rk} + 80]",
+                "aesenc xmm0, xmm1",
+                "movdqu xmm1, [{rk} + 96]",
+                "aesenc xmm0, xmm1",
+                "movdqu xmm1, [{rk} + 112]",
+                "aesenc xmm0, xmm1",
+                "movdqu xmm1, [{rk} + 128]",
+                "aesenc xmm0, xmm1",
+                "movdqu xmm1, [{rk} + 144]",
+                "aesenc xmm0, xmm1",
+                "movdqu xmm1, [{rk} + 160]",
+                "aesenclast xmm0, xmm1",
+                "movdqu [{block}], xmm0",
+                "movdqu xmm0, [{save}]",
+                "movdqu xmm1, [{save} + 16]",
+                "popfq",
+                save = in(reg) save.as_mut_ptr(),
+                block = in(reg) block.as_mut_ptr(),
+                rk = in(reg) round_keys.as_ptr(),
+            );
+        }
+    }
+}
+
+static AES_NI: AesNi = AesNi;
+
+/// İşlemci destekliyorsa AES-NI'yi şifreleme modülüne kaydeder.
+pub fn register_crypto_accel() {
+    if crate::cpu::features().aes && unsafe { read_cr4() } & CR4_OSFXSR != 0 {
+        crate::crypto::crypto::register_aes_accel(&AES_NI);
+    }
+}
+
 // -----------------------------------------------------------------------------
 // Temel Güvenlik Başlatma
 // -----------------------------------------------------------------------------
@@ -122,9 +367,14 @@ pub fn security_init() {
     }
     serial_println!("[SECURITY] CR0 WP (Write Protect) Biti Etkinleştirildi.");
     
-    // 3. İlk rastgelelik testi (başarısız olması normaldir, donanım gerektirir)
-    let (rand_val, success) = get_hardware_random_u64();
-    serial_println!("[SECURITY] RDRAND Testi: Başarılı: {}, Değer: {:#x}", success, rand_val);
+    // 3. İlk rastgelelik testi (RDRAND yoksa talimat #UD üretir)
+    if crate::cpu::features().rng {
+        let (rand_val, success) = get_hardware_random_u64();
+        serial_println!("[SECURITY] RDRAND Testi: Başarılı: {}, Değer: {:#x}", success, rand_val);
+    }
+
+    // 4. Donanım şifreleme hızlandırması (AES-NI)
+    register_crypto_accel();
     
     serial_println!("[SECURITY] Temel Güvenlik Yapılandırması Tamamlandı.");
 }
\ No newline at end of file
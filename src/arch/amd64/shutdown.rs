@@ -19,6 +19,15 @@ const KBD_CMD_REBOOT: u8 = 0xFE; // Klavye Denetleyicisine Gönderilen Yeniden B
 const ACPI_PM_CTRL_PORT: u16 = 0xB004; // Temsili bir ACPI PM Portu
 const ACPI_POWEROFF_CMD: u8 = 0x20; // Temsili Kapatma Komutu
 
+// BIOS Veri Alanı sıcak başlatma bayrağı: 0x1234 ise BIOS bellek testini atlar
+// ve RAM içeriği (korunan `.noinit` bölümü dahil) silinmez.
+const BDA_RESET_FLAG: usize = 0x472;
+const BDA_WARM_BOOT: u16 = 0x1234;
+
+// Sıfırlama Denetim Yazmacı (yonga seti): bit 1 SYS_RST, bit 2 RST_CPU, bit 3 FULL_RST
+const RESET_CONTROL_PORT: u16 = 0xCF9;
+const RESET_CONTROL_FULL: u8 = 0x0E; // Güç döngüsüyle tam (soğuk) sıfırlama
+
 /// İşlemciyi sonsuz bir bekleme döngüsüne sokar.
 /// Başarısız kapatma/yeniden başlatma sonrası kullanılır.
 #[inline(always)]
@@ -90,6 +99,38 @@ pub fn system_reboot() -> ! {
     halt_loop();
 }
 
+/// Sıcak veya soğuk yeniden başlatma ister (bkz. `reboot.rs`).
+///
+/// x86'da sıfırlama nedeni donanıma iletilemez; `reason` ve `failure` yalnızca
+/// korunan bellekte taşınır.
+pub fn system_reset(warm: bool, _reason: u32, _failure: bool) -> ! {
+    serial_println!("[SHUTDOWN] {} Yeniden Başlatma İsteniyor...", if warm { "Sıcak" } else { "Soğuk" });
+
+    // 1. Kesmeleri devre dışı bırak
+    unsafe {
+        io::disable_interrupts();
+    }
+
+    if warm {
+        // 2a. BIOS'a sıcak başlatmayı bildir ve yalnızca işlemciyi sıfırla
+        unsafe {
+            (BDA_RESET_FLAG as *mut u16).write_volatile(BDA_WARM_BOOT);
+        }
+        reboot_via_keyboard_controller();
+    } else {
+        // 2b. Yonga setiyle tam sıfırlama (RAM korunmaz)
+        unsafe {
+            io::outb(RESET_CONTROL_PORT, RESET_CONTROL_FULL);
+            for _ in 0..500_000 {
+                io::pause();
+            }
+        }
+    }
+
+    // 3. Başarısız olursa genel yeniden başlatma yolları
+    system_reboot();
+}
+
 // -----------------------------------------------------------------------------
 // Kapatma İşlevleri
 // -----------------------------------------------------------------------------
@@ -2,14 +2,15 @@
 // AMD64 (x86_64) mimarisine özgü görev (task) ve bağlam (context) yönetimi.
 
 use core::arch::asm;
+use core::mem::offset_of;
+use crate::context::ArchContext;
 use crate::serial_println;
 
 /// Görev bağlamını (task context) saklamak için kullanılan yapı.
 /// Bu yapı, görev anahtarlama sırasında kurtarılması gereken tüm 
 /// genel amaçlı yazmaçları içerir.
 ///
-/// Not: Yazmaçlar, yığına tam olarak hangi sırayla kaydedilip 
-/// geri yüklenecekse o sırayla tanımlanmalıdır.
+/// Not: Anahtarlama kodu alanlara `offset_of!` ile erişir; sıra serbesttir.
 #[repr(C)]
 #[derive(Debug, Default)]
 pub struct TaskContext {
@@ -32,109 +33,157 @@ pub struct TaskContext {
     
     // Komut işaretçisi (Instruction Pointer) - görev başladığında nereye zıplanacağını belirler.
     rip: u64, 
+
+    // Görev devam ettiğinde rax'ta göreceği değer (`set_return_value`).
+    ret: u64,
 }
 
 impl TaskContext {
-    /// Yeni bir görev bağlamı oluşturur.
-    /// 
-    /// # Argümanlar
-    /// * `stack_top`: Görevin yığınının en üst adresi.
-    /// * `entry_point`: Görevin başlayacağı fonksiyonun adresi.
-    pub fn new(stack_top: u64, entry_point: u64) -> Self {
-        // Yeni bir görev başlatıldığında, görev anahtarlama assembly kodu 
-        // yığından bu yazmaçları POP etmeyi bekleyecektir.
-        Self {
-            // Yazmaçlar sıfırlanır.
-            r15: 0,
-            r14: 0,
-            r13: 0,
-            r12: 0,
-            rbp: 0,
-            rbx: 0,
-            
-            // Yeni görev için rsp, yığının üstü olarak ayarlanır.
-            rsp: stack_top,
-            // rip, görevin giriş noktası (fonksiyon adresi) olarak ayarlanır.
-            rip: entry_point,
-        }
-    }
-
-    /// Bir bağlam anahtarlama işlemi sırasında yazmaç durumunu kaydetmek ve 
-    /// yeni görevden yazmaç durumunu yüklemek için kullanılan harici assembly fonksiyonu.
+    /// Callee-saved yazmaçları, RSP'yi ve dönüş noktasını `old_context`'e
+    /// kaydeder, `new_context`'ten yükler ve onun RIP'ine zıplar. Eski görev
+    /// yeniden seçildiğinde `2:` etiketinden, yani bu çağrının sonundan devam
+    /// eder.
     ///
     /// # Güvenlik Notu
-    /// Bu fonksiyon, C ABI'sine uymalıdır: `fn switch_context(old_context: *mut TaskContext, new_context: *const TaskContext)`.
-    ///
-    /// # Güvenilirlik
-    /// `switch_context`'in doğru çalışması, `TaskContext` yapısının düzenine
-    /// ve assembly kodunun yığın üzerindeki yazmaçları doğru sırayla kaydetmesine/yüklemesine bağlıdır.
+    /// İki işaretçi de geçiş boyunca geçerli olmalıdır; `new_context` ya
+    /// `ArchContext::new` ile kurulmuş ya da daha önce kaydedilmiş olmalıdır.
+    /// rbx ve rbp işlenen olarak verilemez; blok onları kendisi kaydedip yükler.
     #[inline(always)]
     pub unsafe fn switch_context(old_context: *mut TaskContext, new_context: *const TaskContext) {
-        serial_println!("[TASK] Bağlam Anahtarlama: {:#x} -> {:#x}", 
-                        old_context as u64, new_context as u64);
-
-        // Bu fonksiyon, Rust'ta tanımlanmamış, ancak harici bir assembly bloğu veya 
-        // ayrı bir assembly dosyası tarafından sağlanacak olan çekirdek anahtarlama rutinine zıplar.
-        // Rust'ta inline assembly ile yazılım anahtarlama örneği:
         asm!(
             // --------------------- Mevcut Görevin Durumunu Kaydet ---------------------
-            // rdi: old_context, rsi: new_context (C ABI)
-            
-            // 1. Callee-Saved (çağrılan tarafından korunan) GPR'ları yığına kaydet.
-            "push r15",
-            "push r14",
-            "push r13",
-            "push r12",
-            "push rbp",
-            "push rbx",
-            
-            // 2. Mevcut RSP'yi (yığın işaretçisi) TaskContext.rsp alanına kaydet.
-            // MOV RDI, RSP
-            "mov [rdi + 48], rsp", // 48: rsp alanının TaskContext'teki offset'i (6 yazmaç * 8 bayt = 48)
-            
-            // 3. Mevcut RIP'i (dönüş adresini) TaskContext.rip alanına kaydet.
-            // Bu, `call` talimatı tarafından yığına atılan adrestir. Bu adresi 
-            // manuel olarak bulmak zordur, bu nedenle bu kodda RIP'i doğru kaydetmek için 
-            // genellikle yığın üzerinde ek işlemler gerekir veya bu fonksiyonun 
-            // başlangıcındaki dönüş adresi okunmalıdır. Basitlik için bu adım ihmal edilebilir 
-            // veya anahtarlama işlemi doğrudan yığının kendisinden başlatılmalıdır.
-            
-            // Basitleştirilmiş: Sadece RSP'yi kaydetmek, dönüş için yeterlidir (RIP yığında kalır).
-            // Ancak TaskContext yapımız RIP içerdiği için, bu anahtarlamanın 
-            // yığın üzerinde gerçekleşen bir 'zıplama' olduğunu varsayıyoruz.
+            "mov [rdi + {rbx}], rbx",
+            "mov [rdi + {rbp}], rbp",
+            "mov [rdi + {r12}], r12",
+            "mov [rdi + {r13}], r13",
+            "mov [rdi + {r14}], r14",
+            "mov [rdi + {r15}], r15",
+            "mov [rdi + {rsp}], rsp",
+            // Devam noktası: görev yeniden seçildiğinde `2:`'ye döner
+            "lea rax, [rip + 2f]",
+            "mov [rdi + {rip}], rax",
 
             // --------------------- Yeni Görevin Durumunu Yükle ---------------------
-            // rsi: new_context
-            
-            // 1. Yeni RSP'yi yükle
-            // MOV RSP, [RSI + 48]
-            "mov rsp, [rsi + 48]", 
-            
-            // 2. Callee-Saved GPR'ları TaskContext'ten yükle
-            // POP RDX, POP RBP, ...
-            "pop rbx",
-            "pop rbp",
-            "pop r12",
-            "pop r13",
-            "pop r14",
-            "pop r15",
-            
-            // 3. Yeni görevin giriş noktasına zıpla (RIP yüklemesi)
-            // RIP'i yüklemek için TaskContext.rip adresi okunup zıplanır.
-            // Assembly'de bu, genellikle `ret` veya `jmp` ile yapılır.
-            // TaskContext'in 56 offset'te RIP tuttuğunu varsayalım (48 + 8 = 56).
-            "mov rax, [rsi + 56]", // rax = new_context->rip
-            "jmp rax",
-            
-            // Bu inline assembly bloğu, tüm anahtarlama görevini yapar.
-            in("rdi") old_context,
-            in("rsi") new_context,
-            // rdx, rcx, r8, r9'u kullanmıyoruz, ancak C ABI'sinde bunlar caller-saved.
-            options(noreturn, preserves_flags)
+            "mov rbx, [rsi + {rbx}]",
+            "mov rbp, [rsi + {rbp}]",
+            "mov r12, [rsi + {r12}]",
+            "mov r13, [rsi + {r13}]",
+            "mov r14, [rsi + {r14}]",
+            "mov r15, [rsi + {r15}]",
+            "mov rsp, [rsi + {rsp}]",
+            "mov rax, [rsi + {ret}]",
+            "jmp qword ptr [rsi + {rip}]",
+            "2:",
+
+            rbx = const offset_of!(TaskContext, rbx),
+            rbp = const offset_of!(TaskContext, rbp),
+            r12 = const offset_of!(TaskContext, r12),
+            r13 = const offset_of!(TaskContext, r13),
+            r14 = const offset_of!(TaskContext, r14),
+            r15 = const offset_of!(TaskContext, r15),
+            rsp = const offset_of!(TaskContext, rsp),
+            rip = const offset_of!(TaskContext, rip),
+            ret = const offset_of!(TaskContext, ret),
+            inout("rdi") old_context => _,
+            inout("rsi") new_context => _,
+            // Devam eden görev için caller-saved yazmaçların hepsi bozulmuş sayılır
+            clobber_abi("C"),
+        );
+    }
+
+    /// Callee-saved yazmaçları, yığın işaretçisini ve devam noktasını
+    /// `context`'e kaydeder, ardından `body(data)`'yı çağırır. `body` dönerse
+    /// 0 döner; `context` daha sonra `switch_context` ile yüklenirse yürütme
+    /// `2:` etiketinden sürer ve dönüş değeri bağlamın `ret` alanı olur (bkz.
+    /// `fixup::catch`).
+    ///
+    /// # Güvenlik Notu
+    /// `context` geri dönüşe kadar geçerli kalmalıdır; bağlam yalnızca `body`
+    /// henüz dönmemişken yüklenebilir.
+    #[inline(always)]
+    pub unsafe fn catch_call(context: *mut TaskContext, body: extern "C" fn(*mut u8), data: *mut u8) -> usize {
+        let resumed: usize;
+        asm!(
+            // --------------------- Geri Dönüş Noktasını Kaydet ---------------------
+            "mov [rdi + {rbx}], rbx",
+            "mov [rdi + {rbp}], rbp",
+            "mov [rdi + {r12}], r12",
+            "mov [rdi + {r13}], r13",
+            "mov [rdi + {r14}], r14",
+            "mov [rdi + {r15}], r15",
+            "mov [rdi + {rsp}], rsp",
+            // Devam noktası: bağlam yüklenirse `2:`'ye döner
+            "lea rax, [rip + 2f]",
+            "mov [rdi + {rip}], rax",
+
+            // --------------------- Gövdeyi Çağır ---------------------
+            "mov rdi, rdx",
+            "call rsi",
+            "xor eax, eax",
+            "2:",
+
+            rbx = const offset_of!(TaskContext, rbx),
+            rbp = const offset_of!(TaskContext, rbp),
+            r12 = const offset_of!(TaskContext, r12),
+            r13 = const offset_of!(TaskContext, r13),
+            r14 = const offset_of!(TaskContext, r14),
+            r15 = const offset_of!(TaskContext, r15),
+            rsp = const offset_of!(TaskContext, rsp),
+            rip = const offset_of!(TaskContext, rip),
+            inout("rdi") context => _,
+            inout("rsi") body => _,
+            inout("rdx") data => _,
+            lateout("rax") resumed,
+            clobber_abi("C"),
         );
+        resumed
+    }
+}
+
+impl ArchContext for TaskContext {
+    /// İlk geçiş `task_start`'a zıplar; o da r12/r13'teki işlev ve argümanla
+    /// `task_entry`'yi çağırır.
+    fn new(entry: usize, arg: usize, stack_top: usize) -> Self {
+        Self {
+            r12: entry as u64,
+            r13: arg as u64,
+            // `call` öncesi RSP 16 bayt hizalı olmalıdır
+            rsp: (stack_top & !0xF) as u64,
+            rip: task_start as usize as u64,
+            ..Default::default()
+        }
+    }
+
+    unsafe fn switch(&mut self, next: &Self) {
+        Self::switch_context(self, next);
+    }
+
+    unsafe fn catch(&mut self, body: extern "C" fn(*mut u8), data: *mut u8) -> usize {
+        Self::catch_call(self, body, data)
+    }
+
+    fn set_return_value(&mut self, value: usize) {
+        self.ret = value as u64;
     }
 }
 
+extern "C" {
+    /// Yeni görevin ilk komutu (aşağıdaki `global_asm!`).
+    fn task_start();
+}
+
+core::arch::global_asm!(
+    ".global task_start",
+    "task_start:",
+    "mov rdi, r12",
+    "mov rsi, r13",
+    // Yığın geri izleme burada dursun
+    "xor ebp, ebp",
+    "call {entry}",
+    "ud2",
+    entry = sym task_entry,
+);
+
 
 // -----------------------------------------------------------------------------
 // Görev Başlatma (Task Entry)
@@ -147,6 +196,7 @@ impl TaskContext {
 /// * `arg`: Göreve geçirilen u64 formatında argüman.
 /// * `func`: Görevin gerçek giriş noktası (başlatılacak fonksiyon).
 extern "C" fn task_entry(func: u64, arg: u64) -> ! {
+    crate::context::task_started();
     serial_println!("[TASK] Yeni Görev Başlatılıyor. Argüman: {:#x}", arg);
 
     // Fonksiyon işaretçisini (u64) gerçek fonksiyona dönüştür.
@@ -157,17 +207,10 @@ extern "C" fn task_entry(func: u64, arg: u64) -> ! {
     // Gerçek görev fonksiyonunu çağır
     entry_func(arg);
 
-    // Görev tamamlandı. Normalde bu noktaya gelinmemelidir.
-    serial_println!("[TASK] Görev Tamamlandı! Çekirdek Kapatma İsteği Gönderiliyor.");
-    
-    // Görev tamamlandığında, çekirdek durdurulmalıdır.
-    // Bu, çekirdek ana iş parçacığı tarafından temizlenmeli veya 
-    // özel bir "Exit" system call'u çağrılmalıdır.
-    loop {
-        unsafe {
-            io::hlt();
-        }
-    }
+    // Görev işlevi döndü: görev sonlandırılır ve işlemci sıradaki göreve
+    // (en azından boşta görevine) bırakılır
+    serial_println!("[TASK] Görev Tamamlandı.");
+    crate::sched::exit_current()
 }
 
 
@@ -184,6 +227,32 @@ pub fn initialize_tasking() {
     
     serial_println!("[TASK] Task Entry Adresi: {:#x}", entry_point_addr as u64);
     
-    // NOT: Gerçek bir çekirdek, bu TaskContext'leri bellekten ayırır 
-    // ve switch_context'i kullanarak görevleri yönetir.
-}
\ No newline at end of file
+    // Zamanlayıcı bundan sonra görevler arasında gerçekten geçer (bkz. `context`)
+    crate::context::enable();
+}
+
+// -----------------------------------------------------------------------------
+// İŞLEMCİ BAŞINA ALAN TABANI
+// -----------------------------------------------------------------------------
+
+/// GS segment tabanı (kullanıcı kipine geçişte `swapgs` ile değiştirilir).
+const IA32_GS_BASE: u32 = 0xC000_0101;
+
+/// Bu işlemcinin işlemci başına alanının (`percpu`) tabanını yazar (GS_BASE MSR).
+///
+/// # Safety
+/// `base` bu işlemciye ayrılmış, `.percpu` şablonunun kopyası olan alanı
+/// göstermelidir.
+pub unsafe fn set_cpu_local_base(base: usize) {
+    let low = base as u32;
+    let high = (base >> 32) as u32;
+    asm!("wrmsr", in("ecx") IA32_GS_BASE, in("eax") low, in("edx") high, options(nostack, preserves_flags));
+}
+
+/// Bu işlemcinin işlemci başına alanının tabanı (henüz yazılmadıysa 0).
+pub fn cpu_local_base() -> usize {
+    let (low, high): (u32, u32);
+    // SAFETY: GS_BASE her zaman okunabilir.
+    unsafe { asm!("rdmsr", in("ecx") IA32_GS_BASE, out("eax") low, out("edx") high, options(nostack, preserves_flags, nomem)) };
+    ((u64::from(high) << 32) | u64::from(low)) as usize
+}
@@ -42,6 +42,18 @@ pub fn read_tsc() -> Cycles {
     Cycles(((high as u64) << 32) | (low as u64))
 }
 
+/// Serbest çalışan sayacın anlık değeri (bkz. `crate::clock`).
+#[inline(always)]
+pub fn read_cycles() -> Cycles {
+    read_tsc()
+}
+
+/// Sayacın frekansını (Hz) döndürür. TSC henüz kalibre edilmediğinden 0
+/// (bkz. `initialize_time_system`); raporlar ham döngü cinsindendir.
+pub fn get_frequency() -> u64 {
+    0
+}
+
 
 /// TSC yazmacını okur ve sonuçları seri hale getirilmiş (serialized) olarak döndürür.
 ///
@@ -106,6 +118,13 @@ pub fn delay_cycles(cycles: u64) {
 /// ve global bir değişkene kaydeder.
 pub fn initialize_time_system() {
     serial_println!("[TIME] AMD64 Zamanlama Modülü Başlatılıyor...");
+
+    // KVM altında kvmclock TSC frekansını ve ölçeğini zaten sağlar; öykünülmüş
+    // PIT/HPET ile kalibrasyon hem yavaş hem de güvenilmezdir.
+    if let Some(ns) = crate::hypervisor::clock_ns() {
+        serial_println!("[TIME] kvmclock kullanılıyor, kalibrasyon atlandı. Zaman: {} ns", ns);
+        return;
+    }
     
     // Gerçek bir başlatma:
     // 1. HPET veya PIT kullanarak sabit bir süre bekle.
@@ -118,6 +137,26 @@ pub fn initialize_time_system() {
     // Bu değer, frekans hesaplanana kadar anlamsızdır.
 }
 
+// -----------------------------------------------------------------------------
+// Zamanlayıcı Karşılaştırıcısı (TSC-Deadline)
+// -----------------------------------------------------------------------------
+
+/// IA32_TSC_DEADLINE MSR'ı: TSC bu değere ulaşınca yerel APIC zamanlayıcı
+/// kesmesi üretir (LVT Timer TSC-deadline kipinde olmalıdır).
+const IA32_TSC_DEADLINE: u32 = 0x6E0;
+
+/// Zamanlayıcı kesmesini TSC `deadline` değerine kurar. Geçmiş bir değer
+/// kesmeyi hemen tetikler; 0 yazmak zamanlayıcıyı durdurur.
+pub unsafe fn set_timer_deadline(deadline: Cycles) {
+    asm!(
+        "wrmsr",
+        in("ecx") IA32_TSC_DEADLINE,
+        in("eax") deadline.0 as u32,
+        in("edx") (deadline.0 >> 32) as u32,
+        options(nostack, preserves_flags)
+    );
+}
+
 // Platforma özel G/Ç fonksiyonları için bir yer tutucu (main.rs veya platformmod.rs'de tamamlanmalıdır)
 mod io {
     use core::arch::asm;
@@ -1,15 +1,39 @@
 use crate::platform::PlatformManager;
 use core::fmt::{self, Write};
 use core::sync::atomic::{AtomicBool, Ordering};
+use crate::uart::{self, FlowControl, TxQueue, UartTx};
 
 // Genellikle Raspberry Pi gibi gelişim kartlarında kullanılan PL011 UART'ın
-// veya 16550 uyumlu bir UART'ın TEMEL MMIO adresi.
-// Not: Gerçek gömülü sisteminizde bu adres farklı olacaktır!
+// TEMEL MMIO adresi. Önyükleyici başka bir PL011 bildirirse (`stdout-path`,
+// SPCR, `earlycon=`) o kullanılır; bkz. `crate::earlycon`.
 const UART_MMIO_ADDR: usize = 0xFE20_1000; // Örnek adres (Raspberry Pi 3/4)
 
-// 16550 Uyumlu UART Yazmaçları için Ofsetler
-const DATA_REGISTER_OFFSET: usize = 0x00; // Veri (TX/RX)
-const LINE_STATUS_REGISTER_OFFSET: usize = 0x05; // Hat Durumu (TX Boş mu?)
+// PL011 Yazmaç Ofsetleri
+const DATA_REGISTER_OFFSET: usize = 0x00; // UARTDR: Veri (TX/RX)
+const FLAG_REGISTER_OFFSET: usize = 0x18; // UARTFR: Bayrak (FIFO durumu, CTS)
+const CONTROL_REGISTER_OFFSET: usize = 0x30; // UARTCR: Kontrol
+const FIFO_LEVEL_REGISTER_OFFSET: usize = 0x34; // UARTIFLS: FIFO kesme eşiği
+const INT_MASK_REGISTER_OFFSET: usize = 0x38; // UARTIMSC: Kesme maskesi
+const INT_CLEAR_REGISTER_OFFSET: usize = 0x44; // UARTICR: Kesme temizleme
+
+// UARTFR bitleri
+const FR_TXFF: u32 = 1 << 5; // Gönderim FIFO'su dolu
+
+// UARTCR bitleri
+const CR_RTSEN: u32 = 1 << 14; // Donanım RTS akış denetimi
+const CR_CTSEN: u32 = 1 << 15; // Donanım CTS akış denetimi
+
+// UARTIMSC / UARTICR bitleri
+const INT_TX: u32 = 1 << 5;
+
+/// TX FIFO eşiği: FIFO yarıya (8 bayt) indiğinde kesme üretilir ve kuyruktan yeniden doldurulur.
+const IFLS_TX_HALF: u32 = 0b010;
+
+/// PL011'in GIC kesme kimliği (Raspberry Pi 4: SPI 121).
+pub const UART_IRQ: u32 = 153;
+
+/// Kesme kipinde kullanılan gönderim kuyruğu.
+static TX_QUEUE: TxQueue = TxQueue::new();
 
 /// ARMv9 MMIO UART'ı yöneten yapı.
 ///
@@ -22,12 +46,10 @@ static IS_INITIALIZED: AtomicBool = AtomicBool::new(false);
 impl Uart {
     /// UART MMIO adresini döndürür.
     fn get_register_addr(offset: usize) -> usize {
-        // Güvenli toplama işlemi (overflow kontrolü)
-        UART_MMIO_ADDR.checked_add(offset).unwrap_or_else(|| 
-            // no-std ortamında panik yapma veya hata döndürme
-            // Basitlik için burada unwrap kullanılmıştır.
-            panic!("UART MMIO adresi taşması")
-        )
+        let base = crate::earlycon::mmio_base(crate::earlycon::UartKind::Pl011).unwrap_or(UART_MMIO_ADDR);
+        // Erken konsoldan gelen taban taşarsa varsayılan UART'a dönülür;
+        // konsol yolunda panik olmamalı (panik yolu da bu konsola yazar)
+        base.checked_add(offset).unwrap_or(UART_MMIO_ADDR + offset)
     }
 
     /// UART'ı temel bir konfigürasyonla başlatır.
@@ -49,35 +71,81 @@ impl Uart {
         IS_INITIALIZED.store(true, Ordering::Release);
     }
     
-    /// UART'ın boş olup olmadığını kontrol eder (göndermeye hazır mı?).
-    ///
-    /// # Güvenlik Notu
-    /// MMIO okuma işlemi olduğu için `unsafe` gerektirir.
+    #[inline(always)]
+    fn read_reg(offset: usize) -> u32 {
+        unsafe { core::ptr::read_volatile(Self::get_register_addr(offset) as *const u32) }
+    }
+
+    #[inline(always)]
+    fn write_reg(offset: usize, value: u32) {
+        unsafe { core::ptr::write_volatile(Self::get_register_addr(offset) as *mut u32, value) }
+    }
+
+    /// UART'ın göndermeye hazır olup olmadığını kontrol eder (TX FIFO'da yer var mı?).
     fn is_transmit_empty() -> bool {
-        // PlatformManager'ı kullanarak Hat Durumu Yazmacını oku (MMIO)
-        let status = unsafe { 
-            PlatformManager::read_byte_from_address(
-                Self::get_register_addr(LINE_STATUS_REGISTER_OFFSET)
-            ) 
-        };
-        
-        // 16550 Uyumlu: Bit 5 (THRE - Transmitter Holding Register Empty)
-        (status & 0x20) != 0 // Eğer bit 5 ayarlanmışsa (1) -> Boş
+        // PL011: UARTFR.TXFF ayarlıysa FIFO dolu
+        Self::read_reg(FLAG_REGISTER_OFFSET) & FR_TXFF == 0
     }
 
-    /// UART'a bir bayt yazar.
+    /// UART'a bir bayt yazar. Kesme kipi açıksa bayt gönderim kuyruğuna
+    /// alınır; değilse FIFO'da yer açılana kadar beklenir.
     pub fn write_byte(byte: u8) {
-        // Portun boş olmasını bekle (Busy-Waiting)
-        // Sert Gerçek Zamanlı sistemde determinizmi korur.
-        while !Self::is_transmit_empty() {}
+        uart::write::<Self>(&TX_QUEUE, byte);
+    }
+
+    /// Kuyruğu boşaltıp baytı yoklamalı yazar (panik ve GDB yolu).
+    pub fn write_byte_polled(byte: u8) {
+        uart::write_polled::<Self>(&TX_QUEUE, byte);
+    }
 
-        // Veri Yazmacına baytı yaz
+    /// Kesme güdümlü gönderimi açar.
+    ///
+    /// # Parametreler
+    /// * `flow`: `RtsCts` seçilirse PL011'in donanım RTS/CTS el sıkışması
+    ///   açılır; CTS düşükken FIFO donanım tarafından bekletilir.
+    pub fn enable_tx_interrupts(flow: FlowControl) {
+        // SAFETY: PL011 yazmaçları açılışta eşlenmiştir.
         unsafe {
-            PlatformManager::write_byte_from_address(
-                Self::get_register_addr(DATA_REGISTER_OFFSET), 
-                byte
-            )
+            crate::mmio::assign_bits(
+                Self::get_register_addr(CONTROL_REGISTER_OFFSET),
+                CR_RTSEN | CR_CTSEN,
+                flow == FlowControl::RtsCts,
+            );
+            crate::mmio::update_bits(Self::get_register_addr(FIFO_LEVEL_REGISTER_OFFSET), 0b111, IFLS_TX_HALF);
         }
+
+        TX_QUEUE.set_irq_mode(true, flow);
+        unsafe { crate::arch::armv9::interrupt::GicDistributor::enable_irq(UART_IRQ) };
+    }
+
+    /// Kesme güdümlü gönderimi kapatır; kuyruktaki baytlar önce gönderilir.
+    pub fn disable_tx_interrupts() {
+        uart::flush::<Self>(&TX_QUEUE);
+        TX_QUEUE.set_irq_mode(false, FlowControl::None);
+        Self::set_tx_interrupt(false);
+    }
+
+    /// PL011 kesme işleyicisi (GIC `UART_IRQ`).
+    pub fn handle_interrupt() {
+        Self::write_reg(INT_CLEAR_REGISTER_OFFSET, INT_TX);
+        uart::handle_tx_interrupt::<Self>(&TX_QUEUE);
+    }
+}
+
+impl UartTx for Uart {
+    fn tx_room() -> usize {
+        // PL011 doluluk seviyesini vermez; bayt bayt denetlenir
+        usize::from(Self::is_transmit_empty())
+    }
+
+    fn put(byte: u8) {
+        Self::write_reg(DATA_REGISTER_OFFSET, byte as u32);
+    }
+
+    fn set_tx_interrupt(enable: bool) {
+        // IMSC, TX kesme işleyicisiyle paylaşılır: RMW kilit altında yapılır
+        // SAFETY: PL011 yazmaçları açılışta eşlenmiştir.
+        unsafe { crate::mmio::assign_bits(Self::get_register_addr(INT_MASK_REGISTER_OFFSET), INT_TX, enable) };
     }
 }
 
@@ -99,6 +167,26 @@ impl fmt::Write for Uart {
     }
 }
 
+// -----------------------------------------------------------------------------
+// PANİK VE İKİNCİL KONSOL
+// -----------------------------------------------------------------------------
+
+/// Panik yolunun kilitsiz yazıcısı (bkz. `crate::panic`): TX kuyruğu deneme
+/// kilidiyle boşaltılır, bayt yoklamalı gönderilir.
+pub fn panic_write_byte(byte: u8) {
+    Uart::write_byte_polled(byte);
+}
+
+/// Kesme güdümlü gönderimi kapatır; sonraki yazmalar yoklamalıdır.
+pub fn panic_quiesce() {
+    Uart::disable_tx_interrupts();
+}
+
+/// İkincil UART yok; `serial1` hedefi kaydedilmez.
+pub fn init_aux_serial() -> Option<fn(fmt::Arguments)> {
+    None
+}
+
 // -----------------------------------------------------------------------------
 // GENEL KONSOL ÇIKTI FONKSİYONLARI (Makrolar)
 // -----------------------------------------------------------------------------
@@ -116,8 +204,11 @@ macro_rules! serial_print {
 
         // Not: Gerçek bir çekirdekte, bu kısım yarış koşullarını önlemek için 
         // ya bir Spinlock ile korunmalı ya da Kesmeler devre dışı bırakılmalıdır.
-        unsafe {
-             let _ = write!($crate::arch::armv9::console::Uart, $($arg)*);
+        // Yönlendirilmiş görev çıktısı (örn. uzak kabuk) seri porta yazılmaz
+        if !$crate::shell::redirect(format_args!($($arg)*)) {
+            unsafe {
+                 let _ = write!($crate::arch::armv9::console::Uart, $($arg)*);
+            }
         }
     });
 }
@@ -0,0 +1,43 @@
+// src/arch/armv9/cpu.rs
+// ARMv9 (AArch64) işlemci özelliği algılama (MIDR_EL1, ID_AA64*_EL1).
+
+#![allow(dead_code)]
+
+use core::arch::asm;
+use crate::cpu::CpuFeatures;
+
+/// 4 bitlik kimlik alanı.
+fn field(reg: u64, shift: u32) -> u64 {
+    (reg >> shift) & 0xF
+}
+
+pub fn detect() -> CpuFeatures {
+    let (midr, pfr0, isar0, mmfr0): (u64, u64, u64, u64);
+    // SAFETY: Kimlik yazmaçları EL1'de her zaman okunabilir.
+    unsafe {
+        asm!("mrs {0}, midr_el1", out(reg) midr, options(nomem, nostack));
+        asm!("mrs {0}, id_aa64pfr0_el1", out(reg) pfr0, options(nomem, nostack));
+        asm!("mrs {0}, id_aa64isar0_el1", out(reg) isar0, options(nomem, nostack));
+        asm!("mrs {0}, id_aa64mmfr0_el1", out(reg) mmfr0, options(nomem, nostack));
+    }
+
+    // ID_AA64MMFR0_EL1.TGran4 [31:28]: 0xF = 4 KiB granül yok. 4 KiB
+    // granülde seviye 2 blok 2 MiB, seviye 1 blok 1 GiB'dir.
+    let tgran4 = field(mmfr0, 28) != 0xF;
+    CpuFeatures {
+        id: midr,
+        // ID_AA64PFR0_EL1.FP [19:16] ve AdvSIMD [23:20]: 0xF = yok
+        fpu: field(pfr0, 16) != 0xF,
+        simd: field(pfr0, 20) != 0xF,
+        // ID_AA64ISAR0_EL1.AES [7:4], SHA2 [15:12], CRC32 [19:16]
+        aes: field(isar0, 4) != 0,
+        sha2: field(isar0, 12) != 0,
+        crc32: field(isar0, 16) != 0,
+        // ID_AA64ISAR0_EL1.Atomic [23:20]: 2 = LSE
+        atomics: field(isar0, 20) >= 2,
+        large_pages: tgran4,
+        huge_pages: tgran4,
+        // ID_AA64ISAR0_EL1.RNDR [63:60]
+        rng: field(isar0, 60) != 0,
+    }
+}
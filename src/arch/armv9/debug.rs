@@ -0,0 +1,118 @@
+// src/arch/armv9/debug.rs
+// ARMv9 (AArch64) mimarisine özgü kesme noktası, tek adım ve gözlem noktası desteği.
+
+#![allow(dead_code)]
+
+use core::arch::asm;
+use crate::debug::WatchKind;
+use crate::platformgeneric::KernelError;
+
+/// `brk #0` talimatı.
+pub const BREAKPOINT_INSN: u32 = 0xD420_0000;
+
+/// Kesme noktası talimatının uzunluğu (bayt).
+pub const BREAKPOINT_INSN_LEN: usize = 4;
+
+/// ELR_EL1 `brk` talimatının kendisini gösterir; düzeltme gerekmez.
+pub const BREAKPOINT_PC_ADJUST: usize = 0;
+
+/// SPSR_EL1.SS (Software Step) biti.
+const SPSR_SS: u64 = 1 << 21;
+/// MDSCR_EL1.SS (Software Step Enable) biti.
+const MDSCR_SS: u64 = 1 << 0;
+/// MDSCR_EL1.KDE (Kernel Debug Enable) biti; EL1'de adımlamak için gereklidir.
+const MDSCR_KDE: u64 = 1 << 13;
+/// MDSCR_EL1.MDE (Monitor Debug Enable) biti; donanım kesme/gözlem noktalarını açar.
+const MDSCR_MDE: u64 = 1 << 15;
+
+/// Değiştirilen talimatı veri önbelleğinden PoU'ya yazar ve talimat önbelleğini geçersiz kılar.
+pub unsafe fn sync_icache(addr: usize, _len: usize) {
+    asm!(
+        "dc cvau, {0}",
+        "dsb ish",
+        "ic ivau, {0}",
+        "dsb ish",
+        "isb",
+        in(reg) addr,
+        options(nostack)
+    );
+}
+
+/// MDSCR_EL1 ve kaydedilmiş SPSR_EL1 üzerinden yazılım adımlamayı açar/kapatır.
+/// Dönüşten (eret) sonra tek talimat yürütülür ve EC 0x32/0x33 tuzağı oluşur.
+pub fn set_single_step(spsr: &mut u64, enable: bool) -> Result<(), KernelError> {
+    unsafe {
+        let mut mdscr: u64;
+        asm!("mrs {}, MDSCR_EL1", out(reg) mdscr, options(nostack, nomem));
+        if enable {
+            mdscr |= MDSCR_SS | MDSCR_KDE;
+        } else {
+            mdscr &= !MDSCR_SS;
+        }
+        asm!("msr MDSCR_EL1, {}", "isb", in(reg) mdscr, options(nostack, nomem));
+    }
+
+    if enable {
+        *spsr |= SPSR_SS;
+    } else {
+        *spsr &= !SPSR_SS;
+    }
+    Ok(())
+}
+
+// -----------------------------------------------------------------------------
+// DONANIM GÖZLEM NOKTALARI (DBGWVR<n>_EL1 / DBGWCR<n>_EL1)
+// -----------------------------------------------------------------------------
+
+/// Mimari en az 2 gözlem noktası garanti eder; yaygın çekirdeklerde 4 bulunur
+/// (ID_AA64DFR0_EL1.WRPs).
+pub const MAX_WATCHPOINTS: usize = 4;
+
+/// Gözlem noktası istisnası erişim gerçekleşmeden önce alınır (EC 0x34/0x35).
+pub const WATCH_BEFORE_ACCESS: bool = true;
+
+// DBGWCR alanları
+const WCR_E: u64 = 1 << 0;          // Etkin
+const WCR_PAC_EL1_EL0: u64 = 0b11 << 1; // EL1 ve EL0 erişimlerinde eşleş
+const WCR_LSC_SHIFT: u64 = 3;       // Load/Store Control
+const WCR_BAS_SHIFT: u64 = 5;       // Byte Address Select
+
+unsafe fn write_wvr_wcr(slot: usize, wvr: u64, wcr: u64) {
+    match slot {
+        0 => asm!("msr DBGWVR0_EL1, {}", "msr DBGWCR0_EL1, {}", in(reg) wvr, in(reg) wcr, options(nostack, nomem)),
+        1 => asm!("msr DBGWVR1_EL1, {}", "msr DBGWCR1_EL1, {}", in(reg) wvr, in(reg) wcr, options(nostack, nomem)),
+        2 => asm!("msr DBGWVR2_EL1, {}", "msr DBGWCR2_EL1, {}", in(reg) wvr, in(reg) wcr, options(nostack, nomem)),
+        _ => asm!("msr DBGWVR3_EL1, {}", "msr DBGWCR3_EL1, {}", in(reg) wvr, in(reg) wcr, options(nostack, nomem)),
+    }
+    asm!("isb", options(nostack, nomem));
+}
+
+/// Çift kelime (8 bayt) hizalı adresi DBGWVR'ye, bayt maskesini (BAS) ve erişim
+/// türünü DBGWCR'ye yazar; MDSCR_EL1.MDE/KDE ve PSTATE.D ile EL1 hata ayıklamayı açar.
+pub unsafe fn set_watchpoint(slot: usize, addr: usize, len: usize, kind: WatchKind) -> Result<(), KernelError> {
+    let lsc: u64 = match kind {
+        WatchKind::Read => 0b01,
+        WatchKind::Write => 0b10,
+        WatchKind::ReadWrite => 0b11,
+    };
+    let bas = ((1u64 << len) - 1) << (addr & 7);
+    let wcr = WCR_E | WCR_PAC_EL1_EL0 | (lsc << WCR_LSC_SHIFT) | (bas << WCR_BAS_SHIFT);
+
+    write_wvr_wcr(slot, (addr & !7) as u64, wcr);
+
+    let mut mdscr: u64;
+    asm!("mrs {}, MDSCR_EL1", out(reg) mdscr, options(nostack, nomem));
+    mdscr |= MDSCR_MDE | MDSCR_KDE;
+    asm!("msr MDSCR_EL1, {}", "isb", "msr daifclr, #8", in(reg) mdscr, options(nostack, nomem));
+    Ok(())
+}
+
+/// DBGWCR<slot>.E bitini temizler.
+pub unsafe fn clear_watchpoint(slot: usize) {
+    write_wvr_wcr(slot, 0, 0);
+}
+
+/// AArch64 hatalı veri adresini FAR_EL1'de bildirir; yuvanın ayrıca sorgulanması gerekmez.
+pub fn watchpoint_hit() -> Option<usize> {
+    None
+}
@@ -29,6 +29,10 @@ pub struct HardwareConfig {
     pub gic_redist_addr: usize,
     /// Generic Interrupt Controller (GIC) Distributor'ın MMIO adresi
     pub gic_dist_addr: usize,
+    /// `/psci` düğümünün `method` özelliği "hvc" mi? (Aksi halde "smc")
+    pub psci_method_hvc: bool,
+    /// `/hypervisor` düğümünün `compatible` değeri (örn. "xen,xen"), varsa
+    pub hypervisor_compatible: Option<&'static str>,
 }
 
 /// Aygıt Ağacını ayrıştırmaktan sorumlu statik yapı.
@@ -49,6 +53,8 @@ impl DtbParser {
             unsafe {
                 DTB_INFO.physical_address = addr;
             }
+            // Sürücülerin aygıt keşfi için genel FDT okuyucusuna da bildir
+            crate::fdt::init(addr).ok();
         }
     }
 
@@ -78,6 +84,10 @@ impl DtbParser {
             // Kesme Kontrolcüsü (GICv3) adresleri (Temsili)
             gic_redist_addr: 0xFF20_0000, 
             gic_dist_addr: 0xFF00_0000, 
+
+            // QEMU 'virt' makinesi EL3 firmware olmadan PSCI'yi HVC ile sunar
+            psci_method_hvc: true,
+            hypervisor_compatible: None,
         };
         
         unsafe {
@@ -1,26 +1,9 @@
 use core::arch::asm;
 use core::fmt;
+use crate::platformgeneric::KernelError;
 use crate::serial_println;
-
-// -----------------------------------------------------------------------------
-// HARİCİ MONTAJ DİLİ İŞLEYİCİLERİ
-// -----------------------------------------------------------------------------
-
-// Bu fonksiyonlar, Vektör Tablosu'nun her bir girişi için gereken
-// montaj kodu bağlayıcılarıdır. Montaj kodu, gelen istisnayı yakalar, 
-// tüm GPR'ları yığına kaydeder ve ardından uygun Rust fonksiyonunu çağırır.
-
-extern "C" {
-    // Current EL, SP0 (Synchronous, IRQ, FIQ, SError)
-    fn vector_table_sync_sp0();
-    fn vector_table_irq_sp0();
-    // Current EL, SPx (Synchronous, IRQ, FIQ, SError)
-    fn vector_table_sync_spx();
-    fn vector_table_irq_spx();
-    // Lower EL, AArch64 (Synchronous, IRQ, FIQ, SError)
-    fn vector_table_sync_lower_aarch64();
-    fn vector_table_irq_lower_aarch64();
-}
+use crate::trap::{handle_fatal, Access, Cause};
+use super::vectors::armv9_vectors;
 
 // -----------------------------------------------------------------------------
 // 1. İSTİSNA VEKTÖR TABLOSU (VBAR_EL1)
@@ -31,80 +14,90 @@ extern "C" {
 // 2. Current EL, SPx
 // 3. Lower EL, AArch64
 // 4. Lower EL, AArch32
-// Her bir giriş 0x80 bayt uzunluğundadır.
-
-/// VBAR_EL1 tarafından kullanılan Vektör Tablosu.
-/// Montaj kodunun beklediği sıraya uygun olmalıdır.
-#[repr(C, align(0x1000))] // VBAR_EL1 4KB hizalama ister
-pub struct VectorTable {
-    // Current EL with SP0
-    pub current_el_sp0: [usize; 4],
-    // Current EL with SPx
-    pub current_el_spx: [usize; 4],
-    // Lower EL using AArch64
-    pub lower_el_aarch64: [usize; 4],
-    // Lower EL using AArch32 (şimdilik sıfır)
-    pub lower_el_aarch32: [usize; 4],
-}
+// Her bir giriş 0x80 bayt uzunluğundadır. Tablo `vectors.rs`'de `trap_vectors!`
+// ile üretilir; her girdi bağlamı kaydeder ve aşağıdaki işleyicilerden birini
+// `(vector, &mut ExceptionContext)` ile çağırır. `vector` yuva numarasıdır (0-15).
 
-/// Statik Vektör Tablosu örneği.
-static mut VECTOR_TABLE: VectorTable = VectorTable {
-    current_el_sp0: [0; 4],
-    current_el_spx: [0; 4],
-    lower_el_aarch64: [0; 4],
-    lower_el_aarch32: [0; 4],
-};
-
-impl VectorTable {
-    /// Vektör Tablosunu başlatır ve montaj dili işleyicilerinin adresleriyle doldurur.
-    pub fn init(&mut self) {
-        // Current EL with SP0: [Sync, IRQ, FIQ, SError]
-        self.current_el_sp0[0] = vector_table_sync_sp0 as usize;
-        self.current_el_sp0[1] = vector_table_irq_sp0 as usize;
-        self.current_el_sp0[2] = 0; // FIQ - Şimdilik Yoksay
-        self.current_el_sp0[3] = 0; // SError - Şimdilik Yoksay
-        
-        // Current EL with SPx: [Sync, IRQ, FIQ, SError]
-        self.current_el_spx[0] = vector_table_sync_spx as usize;
-        self.current_el_spx[1] = vector_table_irq_spx as usize;
-        self.current_el_spx[2] = 0; // FIQ - Şimdilik Yoksay
-        self.current_el_spx[3] = 0; // SError - Şimdilik Yoksay
-
-        // Lower EL using AArch64: [Sync, IRQ, FIQ, SError]
-        self.lower_el_aarch64[0] = vector_table_sync_lower_aarch64 as usize;
-        self.lower_el_aarch64[1] = vector_table_irq_lower_aarch64 as usize;
-        self.lower_el_aarch64[2] = 0; // FIQ - Şimdilik Yoksay
-        self.lower_el_aarch64[3] = 0; // SError - Şimdilik Yoksay
-        
-        // Lower EL using AArch32: [Sync, IRQ, FIQ, SError]
-        // Tamamen sıfır bırakılıyor.
-    }
-}
+/// Yuva numaralarının okunabilir adları (grup, tür).
+const VECTOR_GROUPS: [&str; 4] = ["Current EL SP0", "Current EL SPx", "Lower EL AArch64", "Lower EL AArch32"];
+const VECTOR_KINDS: [&str; 4] = ["Sync", "IRQ", "FIQ", "SError"];
 
 // -----------------------------------------------------------------------------
 // 2. TEMEL İŞLEYİCİ FONKSİYONLARI (Rust Kodu)
 // -----------------------------------------------------------------------------
 
-/// İstisna oluştuğunda CPU tarafından yığına (stack) kaydedilen register yapısı.
-/// Bu yapı, montaj kodunuzun GPR'ları yığına kaydettiği sıraya UYGUN olmalıdır.
-/// Genellikle x0-x30, LR, SP, ESR, ELR, SPSR, vb. içerir.
+/// İstisna oluştuğunda vektör girdisinin yığına (stack) kaydettiği register yapısı.
+/// Düzen `vectors.rs`'deki ortak kodla aynıdır: x0-x30 (0..240), ELR_EL1 (248),
+/// SPSR_EL1 (256).
 #[repr(C)]
 pub struct ExceptionContext {
-    // Montaj kodunun kaydettiği tüm GPR'lar (x0-x30)
-    // Şimdilik sadece kritik olanları (ELR, SPSR) temsil ediyoruz
-    // Gerçekte, burada 31 adet 64-bit GPR + SPSR_EL1 + ELR_EL1 + SP_EL0 olmalıdır.
+    pub gpr: [u64; 31], // x0-x30 (x30 = LR)
     pub elr_el1: u64, // İstisna sonrası dönüş adresi
     pub spsr_el1: u64, // İstisna öncesi durum kaydı
-    // ... Diğer kritik registerlar (X0-X30, LR)
+}
+
+// Ofsetler `vectors.rs`'deki `armv9_common` gövdesine gömülüdür.
+crate::asm_layout!(ExceptionContext {
+    gpr: 0,
+    elr_el1: 248,
+    spsr_el1: 256,
+}, size <= 272);
+
+/// ESR_EL1 sendrom kodunu ortak `trap::Cause` tipine çevirir.
+///
+/// # Parametreler
+/// * `esr_el1`: İstisna Durum Kaydı.
+/// * `far_el1`: Hatalı Adres Kaydı (yalnızca abort'larda anlamlıdır).
+pub fn decode_cause(esr_el1: u64, far_el1: u64) -> Cause {
+    let ec = (esr_el1 >> 26) & 0x3F;
+    let iss = esr_el1 & 0x01FF_FFFF;
+
+    match ec {
+        // Talimat Engelleme (Alt EL / Mevcut EL)
+        0x20 | 0x21 => Cause::PageFault { access: Access::Execute, addr: far_el1 as usize },
+        // Veri Engelleme (Alt EL / Mevcut EL): ISS.WnR (6. bit) yazma erişimini belirtir
+        0x24 | 0x25 => {
+            let access = if iss & (1 << 6) != 0 { Access::Write } else { Access::Read };
+            Cause::PageFault { access, addr: far_el1 as usize }
+        }
+        // PC / SP Hizalama Hatası
+        0x22 | 0x26 => Cause::Misaligned { addr: far_el1 as usize },
+        0x15 => Cause::Syscall,          // SVC (AArch64)
+        0x3C => Cause::Breakpoint,       // BRK #imm
+        0x32 | 0x33 => Cause::SingleStep, // Yazılım Adımı (Alt EL / Mevcut EL)
+        0x34 | 0x35 => Cause::Watchpoint { addr: far_el1 as usize }, // Gözlem Noktası (Alt EL / Mevcut EL)
+        0x00 | 0x0E => Cause::IllegalInstruction, // Bilinmeyen neden / Geçersiz Yürütme Durumu
+        0x2F => Cause::MachineError,     // SError
+        _ => Cause::Unknown(ec),
+    }
 }
 
 /// Senkron İstisnalar için genel işleyici (Data Abort, Prefetch Abort, vb.).
 ///
 /// # Parametreler
-/// * `esr_el1`: İstisna Durum Kaydı (Exception Syndrome Register), istisnanın nedenini içerir.
+/// * `_vector`: Vektör tablosundaki yuva (0, 4 veya 8).
 /// * `context`: İstisna öncesi CPU durumunu içeren yapı.
 #[no_mangle]
-pub extern "C" fn generic_sync_handler(esr_el1: u64, context: &ExceptionContext) {
+pub extern "C" fn generic_sync_handler(_vector: u64, context: &mut ExceptionContext) {
+    // ESR_EL1: İstisna Durum Kaydı (Exception Syndrome Register), istisnanın nedenini içerir.
+    let esr_el1: u64;
+    let far_el1: u64;
+    unsafe {
+        asm!("mrs {}, ESR_EL1", out(reg) esr_el1, options(nostack, nomem));
+        asm!("mrs {}, FAR_EL1", out(reg) far_el1, options(nostack, nomem));
+    }
+
+    // Kesme noktası, tek adım ve gözlem noktası tuzakları önce hata ayıklayıcıya sunulur.
+    let trap_cause = decode_cause(esr_el1, far_el1);
+    if crate::debug::handle_trap(trap_cause, &mut context.elr_el1, &mut context.spsr_el1) {
+        return;
+    }
+
+    // Kullanıcı belleği erişimi veya bellek yoklaması ise düzeltme tablosuna bak
+    if crate::fixup::fixup_exception(trap_cause, &mut context.elr_el1) {
+        return;
+    }
+
     serial_println!("\n--- ARMv9 SENKRON İSTİSNASI ---");
     serial_println!("ELR_EL1 (Hata Adresi): {:#x}", context.elr_el1);
     serial_println!("SPSR_EL1 (Eski Durum): {:#x}", context.spsr_el1);
@@ -120,26 +113,101 @@ pub extern "C" fn generic_sync_handler(esr_el1: u64, context: &ExceptionContext)
         _ => serial_println!("-> Bilinmeyen Hata Sınıfı: {:#x}", ec),
     }
 
-    panic!("Kritik Senkron İstisna!");
+    handle_fatal(trap_cause, context.elr_el1 as usize);
 }
 
 /// Donanım Kesmeleri (IRQ) için genel işleyici.
 ///
 /// # Parametreler
+/// * `_vector`: Vektör tablosundaki yuva (1, 5 veya 9).
 /// * `context`: İstisna öncesi CPU durumunu içeren yapı.
 #[no_mangle]
-pub extern "C" fn generic_irq_handler(_context: &ExceptionContext) {
+pub extern "C" fn generic_irq_handler(_vector: u64, context: &mut ExceptionContext) {
+    use crate::arch::armv9::interrupt::GicCpuInterface;
+    let entry = crate::latency::irq_enter();
+    let _irq = crate::irqstack::enter(context as *const _ as usize);
+
     // 1. GIC (Generic Interrupt Controller) veya yerel kesme kontrolcüsünden
     //    hangi kesmenin geldiğini oku.
-    
+    let irq_id = unsafe { GicCpuInterface::get_irq() } & 0x00FF_FFFF;
+    // 1020-1023 özel kimliklerdir; 1023 sahte (spurious) kesmedir. Bunlar
+    // onaylanmış sayılmaz, EOI gönderilmez ve kesme istatistiğine girmez.
+    if (GIC_SPECIAL_FIRST..=GIC_SPURIOUS).contains(&irq_id) {
+        SPURIOUS_IRQS.inc();
+        return;
+    }
+    crate::stats::record_irq(irq_id as usize);
+    crate::replay::irq(irq_id);
+    crate::latency::irq_dispatch(irq_id, entry);
+    if !crate::irqstorm::irq_fired(irq_id, irq_handler_name) {
+        unsafe { GicCpuInterface::send_eoi(irq_id) };
+        return;
+    }
+
     // 2. Uygun sürücüyü çağır.
-    
-    // 3. Kesme işleminin bittiğini GIC'ye bildir (End of Interrupt - EOI).
+    match irq_id {
+        crate::arch::armv9::console::UART_IRQ => crate::arch::armv9::console::Uart::handle_interrupt(),
+        TIMER_PPI => crate::hrtimer::timer_interrupt(),
+        irq if crate::drivers::usb::xhci::handle_irq(irq) => {}
+        _ => {}
+    }
 
     // serial_print!("!"); // Sık kesme durumunda loglamayı engelle
 
-    // Örn: GICv3'e EOI gönderme (Bu, platform modülünde yapılmalıdır)
-    // unsafe { arch::armv9::gic::send_eoi(); }
+    // 3. Kesme işleminin bittiğini GIC'ye bildir (End of Interrupt - EOI).
+    unsafe { GicCpuInterface::send_eoi(irq_id) };
+}
+
+/// EL1 fiziksel zamanlayıcısının (CNTP) PPI numarası.
+const TIMER_PPI: u32 = 30;
+
+/// GIC özel kesme kimlikleri (1020-1023); 1023 sahte kesmedir.
+const GIC_SPECIAL_FIRST: u32 = 1020;
+const GIC_SPURIOUS: u32 = 1023;
+
+crate::kernel_counter!(SPURIOUS_IRQS, "irq.spurious");
+
+/// Kesme fırtınası uyarısında gösterilen işleyici adı.
+fn irq_handler_name(irq_id: u32) -> &'static str {
+    match irq_id {
+        crate::arch::armv9::console::UART_IRQ => "uart",
+        TIMER_PPI => "timer",
+        _ => "xhci/bilinmeyen",
+    }
+}
+
+/// Desteklenmeyen girdiler (FIQ, SError, AArch32) için işleyici; her zaman ölümcüldür.
+///
+/// # Parametreler
+/// * `vector`: Vektör tablosundaki yuva (0-15).
+/// * `context`: İstisna öncesi CPU durumunu içeren yapı.
+#[no_mangle]
+pub extern "C" fn generic_unhandled_vector(vector: u64, context: &mut ExceptionContext) {
+    let esr_el1: u64;
+    unsafe {
+        asm!("mrs {}, ESR_EL1", out(reg) esr_el1, options(nostack, nomem));
+    }
+
+    // SError asenkron bir veri yolu/bellek hatasıdır; diğerleri beklenmeyen girdidir
+    let cause = if vector & 3 == 3 { Cause::MachineError } else { Cause::Unknown(vector) };
+
+    // Etkin bir `catch` gövdesindeki SError (örn. `mmio::try_read32` yoklaması)
+    // gövdeyi terk ederek kurtarılır
+    if vector & 3 == 3 && crate::fixup::fixup_exception(cause, &mut context.elr_el1) {
+        return;
+    }
+
+    serial_println!("\n--- ARMv9 İŞLENMEYEN İSTİSNA VEKTÖRÜ ---");
+    serial_println!(
+        "Vektör: {} ({}, {})",
+        vector,
+        VECTOR_GROUPS[(vector as usize >> 2) & 3],
+        VECTOR_KINDS[vector as usize & 3]
+    );
+    serial_println!("ELR_EL1 (Hata Adresi): {:#x}", context.elr_el1);
+    serial_println!("ESR_EL1 (Sendrom Kodu): {:#x}", esr_el1);
+
+    handle_fatal(cause, context.elr_el1 as usize);
 }
 
 
@@ -150,14 +218,11 @@ pub extern "C" fn generic_irq_handler(_context: &ExceptionContext) {
 /// Vektör Tablosunu başlatır ve CPU'ya yükler.
 pub fn init_exceptions() {
     unsafe {
-        // 1. Vektör Tablosunu montaj işleyicileriyle doldur.
-        VECTOR_TABLE.init();
-        
-        // 2. VBAR_EL1 yazmacını Vektör Tablosunun adresine ayarla.
-        let table_addr = &VECTOR_TABLE as *const _ as u64;
+        // VBAR_EL1 yazmacını üretilen Vektör Tablosunun adresine ayarla (2 KiB hizalı).
+        let table_addr = armv9_vectors as usize as u64;
 
         // VBAR_EL1 yazmacına yazma:
-        asm!("msr VBAR_EL1, {}", in(reg) table_addr, options(nostack, nomem));
+        asm!("msr VBAR_EL1, {}", "isb", in(reg) table_addr, options(nostack, nomem));
     }
     
     serial_println!("[ARMv9] Vektör Tablosu (VBAR_EL1) yüklendi.");
@@ -173,4 +238,36 @@ pub fn enable_interrupts() {
         asm!("msr daifclr, #2", options(nostack, nomem)); // #2 = IRQ maskesi (I)
     }
     serial_println!("[ARMv9] Harici kesmeler (IRQ) etkinleştirildi.");
-}
\ No newline at end of file
+}
+// -----------------------------------------------------------------------------
+// 4. KESME YIĞINI (SP_EL1 / SP_EL0)
+// -----------------------------------------------------------------------------
+
+/// Kesme girişi ayrı kesme yığınına geçer (bkz. `crate::irqstack`).
+pub const SWITCHES_IRQ_STACK: bool = true;
+
+/// Görevleri `SP_EL0`'a, istisnaları `SP_EL1`'e ayırır: çalışan yığın
+/// `SP_EL0`'a taşınır, `SP_EL1` kesme yığınının üstüne (`top`) kurulur ve
+/// `SPSel = 0` yapılır. Bundan sonra istisnalar "Current EL with SP0"
+/// girdilerinden gelir ve donanım girişte `SP_EL1`'e geçer; iç içe
+/// istisnalar aynı yığında aşağı doğru devam eder.
+///
+/// # Safety
+/// `SPSel = 1` iken ve `top` bu işlemciye ait bir yığının üstüyken çağrılmalıdır.
+/// `SP_EL1`'in eski değeri (önyükleme yığını) görev yığını olarak kullanılmaya devam eder.
+pub unsafe fn install_irq_stack(top: usize) -> Result<(), KernelError> {
+    asm!(
+        "mrs {daif}, daif",
+        "msr daifset, #0xf",
+        "mov {tmp}, sp",
+        "msr sp_el0, {tmp}",
+        "mov sp, {top}",
+        "msr spsel, #0",
+        "msr daif, {daif}",
+        daif = out(reg) _,
+        tmp = out(reg) _,
+        top = in(reg) top,
+        options(nomem),
+    );
+    Ok(())
+}
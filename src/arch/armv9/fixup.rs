@@ -0,0 +1,79 @@
+// src/arch/armv9/fixup.rs
+// ARMv9 (AArch64) hata güvenli bellek yoklama talimatları.
+
+#![allow(dead_code)]
+
+use core::arch::asm;
+
+/// Tek bayt okur; Data Abort durumunda `None` döner.
+pub unsafe fn probe_read_u8(addr: usize) -> Option<u8> {
+    let value: u32;
+    let err: u32;
+    asm!(
+        "mov {err:w}, #0",
+        "2: ldrb {val:w}, [{addr}]",
+        "3:",
+        ".pushsection .text.fixup, \"ax\"",
+        "4: mov {err:w}, #1",
+        "mov {val:w}, #0",
+        "b 3b",
+        ".popsection",
+        ".pushsection .ex_table, \"a\"",
+        ".balign 8",
+        ".quad 2b, 4b",
+        ".popsection",
+        addr = in(reg) addr,
+        val = out(reg) value,
+        err = out(reg) err,
+        options(nostack, readonly)
+    );
+    if err == 0 { Some(value as u8) } else { None }
+}
+
+/// Tek bayt yazar; erişim hatasında `false` döner.
+pub unsafe fn probe_write_u8(addr: usize, value: u8) -> bool {
+    let err: u32;
+    asm!(
+        "mov {err:w}, #0",
+        "2: strb {val:w}, [{addr}]",
+        "3:",
+        ".pushsection .text.fixup, \"ax\"",
+        "4: mov {err:w}, #1",
+        "b 3b",
+        ".popsection",
+        ".pushsection .ex_table, \"a\"",
+        ".balign 8",
+        ".quad 2b, 4b",
+        ".popsection",
+        addr = in(reg) addr,
+        val = in(reg) u32::from(value),
+        err = out(reg) err,
+        options(nostack)
+    );
+    err == 0
+}
+
+/// 32 bit okur; eşzamanlı Data Abort durumunda `None` döner.
+pub unsafe fn probe_read_u32(addr: usize) -> Option<u32> {
+    let value: u32;
+    let err: u32;
+    asm!(
+        "mov {err:w}, #0",
+        "2: ldr {val:w}, [{addr}]",
+        "3:",
+        ".pushsection .text.fixup, \"ax\"",
+        "4: mov {err:w}, #1",
+        "mov {val:w}, #0",
+        "b 3b",
+        ".popsection",
+        ".pushsection .ex_table, \"a\"",
+        ".balign 8",
+        ".quad 2b, 4b",
+        ".popsection",
+        addr = in(reg) addr,
+        val = out(reg) value,
+        err = out(reg) err,
+        options(nostack, readonly)
+    );
+    if err == 0 { Some(value) } else { None }
+}
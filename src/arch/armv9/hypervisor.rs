@@ -0,0 +1,69 @@
+// src/arch/armv9/hypervisor.rs
+// ARMv9 (AArch64) hypervisor algılama: Aygıt Ağacı ve SMCCC Vendor Hyp UID.
+
+#![allow(dead_code)]
+
+use core::arch::asm;
+use crate::arch::armv9::dtb::DtbParser;
+use crate::hypervisor::{HypervisorKind, Paravirt};
+
+/// SMCCC: Vendor Specific Hypervisor Service Call UID sorgusu.
+const SMCCC_VENDOR_HYP_CALL_UID: u64 = 0x8600_FF01;
+
+/// KVM'nin döndürdüğü UID (x0-x3).
+const KVM_UID: [u32; 4] = [0xb66f_b428, 0xe911_c52e, 0x564b_caa9, 0x743a_004d];
+
+/// HVC ile SMCCC çağrısı yapar ve x0-x3'ü döndürür.
+unsafe fn hvc_call(function_id: u64) -> [u64; 4] {
+    let (r0, r1, r2, r3): (u64, u64, u64, u64);
+    asm!(
+        "hvc #0",
+        inout("x0") function_id => r0,
+        out("x1") r1,
+        out("x2") r2,
+        out("x3") r3,
+        options(nomem, nostack)
+    );
+    [r0, r1, r2, r3]
+}
+
+/// Önce Aygıt Ağacının `/hypervisor` düğümüne bakar. PSCI HVC ile sunuluyorsa
+/// EL1 bir hypervisor altında çalışıyordur; tür SMCCC UID sorgusuyla belirlenir.
+///
+/// PSCI SMC ile sunuluyorsa HVC güvenli değildir (EL2 yoksa tanımsız talimat);
+/// bu durumda çıplak donanım varsayılır.
+pub fn detect() -> HypervisorKind {
+    let Ok(config) = DtbParser::get_config() else {
+        return HypervisorKind::None;
+    };
+
+    if let Some(compatible) = config.hypervisor_compatible {
+        return if compatible.starts_with("xen,") { HypervisorKind::Xen } else { HypervisorKind::Other };
+    }
+    if !config.psci_method_hvc {
+        return HypervisorKind::None;
+    }
+
+    let uid = unsafe { hvc_call(SMCCC_VENDOR_HYP_CALL_UID) };
+    if uid.iter().zip(KVM_UID.iter()).all(|(reg, expected)| *reg as u32 == *expected) {
+        HypervisorKind::Kvm
+    } else {
+        // TCG altında QEMU, PSCI'yi HVC ile öykünür ancak UID sorgusunu desteklemez.
+        HypervisorKind::Other
+    }
+}
+
+/// Firmware çağrılarının HVC ile yapılmasını bildirir. Genel zamanlayıcının sanal
+/// sayacı (CNTVCT_EL0) donanımca sanallaştırıldığından ek bir paravirt saat gerekmez.
+pub unsafe fn enable_paravirt(_kind: HypervisorKind) -> Paravirt {
+    let hvc = DtbParser::get_config().map(|c| c.psci_method_hvc).unwrap_or(false);
+    Paravirt { hvc_conduit: hvc, ..Paravirt::default() }
+}
+
+/// `enable_paravirt` saat olanağını açmaz.
+pub fn pv_clock_ns() -> u64 {
+    0
+}
+
+/// `enable_paravirt` konsol olanağını açmaz; PL011 sanal makinelerde de kullanılır.
+pub fn pv_console_write_byte(_byte: u8) {}
@@ -1,6 +1,7 @@
 #![allow(dead_code)]
 
 use core::ptr::{read_volatile, write_volatile};
+use core::sync::atomic::{AtomicUsize, Ordering};
 use crate::serial_println;
 
 // -----------------------------------------------------------------------------
@@ -13,6 +14,11 @@ const GICD_BASE: usize = 0x0800_0000; // Distributor Base Adresi
 const GICC_BASE: usize = 0x0800_0000; // CPU Interface Base Adresi (Genellikle farklıdır, 
                                       // ancak GICv3/4'te bu CPU arabirimi sistem yazmaçlarıdır.)
 
+const GICD_SIZE: usize = 0x1_0000;
+
+/// Dağıtıcının `physmap::map_mmio` ile eşlenen sanal adresi (eşlenene kadar fiziksel).
+static GICD: AtomicUsize = AtomicUsize::new(GICD_BASE);
+
 // NOT: Modern ARMv9 sistemlerinde, CPU Interface için GIC system registers kullanılır (ICC_* yazmaçları).
 
 // -----------------------------------------------------------------------------
@@ -26,13 +32,13 @@ impl GicDistributor {
     /// Bir Dağıtıcı Yazmacından 32 bitlik veri okur.
     #[inline(always)]
     unsafe fn read_reg(offset: usize) -> u32 {
-        read_volatile((GICD_BASE + offset) as *const u32)
+        read_volatile((GICD.load(Ordering::Relaxed) + offset) as *const u32)
     }
 
     /// Bir Dağıtıcı Yazmacına 32 bitlik veri yazar.
     #[inline(always)]
     unsafe fn write_reg(offset: usize, value: u32) {
-        write_volatile((GICD_BASE + offset) as *mut u32, value)
+        write_volatile((GICD.load(Ordering::Relaxed) + offset) as *mut u32, value)
     }
     
     // GICD_CTLR (Kontrol Yazmacı - Offset 0x000)
@@ -47,6 +53,12 @@ impl GicDistributor {
 
     /// GIC Dağıtıcısını (Distributor) başlatır.
     pub unsafe fn init() {
+        // Dağıtıcı RAM eşlemesinin dışındadır; yüksek yarıda aygıt olarak eşlenir.
+        match crate::physmap::map_mmio(GICD_BASE, GICD_SIZE) {
+            Ok(virt) => GICD.store(virt, Ordering::Relaxed),
+            Err(e) => serial_println!("[ARMv9] GICD eşlenemedi: {:?}", e),
+        }
+
         // Dağıtıcıyı devre dışı bırak
         Self::write_reg(Self::CTLR, 0); 
         
@@ -152,4 +164,74 @@ pub fn init_gic() {
     }
 
     serial_println!("[ARMv9] GICv3/v4 Başlatıldı (Distributor ve CPU Interface).");
-}
\ No newline at end of file
+}
+
+// -----------------------------------------------------------------------------
+// YEREL KESME MASKESİ
+// -----------------------------------------------------------------------------
+
+// Genel kod bunları `crate::irqoff` üzerinden kullanır. `asm!` bloklarında
+// `nomem` yoktur: maske değişikliği derleyici için bir bellek engelidir,
+// kilitli bölgenin erişimleri dışarı taşınmaz.
+
+/// DAIF.I biti: 1 ise IRQ maskelidir.
+const DAIF_I: usize = 1 << 7;
+
+/// Yerel IRQ'ları maskeler; önceki DAIF değerini döndürür.
+#[inline(always)]
+pub fn local_irq_save() -> usize {
+    let flags: usize;
+    // SAFETY: Yalnızca DAIF okunur ve I biti kurulur.
+    unsafe { core::arch::asm!("mrs {}, daif", "msr daifset, #2", out(reg) flags, options(nostack)) };
+    flags
+}
+
+/// `local_irq_save` öncesindeki DAIF değerini geri yazar.
+#[inline(always)]
+pub fn local_irq_restore(flags: usize) {
+    // SAFETY: Yalnızca `local_irq_save`'in okuduğu değer geri yazılır.
+    unsafe { core::arch::asm!("msr daif, {}", in(reg) flags, options(nostack)) };
+}
+
+/// Kaydedilen durumda IRQ'lar açık mıydı?
+#[inline(always)]
+pub fn irqs_were_enabled(flags: usize) -> bool {
+    flags & DAIF_I == 0
+}
+
+/// Yerel IRQ'ları koşulsuz açar.
+#[inline(always)]
+pub fn local_irq_enable() {
+    // SAFETY: Çağıran kesme almaya hazırdır.
+    unsafe { core::arch::asm!("msr daifclr, #2", options(nostack)) };
+}
+
+// -----------------------------------------------------------------------------
+// HAT MASKESİ VE PCI INTx
+// -----------------------------------------------------------------------------
+
+/// Kesme hattını denetleyicide maskeler veya açar (bkz. `crate::irqstorm`);
+/// `irq` dağıtıcının gördüğü numaradır. Desteklenmiyorsa `false`.
+pub fn set_irq_masked(irq: u32, masked: bool) -> bool {
+    // SAFETY: GIC ISENABLER/ICENABLER yazmaçları yalnızca ilgili biti etkiler.
+    unsafe {
+        if masked {
+            GicDistributor::disable_irq(irq);
+        } else {
+            GicDistributor::enable_irq(irq);
+        }
+    }
+    true
+}
+
+/// PCI aygıtının INTx hattını açar ve dağıtıcının göreceği kesme numarasını
+/// döndürür. INTx eşlemesi yoksa `None`; sürücü yoklamayla çalışır.
+pub fn route_pci_intx(_line: u8) -> Option<u32> {
+    None
+}
+
+/// Aygıt ağacı düğümünün ilk kesmesini GIC kesme kimliğine çevirir; GIC
+/// belirteci üç hücrelidir (bkz. `fdt::Node::gic_irq`).
+pub fn decode_fdt_irq(node: &crate::fdt::Node) -> Option<u32> {
+    node.gic_irq()
+}
@@ -3,7 +3,10 @@
 
 use core::arch::asm;
 use core::ptr::NonNull;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use crate::platformgeneric::KernelError;
 use crate::serial_println;
+use crate::memory::vmaudit::{validate_mapping, CacheAttr, MapError, Mapping, Perms};
 use super::io; // Bariyerler için io modülünü kullanacağız
 
 // -----------------------------------------------------------------------------
@@ -111,23 +114,82 @@ fn get_indices(virtual_addr: usize) -> (usize, usize, usize, usize) {
 }
 
 
-/// Yeni bir sayfa tablosu tahsis etmeyi simüle eder.
-/// (Gerçekte bir fiziksel bellek yöneticisi gereklidir.)
-fn alloc_page_table() -> NonNull<PageTable> {
-    // Lütfen unutmayın: Bu, gerçek bellek yöneticisi olmadan tehlikelidir ve sadece bir mock'tur.
-    static mut PAGE_TABLE_MOCK: [u8; PAGE_SIZE] = [0; PAGE_SIZE];
-    
-    let ptr = unsafe { PAGE_TABLE_MOCK.as_mut_ptr() as *mut PageTable };
-    let table = unsafe { &mut *ptr };
-    table.entries.iter_mut().for_each(|e| *e = PageTableEntry::zero());
-    
-    // Güvenli olmayan bir şekilde NonNull oluşturma.
-    unsafe { NonNull::new_unchecked(ptr) }
+/// Sıfırlanmış yeni bir sayfa tablosu ayırır (bkz. `physmap::alloc_table`).
+fn alloc_page_table() -> Result<NonNull<PageTable>, MapError> {
+    let addr = crate::physmap::alloc_table().ok_or(MapError::OutOfMemory)?;
+    // SAFETY: `alloc_table` sıfırdan farklı, sayfa hizalı bir adres döndürür.
+    Ok(unsafe { NonNull::new_unchecked(addr as *mut PageTable) })
+}
+
+/// Blok tanımlayıcısı mı? (geçerli, bit 1 = 0; yalnızca L1/L2 seviyesinde)
+fn is_block(entry: &PageTableEntry) -> bool {
+    entry.0 & (DescriptorFlags::PRESENT as u64 | DescriptorFlags::TABLE_OR_BLOCK as u64) == DescriptorFlags::PRESENT as u64
+}
+
+/// Tablo girişi geçerliyse gösterdiği tabloyu, değilse yeni ayrılan tabloyu
+/// döndürür; yeni tablo üst tablonun giriş sayısına eklenir (`physmap::table_ref`).
+/// Giriş bir blok tanımlayıcısıysa adres zaten eşlidir.
+unsafe fn next_table(table: &mut PageTable, index: usize) -> Result<&'static mut PageTable, MapError> {
+    let table_addr = table as *mut PageTable as usize;
+    let entry = &mut table.entries[index];
+    if !entry.is_present() {
+        *entry = PageTableEntry::new_table(alloc_page_table()?.as_ptr() as usize);
+        crate::physmap::table_ref(table_addr);
+    } else if is_block(entry) {
+        return Err(MapError::AlreadyMapped);
+    }
+    Ok(&mut *((entry.0 & DESC_ADDR_MASK) as *mut PageTable))
+}
+
+/// Yaprak girişini yazar. Aynı eşlemenin tekrarı etkisizdir; farklı bir
+/// eşleme yalnızca `replace` ile değiştirilir.
+unsafe fn set_leaf(table: &mut PageTable, index: usize, new: PageTableEntry, replace: bool) -> Result<(), MapError> {
+    let table_addr = table as *mut PageTable as usize;
+    let entry = &mut table.entries[index];
+    if entry.is_present() {
+        if entry.0 == new.0 {
+            return Ok(());
+        }
+        if !replace {
+            return Err(MapError::AlreadyMapped);
+        }
+    } else {
+        crate::physmap::table_ref(table_addr);
+    }
+    *entry = new;
+    Ok(())
+}
+
+/// Adres ve bayrakların ortak denetimi: 48 bit sanal (TTBR0 alt yarı veya
+/// TTBR1 üst yarı) ve 48 bit fiziksel adres; bayraklar çıkış adresi alanına
+/// (bit 12..47) taşamaz.
+fn validate(virtual_addr: usize, physical_addr: usize, size: usize, flags: u64) -> Result<(), MapError> {
+    validate_mapping(virtual_addr, physical_addr, size, 48, 48, flags, DESC_ADDR_MASK)
 }
 
+/// 4 KiB eşlemenin ortak yolu (`map_page` / `remap_page`).
+unsafe fn map_small(root_table_addr: usize, virtual_addr: usize, physical_addr: usize, flags: u64, replace: bool) -> Result<(), MapError> {
+    validate(virtual_addr, physical_addr, PAGE_SIZE, flags)?;
+    let (l1i, l2i, l3i, l4i) = get_indices(virtual_addr);
+
+    let l1_table = &mut *(root_table_addr as *mut PageTable);
+    // L1 -> L2 -> L3 -> L4; eksik tablolar oluşturulur
+    let l2_table = next_table(l1_table, l1i)?;
+    let l3_table = next_table(l2_table, l2i)?;
+    let l4_table = next_table(l3_table, l3i)?;
+
+    // L4 Girişini ayarla (Son eşleme, 4K Sayfa)
+    set_leaf(l4_table, l4i, PageTableEntry::new_page(physical_addr, flags), replace)
+}
 
 /// Sanal adresi fiziksel adrese eşler (4KiB sayfa).
 ///
+/// # Hatalar
+/// * `Unaligned` / `OutOfRange` / `InvalidFlags`: bkz. `validate_mapping`.
+/// * `AlreadyMapped`: Adres başka bir adrese veya özniteliklerle eşli ya da
+///   bir blok tanımlayıcısının içinde.
+/// * `OutOfMemory`: Ara tablo ayrılamadı.
+///
 /// # Güvenlik Notu
 /// Bu fonksiyon doğrudan bellek adreslerini değiştirir.
 pub unsafe fn map_page(
@@ -135,82 +197,117 @@ pub unsafe fn map_page(
     virtual_addr: usize,
     physical_addr: usize,
     flags: u64,
-) {
-    let (l1i, l2i, l3i, l4i) = get_indices(virtual_addr);
-    
+) -> Result<(), MapError> {
+    // Eşleme yapıldıktan sonra TLB temizliği veya I-sync gereklidir, ancak 
+    // MMU etkinleştirme sırasında bu genellikle genel bir işlemle yapılır.
+    map_small(root_table_addr, virtual_addr, physical_addr, flags, false)
+}
+
+/// `map_page` gibi, ancak mevcut 4 KiB eşlemeyi (adres veya öznitelikler)
+/// değiştirir. Çağıran eski çeviri için TLB'yi boşaltmalıdır.
+pub unsafe fn remap_page(root_table_addr: usize, virtual_addr: usize, physical_addr: usize, flags: u64) -> Result<(), MapError> {
+    map_small(root_table_addr, virtual_addr, physical_addr, flags, true)
+}
+
+/// Sanal adresi 2 MiB'lık blok tanımlayıcısıyla (L2 seviyesi) eşler.
+/// Adresler `physmap::LARGE_PAGE_SIZE` hizalı olmalıdır.
+///
+/// # Hatalar
+/// `map_page` ile aynı; ayrıca giriş zaten bir tablo gösteriyorsa `AlreadyMapped`.
+pub unsafe fn map_large_page(root_table_addr: usize, virtual_addr: usize, physical_addr: usize, flags: u64) -> Result<(), MapError> {
+    validate(virtual_addr, physical_addr, crate::physmap::LARGE_PAGE_SIZE, flags)?;
+    let (l1i, l2i, l3i, _) = get_indices(virtual_addr);
     let l1_table = &mut *(root_table_addr as *mut PageTable);
-    
-    // 1. L2'yi al veya oluştur
-    let l2_entry = l1_table.entries.get_mut(l1i).expect("L1 Index Hata");
-    let l2_addr = if l2_entry.is_present() {
-        l2_entry.0 & 0x0000_FFFF_FFFF_F000 // Adresi al
-    } else {
-        let new_l2 = alloc_page_table();
-        let new_addr = new_l2.as_ptr() as usize;
-        *l2_entry = PageTableEntry::new_table(new_addr);
-        new_addr
-    };
-    
-    let l2_table = &mut *(l2_addr as *mut PageTable);
-    
-    // 2. L3'ü al veya oluştur
-    let l3_entry = l2_table.entries.get_mut(l2i).expect("L2 Index Hata");
-    let l3_addr = if l3_entry.is_present() {
-        l3_entry.0 & 0x0000_FFFF_FFFF_F000
-    } else {
-        let new_l3 = alloc_page_table();
-        let new_addr = new_l3.as_ptr() as usize;
-        *l3_entry = PageTableEntry::new_table(new_addr);
-        new_addr
-    };
+    let l2_table = next_table(l1_table, l1i)?;
+    let l3_table = next_table(l2_table, l2i)?;
+    let entry = &l3_table.entries[l3i];
+    if entry.is_present() && !is_block(entry) {
+        return Err(MapError::AlreadyMapped);
+    }
+    // Blok tanımlayıcısı: bit 1 = 0 (tablo değil), bit 0 = geçerli.
+    let block = PageTableEntry((physical_addr as u64) | (flags & !(DescriptorFlags::TABLE_OR_BLOCK as u64)) | DescriptorFlags::PRESENT as u64);
+    set_leaf(l3_table, l3i, block, false)
+}
 
-    let l3_table = &mut *(l3_addr as *mut PageTable);
-    
-    // 3. L4'ü al veya oluştur
-    let l4_entry = l3_table.entries.get_mut(l3i).expect("L3 Index Hata");
-    let l4_addr = if l4_entry.is_present() {
-        l4_entry.0 & 0x0000_FFFF_FFFF_F000
-    } else {
-        let new_l4 = alloc_page_table();
-        let new_addr = new_l4.as_ptr() as usize;
-        *l4_entry = PageTableEntry::new_table(new_addr);
-        new_addr
+/// Sanal adresi içeren yaprak eşlemeyi (4 KiB sayfa veya blok) kaldırır,
+/// TLB girişini geçersiz kılar ve boşalan ara tabloları serbest bırakır.
+/// Eşlenmiş fiziksel adresi döndürür.
+///
+/// # Hatalar
+/// * `NotMapped`: Adres eşli değil.
+/// * `Unaligned`: Adres, onu içeren bloğun başlangıcı değil.
+pub unsafe fn unmap_page(root_table_addr: usize, virtual_addr: usize) -> Result<usize, MapError> {
+    let (l1i, l2i, l3i, l4i) = get_indices(virtual_addr);
+    let indices = [l1i, l2i, l3i, l4i];
+    let mut path = [root_table_addr, 0, 0, 0];
+    let mut level = 0;
+    let physical_addr = loop {
+        let entry = &mut (*(path[level] as *mut PageTable)).entries[indices[level]];
+        if !entry.is_present() {
+            return Err(MapError::NotMapped);
+        }
+        if level == 3 || (level > 0 && is_block(entry)) {
+            let size = 1usize << (12 + 9 * (3 - level));
+            if !virtual_addr.is_multiple_of(size) {
+                return Err(MapError::Unaligned);
+            }
+            let physical_addr = (entry.0 & DESC_ADDR_MASK) as usize & !(size - 1);
+            *entry = PageTableEntry::zero();
+            break physical_addr;
+        }
+        path[level + 1] = (entry.0 & DESC_ADDR_MASK) as usize;
+        level += 1;
     };
+    flush_page(virtual_addr);
 
-    let l4_table = &mut *(l4_addr as *mut PageTable);
-    
-    // 4. L4 Girişini ayarla (Son eşleme, 4K Sayfa)
-    let final_entry = l4_table.entries.get_mut(l4i).expect("L4 Index Hata");
-    *final_entry = PageTableEntry::new_page(physical_addr, flags);
-    
-    // Eşleme yapıldıktan sonra TLB temizliği veya I-sync gereklidir, ancak 
-    // MMU etkinleştirme sırasında bu genellikle genel bir işlemle yapılır.
+    // Boşalan tablolar yukarı doğru serbest bırakılır; kök izlenmez (`None`).
+    // Tablo, üst giriş temizlenip TLB (yürüyüş önbelleği dahil) boşaltıldıktan
+    // sonra geri verilir.
+    while crate::physmap::table_unref(path[level]) == Some(0) && level > 0 {
+        (*(path[level - 1] as *mut PageTable)).entries[indices[level - 1]] = PageTableEntry::zero();
+        flush_page(virtual_addr);
+        crate::physmap::free_table(path[level]);
+        level -= 1;
+    }
+    Ok(physical_addr)
+}
+
+/// Tek bir sanal adresin TLB girişlerini (tüm ASID'ler, iç paylaşım alanı) geçersiz kılar.
+#[inline(always)]
+unsafe fn flush_page(virtual_addr: usize) {
+    if crate::quirks::has(crate::quirks::TLB_FLUSH_ALL) {
+        asm!("dsb ishst", "tlbi vmalle1is", "dsb ish", "isb");
+        return;
+    }
+    asm!("dsb ishst", "tlbi vaae1is, {0}", "dsb ish", "isb", in(reg) (virtual_addr >> 12) & 0xFFF_FFFF_FFFF);
 }
 
 // -----------------------------------------------------------------------------
 // ÇEKİRDEK BAŞLATMA VE AKTİVASYON
 // -----------------------------------------------------------------------------
 
-/// SCTLR_EL1 yazmacını ayarlar ve MMU'yu etkinleştirir.
-pub unsafe fn enable_mmu() {
+/// SCTLR_EL1 yazmacını ayarlar ve MMU'yu etkinleştirir. RAM eşlenemezse
+/// hiçbir sistem yazmacına dokunmadan hata döner.
+pub unsafe fn enable_mmu() -> Result<(), MapError> {
     // 1. TTBR'ları (Translation Table Base Registers) ayarla (L1 adresini TTBR1_EL1'e yaz)
     // Bu, önceki map_page çağrılarında kullanılan root_table_addr'ın fiziksel adresi olmalıdır.
     static mut L1_TABLE: [u8; PAGE_SIZE] = [0; PAGE_SIZE];
     let l1_addr = L1_TABLE.as_mut_ptr() as usize;
 
-    // Örnek eşleme: İlk 16MB'ı birebir eşle (MMIO ve çekirdek kod/veri için).
-    let mapping_size = 16 * 1024 * 1024; // 16 MB
+    // Tüm RAM yüksek yarıda doğrusal eşlenir (sanal = KERNEL_START_VADDR + fiziksel);
+    // aygıtlar `physmap::map_mmio` ile.
     let flags = DescriptorFlags::PRESENT as u64 
                 | DescriptorFlags::AP_RW_KERN_ONLY as u64
                 | DescriptorFlags::SH_INNER as u64
                 | DescriptorFlags::AF_ACCESSED as u64
                 | DescriptorFlags::ATTR_IDX_NORM_RW as u64;
 
-    for addr in (0..mapping_size).step_by(PAGE_SIZE) {
-        // Sanal ve fiziksel adresler aynı olsun
-        map_page(l1_addr, KERNEL_START_VADDR + addr, addr, flags);
-    }
-    serial_println!("[ARMv9] İlk 16MB Yüksek Adrese Eşlendi ({:#x}).", KERNEL_START_VADDR);
+    crate::physmap::map_ram(
+        KERNEL_START_VADDR,
+        &mut |virt, phys| map_large_page(l1_addr, virt, phys, flags),
+        &mut |virt, phys| map_page(l1_addr, virt, phys, flags),
+    )?;
+    ROOT_TABLE.store(l1_addr, Ordering::Relaxed);
     
     // TTBR1_EL1'e L1 tablonun adresini yaz
     asm!("msr ttbr1_el1, {0}", in(reg) l1_addr);
@@ -256,6 +353,7 @@ pub unsafe fn enable_mmu() {
     io::isb();
     
     serial_println!("[ARMv9] MMU başarıyla etkinleştirildi.");
+    Ok(())
 }
 
 
@@ -263,7 +361,176 @@ pub unsafe fn enable_mmu() {
 /// `main.rs` içinden çağrılmalıdır.
 pub fn init_mmu() {
     // MMU'yu başlat ve etkinleştir.
+    if let Err(e) = unsafe { enable_mmu() } {
+        serial_println!("[ARMv9] İlk sayfa tablosu kurulamadı, MMU kapalı kaldı: {:?}", e);
+    }
+}
+
+// -----------------------------------------------------------------------------
+// AYGIT EŞLEMELERİ (physmap)
+// -----------------------------------------------------------------------------
+
+/// `enable_mmu` tarafından kurulan TTBR1 kök tablosu (0: henüz yok).
+static ROOT_TABLE: AtomicUsize = AtomicUsize::new(0);
+
+/// Aygıt bölgesini yüksek yarıda Device-nGnRE özniteliğiyle eşler
+/// (`physmap::map_mmio`) ve sanal adresini döndürür.
+///
+/// # Hatalar
+/// * `Map(NotReady)`: MMU henüz kurulmadı.
+/// * Eşleme hataları `MapError` dönüşümüyle (bkz. `vmaudit`).
+pub fn map_device(phys: usize, len: usize) -> Result<usize, KernelError> {
+    let root = ROOT_TABLE.load(Ordering::Relaxed);
+    if root == 0 {
+        return Err(MapError::NotReady.into());
+    }
+    let flags = DescriptorFlags::PRESENT as u64
+        | DescriptorFlags::AP_RW_KERN_ONLY as u64
+        | DescriptorFlags::AF_ACCESSED as u64
+        | DescriptorFlags::ATTR_IDX_DEVICE_NGNRE as u64
+        | DescriptorFlags::UXN_XN as u64;
+    let virt = KERNEL_START_VADDR + phys;
+    crate::physmap::map_range(
+        virt,
+        phys,
+        len,
+        &mut |virt, phys| unsafe { map_large_page(root, virt, phys, flags) },
+        &mut |virt, phys| unsafe { map_page(root, virt, phys, flags) },
+    )?;
+    // SAFETY: Yalnızca yeni girişler eklendi; tablo yazımları görünür kılınıp TLB temizlenir.
+    unsafe { asm!("dsb ishst", "tlbi vmalle1", "dsb ish", "isb") };
+    Ok(virt)
+}
+
+// -----------------------------------------------------------------------------
+// ÇEKİRDEK SANAL ALANI (vmalloc)
+// -----------------------------------------------------------------------------
+
+/// `memory::vmalloc` penceresinin başlangıcı (TTBR1, doğrusal RAM eşlemesinin üstünde).
+pub const VMALLOC_BASE: usize = 0xFFFF_C000_0000_0000;
+/// `memory::vmalloc` penceresinin boyutu.
+pub const VMALLOC_SIZE: usize = 1 << 30;
+
+/// Çekirdek verisi için 4 KiB sayfa eşler (normal bellek, yalnızca çekirdek,
+/// yürütülemez).
+///
+/// # Hatalar
+/// * `Map(NotReady)`: MMU henüz kurulmadı.
+/// * Eşleme hataları `MapError` dönüşümüyle (bkz. `vmaudit`).
+pub fn map_kernel_page(virt: usize, phys: usize) -> Result<(), KernelError> {
+    let root = ROOT_TABLE.load(Ordering::Relaxed);
+    if root == 0 {
+        return Err(MapError::NotReady.into());
+    }
+    let flags = DescriptorFlags::PRESENT as u64
+        | DescriptorFlags::AP_RW_KERN_ONLY as u64
+        | DescriptorFlags::SH_INNER as u64
+        | DescriptorFlags::AF_ACCESSED as u64
+        | DescriptorFlags::ATTR_IDX_NORM_RW as u64
+        | DescriptorFlags::UXN_XN as u64;
+    // SAFETY: Pencere yalnızca `vmalloc` tarafından yönetilir; tablo yazımı
+    // yürüyücüye görünür kılınır.
     unsafe {
-        enable_mmu();
+        map_page(root, virt, phys, flags)?;
+        asm!("dsb ishst", "isb");
+    }
+    Ok(())
+}
+
+/// `map_kernel_page` eşlemesini kaldırır ve fiziksel adresini döndürür.
+pub unsafe fn unmap_kernel_page(virt: usize) -> Option<usize> {
+    match ROOT_TABLE.load(Ordering::Relaxed) {
+        0 => None,
+        root => unmap_page(root, virt).ok(),
     }
-}
\ No newline at end of file
+}
+
+// -----------------------------------------------------------------------------
+// SAYFA TABLOSU DENETİMİ (vm dump)
+// -----------------------------------------------------------------------------
+
+/// Tanımlayıcıdaki çıkış adresi alanı [47:12].
+const DESC_ADDR_MASK: u64 = 0x0000_FFFF_FFFF_F000;
+/// AP[2:1]: bit 6 = EL0 erişimi, bit 7 = salt okunur.
+const DESC_AP_EL0: u64 = 1 << 6;
+const DESC_AP_RO: u64 = 1 << 7;
+/// Ayrıcalıklı (EL1) ve kullanıcı (EL0) yürütme yasağı.
+const DESC_PXN: u64 = 1 << 53;
+const DESC_UXN: u64 = 1 << 54;
+
+/// Bir tablo seviyesini özyinelemeli olarak dolaşır (seviye 0: 512G ... seviye 3: 4K).
+unsafe fn walk_level(table_addr: usize, level: usize, base: usize, visit: &mut dyn FnMut(Mapping)) {
+    let table = &*(table_addr as *const PageTable);
+    let shift = 12 + 9 * (3 - level);
+
+    for (index, entry) in table.entries.iter().enumerate() {
+        let raw = entry.0;
+        if raw & DescriptorFlags::PRESENT as u64 == 0 {
+            continue;
+        }
+        let vaddr = base | (index << shift);
+        let is_table = raw & DescriptorFlags::TABLE_OR_BLOCK as u64 != 0;
+
+        // Seviye 0-2'de bit 1 = tablo; seviye 3'te bit 1 = sayfa. Seviye 0'da blok yoktur.
+        if level < 3 && is_table {
+            walk_level((raw & DESC_ADDR_MASK) as usize, level + 1, vaddr, visit);
+            continue;
+        }
+        if level == 0 || (level == 3 && !is_table) {
+            continue;
+        }
+
+        let user = raw & DESC_AP_EL0 != 0;
+        let exec = if user { raw & DESC_UXN == 0 } else { raw & DESC_PXN == 0 };
+        // MAIR_EL1 düzeni `enable_mmu` ile aynıdır: indeks 0 normal WB, indeks 1 aygıt.
+        let cache = match (raw >> 2) & 0x7 {
+            0 => CacheAttr::WriteBack,
+            1 => CacheAttr::Device,
+            _ => CacheAttr::Uncached,
+        };
+
+        let size = 1usize << shift;
+        visit(Mapping {
+            vaddr,
+            paddr: (raw & DESC_ADDR_MASK) as usize & !(size - 1),
+            size,
+            perms: Perms { read: true, write: raw & DESC_AP_RO == 0, exec, user },
+            cache,
+        });
+    }
+}
+
+/// TTBR0_EL1 (kullanıcı) ve TTBR1_EL1 (çekirdek) tablolarındaki tüm yaprak
+/// eşlemeleri artan sanal adres sırasıyla bildirir.
+pub fn walk_mappings(visit: &mut dyn FnMut(Mapping)) {
+    let ttbr0: u64;
+    let ttbr1: u64;
+    unsafe {
+        asm!("mrs {}, ttbr0_el1", out(reg) ttbr0, options(nomem, nostack));
+        asm!("mrs {}, ttbr1_el1", out(reg) ttbr1, options(nomem, nostack));
+
+        // TTBRx[47:1] tablo adresi; üst 16 bit ASID'dir. Tablolar birebir eşlidir.
+        if ttbr0 & DESC_ADDR_MASK != 0 {
+            walk_level((ttbr0 & DESC_ADDR_MASK) as usize, 0, 0, visit);
+        }
+        if ttbr1 & DESC_ADDR_MASK != 0 {
+            walk_level((ttbr1 & DESC_ADDR_MASK) as usize, 0, KERNEL_START_VADDR & !((1 << 48) - 1), visit);
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// KULLANICI SAYFALARI (mmap)
+// -----------------------------------------------------------------------------
+
+/// Kullanıcı sayfası eşlemesi bu portta henüz yok; `mmap` dosya eşlemeleri
+/// `PlatformSpecificError` ile reddedilir.
+///
+/// # Safety
+/// Diğer portlarla aynı sözleşme (bkz. `rv64i::mmu::map_user_page`).
+pub unsafe fn map_user_page(_virtual_addr: usize, _physical_addr: usize, _write: bool, _exec: bool) -> Result<(), crate::platformgeneric::KernelError> {
+    Err(crate::platformgeneric::KernelError::PlatformSpecificError(0))
+}
+
+/// Kullanıcı sayfası eşlenemediğinden etkisizdir.
+pub unsafe fn unmap_user_page(_virtual_addr: usize) {}
@@ -83,9 +83,12 @@ pub fn system_shutdown() -> ! {
 /// Kesme veya olay gelene kadar işlemciyi duraklatır.
 pub fn system_idle() {
     unsafe {
-        // İşlemciyi bir olay gelene kadar beklet (WFE)
-        io::wfe();
-        // Alternatif: Kesme gelene kadar beklet (WFI)
-        // io::wfi(); 
+        if crate::quirks::has(crate::quirks::NO_WFE) {
+            // Errata: WFE'den uyanma güvenilir değil, kesme gelene kadar beklet (WFI)
+            io::wfi();
+        } else {
+            // İşlemciyi bir olay gelene kadar beklet (WFE)
+            io::wfe();
+        }
     }
 }
\ No newline at end of file
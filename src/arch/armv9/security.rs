@@ -2,6 +2,8 @@
 // ARMv9 (aarch64) mimarisine özgü temel güvenlik işlevleri.
 
 use core::arch::asm;
+use core::sync::atomic::{AtomicBool, Ordering};
+use crate::platformgeneric::KernelError;
 use crate::serial_println;
 // arch/armv9/platformmod.rs dosyasından temel bariyer işlevlerini içe aktarır.
 use super::platformmod::io; 
@@ -78,7 +80,9 @@ pub fn configure_security_features() {
     serial_println!("[SECURITY] Temel Kontrol Yazmaçları Yapılandırılıyor...");
     
     const SCTLR_EL1: u64 = 0x8; // Temsili bir System Register, gerçek SCTLR_EL1 numarası
-    const SCTLR_EL1_RES1: u64 = (1 << 31) | (1 << 30) | (1 << 29) | (1 << 28) | (1 << 25) | (1 << 21) | (1 << 19) | (1 << 18) | (1 << 17) | (1 << 14) | (1 << 11) | (1 << 7) | (1 << 6) | (1 << 5) | (1 << 4);
+    // Not: Bit 31/30 ARMv8.3+ ile EnIA/EnIB olmuştur; anahtarlar yüklenmeden
+    // ayarlanmamaları için `enable_pointer_auth` tarafından yönetilir.
+    const SCTLR_EL1_RES1: u64 = (1 << 29) | (1 << 28) | (1 << 25) | (1 << 21) | (1 << 19) | (1 << 18) | (1 << 17) | (1 << 14) | (1 << 11) | (1 << 7) | (1 << 6) | (1 << 5) | (1 << 4);
     
     // Genellikle MMU kodunda yapılır, burada sadece temsili bir güvenlik kontrolü.
     // Sistemin doğru çalışması için rezerve edilmiş bitlerin ayarlanması önemlidir (RES1).
@@ -102,6 +106,293 @@ pub fn configure_security_features() {
 }
 
 
+// -----------------------------------------------------------------------------
+// Özellik Algılama (ID Yazmaçları)
+// -----------------------------------------------------------------------------
+
+/// ARMv8.1+ / ARMv9 güvenlik özelliklerinin donanım desteği.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SecurityFeatures {
+    /// Privileged Access Never (ID_AA64MMFR1_EL1.PAN).
+    pub pan: bool,
+    /// Branch Target Identification (ID_AA64PFR1_EL1.BT).
+    pub bti: bool,
+    /// Pointer Authentication (ID_AA64ISAR1_EL1 APA/API, ID_AA64ISAR2_EL1 APA3).
+    pub pauth: bool,
+}
+
+/// ID yazmaçlarından özellik desteğini okur.
+pub fn detect_features() -> SecurityFeatures {
+    let (mmfr1, pfr1, isar1, isar2): (u64, u64, u64, u64);
+    unsafe {
+        asm!("mrs {0}, id_aa64mmfr1_el1", out(reg) mmfr1, options(nomem, nostack));
+        asm!("mrs {0}, id_aa64pfr1_el1", out(reg) pfr1, options(nomem, nostack));
+        asm!("mrs {0}, id_aa64isar1_el1", out(reg) isar1, options(nomem, nostack));
+        // ID_AA64ISAR2_EL1 (S3_0_C0_C6_2) eski derleyicilerde adıyla tanınmaz.
+        asm!("mrs {0}, s3_0_c0_c6_2", out(reg) isar2, options(nomem, nostack));
+    }
+
+    let field = |reg: u64, shift: u32| (reg >> shift) & 0xF;
+
+    SecurityFeatures {
+        pan: field(mmfr1, 20) != 0,
+        bti: field(pfr1, 0) != 0,
+        // APA (QARMA5) [7:4], API (IMPDEF) [11:8], APA3 (QARMA3) [15:12]
+        pauth: field(isar1, 4) != 0 || field(isar1, 8) != 0 || field(isar2, 12) != 0,
+    }
+}
+
+// -----------------------------------------------------------------------------
+// PAN, BTI ve Pointer Authentication
+// -----------------------------------------------------------------------------
+
+/// SCTLR_EL1.EnDB: DB anahtarı ile veri işaretçisi doğrulaması.
+const SCTLR_EL1_ENDB: u64 = 1 << 13;
+/// SCTLR_EL1.SPAN: 0 iken EL1'e her istisna girişinde PSTATE.PAN otomatik ayarlanır.
+const SCTLR_EL1_SPAN: u64 = 1 << 23;
+/// SCTLR_EL1.EnDA: DA anahtarı ile veri işaretçisi doğrulaması.
+const SCTLR_EL1_ENDA: u64 = 1 << 27;
+/// SCTLR_EL1.EnIB: IB anahtarı ile komut işaretçisi doğrulaması (PACIBSP/AUTIBSP).
+const SCTLR_EL1_ENIB: u64 = 1 << 30;
+/// SCTLR_EL1.EnIA: IA anahtarı ile komut işaretçisi doğrulaması (PACIASP/AUTIASP).
+const SCTLR_EL1_ENIA: u64 = 1 << 31;
+/// SCTLR_EL1.BT0: EL0 korumalı sayfalarında BTI denetimi.
+const SCTLR_EL1_BT0: u64 = 1 << 35;
+/// SCTLR_EL1.BT1: EL1 korumalı sayfalarında BTI denetimi.
+const SCTLR_EL1_BT1: u64 = 1 << 36;
+
+/// PAN etkinleştirildi mi? `copy_*_user` yalnızca bu durumda PSTATE.PAN'a dokunur;
+/// PAN'sız bir çekirdekte `msr pan, #imm` tanımsız talimattır.
+static PAN_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// PSTATE.PAN = 1 (`msr pan, #1`). Eski assembler'lar için ham kodlama kullanılır.
+#[inline(always)]
+unsafe fn set_pan() {
+    asm!(".inst 0xd500419f", options(nomem, nostack));
+}
+
+/// PSTATE.PAN = 0 (`msr pan, #0`).
+#[inline(always)]
+unsafe fn clear_pan() {
+    asm!(".inst 0xd500409f", options(nomem, nostack));
+}
+
+/// Privileged Access Never'ı etkinleştirir: çekirdek (EL1), EL0'dan erişilebilen
+/// sayfalara yanlışlıkla erişirse izin hatası alır.
+///
+/// SCTLR_EL1.SPAN temizlenir; böylece kullanıcı modundan gelen her istisnada
+/// PAN donanım tarafından yeniden ayarlanır.
+unsafe fn enable_pan() {
+    let sctlr = io::read_sctlr_el1() & !SCTLR_EL1_SPAN;
+    io::write_sctlr_el1(sctlr);
+    io::isb();
+    set_pan();
+    PAN_ENABLED.store(true, Ordering::Release);
+}
+
+/// Pointer Authentication anahtarlarını RNDR ile üretir ve IA/IB/DA/DB doğrulamasını açar.
+///
+/// # Güvenlik Notu
+/// Bu fonksiyonun kendisi PACIASP/PACIBSP ile derlenmemiş olmalıdır (anahtar
+/// değiştiği için dönüş adresi doğrulanamaz). Çekirdek `-Z branch-protection`
+/// ile derlendiğinde bu çağrı, ilk PAC'li fonksiyon girişinden önce yapılmalıdır.
+unsafe fn enable_pointer_auth() {
+    // APIAKey/APIBKey/APDAKey/APDBKey {Lo, Hi} yazmaçları (S3_0_C2_C1_0 .. S3_0_C2_C2_3)
+    asm!(
+        "msr s3_0_c2_c1_0, {0}", // APIAKeyLo_EL1
+        "msr s3_0_c2_c1_1, {1}", // APIAKeyHi_EL1
+        "msr s3_0_c2_c1_2, {2}", // APIBKeyLo_EL1
+        "msr s3_0_c2_c1_3, {3}", // APIBKeyHi_EL1
+        in(reg) get_hardware_random_u64(),
+        in(reg) get_hardware_random_u64(),
+        in(reg) get_hardware_random_u64(),
+        in(reg) get_hardware_random_u64(),
+        options(nomem, nostack)
+    );
+    asm!(
+        "msr s3_0_c2_c2_0, {0}", // APDAKeyLo_EL1
+        "msr s3_0_c2_c2_1, {1}", // APDAKeyHi_EL1
+        "msr s3_0_c2_c2_2, {2}", // APDBKeyLo_EL1
+        "msr s3_0_c2_c2_3, {3}", // APDBKeyHi_EL1
+        in(reg) get_hardware_random_u64(),
+        in(reg) get_hardware_random_u64(),
+        in(reg) get_hardware_random_u64(),
+        in(reg) get_hardware_random_u64(),
+        options(nomem, nostack)
+    );
+    io::isb();
+
+    let sctlr = io::read_sctlr_el1() | SCTLR_EL1_ENIA | SCTLR_EL1_ENIB | SCTLR_EL1_ENDA | SCTLR_EL1_ENDB;
+    io::write_sctlr_el1(sctlr);
+    io::isb();
+}
+
+/// Branch Target Identification denetimini EL0 ve EL1 için açar. Denetim yalnızca
+/// sayfa tablosunda GP (Guarded Page) biti ayarlı kod sayfalarında uygulanır.
+unsafe fn enable_bti() {
+    let sctlr = io::read_sctlr_el1() | SCTLR_EL1_BT0 | SCTLR_EL1_BT1;
+    io::write_sctlr_el1(sctlr);
+    io::isb();
+}
+
+/// Donanımın desteklediği PAN/BTI/PAuth korumalarını etkinleştirir.
+pub fn enable_hardening(features: SecurityFeatures) {
+    unsafe {
+        if features.pan {
+            enable_pan();
+        }
+        if features.pauth {
+            enable_pointer_auth();
+        }
+        if features.bti {
+            enable_bti();
+        }
+    }
+    serial_println!(
+        "[SECURITY] PAN: {} | PAuth: {} | BTI: {}",
+        features.pan, features.pauth, features.bti
+    );
+}
+
+// -----------------------------------------------------------------------------
+// Kullanıcı Belleği Erişimi
+// -----------------------------------------------------------------------------
+
+/// Kullanıcı alanının üst sınırı (TTBR0, T0SZ=16 -> 48 bit).
+pub const USER_SPACE_END: usize = 1 << 48;
+
+/// `[addr, addr + len)` aralığının tamamen kullanıcı alanında olup olmadığını denetler.
+#[inline]
+fn user_range_ok(addr: usize, len: usize) -> bool {
+    match addr.checked_add(len) {
+        Some(end) => end <= USER_SPACE_END,
+        None => false,
+    }
+}
+
+/// PAN'ı geçici olarak kaldırarak `f`'yi çalıştırır.
+#[inline(always)]
+unsafe fn with_user_access<R>(f: impl FnOnce() -> R) -> R {
+    let pan = PAN_ENABLED.load(Ordering::Acquire);
+    if pan {
+        clear_pan();
+    }
+    let result = f();
+    if pan {
+        set_pan();
+    }
+    result
+}
+
+/// Kullanıcı alanındaki `src` adresinden çekirdek arabelleğine `dst.len()` bayt
+/// kopyalar. Kopya düzeltme tablolu yoklamalarla yapılır; eşlenmemiş sayfa
+/// panik yerine hata döner.
+///
+/// # Hatalar
+/// * `InvalidArgument`: Aralık kullanıcı alanının dışına taşıyor veya
+///   kopyalama sırasında okunamayan bir bayta rastlandı.
+pub fn copy_from_user(dst: &mut [u8], src: usize) -> Result<(), KernelError> {
+    if !user_range_ok(src, dst.len()) {
+        return Err(KernelError::InvalidArgument);
+    }
+    // SAFETY: PAN yalnızca kopya süresince kalkar; istisna girişi onu yeniden kurar.
+    unsafe { with_user_access(|| crate::fixup::probe_kernel_read(src, dst)) }
+}
+
+/// Çekirdek arabelleği `src`'yi kullanıcı alanındaki `dst` adresine kopyalar.
+///
+/// # Hatalar
+/// Bkz. [`copy_from_user`]; hata yazma sırasındaysa önceki baytlar yazılmış olabilir.
+pub fn copy_to_user(dst: usize, src: &[u8]) -> Result<(), KernelError> {
+    if !user_range_ok(dst, src.len()) {
+        return Err(KernelError::InvalidArgument);
+    }
+    // SAFETY: Bkz. `copy_from_user`.
+    unsafe { with_user_access(|| crate::fixup::probe_kernel_write(dst, src)) }
+}
+
+// -----------------------------------------------------------------------------
+// Donanım Şifreleme Hızlandırması (ARMv8 Crypto Extensions)
+// -----------------------------------------------------------------------------
+
+/// CPACR_EL1.FPEN == 0b11: EL1/EL0'da FP/SIMD erişimi tuzaklanmaz.
+const CPACR_EL1_FPEN: u64 = 0b11 << 20;
+
+/// AESE/AESMC ile blok şifreleyici. Görev geçişleri SIMD durumunu
+/// kaydetmediği için kullanılan q0/q1 yazmaçları IRQ'lar maskeliyken
+/// saklanır ve geri yüklenir.
+struct ArmAes;
+
+impl crate::crypto::crypto::AesAccel for ArmAes {
+    fn name(&self) -> &'static str {
+        "armv8-ce"
+    }
+
+    fn encrypt_block(&self, round_keys: &[[u8; 16]; 11], block: &mut [u8; 16]) {
+        let mut save = [0u8; 32];
+        // SAFETY: Yalnızca ID_AA64ISAR0_EL1.AES ve CPACR_EL1.FPEN doğrulandıktan sonra kaydedilir.
+        unsafe {
+            asm!(
+                ".arch_extension aes",
+                "mrs {daif}, daif",
+                "msr daifset, #2",
+                "stp q0, q1, [{save}]",
+                "ld1 {{v0.16b}}, [{block}]",
+                "ld1 {{v1.16b}}, [{rk}], #16",
+                "aese v0.16b, v1.16b",
+                "aesmc v0.16b, v0.16b",
+                "ld1 {{v1.16b}}, [{rk}], #16",
+                "aese v0.16b, v1.16b",
+                "aesmc v0.16b, v0.16b",
+                "ld1 {{v1.16b}}, [{rk}], #16",
+                "aese v0.16b, v1.16b",
+                "aesmc v0.16b, v0.16b",
+                "ld1 {{v1.16b}}, [{rk}], #16",
+                "aese v0.16b, v1.16b",
+                "aesmc v0.16b, v0.16b",
+                "ld1 {{v1.16b}}, [{rk}], #16",
+                "aese v0.16b, v1.16b",
+                "aesmc v0.16b, v0.16b",
+                "ld1 {{v1.16b}}, [{rk}], #16",
+                "aese v0.16b, v1.16b",
+                "aesmc v0.16b, v0.16b",
+                "ld1 {{v1.16b}}, [{rk}], #16",
+                "aese v0.16b, v1.16b",
+                "aesmc v0.16b, v0.16b",
+                "ld1 {{v1.16b}}, [{rk}], #16",
+                "aese v0.16b, v1.16b",
+                "aesmc v0.16b, v0.16b",
+                "ld1 {{v1.16b}}, [{rk}], #16",
+                "aese v0.16b, v1.16b",
+                "aesmc v0.16b, v0.16b",
+                "ld1 {{v1.16b}}, [{rk}], #16",
+                "aese v0.16b, v1.16b",
+                "ld1 {{v1.16b}}, [{rk}]",
+                "eor v0.16b, v0.16b, v1.16b",
+                "st1 {{v0.16b}}, [{block}]",
+                "ldp q0, q1, [{save}]",
+                "msr daif, {daif}",
+                daif = out(reg) _,
+                save = in(reg) save.as_mut_ptr(),
+                block = in(reg) block.as_mut_ptr(),
+                rk = inout(reg) round_keys.as_ptr() => _,
+                options(nostack),
+            );
+        }
+    }
+}
+
+static ARM_AES: ArmAes = ArmAes;
+
+/// İşlemci destekliyorsa AES talimatlarını şifreleme modülüne kaydeder.
+pub fn register_crypto_accel() {
+    let cpacr: u64;
+    unsafe { asm!("mrs {0}, cpacr_el1", out(reg) cpacr, options(nomem, nostack)) };
+    if crate::cpu::features().aes && cpacr & CPACR_EL1_FPEN == CPACR_EL1_FPEN {
+        crate::crypto::crypto::register_aes_accel(&ARM_AES);
+    }
+}
+
 // -----------------------------------------------------------------------------
 // Temel Güvenlik Başlatma
 // -----------------------------------------------------------------------------
@@ -113,12 +404,17 @@ pub fn security_init() {
     // 1. Temel sistem kontrol yazmaçlarını yapılandır
     configure_security_features();
 
-    // 2. İlk rastgelelik testi
-    let rand_val = get_hardware_random_u64();
-    serial_println!("[SECURITY] RNDR Testi: Değer: {:#x}", rand_val);
+    // 2. İlk rastgelelik testi (RNDR yoksa talimat tanımsızdır)
+    if crate::cpu::features().rng {
+        let rand_val = get_hardware_random_u64();
+        serial_println!("[SECURITY] RNDR Testi: Değer: {:#x}", rand_val);
+    }
     
-    // 3. (Gelişmiş Kodda): MTE (Memory Tagging Extension) veya PAuth (Pointer Authentication)
-    // gibi ARMv9 özel güvenlik özelliklerinin yapılandırılması buraya eklenirdi.
+    // 3. PAN, Pointer Authentication ve BTI (donanım destekliyorsa)
+    enable_hardening(detect_features());
+
+    // 4. Donanım şifreleme hızlandırması (ARMv8 Crypto Extensions)
+    register_crypto_accel();
     
     serial_println!("[SECURITY] Temel Güvenlik Yapılandırması Tamamlandı.");
 }
\ No newline at end of file
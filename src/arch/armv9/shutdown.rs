@@ -10,11 +10,16 @@ use super::platformmod::io;
 // PSCI (Power State Coordination Interface) Sabitleri
 // -----------------------------------------------------------------------------
 // PSCI fonksiyonlarını SMC (Secure Monitor Call) veya HVC (Hypervisor Call) ile çağırırız.
-// Genellikle Kernel (EL1) SMC kullanır.
+// Çıplak donanımda Kernel (EL1) SMC kullanır; bir hypervisor altında (KVM/QEMU) HVC kullanılır.
 
 // SMC çağrı numaraları (Hizmet Kimlikleri)
 const PSCI_FN_SYSTEM_OFF: u64 = 0x84000008; // Kapatma (Shutdown)
 const PSCI_FN_SYSTEM_RESET: u64 = 0x84000009; // Yeniden Başlatma (Reboot)
+const PSCI_FN_PSCI_FEATURES: u64 = 0x8400000A; // Fonksiyon desteği sorgusu
+const PSCI_FN_SYSTEM_RESET2: u64 = 0xC4000012; // Türlü yeniden başlatma (PSCI 1.1, SMC64)
+// SYSTEM_RESET2 sıfırlama türleri: mimari sıcak sıfırlama ve satıcıya özgü (bit 31)
+const PSCI_RESET2_WARM: u64 = 0;
+const PSCI_RESET2_VENDOR: u64 = 1 << 31;
 const PSCI_SUCCESS: i32 = 0;
 
 // -----------------------------------------------------------------------------
@@ -46,7 +51,7 @@ fn halt_loop() -> ! {
 // PSCI Arayüzü
 // -----------------------------------------------------------------------------
 
-/// PSCI hizmet çağrısını (SMC) gerçekleştirir.
+/// PSCI hizmet çağrısını (SMC veya hypervisor altında HVC) gerçekleştirir.
 /// PSCI hizmetleri genellikle 64-bit çağrılar kullanır.
 ///
 /// # Argümanlar
@@ -60,15 +65,26 @@ fn psci_call(function_id: u64, arg0: u64, arg1: u64, arg2: u64) -> i32 {
     
     // x0'da fonksiyon ID'si ve argümanlar x1-x3'te olmalıdır.
     unsafe {
-        asm!(
-            "smc #0", // Secure Monitor Call
-            in("x0") function_id,
-            in("x1") arg0,
-            in("x2") arg1,
-            in("x3") arg2,
-            out("x0") ret,
-            options(nomem, nostack, preserves_flags)
-        );
+        if crate::hypervisor::paravirt().hvc_conduit {
+            asm!(
+                "hvc #0", // Hypervisor Call
+                inlateout("x0") function_id => ret,
+                in("x1") arg0,
+                in("x2") arg1,
+                in("x3") arg2,
+                options(nomem, nostack, preserves_flags)
+            );
+        } else {
+            asm!(
+                "smc #0", // Secure Monitor Call
+                in("x0") function_id,
+                in("x1") arg0,
+                in("x2") arg1,
+                in("x3") arg2,
+                out("x0") ret,
+                options(nomem, nostack, preserves_flags)
+            );
+        }
     }
     ret as i32
 }
@@ -127,6 +143,31 @@ pub fn system_reboot() -> ! {
     halt_loop();
 }
 
+/// Sıcak veya soğuk yeniden başlatma ister (bkz. `reboot.rs`).
+///
+/// Sıcak istek PSCI SYSTEM_RESET2 ile yapılır; `reason` çağrının `cookie`
+/// argümanıdır. Hata kaynaklı sıfırlamalar (`failure`) satıcıya özgü türle
+/// istenir, böylece ürün yazılımı nedeni kaydedebilir. SYSTEM_RESET2 yoksa
+/// soğuk SYSTEM_RESET'e düşülür.
+pub fn system_reset(warm: bool, reason: u32, failure: bool) -> ! {
+    serial_println!("[SHUTDOWN] {} Yeniden Başlatma İsteniyor...", if warm { "Sıcak" } else { "Soğuk" });
+
+    // 1. Kesmeleri devre dışı bırak
+    unsafe {
+        io::disable_interrupts();
+    }
+
+    // 2. SYSTEM_RESET2 destekleniyorsa sıcak sıfırlama
+    if warm && psci_call(PSCI_FN_PSCI_FEATURES, PSCI_FN_SYSTEM_RESET2, 0, 0) >= 0 {
+        let reset_type = if failure { PSCI_RESET2_VENDOR } else { PSCI_RESET2_WARM };
+        let result = psci_call(PSCI_FN_SYSTEM_RESET2, reset_type, u64::from(reason), 0);
+        serial_println!("[SHUTDOWN] PSCI SYSTEM_RESET2 Hata Kodu: {}", result);
+    }
+
+    // 3. Soğuk yeniden başlatma yolları
+    system_reboot();
+}
+
 // -----------------------------------------------------------------------------
 // Kapatma İşlevleri
 // -----------------------------------------------------------------------------
@@ -2,6 +2,8 @@
 // ARMv9 (aarch64) mimarisine özgü görev (task) ve bağlam (context) yönetimi.
 
 use core::arch::asm;
+use core::mem::offset_of;
+use crate::context::ArchContext;
 use crate::serial_println;
 
 /// Görev bağlamını (task context) saklamak için kullanılan yapı.
@@ -36,106 +38,172 @@ pub struct TaskContext {
     // Komut işaretçisi (Instruction Pointer) - Yeni görevin başladığı yer
     // Bu, anahtarlama fonksiyonundan dönüş adresi olarak kullanılır.
     pc: u64, 
+
+    // Görev devam ettiğinde x0'da göreceği değer (`set_return_value`).
+    ret: u64,
 }
 
-impl TaskContext {
-    /// Yeni bir görev bağlamı oluşturur.
-    /// 
-    /// # Argümanlar
-    /// * `stack_top`: Görevin yığınının en üst adresi.
-    /// * `entry_point`: Görevin başlayacağı fonksiyonun adresi.
-    pub fn new(stack_top: u64, entry_point: u64) -> Self {
-        // Yeni bir görev başlatıldığında, anahtarlama kodu bu yapıyı yükler.
-        Self {
-            // Callee-Saved yazmaçlar sıfırlanır.
-            ..Default::default()
-            
-            // sp, yeni görevin yığınının üstü olarak ayarlanır.
-            sp: stack_top,
-            
-            // pc, görevin giriş noktası (fonksiyon adresi) olarak ayarlanır.
-            pc: entry_point,
-            
-            // x30 (LR) da aslında entry_point olarak ayarlanabilir, 
-            // böylece anahtarlama fonksiyonundan `ret` ile dönülür.
-            x30: entry_point,
-        }
-    }
+// `switch_context` ofsetleri `offset_of!` ile alır; `stp`/`ldp` çiftleri ise
+// ikinci yazmacı ilk alanın 8 bayt ötesine yazar, bu yüzden bitişiklik ayrıca
+// denetlenir.
+crate::asm_layout!(TaskContext {
+    x20: offset_of!(TaskContext, x19) + 8,
+    x22: offset_of!(TaskContext, x21) + 8,
+    x24: offset_of!(TaskContext, x23) + 8,
+    x26: offset_of!(TaskContext, x25) + 8,
+    x28: offset_of!(TaskContext, x27) + 8,
+    x30: offset_of!(TaskContext, x29) + 8,
+});
 
-    /// Bir bağlam anahtarlama işlemi sırasında yazmaç durumunu kaydetmek ve 
-    /// yeni görevden yazmaç durumunu yüklemek için kullanılan harici assembly fonksiyonu.
+impl TaskContext {
+    /// x19-x30'u, SP'yi ve dönüş noktasını `old_context`'e kaydeder,
+    /// `new_context`'ten yükler ve onun PC'sine dallanır. Eski görev yeniden
+    /// seçildiğinde `2:` etiketinden, yani bu çağrının sonundan devam eder.
     ///
     /// # Güvenlik Notu
-    /// Bu fonksiyon, C ABI'sine uymalıdır: `fn switch_context(old_context: *mut TaskContext, new_context: *const TaskContext)`.
-    /// Argümanlar x0 ve x1'e geçirilir.
+    /// İki işaretçi de geçiş boyunca geçerli olmalıdır; `new_context` ya
+    /// `ArchContext::new` ile kurulmuş ya da daha önce kaydedilmiş olmalıdır.
+    /// x19, x29 ve x30 işlenen olarak verilemez; blok onları kendisi kaydedip yükler.
     #[inline(always)]
     pub unsafe fn switch_context(old_context: *mut TaskContext, new_context: *const TaskContext) {
-        serial_println!("[TASK] Bağlam Anahtarlama: {:#x} -> {:#x}", 
-                        old_context as u64, new_context as u64);
-
         asm!(
             // --------------------- Mevcut Görevin Durumunu Kaydet ---------------------
-            // x0: old_context, x1: new_context (C ABI)
-            
-            // 1. Callee-Saved GPR'ları TaskContext'e kaydet.
-            // x19-x28 (10 adet) ve x29/x30 (2 adet) = 12 yazmaç.
-            // str x1, [base, #offset] (Store Register)
-            
-            // x19-x28'i kaydet (10 yazmaç = 80 bayt). x19 offset 0'da başlar.
-            "stp x19, x20, [x0, #0]",  
-            "stp x21, x22, [x0, #16]",
-            "stp x23, x24, [x0, #32]",
-            "stp x25, x26, [x0, #48]",
-            "stp x27, x28, [x0, #64]",
-            
-            // x29 (FP) ve x30 (LR) kaydet. x29 offset 80'de başlar.
-            "stp x29, x30, [x0, #80]",
-            
-            // 2. Mevcut SP'yi (yığın işaretçisi) TaskContext.sp alanına kaydet. (Offset 96)
-            "mov x10, sp", // sp'yi x10'a taşı
-            "str x10, [x0, #96]", 
-
-            // 3. Mevcut PC'yi (dönüş adresini) TaskContext.pc alanına kaydet. (Offset 104)
-            // Anahtarlama rutinine `blr` ile çağrıldığı varsayılarak, dönüş adresi x30 (LR)'dadır.
-            // Bu zaten x30 kaydıyla yapıldı, ancak PC'yi kaydetmek için ek bir alan (TaskContext.pc) var.
-            // Bu alana, bu fonksiyonun dönüş adresini kaydetmek için LR'ın içeriği kullanılır.
-            // NOT: Rust'tan asm'e girildiğinde LR zaten yığında olabilir. Basitleştirme için:
-            "mov x10, x30",
-            "str x10, [x0, #104]",
-            
+            "stp x19, x20, [x0, #{x19}]",
+            "stp x21, x22, [x0, #{x21}]",
+            "stp x23, x24, [x0, #{x23}]",
+            "stp x25, x26, [x0, #{x25}]",
+            "stp x27, x28, [x0, #{x27}]",
+            "stp x29, x30, [x0, #{x29}]",
+            "mov x9, sp",
+            "str x9, [x0, #{sp}]",
+            // Devam noktası: görev yeniden seçildiğinde `2:`'ye döner
+            "adr x9, 2f",
+            "str x9, [x0, #{pc}]",
+
             // --------------------- Yeni Görevin Durumunu Yükle ---------------------
-            // x1: new_context
-            
-            // 1. Yeni SP'yi yükle (Offset 96)
-            "ldr x10, [x1, #96]", 
-            "mov sp, x10", 
-            
-            // 2. Callee-Saved GPR'ları TaskContext'ten yükle
-            // x19-x28 (Offset 0)
-            "ldp x19, x20, [x1, #0]",  
-            "ldp x21, x22, [x1, #16]",
-            "ldp x23, x24, [x1, #32]",
-            "ldp x25, x26, [x1, #48]",
-            "ldp x27, x28, [x1, #64]",
-
-            // x29 (FP) ve x30 (LR) yükle (Offset 80)
-            "ldp x29, x30, [x1, #80]",
-            
-            // 3. Yeni görevin giriş noktasına zıpla (PC yüklemesi)
-            // PC'yi yüklemek için TaskContext.pc adresi okunup zıplanır.
-            // PC Offset 104
-            "ldr x10, [x1, #104]", 
-            "br x10", // Branch to Register (Görevi başlatır)
-            
-            in("x0") old_context,
-            in("x1") new_context,
-            // x2-x18 caller-saved, x19-x30 callee-saved
-            out("x10") _, // x10 geçici olarak kullanıldı
-            options(noreturn, preserves_flags)
+            "ldp x19, x20, [x1, #{x19}]",
+            "ldp x21, x22, [x1, #{x21}]",
+            "ldp x23, x24, [x1, #{x23}]",
+            "ldp x25, x26, [x1, #{x25}]",
+            "ldp x27, x28, [x1, #{x27}]",
+            "ldp x29, x30, [x1, #{x29}]",
+            "ldr x9, [x1, #{sp}]",
+            "mov sp, x9",
+            "ldr x0, [x1, #{ret}]",
+            "ldr x9, [x1, #{pc}]",
+            "br x9",
+            "2:",
+
+            x19 = const offset_of!(TaskContext, x19),
+            x21 = const offset_of!(TaskContext, x21),
+            x23 = const offset_of!(TaskContext, x23),
+            x25 = const offset_of!(TaskContext, x25),
+            x27 = const offset_of!(TaskContext, x27),
+            x29 = const offset_of!(TaskContext, x29),
+            sp = const offset_of!(TaskContext, sp),
+            pc = const offset_of!(TaskContext, pc),
+            ret = const offset_of!(TaskContext, ret),
+            inout("x0") old_context => _,
+            inout("x1") new_context => _,
+            // Devam eden görev için caller-saved yazmaçların hepsi bozulmuş sayılır
+            clobber_abi("C"),
+        );
+    }
+
+    /// Callee-saved yazmaçları, yığın işaretçisini ve devam noktasını
+    /// `context`'e kaydeder, ardından `body(data)`'yı çağırır. `body` dönerse
+    /// 0 döner; `context` daha sonra `switch_context` ile yüklenirse yürütme
+    /// `2:` etiketinden sürer ve dönüş değeri bağlamın `ret` alanı olur (bkz.
+    /// `fixup::catch`).
+    ///
+    /// # Güvenlik Notu
+    /// `context` geri dönüşe kadar geçerli kalmalıdır; bağlam yalnızca `body`
+    /// henüz dönmemişken yüklenebilir.
+    #[inline(always)]
+    pub unsafe fn catch_call(context: *mut TaskContext, body: extern "C" fn(*mut u8), data: *mut u8) -> usize {
+        let resumed: usize;
+        asm!(
+            // --------------------- Geri Dönüş Noktasını Kaydet ---------------------
+            "stp x19, x20, [x0, #{x19}]",
+            "stp x21, x22, [x0, #{x21}]",
+            "stp x23, x24, [x0, #{x23}]",
+            "stp x25, x26, [x0, #{x25}]",
+            "stp x27, x28, [x0, #{x27}]",
+            "stp x29, x30, [x0, #{x29}]",
+            "mov x9, sp",
+            "str x9, [x0, #{sp}]",
+            // Devam noktası: bağlam yüklenirse `2:`'ye döner
+            "adr x9, 2f",
+            "str x9, [x0, #{pc}]",
+
+            // --------------------- Gövdeyi Çağır ---------------------
+            "mov x0, x2",
+            "blr x1",
+            "mov x0, xzr",
+            "2:",
+
+            x19 = const offset_of!(TaskContext, x19),
+            x21 = const offset_of!(TaskContext, x21),
+            x23 = const offset_of!(TaskContext, x23),
+            x25 = const offset_of!(TaskContext, x25),
+            x27 = const offset_of!(TaskContext, x27),
+            x29 = const offset_of!(TaskContext, x29),
+            sp = const offset_of!(TaskContext, sp),
+            pc = const offset_of!(TaskContext, pc),
+            inout("x0") context as usize => resumed,
+            inout("x1") body => _,
+            inout("x2") data => _,
+            clobber_abi("C"),
         );
+        resumed
     }
 }
 
+impl ArchContext for TaskContext {
+    /// İlk geçiş `task_start`'a dallanır; o da x19/x20'deki işlev ve
+    /// argümanla `task_entry`'yi çağırır.
+    fn new(entry: usize, arg: usize, stack_top: usize) -> Self {
+        Self {
+            x19: entry as u64,
+            x20: arg as u64,
+            // AAPCS64: SP her zaman 16 bayt hizalıdır
+            sp: (stack_top & !0xF) as u64,
+            pc: task_start as usize as u64,
+            ..Default::default()
+        }
+    }
+
+    unsafe fn switch(&mut self, next: &Self) {
+        Self::switch_context(self, next);
+    }
+
+    unsafe fn catch(&mut self, body: extern "C" fn(*mut u8), data: *mut u8) -> usize {
+        Self::catch_call(self, body, data)
+    }
+
+    fn set_return_value(&mut self, value: usize) {
+        self.ret = value as u64;
+    }
+}
+
+extern "C" {
+    /// Yeni görevin ilk komutu (aşağıdaki `global_asm!`).
+    fn task_start();
+}
+
+core::arch::global_asm!(
+    ".global task_start",
+    "task_start:",
+    "mov x0, x19",
+    "mov x1, x20",
+    // Yığın geri izleme burada dursun
+    "mov x29, xzr",
+    "mov x30, xzr",
+    "bl {entry}",
+    "brk #0",
+    entry = sym task_entry,
+);
+
 
 // -----------------------------------------------------------------------------
 // Görev Başlatma (Task Entry)
@@ -148,6 +216,7 @@ impl TaskContext {
 /// * `func`: Görevin gerçek giriş noktası (başlatılacak fonksiyonun adresi, x0'da olmalı).
 /// * `arg`: Göreve geçirilen u64 formatında argüman (x1'de olmalı).
 extern "C" fn task_entry(func: u64, arg: u64) -> ! {
+    crate::context::task_started();
     serial_println!("[TASK] Yeni Görev Başlatılıyor. Argüman: {:#x}", arg);
 
     // Fonksiyon işaretçisini (u64) gerçek fonksiyona dönüştür.
@@ -159,15 +228,10 @@ extern "C" fn task_entry(func: u64, arg: u64) -> ! {
     // Gerçek görev fonksiyonunu çağır (arg, x1'de beklenir, bu da arg'a karşılık gelir)
     entry_func(arg);
 
-    // Görev tamamlandı. Normalde bu noktaya gelinmemelidir.
-    serial_println!("[TASK] Görev Tamamlandı! İşlemci durduruluyor.");
-    
-    // Görev tamamlandığında, çekirdek durdurulmalıdır.
-    loop {
-        unsafe {
-            io::wfi(); // Wait For Interrupt
-        }
-    }
+    // Görev işlevi döndü: görev sonlandırılır ve işlemci sıradaki göreve
+    // (en azından boşta görevine) bırakılır
+    serial_println!("[TASK] Görev Tamamlandı.");
+    crate::sched::exit_current()
 }
 
 
@@ -183,4 +247,28 @@ pub fn initialize_tasking() {
     let entry_point_addr = task_entry as *const (); 
     
     serial_println!("[TASK] Task Entry Adresi: {:#x}", entry_point_addr as u64);
-}
\ No newline at end of file
+
+    // Zamanlayıcı bundan sonra görevler arasında gerçekten geçer (bkz. `context`)
+    crate::context::enable();
+}
+
+// -----------------------------------------------------------------------------
+// İŞLEMCİ BAŞINA ALAN TABANI
+// -----------------------------------------------------------------------------
+
+/// Bu işlemcinin işlemci başına alanının (`percpu`) tabanını yazar (`TPIDR_EL1`).
+///
+/// # Safety
+/// `base` bu işlemciye ayrılmış, `.percpu` şablonunun kopyası olan alanı
+/// göstermelidir.
+pub unsafe fn set_cpu_local_base(base: usize) {
+    asm!("msr tpidr_el1, {}", in(reg) base, options(nostack, preserves_flags));
+}
+
+/// Bu işlemcinin işlemci başına alanının tabanı (henüz yazılmadıysa 0).
+pub fn cpu_local_base() -> usize {
+    let base: usize;
+    // SAFETY: TPIDR_EL1 yalnızca çekirdek tarafından kullanılır.
+    unsafe { asm!("mrs {}, tpidr_el1", out(reg) base, options(nostack, preserves_flags, nomem)) };
+    base
+}
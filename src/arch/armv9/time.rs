@@ -35,6 +35,12 @@ pub fn read_cntpct() -> Cycles {
     Cycles(cycles)
 }
 
+/// Serbest çalışan sayacın anlık değeri (bkz. `crate::clock`).
+#[inline(always)]
+pub fn read_cycles() -> Cycles {
+    read_cntpct()
+}
+
 /// CNTFID_EL0 (Counter Frequency) yazmacını okur ve sayacın frekansını (Hz) döndürür.
 /// Bu, saniye başına sayım sayısıdır.
 #[inline(always)]
@@ -116,6 +122,23 @@ pub fn initialize_time_system() {
     // somut zaman değerleri hesaplanabilir.
 }
 
+// -----------------------------------------------------------------------------
+// Zamanlayıcı Karşılaştırıcısı (CNTP_CVAL_EL0)
+// -----------------------------------------------------------------------------
+
+/// EL1 fiziksel zamanlayıcısını CNTPCT_EL0 `deadline` değerine kurar ve
+/// etkinleştirir (PPI 30). Geçmiş bir değer kesmeyi hemen tetikler.
+pub unsafe fn set_timer_deadline(deadline: Cycles) {
+    asm!(
+        "msr cntp_cval_el0, {}",
+        "msr cntp_ctl_el0, {}", // ENABLE = 1, IMASK = 0
+        "isb",
+        in(reg) deadline.0,
+        in(reg) 1u64,
+        options(nostack, preserves_flags)
+    );
+}
+
 // Platforma özel G/Ç fonksiyonları için bir yer tutucu (main.rs veya platformmod.rs'de tamamlanmalıdır)
 mod io {
     use core::arch::asm;
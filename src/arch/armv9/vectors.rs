@@ -0,0 +1,141 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/arch/armv9/vectors.rs
+// ARMv9 istisna vektör tablosu (VBAR_EL1), `trap_vectors!` ile üretilir.
+//
+// Tablo 2 KiB hizalı, 16 yuva x 0x80 bayttır: dört grup (Current EL SP0,
+// Current EL SPx, Lower EL AArch64, Lower EL AArch32) ve her grupta Sync,
+// IRQ, FIQ, SError. Yuva taslağı x0/x1'i yığına koyar, x0'a yuva numarasını,
+// x1'e işleyiciyi yükler ve ortak koda sıçrar. Ortak kod `ExceptionContext`
+// çerçevesini (x0..x30, ELR_EL1, SPSR_EL1) tamamlar ve işleyiciyi
+// `handler(vector, &mut ExceptionContext)` olarak çağırır.
+//
+// FIQ, SError ve AArch32 girdileri artık boş değildir; hepsi
+// `generic_unhandled_vector`'a düşer ve ölümcül hata olarak raporlanır.
+//
+// Dönüşte kesilen kod EL1t ise (görev, `SP_EL0` ile) çerçeve görevin
+// yığınına kopyalanır, `SPSel = 0`'a geçilir ve `sched_preempt_from_trap`
+// orada çağrılır; kesme yığını (`SP_EL1`) boşalmış olur ve görev değişse de
+// orada çerçeve kalmaz. ELR/SPSR bundan sonra çerçeveden yazılır; `eret`
+// SPSR'daki EL1t ile yığın seçimini geri yükler.
+
+/// Çerçeve boyutu: `ExceptionContext` (33 x 8 bayt) 16 bayta yuvarlanmış.
+/// Boy ve aşağıdaki ofsetler `exception.rs`'de `asm_layout!` ile denetlenir.
+macro_rules! armv9_frame {
+    () => {
+        "272"
+    };
+}
+
+macro_rules! armv9_entry {
+    ($name:literal, $handler:literal) => {
+        concat!(
+            "sub sp, sp, #", armv9_frame!(), "\n",
+            "stp x0, x1, [sp, #0]\n",
+            "mov x0, #", $name, "_slot\n",
+            "adrp x1, ", $handler, "\n",
+            "add x1, x1, :lo12:", $handler, "\n",
+            "b ", $name, "_common\n",
+        )
+    };
+}
+
+macro_rules! armv9_common {
+    ($name:literal) => {
+        concat!(
+            $name, "_common:\n",
+            "stp x2, x3, [sp, #16]\n",
+            "stp x4, x5, [sp, #32]\n",
+            "stp x6, x7, [sp, #48]\n",
+            "stp x8, x9, [sp, #64]\n",
+            "stp x10, x11, [sp, #80]\n",
+            "stp x12, x13, [sp, #96]\n",
+            "stp x14, x15, [sp, #112]\n",
+            "stp x16, x17, [sp, #128]\n",
+            "stp x18, x19, [sp, #144]\n",
+            "stp x20, x21, [sp, #160]\n",
+            "stp x22, x23, [sp, #176]\n",
+            "stp x24, x25, [sp, #192]\n",
+            "stp x26, x27, [sp, #208]\n",
+            "stp x28, x29, [sp, #224]\n",
+            "str x30, [sp, #240]\n",
+            "mrs x2, elr_el1\n",
+            "mrs x3, spsr_el1\n",
+            "stp x2, x3, [sp, #248]\n",
+            // handler(x0 = yuva, x1 = &mut ExceptionContext)
+            "mov x2, x1\n",
+            "mov x1, sp\n",
+            "blr x2\n",
+            // Bekleyen yeniden zamanlama: yalnızca EL1t'ye (görev) dönüşte
+            "ldr x3, [sp, #256]\n",
+            "and x3, x3, #0xf\n",
+            "cmp x3, #4\n",
+            "b.ne 2f\n",
+            "mrs x2, sp_el0\n",
+            "sub x2, x2, #", armv9_frame!(), "\n",
+            "mov x3, sp\n",
+            "mov x4, x2\n",
+            "mov x5, #(", armv9_frame!(), " / 16)\n",
+            "1:\n",
+            "ldp x6, x7, [x3], #16\n",
+            "stp x6, x7, [x4], #16\n",
+            "subs x5, x5, #1\n",
+            "b.ne 1b\n",
+            "add sp, sp, #", armv9_frame!(), "\n",
+            "msr spsel, #0\n",
+            "mov sp, x2\n",
+            "bl sched_preempt_from_trap\n",
+            "2:\n",
+            // İşleyici ELR/SPSR'ı değiştirebilir (syscall, hata ayıklayıcı, düzeltme)
+            "ldp x2, x3, [sp, #248]\n",
+            "msr elr_el1, x2\n",
+            "msr spsr_el1, x3\n",
+            "ldp x0, x1, [sp, #0]\n",
+            "ldp x2, x3, [sp, #16]\n",
+            "ldp x4, x5, [sp, #32]\n",
+            "ldp x6, x7, [sp, #48]\n",
+            "ldp x8, x9, [sp, #64]\n",
+            "ldp x10, x11, [sp, #80]\n",
+            "ldp x12, x13, [sp, #96]\n",
+            "ldp x14, x15, [sp, #112]\n",
+            "ldp x16, x17, [sp, #128]\n",
+            "ldp x18, x19, [sp, #144]\n",
+            "ldp x20, x21, [sp, #160]\n",
+            "ldp x22, x23, [sp, #176]\n",
+            "ldp x24, x25, [sp, #192]\n",
+            "ldp x26, x27, [sp, #208]\n",
+            "ldp x28, x29, [sp, #224]\n",
+            "ldr x30, [sp, #240]\n",
+            "add sp, sp, #", armv9_frame!(), "\n",
+            "eret\n",
+        )
+    };
+}
+
+crate::trap_vectors! {
+    name: "armv9_vectors",
+    align: 2048,
+    slot: 0x80,
+    slots: 16,
+    common: armv9_common,
+    vectors: [
+        // Current EL, SP0 (görevler SP_EL0 ile çalışır; bkz. `irqstack`)
+        0 ..= 0 => armv9_entry("generic_sync_handler"),
+        1 ..= 1 => armv9_entry("generic_irq_handler"),
+        2 ..= 3 => armv9_entry("generic_unhandled_vector"),
+        // Current EL, SPx (kesme yığını kurulmadan önce)
+        4 ..= 4 => armv9_entry("generic_sync_handler"),
+        5 ..= 5 => armv9_entry("generic_irq_handler"),
+        6 ..= 7 => armv9_entry("generic_unhandled_vector"),
+        // Lower EL, AArch64
+        8 ..= 8 => armv9_entry("generic_sync_handler"),
+        9 ..= 9 => armv9_entry("generic_irq_handler"),
+        // Lower EL FIQ/SError ve AArch32 desteklenmez
+        10 ..= 15 => armv9_entry("generic_unhandled_vector"),
+    ]
+}
+
+extern "C" {
+    /// Üretilen tablonun başı (VBAR_EL1 değeri).
+    pub fn armv9_vectors();
+}
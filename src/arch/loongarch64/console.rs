@@ -24,11 +24,9 @@ static IS_INITIALIZED: AtomicBool = AtomicBool::new(false);
 impl Uart {
     /// UART yazmacının tam MMIO adresini hesaplar.
     fn get_register_addr(offset: usize) -> usize {
-        // Güvenli toplama işlemi (overflow kontrolü)
-        UART_MMIO_ADDR.checked_add(offset).unwrap_or_else(|| 
-            // no-std ortamında panik
-            panic!("UART MMIO adresi taşması")
-        )
+        // Sabit taban ve küçük ofset taşamaz; konsol yolunda panik olmamalı
+        // (panik yolu da bu konsola yazar)
+        UART_MMIO_ADDR + offset
     }
 
     /// UART'ı temel bir konfigürasyonla başlatır (8N1, FIFO açık).
@@ -112,6 +110,24 @@ impl fmt::Write for Uart {
     }
 }
 
+// -----------------------------------------------------------------------------
+// PANİK VE İKİNCİL KONSOL
+// -----------------------------------------------------------------------------
+
+/// Panik yolunun kilitsiz yazıcısı (bkz. `crate::panic`). UART zaten
+/// yoklamalı yazar.
+pub fn panic_write_byte(byte: u8) {
+    Uart::write_byte(byte);
+}
+
+/// Kesme güdümlü gönderim olmadığından etkisizdir.
+pub fn panic_quiesce() {}
+
+/// İkincil UART yok; `serial1` hedefi kaydedilmez.
+pub fn init_aux_serial() -> Option<fn(fmt::Arguments)> {
+    None
+}
+
 // -----------------------------------------------------------------------------
 // GENEL KONSOL ÇIKTI FONKSİYONLARI (Makrolar)
 // -----------------------------------------------------------------------------
@@ -126,8 +142,11 @@ macro_rules! serial_print {
 
         // Not: Gerçek bir çekirdekte, bu kısım yarış koşullarını önlemek için 
         // Spinlock ile korunmalı ya da Kesmeler devre dışı bırakılmalıdır.
-        unsafe {
-             let _ = write!($crate::arch::loongarch64::console::Uart, $($arg)*);
+        // Yönlendirilmiş görev çıktısı (örn. uzak kabuk) seri porta yazılmaz
+        if !$crate::shell::redirect(format_args!($($arg)*)) {
+            unsafe {
+                 let _ = write!($crate::arch::loongarch64::console::Uart, $($arg)*);
+            }
         }
     });
 }
@@ -0,0 +1,40 @@
+// src/arch/loongarch64/cpu.rs
+// LoongArch 64 işlemci özelliği algılama: CPUCFG kelimeleri.
+
+#![allow(dead_code)]
+
+use core::arch::asm;
+use crate::cpu::CpuFeatures;
+
+/// CPUCFG.1[25] - CRC talimatları.
+const CFG1_CRC: u32 = 1 << 25;
+/// CPUCFG.2[0] - FP, [6] - LSX, [9] - şifreleme, [22] - LAM (AM* atomikleri).
+const CFG2_FP: u32 = 1 << 0;
+const CFG2_LSX: u32 = 1 << 6;
+const CFG2_CRYPTO: u32 = 1 << 9;
+const CFG2_LAM: u32 = 1 << 22;
+
+/// CPUCFG yazmacını okur. Tanımsız kelimeler 0 döndürür.
+fn cpucfg(word: u32) -> u32 {
+    let value: u32;
+    unsafe { asm!("cpucfg {}, {}", out(reg) value, in(reg) word, options(nomem, nostack)) };
+    value
+}
+
+pub fn detect() -> CpuFeatures {
+    let (cfg1, cfg2) = (cpucfg(1), cpucfg(2));
+    CpuFeatures {
+        // CPUCFG.0: PRID
+        id: cpucfg(0) as u64,
+        fpu: cfg2 & CFG2_FP != 0,
+        simd: cfg2 & CFG2_LSX != 0,
+        aes: cfg2 & CFG2_CRYPTO != 0,
+        sha2: cfg2 & CFG2_CRYPTO != 0,
+        crc32: cfg1 & CFG1_CRC != 0,
+        atomics: cfg2 & CFG2_LAM != 0,
+        // Dizin seviyesinde büyük sayfa girişleri (2 MiB, 1 GiB)
+        large_pages: true,
+        huge_pages: true,
+        rng: false,
+    }
+}
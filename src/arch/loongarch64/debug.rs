@@ -0,0 +1,94 @@
+// src/arch/loongarch64/debug.rs
+// LoongArch 64 mimarisine özgü kesme noktası ve gözlem noktası desteği.
+
+#![allow(dead_code)]
+
+use core::arch::asm;
+use crate::debug::{WatchKind, ERR_NO_HW_SINGLE_STEP};
+use crate::platformgeneric::KernelError;
+
+/// `break 0` talimatı.
+pub const BREAKPOINT_INSN: u32 = 0x002A_0000;
+
+/// Kesme noktası talimatının uzunluğu (bayt).
+pub const BREAKPOINT_INSN_LEN: usize = 4;
+
+/// ERA `break` talimatının kendisini gösterir; düzeltme gerekmez.
+pub const BREAKPOINT_PC_ADJUST: usize = 0;
+
+/// Talimat getirme bariyeri (`ibar 0`) ile değiştirilen kodu görünür kılar.
+#[inline(always)]
+pub unsafe fn sync_icache(_addr: usize, _len: usize) {
+    asm!("ibar 0", options(nostack));
+}
+
+/// LoongArch tek adımı yalnızca hata ayıklama modunda (DBG) sunar; çekirdek bunu kullanmaz.
+pub fn set_single_step(_status: &mut u64, enable: bool) -> Result<(), KernelError> {
+    if enable {
+        Err(KernelError::PlatformSpecificError(ERR_NO_HW_SINGLE_STEP))
+    } else {
+        Ok(())
+    }
+}
+
+// -----------------------------------------------------------------------------
+// DONANIM GÖZLEM NOKTALARI (MWP<n>CFG1-3, MWPS)
+// -----------------------------------------------------------------------------
+
+/// Kullanılan yükleme/depolama gözlem yuvası sayısı (CSR.MWPC.NUM en az 4'tür).
+pub const MAX_WATCHPOINTS: usize = 4;
+
+/// WPE istisnası (ECode 0x13) erişimden önce alınır.
+pub const WATCH_BEFORE_ACCESS: bool = true;
+
+// MWP<n>CFG3 alanları
+const MWPCFG3_PLV0: u64 = 1 << 0;  // Çekirdek (PLV0) erişimleri
+const MWPCFG3_PLV3: u64 = 1 << 3;  // Kullanıcı (PLV3) erişimleri
+const MWPCFG3_LOAD: u64 = 1 << 8;
+const MWPCFG3_STORE: u64 = 1 << 9;
+
+/// Yuvanın adres (CFG1), maske (CFG2) ve denetim (CFG3) yazmaçlarını yazar.
+/// CSR numaraları anlık değer olduğundan her yuva ayrı kodlanır (yuva başına 8 CSR).
+unsafe fn write_cfg(slot: usize, addr: u64, mask: u64, ctl: u64) {
+    match slot {
+        0 => asm!("csrwr {0}, 0x310", "csrwr {1}, 0x311", "csrwr {2}, 0x312",
+                  inout(reg) addr => _, inout(reg) mask => _, inout(reg) ctl => _, options(nostack)),
+        1 => asm!("csrwr {0}, 0x318", "csrwr {1}, 0x319", "csrwr {2}, 0x31a",
+                  inout(reg) addr => _, inout(reg) mask => _, inout(reg) ctl => _, options(nostack)),
+        2 => asm!("csrwr {0}, 0x320", "csrwr {1}, 0x321", "csrwr {2}, 0x322",
+                  inout(reg) addr => _, inout(reg) mask => _, inout(reg) ctl => _, options(nostack)),
+        _ => asm!("csrwr {0}, 0x328", "csrwr {1}, 0x329", "csrwr {2}, 0x32a",
+                  inout(reg) addr => _, inout(reg) mask => _, inout(reg) ctl => _, options(nostack)),
+    }
+}
+
+/// Adresi CFG1'e, yok sayılacak düşük bitleri (uzunluk - 1) CFG2'ye, ayrıcalık
+/// seviyesi ve erişim türünü CFG3'e yazar.
+pub unsafe fn set_watchpoint(slot: usize, addr: usize, len: usize, kind: WatchKind) -> Result<(), KernelError> {
+    let access = match kind {
+        WatchKind::Read => MWPCFG3_LOAD,
+        WatchKind::Write => MWPCFG3_STORE,
+        WatchKind::ReadWrite => MWPCFG3_LOAD | MWPCFG3_STORE,
+    };
+    write_cfg(slot, addr as u64, (len - 1) as u64, MWPCFG3_PLV0 | MWPCFG3_PLV3 | access);
+    Ok(())
+}
+
+/// CFG3'ü sıfırlayarak yuvayı kapatır.
+pub unsafe fn clear_watchpoint(slot: usize) {
+    write_cfg(slot, 0, 0, 0);
+}
+
+/// CSR.MWPS'teki durum bitlerinden eşleşen yuvayı okur ve temizler (1 yazarak).
+pub fn watchpoint_hit() -> Option<usize> {
+    unsafe {
+        let mwps: u64;
+        asm!("csrrd {0}, 0x301", out(reg) mwps, options(nostack));
+        let hits = mwps & ((1 << MAX_WATCHPOINTS) - 1);
+        if hits == 0 {
+            return None;
+        }
+        asm!("csrwr {0}, 0x301", inout(reg) hits => _, options(nostack));
+        Some(hits.trailing_zeros() as usize)
+    }
+}
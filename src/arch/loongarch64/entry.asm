@@ -108,10 +108,12 @@ error_hang:
 # 4. Veri Bölümü
 # --------------------------------------------------------------------------------
 
-.section .bss
+# Yığın linker betiğindeki `.boot_stack` bölümüne, koruma sayfasının hemen
+# üstüne yerleşir.
+.section .boot_stack, "aw", @nobits
 .align 16
-stack_top: 
-    .skip STACK_SIZE            # Yığın için yer ayır
 stack_bottom:
+    .skip STACK_SIZE            # Yığın için yer ayır
+stack_top:                      # Yığın aşağı doğru büyür; sp en üstten başlar
 
 .size _start, . - _start
\ No newline at end of file
@@ -1,6 +1,7 @@
 use core::arch::asm;
 use core::fmt;
 use crate::serial_println;
+use crate::trap::{handle_fatal, Access, Cause};
 
 // -----------------------------------------------------------------------------
 // HARİCİ MONTAJ DİLİ İŞLEYİCİSİ
@@ -40,13 +41,23 @@ pub struct ExceptionContext {
     pub csr_status: u64, // İşlemci durum yazmacı (Kesme durumu vb.)
 }
 
+// Tuzak giriş gövdesi bu düzene göre yazılmalıdır; ofsetler burada sabitlenir.
+crate::asm_layout!(ExceptionContext {
+    gpr: 0,
+    csr_era: 248,
+    csr_badv: 256,
+    csr_cause: 264,
+    csr_prid: 272,
+    csr_status: 280,
+}, size == 288);
+
 /// İstisna nedenleri (CSR.CAUSE yazmacının 2-6. bitleri).
 /// LoongArch Mimari Kaynakları'ndan alınmıştır.
 #[repr(u64)]
 #[derive(Debug, PartialEq)]
 pub enum ExceptionCause {
     Int = 0, // Kesme (Hardware Interrupt)
-    TlbRefill = 1, // TLB Yenileme (Load/Store/Instruction)
+    TlbRefill = 1, // TLB Yenileme; TLBRENTRY'de ele alınır (`mmu::init_tlb_refill`)
     TlbInvalid = 2, // TLB Geçersiz
     TlbModified = 3, // TLB Salt Okunur (Write to Read-Only)
     LoadPageFault = 4, // Yükleme Sayfa Hatası
@@ -57,6 +68,7 @@ pub enum ExceptionCause {
     Breakpoint = 12, // Kesme Noktası (BREAK)
     InstrInvalid = 14, // Geçersiz Talimat
     FpDisabled = 18, // FPU devre dışı
+    Watchpoint = 19, // Gözlem Noktası (WPE; WPEF talimat / WPEM bellek)
     _Unknown,
 }
 
@@ -75,11 +87,39 @@ impl From<u64> for ExceptionCause {
             12 => ExceptionCause::Breakpoint,
             14 => ExceptionCause::InstrInvalid,
             18 => ExceptionCause::FpDisabled,
+            19 => ExceptionCause::Watchpoint,
             _  => ExceptionCause::_Unknown,
         }
     }
 }
 
+/// Ham CAUSE ve BADV değerlerini ortak `trap::Cause` tipine çevirir.
+pub fn decode_cause(csr_cause: u64, csr_badv: u64) -> Cause {
+    let addr = csr_badv as usize;
+
+    match ExceptionCause::from((csr_cause >> 2) & 0x1F) {
+        // CAUSE yazmacının 10-15. bitleri bekleyen donanım kesme hatlarıdır
+        ExceptionCause::Int => Cause::Interrupt(((csr_cause >> 10) & 0x3F).trailing_zeros()),
+        ExceptionCause::TlbRefill | ExceptionCause::TlbInvalid | ExceptionCause::LoadPageFault => {
+            Cause::PageFault { access: Access::Read, addr }
+        }
+        ExceptionCause::TlbModified | ExceptionCause::StorePageFault => {
+            Cause::PageFault { access: Access::Write, addr }
+        }
+        ExceptionCause::InstPageFault => Cause::PageFault { access: Access::Execute, addr },
+        ExceptionCause::AddrInvalid => Cause::Misaligned { addr },
+        ExceptionCause::Syscall => Cause::Syscall,
+        ExceptionCause::Breakpoint => Cause::Breakpoint,
+        // BADV gözlenen veri adresini tutar; MWPS durumu `handle_trap` öncesi temizlenir
+        ExceptionCause::Watchpoint => {
+            let _ = crate::debug::watchpoint_hit();
+            Cause::Watchpoint { addr }
+        }
+        ExceptionCause::InstrInvalid => Cause::IllegalInstruction,
+        ExceptionCause::FpDisabled | ExceptionCause::_Unknown => Cause::Unknown(csr_cause),
+    }
+}
+
 // -----------------------------------------------------------------------------
 // 2. TEMEL İŞLEYİCİ FONKSİYONLARI (Rust Kodu)
 // -----------------------------------------------------------------------------
@@ -94,35 +134,82 @@ pub extern "C" fn generic_exception_handler(context: &mut ExceptionContext) {
         ExceptionCause::Int => {
             // Kesme İşleyicisi
             handle_interrupt(context);
+            preempt(context);
         }
-        ExceptionCause::InstPageFault | ExceptionCause::LoadPageFault | ExceptionCause::StorePageFault => {
+        // Geçersiz girdi ve salt okunur sayfaya yazma, TLB yenileme hızlı
+        // yolunun (`tlb_refill_entry`) çözemediği durumlardır.
+        ExceptionCause::TlbInvalid
+        | ExceptionCause::TlbModified
+        | ExceptionCause::InstPageFault
+        | ExceptionCause::LoadPageFault
+        | ExceptionCause::StorePageFault => {
             // Sayfa Hatası İşleyicisi
+            // Kullanıcı belleği erişimi veya bellek yoklaması ise düzeltme tablosuna bak
+            let trap_cause = decode_cause(context.csr_cause, context.csr_badv);
+            if crate::fixup::fixup_exception(trap_cause, &mut context.csr_era) {
+                return;
+            }
+
             serial_println!("\n--- SAYFA HATASI ---");
             serial_println!("Neden: {:?}", cause);
             serial_println!("Hata Adresi (BadV): {:#x}", context.csr_badv);
             serial_println!("RIP: {:#x}", context.csr_era);
-            panic!("Kritik Sayfa Hatası!");
+            handle_fatal(trap_cause, context.csr_era as usize);
         }
         ExceptionCause::Syscall => {
             // Sistem Çağrısı
             serial_println!("SYSCALL: Çağrı kodu: {}", context.gpr[10]); // Genellikle a0 (r10)
             // Syscall'dan dönmek için ERA'yı bir sonraki talimata ilerletmeniz GEREKİR.
             // context.csr_era += 4; // LoongArch talimatları 4 bayttır.
+            preempt(context);
         }
         _ => {
+            // Kesme noktası (break) ve gözlem noktası önce hata ayıklayıcıya sunulur.
+            let trap_cause = decode_cause(context.csr_cause, context.csr_badv);
+            if crate::debug::handle_trap(trap_cause, &mut context.csr_era, &mut context.csr_status) {
+                return;
+            }
+
+            // Hizalama ve erişim hataları için düzeltme tablosuna bak
+            if crate::fixup::fixup_exception(trap_cause, &mut context.csr_era) {
+                return;
+            }
+
             // Diğer senkron istisnalar
             serial_println!("\n--- KRİTİK İSTİSNA ---");
             serial_println!("Neden Kodu: {:#x} ({:?})", cause_code, cause);
             serial_println!("RIP (ERA): {:#x}", context.csr_era);
             serial_println!("Durum (Status): {:#x}", context.csr_status);
             
-            panic!("İşlenmemiş İstisna!");
+            handle_fatal(trap_cause, context.csr_era as usize);
         }
     }
 }
 
+/// Tuzak dönüşü: çekirdek kipindeki görev kesildiyse bekleyen yeniden
+/// zamanlamayı uygular (`IrqGuard` bırakıldıktan sonra). Görev başka bir
+/// işlemcide devam edebileceğinden çerçevedeki işlemci başına taban ($r21)
+/// güncellenir. Giriş kodu ERA ve PRMD'yi çerçeveden geri yazar; aradaki
+/// istisnalar yazmaçları bozsa da dönüş doğru olur.
+fn preempt(context: &mut ExceptionContext) {
+    // `csr_status` girişteki PRMD'dir; PPLV = 0 ise çekirdek kesildi
+    if context.csr_status & 0b11 != 0 {
+        return;
+    }
+    crate::sched::preempt_if_needed();
+    context.gpr[20] = crate::arch::loongarch64::task::cpu_local_base() as u64;
+}
+
 /// Donanım Kesmeleri (IRQ) için özel işleyici.
 fn handle_interrupt(context: &mut ExceptionContext) {
+    let _irq = crate::irqstack::enter(context as *const _ as usize);
+    // ESTAT.IS[11] (TI): sabit zamanlayıcı. TICLR önceden temizlenir; karşılaştırıcı
+    // kipi kapalıysa kesme başka türlü düşmez.
+    if context.csr_cause & (1 << 11) != 0 {
+        unsafe { asm!("csrwr {}, 0x44", inout(reg) 1u64 => _) };
+        crate::hrtimer::timer_interrupt();
+        return;
+    }
     let pending_interrupts = context.csr_status & (context.csr_cause >> 10);
     
     // LoongArch'ta CAUSE yazmacının 10-15 bitleri donanım kesmelerini gösterir.
@@ -168,4 +255,20 @@ pub fn init_exceptions() {
     
     serial_println!("[LA64] İstisna Giriş Noktası (EENTRY) yüklendi.");
     serial_println!("[LA64] Harici kesmeler (IE) etkinleştirildi.");
-}
\ No newline at end of file
+}
+
+// -----------------------------------------------------------------------------
+// 4. KESME YIĞINI
+// -----------------------------------------------------------------------------
+
+/// Kesmeler kesilen görevin yığınında çalışır; giriş kodu yığın değiştirmez
+/// (bkz. `crate::irqstack`).
+pub const SWITCHES_IRQ_STACK: bool = false;
+
+/// Ayrı kesme yığını olmadığından etkisizdir.
+///
+/// # Safety
+/// Diğer portlarla aynı sözleşme: `top` bu işlemciye ait bir yığının üstü olmalıdır.
+pub unsafe fn install_irq_stack(_top: usize) -> Result<(), crate::platformgeneric::KernelError> {
+    Ok(())
+}
@@ -0,0 +1,34 @@
+// src/arch/loongarch64/hypervisor.rs
+// LoongArch 64 hypervisor algılama: CPUCFG sanallaştırma imzası.
+
+#![allow(dead_code)]
+
+use core::arch::asm;
+use crate::hypervisor::{HypervisorKind, Paravirt};
+
+/// KVM'nin misafire sunduğu CPUCFG imza kelimesi.
+const CPUCFG_KVM_SIG: u32 = 0x4000_0000;
+/// "KVM\0" (little-endian).
+const KVM_SIGNATURE: u32 = u32::from_le_bytes(*b"KVM\0");
+
+/// CPUCFG yazmacını okur. Tanımsız kelimeler çıplak donanımda 0 döndürür.
+fn cpucfg(word: u32) -> u32 {
+    let value: u32;
+    unsafe { asm!("cpucfg {}, {}", out(reg) value, in(reg) word, options(nomem, nostack)) };
+    value
+}
+
+pub fn detect() -> HypervisorKind {
+    if cpucfg(CPUCFG_KVM_SIG) == KVM_SIGNATURE { HypervisorKind::Kvm } else { HypervisorKind::None }
+}
+
+/// Sabit frekanslı sayaç misafire doğrudan açıktır; henüz bir paravirt olanak kullanılmaz.
+pub unsafe fn enable_paravirt(_kind: HypervisorKind) -> Paravirt {
+    Paravirt::default()
+}
+
+pub fn pv_clock_ns() -> u64 {
+    0
+}
+
+pub fn pv_console_write_byte(_byte: u8) {}
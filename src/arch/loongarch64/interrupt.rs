@@ -120,4 +120,66 @@ pub fn handle_external_interrupts() {
             send_eoi(irq_line);
         }
     }
-}
\ No newline at end of file
+}
+
+// -----------------------------------------------------------------------------
+// YEREL KESME MASKESİ
+// -----------------------------------------------------------------------------
+
+// Genel kod bunları `crate::irqoff` üzerinden kullanır. `asm!` bloklarında
+// `nomem` yoktur: maske değişikliği derleyici için bir bellek engelidir,
+// kilitli bölgenin erişimleri dışarı taşınmaz.
+
+/// CRMD.IE biti (genel kesme etkin).
+const CRMD_IE: usize = 1 << 2;
+
+/// Yerel kesmeleri kapatır; önceki CRMD değerini döndürür.
+#[inline(always)]
+pub fn local_irq_save() -> usize {
+    let flags: usize;
+    // SAFETY: `csrxchg` yalnızca CRMD.IE'yi (maske) sıfırla değiştirir ve
+    // eski CRMD'yi döndürür.
+    unsafe { core::arch::asm!("csrxchg {v}, {m}, 0x0", v = inout(reg) 0usize => flags, m = in(reg) CRMD_IE, options(nostack)) };
+    flags
+}
+
+/// `local_irq_save` öncesindeki CRMD.IE değerini geri yazar.
+#[inline(always)]
+pub fn local_irq_restore(flags: usize) {
+    // SAFETY: Yalnızca kaydedilen IE biti geri yazılır.
+    unsafe { core::arch::asm!("csrxchg {v}, {m}, 0x0", v = inout(reg) flags & CRMD_IE => _, m = in(reg) CRMD_IE, options(nostack)) };
+}
+
+/// Kaydedilen durumda CRMD.IE açık mıydı?
+#[inline(always)]
+pub fn irqs_were_enabled(flags: usize) -> bool {
+    flags & CRMD_IE != 0
+}
+
+/// Yerel kesmeleri koşulsuz açar.
+#[inline(always)]
+pub fn local_irq_enable() {
+    local_irq_restore(CRMD_IE);
+}
+
+// -----------------------------------------------------------------------------
+// HAT MASKESİ VE PCI INTx
+// -----------------------------------------------------------------------------
+
+/// Kesme hattını denetleyicide maskeler veya açar (bkz. `crate::irqstorm`);
+/// `irq` dağıtıcının gördüğü numaradır. Desteklenmiyorsa `false`.
+pub fn set_irq_masked(_irq: u32, _masked: bool) -> bool {
+    false
+}
+
+/// PCI aygıtının INTx hattını açar ve dağıtıcının göreceği kesme numarasını
+/// döndürür. INTx eşlemesi yoksa `None`; sürücü yoklamayla çalışır.
+pub fn route_pci_intx(_line: u8) -> Option<u32> {
+    None
+}
+
+/// Aygıt ağacı düğümünün ilk kesmesini denetleyicinin göreceği numaraya
+/// çevirir. Bu portun denetleyicisi tek hücreli kesme belirteci kullanır.
+pub fn decode_fdt_irq(node: &crate::fdt::Node) -> Option<u32> {
+    node.interrupt_cell(0)
+}
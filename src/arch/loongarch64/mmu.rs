@@ -3,7 +3,10 @@
 
 use core::arch::asm;
 use core::ptr::NonNull;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use crate::platformgeneric::KernelError;
 use crate::serial_println;
+use crate::memory::vmaudit::{validate_mapping, CacheAttr, MapError, Mapping, Perms};
 use super::io; // Bariyerler için io modülünü kullanacağız
 
 // -----------------------------------------------------------------------------
@@ -93,23 +96,36 @@ fn get_indices(virtual_addr: usize) -> (usize, usize, usize, usize) {
 }
 
 
-/// Yeni bir sayfa tablosu tahsis etmeyi simüle eder.
-/// (Gerçekte bir fiziksel bellek yöneticisi gereklidir.)
-fn alloc_page_table() -> NonNull<PageTable> {
-    // Lütfen unutmayın: Bu, gerçek bellek yöneticisi olmadan tehlikelidir ve sadece bir mock'tur.
-    static mut PAGE_TABLE_MOCK: [u8; PAGE_SIZE] = [0; PAGE_SIZE];
-    
-    let ptr = unsafe { PAGE_TABLE_MOCK.as_mut_ptr() as *mut PageTable };
-    let table = unsafe { &mut *ptr };
-    table.entries.iter_mut().for_each(|e| *e = PageTableEntry::zero());
-    
-    // Güvenli olmayan bir şekilde NonNull oluşturma.
-    unsafe { NonNull::new_unchecked(ptr) }
+/// Sıfırlanmış yeni bir sayfa tablosu ayırır (bkz. `physmap::alloc_table`).
+fn alloc_page_table() -> Result<NonNull<PageTable>, MapError> {
+    let addr = crate::physmap::alloc_table().ok_or(MapError::OutOfMemory)?;
+    // SAFETY: `alloc_table` sıfırdan farklı, sayfa hizalı bir adres döndürür.
+    Ok(unsafe { NonNull::new_unchecked(addr as *mut PageTable) })
 }
 
+/// Tablo girişi geçerliyse gösterdiği tabloyu, değilse yeni ayrılan tabloyu
+/// döndürür; yeni tablo üst tablonun giriş sayısına eklenir (`physmap::table_ref`).
+unsafe fn next_table(table: &mut PageTable, index: usize) -> Result<&'static mut PageTable, MapError> {
+    let table_addr = table as *mut PageTable as usize;
+    let entry = &mut table.entries[index];
+    if !entry.is_valid() {
+        // Tablo girişleri için sadece VALID bayrağı yeterlidir
+        *entry = PageTableEntry::new(alloc_page_table()?.as_ptr() as usize, PageFlags::VALID as u64);
+        crate::physmap::table_ref(table_addr);
+    }
+    Ok(&mut *(entry.addr() as *mut PageTable))
+}
 
 /// Sanal adresi fiziksel adrese eşler (4KiB sayfa).
 ///
+/// Sanal adres 48 bit kanonik, fiziksel adres 48 bit olmalıdır; bayraklar
+/// adres alanına (bit 12 ve üstü) taşamaz.
+///
+/// # Hatalar
+/// * `Unaligned` / `OutOfRange` / `InvalidFlags`: bkz. `validate_mapping`.
+/// * `AlreadyMapped`: Adres başka bir adrese veya bayraklarla eşli.
+/// * `OutOfMemory`: Ara tablo ayrılamadı.
+///
 /// # Güvenlik Notu
 /// Bu fonksiyon doğrudan bellek adreslerini değiştirir.
 pub unsafe fn map_page(
@@ -117,58 +133,73 @@ pub unsafe fn map_page(
     virtual_addr: usize,
     physical_addr: usize,
     flags: u64,
-) {
+) -> Result<(), MapError> {
+    validate_mapping(virtual_addr, physical_addr, PAGE_SIZE, 48, 48, flags, PageFlags::ADDR_MASK as u64)?;
     let (l1i, l2i, l3i, l4i) = get_indices(virtual_addr);
-    
+
     // Not: LoongArch'ta sayfa tablosu adresi genellikle KSEG0/KSEG1 adresindedir 
     // ve eşlemeyi yapabilmek için erişilebilir olmalıdır.
     let l1_table = &mut *(root_table_addr as *mut PageTable);
-    
-    // 1. L2'yi al veya oluştur
-    let l2_entry = l1_table.entries.get_mut(l1i).expect("L1 Index Hata");
-    let l2_addr = if l2_entry.is_valid() {
-        l2_entry.addr()
-    } else {
-        let new_l2 = alloc_page_table();
-        let new_addr = new_l2.as_ptr() as usize;
-        // Tablo girişleri için sadece VALID bayrağı yeterlidir
-        *l2_entry = PageTableEntry::new(new_addr, PageFlags::VALID as u64);
-        new_addr
-    };
-    
-    let l2_table = &mut *(l2_addr as *mut PageTable);
-    
-    // 2. L3'ü al veya oluştur
-    let l3_entry = l2_table.entries.get_mut(l2i).expect("L2 Index Hata");
-    let l3_addr = if l3_entry.is_valid() {
-        l3_entry.addr()
-    } else {
-        let new_l3 = alloc_page_table();
-        let new_addr = new_l3.as_ptr() as usize;
-        *l3_entry = PageTableEntry::new(new_addr, PageFlags::VALID as u64);
-        new_addr
-    };
 
-    let l3_table = &mut *(l3_addr as *mut PageTable);
-    
-    // 3. L4'ü al veya oluştur
-    let l4_entry = l3_table.entries.get_mut(l3i).expect("L3 Index Hata");
-    let l4_addr = if l4_entry.is_valid() {
-        l4_entry.addr()
-    } else {
-        let new_l4 = alloc_page_table();
-        let new_addr = new_l4.as_ptr() as usize;
-        *l4_entry = PageTableEntry::new(new_addr, PageFlags::VALID as u64);
-        new_addr
-    };
+    // L1 -> L2 -> L3 -> L4; eksik tablolar oluşturulur
+    let l2_table = next_table(l1_table, l1i)?;
+    let l3_table = next_table(l2_table, l2i)?;
+    let l4_table = next_table(l3_table, l3i)?;
+
+    // L4 Girişini ayarla (Son eşleme, 4K Sayfa). Aynı eşlemenin tekrarı
+    // etkisizdir; farklı bir eşlemenin üzerine yazılmaz.
+    let l4_addr = l4_table as *mut PageTable as usize;
+    let entry = &mut l4_table.entries[l4i];
+    let new = PageTableEntry::new(physical_addr, flags | PageFlags::VALID as u64);
+    if entry.is_valid() {
+        return if entry.0 == new.0 { Ok(()) } else { Err(MapError::AlreadyMapped) };
+    }
+    *entry = new;
+    crate::physmap::table_ref(l4_addr);
 
-    let l4_table = &mut *(l4_addr as *mut PageTable);
-    
-    // 4. L4 Girişini ayarla (Son eşleme, 4K Sayfa)
-    let final_entry = l4_table.entries.get_mut(l4i).expect("L4 Index Hata");
-    *final_entry = PageTableEntry::new(physical_addr, flags | PageFlags::VALID as u64);
-    
     // Sayfa tablosu güncellendi, TLB temizliği gereklidir.
+    Ok(())
+}
+
+/// Tüm TLB girişlerini geçersiz kılar (`invtlb` op 0).
+#[inline(always)]
+unsafe fn flush_tlb() {
+    asm!("dbar 0", "invtlb 0, $zero, $zero", options(nostack));
+}
+
+/// Sanal adresin 4 KiB eşlemesini kaldırır, TLB'yi boşaltır ve boşalan ara
+/// tabloları serbest bırakır. Eşlenmiş fiziksel adresi döndürür.
+///
+/// # Hatalar
+/// * `NotMapped`: Adres eşli değil.
+pub unsafe fn unmap_page(root_table_addr: usize, virtual_addr: usize) -> Result<usize, MapError> {
+    let (l1i, l2i, l3i, l4i) = get_indices(virtual_addr);
+    let indices = [l1i, l2i, l3i, l4i];
+    let mut path = [root_table_addr, 0, 0, 0];
+    for level in 0..3 {
+        let entry = (*(path[level] as *const PageTable)).entries[indices[level]];
+        if !entry.is_valid() {
+            return Err(MapError::NotMapped);
+        }
+        path[level + 1] = entry.addr();
+    }
+    let entry = &mut (*(path[3] as *mut PageTable)).entries[l4i];
+    if !entry.is_valid() {
+        return Err(MapError::NotMapped);
+    }
+    let physical_addr = entry.addr();
+    *entry = PageTableEntry::zero();
+    flush_tlb();
+
+    // Boşalan tablolar yukarı doğru serbest bırakılır; kök izlenmez (`None`).
+    let mut level = 3;
+    while crate::physmap::table_unref(path[level]) == Some(0) && level > 0 {
+        (*(path[level - 1] as *mut PageTable)).entries[indices[level - 1]] = PageTableEntry::zero();
+        flush_tlb();
+        crate::physmap::free_table(path[level]);
+        level -= 1;
+    }
+    Ok(physical_addr)
 }
 
 // -----------------------------------------------------------------------------
@@ -194,8 +225,9 @@ unsafe fn write_csr(csr_num: u32, value: u64) {
 /// Sayfalama için yeni L1 tablosunu hazırlar ve sanal adresleri eşler.
 ///
 /// # Geri Dönüş
-/// Yeni sayfa tablosunun fiziksel adresi (PTEBase'e yazılacak adres).
-pub fn setup_initial_paging() -> usize {
+/// Yeni sayfa tablosunun fiziksel adresi (PTEBase'e yazılacak adres); bir
+/// sayfa eşlenemezse eşleme hatası.
+pub fn setup_initial_paging() -> Result<usize, MapError> {
     serial_println!("[LA64] İlk Sayfa Tablosu Hazırlanıyor...");
     
     // Statik olarak hizalanmış bir bellek bloğunu L1 (Kök) Tablo olarak kullan.
@@ -220,15 +252,14 @@ pub fn setup_initial_paging() -> usize {
     let identity_mapping_size = 16 * 1024 * 1024; // 16 MB
 
     for addr in (0..identity_mapping_size).step_by(PAGE_SIZE) {
-        unsafe {
-            // Sanal adresi KSEG0'da (önbellekli) eşle: 0xFFF0_0000_...
-            // Sanal adres ve fiziksel adres aynı kabul edilir (birebir eşleme)
-            map_page(l1_addr, addr, addr, kernel_flags);
-        }
+        // Sanal adresi KSEG0'da (önbellekli) eşle: 0xFFF0_0000_...
+        // Sanal adres ve fiziksel adres aynı kabul edilir (birebir eşleme)
+        unsafe { map_page(l1_addr, addr, addr, kernel_flags)? };
     }
 
     serial_println!("[LA64] İlk 16MB birebir eşlendi (Identity Mapped).");
-    l1_addr
+    ROOT_TABLE.store(l1_addr, Ordering::Relaxed);
+    Ok(l1_addr)
 }
 
 /// Sayfalama mekanizmasını etkinleştirir.
@@ -256,9 +287,9 @@ pub unsafe fn enable_paging(l1_phys_addr: usize) {
     
     write_csr(0x0, crmd); 
     
-    // c) PTEBase'i ayarla (TLB'nin sayfa tablosunu bulması için kök adres)
-    // PTEBase yazmacı (CSR 0x18 - Temsili)
-    write_csr(0x18, l1_phys_addr as u64);
+    // c) Kök tabloyu ve donanım destekli TLB yenileme yolunu kur
+    //    (PGDL/PGDH, PWCL/PWCH, TLBRENTRY)
+    init_tlb_refill(l1_phys_addr);
 
     // d) TLB Temizliği ve I-Sync
     // LoongArch'ta TLB'yi temizlemek için özel talimatlar/CSR'lar kullanılır.
@@ -279,15 +310,182 @@ pub unsafe fn enable_paging(l1_phys_addr: usize) {
 }
 
 
+// -----------------------------------------------------------------------------
+// TLB YENİLEME (TLBRENTRY)
+// -----------------------------------------------------------------------------
+
+extern "C" {
+    /// TLB yenileme işleyicisi (`exception_handlers.S`); 4 KiB hizalıdır.
+    fn tlb_refill_entry();
+}
+
+/// Sayfa boyutunun kuvveti (4 KiB).
+const PAGE_SHIFT: u64 = 12;
+
+/// PWCL: sayfa tablosu (PT) bit 12'den, Dir1 bit 21'den, Dir2 bit 30'dan;
+/// her biri 9 bit. PTEWidth = 0 (64 bit girdiler).
+const PWCL_VALUE: u64 = PAGE_SHIFT | 9 << 5 | 21 << 10 | 9 << 15 | 30 << 20 | 9 << 25;
+
+/// PWCH: kök dizin (Dir3) bit 39'dan 9 bit; Dir4 kullanılmaz.
+const PWCH_VALUE: u64 = 39 | 9 << 6;
+
+/// TLB yenileme istisnasını `tlb_refill_entry`'ye yönlendirir ve yürüyüşün
+/// okuyacağı sayfa tablosu düzenini kurar. Yenileme yolu yalnızca boş TLB
+/// yuvalarını doldurur; geçersiz (V=0) veya salt okunur sayfaya yazma
+/// durumları PIL/PIS/PIF/PME olarak genel istisna işleyicisine gider.
+///
+/// # Parametreler
+/// * `root`: Kök (Dir3) tablonun fiziksel adresi; alt ve üst yarı aynı kökü kullanır.
+///
+/// # Safety
+/// `root` geçerli bir sayfa tablosu olmalı ve işleyici fiziksel adresinden
+/// (çekirdek imajı birebir eşlenmiş) çalıştırılabilir olmalıdır.
+pub unsafe fn init_tlb_refill(root: usize) {
+    // `csrwr` yazmacı CSR ile takas eder; eski değerler atılır.
+    asm!("csrwr {}, 0x1c", inout(reg) PWCL_VALUE => _); // PWCL
+    asm!("csrwr {}, 0x1d", inout(reg) PWCH_VALUE => _); // PWCH
+    asm!("csrwr {}, 0x1e", inout(reg) PAGE_SHIFT => _); // STLBPS
+    asm!("csrwr {}, 0x8e", inout(reg) PAGE_SHIFT => _); // TLBREHI.PS
+    asm!("csrwr {}, 0x19", inout(reg) root as u64 => _); // PGDL
+    asm!("csrwr {}, 0x1a", inout(reg) root as u64 => _); // PGDH
+    let entry = tlb_refill_entry as usize as u64;
+    asm!("csrwr {}, 0x88", inout(reg) entry => _); // TLBRENTRY
+    serial_println!("[LA64] TLB yenileme işleyicisi (TLBRENTRY) {:#x} adresinde.", entry);
+}
+
 /// Sayfalama sonrası çekirdek başlatma işlevi.
 /// `main.rs` içinden çağrılmalıdır.
 pub fn init_mmu() {
-    // İlk sayfa tablosunu hazırla
-    let l1_addr = setup_initial_paging();
+    // İlk sayfa tablosunu hazırla; eksik tabloyla sayfalama açılmaz
+    let l1_addr = match setup_initial_paging() {
+        Ok(addr) => addr,
+        Err(e) => {
+            serial_println!("[LA64] İlk sayfa tablosu kurulamadı: {:?}", e);
+            return;
+        }
+    };
     
     // Sayfalamayı etkinleştir
     unsafe {
         // Not: l1_addr'ın fiziksel adresi olduğundan emin olun.
         enable_paging(l1_addr);
     }
-}
\ No newline at end of file
+}
+
+// -----------------------------------------------------------------------------
+// ÇEKİRDEK SANAL ALANI (vmalloc)
+// -----------------------------------------------------------------------------
+
+/// `memory::vmalloc` penceresinin başlangıcı (birebir eşlemenin dışında).
+pub const VMALLOC_BASE: usize = 0xFFFF_C000_0000_0000;
+/// `memory::vmalloc` penceresinin boyutu.
+pub const VMALLOC_SIZE: usize = 1 << 30;
+
+/// `setup_initial_paging` tarafından kurulan kök tablo (0: henüz yok).
+static ROOT_TABLE: AtomicUsize = AtomicUsize::new(0);
+
+/// Çekirdek verisi için 4 KiB sayfa eşler (R/W, global, yürütülemez).
+///
+/// # Hatalar
+/// * `Map(NotReady)`: Sayfalama henüz kurulmadı.
+/// * Eşleme hataları `MapError` dönüşümüyle (bkz. `vmaudit`).
+pub fn map_kernel_page(virt: usize, phys: usize) -> Result<(), KernelError> {
+    let root = ROOT_TABLE.load(Ordering::Relaxed);
+    if root == 0 {
+        return Err(MapError::NotReady.into());
+    }
+    let flags = PageFlags::READ as u64
+        | PageFlags::WRITE as u64
+        | PageFlags::DIRTY as u64
+        | PageFlags::GLOBAL as u64
+        | PageFlags::CACHE_K0 as u64;
+    // SAFETY: Pencere yalnızca `vmalloc` tarafından yönetilir.
+    unsafe { map_page(root, virt, phys, flags)? };
+    Ok(())
+}
+
+/// `map_kernel_page` eşlemesini kaldırır ve fiziksel adresini döndürür.
+pub unsafe fn unmap_kernel_page(virt: usize) -> Option<usize> {
+    match ROOT_TABLE.load(Ordering::Relaxed) {
+        0 => None,
+        root => unmap_page(root, virt).ok(),
+    }
+}
+
+// -----------------------------------------------------------------------------
+// SAYFA TABLOSU DENETİMİ (vm dump)
+// -----------------------------------------------------------------------------
+
+/// 48 bitlik sanal adresi kanonik biçime (bit 47'nin işaret genişletmesi) getirir.
+fn canonical(addr: usize) -> usize {
+    (((addr << 16) as isize) >> 16) as usize
+}
+
+/// Bir tablo seviyesini özyinelemeli olarak dolaşır (seviye 3: L1 ... seviye 0: 4K yaprak).
+///
+/// `map_page` yalnızca 4K sayfalar kurduğundan yapraklar en alt seviyededir.
+unsafe fn walk_level(table_addr: usize, level: usize, base: usize, visit: &mut dyn FnMut(Mapping)) {
+    let table = &*(table_addr as *const PageTable);
+    let shift = 12 + 9 * level;
+
+    for (index, entry) in table.entries.iter().enumerate() {
+        if !entry.is_valid() {
+            continue;
+        }
+        let vaddr = canonical(base | (index << shift));
+        if level > 0 {
+            walk_level(entry.addr(), level - 1, vaddr, visit);
+            continue;
+        }
+
+        let raw = entry.0;
+        visit(Mapping {
+            vaddr,
+            paddr: entry.addr(),
+            size: PAGE_SIZE,
+            perms: Perms {
+                read: raw & PageFlags::READ as u64 != 0,
+                write: raw & PageFlags::WRITE as u64 != 0,
+                exec: raw & PageFlags::EXEC as u64 != 0,
+                user: raw & PageFlags::USER as u64 != 0,
+            },
+            cache: if raw & PageFlags::CACHE_NC as u64 != 0 { CacheAttr::Uncached } else { CacheAttr::WriteBack },
+        });
+    }
+}
+
+/// PTEBase (CSR 0x18) yazmacındaki kök tablodan başlayarak tüm yaprak eşlemeleri bildirir.
+pub fn walk_mappings(visit: &mut dyn FnMut(Mapping)) {
+    unsafe {
+        let root = read_csr(0x18) & PageFlags::ADDR_MASK as u64;
+        if root != 0 {
+            walk_level(root as usize, 3, 0, visit);
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// KULLANICI SAYFALARI (mmap)
+// -----------------------------------------------------------------------------
+
+/// Kullanıcı sayfası eşlemesi bu portta henüz yok; `mmap` dosya eşlemeleri
+/// `PlatformSpecificError` ile reddedilir.
+///
+/// # Safety
+/// Diğer portlarla aynı sözleşme (bkz. `rv64i::mmu::map_user_page`).
+pub unsafe fn map_user_page(_virtual_addr: usize, _physical_addr: usize, _write: bool, _exec: bool) -> Result<(), crate::platformgeneric::KernelError> {
+    Err(crate::platformgeneric::KernelError::PlatformSpecificError(0))
+}
+
+/// Kullanıcı sayfası eşlenemediğinden etkisizdir.
+pub unsafe fn unmap_user_page(_virtual_addr: usize) {}
+
+// -----------------------------------------------------------------------------
+// AYGIT PENCERELERİ (physmap)
+// -----------------------------------------------------------------------------
+
+/// Büyük sayfalı çekirdek eşlemesi bu portta henüz yok; `physmap::map_mmio`
+/// `PlatformSpecificError` ile reddedilir.
+pub fn map_device(_phys: usize, _len: usize) -> Result<usize, crate::platformgeneric::KernelError> {
+    Err(crate::platformgeneric::KernelError::PlatformSpecificError(0))
+}
@@ -3,7 +3,6 @@
 
 use core::arch::asm;
 use core::panic::PanicInfo;
-use crate::serial_println;
 
 /// Panik anında işlemcinin durması için kullanılan sonsuz döngü.
 ///
@@ -11,8 +10,7 @@ use crate::serial_println;
 /// genellikle 'idle' veya özel bir sistem çağrısı kullanılır. 
 /// Eğer 'idle' mevcut değilse, basit bir 'nop' döngüsü yeterlidir.
 #[inline(always)]
-fn halt_loop() -> ! {
-    serial_println!("... İşlemci Durduruluyor (NOP/IDLE döngüsü).");
+pub(crate) fn halt_loop() -> ! {
     loop {
         // LoongArch'ta IDLE talimatı (0x018 talimat kodu) veya basit bir NOP döngüsü kullanılır.
         unsafe {
@@ -30,32 +28,7 @@ fn halt_loop() -> ! {
 /// Bu fonksiyon, `panic!` makrosu çağrıldığında tetiklenir.
 #[panic_handler]
 fn panic(info: &PanicInfo) -> ! {
-    // 1. Seri porta veya ekrana hata mesajını yazdır.
-    serial_println!("\n========================================");
-    serial_println!("!!! KERNEL PANIC (LOONGARCH64) !!!");
-    
-    // Panik mesajını yazdır
-    if let Some(location) = info.location() {
-        serial_println!("Hata Konumu: {}:{}:{}", 
-            location.file(), 
-            location.line(), 
-            location.column()
-        );
-    } else {
-        serial_println!("Hata Konumu: Bilinmiyor");
-    }
-    
-    if let Some(message) = info.message() {
-        serial_println!("Hata Mesajı: {}", message);
-    } else {
-        serial_println!("Hata Mesajı: Yok");
-    }
-
-    serial_println!("========================================");
-
-    // 2. Tüm işlemci çekirdeklerini durdur.
-    // Çok çekirdekli sistemlerde, buraya gelindiğinde diğer çekirdekleri 
-    // durdurmak için bir IPI (Inter-Processor Interrupt) göndermek gerekebilir.
-    
-    halt_loop();
-}
\ No newline at end of file
+    // Kesme bağlamı, iç içe panik ve diğer işlemcilerin durdurulması
+    // mimariden bağımsız yolda ele alınır (bkz. `crate::panic`).
+    crate::panic::handle(info, "LOONGARCH64")
+}
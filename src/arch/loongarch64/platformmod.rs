@@ -143,7 +143,22 @@ pub fn platform_init() {
         io::membar_all();
     }
 
+    // Errata ve kart tuhaflıkları (bkz. `quirks`) alt sistemlerden önce uygulanır.
+    crate::quirks::apply();
+
     // 4. Diğer alt sistemleri başlat (MMU, İstisnalar, Zamanlayıcı, vb.)
-    
+    // Alt sistemler kendilerini `initcall!` ile kaydeder; sıra tüm mimarilerde aynıdır.
+    crate::initcall::run_initcalls();
+
     serial_println!("[LA64] Temel Platform Hazır.");
-}
\ No newline at end of file
+}
+
+// -----------------------------------------------------------------------------
+// ÖNYÜKLEME DEVRİ
+// -----------------------------------------------------------------------------
+
+/// Porta özgü devir yapısı yok; `false` döner ve `crate::boot` aygıt ağacını
+/// veya OpenFirmware'ı dener.
+pub fn parse_boot_handoff(_regs: &crate::boot::BootRegs, _info: &mut crate::boot::BootInfo) -> bool {
+    false
+}
@@ -2,124 +2,207 @@
 // LoongArch 64 (LA64) mimarisine özgü görev (task) ve bağlam (context) yönetimi.
 
 use core::arch::asm;
+use core::mem::offset_of;
+use crate::context::ArchContext;
 use crate::serial_println;
 
 /// Görev bağlamını (task context) saklamak için kullanılan yapı.
 /// LA64'te Callee-Saved (çağrılan tarafından korunan) yazmaçlar:
-/// r22-r31 (S8-S15, FP, SP), r4 (ra/LR).
-/// r3 (sp) ve r4 (ra/LR) bağlam anahtarlamada kritik öneme sahiptir.
+/// r22 (fp) ve r23-r31 (s0-s8).
+/// r3 (sp) ve r1 (ra) bağlam anahtarlamada kritik öneme sahiptir.
 #[repr(C)]
 #[derive(Debug, Default)]
 pub struct TaskContext {
     // Callee-Saved GPR'lar (r22 - r31) - 10 adet
-    // LoongArch ABI: r22-r30 (s8-s14, fp)
-    r22: u64, // s8
-    r23: u64, // s9
-    r24: u64, // s10
-    r25: u64, // s11
-    r26: u64, // s12
-    r27: u64, // s13
-    r28: u64, // s14
-    r29: u64, // s15 (Genellikle ekstra geçici)
-    r30: u64, // fp (Frame Pointer)
-    r31: u64, // tp (Thread Pointer - Genellikle Callee-Saved değil, ancak kaydedilir)
+    r22: u64, // fp (Frame Pointer)
+    r23: u64, // s0
+    r24: u64, // s1
+    r25: u64, // s2
+    r26: u64, // s3
+    r27: u64, // s4
+    r28: u64, // s5
+    r29: u64, // s6
+    r30: u64, // s7
+    r31: u64, // s8
     
-    // Link Register (r4) - Görev anahtarlamadan sonra geri döneceği adres (PC).
-    ra: u64, // r4 (Return Address / Link Register)
+    // Dönüş adresi (r1) - Görev anahtarlamadan sonra geri döneceği adres (PC).
+    ra: u64, // r1 (Return Address)
     
     // Yığın İşaretçisi (r3) - Görevin yeni yığınının adresi.
     sp: u64, // r3 (Stack Pointer) 
+
+    // Görev devam ettiğinde a0'da (r4) göreceği değer (`set_return_value`).
+    ret: u64,
 }
 
 impl TaskContext {
-    /// Yeni bir görev bağlamı oluşturur.
-    /// 
-    /// # Argümanlar
-    /// * `stack_top`: Görevin yığınının en üst adresi.
-    /// * `entry_point`: Görevin başlayacağı fonksiyonun adresi.
-    pub fn new(stack_top: u64, entry_point: u64) -> Self {
-        // Yeni bir görev başlatıldığında, anahtarlama kodu bu yapıyı yükler.
-        Self {
-            // Callee-Saved yazmaçlar sıfırlanır.
-            ..Default::default()
-            
-            // sp, yığının üstü olarak ayarlanır. (LA64'te r3)
-            sp: stack_top,
-            
-            // ra (r4) ve pc (entry_point), görevin ilk başlayacağı adres olarak ayarlanır.
-            // Anahtarlama `jr ra` ile görev döndüğünde, bu ra'ya zıplayacaktır.
-            ra: entry_point, 
-        }
-    }
-
-    /// Bir bağlam anahtarlama işlemi sırasında yazmaç durumunu kaydetmek ve 
-    /// yeni görevden yazmaç durumunu yüklemek için kullanılan harici assembly fonksiyonu.
+    /// r22-r31'i, sp'yi ve dönüş noktasını `old_context`'e kaydeder,
+    /// `new_context`'ten yükler ve onun ra alanındaki adrese zıplar. Eski görev
+    /// yeniden seçildiğinde `2:` etiketinden, yani bu çağrının sonundan devam
+    /// eder.
     ///
     /// # Güvenlik Notu
-    /// Bu fonksiyon, C ABI'sine uymalıdır: `fn switch_context(old_context: *mut TaskContext, new_context: *const TaskContext)`.
-    /// Argümanlar r5 ve r6'ya (a0 ve a1) geçirilir.
+    /// İki işaretçi de geçiş boyunca geçerli olmalıdır; `new_context` ya
+    /// `ArchContext::new` ile kurulmuş ya da daha önce kaydedilmiş olmalıdır.
+    /// r22 (fp) ve r31 işlenen olarak verilemez; blok onları kendisi kaydedip yükler.
     #[inline(always)]
     pub unsafe fn switch_context(old_context: *mut TaskContext, new_context: *const TaskContext) {
-        serial_println!("[TASK] Bağlam Anahtarlama: {:#x} -> {:#x}", 
-                        old_context as u64, new_context as u64);
-
         asm!(
             // --------------------- Mevcut Görevin Durumunu Kaydet ---------------------
-            // r5: old_context, r6: new_context (C ABI'de a0 ve a1)
-            
-            // 1. Callee-Saved GPR'ları TaskContext'e kaydet (r22-r31)
-            // st.d rN, r5, #offset (Store Doubleword)
-            
-            // r22 offset 0'da başlar. 10 yazmaç = 80 bayt.
-            "st.d r22, r5, 0", 
-            "st.d r23, r5, 8",
-            "st.d r24, r5, 16",
-            "st.d r25, r5, 24",
-            "st.d r26, r5, 32",
-            "st.d r27, r5, 40",
-            "st.d r28, r5, 48",
-            "st.d r29, r5, 56",
-            "st.d r30, r5, 64",
-            "st.d r31, r5, 72",
-            
-            // 2. r4 (ra) ve r3 (sp) kaydet.
-            // ra (r4) offset 80, sp (r3) offset 88.
-            "st.d r4, r5, 80",  // ra
-            "st.d r3, r5, 88",  // sp
-            
+            "st.d $r22, $r5, {r22}",
+            "st.d $r23, $r5, {r23}",
+            "st.d $r24, $r5, {r24}",
+            "st.d $r25, $r5, {r25}",
+            "st.d $r26, $r5, {r26}",
+            "st.d $r27, $r5, {r27}",
+            "st.d $r28, $r5, {r28}",
+            "st.d $r29, $r5, {r29}",
+            "st.d $r30, $r5, {r30}",
+            "st.d $r31, $r5, {r31}",
+            "st.d $r3, $r5, {sp}",
+            // Devam noktası: görev yeniden seçildiğinde `2:`'ye döner
+            "la.local $r12, 2f",
+            "st.d $r12, $r5, {ra}",
+
             // --------------------- Yeni Görevin Durumunu Yükle ---------------------
-            // r6: new_context
-            
-            // 1. Yeni r3 (sp) yükle (Offset 88)
-            "ld.d r3, r6, 88",  
-            
-            // 2. Callee-Saved GPR'ları yükle (r22-r31) (Offset 0)
-            "ld.d r22, r6, 0", 
-            "ld.d r23, r6, 8",
-            "ld.d r24, r6, 16",
-            "ld.d r25, r6, 24",
-            "ld.d r26, r6, 32",
-            "ld.d r27, r6, 40",
-            "ld.d r28, r6, 48",
-            "ld.d r29, r6, 56",
-            "ld.d r30, r6, 64",
-            "ld.d r31, r6, 72",
-            
-            // 3. Yeni r4 (ra) yükle (Offset 80)
-            "ld.d r4, r6, 80",
-            
-            // 4. Yeni görevin giriş noktasına zıpla (jr ra)
-            "jr r4", // Jump Register (Görevi başlatır/devam ettirir)
-            
-            in("r5") old_context,
-            in("r6") new_context,
-            // r7-r12 (a2-a7) caller-saved
-            // r1, r2, r13-r21 (t0-t9) temporaries/caller-saved
-            options(noreturn, preserves_flags)
+            "ld.d $r22, $r6, {r22}",
+            "ld.d $r23, $r6, {r23}",
+            "ld.d $r24, $r6, {r24}",
+            "ld.d $r25, $r6, {r25}",
+            "ld.d $r26, $r6, {r26}",
+            "ld.d $r27, $r6, {r27}",
+            "ld.d $r28, $r6, {r28}",
+            "ld.d $r29, $r6, {r29}",
+            "ld.d $r30, $r6, {r30}",
+            "ld.d $r31, $r6, {r31}",
+            "ld.d $r3, $r6, {sp}",
+            "ld.d $r12, $r6, {ra}",
+            "ld.d $r4, $r6, {ret}",
+            "jr $r12",
+            "2:",
+
+            r22 = const offset_of!(TaskContext, r22),
+            r23 = const offset_of!(TaskContext, r23),
+            r24 = const offset_of!(TaskContext, r24),
+            r25 = const offset_of!(TaskContext, r25),
+            r26 = const offset_of!(TaskContext, r26),
+            r27 = const offset_of!(TaskContext, r27),
+            r28 = const offset_of!(TaskContext, r28),
+            r29 = const offset_of!(TaskContext, r29),
+            r30 = const offset_of!(TaskContext, r30),
+            r31 = const offset_of!(TaskContext, r31),
+            ra = const offset_of!(TaskContext, ra),
+            sp = const offset_of!(TaskContext, sp),
+            ret = const offset_of!(TaskContext, ret),
+            inout("$r5") old_context => _,
+            inout("$r6") new_context => _,
+            // Devam eden görev için caller-saved yazmaçların (ra dahil) hepsi bozulmuş sayılır
+            clobber_abi("C"),
         );
     }
+
+    /// Callee-saved yazmaçları, yığın işaretçisini ve devam noktasını
+    /// `context`'e kaydeder, ardından `body(data)`'yı çağırır. `body` dönerse
+    /// 0 döner; `context` daha sonra `switch_context` ile yüklenirse yürütme
+    /// `2:` etiketinden sürer ve dönüş değeri bağlamın `ret` alanı olur (bkz.
+    /// `fixup::catch`).
+    ///
+    /// # Güvenlik Notu
+    /// `context` geri dönüşe kadar geçerli kalmalıdır; bağlam yalnızca `body`
+    /// henüz dönmemişken yüklenebilir.
+    #[inline(always)]
+    pub unsafe fn catch_call(context: *mut TaskContext, body: extern "C" fn(*mut u8), data: *mut u8) -> usize {
+        let resumed: usize;
+        asm!(
+            // --------------------- Geri Dönüş Noktasını Kaydet ---------------------
+            "st.d $r22, $r5, {r22}",
+            "st.d $r23, $r5, {r23}",
+            "st.d $r24, $r5, {r24}",
+            "st.d $r25, $r5, {r25}",
+            "st.d $r26, $r5, {r26}",
+            "st.d $r27, $r5, {r27}",
+            "st.d $r28, $r5, {r28}",
+            "st.d $r29, $r5, {r29}",
+            "st.d $r30, $r5, {r30}",
+            "st.d $r31, $r5, {r31}",
+            "st.d $r3, $r5, {sp}",
+            // Devam noktası: bağlam yüklenirse `2:`'ye döner
+            "la.local $r12, 2f",
+            "st.d $r12, $r5, {ra}",
+
+            // --------------------- Gövdeyi Çağır ---------------------
+            "move $r4, $r7",
+            "jirl $r1, $r6, 0",
+            "move $r4, $r0",
+            "2:",
+
+            r22 = const offset_of!(TaskContext, r22),
+            r23 = const offset_of!(TaskContext, r23),
+            r24 = const offset_of!(TaskContext, r24),
+            r25 = const offset_of!(TaskContext, r25),
+            r26 = const offset_of!(TaskContext, r26),
+            r27 = const offset_of!(TaskContext, r27),
+            r28 = const offset_of!(TaskContext, r28),
+            r29 = const offset_of!(TaskContext, r29),
+            r30 = const offset_of!(TaskContext, r30),
+            r31 = const offset_of!(TaskContext, r31),
+            ra = const offset_of!(TaskContext, ra),
+            sp = const offset_of!(TaskContext, sp),
+            inout("$r5") context => _,
+            inout("$r6") body => _,
+            inout("$r7") data => _,
+            lateout("$r4") resumed,
+            clobber_abi("C"),
+        );
+        resumed
+    }
+}
+
+impl ArchContext for TaskContext {
+    /// İlk geçiş `task_start`'a zıplar; o da r23/r24'teki işlev ve argümanla
+    /// `task_entry`'yi çağırır.
+    fn new(entry: usize, arg: usize, stack_top: usize) -> Self {
+        Self {
+            r23: entry as u64,
+            r24: arg as u64,
+            // LP64: sp 16 bayt hizalıdır
+            sp: (stack_top & !0xF) as u64,
+            ra: task_start as usize as u64,
+            ..Default::default()
+        }
+    }
+
+    unsafe fn switch(&mut self, next: &Self) {
+        Self::switch_context(self, next);
+    }
+
+    unsafe fn catch(&mut self, body: extern "C" fn(*mut u8), data: *mut u8) -> usize {
+        Self::catch_call(self, body, data)
+    }
+
+    fn set_return_value(&mut self, value: usize) {
+        self.ret = value as u64;
+    }
+}
+
+extern "C" {
+    /// Yeni görevin ilk komutu (aşağıdaki `global_asm!`).
+    fn task_start();
 }
 
+core::arch::global_asm!(
+    ".global task_start",
+    "task_start:",
+    "move $r4, $r23",
+    "move $r5, $r24",
+    // Yığın geri izleme burada dursun
+    "move $r22, $r0",
+    "move $r1, $r0",
+    "bl {entry}",
+    "break 0",
+    entry = sym task_entry,
+);
+
 
 // -----------------------------------------------------------------------------
 // Görev Başlatma (Task Entry)
@@ -132,26 +215,22 @@ impl TaskContext {
 /// * `func`: Görevin gerçek giriş noktası (başlatılacak fonksiyonun adresi, r5'te olmalı).
 /// * `arg`: Göreve geçirilen u64 formatında argüman (r6'da olmalı).
 extern "C" fn task_entry(func: u64, arg: u64) -> ! {
+    crate::context::task_started();
     serial_println!("[TASK] Yeni Görev Başlatılıyor. Argüman: {:#x}", arg);
 
     // Fonksiyon işaretçisini (u64) gerçek fonksiyona dönüştür.
-    // LA64 C ABI'sinde ilk argüman r5 (a0)'ta beklenir.
+    // LA64 C ABI'sinde ilk argüman r4 (a0)'te beklenir.
     let entry_func: fn(u64) = unsafe { 
         core::mem::transmute(func as *const ()) 
     };
 
-    // Gerçek görev fonksiyonunu çağır (arg, r6 (a1)'da beklenir)
+    // Gerçek görev fonksiyonunu çağır (arg, r5 (a1)'te beklenir)
     entry_func(arg);
 
-    // Görev tamamlandı. Normalde bu noktaya gelinmemelidir.
-    serial_println!("[TASK] Görev Tamamlandı! İşlemci durduruluyor.");
-    
-    // Görev tamamlandığında, çekirdek durdurulmalıdır.
-    loop {
-        unsafe {
-            io::idle(); // LoongArch'ta bekleme talimatı
-        }
-    }
+    // Görev işlevi döndü: görev sonlandırılır ve işlemci sıradaki göreve
+    // (en azından boşta görevine) bırakılır
+    serial_println!("[TASK] Görev Tamamlandı.");
+    crate::sched::exit_current()
 }
 
 
@@ -167,4 +246,28 @@ pub fn initialize_tasking() {
     let entry_point_addr = task_entry as *const (); 
     
     serial_println!("[TASK] Task Entry Adresi: {:#x}", entry_point_addr as u64);
-}
\ No newline at end of file
+
+    // Zamanlayıcı bundan sonra görevler arasında gerçekten geçer (bkz. `context`)
+    crate::context::enable();
+}
+
+// -----------------------------------------------------------------------------
+// İŞLEMCİ BAŞINA ALAN TABANI
+// -----------------------------------------------------------------------------
+
+/// Bu işlemcinin işlemci başına alanının (`percpu`) tabanını yazar (`$r21`; ABI'de ayrılmıştır).
+///
+/// # Safety
+/// `base` bu işlemciye ayrılmış, `.percpu` şablonunun kopyası olan alanı
+/// göstermelidir.
+pub unsafe fn set_cpu_local_base(base: usize) {
+    asm!("move $r21, {}", in(reg) base, options(nostack, preserves_flags, nomem));
+}
+
+/// Bu işlemcinin işlemci başına alanının tabanı (henüz yazılmadıysa 0).
+pub fn cpu_local_base() -> usize {
+    let base: usize;
+    // SAFETY: $r21 derleyici tarafından kullanılmaz.
+    unsafe { asm!("move {}, $r21", out(reg) base, options(nostack, preserves_flags, nomem)) };
+    base
+}
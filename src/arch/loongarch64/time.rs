@@ -41,6 +41,12 @@ pub fn read_time_counter() -> Cycles {
     Cycles(cycles)
 }
 
+/// Serbest çalışan sayacın anlık değeri (bkz. `crate::clock`).
+#[inline(always)]
+pub fn read_cycles() -> Cycles {
+    read_time_counter()
+}
+
 /// TMFREQL (Timer Frequency Low) CSR'dan sayacın frekansını (Hz) döndürür.
 /// Bu, saniye başına sayım sayısıdır.
 ///
@@ -128,6 +134,25 @@ pub fn initialize_time_system() {
     }
 }
 
+// -----------------------------------------------------------------------------
+// Zamanlayıcı Karşılaştırıcısı (TCFG)
+// -----------------------------------------------------------------------------
+
+/// Sabit zamanlayıcıyı (CSR.TCFG) `deadline` değerine kadar tek seferlik
+/// sayacak şekilde kurar ve bekleyen kesmeyi CSR.TICLR ile temizler.
+/// TCFG.InitVal 4'ün katı olmalıdır; fark en az 4'e yuvarlanır.
+pub unsafe fn set_timer_deadline(deadline: Cycles) {
+    let delta = deadline.0.saturating_sub(read_time_counter().0).max(4);
+    let tcfg = (delta & !0x3) | 0x1; // En = 1, Periodic = 0
+    asm!(
+        "csrwr {0}, 0x44", // TICLR.CLR
+        "csrwr {1}, 0x41", // TCFG
+        inout(reg) 1u64 => _,
+        inout(reg) tcfg => _,
+        options(nostack, nomem)
+    );
+}
+
 // Platforma özel G/Ç fonksiyonları için bir yer tutucu (main.rs veya platformmod.rs'de tamamlanmalıdır)
 mod io {
     use core::arch::asm;
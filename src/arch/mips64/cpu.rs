@@ -0,0 +1,48 @@
+// src/arch/mips64/cpu.rs
+// MIPS 64 işlemci özelliği algılama: PRId ve Config1/Config3.
+
+#![allow(dead_code)]
+
+use core::arch::asm;
+use crate::cpu::CpuFeatures;
+
+/// Config[31] - sonraki Config yazmacı var.
+const CONFIG_M: u32 = 1 << 31;
+/// Config1[0] - FPU.
+const CONFIG1_FP: u32 = 1 << 0;
+/// Config3[28] - MSA (SIMD).
+const CONFIG3_MSAP: u32 = 1 << 28;
+
+pub fn detect() -> CpuFeatures {
+    let (prid, config, config1): (u32, u32, u32);
+    let mut config3 = 0u32;
+    // SAFETY: PRId ve Config0/1 her MIPS64 işlemcisinde vardır; Config2/3
+    // yalnızca önceki yazmacın M biti ayarlıysa okunur.
+    unsafe {
+        asm!("mfc0 {}, $15, 0", out(reg) prid, options(nomem, nostack));
+        asm!("mfc0 {}, $16, 0", out(reg) config, options(nomem, nostack));
+        asm!("mfc0 {}, $16, 1", out(reg) config1, options(nomem, nostack));
+        if config & CONFIG_M != 0 && config1 & CONFIG_M != 0 {
+            let config2: u32;
+            asm!("mfc0 {}, $16, 2", out(reg) config2, options(nomem, nostack));
+            if config2 & CONFIG_M != 0 {
+                asm!("mfc0 {}, $16, 3", out(reg) config3, options(nomem, nostack));
+            }
+        }
+    }
+    CpuFeatures {
+        id: prid as u64,
+        fpu: config1 & CONFIG1_FP != 0,
+        simd: config3 & CONFIG3_MSAP != 0,
+        aes: false,
+        sha2: false,
+        // MIPS R6 CRC32 uzantısı Config5'te bildirilir; kullanılmıyor
+        crc32: false,
+        // LL/SC tek talimatlı RMW değildir
+        atomics: false,
+        // Yazılım TLB; sayfa tablosu ve büyük sayfa eşlemesi kullanılmaz
+        large_pages: false,
+        huge_pages: false,
+        rng: false,
+    }
+}
@@ -0,0 +1,86 @@
+// src/arch/mips64/debug.rs
+// MIPS64 mimarisine özgü kesme noktası ve gözlem noktası desteği.
+
+#![allow(dead_code)]
+
+use core::arch::asm;
+use crate::debug::{WatchKind, ERR_NO_HW_SINGLE_STEP};
+use crate::platformgeneric::KernelError;
+
+/// `break` talimatı (kod 0).
+pub const BREAKPOINT_INSN: u32 = 0x0000_000D;
+
+/// Kesme noktası talimatının uzunluğu (bayt).
+pub const BREAKPOINT_INSN_LEN: usize = 4;
+
+/// EPC `break` talimatının kendisini gösterir (gecikme yuvası dışında).
+pub const BREAKPOINT_PC_ADJUST: usize = 0;
+
+/// `synci` ile değiştirilen önbellek satırını talimat önbelleğine yansıtır.
+#[inline(always)]
+pub unsafe fn sync_icache(addr: usize, _len: usize) {
+    asm!("synci 0({0})", "sync", in(reg) addr, options(nostack));
+}
+
+/// MIPS64'te donanım tek adım yalnızca EJTAG hata ayıklama modunda vardır.
+pub fn set_single_step(_status: &mut u64, enable: bool) -> Result<(), KernelError> {
+    if enable {
+        Err(KernelError::PlatformSpecificError(ERR_NO_HW_SINGLE_STEP))
+    } else {
+        Ok(())
+    }
+}
+
+// -----------------------------------------------------------------------------
+// DONANIM GÖZLEM NOKTASI (CP0 WatchLo / WatchHi)
+// -----------------------------------------------------------------------------
+
+/// Select 0 gözlem yazmaç çifti kullanılır (Config1.WR).
+pub const MAX_WATCHPOINTS: usize = 1;
+
+/// Watch istisnası (ExcCode 23) erişimden önce alınır.
+pub const WATCH_BEFORE_ACCESS: bool = true;
+
+// WatchLo alanları
+const WATCHLO_W: u64 = 1 << 0; // Yazmada eşleş
+const WATCHLO_R: u64 = 1 << 1; // Okumada eşleş
+// WatchHi alanları
+const WATCHHI_G: u64 = 1 << 30;     // ASID'den bağımsız eşleş
+const WATCHHI_HIT_MASK: u64 = 0x7;  // I/R/W durum bitleri (1 yazarak temizlenir)
+
+/// Çift kelime hizalı adresi ve erişim türünü WatchLo'ya, global eşleşmeyi WatchHi'ye yazar.
+/// Adresin [2:0] bitleri donanımca yok sayılır; çift kelimenin tamamı izlenir.
+pub unsafe fn set_watchpoint(_slot: usize, addr: usize, _len: usize, kind: WatchKind) -> Result<(), KernelError> {
+    let access = match kind {
+        WatchKind::Read => WATCHLO_R,
+        WatchKind::Write => WATCHLO_W,
+        WatchKind::ReadWrite => WATCHLO_R | WATCHLO_W,
+    };
+    asm!(
+        "dmtc0 {hi}, $19",
+        "dmtc0 {lo}, $18",
+        "ehb",
+        hi = in(reg) WATCHHI_G | WATCHHI_HIT_MASK,
+        lo = in(reg) ((addr & !7) as u64) | access,
+        options(nostack)
+    );
+    Ok(())
+}
+
+/// WatchLo'yu sıfırlayarak eşleşmeyi kapatır.
+pub unsafe fn clear_watchpoint(_slot: usize) {
+    asm!("dmtc0 $zero, $18", "ehb", options(nostack));
+}
+
+/// WatchHi durum bitleri ayarlıysa yuva 0'ı döndürür ve bitleri temizler.
+pub fn watchpoint_hit() -> Option<usize> {
+    unsafe {
+        let watch_hi: u64;
+        asm!("dmfc0 {0}, $19", out(reg) watch_hi, options(nostack));
+        if watch_hi & WATCHHI_HIT_MASK == 0 {
+            return None;
+        }
+        asm!("dmtc0 {0}, $19", in(reg) watch_hi, options(nostack));
+    }
+    Some(0)
+}
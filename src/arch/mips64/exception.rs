@@ -1,32 +1,28 @@
 use core::arch::asm;
 use core::fmt;
 use crate::serial_println;
+use crate::trap::{handle_fatal, Access, Cause};
+use super::vectors::mips64_vectors;
 
 // -----------------------------------------------------------------------------
-// HARİCİ MONTAJ DİLİ İŞLEYİCİLERİ
+// İSTİSNA VEKTÖRLERİ
 // -----------------------------------------------------------------------------
 
-extern "C" {
-    /// Tüm istisnalar için ortak giriş noktası (Montaj kodu).
-    /// Bu fonksiyon, 0x8000_0000_0000_0180 adresinde (Genel İstisna Vektörü) 
-    /// veya EBase yazmacı tarafından belirlenen adreste bulunmalıdır.
-    fn exception_entry();
-
-    /// TLB Miss istisnaları için özel giriş noktası (Montaj kodu).
-    /// Bu, 0x8000_0000_0000_0000 adresinde bulunmalıdır (Vektör Tablosu).
-    fn tlb_exception_entry();
-}
+// Vektörler (TLB yenileme, XTLB yenileme, önbellek hatası, genel istisna,
+// kesme) `vectors.rs`'de `trap_vectors!` ile üretilir ve EBase'in gösterdiği
+// 4 KiB hizalı tabloya yerleşir. Önbellek hatası dışındaki tüm girdiler
+// `generic_exception_handler`'ı çağırır; neden CP0.Cause'dan okunur.
 
 // -----------------------------------------------------------------------------
 // 1. İSTİSNA KAYIT YAPILARI
 // -----------------------------------------------------------------------------
 
 /// MIPS64'te istisna oluştuğunda yığına kaydedilen CPU durumu (Context).
-/// Bu yapının düzeni, montaj kodunun registerları yığına kaydettiği sıraya UYMALIDIR.
+/// Bu yapının düzeni, `vectors.rs`'deki ortak kodun registerları yığına kaydettiği sıraya UYMALIDIR.
 #[repr(C)]
 pub struct ExceptionContext {
-    // Montaj kodunun kaydettiği tüm genel amaçlı registerlar (r0-r31)
-    // MIPS çağrı kuralına göre $0 (zero) genellikle kaydedilmez.
+    // Ortak kodun kaydettiği genel amaçlı registerlar: gpr[n - 1] = $n (1-31).
+    // $0 (zero) kaydedilmez; k0/k1 ($26/$27) kaydedilmez ve sıfır okunur.
     pub gpr: [u64; 31], 
     
     // Kontrol ve Durum Yazmaçları (CP0)
@@ -36,6 +32,15 @@ pub struct ExceptionContext {
     pub cp0_status: u64, // İşlemci durum yazmacı (Kesme durumu vb.)
 }
 
+// Ofsetler `vectors.rs`'deki `mips64_common` gövdesine gömülüdür.
+crate::asm_layout!(ExceptionContext {
+    gpr: 0,
+    cp0_epc: 248,
+    cp0_badvaddr: 256,
+    cp0_cause: 264,
+    cp0_status: 272,
+}, size <= 288);
+
 /// İstisna nedenleri (CP0.CAUSE yazmacının 2-6. bitleri).
 #[repr(u64)]
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -72,6 +77,31 @@ impl From<u64> for ExceptionCause {
     }
 }
 
+/// Ham CP0.CAUSE ve BadVAddr değerlerini ortak `trap::Cause` tipine çevirir.
+pub fn decode_cause(cp0_cause: u64, cp0_badvaddr: u64) -> Cause {
+    let addr = cp0_badvaddr as usize;
+
+    match (cp0_cause >> 2) & 0x1F {
+        // CAUSE.IP (8-15. bitler) bekleyen kesme hatlarıdır
+        0 => Cause::Interrupt(((cp0_cause >> 8) & 0xFF).trailing_zeros()),
+        1 | 3 => Cause::PageFault { access: Access::Write, addr }, // TLB Mod / TLB Store
+        2 => Cause::PageFault { access: Access::Read, addr },      // TLB Load (veya talimat getirme)
+        4 | 5 => Cause::Misaligned { addr },
+        6 | 7 => Cause::MachineError,                              // Bus Hatası
+        8 => Cause::Syscall,
+        9 => Cause::Breakpoint,
+        10 => Cause::IllegalInstruction,                           // Reserved Instruction
+        13 => Cause::Breakpoint,                                   // Trap (teq vb.)
+        // Watch: BadVAddr güncellenmez; adres gözlem tablosundan alınır
+        23 => match crate::debug::watchpoint_hit() {
+            Some(watched) => Cause::Watchpoint { addr: watched },
+            None => Cause::Unknown(23),
+        },
+        24 => Cause::MachineError,                                 // Machine Check
+        code => Cause::Unknown(code),
+    }
+}
+
 // -----------------------------------------------------------------------------
 // 2. TEMEL İŞLEYİCİ FONKSİYONLARI (Rust Kodu)
 // -----------------------------------------------------------------------------
@@ -88,6 +118,12 @@ pub extern "C" fn generic_exception_handler(context: &mut ExceptionContext) {
         }
         ExceptionCause::TLB_Load | ExceptionCause::TLB_Store | ExceptionCause::TLB_Mod => {
             // TLB ve Sayfa Hatası İşleyicileri
+            // Kullanıcı belleği erişimi veya bellek yoklaması ise düzeltme tablosuna bak
+            let trap_cause = decode_cause(context.cp0_cause, context.cp0_badvaddr);
+            if crate::fixup::fixup_exception(trap_cause, &mut context.cp0_epc) {
+                return;
+            }
+
             serial_println!("\n--- TLB/SAYFA HATASI ---");
             serial_println!("Neden: {:?}", cause);
             serial_println!("Hata Adresi (BadVAddr): {:#x}", context.cp0_badvaddr);
@@ -96,7 +132,7 @@ pub extern "C" fn generic_exception_handler(context: &mut ExceptionContext) {
             // Eğer TLB Load/Store Miss ise, TLB'yi doldurmayı deneyin.
             // Bu, sanal bellek yöneticisi modülünde yapılmalıdır.
             
-            panic!("Kritik TLB Hatası!");
+            handle_fatal(trap_cause, context.cp0_epc as usize);
         }
         ExceptionCause::Syscall => {
             // Sistem Çağrısı
@@ -107,19 +143,52 @@ pub extern "C" fn generic_exception_handler(context: &mut ExceptionContext) {
             context.cp0_epc += 4; 
         }
         _ => {
+            // Kesme noktası (break) ve gözlem noktası önce hata ayıklayıcıya sunulur.
+            let trap_cause = decode_cause(context.cp0_cause, context.cp0_badvaddr);
+            if crate::debug::handle_trap(trap_cause, &mut context.cp0_epc, &mut context.cp0_status) {
+                return;
+            }
+
+            // Adres hatası (hizalama) ve veri yolu hataları için düzeltme tablosuna bak
+            if crate::fixup::fixup_exception(trap_cause, &mut context.cp0_epc) {
+                return;
+            }
+
             // Diğer senkron istisnalar
             serial_println!("\n--- KRİTİK İSTİSNA ---");
             serial_println!("Neden Kodu: {:#x} ({:?})", context.cp0_cause, cause);
             serial_println!("EPC: {:#x}", context.cp0_epc);
             serial_println!("Durum (Status): {:#x}", context.cp0_status);
             
-            panic!("İşlenmemiş İstisna!");
+            handle_fatal(trap_cause, context.cp0_epc as usize);
         }
     }
 }
 
+/// Önbellek hatası vektörü (0x100) için işleyici; her zaman ölümcüldür.
+///
+/// Önbellek hatası ERL ile alınır ve dönüş adresi ErrorEPC'dedir; bozuk
+/// önbellek satırından sonra güvenle devam edilemez.
+#[no_mangle]
+pub extern "C" fn generic_cache_error_handler(context: &mut ExceptionContext) {
+    let cache_err: u64;
+    let error_epc: u64;
+    unsafe {
+        asm!("mfc0 {}, $27, 0", out(reg) cache_err); // CacheErr
+        asm!("dmfc0 {}, $30, 0", out(reg) error_epc); // ErrorEPC
+    }
+
+    serial_println!("\n--- ÖNBELLEK HATASI ---");
+    serial_println!("CacheErr: {:#x}", cache_err);
+    serial_println!("ErrorEPC: {:#x}", error_epc);
+    serial_println!("Durum (Status): {:#x}", context.cp0_status);
+
+    handle_fatal(Cause::MachineError, error_epc as usize);
+}
+
 /// Donanım Kesmeleri (IRQ) için özel işleyici.
 fn handle_interrupt(context: &mut ExceptionContext) {
+    let _irq = crate::irqstack::enter(context as *const _ as usize);
     // CP0.CAUSE.IP (Kesme Bekleyen Bitler) ve CP0.STATUS.IM (Kesme Maskesi) oku
     let pending_interrupts = (context.cp0_cause >> 8) & 0xFF; // IP[0-7]
     let interrupt_mask = (context.cp0_status >> 8) & 0xFF; // IM[0-7]
@@ -128,9 +197,30 @@ fn handle_interrupt(context: &mut ExceptionContext) {
 
     if active_interrupts != 0 {
         // 1. Hangi Kesmenin geldiğini belirle.
-        // 2. Uygun sürücüyü çağır.
-
-        // serial_print!("|");
+        // IP7: Count == Compare; Compare yeniden yazılınca temizlenir.
+        if active_interrupts & (1 << 7) != 0 {
+            crate::hrtimer::timer_interrupt();
+        }
+        // 2. İşleyicisi olmayan hatlar karantinaya alınır: bir kez raporlanır
+        // ve IM biti temizlenir. Dönüşte kaydedilen Status geri yüklendiği
+        // için maske orada da uygulanır; aksi halde hat hemen yeniden gelir.
+        let unhandled = active_interrupts & !(1 << 7);
+        for line in (0..8).filter(|line| unhandled & (1 << line) != 0) {
+            let masked = crate::irqquarantine::quarantine(
+                line as u32,
+                format_args!(
+                    "CP0 Cause {:#x} (IP {:#04x}) Status {:#x} (IM {:#04x})",
+                    context.cp0_cause, pending_interrupts, context.cp0_status, interrupt_mask
+                ),
+                format_args!(
+                    "EPC {:#x} BadVAddr {:#x} ra {:#x} sp {:#x}",
+                    context.cp0_epc, context.cp0_badvaddr, context.gpr[30], context.gpr[28]
+                ),
+            );
+            if masked {
+                context.cp0_status &= !(1 << (8 + line));
+            }
+        }
     
         // 3. Kesmenin bittiğini (EOI) Kesme Kontrolcüsüne bildir (Gerekirse).
         // MIPS'te yerel kesmeler için EOI, CP0.Status yazmacı temizlenerek yapılır.
@@ -151,13 +241,12 @@ pub fn init_exceptions() {
     unsafe {
         // 1. İstisna giriş adreslerini (Vektör Tablosu) kur.
         
-        // Loongson gibi bazı MIPS türevlerinde EBase kullanılır.
-        // EBase'i genel istisna giriş noktasına ayarla.
-        let entry_addr = exception_entry as u64;
+        // EBase'i üretilen vektör tablosuna ayarla (4 KiB hizalı, KSEG0).
+        let entry_addr = mips64_vectors as usize as u64;
 
         // CP0.EBase yazmacına yazma
-        // CR 26 (EBase), 0. Selektör (0)
-        asm!("mtc0 {}, $26, 0", in(reg) entry_addr);
+        // CR 15 (EBase), 1. Selektör
+        asm!("mtc0 {}, $15, 1", in(reg) entry_addr);
         
         // 2. CP0.Status yazmacını ayarla (Kesmeleri etkinleştirme).
         let mut status: u64;
@@ -180,4 +269,20 @@ pub fn init_exceptions() {
     
     serial_println!("[MIPS64] İstisna Giriş Noktası (EBase) yüklendi.");
     serial_println!("[MIPS64] Harici kesmeler (IE) etkinleştirildi.");
-}
\ No newline at end of file
+}
+
+// -----------------------------------------------------------------------------
+// 4. KESME YIĞINI
+// -----------------------------------------------------------------------------
+
+/// Kesmeler kesilen görevin yığınında çalışır; giriş kodu yığın değiştirmez
+/// (bkz. `crate::irqstack`).
+pub const SWITCHES_IRQ_STACK: bool = false;
+
+/// Ayrı kesme yığını olmadığından etkisizdir.
+///
+/// # Safety
+/// Diğer portlarla aynı sözleşme: `top` bu işlemciye ait bir yığının üstü olmalıdır.
+pub unsafe fn install_irq_stack(_top: usize) -> Result<(), crate::platformgeneric::KernelError> {
+    Ok(())
+}
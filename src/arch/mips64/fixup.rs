@@ -0,0 +1,79 @@
+// src/arch/mips64/fixup.rs
+// MIPS 64 hata güvenli bellek yoklama talimatları.
+
+#![allow(dead_code)]
+
+use core::arch::asm;
+
+/// Tek bayt okur; erişim hatasında `None` döner.
+pub unsafe fn probe_read_u8(addr: usize) -> Option<u8> {
+    let value: u64;
+    let err: u64;
+    asm!(
+        "li {err}, 0",
+        "2: lbu {val}, 0({addr})",
+        "3:",
+        ".pushsection .text.fixup, \"ax\"",
+        "4: li {err}, 1",
+        "li {val}, 0",
+        "b 3b", // gecikme yuvasını assembler doldurur (reorder kipi)
+        ".popsection",
+        ".pushsection .ex_table, \"a\"",
+        ".balign 8",
+        ".dword 2b, 4b",
+        ".popsection",
+        addr = in(reg) addr,
+        val = out(reg) value,
+        err = out(reg) err,
+        options(nostack, readonly)
+    );
+    if err == 0 { Some(value as u8) } else { None }
+}
+
+/// Tek bayt yazar; erişim hatasında `false` döner.
+pub unsafe fn probe_write_u8(addr: usize, value: u8) -> bool {
+    let err: u64;
+    asm!(
+        "li {err}, 0",
+        "2: sb {val}, 0({addr})",
+        "3:",
+        ".pushsection .text.fixup, \"ax\"",
+        "4: li {err}, 1",
+        "b 3b", // gecikme yuvasını assembler doldurur (reorder kipi)
+        ".popsection",
+        ".pushsection .ex_table, \"a\"",
+        ".balign 8",
+        ".dword 2b, 4b",
+        ".popsection",
+        addr = in(reg) addr,
+        val = in(reg) u64::from(value),
+        err = out(reg) err,
+        options(nostack)
+    );
+    err == 0
+}
+
+/// 32 bit okur; erişim veya veri yolu hatasında `None` döner.
+pub unsafe fn probe_read_u32(addr: usize) -> Option<u32> {
+    let value: u64;
+    let err: u64;
+    asm!(
+        "li {err}, 0",
+        "2: lwu {val}, 0({addr})",
+        "3:",
+        ".pushsection .text.fixup, \"ax\"",
+        "4: li {err}, 1",
+        "li {val}, 0",
+        "b 3b", // gecikme yuvasını assembler doldurur (reorder kipi)
+        ".popsection",
+        ".pushsection .ex_table, \"a\"",
+        ".balign 8",
+        ".dword 2b, 4b",
+        ".popsection",
+        addr = in(reg) addr,
+        val = out(reg) value,
+        err = out(reg) err,
+        options(nostack, readonly)
+    );
+    if err == 0 { Some(value as u32) } else { None }
+}
@@ -0,0 +1,23 @@
+// src/arch/mips64/hypervisor.rs
+// MIPS 64 hypervisor algılama.
+//
+// MIPS için standart bir hypervisor algılama arayüzü yoktur (VZ uzantısı
+// misafire kendini bildirmez); QEMU Malta/virt çıplak donanım gibi davranır.
+
+#![allow(dead_code)]
+
+use crate::hypervisor::{HypervisorKind, Paravirt};
+
+pub fn detect() -> HypervisorKind {
+    HypervisorKind::None
+}
+
+pub unsafe fn enable_paravirt(_kind: HypervisorKind) -> Paravirt {
+    Paravirt::default()
+}
+
+pub fn pv_clock_ns() -> u64 {
+    0
+}
+
+pub fn pv_console_write_byte(_byte: u8) {}
@@ -170,4 +170,78 @@ pub fn init_interrupts() {
         write_status(status);
     }
     serial_println!("[MIPS64] Kesme Kontrolü (CP0) başlatıldı.");
-}
\ No newline at end of file
+}
+
+// -----------------------------------------------------------------------------
+// YEREL KESME MASKESİ
+// -----------------------------------------------------------------------------
+
+// Genel kod bunları `crate::irqoff` üzerinden kullanır. `asm!` bloklarında
+// `nomem` yoktur: maske değişikliği derleyici için bir bellek engelidir,
+// kilitli bölgenin erişimleri dışarı taşınmaz.
+
+/// Genel kesmeleri (Status.IE) kapatır; önceki Status değerini döndürür.
+///
+/// Oku-değiştir-yaz dizisi kesilebilir; araya giren işleyici Status'u
+/// değiştirmeden döndüğü için sonuç doğrudur.
+#[inline(always)]
+pub fn local_irq_save() -> usize {
+    // SAFETY: Yalnızca Status.IE temizlenir.
+    unsafe {
+        let status = read_status();
+        write_status(status & !STATUS_IE);
+        status as usize
+    }
+}
+
+/// `local_irq_save` öncesinde IE açıksa yeniden açar.
+#[inline(always)]
+pub fn local_irq_restore(flags: usize) {
+    if irqs_were_enabled(flags) {
+        enable_interrupts();
+    }
+}
+
+/// Kaydedilen durumda Status.IE açık mıydı?
+#[inline(always)]
+pub fn irqs_were_enabled(flags: usize) -> bool {
+    flags as u64 & STATUS_IE != 0
+}
+
+/// Genel kesmeleri koşulsuz açar.
+#[inline(always)]
+pub fn local_irq_enable() {
+    enable_interrupts();
+}
+
+// -----------------------------------------------------------------------------
+// HAT MASKESİ VE PCI INTx
+// -----------------------------------------------------------------------------
+
+/// Kesme hattını denetleyicide maskeler veya açar (bkz. `crate::irqstorm`);
+/// `irq` dağıtıcının gördüğü numaradır. Desteklenmiyorsa `false`.
+pub fn set_irq_masked(irq: u32, masked: bool) -> bool {
+    // IP0-7: Status.IM (bit 8 + hat); yalnızca bu işlemcide etkilidir. Kesme
+    // dönüşünde kaydedilen Status geri yüklenir, dağıtıcı onu da günceller.
+    if irq >= 8 {
+        return false;
+    }
+    if masked {
+        mask_irq(irq as u8);
+    } else {
+        unmask_irq(irq as u8);
+    }
+    true
+}
+
+/// PCI aygıtının INTx hattını açar ve dağıtıcının göreceği kesme numarasını
+/// döndürür. INTx eşlemesi yoksa `None`; sürücü yoklamayla çalışır.
+pub fn route_pci_intx(_line: u8) -> Option<u32> {
+    None
+}
+
+/// Aygıt ağacı düğümünün ilk kesmesini denetleyicinin göreceği numaraya
+/// çevirir. Bu portun denetleyicisi tek hücreli kesme belirteci kullanır.
+pub fn decode_fdt_irq(node: &crate::fdt::Node) -> Option<u32> {
+    node.interrupt_cell(0)
+}
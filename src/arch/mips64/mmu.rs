@@ -4,6 +4,7 @@
 use core::arch::asm;
 use core::ptr::NonNull;
 use crate::serial_println;
+use crate::memory::vmaudit::{validate_mapping, CacheAttr, MapError, Mapping, Perms};
 use super::io; // Bariyerler için io modülünü kullanacağız
 
 // -----------------------------------------------------------------------------
@@ -104,14 +105,32 @@ unsafe fn tlb_clear_all() {
 /// * `virtual_addr`: Sanal adres (Çift sayfa çifti için çift olmalıdır).
 /// * `phys_addr_low`: Sanal adrese karşılık gelen fiziksel adres (Çift sayfa çiftinin altı).
 /// * `phys_addr_high`: Sanal adrese +4K karşılık gelen fiziksel adres (Çift sayfa çiftinin üstü).
-/// * `flags`: Sayfa bayrakları.
+/// * `flags`: Sayfa bayrakları (EntryLo'nun alt 6 biti: G, V, D, C).
+///
+/// # Hatalar
+/// * `OutOfRange`: İndeks TLB boyutunu aşıyor veya fiziksel adres 36 biti
+///   (EntryLo PFN alanı) aşıyor. Sanal adresin geçerliliği segmente bağlıdır,
+///   burada denetlenmez.
+/// * `Unaligned`: Sanal adres 8K, fiziksel adresler 4K hizalı değil.
+/// * `InvalidFlags`: Bayraklar PFN alanına taşıyor.
 pub unsafe fn map_tlb_entry(
     tlb_index: u32,
     virtual_addr: usize,
     phys_addr_low: usize,
     phys_addr_high: usize,
     flags: u64,
-) {
+) -> Result<(), MapError> {
+    if tlb_index as usize >= TLB_ENTRY_COUNT {
+        return Err(MapError::OutOfRange);
+    }
+    // Giriş çift sayfa eşler; sanal adres 8K sınırında olmalıdır
+    if !virtual_addr.is_multiple_of(PAGE_SIZE * 2) {
+        return Err(MapError::Unaligned);
+    }
+    const FLAG_BITS: u64 = (1 << 6) - 1;
+    validate_mapping(virtual_addr, phys_addr_low, PAGE_SIZE, 64, 36, flags, !FLAG_BITS)?;
+    validate_mapping(virtual_addr, phys_addr_high, PAGE_SIZE, 64, 36, flags, !FLAG_BITS)?;
+
     // 1. PageMask (Sayfa Boyutu) ayarla
     // 4K sayfa için PageMask = 0 (12. bit 0, 13. bit 1) -> 0x0
     write_cp0(CP0_PAGE_MASK, 0); 
@@ -137,6 +156,7 @@ pub unsafe fn map_tlb_entry(
     tlb_write();
     
     io::sync(); // Senkronizasyon bariyeri
+    Ok(())
 }
 
 
@@ -157,12 +177,15 @@ pub unsafe fn enable_paging() {
     // Her TLB girişi 8KB eşlediği için 8KB adımlarla döngü yapılır
     let mut tlb_idx = 0;
     for addr in (0..identity_mapping_size).step_by(PAGE_SIZE * 2) {
-        if tlb_idx >= 64 { break; } // TLB doldu
+        if tlb_idx as usize >= TLB_ENTRY_COUNT { break; } // TLB doldu
         
         let phys_low = addr;
         let phys_high = addr + PAGE_SIZE;
 
-        map_tlb_entry(tlb_idx, addr, phys_low, phys_high, flags);
+        if let Err(e) = map_tlb_entry(tlb_idx, addr, phys_low, phys_high, flags) {
+            serial_println!("[MIPS64] {:#x} eşlenemedi ({:?}); sanal adresleme etkinleştirilmedi.", addr, e);
+            return;
+        }
         tlb_idx += 1;
     }
     
@@ -193,4 +216,104 @@ pub fn init_mmu() {
     unsafe {
         enable_paging();
     }
-}
\ No newline at end of file
+}
+
+// -----------------------------------------------------------------------------
+// TLB DENETİMİ (vm dump)
+// -----------------------------------------------------------------------------
+
+/// Denetlenen TLB giriş sayısı (`tlb_clear_all` ile aynı varsayım).
+const TLB_ENTRY_COUNT: usize = 64;
+
+/// EntryLo içindeki PFN alanı (bit 6'dan başlar).
+const ENTRY_LO_PFN_MASK: u64 = 0x03FF_FFFF_FFFF_FFC0;
+/// EntryLo yürütme yasağı (XI) biti.
+const ENTRY_LO_XI: u64 = 1 << 62;
+/// Kullanıcı segmenti (xuseg) üst sınırı.
+const XUSEG_END: usize = 0x4000_0000_0000_0000;
+
+/// Tek bir EntryLo değerini (4K yarım çift) eşleme olarak bildirir.
+fn visit_entry_lo(vaddr: usize, lo: u64, visit: &mut dyn FnMut(Mapping)) {
+    if lo & EntryLoFlags::VALID as u64 == 0 {
+        return;
+    }
+    let cache = match (lo >> 3) & 0x7 {
+        3 => CacheAttr::WriteBack,
+        1 | 2 => CacheAttr::Uncached,
+        _ => CacheAttr::WriteThrough,
+    };
+    visit(Mapping {
+        vaddr,
+        paddr: ((lo & ENTRY_LO_PFN_MASK) << 6) as usize,
+        size: PAGE_SIZE,
+        perms: Perms {
+            read: true,
+            write: lo & EntryLoFlags::DIRTY as u64 != 0,
+            exec: lo & ENTRY_LO_XI == 0,
+            user: vaddr < XUSEG_END,
+        },
+        cache,
+    });
+}
+
+/// MIPS'te donanım sayfa tablosu yoktur; etkin çeviriler TLB'dedir. Tüm TLB
+/// girişleri `tlbr` ile okunur, sanal adrese göre sıralanır ve bildirilir.
+pub fn walk_mappings(visit: &mut dyn FnMut(Mapping)) {
+    let mut entries = [(0usize, 0u64, 0u64); TLB_ENTRY_COUNT];
+    let mut count = 0;
+
+    unsafe {
+        // tlbr EntryHi'yi (ASID dahil) ezer; çıkışta geri yüklenir.
+        let saved_hi = read_cp0(CP0_ENTRY_HI);
+
+        for index in 0..TLB_ENTRY_COUNT {
+            write_cp0(CP0_INDEX, index as u64);
+            asm!("tlbr", options(nomem, nostack));
+
+            let vpn2 = (read_cp0(CP0_ENTRY_HI) & 0xFFFF_FFFF_FFFF_E000) as usize;
+            let lo0 = read_cp0(CP0_ENTRY_LO0);
+            let lo1 = read_cp0(CP0_ENTRY_LO1);
+            if (lo0 | lo1) & EntryLoFlags::VALID as u64 != 0 {
+                entries[count] = (vpn2, lo0, lo1);
+                count += 1;
+            }
+        }
+
+        write_cp0(CP0_ENTRY_HI, saved_hi);
+        io::sync();
+    }
+
+    let entries = &mut entries[..count];
+    entries.sort_unstable_by_key(|e| e.0);
+
+    for &(vaddr, lo0, lo1) in entries.iter() {
+        visit_entry_lo(vaddr, lo0, visit);
+        visit_entry_lo(vaddr + PAGE_SIZE, lo1, visit);
+    }
+}
+
+// -----------------------------------------------------------------------------
+// KULLANICI SAYFALARI (mmap)
+// -----------------------------------------------------------------------------
+
+/// Kullanıcı sayfası eşlemesi bu portta henüz yok; `mmap` dosya eşlemeleri
+/// `PlatformSpecificError` ile reddedilir.
+///
+/// # Safety
+/// Diğer portlarla aynı sözleşme (bkz. `rv64i::mmu::map_user_page`).
+pub unsafe fn map_user_page(_virtual_addr: usize, _physical_addr: usize, _write: bool, _exec: bool) -> Result<(), crate::platformgeneric::KernelError> {
+    Err(crate::platformgeneric::KernelError::PlatformSpecificError(0))
+}
+
+/// Kullanıcı sayfası eşlenemediğinden etkisizdir.
+pub unsafe fn unmap_user_page(_virtual_addr: usize) {}
+
+// -----------------------------------------------------------------------------
+// AYGIT PENCERELERİ (physmap)
+// -----------------------------------------------------------------------------
+
+/// Büyük sayfalı çekirdek eşlemesi bu portta henüz yok; `physmap::map_mmio`
+/// `PlatformSpecificError` ile reddedilir.
+pub fn map_device(_phys: usize, _len: usize) -> Result<usize, crate::platformgeneric::KernelError> {
+    Err(crate::platformgeneric::KernelError::PlatformSpecificError(0))
+}
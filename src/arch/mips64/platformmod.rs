@@ -125,13 +125,27 @@ pub fn platform_init() {
     let current_status = unsafe { io::read_cp0(io::CP0_STATUS, 0) };
     serial_println!("[MIPS64] Başlangıç STATUS Değeri: {:#x}", current_status);
 
-    // 3. Temsili MMU/Önbellek ayarları (gelişmiş kodda buraya eklenir)
-    // MMU'nun başlatılması ayrı `mmu.rs` modülünde yapılır.
-
-    // 4. Talimat senkronizasyonu
+    // 3. Talimat senkronizasyonu
     unsafe {
         io::sync();
     }
 
+    // Errata ve kart tuhaflıkları (bkz. `quirks`) alt sistemlerden önce uygulanır.
+    crate::quirks::apply();
+
+    // 4. Diğer alt sistemleri başlat (MMU, İstisnalar, Zamanlayıcı, vb.)
+    // Alt sistemler kendilerini `initcall!` ile kaydeder; sıra tüm mimarilerde aynıdır.
+    crate::initcall::run_initcalls();
+
     serial_println!("[MIPS64] Temel Platform Hazır.");
-}
\ No newline at end of file
+}
+
+// -----------------------------------------------------------------------------
+// ÖNYÜKLEME DEVRİ
+// -----------------------------------------------------------------------------
+
+/// Porta özgü devir yapısı yok; `false` döner ve `crate::boot` aygıt ağacını
+/// veya OpenFirmware'ı dener.
+pub fn parse_boot_handoff(_regs: &crate::boot::BootRegs, _info: &mut crate::boot::BootInfo) -> bool {
+    false
+}
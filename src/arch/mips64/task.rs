@@ -2,6 +2,8 @@
 // MIPS 64 (MIPS64) mimarisine özgü görev (task) ve bağlam (context) yönetimi.
 
 use core::arch::asm;
+use core::mem::offset_of;
+use crate::context::ArchContext;
 use crate::serial_println;
 
 /// Görev bağlamını (task context) saklamak için kullanılan yapı.
@@ -28,91 +30,174 @@ pub struct TaskContext {
     
     // Yığın İşaretçisi (r29) - Görevin yeni yığınının adresi.
     r29: u64, // sp (Stack Pointer) 
+
+    // Görev devam ettiğinde v0'da (r2) göreceği değer (`set_return_value`).
+    ret: u64,
 }
 
 impl TaskContext {
-    /// Yeni bir görev bağlamı oluşturur.
-    /// 
-    /// # Argümanlar
-    /// * `stack_top`: Görevin yığınının en üst adresi.
-    /// * `entry_point`: Görevin başlayacağı fonksiyonun adresi.
-    pub fn new(stack_top: u64, entry_point: u64) -> Self {
-        // Yeni bir görev başlatıldığında, anahtarlama kodu bu yapıyı yükler.
-        Self {
-            // Callee-Saved yazmaçlar sıfırlanır.
-            ..Default::default()
-            
-            // r29 (sp), yığının üstü olarak ayarlanır.
-            r29: stack_top,
-            
-            // r31 (ra) ve pc (entry_point), görevin ilk başlayacağı adres olarak ayarlanır.
-            // Anahtarlama `jr ra` ile görev döndüğünde, bu ra'ya zıplayacaktır.
-            r31: entry_point, 
-        }
-    }
-
-    /// Bir bağlam anahtarlama işlemi sırasında yazmaç durumunu kaydetmek ve 
-    /// yeni görevden yazmaç durumunu yüklemek için kullanılan harici assembly fonksiyonu.
+    /// s0-s8'i, sp'yi ve dönüş noktasını `old_context`'e kaydeder,
+    /// `new_context`'ten yükler ve onun ra alanındaki adrese zıplar. Eski görev
+    /// yeniden seçildiğinde `2:` etiketinden, yani bu çağrının sonundan devam
+    /// eder. Gecikme yuvalarını montajcı doldurur (`reorder` kipi).
     ///
     /// # Güvenlik Notu
-    /// Bu fonksiyon, C ABI'sine uymalıdır: `fn switch_context(old_context: *mut TaskContext, new_context: *const TaskContext)`.
-    /// Argümanlar r4 ve r5'e (a0 ve a1) geçirilir.
+    /// İki işaretçi de geçiş boyunca geçerli olmalıdır; `new_context` ya
+    /// `ArchContext::new` ile kurulmuş ya da daha önce kaydedilmiş olmalıdır.
+    /// $30 (fp) işlenen olarak verilemez; blok onu kendisi kaydedip yükler.
     #[inline(always)]
     pub unsafe fn switch_context(old_context: *mut TaskContext, new_context: *const TaskContext) {
-        serial_println!("[TASK] Bağlam Anahtarlama: {:#x} -> {:#x}", 
-                        old_context as u64, new_context as u64);
-
         asm!(
             // --------------------- Mevcut Görevin Durumunu Kaydet ---------------------
-            // r4: old_context, r5: new_context (C ABI'de a0 ve a1)
-            
-            // 1. Callee-Saved GPR'ları TaskContext'e kaydet (r16-r23, r30, r31)
-            // sd rN, offset(r4) (Store Doubleword)
-            
-            // r16 offset 0'da başlar. 10 yazmaç = 80 bayt.
-            "sd r16, 0(r4)",   // s0
-            "sd r17, 8(r4)",   // s1
-            "sd r18, 16(r4)",  // s2
-            "sd r19, 24(r4)",  // s3
-            "sd r20, 32(r4)",  // s4
-            "sd r21, 40(r4)",  // s5
-            "sd r22, 48(r4)",  // s6
-            "sd r23, 56(r4)",  // s7
-            "sd r30, 64(r4)",  // s8/fp
-            "sd r31, 72(r4)",  // ra
-            
-            // 2. r29 (sp) kaydet. Offset 80.
-            "sd r29, 80(r4)",  // sp
-            
+            "sd $16, {r16}($4)",
+            "sd $17, {r17}($4)",
+            "sd $18, {r18}($4)",
+            "sd $19, {r19}($4)",
+            "sd $20, {r20}($4)",
+            "sd $21, {r21}($4)",
+            "sd $22, {r22}($4)",
+            "sd $23, {r23}($4)",
+            "sd $30, {r30}($4)",
+            "sd $29, {r29}($4)",
+            // Devam noktası: görev yeniden seçildiğinde `2:`'ye döner
+            "dla $12, 2f",
+            "sd $12, {r31}($4)",
+
             // --------------------- Yeni Görevin Durumunu Yükle ---------------------
-            // r5: new_context
-            
-            // 1. Yeni r29 (sp) yükle (Offset 80)
-            "ld r29, 80(r5)",  
-            
-            // 2. Callee-Saved GPR'ları yükle (r16-r23, r30, r31) (Offset 0)
-            "ld r16, 0(r5)", 
-            "ld r17, 8(r5)",
-            "ld r18, 16(r5)",
-            "ld r19, 24(r5)",
-            "ld r20, 32(r5)",
-            "ld r21, 40(r5)",
-            "ld r22, 48(r5)",
-            "ld r23, 56(r5)",
-            "ld r30, 64(r5)",
-            "ld r31, 72(r5)",
-            
-            // 3. Yeni görevin giriş noktasına zıpla (jr ra)
-            "jr r31", // Jump Register (r31 = ra). Görevi başlatır/devam ettirir.
-            
-            in("r4") old_context,
-            in("r5") new_context,
-            // r6-r11 (a2-a7) caller-saved
-            options(noreturn, preserves_flags)
+            "ld $16, {r16}($5)",
+            "ld $17, {r17}($5)",
+            "ld $18, {r18}($5)",
+            "ld $19, {r19}($5)",
+            "ld $20, {r20}($5)",
+            "ld $21, {r21}($5)",
+            "ld $22, {r22}($5)",
+            "ld $23, {r23}($5)",
+            "ld $30, {r30}($5)",
+            "ld $29, {r29}($5)",
+            "ld $12, {r31}($5)",
+            "ld $2, {ret}($5)",
+            "jr $12",
+            "2:",
+
+            r16 = const offset_of!(TaskContext, r16),
+            r17 = const offset_of!(TaskContext, r17),
+            r18 = const offset_of!(TaskContext, r18),
+            r19 = const offset_of!(TaskContext, r19),
+            r20 = const offset_of!(TaskContext, r20),
+            r21 = const offset_of!(TaskContext, r21),
+            r22 = const offset_of!(TaskContext, r22),
+            r23 = const offset_of!(TaskContext, r23),
+            r30 = const offset_of!(TaskContext, r30),
+            r31 = const offset_of!(TaskContext, r31),
+            r29 = const offset_of!(TaskContext, r29),
+            ret = const offset_of!(TaskContext, ret),
+            inout("$4") old_context => _,
+            inout("$5") new_context => _,
+            // Devam eden görev için caller-saved yazmaçların (ra dahil) hepsi bozulmuş sayılır
+            clobber_abi("C"),
         );
     }
+
+    /// Callee-saved yazmaçları, yığın işaretçisini ve devam noktasını
+    /// `context`'e kaydeder, ardından `body(data)`'yı çağırır. `body` dönerse
+    /// 0 döner; `context` daha sonra `switch_context` ile yüklenirse yürütme
+    /// `2:` etiketinden sürer ve dönüş değeri bağlamın `ret` alanı olur (bkz.
+    /// `fixup::catch`).
+    ///
+    /// # Güvenlik Notu
+    /// `context` geri dönüşe kadar geçerli kalmalıdır; bağlam yalnızca `body`
+    /// henüz dönmemişken yüklenebilir.
+    #[inline(always)]
+    pub unsafe fn catch_call(context: *mut TaskContext, body: extern "C" fn(*mut u8), data: *mut u8) -> usize {
+        let resumed: usize;
+        asm!(
+            // --------------------- Geri Dönüş Noktasını Kaydet ---------------------
+            "sd $16, {r16}($4)",
+            "sd $17, {r17}($4)",
+            "sd $18, {r18}($4)",
+            "sd $19, {r19}($4)",
+            "sd $20, {r20}($4)",
+            "sd $21, {r21}($4)",
+            "sd $22, {r22}($4)",
+            "sd $23, {r23}($4)",
+            "sd $30, {r30}($4)",
+            "sd $29, {r29}($4)",
+            // Devam noktası: bağlam yüklenirse `2:`'ye döner
+            "dla $12, 2f",
+            "sd $12, {r31}($4)",
+
+            // --------------------- Gövdeyi Çağır ---------------------
+            "move $25, $5",
+            "move $4, $6",
+            "jalr $25",
+            "move $2, $0",
+            "2:",
+
+            r16 = const offset_of!(TaskContext, r16),
+            r17 = const offset_of!(TaskContext, r17),
+            r18 = const offset_of!(TaskContext, r18),
+            r19 = const offset_of!(TaskContext, r19),
+            r20 = const offset_of!(TaskContext, r20),
+            r21 = const offset_of!(TaskContext, r21),
+            r22 = const offset_of!(TaskContext, r22),
+            r23 = const offset_of!(TaskContext, r23),
+            r30 = const offset_of!(TaskContext, r30),
+            r31 = const offset_of!(TaskContext, r31),
+            r29 = const offset_of!(TaskContext, r29),
+            inout("$4") context => _,
+            inout("$5") body => _,
+            inout("$6") data => _,
+            lateout("$2") resumed,
+            clobber_abi("C"),
+        );
+        resumed
+    }
+}
+
+impl ArchContext for TaskContext {
+    /// İlk geçiş `task_start`'a zıplar; o da s0/s1'deki işlev ve argümanla
+    /// `task_entry`'yi çağırır.
+    fn new(entry: usize, arg: usize, stack_top: usize) -> Self {
+        Self {
+            r16: entry as u64,
+            r17: arg as u64,
+            // n64: sp 16 bayt hizalıdır
+            r29: (stack_top & !0xF) as u64,
+            r31: task_start as usize as u64,
+            ..Default::default()
+        }
+    }
+
+    unsafe fn switch(&mut self, next: &Self) {
+        Self::switch_context(self, next);
+    }
+
+    unsafe fn catch(&mut self, body: extern "C" fn(*mut u8), data: *mut u8) -> usize {
+        Self::catch_call(self, body, data)
+    }
+
+    fn set_return_value(&mut self, value: usize) {
+        self.ret = value as u64;
+    }
+}
+
+extern "C" {
+    /// Yeni görevin ilk komutu (aşağıdaki `global_asm!`).
+    fn task_start();
 }
 
+core::arch::global_asm!(
+    ".global task_start",
+    "task_start:",
+    "move $4, $16",
+    "move $5, $17",
+    // Yığın geri izleme burada dursun
+    "move $30, $0",
+    "jal {entry}",
+    "break",
+    entry = sym task_entry,
+);
+
 
 // -----------------------------------------------------------------------------
 // Görev Başlatma (Task Entry)
@@ -125,6 +210,7 @@ impl TaskContext {
 /// * `func`: Görevin gerçek giriş noktası (başlatılacak fonksiyonun adresi, r4'te olmalı).
 /// * `arg`: Göreve geçirilen u64 formatında argüman (r5'te olmalı).
 extern "C" fn task_entry(func: u64, arg: u64) -> ! {
+    crate::context::task_started();
     serial_println!("[TASK] Yeni Görev Başlatılıyor. Argüman: {:#x}", arg);
 
     // Fonksiyon işaretçisini (u64) gerçek fonksiyona dönüştür.
@@ -136,15 +222,10 @@ extern "C" fn task_entry(func: u64, arg: u64) -> ! {
     // Gerçek görev fonksiyonunu çağır (arg, r5 (a1)'da beklenir)
     entry_func(arg);
 
-    // Görev tamamlandı. Normalde bu noktaya gelinmemelidir.
-    serial_println!("[TASK] Görev Tamamlandı! İşlemci durduruluyor.");
-    
-    // Görev tamamlandığında, çekirdek durdurulmalıdır.
-    loop {
-        unsafe {
-            io::wait(); // MIPS'te bekleme talimatı
-        }
-    }
+    // Görev işlevi döndü: görev sonlandırılır ve işlemci sıradaki göreve
+    // (en azından boşta görevine) bırakılır
+    serial_println!("[TASK] Görev Tamamlandı.");
+    crate::sched::exit_current()
 }
 
 
@@ -160,4 +241,28 @@ pub fn initialize_tasking() {
     let entry_point_addr = task_entry as *const (); 
     
     serial_println!("[TASK] Task Entry Adresi: {:#x}", entry_point_addr as u64);
-}
\ No newline at end of file
+
+    // Zamanlayıcı bundan sonra görevler arasında gerçekten geçer (bkz. `context`)
+    crate::context::enable();
+}
+
+// -----------------------------------------------------------------------------
+// İŞLEMCİ BAŞINA ALAN TABANI
+// -----------------------------------------------------------------------------
+
+/// Bu işlemcinin işlemci başına alanının (`percpu`) tabanını yazar (CP0 `KScratch1`, MIPS64r2+).
+///
+/// # Safety
+/// `base` bu işlemciye ayrılmış, `.percpu` şablonunun kopyası olan alanı
+/// göstermelidir.
+pub unsafe fn set_cpu_local_base(base: usize) {
+    asm!("dmtc0 {}, $31, 2", in(reg) base, options(nostack, preserves_flags, nomem));
+}
+
+/// Bu işlemcinin işlemci başına alanının tabanı (henüz yazılmadıysa 0).
+pub fn cpu_local_base() -> usize {
+    let base: usize;
+    // SAFETY: KScratch1 yalnızca çekirdek tarafından kullanılır.
+    unsafe { asm!("dmfc0 {}, $31, 2", out(reg) base, options(nostack, preserves_flags, nomem)) };
+    base
+}
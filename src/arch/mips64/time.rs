@@ -42,6 +42,12 @@ pub fn read_count_register() -> Cycles {
     Cycles(cycles)
 }
 
+/// Serbest çalışan sayacın anlık değeri (bkz. `crate::clock`).
+#[inline(always)]
+pub fn read_cycles() -> Cycles {
+    read_count_register()
+}
+
 /// Çekirdek yazmacının frekansını (genellikle CPU frekansının yarısı) döndürür.
 /// Gerçek frekans donanımdan öğrenilemediği için sabit bir varsayım kullanılır.
 pub fn get_frequency() -> u64 {
@@ -104,6 +110,17 @@ pub fn initialize_time_system() {
     // somut zaman değerleri hesaplanabilir.
 }
 
+// -----------------------------------------------------------------------------
+// Zamanlayıcı Karşılaştırıcısı (Compare)
+// -----------------------------------------------------------------------------
+
+/// CP0 Compare yazmacını `deadline`'ın alt 32 bitine kurar; Count eşit
+/// olunca IP7 yükselir. Compare'e yazmak bekleyen kesmeyi de temizler.
+/// Count 32 bit olduğundan hedef bir tur (~2^32 döngü) içinde olmalıdır.
+pub unsafe fn set_timer_deadline(deadline: Cycles) {
+    asm!("mtc0 {}, $11", "ehb", in(reg) deadline.0 as u32 as u64, options(nostack, nomem));
+}
+
 // Platforma özel G/Ç fonksiyonları için bir yer tutucu (main.rs veya platformmod.rs'de tamamlanmalıdır)
 mod io {
     use core::arch::asm;
@@ -0,0 +1,118 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/arch/mips64/vectors.rs
+// MIPS64 istisna vektörleri (EBase), `trap_vectors!` ile üretilir.
+//
+// Tablo 4 KiB hizalı, 5 yuva x 0x80 bayttır: TLB yenileme (0x000), XTLB
+// yenileme (0x080), önbellek hatası (0x100), genel istisna (0x180) ve
+// Cause.IV = 1 iken kesme (0x200). Taslaklar yalnızca k0'ı kullanır: k0'a
+// işleyici yüklenir ve ortak koda sıçranır; istisna Cause'dan ayırt edilir. Ortak
+// kod `ExceptionContext`'i ($1..$31, EPC, BadVAddr, Cause, Status) yığına
+// yazar ve işleyiciyi `handler(&mut ExceptionContext)` olarak çağırır;
+// çekirdek kipine dönülüyorsa ardından `sched_preempt_from_trap` gelir.
+// k0/k1 kaydedilmez; bağlamdaki karşılıkları sıfırdır.
+
+/// Çerçeve boyutu: `ExceptionContext` (35 x 8 bayt) 16 bayta yuvarlanmış.
+/// Boy ve aşağıdaki ofsetler `exception.rs`'de `asm_layout!` ile denetlenir.
+macro_rules! mips64_frame {
+    () => {
+        "288"
+    };
+}
+
+macro_rules! mips64_entry {
+    ($name:literal, $handler:literal) => {
+        concat!(
+            ".set push\n.set noreorder\n.set noat\n",
+            // Mutlak 64 bit adres (`dla` PIC'te GOT'a gider; burada $gp güvenilmez)
+            "lui $26, %highest(", $handler, ")\n",
+            "daddiu $26, $26, %higher(", $handler, ")\n",
+            "dsll $26, $26, 16\n",
+            "daddiu $26, $26, %hi(", $handler, ")\n",
+            "dsll $26, $26, 16\n",
+            "daddiu $26, $26, %lo(", $handler, ")\n",
+            "b ", $name, "_common\n",
+            " nop\n",
+            ".set pop\n",
+        )
+    };
+}
+
+macro_rules! mips64_common {
+    ($name:literal) => {
+        concat!(
+            ".set push\n.set noreorder\n.set noat\n",
+            $name, "_common:\n",
+            "daddiu $29, $29, -", mips64_frame!(), "\n",
+            ".irp n, 1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16,17,18,19,20,21,22,23,24,25,28,30,31\n",
+            "sd $\\n, ((\\n - 1) * 8)($29)\n",
+            ".endr\n",
+            "sd $0, 200($29)\n",
+            "sd $0, 208($29)\n",
+            "daddiu $1, $29, ", mips64_frame!(), "\n",
+            "sd $1, 224($29)\n",
+            "dmfc0 $1, $14\n",          // EPC
+            "sd $1, 248($29)\n",
+            "dmfc0 $1, $8\n",           // BadVAddr
+            "sd $1, 256($29)\n",
+            "mfc0 $1, $13\n",           // Cause
+            "sd $1, 264($29)\n",
+            "mfc0 $1, $12\n",           // Status
+            "sd $1, 272($29)\n",
+            // handler(&mut ExceptionContext); n64 ABI'de çağrı adresi t9'da olmalı
+            "move $4, $29\n",
+            "move $25, $26\n",
+            "jalr $25\n",
+            " nop\n",
+            // Çekirdek kipine (Status.KSU = 0) dönülüyorsa kesinti noktası: EXL
+            // ve IE temizlenir, EPC/Status aşağıda çerçeveden yeniden yüklenir
+            "ld $1, 272($29)\n",
+            "andi $1, $1, 0x18\n",
+            "bnez $1, 1f\n",
+            " nop\n",
+            "mfc0 $1, $12\n",
+            "ori $1, $1, 0x3\n",
+            "xori $1, $1, 0x3\n",
+            "mtc0 $1, $12\n",
+            "ehb\n",
+            "lui $25, %highest(sched_preempt_from_trap)\n",
+            "daddiu $25, $25, %higher(sched_preempt_from_trap)\n",
+            "dsll $25, $25, 16\n",
+            "daddiu $25, $25, %hi(sched_preempt_from_trap)\n",
+            "dsll $25, $25, 16\n",
+            "daddiu $25, $25, %lo(sched_preempt_from_trap)\n",
+            "jalr $25\n",
+            " nop\n",
+            "1:\n",
+            // İşleyici EPC'yi (syscall, düzeltme) ve Status'u (hata ayıklayıcı) değiştirebilir
+            "ld $1, 248($29)\n",
+            "dmtc0 $1, $14\n",
+            "ld $1, 272($29)\n",
+            "mtc0 $1, $12\n",
+            ".irp n, 1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16,17,18,19,20,21,22,23,24,25,28,30,31\n",
+            "ld $\\n, ((\\n - 1) * 8)($29)\n",
+            ".endr\n",
+            "ld $29, 224($29)\n",
+            "eret\n",
+            ".set pop\n",
+        )
+    };
+}
+
+crate::trap_vectors! {
+    name: "mips64_vectors",
+    align: 4096,
+    slot: 0x80,
+    slots: 5,
+    common: mips64_common,
+    vectors: [
+        0 ..= 1 => mips64_entry("generic_exception_handler"),
+        2 ..= 2 => mips64_entry("generic_cache_error_handler"),
+        3 ..= 4 => mips64_entry("generic_exception_handler"),
+    ]
+}
+
+extern "C" {
+    /// Üretilen tablonun başı (EBase değeri).
+    pub fn mips64_vectors();
+}
@@ -0,0 +1,43 @@
+// src/arch/openrisc64/cpu.rs
+// OpenRISC işlemci özelliği algılama: VR2, UPR ve CPUCFGR.
+
+#![allow(dead_code)]
+
+use core::arch::asm;
+use crate::cpu::CpuFeatures;
+
+/// SPR numaraları (grup 0).
+const SPR_UPR: usize = 1;
+const SPR_CPUCFGR: usize = 2;
+const SPR_VR2: usize = 9;
+
+/// CPUCFGR[7] - ORFPX32 (tek duyarlıklı FPU), [9] - ORVDX64 (vektör).
+const CPUCFGR_OF32S: u64 = 1 << 7;
+const CPUCFGR_OV64S: u64 = 1 << 9;
+/// CPUCFGR[10] - AVRP: mimari sürüm yazmacı (AVR) var (OpenRISC 1.1+).
+const CPUCFGR_AVRP: u64 = 1 << 10;
+
+fn mfspr<const SPR: usize>() -> u64 {
+    let value: u64;
+    unsafe { asm!("l.mfspr {0}, r0, {1}", out(reg) value, const SPR, options(nostack, nomem)) };
+    value
+}
+
+pub fn detect() -> CpuFeatures {
+    // UPR[0]: UPR'nin kendisi mevcut; değilse diğer yapılandırma yazmaçları da yoktur.
+    let cpucfgr = if mfspr::<SPR_UPR>() & 1 != 0 { mfspr::<SPR_CPUCFGR>() } else { 0 };
+    CpuFeatures {
+        id: mfspr::<SPR_VR2>(),
+        fpu: cpucfgr & CPUCFGR_OF32S != 0,
+        simd: cpucfgr & CPUCFGR_OV64S != 0,
+        aes: false,
+        sha2: false,
+        crc32: false,
+        // l.lwa/l.swa (OpenRISC 1.1) LL/SC'dir, tek talimatlı RMW değildir
+        atomics: false,
+        // Yazılım TLB; büyük sayfa eşlemesi kullanılmaz
+        large_pages: false,
+        huge_pages: false,
+        rng: false,
+    }
+}
@@ -0,0 +1,126 @@
+// src/arch/openrisc64/debug.rs
+// OpenRISC 64 mimarisine özgü kesme noktası, tek adım ve gözlem noktası desteği.
+
+#![allow(dead_code)]
+
+use core::arch::asm;
+use crate::debug::WatchKind;
+use crate::platformgeneric::KernelError;
+
+/// `l.trap 0` talimatı.
+pub const BREAKPOINT_INSN: u32 = 0x2100_0000;
+
+/// Kesme noktası talimatının uzunluğu (bayt).
+pub const BREAKPOINT_INSN_LEN: usize = 4;
+
+/// EPCR `l.trap` talimatının kendisini gösterir; düzeltme gerekmez.
+pub const BREAKPOINT_PC_ADJUST: usize = 0;
+
+/// ICBIR (Instruction Cache Block Invalidate Register) SPR numarası.
+const SPR_ICBIR: usize = (4 << 11) | 2;
+/// DMR1 (Debug Mode Register 1) SPR numarası.
+const SPR_DMR1: usize = (6 << 11) | 16;
+/// DMR1.ST (Single-step Trace) biti.
+const DMR1_ST: u64 = 1 << 22;
+
+/// Değiştirilen adresin talimat önbelleği bloğunu geçersiz kılar.
+#[inline(always)]
+pub unsafe fn sync_icache(addr: usize, _len: usize) {
+    asm!("l.mtspr r0, {0}, {1}", "l.csync", in(reg) addr, const SPR_ICBIR, options(nostack));
+}
+
+/// DMR1.ST bitini ayarlayarak her talimattan sonra tuzak oluşturur.
+/// Durum yazmacı değiştirilmez; bayrak SPR'da tutulur.
+pub fn set_single_step(_status: &mut u64, enable: bool) -> Result<(), KernelError> {
+    unsafe {
+        let mut dmr1: u64;
+        asm!("l.mfspr {0}, r0, {1}", out(reg) dmr1, const SPR_DMR1, options(nostack, nomem));
+        if enable {
+            dmr1 |= DMR1_ST;
+        } else {
+            dmr1 &= !DMR1_ST;
+        }
+        asm!("l.mtspr r0, {0}, {1}", in(reg) dmr1, const SPR_DMR1, options(nostack, nomem));
+    }
+    Ok(())
+}
+
+// -----------------------------------------------------------------------------
+// DONANIM GÖZLEM NOKTALARI (DVR0-7 / DCR0-7 / DMR2)
+// -----------------------------------------------------------------------------
+
+/// Hata ayıklama biriminin sunduğu değer/denetim yazmaç çifti sayısı.
+pub const MAX_WATCHPOINTS: usize = 8;
+
+/// Eşleşme, erişim tamamlanmadan tuzak (0xE00) üretir.
+pub const WATCH_BEFORE_ACCESS: bool = true;
+
+const SPR_DVR0: usize = (6 << 11) | 0;
+const SPR_DCR0: usize = (6 << 11) | 8;
+const SPR_DMR2: usize = (6 << 11) | 17;
+
+// DCR alanları
+const DCR_CC_EQUAL: u64 = 1 << 1;  // Karşılaştırma koşulu: eşit
+const DCR_CT_SHIFT: u64 = 5;       // Karşılaştırma türü
+const DCR_CT_LOAD_EA: u64 = 2;
+const DCR_CT_STORE_EA: u64 = 3;
+const DCR_CT_LS_EA: u64 = 6;
+// DMR2 alanları
+const DMR2_WGB_SHIFT: u64 = 12;    // Kesme noktası üreten gözlem noktaları
+const DMR2_WBS_SHIFT: u64 = 22;    // Kesme noktası durum bitleri
+
+unsafe fn read_dmr2() -> u64 {
+    let value: u64;
+    asm!("l.mfspr {0}, r0, {1}", out(reg) value, const SPR_DMR2, options(nostack, nomem));
+    value
+}
+
+unsafe fn write_dmr2(value: u64) {
+    asm!("l.mtspr r0, {0}, {1}", in(reg) value, const SPR_DMR2, options(nostack, nomem));
+}
+
+/// DVR<slot>'a adresi, DCR<slot>'a eşitlik koşulunu ve erişim türünü yazar;
+/// DMR2.WGB ile eşleşmenin tuzak üretmesini sağlar.
+///
+/// Karşılaştırma etkin adres eşitliğidir: erişim tam olarak `addr`'den başlamalıdır.
+pub unsafe fn set_watchpoint(slot: usize, addr: usize, _len: usize, kind: WatchKind) -> Result<(), KernelError> {
+    let ct = match kind {
+        WatchKind::Read => DCR_CT_LOAD_EA,
+        WatchKind::Write => DCR_CT_STORE_EA,
+        WatchKind::ReadWrite => DCR_CT_LS_EA,
+    };
+    let dcr = DCR_CC_EQUAL | (ct << DCR_CT_SHIFT);
+
+    // `l.mtspr rA, rB, K`: SPR numarası rA | K'dır; yuva indeksi rA'da verilir.
+    asm!(
+        "l.mtspr {slot}, {addr}, {dvr}",
+        "l.mtspr {slot}, {dcr}, {dcr_base}",
+        slot = in(reg) slot,
+        addr = in(reg) addr,
+        dcr = in(reg) dcr,
+        dvr = const SPR_DVR0,
+        dcr_base = const SPR_DCR0,
+        options(nostack, nomem)
+    );
+    write_dmr2(read_dmr2() | (1 << (DMR2_WGB_SHIFT + slot as u64)));
+    Ok(())
+}
+
+/// DCR<slot>'u sıfırlar ve DMR2.WGB'deki bitini temizler.
+pub unsafe fn clear_watchpoint(slot: usize) {
+    asm!("l.mtspr {slot}, r0, {dcr_base}", slot = in(reg) slot, dcr_base = const SPR_DCR0, options(nostack, nomem));
+    write_dmr2(read_dmr2() & !(1 << (DMR2_WGB_SHIFT + slot as u64)));
+}
+
+/// DMR2.WBS'ten eşleşen yuvayı okur ve durum bitlerini temizler.
+pub fn watchpoint_hit() -> Option<usize> {
+    unsafe {
+        let dmr2 = read_dmr2();
+        let hits = (dmr2 >> DMR2_WBS_SHIFT) & 0xFF;
+        if hits == 0 {
+            return None;
+        }
+        write_dmr2(dmr2 & !(0x3FF << DMR2_WBS_SHIFT));
+        Some(hits.trailing_zeros() as usize)
+    }
+}
@@ -1,6 +1,7 @@
 use core::arch::asm;
 use core::fmt;
 use crate::serial_println;
+use crate::trap::{handle_fatal, Access, Cause};
 
 // -----------------------------------------------------------------------------
 // HARİCİ MONTAJ DİLİ İŞLEYİCİSİ
@@ -31,6 +32,17 @@ pub struct ExceptionContext {
     pub srr: u64,  // Süpervizör Durum Yazmacı (Supervisor Register Register - Kesme durumu vb.)
 }
 
+// Tuzak giriş gövdesi (`exception_entry.S`) bu düzene göre yazılmalıdır;
+// ofsetler burada sabitlenir.
+crate::asm_layout!(ExceptionContext {
+    gpr: 0,
+    epcr: 248,
+    eear: 256,
+    esr: 264,
+    tsr: 272,
+    srr: 280,
+}, size == 288);
+
 /// İstisna nedenleri (TSR yazmacından alınmıştır).
 /// OpenRISC Mimari Kaynakları'ndan alınmıştır.
 #[repr(u64)]
@@ -64,6 +76,29 @@ impl From<u64> for ExceptionCause {
     }
 }
 
+/// Ham TSR ve EEAR değerlerini ortak `trap::Cause` tipine çevirir.
+pub fn decode_cause(tsr: u64, eear: u64) -> Cause {
+    let addr = eear as usize;
+
+    match ExceptionCause::from((tsr >> 1) & 0x7) {
+        // Gerçek IRQ numarası PIC durum yazmacından (PICSR) okunur
+        ExceptionCause::Int => Cause::Interrupt(0),
+        // Tuzak vektörü hata ayıklama biriminin gözlem noktası eşleşmelerini de taşır
+        ExceptionCause::Trap => match crate::debug::watchpoint_hit() {
+            Some(watched) => Cause::Watchpoint { addr: watched },
+            None => Cause::Syscall,
+        },
+        ExceptionCause::TLBMissLoad => Cause::PageFault { access: Access::Read, addr },
+        ExceptionCause::TLBMissStore | ExceptionCause::TLBProtection => {
+            Cause::PageFault { access: Access::Write, addr }
+        }
+        ExceptionCause::AddrInvalid => Cause::Misaligned { addr },
+        ExceptionCause::InstFault => Cause::IllegalInstruction,
+        ExceptionCause::DataFault => Cause::MachineError,
+        ExceptionCause::_Unknown => Cause::Unknown(tsr),
+    }
+}
+
 // -----------------------------------------------------------------------------
 // 2. TEMEL İŞLEYİCİ FONKSİYONLARI (Rust Kodu)
 // -----------------------------------------------------------------------------
@@ -78,35 +113,78 @@ pub extern "C" fn generic_exception_handler(context: &mut ExceptionContext) {
         ExceptionCause::Int => {
             // Kesme İşleyicisi
             handle_interrupt(context);
+            preempt(context);
         }
         ExceptionCause::TLBMissLoad | ExceptionCause::TLBMissStore | ExceptionCause::TLBProtection => {
             // TLB ve Sayfa Hatası İşleyicileri
+            // Kullanıcı belleği erişimi veya bellek yoklaması ise düzeltme tablosuna bak
+            let trap_cause = decode_cause(context.tsr, context.eear);
+            if crate::fixup::fixup_exception(trap_cause, &mut context.epcr) {
+                return;
+            }
+
             serial_println!("\n--- TLB/SAYFA HATASI ---");
             serial_println!("Neden: {:?}", cause);
             serial_println!("Hata Adresi (EEAR): {:#x}", context.eear);
             serial_println!("EPCR: {:#x}", context.epcr);
-            panic!("Kritik TLB Hatası!");
+            handle_fatal(trap_cause, context.epcr as usize);
         }
         ExceptionCause::Trap => {
+            // Gözlem noktası eşleşmesi önce hata ayıklayıcıya sunulur.
+            let trap_cause = decode_cause(context.tsr, context.eear);
+            if crate::debug::handle_trap(trap_cause, &mut context.epcr, &mut context.srr) {
+                return;
+            }
+
             // Sistem Çağrısı (SYSCALL)
             serial_println!("SYSCALL: Çağrı kodu: {}", context.gpr[11]); // Genellikle r11
             
             // Trap'ten dönmek için EPCR'yi bir sonraki talimata ilerletmeniz GEREKİR.
             context.epcr += 4; // OpenRISC talimatları 4 bayttır.
+            preempt(context);
         }
         _ => {
+            // Kesme noktası önce hata ayıklayıcıya sunulur.
+            let trap_cause = decode_cause(context.tsr, context.eear);
+            if crate::debug::handle_trap(trap_cause, &mut context.epcr, &mut context.srr) {
+                return;
+            }
+
+            // Hizalama ve veri yolu hataları için düzeltme tablosuna bak
+            if crate::fixup::fixup_exception(trap_cause, &mut context.epcr) {
+                return;
+            }
+
             // Diğer senkron istisnalar
             serial_println!("\n--- KRİTİK İSTİSNA ---");
             serial_println!("Neden Kodu: {:#x} ({:?})", cause_code, cause);
             serial_println!("EPCR: {:#x}", context.epcr);
             
-            panic!("İşlenmemiş İstisna!");
+            handle_fatal(trap_cause, context.epcr as usize);
         }
     }
 }
 
+/// Tuzak dönüşü: süpervizör kipindeki görev kesildiyse bekleyen yeniden
+/// zamanlamayı uygular (`IrqGuard` bırakıldıktan sonra). Görev başka bir
+/// işlemcide devam edebileceğinden çerçevedeki işlemci başına taban (r10)
+/// güncellenir. Giriş kodu EPCR ve ESR'yi çerçeveden geri yazar.
+fn preempt(context: &mut ExceptionContext) {
+    // ESR girişteki SR'dir; SM = 1 ise süpervizör kesildi
+    if context.esr & 1 == 0 {
+        return;
+    }
+    crate::sched::preempt_if_needed();
+    context.gpr[9] = crate::arch::openrisc64::task::cpu_local_base() as u64;
+}
+
 /// Donanım Kesmeleri (IRQ) için özel işleyici.
-fn handle_interrupt(_context: &mut ExceptionContext) {
+fn handle_interrupt(context: &mut ExceptionContext) {
+    let _irq = crate::irqstack::enter(context as *const _ as usize);
+    // Tick Timer eşleşmesi (TTMR.IP); TTMR yeniden kurulunca temizlenir
+    if crate::arch::openrisc64::time::timer_pending() {
+        crate::hrtimer::timer_interrupt();
+    }
     // 1. PIC (Platform Interrupt Controller) adresinden hangi IRQ'nun geldiğini oku
     //    (Örn: 0x9000_1000'deki bir MMIO yazmacı).
     
@@ -149,4 +227,20 @@ pub fn init_exceptions() {
     
     // NOT: OR64'te Vektörler sabit adreslerdir. Gerekirse 'mtex' komutu ile 
     // özel vektör adresleri ayarlanabilir, ancak genel kural bu şekilde başlar.
-}
\ No newline at end of file
+}
+
+// -----------------------------------------------------------------------------
+// 4. KESME YIĞINI
+// -----------------------------------------------------------------------------
+
+/// Kesmeler kesilen görevin yığınında çalışır; giriş kodu yığın değiştirmez
+/// (bkz. `crate::irqstack`).
+pub const SWITCHES_IRQ_STACK: bool = false;
+
+/// Ayrı kesme yığını olmadığından etkisizdir.
+///
+/// # Safety
+/// Diğer portlarla aynı sözleşme: `top` bu işlemciye ait bir yığının üstü olmalıdır.
+pub unsafe fn install_irq_stack(_top: usize) -> Result<(), crate::platformgeneric::KernelError> {
+    Ok(())
+}
@@ -0,0 +1,79 @@
+// src/arch/openrisc64/fixup.rs
+// OpenRISC 64 hata güvenli bellek yoklama talimatları.
+
+#![allow(dead_code)]
+
+use core::arch::asm;
+
+/// Tek bayt okur; erişim hatasında `None` döner.
+pub unsafe fn probe_read_u8(addr: usize) -> Option<u8> {
+    let value: u64;
+    let err: u64;
+    asm!(
+        "l.ori {err}, r0, 0",
+        "2: l.lbz {val}, 0({addr})",
+        "3:",
+        ".pushsection .text.fixup, \"ax\"",
+        "4: l.ori {val}, r0, 0",
+        "l.j 3b",
+        "l.ori {err}, r0, 1", // gecikme yuvası (delay slot)
+        ".popsection",
+        ".pushsection .ex_table, \"a\"",
+        ".balign 8",
+        ".quad 2b, 4b",
+        ".popsection",
+        addr = in(reg) addr,
+        val = out(reg) value,
+        err = out(reg) err,
+        options(nostack, readonly)
+    );
+    if err == 0 { Some(value as u8) } else { None }
+}
+
+/// Tek bayt yazar; erişim hatasında `false` döner.
+pub unsafe fn probe_write_u8(addr: usize, value: u8) -> bool {
+    let err: u64;
+    asm!(
+        "l.ori {err}, r0, 0",
+        "2: l.sb 0({addr}), {val}",
+        "3:",
+        ".pushsection .text.fixup, \"ax\"",
+        "4: l.j 3b",
+        "l.ori {err}, r0, 1", // gecikme yuvası (delay slot)
+        ".popsection",
+        ".pushsection .ex_table, \"a\"",
+        ".balign 8",
+        ".quad 2b, 4b",
+        ".popsection",
+        addr = in(reg) addr,
+        val = in(reg) u64::from(value),
+        err = out(reg) err,
+        options(nostack)
+    );
+    err == 0
+}
+
+/// 32 bit okur; erişim veya veri yolu hatasında `None` döner.
+pub unsafe fn probe_read_u32(addr: usize) -> Option<u32> {
+    let value: u64;
+    let err: u64;
+    asm!(
+        "l.ori {err}, r0, 0",
+        "2: l.lwz {val}, 0({addr})",
+        "3:",
+        ".pushsection .text.fixup, \"ax\"",
+        "4: l.ori {val}, r0, 0",
+        "l.j 3b",
+        "l.ori {err}, r0, 1", // gecikme yuvası (delay slot)
+        ".popsection",
+        ".pushsection .ex_table, \"a\"",
+        ".balign 8",
+        ".quad 2b, 4b",
+        ".popsection",
+        addr = in(reg) addr,
+        val = out(reg) value,
+        err = out(reg) err,
+        options(nostack, readonly)
+    );
+    if err == 0 { Some(value as u32) } else { None }
+}
@@ -144,4 +144,91 @@ pub fn handle_external_interrupts() {
             send_eoi(irq_line);
         }
     }
-}
\ No newline at end of file
+}
+
+// -----------------------------------------------------------------------------
+// YEREL KESME MASKESİ
+// -----------------------------------------------------------------------------
+
+// Genel kod bunları `crate::irqoff` üzerinden kullanır. `asm!` bloklarında
+// `nomem` yoktur: maske değişikliği derleyici için bir bellek engelidir,
+// kilitli bölgenin erişimleri dışarı taşınmaz.
+
+/// SR yazmacının SPR numarası ve kesme bitleri (IEE: aygıt, TEE: tik).
+const SPR_SR: usize = 17;
+const SR_IRQ_BITS: usize = (1 << 2) | (1 << 1);
+
+/// SR'deki aygıt ve tik kesmelerini kapatır; önceki SR değerini döndürür.
+#[inline(always)]
+pub fn local_irq_save() -> usize {
+    let flags: usize;
+    // SAFETY: Yalnızca SR.IEE ve SR.TEE temizlenir.
+    unsafe {
+        asm!(
+            "l.mfspr {f}, r0, {sr}",
+            "l.and {t}, {f}, {mask}",
+            "l.mtspr r0, {t}, {sr}",
+            f = out(reg) flags,
+            t = out(reg) _,
+            mask = in(reg) !SR_IRQ_BITS,
+            sr = const SPR_SR,
+            options(nostack)
+        )
+    };
+    flags
+}
+
+/// `local_irq_save` öncesindeki IEE/TEE bitlerini geri kurar.
+#[inline(always)]
+pub fn local_irq_restore(flags: usize) {
+    let bits = flags & SR_IRQ_BITS;
+    if bits == 0 {
+        return;
+    }
+    // SAFETY: Yalnızca kaydedilen kesme bitleri geri kurulur.
+    unsafe {
+        asm!(
+            "l.mfspr {t}, r0, {sr}",
+            "l.or {t}, {t}, {bits}",
+            "l.mtspr r0, {t}, {sr}",
+            t = out(reg) _,
+            bits = in(reg) bits,
+            sr = const SPR_SR,
+            options(nostack)
+        )
+    };
+}
+
+/// Kaydedilen durumda aygıt kesmeleri (SR.IEE) açık mıydı?
+#[inline(always)]
+pub fn irqs_were_enabled(flags: usize) -> bool {
+    flags & (1 << 2) != 0
+}
+
+/// Aygıt ve tik kesmelerini koşulsuz açar.
+#[inline(always)]
+pub fn local_irq_enable() {
+    local_irq_restore(SR_IRQ_BITS);
+}
+
+// -----------------------------------------------------------------------------
+// HAT MASKESİ VE PCI INTx
+// -----------------------------------------------------------------------------
+
+/// Kesme hattını denetleyicide maskeler veya açar (bkz. `crate::irqstorm`);
+/// `irq` dağıtıcının gördüğü numaradır. Desteklenmiyorsa `false`.
+pub fn set_irq_masked(_irq: u32, _masked: bool) -> bool {
+    false
+}
+
+/// PCI aygıtının INTx hattını açar ve dağıtıcının göreceği kesme numarasını
+/// döndürür. INTx eşlemesi yoksa `None`; sürücü yoklamayla çalışır.
+pub fn route_pci_intx(_line: u8) -> Option<u32> {
+    None
+}
+
+/// Aygıt ağacı düğümünün ilk kesmesini denetleyicinin göreceği numaraya
+/// çevirir. Bu portun denetleyicisi tek hücreli kesme belirteci kullanır.
+pub fn decode_fdt_irq(node: &crate::fdt::Node) -> Option<u32> {
+    node.interrupt_cell(0)
+}
@@ -4,6 +4,7 @@
 use core::arch::asm;
 use core::ptr::NonNull;
 use crate::serial_println;
+use crate::memory::vmaudit::{validate_mapping, CacheAttr, MapError, Mapping, Perms};
 use super::io; // Bariyerler için io modülünü kullanacağız
 
 // -----------------------------------------------------------------------------
@@ -95,6 +96,11 @@ unsafe fn tlb_write(tlb_index: u64) {
 
 /// Sanal adresi fiziksel adrese eşler ve TLB'ye yazar (4KiB sayfa).
 ///
+/// # Hatalar
+/// * `OutOfRange`: İndeks `TLB_ENTRY_COUNT` veya üstü.
+/// * `Unaligned`: Adresler sayfa hizalı değil.
+/// * `InvalidFlags`: Bayraklar adres alanına (bit 12 ve üstü) taşıyor.
+///
 /// # Güvenlik Notu
 /// Bu fonksiyon doğrudan SPR'ları değiştirir.
 pub unsafe fn map_tlb_entry(
@@ -102,7 +108,12 @@ pub unsafe fn map_tlb_entry(
     virtual_addr: usize,
     physical_addr: usize,
     flags: u64,
-) {
+) -> Result<(), MapError> {
+    if tlb_index >= TLB_ENTRY_COUNT as u64 {
+        return Err(MapError::OutOfRange);
+    }
+    validate_mapping(virtual_addr, physical_addr, PAGE_SIZE, 64, 64, flags, PageFlags::ADDR_MASK as u64)?;
+
     // 1. Match Register'ı ayarla (Sanal Adres)
     // OpenRISC'te sanal adres ve sayfa boyutu (Page Size) bilgisi içerir.
     let match_reg_val = (virtual_addr as u64) | PAGE_SIZE as u64; // PAGE_SIZE VADDR'ın alt bitlerinde maske olarak kullanılır
@@ -122,6 +133,7 @@ pub unsafe fn map_tlb_entry(
     write_spr(SPR_ITLBLB, tlb_index);
     
     io::msync(); // Senkronizasyon bariyeri
+    Ok(())
 }
 
 
@@ -143,7 +155,10 @@ pub unsafe fn enable_paging() {
         if tlb_idx >= TLB_ENTRY_COUNT { break; } 
         
         // Sanal ve fiziksel adres aynı
-        map_tlb_entry(tlb_idx as u64, addr, addr, flags);
+        if let Err(e) = map_tlb_entry(tlb_idx as u64, addr, addr, flags) {
+            serial_println!("[OR64] {:#x} eşlenemedi ({:?}); MMU etkinleştirilmedi.", addr, e);
+            return;
+        }
         tlb_idx += 1;
     }
     
@@ -177,4 +192,75 @@ pub fn init_mmu() {
     unsafe {
         enable_paging();
     }
-}
\ No newline at end of file
+}
+
+// -----------------------------------------------------------------------------
+// TLB DENETİMİ (vm dump)
+// -----------------------------------------------------------------------------
+
+/// OpenRISC'te donanım sayfa tablosu yoktur; etkin çeviriler DTLB'dedir. Her giriş,
+/// `map_tlb_entry` ile aynı modelle (indeks DTLBLB'ye yazılır, Match/Translate
+/// yazmaçları okunur) geri okunur, sanal adrese göre sıralanır ve bildirilir.
+pub fn walk_mappings(visit: &mut dyn FnMut(Mapping)) {
+    let mut entries = [(0usize, 0u64); TLB_ENTRY_COUNT];
+    let mut count = 0;
+
+    unsafe {
+        for index in 0..TLB_ENTRY_COUNT {
+            write_spr(SPR_DTLBLB, index as u64);
+            io::msync();
+
+            let match_reg = read_spr(SPR_DTLBMR);
+            let translate = read_spr(SPR_DTLBTR);
+            if translate & PageFlags::VALID as u64 != 0 {
+                entries[count] = ((match_reg & PageFlags::ADDR_MASK as u64) as usize, translate);
+                count += 1;
+            }
+        }
+    }
+
+    let entries = &mut entries[..count];
+    entries.sort_unstable_by_key(|e| e.0);
+
+    for &(vaddr, tr) in entries.iter() {
+        visit(Mapping {
+            vaddr,
+            paddr: (tr & PageFlags::ADDR_MASK as u64) as usize,
+            size: PAGE_SIZE,
+            perms: Perms {
+                read: true,
+                write: tr & PageFlags::WRITE as u64 != 0,
+                exec: tr & PageFlags::EXEC as u64 != 0,
+                // Temsili TR biçiminde kullanıcı erişim bitleri yoktur; tüm girişler çekirdeğindir.
+                user: false,
+            },
+            cache: if tr & PageFlags::CACHE_ENABLE as u64 != 0 { CacheAttr::WriteBack } else { CacheAttr::Uncached },
+        });
+    }
+}
+
+// -----------------------------------------------------------------------------
+// KULLANICI SAYFALARI (mmap)
+// -----------------------------------------------------------------------------
+
+/// Kullanıcı sayfası eşlemesi bu portta henüz yok; `mmap` dosya eşlemeleri
+/// `PlatformSpecificError` ile reddedilir.
+///
+/// # Safety
+/// Diğer portlarla aynı sözleşme (bkz. `rv64i::mmu::map_user_page`).
+pub unsafe fn map_user_page(_virtual_addr: usize, _physical_addr: usize, _write: bool, _exec: bool) -> Result<(), crate::platformgeneric::KernelError> {
+    Err(crate::platformgeneric::KernelError::PlatformSpecificError(0))
+}
+
+/// Kullanıcı sayfası eşlenemediğinden etkisizdir.
+pub unsafe fn unmap_user_page(_virtual_addr: usize) {}
+
+// -----------------------------------------------------------------------------
+// AYGIT PENCERELERİ (physmap)
+// -----------------------------------------------------------------------------
+
+/// Büyük sayfalı çekirdek eşlemesi bu portta henüz yok; `physmap::map_mmio`
+/// `PlatformSpecificError` ile reddedilir.
+pub fn map_device(_phys: usize, _len: usize) -> Result<usize, crate::platformgeneric::KernelError> {
+    Err(crate::platformgeneric::KernelError::PlatformSpecificError(0))
+}
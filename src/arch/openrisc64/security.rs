@@ -2,6 +2,7 @@
 // OpenRISC 64 (OR64) mimarisine özgü temel güvenlik işlevleri.
 
 use core::arch::asm;
+use crate::platformgeneric::KernelError;
 use crate::serial_println;
 // arch/openrisc64/platformmod.rs dosyasından temel SPR erişimi ve bariyer işlevlerini içe aktarır.
 use super::platformmod::io; 
@@ -115,4 +116,29 @@ pub fn security_init() {
     // ve diğer spekülatif yürütme mitigasyonları buraya eklenirdi.
     
     serial_println!("[SECURITY] Temel Güvenlik Yapılandırması Tamamlandı.");
-}
\ No newline at end of file
+}
+
+// -----------------------------------------------------------------------------
+// Kullanıcı Belleği Erişimi
+// -----------------------------------------------------------------------------
+
+// Bu port çekirdeğin kullanıcı sayfalarına erişimini donanımla kısıtlamaz
+// (SMAP/PAN karşılığı açılmaz); aralık denetimi `uaccess::AddressSpace`'tedir.
+// Kopya düzeltme tablolu yoklamalarla yapılır, eşlenmemiş sayfa hata döner.
+
+/// Kullanıcı alanındaki `src` adresinden çekirdek arabelleğine `dst.len()` bayt kopyalar.
+///
+/// # Hatalar
+/// * `InvalidArgument`: Kopyalama sırasında okunamayan bir bayta rastlandı.
+pub fn copy_from_user(dst: &mut [u8], src: usize) -> Result<(), KernelError> {
+    crate::fixup::probe_kernel_read(src, dst)
+}
+
+/// Çekirdek arabelleği `src`'yi kullanıcı alanındaki `dst` adresine kopyalar.
+///
+/// # Hatalar
+/// * `InvalidArgument`: Kopyalama sırasında yazılamayan bir bayta rastlandı;
+///   önceki baytlar yazılmış olabilir.
+pub fn copy_to_user(dst: usize, src: &[u8]) -> Result<(), KernelError> {
+    crate::fixup::probe_kernel_write(dst, src)
+}
@@ -2,127 +2,209 @@
 // OpenRISC 64 (OR64) mimarisine özgü görev (task) ve bağlam (context) yönetimi.
 
 use core::arch::asm;
+use core::mem::offset_of;
+use crate::context::ArchContext;
 use crate::serial_println;
 
 /// Görev bağlamını (task context) saklamak için kullanılan yapı.
 /// OR64'te Callee-Saved (çağrılan tarafından korunan) yazmaçlar:
-/// r9-r20 (s0-s11), r1 (LR), r2 (SP).
+/// r14-r30 arasındaki çift numaralılar, r2 (FP); ayrıca r9 (LR) ve r1 (SP).
+/// r10 işlemci başına alanı gösterir (bkz. `set_cpu_local_base`) ve kaydedilmez.
 #[repr(C)]
 #[derive(Debug, Default)]
 pub struct TaskContext {
-    // Callee-Saved GPR'lar (r9 - r20) - 12 adet
-    r9: u64,  // s0
-    r10: u64, // s1
-    r11: u64, // s2
-    r12: u64, // s3
-    r13: u64, // s4
-    r14: u64, // s5
-    r15: u64, // s6
-    r16: u64, // s7
-    r17: u64, // s8
-    r18: u64, // s9
-    r19: u64, // s10
-    r20: u64, // s11
+    // Callee-Saved GPR'lar (r14, r16, ..., r30) - 9 adet
+    r14: u64,
+    r16: u64,
+    r18: u64,
+    r20: u64,
+    r22: u64,
+    r24: u64,
+    r26: u64,
+    r28: u64,
+    r30: u64,
+
+    // Çerçeve İşaretçisi (r2)
+    r2: u64,  // FP (Frame Pointer)
     
-    // Link Register (r1) - Görev anahtarlamadan sonra geri döneceği adres (PC).
-    r1: u64,  // LR (Link Register)
+    // Link Register (r9) - Görev anahtarlamadan sonra geri döneceği adres (PC).
+    r9: u64,  // LR (Link Register)
     
-    // Yığın İşaretçisi (r2) - Görevin yeni yığınının adresi.
-    r2: u64,  // SP (Stack Pointer) 
+    // Yığın İşaretçisi (r1) - Görevin yeni yığınının adresi.
+    r1: u64,  // SP (Stack Pointer) 
+
+    // Görev devam ettiğinde r11'de göreceği değer (`set_return_value`).
+    ret: u64,
 }
 
 impl TaskContext {
-    /// Yeni bir görev bağlamı oluşturur.
-    /// 
-    /// # Argümanlar
-    /// * `stack_top`: Görevin yığınının en üst adresi.
-    /// * `entry_point`: Görevin başlayacağı fonksiyonun adresi.
-    pub fn new(stack_top: u64, entry_point: u64) -> Self {
-        // Yeni bir görev başlatıldığında, anahtarlama kodu bu yapıyı yükler.
-        Self {
-            // Callee-Saved yazmaçlar sıfırlanır.
-            ..Default::default()
-            
-            // r2 (sp), yığının üstü olarak ayarlanır.
-            r2: stack_top,
-            
-            // r1 (LR) ve pc (entry_point), görevin ilk başlayacağı adres olarak ayarlanır.
-            // Anahtarlama `l.jr r1` ile görev döndüğünde, bu r1'e zıplayacaktır.
-            r1: entry_point, 
-        }
-    }
-
-    /// Bir bağlam anahtarlama işlemi sırasında yazmaç durumunu kaydetmek ve 
-    /// yeni görevden yazmaç durumunu yüklemek için kullanılan harici assembly fonksiyonu.
+    /// Callee-saved yazmaçları, r1/r2'yi ve dönüş noktasını `old_context`'e
+    /// kaydeder, `new_context`'ten yükler ve onun r9 alanındaki adrese zıplar.
+    /// Eski görev yeniden seçildiğinde `2:` etiketinden, yani bu çağrının
+    /// sonundan devam eder.
     ///
     /// # Güvenlik Notu
-    /// Bu fonksiyon, C ABI'sine uymalıdır: `fn switch_context(old_context: *mut TaskContext, new_context: *const TaskContext)`.
-    /// Argümanlar r3 ve r4'e (a0 ve a1) geçirilir.
+    /// İki işaretçi de geçiş boyunca geçerli olmalıdır; `new_context` ya
+    /// `ArchContext::new` ile kurulmuş ya da daha önce kaydedilmiş olmalıdır.
+    /// r1 ve r2 işlenen olarak verilemez; blok onları kendisi kaydedip yükler.
     #[inline(always)]
     pub unsafe fn switch_context(old_context: *mut TaskContext, new_context: *const TaskContext) {
-        serial_println!("[TASK] Bağlam Anahtarlama: {:#x} -> {:#x}", 
-                        old_context as u64, new_context as u64);
-
         asm!(
             // --------------------- Mevcut Görevin Durumunu Kaydet ---------------------
-            // r3: old_context, r4: new_context (C ABI'de a0 ve a1)
-            
-            // 1. Callee-Saved GPR'ları TaskContext'e kaydet (r9-r20)
-            // l.sd rN, offset(r3) (Store Doubleword)
-            
-            // r9 offset 0'da başlar. 12 yazmaç = 96 bayt.
-            "l.sd r9, 0(r3)",   // s0
-            "l.sd r10, 8(r3)",  // s1
-            "l.sd r11, 16(r3)", // s2
-            "l.sd r12, 24(r3)", // s3
-            "l.sd r13, 32(r3)", // s4
-            "l.sd r14, 40(r3)", // s5
-            "l.sd r15, 48(r3)", // s6
-            "l.sd r16, 56(r3)", // s7
-            "l.sd r17, 64(r3)", // s8
-            "l.sd r18, 72(r3)", // s9
-            "l.sd r19, 80(r3)", // s10
-            "l.sd r20, 88(r3)", // s11
-            
-            // 2. r1 (LR) ve r2 (SP) kaydet.
-            // r1 (LR) offset 96, r2 (SP) offset 104.
-            "l.sd r1, 96(r3)",  // LR
-            "l.sd r2, 104(r3)", // SP
-            
+            "l.sd {r14}(r3), r14",
+            "l.sd {r16}(r3), r16",
+            "l.sd {r18}(r3), r18",
+            "l.sd {r20}(r3), r20",
+            "l.sd {r22}(r3), r22",
+            "l.sd {r24}(r3), r24",
+            "l.sd {r26}(r3), r26",
+            "l.sd {r28}(r3), r28",
+            "l.sd {r30}(r3), r30",
+            "l.sd {r2}(r3), r2",
+            "l.sd {r1}(r3), r1",
+            // Devam noktası: görev yeniden seçildiğinde `2:`'ye döner
+            "l.movhi r13, hi(2f)",
+            "l.ori r13, r13, lo(2f)",
+            "l.sd {r9}(r3), r13",
+
             // --------------------- Yeni Görevin Durumunu Yükle ---------------------
-            // r4: new_context
-            
-            // 1. Yeni r2 (SP) yükle (Offset 104)
-            "l.ld r2, 104(r4)",  
-            
-            // 2. Callee-Saved GPR'ları yükle (r9-r20) (Offset 0)
-            "l.ld r9, 0(r4)", 
-            "l.ld r10, 8(r4)",
-            "l.ld r11, 16(r4)",
-            "l.ld r12, 24(r4)",
-            "l.ld r13, 32(r4)",
-            "l.ld r14, 40(r4)",
-            "l.ld r15, 48(r4)",
-            "l.ld r16, 56(r4)",
-            "l.ld r17, 64(r4)",
-            "l.ld r18, 72(r4)",
-            "l.ld r19, 80(r4)",
-            "l.ld r20, 88(r4)",
-            
-            // 3. Yeni r1 (LR) yükle (Offset 96)
-            "l.ld r1, 96(r4)",
-            
-            // 4. Yeni görevin giriş noktasına zıpla (l.jr r1)
-            "l.jr r1", // Jump Register (r1 = LR). Görevi başlatır/devam ettirir.
-            
-            in("r3") old_context,
-            in("r4") new_context,
-            // r5-r8 (a2-a5) caller-saved
-            options(noreturn, preserves_flags)
+            "l.ld r14, {r14}(r4)",
+            "l.ld r16, {r16}(r4)",
+            "l.ld r18, {r18}(r4)",
+            "l.ld r20, {r20}(r4)",
+            "l.ld r22, {r22}(r4)",
+            "l.ld r24, {r24}(r4)",
+            "l.ld r26, {r26}(r4)",
+            "l.ld r28, {r28}(r4)",
+            "l.ld r30, {r30}(r4)",
+            "l.ld r2, {r2}(r4)",
+            "l.ld r1, {r1}(r4)",
+            "l.ld r13, {r9}(r4)",
+            "l.jr r13",
+            " l.ld r11, {ret}(r4)", // Gecikme yuvası
+            "2:",
+
+            r14 = const offset_of!(TaskContext, r14),
+            r16 = const offset_of!(TaskContext, r16),
+            r18 = const offset_of!(TaskContext, r18),
+            r20 = const offset_of!(TaskContext, r20),
+            r22 = const offset_of!(TaskContext, r22),
+            r24 = const offset_of!(TaskContext, r24),
+            r26 = const offset_of!(TaskContext, r26),
+            r28 = const offset_of!(TaskContext, r28),
+            r30 = const offset_of!(TaskContext, r30),
+            r2 = const offset_of!(TaskContext, r2),
+            r9 = const offset_of!(TaskContext, r9),
+            r1 = const offset_of!(TaskContext, r1),
+            ret = const offset_of!(TaskContext, ret),
+            inout("r3") old_context => _,
+            inout("r4") new_context => _,
+            // Devam eden görev için caller-saved yazmaçların (r9 dahil) hepsi bozulmuş sayılır
+            clobber_abi("C"),
+        );
+    }
+
+    /// Callee-saved yazmaçları, yığın işaretçisini ve devam noktasını
+    /// `context`'e kaydeder, ardından `body(data)`'yı çağırır. `body` dönerse
+    /// 0 döner; `context` daha sonra `switch_context` ile yüklenirse yürütme
+    /// `2:` etiketinden sürer ve dönüş değeri bağlamın `ret` alanı olur (bkz.
+    /// `fixup::catch`).
+    ///
+    /// # Güvenlik Notu
+    /// `context` geri dönüşe kadar geçerli kalmalıdır; bağlam yalnızca `body`
+    /// henüz dönmemişken yüklenebilir.
+    #[inline(always)]
+    pub unsafe fn catch_call(context: *mut TaskContext, body: extern "C" fn(*mut u8), data: *mut u8) -> usize {
+        let resumed: usize;
+        asm!(
+            // --------------------- Geri Dönüş Noktasını Kaydet ---------------------
+            "l.sd {r14}(r3), r14",
+            "l.sd {r16}(r3), r16",
+            "l.sd {r18}(r3), r18",
+            "l.sd {r20}(r3), r20",
+            "l.sd {r22}(r3), r22",
+            "l.sd {r24}(r3), r24",
+            "l.sd {r26}(r3), r26",
+            "l.sd {r28}(r3), r28",
+            "l.sd {r30}(r3), r30",
+            "l.sd {r2}(r3), r2",
+            "l.sd {r1}(r3), r1",
+            // Devam noktası: bağlam yüklenirse `2:`'ye döner
+            "l.movhi r13, hi(2f)",
+            "l.ori r13, r13, lo(2f)",
+            "l.sd {r9}(r3), r13",
+
+            // --------------------- Gövdeyi Çağır ---------------------
+            "l.jalr r4",
+            " l.or r3, r5, r0", // Gecikme yuvası
+            "l.or r11, r0, r0",
+            "2:",
+
+            r14 = const offset_of!(TaskContext, r14),
+            r16 = const offset_of!(TaskContext, r16),
+            r18 = const offset_of!(TaskContext, r18),
+            r20 = const offset_of!(TaskContext, r20),
+            r22 = const offset_of!(TaskContext, r22),
+            r24 = const offset_of!(TaskContext, r24),
+            r26 = const offset_of!(TaskContext, r26),
+            r28 = const offset_of!(TaskContext, r28),
+            r30 = const offset_of!(TaskContext, r30),
+            r2 = const offset_of!(TaskContext, r2),
+            r9 = const offset_of!(TaskContext, r9),
+            r1 = const offset_of!(TaskContext, r1),
+            inout("r3") context => _,
+            inout("r4") body => _,
+            inout("r5") data => _,
+            lateout("r11") resumed,
+            clobber_abi("C"),
         );
+        resumed
     }
 }
 
+impl ArchContext for TaskContext {
+    /// İlk geçiş `task_start`'a zıplar; o da r14/r16'daki işlev ve argümanla
+    /// `task_entry`'yi çağırır.
+    fn new(entry: usize, arg: usize, stack_top: usize) -> Self {
+        Self {
+            r14: entry as u64,
+            r16: arg as u64,
+            r1: (stack_top & !0xF) as u64,
+            r9: task_start as usize as u64,
+            ..Default::default()
+        }
+    }
+
+    unsafe fn switch(&mut self, next: &Self) {
+        Self::switch_context(self, next);
+    }
+
+    unsafe fn catch(&mut self, body: extern "C" fn(*mut u8), data: *mut u8) -> usize {
+        Self::catch_call(self, body, data)
+    }
+
+    fn set_return_value(&mut self, value: usize) {
+        self.ret = value as u64;
+    }
+}
+
+extern "C" {
+    /// Yeni görevin ilk komutu (aşağıdaki `global_asm!`).
+    fn task_start();
+}
+
+core::arch::global_asm!(
+    ".global task_start",
+    "task_start:",
+    "l.or r3, r14, r0",
+    // Yığın geri izleme burada dursun
+    "l.or r2, r0, r0",
+    "l.jal {entry}",
+    " l.or r4, r16, r0", // Gecikme yuvası
+    "l.trap 0",
+    entry = sym task_entry,
+);
+
 
 // -----------------------------------------------------------------------------
 // Görev Başlatma (Task Entry)
@@ -135,6 +217,7 @@ impl TaskContext {
 /// * `func`: Görevin gerçek giriş noktası (başlatılacak fonksiyonun adresi, r3'te olmalı).
 /// * `arg`: Göreve geçirilen u64 formatında argüman (r4'te olmalı).
 extern "C" fn task_entry(func: u64, arg: u64) -> ! {
+    crate::context::task_started();
     serial_println!("[TASK] Yeni Görev Başlatılıyor. Argüman: {:#x}", arg);
 
     // Fonksiyon işaretçisini (u64) gerçek fonksiyona dönüştür.
@@ -146,15 +229,10 @@ extern "C" fn task_entry(func: u64, arg: u64) -> ! {
     // Gerçek görev fonksiyonunu çağır (arg, r4 (a1)'da beklenir)
     entry_func(arg);
 
-    // Görev tamamlandı. Normalde bu noktaya gelinmemelidir.
-    serial_println!("[TASK] Görev Tamamlandı! İşlemci durduruluyor.");
-    
-    // Görev tamamlandığında, çekirdek durdurulmalıdır.
-    loop {
-        unsafe {
-            io::idle(); // OpenRISC'te bekleme talimatı
-        }
-    }
+    // Görev işlevi döndü: görev sonlandırılır ve işlemci sıradaki göreve
+    // (en azından boşta görevine) bırakılır
+    serial_println!("[TASK] Görev Tamamlandı.");
+    crate::sched::exit_current()
 }
 
 
@@ -170,4 +248,28 @@ pub fn initialize_tasking() {
     let entry_point_addr = task_entry as *const (); 
     
     serial_println!("[TASK] Task Entry Adresi: {:#x}", entry_point_addr as u64);
-}
\ No newline at end of file
+
+    // Zamanlayıcı bundan sonra görevler arasında gerçekten geçer (bkz. `context`)
+    crate::context::enable();
+}
+
+// -----------------------------------------------------------------------------
+// İŞLEMCİ BAŞINA ALAN TABANI
+// -----------------------------------------------------------------------------
+
+/// Bu işlemcinin işlemci başına alanının (`percpu`) tabanını yazar (`r10`; ABI'de iş parçacığı işaretçisi olarak ayrılmıştır).
+///
+/// # Safety
+/// `base` bu işlemciye ayrılmış, `.percpu` şablonunun kopyası olan alanı
+/// göstermelidir.
+pub unsafe fn set_cpu_local_base(base: usize) {
+    asm!("l.ori r10, {}, 0", in(reg) base, options(nostack, preserves_flags, nomem));
+}
+
+/// Bu işlemcinin işlemci başına alanının tabanı (henüz yazılmadıysa 0).
+pub fn cpu_local_base() -> usize {
+    let base: usize;
+    // SAFETY: r10 derleyici tarafından kullanılmaz.
+    unsafe { asm!("l.ori {}, r10, 0", out(reg) base, options(nostack, preserves_flags, nomem)) };
+    base
+}
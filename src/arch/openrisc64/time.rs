@@ -64,6 +64,12 @@ pub fn read_timebase() -> Cycles {
     Cycles(cycles)
 }
 
+/// Serbest çalışan sayacın anlık değeri (bkz. `crate::clock`).
+#[inline(always)]
+pub fn read_cycles() -> Cycles {
+    read_timebase()
+}
+
 /// Çekirdek sayacının frekansını (Hz) döndürür.
 pub fn get_frequency() -> u64 {
     unsafe {
@@ -115,6 +121,33 @@ pub fn initialize_time_system() {
     serial_println!("[TIME] Varsayılan Frekans (Hz): {}", presumed_freq_hz);
 }
 
+// -----------------------------------------------------------------------------
+// Zamanlayıcı Karşılaştırıcısı (TTMR)
+// -----------------------------------------------------------------------------
+
+/// Tick Timer Mode Register (SPR 0x5000).
+const SPR_TTMR: u32 = 0x5000;
+/// TTMR: tek seferlik kip (M = 0b10, "eşleşince dur"), kesme etkin (IE).
+const TTMR_ONESHOT_IE: u64 = (0b10 << 30) | (1 << 29);
+/// TTMR.TP karşılaştırma alanı (28 bit).
+const TTMR_TP_MASK: u64 = 0x0FFF_FFFF;
+
+/// Tick Timer'ı `deadline`'ın alt 28 bitine eşleşince kesme üretecek
+/// şekilde kurar. Yazma TTMR.IP'yi de temizler.
+pub unsafe fn set_timer_deadline(deadline: Cycles) {
+    let ttmr = TTMR_ONESHOT_IE | (deadline.0 & TTMR_TP_MASK);
+    asm!("l.mtspr r0, {0}, {1}", in(reg) ttmr, const SPR_TTMR, options(nostack, nomem));
+}
+
+/// Tick Timer kesmesi bekliyor mu (TTMR.IP)?
+pub fn timer_pending() -> bool {
+    let ttmr: u64;
+    unsafe {
+        asm!("l.mfspr {0}, r0, {1}", out(reg) ttmr, const SPR_TTMR, options(nostack, nomem));
+    }
+    ttmr & (1 << 28) != 0
+}
+
 // Platforma özel G/Ç fonksiyonları için bir yer tutucu (main.rs veya platformmod.rs'de tamamlanmalıdır)
 mod io {
     use core::arch::asm;
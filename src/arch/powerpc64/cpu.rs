@@ -0,0 +1,51 @@
+// src/arch/powerpc64/cpu.rs
+// PowerPC 64 işlemci özelliği algılama: PVR sürümünden işlemci kuşağı.
+
+#![allow(dead_code)]
+
+use core::arch::asm;
+use crate::cpu::CpuFeatures;
+
+/// PVR üst 16 bit sürüm değerleri.
+const PVR_POWER7: u64 = 0x003F;
+const PVR_POWER7P: u64 = 0x004A;
+const PVR_POWER8E: u64 = 0x004B;
+const PVR_POWER8NVL: u64 = 0x004C;
+const PVR_POWER8: u64 = 0x004D;
+const PVR_POWER9: u64 = 0x004E;
+const PVR_POWER10: u64 = 0x0080;
+
+/// Desteklenen kuşak (7, 8, 9, 10); tanınmayan işlemci 0.
+fn generation(pvr: u64) -> u32 {
+    match pvr >> 16 {
+        PVR_POWER7 | PVR_POWER7P => 7,
+        PVR_POWER8E | PVR_POWER8NVL | PVR_POWER8 => 8,
+        PVR_POWER9 => 9,
+        PVR_POWER10 => 10,
+        _ => 0,
+    }
+}
+
+pub fn detect() -> CpuFeatures {
+    let pvr: u64;
+    // SAFETY: PVR (SPR 287) her kipte okunabilir.
+    unsafe { asm!("mfpvr {0}", out(reg) pvr, options(nomem, nostack)) };
+    let generation = generation(pvr);
+    CpuFeatures {
+        id: pvr,
+        fpu: true,
+        // VMX (AltiVec) POWER6'dan beri vardır
+        simd: generation >= 7,
+        // vcipher, vshasigmaw, vpmsumw POWER8 (ISA 2.07) ile gelir
+        aes: generation >= 8,
+        sha2: generation >= 8,
+        crc32: generation >= 8,
+        // LL/SC (ldarx/stdcx.) tek talimatlı RMW değildir
+        atomics: false,
+        large_pages: true,
+        // Radix MMU 1 GiB sayfaları POWER9 ile gelir
+        huge_pages: generation >= 9,
+        // darn POWER9 (ISA 3.0) ile gelir
+        rng: generation >= 9,
+    }
+}
@@ -0,0 +1,89 @@
+// src/arch/powerpc64/debug.rs
+// PowerPC 64 mimarisine özgü kesme noktası, tek adım ve gözlem noktası desteği.
+
+#![allow(dead_code)]
+
+use core::arch::asm;
+use crate::debug::WatchKind;
+use crate::platformgeneric::KernelError;
+
+/// `tw 31, 0, 0` (koşulsuz tuzak) talimatı.
+pub const BREAKPOINT_INSN: u32 = 0x7FE0_0008;
+
+/// Kesme noktası talimatının uzunluğu (bayt).
+pub const BREAKPOINT_INSN_LEN: usize = 4;
+
+/// SRR0 tuzak talimatının kendisini gösterir; düzeltme gerekmez.
+pub const BREAKPOINT_PC_ADJUST: usize = 0;
+
+/// MSR.SE (Single-Step Trace Enable) biti.
+const MSR_SE: u64 = 1 << 10;
+
+/// Veri önbelleği bloğunu yazar, talimat önbelleği bloğunu geçersiz kılar.
+#[inline(always)]
+pub unsafe fn sync_icache(addr: usize, _len: usize) {
+    asm!("dcbst 0, {0}", "sync", "icbi 0, {0}", "isync", in(reg) addr, options(nostack));
+}
+
+/// Kaydedilmiş MSR kopyasında (SRR1) SE bitini ayarlar. `rfid` sonrası tek
+/// talimat yürütülür ve İz (Trace) kesmesi oluşur.
+pub fn set_single_step(srr1: &mut u64, enable: bool) -> Result<(), KernelError> {
+    if enable {
+        *srr1 |= MSR_SE;
+    } else {
+        *srr1 &= !MSR_SE;
+    }
+    Ok(())
+}
+
+// -----------------------------------------------------------------------------
+// DONANIM GÖZLEM NOKTASI (DAWR0 / DAWRX0, POWER8+)
+// -----------------------------------------------------------------------------
+
+/// Tek bir Data Address Watchpoint yazmaç çifti.
+pub const MAX_WATCHPOINTS: usize = 1;
+
+/// Eşleşme, erişim gerçekleşmeden önce Veri Depolama kesmesi (DSISR.DABRMATCH) üretir.
+pub const WATCH_BEFORE_ACCESS: bool = true;
+
+const SPR_DAWR0: u32 = 180;
+const SPR_DAWRX0: u32 = 188;
+
+// DAWRX alanları
+const DAWRX_USER: u64 = 1 << 0;   // Problem durumu (kullanıcı) erişimleri
+const DAWRX_KERNEL: u64 = 1 << 1; // Ayrıcalıklı erişimler
+const DAWRX_DR: u64 = 1 << 5;     // Okumada eşleş
+const DAWRX_DW: u64 = 1 << 6;     // Yazmada eşleş
+
+/// Çift kelime hizalı adresi DAWR0'a, erişim türünü DAWRX0'a yazar.
+/// MRD (eşleşme aralığı) 0'dır: tek çift kelime (8 bayt) izlenir.
+pub unsafe fn set_watchpoint(_slot: usize, addr: usize, _len: usize, kind: WatchKind) -> Result<(), KernelError> {
+    let access = match kind {
+        WatchKind::Read => DAWRX_DR,
+        WatchKind::Write => DAWRX_DW,
+        WatchKind::ReadWrite => DAWRX_DR | DAWRX_DW,
+    };
+    let dawrx = DAWRX_USER | DAWRX_KERNEL | access;
+
+    asm!(
+        "mtspr {dawr}, {addr}",
+        "mtspr {dawrx}, {ctl}",
+        "isync",
+        dawr = const SPR_DAWR0,
+        dawrx = const SPR_DAWRX0,
+        addr = in(reg) (addr & !7) as u64,
+        ctl = in(reg) dawrx,
+        options(nostack, nomem)
+    );
+    Ok(())
+}
+
+/// DAWRX0'ı sıfırlayarak eşleşmeyi kapatır.
+pub unsafe fn clear_watchpoint(_slot: usize) {
+    asm!("mtspr {dawrx}, {zero}", "isync", dawrx = const SPR_DAWRX0, zero = in(reg) 0u64, options(nostack, nomem));
+}
+
+/// Hatalı veri adresi DAR'da bildirilir; yuvanın ayrıca sorgulanması gerekmez.
+pub fn watchpoint_hit() -> Option<usize> {
+    None
+}
@@ -1,40 +1,29 @@
 use core::arch::asm;
 use core::fmt;
 use crate::serial_println;
+use crate::trap::{handle_fatal, Access, Cause};
+use super::vectors::{powerpc64_vectors, VECTORS_SIZE};
 
 // -----------------------------------------------------------------------------
-// HARİCİ MONTAJ DİLİ İŞLEYİCİLERİ
+// İSTİSNA VEKTÖRLERİ
 // -----------------------------------------------------------------------------
 
-// PowerPC'de her istisna (exception) sabit bir vektör adresine sıçrar. 
-// Montaj kodu, bu adreslere yerleştirilmelidir (genellikle 0x100, 0x200, 0x300, ...).
-extern "C" {
-    /// 0x100: Makine Kontrol Programı Kesmesi (Machine Check Interrupt - MCP)
-    fn vector_mcp();
-    /// 0x200: Sistem Yazılımı Tuzağı (System Software Trap)
-    fn vector_system_software_trap();
-    /// 0x300: Dış Kesme (Harici IRQ)
-    fn vector_external_interrupt();
-    /// 0x500: Veri Depolama Kesmesi (Data Storage Interrupt - Sayfa Hatası Yükleme/Depolama)
-    fn vector_data_storage();
-    /// 0x600: Talimat Depolama Kesmesi (Instruction Storage Interrupt - Sayfa Hatası Talimat)
-    fn vector_instruction_storage();
-    /// 0xC00: Sistem Çağrısı (SYSCALL)
-    fn vector_syscall();
-    /// 0xD00: Program Kontrol Kesmesi (Program Interrupt - Geçersiz Talimat vb.)
-    fn vector_program_interrupt();
-}
+// PowerPC'de her istisna (exception) sabit bir vektör adresine sıçrar
+// (0x100, 0x200, 0x300, ...). Vektörler `vectors.rs`'de `trap_vectors!` ile
+// 0x80 baytlık yuvalar halinde üretilir ve `init_exceptions` tarafından
+// fiziksel 0x100..0x1000 aralığına kopyalanır. Her yuva
+// `generic_exception_handler`'ı vektör ofsetiyle çağırır.
 
 // -----------------------------------------------------------------------------
 // 1. İSTİSNA KAYIT YAPILARI
 // -----------------------------------------------------------------------------
 
 /// PowerPC 64'te istisna oluştuğunda yığına kaydedilen CPU durumu (Context).
-/// Bu yapının düzeni, montaj kodunun registerları yığına kaydettiği sıraya UYMALIDIR.
+/// Bu yapının düzeni, `vectors.rs`'deki ortak kodun registerları yığına kaydettiği sıraya UYMALIDIR.
 #[repr(C)]
 pub struct ExceptionContext {
-    // Montaj kodunun kaydettiği tüm genel amaçlı registerlar (r1-r31)
-    // r1, r2 (SP, RTOC) dahil edilmezse 30 GPR
+    // Ortak kodun kaydettiği genel amaçlı registerlar: gpr[0] = r0, gpr[n - 2] = rN (3-31).
+    // r1 (SP) aşağıda ayrıca tutulur, r2 (TOC) çekirdekte değişmediğinden kaydedilmez.
     pub gpr: [u64; 30], 
     
     // Kritik Kontrol Yazmaçları
@@ -48,6 +37,20 @@ pub struct ExceptionContext {
     pub sp: u64, // Yığın İşaretçisi (r1)
 }
 
+// Ofsetler `vectors.rs`'deki `powerpc64_common` gövdesine gömülüdür
+// (çerçeve: 32 baytlık ELFv2 alanı + bu yapı).
+crate::asm_layout!(ExceptionContext {
+    gpr: 0,
+    srr0: 240,
+    srr1: 248,
+    xer: 256,
+    ctr: 264,
+    lr: 272,
+    dar: 280,
+    dsisr: 288,
+    sp: 296,
+}, size == 336 - 32);
+
 /// Temel istisna tipleri (Vektör adreslerine göre).
 #[repr(u64)]
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -57,11 +60,42 @@ pub enum ExceptionType {
     ExternalInt = 0x300, // Harici Kesme (IRQ)
     DataStorage = 0x500, // Veri Depolama (Sayfa Hatası Yükleme/Depolama)
     InstructionStorage = 0x600, // Talimat Depolama (Sayfa Hatası Talimat)
+    Decrementer = 0x900, // Decrementer (zamanlayıcı karşılaştırıcısı)
     Syscall = 0xC00,    // Sistem Çağrısı
     ProgramInt = 0xD00,  // Program Kesmesi
     _Unknown,
 }
 
+/// Vektör ofsetini ve kayıtlı durum yazmaçlarını ortak `trap::Cause` tipine çevirir.
+pub fn decode_cause(vector_offset: u64, context: &ExceptionContext) -> Cause {
+    match vector_offset {
+        0x100 => Cause::MachineError,
+        // Gerçek IRQ numarası kesme denetleyicisinden (XICS/MPIC) okunur
+        0x300 => Cause::Interrupt(0),
+        // DSISR 9. biti (IBM numaralandırması, 0x0040_0000): DAWR/DABR eşleşmesi
+        0x500 if context.dsisr & 0x0040_0000 != 0 => Cause::Watchpoint { addr: context.dar as usize },
+        0x500 => {
+            // DSISR 6. biti (IBM numaralandırması, 0x0200_0000) yazma erişimini belirtir
+            let access = if context.dsisr & 0x0200_0000 != 0 { Access::Write } else { Access::Read };
+            Cause::PageFault { access, addr: context.dar as usize }
+        }
+        0x600 => Cause::PageFault { access: Access::Execute, addr: context.srr0 as usize },
+        0xC00 => Cause::Syscall,
+        0xD00 => {
+            // SRR1 nedeni: 0x20000 = tuzak talimatı (tw/td), 0x80000 = geçersiz talimat.
+            // MSR.SE (0x400) kayıtlı durumda açıksa bu bir iz (tek adım) tuzağıdır.
+            if context.srr1 & 0x400 != 0 {
+                Cause::SingleStep
+            } else if context.srr1 & 0x20000 != 0 {
+                Cause::Breakpoint
+            } else {
+                Cause::IllegalInstruction
+            }
+        }
+        _ => Cause::Unknown(vector_offset),
+    }
+}
+
 // -----------------------------------------------------------------------------
 // 2. TEMEL İŞLEYİCİ FONKSİYONLARI (Rust Kodu)
 // -----------------------------------------------------------------------------
@@ -77,6 +111,7 @@ pub extern "C" fn generic_exception_handler(vector_offset: u64, context: &mut Ex
         0x300 => ExceptionType::ExternalInt,
         0x500 => ExceptionType::DataStorage,
         0x600 => ExceptionType::InstructionStorage,
+        0x900 => ExceptionType::Decrementer,
         0xC00 => ExceptionType::Syscall,
         0xD00 => ExceptionType::ProgramInt,
         _ => ExceptionType::_Unknown,
@@ -87,37 +122,65 @@ pub extern "C" fn generic_exception_handler(vector_offset: u64, context: &mut Ex
             // Harici Kesme İşleyicisi
             handle_interrupt(context);
         }
+        ExceptionType::Decrementer => {
+            // DEC yeniden kurulana dek kesme bekler durumda kalmaz (kenar tetiklemeli)
+            let _irq = crate::irqstack::enter(context as *const _ as usize);
+            crate::hrtimer::timer_interrupt();
+        }
         ExceptionType::DataStorage | ExceptionType::InstructionStorage => {
             // Sayfa Hatası İşleyicileri
+            // DAWR eşleşmeleri de Veri Depolama kesmesiyle gelir; önce hata ayıklayıcıya sunulur.
+            let trap_cause = decode_cause(vector_offset, context);
+            if crate::debug::handle_trap(trap_cause, &mut context.srr0, &mut context.srr1) {
+                return;
+            }
+
+            // Kullanıcı belleği erişimi veya bellek yoklaması ise düzeltme tablosuna bak
+            if crate::fixup::fixup_exception(trap_cause, &mut context.srr0) {
+                return;
+            }
+
             serial_println!("\n--- SAYFA HATASI ---");
             serial_println!("Neden: {:?}", cause);
             serial_println!("Hata Adresi (DAR): {:#x}", context.dar);
             serial_println!("RIP (SRR0): {:#x}", context.srr0);
             
             // DSISR'ı kontrol ederek hatanın tipini (yazma/okuma) belirle.
-            panic!("Kritik Sayfa Hatası!");
+            handle_fatal(trap_cause, context.srr0 as usize);
         }
         ExceptionType::Syscall => {
             // Sistem Çağrısı
-            serial_println!("SYSCALL: Çağrı kodu: {}", context.gpr[0]); // r3'te olması gerekir, r0'ı kullanıyoruz
+            serial_println!("SYSCALL: Çağrı kodu: {}", context.gpr[0]); // r0
             
             // Syscall'dan dönmek için SRR0'ı bir sonraki talimata ilerletmeniz GEREKİR.
             context.srr0 += 4; // PPC64 talimatları 4 bayttır.
         }
         _ => {
+            // Kesme noktası (tw) ve tek adım önce hata ayıklayıcıya sunulur.
+            let trap_cause = decode_cause(vector_offset, context);
+            if crate::debug::handle_trap(trap_cause, &mut context.srr0, &mut context.srr1) {
+                return;
+            }
+
+            // Makine denetimi (MMIO yoklaması) ve hizalama hataları için düzeltme tablosuna bak
+            if crate::fixup::fixup_exception(trap_cause, &mut context.srr0) {
+                return;
+            }
+
             // Diğer kritik senkron istisnalar
             serial_println!("\n--- KRİTİK İSTİSNA ---");
             serial_println!("Vektör: {:#x} ({:?})", vector_offset, cause);
             serial_println!("RIP (SRR0): {:#x}", context.srr0);
             serial_println!("MSR (SRR1): {:#x}", context.srr1);
             
-            panic!("İşlenmemiş İstisna!");
+            handle_fatal(trap_cause, context.srr0 as usize);
         }
     }
 }
 
 /// Donanım Kesmeleri (IRQ) için özel işleyici.
-fn handle_interrupt(_context: &mut ExceptionContext) {
+fn handle_interrupt(context: &mut ExceptionContext) {
+    let _irq = crate::irqstack::enter(context as *const _ as usize);
     // 1. PIC (MPIC/PIM) MMIO adresinden hangi IRQ'nun geldiğini oku.
     
     // 2. Uygun sürücüyü çağır.
@@ -136,8 +199,10 @@ fn handle_interrupt(_context: &mut ExceptionContext) {
 /// İstisna vektörlerini kurar ve kesmeleri etkinleştirir.
 pub fn init_exceptions() {
     unsafe {
-        // 1. Vektör İşleyicileri (Montaj Kodu) sabit adreslerde (0x000...0x1000)
-        //    bulunmalıdır. Bu, bağlayıcı (linker) komut dosyası tarafından sağlanır.
+        // 1. Vektör İşleyicileri sabit adreslerde (0x000...0x1000) bulunmalıdır.
+        //    Üretilen tablo oraya kopyalanır; 0x000..0x100 ayrılmıştır ve atlanır
+        //    (aynı zamanda boş göstericiye yazmaktan kaçınılır).
+        install_vectors();
         
         // 2. MSR (Machine State Register) yazmacını ayarla (Kesmeleri etkinleştirme).
         // MSR'nin bitleri, harici kesme (EE), makine kontrol kesmesi (ME) gibi 
@@ -159,4 +224,36 @@ pub fn init_exceptions() {
     
     serial_println!("[PPC64] İstisna Yönetimi başlatıldı.");
     serial_println!("[PPC64] Harici kesmeler (EE) etkinleştirildi.");
-}
\ No newline at end of file
+}
+
+/// Vektör tablosunu fiziksel 0x100..0x1000'e kopyalar ve talimat önbelleğini
+/// eşitler (dcbst + icbi, 128 baytlık satırlar).
+///
+/// # Safety
+/// Gerçek kipte veya kimlik eşlemesiyle, 0x100..0x1000 çekirdek tarafından
+/// başka amaçla kullanılmıyorken çağrılmalıdır.
+unsafe fn install_vectors() {
+    const FIRST: usize = 0x100;
+    let src = powerpc64_vectors as usize as *const u8;
+    core::ptr::copy_nonoverlapping(src.add(FIRST), FIRST as *mut u8, VECTORS_SIZE - FIRST);
+    for line in (FIRST..VECTORS_SIZE).step_by(128) {
+        asm!("dcbst 0, {0}", "sync", "icbi 0, {0}", in(reg_nonzero) line, options(nostack));
+    }
+    asm!("sync", "isync", options(nostack));
+}
+
+// -----------------------------------------------------------------------------
+// 4. KESME YIĞINI
+// -----------------------------------------------------------------------------
+
+/// Kesmeler kesilen görevin yığınında çalışır; giriş kodu yığın değiştirmez
+/// (bkz. `crate::irqstack`).
+pub const SWITCHES_IRQ_STACK: bool = false;
+
+/// Ayrı kesme yığını olmadığından etkisizdir.
+///
+/// # Safety
+/// Diğer portlarla aynı sözleşme: `top` bu işlemciye ait bir yığının üstü olmalıdır.
+pub unsafe fn install_irq_stack(_top: usize) -> Result<(), crate::platformgeneric::KernelError> {
+    Ok(())
+}
@@ -0,0 +1,56 @@
+// src/arch/powerpc64/hypervisor.rs
+// PowerPC 64 hypervisor algılama: MSR.HV ve PAPR hvterm konsolu.
+
+#![allow(dead_code)]
+
+use core::arch::asm;
+use crate::hypervisor::{HypervisorKind, Paravirt};
+
+/// MSR.HV (Hypervisor State) biti.
+const MSR_HV: u64 = 1 << 60;
+
+/// PAPR hcall: H_PUT_TERM_CHAR.
+const H_PUT_TERM_CHAR: u64 = 0x58;
+/// H_SUCCESS dönüş kodu.
+const H_SUCCESS: i64 = 0;
+/// İlk sanal terminalin (vty) kimliği.
+const VTERM_NO: u64 = 0;
+
+/// PAPR hypervisor çağrısı (`sc 1`).
+unsafe fn hcall(opcode: u64, arg0: u64, arg1: u64, arg2: u64) -> i64 {
+    let ret: i64;
+    asm!(
+        "sc 1",
+        inlateout("r3") opcode => ret,
+        in("r4") arg0,
+        in("r5") arg1,
+        in("r6") arg2,
+        options(nostack)
+    );
+    ret
+}
+
+/// MSR.HV temizse çekirdek LPAR/misafir olarak çalışıyordur (KVM-PR/HV, PowerVM).
+/// Hangi hypervisor olduğu `/hypervisor` düğümü olmadan ayırt edilemez.
+pub fn detect() -> HypervisorKind {
+    let msr: u64;
+    unsafe { asm!("mfmsr {}", out(reg) msr, options(nomem, nostack)) };
+    if msr & MSR_HV != 0 { HypervisorKind::None } else { HypervisorKind::Other }
+}
+
+/// Misafir modunda öykünülmüş UART yerine hvterm konsolunu kullanır.
+pub unsafe fn enable_paravirt(_kind: HypervisorKind) -> Paravirt {
+    Paravirt { console: true, ..Paravirt::default() }
+}
+
+/// `enable_paravirt` saat olanağını açmaz; Timebase zaten sanallaştırılmıştır.
+pub fn pv_clock_ns() -> u64 {
+    0
+}
+
+/// Baytı H_PUT_TERM_CHAR ile yazar. Karakterler r5/r6'da sola dayalı paketlenir.
+pub fn pv_console_write_byte(byte: u8) {
+    let packed = (byte as u64) << 56;
+    // Meşgul dönüşünde (H_BUSY) tekrar dene
+    while unsafe { hcall(H_PUT_TERM_CHAR, VTERM_NO, 1, packed) } != H_SUCCESS {}
+}
@@ -147,4 +147,85 @@ pub fn handle_external_interrupts() {
             send_eoi(irq_id);
         }
     }
-}
\ No newline at end of file
+}
+
+// -----------------------------------------------------------------------------
+// YEREL KESME MASKESİ
+// -----------------------------------------------------------------------------
+
+// Genel kod bunları `crate::irqoff` üzerinden kullanır. `asm!` bloklarında
+// `nomem` yoktur: maske değişikliği derleyici için bir bellek engelidir,
+// kilitli bölgenin erişimleri dışarı taşınmaz.
+
+/// MSR.EE biti (harici kesme etkin).
+const MSR_EE: usize = 1 << 15;
+
+/// Harici kesmeleri kapatır; önceki MSR değerini döndürür.
+#[inline(always)]
+pub fn local_irq_save() -> usize {
+    let flags: usize;
+    // SAFETY: `mtmsrd r,1` yalnızca EE ve RI bitlerini yazar; RI korunur.
+    unsafe {
+        asm!(
+            "mfmsr {f}",
+            "andc {t}, {f}, {ee}",
+            "mtmsrd {t}, 1",
+            f = out(reg) flags,
+            t = out(reg) _,
+            ee = in(reg) MSR_EE,
+            options(nostack)
+        )
+    };
+    flags
+}
+
+/// `local_irq_save` öncesindeki MSR.EE değerini geri yazar.
+#[inline(always)]
+pub fn local_irq_restore(flags: usize) {
+    // SAFETY: L=1 kipi yalnızca EE ve RI'yi kaydedilen değerden geri yazar.
+    unsafe { asm!("mtmsrd {}, 1", in(reg) flags, options(nostack)) };
+}
+
+/// Kaydedilen durumda MSR.EE açık mıydı?
+#[inline(always)]
+pub fn irqs_were_enabled(flags: usize) -> bool {
+    flags & MSR_EE != 0
+}
+
+/// Harici kesmeleri koşulsuz açar.
+#[inline(always)]
+pub fn local_irq_enable() {
+    // SAFETY: Çağıran kesme almaya hazırdır.
+    unsafe {
+        asm!(
+            "mfmsr {t}",
+            "or {t}, {t}, {ee}",
+            "mtmsrd {t}, 1",
+            t = out(reg) _,
+            ee = in(reg) MSR_EE,
+            options(nostack)
+        )
+    };
+}
+
+// -----------------------------------------------------------------------------
+// HAT MASKESİ VE PCI INTx
+// -----------------------------------------------------------------------------
+
+/// Kesme hattını denetleyicide maskeler veya açar (bkz. `crate::irqstorm`);
+/// `irq` dağıtıcının gördüğü numaradır. Desteklenmiyorsa `false`.
+pub fn set_irq_masked(_irq: u32, _masked: bool) -> bool {
+    false
+}
+
+/// PCI aygıtının INTx hattını açar ve dağıtıcının göreceği kesme numarasını
+/// döndürür. INTx eşlemesi yoksa `None`; sürücü yoklamayla çalışır.
+pub fn route_pci_intx(_line: u8) -> Option<u32> {
+    None
+}
+
+/// Aygıt ağacı düğümünün ilk kesmesini denetleyicinin göreceği numaraya
+/// çevirir. Bu portun denetleyicisi tek hücreli kesme belirteci kullanır.
+pub fn decode_fdt_irq(node: &crate::fdt::Node) -> Option<u32> {
+    node.interrupt_cell(0)
+}
@@ -22,6 +22,18 @@ pub unsafe fn mmio_write_u8(addr: usize, value: u8) {
     write_volatile(addr as *mut u8, value)
 }
 
+/// Belirtilen bellek adresinden (MMIO) bir u16 okur.
+#[inline(always)]
+pub unsafe fn mmio_read_u16(addr: usize) -> u16 {
+    read_volatile(addr as *const u16)
+}
+
+/// Belirtilen bellek adresine (MMIO) bir u16 yazar.
+#[inline(always)]
+pub unsafe fn mmio_write_u16(addr: usize, value: u16) {
+    write_volatile(addr as *mut u16, value)
+}
+
 /// Belirtilen bellek adresinden (MMIO) bir u32 okur.
 #[inline(always)]
 pub unsafe fn mmio_read_u32(addr: usize) -> u32 {
@@ -85,4 +97,20 @@ pub fn isync() {
 #[inline(always)]
 pub fn io_wait() {
     sync();
-}
\ No newline at end of file
+}
+
+// -----------------------------------------------------------------------------
+// PCI YAPILANDIRMA ALANI
+// -----------------------------------------------------------------------------
+
+/// G/Ç portu yok; yapılandırma alanına ECAM penceresinden erişilir
+/// (bkz. `crate::drivers::pci`).
+pub const PCI_CONFIG_PORTS: bool = false;
+
+/// Port mekanizması olmadığından okunan her kelime `u32::MAX`'tır.
+pub fn pci_config_read32(_address: u32) -> u32 {
+    u32::MAX
+}
+
+/// Port mekanizması olmadığından etkisizdir.
+pub fn pci_config_write32(_address: u32, _value: u32) {}
@@ -3,7 +3,10 @@
 
 use core::arch::asm;
 use core::ptr::NonNull;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use crate::platformgeneric::KernelError;
 use crate::serial_println;
+use crate::memory::vmaudit::{validate_mapping, CacheAttr, MapError, Mapping, Perms};
 use super::io; // Bariyerler için io modülünü kullanacağız
 
 // -----------------------------------------------------------------------------
@@ -95,57 +98,111 @@ fn get_indices(virtual_addr: usize) -> (usize, usize, usize, usize) {
     (l1, l2, l3, l4) 
 }
 
-/// Yeni bir sayfa tablosu tahsis etmeyi simüle eder.
-fn alloc_page_table() -> NonNull<PageTable> {
-    // Mock Tahsis
-    static mut PAGE_TABLE_MOCK: [u8; PAGE_SIZE] = [0; PAGE_SIZE];
-    
-    let ptr = unsafe { PAGE_TABLE_MOCK.as_mut_ptr() as *mut PageTable };
-    let table = unsafe { &mut *ptr };
-    table.entries.iter_mut().for_each(|e| *e = PageTableEntry::zero());
-    
-    unsafe { NonNull::new_unchecked(ptr) }
+/// Sıfırlanmış yeni bir sayfa tablosu ayırır (bkz. `physmap::alloc_table`).
+fn alloc_page_table() -> Result<NonNull<PageTable>, MapError> {
+    let addr = crate::physmap::alloc_table().ok_or(MapError::OutOfMemory)?;
+    // SAFETY: `alloc_table` sıfırdan farklı, sayfa hizalı bir adres döndürür.
+    Ok(unsafe { NonNull::new_unchecked(addr as *mut PageTable) })
+}
+
+/// Giriş bir alt tabloyu mu gösteriyor? (TABLE=1, LARGE_PAGE=0)
+fn is_table(entry: &PageTableEntry) -> bool {
+    entry.0 & PageFlags::TABLE as u64 != 0 && entry.0 & PageFlags::LARGE_PAGE as u64 == 0
 }
 
+/// Tablo girişi geçerliyse gösterdiği tabloyu, değilse yeni ayrılan tabloyu
+/// döndürür; yeni tablo üst tablonun giriş sayısına eklenir (`physmap::table_ref`).
+/// Giriş bir yapraksa (büyük sayfa) adres zaten eşlidir.
+unsafe fn next_table(table: &mut PageTable, index: usize) -> Result<&'static mut PageTable, MapError> {
+    let table_addr = table as *mut PageTable as usize;
+    let entry = &mut table.entries[index];
+    if !entry.is_valid() {
+        *entry = PageTableEntry::new_table(alloc_page_table()?.as_ptr() as usize);
+        crate::physmap::table_ref(table_addr);
+    } else if !is_table(entry) {
+        return Err(MapError::AlreadyMapped);
+    }
+    Ok(&mut *((entry.0 & PageFlags::ADDR_MASK as u64) as *mut PageTable))
+}
 
 /// Sanal adresi fiziksel adrese eşler (4KiB sayfa).
+///
+/// Sanal adres 48 bit kanonik, fiziksel adres 52 bit olmalıdır. Bayraklar
+/// adres alanına taşamaz: WIMGE alanının W biti (bit 12) adresin en düşük
+/// bitiyle çakıştığından bu temsili düzende kullanılamaz. TABLE/LARGE_PAGE
+/// yaprak bayrağı değildir.
+///
+/// # Hatalar
+/// * `Unaligned` / `OutOfRange` / `InvalidFlags`: bkz. yukarısı ve `validate_mapping`.
+/// * `AlreadyMapped`: Adres başka bir adrese veya bayraklarla eşli ya da bir
+///   büyük sayfanın içinde.
+/// * `OutOfMemory`: Ara tablo ayrılamadı.
 pub unsafe fn map_page(
     root_table_addr: usize, // L1 tablosunun fiziksel adresi
     virtual_addr: usize,
     physical_addr: usize,
     flags: u64,
-) {
+) -> Result<(), MapError> {
+    validate_mapping(virtual_addr, physical_addr, PAGE_SIZE, 48, 52, flags, PageFlags::ADDR_MASK as u64)?;
+    if flags & (PageFlags::TABLE as u64 | PageFlags::LARGE_PAGE as u64) != 0 {
+        return Err(MapError::InvalidFlags);
+    }
     let (l1i, l2i, l3i, l4i) = get_indices(virtual_addr);
     let l1_table = &mut *(root_table_addr as *mut PageTable);
-    
-    // L1
-    let l2_entry = l1_table.entries.get_mut(l1i).expect("L1 Index Hata");
-    let l2_addr = if l2_entry.is_valid() {
-        l2_entry.0 & PageFlags::ADDR_MASK as u64
-    } else {
-        let new_l2 = alloc_page_table();
-        let new_addr = new_l2.as_ptr() as usize;
-        *l2_entry = PageTableEntry::new_table(new_addr);
-        new_addr as u64
-    } as usize;
-    
-    // ... (L2, L3, L4 için benzer mantık uygulanır)
-    
-    let l2_table = &mut *(l2_addr as *mut PageTable);
-    let l3_entry = l2_table.entries.get_mut(l2i).expect("L2 Index Hata");
-    // Basitleştirme: L3 ve L4 oluşturma adımları atlanmıştır.
-    
-    // Son eşleme: L4 tablosunda
-    // Bu, L3'ün işaret ettiği L4 tablosu olmalıdır.
-    // L4 tablosunun fiziksel adresini almanız ve ardından:
-    
-    // Temsili L4 Tablosu Erişimi (Gerçek kodda yukarıdaki gibi hiyerarşi takip edilmeli)
-    let l4_table = &mut *(l2_addr as *mut PageTable); // Hata: Bu L2 adresidir.
-    let final_entry = l4_table.entries.get_mut(l4i).expect("L4 Index Hata");
 
-    *final_entry = PageTableEntry::new_page(physical_addr, flags);
-    
+    // L1 -> L2 -> L3 -> L4; eksik tablolar oluşturulur
+    let l2_table = next_table(l1_table, l1i)?;
+    let l3_table = next_table(l2_table, l2i)?;
+    let l4_table = next_table(l3_table, l3i)?;
+
+    // Son eşleme: L4 tablosunda. Aynı eşlemenin tekrarı etkisizdir; farklı
+    // bir eşlemenin üzerine yazılmaz.
+    let l4_addr = l4_table as *mut PageTable as usize;
+    let entry = &mut l4_table.entries[l4i];
+    let new = PageTableEntry::new_page(physical_addr, flags);
+    if entry.is_valid() {
+        return if entry.0 == new.0 { Ok(()) } else { Err(MapError::AlreadyMapped) };
+    }
+    *entry = new;
+    crate::physmap::table_ref(l4_addr);
+
     // Sayfa tablosu güncellendi, TLB temizliği gereklidir (tlbia ile yapılır).
+    Ok(())
+}
+
+/// Sanal adresin 4 KiB eşlemesini kaldırır, TLB'yi boşaltır ve boşalan ara
+/// tabloları serbest bırakır. Eşlenmiş fiziksel adresi döndürür.
+///
+/// # Hatalar
+/// * `NotMapped`: Adres 4 KiB sayfa olarak eşli değil.
+pub unsafe fn unmap_page(root_table_addr: usize, virtual_addr: usize) -> Result<usize, MapError> {
+    let (l1i, l2i, l3i, l4i) = get_indices(virtual_addr);
+    let indices = [l1i, l2i, l3i, l4i];
+    let mut path = [root_table_addr, 0, 0, 0];
+    for level in 0..3 {
+        let entry = (*(path[level] as *const PageTable)).entries[indices[level]];
+        if !entry.is_valid() || !is_table(&entry) {
+            return Err(MapError::NotMapped);
+        }
+        path[level + 1] = (entry.0 & PageFlags::ADDR_MASK as u64) as usize;
+    }
+    let entry = &mut (*(path[3] as *mut PageTable)).entries[l4i];
+    if !entry.is_valid() {
+        return Err(MapError::NotMapped);
+    }
+    let physical_addr = (entry.0 & PageFlags::ADDR_MASK as u64) as usize;
+    *entry = PageTableEntry::zero();
+    tlb_invalidate_all();
+
+    // Boşalan tablolar yukarı doğru serbest bırakılır; kök izlenmez (`None`).
+    let mut level = 3;
+    while crate::physmap::table_unref(path[level]) == Some(0) && level > 0 {
+        (*(path[level - 1] as *mut PageTable)).entries[indices[level - 1]] = PageTableEntry::zero();
+        tlb_invalidate_all();
+        crate::physmap::free_table(path[level]);
+        level -= 1;
+    }
+    Ok(physical_addr)
 }
 
 // -----------------------------------------------------------------------------
@@ -224,19 +281,147 @@ pub fn init_mmu() {
     let flags = PageFlags::KERNEL_RW as u64 
               | PageFlags::EXEC as u64 
               | PageFlags::ACCESSED as u64
-              | PageFlags::WIMGE_MASK as u64; // Temsili önbellek bayrakları
+              // Temsili önbellek bayrakları; W biti adres alanıyla çakışır (bkz. `map_page`)
+              | (PageFlags::WIMGE_MASK as u64 & !(PageFlags::ADDR_MASK as u64));
 
     for addr in (0..mapping_size).step_by(PAGE_SIZE) {
-        unsafe {
-            // Sanal ve fiziksel adresler aynı
-            map_page(l1_addr, addr, addr, flags);
+        // Sanal ve fiziksel adresler aynı
+        if let Err(e) = unsafe { map_page(l1_addr, addr, addr, flags) } {
+            serial_println!("[PPC64] {:#x} eşlenemedi ({:?}); sayfalama etkinleştirilmedi.", addr, e);
+            return;
         }
     }
     serial_println!("[PPC64] İlk 16MB birebir eşlendi.");
+    ROOT_TABLE.store(l1_addr, Ordering::Relaxed);
 
     // Sayfalamayı etkinleştir
     unsafe {
         // Not: l1_addr'ın fiziksel adresi olduğundan emin olun.
         enable_paging(l1_addr);
     }
-}
\ No newline at end of file
+}
+
+// -----------------------------------------------------------------------------
+// ÇEKİRDEK SANAL ALANI (vmalloc)
+// -----------------------------------------------------------------------------
+
+/// `memory::vmalloc` penceresinin başlangıcı (birebir eşlemenin dışında).
+pub const VMALLOC_BASE: usize = 0xFFFF_C000_0000_0000;
+/// `memory::vmalloc` penceresinin boyutu.
+pub const VMALLOC_SIZE: usize = 1 << 30;
+
+/// `init_mmu` tarafından kurulan kök tablo (0: henüz yok).
+static ROOT_TABLE: AtomicUsize = AtomicUsize::new(0);
+
+/// Çekirdek verisi için 4 KiB sayfa eşler (çekirdek R/W, yürütülemez).
+///
+/// # Hatalar
+/// * `Map(NotReady)`: Sayfalama henüz kurulmadı.
+/// * Eşleme hataları `MapError` dönüşümüyle (bkz. `vmaudit`).
+pub fn map_kernel_page(virt: usize, phys: usize) -> Result<(), KernelError> {
+    let root = ROOT_TABLE.load(Ordering::Relaxed);
+    if root == 0 {
+        return Err(MapError::NotReady.into());
+    }
+    let flags = PageFlags::KERNEL_RW as u64 | PageFlags::ACCESSED as u64 | PageFlags::DIRTY as u64;
+    // SAFETY: Pencere yalnızca `vmalloc` tarafından yönetilir.
+    unsafe { map_page(root, virt, phys, flags)? };
+    Ok(())
+}
+
+/// `map_kernel_page` eşlemesini kaldırır ve fiziksel adresini döndürür.
+pub unsafe fn unmap_kernel_page(virt: usize) -> Option<usize> {
+    match ROOT_TABLE.load(Ordering::Relaxed) {
+        0 => None,
+        root => unmap_page(root, virt).ok(),
+    }
+}
+
+// -----------------------------------------------------------------------------
+// SAYFA TABLOSU DENETİMİ (vm dump)
+// -----------------------------------------------------------------------------
+
+// WIMGE alanındaki bitler (bit 12: W ... bit 8: E).
+const WIMG_W: u64 = 1 << 12; // Write-through
+const WIMG_I: u64 = 1 << 11; // Caching-inhibited
+const WIMG_G: u64 = 1 << 9;  // Guarded (MMIO)
+
+/// Bir tablo seviyesini özyinelemeli olarak dolaşır (seviye 3: L1 ... seviye 0: 4K yaprak).
+unsafe fn walk_level(table_addr: usize, level: usize, base: usize, visit: &mut dyn FnMut(Mapping)) {
+    let table = &*(table_addr as *const PageTable);
+    let shift = 12 + 9 * level;
+
+    for (index, entry) in table.entries.iter().enumerate() {
+        if !entry.is_valid() {
+            continue;
+        }
+        let raw = entry.0;
+        let vaddr = base | (index << shift);
+        let addr = (raw & PageFlags::ADDR_MASK as u64) as usize;
+
+        let is_table = raw & PageFlags::TABLE as u64 != 0 && raw & PageFlags::LARGE_PAGE as u64 == 0;
+        if level > 0 && is_table {
+            walk_level(addr, level - 1, vaddr, visit);
+            continue;
+        }
+
+        let cache = if raw & WIMG_I != 0 {
+            if raw & WIMG_G != 0 { CacheAttr::Device } else { CacheAttr::Uncached }
+        } else if raw & WIMG_W != 0 {
+            CacheAttr::WriteThrough
+        } else {
+            CacheAttr::WriteBack
+        };
+        let user = raw & PageFlags::USER_RW as u64 != 0;
+
+        let size = 1usize << shift;
+        visit(Mapping {
+            vaddr,
+            paddr: addr & !(size - 1),
+            size,
+            perms: Perms {
+                read: true,
+                write: user || raw & PageFlags::KERNEL_RW as u64 != 0,
+                exec: raw & PageFlags::EXEC as u64 != 0,
+                user,
+            },
+            cache,
+        });
+    }
+}
+
+/// SDR1'deki kök tablodan başlayarak tüm yaprak eşlemeleri artan sanal adres sırasıyla bildirir.
+pub fn walk_mappings(visit: &mut dyn FnMut(Mapping)) {
+    unsafe {
+        let root = read_sdr1() & PageFlags::ADDR_MASK as u64;
+        if root != 0 {
+            walk_level(root as usize, 3, 0, visit);
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// KULLANICI SAYFALARI (mmap)
+// -----------------------------------------------------------------------------
+
+/// Kullanıcı sayfası eşlemesi bu portta henüz yok; `mmap` dosya eşlemeleri
+/// `PlatformSpecificError` ile reddedilir.
+///
+/// # Safety
+/// Diğer portlarla aynı sözleşme (bkz. `rv64i::mmu::map_user_page`).
+pub unsafe fn map_user_page(_virtual_addr: usize, _physical_addr: usize, _write: bool, _exec: bool) -> Result<(), crate::platformgeneric::KernelError> {
+    Err(crate::platformgeneric::KernelError::PlatformSpecificError(0))
+}
+
+/// Kullanıcı sayfası eşlenemediğinden etkisizdir.
+pub unsafe fn unmap_user_page(_virtual_addr: usize) {}
+
+// -----------------------------------------------------------------------------
+// AYGIT PENCERELERİ (physmap)
+// -----------------------------------------------------------------------------
+
+/// Büyük sayfalı çekirdek eşlemesi bu portta henüz yok; `physmap::map_mmio`
+/// `PlatformSpecificError` ile reddedilir.
+pub fn map_device(_phys: usize, _len: usize) -> Result<usize, crate::platformgeneric::KernelError> {
+    Err(crate::platformgeneric::KernelError::PlatformSpecificError(0))
+}
@@ -3,7 +3,6 @@
 
 use core::arch::asm;
 use core::panic::PanicInfo;
-use crate::serial_println;
 
 /// Panik anında işlemcinin durması için kullanılan sonsuz döngü.
 ///
@@ -11,8 +10,7 @@ use crate::serial_println;
 /// ve genellikle 'Halt' benzeri bir davranış (e.g., 'wait' talimatı veya 
 /// basit bir döngü) kullanılır.
 #[inline(always)]
-fn halt_loop() -> ! {
-    serial_println!("... İşlemci Durduruluyor (NOP döngüsü).");
+pub(crate) fn halt_loop() -> ! {
     loop {
         // PowerPC assembly'de doğrudan bir `hlt` yoktur. 
         // Bu yüzden kesmeleri devre dışı bırakmış bir ortamda 
@@ -33,32 +31,7 @@ fn halt_loop() -> ! {
 /// Bu fonksiyon, `panic!` makrosu çağrıldığında tetiklenir.
 #[panic_handler]
 fn panic(info: &PanicInfo) -> ! {
-    // 1. Seri porta veya ekrana hata mesajını yazdır.
-    serial_println!("\n========================================");
-    serial_println!("!!! KERNEL PANIC (POWERPC64) !!!");
-    
-    // Panik mesajını yazdır
-    if let Some(location) = info.location() {
-        serial_println!("Hata Konumu: {}:{}:{}", 
-            location.file(), 
-            location.line(), 
-            location.column()
-        );
-    } else {
-        serial_println!("Hata Konumu: Bilinmiyor");
-    }
-    
-    if let Some(message) = info.message() {
-        serial_println!("Hata Mesajı: {}", message);
-    } else {
-        serial_println!("Hata Mesajı: Yok");
-    }
-
-    serial_println!("========================================");
-
-    // 2. Tüm işlemci çekirdeklerini durdur.
-    // Çok çekirdekli sistemlerde (ki PPC64 genellikle öyledir), buraya gelindiğinde 
-    // diğer çekirdekleri durdurmak için bir IPI gönderilmesi gerekebilir.
-    
-    halt_loop();
-}
\ No newline at end of file
+    // Kesme bağlamı, iç içe panik ve diğer işlemcilerin durdurulması
+    // mimariden bağımsız yolda ele alınır (bkz. `crate::panic`).
+    crate::panic::handle(info, "POWERPC64")
+}
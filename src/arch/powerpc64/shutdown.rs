@@ -137,6 +137,13 @@ pub fn system_reboot() -> ! {
     halt_loop();
 }
 
+/// Sıcak veya soğuk yeniden başlatma ister (bkz. `crate::reboot`). Bu
+/// platformda tür ve neden donanıma iletilemez; her istek `system_reboot`
+/// ile yapılır.
+pub fn system_reset(_warm: bool, _reason: u32, _failure: bool) -> ! {
+    system_reboot()
+}
+
 // -----------------------------------------------------------------------------
 // Kapatma İşlevleri
 // -----------------------------------------------------------------------------
@@ -2,6 +2,8 @@
 // PowerPC 64 (PPC64) mimarisine özgü görev (task) ve bağlam (context) yönetimi.
 
 use core::arch::asm;
+use core::mem::offset_of;
+use crate::context::ArchContext;
 use crate::serial_println;
 
 /// Görev bağlamını (task context) saklamak için kullanılan yapı.
@@ -43,114 +45,267 @@ pub struct TaskContext {
     
     // Program Sayacı (r1'e geri dönüş adresi olarak kullanılır)
     pc: u64,      // PC - Görevin başlayacağı adres (genellikle LR ile aynıdır)
+
+    // Görev devam ettiğinde r3'te göreceği değer (`set_return_value`).
+    ret: u64,
 }
 
 impl TaskContext {
-    /// Yeni bir görev bağlamı oluşturur.
-    /// 
-    /// # Argümanlar
-    /// * `stack_top`: Görevin yığınının en üst adresi.
-    /// * `entry_point`: Görevin başlayacağı fonksiyonun adresi.
-    pub fn new(stack_top: u64, entry_point: u64, rtoc: u64, thread_ptr: u64) -> Self {
-        // PPC'de TOC ve TP'nin ayarlanması zorunludur.
-        Self {
-            ..Default::default()
-            
-            // Yığın işaretçisi ayarlanır
-            r1_sp: stack_top,
-            
-            // Link Register/PC, görevin ilk başlayacağı adres olarak ayarlanır.
-            lr: entry_point,
-            pc: entry_point, 
-            
-            // TOC ve TP (Thread Pointer) değerleri ayarlanır.
-            r2_toc: rtoc,
-            r13_tp: thread_ptr, 
-        }
-    }
-
-    /// Bir bağlam anahtarlama işlemi sırasında yazmaç durumunu kaydetmek ve 
-    /// yeni görevden yazmaç durumunu yüklemek için kullanılan harici assembly fonksiyonu.
+    /// r14-r31'i, r2/r13'ü, CR/LR'yi, r1'i ve dönüş noktasını `old_context`'e
+    /// kaydeder, `new_context`'ten yükler ve onun PC'sine dallanır. Eski görev
+    /// yeniden seçildiğinde `2:` etiketinden, yani bu çağrının sonundan devam
+    /// eder.
     ///
     /// # Güvenlik Notu
-    /// Bu fonksiyon, C ABI'sine uymalıdır: `fn switch_context(old_context: *mut TaskContext, new_context: *const TaskContext)`.
-    /// Argümanlar r3 ve r4'e (a0 ve a1) geçirilir.
+    /// İki işaretçi de geçiş boyunca geçerli olmalıdır; `new_context` ya
+    /// `ArchContext::new` ile kurulmuş ya da daha önce kaydedilmiş olmalıdır.
+    /// r1, r2, r13 ve r29-r31 işlenen olarak verilemez; blok onları kendisi
+    /// kaydedip yükler.
     #[inline(always)]
     pub unsafe fn switch_context(old_context: *mut TaskContext, new_context: *const TaskContext) {
-        serial_println!("[TASK] Bağlam Anahtarlama: {:#x} -> {:#x}", 
-                        old_context as u64, new_context as u64);
-
         asm!(
             // --------------------- Mevcut Görevin Durumunu Kaydet ---------------------
-            // r3: old_context, r4: new_context (C ABI'de a0 ve a1)
-            
-            // 1. Callee-Saved GPR'ları TaskContext'e kaydet (r14-r31)
-            // std rN, offset(r3) (Store Doubleword)
-            
-            // r14 offset 0'da başlar. 18 GPR = 144 bayt.
-            "std r14, 0(r3)",
-            "std r15, 8(r3)",
-            // ... r16'dan r31'e kadar (16 tanesi atlandı)
-            "std r31, 136(r3)", // r31 son GPR.
-
-            // 2. Özel Callee-Saved GPR'ları kaydet
-            // r2 (TOC) offset 144
-            "std r2, 144(r3)",
-            // r13 (TP) offset 152
-            "std r13, 152(r3)",
-            
-            // 3. CR ve LR yazmaçlarını kaydet (mfspr talimatı ile)
-            // LR offset 160, CR offset 168
-            "mflr r5",         // Move from Link Register (r5 geçici)
-            "std r5, 160(r3)", // LR
-            "mfcr r5",         // Move from Condition Register
-            "std r5, 168(r3)", // CR
-            
-            // 4. r1 (SP) kaydet. Offset 176
-            "std r1, 176(r3)",
-            
-            // 5. PC'yi kaydet (Genellikle LR'dır, burada basitleştirildi)
-            "std r5, 184(r3)", // PC (LR'dan kopyalanan r5, tekrar kullanıldı)
+            "std r14, {r14}(r3)",
+            "std r15, {r15}(r3)",
+            "std r16, {r16}(r3)",
+            "std r17, {r17}(r3)",
+            "std r18, {r18}(r3)",
+            "std r19, {r19}(r3)",
+            "std r20, {r20}(r3)",
+            "std r21, {r21}(r3)",
+            "std r22, {r22}(r3)",
+            "std r23, {r23}(r3)",
+            "std r24, {r24}(r3)",
+            "std r25, {r25}(r3)",
+            "std r26, {r26}(r3)",
+            "std r27, {r27}(r3)",
+            "std r28, {r28}(r3)",
+            "std r29, {r29}(r3)",
+            "std r30, {r30}(r3)",
+            "std r31, {r31}(r3)",
+            "std r2, {r2_toc}(r3)",
+            "std r13, {r13_tp}(r3)",
+            "mflr r5",
+            "std r5, {lr}(r3)",
+            "mfcr r5",
+            "std r5, {cr}(r3)",
+            "std r1, {r1_sp}(r3)",
+            // Devam noktası: görev yeniden seçildiğinde `2:`'ye döner
+            "bcl 20, 31, 3f",
+            "3:",
+            "mflr r5",
+            "addi r5, r5, 2f - 3b",
+            "std r5, {pc}(r3)",
 
             // --------------------- Yeni Görevin Durumunu Yükle ---------------------
-            // r4: new_context
-            
-            // 1. Yeni r1 (SP) yükle (Offset 176)
-            "ld r1, 176(r4)",  
-            
-            // 2. Callee-Saved GPR'ları yükle (r14-r31) (Offset 0)
-            "ld r14, 0(r4)", 
-            "ld r15, 8(r4)",
-            // ... r16'dan r31'e kadar (16 tanesi atlandı)
-            "ld r31, 136(r4)",
-            
-            // 3. Özel Callee-Saved GPR'ları yükle
-            // r2 (TOC) offset 144
-            "ld r2, 144(r4)",
-            // r13 (TP) offset 152
-            "ld r13, 152(r4)",
-            
-            // 4. CR ve LR yazmaçlarını yükle (mtspr talimatı ile)
-            // LR offset 160, CR offset 168
-            "ld r5, 160(r4)",  // LR
-            "mtlr r5",         // Move to Link Register
-            "ld r5, 168(r4)",  // CR
-            "mtcrf 0xff, r5",  // Move to Condition Register Field (tüm alanlar)
-
-            // 5. Yeni görevin PC'sine zıpla (PC offset 184)
-            "ld r5, 184(r4)",
-            "mtctr r5",        // Move to Count Register
-            "bctr",            // Branch to Count Register (Görevi başlatır/devam ettirir)
-            
-            in("r3") old_context,
-            in("r4") new_context,
-            // r5 geçici olarak kullanıldı
-            out("r5") _,
-            options(noreturn, preserves_flags)
+            "ld r14, {r14}(r4)",
+            "ld r15, {r15}(r4)",
+            "ld r16, {r16}(r4)",
+            "ld r17, {r17}(r4)",
+            "ld r18, {r18}(r4)",
+            "ld r19, {r19}(r4)",
+            "ld r20, {r20}(r4)",
+            "ld r21, {r21}(r4)",
+            "ld r22, {r22}(r4)",
+            "ld r23, {r23}(r4)",
+            "ld r24, {r24}(r4)",
+            "ld r25, {r25}(r4)",
+            "ld r26, {r26}(r4)",
+            "ld r27, {r27}(r4)",
+            "ld r28, {r28}(r4)",
+            "ld r29, {r29}(r4)",
+            "ld r30, {r30}(r4)",
+            "ld r31, {r31}(r4)",
+            "ld r2, {r2_toc}(r4)",
+            "ld r13, {r13_tp}(r4)",
+            "ld r5, {lr}(r4)",
+            "mtlr r5",
+            "ld r5, {cr}(r4)",
+            "mtcrf 0xff, r5",
+            "ld r1, {r1_sp}(r4)",
+            "ld r5, {pc}(r4)",
+            "mtctr r5",
+            "ld r3, {ret}(r4)",
+            "bctr",
+            "2:",
+
+            r14 = const offset_of!(TaskContext, r14),
+            r15 = const offset_of!(TaskContext, r15),
+            r16 = const offset_of!(TaskContext, r16),
+            r17 = const offset_of!(TaskContext, r17),
+            r18 = const offset_of!(TaskContext, r18),
+            r19 = const offset_of!(TaskContext, r19),
+            r20 = const offset_of!(TaskContext, r20),
+            r21 = const offset_of!(TaskContext, r21),
+            r22 = const offset_of!(TaskContext, r22),
+            r23 = const offset_of!(TaskContext, r23),
+            r24 = const offset_of!(TaskContext, r24),
+            r25 = const offset_of!(TaskContext, r25),
+            r26 = const offset_of!(TaskContext, r26),
+            r27 = const offset_of!(TaskContext, r27),
+            r28 = const offset_of!(TaskContext, r28),
+            r29 = const offset_of!(TaskContext, r29),
+            r30 = const offset_of!(TaskContext, r30),
+            r31 = const offset_of!(TaskContext, r31),
+            r2_toc = const offset_of!(TaskContext, r2_toc),
+            r13_tp = const offset_of!(TaskContext, r13_tp),
+            cr = const offset_of!(TaskContext, cr),
+            lr = const offset_of!(TaskContext, lr),
+            r1_sp = const offset_of!(TaskContext, r1_sp),
+            pc = const offset_of!(TaskContext, pc),
+            ret = const offset_of!(TaskContext, ret),
+            inout("r3") old_context => _,
+            inout("r4") new_context => _,
+            // Devam eden görev için caller-saved yazmaçların hepsi bozulmuş sayılır
+            clobber_abi("C"),
+        );
+    }
+
+    /// Callee-saved yazmaçları, yığın işaretçisini ve devam noktasını
+    /// `context`'e kaydeder, ardından `body(data)`'yı çağırır. `body` dönerse
+    /// 0 döner; `context` daha sonra `switch_context` ile yüklenirse yürütme
+    /// `2:` etiketinden sürer ve dönüş değeri bağlamın `ret` alanı olur (bkz.
+    /// `fixup::catch`).
+    ///
+    /// # Güvenlik Notu
+    /// `context` geri dönüşe kadar geçerli kalmalıdır; bağlam yalnızca `body`
+    /// henüz dönmemişken yüklenebilir.
+    #[inline(always)]
+    pub unsafe fn catch_call(context: *mut TaskContext, body: extern "C" fn(*mut u8), data: *mut u8) -> usize {
+        let resumed: usize;
+        asm!(
+            // --------------------- Geri Dönüş Noktasını Kaydet ---------------------
+            "std r14, {r14}(r3)",
+            "std r15, {r15}(r3)",
+            "std r16, {r16}(r3)",
+            "std r17, {r17}(r3)",
+            "std r18, {r18}(r3)",
+            "std r19, {r19}(r3)",
+            "std r20, {r20}(r3)",
+            "std r21, {r21}(r3)",
+            "std r22, {r22}(r3)",
+            "std r23, {r23}(r3)",
+            "std r24, {r24}(r3)",
+            "std r25, {r25}(r3)",
+            "std r26, {r26}(r3)",
+            "std r27, {r27}(r3)",
+            "std r28, {r28}(r3)",
+            "std r29, {r29}(r3)",
+            "std r30, {r30}(r3)",
+            "std r31, {r31}(r3)",
+            "std r2, {r2_toc}(r3)",
+            "std r13, {r13_tp}(r3)",
+            "mflr r5",
+            "std r5, {lr}(r3)",
+            "mfcr r5",
+            "std r5, {cr}(r3)",
+            "std r1, {r1_sp}(r3)",
+            // Devam noktası: bağlam yüklenirse `2:`'ye döner
+            "bcl 20, 31, 3f",
+            "3:",
+            "mflr r5",
+            "addi r5, r5, 2f - 3b",
+            "std r5, {pc}(r3)",
+
+            // --------------------- Gövdeyi Çağır ---------------------
+            // ELFv1: işlev işaretçisi bir tanımlayıcıyı (giriş adresi, TOC) gösterir
+            "ld r0, 0(r4)",
+            "ld r2, 8(r4)",
+            "mtctr r0",
+            "mr r3, r6",
+            // Çağrılan LR'yi bu çerçevenin başlığına kaydeder
+            "stdu r1, -112(r1)",
+            "bctrl",
+            "addi r1, r1, 112",
+            "li r3, 0",
+            "2:",
+
+            r14 = const offset_of!(TaskContext, r14),
+            r15 = const offset_of!(TaskContext, r15),
+            r16 = const offset_of!(TaskContext, r16),
+            r17 = const offset_of!(TaskContext, r17),
+            r18 = const offset_of!(TaskContext, r18),
+            r19 = const offset_of!(TaskContext, r19),
+            r20 = const offset_of!(TaskContext, r20),
+            r21 = const offset_of!(TaskContext, r21),
+            r22 = const offset_of!(TaskContext, r22),
+            r23 = const offset_of!(TaskContext, r23),
+            r24 = const offset_of!(TaskContext, r24),
+            r25 = const offset_of!(TaskContext, r25),
+            r26 = const offset_of!(TaskContext, r26),
+            r27 = const offset_of!(TaskContext, r27),
+            r28 = const offset_of!(TaskContext, r28),
+            r29 = const offset_of!(TaskContext, r29),
+            r30 = const offset_of!(TaskContext, r30),
+            r31 = const offset_of!(TaskContext, r31),
+            r2_toc = const offset_of!(TaskContext, r2_toc),
+            r13_tp = const offset_of!(TaskContext, r13_tp),
+            cr = const offset_of!(TaskContext, cr),
+            lr = const offset_of!(TaskContext, lr),
+            r1_sp = const offset_of!(TaskContext, r1_sp),
+            pc = const offset_of!(TaskContext, pc),
+            inout("r3") context as usize => resumed,
+            inout("r4") body => _,
+            inout("r6") data => _,
+            clobber_abi("C"),
         );
+        resumed
     }
 }
 
+impl ArchContext for TaskContext {
+    /// İlk geçiş `task_start`'a dallanır; o da r14/r15'teki işlev ve
+    /// argümanla `task_entry`'yi çağırır. TOC (r2) ve iş parçacığı işaretçisi
+    /// (r13) çekirdeğin kendi değerleridir; bağlamı kuran çağıranınkiler alınır.
+    fn new(entry: usize, arg: usize, stack_top: usize) -> Self {
+        let (rtoc, thread_ptr): (u64, u64);
+        // SAFETY: Yalnızca yazmaçlar okunur.
+        unsafe { asm!("mr {}, r2", "mr {}, r13", out(reg) rtoc, out(reg) thread_ptr, options(nomem, nostack, preserves_flags)) };
+        Self {
+            r14: entry as u64,
+            r15: arg as u64,
+            // ELFv2: r1 16 bayt hizalıdır; ilk çerçeveyi `task_start` açar
+            r1_sp: (stack_top & !0xF) as u64,
+            lr: task_start as usize as u64,
+            pc: task_start as usize as u64,
+            r2_toc: rtoc,
+            r13_tp: thread_ptr,
+            ..Default::default()
+        }
+    }
+
+    unsafe fn switch(&mut self, next: &Self) {
+        Self::switch_context(self, next);
+    }
+
+    unsafe fn catch(&mut self, body: extern "C" fn(*mut u8), data: *mut u8) -> usize {
+        Self::catch_call(self, body, data)
+    }
+
+    fn set_return_value(&mut self, value: usize) {
+        self.ret = value as u64;
+    }
+}
+
+extern "C" {
+    /// Yeni görevin ilk komutu (aşağıdaki `global_asm!`).
+    fn task_start();
+}
+
+core::arch::global_asm!(
+    ".global task_start",
+    "task_start:",
+    // Geri zinciri 0 olan ilk yığın çerçevesi (geri izleme burada durur)
+    "li r0, 0",
+    "stdu r0, -112(r1)",
+    "mr r3, r14",
+    "mr r4, r15",
+    "bl {entry}",
+    "nop",
+    "trap",
+    entry = sym task_entry,
+);
+
 
 // -----------------------------------------------------------------------------
 // Görev Başlatma (Task Entry)
@@ -163,6 +318,7 @@ impl TaskContext {
 /// * `func`: Görevin gerçek giriş noktası (başlatılacak fonksiyonun adresi, r3'te olmalı).
 /// * `arg`: Göreve geçirilen u64 formatında argüman (r4'te olmalı).
 extern "C" fn task_entry(func: u64, arg: u64) -> ! {
+    crate::context::task_started();
     serial_println!("[TASK] Yeni Görev Başlatılıyor. Argüman: {:#x}", arg);
 
     // Fonksiyon işaretçisini (u64) gerçek fonksiyona dönüştür.
@@ -174,15 +330,10 @@ extern "C" fn task_entry(func: u64, arg: u64) -> ! {
     // Gerçek görev fonksiyonunu çağır (arg, r4 (a1)'da beklenir)
     entry_func(arg);
 
-    // Görev tamamlandı. Normalde bu noktaya gelinmemelidir.
-    serial_println!("[TASK] Görev Tamamlandı! İşlemci durduruluyor.");
-    
-    // Görev tamamlandığında, çekirdek durdurulmalıdır.
-    loop {
-        unsafe {
-            io::wait(); // PowerPC'de bekleme talimatı
-        }
-    }
+    // Görev işlevi döndü: görev sonlandırılır ve işlemci sıradaki göreve
+    // (en azından boşta görevine) bırakılır
+    serial_println!("[TASK] Görev Tamamlandı.");
+    crate::sched::exit_current()
 }
 
 
@@ -198,4 +349,28 @@ pub fn initialize_tasking() {
     let entry_point_addr = task_entry as *const (); 
     
     serial_println!("[TASK] Task Entry Adresi: {:#x}", entry_point_addr as u64);
-}
\ No newline at end of file
+
+    // Zamanlayıcı bundan sonra görevler arasında gerçekten geçer (bkz. `context`)
+    crate::context::enable();
+}
+
+// -----------------------------------------------------------------------------
+// İŞLEMCİ BAŞINA ALAN TABANI
+// -----------------------------------------------------------------------------
+
+/// Bu işlemcinin işlemci başına alanının (`percpu`) tabanını yazar (`r13`; ABI'de iş parçacığı işaretçisi olarak ayrılmıştır).
+///
+/// # Safety
+/// `base` bu işlemciye ayrılmış, `.percpu` şablonunun kopyası olan alanı
+/// göstermelidir.
+pub unsafe fn set_cpu_local_base(base: usize) {
+    asm!("mr 13, {}", in(reg) base, options(nostack, preserves_flags, nomem));
+}
+
+/// Bu işlemcinin işlemci başına alanının tabanı (henüz yazılmadıysa 0).
+pub fn cpu_local_base() -> usize {
+    let base: usize;
+    // SAFETY: r13 derleyici tarafından kullanılmaz.
+    unsafe { asm!("mr {}, 13", out(reg) base, options(nostack, preserves_flags, nomem)) };
+    base
+}
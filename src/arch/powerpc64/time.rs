@@ -76,6 +76,12 @@ pub fn read_time_base() -> Cycles {
     Cycles(cycles)
 }
 
+/// Serbest çalışan sayacın anlık değeri (bkz. `crate::clock`).
+#[inline(always)]
+pub fn read_cycles() -> Cycles {
+    read_time_base()
+}
+
 /// Time Base Frekansını (Hz) döndürür.
 pub fn get_frequency() -> u64 {
     unsafe {
@@ -131,6 +137,18 @@ pub fn initialize_time_system() {
     // somut zaman değerleri hesaplanabilir.
 }
 
+// -----------------------------------------------------------------------------
+// Zamanlayıcı Karşılaştırıcısı (Decrementer)
+// -----------------------------------------------------------------------------
+
+/// Decrementer'ı Time Base `deadline` değerine kadar sayacak şekilde kurar.
+/// DEC göreli ve 32 bit işaretli olduğundan fark 1..=i32::MAX'a kırpılır;
+/// daha uzak hedefler ara kesmelerle yeniden kurulur.
+pub unsafe fn set_timer_deadline(deadline: Cycles) {
+    let delta = deadline.0.saturating_sub(read_time_base().0).clamp(1, i32::MAX as u64);
+    asm!("mtdec {}", in(reg) delta, options(nostack, nomem));
+}
+
 // Platforma özel G/Ç fonksiyonları için bir yer tutucu (main.rs veya platformmod.rs'de tamamlanmalıdır)
 mod io {
     use core::arch::asm;
@@ -0,0 +1,146 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/arch/powerpc64/vectors.rs
+// PowerPC64 istisna vektörleri, `trap_vectors!` ile üretilir.
+//
+// Tablo 32 yuva x 0x80 bayttır ve 0x000..0x1000 gerçek adres aralığının
+// görüntüsüdür; `init_exceptions` onu fiziksel 0'a kopyalar (0x000..0x100
+// ayrılmış olduğundan kopya 0x100'den başlar). İstisnalar gerçek kipte
+// (MSR.IR/DR = 0) gelir; çekirdek kimlik eşlemeli bağlandığından ortak koda
+// ve işleyiciye mutlak adresle sıçranabilir.
+//
+// Taslak r11/r12'yi SPRG1/SPRG2'ye, CTR'yi SPRG3'e kaydeder, CTR'ye ortak
+// kodun, r11'e işleyicinin adresini, r12'ye yuva numarasını yükler. Ortak
+// kod `ExceptionContext`'i tamamlar ve işleyiciyi
+// `handler(vector_offset, &mut ExceptionContext)` olarak çağırır. Çekirdek
+// kipine dönülüyorsa ardından `sched_preempt_from_trap` çağrılır.
+
+/// Çerçeve: ELFv2 asgari yığın çerçevesi (32) + `ExceptionContext` (38 x 8).
+/// Boy ve aşağıdaki ofsetler `exception.rs`'de `asm_layout!` ile denetlenir.
+macro_rules! powerpc64_frame {
+    () => {
+        "336"
+    };
+}
+
+/// 64 bit mutlak adresi `reg`'e yükler (5 talimat).
+macro_rules! powerpc64_load_addr {
+    ($reg:literal, $sym:expr) => {
+        concat!(
+            "lis ", $reg, ", (", $sym, ")@highest\n",
+            "ori ", $reg, ", ", $reg, ", (", $sym, ")@higher\n",
+            "rldicr ", $reg, ", ", $reg, ", 32, 31\n",
+            "oris ", $reg, ", ", $reg, ", (", $sym, ")@h\n",
+            "ori ", $reg, ", ", $reg, ", (", $sym, ")@l\n",
+        )
+    };
+}
+
+macro_rules! powerpc64_entry {
+    ($name:literal, $handler:literal) => {
+        concat!(
+            "mtspr 273, 11\n",
+            "mtspr 274, 12\n",
+            "mfctr 12\n",
+            "mtspr 275, 12\n",
+            powerpc64_load_addr!("11", concat!($name, "_common")),
+            "mtctr 11\n",
+            powerpc64_load_addr!("11", $handler),
+            "li 12, ", $name, "_slot\n",
+            "bctr\n",
+        )
+    };
+}
+
+macro_rules! powerpc64_common {
+    ($name:literal) => {
+        concat!(
+            $name, "_common:\n",
+            "stdu 1, -", powerpc64_frame!(), "(1)\n",
+            "std 0, 32(1)\n",
+            ".irp n, 3,4,5,6,7,8,9,10,13,14,15,16,17,18,19,20,21,22,23,24,25,26,27,28,29,30,31\n",
+            "std \\n, (32 + (\\n - 2) * 8)(1)\n",
+            ".endr\n",
+            // İşleyici ve yuva kalıcı yazmaçlara; r11/r12/CTR SPRG'lerden
+            "mr 14, 11\n",
+            "mr 15, 12\n",
+            "mfspr 0, 273\n",
+            "std 0, (32 + 9 * 8)(1)\n",
+            "mfspr 0, 274\n",
+            "std 0, (32 + 10 * 8)(1)\n",
+            "mfspr 0, 275\n",
+            "std 0, (32 + 264)(1)\n",
+            "mfsrr0 0\n",
+            "std 0, (32 + 240)(1)\n",
+            "mfsrr1 0\n",
+            "std 0, (32 + 248)(1)\n",
+            "mfxer 0\n",
+            "std 0, (32 + 256)(1)\n",
+            "mflr 0\n",
+            "std 0, (32 + 272)(1)\n",
+            "mfspr 0, 19\n",            // DAR
+            "std 0, (32 + 280)(1)\n",
+            "mfspr 0, 18\n",            // DSISR
+            "std 0, (32 + 288)(1)\n",
+            "ld 0, 0(1)\n",             // stdu'nun bıraktığı geri zincir = kesilen r1
+            "std 0, (32 + 296)(1)\n",
+            // handler(vector_offset, &mut ExceptionContext); ELFv2'de r12 = giriş adresi
+            "sldi 3, 15, 7\n",
+            "addi 4, 1, 32\n",
+            "mtctr 14\n",
+            "mr 12, 14\n",
+            "bctrl\n",
+            // Çekirdek kipine dönüşte kesinti noktası: MSR kesilen koddaki gibi
+            // (IR/DR açık) ama EE kapalı kurulur. Görev değişip geri dönülmüşse
+            // r13 (işlemci başı taban) yeni işlemcininkidir; çerçeveye yazılır.
+            "ld 0, (32 + 248)(1)\n",
+            "andi. 14, 0, 0x4000\n",   // SRR1.PR
+            "bne 1f\n",
+            "ori 14, 0, 0x8000\n",
+            "xori 14, 14, 0x8000\n",
+            "mtmsrd 14\n",
+            "isync\n",
+            powerpc64_load_addr!("12", "sched_preempt_from_trap"),
+            "mtctr 12\n",
+            "bctrl\n",
+            "std 13, (32 + 11 * 8)(1)\n",
+            "1:\n",
+            // İşleyici SRR0'ı (syscall, düzeltme) ve SRR1'i (hata ayıklayıcı) değiştirebilir
+            "ld 0, (32 + 240)(1)\n",
+            "mtsrr0 0\n",
+            "ld 0, (32 + 248)(1)\n",
+            "mtsrr1 0\n",
+            "ld 0, (32 + 256)(1)\n",
+            "mtxer 0\n",
+            "ld 0, (32 + 264)(1)\n",
+            "mtctr 0\n",
+            "ld 0, (32 + 272)(1)\n",
+            "mtlr 0\n",
+            "ld 0, 32(1)\n",
+            ".irp n, 3,4,5,6,7,8,9,10,11,12,13,14,15,16,17,18,19,20,21,22,23,24,25,26,27,28,29,30,31\n",
+            "ld \\n, (32 + (\\n - 2) * 8)(1)\n",
+            ".endr\n",
+            "ld 1, (32 + 296)(1)\n",
+            "rfid\n",
+        )
+    };
+}
+
+crate::trap_vectors! {
+    name: "powerpc64_vectors",
+    align: 4096,
+    slot: 0x80,
+    slots: 32,
+    common: powerpc64_common,
+    vectors: [
+        0 ..= 31 => powerpc64_entry("generic_exception_handler"),
+    ]
+}
+
+extern "C" {
+    /// Üretilen tablonun başı (0x000 vektörünün görüntüsü).
+    pub fn powerpc64_vectors();
+}
+
+/// Tablonun boyutu (bayt).
+pub const VECTORS_SIZE: usize = 32 * 0x80;
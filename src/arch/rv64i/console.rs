@@ -3,8 +3,9 @@ use core::fmt::{self, Write};
 use core::sync::atomic::{AtomicBool, Ordering};
 
 // RISC-V Gömülü Sistemlerde (örneğin QEMU'da virt veya SiFive platformları) 
-// 16550 uyumlu UART'ın TEMEL MMIO adresi.
-// Not: Gerçek donanımınızda bu adres farklı olacaktır!
+// 16550 uyumlu UART'ın TEMEL MMIO adresi. Önyükleyici başka bir 16550
+// bildirirse (`stdout-path`, `earlycon=`) o, yazmaç aralığıyla (`reg-shift`)
+// birlikte kullanılır; bkz. `crate::earlycon`.
 const UART_MMIO_ADDR: usize = 0x1000_0000; // Temsili (virt QEMU varsayılanı)
 
 // 16550 Uyumlu UART Yazmaçları için Ofsetler
@@ -24,10 +25,13 @@ static IS_INITIALIZED: AtomicBool = AtomicBool::new(false);
 impl Uart {
     /// UART yazmacının tam MMIO adresini hesaplar.
     fn get_register_addr(offset: usize) -> usize {
-        // Güvenli toplama işlemi
-        UART_MMIO_ADDR.checked_add(offset).unwrap_or_else(|| 
-            panic!("UART MMIO adresi taşması")
-        )
+        let (base, shift) = match crate::earlycon::mmio_base(crate::earlycon::UartKind::Ns16550) {
+            Some(base) => (base, crate::earlycon::reg_shift()),
+            None => (UART_MMIO_ADDR, 0),
+        };
+        // Erken konsoldan gelen taban taşarsa varsayılan UART'a dönülür;
+        // konsol yolunda panik olmamalı (panik yolu da bu konsola yazar)
+        base.checked_add(offset << shift).unwrap_or(UART_MMIO_ADDR + offset)
     }
 
     /// UART'ı temel bir konfigürasyonla başlatır (8N1, FIFO açık).
@@ -45,9 +49,10 @@ impl Uart {
         // 1. DLAB'ı (Divisor Latch Access Bit) ayarla (Baud Hızı için)
         platform_write(LINE_CTRL_OFFSET, 0x80);
 
-        // 2. Baud Hızını Ayarla (Örn: 115200 baud -> Bölücü 1, Varsayılan)
-        platform_write(DATA_REGISTER_OFFSET + 0, 0x01); // Bölücü Alt Bayt (LSB)
-        platform_write(DATA_REGISTER_OFFSET + 1, 0x00); // Bölücü Üst Bayt (MSB)
+        // 2. Baud Hızını Ayarla (varsayılan 115200; bölücü kartın UART saatinden, bkz. `quirks`)
+        let [divisor_lo, divisor_hi] = crate::quirks::uart_divisor(crate::earlycon::baud()).to_le_bytes();
+        platform_write(DATA_REGISTER_OFFSET + 0, divisor_lo); // Bölücü Alt Bayt (LSB)
+        platform_write(DATA_REGISTER_OFFSET + 1, divisor_hi); // Bölücü Üst Bayt (MSB)
 
         // 3. Hat Kontrol Yazmacını Ayarla (8 Veri Biti, 1 Stop Biti, Parite Yok)
         // DLAB'ı sıfırla (0x03 = 8N1 konfigürasyonu)
@@ -61,6 +66,12 @@ impl Uart {
 
         IS_INITIALIZED.store(true, Ordering::Release);
     }
+
+    /// UART'ı yeniden yapılandırır (örn. `quirks` UART saatini değiştirdikten sonra).
+    pub fn reinit() {
+        IS_INITIALIZED.store(false, Ordering::Release);
+        Self::init();
+    }
     
     /// UART'ın boş olup olmadığını kontrol eder (göndermeye hazır mı?).
     ///
@@ -80,6 +91,11 @@ impl Uart {
 
     /// UART'a bir bayt yazar.
     pub fn write_byte(byte: u8) {
+        // Hypervisor altında paravirt konsol varsa öykünülmüş UART'a dokunma
+        if crate::hypervisor::console_write_byte(byte) {
+            return;
+        }
+
         // Portun boş olmasını bekle (Busy-Waiting)
         while !Self::is_transmit_empty() {}
 
@@ -111,6 +127,24 @@ impl fmt::Write for Uart {
     }
 }
 
+// -----------------------------------------------------------------------------
+// PANİK VE İKİNCİL KONSOL
+// -----------------------------------------------------------------------------
+
+/// Panik yolunun kilitsiz yazıcısı (bkz. `crate::panic`). UART zaten
+/// yoklamalı yazar.
+pub fn panic_write_byte(byte: u8) {
+    Uart::write_byte(byte);
+}
+
+/// Kesme güdümlü gönderim olmadığından etkisizdir.
+pub fn panic_quiesce() {}
+
+/// İkincil UART yok; `serial1` hedefi kaydedilmez.
+pub fn init_aux_serial() -> Option<fn(fmt::Arguments)> {
+    None
+}
+
 // -----------------------------------------------------------------------------
 // GENEL KONSOL ÇIKTI FONKSİYONLARI (Makrolar)
 // -----------------------------------------------------------------------------
@@ -125,8 +159,11 @@ macro_rules! serial_print {
 
         // Not: Gerçek bir çekirdekte, bu kısım yarış koşullarını önlemek için 
         // Spinlock ile korunmalı ya da Kesmeler devre dışı bırakılmalıdır.
-        unsafe {
-             let _ = write!($crate::arch::rv64i::console::Uart, $($arg)*);
+        // Yönlendirilmiş görev çıktısı (örn. uzak kabuk) seri porta yazılmaz
+        if !$crate::shell::redirect(format_args!($($arg)*)) {
+            unsafe {
+                 let _ = write!($crate::arch::rv64i::console::Uart, $($arg)*);
+            }
         }
     });
 }
@@ -0,0 +1,65 @@
+// src/arch/rv64i/cpu.rs
+// RISC-V 64 işlemci özelliği algılama: aygıt ağacındaki ISA dizesi.
+//
+// `misa` yalnızca M kipinde okunabilir; S kipindeki çekirdek ISA'yı
+// `/cpus/cpu@0` düğümünün `riscv,isa` özelliğinden (örn.
+// "rv64imafdcv_zicsr_zba_zkne_zknh_zkr") okur. Kimlik olarak SBI
+// `mimpid` değeri kullanılır.
+
+#![allow(dead_code)]
+
+use core::arch::asm;
+use crate::cpu::CpuFeatures;
+
+/// SBI temel uzantısı ve `sbi_get_mimpid` fonksiyonu.
+const SBI_EXT_BASE: u64 = 0x10;
+const SBI_BASE_GET_MIMPID: u64 = 6;
+
+fn sbi_get_mimpid() -> u64 {
+    let (error, value): (i64, u64);
+    // SAFETY: SBI temel uzantısı her SBI uygulamasında vardır.
+    unsafe {
+        asm!(
+            "ecall",
+            in("a7") SBI_EXT_BASE,
+            in("a6") SBI_BASE_GET_MIMPID,
+            lateout("a0") error,
+            lateout("a1") value,
+            options(nomem, nostack, preserves_flags)
+        );
+    }
+    if error == 0 { value } else { 0 }
+}
+
+/// ISA dizesindeki tek harfli uzantı (örn. `f`) veya `_` ile ayrılmış çok
+/// harfli uzantı (örn. `zkne`) var mı?
+fn has_ext(isa: &str, ext: &str) -> bool {
+    let mut parts = isa.split('_');
+    let base = parts.next().unwrap_or("");
+    // "rv64" önekinden sonraki tek harfli uzantılar; `g` = imafd
+    let letters = base.get(4..).unwrap_or("");
+    if ext.len() == 1 {
+        return letters.contains(ext) || (letters.contains('g') && "imafd".contains(ext));
+    }
+    parts.any(|part| part.eq_ignore_ascii_case(ext))
+}
+
+pub fn detect() -> CpuFeatures {
+    let isa = crate::fdt::get()
+        .and_then(|fdt| fdt.property_str("/cpus/cpu@0", "riscv,isa"))
+        .unwrap_or("rv64imac");
+    CpuFeatures {
+        id: sbi_get_mimpid(),
+        fpu: has_ext(isa, "f") || has_ext(isa, "d"),
+        simd: has_ext(isa, "v"),
+        aes: has_ext(isa, "zkne") && has_ext(isa, "zknd"),
+        sha2: has_ext(isa, "zknh"),
+        // Zbc (carry-less çarpma) CRC hesaplamasını hızlandırır
+        crc32: has_ext(isa, "zbc"),
+        atomics: has_ext(isa, "a"),
+        // Sv39'da megasayfa (2 MiB) ve gigasayfa (1 GiB) her zaman vardır
+        large_pages: true,
+        huge_pages: true,
+        rng: has_ext(isa, "zkr"),
+    }
+}
@@ -0,0 +1,113 @@
+// src/arch/rv64i/debug.rs
+// RISC-V 64 (RV64I) mimarisine özgü kesme noktası ve gözlem noktası desteği.
+
+#![allow(dead_code)]
+
+use core::arch::asm;
+use crate::debug::{WatchKind, ERR_NO_HW_SINGLE_STEP};
+use crate::platformgeneric::KernelError;
+
+/// `ebreak` talimatı (sıkıştırılmamış, 32 bit).
+pub const BREAKPOINT_INSN: u32 = 0x0010_0073;
+
+/// Kesme noktası talimatının uzunluğu (bayt).
+pub const BREAKPOINT_INSN_LEN: usize = 4;
+
+/// SEPC `ebreak` talimatının kendisini gösterir; düzeltme gerekmez.
+pub const BREAKPOINT_PC_ADJUST: usize = 0;
+
+/// Talimat önbelleğini yerel hart için senkronize eder.
+/// SMP'de diğer hart'lara SBI `remote_fence_i` gönderilmelidir.
+#[inline(always)]
+pub unsafe fn sync_icache(_addr: usize, _len: usize) {
+    asm!("fence.i", options(nostack));
+}
+
+/// S-Mode'da standart bir tek adım biti yoktur (Sdtrig `icount` tetikleyicisi
+/// yalnızca M-Mode firmware üzerinden erişilebilir).
+pub fn set_single_step(_sstatus: &mut u64, enable: bool) -> Result<(), KernelError> {
+    if enable {
+        Err(KernelError::PlatformSpecificError(ERR_NO_HW_SINGLE_STEP))
+    } else {
+        Ok(())
+    }
+}
+
+// -----------------------------------------------------------------------------
+// DONANIM GÖZLEM NOKTALARI (Sdtrig tetikleyici modülü)
+// -----------------------------------------------------------------------------
+
+// Not: tselect/tdata CSR'ları M-Mode adres aralığındadır. Çekirdek S-Mode'da
+// çalışıyorsa firmware bu erişimi taklit etmeli (veya SBI DBTR kullanılmalıdır).
+
+/// Kullanılan tetikleyici sayısı (tselect 0-3). Gerçek sayı uygulamaya bağlıdır;
+/// desteklenmeyen bir `tselect` yazıldığında geri okunan değer farklı olur.
+pub const MAX_WATCHPOINTS: usize = 4;
+
+/// `mcontrol` tetikleyicisi (timing=0) erişimden önce breakpoint istisnası üretir.
+pub const WATCH_BEFORE_ACCESS: bool = true;
+
+// mcontrol (tdata1, type=2) alanları
+const MCONTROL_TYPE: u64 = 2 << 60;
+const MCONTROL_HIT: u64 = 1 << 20;
+const MCONTROL_MATCH_NAPOT: u64 = 1 << 7;
+const MCONTROL_S: u64 = 1 << 4;
+const MCONTROL_U: u64 = 1 << 3;
+const MCONTROL_STORE: u64 = 1 << 1;
+const MCONTROL_LOAD: u64 = 1 << 0;
+
+/// Tetikleyiciyi seçer; uygulama yuvayı desteklemiyorsa `false` döner.
+unsafe fn select_trigger(slot: usize) -> bool {
+    let selected: usize;
+    asm!("csrw 0x7a0, {0}", "csrr {1}, 0x7a0", in(reg) slot, out(reg) selected, options(nostack, nomem));
+    selected == slot
+}
+
+/// tdata2'ye adresi (uzunluk > 1 ise NAPOT kodlamasıyla), tdata1'e load/store
+/// eşleştirmesini yazar. S ve U modu erişimleri izlenir.
+pub unsafe fn set_watchpoint(slot: usize, addr: usize, len: usize, kind: WatchKind) -> Result<(), KernelError> {
+    if !select_trigger(slot) {
+        return Err(KernelError::NotFound);
+    }
+
+    let access = match kind {
+        WatchKind::Read => MCONTROL_LOAD,
+        WatchKind::Write => MCONTROL_STORE,
+        WatchKind::ReadWrite => MCONTROL_LOAD | MCONTROL_STORE,
+    };
+    let (tdata2, match_bits) = if len > 1 {
+        (addr | (len / 2 - 1), MCONTROL_MATCH_NAPOT)
+    } else {
+        (addr, 0)
+    };
+    let tdata1 = MCONTROL_TYPE | match_bits | MCONTROL_S | MCONTROL_U | access;
+
+    // Tetikleyici yarım yapılandırılmışken eşleşmemesi için önce devre dışı bırakılır.
+    asm!("csrw 0x7a1, zero", "csrw 0x7a2, {0}", "csrw 0x7a1, {1}", in(reg) tdata2, in(reg) tdata1, options(nostack, nomem));
+    Ok(())
+}
+
+/// Seçili tetikleyicinin tdata1 değerini sıfırlar.
+pub unsafe fn clear_watchpoint(slot: usize) {
+    if select_trigger(slot) {
+        asm!("csrw 0x7a1, zero", options(nostack, nomem));
+    }
+}
+
+/// tdata1.hit biti ayarlı ilk tetikleyiciyi döndürür ve biti temizler.
+pub fn watchpoint_hit() -> Option<usize> {
+    unsafe {
+        for slot in 0..MAX_WATCHPOINTS {
+            if !select_trigger(slot) {
+                break;
+            }
+            let tdata1: u64;
+            asm!("csrr {0}, 0x7a1", out(reg) tdata1, options(nostack, nomem));
+            if tdata1 & MCONTROL_HIT != 0 {
+                asm!("csrw 0x7a1, {0}", in(reg) tdata1 & !MCONTROL_HIT, options(nostack, nomem));
+                return Some(slot);
+            }
+        }
+    }
+    None
+}
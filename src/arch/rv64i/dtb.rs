@@ -51,6 +51,8 @@ impl DtbParser {
             unsafe {
                 DTB_INFO.physical_address = addr;
             }
+            // Sürücülerin aygıt keşfi için genel FDT okuyucusuna da bildir
+            crate::fdt::init(addr).ok();
         }
     }
 
@@ -2,7 +2,10 @@
 
 use core::arch::asm;
 use core::fmt;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use crate::platformgeneric::KernelError;
 use crate::serial_println;
+use crate::trap::{handle_fatal, Access, Cause};
 
 // -----------------------------------------------------------------------------
 // HARİCİ MONTAJ DİLİ İŞLEYİCİSİ
@@ -33,6 +36,15 @@ pub struct ExceptionContext {
     pub SSTATUS: u64, // Süpervizör Durum Yazmacı (Kesme durumu vb.)
 }
 
+// Ofsetler `exception_handlers.S`'deki `OFF_*` / `FRAME_SIZE` ile aynıdır.
+crate::asm_layout!(ExceptionContext {
+    gpr: 0,
+    SCAUSE: 248,
+    SEPC: 256,
+    STVAL: 264,
+    SSTATUS: 272,
+}, size <= 288);
+
 /// İstisna nedenleri (SCAUSE yazmacından alınmıştır).
 /// Yüksek bit (63), kesme (1) veya senkron istisna (0) olduğunu belirtir.
 #[repr(i64)]
@@ -76,10 +88,97 @@ impl ExceptionCause {
     }
 }
 
+/// Ham SCAUSE ve STVAL değerlerini ortak `trap::Cause` tipine çevirir.
+pub fn decode_cause(scause: u64, stval: u64) -> Cause {
+    let code = scause & 0x7FFFFFFFFFFFFFFF;
+    let addr = stval as usize;
+
+    if (scause as i64) < 0 {
+        // S-Mode harici kesmeler için gerçek IRQ numarası PLIC'ten okunur;
+        // burada yerel kesme numarası (1: yazılım, 5: zamanlayıcı, 9: harici) verilir.
+        return Cause::Interrupt(code as u32);
+    }
+
+    match code {
+        0 | 4 | 6 => Cause::Misaligned { addr },       // Talimat / Yükleme / Depolama hizalama
+        1 | 5 | 7 => Cause::MachineError,              // Erişim Hataları (PMP, veri yolu)
+        2 => Cause::IllegalInstruction,
+        // ebreak veya tetikleyici modülü (tdata1.hit ayarlıysa gözlem noktası)
+        3 => match crate::debug::watchpoint_hit() {
+            Some(watched) => Cause::Watchpoint { addr: watched },
+            None => Cause::Breakpoint,
+        },
+        8 | 9 => Cause::Syscall,                       // ecall (U / S)
+        12 => Cause::PageFault { access: Access::Execute, addr },
+        13 => Cause::PageFault { access: Access::Read, addr },
+        15 => Cause::PageFault { access: Access::Write, addr },
+        _ => Cause::Unknown(scause),
+    }
+}
+
 // -----------------------------------------------------------------------------
 // 2. TEMEL İŞLEYİCİ FONKSİYONLARI (Rust Kodu)
 // -----------------------------------------------------------------------------
 
+/// SSTATUS.SPP: tuzaktan önceki ayrıcalık seviyesi (0: U kipi).
+const SSTATUS_SPP: u64 = 1 << 8;
+
+/// Tuzak girişinin işlemci başına çalışma alanı; `sscratch` her zaman bu
+/// işlemcininkini gösterir. Alanlar yalnızca `exception_handlers.S` ve
+/// kurulum kodu tarafından yazılır; düzen oradaki `TS_*` ofsetleriyle aynıdır.
+#[repr(C)]
+pub struct TrapScratch {
+    /// Kesme yığınının üstü (0: kurulmadı, kesilen yığında kalınır).
+    kernel_sp: AtomicUsize,
+    /// Çekirdeğin `tp` değeri (işlemci başına alan tabanı).
+    kernel_tp: AtomicUsize,
+    /// Süren tuzak sayısı; 0 ise girişte kesme yığınına geçilir.
+    depth: AtomicUsize,
+    /// Girişte kesilen `sp` ve `t0` için geçici yuvalar.
+    saved_sp: AtomicUsize,
+    saved_t0: AtomicUsize,
+}
+
+// `exception_handlers.S`'deki `TS_*` ofsetleri.
+crate::asm_layout!(TrapScratch {
+    kernel_sp: 0,
+    kernel_tp: 8,
+    depth: 16,
+    saved_sp: 24,
+    saved_t0: 32,
+});
+
+crate::percpu!(TRAP_SCRATCH: TrapScratch = TrapScratch {
+    kernel_sp: AtomicUsize::new(0),
+    kernel_tp: AtomicUsize::new(0),
+    depth: AtomicUsize::new(0),
+    saved_sp: AtomicUsize::new(0),
+    saved_t0: AtomicUsize::new(0),
+});
+
+/// `sscratch`'i bu işlemcinin `TrapScratch` yapısına kurar.
+///
+/// # Safety
+/// Tuzak işleyicisinin dışında çağrılmalıdır.
+unsafe fn load_trap_scratch() {
+    let scratch = TRAP_SCRATCH.get();
+    scratch.kernel_tp.store(crate::arch::rv64i::task::cpu_local_base(), Ordering::Relaxed);
+    asm!("csrw sscratch, {}", in(reg) scratch as *const TrapScratch as usize, options(nostack, nomem));
+}
+
+/// Tuzak kullanıcı kipinden mi geldi?
+fn from_user(context: &ExceptionContext) -> bool {
+    context.SSTATUS & SSTATUS_SPP == 0
+}
+
+/// Çekirdek dökümü için ELF `elf_gregset_t` sırası: pc, x1..x31.
+fn elf_gregs(context: &ExceptionContext) -> [u64; 32] {
+    let mut regs = [0u64; 32];
+    regs[0] = context.SEPC;
+    regs[1..].copy_from_slice(&context.gpr);
+    regs
+}
+
 /// Tüm istisna giriş noktalarından montaj kodu tarafından çağrılan Rust işleyicisi.
 #[no_mangle]
 pub extern "C" fn generic_trap_handler(context: &mut ExceptionContext) {
@@ -91,10 +190,24 @@ pub extern "C" fn generic_trap_handler(context: &mut ExceptionContext) {
         ExceptionCause::SupervisorExternalInterrupt => 
         {
             // Kesme İşleyicisi
+            let _irq = crate::irqstack::enter(context as *const _ as usize);
             handle_interrupt(context, cause);
         }
         ExceptionCause::LoadPageFault | ExceptionCause::StorePageFault | ExceptionCause::InstructionPageFault => {
             // Sayfa Hatası İşleyicileri
+            // Kullanıcı belleği erişimi veya bellek yoklaması ise düzeltme tablosuna bak
+            let trap_cause = decode_cause(context.SCAUSE, context.STVAL);
+            if crate::fixup::fixup_exception(trap_cause, &mut context.SEPC) {
+                return;
+            }
+
+            // Dosya eşlemesi (mmap) sayfası ise yükle ve talimatı yeniden dene
+            if let Cause::PageFault { access, addr } = trap_cause {
+                if from_user(context) && crate::mmap::handle_fault(addr, access) {
+                    return;
+                }
+            }
+
             serial_println!("\n--- SAYFA HATASI ---");
             serial_println!("Neden: {:?}", cause);
             serial_println!("Hata Adresi (STVAL): {:#x}", context.STVAL);
@@ -102,22 +215,47 @@ pub extern "C" fn generic_trap_handler(context: &mut ExceptionContext) {
             
             // Eğer Sayfa Hatası ise, MMU'yu kullanarak çeviri yapmayı deneyin.
             
-            panic!("Kritik Sayfa Hatası!");
+            if from_user(context) {
+                crate::trap::handle_user_fatal(trap_cause, context.SEPC as usize, &elf_gregs(context));
+                return;
+            }
+            handle_fatal(trap_cause, context.SEPC as usize);
         }
         ExceptionCause::EnvironmentCallFromUMode | ExceptionCause::EnvironmentCallFromSMode => {
-            // Sistem Çağrısı (SYSCALL)
-            serial_println!("SYSCALL: Çağrı kodu: {}", context.gpr[10]); // a0 (x10)
-            
+            // Sistem Çağrısı (SYSCALL): numara a7 (x17), argümanlar a0..a5 (x10..x15),
+            // sonuç a0. `gpr[0]` x1 olduğundan xN, `gpr[N - 1]`'dedir.
+            let nr = context.gpr[16] as usize;
+            let mut args = [0usize; 6];
+            for (arg, &reg) in args.iter_mut().zip(&context.gpr[9..15]) {
+                *arg = reg as usize;
+            }
+            context.gpr[9] = crate::syscall::dispatch(nr, args) as u64;
+
             // Syscall'dan dönmek için SEPC'yi bir sonraki talimata ilerletmeyi UNUTMAYIN.
             context.SEPC += 4; 
         }
         _ => {
+            // Kesme noktası (ebreak) ve gözlem noktaları önce hata ayıklayıcıya sunulur.
+            let trap_cause = decode_cause(context.SCAUSE, context.STVAL);
+            if crate::debug::handle_trap(trap_cause, &mut context.SEPC, &mut context.SSTATUS) {
+                return;
+            }
+
+            // Hizalama ve erişim hataları için düzeltme tablosuna bak
+            if crate::fixup::fixup_exception(trap_cause, &mut context.SEPC) {
+                return;
+            }
+
             // Diğer senkron istisnalar
             serial_println!("\n--- KRİTİK İSTİSNA ---");
             serial_println!("SCAUSE: {:#x} ({:?})", context.SCAUSE, cause);
             serial_println!("SEPC: {:#x}", context.SEPC);
             
-            panic!("İşlenmemiş İstisna!");
+            if from_user(context) {
+                crate::trap::handle_user_fatal(trap_cause, context.SEPC as usize, &elf_gregs(context));
+                return;
+            }
+            handle_fatal(trap_cause, context.SEPC as usize);
         }
     }
 }
@@ -152,6 +290,8 @@ pub fn init_exceptions() {
     unsafe {
         // 1. STVEC yazmacını montaj dilindeki istisna giriş noktasına ayarla.
         // Mod 0 (Doğrudan) - Tüm istisnalar tek bir noktaya sıçrar.
+        // Giriş kodu ilk iş olarak `sscratch`'i kullandığından önce o kurulur.
+        load_trap_scratch();
         let entry_addr = trap_entry as u64;
         // STVEC yazmacına yaz
         asm!("csrw stvec, {}", in(reg) entry_addr); 
@@ -169,4 +309,18 @@ pub fn init_exceptions() {
     
     serial_println!("[RV64I] Tuzak (Trap) Yönetimi başlatıldı (S-Mode).");
     serial_println!("[RV64I] Harici, Zamanlayıcı ve Yazılım Kesmeleri etkinleştirildi.");
-}
\ No newline at end of file
+}
+
+/// Kesme girişi ayrı kesme yığınına geçer (bkz. `crate::irqstack`).
+pub const SWITCHES_IRQ_STACK: bool = true;
+
+/// Kesme yığınını tuzak girişine tanıtır. İşlemci başına alan bu noktada
+/// kendi kopyasına geçmiş olduğundan `sscratch` de yeniden kurulur.
+///
+/// # Safety
+/// Tuzak işleyicisinin dışında ve `top` bu işlemciye ait bir yığının üstüyken çağrılmalıdır.
+pub unsafe fn install_irq_stack(top: usize) -> Result<(), KernelError> {
+    TRAP_SCRATCH.get().kernel_sp.store(top, Ordering::Relaxed);
+    load_trap_scratch();
+    Ok(())
+}
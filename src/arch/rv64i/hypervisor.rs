@@ -0,0 +1,84 @@
+// src/arch/rv64i/hypervisor.rs
+// RISC-V 64 (RV64I) hypervisor algılama: SBI uygulama kimliği ve SBI konsolu.
+
+#![allow(dead_code)]
+
+use core::arch::asm;
+use crate::hypervisor::{HypervisorKind, Paravirt};
+
+// -----------------------------------------------------------------------------
+// SBI SABİTLERİ
+// -----------------------------------------------------------------------------
+
+/// SBI Base uzantısı.
+const SBI_EXT_BASE: u64 = 0x10;
+const SBI_BASE_GET_IMPL_ID: u64 = 1;
+const SBI_BASE_PROBE_EXTENSION: u64 = 3;
+const SBI_BASE_GET_MVENDORID: u64 = 4;
+
+/// SBI Debug Console uzantısı ("DBCN").
+const SBI_EXT_DBCN: u64 = 0x4442_434E;
+const SBI_DBCN_WRITE_BYTE: u64 = 2;
+
+/// SBI uygulama kimlikleri (SBI spesifikasyonu, Bölüm 4.9).
+const SBI_IMPL_OPENSBI: u64 = 1;
+const SBI_IMPL_XVISOR: u64 = 2;
+const SBI_IMPL_KVM: u64 = 3;
+
+/// Dönüş değeri (a0 = hata, a1 = değer) olan SBI çağrısı.
+fn sbi_call(eid: u64, fid: u64, arg0: u64) -> (i64, u64) {
+    let (error, value): (i64, u64);
+    unsafe {
+        asm!(
+            "ecall",
+            in("a7") eid,
+            in("a6") fid,
+            inlateout("a0") arg0 => error,
+            lateout("a1") value,
+            options(nomem, nostack, preserves_flags)
+        );
+    }
+    (error, value)
+}
+
+// -----------------------------------------------------------------------------
+// ARAYÜZ
+// -----------------------------------------------------------------------------
+
+/// SBI uygulama kimliğine göre hypervisor türünü belirler.
+///
+/// KVM kendi SBI uygulamasını sunar. QEMU (TCG) ise `virt` makinesinde OpenSBI'yi
+/// yükler ve öykünülmüş hart'ın `mvendorid` değeri 0'dır; gerçek donanımda bu
+/// değer JEDEC satıcı kimliğidir.
+pub fn detect() -> HypervisorKind {
+    let (err, impl_id) = sbi_call(SBI_EXT_BASE, SBI_BASE_GET_IMPL_ID, 0);
+    if err != 0 {
+        return HypervisorKind::None;
+    }
+
+    match impl_id {
+        SBI_IMPL_KVM => HypervisorKind::Kvm,
+        SBI_IMPL_XVISOR => HypervisorKind::Other,
+        SBI_IMPL_OPENSBI => {
+            let (err, vendor) = sbi_call(SBI_EXT_BASE, SBI_BASE_GET_MVENDORID, 0);
+            if err == 0 && vendor == 0 { HypervisorKind::Qemu } else { HypervisorKind::None }
+        }
+        _ => HypervisorKind::None,
+    }
+}
+
+/// SBI DBCN uzantısı varsa konsolu öykünülmüş 16550 yerine ona yönlendirir.
+pub unsafe fn enable_paravirt(_kind: HypervisorKind) -> Paravirt {
+    let (err, present) = sbi_call(SBI_EXT_BASE, SBI_BASE_PROBE_EXTENSION, SBI_EXT_DBCN);
+    Paravirt { console: err == 0 && present != 0, ..Paravirt::default() }
+}
+
+/// `enable_paravirt` saat olanağını açmaz; `time` CSR'si zaten sanallaştırılmıştır.
+pub fn pv_clock_ns() -> u64 {
+    0
+}
+
+/// Baytı SBI DBCN üzerinden yazar.
+pub fn pv_console_write_byte(byte: u8) {
+    sbi_call(SBI_EXT_DBCN, SBI_DBCN_WRITE_BYTE, byte as u64);
+}
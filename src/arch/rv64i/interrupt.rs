@@ -12,6 +12,7 @@ use crate::serial_println;
 // Bu adresler DTB'den (Device Tree) okunmalıdır, ancak temsili adresler kullanıyoruz.
 const CLINT_BASE: usize = 0x200_0000;
 const PLIC_BASE: usize  = 0xC00_0000;
+const PLIC_SIZE: usize  = 0x400_0000;
 
 // CLINT Yazmaç Ofsetleri
 const MTIMECMP: usize = 0x4000;  // Zamanlayıcı Karşılaştırma Yazmacı (64-bit)
@@ -61,6 +62,11 @@ pub struct Plic;
 impl Plic {
     // Tüm PLIC kesmelerini maskele ve öncelikleri sıfırla.
     pub unsafe fn init() {
+        // PLIC RAM eşlemesinin dışındadır; birebir eşlenir (sanal = fiziksel).
+        if let Err(e) = crate::physmap::map_mmio(PLIC_BASE, PLIC_SIZE) {
+            serial_println!("[RV64I] PLIC eşlenemedi: {:?}", e);
+        }
+
         // Tüm S-Mode kesmelerini devre dışı bırak.
         mmio_write_u32(PLIC_BASE + PLIC_ENABLE_BASE, 0); 
         
@@ -83,9 +89,8 @@ impl Plic {
         let bit = irq_id % 32;
         let addr = PLIC_BASE + PLIC_ENABLE_BASE + (index as usize) * 4;
         
-        let mut enable = mmio_read_u32(addr);
-        enable |= 1 << bit;
-        mmio_write_u32(addr, enable);
+        // Etkinleştirme kelimesi 32 kesmeyi paylaşır: RMW kilit altında yapılır
+        crate::mmio::set_bits(addr, 1u32 << bit);
     }
     
     /// Harici IRQ'yu devre dışı bırak (S-Mode).
@@ -94,9 +99,7 @@ impl Plic {
         let bit = irq_id % 32;
         let addr = PLIC_BASE + PLIC_ENABLE_BASE + (index as usize) * 4;
         
-        let mut enable = mmio_read_u32(addr);
-        enable &= !(1 << bit);
-        mmio_write_u32(addr, enable);
+        crate::mmio::clear_bits(addr, 1u32 << bit);
     }
 
     /// İşlenecek bekleyen kesmenin ID'sini alır (Acknowledge).
@@ -134,21 +137,45 @@ impl Clint {
         // Zamanlayıcıyı mevcut zamana interval ekleyerek ayarla
         mmio_write_u64(CLINT_BASE + MTIMECMP, current_time + interval);
     }
+
+    /// Zamanlayıcı kesmesini mutlak MTIME değerine ayarla.
+    pub unsafe fn set_timer_deadline(deadline: u64) {
+        mmio_write_u64(CLINT_BASE + MTIMECMP, deadline);
+    }
 }
 
 // -----------------------------------------------------------------------------
 // 3. GENEL KESME İŞLEME MANTIĞI
 // -----------------------------------------------------------------------------
 
+crate::kernel_counter!(SPURIOUS_IRQS, "irq.spurious");
+
 /// Gelen Harici Kesmeleri (PLIC) İşleme.
 pub fn handle_external_interrupts() {
+    let entry = crate::latency::irq_enter();
     unsafe {
         // PLIC'ten bekleyen IRQ ID'sini al (Claim)
         let irq_id = Plic::claim_irq();
 
-        if irq_id > 0 && irq_id < 1024 { // Geçerli bir IRQ olduğunu varsayalım
+        // 0: bekleyen kesme yok (başka bir hart talep etti veya istek düştü).
+        // Sahte kesme tamamlanmaz ve kesme istatistiğine girmez.
+        if irq_id == 0 {
+            SPURIOUS_IRQS.inc();
+            return;
+        }
+
+        if irq_id < 1024 { // Geçerli bir IRQ olduğunu varsayalım
             // serial_println!("Harici IRQ {} geldi.", irq_id);
-            
+            crate::stats::record_irq(irq_id as usize);
+            crate::replay::irq(irq_id);
+            crate::latency::irq_dispatch(irq_id, entry);
+
+            // Fırtınada maskelenen kesme dağıtılmaz, yalnızca tamamlanır
+            if !crate::irqstorm::irq_fired(irq_id, |_| "plic") {
+                Plic::complete_irq(irq_id);
+                return;
+            }
+
             // 1. IRQ'yu işle (Uygun sürücüyü çağır)
             // driver::handle_irq(irq_id);
             
@@ -162,13 +189,17 @@ pub fn handle_external_interrupts() {
 pub fn handle_timer_interrupt() {
     // serial_println!("Zamanlayıcı kesmesi!");
     
-    // 1. Bir sonraki kesme anını ayarla (Örn: 1 saniye sonra)
-    unsafe {
-        // 1 saniye = 10.000.000 döngü (Temsili, frekans 10MHz varsayımıyla)
-        Clint::set_next_timer_interrupt(10_000_000); 
+    // 1. Karşılaştırıcı kipi kapalıysa bir sonraki kesme anını periyodik ayarla
+    //    (açıksa MTIMECMP'yi `hrtimer` kurar)
+    if !crate::hrtimer::is_active() {
+        unsafe {
+            // 1 saniye = 10.000.000 döngü (Temsili, frekans 10MHz varsayımıyla)
+            Clint::set_next_timer_interrupt(10_000_000); 
+        }
     }
     
-    // 2. İşletim sistemi zamanlayıcı/görev değiştirici (scheduler) mantığını çağır.
+    // 2. Tik ve yüksek çözünürlüklü zamanlayıcılar.
+    crate::hrtimer::timer_interrupt();
 }
 
 /// Tüm kesme kontrolcülerini başlatır.
@@ -185,4 +216,74 @@ pub fn init_interrupts() {
     }
     
     serial_println!("[RV64I] CLINT/PLIC Başlatıldı.");
-}
\ No newline at end of file
+}
+
+// -----------------------------------------------------------------------------
+// YEREL KESME MASKESİ
+// -----------------------------------------------------------------------------
+
+// Genel kod bunları `crate::irqoff` üzerinden kullanır. `asm!` bloklarında
+// `nomem` yoktur: maske değişikliği derleyici için bir bellek engelidir,
+// kilitli bölgenin erişimleri dışarı taşınmaz.
+
+/// sstatus.SIE biti.
+const SSTATUS_SIE: usize = 1 << 1;
+
+/// Gözetmen kesmelerini kapatır; önceki sstatus değerini döndürür.
+#[inline(always)]
+pub fn local_irq_save() -> usize {
+    let flags: usize;
+    // SAFETY: Yalnızca sstatus.SIE okunur ve temizlenir.
+    unsafe { asm!("csrrci {}, sstatus, 2", out(reg) flags, options(nostack)) };
+    flags
+}
+
+/// `local_irq_save` öncesinde SIE açıksa yeniden açar.
+#[inline(always)]
+pub fn local_irq_restore(flags: usize) {
+    // SAFETY: Yalnızca kaydedilen SIE biti geri kurulur.
+    unsafe { asm!("csrs sstatus, {}", in(reg) flags & SSTATUS_SIE, options(nostack)) };
+}
+
+/// Kaydedilen durumda SIE açık mıydı?
+#[inline(always)]
+pub fn irqs_were_enabled(flags: usize) -> bool {
+    flags & SSTATUS_SIE != 0
+}
+
+/// Gözetmen kesmelerini koşulsuz açar.
+#[inline(always)]
+pub fn local_irq_enable() {
+    // SAFETY: Çağıran kesme almaya hazırdır.
+    unsafe { asm!("csrsi sstatus, 2", options(nostack)) };
+}
+
+// -----------------------------------------------------------------------------
+// HAT MASKESİ VE PCI INTx
+// -----------------------------------------------------------------------------
+
+/// Kesme hattını denetleyicide maskeler veya açar (bkz. `crate::irqstorm`);
+/// `irq` dağıtıcının gördüğü numaradır. Desteklenmiyorsa `false`.
+pub fn set_irq_masked(irq: u32, masked: bool) -> bool {
+    // SAFETY: PLIC etkinleştirme kelimesinde tek bit değiştirilir.
+    unsafe {
+        if masked {
+            Plic::disable_irq(irq);
+        } else {
+            Plic::enable_irq(irq);
+        }
+    }
+    true
+}
+
+/// PCI aygıtının INTx hattını açar ve dağıtıcının göreceği kesme numarasını
+/// döndürür. INTx eşlemesi yoksa `None`; sürücü yoklamayla çalışır.
+pub fn route_pci_intx(_line: u8) -> Option<u32> {
+    None
+}
+
+/// Aygıt ağacı düğümünün ilk kesmesini denetleyicinin göreceği numaraya
+/// çevirir. Bu portun denetleyicisi tek hücreli kesme belirteci kullanır.
+pub fn decode_fdt_irq(node: &crate::fdt::Node) -> Option<u32> {
+    node.interrupt_cell(0)
+}
@@ -3,7 +3,10 @@
 
 use core::arch::asm;
 use core::ptr::NonNull;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use crate::platformgeneric::KernelError;
 use crate::serial_println;
+use crate::memory::vmaudit::{validate_mapping, CacheAttr, MapError, Mapping, Perms};
 use super::io; // Bariyerler için io modülünü kullanacağız
 
 // -----------------------------------------------------------------------------
@@ -95,60 +98,167 @@ fn get_indices(virtual_addr: usize) -> (usize, usize, usize) {
     (l1, l2, l3) 
 }
 
-/// Yeni bir sayfa tablosu tahsis etmeyi simüle eder.
-fn alloc_page_table() -> NonNull<PageTable> {
-    // Mock Tahsis
-    static mut PAGE_TABLE_MOCK: [u8; PAGE_SIZE] = [0; PAGE_SIZE];
-    
-    let ptr = unsafe { PAGE_TABLE_MOCK.as_mut_ptr() as *mut PageTable };
-    let table = unsafe { &mut *ptr };
-    table.entries.iter_mut().for_each(|e| *e = PageTableEntry::zero());
-    
-    unsafe { NonNull::new_unchecked(ptr) }
+/// Sıfırlanmış yeni bir sayfa tablosu ayırır (bkz. `physmap::alloc_table`).
+fn alloc_page_table() -> Result<NonNull<PageTable>, MapError> {
+    let addr = crate::physmap::alloc_table().ok_or(MapError::OutOfMemory)?;
+    // SAFETY: `alloc_table` sıfırdan farklı, sayfa hizalı bir adres döndürür.
+    Ok(unsafe { NonNull::new_unchecked(addr as *mut PageTable) })
+}
+
+/// R, W veya X bitlerinden biri ayarlı geçerli giriş yapraktır.
+fn is_leaf(entry: &PageTableEntry) -> bool {
+    entry.0 & (PageFlags::READ as u64 | PageFlags::WRITE as u64 | PageFlags::EXEC as u64) != 0
+}
+
+/// Donanımın güncelleyebildiği bitler (Svadu); eşlemelerin karşılaştırılmasında yok sayılır.
+const HW_BITS: u64 = PageFlags::ACCESSED as u64 | PageFlags::DIRTY as u64;
+
+/// Tablo girişi geçerliyse gösterdiği tabloyu, değilse yeni ayrılan tabloyu
+/// döndürür; yeni tablo üst tablonun giriş sayısına eklenir (`physmap::table_ref`).
+/// Giriş bir yapraksa (büyük sayfa) adres zaten eşlidir.
+unsafe fn next_table(table: &mut PageTable, index: usize) -> Result<&'static mut PageTable, MapError> {
+    let table_addr = table as *mut PageTable as usize;
+    let entry = &mut table.entries[index];
+    if !entry.is_valid() {
+        *entry = PageTableEntry::new_table(alloc_page_table()?.as_ptr() as usize);
+        crate::physmap::table_ref(table_addr);
+    } else if is_leaf(entry) {
+        return Err(MapError::AlreadyMapped);
+    }
+    Ok(&mut *(((entry.0 & PageFlags::PPN_MASK as u64) << 2) as *mut PageTable))
+}
+
+/// Yaprak girişini yazar. Aynı eşlemenin tekrarı (A/D bitleri hariç)
+/// etkisizdir; farklı bir eşleme yalnızca `replace` ile değiştirilir.
+unsafe fn set_leaf(table: &mut PageTable, index: usize, new: PageTableEntry, replace: bool) -> Result<(), MapError> {
+    let table_addr = table as *mut PageTable as usize;
+    let entry = &mut table.entries[index];
+    if entry.is_valid() {
+        if (entry.0 ^ new.0) & !HW_BITS == 0 {
+            return Ok(());
+        }
+        if !replace {
+            return Err(MapError::AlreadyMapped);
+        }
+    } else {
+        crate::physmap::table_ref(table_addr);
+    }
+    *entry = new;
+    Ok(())
 }
 
+/// Adres ve bayrakların ortak denetimi: Sv39 kanonik sanal adres, 56 bit
+/// fiziksel adres. Bayraklar PPN alanına taşamaz, yaprak için R veya X
+/// gerekir (R=W=X=0 tablo göstericisidir) ve W tek başına ayrılmıştır.
+fn validate(virtual_addr: usize, physical_addr: usize, size: usize, flags: u64) -> Result<(), MapError> {
+    validate_mapping(virtual_addr, physical_addr, size, 39, 56, flags, PageFlags::PPN_MASK as u64)?;
+    let leaf = PageTableEntry(flags);
+    if !is_leaf(&leaf) || (flags & PageFlags::WRITE as u64 != 0 && flags & PageFlags::READ as u64 == 0) {
+        return Err(MapError::InvalidFlags);
+    }
+    Ok(())
+}
+
+/// 4 KiB eşlemenin ortak yolu (`map_page` / `remap_page`).
+unsafe fn map_small(root_table_addr: usize, virtual_addr: usize, physical_addr: usize, flags: u64, replace: bool) -> Result<(), MapError> {
+    validate(virtual_addr, physical_addr, PAGE_SIZE, flags)?;
+    let (l1i, l2i, l3i) = get_indices(virtual_addr);
+    let l1_table = &mut *(root_table_addr as *mut PageTable);
+
+    // L1 -> L2 -> L3; eksik tablolar oluşturulur
+    let l2_table = next_table(l1_table, l1i)?;
+    let l3_table = next_table(l2_table, l2i)?;
+
+    // L3 Girişini ayarla (Son eşleme, 4K Sayfa)
+    set_leaf(l3_table, l3i, PageTableEntry::new_page(physical_addr, flags), replace)
+}
 
 /// Sanal adresi fiziksel adrese eşler (4KiB sayfa).
+///
+/// # Hatalar
+/// * `Unaligned` / `OutOfRange` / `InvalidFlags`: bkz. `validate`.
+/// * `AlreadyMapped`: Adres başka bir adrese veya bayraklarla eşli ya da bir
+///   büyük sayfanın içinde.
+/// * `OutOfMemory`: Ara tablo ayrılamadı.
 pub unsafe fn map_page(
     root_table_addr: usize, // L1 tablosunun fiziksel adresi
     virtual_addr: usize,
     physical_addr: usize,
     flags: u64,
-) {
-    let (l1i, l2i, l3i) = get_indices(virtual_addr);
+) -> Result<(), MapError> {
+    // Sayfa tablosu güncellendi, TLB temizliği (fence.i) gereklidir.
+    map_small(root_table_addr, virtual_addr, physical_addr, flags, false)
+}
+
+/// `map_page` gibi, ancak mevcut 4 KiB eşlemeyi (adres veya bayraklar)
+/// değiştirir. Çağıran eski çeviri için TLB'yi boşaltmalıdır.
+pub unsafe fn remap_page(root_table_addr: usize, virtual_addr: usize, physical_addr: usize, flags: u64) -> Result<(), MapError> {
+    map_small(root_table_addr, virtual_addr, physical_addr, flags, true)
+}
+
+/// Sanal adresi 2 MiB'lık büyük sayfa (seviye 1 yaprağı) olarak eşler.
+/// Adresler `physmap::LARGE_PAGE_SIZE` hizalı olmalıdır.
+///
+/// # Hatalar
+/// `map_page` ile aynı; ayrıca giriş zaten bir tablo gösteriyorsa `AlreadyMapped`.
+pub unsafe fn map_large_page(root_table_addr: usize, virtual_addr: usize, physical_addr: usize, flags: u64) -> Result<(), MapError> {
+    validate(virtual_addr, physical_addr, crate::physmap::LARGE_PAGE_SIZE, flags)?;
+    let (l1i, l2i, _) = get_indices(virtual_addr);
     let l1_table = &mut *(root_table_addr as *mut PageTable);
-    
-    // 1. L2'yi al veya oluştur
-    let l2_entry = l1_table.entries.get_mut(l1i).expect("L1 Index Hata");
-    let l2_addr = if l2_entry.is_valid() {
-        (l2_entry.0 & PageFlags::PPN_MASK as u64) << 2 // PPN -> Fiziksel Adres
-    } else {
-        let new_l2 = alloc_page_table();
-        let new_addr = new_l2.as_ptr() as usize;
-        *l2_entry = PageTableEntry::new_table(new_addr);
-        new_addr as u64
-    } as usize;
-    
-    let l2_table = &mut *(l2_addr as *mut PageTable);
-    
-    // 2. L3'ü al veya oluştur
-    let l3_entry = l2_table.entries.get_mut(l2i).expect("L2 Index Hata");
-    let l3_addr = if l3_entry.is_valid() {
-        (l3_entry.0 & PageFlags::PPN_MASK as u64) << 2
-    } else {
-        let new_l3 = alloc_page_table();
-        let new_addr = new_l3.as_ptr() as usize;
-        *l3_entry = PageTableEntry::new_table(new_addr);
-        new_addr as u64
-    } as usize;
+    let l2_table = next_table(l1_table, l1i)?;
 
-    let l3_table = &mut *(l3_addr as *mut PageTable);
-    
-    // 3. L4 Girişini ayarla (Son eşleme, 4K Sayfa)
-    let final_entry = l3_table.entries.get_mut(l3i).expect("L3 Index Hata");
-    *final_entry = PageTableEntry::new_page(physical_addr, flags);
-    
-    // Sayfa tablosu güncellendi, TLB temizliği (fence.i) gereklidir.
+    // R/W/X bitlerinden biri ayarlı giriş yapraktır; PPN 2 MiB hizalıdır.
+    let entry = &l2_table.entries[l2i];
+    if entry.is_valid() && !is_leaf(entry) {
+        return Err(MapError::AlreadyMapped);
+    }
+    set_leaf(l2_table, l2i, PageTableEntry::new_page(physical_addr, flags), false)
+}
+
+/// Sanal adresi içeren yaprak eşlemeyi (4 KiB, 2 MiB veya 1 GiB) kaldırır,
+/// TLB girişini geçersiz kılar ve boşalan ara tabloları serbest bırakır.
+/// Eşlenmiş fiziksel adresi döndürür.
+///
+/// # Hatalar
+/// * `NotMapped`: Adres eşli değil.
+/// * `Unaligned`: Adres, onu içeren büyük sayfanın başlangıcı değil.
+pub unsafe fn unmap_page(root_table_addr: usize, virtual_addr: usize) -> Result<usize, MapError> {
+    let (l1i, l2i, l3i) = get_indices(virtual_addr);
+    let indices = [l1i, l2i, l3i];
+    let mut path = [root_table_addr, 0, 0];
+    let mut level = 0;
+    let physical_addr = loop {
+        let entry = &mut (*(path[level] as *mut PageTable)).entries[indices[level]];
+        if !entry.is_valid() {
+            return Err(MapError::NotMapped);
+        }
+        let addr = ((entry.0 & PageFlags::PPN_MASK as u64) << 2) as usize;
+        if is_leaf(entry) {
+            let size = 1usize << (12 + 9 * (2 - level));
+            if !virtual_addr.is_multiple_of(size) {
+                return Err(MapError::Unaligned);
+            }
+            *entry = PageTableEntry::zero();
+            break addr;
+        }
+        if level == 2 {
+            // Son seviyede yaprak olmayan geçerli giriş ayrılmıştır
+            return Err(MapError::NotMapped);
+        }
+        path[level + 1] = addr;
+        level += 1;
+    };
+    flush_page(virtual_addr);
+
+    // Boşalan tablolar yukarı doğru serbest bırakılır; kök izlenmez (`None`).
+    // `sfence.vma` adrese ait ara tablo önbelleklerini de geçersiz kılar.
+    while crate::physmap::table_unref(path[level]) == Some(0) && level > 0 {
+        (*(path[level - 1] as *mut PageTable)).entries[indices[level - 1]] = PageTableEntry::zero();
+        flush_page(virtual_addr);
+        crate::physmap::free_table(path[level]);
+        level -= 1;
+    }
+    Ok(physical_addr)
 }
 
 // -----------------------------------------------------------------------------
@@ -171,13 +281,15 @@ unsafe fn write_csr(csr_num: u32, value: u64) {
 }
 
 /// Sayfalama için yeni L1 tablosunu hazırlar ve sanal adresleri eşler.
-pub fn setup_initial_paging() -> usize {
+/// RAM eşlenemezse eşleme hatası döner.
+pub fn setup_initial_paging() -> Result<usize, MapError> {
     serial_println!("[RV64I] Sv39 Sayfalama Hazırlanıyor...");
     
     // Statik olarak hizalanmış bir bellek bloğunu L1 (Kök) Tablo olarak kullan.
     static mut L1_TABLE: [u8; PAGE_SIZE] = [0; PAGE_SIZE];
     
     let l1_addr = unsafe { L1_TABLE.as_mut_ptr() as usize };
+    ROOT_TABLE.store(l1_addr, Ordering::Relaxed);
     
     // Sayfa Tablosunu sıfırla
     let l1_table = unsafe { &mut *(l1_addr as *mut PageTable) };
@@ -191,18 +303,13 @@ pub fn setup_initial_paging() -> usize {
                      | PageFlags::ACCESSED as u64
                      | PageFlags::DIRTY as u64; 
 
-    // Örnek eşleme: İlk 16MB'ı birebir (identity) eşle.
-    let identity_mapping_size = 16 * 1024 * 1024; // 16 MB
-
-    for addr in (0..identity_mapping_size).step_by(PAGE_SIZE) {
-        unsafe {
-            // Sanal ve fiziksel adresler aynı kabul edilir (birebir eşleme)
-            map_page(l1_addr, addr, addr, kernel_flags);
-        }
-    }
-
-    serial_println!("[RV64I] İlk 16MB birebir eşlendi (Identity Mapped).");
-    l1_addr
+    // Tüm RAM birebir eşlenir (sanal = fiziksel); aygıtlar `physmap::map_mmio` ile.
+    crate::physmap::map_ram(
+        0,
+        &mut |virt, phys| unsafe { map_large_page(l1_addr, virt, phys, kernel_flags) },
+        &mut |virt, phys| unsafe { map_page(l1_addr, virt, phys, kernel_flags) },
+    )?;
+    Ok(l1_addr)
 }
 
 /// Sayfalama mekanizmasını etkinleştirir.
@@ -233,12 +340,194 @@ pub unsafe fn enable_paging(l1_phys_addr: usize) {
 /// Sayfalama sonrası çekirdek başlatma işlevi.
 /// `main.rs` içinden çağrılmalıdır.
 pub fn init_mmu() {
-    // İlk sayfa tablosunu hazırla
-    let l1_addr = setup_initial_paging();
+    // İlk sayfa tablosunu hazırla; eksik tabloyla satp yazılmaz
+    let l1_addr = match setup_initial_paging() {
+        Ok(addr) => addr,
+        Err(e) => {
+            serial_println!("[RV64I] İlk sayfa tablosu kurulamadı: {:?}", e);
+            return;
+        }
+    };
     
     // Sayfalamayı etkinleştir
     unsafe {
         // Not: l1_addr'ın fiziksel adresi olduğundan emin olun.
         enable_paging(l1_addr);
     }
+}
+
+// -----------------------------------------------------------------------------
+// KULLANICI SAYFALARI (mmap)
+// -----------------------------------------------------------------------------
+
+/// `setup_initial_paging` tarafından kurulan kök tablo (0: henüz yok).
+static ROOT_TABLE: AtomicUsize = AtomicUsize::new(0);
+
+/// Tek bir sayfanın TLB girişini geçersiz kılar.
+#[inline(always)]
+unsafe fn flush_page(virtual_addr: usize) {
+    asm!("sfence.vma {0}, zero", in(reg) virtual_addr);
+}
+
+/// Kullanıcı erişimli bir 4K sayfa eşler veya mevcut eşlemenin izinlerini
+/// değiştirir (U=1; R her zaman, W ve X isteğe bağlı).
+///
+/// # Hatalar
+/// * `Map(NotReady)`: Sayfalama henüz kurulmadı.
+/// * Eşleme hataları `MapError` dönüşümüyle (bkz. `vmaudit`).
+pub unsafe fn map_user_page(virtual_addr: usize, physical_addr: usize, write: bool, exec: bool) -> Result<(), KernelError> {
+    let root = ROOT_TABLE.load(Ordering::Relaxed);
+    if root == 0 {
+        return Err(MapError::NotReady.into());
+    }
+    let mut flags = PageFlags::READ as u64 | PageFlags::USER as u64 | PageFlags::ACCESSED as u64;
+    if write {
+        flags |= PageFlags::WRITE as u64 | PageFlags::DIRTY as u64;
+    }
+    if exec {
+        flags |= PageFlags::EXEC as u64;
+    }
+    remap_page(root, virtual_addr, physical_addr, flags)?;
+    flush_page(virtual_addr);
+    Ok(())
+}
+
+/// Aygıt bölgesini çekirdek için birebir eşler (`physmap::map_mmio`) ve sanal
+/// adresini döndürür. Önbellek özniteliği platformun PMA'larından gelir.
+///
+/// # Hatalar
+/// * `Map(NotReady)`: Sayfalama henüz kurulmadı.
+/// * Eşleme hataları `MapError` dönüşümüyle (bkz. `vmaudit`).
+pub fn map_device(phys: usize, len: usize) -> Result<usize, KernelError> {
+    let root = ROOT_TABLE.load(Ordering::Relaxed);
+    if root == 0 {
+        return Err(MapError::NotReady.into());
+    }
+    let flags = PageFlags::READ as u64 | PageFlags::WRITE as u64 | PageFlags::GLOBAL as u64 | PageFlags::ACCESSED as u64 | PageFlags::DIRTY as u64;
+    crate::physmap::map_range(
+        phys,
+        phys,
+        len,
+        &mut |virt, phys| unsafe { map_large_page(root, virt, phys, flags) },
+        &mut |virt, phys| unsafe { map_page(root, virt, phys, flags) },
+    )?;
+    // SAFETY: Yalnızca yeni girişler eklendi; tüm TLB temizlenir.
+    unsafe { asm!("sfence.vma zero, zero") };
+    Ok(phys)
+}
+
+/// Kullanıcı sayfasının eşlemesini kaldırır; eşli değilse etkisizdir.
+/// Boşalan ara tablolar serbest bırakılır (bkz. `unmap_page`).
+pub unsafe fn unmap_user_page(virtual_addr: usize) {
+    let root = ROOT_TABLE.load(Ordering::Relaxed);
+    if root == 0 {
+        return;
+    }
+    let _ = unmap_page(root, virtual_addr);
+}
+
+// -----------------------------------------------------------------------------
+// ÇEKİRDEK SANAL ALANI (vmalloc)
+// -----------------------------------------------------------------------------
+
+/// `memory::vmalloc` penceresinin başlangıcı (Sv39 üst yarı).
+pub const VMALLOC_BASE: usize = 0xFFFF_FFD0_0000_0000;
+/// `memory::vmalloc` penceresinin boyutu.
+pub const VMALLOC_SIZE: usize = 1 << 30;
+
+/// Çekirdek verisi için 4 KiB sayfa eşler (R/W, global, yürütülemez).
+///
+/// # Hatalar
+/// * `Map(NotReady)`: Sayfalama henüz kurulmadı.
+/// * Eşleme hataları `MapError` dönüşümüyle (bkz. `vmaudit`).
+pub fn map_kernel_page(virt: usize, phys: usize) -> Result<(), KernelError> {
+    let root = ROOT_TABLE.load(Ordering::Relaxed);
+    if root == 0 {
+        return Err(MapError::NotReady.into());
+    }
+    let flags = PageFlags::READ as u64 | PageFlags::WRITE as u64 | PageFlags::GLOBAL as u64 | PageFlags::ACCESSED as u64 | PageFlags::DIRTY as u64;
+    // SAFETY: Pencere yalnızca `vmalloc` tarafından yönetilir.
+    unsafe {
+        map_page(root, virt, phys, flags)?;
+        flush_page(virt);
+    }
+    Ok(())
+}
+
+/// `map_kernel_page` eşlemesini kaldırır ve fiziksel adresini döndürür.
+pub unsafe fn unmap_kernel_page(virt: usize) -> Option<usize> {
+    match ROOT_TABLE.load(Ordering::Relaxed) {
+        0 => None,
+        root => unmap_page(root, virt).ok(),
+    }
+}
+
+// -----------------------------------------------------------------------------
+// SAYFA TABLOSU DENETİMİ (vm dump)
+// -----------------------------------------------------------------------------
+
+/// Svpbmt sayfa tabanlı bellek türü alanı (bit 62:61): 0 = PMA, 1 = NC, 2 = IO.
+const PTE_PBMT_SHIFT: u64 = 61;
+
+/// Sv39 sanal adresini kanonik biçime (bit 38'in işaret genişletmesi) getirir.
+fn canonical(addr: usize) -> usize {
+    (((addr << 25) as isize) >> 25) as usize
+}
+
+/// Bir tablo seviyesini özyinelemeli olarak dolaşır (seviye 2: 1G, 1: 2M, 0: 4K).
+unsafe fn walk_level(table_addr: usize, level: usize, base: usize, visit: &mut dyn FnMut(Mapping)) {
+    let table = &*(table_addr as *const PageTable);
+    let shift = 12 + 9 * level;
+
+    for (index, entry) in table.entries.iter().enumerate() {
+        if !entry.is_valid() {
+            continue;
+        }
+        let raw = entry.0;
+        let vaddr = canonical(base | (index << shift));
+        let paddr = (((raw & PageFlags::PPN_MASK as u64) >> 10) as usize) * PAGE_SIZE;
+        let rwx = PageFlags::READ as u64 | PageFlags::WRITE as u64 | PageFlags::EXEC as u64;
+
+        // R/W/X bitlerinin hepsi sıfırsa giriş bir sonraki seviyeye işaret eder.
+        if raw & rwx == 0 {
+            if level > 0 {
+                walk_level(paddr, level - 1, vaddr, visit);
+            }
+            continue;
+        }
+
+        let cache = match (raw >> PTE_PBMT_SHIFT) & 0x3 {
+            1 => CacheAttr::Uncached,
+            2 => CacheAttr::Device,
+            _ => CacheAttr::WriteBack,
+        };
+
+        let size = 1usize << shift;
+        visit(Mapping {
+            vaddr,
+            paddr: paddr & !(size - 1),
+            size,
+            perms: Perms {
+                read: raw & PageFlags::READ as u64 != 0,
+                write: raw & PageFlags::WRITE as u64 != 0,
+                exec: raw & PageFlags::EXEC as u64 != 0,
+                user: raw & PageFlags::USER as u64 != 0,
+            },
+            cache,
+        });
+    }
+}
+
+/// satp'deki etkin Sv39 kök tablosundan başlayarak tüm yaprak eşlemeleri bildirir.
+pub fn walk_mappings(visit: &mut dyn FnMut(Mapping)) {
+    const CSR_SATP: u32 = 0x180;
+    unsafe {
+        let satp = read_csr(CSR_SATP);
+        // MODE = 0 (Bare): çeviri kapalı, dolaşılacak tablo yok.
+        if satp >> 60 == 0 {
+            return;
+        }
+        let root = ((satp & 0x0000_0FFF_FFFF_FFFF) as usize) * PAGE_SIZE;
+        walk_level(root, 2, 0, visit);
+    }
 }
\ No newline at end of file
@@ -2,6 +2,7 @@
 // RISC-V 64 (RV64I) mimarisine özgü temel güvenlik işlevleri.
 
 use core::arch::asm;
+use crate::platformgeneric::KernelError;
 use crate::serial_println;
 // arch/rv64i/platformmod.rs dosyasından temel CSR erişimi ve bariyer işlevlerini içe aktarır.
 use super::platformmod::io; 
@@ -113,4 +114,40 @@ pub fn security_init() {
     // yürütme mitigasyonları buraya eklenirdi.
     
     serial_println!("[SECURITY] Temel Güvenlik Yapılandırması Tamamlandı.");
-}
\ No newline at end of file
+}
+
+// -----------------------------------------------------------------------------
+// Kullanıcı Belleği Erişimi
+// -----------------------------------------------------------------------------
+
+/// sstatus.SUM: S kipinin U sayfalarına erişimi (`configure_security_features` kapatır).
+const SSTATUS_SUM: usize = 1 << 18;
+
+/// SUM'u yalnızca kopya süresince açarak `f`'yi çalıştırır. Tuzak dönüşü
+/// sstatus'u çerçeveden yüklediğinden araya giren kesme durumu bozmaz.
+#[inline(always)]
+fn with_user_access<R>(f: impl FnOnce() -> R) -> R {
+    // SAFETY: Yalnızca SUM biti değişir.
+    unsafe { asm!("csrs sstatus, {}", in(reg) SSTATUS_SUM, options(nostack)) };
+    let result = f();
+    unsafe { asm!("csrc sstatus, {}", in(reg) SSTATUS_SUM, options(nostack)) };
+    result
+}
+
+/// Kullanıcı alanındaki `src` adresinden çekirdek arabelleğine `dst.len()` bayt
+/// kopyalar. Kopya düzeltme tablolu yoklamalarla yapılır.
+///
+/// # Hatalar
+/// * `InvalidArgument`: Kopyalama sırasında okunamayan bir bayta rastlandı.
+pub fn copy_from_user(dst: &mut [u8], src: usize) -> Result<(), KernelError> {
+    with_user_access(|| crate::fixup::probe_kernel_read(src, dst))
+}
+
+/// Çekirdek arabelleği `src`'yi kullanıcı alanındaki `dst` adresine kopyalar.
+///
+/// # Hatalar
+/// * `InvalidArgument`: Kopyalama sırasında yazılamayan bir bayta rastlandı;
+///   önceki baytlar yazılmış olabilir.
+pub fn copy_to_user(dst: usize, src: &[u8]) -> Result<(), KernelError> {
+    with_user_access(|| crate::fixup::probe_kernel_write(dst, src))
+}
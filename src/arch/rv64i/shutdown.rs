@@ -100,6 +100,33 @@ pub fn system_reboot() -> ! {
     halt_loop();
 }
 
+/// Sıcak veya soğuk yeniden başlatma ister (bkz. `reboot.rs`).
+///
+/// SBI SRST yalnızca iki standart neden tanır; hata kaynaklı sıfırlamalar
+/// (`failure`) SYSTEM_FAILURE ile bildirilir. Ayrıntılı `reason` korunan
+/// bellekte taşınır.
+pub fn system_reset(warm: bool, _reason: u32, failure: bool) -> ! {
+    serial_println!("[SHUTDOWN] {} Yeniden Başlatma İsteniyor (SBI)...", if warm { "Sıcak" } else { "Soğuk" });
+
+    // 1. Kesmeleri devre dışı bırak
+    unsafe {
+        io::disable_interrupts();
+    }
+
+    // 2. SBI SRST çağrısı
+    // a0: Reset Type (WARM/COLD_REBOOT), a1: Reset Reason (NONE/SYSTEM_FAILURE)
+    sbi_call(
+        EID_SRST,
+        SRST_FUNCTION_SYSTEM_RESET,
+        if warm { SRST_TYPE_WARM_REBOOT } else { SRST_TYPE_COLD_REBOOT },
+        if failure { SRST_REASON_SYSTEM_FAILURE } else { SRST_REASON_NONE },
+        0, 0, 0, 0
+    );
+
+    // 3. SBI başarısız olursa
+    halt_loop();
+}
+
 // -----------------------------------------------------------------------------
 // Kapatma İşlevleri
 // -----------------------------------------------------------------------------
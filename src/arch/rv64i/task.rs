@@ -2,6 +2,8 @@
 // RISC-V 64 (RV64I) mimarisine özgü görev (task) ve bağlam (context) yönetimi.
 
 use core::arch::asm;
+use core::mem::offset_of;
+use crate::context::ArchContext;
 use crate::serial_println;
 
 /// Görev bağlamını (task context) saklamak için kullanılan yapı.
@@ -28,100 +30,188 @@ pub struct TaskContext {
     // Bağlam Anahtarlama için kritik yazmaçlar
     x1_ra: u64,  // ra (Return Address) - PC görevi görür
     x2_sp: u64,  // sp (Stack Pointer)
+
+    // Görev devam ettiğinde a0'da göreceği değer (`set_return_value`).
+    ret: u64,
 }
 
 impl TaskContext {
-    /// Yeni bir görev bağlamı oluşturur.
-    /// 
-    /// # Argümanlar
-    /// * `stack_top`: Görevin yığınının en üst adresi.
-    /// * `entry_point`: Görevin başlayacağı fonksiyonun adresi.
-    pub fn new(stack_top: u64, entry_point: u64) -> Self {
-        // Yeni bir görev başlatıldığında, anahtarlama kodu bu yapıyı yükler.
-        Self {
-            // Callee-Saved yazmaçlar sıfırlanır.
-            ..Default::default()
-            
-            // x2_sp, yığının üstü olarak ayarlanır.
-            x2_sp: stack_top,
-            
-            // x1_ra, görevin ilk başlayacağı adres olarak ayarlanır.
-            // Anahtarlama `jr ra` ile görev döndüğünde, bu ra'ya zıplayacaktır.
-            x1_ra: entry_point, 
-        }
-    }
-
-    /// Bir bağlam anahtarlama işlemi sırasında yazmaç durumunu kaydetmek ve 
-    /// yeni görevden yazmaç durumunu yüklemek için kullanılan harici assembly fonksiyonu.
+    /// s0-s11'i, sp'yi ve dönüş noktasını `old_context`'e kaydeder,
+    /// `new_context`'ten yükler ve onun ra alanındaki adrese zıplar. Eski görev
+    /// yeniden seçildiğinde `2:` etiketinden, yani bu çağrının sonundan devam
+    /// eder.
     ///
     /// # Güvenlik Notu
-    /// Bu fonksiyon, C ABI'sine uymalıdır: `fn switch_context(old_context: *mut TaskContext, new_context: *const TaskContext)`.
-    /// Argümanlar x10 ve x11'e (a0 ve a1) geçirilir.
+    /// İki işaretçi de geçiş boyunca geçerli olmalıdır; `new_context` ya
+    /// `ArchContext::new` ile kurulmuş ya da daha önce kaydedilmiş olmalıdır.
+    /// s0 (fp) ve s1 işlenen olarak verilemez; blok onları kendisi kaydedip yükler.
     #[inline(always)]
     pub unsafe fn switch_context(old_context: *mut TaskContext, new_context: *const TaskContext) {
-        serial_println!("[TASK] Bağlam Anahtarlama: {:#x} -> {:#x}", 
-                        old_context as u64, new_context as u64);
-
         asm!(
             // --------------------- Mevcut Görevin Durumunu Kaydet ---------------------
-            // x10: old_context, x11: new_context (C ABI'de a0 ve a1)
-            
-            // 1. Callee-Saved GPR'ları TaskContext'e kaydet (s0-s11)
-            // sd xN, offset(x10) (Store Doubleword)
-            
-            // x8_s0 offset 0'da başlar. 12 yazmaç = 96 bayt.
-            "sd x8, 0(x10)",   // s0 (x8)
-            "sd x9, 8(x10)",   // s1 (x9)
-            "sd x18, 16(x10)", // s2 (x18)
-            "sd x19, 24(x10)", // s3 (x19)
-            "sd x20, 32(x10)", // s4 (x20)
-            "sd x21, 40(x10)", // s5 (x21)
-            "sd x22, 48(x10)", // s6 (x22)
-            "sd x23, 56(x10)", // s7 (x23)
-            "sd x24, 64(x10)", // s8 (x24)
-            "sd x25, 72(x10)", // s9 (x25)
-            "sd x26, 80(x10)", // s10 (x26)
-            "sd x27, 88(x10)", // s11 (x27)
-            
-            // 2. ra (x1) ve sp (x2) kaydet.
-            // ra (x1) offset 96, sp (x2) offset 104.
-            "sd x1, 96(x10)",  // ra
-            "sd x2, 104(x10)", // sp
-            
+            "sd x8, {x8_s0}(x10)",
+            "sd x9, {x9_s1}(x10)",
+            "sd x18, {x18_s2}(x10)",
+            "sd x19, {x19_s3}(x10)",
+            "sd x20, {x20_s4}(x10)",
+            "sd x21, {x21_s5}(x10)",
+            "sd x22, {x22_s6}(x10)",
+            "sd x23, {x23_s7}(x10)",
+            "sd x24, {x24_s8}(x10)",
+            "sd x25, {x25_s9}(x10)",
+            "sd x26, {x26_s10}(x10)",
+            "sd x27, {x27_s11}(x10)",
+            "sd x2, {x2_sp}(x10)",
+            // Devam noktası: görev yeniden seçildiğinde `2:`'ye döner
+            "la x5, 2f",
+            "sd x5, {x1_ra}(x10)",
+
             // --------------------- Yeni Görevin Durumunu Yükle ---------------------
-            // x11: new_context
-            
-            // 1. Yeni x2 (sp) yükle (Offset 104)
-            "ld x2, 104(x11)",  
-            
-            // 2. Callee-Saved GPR'ları yükle (s0-s11) (Offset 0)
-            "ld x8, 0(x11)", 
-            "ld x9, 8(x11)",
-            "ld x18, 16(x11)",
-            "ld x19, 24(x11)",
-            "ld x20, 32(x11)",
-            "ld x21, 40(x11)",
-            "ld x22, 48(x11)",
-            "ld x23, 56(x11)",
-            "ld x24, 64(x11)",
-            "ld x25, 72(x11)",
-            "ld x26, 80(x11)",
-            "ld x27, 88(x11)",
-            
-            // 3. Yeni x1 (ra) yükle (Offset 96)
-            "ld x1, 96(x11)",
-            
-            // 4. Yeni görevin giriş noktasına zıpla (jr ra)
-            "jr x1", // Jump Register (x1 = ra). Görevi başlatır/devam ettirir.
-            
-            in("x10") old_context,
-            in("x11") new_context,
-            // x12-x17 (a2-a7) caller-saved
-            options(noreturn, preserves_flags)
+            "ld x8, {x8_s0}(x11)",
+            "ld x9, {x9_s1}(x11)",
+            "ld x18, {x18_s2}(x11)",
+            "ld x19, {x19_s3}(x11)",
+            "ld x20, {x20_s4}(x11)",
+            "ld x21, {x21_s5}(x11)",
+            "ld x22, {x22_s6}(x11)",
+            "ld x23, {x23_s7}(x11)",
+            "ld x24, {x24_s8}(x11)",
+            "ld x25, {x25_s9}(x11)",
+            "ld x26, {x26_s10}(x11)",
+            "ld x27, {x27_s11}(x11)",
+            "ld x2, {x2_sp}(x11)",
+            "ld x5, {x1_ra}(x11)",
+            "ld x10, {ret}(x11)",
+            "jr x5",
+            "2:",
+
+            x8_s0 = const offset_of!(TaskContext, x8_s0),
+            x9_s1 = const offset_of!(TaskContext, x9_s1),
+            x18_s2 = const offset_of!(TaskContext, x18_s2),
+            x19_s3 = const offset_of!(TaskContext, x19_s3),
+            x20_s4 = const offset_of!(TaskContext, x20_s4),
+            x21_s5 = const offset_of!(TaskContext, x21_s5),
+            x22_s6 = const offset_of!(TaskContext, x22_s6),
+            x23_s7 = const offset_of!(TaskContext, x23_s7),
+            x24_s8 = const offset_of!(TaskContext, x24_s8),
+            x25_s9 = const offset_of!(TaskContext, x25_s9),
+            x26_s10 = const offset_of!(TaskContext, x26_s10),
+            x27_s11 = const offset_of!(TaskContext, x27_s11),
+            x1_ra = const offset_of!(TaskContext, x1_ra),
+            x2_sp = const offset_of!(TaskContext, x2_sp),
+            ret = const offset_of!(TaskContext, ret),
+            inout("x10") old_context => _,
+            inout("x11") new_context => _,
+            // Devam eden görev için caller-saved yazmaçların (ra dahil) hepsi bozulmuş sayılır
+            clobber_abi("C"),
         );
     }
+
+    /// Callee-saved yazmaçları, yığın işaretçisini ve devam noktasını
+    /// `context`'e kaydeder, ardından `body(data)`'yı çağırır. `body` dönerse
+    /// 0 döner; `context` daha sonra `switch_context` ile yüklenirse yürütme
+    /// `2:` etiketinden sürer ve dönüş değeri bağlamın `ret` alanı olur (bkz.
+    /// `fixup::catch`).
+    ///
+    /// # Güvenlik Notu
+    /// `context` geri dönüşe kadar geçerli kalmalıdır; bağlam yalnızca `body`
+    /// henüz dönmemişken yüklenebilir.
+    #[inline(always)]
+    pub unsafe fn catch_call(context: *mut TaskContext, body: extern "C" fn(*mut u8), data: *mut u8) -> usize {
+        let resumed: usize;
+        asm!(
+            // --------------------- Geri Dönüş Noktasını Kaydet ---------------------
+            "sd x8, {x8_s0}(x10)",
+            "sd x9, {x9_s1}(x10)",
+            "sd x18, {x18_s2}(x10)",
+            "sd x19, {x19_s3}(x10)",
+            "sd x20, {x20_s4}(x10)",
+            "sd x21, {x21_s5}(x10)",
+            "sd x22, {x22_s6}(x10)",
+            "sd x23, {x23_s7}(x10)",
+            "sd x24, {x24_s8}(x10)",
+            "sd x25, {x25_s9}(x10)",
+            "sd x26, {x26_s10}(x10)",
+            "sd x27, {x27_s11}(x10)",
+            "sd x2, {x2_sp}(x10)",
+            // Devam noktası: bağlam yüklenirse `2:`'ye döner
+            "la x5, 2f",
+            "sd x5, {x1_ra}(x10)",
+
+            // --------------------- Gövdeyi Çağır ---------------------
+            "mv x10, x12",
+            "jalr x11",
+            "li x10, 0",
+            "2:",
+
+            x8_s0 = const offset_of!(TaskContext, x8_s0),
+            x9_s1 = const offset_of!(TaskContext, x9_s1),
+            x18_s2 = const offset_of!(TaskContext, x18_s2),
+            x19_s3 = const offset_of!(TaskContext, x19_s3),
+            x20_s4 = const offset_of!(TaskContext, x20_s4),
+            x21_s5 = const offset_of!(TaskContext, x21_s5),
+            x22_s6 = const offset_of!(TaskContext, x22_s6),
+            x23_s7 = const offset_of!(TaskContext, x23_s7),
+            x24_s8 = const offset_of!(TaskContext, x24_s8),
+            x25_s9 = const offset_of!(TaskContext, x25_s9),
+            x26_s10 = const offset_of!(TaskContext, x26_s10),
+            x27_s11 = const offset_of!(TaskContext, x27_s11),
+            x1_ra = const offset_of!(TaskContext, x1_ra),
+            x2_sp = const offset_of!(TaskContext, x2_sp),
+            inout("x10") context as usize => resumed,
+            inout("x11") body => _,
+            inout("x12") data => _,
+            clobber_abi("C"),
+        );
+        resumed
+    }
+}
+
+impl ArchContext for TaskContext {
+    /// İlk geçiş `task_start`'a zıplar; o da s1/s2'deki işlev ve argümanla
+    /// `task_entry`'yi çağırır.
+    fn new(entry: usize, arg: usize, stack_top: usize) -> Self {
+        Self {
+            x9_s1: entry as u64,
+            x18_s2: arg as u64,
+            // psABI: sp 16 bayt hizalıdır
+            x2_sp: (stack_top & !0xF) as u64,
+            x1_ra: task_start as usize as u64,
+            ..Default::default()
+        }
+    }
+
+    unsafe fn switch(&mut self, next: &Self) {
+        Self::switch_context(self, next);
+    }
+
+    unsafe fn catch(&mut self, body: extern "C" fn(*mut u8), data: *mut u8) -> usize {
+        Self::catch_call(self, body, data)
+    }
+
+    fn set_return_value(&mut self, value: usize) {
+        self.ret = value as u64;
+    }
+}
+
+extern "C" {
+    /// Yeni görevin ilk komutu (aşağıdaki `global_asm!`).
+    fn task_start();
 }
 
+core::arch::global_asm!(
+    ".global task_start",
+    "task_start:",
+    "mv a0, s1",
+    "mv a1, s2",
+    // Yığın geri izleme burada dursun
+    "li s0, 0",
+    "li ra, 0",
+    "call {entry}",
+    "unimp",
+    entry = sym task_entry,
+);
+
 
 // -----------------------------------------------------------------------------
 // Görev Başlatma (Task Entry)
@@ -134,6 +224,7 @@ impl TaskContext {
 /// * `func`: Görevin gerçek giriş noktası (başlatılacak fonksiyonun adresi, x10'da olmalı).
 /// * `arg`: Göreve geçirilen u64 formatında argüman (x11'de olmalı).
 extern "C" fn task_entry(func: u64, arg: u64) -> ! {
+    crate::context::task_started();
     serial_println!("[TASK] Yeni Görev Başlatılıyor. Argüman: {:#x}", arg);
 
     // Fonksiyon işaretçisini (u64) gerçek fonksiyona dönüştür.
@@ -145,15 +236,10 @@ extern "C" fn task_entry(func: u64, arg: u64) -> ! {
     // Gerçek görev fonksiyonunu çağır (arg, x11 (a1)'da beklenir)
     entry_func(arg);
 
-    // Görev tamamlandı. Normalde bu noktaya gelinmemelidir.
-    serial_println!("[TASK] Görev Tamamlandı! İşlemci durduruluyor.");
-    
-    // Görev tamamlandığında, çekirdek durdurulmalıdır.
-    loop {
-        unsafe {
-            io::wfi(); // RISC-V'de bekleme talimatı
-        }
-    }
+    // Görev işlevi döndü: görev sonlandırılır ve işlemci sıradaki göreve
+    // (en azından boşta görevine) bırakılır
+    serial_println!("[TASK] Görev Tamamlandı.");
+    crate::sched::exit_current()
 }
 
 
@@ -169,4 +255,28 @@ pub fn initialize_tasking() {
     let entry_point_addr = task_entry as *const (); 
     
     serial_println!("[TASK] Task Entry Adresi: {:#x}", entry_point_addr as u64);
-}
\ No newline at end of file
+
+    // Zamanlayıcı bundan sonra görevler arasında gerçekten geçer (bkz. `context`)
+    crate::context::enable();
+}
+
+// -----------------------------------------------------------------------------
+// İŞLEMCİ BAŞINA ALAN TABANI
+// -----------------------------------------------------------------------------
+
+/// Bu işlemcinin işlemci başına alanının (`percpu`) tabanını yazar (`tp`).
+///
+/// # Safety
+/// `base` bu işlemciye ayrılmış, `.percpu` şablonunun kopyası olan alanı
+/// göstermelidir.
+pub unsafe fn set_cpu_local_base(base: usize) {
+    asm!("mv tp, {}", in(reg) base, options(nostack, preserves_flags, nomem));
+}
+
+/// Bu işlemcinin işlemci başına alanının tabanı (henüz yazılmadıysa 0).
+pub fn cpu_local_base() -> usize {
+    let base: usize;
+    // SAFETY: Çekirdekte TLS yoktur; `tp` yalnızca bu amaçla kullanılır.
+    unsafe { asm!("mv {}, tp", out(reg) base, options(nostack, preserves_flags, nomem)) };
+    base
+}
@@ -40,6 +40,12 @@ pub fn read_time_counter() -> Cycles {
     Cycles(cycles)
 }
 
+/// Serbest çalışan sayacın anlık değeri (bkz. `crate::clock`).
+#[inline(always)]
+pub fn read_cycles() -> Cycles {
+    read_time_counter()
+}
+
 /// Sayaç frekansını (Hz) döndürür.
 ///
 /// Not: RISC-V mimarisi standardı, frekansı bir yazmaçta saklamaz.
@@ -95,6 +101,16 @@ pub fn initialize_time_system() {
     serial_println!("[TIME] Varsayılan Frekans (Hz): {}", presumed_freq_hz);
 }
 
+// -----------------------------------------------------------------------------
+// Zamanlayıcı Karşılaştırıcısı (MTIMECMP)
+// -----------------------------------------------------------------------------
+
+/// CLINT MTIMECMP'yi mutlak `deadline` değerine kurar. MTIME ile `time`
+/// CSR'ı aynı sayacı gösterdiğinden değer doğrudan yazılır.
+pub unsafe fn set_timer_deadline(deadline: Cycles) {
+    super::interrupt::Clint::set_timer_deadline(deadline.0);
+}
+
 // Platforma özel G/Ç fonksiyonları için bir yer tutucu (main.rs veya platformmod.rs'de tamamlanmalıdır)
 mod io {
     use core::arch::asm;
@@ -0,0 +1,34 @@
+// src/arch/sparcv9/cpu.rs
+// SPARC V9 işlemci özelliği algılama: %ver yazmacı.
+//
+// sun4u (UltraSPARC I-IV) hedeflenir. Bu işlemcilerde FPU ve VIS her zaman
+// vardır; şifreleme talimatları (%cfr) yalnızca SPARC T4 ve sonrasında
+// bulunur ve eski işlemcilerde %cfr okumak tuzağa düşer, bu yüzden
+// sorgulanmaz.
+
+#![allow(dead_code)]
+
+use core::arch::asm;
+use crate::cpu::CpuFeatures;
+
+pub fn detect() -> CpuFeatures {
+    let ver: u64;
+    // SAFETY: %ver ayrıcalıklı kipte her zaman okunabilir.
+    unsafe { asm!("rdpr %ver, {0}", out(reg) ver, options(nomem, nostack)) };
+    CpuFeatures {
+        // manuf [63:48], impl [47:32], mask [31:24]
+        id: ver,
+        fpu: true,
+        // VIS 1.0
+        simd: true,
+        aes: false,
+        sha2: false,
+        crc32: false,
+        // casx tek talimatlı karşılaştır-değiştirdir
+        atomics: true,
+        // 4 MiB TTE
+        large_pages: true,
+        huge_pages: false,
+        rng: false,
+    }
+}
@@ -0,0 +1,98 @@
+// src/arch/sparcv9/debug.rs
+// SPARC V9 mimarisine özgü kesme noktası ve gözlem noktası desteği.
+
+#![allow(dead_code)]
+
+use core::arch::asm;
+use crate::debug::{WatchKind, ERR_NO_HW_SINGLE_STEP};
+use crate::platformgeneric::KernelError;
+
+/// `ta 1` (yazılım tuzağı 1, TT 0x101) talimatı.
+pub const BREAKPOINT_INSN: u32 = 0x91D0_2001;
+
+/// Kesme noktası talimatının uzunluğu (bayt).
+pub const BREAKPOINT_INSN_LEN: usize = 4;
+
+/// TPC `ta` talimatının kendisini gösterir; düzeltme gerekmez.
+pub const BREAKPOINT_PC_ADJUST: usize = 0;
+
+/// `flush` talimatı ile değiştirilen çift kelimeyi talimat akışına yansıtır.
+#[inline(always)]
+pub unsafe fn sync_icache(addr: usize, _len: usize) {
+    asm!("flush {0}", in(reg) addr, options(nostack));
+}
+
+/// SPARC V9'da donanım tek adım yoktur; hata ayıklayıcı TNPC'ye geçici kesme noktası koymalıdır.
+pub fn set_single_step(_tstate: &mut u64, enable: bool) -> Result<(), KernelError> {
+    if enable {
+        Err(KernelError::PlatformSpecificError(ERR_NO_HW_SINGLE_STEP))
+    } else {
+        Ok(())
+    }
+}
+
+// -----------------------------------------------------------------------------
+// DONANIM GÖZLEM NOKTASI (UltraSPARC VA Watchpoint)
+// -----------------------------------------------------------------------------
+
+/// Tek bir sanal adres gözlem yazmacı.
+pub const MAX_WATCHPOINTS: usize = 1;
+
+/// VA_watchpoint tuzağı (TT 0x62) erişimden önce alınır.
+pub const WATCH_BEFORE_ACCESS: bool = true;
+
+const ASI_LSU_CONTROL: u8 = 0x45; // LSU Kontrol Yazmacı
+const ASI_DMMU: u8 = 0x58;        // D-MMU yazmaçları
+const DMMU_VA_WATCHPOINT: usize = 0x38;
+
+// LSU Kontrol Yazmacı alanları
+const LSU_VW: u64 = 1 << 21;      // VA gözleminde yazmada eşleş
+const LSU_VR: u64 = 1 << 22;      // VA gözleminde okumada eşleş
+const LSU_VM_SHIFT: u64 = 25;     // 8 bitlik bayt maskesi [32:25]
+const LSU_VM_MASK: u64 = 0xFF << LSU_VM_SHIFT;
+
+unsafe fn read_lsu_control() -> u64 {
+    let value: u64;
+    asm!("ldxa [%g0] {asi}, {value}", asi = const ASI_LSU_CONTROL, value = out(reg) value, options(nostack));
+    value
+}
+
+unsafe fn write_lsu_control(value: u64) {
+    asm!("stxa {value}, [%g0] {asi}", "membar #Sync", value = in(reg) value, asi = const ASI_LSU_CONTROL, options(nostack));
+}
+
+/// Çift kelime hizalı adresi VA gözlem yazmacına yazar; bayt maskesini (büyük
+/// endian: bayt 0 en anlamlı bit) ve erişim türünü LSU Kontrol Yazmacında ayarlar.
+pub unsafe fn set_watchpoint(_slot: usize, addr: usize, len: usize, kind: WatchKind) -> Result<(), KernelError> {
+    let access = match kind {
+        WatchKind::Read => LSU_VR,
+        WatchKind::Write => LSU_VW,
+        WatchKind::ReadWrite => LSU_VR | LSU_VW,
+    };
+    let byte_mask = ((1u64 << len) - 1) << (8 - len - (addr & 7));
+
+    asm!(
+        "stxa {value}, [{offset}] {asi}",
+        "membar #Sync",
+        value = in(reg) (addr & !7) as u64,
+        offset = in(reg) DMMU_VA_WATCHPOINT,
+        asi = const ASI_DMMU,
+        options(nostack)
+    );
+
+    let mut lsu = read_lsu_control();
+    lsu &= !(LSU_VM_MASK | LSU_VR | LSU_VW);
+    lsu |= (byte_mask << LSU_VM_SHIFT) | access;
+    write_lsu_control(lsu);
+    Ok(())
+}
+
+/// LSU Kontrol Yazmacındaki VR/VW bitlerini temizler.
+pub unsafe fn clear_watchpoint(_slot: usize) {
+    write_lsu_control(read_lsu_control() & !(LSU_VR | LSU_VW));
+}
+
+/// Tek yuva olduğundan TT 0x62 her zaman yuva 0'dır.
+pub fn watchpoint_hit() -> Option<usize> {
+    Some(0)
+}
@@ -1,24 +1,17 @@
 use core::arch::asm;
 use core::fmt;
 use crate::serial_println;
+use crate::trap::{handle_fatal, Access, Cause};
+use super::vectors::sparcv9_vectors;
 
 // -----------------------------------------------------------------------------
-// HARİCİ MONTAJ DİLİ İŞLEYİCİLERİ
+// TUZAK TABLOSU
 // -----------------------------------------------------------------------------
 
-// SPARC V9'da her tuzak tipi için TBR + (TID * 0x40) adresinde bir vektör bulunur.
-extern "C" {
-    /// 0x00: Reset, Genişletilmiş Reset, Yazılım Tuzağı 0 (Genellikle Reset Vektörü)
-    fn vector_trap_reset();
-    /// 0x40: Talimat Erişim Hataları (ITLBMISS/ITLBHIT)
-    fn vector_trap_instruction_access();
-    /// 0x60: Sistem Çağrısı (SYSCALL)
-    fn vector_trap_syscall();
-    /// 0x80: Veri Erişim Hataları (DTLBMISS/DTLBHIT)
-    fn vector_trap_data_access();
-    /// 0x100: Harici Kesme (IRQ)
-    fn vector_trap_interrupt();
-}
+// SPARC V9'da her tuzak tipi (TT) için TBA + (TL > 0 ? 0x4000 : 0) + TT * 0x20
+// adresinde bir girdi bulunur. Tablo `vectors.rs`'de `trap_vectors!` ile
+// üretilir: pencere taşma/dolum ve temiz pencere tuzakları orada halledilir,
+// diğer tüm girdiler `generic_trap_handler`'ı gerçek TT ile çağırır.
 
 // -----------------------------------------------------------------------------
 // 1. TUZAK KAYIT YAPILARI
@@ -27,11 +20,11 @@ extern "C" {
 /// SPARC V9'da tuzak (trap) oluştuğunda yığına kaydedilen CPU durumu (Context).
 /// SPARC Register Pencereleri kullandığından, istisna işleyici R-Penceresini değiştirir
 /// ve yığına sadece kritik GPR'ları ve kontrol yazmaçlarını kaydeder.
+/// Düzen `vectors.rs`'deki ortak kodla aynıdır.
 #[repr(C)]
 pub struct ExceptionContext {
-    // Montaj kodunun kaydettiği tüm genel amaçlı registerlar (g1-g7) + (o0-o7)
-    // SPARC'ın Window yapısı nedeniyle bu biraz karmaşıktır.
-    // Temsili olarak Global Registerları ve kritik kontrol yazmaçlarını ekliyoruz:
+    // Ortak kodun kaydettiği normal globaller (g1-g7) ve kesilen pencerenin
+    // çıkış yazmaçları (o0-o7; ortak kodun penceresinde i0-i7).
     pub gpr_g: [u64; 7], // g1-g7 (g0=zero, g7=thread/asi)
     pub gpr_o: [u64; 8], // o0-o7 (o6=SP)
     
@@ -43,18 +36,56 @@ pub struct ExceptionContext {
     pub can_restore: u64, // Kayıt Pencere Sayısı
 }
 
-/// Tuzak Tipleri (TID - Trap Identification Number)
+// Ofsetler `vectors.rs`'deki `sparcv9_common` gövdesine gömülüdür (bağlam
+// `save` çerçevesinin 176 baytlık kayıt alanından sonra gelir).
+crate::asm_layout!(ExceptionContext {
+    gpr_g: 0,
+    gpr_o: 56,
+    tstate: 120,
+    tba: 128,
+    tpc: 136,
+    tnpc: 144,
+    can_restore: 152,
+}, size <= 336 - 176);
+
+/// Tuzak Tipleri (TID - Trap Identification Number); değer grubun ilk TT'sidir.
 #[repr(u64)]
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum TrapType {
-    Reset = 0x00,               // Reset veya Genişletilmiş Reset
-    InstructionAccessException = 0x04, // Talimat TLB Miss/Hata
-    Syscall = 0x20,             // Yazılım Tuzağı / SYSCALL
-    DataAccessException = 0x28,  // Veri TLB Miss/Hata
-    Interrupt = 0x100,          // Harici Kesme (IRQ)
+    Reset = 0x00,               // Güç açılışı, Watchdog, XIR, SIR (0x01-0x05)
+    InstructionAccessException = 0x08, // Talimat erişim hatası/MMU miss (0x08-0x09, 0x64-0x67)
+    DataAccessException = 0x30,  // Veri erişim hatası/MMU miss/koruma (0x30-0x31, 0x68-0x6F)
+    Interrupt = 0x41,           // Kesme seviyesi 1-15 (0x41-0x4F)
+    Syscall = 0x110,            // Yazılım Tuzağı: `ta 0x10`
     _Unknown,
 }
 
+/// Seviye 14 kesmesi: TICK_CMPR eşleşmesi (SOFTINT.TICK_INT).
+const TICK_INTERRUPT_TT: u64 = 0x4E;
+
+/// Tuzak kimliğini (TT) ortak `trap::Cause` tipine çevirir.
+///
+/// Veri erişim hatalarında hatalı adres MMU yazmaçlarından okunmalıdır; burada
+/// bilinmediği için 0 verilir.
+pub fn decode_cause(trap_id: u64, context: &ExceptionContext) -> Cause {
+    match trap_id {
+        0x01 | 0x08 | 0x09 => Cause::PageFault { access: Access::Execute, addr: context.tpc as usize },
+        0x02 | 0x10 | 0x11 => Cause::IllegalInstruction,  // Illegal / Privileged opcode
+        0x0A | 0x20 | 0x110 => Cause::Syscall,
+        0x28 | 0x30 | 0x68 => Cause::PageFault { access: Access::Read, addr: 0 },
+        0x34 => Cause::Misaligned { addr: 0 },
+        0x41..=0x4F => Cause::Interrupt((trap_id - 0x40) as u32), // Kesme seviyesi (PIL)
+        0x101 => Cause::Breakpoint,                       // ta 1
+        // VA gözlem noktası: adres MMU yazmacında değil, gözlem tablosunda tutulur
+        0x62 => match crate::debug::watchpoint_hit() {
+            Some(addr) => Cause::Watchpoint { addr },
+            None => Cause::Unknown(trap_id),
+        },
+        0x00 | 0x32 | 0x40 => Cause::MachineError,        // Reset / Veri erişim hatası / Async hata
+        _ => Cause::Unknown(trap_id),
+    }
+}
+
 // -----------------------------------------------------------------------------
 // 2. TEMEL İŞLEYİCİ FONKSİYONLARI (Rust Kodu)
 // -----------------------------------------------------------------------------
@@ -62,27 +93,38 @@ pub enum TrapType {
 /// Tüm tuzak giriş noktalarından montaj kodu tarafından çağrılan Rust işleyicisi.
 ///
 /// # Parametreler
-/// * `trap_id`: Tuzak Kimlik Numarası (TID)
+/// * `trap_id`: Tuzak Kimlik Numarası (TID, 0x000-0x1FF; TL > 0 tuzakları da aynı TT ile gelir)
 #[no_mangle]
 pub extern "C" fn generic_trap_handler(trap_id: u64, context: &mut ExceptionContext) {
-    let trap_offset = trap_id * 0x40; // TID * Vektör Boyutu (0x40 bayt)
-
-    let cause = match trap_offset {
-        0x00 => TrapType::Reset,
-        0x40 => TrapType::InstructionAccessException,
-        0x80 => TrapType::DataAccessException,
-        0x100 => TrapType::Interrupt,
-        0x60 => TrapType::Syscall,
+    let cause = match trap_id {
+        0x00..=0x05 => TrapType::Reset,
+        0x08 | 0x09 | 0x64..=0x67 => TrapType::InstructionAccessException,
+        0x30 | 0x31 | 0x68..=0x6F => TrapType::DataAccessException,
+        0x41..=0x4F => TrapType::Interrupt,
+        0x110 => TrapType::Syscall,
         _ => TrapType::_Unknown,
     };
     
     match cause {
+        TrapType::Interrupt if trap_id == TICK_INTERRUPT_TT => {
+            // Seviye 14: TICK_CMPR eşleşmesi; SOFTINT.TICK_INT karşılaştırıcı
+            // yeniden kurulurken temizlenir
+            let _irq = crate::irqstack::enter(context as *const _ as usize);
+            crate::hrtimer::timer_interrupt();
+        }
         TrapType::Interrupt => {
             // Harici Kesme İşleyicisi
             handle_interrupt(context);
         }
         TrapType::InstructionAccessException | TrapType::DataAccessException => {
             // MMU/Sayfa Hatası İşleyicileri
+            // Kullanıcı belleği erişimi veya bellek yoklaması ise düzeltme tablosuna bak
+            let trap_cause = decode_cause(trap_id, context);
+            if crate::fixup::fixup_exception(trap_cause, &mut context.tpc) {
+                context.tnpc = context.tpc + 4;
+                return;
+            }
+
             serial_println!("\n--- TLB/SAYFA HATASI ---");
             serial_println!("Neden: {:?}", cause);
             // SPARC'ta hatalı adresı, istisna sırasında özel MMU yazmaçlarından (MMU_HETAG, vb.) 
@@ -90,7 +132,7 @@ pub extern "C" fn generic_trap_handler(trap_id: u64, context: &mut ExceptionCont
             serial_println!("Hata Adresi: Okunmalı (MMU CSR)");
             serial_println!("TPC (RIP): {:#x}", context.tpc);
             
-            panic!("Kritik MMU Hatası!");
+            handle_fatal(trap_cause, context.tpc as usize);
         }
         TrapType::Syscall => {
             // Sistem Çağrısı
@@ -98,22 +140,38 @@ pub extern "C" fn generic_trap_handler(trap_id: u64, context: &mut ExceptionCont
             
             // Syscall'dan dönmek için TPC/TNPC'yi ayarlamanız GEREKİR.
             // İşlem tamamlandıktan sonra TPC = TNPC olmalıdır.
-            context.tpc = context.tnpc; 
+            context.tpc = context.tnpc;
+            context.tnpc += 4;
         }
         _ => {
+            // Kesme noktası (ta 1) ve gözlem noktası önce hata ayıklayıcıya sunulur.
+            let trap_cause = decode_cause(trap_id, context);
+            if crate::debug::handle_trap(trap_cause, &mut context.tpc, &mut context.tstate) {
+                // Devam: TNPC, TPC'yi izlemelidir (`ta` sonrası veya yeniden denenen erişim).
+                context.tnpc = context.tpc + 4;
+                return;
+            }
+
+            // Hizalama ve veri yolu hataları için düzeltme tablosuna bak
+            if crate::fixup::fixup_exception(trap_cause, &mut context.tpc) {
+                context.tnpc = context.tpc + 4;
+                return;
+            }
+
             // Diğer kritik tuzaklar
             serial_println!("\n--- KRİTİK TUZAK ---");
             serial_println!("TID: {:#x} ({:?})", trap_id, cause);
             serial_println!("TPC: {:#x}", context.tpc);
             serial_println!("TSTATE: {:#x}", context.tstate);
             
-            panic!("İşlenmemiş Tuzak!");
+            handle_fatal(trap_cause, context.tpc as usize);
         }
     }
 }
 
 /// Donanım Kesmeleri (IRQ) için özel işleyici.
-fn handle_interrupt(_context: &mut ExceptionContext) {
+fn handle_interrupt(context: &mut ExceptionContext) {
+    let _irq = crate::irqstack::enter(context as *const _ as usize);
     // 1. PIC (Sun4u/UPA) adresinden hangi IRQ'nun geldiğini oku.
     
     // 2. Uygun sürücüyü çağır.
@@ -132,19 +190,10 @@ fn handle_interrupt(_context: &mut ExceptionContext) {
 /// Tuzak Vektör Tablosunu kurar ve kesmeleri etkinleştirir.
 pub fn init_exceptions() {
     unsafe {
-        // 1. TBR (Trap Base Register) yazmacını kur.
-        // Vektör tablosu 4KB'ta hizalanmalıdır.
-        // Linker script'te 0x0000_0000_0010_0000 (Temsili) adresine yerleştirdiğimizi varsayalım.
-        const TRAP_BASE_ADDRESS: u64 = 0x0000_0000_0010_0000;
-        
-        // Bu montaj kodunun, tüm vektör işleyicilerini (vector_trap_reset, vb.)
-        // bu adresten başlayarak doğru ofsetlere yerleştirdiği varsayılır.
-
-        // TBR'ye yazma (TBR: ccr 0, trap base register)
-        asm!("mov %g0, %l0; mov {}, %l0; sethi %hi(0), %tbr; or %l0, %lo(0), %tbr", 
-             in(reg) TRAP_BASE_ADDRESS, 
-             out("l0") _, 
-             options(nostack, nomem)); // Basit bir mtspr/mtc0 simülasyonu
+        // 1. TBA (Trap Base Address) yazmacını üretilen tuzak tablosuna kur.
+        // Tablo 32 KiB hizalıdır (TL = 0 ve TL > 0 yarıları).
+        let trap_base = sparcv9_vectors as usize as u64;
+        asm!("wrpr {}, %tba", in(reg) trap_base, options(nostack, nomem));
 
         // 2. PSTATE (Processor State Register) yazmacını ayarla (Kesmeleri etkinleştirme).
         
@@ -163,4 +212,20 @@ pub fn init_exceptions() {
     
     serial_println!("[SPARCV9] Tuzak Yönetimi başlatıldı.");
     serial_println!("[SPARCV9] Harici kesmeler (IE) etkinleştirildi.");
-}
\ No newline at end of file
+}
+
+// -----------------------------------------------------------------------------
+// 4. KESME YIĞINI
+// -----------------------------------------------------------------------------
+
+/// Kesmeler kesilen görevin yığınında çalışır; giriş kodu yığın değiştirmez
+/// (bkz. `crate::irqstack`).
+pub const SWITCHES_IRQ_STACK: bool = false;
+
+/// Ayrı kesme yığını olmadığından etkisizdir.
+///
+/// # Safety
+/// Diğer portlarla aynı sözleşme: `top` bu işlemciye ait bir yığının üstü olmalıdır.
+pub unsafe fn install_irq_stack(_top: usize) -> Result<(), crate::platformgeneric::KernelError> {
+    Ok(())
+}
@@ -0,0 +1,23 @@
+// src/arch/sparcv9/hypervisor.rs
+// SPARC V9 hypervisor algılama.
+//
+// sun4u hedeflenir; sun4v Hypervisor API'si kullanılmadığından misafir
+// ortamı algılanmaz ve çıplak donanım varsayılır.
+
+#![allow(dead_code)]
+
+use crate::hypervisor::{HypervisorKind, Paravirt};
+
+pub fn detect() -> HypervisorKind {
+    HypervisorKind::None
+}
+
+pub unsafe fn enable_paravirt(_kind: HypervisorKind) -> Paravirt {
+    Paravirt::default()
+}
+
+pub fn pv_clock_ns() -> u64 {
+    0
+}
+
+pub fn pv_console_write_byte(_byte: u8) {}
@@ -145,4 +145,65 @@ pub fn handle_external_interrupts() {
             send_eoi(irq_line);
         }
     }
-}
\ No newline at end of file
+}
+
+// -----------------------------------------------------------------------------
+// YEREL KESME MASKESİ
+// -----------------------------------------------------------------------------
+
+// Genel kod bunları `crate::irqoff` üzerinden kullanır. `asm!` bloklarında
+// `nomem` yoktur: maske değişikliği derleyici için bir bellek engelidir,
+// kilitli bölgenin erişimleri dışarı taşınmaz.
+
+/// Tüm aygıt kesmelerini maskeleyen işlemci kesme düzeyi (%pil).
+const PIL_MAX: usize = 15;
+
+/// Kesme düzeyini en yükseğe çıkarır; önceki %pil değerini döndürür.
+#[inline(always)]
+pub fn local_irq_save() -> usize {
+    let flags: usize;
+    // SAFETY: Yalnızca %pil okunur ve yazılır.
+    unsafe { asm!("rdpr %pil, {}", "wrpr %g0, 15, %pil", out(reg) flags, options(nostack)) };
+    flags
+}
+
+/// `local_irq_save` öncesindeki %pil değerini geri yazar.
+#[inline(always)]
+pub fn local_irq_restore(flags: usize) {
+    // SAFETY: Yalnızca kaydedilen düzey geri yazılır.
+    unsafe { asm!("wrpr {}, 0, %pil", in(reg) flags & PIL_MAX, options(nostack)) };
+}
+
+/// Kaydedilen durumda kesmeler açık mıydı (%pil sıfır)?
+#[inline(always)]
+pub fn irqs_were_enabled(flags: usize) -> bool {
+    flags & PIL_MAX == 0
+}
+
+/// Kesme düzeyini sıfırlar (tüm kesmeler açık).
+#[inline(always)]
+pub fn local_irq_enable() {
+    local_irq_restore(0);
+}
+
+// -----------------------------------------------------------------------------
+// HAT MASKESİ VE PCI INTx
+// -----------------------------------------------------------------------------
+
+/// Kesme hattını denetleyicide maskeler veya açar (bkz. `crate::irqstorm`);
+/// `irq` dağıtıcının gördüğü numaradır. Desteklenmiyorsa `false`.
+pub fn set_irq_masked(_irq: u32, _masked: bool) -> bool {
+    false
+}
+
+/// PCI aygıtının INTx hattını açar ve dağıtıcının göreceği kesme numarasını
+/// döndürür. INTx eşlemesi yoksa `None`; sürücü yoklamayla çalışır.
+pub fn route_pci_intx(_line: u8) -> Option<u32> {
+    None
+}
+
+/// Aygıt ağacı düğümünün ilk kesmesini denetleyicinin göreceği numaraya
+/// çevirir. Bu portun denetleyicisi tek hücreli kesme belirteci kullanır.
+pub fn decode_fdt_irq(node: &crate::fdt::Node) -> Option<u32> {
+    node.interrupt_cell(0)
+}
@@ -3,7 +3,10 @@
 
 use core::arch::asm;
 use core::ptr::NonNull;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use crate::platformgeneric::KernelError;
 use crate::serial_println;
+use crate::memory::vmaudit::{validate_mapping, CacheAttr, MapError, Mapping, Perms};
 use super::io; // Bariyerler için io modülünü kullanacağız
 
 // -----------------------------------------------------------------------------
@@ -88,85 +91,108 @@ const MMU_TSB_BASE_OFFSET: usize = 0x010; // TSB Base Register
 // ÇEKİRDEK SAYFALAMA YÖNETİMİ
 // -----------------------------------------------------------------------------
 
-/// Yeni bir sayfa tablosu tahsis etmeyi simüle eder.
-fn alloc_page_table() -> NonNull<PageTable> {
-    // Mock Tahsis
-    static mut PAGE_TABLE_MOCK: [u8; PAGE_SIZE] = [0; PAGE_SIZE];
-    
-    let ptr = unsafe { PAGE_TABLE_MOCK.as_mut_ptr() as *mut PageTable };
-    let table = unsafe { &mut *ptr };
-    table.entries.iter_mut().for_each(|e| *e = PageTableEntry(0));
-    
-    unsafe { NonNull::new_unchecked(ptr) }
+/// Sıfırlanmış yeni bir sayfa tablosu ayırır. Tablolar 8K hizalı olmalıdır;
+/// `physmap` havuzu 4K hizalı olduğundan doğrudan çerçeve ayırıcısı kullanılır.
+fn alloc_page_table() -> Result<NonNull<PageTable>, MapError> {
+    let addr = crate::memory::frame::alloc().map_err(|_| MapError::OutOfMemory)?;
+    // SAFETY: Çerçeve bu çağrıya aittir, 8K hizalıdır ve çekirdekte erişilebilirdir.
+    unsafe {
+        core::ptr::write_bytes(addr as *mut u8, 0, PAGE_SIZE);
+        Ok(NonNull::new_unchecked(addr as *mut PageTable))
+    }
 }
 
+/// Sanal adres genişliği: 8K sayfa (13 bit ofset) + 3 x 9 bit indeks.
+const VA_BITS: u32 = 40;
+/// Fiziksel adres genişliği (UltraSPARC III: 43 bit).
+const PA_BITS: u32 = 43;
 
 /// Sayfa tablosu hiyerarşisinde verilen sanal adrese karşılık gelen dizinleri döndürür.
 /// 3 seviyeli 8K sayfalama varsayımı.
 fn get_indices(virtual_addr: usize) -> (usize, usize, usize) {
-    // 3 seviye: 39-bit VAddr, 3 x 9 bit indeks (VADDR[40:32], VADDR[31:23], VADDR[22:14])
-    // 8K sayfa boyutu (13 bit ofset)
-    
-    // L1 indeksi: [31:39] bitleri
-    let l1 = (virtual_addr >> 30) & 0x1FF;
-    // L2 indeksi: [22:30] bitleri
-    let l2 = (virtual_addr >> 21) & 0x1FF;
-    // L3 indeksi: [13:21] bitleri
-    let l3 = (virtual_addr >> 12) & 0x1FF; // Yanlış: 13 bit ofset olduğu için 13'ten başlamalı
-    
-    // Düzeltilmiş (8K sayfa için 13 bit ofset, yani 13. bitten başlar):
-    let l1 = (virtual_addr >> 39) & 0x1FF; // VADDR[47:39]
-    let l2 = (virtual_addr >> 30) & 0x1FF; // VADDR[38:30]
-    let l3 = (virtual_addr >> 21) & 0x1FF; // VADDR[29:21] - Son seviye (8K/4K/...)
-    
-    (l1, l2, l3) 
+    // 8K sayfa boyutu (13 bit ofset), 3 x 9 bit indeks
+    let l1 = (virtual_addr >> 31) & 0x1FF; // VADDR[39:31]
+    let l2 = (virtual_addr >> 22) & 0x1FF; // VADDR[30:22]
+    let l3 = (virtual_addr >> 13) & 0x1FF; // VADDR[21:13] - Son seviye (8K)
+
+    (l1, l2, l3)
 }
 
-/// Sanal adresi fiziksel adrese eşler.
+/// Tablo girişi geçerliyse gösterdiği tabloyu, değilse yeni ayrılan tabloyu döndürür.
+/// Giriş bir sayfaysa adres zaten eşlidir.
+unsafe fn next_table(entry: &mut PageTableEntry) -> Result<&'static mut PageTable, MapError> {
+    match entry.0 & PageFlags::TYPE_MASK as u64 {
+        t if t == PageFlags::TABLE as u64 => {}
+        t if t == PageFlags::INVALID as u64 => {
+            *entry = PageTableEntry((alloc_page_table()?.as_ptr() as u64) | PageFlags::TABLE as u64);
+        }
+        _ => return Err(MapError::AlreadyMapped),
+    }
+    Ok(&mut *((entry.0 & PageFlags::PFN_MASK as u64) as *mut PageTable))
+}
+
+/// Sanal adresi fiziksel adrese eşler (8K sayfa).
+///
+/// Bayraklar PFN alanına ve tip bitlerine taşamaz; tip `PAGE_8K` olarak eklenir.
+///
+/// # Hatalar
+/// * `Unaligned` / `OutOfRange` / `InvalidFlags`: bkz. yukarısı ve `validate_mapping`.
+/// * `AlreadyMapped`: Adres başka bir adrese veya bayraklarla eşli ya da ara
+///   seviyede bir sayfa girişi var.
+/// * `OutOfMemory`: Ara tablo ayrılamadı.
 pub unsafe fn map_page(
     root_table_addr: usize, // L1 tablosunun fiziksel adresi
     virtual_addr: usize,
     physical_addr: usize,
     flags: u64,
-) {
+) -> Result<(), MapError> {
+    let reserved = PageFlags::PFN_MASK as u64 | PageFlags::TYPE_MASK as u64;
+    validate_mapping(virtual_addr, physical_addr, PAGE_SIZE, VA_BITS, PA_BITS, flags, reserved)?;
     let (l1i, l2i, l3i) = get_indices(virtual_addr);
     let l1_table = &mut *(root_table_addr as *mut PageTable);
-    
-    // L1 -> L2 Tablosu
-    let l2_entry = l1_table.entries.get_mut(l1i).expect("L1 Index Hata");
-    let l2_addr = if (l2_entry.0 & PageFlags::TYPE_MASK as u64) == PageFlags::TABLE as u64 {
-        l2_entry.0 & PageFlags::PFN_MASK as u64
-    } else {
-        let new_l2 = alloc_page_table();
-        let new_addr = new_l2.as_ptr() as usize;
-        *l2_entry = PageTableEntry((new_addr as u64) | PageFlags::TABLE as u64);
-        new_addr as u64
-    } as usize;
-    
-    let l2_table = &mut *(l2_addr as *mut PageTable);
-    
-    // L2 -> L3 Tablosu
-    let l3_entry = l2_table.entries.get_mut(l2i).expect("L2 Index Hata");
-    let l3_addr = if (l3_entry.0 & PageFlags::TYPE_MASK as u64) == PageFlags::TABLE as u64 {
-        l3_entry.0 & PageFlags::PFN_MASK as u64
-    } else {
-        let new_l3 = alloc_page_table();
-        let new_addr = new_l3.as_ptr() as usize;
-        *l3_entry = PageTableEntry((new_addr as u64) | PageFlags::TABLE as u64);
-        new_addr as u64
-    } as usize;
-
-    let l3_table = &mut *(l3_addr as *mut PageTable);
-    
-    // Son eşleme: L3 Tablosunda 8K Sayfa Girişi
-    let final_entry = l3_table.entries.get_mut(l3i).expect("L3 Index Hata");
-    
-    // Bayrakları ve PFN'i birleştir
-    let pfn = (physical_addr as u64) & PageFlags::PFN_MASK as u64;
-    *final_entry = PageTableEntry(pfn | flags | PageFlags::PAGE_8K as u64);
-    
+
+    // L1 -> L2 -> L3; eksik tablolar oluşturulur
+    let l2_table = next_table(&mut l1_table.entries[l1i])?;
+    let l3_table = next_table(&mut l2_table.entries[l2i])?;
+
+    // Son eşleme: L3 Tablosunda 8K Sayfa Girişi (bayraklar ve PFN birleştirilir).
+    // Aynı eşlemenin tekrarı etkisizdir; farklı bir eşlemenin üzerine yazılmaz.
+    let entry = &mut l3_table.entries[l3i];
+    let new = PageTableEntry(physical_addr as u64 | flags | PageFlags::PAGE_8K as u64);
+    if entry.0 & PageFlags::TYPE_MASK as u64 != PageFlags::INVALID as u64 {
+        return if entry.0 == new.0 { Ok(()) } else { Err(MapError::AlreadyMapped) };
+    }
+    *entry = new;
+
     // TSB'yi temizle (işlemciye TLB/TSB'nin güncellenmesi gerektiğini bildir)
     io::membar_all();
+    Ok(())
+}
+
+/// Sanal adresin 8K eşlemesini kaldırır ve TSB'yi temizler. Eşlenmiş fiziksel
+/// adresi döndürür. Ara tablolar `physmap` tarafından izlenmediğinden
+/// serbest bırakılmaz.
+///
+/// # Hatalar
+/// * `NotMapped`: Adres 8K sayfa olarak eşli değil.
+pub unsafe fn unmap_page(root_table_addr: usize, virtual_addr: usize) -> Result<usize, MapError> {
+    let (l1i, l2i, l3i) = get_indices(virtual_addr);
+    let mut table = root_table_addr;
+    for index in [l1i, l2i] {
+        let entry = (*(table as *const PageTable)).entries[index];
+        if entry.0 & PageFlags::TYPE_MASK as u64 != PageFlags::TABLE as u64 {
+            return Err(MapError::NotMapped);
+        }
+        table = (entry.0 & PageFlags::PFN_MASK as u64) as usize;
+    }
+    let entry = &mut (*(table as *mut PageTable)).entries[l3i];
+    if entry.0 & PageFlags::TYPE_MASK as u64 != PageFlags::PAGE_8K as u64 {
+        return Err(MapError::NotMapped);
+    }
+    let physical_addr = (entry.0 & PageFlags::PFN_MASK as u64) as usize;
+    *entry = PageTableEntry(PageFlags::INVALID as u64);
+    tsb_invalidate_all();
+    Ok(physical_addr)
 }
 
 // -----------------------------------------------------------------------------
@@ -241,16 +267,130 @@ pub fn init_mmu() {
               | PageFlags::MODIFIED as u64;
 
     for addr in (0..mapping_size).step_by(PAGE_SIZE) {
-        unsafe {
-            // Sanal ve fiziksel adresler aynı
-            map_page(l1_addr, addr, addr, flags);
+        // Sanal ve fiziksel adresler aynı
+        if let Err(e) = unsafe { map_page(l1_addr, addr, addr, flags) } {
+            serial_println!("[SPARC V9] {:#x} eşlenemedi ({:?}); MMU etkinleştirilmedi.", addr, e);
+            return;
         }
     }
     serial_println!("[SPARC V9] İlk 16MB birebir eşlendi.");
+    ROOT_TABLE.store(l1_addr, Ordering::Relaxed);
 
     // Sayfalamayı etkinleştir
     unsafe {
         // Not: l1_addr'ın fiziksel adresi olduğundan emin olun.
         enable_paging(l1_addr);
     }
-}
\ No newline at end of file
+}
+
+// -----------------------------------------------------------------------------
+// ÇEKİRDEK SANAL ALANI (vmalloc)
+// -----------------------------------------------------------------------------
+
+/// `memory::vmalloc` penceresinin başlangıcı (40 bit kanonik üst yarı).
+pub const VMALLOC_BASE: usize = 0xFFFF_FFC0_0000_0000;
+/// `memory::vmalloc` penceresinin boyutu.
+pub const VMALLOC_SIZE: usize = 1 << 30;
+
+/// `init_mmu` tarafından kurulan kök tablo (0: henüz yok).
+static ROOT_TABLE: AtomicUsize = AtomicUsize::new(0);
+
+/// Çekirdek verisi için 8K sayfa eşler (ayrıcalıklı, yazılabilir, yürütülemez).
+///
+/// # Hatalar
+/// * `Map(NotReady)`: Sayfalama henüz kurulmadı.
+/// * Eşleme hataları `MapError` dönüşümüyle (bkz. `vmaudit`).
+pub fn map_kernel_page(virt: usize, phys: usize) -> Result<(), KernelError> {
+    let root = ROOT_TABLE.load(Ordering::Relaxed);
+    if root == 0 {
+        return Err(MapError::NotReady.into());
+    }
+    let flags = PageFlags::PRIVILEGED as u64
+        | PageFlags::WRITE_ENA as u64
+        | PageFlags::CACHEABLE as u64
+        | PageFlags::ACCESSED as u64
+        | PageFlags::MODIFIED as u64;
+    // SAFETY: Pencere yalnızca `vmalloc` tarafından yönetilir.
+    unsafe { map_page(root, virt, phys, flags)? };
+    Ok(())
+}
+
+/// `map_kernel_page` eşlemesini kaldırır ve fiziksel adresini döndürür.
+pub unsafe fn unmap_kernel_page(virt: usize) -> Option<usize> {
+    match ROOT_TABLE.load(Ordering::Relaxed) {
+        0 => None,
+        root => unmap_page(root, virt).ok(),
+    }
+}
+
+// -----------------------------------------------------------------------------
+// SAYFA TABLOSU DENETİMİ (vm dump)
+// -----------------------------------------------------------------------------
+
+/// Seviye başına indeks kaydırmaları (`get_indices` ile aynı düzen).
+const LEVEL_SHIFTS: [usize; 3] = [31, 22, 13];
+
+/// Bir tablo seviyesini özyinelemeli olarak dolaşır (seviye 0: L1 ... seviye 2: 8K yaprak).
+unsafe fn walk_level(table_addr: usize, level: usize, base: usize, visit: &mut dyn FnMut(Mapping)) {
+    let table = &*(table_addr as *const PageTable);
+
+    for (index, entry) in table.entries.iter().enumerate() {
+        let raw = entry.0;
+        let vaddr = base | (index << LEVEL_SHIFTS[level]);
+        let addr = (raw & PageFlags::PFN_MASK as u64) as usize;
+
+        match raw & PageFlags::TYPE_MASK as u64 {
+            t if t == PageFlags::TABLE as u64 && level < 2 => walk_level(addr, level + 1, vaddr, visit),
+            t if t == PageFlags::PAGE_8K as u64 => visit(Mapping {
+                vaddr,
+                paddr: addr,
+                size: PAGE_SIZE,
+                perms: Perms {
+                    read: true,
+                    write: raw & PageFlags::WRITE_ENA as u64 != 0,
+                    exec: raw & PageFlags::EXEC_ENA as u64 != 0,
+                    user: raw & PageFlags::PRIVILEGED as u64 == 0,
+                },
+                cache: if raw & PageFlags::CACHEABLE as u64 != 0 { CacheAttr::WriteBack } else { CacheAttr::Uncached },
+            }),
+            _ => {}
+        }
+    }
+}
+
+/// TSB Base yazmacındaki kök tablodan başlayarak tüm yaprak eşlemeleri bildirir.
+pub fn walk_mappings(visit: &mut dyn FnMut(Mapping)) {
+    unsafe {
+        // Alt bitler TSB boyut bayraklarıdır; kök adres 8K hizalıdır.
+        let root = read_mmu_reg(MMU_TSB_BASE_OFFSET) & PageFlags::PFN_MASK as u64;
+        if root != 0 {
+            walk_level(root as usize, 0, 0, visit);
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// KULLANICI SAYFALARI (mmap)
+// -----------------------------------------------------------------------------
+
+/// Kullanıcı sayfası eşlemesi bu portta henüz yok; `mmap` dosya eşlemeleri
+/// `PlatformSpecificError` ile reddedilir.
+///
+/// # Safety
+/// Diğer portlarla aynı sözleşme (bkz. `rv64i::mmu::map_user_page`).
+pub unsafe fn map_user_page(_virtual_addr: usize, _physical_addr: usize, _write: bool, _exec: bool) -> Result<(), crate::platformgeneric::KernelError> {
+    Err(crate::platformgeneric::KernelError::PlatformSpecificError(0))
+}
+
+/// Kullanıcı sayfası eşlenemediğinden etkisizdir.
+pub unsafe fn unmap_user_page(_virtual_addr: usize) {}
+
+// -----------------------------------------------------------------------------
+// AYGIT PENCERELERİ (physmap)
+// -----------------------------------------------------------------------------
+
+/// Büyük sayfalı çekirdek eşlemesi bu portta henüz yok; `physmap::map_mmio`
+/// `PlatformSpecificError` ile reddedilir.
+pub fn map_device(_phys: usize, _len: usize) -> Result<usize, crate::platformgeneric::KernelError> {
+    Err(crate::platformgeneric::KernelError::PlatformSpecificError(0))
+}
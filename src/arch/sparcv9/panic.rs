@@ -3,7 +3,6 @@
 
 use core::arch::asm;
 use core::panic::PanicInfo;
-use crate::serial_println;
 
 /// Panik anında işlemcinin durması için kullanılan sonsuz döngü.
 ///
@@ -11,8 +10,7 @@ use crate::serial_println;
 /// kesmeler devre dışı bırakılmış bir ortamda 'nop' döngüsüne girmek
 /// en güvenli durdurma yöntemidir.
 #[inline(always)]
-fn halt_loop() -> ! {
-    serial_println!("... İşlemci Durduruluyor (FLUSHW/NOP döngüsü).");
+pub(crate) fn halt_loop() -> ! {
     unsafe {
         // Kayıt pencerelerini ana belleğe veya yığına boşalt.
         // Bu, çekirdek yığınındaki kritik verilerin kaybolmamasını sağlar.
@@ -32,31 +30,7 @@ fn halt_loop() -> ! {
 /// Bu fonksiyon, `panic!` makrosu çağrıldığında tetiklenir.
 #[panic_handler]
 fn panic(info: &PanicInfo) -> ! {
-    // 1. Seri porta veya ekrana hata mesajını yazdır.
-    serial_println!("\n========================================");
-    serial_println!("!!! KERNEL PANIC (SPARC V9) !!!");
-    
-    // Panik mesajını yazdır
-    if let Some(location) = info.location() {
-        serial_println!("Hata Konumu: {}:{}:{}", 
-            location.file(), 
-            location.line(), 
-            location.column()
-        );
-    } else {
-        serial_println!("Hata Konumu: Bilinmiyor");
-    }
-    
-    if let Some(message) = info.message() {
-        serial_println!("Hata Mesajı: {}", message);
-    } else {
-        serial_println!("Hata Mesajı: Yok");
-    }
-
-    serial_println!("========================================");
-
-    // Not: Bu noktada kesmelerin devre dışı olduğundan emin olunmalıdır.
-    
-    // 2. İşlemciyi durdur.
-    halt_loop();
-}
\ No newline at end of file
+    // Kesme bağlamı, iç içe panik ve diğer işlemcilerin durdurulması
+    // mimariden bağımsız yolda ele alınır (bkz. `crate::panic`).
+    crate::panic::handle(info, "SPARC V9")
+}
@@ -2,6 +2,8 @@
 // SPARC V9 (UltraSPARC) mimarisine özgü görev (task) ve bağlam (context) yönetimi.
 
 use core::arch::asm;
+use core::mem::offset_of;
+use crate::context::ArchContext;
 use crate::serial_println;
 
 /// Görev bağlamını (task context) saklamak için kullanılan yapı.
@@ -22,6 +24,9 @@ pub struct TaskContext {
     
     // Yığın İşaretçisi (Stack Pointer) - %sp / %o6
     r_sp: u64, // r_o6
+
+    // Çerçeve İşaretçisi - %fp / %i6
+    r_fp: u64,
     
     // Link Register (Return Address) - %o7
     r_lr: u64, // r_o7
@@ -38,113 +43,172 @@ pub struct TaskContext {
     
     // CWP (Current Window Pointer) veya GSW (Global Status Word) gibi özel yazmaçlar
     // Not: Gerçek bir çekirdek bu yazmaçları da kaydetmelidir.
+
+    // Görev devam ettiğinde %o0'da göreceği değer (`set_return_value`).
+    ret: u64,
 }
 
 impl TaskContext {
-    /// Yeni bir görev bağlamı oluşturur.
-    /// 
-    /// # Argümanlar
-    /// * `stack_top`: Görevin yığınının en üst adresi.
-    /// * `entry_point`: Görevin başlayacağı fonksiyonun adresi.
-    pub fn new(stack_top: u64, entry_point: u64) -> Self {
-        Self {
-            ..Default::default()
-            
-            // r_sp (%o6), yığının üstü olarak ayarlanır.
-            r_sp: stack_top,
-            
-            // r_lr (%o7) ve PC/nPC, görevin ilk başlayacağı adres olarak ayarlanır.
-            // SPARC'ta dallanmalar gecikmeli dallanma olduğu için nPC de kritiktir.
-            r_lr: entry_point, 
-            r_pc: entry_point,
-            r_npc: entry_point.wrapping_add(4), // PC + 4
-        }
-    }
-
-    /// Bir bağlam anahtarlama işlemi sırasında yazmaç durumunu kaydetmek ve 
-    /// yeni görevden yazmaç durumunu yüklemek için kullanılan harici assembly fonksiyonu.
+    /// Yazmaç pencerelerini yığına boşaltır (`flushw`), %sp/%fp/%i7'yi, %g2-%g4'ü
+    /// ve dönüş noktasını `old_context`'e kaydeder, `new_context`'ten yükler ve
+    /// onun PC'sine zıplar. Eski görev yeniden seçildiğinde `2:` etiketinden,
+    /// yani bu çağrının sonundan devam eder; çağıranın penceresi dönüşte
+    /// yığından doldurulur.
     ///
     /// # Güvenlik Notu
-    /// Bu fonksiyon, C ABI'sine uymalıdır: `fn switch_context(old_context: *mut TaskContext, new_context: *const TaskContext)`.
-    /// Argümanlar %o0 ve %o1'e (r24 ve r25) geçirilir.
+    /// İki işaretçi de geçiş boyunca geçerli olmalıdır; `new_context` ya
+    /// `ArchContext::new` ile kurulmuş ya da daha önce kaydedilmiş olmalıdır.
+    /// %g7 işlemci başına alanı gösterdiği için (bkz. `set_cpu_local_base`)
+    /// görevle birlikte taşınmaz.
     #[inline(always)]
     pub unsafe fn switch_context(old_context: *mut TaskContext, new_context: *const TaskContext) {
-        serial_println!("[TASK] Bağlam Anahtarlama: {:#x} -> {:#x}", 
-                        old_context as u64, new_context as u64);
-
-        // Not: SPARC'ta pencere anahtarlama (save/restore) görevden önce/sonra yapılmalıdır.
-        // Bu, çekirdek yazmaçlarını korumak için `save` veya `restore` talimatının çağrılmasını gerektirir.
-        
         asm!(
-            // SPARC'ın Yazmaç Penceresi mimarisi nedeniyle, Rust/C fonksiyonları
-            // genellikle yazmaçları yığına kaydetmek için save/restore kullanır.
-            // Bu switch, mevcut görevden bir SAVE ve yeni göreve bir RESTORE gibi davranmalıdır.
-            
             // --------------------- Mevcut Görevin Durumunu Kaydet ---------------------
-            // %o0 (r24): old_context, %o1 (r25): new_context
-            
-            // 1. Global GPR'ları TaskContext'e kaydet (g1-g7)
-            // std rN, [r_base + offset] (Store Doubleword)
-            // g1-g7, r1-r7'dir.
-            "std %g1, [r24 + 0]",
-            "std %g2, [r24 + 8]",
-            // ... r_g3'den r_g7'ye kadar (atlandı)
-            "std %g7, [r24 + 48]", // g7 offset 48
-
-            // 2. SP (%o6) ve LR (%o7) kaydet.
-            "std %o6, [r24 + 56]", // r_sp (o6)
-            "std %o7, [r24 + 64]", // r_lr (o7)
-
-            // 3. Özel Yazmaçları kaydet (PC, nPC, Y, CCR)
-            // mfsr talimatı ile PSR (veya PSTATE), Y, CCR okunur.
-            "rd %pc, %l0",         // r_pc'yi l0'a oku
-            "rd %npc, %l1",        // r_npc'yi l1'e oku
-            "std %l0, [r24 + 72]", // r_pc
-            "std %l1, [r24 + 80]", // r_npc
-            
-            "rd %y, %l0",          // Y yazmacı
-            "wr %l0, 0x1000, %asr24", // CCR (ASR24'te tutulur)
-            "rd %asr24, %l1",
-            "std %l0, [r24 + 88]", // r_y
-            "std %l1, [r24 + 96]", // r_ccr
+            "flushw",
+            "stx %sp, [%o0 + {r_sp}]",
+            "stx %fp, [%o0 + {r_fp}]",
+            "stx %i7, [%o0 + {r_lr}]",
+            "stx %g2, [%o0 + {r_g2}]",
+            "stx %g3, [%o0 + {r_g3}]",
+            "stx %g4, [%o0 + {r_g4}]",
+            // Devam noktası: görev yeniden seçildiğinde `2:`'ye döner
+            "3:",
+            "rd %pc, %g1",
+            "add %g1, 2f - 3b, %g1",
+            "stx %g1, [%o0 + {r_pc}]",
 
             // --------------------- Yeni Görevin Durumunu Yükle ---------------------
-            // %o1 (r25): new_context
-            
-            // 1. Özel Yazmaçları yükle (Y, CCR)
-            "ldd [r25 + 88], %l0", // r_y
-            "wry %l0",
-            "ldd [r25 + 96], %l0", // r_ccr
-            "wr %l0, 0, %asr24",   // CCR
-
-            // 2. Global GPR'ları yükle (g1-g7)
-            "ldd [r25 + 0], %g1",
-            "ldd [r25 + 8], %g2",
-            // ... r_g3'den r_g7'ye kadar (atlandı)
-            "ldd [r25 + 48], %g7",
-            
-            // 3. Yeni SP (%o6) ve LR (%o7) yükle.
-            "ldd [r25 + 56], %o6", // r_sp (o6)
-            "ldd [r25 + 64], %o7", // r_lr (o7)
-            
-            // 4. Yeni görevin PC/nPC'sine zıpla
-            // PC/nPC'yi özel yazmaçlara yükle
-            "ldd [r25 + 72], %l0", // r_pc
-            "ldd [r25 + 80], %l1", // r_npc
-            "jmpl %l0, %g0",       // Jump to PC (%l0)
-            " nop",                // Gecikme Yuvası (nPC'yi doldurur)
-            
-            in("r24") old_context,
-            in("r25") new_context,
-            // r26-r31 (o2-o7) caller-saved
-            out("r8") _, out("r9") _, // l0 ve l1 geçici olarak kullanıldı
-            options(noreturn, preserves_flags)
+            "ldx [%o1 + {r_g2}], %g2",
+            "ldx [%o1 + {r_g3}], %g3",
+            "ldx [%o1 + {r_g4}], %g4",
+            "ldx [%o1 + {r_sp}], %sp",
+            "ldx [%o1 + {r_fp}], %fp",
+            "ldx [%o1 + {r_lr}], %i7",
+            "ldx [%o1 + {r_pc}], %g1",
+            "jmpl %g1, %g0",
+            " ldx [%o1 + {ret}], %o0", // Gecikme yuvası
+            "2:",
+
+            r_g2 = const offset_of!(TaskContext, r_g2),
+            r_g3 = const offset_of!(TaskContext, r_g3),
+            r_g4 = const offset_of!(TaskContext, r_g4),
+            r_sp = const offset_of!(TaskContext, r_sp),
+            r_fp = const offset_of!(TaskContext, r_fp),
+            r_lr = const offset_of!(TaskContext, r_lr),
+            r_pc = const offset_of!(TaskContext, r_pc),
+            ret = const offset_of!(TaskContext, ret),
+            inout("o0") old_context => _,
+            inout("o1") new_context => _,
+            // Pencerenin yerel ve giriş yazmaçları diğer görevden kalır
+            out("l0") _, out("l1") _, out("l2") _, out("l3") _,
+            out("l4") _, out("l5") _, out("l6") _, out("l7") _,
+            out("i0") _, out("i1") _, out("i2") _, out("i3") _, out("i4") _, out("i5") _,
+            clobber_abi("C"),
+        );
+    }
+
+    /// Callee-saved yazmaçları, yığın işaretçisini ve devam noktasını
+    /// `context`'e kaydeder, ardından `body(data)`'yı çağırır. `body` dönerse
+    /// 0 döner; `context` daha sonra `switch_context` ile yüklenirse yürütme
+    /// `2:` etiketinden sürer ve dönüş değeri bağlamın `ret` alanı olur (bkz.
+    /// `fixup::catch`).
+    ///
+    /// # Güvenlik Notu
+    /// `context` geri dönüşe kadar geçerli kalmalıdır; bağlam yalnızca `body`
+    /// henüz dönmemişken yüklenebilir.
+    #[inline(always)]
+    pub unsafe fn catch_call(context: *mut TaskContext, body: extern "C" fn(*mut u8), data: *mut u8) -> usize {
+        let resumed: usize;
+        asm!(
+            // --------------------- Geri Dönüş Noktasını Kaydet ---------------------
+            "flushw",
+            "stx %sp, [%o0 + {r_sp}]",
+            "stx %fp, [%o0 + {r_fp}]",
+            "stx %i7, [%o0 + {r_lr}]",
+            "stx %g2, [%o0 + {r_g2}]",
+            "stx %g3, [%o0 + {r_g3}]",
+            "stx %g4, [%o0 + {r_g4}]",
+            // Devam noktası: bağlam yüklenirse `2:`'ye döner
+            "3:",
+            "rd %pc, %g1",
+            "add %g1, 2f - 3b, %g1",
+            "stx %g1, [%o0 + {r_pc}]",
+
+            // --------------------- Gövdeyi Çağır ---------------------
+            "jmpl %o1, %o7",
+            " mov %o2, %o0", // Gecikme yuvası
+            "mov %g0, %o0",
+            "2:",
+
+            r_g2 = const offset_of!(TaskContext, r_g2),
+            r_g3 = const offset_of!(TaskContext, r_g3),
+            r_g4 = const offset_of!(TaskContext, r_g4),
+            r_sp = const offset_of!(TaskContext, r_sp),
+            r_fp = const offset_of!(TaskContext, r_fp),
+            r_lr = const offset_of!(TaskContext, r_lr),
+            r_pc = const offset_of!(TaskContext, r_pc),
+            inout("o0") context as usize => resumed,
+            inout("o1") body => _,
+            inout("o2") data => _,
+            // Geri dönüşte pencerenin yerel ve giriş yazmaçları hata anından kalır
+            out("l0") _, out("l1") _, out("l2") _, out("l3") _,
+            out("l4") _, out("l5") _, out("l6") _, out("l7") _,
+            out("i0") _, out("i1") _, out("i2") _, out("i3") _, out("i4") _, out("i5") _,
+            clobber_abi("C"),
         );
-        // Yazmaç Penceresi Yönetimi: Başarılı bir anahtarlamada buraya dönülmez.
-        // Geri dönülürse, bir yazmaç penceresi tutarsızlığı yaşanmış demektir.
+        resumed
+    }
+}
+
+/// SPARC V9 yığın sapması: %sp ve %fp gerçek adresin 2047 bayt altını gösterir.
+const STACK_BIAS: usize = 2047;
+
+/// Pencere taşmasında yerel/giriş yazmaçlarının yazıldığı en küçük çerçeve.
+const MIN_FRAME: usize = 176;
+
+impl ArchContext for TaskContext {
+    /// İlk geçiş `task_start`'a zıplar; o da %g2/%g3'teki işlev ve argümanla
+    /// `task_entry`'yi çağırır.
+    fn new(entry: usize, arg: usize, stack_top: usize) -> Self {
+        let start = task_start as usize as u64;
+        Self {
+            r_g2: entry as u64,
+            r_g3: arg as u64,
+            r_sp: (stack_top & !0xF).wrapping_sub(MIN_FRAME + STACK_BIAS) as u64,
+            r_pc: start,
+            r_npc: start.wrapping_add(4),
+            ..Default::default()
+        }
+    }
+
+    unsafe fn switch(&mut self, next: &Self) {
+        Self::switch_context(self, next);
+    }
+
+    unsafe fn catch(&mut self, body: extern "C" fn(*mut u8), data: *mut u8) -> usize {
+        Self::catch_call(self, body, data)
     }
+
+    fn set_return_value(&mut self, value: usize) {
+        self.ret = value as u64;
+    }
+}
+
+extern "C" {
+    /// Yeni görevin ilk komutu (aşağıdaki `global_asm!`).
+    fn task_start();
 }
 
+core::arch::global_asm!(
+    ".global task_start",
+    "task_start:",
+    "mov %g2, %o0",
+    "call {entry}",
+    " mov %g3, %o1", // Gecikme yuvası
+    "illtrap 0",
+    entry = sym task_entry,
+);
+
 
 // -----------------------------------------------------------------------------
 // Görev Başlatma (Task Entry)
@@ -157,6 +221,7 @@ impl TaskContext {
 /// * `func`: Görevin gerçek giriş noktası (başlatılacak fonksiyonun adresi, %o0'da olmalı).
 /// * `arg`: Göreve geçirilen u64 formatında argüman (%o1'de olmalı).
 extern "C" fn task_entry(func: u64, arg: u64) -> ! {
+    crate::context::task_started();
     serial_println!("[TASK] Yeni Görev Başlatılıyor. Argüman: {:#x}", arg);
 
     // Fonksiyon işaretçisini (u64) gerçek fonksiyona dönüştür.
@@ -168,15 +233,10 @@ extern "C" fn task_entry(func: u64, arg: u64) -> ! {
     // Gerçek görev fonksiyonunu çağır (arg, %o1'de beklenir)
     entry_func(arg);
 
-    // Görev tamamlandı. Normalde bu noktaya gelinmemelidir.
-    serial_println!("[TASK] Görev Tamamlandı! İşlemci durduruluyor.");
-    
-    // Görev tamamlandığında, çekirdek durdurulmalıdır.
-    loop {
-        unsafe {
-            io::idle(); // SPARC'ta bekleme talimatı
-        }
-    }
+    // Görev işlevi döndü: görev sonlandırılır ve işlemci sıradaki göreve
+    // (en azından boşta görevine) bırakılır
+    serial_println!("[TASK] Görev Tamamlandı.");
+    crate::sched::exit_current()
 }
 
 
@@ -192,4 +252,28 @@ pub fn initialize_tasking() {
     let entry_point_addr = task_entry as *const (); 
     
     serial_println!("[TASK] Task Entry Adresi: {:#x}", entry_point_addr as u64);
-}
\ No newline at end of file
+
+    // Zamanlayıcı bundan sonra görevler arasında gerçekten geçer (bkz. `context`)
+    crate::context::enable();
+}
+
+// -----------------------------------------------------------------------------
+// İŞLEMCİ BAŞINA ALAN TABANI
+// -----------------------------------------------------------------------------
+
+/// Bu işlemcinin işlemci başına alanının (`percpu`) tabanını yazar (`%g7`; ABI'de iş parçacığı işaretçisi olarak ayrılmıştır).
+///
+/// # Safety
+/// `base` bu işlemciye ayrılmış, `.percpu` şablonunun kopyası olan alanı
+/// göstermelidir.
+pub unsafe fn set_cpu_local_base(base: usize) {
+    asm!("mov {}, %g7", in(reg) base, options(nostack, preserves_flags, nomem));
+}
+
+/// Bu işlemcinin işlemci başına alanının tabanı (henüz yazılmadıysa 0).
+pub fn cpu_local_base() -> usize {
+    let base: usize;
+    // SAFETY: %g7 derleyici tarafından kullanılmaz.
+    unsafe { asm!("mov %g7, {}", out(reg) base, options(nostack, preserves_flags, nomem)) };
+    base
+}
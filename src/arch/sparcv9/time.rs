@@ -43,6 +43,12 @@ pub fn read_tick_register() -> Cycles {
     Cycles(cycles)
 }
 
+/// Serbest çalışan sayacın anlık değeri (bkz. `crate::clock`).
+#[inline(always)]
+pub fn read_cycles() -> Cycles {
+    read_tick_register()
+}
+
 /// Saat frekansını (Hz) döndürür.
 pub fn get_frequency() -> u64 {
     unsafe {
@@ -98,6 +104,23 @@ pub fn initialize_time_system() {
     // somut zaman değerleri hesaplanabilir.
 }
 
+// -----------------------------------------------------------------------------
+// Zamanlayıcı Karşılaştırıcısı (TICK_CMPR)
+// -----------------------------------------------------------------------------
+
+/// TICK_CMPR (ASR23) yazmacını `deadline` değerine kurar; TICK bu değere
+/// ulaşınca SOFTINT.TICK_INT (seviye 14) yükselir. Bekleyen kesme önce
+/// SOFTINT_CLR (ASR21) ile temizlenir. Bit 63 (INT_DIS) 0 kalır.
+pub unsafe fn set_timer_deadline(deadline: Cycles) {
+    asm!(
+        "wr {0}, %g0, %asr21",
+        "wr {1}, %g0, %asr23",
+        in(reg) 1u64,
+        in(reg) deadline.0 & !(1u64 << 63),
+        options(nostack, nomem)
+    );
+}
+
 // Platforma özel G/Ç fonksiyonları için bir yer tutucu (main.rs veya platformmod.rs'de tamamlanmalıdır)
 mod io {
     use core::arch::asm;
@@ -0,0 +1,199 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/arch/sparcv9/vectors.rs
+// SPARC V9 tuzak tablosu (TBA), `trap_vectors!` ile üretilir.
+//
+// Tablo 32 KiB hizalı, 1024 yuva x 32 bayttır: TL = 0 için 0x000..0x1FF ve
+// TL > 0 için 0x200..0x3FF tuzak tipleri (TT). Tuzak girişinde donanım
+// alternatif globallere geçer (PSTATE.AG); taslaklar yalnızca bunları kullanır.
+//
+// * Pencere taşma (spill, 0x080..0x0BF) ve dolum (fill, 0x0C0..0x0FF)
+//   tuzakları yerinde halledilir: pencere yığındaki kayıt alanına yazılır
+//   veya oradan okunur, ardından `saved`/`restored` ve `retry`. Ortak kod
+//   `save` ile yeni pencere açtığından TL > 0 karşılıkları da bağlanır.
+// * Temiz pencere (clean_window, 0x024..0x027) yeni pencereyi sıfırlar.
+// * Diğer tüm tuzaklar ortak koda gider: yeni pencere açılır, normal
+//   globaller ve kesilen pencerenin %o yazmaçları (artık %i) ile TSTATE/TPC/
+//   TNPC `ExceptionContext`'e yazılır ve `handler(tt, &mut ExceptionContext)`
+//   çağrılır. Çekirdek kipine dönülüyorsa `sched_preempt_from_trap` TL = 0'da
+//   çağrılır. Dönüşte bağlam geri yazılır ve `retry` yapılır.
+
+/// 64 bit yığın sapması (2047) + pencere kayıt alanı ve argümanlar (176).
+/// `ExceptionContext` ofsetleri `exception.rs`'de `asm_layout!` ile denetlenir.
+macro_rules! sparcv9_ctx {
+    () => {
+        "2223"
+    };
+}
+
+macro_rules! sparcv9_entry {
+    ($name:literal, $handler:literal) => {
+        concat!(
+            "sethi %hi(", $handler, "), %g1\n",
+            "or %g1, %lo(", $handler, "), %g1\n",
+            "ba,pt %xcc, ", $name, "_common\n",
+            " mov ", $name, "_slot, %g2\n",
+        )
+    };
+}
+
+macro_rules! sparcv9_spill {
+    ($name:literal, $_handler:literal) => {
+        concat!("ba,pt %xcc, ", $name, "_spill\n", " nop\n")
+    };
+}
+
+macro_rules! sparcv9_fill {
+    ($name:literal, $_handler:literal) => {
+        concat!("ba,pt %xcc, ", $name, "_fill\n", " nop\n")
+    };
+}
+
+macro_rules! sparcv9_clean {
+    ($name:literal, $_handler:literal) => {
+        concat!("ba,pt %xcc, ", $name, "_clean\n", " nop\n")
+    };
+}
+
+macro_rules! sparcv9_common {
+    ($name:literal) => {
+        concat!(
+            $name, "_spill:\n",
+            ".set ", $name, "_off, 0\n",
+            ".irp r, l0,l1,l2,l3,l4,l5,l6,l7,i0,i1,i2,i3,i4,i5,i6,i7\n",
+            "stx %\\r, [%sp + 2047 + ", $name, "_off]\n",
+            ".set ", $name, "_off, ", $name, "_off + 8\n",
+            ".endr\n",
+            "saved\n",
+            "retry\n",
+
+            $name, "_fill:\n",
+            ".set ", $name, "_off, 0\n",
+            ".irp r, l0,l1,l2,l3,l4,l5,l6,l7,i0,i1,i2,i3,i4,i5,i6,i7\n",
+            "ldx [%sp + 2047 + ", $name, "_off], %\\r\n",
+            ".set ", $name, "_off, ", $name, "_off + 8\n",
+            ".endr\n",
+            "restored\n",
+            "retry\n",
+
+            $name, "_clean:\n",
+            ".irp r, l0,l1,l2,l3,l4,l5,l6,l7,o0,o1,o2,o3,o4,o5,o6,o7\n",
+            "clr %\\r\n",
+            ".endr\n",
+            "rdpr %cleanwin, %g1\n",
+            "inc %g1\n",
+            "wrpr %g1, %cleanwin\n",
+            "retry\n",
+
+            $name, "_common:\n",
+            "save %sp, -336, %sp\n",
+            "mov %g1, %l0\n",
+            "mov %g2, %l1\n",
+            // PSTATE.AG'yi kapat: kesilen kodun globallerine erişilir
+            "rdpr %pstate, %l2\n",
+            "wrpr %l2, 1, %pstate\n",
+            "stx %g1, [%sp + ", sparcv9_ctx!(), " + 0]\n",
+            "stx %g2, [%sp + ", sparcv9_ctx!(), " + 8]\n",
+            "stx %g3, [%sp + ", sparcv9_ctx!(), " + 16]\n",
+            "stx %g4, [%sp + ", sparcv9_ctx!(), " + 24]\n",
+            "stx %g5, [%sp + ", sparcv9_ctx!(), " + 32]\n",
+            "stx %g6, [%sp + ", sparcv9_ctx!(), " + 40]\n",
+            "stx %g7, [%sp + ", sparcv9_ctx!(), " + 48]\n",
+            "stx %i0, [%sp + ", sparcv9_ctx!(), " + 56]\n",
+            "stx %i1, [%sp + ", sparcv9_ctx!(), " + 64]\n",
+            "stx %i2, [%sp + ", sparcv9_ctx!(), " + 72]\n",
+            "stx %i3, [%sp + ", sparcv9_ctx!(), " + 80]\n",
+            "stx %i4, [%sp + ", sparcv9_ctx!(), " + 88]\n",
+            "stx %i5, [%sp + ", sparcv9_ctx!(), " + 96]\n",
+            "stx %i6, [%sp + ", sparcv9_ctx!(), " + 104]\n",
+            "stx %i7, [%sp + ", sparcv9_ctx!(), " + 112]\n",
+            "rdpr %tstate, %l3\n",
+            "stx %l3, [%sp + ", sparcv9_ctx!(), " + 120]\n",
+            "rdpr %tba, %l3\n",
+            "stx %l3, [%sp + ", sparcv9_ctx!(), " + 128]\n",
+            "rdpr %tpc, %l3\n",
+            "stx %l3, [%sp + ", sparcv9_ctx!(), " + 136]\n",
+            "rdpr %tnpc, %l3\n",
+            "stx %l3, [%sp + ", sparcv9_ctx!(), " + 144]\n",
+            "rdpr %canrestore, %l3\n",
+            "stx %l3, [%sp + ", sparcv9_ctx!(), " + 152]\n",
+            // handler(tt, &mut ExceptionContext); TL > 0 yuvaları aynı TT'ye iner
+            "and %l1, 0x1ff, %o0\n",
+            "jmpl %l0, %o7\n",
+            " add %sp, ", sparcv9_ctx!(), ", %o1\n",
+            // Çekirdek kipine (TSTATE.PRIV) ve TL = 1'den dönülüyorsa kesinti
+            // noktası: TL 0'a indirilir, kesmeler PIL 15 ile maskeli kalır ama
+            // PSTATE.IE açılır (`irqoff` PIL'i kullanır). Görev değişip geri
+            // dönülmüşse %g7 (işlemci başı taban) yeni işlemcininkidir.
+            "ldx [%sp + ", sparcv9_ctx!(), " + 120], %l3\n",
+            "andcc %l3, 0x400, %g0\n",
+            "be,pn %xcc, 1f\n",
+            " rdpr %tl, %l3\n",
+            "cmp %l3, 1\n",
+            "bne,pn %xcc, 1f\n",
+            " rdpr %pil, %l4\n",
+            "wrpr %g0, 15, %pil\n",
+            "wrpr %g0, 0, %tl\n",
+            "rdpr %pstate, %l5\n",
+            "wrpr %l5, 2, %pstate\n",
+            "call sched_preempt_from_trap\n",
+            " nop\n",
+            "wrpr %l5, 0, %pstate\n",
+            "wrpr %g0, 1, %tl\n",
+            "wrpr %l4, 0, %pil\n",
+            "stx %g7, [%sp + ", sparcv9_ctx!(), " + 48]\n",
+            "1:\n",
+            // İşleyici TSTATE/TPC/TNPC'yi değiştirebilir (syscall, düzeltme)
+            "ldx [%sp + ", sparcv9_ctx!(), " + 120], %l3\n",
+            "wrpr %l3, %tstate\n",
+            "ldx [%sp + ", sparcv9_ctx!(), " + 136], %l3\n",
+            "wrpr %l3, %tpc\n",
+            "ldx [%sp + ", sparcv9_ctx!(), " + 144], %l3\n",
+            "wrpr %l3, %tnpc\n",
+            "ldx [%sp + ", sparcv9_ctx!(), " + 0], %g1\n",
+            "ldx [%sp + ", sparcv9_ctx!(), " + 8], %g2\n",
+            "ldx [%sp + ", sparcv9_ctx!(), " + 16], %g3\n",
+            "ldx [%sp + ", sparcv9_ctx!(), " + 24], %g4\n",
+            "ldx [%sp + ", sparcv9_ctx!(), " + 32], %g5\n",
+            "ldx [%sp + ", sparcv9_ctx!(), " + 40], %g6\n",
+            "ldx [%sp + ", sparcv9_ctx!(), " + 48], %g7\n",
+            "ldx [%sp + ", sparcv9_ctx!(), " + 56], %i0\n",
+            "ldx [%sp + ", sparcv9_ctx!(), " + 64], %i1\n",
+            "ldx [%sp + ", sparcv9_ctx!(), " + 72], %i2\n",
+            "ldx [%sp + ", sparcv9_ctx!(), " + 80], %i3\n",
+            "ldx [%sp + ", sparcv9_ctx!(), " + 88], %i4\n",
+            "ldx [%sp + ", sparcv9_ctx!(), " + 96], %i5\n",
+            "ldx [%sp + ", sparcv9_ctx!(), " + 104], %i6\n",
+            "ldx [%sp + ", sparcv9_ctx!(), " + 112], %i7\n",
+            "wrpr %l2, 0, %pstate\n",
+            "restore\n",
+            "retry\n",
+        )
+    };
+}
+
+crate::trap_vectors! {
+    name: "sparcv9_vectors",
+    align: 32768,
+    slot: 32,
+    slots: 1024,
+    common: sparcv9_common,
+    vectors: [
+        0x000 ..= 0x023 => sparcv9_entry("generic_trap_handler"),
+        0x024 ..= 0x027 => sparcv9_clean(""),
+        0x028 ..= 0x07F => sparcv9_entry("generic_trap_handler"),
+        0x080 ..= 0x0BF => sparcv9_spill(""),
+        0x0C0 ..= 0x0FF => sparcv9_fill(""),
+        0x100 ..= 0x223 => sparcv9_entry("generic_trap_handler"),
+        0x224 ..= 0x227 => sparcv9_clean(""),
+        0x228 ..= 0x27F => sparcv9_entry("generic_trap_handler"),
+        0x280 ..= 0x2BF => sparcv9_spill(""),
+        0x2C0 ..= 0x2FF => sparcv9_fill(""),
+        0x300 ..= 0x3FF => sparcv9_entry("generic_trap_handler"),
+    ]
+}
+
+extern "C" {
+    /// Üretilen tablonun başı (TBA değeri).
+    pub fn sparcv9_vectors();
+}
@@ -1 +1,195 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
 
+// src/arch/srcarch.rs
+// `crate::arch` modülünün kökü: hedef bağlantısı ve mimari cephesi.
+//
+// Hangi `target_arch` hangi port dizinine karşılık gelir ve hedef tanımı
+// portun varsaydığı düzenle uyuşuyor mu?
+//
+//   target_arch   port           hedef
+//   x86_64        amd64          x86_64-unknown-none (yerleşik)
+//   aarch64       armv9          aarch64-unknown-none (yerleşik)
+//   riscv64       rv64i          riscv64gc-unknown-none-elf (yerleşik)
+//   loongarch64   loongarch64    loongarch64-unknown-none-softfloat (yerleşik)
+//   powerpc64     powerpc64      targets/powerpc64-sahne-none.json
+//   sparc64       sparcv9        targets/sparc64-sahne-none.json
+//   mips64        mips64         targets/mips64-sahne-none.json
+//   or1k          openrisc64     targets/or1k-sahne-none.json (ağaç dışı LLVM)
+//
+// Özel hedef dosyaları `xtask/src/targets.rs` tablosundan üretilir. Yanlış
+// hedefle (örn. küçük sonlu bir MIPS üçlüsüyle) derleme, çalışırken bozuk
+// aygıt erişimi yerine burada derleme hatası verir.
+//
+// Cephe: etkin portun modülleri sabit adlarla yeniden dışa verilir
+// (`crate::arch::mmu`, `crate::arch::task`, ...). Mimariden bağımsız kod
+// `crate::arch::mmu::map_page` yazar, port modüllerini adıyla çağırmaz.
+// Portta karşılığı olmayan bir işlev yine tanımlanır; etkisiz kalır veya
+// "desteklenmiyor" döndürür (örn. `map_user_page`, `install_irq_stack`,
+// `init_aux_serial`). Genel kodda `cfg(target_arch)` yalnızca sabitler (ELF
+// makine türü, sürüm dizgesi), satır içi engel komutları ve aşağıda
+// sözleşme dışı bırakılan adlar için kalır. Yeni port eklemek, tablodaki
+// satır ile "CEPHE SÖZLEŞMESİ" bölümündeki adları sağlamak demektir.
+//
+// Kabuk komutu: yok; procfs: yok
+
+// -----------------------------------------------------------------------------
+// DESTEKLENEN HEDEFLER
+// -----------------------------------------------------------------------------
+
+#[cfg(not(any(
+    target_arch = "x86_64",
+    target_arch = "aarch64",
+    target_arch = "riscv64",
+    target_arch = "loongarch64",
+    target_arch = "powerpc64",
+    target_arch = "sparc64",
+    target_arch = "mips64",
+    target_arch = "or1k",
+)))]
+compile_error!("desteklenmeyen mimari: hedefler için `cargo xtask list`");
+
+/// Etkin portun dizin adı (`src/arch/<ad>`); `xtask` ve tanılama iletileri
+/// aynı adı kullanır.
+#[cfg(target_arch = "x86_64")]
+pub const PORT: &str = "amd64";
+#[cfg(target_arch = "aarch64")]
+pub const PORT: &str = "armv9";
+#[cfg(target_arch = "riscv64")]
+pub const PORT: &str = "rv64i";
+#[cfg(target_arch = "loongarch64")]
+pub const PORT: &str = "loongarch64";
+#[cfg(target_arch = "powerpc64")]
+pub const PORT: &str = "powerpc64";
+#[cfg(target_arch = "sparc64")]
+pub const PORT: &str = "sparcv9";
+#[cfg(target_arch = "mips64")]
+pub const PORT: &str = "mips64";
+#[cfg(target_arch = "or1k")]
+pub const PORT: &str = "openrisc64";
+
+// -----------------------------------------------------------------------------
+// HEDEF TANIMI DENETİMİ
+// -----------------------------------------------------------------------------
+
+// Tüm portlar 64 bittir: sayfa tabloları, bağlam çerçeveleri ve montaj
+// ofsetleri 8 baytlık sözcük varsayar
+#[cfg(not(target_pointer_width = "64"))]
+compile_error!("hedef tanımı 64 bit değil (`target-pointer-width`)");
+
+// Büyük sonlu portlar: MMIO ve açılış yapıları (OpenFirmware, FDT) buna göre yazıldı
+#[cfg(all(
+    any(target_arch = "powerpc64", target_arch = "sparc64", target_arch = "mips64", target_arch = "or1k"),
+    not(target_endian = "big")
+))]
+compile_error!("bu port büyük sonlu hedef bekler (`target-endian: big`)");
+
+#[cfg(all(
+    any(target_arch = "x86_64", target_arch = "aarch64", target_arch = "riscv64", target_arch = "loongarch64"),
+    not(target_endian = "little")
+))]
+compile_error!("bu port küçük sonlu hedef bekler");
+
+// Bağlam geçişi FP yazmaçlarını kaydetmez (bkz. `xtask/src/targets.rs`)
+#[cfg(all(target_arch = "powerpc64", target_feature = "altivec"))]
+compile_error!("powerpc64 hedefi AltiVec'siz olmalıdır (`-altivec`)");
+
+// -----------------------------------------------------------------------------
+// MİMARİ CEPHESİ
+// -----------------------------------------------------------------------------
+
+#[cfg(target_arch = "x86_64")]
+use crate::arch::amd64 as port;
+#[cfg(target_arch = "aarch64")]
+use crate::arch::armv9 as port;
+#[cfg(target_arch = "riscv64")]
+use crate::arch::rv64i as port;
+#[cfg(target_arch = "loongarch64")]
+use crate::arch::loongarch64 as port;
+#[cfg(target_arch = "powerpc64")]
+use crate::arch::powerpc64 as port;
+#[cfg(target_arch = "sparc64")]
+use crate::arch::sparcv9 as port;
+#[cfg(target_arch = "mips64")]
+use crate::arch::mips64 as port;
+#[cfg(target_arch = "or1k")]
+use crate::arch::openrisc64 as port;
+
+pub use port::{
+    console, cpu, debug, exception, fixup, hypervisor, interrupt, io, mmu, panic, platformmod, power, security, shutdown,
+    task, time,
+};
+
+// -----------------------------------------------------------------------------
+// CEPHE SÖZLEŞMESİ
+// -----------------------------------------------------------------------------
+
+// Her portun aynı imzayla sağlaması gereken adlar; eksik ya da farklı imzalı
+// bir ad, kullanıldığı genel dosyada değil burada hata verir.
+//
+// Sözleşme dışında kalanlar bilerek porta özgüdür: kesme denetleyicisi
+// (`interrupt`'ın maske, hat ve aygıt ağacı işlevleri dışındaki kısmı: 8259,
+// GIC, PLIC, ...), `decode_cause`'un girdileri ve `mmu::map_page` ile
+// `mmu::map_large_page` (yalnızca sayfa tablolu portlarda; MIPS ve OpenRISC
+// yazılım TLB'si kullanır). Bunları kullanan kod `cfg` ile daraltılır.
+const _: () = {
+    // Bellek yönetimi
+    let _: usize = mmu::PAGE_SIZE;
+    let _: fn() = mmu::init_mmu;
+    let _: fn(&mut dyn FnMut(crate::memory::vmaudit::Mapping)) = mmu::walk_mappings;
+    let _: unsafe fn(usize, usize, bool, bool) -> Result<(), crate::platformgeneric::KernelError> = mmu::map_user_page;
+    let _: unsafe fn(usize) = mmu::unmap_user_page;
+    let _: fn(usize, usize) -> Result<usize, crate::platformgeneric::KernelError> = mmu::map_device;
+    // Tuzaklar ve yerel kesme maskesi (bkz. `irqoff.rs`)
+    let _: fn() = exception::init_exceptions;
+    let _: bool = exception::SWITCHES_IRQ_STACK;
+    let _: unsafe fn(usize) -> Result<(), crate::platformgeneric::KernelError> = exception::install_irq_stack;
+    let _: fn() -> usize = interrupt::local_irq_save;
+    let _: fn(usize) = interrupt::local_irq_restore;
+    let _: fn(usize) -> bool = interrupt::irqs_were_enabled;
+    let _: fn() = interrupt::local_irq_enable;
+    let _: fn(u32, bool) -> bool = interrupt::set_irq_masked;
+    let _: fn(u8) -> Option<u32> = interrupt::route_pci_intx;
+    let _: fn(&crate::fdt::Node) -> Option<u32> = interrupt::decode_fdt_irq;
+    // Kullanıcı belleği kopyası (bkz. `uaccess.rs`)
+    let _: fn(&mut [u8], usize) -> Result<(), crate::platformgeneric::KernelError> = security::copy_from_user;
+    let _: fn(usize, &[u8]) -> Result<(), crate::platformgeneric::KernelError> = security::copy_to_user;
+    // Görevler ve işlemci başına veri
+    let _: fn() = task::initialize_tasking;
+    let _: unsafe fn(usize) = task::set_cpu_local_base;
+    let _: fn() -> usize = task::cpu_local_base;
+    // Güç ve durdurma
+    let _: fn() = power::system_idle;
+    let _: fn() -> ! = shutdown::system_shutdown;
+    let _: fn() -> ! = shutdown::system_reboot;
+    let _: fn(bool, u32, bool) -> ! = shutdown::system_reset;
+    let _: fn() -> ! = panic::halt_loop;
+    // Zaman sayacı (bkz. `clock.rs`)
+    let _: fn() -> time::Cycles = time::read_cycles;
+    let _: fn() -> u64 = time::get_frequency;
+    // Konsol ve önyükleme devri (bkz. `panic.rs`, `console.rs`, `boot.rs`)
+    let _: fn(u8) = console::panic_write_byte;
+    let _: fn() = console::panic_quiesce;
+    let _: fn() -> Option<crate::console::SinkWrite> = console::init_aux_serial;
+    let _: fn(&crate::boot::BootRegs, &mut crate::boot::BootInfo) -> bool = platformmod::parse_boot_handoff;
+    // PCI yapılandırma alanı (bkz. `drivers/pci.rs`)
+    let _: bool = io::PCI_CONFIG_PORTS;
+    let _: fn(u32) -> u32 = io::pci_config_read32;
+    let _: fn(u32, u32) = io::pci_config_write32;
+    // İşlemci, hata ayıklama, hata düzeltme ve sanallaştırma (bkz. `cpu.rs`,
+    // `debug.rs`, `fixup.rs`, `hypervisor.rs`)
+    let _: fn() -> crate::cpu::CpuFeatures = cpu::detect;
+    let _: unsafe fn(usize) -> Option<u8> = fixup::probe_read_u8;
+    let _: unsafe fn(usize, u8) -> bool = fixup::probe_write_u8;
+    let _: unsafe fn(usize) -> Option<u32> = fixup::probe_read_u32;
+    let _: fn() -> crate::hypervisor::HypervisorKind = hypervisor::detect;
+    let _: fn(u8) = hypervisor::pv_console_write_byte;
+    let _: fn() -> u64 = hypervisor::pv_clock_ns;
+    let _: u32 = debug::BREAKPOINT_INSN;
+    let _: usize = debug::BREAKPOINT_INSN_LEN;
+    let _: usize = debug::MAX_WATCHPOINTS;
+    let _: unsafe fn(usize, usize) = debug::sync_icache;
+};
+
+/// Görev bağlamı `ArchContext`'i uygulamalıdır (bkz. `context.rs`).
+const fn assert_context<T: crate::context::ArchContext>() {}
+const _: () = assert_context::<task::TaskContext>();
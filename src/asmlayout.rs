@@ -0,0 +1,50 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/asmlayout.rs
+// Montaj kodu ile Rust yapıları arasındaki düzen sözleşmesinin derleme
+// zamanı denetimi.
+//
+// Bağlam anahtarlama ve tuzak girişleri yapı alanlarına sabit ofsetlerle
+// erişir. Yapıya alan eklenir ya da sıra değişirse montaj kodu sessizce
+// yanlış alana yazar ve hata ancak çalışırken, çoğu zaman başka bir görevin
+// yazmaçları bozulduğunda görünür. İki yol kullanılır:
+//
+// * Satır içi `asm!` (`arch/*/task.rs`): ofsetler `offset_of!` ile üretilip
+//   `const` işlenen olarak verilir; elle yazılmış sayı kalmaz.
+// * Ayrı montaj (`.S`) ve `trap_vectors!` gövdeleri: sayılar dizge içinde
+//   sabittir (`concat!` işlenen kabul etmez). Yapının yanında
+//   `asm_layout!` her alanın montajdaki ofsetini ve çerçeve boyunu
+//   denetler; uyuşmazlık derleme hatasıdır ve iletide alan adı geçer.
+//   Montaj tarafındaki yorum denetimin yerini gösterir.
+//
+// Kabuk komutu: yok; procfs: yok
+
+/// Yapı alanlarının montaj kodundaki ofsetlerle aynı olduğunu derleme
+/// zamanında doğrular.
+///
+/// ```ignore
+/// crate::asm_layout!(ExceptionContext {
+///     gpr: 0,
+///     elr_el1: 248,
+///     spsr_el1: 256,
+/// }, size <= 272);
+/// ```
+#[macro_export]
+macro_rules! asm_layout {
+    ($ty:ty { $( $field:ident : $offset:expr ),* $(,)? } $(, size $op:tt $size:expr)?) => {
+        const _: () = {
+            $(
+                assert!(
+                    core::mem::offset_of!($ty, $field) == $offset,
+                    concat!(stringify!($ty), "::", stringify!($field), " montaj kodundaki ofsetle (", stringify!($offset), ") uyuşmuyor")
+                );
+            )*
+            $(
+                assert!(
+                    core::mem::size_of::<$ty>() $op $size,
+                    concat!(stringify!($ty), " boyutu montaj çerçevesine uymuyor (", stringify!($op), " ", stringify!($size), ")")
+                );
+            )?
+        };
+    };
+}
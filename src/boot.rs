@@ -0,0 +1,371 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/boot.rs
+// Erken açılış sözleşmesi ve mimariden bağımsız önyükleme bilgisi.
+//
+// Her mimarinin `src/arch/<mimari>/boot.S` giriş kodu, yükleyicinin
+// davranışına dair hiçbir şey varsaymadan Rust'a şu durumda geçer:
+//
+// 1. Önyükleyicinin bıraktığı yazmaçlar (sihirli sayı, DTB / Multiboot2
+//    işaretçisi, açılış işlemcisinin kimliği, yükleyiciye özgü ek değer)
+//    ilk talimatlarda kalıcı yazmaçlara alınır.
+// 2. `.bss` (`__bss_start`..`__bss_end`) ve önyükleme yığını
+//    (`__boot_stack_bottom`..`__boot_stack_top`) sıfırlanır. Sıfır başlangıçlı
+//    statikler bu adımdan önce okunmaz. `.noinit` sıfırlanmaz (`reboot.rs`).
+// 3. Yığın işaretçisi `__boot_stack_top`'a (ABI'nin istediği hizada / bias
+//    ile) kurulur; çerçeve işaretçisi ve dönüş adresi sıfırlanır, böylece
+//    geri izleme ilk çerçevede biter.
+// 4. Saklanan yazmaçlar `BOOT_REGS`'e yazılır ve `kernel_main(&BOOT_REGS)`
+//    çağrılır. `kernel_main` dönmez; dönerse giriş kodu işlemciyi durdurur.
+//
+// Mimariye göre yazmaçlar:
+//
+//   mimari       magic               firmware            boot_cpu          aux
+//   amd64        eax (0x36D76289)    ebx (MBI)           CPUID.1 APIC id   -
+//   armv9        -                   x0 (DTB)            MPIDR_EL1 afinite -
+//   rv64i        -                   a1 (DTB)            a0 (hart id)      -
+//   powerpc64    r6 (ePAPR)          r3 (DTB)            PIR               r5 (OF girişi)
+//   sparcv9      -                   -                   -                 %o4 (OF girişi)
+//   loongarch64  -                   a0 (DTB)            CPUID CSR         a1
+//   mips64       a0 (UHI: -2)        a1 / a0 (DTB)       EBase CPUNum      -
+//   openrisc64   -                   r3 (DTB)            COREID SPR        -
+//
+// Alan ofsetleri giriş kodlarında sabit olarak kullanılır (`OFFSET_*`);
+// düzen değişirse sekiz `boot.S` birlikte güncellenmelidir.
+//
+// `kernel_main` ilk iş olarak `init(regs)` çağırır: ham yazmaçlar ürün
+// yazılımına göre çözülür ve `BootInfo`'ya (bellek haritası, DTB / ACPI
+// işaretçisi, initramfs alanı, komut satırı, açılış işlemcisi) dönüştürülür.
+// Multiboot2 bilgi yapısını `arch/amd64/multiboot.rs`, aygıt ağacını bu
+// dosya çözer; aygıt ağacı vermeyen OpenFirmware makinelerinde (SPARC,
+// PowerPC SLOF/OpenBIOS) `of.rs` ağacı istemci arayüzünden düzleştirir. Genel alt sistemler (physmap, initrd, açılış argümanları)
+// ürün yazılımına özgü yapıları kendileri okumaz; yalnızca `info()`'yu
+// kullanır. Bellek haritası ve komut satırı kopyalanır, yükleyicinin
+// bellek alanı açılıştan sonra geri alınabilir. Bilgi dolduktan sonra konsol
+// UART'ı seçilir (`earlycon.rs`). Hemen ardından, yine `kernel_main`'in
+// gövdesinde, açılışın yığın kanaryası kurulur (`canary::boot_init`).
+//
+// Kabuk komutu: `bootinfo`; procfs: `/proc/cmdline`
+
+use core::cell::UnsafeCell;
+use core::fmt::{self, Write};
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// YAZMAÇ KAYDI
+// -----------------------------------------------------------------------------
+
+/// Multiboot2 yükleyicisinin `eax`'te bıraktığı sihirli sayı.
+pub const MULTIBOOT2_MAGIC: u64 = 0x36D7_6289;
+/// ePAPR uyumlu PowerPC yükleyicisinin `r6`'da bıraktığı sihirli sayı.
+pub const EPAPR_MAGIC: u64 = 0x6550_4150;
+
+/// Giriş kodunun önyükleyiciden devraldığı ham yazmaç değerleri.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BootRegs {
+    /// Önyükleyici sihirli sayısı (yoksa 0).
+    pub magic: u64,
+    /// Ürün yazılımı tablosu: amd64'te Multiboot2 bilgi yapısı, diğerlerinde
+    /// düzleştirilmiş aygıt ağacı (yoksa 0).
+    pub firmware: u64,
+    /// Açılış işlemcisinin donanım kimliği (APIC id, MPIDR, hart id, PIR...).
+    pub boot_cpu: u64,
+    /// Yükleyiciye özgü ek değer (PowerPC/SPARC: OpenFirmware istemci
+    /// arayüzü giriş noktası; yoksa 0).
+    pub aux: u64,
+}
+
+pub const OFFSET_MAGIC: usize = 0;
+pub const OFFSET_FIRMWARE: usize = 8;
+pub const OFFSET_BOOT_CPU: usize = 16;
+pub const OFFSET_AUX: usize = 24;
+
+const _: () = {
+    assert!(core::mem::offset_of!(BootRegs, magic) == OFFSET_MAGIC);
+    assert!(core::mem::offset_of!(BootRegs, firmware) == OFFSET_FIRMWARE);
+    assert!(core::mem::offset_of!(BootRegs, boot_cpu) == OFFSET_BOOT_CPU);
+    assert!(core::mem::offset_of!(BootRegs, aux) == OFFSET_AUX);
+};
+
+impl BootRegs {
+    const fn empty() -> Self {
+        BootRegs { magic: 0, firmware: 0, boot_cpu: 0, aux: 0 }
+    }
+}
+
+/// Giriş kodunun doldurduğu kayıt. `.bss` sıfırlandıktan sonra ve Rust'a
+/// geçmeden önce bir kez yazılır; sonrasında yalnızca okunur.
+#[no_mangle]
+pub static mut BOOT_REGS: BootRegs = BootRegs::empty();
+
+/// Önyükleyiciden devralınan yazmaçlar.
+pub fn regs() -> BootRegs {
+    // SAFETY: Kayıt Rust'a geçilmeden önce yazıldı ve bir daha değişmez.
+    unsafe { core::ptr::addr_of!(BOOT_REGS).read() }
+}
+
+// -----------------------------------------------------------------------------
+// ÖNYÜKLEME BİLGİSİ
+// -----------------------------------------------------------------------------
+
+/// Bellek haritasında tutulan en fazla bölge.
+pub const MAX_MEMORY_REGIONS: usize = 32;
+/// Komut satırının en fazla uzunluğu (bayt); fazlası kesilir.
+pub const CMDLINE_MAX: usize = 512;
+
+/// Önyükleme bilgisinin geldiği ürün yazılımı arayüzü.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Firmware {
+    /// Tanınan bir yapı yok; yalnızca yazmaçlar kullanılabilir.
+    None,
+    Multiboot2,
+    /// Düzleştirilmiş aygıt ağacı (U-Boot, OpenSBI, QEMU, ePAPR).
+    Fdt,
+    /// IEEE 1275 istemci arayüzü (SPARC OBP, PowerPC SLOF/OpenBIOS).
+    OpenFirmware,
+}
+
+impl Firmware {
+    pub fn name(self) -> &'static str {
+        match self {
+            Firmware::None => "yok",
+            Firmware::Multiboot2 => "multiboot2",
+            Firmware::Fdt => "fdt",
+            Firmware::OpenFirmware => "openfirmware",
+        }
+    }
+}
+
+/// Bellek bölgesinin türü (Multiboot2 / E820 sınıflandırması).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryKind {
+    Usable,
+    Reserved,
+    AcpiReclaimable,
+    AcpiNvs,
+    Bad,
+}
+
+impl MemoryKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            MemoryKind::Usable => "ram",
+            MemoryKind::Reserved => "reserved",
+            MemoryKind::AcpiReclaimable => "acpi",
+            MemoryKind::AcpiNvs => "acpi-nvs",
+            MemoryKind::Bad => "bad",
+        }
+    }
+}
+
+/// Fiziksel bellek haritasındaki bir bölge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryRegion {
+    pub base: u64,
+    pub len: u64,
+    pub kind: MemoryKind,
+}
+
+/// Mimariden bağımsız önyükleme bilgisi.
+pub struct BootInfo {
+    /// Bilginin çözüldüğü arayüz.
+    pub firmware: Firmware,
+    /// Açılış işlemcisinin donanım kimliği (bkz. yazmaç tablosu).
+    pub boot_cpu: u64,
+    /// Düzleştirilmiş aygıt ağacının fiziksel adresi (doğrulanmış).
+    pub dtb: Option<usize>,
+    /// ACPI RSDP yapısının adresi (amd64).
+    pub acpi_rsdp: Option<usize>,
+    /// initramfs görüntüsü: (fiziksel adres, uzunluk).
+    pub initramfs: Option<(usize, usize)>,
+    /// OpenFirmware istemci arayüzü giriş noktası.
+    pub of_entry: Option<usize>,
+    memory: [MemoryRegion; MAX_MEMORY_REGIONS],
+    memory_len: usize,
+    cmdline: [u8; CMDLINE_MAX],
+    cmdline_len: usize,
+}
+
+impl BootInfo {
+    const fn empty() -> Self {
+        BootInfo {
+            firmware: Firmware::None,
+            boot_cpu: 0,
+            dtb: None,
+            acpi_rsdp: None,
+            initramfs: None,
+            of_entry: None,
+            memory: [MemoryRegion { base: 0, len: 0, kind: MemoryKind::Reserved }; MAX_MEMORY_REGIONS],
+            memory_len: 0,
+            cmdline: [0; CMDLINE_MAX],
+            cmdline_len: 0,
+        }
+    }
+
+    /// Bellek haritası (yükleyicinin bildirdiği sırayla).
+    pub fn memory_map(&self) -> &[MemoryRegion] {
+        &self.memory[..self.memory_len]
+    }
+
+    /// Çekirdeğin kullanabileceği RAM bölgeleri.
+    pub fn usable_memory(&self) -> impl Iterator<Item = &MemoryRegion> {
+        self.memory_map().iter().filter(|region| region.kind == MemoryKind::Usable)
+    }
+
+    /// Haritaya bölge ekler. Boş bölgeler atlanır.
+    ///
+    /// # Hatalar
+    /// * `OutOfMemoryStatic`: Harita dolu.
+    pub fn add_memory(&mut self, base: u64, len: u64, kind: MemoryKind) -> Result<(), KernelError> {
+        if len == 0 {
+            return Ok(());
+        }
+        let slot = self.memory.get_mut(self.memory_len).ok_or(KernelError::OutOfMemoryStatic)?;
+        *slot = MemoryRegion { base, len, kind };
+        self.memory_len += 1;
+        Ok(())
+    }
+
+    /// Açılış komut satırı (yoksa boş).
+    pub fn cmdline(&self) -> &str {
+        // `set_cmdline` yalnızca geçerli UTF-8 saklar
+        core::str::from_utf8(&self.cmdline[..self.cmdline_len]).unwrap_or("")
+    }
+
+    /// Komut satırını kopyalar: ilk NUL'da biter, `CMDLINE_MAX`'ta ve son
+    /// geçerli UTF-8 karakterinde kesilir.
+    pub fn set_cmdline(&mut self, bytes: &[u8]) {
+        let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len()).min(CMDLINE_MAX);
+        let len = match core::str::from_utf8(&bytes[..end]) {
+            Ok(_) => end,
+            Err(err) => err.valid_up_to(),
+        };
+        self.cmdline[..len].copy_from_slice(&bytes[..len]);
+        self.cmdline_len = len;
+    }
+
+    /// Komut satırındaki argümanlar.
+    pub fn args(&self) -> core::str::SplitWhitespace<'_> {
+        self.cmdline().split_whitespace()
+    }
+
+    /// Komut satırında `name` argümanı (değersiz) var mı?
+    pub fn has_arg(&self, name: &str) -> bool {
+        self.args().any(|arg| arg == name)
+    }
+}
+
+struct InfoCell(UnsafeCell<BootInfo>);
+
+// Kayıt yalnızca `init` içinde, diğer işlemciler başlamadan yazılır.
+unsafe impl Sync for InfoCell {}
+
+static INFO: InfoCell = InfoCell(UnsafeCell::new(BootInfo::empty()));
+
+/// Önyükleme bilgisi (`init`'ten önce boş).
+pub fn info() -> &'static BootInfo {
+    // SAFETY: `init` tamamlandıktan sonra kayıt yalnızca okunur.
+    unsafe { &*INFO.0.get() }
+}
+
+/// Ham yazmaçları çözer ve önyükleme bilgisini doldurur. `kernel_main`
+/// tarafından, tek işlemcide ve her şeyden önce bir kez çağrılır.
+pub fn init(regs: &BootRegs) -> &'static BootInfo {
+    // SAFETY: Henüz okuyucu yok (bkz. `InfoCell`).
+    let info = unsafe { &mut *INFO.0.get() };
+    info.boot_cpu = regs.boot_cpu;
+    populate(info, regs);
+    crate::earlycon::select(info);
+    info
+}
+
+fn populate(info: &mut BootInfo, regs: &BootRegs) {
+    // amd64: Multiboot2 bilgi yapısı
+    if crate::arch::platformmod::parse_boot_handoff(regs, info) {
+        return;
+    }
+    let dtb = regs.firmware as usize;
+    if dtb != 0 && crate::fdt::init(dtb).is_ok() {
+        info.firmware = Firmware::Fdt;
+        info.dtb = Some(dtb);
+        from_fdt(info);
+    } else if regs.aux != 0 {
+        // Aygıt ağacı verilmedi; istemci arayüzünden okunur
+        info.firmware = Firmware::OpenFirmware;
+        info.of_entry = Some(regs.aux as usize);
+        crate::of::populate(info, regs.aux as usize);
+    }
+}
+
+/// Aygıt ağacındaki bellek düğümlerini, `/chosen` komut satırını ve
+/// initramfs alanını okur. Bellek haritası önceden doldurulduysa
+/// (OpenFirmware `available`) bellek düğümleri okunmaz.
+pub(crate) fn from_fdt(info: &mut BootInfo) {
+    let Some(fdt) = crate::fdt::get() else { return };
+
+    let prefilled = !info.memory_map().is_empty();
+    let memory = fdt.nodes().filter(|n| n.property_str("device_type") == Some("memory") && n.is_enabled());
+    for node in memory.filter(|_| !prefilled) {
+        for (base, size) in (0..).map_while(|index| node.reg(index)) {
+            if info.add_memory(base, size, MemoryKind::Usable).is_err() {
+                serial_println!("[BOOT] UYARI: {} bölgeden fazlası yok sayıldı", MAX_MEMORY_REGIONS);
+                break;
+            }
+        }
+    }
+
+    let Some(chosen) = fdt.find_path("/chosen") else { return };
+    if let Some(bootargs) = chosen.property_str("bootargs") {
+        info.set_cmdline(bootargs.as_bytes());
+    }
+    let start = chosen.property_u64("linux,initrd-start");
+    let end = chosen.property_u64("linux,initrd-end");
+    if let (Some(start), Some(end)) = (start, end) {
+        if end > start {
+            info.initramfs = Some((start as usize, (end - start) as usize));
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// KABUK VE PROCFS
+// -----------------------------------------------------------------------------
+
+fn show_cmdline(out: &mut dyn Write) -> fmt::Result {
+    writeln!(out, "{}", info().cmdline())
+}
+
+crate::proc_entry!("cmdline", show_cmdline);
+
+fn bootinfo_command(_args: &[&str]) -> Result<(), KernelError> {
+    let info = info();
+    serial_println!("  arayüz      {}", info.firmware.name());
+    serial_println!("  işlemci     {:#x}", info.boot_cpu);
+    if let Some(dtb) = info.dtb {
+        serial_println!("  dtb         {:#x}", dtb);
+    }
+    if let Some(rsdp) = info.acpi_rsdp {
+        serial_println!("  acpi rsdp   {:#x}", rsdp);
+    }
+    if let Some(entry) = info.of_entry {
+        serial_println!("  of girişi   {:#x}", entry);
+    }
+    if let Some((addr, len)) = info.initramfs {
+        serial_println!("  initramfs   {:#x} ({} bayt)", addr, len);
+    }
+    serial_println!("  komut satırı \"{}\"", info.cmdline());
+    for region in info.memory_map() {
+        serial_println!(
+            "  {:#014x}-{:#014x} {:<8} {} KiB",
+            region.base,
+            region.base + region.len,
+            region.kind.name(),
+            region.len / 1024
+        );
+    }
+    Ok(())
+}
+
+crate::shell_command!("bootinfo", "Önyükleyiciden gelen bilgileri gösterir", bootinfo_command);
@@ -0,0 +1,176 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/canary.rs
+// Yığın koruyucu (stack protector) kanaryası.
+//
+// `stackprotector` özelliğiyle derlenen çekirdekte (`cargo xtask build
+// <mimari> --features stackprotector`, xtask `-Z stack-protector=strong`
+// ekler) derleyici dizi ya da adresi alınan yerel tutan her işlevin
+// girişinde `__stack_chk_guard`'ı çerçeveye kopyalar, çıkışta karşılaştırır;
+// fark varsa `__stack_chk_fail` çağrılır.
+//
+// Değerler `entropy` havuzundan gelir:
+// * Açılış değeri: her açılışta yeni. `kernel_main` `boot::init`'ten hemen
+//   sonra `boot_init`'i çağırır; `boot_init` satır içidir ve `kernel_main`
+//   dönmediğinden eski değerle kurulmuş hiçbir çerçeve karşılaştırılmaz.
+// * Görev değeri: görevin bağlamı ilk kez kurulurken (`context::switch`)
+//   atanır; geçişte `switch_to` simgeyi sıradaki görevin değerine çevirir.
+//   Görev dışı akış (açılış, boşta döngü) açılış değerini kullanır.
+//
+// `__stack_chk_guard` tek bir küresel simgedir; birden çok işlemci
+// çalışırken görev başına değer olamaz. Görev başına kanarya yalnızca tek
+// işlemcide kullanılır: `percpu::prepare` `smp_prepare`'i çağırır, o andan
+// sonra kurulan görevler açılış değerini alır ve geçişte simge değişmez.
+// Görev başına değer almış bir görev varken ikinci işlemci açılamaz
+// (`ResourceBusy`); ikincil işlemciler görevler başlamadan açılmalıdır.
+//
+// Değerin ilk baytı hep sıfırdır: dizgi işlevleriyle yapılan taşma
+// kanaryayı okuyup aynen geri yazamaz.
+//
+// Bozulma panik değildir: kanarya bir görev yığınında bozulduysa görev (ve
+// varsa grubu) sonlandırılır, görev adı, giriş adresi ve yığının en yüksek
+// kullanımı yazdırılır (taşmanın sık nedeni yığının tükenmesidir). Görev
+// `sched::exit_current` ile zombi bırakılır; yığın, işlemci başka göreve
+// geçtikten sonra toplanır. Kesme işleyicisinde veya görev dışı akışta
+// bozulma çekirdeği durdurur.
+//
+// Kabuk komutu: yok; procfs: `/proc/canary`
+
+use core::fmt::{self, Write};
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use crate::memory::memory::{MemoryRegions, TaskStackAllocator};
+use crate::platformgeneric::KernelError;
+use crate::sched::{TaskId, MAX_TASKS};
+use crate::serial_println;
+
+/// Rastgele değer atanana kadar kullanılan sabit değer (ilk baytı sıfır).
+const INITIAL: usize = 0x5A17_E3C9_84B6_2D00;
+
+/// Derleyicinin karşılaştırdığı simge.
+#[no_mangle]
+pub static mut __stack_chk_guard: usize = INITIAL;
+
+/// Açılış değeri.
+static BOOT: AtomicUsize = AtomicUsize::new(INITIAL);
+/// Görev değerleri (0: atanmadı, açılış değeri kullanılır).
+static TASKS: [AtomicUsize; MAX_TASKS] = [const { AtomicUsize::new(0) }; MAX_TASKS];
+/// Görev başına değer kullanılıyor mu (tek işlemci).
+static PER_TASK: AtomicBool = AtomicBool::new(true);
+
+crate::kernel_counter!(FAILURES, "canary.failures");
+
+// -----------------------------------------------------------------------------
+// DEĞERLER
+// -----------------------------------------------------------------------------
+
+fn fresh() -> usize {
+    // Bellekteki ilk bayt sıfır (büyük sonlu hedeflerde en anlamlı bayt)
+    usize::from_le(crate::entropy::random_u64() as usize & !0xff)
+}
+
+/// Simgeyi değiştirir.
+#[inline(always)]
+fn set_guard(value: usize) {
+    // SAFETY: Simgeyi yalnızca bu modül yazar; değiştiği anda yaşayan
+    // çerçeveler aynı değeri tekrar görecek şekilde çağrılır (bkz. dosya başı).
+    unsafe { core::ptr::write_volatile(core::ptr::addr_of_mut!(__stack_chk_guard), value) };
+}
+
+/// Açılış değerini üretip kurar. Yalnızca `kernel_main`'in gövdesinden,
+/// `boot::init`'ten sonra bir kez çağrılır.
+#[inline(always)]
+pub fn boot_init() {
+    let value = fresh();
+    BOOT.store(value, Ordering::Relaxed);
+    set_guard(value);
+}
+
+/// `task`'ın yeni bağlamı için değer atar (`context::switch`).
+pub fn assign(task: TaskId) {
+    let value = if PER_TASK.load(Ordering::Acquire) { fresh() } else { 0 };
+    if let Some(slot) = TASKS.get(task) {
+        slot.store(value, Ordering::Relaxed);
+    }
+}
+
+/// Görev değeri; görev dışı akış ve değersiz görevler için açılış değeri.
+fn value_for(task: Option<TaskId>) -> usize {
+    match task.and_then(|t| TASKS.get(t)).map(|v| v.load(Ordering::Relaxed)) {
+        Some(value) if value != 0 => value,
+        _ => BOOT.load(Ordering::Relaxed),
+    }
+}
+
+/// Bağlam geçişinden hemen önce simgeyi `next`'in değerine çevirir.
+#[inline(always)]
+pub fn switch_to(next: Option<TaskId>) {
+    if PER_TASK.load(Ordering::Relaxed) {
+        set_guard(value_for(next));
+    }
+}
+
+/// İkincil işlemci açılmadan önce görev başına değerleri kapatır
+/// (`percpu::prepare`).
+///
+/// # Hatalar
+/// * `ResourceBusy`: Görev başına değer almış bir görev var.
+pub fn smp_prepare() -> Result<(), KernelError> {
+    if !PER_TASK.load(Ordering::Acquire) {
+        return Ok(());
+    }
+    if TASKS.iter().any(|v| v.load(Ordering::Relaxed) != 0) {
+        serial_println!("[CANARY] Görev başına kanaryası olan görevler var; ikincil işlemci açılamaz");
+        return Err(KernelError::ResourceBusy);
+    }
+    PER_TASK.store(false, Ordering::Release);
+    set_guard(BOOT.load(Ordering::Relaxed));
+    Ok(())
+}
+
+/// Sonlanan görevin değerini siler (`context::release`).
+pub fn release(task: TaskId) {
+    if let Some(slot) = TASKS.get(task) {
+        slot.store(0, Ordering::Relaxed);
+    }
+}
+
+// -----------------------------------------------------------------------------
+// BOZULMA
+// -----------------------------------------------------------------------------
+
+/// Derleyicinin, kanaryası bozulan işlevin çıkışında çağırdığı işlev.
+#[no_mangle]
+pub extern "C" fn __stack_chk_fail() -> ! {
+    FAILURES.inc();
+    let task = crate::sched::current().filter(|_| !crate::irqstack::in_interrupt());
+    let Some(task) = task else {
+        panic!("yığın kanaryası bozuldu (kesme işleyicisi veya görev dışı akış)");
+    };
+    match crate::sched::task_info(task) {
+        Some(info) => serial_println!("[CANARY] Görev {} ({}, giriş {:#x}) yığını bozuldu", task, info.name, info.entry),
+        None => serial_println!("[CANARY] Görev {} yığını bozuldu", task),
+    }
+    if let Some(used) = TaskStackAllocator::high_water_mark(task) {
+        serial_println!("[CANARY] Yığın kullanımı: {} / {} bayt", used, MemoryRegions::TASK_STACK_SIZE);
+    }
+    // SIGABRT (6); çıkış kodu `trap::handle_user_fatal` geleneğiyle 128 + sinyal
+    let code = 128 + 6;
+    if let Some(group) = crate::process::group_of(task) {
+        crate::process::kill(group, code).ok();
+    }
+    // Bu yığın hâlâ kullanılıyor: görev zombi bırakılır, yığını geçişten
+    // sonra `sched::finish_switch` toplar
+    crate::sched::exit_current()
+}
+
+// -----------------------------------------------------------------------------
+// PROCFS
+// -----------------------------------------------------------------------------
+
+fn show_canary(out: &mut dyn Write) -> fmt::Result {
+    writeln!(out, "enabled {}", cfg!(feature = "stackprotector"))?;
+    writeln!(out, "per_task {}", PER_TASK.load(Ordering::Relaxed))?;
+    writeln!(out, "failures {}", FAILURES.get())
+}
+
+crate::proc_entry!("canary", show_canary);
@@ -0,0 +1,31 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/clock.rs
+// Monotonik saat: mimarinin serbest çalışan sayacı (TSC, CNTPCT, time, TB
+// vb.) üzerinden zaman damgası.
+//
+// Sayaç açılıştan beri geriye gitmez; farklar `wrapping_sub` ile alınmalıdır.
+// Frekans bilinmiyorsa (`cycles_hz() == 0`) değerler ham döngü olarak
+// raporlanır.
+
+// -----------------------------------------------------------------------------
+// SAYAÇ
+// -----------------------------------------------------------------------------
+
+/// Serbest çalışan sayacın anlık değeri.
+pub fn cycles() -> u64 {
+    crate::arch::time::read_cycles().0
+}
+
+/// Sayaç frekansı (Hz); bilinmiyorsa 0 ve raporlar ham döngü cinsindendir.
+pub fn cycles_hz() -> u64 {
+    crate::arch::time::get_frequency()
+}
+
+/// Sayaç farkını nanosaniyeye çevirir; frekans bilinmiyorsa `None`.
+pub fn cycles_to_ns(cycles: u64) -> Option<u64> {
+    match cycles_hz() {
+        0 => None,
+        hz => Some((u128::from(cycles) * 1_000_000_000 / u128::from(hz)) as u64),
+    }
+}
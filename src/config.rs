@@ -0,0 +1,318 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/config.rs
+// Tipli, sürümlü ve sağlama toplamlı kalıcı yapılandırma (`kvs` üzerinde).
+//
+// Her alt sistem ayarlarını bir yapı olarak tanımlar ve `Config`'i
+// gerçekler; `config::get::<NetConfig>()` ve `config::set(&cfg)` yapıyı
+// kendi `kvs` anahtarında saklar. Kayıt biçimi:
+//
+//   sürüm (u16 LE) | TLV alanları | CRC-32 (u32 LE, önceki tüm baytlar)
+//
+// TLV alanı: etiket (1 B), uzunluk (1 B), değer (küçük uçlu tamsayılar,
+// bayt dizileri). Çözümlemede bilinmeyen etiketler atlanır, eksik alanlar
+// varsayılan değerini alır; böylece ürün yazılımı güncellemesinde alan
+// eklemek veya kaldırmak eski kaydı bozmaz. Anlamı değişen alanlar için
+// şema sürümü artırılır ve `migrate` eski sürümden okunan değeri çevirir;
+// göç eden kayıt yeni sürümle hemen geri yazılır.
+//
+// CRC'si tutmayan veya çözümlenemeyen kayıt kullanılmaz: `get` varsayılan
+// ayarları döndürür ve `config.corrupt` sayacı artar.
+
+use crate::kvs;
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+use crate::update::crc32_update;
+
+// -----------------------------------------------------------------------------
+// SABİTLER VE TİPLER
+// -----------------------------------------------------------------------------
+
+/// Kaydın en büyük boyutu (sürüm ve CRC dahil).
+pub const MAX_CONFIG: usize = kvs::MAX_VALUE;
+const VERSION_LEN: usize = 2;
+const CRC_LEN: usize = 4;
+const TLV_HEADER_LEN: usize = 2;
+
+crate::kernel_counter!(CORRUPT, "config.corrupt");
+crate::kernel_counter!(MIGRATED, "config.migrated");
+
+/// Kalıcı yapılandırma yapısı.
+pub trait Config: Default {
+    /// `kvs` anahtarı (örn. "cfg.net").
+    const KEY: &'static str;
+    /// Şema sürümü; alanların anlamı değiştiğinde artırılır.
+    const VERSION: u16;
+
+    /// Alanları TLV olarak yazar.
+    fn encode(&self, out: &mut TlvWriter) -> Result<(), KernelError>;
+
+    /// Bilinen alanları okur; eksik alanlar `Default` değerinde kalır.
+    fn decode(&mut self, fields: &TlvReader);
+
+    /// `from` sürümünde kaydedilmiş ve çözümlenmiş ayarları `VERSION`'a
+    /// çevirir. Varsayılan: yalnızca alan ekleyen/kaldıran şemalar için
+    /// hiçbir şey yapmaz.
+    fn migrate(&mut self, from: u16) {
+        let _ = from;
+    }
+}
+
+// -----------------------------------------------------------------------------
+// TLV
+// -----------------------------------------------------------------------------
+
+/// Sabit tampona TLV alanları yazar.
+pub struct TlvWriter {
+    buf: [u8; MAX_CONFIG],
+    len: usize,
+}
+
+impl TlvWriter {
+    fn new() -> Self {
+        TlvWriter { buf: [0; MAX_CONFIG], len: VERSION_LEN }
+    }
+
+    /// Ham alan yazar.
+    ///
+    /// # Hatalar
+    /// * `InvalidArgument`: Değer 255 bayttan uzun.
+    /// * `OutOfMemoryStatic`: Kayıt `MAX_CONFIG`'e sığmıyor.
+    pub fn bytes(&mut self, tag: u8, value: &[u8]) -> Result<(), KernelError> {
+        let len = u8::try_from(value.len()).map_err(|_| KernelError::InvalidArgument)?;
+        let end = self.len + TLV_HEADER_LEN + value.len();
+        if end + CRC_LEN > MAX_CONFIG {
+            return Err(KernelError::OutOfMemoryStatic);
+        }
+        self.buf[self.len] = tag;
+        self.buf[self.len + 1] = len;
+        self.buf[self.len + TLV_HEADER_LEN..end].copy_from_slice(value);
+        self.len = end;
+        Ok(())
+    }
+
+    pub fn u8(&mut self, tag: u8, value: u8) -> Result<(), KernelError> {
+        self.bytes(tag, &[value])
+    }
+
+    pub fn bool(&mut self, tag: u8, value: bool) -> Result<(), KernelError> {
+        self.u8(tag, u8::from(value))
+    }
+
+    pub fn u16(&mut self, tag: u8, value: u16) -> Result<(), KernelError> {
+        self.bytes(tag, &value.to_le_bytes())
+    }
+
+    pub fn u32(&mut self, tag: u8, value: u32) -> Result<(), KernelError> {
+        self.bytes(tag, &value.to_le_bytes())
+    }
+
+    pub fn u64(&mut self, tag: u8, value: u64) -> Result<(), KernelError> {
+        self.bytes(tag, &value.to_le_bytes())
+    }
+
+    pub fn str(&mut self, tag: u8, value: &str) -> Result<(), KernelError> {
+        self.bytes(tag, value.as_bytes())
+    }
+
+    /// Sürümü başa, CRC'yi sona yazar ve kaydı döndürür.
+    fn finish(&mut self, version: u16) -> &[u8] {
+        self.buf[..VERSION_LEN].copy_from_slice(&version.to_le_bytes());
+        let crc = crc32_update(0, &self.buf[..self.len]);
+        self.buf[self.len..self.len + CRC_LEN].copy_from_slice(&crc.to_le_bytes());
+        &self.buf[..self.len + CRC_LEN]
+    }
+}
+
+/// Doğrulanmış bir kaydın TLV alanları.
+pub struct TlvReader<'a> {
+    fields: &'a [u8],
+}
+
+impl<'a> TlvReader<'a> {
+    /// Alanların sınırlarını denetler; taşan alan varsa `None`.
+    fn new(fields: &'a [u8]) -> Option<Self> {
+        let reader = TlvReader { fields };
+        let mut rest = fields;
+        while !rest.is_empty() {
+            let len = TLV_HEADER_LEN + usize::from(*rest.get(1)?);
+            rest = rest.get(len..)?;
+        }
+        Some(reader)
+    }
+
+    /// Tüm alanlar: (etiket, değer).
+    pub fn iter(&self) -> impl Iterator<Item = (u8, &'a [u8])> {
+        let mut rest = self.fields;
+        core::iter::from_fn(move || {
+            let (&tag, tail) = rest.split_first()?;
+            let (&len, tail) = tail.split_first()?;
+            let (value, tail) = tail.split_at(usize::from(len));
+            rest = tail;
+            Some((tag, value))
+        })
+    }
+
+    /// Etiketin değeri (tekrarlanan etikette sonuncusu).
+    pub fn bytes(&self, tag: u8) -> Option<&'a [u8]> {
+        self.iter().filter(|(t, _)| *t == tag).map(|(_, v)| v).last()
+    }
+
+    /// Etiketin değeri `N` baytlık dizi olarak; uzunluk farklıysa `None`.
+    pub fn array<const N: usize>(&self, tag: u8) -> Option<[u8; N]> {
+        self.bytes(tag)?.try_into().ok()
+    }
+
+    pub fn u8(&self, tag: u8) -> Option<u8> {
+        self.array::<1>(tag).map(|[b]| b)
+    }
+
+    pub fn bool(&self, tag: u8) -> Option<bool> {
+        self.u8(tag).map(|b| b != 0)
+    }
+
+    pub fn u16(&self, tag: u8) -> Option<u16> {
+        self.array(tag).map(u16::from_le_bytes)
+    }
+
+    pub fn u32(&self, tag: u8) -> Option<u32> {
+        self.array(tag).map(u32::from_le_bytes)
+    }
+
+    pub fn u64(&self, tag: u8) -> Option<u64> {
+        self.array(tag).map(u64::from_le_bytes)
+    }
+
+    pub fn str(&self, tag: u8) -> Option<&'a str> {
+        core::str::from_utf8(self.bytes(tag)?).ok()
+    }
+}
+
+/// Kaydı doğrular: (sürüm, alanlar). CRC veya TLV yapısı bozuksa `None`.
+fn parse(record: &[u8]) -> Option<(u16, TlvReader<'_>)> {
+    if record.len() < VERSION_LEN + CRC_LEN {
+        return None;
+    }
+    let (body, crc) = record.split_at(record.len() - CRC_LEN);
+    if crc32_update(0, body) != u32::from_le_bytes(crc.try_into().ok()?) {
+        return None;
+    }
+    let version = u16::from_le_bytes([body[0], body[1]]);
+    Some((version, TlvReader::new(&body[VERSION_LEN..])?))
+}
+
+// -----------------------------------------------------------------------------
+// ARAYÜZ
+// -----------------------------------------------------------------------------
+
+/// Kayıtlı ayarları okur.
+///
+/// # Dönüş Değeri
+/// Kayıt yoksa `None`. Eski sürümden okunan ayarlar `migrate` ile çevrilir.
+///
+/// # Hatalar
+/// * `InvalidArgument`: Kayıt bozuk (CRC veya TLV yapısı).
+/// * `kvs` hataları (örn. depo yoksa `NotFound`).
+pub fn load<T: Config>() -> Result<Option<T>, KernelError> {
+    let mut record = [0u8; MAX_CONFIG];
+    let len = match kvs::get(T::KEY, &mut record) {
+        Ok(len) => len.min(MAX_CONFIG),
+        Err(KernelError::NotFound) => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    let (version, fields) = parse(&record[..len]).ok_or(KernelError::InvalidArgument)?;
+    let mut config = T::default();
+    config.decode(&fields);
+    if version < T::VERSION {
+        config.migrate(version);
+        // Göç bir kez yapılır; yazılamazsa bir sonraki okumada yinelenir
+        if store(&config).is_ok() {
+            MIGRATED.inc();
+            serial_println!("[CONFIG] {} sürüm {} -> {} göç etti", T::KEY, version, T::VERSION);
+        }
+    }
+    Ok(Some(config))
+}
+
+/// Kayıtlı ayarlar; kayıt yoksa, bozuksa veya depo açılamadıysa varsayılan
+/// ayarlar.
+pub fn get<T: Config>() -> T {
+    match load::<T>() {
+        Ok(Some(config)) => config,
+        Ok(None) => T::default(),
+        Err(e) => {
+            if e == KernelError::InvalidArgument {
+                CORRUPT.inc();
+                serial_println!("[CONFIG] {} kaydı bozuk, varsayılanlar kullanılıyor", T::KEY);
+            }
+            T::default()
+        }
+    }
+}
+
+fn store<T: Config>(config: &T) -> Result<(), KernelError> {
+    let mut writer = TlvWriter::new();
+    config.encode(&mut writer)?;
+    kvs::set(T::KEY, writer.finish(T::VERSION))
+}
+
+/// Ayarları güncel şema sürümüyle kalıcı olarak yazar. Değişmeyen ayarlar
+/// flash'a yazılmaz (bkz. `kvs::set`).
+pub fn set<T: Config>(config: &T) -> Result<(), KernelError> {
+    store(config)
+}
+
+/// Kaydı siler; sonraki `get` varsayılanları döndürür.
+pub fn reset<T: Config>() -> Result<(), KernelError> {
+    match kvs::remove(T::KEY) {
+        Err(KernelError::NotFound) => Ok(()),
+        result => result,
+    }
+}
+
+// -----------------------------------------------------------------------------
+// KABUK
+// -----------------------------------------------------------------------------
+
+/// `config <anahtar>`: kaydın sürümünü ve ham alanlarını gösterir.
+fn config_command(args: &[&str]) -> Result<(), KernelError> {
+    let key = args.get(1).ok_or(KernelError::InvalidArgument)?;
+    let mut record = [0u8; MAX_CONFIG];
+    let len = kvs::get(key, &mut record)?.min(MAX_CONFIG);
+    let Some((version, fields)) = parse(&record[..len]) else {
+        serial_println!("  {}: bozuk kayıt ({} bayt)", key, len);
+        return Ok(());
+    };
+    serial_println!("  {}: sürüm {}, {} bayt", key, version, len);
+    for (tag, value) in fields.iter() {
+        serial_println!("    [{:3}] {:02x?}", tag, value);
+    }
+    Ok(())
+}
+
+crate::shell_command!("config", "Kalıcı yapılandırma kaydını göster (config <anahtar>)", config_command);
+
+// -----------------------------------------------------------------------------
+// TESTLER
+// -----------------------------------------------------------------------------
+
+crate::ktest! {
+    fn config_tlv_roundtrip() {
+        let mut writer = TlvWriter::new();
+        writer.u32(1, 0xDEAD_BEEF)?;
+        writer.str(2, "sahne")?;
+        // Tekrarlanan etikette son değer geçerlidir
+        writer.u32(1, 7)?;
+        let record = writer.finish(3);
+        let (version, fields) = parse(record).ok_or(KernelError::InvalidArgument)?;
+        crate::ktest_assert_eq!(version, 3);
+        crate::ktest_assert_eq!(fields.u32(1), Some(7));
+        crate::ktest_assert_eq!(fields.str(2), Some("sahne"));
+        crate::ktest_assert_eq!(fields.u16(1), None);
+
+        let mut corrupt = [0u8; MAX_CONFIG];
+        corrupt[..record.len()].copy_from_slice(record);
+        corrupt[4] ^= 1;
+        crate::ktest_assert!(parse(&corrupt[..record.len()]).is_none());
+    }
+}
@@ -0,0 +1,310 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/console.rs
+// Çoklu konsol çoğullayıcısı: görev başına çıktı ve günlük yönlendirme.
+//
+// Çıktı hedefleri (`Sink`): `serial0` (mimarinin ana konsolu), `serial1`
+// (ikincil UART), `fb` (çerçeve arabelleği konsolu), `netlog` (ağ günlük
+// toplayıcısı) ve `null` (çıktı atılır). `serial0` ve `null` her zaman
+// vardır; diğerleri sürücüleri tarafından `register` ile kaydedilir. Kaydı
+// olmayan bir hedefe bağlanan görevin çıktısı kaybolmaz, `serial0`'a yazılır.
+//
+// Her görevin iki akışı ayrı ayrı bağlanır:
+// * `Stdio`: görevin `serial_print!`/`serial_println!` çıktısı.
+// * `Log`: görevin `klog!` iletileri; bağlanmamışsa `Stdio` bağlamasını izler.
+// Böylece ayrıntılı sürücü izlemesi `serial1`'e veya `netlog`'a gönderilirken
+// etkileşimli konsol temiz kalır.
+//
+// Günlük dinleyicisi (`set_log_tap`, örn. `net::syslog`) bağlamadan bağımsız
+// olarak tüm `klog!` iletilerini önem seviyesiyle birlikte alır. İletiler
+// ayrıca her zaman sıkıştırılmış günlük halkasına (`logbuf`, `dmesg`) yazılır.
+//
+// Açık bağlama, uzak oturum yönlendirmesinden (`shell::set_redirect`, örn.
+// telnet) önce gelir. Kesme bağlamındaki çıktı her zaman `serial0`'a gider.
+// Görev çıkınca bağlamaları sıfırlanır.
+//
+// Hedef yazma fonksiyonları `serial_print!`/`klog!` çağırmamalıdır (kendi
+// kendine yönlenir).
+//
+// Ayar: kabuk `console [bind <görev> <stdio|log> <hedef> | unbind <görev>]`,
+// sistem çağrısı `SYS_CONSOLE_BIND(akış, hedef)` (çağıran görev için).
+
+use core::fmt;
+use core::sync::atomic::{AtomicBool, AtomicPtr, AtomicU8, Ordering};
+use crate::platformgeneric::KernelError;
+use crate::sched::{TaskId, MAX_TASKS};
+use crate::serial_println;
+use crate::sysctl::LogLevel;
+
+// -----------------------------------------------------------------------------
+// HEDEFLER
+// -----------------------------------------------------------------------------
+
+/// Konsol çıktı hedefi.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Sink {
+    Serial0 = 0,
+    Serial1 = 1,
+    Framebuffer = 2,
+    Netlog = 3,
+    Null = 4,
+}
+
+const SINK_COUNT: usize = 5;
+
+const SINKS: [Sink; SINK_COUNT] = [Sink::Serial0, Sink::Serial1, Sink::Framebuffer, Sink::Netlog, Sink::Null];
+
+impl Sink {
+    pub fn name(self) -> &'static str {
+        match self {
+            Sink::Serial0 => "serial0",
+            Sink::Serial1 => "serial1",
+            Sink::Framebuffer => "fb",
+            Sink::Netlog => "netlog",
+            Sink::Null => "null",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Sink> {
+        SINKS.iter().copied().find(|sink| sink.name() == name)
+    }
+
+    fn from_u8(value: u8) -> Option<Sink> {
+        SINKS.get(usize::from(value)).copied()
+    }
+}
+
+/// Görevin bağlanabilen çıktı akışı.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Stream {
+    Stdio = 0,
+    Log = 1,
+}
+
+impl Stream {
+    fn from_name(name: &str) -> Option<Stream> {
+        match name {
+            "stdio" => Some(Stream::Stdio),
+            "log" => Some(Stream::Log),
+            _ => None,
+        }
+    }
+}
+
+/// Hedef sürücüsünün yazma fonksiyonu. Görev bağlamında çağrılır.
+pub type SinkWrite = fn(args: fmt::Arguments);
+
+/// Kayıtlı hedef sürücüleri (`SinkWrite` işaretçisi; boşsa kayıt yok).
+static BACKENDS: [AtomicPtr<()>; SINK_COUNT] = [const { AtomicPtr::new(core::ptr::null_mut()) }; SINK_COUNT];
+
+/// `sink` hedefinin sürücüsünü kaydeder (`None`: kaydı siler). `serial0` ve
+/// `null` kaydedilemez.
+pub fn register(sink: Sink, write: Option<SinkWrite>) -> Result<(), KernelError> {
+    if matches!(sink, Sink::Serial0 | Sink::Null) {
+        return Err(KernelError::InvalidArgument);
+    }
+    BACKENDS[sink as usize].store(write.map_or(core::ptr::null_mut(), |f| f as *mut ()), Ordering::Release);
+    Ok(())
+}
+
+/// Hedefe şu an yazılabilir mi?
+pub fn is_available(sink: Sink) -> bool {
+    match sink {
+        Sink::Serial0 | Sink::Null => true,
+        _ => !BACKENDS[sink as usize].load(Ordering::Acquire).is_null(),
+    }
+}
+
+/// Çıktıyı hedefe yazar.
+///
+/// # Dönüş Değeri
+/// Çıktı hedef tarafından alındıysa `true`; `serial0` veya kaydı olmayan
+/// hedef için `false` (çağıran seri porta yazar).
+fn write_to(sink: Sink, args: fmt::Arguments) -> bool {
+    match sink {
+        Sink::Serial0 => false,
+        Sink::Null => true,
+        _ => {
+            let write = BACKENDS[sink as usize].load(Ordering::Acquire);
+            if write.is_null() {
+                return false;
+            }
+            // SAFETY: Tabloya yalnızca `register` bir `SinkWrite` işaretçisi yazar.
+            let write: SinkWrite = unsafe { core::mem::transmute(write) };
+            write(args);
+            true
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// GÖREV BAĞLAMALARI
+// -----------------------------------------------------------------------------
+
+const UNBOUND: u8 = u8::MAX;
+
+/// Görev başına akış bağlamaları (`Sink` değeri; `UNBOUND`: bağlanmamış).
+static BINDINGS: [[AtomicU8; 2]; MAX_TASKS] = [const { [const { AtomicU8::new(UNBOUND) }; 2] }; MAX_TASKS];
+
+/// Görev şu an `klog!` iletisi yazıyor mu?
+static IN_LOG: [AtomicBool; MAX_TASKS] = [const { AtomicBool::new(false) }; MAX_TASKS];
+
+/// Tüm `klog!` iletilerini alan dinleyici. Kesme bağlamından da çağrılır;
+/// `serial_print!`/`klog!` çağırmamalıdır.
+pub type LogTap = fn(task: Option<TaskId>, level: LogLevel, args: fmt::Arguments);
+
+static LOG_TAP: AtomicPtr<()> = AtomicPtr::new(core::ptr::null_mut());
+
+/// Günlük dinleyicisini kaydeder (`None`: kaldırır).
+pub fn set_log_tap(tap: Option<LogTap>) {
+    LOG_TAP.store(tap.map_or(core::ptr::null_mut(), |f| f as *mut ()), Ordering::Release);
+}
+
+/// `task` görevinin `stream` akışını `sink`'e bağlar (`None`: bağlamayı kaldırır).
+pub fn bind(task: TaskId, stream: Stream, sink: Option<Sink>) -> Result<(), KernelError> {
+    let slot = BINDINGS.get(task).ok_or(KernelError::InvalidArgument)?;
+    crate::sched::task_info(task).ok_or(KernelError::NotFound)?;
+    slot[stream as usize].store(sink.map_or(UNBOUND, |sink| sink as u8), Ordering::Release);
+    Ok(())
+}
+
+/// Görevin akış bağlaması (`None`: bağlanmamış).
+pub fn binding(task: TaskId, stream: Stream) -> Option<Sink> {
+    BINDINGS.get(task).and_then(|slot| Sink::from_u8(slot[stream as usize].load(Ordering::Acquire)))
+}
+
+/// Görev şu an bir `klog!` iletisi mi yazıyor?
+pub fn in_log(task: TaskId) -> bool {
+    IN_LOG.get(task).is_some_and(|flag| flag.load(Ordering::Relaxed))
+}
+
+/// Görev tablosu girişi yeniden kullanılmadan önce `sched::exit` tarafından çağrılır.
+pub fn task_exited(task: TaskId) {
+    if let Some(slot) = BINDINGS.get(task) {
+        slot[Stream::Stdio as usize].store(UNBOUND, Ordering::Release);
+        slot[Stream::Log as usize].store(UNBOUND, Ordering::Release);
+    }
+}
+
+/// `shell::redirect` tarafından çağrılır: görevin etkin akışı açıkça
+/// bağlandıysa çıktıyı o hedefe yönlendirir.
+///
+/// # Dönüş Değeri
+/// * `None`: bağlama yok, oturum yönlendirmesi denenir.
+/// * `Some(true)`: çıktı hedef tarafından alındı.
+/// * `Some(false)`: çıktı seri porta yazılmalı.
+#[doc(hidden)]
+pub fn route(task: TaskId, args: fmt::Arguments) -> Option<bool> {
+    let sink = if IN_LOG[task].load(Ordering::Relaxed) {
+        binding(task, Stream::Log).or_else(|| binding(task, Stream::Stdio))
+    } else {
+        binding(task, Stream::Stdio)
+    }?;
+    Some(write_to(sink, args))
+}
+
+/// `klog!` tarafından çağrılır: iletiyi günlük halkasına ve dinleyiciye verir
+/// ve görevin `Log` akışına yazar.
+#[doc(hidden)]
+pub fn log(level: LogLevel, args: fmt::Arguments) {
+    crate::logbuf::record(level, args);
+    let task = crate::sched::current().filter(|_| !crate::irqstack::in_interrupt());
+    let tap = LOG_TAP.load(Ordering::Acquire);
+    if !tap.is_null() {
+        // SAFETY: `LOG_TAP`'a yalnızca `set_log_tap` bir `LogTap` işaretçisi yazar.
+        let tap: LogTap = unsafe { core::mem::transmute(tap) };
+        tap(task, level, args);
+    }
+    let Some(task) = task else {
+        serial_println!("{}", args);
+        return;
+    };
+    IN_LOG[task].store(true, Ordering::Relaxed);
+    serial_println!("{}", args);
+    IN_LOG[task].store(false, Ordering::Relaxed);
+}
+
+// -----------------------------------------------------------------------------
+// SİSTEM ÇAĞRISI VE KABUK
+// -----------------------------------------------------------------------------
+
+/// `console_bind(stream, sink)`: çağıran görevin akışını bağlar. `sink`
+/// `Sink` değeridir; `u8::MAX` bağlamayı kaldırır.
+pub fn sys_console_bind(stream: usize, sink: usize) -> Result<usize, KernelError> {
+    let stream = match stream {
+        0 => Stream::Stdio,
+        1 => Stream::Log,
+        _ => return Err(KernelError::InvalidArgument),
+    };
+    let sink = match sink {
+        s if s == usize::from(UNBOUND) => None,
+        s => Some(u8::try_from(s).ok().and_then(Sink::from_u8).ok_or(KernelError::InvalidArgument)?),
+    };
+    let task = crate::sched::current().ok_or(KernelError::NotFound)?;
+    bind(task, stream, sink)?;
+    Ok(0)
+}
+
+fn show_binding(sink: Option<Sink>) -> &'static str {
+    sink.map_or("-", Sink::name)
+}
+
+/// `console [bind <görev> <stdio | log> <hedef> | unbind <görev>]`
+fn console_command(args: &[&str]) -> Result<(), KernelError> {
+    let task = |index: usize| -> Result<TaskId, KernelError> {
+        args.get(index).and_then(|s| s.parse().ok()).ok_or(KernelError::InvalidArgument)
+    };
+    match args.get(1).copied() {
+        None => {
+            serial_println!("Hedefler:");
+            for sink in SINKS {
+                serial_println!("  {:<8} {}", sink.name(), if is_available(sink) { "hazır" } else { "kayıtsız" });
+            }
+            serial_println!("  ID AD               STDIO    LOG");
+            for id in 0..MAX_TASKS {
+                let Some(info) = crate::sched::task_info(id) else { continue };
+                let (stdio, log) = (binding(id, Stream::Stdio), binding(id, Stream::Log));
+                if stdio.is_some() || log.is_some() {
+                    serial_println!("  {:>2} {:<16} {:<8} {}", id, info.name, show_binding(stdio), show_binding(log));
+                }
+            }
+            Ok(())
+        }
+        Some("bind") => {
+            let stream = args.get(3).and_then(|s| Stream::from_name(s)).ok_or(KernelError::InvalidArgument)?;
+            let sink = args.get(4).and_then(|s| Sink::from_name(s)).ok_or(KernelError::InvalidArgument)?;
+            if !is_available(sink) {
+                serial_println!("Uyarı: '{}' kayıtsız, çıktı serial0'a yazılacak", sink.name());
+            }
+            bind(task(2)?, stream, Some(sink))
+        }
+        Some("unbind") => {
+            let id = task(2)?;
+            bind(id, Stream::Stdio, None)?;
+            bind(id, Stream::Log, None)
+        }
+        Some(_) => Err(KernelError::InvalidArgument),
+    }
+}
+
+crate::shell_command!(
+    "console",
+    "Görev çıktı hedefleri (console [bind <görev> <stdio|log> <hedef> | unbind <görev>])",
+    console_command
+);
+
+// -----------------------------------------------------------------------------
+// İKİNCİL SERİ PORT
+// -----------------------------------------------------------------------------
+
+/// İkinci bir UART'ı olan mimarilerde `serial1`'i kaydeder.
+fn init_serial1() -> Result<(), KernelError> {
+    match crate::arch::console::init_aux_serial() {
+        Some(write) => register(Sink::Serial1, Some(write)),
+        None => Ok(()),
+    }
+}
+
+crate::initcall!(drivers, "serial1", init_serial1);
@@ -0,0 +1,191 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/context.rs
+// Mimariden bağımsız görev bağlamı arayüzü (`ArchContext`) ve görev
+// bağlamlarının tablosu.
+//
+// Her mimarinin `task.rs` dosyası kendi `TaskContext` yapısını tanımlar;
+// yapıların içeriği (kaydedilen yazmaçlar) ve kurulumu (PowerPC'de TOC ve
+// iş parçacığı işaretçisi, SPARC'ta nPC) farklıdır. Zamanlayıcı bunları
+// yalnızca `ArchContext` üzerinden görür:
+//
+// * `new(entry, arg, stack_top)`: ilk geçişte `entry(arg)` çağrılacak
+//   şekilde, `stack_top`'tan aşağı büyüyen yığında bağlam kurar. Mimari
+//   işlev ve argümanı kalıcı (callee-saved) yazmaçlara koyar; ilk geçiş
+//   mimarinin `task_start` basamağına sıçrar, o da `task_entry(func, arg)`
+//   çağrısını ABI'ye uygun yapar.
+// * `switch(&mut self, next)`: geçerli yazmaçları `self`'e kaydeder ve
+//   `next`'i yükler.
+// * `set_return_value`: görev yeniden çalıştığında `switch` çağrısının
+//   ABI dönüş yazmacında göreceği değer (örn. uyandıran IPC'nin sonucu).
+//   Değer bir kez görülür; görev devam edince `switch` onu sıfırlar.
+// * `catch(body, data)`: `switch`'in kaydetme yarısını yapıp `body(data)`'yı
+//   çağırır; kaydedilen bağlam sonradan `switch` ile yüklenirse `catch`
+//   ikinci kez döner (`fixup::catch` hata kurtarması).
+//
+// Bağlamlar görev kimliğiyle dizinlenir ve ilk geçişte tembel kurulur:
+// yığın `TaskStackAllocator`'dan alınır. Görev dışından (açılış akışı,
+// boşta döngü) yapılan geçişin kaydı işlemci başına ayrı tutulur. Geçiş
+// mimari `initialize_tasking` içinde `enable` çağırana kadar kapalıdır;
+// o zamana kadar zamanlayıcı yalnızca seçim yapar.
+//
+// Yığın koruyucu kanaryası görev başınadır (bkz. `canary`): değer bağlam
+// kurulurken atanır, simge geçişten hemen önce sıradaki görevinkine çevrilir.
+//
+// Kabuk komutu: yok; procfs: yok
+
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicBool, Ordering};
+use crate::memory::memory::TaskStackAllocator;
+use crate::percpu::{self, MAX_CPUS};
+use crate::platformgeneric::spinlock::IrqSafeSpinlock;
+use crate::platformgeneric::KernelError;
+use crate::sched::{TaskId, MAX_TASKS};
+use crate::serial_println;
+
+pub use crate::arch::task::TaskContext as Context;
+
+// -----------------------------------------------------------------------------
+// ARAYÜZ
+// -----------------------------------------------------------------------------
+
+/// Mimarinin görev bağlamı (bkz. dosya başı).
+pub trait ArchContext: Sized {
+    /// İlk geçişte `entry(arg)`'ı `stack_top` yığınında başlatacak bağlamı kurar.
+    fn new(entry: usize, arg: usize, stack_top: usize) -> Self;
+
+    /// Geçerli yazmaçları `self`'e kaydeder ve `next`'e geçer. Görev yeniden
+    /// seçildiğinde bu çağrıdan döner.
+    ///
+    /// # Safety
+    /// `self` çalışan görevin, `next` geçerli bir yığını olan bir görevin
+    /// bağlamı olmalıdır; ikisi de geçiş boyunca yerinde kalmalıdır.
+    unsafe fn switch(&mut self, next: &Self);
+
+    /// Görev yeniden çalıştığında dönüş yazmacında görülecek değer.
+    fn set_return_value(&mut self, value: usize);
+
+    /// Callee-saved yazmaçları ve yığın işaretçisini `self`'e kaydedip
+    /// `body(data)`'yı çağırır. `body` dönerse 0 döner; `self` `body` bitmeden
+    /// `switch` ile yüklenirse bu çağrı `set_return_value` değeriyle döner.
+    ///
+    /// # Safety
+    /// `self` geri dönüşe kadar yerinde kalmalıdır; `body`'nin çerçeveleri
+    /// geri dönüşte atılır (yıkıcılar çalışmaz).
+    unsafe fn catch(&mut self, body: extern "C" fn(*mut u8), data: *mut u8) -> usize;
+}
+
+// -----------------------------------------------------------------------------
+// BAĞLAM TABLOSU
+// -----------------------------------------------------------------------------
+
+struct Contexts {
+    lock: IrqSafeSpinlock,
+    tasks: UnsafeCell<[Option<Context>; MAX_TASKS]>,
+    /// İşlemci başına, görev dışından yapılan geçişin kaydı.
+    boot: UnsafeCell<[Option<Context>; MAX_CPUS]>,
+}
+
+// Tüm alanlara erişim `lock` ile korunur; geçiş kilit bırakıldıktan sonra yapılır.
+unsafe impl Sync for Contexts {}
+
+static CONTEXTS: Contexts = Contexts {
+    lock: IrqSafeSpinlock::new(),
+    tasks: UnsafeCell::new([const { None }; MAX_TASKS]),
+    boot: UnsafeCell::new([const { None }; MAX_CPUS]),
+};
+
+/// Mimari geçişi açtı mı?
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+crate::kernel_counter!(STACK_FAILURES, "context.stack_failures");
+
+fn with_contexts<R>(f: impl FnOnce(&mut [Option<Context>; MAX_TASKS], &mut [Option<Context>; MAX_CPUS]) -> R) -> R {
+    let flags = CONTEXTS.lock.lock();
+    // SAFETY: Kilit tutulduğu sürece tabloya tek erişen biziz.
+    let result = unsafe { f(&mut *CONTEXTS.tasks.get(), &mut *CONTEXTS.boot.get()) };
+    CONTEXTS.lock.unlock(flags);
+    result
+}
+
+/// Gerçek bağlam geçişini açar; mimarinin `initialize_tasking`'i çağırır.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Release);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Acquire)
+}
+
+/// `prev`'den (`None`: görev dışı akış) `next`'e geçer. `next`'in bağlamı
+/// yoksa `entry(arg)` ile kurulur. Geçiş kapalıysa veya `next` için yığın
+/// alınamazsa hiçbir şey yapmaz.
+pub fn switch(prev: Option<TaskId>, next: TaskId, entry: usize, arg: usize) {
+    if !enabled() || prev == Some(next) {
+        return;
+    }
+    let cpu = percpu::cpu_id();
+    let pair = with_contexts(|tasks, boot| {
+        if tasks.get(next)?.is_none() {
+            match TaskStackAllocator::allocate_stack(next) {
+                Ok(top) => {
+                    tasks[next] = Some(Context::new(entry, arg, top));
+                    crate::canary::assign(next);
+                }
+                Err(e) => {
+                    STACK_FAILURES.inc();
+                    serial_println!("[CONTEXT] Görev {} için yığın alınamadı: {:?}", next, e);
+                    return None;
+                }
+            }
+        }
+        let to: *const Context = tasks[next].as_ref()?;
+        // Kayıt alanı: ilk kez bırakılan görevin (veya açılış akışının)
+        // bağlamı burada oluşur; içeriği `switch` doldurur
+        let from: *mut Context = match prev {
+            Some(p) => tasks.get_mut(p)?.get_or_insert_with(|| Context::new(0, 0, 0)),
+            None => boot[cpu].get_or_insert_with(|| Context::new(0, 0, 0)),
+        };
+        Some((from, to))
+    });
+    if let Some((from, to)) = pair {
+        // Bu çerçeve `prev`'in değeriyle kuruldu; `prev`'e geri geçen taraf
+        // simgeyi yine o değere çevirir
+        crate::canary::switch_to(Some(next));
+        // SAFETY: Tablo statiktir; girişler yalnızca `release` ile boşaltılır ve
+        // çalışan ya da seçilen görevin girişi serbest bırakılmaz.
+        unsafe { (*from).switch(&*to) };
+        // Devam edildi: değer bir kez görülür, sonraki geçişlerde yeniden
+        // yüklenmesin. SAFETY: Çalışan görevin kendi bağlamı; başkası onu
+        // yalnızca görev beklerken yazar.
+        unsafe { (*from).set_return_value(0) };
+    }
+}
+
+/// Yeni görevin ilk işi; mimarinin `task_entry`'si görev işlevinden önce
+/// çağırır. `schedule` geçişi kesmeler kapalıyken yaptığından, ilk kez
+/// çalışan görev önceki görevin geçişini tamamlar ve kesmeleri burada açar.
+pub fn task_started() {
+    crate::sched::finish_switch();
+    crate::irqoff::enable();
+}
+
+/// `id` yeniden çalıştığında `switch` dönüşünde görülecek değeri ayarlar.
+pub fn set_return_value(id: TaskId, value: usize) -> Result<(), KernelError> {
+    with_contexts(|tasks, _| match tasks.get_mut(id) {
+        Some(Some(ctx)) => {
+            ctx.set_return_value(value);
+            Ok(())
+        }
+        _ => Err(KernelError::NotFound),
+    })
+}
+
+/// Sonlanan görevin bağlamını ve yığınını bırakır (`sched::exit`).
+pub fn release(id: TaskId) {
+    let had = with_contexts(|tasks, _| tasks.get_mut(id).and_then(Option::take).is_some());
+    if had {
+        let _ = TaskStackAllocator::deallocate_stack(id);
+        crate::canary::release(id);
+    }
+}
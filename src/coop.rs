@@ -0,0 +1,324 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+#![cfg(feature = "cooperative")]
+
+// src/coop.rs
+// Çok küçük hedefler için tek yığınlı, kesintisiz (run-to-completion) işbirlikçi
+// zamanlama kipi.
+//
+// Görevler sıradan fonksiyonlardır: ana döngü, bekleyen olay bayrağı olan en
+// yüksek öncelikli görevi çağırır, görev işini bitirip döner. Bağlam anahtarlama,
+// görev başına yığın ve öncelik kesmesi (preemption) yoktur; tüm görevler
+// çekirdeğin tek yığınını paylaşır.
+//
+// Olay kaynakları:
+// * `signal` ile gönderilen olay bayrakları (kesme işleyicilerinden çağrılabilir),
+// * yazılım zamanlayıcıları (`timer_start`), zamanlayıcı kesmesinin `tick` çağrısıyla,
+// * göreve bağlanan `IpcQueue`'da bekleyen mesajlar.
+//
+// Cargo özelliği ile seçilir: `[features] cooperative = []`
+
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use crate::ipc::IpcQueue;
+use crate::platformgeneric::spinlock::Spinlock;
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// SABİTLER
+// -----------------------------------------------------------------------------
+
+/// Kaydedilebilecek en fazla işbirlikçi görev sayısı.
+pub const MAX_COOP_TASKS: usize = 16;
+
+/// Aynı anda kurulabilecek en fazla yazılım zamanlayıcısı.
+pub const MAX_SOFT_TIMERS: usize = 16;
+
+/// Bir görev çağrısının aşmaması gereken süre (tik). Kesinti olmadığından aşım
+/// yalnızca raporlanır; uzun işler birden fazla çağrıya bölünmelidir.
+pub const SLICE_TICKS: u64 = 2;
+
+/// Yazılım zamanlayıcısı doldu (zamanlayıcının kendi bayrakları ile birlikte iletilir).
+pub const EVENT_TIMER: u32 = 1 << 31;
+/// Göreve bağlı IPC kuyruğunda mesaj var.
+pub const EVENT_IPC: u32 = 1 << 30;
+/// Uygulamaya ayrılmış olay bitleri.
+pub const EVENT_USER_MASK: u32 = EVENT_IPC - 1;
+
+/// İşbirlikçi görev fonksiyonu. Parametre, çağrı anında bekleyen olay bayraklarıdır.
+pub type CoopTaskFn = fn(events: u32);
+
+/// Görev tablosundaki indeks. Küçük indeks yüksek önceliktir.
+pub type CoopTaskId = usize;
+
+// -----------------------------------------------------------------------------
+// GÖREV TABLOSU
+// -----------------------------------------------------------------------------
+
+/// Kayıtlı bir işbirlikçi görev.
+#[derive(Clone, Copy)]
+struct CoopTask {
+    name: &'static str,
+    func: Option<CoopTaskFn>,
+    /// Mesaj geldiğinde `EVENT_IPC` üreten kuyruk (isteğe bağlı).
+    queue: Option<&'static IpcQueue>,
+    /// Toplam çağrı sayısı.
+    runs: u32,
+    /// `SLICE_TICKS` süresini aşan çağrı sayısı.
+    overruns: u32,
+}
+
+impl CoopTask {
+    const fn empty() -> Self {
+        CoopTask { name: "", func: None, queue: None, runs: 0, overruns: 0 }
+    }
+}
+
+struct TaskTable {
+    lock: Spinlock,
+    entries: UnsafeCell<[CoopTask; MAX_COOP_TASKS]>,
+}
+
+// Tabloya erişim `lock` ile korunur.
+unsafe impl Sync for TaskTable {}
+
+static TASKS: TaskTable = TaskTable {
+    lock: Spinlock::new(),
+    entries: UnsafeCell::new([CoopTask::empty(); MAX_COOP_TASKS]),
+};
+
+// Bekleyen olaylar kilitsiz tutulur; böylece kesme işleyicileri `signal` çağırabilir.
+#[allow(clippy::declare_interior_mutable_const)]
+const NO_EVENTS: AtomicU32 = AtomicU32::new(0);
+static PENDING: [AtomicU32; MAX_COOP_TASKS] = [NO_EVENTS; MAX_COOP_TASKS];
+
+/// Zamanlayıcı kesmesinin artırdığı tik sayacı.
+static TICKS: AtomicU64 = AtomicU64::new(0);
+
+/// Yeni bir görev kaydeder. Öncelik kayıt sırasıdır (ilk kaydedilen en yüksek).
+///
+/// # Parametreler
+/// * `queue`: Verilirse, kuyrukta mesaj bulunduğu sürece göreve `EVENT_IPC` iletilir.
+///
+/// # Dönüş Değeri
+/// Görev kimliği; tablo doluysa `OutOfMemoryStatic`.
+pub fn register(name: &'static str, func: CoopTaskFn, queue: Option<&'static IpcQueue>) -> Result<CoopTaskId, KernelError> {
+    TASKS.lock.lock();
+    let entries = unsafe { &mut *TASKS.entries.get() };
+
+    let result = match entries.iter_mut().enumerate().find(|(_, task)| task.func.is_none()) {
+        Some((id, slot)) => {
+            *slot = CoopTask { name, func: Some(func), queue, runs: 0, overruns: 0 };
+            Ok(id)
+        }
+        None => Err(KernelError::OutOfMemoryStatic),
+    };
+
+    TASKS.lock.unlock();
+    result
+}
+
+/// Göreve olay bayrakları gönderir. Kesme bağlamından çağrılabilir.
+pub fn signal(task: CoopTaskId, flags: u32) -> Result<(), KernelError> {
+    if task >= MAX_COOP_TASKS || flags == 0 {
+        return Err(KernelError::InvalidArgument);
+    }
+    PENDING[task].fetch_or(flags, Ordering::Release);
+    Ok(())
+}
+
+// -----------------------------------------------------------------------------
+// YAZILIM ZAMANLAYICILARI
+// -----------------------------------------------------------------------------
+
+/// Tek atımlık veya periyodik yazılım zamanlayıcısı.
+#[derive(Clone, Copy)]
+struct SoftTimer {
+    task: CoopTaskId,
+    /// Dolduğunda `EVENT_TIMER` ile birlikte iletilecek kullanıcı bayrakları.
+    flags: u32,
+    /// Dolma anı (tik).
+    expires: u64,
+    /// 0 ise tek atımlık.
+    period: u64,
+    active: bool,
+}
+
+impl SoftTimer {
+    const fn empty() -> Self {
+        SoftTimer { task: 0, flags: 0, expires: 0, period: 0, active: false }
+    }
+}
+
+struct TimerTable {
+    lock: Spinlock,
+    entries: UnsafeCell<[SoftTimer; MAX_SOFT_TIMERS]>,
+}
+
+// Tabloya erişim `lock` ile korunur.
+unsafe impl Sync for TimerTable {}
+
+static TIMERS: TimerTable = TimerTable {
+    lock: Spinlock::new(),
+    entries: UnsafeCell::new([SoftTimer::empty(); MAX_SOFT_TIMERS]),
+};
+
+/// Zamanlayıcı kesmesinden her tikte çağrılır. Yalnızca sayacı artırır; süresi
+/// dolan zamanlayıcılar ana döngüde işlenir.
+#[inline]
+pub fn tick() {
+    TICKS.fetch_add(1, Ordering::Release);
+}
+
+/// Açılıştan bu yana geçen tik sayısı.
+pub fn ticks() -> u64 {
+    TICKS.load(Ordering::Acquire)
+}
+
+/// Bir yazılım zamanlayıcısı kurar.
+///
+/// # Parametreler
+/// * `delay`: İlk dolmaya kadar tik sayısı (en az 1).
+/// * `period`: Periyodik zamanlayıcı için tekrar aralığı; 0 ise tek atımlık.
+/// * `flags`: Görevin `EVENT_TIMER` ile birlikte alacağı kullanıcı bayrakları.
+///
+/// # Dönüş Değeri
+/// Zamanlayıcı kimliği; tablo doluysa `OutOfMemoryStatic`.
+pub fn timer_start(task: CoopTaskId, delay: u64, period: u64, flags: u32) -> Result<usize, KernelError> {
+    if task >= MAX_COOP_TASKS || delay == 0 || flags & !EVENT_USER_MASK != 0 {
+        return Err(KernelError::InvalidArgument);
+    }
+
+    TIMERS.lock.lock();
+    let entries = unsafe { &mut *TIMERS.entries.get() };
+
+    let result = match entries.iter_mut().enumerate().find(|(_, t)| !t.active) {
+        Some((id, slot)) => {
+            *slot = SoftTimer { task, flags, expires: ticks() + delay, period, active: true };
+            Ok(id)
+        }
+        None => Err(KernelError::OutOfMemoryStatic),
+    };
+
+    TIMERS.lock.unlock();
+    result
+}
+
+/// Bir yazılım zamanlayıcısını durdurur.
+pub fn timer_stop(id: usize) -> Result<(), KernelError> {
+    if id >= MAX_SOFT_TIMERS {
+        return Err(KernelError::InvalidArgument);
+    }
+
+    TIMERS.lock.lock();
+    let slot = unsafe { &mut (*TIMERS.entries.get())[id] };
+    let result = if slot.active {
+        slot.active = false;
+        Ok(())
+    } else {
+        Err(KernelError::NotFound)
+    };
+    TIMERS.lock.unlock();
+    result
+}
+
+/// Süresi dolan zamanlayıcıların görevlerine `EVENT_TIMER` gönderir.
+fn expire_timers(now: u64) {
+    TIMERS.lock.lock();
+    let entries = unsafe { &mut *TIMERS.entries.get() };
+
+    for timer in entries.iter_mut().filter(|t| t.active && t.expires <= now) {
+        PENDING[timer.task].fetch_or(EVENT_TIMER | timer.flags, Ordering::Release);
+        if timer.period == 0 {
+            timer.active = false;
+        } else {
+            // Kaçırılan periyotlar biriktirilmez; bir sonraki gelecek ana atla
+            let missed = (now - timer.expires) / timer.period;
+            timer.expires += (missed + 1) * timer.period;
+        }
+    }
+
+    TIMERS.lock.unlock();
+}
+
+// -----------------------------------------------------------------------------
+// ANA DÖNGÜ
+// -----------------------------------------------------------------------------
+
+/// Olayı bekleyen en yüksek öncelikli görevi bir kez çalıştırır.
+///
+/// # Dönüş Değeri
+/// Bir görev çalıştırıldıysa `true`.
+pub fn run_once() -> bool {
+    expire_timers(ticks());
+
+    for id in 0..MAX_COOP_TASKS {
+        TASKS.lock.lock();
+        let task = unsafe { (*TASKS.entries.get())[id] };
+        TASKS.lock.unlock();
+
+        let Some(func) = task.func else { continue };
+
+        if task.queue.is_some_and(|q| !q.is_empty()) {
+            PENDING[id].fetch_or(EVENT_IPC, Ordering::Release);
+        }
+
+        let events = PENDING[id].swap(0, Ordering::AcqRel);
+        if events == 0 {
+            continue;
+        }
+
+        let start = ticks();
+        func(events);
+        let elapsed = ticks() - start;
+
+        TASKS.lock.lock();
+        let entry = unsafe { &mut (*TASKS.entries.get())[id] };
+        entry.runs = entry.runs.wrapping_add(1);
+        if elapsed > SLICE_TICKS {
+            entry.overruns = entry.overruns.wrapping_add(1);
+        }
+        TASKS.lock.unlock();
+
+        if elapsed > SLICE_TICKS {
+            serial_println!("[COOP] '{}' dilim süresini aştı: {} tik", task.name, elapsed);
+        }
+        return true;
+    }
+    false
+}
+
+/// İşbirlikçi zamanlayıcının ana döngüsü. Her görev çağrısından sonra tarama en
+/// yüksek öncelikten yeniden başlar; çalıştırılacak görev yoksa işlemci bir
+/// sonraki kesmeye kadar döngüde bekler.
+pub fn run() -> ! {
+    serial_println!("[COOP] İşbirlikçi zamanlayıcı başlatıldı.");
+    loop {
+        if !run_once() {
+            core::hint::spin_loop();
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// KABUK KOMUTU
+// -----------------------------------------------------------------------------
+
+fn coop_command(_args: &[&str]) -> Result<(), KernelError> {
+    serial_println!("  ID AD               ÇAĞRI    AŞIM  BEKLEYEN");
+    for id in 0..MAX_COOP_TASKS {
+        TASKS.lock.lock();
+        let task = unsafe { (*TASKS.entries.get())[id] };
+        TASKS.lock.unlock();
+
+        if task.func.is_some() {
+            serial_println!(
+                "  {:>2} {:<16} {:>6} {:>6}  {:#010x}",
+                id, task.name, task.runs, task.overruns, PENDING[id].load(Ordering::Relaxed)
+            );
+        }
+    }
+    Ok(())
+}
+
+crate::shell_command!("coop", "İşbirlikçi görev istatistikleri", coop_command);
@@ -0,0 +1,387 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/coredump.rs
+// Ölümcül hata veren kullanıcı görevleri için ELF çekirdek dökümü (core file).
+//
+// Döküm standart ELF64 `ET_CORE` biçimindedir; GDB gibi araçlar doğrudan
+// açabilir (`gdb <uygulama> core`):
+//
+//   ELF başlığı | program başlıkları | PT_NOTE | PT_LOAD bölümleri...
+//
+// Not bölümü `NT_PRSTATUS` (sinyal, görev kimliği ve tuzak çerçevesindeki
+// yazmaçlar) ve `NT_PRPSINFO` (görev adı) içerir. Yazmaçlar mimarinin ELF
+// `elf_gregset_t` sırasında verilir (örn. RISC-V: pc, x1..x31). Görevin
+// adres alanı henüz ayrı tutulmadığından bellek bölümleri görev yığını ile
+// hata adresini ve PC'yi içeren sayfalardır. Bellek `fixup::probe_kernel_read`
+// ile okunur; okunamayan parçalar sıfır olarak yazılır.
+//
+// Döküm `coredump.mode` ayarına göre VFS'e (`/core/<ad>.<görev>`) yazılır
+// veya seri konsola parçalar halinde akıtılır. VFS yazımı başarısız olursa
+// seriye düşülür. Seri biçim:
+//
+//   [CORE] BEGIN <ad>.<görev>
+//   [CORE] <onaltılık veri, satır başına 32 bayt>
+//   [CORE] END crc32=<crc>
+//
+// Sunucuda: `grep '^\[CORE\] [0-9a-f]*$' log | cut -c8- | xxd -r -p > core`
+
+use core::fmt::{self, Write};
+use crate::fixup;
+use crate::memory::memory::{MemoryRegions, TaskStackAllocator};
+use crate::platformgeneric::KernelError;
+use crate::sched::{self, TaskId};
+use crate::serial_println;
+use crate::sysctl::U32;
+use crate::trap::Cause;
+use crate::update::crc32_update;
+
+// -----------------------------------------------------------------------------
+// AYARLAR
+// -----------------------------------------------------------------------------
+
+/// Döküm kapalı.
+pub const MODE_OFF: u32 = 0;
+/// VFS'e yaz; başarısızsa seriye akıt.
+pub const MODE_VFS: u32 = 1;
+/// Yalnızca seriye akıt.
+pub const MODE_SERIAL: u32 = 2;
+
+crate::sysctl!(MODE: U32 = U32::new(MODE_VFS, MODE_OFF, MODE_SERIAL),
+    "coredump.mode", "Çekirdek dökümü: 0 kapalı, 1 VFS (yoksa seri), 2 seri");
+
+/// Dökümlerin yazıldığı dizin.
+pub const CORE_DIR: &str = "/core";
+
+crate::kernel_counter!(DUMPS_WRITTEN, "coredump.written");
+crate::kernel_counter!(DUMPS_FAILED, "coredump.failed");
+
+// -----------------------------------------------------------------------------
+// ELF SABİTLERİ
+// -----------------------------------------------------------------------------
+
+const ET_CORE: u16 = 4;
+const PT_LOAD: u32 = 1;
+const PT_NOTE: u32 = 4;
+const PF_R: u32 = 4;
+const PF_W: u32 = 2;
+const PF_X: u32 = 1;
+const NT_PRSTATUS: u32 = 1;
+const NT_PRPSINFO: u32 = 3;
+
+const EHDR_LEN: usize = 64;
+const PHDR_LEN: usize = 56;
+const NOTE_NAME: &[u8; 8] = b"CORE\0\0\0\0";
+/// `elf_prstatus` içinde yazmaçlardan önceki alanlar (sinyal, kimlikler, süreler).
+const PRSTATUS_HEAD: usize = 112;
+/// `elf_prpsinfo` boyutu (64 bit).
+const PRPSINFO_LEN: usize = 136;
+
+/// En fazla bellek bölümü: yığın, hata sayfası, PC sayfası.
+const MAX_SEGMENTS: usize = 3;
+const PAGE_SIZE: usize = 4096;
+
+#[cfg(target_arch = "x86_64")]
+const EM_MACHINE: u16 = 62;
+#[cfg(target_arch = "aarch64")]
+const EM_MACHINE: u16 = 183;
+#[cfg(target_arch = "riscv64")]
+const EM_MACHINE: u16 = 243;
+#[cfg(target_arch = "powerpc64")]
+const EM_MACHINE: u16 = 21;
+#[cfg(target_arch = "sparc64")]
+const EM_MACHINE: u16 = 43;
+#[cfg(target_arch = "loongarch64")]
+const EM_MACHINE: u16 = 258;
+#[cfg(target_arch = "mips64")]
+const EM_MACHINE: u16 = 8;
+#[cfg(target_arch = "or1k")]
+const EM_MACHINE: u16 = 92;
+
+/// `EI_DATA`: 1 küçük uçlu, 2 büyük uçlu. Alanlar yerel sırada yazılır.
+const EI_DATA: u8 = if cfg!(target_endian = "little") { 1 } else { 2 };
+
+/// Tuzak nedenine karşılık gelen POSIX sinyal numarası.
+pub fn signal_for(cause: Cause) -> u32 {
+    match cause {
+        Cause::IllegalInstruction => 4, // SIGILL
+        Cause::Breakpoint | Cause::SingleStep | Cause::Watchpoint { .. } => 5, // SIGTRAP
+        Cause::Misaligned { .. } | Cause::MachineError => 7, // SIGBUS
+        _ => 11, // SIGSEGV
+    }
+}
+
+// -----------------------------------------------------------------------------
+// ÇIKIŞ HEDEFLERİ
+// -----------------------------------------------------------------------------
+
+trait Sink {
+    fn emit(&mut self, data: &[u8]) -> Result<(), KernelError>;
+}
+
+/// Sabit arabellekte yol biçimlendirmek için.
+struct PathBuf {
+    buf: [u8; 64],
+    len: usize,
+}
+
+impl PathBuf {
+    fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.buf[..self.len]).unwrap_or("")
+    }
+}
+
+impl Write for PathBuf {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        if end > self.buf.len() {
+            return Err(fmt::Error);
+        }
+        self.buf[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+struct VfsSink<'a> {
+    path: &'a str,
+    offset: usize,
+}
+
+impl Sink for VfsSink<'_> {
+    fn emit(&mut self, data: &[u8]) -> Result<(), KernelError> {
+        let mut done = 0;
+        while done < data.len() {
+            let n = crate::vfs::write(self.path, self.offset, &data[done..])?;
+            if n == 0 {
+                return Err(KernelError::OutOfMemoryStatic);
+            }
+            done += n;
+            self.offset += n;
+        }
+        Ok(())
+    }
+}
+
+/// Onaltılık satırlar halinde seri konsola akıtır.
+struct SerialSink {
+    line: [u8; SERIAL_LINE],
+    fill: usize,
+    crc: u32,
+}
+
+const SERIAL_LINE: usize = 32;
+
+impl SerialSink {
+    fn flush_line(&mut self) {
+        if self.fill == 0 {
+            return;
+        }
+        crate::serial_print!("[CORE] ");
+        for byte in &self.line[..self.fill] {
+            crate::serial_print!("{:02x}", byte);
+        }
+        serial_println!();
+        self.fill = 0;
+    }
+}
+
+impl Sink for SerialSink {
+    fn emit(&mut self, data: &[u8]) -> Result<(), KernelError> {
+        self.crc = crc32_update(self.crc, data);
+        for &byte in data {
+            self.line[self.fill] = byte;
+            self.fill += 1;
+            if self.fill == SERIAL_LINE {
+                self.flush_line();
+            }
+        }
+        Ok(())
+    }
+}
+
+// -----------------------------------------------------------------------------
+// DÖKÜM
+// -----------------------------------------------------------------------------
+
+#[derive(Clone, Copy)]
+struct Segment {
+    vaddr: usize,
+    len: usize,
+    flags: u32,
+}
+
+/// Çakışmayan bellek bölümlerini toplar.
+fn collect_segments(task: TaskId, pc: usize, fault: Option<usize>, out: &mut [Segment; MAX_SEGMENTS]) -> usize {
+    let mut count = 0;
+    let mut add = |vaddr: usize, len: usize, flags: u32| {
+        let overlaps = out[..count].iter().any(|s| vaddr < s.vaddr + s.len && s.vaddr < vaddr + len);
+        if !overlaps && count < MAX_SEGMENTS {
+            out[count] = Segment { vaddr, len, flags };
+            count += 1;
+        }
+    };
+    if TaskStackAllocator::high_water_mark(task).is_some() {
+        if let Some(base) = TaskStackAllocator::get_stack_base_address(task) {
+            add(base, MemoryRegions::TASK_STACK_SIZE, PF_R | PF_W);
+        }
+    }
+    if let Some(addr) = fault {
+        add(addr & !(PAGE_SIZE - 1), PAGE_SIZE, PF_R | PF_W);
+    }
+    add(pc & !(PAGE_SIZE - 1), PAGE_SIZE, PF_R | PF_X);
+    count
+}
+
+fn put16(buf: &mut [u8], at: usize, v: u16) {
+    buf[at..at + 2].copy_from_slice(&v.to_ne_bytes());
+}
+fn put32(buf: &mut [u8], at: usize, v: u32) {
+    buf[at..at + 4].copy_from_slice(&v.to_ne_bytes());
+}
+fn put64(buf: &mut [u8], at: usize, v: u64) {
+    buf[at..at + 8].copy_from_slice(&v.to_ne_bytes());
+}
+
+fn note_len(desc: usize) -> usize {
+    12 + NOTE_NAME.len() + desc.next_multiple_of(4)
+}
+
+fn emit_note_header(sink: &mut dyn Sink, kind: u32, desc: usize) -> Result<(), KernelError> {
+    let mut head = [0u8; 12];
+    put32(&mut head, 0, 5); // "CORE\0"
+    put32(&mut head, 4, desc as u32);
+    put32(&mut head, 8, kind);
+    sink.emit(&head)?;
+    sink.emit(NOTE_NAME)
+}
+
+fn write_core(sink: &mut dyn Sink, task: TaskId, cause: Cause, regs: &[u64], segments: &[Segment]) -> Result<(), KernelError> {
+    let prstatus_len = PRSTATUS_HEAD + regs.len() * 8 + 8;
+    let notes_len = note_len(prstatus_len) + note_len(PRPSINFO_LEN);
+    let phnum = 1 + segments.len();
+    let notes_offset = EHDR_LEN + phnum * PHDR_LEN;
+
+    // ELF başlığı
+    let mut ehdr = [0u8; EHDR_LEN];
+    ehdr[..4].copy_from_slice(b"\x7fELF");
+    ehdr[4] = 2; // ELFCLASS64
+    ehdr[5] = EI_DATA;
+    ehdr[6] = 1; // EV_CURRENT
+    put16(&mut ehdr, 16, ET_CORE);
+    put16(&mut ehdr, 18, EM_MACHINE);
+    put32(&mut ehdr, 20, 1);
+    put64(&mut ehdr, 32, EHDR_LEN as u64); // e_phoff
+    put16(&mut ehdr, 52, EHDR_LEN as u16);
+    put16(&mut ehdr, 54, PHDR_LEN as u16);
+    put16(&mut ehdr, 56, phnum as u16);
+    sink.emit(&ehdr)?;
+
+    // Program başlıkları
+    let mut phdr = [0u8; PHDR_LEN];
+    put32(&mut phdr, 0, PT_NOTE);
+    put64(&mut phdr, 8, notes_offset as u64);
+    put64(&mut phdr, 32, notes_len as u64);
+    put64(&mut phdr, 48, 4);
+    sink.emit(&phdr)?;
+    let mut offset = notes_offset + notes_len;
+    for seg in segments {
+        let mut phdr = [0u8; PHDR_LEN];
+        put32(&mut phdr, 0, PT_LOAD);
+        put32(&mut phdr, 4, seg.flags);
+        put64(&mut phdr, 8, offset as u64);
+        put64(&mut phdr, 16, seg.vaddr as u64);
+        put64(&mut phdr, 32, seg.len as u64);
+        put64(&mut phdr, 40, seg.len as u64);
+        put64(&mut phdr, 48, 1);
+        sink.emit(&phdr)?;
+        offset += seg.len;
+    }
+
+    // NT_PRSTATUS: sinyal, kimlik ve yazmaçlar
+    let signal = signal_for(cause);
+    emit_note_header(sink, NT_PRSTATUS, prstatus_len)?;
+    let mut head = [0u8; PRSTATUS_HEAD];
+    put32(&mut head, 0, signal); // pr_info.si_signo
+    put16(&mut head, 12, signal as u16); // pr_cursig
+    put32(&mut head, 32, task as u32); // pr_pid
+    if let Some(info) = sched::task_info(task) {
+        // pr_utime: çalışma süresi (tik → sn/µs yaklaşık)
+        let hz = sched::tick_hz().max(1);
+        put64(&mut head, 48, info.runtime_ticks / hz);
+        put64(&mut head, 56, (info.runtime_ticks % hz) * 1_000_000 / hz);
+    }
+    sink.emit(&head)?;
+    for reg in regs {
+        sink.emit(&reg.to_ne_bytes())?;
+    }
+    sink.emit(&[0u8; 8])?; // pr_fpvalid + dolgu
+
+    // NT_PRPSINFO: görev adı
+    emit_note_header(sink, NT_PRPSINFO, PRPSINFO_LEN)?;
+    let mut psinfo = [0u8; PRPSINFO_LEN];
+    psinfo[1] = b'R'; // pr_sname
+    put32(&mut psinfo, 24, task as u32); // pr_pid
+    let name = sched::task_info(task).map_or("?", |info| info.name).as_bytes();
+    let n = name.len().min(15);
+    psinfo[40..40 + n].copy_from_slice(&name[..n]); // pr_fname
+    psinfo[56..56 + n].copy_from_slice(&name[..n]); // pr_psargs
+    sink.emit(&psinfo)?;
+
+    // Bellek bölümleri
+    let mut chunk = [0u8; 256];
+    for seg in segments {
+        let mut done = 0;
+        while done < seg.len {
+            let n = chunk.len().min(seg.len - done);
+            if fixup::probe_kernel_read(seg.vaddr + done, &mut chunk[..n]).is_err() {
+                chunk[..n].fill(0);
+            }
+            sink.emit(&chunk[..n])?;
+            done += n;
+        }
+    }
+    Ok(())
+}
+
+/// Görevin çekirdek dökümünü `coredump.mode` ayarına göre üretir.
+///
+/// # Parametreler
+/// * `task`: Hata veren görev.
+/// * `cause`: Tuzak nedeni (sinyal numarası buradan türetilir).
+/// * `pc`: Hatanın oluştuğu talimat adresi.
+/// * `regs`: Mimarinin `elf_gregset_t` sırasındaki yazmaçları.
+pub fn dump(task: TaskId, cause: Cause, pc: usize, regs: &[u64]) -> Result<(), KernelError> {
+    let mode = MODE.get();
+    if mode == MODE_OFF {
+        return Ok(());
+    }
+    let mut segments = [Segment { vaddr: 0, len: 0, flags: 0 }; MAX_SEGMENTS];
+    let count = collect_segments(task, pc, cause.fault_addr(), &mut segments);
+    let segments = &segments[..count];
+    let name = sched::task_info(task).map_or("task", |info| info.name);
+
+    if mode == MODE_VFS {
+        let mut path = PathBuf { buf: [0; 64], len: 0 };
+        write!(path, "{}/{}.{}", CORE_DIR, name, task).map_err(|_| KernelError::InvalidArgument)?;
+        let mut sink = VfsSink { path: path.as_str(), offset: 0 };
+        match write_core(&mut sink, task, cause, regs, segments) {
+            Ok(()) => {
+                DUMPS_WRITTEN.inc();
+                serial_println!("[CORE] {} yazıldı ({} bayt)", path.as_str(), sink.offset);
+                return Ok(());
+            }
+            Err(e) => serial_println!("[CORE] {} yazılamadı ({:?}), seriye akıtılıyor", path.as_str(), e),
+        }
+    }
+
+    let mut sink = SerialSink { line: [0; SERIAL_LINE], fill: 0, crc: 0 };
+    serial_println!("[CORE] BEGIN {}.{}", name, task);
+    let result = write_core(&mut sink, task, cause, regs, segments);
+    sink.flush_line();
+    serial_println!("[CORE] END crc32={:08x}", sink.crc);
+    match result {
+        Ok(()) => DUMPS_WRITTEN.inc(),
+        Err(_) => DUMPS_FAILED.inc(),
+    }
+    result
+}
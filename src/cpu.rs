@@ -0,0 +1,171 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/cpu.rs
+// İşlemci özelliği algılama ve yetenek raporu.
+//
+// İsteğe bağlı talimatlar kullanan kod yolları (şifreleme hızlandırma,
+// donanım rastgelelik, büyük sayfalar, atomik talimatlar) yazmaç
+// bitlerini kendileri okumak yerine `features()` sonucuna bakar. Algılama
+// mimariye özgüdür:
+// * amd64: CPUID yaprakları 1, 7.0 ve 0x8000_0001.
+// * armv9: MIDR_EL1 ve ID_AA64PFR0/ISAR0/MMFR0_EL1.
+// * rv64i: aygıt ağacındaki `riscv,isa` dizesi (misa yalnızca M kipinde okunur).
+// * powerpc64: PVR sürümüne göre işlemci kuşağı.
+// * sparcv9: %ver (UltraSPARC'ta sabit özellikler).
+// * loongarch64: CPUCFG kelimeleri 0-2.
+// * mips64: PRId ve Config1/Config3.
+// * openrisc64: VR2, UPR ve CPUCFGR.
+//
+// Sonuç ilk çağrıda hesaplanır ve saklanır; açılışta bir kez yazdırılır.
+//
+// Kabuk komutu: `cpuinfo`; procfs: `/proc/cpuinfo`
+
+use core::fmt::{self, Write};
+use core::sync::atomic::{AtomicBool, AtomicU16, AtomicU64, Ordering};
+use crate::arch::cpu as arch_cpu;
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// YETENEKLER
+// -----------------------------------------------------------------------------
+
+/// Algılanan işlemci yetenekleri.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CpuFeatures {
+    /// Ham işlemci kimliği (CPUID.1:EAX, MIDR, PVR, PRId, %ver, VR2...).
+    pub id: u64,
+    /// Kayan nokta birimi.
+    pub fpu: bool,
+    /// SIMD/vektör birimi (SSE2, AdvSIMD, V, VMX, VIS, LSX, MSA).
+    pub simd: bool,
+    /// AES talimatları.
+    pub aes: bool,
+    /// SHA-256 talimatları.
+    pub sha2: bool,
+    /// CRC32 talimatları.
+    pub crc32: bool,
+    /// Tek talimatlı atomik okuma-değiştirme-yazma (LSE, A, CMPXCHG16B, AM*).
+    pub atomics: bool,
+    /// 2 MiB (veya mimarinin orta boy) büyük sayfaları.
+    pub large_pages: bool,
+    /// 1 GiB (veya daha büyük) dev sayfalar.
+    pub huge_pages: bool,
+    /// Donanım rastgele sayı üreteci (RDRAND, RNDR, DARN, Zkr).
+    pub rng: bool,
+}
+
+impl CpuFeatures {
+    /// Ad ve değer çiftleri (rapor sırası).
+    pub fn flags(&self) -> [(&'static str, bool); 9] {
+        [
+            ("fpu", self.fpu),
+            ("simd", self.simd),
+            ("aes", self.aes),
+            ("sha2", self.sha2),
+            ("crc32", self.crc32),
+            ("atomics", self.atomics),
+            ("large-pages", self.large_pages),
+            ("huge-pages", self.huge_pages),
+            ("rng", self.rng),
+        ]
+    }
+
+    fn to_bits(self) -> u16 {
+        self.flags().iter().enumerate().fold(0, |bits, (i, (_, on))| bits | (*on as u16) << i)
+    }
+
+    fn from_bits(id: u64, bits: u16) -> Self {
+        let bit = |i: u32| bits & (1 << i) != 0;
+        CpuFeatures {
+            id,
+            fpu: bit(0),
+            simd: bit(1),
+            aes: bit(2),
+            sha2: bit(3),
+            crc32: bit(4),
+            atomics: bit(5),
+            large_pages: bit(6),
+            huge_pages: bit(7),
+            rng: bit(8),
+        }
+    }
+
+    /// Etkin yetenekleri boşlukla ayrılmış olarak yazar (hiçbiri yoksa `-`).
+    pub fn write_flags(&self, out: &mut dyn Write) -> fmt::Result {
+        let mut any = false;
+        for (name, _) in self.flags().iter().filter(|(_, on)| *on) {
+            if any {
+                out.write_str(" ")?;
+            }
+            out.write_str(name)?;
+            any = true;
+        }
+        if !any {
+            out.write_str("-")?;
+        }
+        Ok(())
+    }
+}
+
+// -----------------------------------------------------------------------------
+// ALGILAMA
+// -----------------------------------------------------------------------------
+
+static DETECTED: AtomicBool = AtomicBool::new(false);
+static ID: AtomicU64 = AtomicU64::new(0);
+static BITS: AtomicU16 = AtomicU16::new(0);
+
+/// İşlemci yeteneklerini algılar (ilk çağrıda) ve sonucu döndürür.
+///
+/// Algılama yan etkisizdir; eşzamanlı ilk çağrılar aynı sonucu yazar.
+pub fn features() -> CpuFeatures {
+    if DETECTED.load(Ordering::Acquire) {
+        return CpuFeatures::from_bits(ID.load(Ordering::Relaxed), BITS.load(Ordering::Relaxed));
+    }
+
+    let features = arch_cpu::detect();
+    ID.store(features.id, Ordering::Relaxed);
+    BITS.store(features.to_bits(), Ordering::Relaxed);
+    DETECTED.store(true, Ordering::Release);
+    features
+}
+
+/// Açılışta yetenekleri yazdırır.
+fn init() -> Result<(), KernelError> {
+    let features = features();
+    let mut console = crate::shell::Console;
+    let _ = write!(console, "[CPU] {} kimlik {:#x} | ", crate::version::ARCH, features.id);
+    let _ = features.write_flags(&mut console);
+    serial_println!();
+    Ok(())
+}
+
+crate::initcall!(earlycon, "cpu", init);
+
+// -----------------------------------------------------------------------------
+// PROCFS VE KABUK
+// -----------------------------------------------------------------------------
+
+fn show_cpuinfo(out: &mut dyn Write) -> fmt::Result {
+    let features = features();
+    writeln!(out, "arch {}", crate::version::ARCH)?;
+    writeln!(out, "id {:#x}", features.id)?;
+    out.write_str("flags ")?;
+    features.write_flags(out)?;
+    out.write_str("\n")
+}
+
+crate::proc_entry!("cpuinfo", show_cpuinfo);
+
+fn cpuinfo_command(_args: &[&str]) -> Result<(), KernelError> {
+    let features = features();
+    serial_println!("  mimari    {}", crate::version::ARCH);
+    serial_println!("  kimlik    {:#x}", features.id);
+    for (name, on) in features.flags() {
+        serial_println!("  {:<12} {}", name, if on { "var" } else { "yok" });
+    }
+    Ok(())
+}
+
+crate::shell_command!("cpuinfo", "İşlemci yeteneklerini gösterir", cpuinfo_command);
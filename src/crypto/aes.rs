@@ -0,0 +1,167 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/crypto/aes.rs
+// AES-128 blok şifreleyici (FIPS-197), yalnızca şifreleme yönü (GCM ve CTR
+// için yeterlidir).
+//
+// Yazılım gerçeklemesi sabit zamanlıdır: S-kutusu tablo araması yerine
+// GF(2^8) tersi (x^254) ve afin dönüşümle hesaplanır. Çarpmalar sekiz
+// baytı bir `u64` içinde paralel işler (SWAR); böylece bir tur iki kelime
+// üzerinde yapılır. Kayıtlı donanım hızlandırıcısı varsa bloklar ona
+// devredilir.
+
+use crate::crypto::crypto::{self, AesAccel};
+
+/// Anahtar ve blok uzunluğu (bayt).
+pub const KEY_LEN: usize = 16;
+pub const BLOCK_LEN: usize = 16;
+const ROUNDS: usize = 10;
+
+const LO: u64 = 0x0101_0101_0101_0101;
+const HI7: u64 = 0x7f7f_7f7f_7f7f_7f7f;
+
+// -----------------------------------------------------------------------------
+// SABİT ZAMANLI S-KUTUSU (8 BAYT PARALEL)
+// -----------------------------------------------------------------------------
+
+/// Her bayt için x·2 (mod x^8 + x^4 + x^3 + x + 1).
+#[inline(always)]
+fn xtime(x: u64) -> u64 {
+    ((x & HI7) << 1) ^ (((x >> 7) & LO) * 0x1b)
+}
+
+/// Bayt bayt GF(2^8) çarpımı.
+#[inline(always)]
+fn gmul(mut a: u64, b: u64) -> u64 {
+    let mut r = 0;
+    for i in 0..8 {
+        r ^= a & (((b >> i) & LO) * 0xff);
+        a = xtime(a);
+    }
+    r
+}
+
+/// Bayt bayt sola döndürme.
+#[inline(always)]
+fn rotl8(x: u64, k: u32) -> u64 {
+    let keep = LO * (0xffu64 >> k);
+    ((x & keep) << k) | ((x >> (8 - k)) & (LO * ((1u64 << k) - 1)))
+}
+
+/// Sekiz baytlık S-kutusu: ters (x^254; 0 → 0) ve afin dönüşüm.
+fn sub_bytes64(x: u64) -> u64 {
+    let x2 = gmul(x, x);
+    let x3 = gmul(x2, x);
+    let x6 = gmul(x3, x3);
+    let x12 = gmul(x6, x6);
+    let x15 = gmul(x12, x3);
+    let x30 = gmul(x15, x15);
+    let x60 = gmul(x30, x30);
+    let x120 = gmul(x60, x60);
+    let x240 = gmul(x120, x120);
+    let x252 = gmul(x240, x12);
+    let inv = gmul(x252, x2);
+    inv ^ rotl8(inv, 1) ^ rotl8(inv, 2) ^ rotl8(inv, 3) ^ rotl8(inv, 4) ^ (LO * 0x63)
+}
+
+fn sub_bytes(state: &mut [u8; 16]) {
+    for half in state.chunks_exact_mut(8) {
+        let word = u64::from_le_bytes(half.try_into().unwrap());
+        half.copy_from_slice(&sub_bytes64(word).to_le_bytes());
+    }
+}
+
+fn shift_rows(s: &mut [u8; 16]) {
+    // Durum sütun öncelikli: s[r + 4c]
+    let t = *s;
+    for r in 1..4 {
+        for c in 0..4 {
+            s[r + 4 * c] = t[r + 4 * ((c + r) % 4)];
+        }
+    }
+}
+
+fn mix_columns(s: &mut [u8; 16]) {
+    let xt = |b: u8| (b << 1) ^ (((b >> 7) & 1) * 0x1b);
+    for col in s.chunks_exact_mut(4) {
+        let [a0, a1, a2, a3] = [col[0], col[1], col[2], col[3]];
+        let all = a0 ^ a1 ^ a2 ^ a3;
+        col[0] = a0 ^ all ^ xt(a0 ^ a1);
+        col[1] = a1 ^ all ^ xt(a1 ^ a2);
+        col[2] = a2 ^ all ^ xt(a2 ^ a3);
+        col[3] = a3 ^ all ^ xt(a3 ^ a0);
+    }
+}
+
+fn add_round_key(s: &mut [u8; 16], key: &[u8; 16]) {
+    s.iter_mut().zip(key).for_each(|(b, k)| *b ^= k);
+}
+
+// -----------------------------------------------------------------------------
+// AES-128
+// -----------------------------------------------------------------------------
+
+/// Genişletilmiş anahtarlı AES-128 şifreleyici.
+#[derive(Clone)]
+pub struct Aes128 {
+    round_keys: [[u8; 16]; ROUNDS + 1],
+    accel: Option<&'static dyn AesAccel>,
+}
+
+impl Aes128 {
+    pub fn new(key: &[u8; KEY_LEN]) -> Self {
+        let mut w = [[0u8; 4]; 4 * (ROUNDS + 1)];
+        for (i, word) in key.chunks_exact(4).enumerate() {
+            w[i].copy_from_slice(word);
+        }
+        let mut rcon = 1u8;
+        for i in 4..w.len() {
+            let mut t = w[i - 1];
+            if i % 4 == 0 {
+                // RotWord + SubWord + Rcon
+                let rotated = u32::from_le_bytes([t[1], t[2], t[3], t[0]]);
+                let sub = sub_bytes64(u64::from(rotated)) as u32;
+                t = sub.to_le_bytes();
+                t[0] ^= rcon;
+                rcon = (rcon << 1) ^ (((rcon >> 7) & 1) * 0x1b);
+            }
+            for j in 0..4 {
+                w[i][j] = w[i - 4][j] ^ t[j];
+            }
+        }
+
+        let mut round_keys = [[0u8; 16]; ROUNDS + 1];
+        for (r, rk) in round_keys.iter_mut().enumerate() {
+            for c in 0..4 {
+                rk[4 * c..4 * c + 4].copy_from_slice(&w[4 * r + c]);
+            }
+        }
+        Aes128 { round_keys, accel: crypto::aes_accel() }
+    }
+
+    /// Tek bloğu yerinde şifreler.
+    pub fn encrypt_block(&self, block: &mut [u8; BLOCK_LEN]) {
+        if let Some(accel) = self.accel {
+            accel.encrypt_block(&self.round_keys, block);
+            return;
+        }
+        add_round_key(block, &self.round_keys[0]);
+        for round in 1..ROUNDS {
+            sub_bytes(block);
+            shift_rows(block);
+            mix_columns(block);
+            add_round_key(block, &self.round_keys[round]);
+        }
+        sub_bytes(block);
+        shift_rows(block);
+        add_round_key(block, &self.round_keys[ROUNDS]);
+    }
+}
+
+impl Drop for Aes128 {
+    fn drop(&mut self) {
+        for rk in self.round_keys.iter_mut() {
+            crypto::zeroize(rk);
+        }
+    }
+}
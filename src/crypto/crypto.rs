@@ -0,0 +1,215 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/crypto/crypto.rs
+// Şifreleme temel işlevleri: SHA-256 (`sha256`), SHA-512 (`sha512`),
+// HMAC-SHA256 (`hmac`), AES-128 (`aes`), AES-128-GCM (`gcm`) ve Ed25519
+// imza doğrulaması (`ed25519`, görüntü politikası `signature`).
+//
+// Yazılım gerçeklemeleri sabit zamanlıdır: gizli veriye bağlı dallanma veya
+// tablo indeksleme yoktur (AES S-kutusu tablo yerine GF(2^8) tersiyle
+// hesaplanır). Mimari kodu, işlemci destekliyorsa donanım hızlandırıcılarını
+// (AES-NI, ARMv8 Crypto Extensions) `register_aes_accel` /
+// `register_sha256_accel` ile kaydeder; sonradan oluşturulan bağlamlar
+// bunları kullanır. Açılışta bilinen test vektörleriyle öz sınama yapılır.
+
+use core::cell::UnsafeCell;
+use crate::platformgeneric::spinlock::Spinlock;
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// SABİT ZAMANLI YARDIMCILAR
+// -----------------------------------------------------------------------------
+
+/// İki dizinin eşitliğini içeriğe bağlı erken çıkış olmadan karşılaştırır
+/// (MAC/etiket doğrulaması için). Uzunluklar gizli sayılmaz.
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let diff = a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y));
+    // Derleyicinin karşılaştırmayı kısa devreye çevirmesini engeller
+    core::hint::black_box(diff) == 0
+}
+
+/// Gizli veriyi tutan arabelleği derleyicinin silmeyeceği şekilde sıfırlar.
+pub fn zeroize(buf: &mut [u8]) {
+    for b in buf.iter_mut() {
+        unsafe { core::ptr::write_volatile(b, 0) };
+    }
+    core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+}
+
+// -----------------------------------------------------------------------------
+// DONANIM HIZLANDIRMA KANCALARI
+// -----------------------------------------------------------------------------
+
+/// Donanım AES blok şifreleyicisi.
+pub trait AesAccel: Sync {
+    fn name(&self) -> &'static str;
+
+    /// FIPS-197 ile genişletilmiş 11 tur anahtarıyla tek blok şifreler.
+    fn encrypt_block(&self, round_keys: &[[u8; 16]; 11], block: &mut [u8; 16]);
+}
+
+/// Donanım SHA-256 sıkıştırma işlevi.
+pub trait Sha256Accel: Sync {
+    fn name(&self) -> &'static str;
+
+    /// Durumu tek bir 64 baytlık blokla günceller.
+    fn compress(&self, state: &mut [u32; 8], block: &[u8; 64]);
+}
+
+struct Accelerators {
+    lock: Spinlock,
+    aes: UnsafeCell<Option<&'static dyn AesAccel>>,
+    sha256: UnsafeCell<Option<&'static dyn Sha256Accel>>,
+}
+
+// Erişim `lock` ile korunur.
+unsafe impl Sync for Accelerators {}
+
+static ACCEL: Accelerators = Accelerators {
+    lock: Spinlock::new(),
+    aes: UnsafeCell::new(None),
+    sha256: UnsafeCell::new(None),
+};
+
+/// AES hızlandırıcısını kaydeder (mimari güvenlik başlatmasından).
+pub fn register_aes_accel(accel: &'static dyn AesAccel) {
+    ACCEL.lock.lock();
+    unsafe { *ACCEL.aes.get() = Some(accel) };
+    ACCEL.lock.unlock();
+    serial_println!("[CRYPTO] AES hızlandırıcısı: {}", accel.name());
+}
+
+/// SHA-256 hızlandırıcısını kaydeder.
+pub fn register_sha256_accel(accel: &'static dyn Sha256Accel) {
+    ACCEL.lock.lock();
+    unsafe { *ACCEL.sha256.get() = Some(accel) };
+    ACCEL.lock.unlock();
+    serial_println!("[CRYPTO] SHA-256 hızlandırıcısı: {}", accel.name());
+}
+
+pub fn aes_accel() -> Option<&'static dyn AesAccel> {
+    ACCEL.lock.lock();
+    let accel = unsafe { *ACCEL.aes.get() };
+    ACCEL.lock.unlock();
+    accel
+}
+
+pub fn sha256_accel() -> Option<&'static dyn Sha256Accel> {
+    ACCEL.lock.lock();
+    let accel = unsafe { *ACCEL.sha256.get() };
+    ACCEL.lock.unlock();
+    accel
+}
+
+// -----------------------------------------------------------------------------
+// ÖZ SINAMA
+// -----------------------------------------------------------------------------
+
+/// Onaltılık dizgeyi (test vektörleri için) diziye çevirir.
+pub(crate) const fn hex<const N: usize>(s: &str) -> [u8; N] {
+    const fn nibble(c: u8) -> u8 {
+        match c {
+            b'0'..=b'9' => c - b'0',
+            b'a'..=b'f' => c - b'a' + 10,
+            _ => panic!("geçersiz onaltılık basamak"),
+        }
+    }
+    let s = s.as_bytes();
+    assert!(s.len() == 2 * N);
+    let mut out = [0u8; N];
+    let mut i = 0;
+    while i < N {
+        out[i] = (nibble(s[2 * i]) << 4) | nibble(s[2 * i + 1]);
+        i += 1;
+    }
+    out
+}
+
+/// FIPS 180-4, RFC 4231, GCM belirtimi ve RFC 8032 test vektörleriyle öz
+/// sınama.
+pub fn self_test() -> Result<(), KernelError> {
+    use crate::crypto::{aes::Aes128, ed25519, gcm::Aes128Gcm, hmac, sha256, sha512};
+
+    // FIPS 180-4 örneği: "abc"
+    let abc = hex::<32>("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+    let abc512 = hex::<64>(
+        "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f",
+    );
+    // RFC 4231 test durumu 2
+    let mac = hex::<32>("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843");
+    // FIPS-197 Ek C.1
+    let aes_key = hex::<16>("000102030405060708090a0b0c0d0e0f");
+    let aes_pt = hex::<16>("00112233445566778899aabbccddeeff");
+    let aes_ct = hex::<16>("69c4e0d86a7b0430d8cdb78070b4c55a");
+    // GCM belirtimi test durumu 3
+    let gcm_key = hex::<16>("feffe9928665731c6d6a8f9467308308");
+    let gcm_iv = hex::<12>("cafebabefacedbaddecaf888");
+    let gcm_pt = hex::<64>(
+        "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a721c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b391aafd255",
+    );
+    let gcm_ct = hex::<64>(
+        "42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e091473f5985",
+    );
+    let gcm_tag = hex::<16>("4d5c2af327cd64a62cf35abd2ba6fab4");
+    // RFC 8032 7.1 TEST 1 (boş ileti)
+    let ed_pk = hex::<32>("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a");
+    let ed_sig = hex::<64>(
+        "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+    );
+
+    let mut ok = sha256::digest(b"abc") == abc;
+    ok &= sha512::digest(b"abc") == abc512;
+    ok &= hmac::hmac_sha256(b"Jefe", b"what do ya want for nothing?") == mac;
+
+    let mut block = aes_pt;
+    Aes128::new(&aes_key).encrypt_block(&mut block);
+    ok &= block == aes_ct;
+
+    let gcm = Aes128Gcm::new(&gcm_key);
+    let mut buf = gcm_pt;
+    let tag = gcm.seal_in_place(&gcm_iv, &[], &mut buf);
+    ok &= buf == gcm_ct && tag == gcm_tag;
+    ok &= gcm.open_in_place(&gcm_iv, &[], &mut buf, &tag).is_ok() && buf == gcm_pt;
+
+    ok &= ed25519::verify(&ed_pk, &[], &ed_sig);
+    ok &= !ed25519::verify(&ed_pk, b"x", &ed_sig);
+
+    if !ok {
+        serial_println!("[CRYPTO] Öz sınama BAŞARISIZ");
+        return Err(KernelError::GenericFailure);
+    }
+    Ok(())
+}
+
+crate::initcall!(late, "crypto-selftest", self_test);
+
+// -----------------------------------------------------------------------------
+// KABUK
+// -----------------------------------------------------------------------------
+
+/// `sha256sum <yol>`: VFS'teki dosyanın özetini yazar.
+fn sha256sum_command(args: &[&str]) -> Result<(), KernelError> {
+    let path = args.get(1).ok_or(KernelError::InvalidArgument)?;
+    let mut ctx = crate::crypto::sha256::Sha256::new();
+    let mut buf = [0u8; 512];
+    let mut offset = 0;
+    loop {
+        let n = crate::vfs::read(path, offset, &mut buf)?;
+        if n == 0 {
+            break;
+        }
+        ctx.update(&buf[..n]);
+        offset += n;
+    }
+    for b in ctx.finalize() {
+        crate::serial_print!("{:02x}", b);
+    }
+    serial_println!("  {}", path);
+    Ok(())
+}
+
+crate::shell_command!("sha256sum", "Dosyanın SHA-256 özeti (sha256sum <yol>)", sha256sum_command);
@@ -0,0 +1,386 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/crypto/ed25519.rs
+// Ed25519 imza doğrulaması (RFC 8032, 5.1.7).
+//
+// Yalnızca doğrulama yapılır; çekirdek gizli anahtar tutmaz. Doğrulamada
+// girdiler (açık anahtar, imza, ileti) gizli olmadığından aritmetik sabit
+// zamanlı değildir. Alan elemanları 2^255 - 19 modunda 51 bitlik beş
+// parçayla, noktalar genişletilmiş twisted Edwards koordinatlarıyla
+// (X:Y:Z:T) tutulur. Akışlı `Verifier`, belleğe sığmayan imajları parça
+// parça doğrulamak içindir.
+
+use crate::crypto::sha512::Sha512;
+
+/// Açık anahtar ve imza uzunluğu (bayt).
+pub const PUBLIC_KEY_LEN: usize = 32;
+pub const SIGNATURE_LEN: usize = 64;
+
+// -----------------------------------------------------------------------------
+// ALAN ARİTMETİĞİ (mod 2^255 - 19)
+// -----------------------------------------------------------------------------
+
+const MASK51: u64 = (1 << 51) - 1;
+
+#[derive(Clone, Copy)]
+struct Fe([u64; 5]);
+
+/// Üs olarak kullanılan sabitler (küçük endian).
+const P_MINUS_2: [u8; 32] = exponent(0xeb, 0x7f);
+/// (p - 5) / 8 = 2^252 - 3
+const P_MINUS_5_DIV_8: [u8; 32] = exponent(0xfd, 0x0f);
+/// (p - 1) / 4 = 2^253 - 5
+const P_MINUS_1_DIV_4: [u8; 32] = exponent(0xfb, 0x1f);
+
+/// Ara baytları 0xff olan 256 bitlik üs.
+const fn exponent(low: u8, high: u8) -> [u8; 32] {
+    let mut e = [0xff; 32];
+    e[0] = low;
+    e[31] = high;
+    e
+}
+
+impl Fe {
+    const ZERO: Fe = Fe([0; 5]);
+    const ONE: Fe = Fe([1, 0, 0, 0, 0]);
+
+    fn from_u64(v: u64) -> Fe {
+        Fe([v & MASK51, v >> 51, 0, 0, 0])
+    }
+
+    /// 255 bitlik küçük endian değeri okur (en üst bit yok sayılır).
+    fn from_bytes(b: &[u8; 32]) -> Fe {
+        let load = |i: usize| u64::from_le_bytes(b[i..i + 8].try_into().unwrap());
+        Fe([
+            load(0) & MASK51,
+            (load(6) >> 3) & MASK51,
+            (load(12) >> 6) & MASK51,
+            (load(19) >> 1) & MASK51,
+            (load(24) >> 12) & MASK51,
+        ])
+    }
+
+    /// Tam indirgenmiş (kanonik) kodlama.
+    fn to_bytes(self) -> [u8; 32] {
+        let mut h = Fe::carry([0, 1, 2, 3, 4].map(|i| u128::from(self.0[i]))).0;
+        // h ≥ p ise q = 1: h + 19 değeri 2^255'i aşar
+        let mut q = (h[0] + 19) >> 51;
+        for limb in &h[1..] {
+            q = (limb + q) >> 51;
+        }
+        h[0] += 19 * q;
+        for i in 0..4 {
+            h[i + 1] += h[i] >> 51;
+            h[i] &= MASK51;
+        }
+        h[4] &= MASK51;
+
+        let mut out = [0u8; 32];
+        let mut acc: u128 = 0;
+        let mut bits = 0;
+        let mut pos = 0;
+        for limb in h {
+            acc |= u128::from(limb) << bits;
+            bits += 51;
+            while bits >= 8 && pos < 32 {
+                out[pos] = acc as u8;
+                acc >>= 8;
+                bits -= 8;
+                pos += 1;
+            }
+        }
+        if pos < 32 {
+            out[pos] = acc as u8;
+        }
+        out
+    }
+
+    /// Geniş parçaları 51 bite taşır; üstten taşan kısım ·19 ile başa döner.
+    fn carry(r: [u128; 5]) -> Fe {
+        let mut r = r;
+        for i in 0..4 {
+            r[i + 1] += r[i] >> 51;
+            r[i] &= u128::from(MASK51);
+        }
+        r[0] += (r[4] >> 51) * 19;
+        r[4] &= u128::from(MASK51);
+        r[1] += r[0] >> 51;
+        r[0] &= u128::from(MASK51);
+        Fe(r.map(|v| v as u64))
+    }
+
+    fn add(self, o: Fe) -> Fe {
+        let a = self.0;
+        let b = o.0;
+        Fe::carry([0, 1, 2, 3, 4].map(|i| u128::from(a[i] + b[i])))
+    }
+
+    fn sub(self, o: Fe) -> Fe {
+        // Taşmayı önlemek için 4p eklenir
+        const FOUR_P: [u64; 5] = [0x1f_ffff_ffff_ffb4, 0x1f_ffff_ffff_fffc, 0x1f_ffff_ffff_fffc, 0x1f_ffff_ffff_fffc, 0x1f_ffff_ffff_fffc];
+        let a = self.0;
+        let b = o.0;
+        Fe::carry([0, 1, 2, 3, 4].map(|i| u128::from(a[i] + FOUR_P[i] - b[i])))
+    }
+
+    fn neg(self) -> Fe {
+        Fe::ZERO.sub(self)
+    }
+
+    fn mul(self, o: Fe) -> Fe {
+        let [a0, a1, a2, a3, a4] = self.0.map(u128::from);
+        let [b0, b1, b2, b3, b4] = o.0.map(u128::from);
+        let (b1_19, b2_19, b3_19, b4_19) = (b1 * 19, b2 * 19, b3 * 19, b4 * 19);
+        Fe::carry([
+            a0 * b0 + a1 * b4_19 + a2 * b3_19 + a3 * b2_19 + a4 * b1_19,
+            a0 * b1 + a1 * b0 + a2 * b4_19 + a3 * b3_19 + a4 * b2_19,
+            a0 * b2 + a1 * b1 + a2 * b0 + a3 * b4_19 + a4 * b3_19,
+            a0 * b3 + a1 * b2 + a2 * b1 + a3 * b0 + a4 * b4_19,
+            a0 * b4 + a1 * b3 + a2 * b2 + a3 * b1 + a4 * b0,
+        ])
+    }
+
+    fn square(self) -> Fe {
+        self.mul(self)
+    }
+
+    /// self^e (e küçük endian, değişken zamanlı).
+    fn pow(self, e: &[u8; 32]) -> Fe {
+        let mut r = Fe::ONE;
+        for i in (0..256).rev() {
+            r = r.square();
+            if (e[i / 8] >> (i % 8)) & 1 == 1 {
+                r = r.mul(self);
+            }
+        }
+        r
+    }
+
+    fn invert(self) -> Fe {
+        self.pow(&P_MINUS_2)
+    }
+
+    fn is_zero(self) -> bool {
+        self.to_bytes() == [0; 32]
+    }
+
+    fn is_negative(self) -> bool {
+        self.to_bytes()[0] & 1 == 1
+    }
+
+    fn equals(self, o: Fe) -> bool {
+        self.to_bytes() == o.to_bytes()
+    }
+}
+
+// -----------------------------------------------------------------------------
+// EĞRİ NOKTALARI
+// -----------------------------------------------------------------------------
+
+/// Eğri sabitleri: d = -121665/121666, 2d ve sqrt(-1).
+struct Curve {
+    d: Fe,
+    d2: Fe,
+    sqrt_m1: Fe,
+}
+
+impl Curve {
+    fn new() -> Curve {
+        let d = Fe::from_u64(121665).neg().mul(Fe::from_u64(121666).invert());
+        Curve { d, d2: d.add(d), sqrt_m1: Fe::from_u64(2).pow(&P_MINUS_1_DIV_4) }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Point {
+    x: Fe,
+    y: Fe,
+    z: Fe,
+    t: Fe,
+}
+
+/// Taban noktası B'nin kodlaması (y = 4/5, x çift).
+const BASE: [u8; 32] = {
+    let mut b = [0x66; 32];
+    b[0] = 0x58;
+    b
+};
+
+impl Point {
+    const IDENTITY: Point = Point { x: Fe::ZERO, y: Fe::ONE, z: Fe::ONE, t: Fe::ZERO };
+
+    /// RFC 8032 5.1.3. Kanonik olmayan y veya eğri dışı nokta `None` döner.
+    fn decompress(c: &Curve, s: &[u8; 32]) -> Option<Point> {
+        let y = Fe::from_bytes(s);
+        let mut masked = *s;
+        masked[31] &= 0x7f;
+        if y.to_bytes() != masked {
+            return None;
+        }
+        let sign = s[31] >> 7 == 1;
+
+        // x^2 = u / v, u = y^2 - 1, v = d·y^2 + 1
+        let y2 = y.square();
+        let u = y2.sub(Fe::ONE);
+        let v = c.d.mul(y2).add(Fe::ONE);
+        let v3 = v.square().mul(v);
+        let v7 = v3.square().mul(v);
+        let mut x = u.mul(v3).mul(u.mul(v7).pow(&P_MINUS_5_DIV_8));
+
+        let vx2 = v.mul(x.square());
+        if !vx2.equals(u) {
+            if !vx2.equals(u.neg()) {
+                return None;
+            }
+            x = x.mul(c.sqrt_m1);
+        }
+        if x.is_zero() && sign {
+            return None;
+        }
+        if x.is_negative() != sign {
+            x = x.neg();
+        }
+        Some(Point { x, y, z: Fe::ONE, t: x.mul(y) })
+    }
+
+    fn compress(&self) -> [u8; 32] {
+        let zinv = self.z.invert();
+        let x = self.x.mul(zinv);
+        let mut out = self.y.mul(zinv).to_bytes();
+        out[31] |= (x.is_negative() as u8) << 7;
+        out
+    }
+
+    /// Birleşik toplama (a = -1 için "add-2008-hwcd-3"); ikilemede de geçerli.
+    fn add(&self, c: &Curve, o: &Point) -> Point {
+        let a = self.y.sub(self.x).mul(o.y.sub(o.x));
+        let b = self.y.add(self.x).mul(o.y.add(o.x));
+        let cc = self.t.mul(c.d2).mul(o.t);
+        let zz = self.z.mul(o.z);
+        let d = zz.add(zz);
+        let e = b.sub(a);
+        let f = d.sub(cc);
+        let g = d.add(cc);
+        let h = b.add(a);
+        Point { x: e.mul(f), y: g.mul(h), z: f.mul(g), t: e.mul(h) }
+    }
+
+    fn neg(&self) -> Point {
+        Point { x: self.x.neg(), y: self.y, z: self.z, t: self.t.neg() }
+    }
+
+    /// [s]P (s küçük endian, değişken zamanlı).
+    fn mul(&self, c: &Curve, s: &[u8; 32]) -> Point {
+        let mut r = Point::IDENTITY;
+        for i in (0..256).rev() {
+            r = r.add(c, &r);
+            if (s[i / 8] >> (i % 8)) & 1 == 1 {
+                r = r.add(c, self);
+            }
+        }
+        r
+    }
+}
+
+// -----------------------------------------------------------------------------
+// SKALERLER (mod L)
+// -----------------------------------------------------------------------------
+
+/// Grup mertebesi L = 2^252 + 27742317777372353535851937790883648493.
+const L: [u64; 4] = [0x5812631a5cf5d3ed, 0x14def9dea2f79cd6, 0, 0x1000000000000000];
+
+fn scalar_lt_l(s: &[u8; 32]) -> bool {
+    for i in (0..4).rev() {
+        let w = u64::from_le_bytes(s[8 * i..8 * i + 8].try_into().unwrap());
+        if w != L[i] {
+            return w < L[i];
+        }
+    }
+    false
+}
+
+/// 512 bitlik özeti L modunda indirger (bit bit kaydır-çıkar).
+fn reduce_wide(h: &[u8; 64]) -> [u8; 32] {
+    let mut r = [0u64; 4];
+    for i in (0..512).rev() {
+        // r = 2r + bit; r < L < 2^253 olduğundan 2r + 1 taşmaz
+        let bit = u64::from((h[i / 8] >> (i % 8)) & 1);
+        for j in (1..4).rev() {
+            r[j] = (r[j] << 1) | (r[j - 1] >> 63);
+        }
+        r[0] = (r[0] << 1) | bit;
+
+        let ge = (0..4).rev().find(|&j| r[j] != L[j]).map_or(true, |j| r[j] > L[j]);
+        if ge {
+            let mut borrow = 0;
+            for j in 0..4 {
+                let (v, b1) = r[j].overflowing_sub(L[j]);
+                let (v, b2) = v.overflowing_sub(borrow);
+                r[j] = v;
+                borrow = u64::from(b1 | b2);
+            }
+        }
+    }
+    let mut out = [0u8; 32];
+    for (chunk, word) in out.chunks_exact_mut(8).zip(r) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+    out
+}
+
+// -----------------------------------------------------------------------------
+// DOĞRULAMA
+// -----------------------------------------------------------------------------
+
+/// Akışlı Ed25519 doğrulayıcı: ileti `update` ile parça parça verilir.
+pub struct Verifier {
+    curve: Curve,
+    a: Point,
+    r: [u8; 32],
+    s: [u8; 32],
+    hasher: Sha512,
+}
+
+impl Verifier {
+    /// Açık anahtar eğri üzerinde değilse veya S ≥ L ise `None` döner.
+    pub fn new(public_key: &[u8; PUBLIC_KEY_LEN], signature: &[u8; SIGNATURE_LEN]) -> Option<Self> {
+        let curve = Curve::new();
+        let a = Point::decompress(&curve, public_key)?;
+        let r: [u8; 32] = signature[..32].try_into().unwrap();
+        let s: [u8; 32] = signature[32..].try_into().unwrap();
+        if !scalar_lt_l(&s) {
+            return None;
+        }
+        let mut hasher = Sha512::new();
+        hasher.update(&r);
+        hasher.update(public_key);
+        Some(Verifier { curve, a, r, s, hasher })
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.hasher.update(data);
+    }
+
+    /// [S]B - [k]A = R denetimi; k = SHA-512(R || A || M) mod L.
+    pub fn finish(self) -> bool {
+        let k = reduce_wide(&self.hasher.finalize());
+        let c = &self.curve;
+        let Some(base) = Point::decompress(c, &BASE) else {
+            return false;
+        };
+        let sb = base.mul(c, &self.s);
+        let ka = self.a.neg().mul(c, &k);
+        sb.add(c, &ka).compress() == self.r
+    }
+}
+
+/// Tek seferde doğrulama.
+pub fn verify(public_key: &[u8; PUBLIC_KEY_LEN], message: &[u8], signature: &[u8; SIGNATURE_LEN]) -> bool {
+    match Verifier::new(public_key, signature) {
+        Some(mut v) => {
+            v.update(message);
+            v.finish()
+        }
+        None => false,
+    }
+}
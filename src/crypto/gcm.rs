@@ -0,0 +1,116 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/crypto/gcm.rs
+// AES-128-GCM doğrulamalı şifreleme (NIST SP 800-38D), 96 bit nonce.
+//
+// GHASH çarpımı GF(2^128)'de bit bit ve maskelerle yapılır (sabit zamanlı,
+// tablo yok). Şifre çözmede etiket sabit zamanda karşılaştırılır; tutmazsa
+// arabellek sıfırlanır, doğrulanmamış düz metin çağırana bırakılmaz.
+
+use crate::crypto::aes::{Aes128, BLOCK_LEN, KEY_LEN};
+use crate::crypto::crypto;
+use crate::platformgeneric::KernelError;
+
+/// Nonce ve etiket uzunluğu (bayt).
+pub const NONCE_LEN: usize = 12;
+pub const TAG_LEN: usize = 16;
+
+/// Bit yansıtılmış indirgeme sabiti (x^128 + x^7 + x^2 + x + 1).
+const R: u128 = 0xE1 << 120;
+
+/// X·Y, GF(2^128) (GCM bit sırası).
+fn gf_mul(x: u128, y: u128) -> u128 {
+    let mut z = 0u128;
+    let mut v = y;
+    for i in 0..128 {
+        let bit = 0u128.wrapping_sub((x >> (127 - i)) & 1);
+        z ^= v & bit;
+        let lsb = 0u128.wrapping_sub(v & 1);
+        v = (v >> 1) ^ (R & lsb);
+    }
+    z
+}
+
+/// GHASH biriktiricisi.
+struct Ghash {
+    h: u128,
+    y: u128,
+}
+
+impl Ghash {
+    /// Veriyi 16 baytlık bloklar halinde (son blok sıfırla doldurularak) işler.
+    fn update(&mut self, data: &[u8]) {
+        for chunk in data.chunks(BLOCK_LEN) {
+            let mut block = [0u8; BLOCK_LEN];
+            block[..chunk.len()].copy_from_slice(chunk);
+            self.y = gf_mul(self.y ^ u128::from_be_bytes(block), self.h);
+        }
+    }
+
+    fn finalize(mut self, aad_len: usize, text_len: usize) -> u128 {
+        let lengths = ((aad_len as u128 * 8) << 64) | (text_len as u128 * 8);
+        self.y = gf_mul(self.y ^ lengths, self.h);
+        self.y
+    }
+}
+
+/// AES-128-GCM bağlamı.
+pub struct Aes128Gcm {
+    aes: Aes128,
+    h: u128,
+}
+
+impl Aes128Gcm {
+    pub fn new(key: &[u8; KEY_LEN]) -> Self {
+        let aes = Aes128::new(key);
+        let mut h = [0u8; BLOCK_LEN];
+        aes.encrypt_block(&mut h);
+        Aes128Gcm { aes, h: u128::from_be_bytes(h) }
+    }
+
+    fn counter_block(nonce: &[u8; NONCE_LEN], counter: u32) -> [u8; BLOCK_LEN] {
+        let mut block = [0u8; BLOCK_LEN];
+        block[..NONCE_LEN].copy_from_slice(nonce);
+        block[NONCE_LEN..].copy_from_slice(&counter.to_be_bytes());
+        block
+    }
+
+    /// CTR kipinde anahtar akışını uygular (sayaç 2'den başlar).
+    fn apply_keystream(&self, nonce: &[u8; NONCE_LEN], buf: &mut [u8]) {
+        for (i, chunk) in buf.chunks_mut(BLOCK_LEN).enumerate() {
+            let mut ks = Self::counter_block(nonce, (i as u32).wrapping_add(2));
+            self.aes.encrypt_block(&mut ks);
+            chunk.iter_mut().zip(&ks).for_each(|(b, k)| *b ^= k);
+        }
+    }
+
+    fn tag(&self, nonce: &[u8; NONCE_LEN], aad: &[u8], ciphertext: &[u8]) -> [u8; TAG_LEN] {
+        let mut ghash = Ghash { h: self.h, y: 0 };
+        ghash.update(aad);
+        ghash.update(ciphertext);
+        let s = ghash.finalize(aad.len(), ciphertext.len());
+        let mut j0 = Self::counter_block(nonce, 1);
+        self.aes.encrypt_block(&mut j0);
+        (u128::from_be_bytes(j0) ^ s).to_be_bytes()
+    }
+
+    /// `buf`'ı yerinde şifreler ve doğrulama etiketini döndürür.
+    pub fn seal_in_place(&self, nonce: &[u8; NONCE_LEN], aad: &[u8], buf: &mut [u8]) -> [u8; TAG_LEN] {
+        self.apply_keystream(nonce, buf);
+        self.tag(nonce, aad, buf)
+    }
+
+    /// Etiketi doğrular ve `buf`'ı yerinde çözer.
+    ///
+    /// # Hatalar
+    /// * `InvalidArgument`: Etiket tutmuyor; `buf` sıfırlanır.
+    pub fn open_in_place(&self, nonce: &[u8; NONCE_LEN], aad: &[u8], buf: &mut [u8], tag: &[u8; TAG_LEN]) -> Result<(), KernelError> {
+        let expected = self.tag(nonce, aad, buf);
+        if !crypto::ct_eq(&expected, tag) {
+            crypto::zeroize(buf);
+            return Err(KernelError::InvalidArgument);
+        }
+        self.apply_keystream(nonce, buf);
+        Ok(())
+    }
+}
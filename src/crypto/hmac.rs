@@ -0,0 +1,61 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/crypto/hmac.rs
+// HMAC-SHA256 (RFC 2104). Etiket doğrulaması sabit zamanlı karşılaştırma
+// ile yapılır.
+
+use crate::crypto::crypto;
+use crate::crypto::sha256::{self, Sha256, BLOCK_LEN, DIGEST_LEN};
+
+/// Artımlı HMAC-SHA256 bağlamı.
+#[derive(Clone)]
+pub struct HmacSha256 {
+    inner: Sha256,
+    outer: Sha256,
+}
+
+impl HmacSha256 {
+    /// Blok boyutundan uzun anahtar önce özetlenir.
+    pub fn new(key: &[u8]) -> Self {
+        let mut block = [0u8; BLOCK_LEN];
+        if key.len() > BLOCK_LEN {
+            block[..DIGEST_LEN].copy_from_slice(&sha256::digest(key));
+        } else {
+            block[..key.len()].copy_from_slice(key);
+        }
+
+        let mut pad = [0u8; BLOCK_LEN];
+        let mut inner = Sha256::new();
+        pad.iter_mut().zip(&block).for_each(|(p, k)| *p = k ^ 0x36);
+        inner.update(&pad);
+        let mut outer = Sha256::new();
+        pad.iter_mut().zip(&block).for_each(|(p, k)| *p = k ^ 0x5c);
+        outer.update(&pad);
+
+        crypto::zeroize(&mut block);
+        crypto::zeroize(&mut pad);
+        HmacSha256 { inner, outer }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    pub fn finalize(self) -> [u8; DIGEST_LEN] {
+        let mut outer = self.outer;
+        outer.update(&self.inner.finalize());
+        outer.finalize()
+    }
+
+    /// Hesaplanan etiketi `tag` ile sabit zamanda karşılaştırır.
+    pub fn verify(self, tag: &[u8]) -> bool {
+        crypto::ct_eq(&self.finalize(), tag)
+    }
+}
+
+/// Tek seferde HMAC-SHA256.
+pub fn hmac_sha256(key: &[u8], data: &[u8]) -> [u8; DIGEST_LEN] {
+    let mut ctx = HmacSha256::new(key);
+    ctx.update(data);
+    ctx.finalize()
+}
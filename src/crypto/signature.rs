@@ -0,0 +1,226 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/crypto/signature.rs
+// Yüklenebilir modüller, kullanıcı ELF ikilileri ve ürün yazılımı
+// görüntüleri için Ed25519 imza doğrulaması (güvenli önyükleme benzeri).
+//
+// İmza, görüntünün sonuna eklenen sabit boyutlu bir kuyruktadır:
+//
+//   yük (payload) | "SKSG" | sürüm | alg | tür | 0 | yük uzunluğu (u32 LE) | imza (64 B)
+//
+// İmzalanan ileti yük ile kuyruğun imzadan önceki 12 baytıdır; böylece
+// görüntü türü de imzaya bağlanır (imzalı bir modül ürün yazılımı olarak
+// kullanılamaz). Doğrulama anahtarı derleme sırasında `SAHNE_SIGNING_KEY`
+// ortam değişkeninden (64 küçük harf onaltılık basamak) çekirdeğe gömülür.
+//
+// `require-signatures` özelliği açıkken imzasız görüntüler reddedilir;
+// kapalıyken uyarıyla kabul edilir. Geçersiz imza her durumda reddedilir.
+// Üç giriş noktası da aynı politikayı uygular ve kullanılacak yükü döndürür:
+// * `check_image`: bellekteki modül veya kullanıcı ELF görüntüsü. Modül ve
+//   ELF yükleyicileri görüntüyü eşlemeden önce bunu çağırmalıdır (çekirdekte
+//   henüz bu yükleyiciler yoktur; ilk çağıranlar onlar olacaktır).
+// * `check_staged`: güncelleme hazırlık alanı (`update.rs`, ürün yazılımı).
+// * `check_file`: VFS'teki dosya (`sigcheck` kabuk komutu).
+
+use crate::crypto::crypto;
+use crate::crypto::ed25519::{self, Verifier, PUBLIC_KEY_LEN, SIGNATURE_LEN};
+use crate::platformgeneric::KernelError;
+use crate::update::Staging;
+use crate::serial_println;
+
+/// Derleme zamanında gömülen doğrulama anahtarı.
+const BUILTIN_KEY: Option<[u8; PUBLIC_KEY_LEN]> = match option_env!("SAHNE_SIGNING_KEY") {
+    Some(key) => Some(crypto::hex::<PUBLIC_KEY_LEN>(key)),
+    None => None,
+};
+
+/// İmzasız görüntüler reddedilsin mi?
+pub const REQUIRE_SIGNATURES: bool = cfg!(feature = "require-signatures");
+
+const MAGIC: [u8; 4] = *b"SKSG";
+const VERSION: u8 = 1;
+const ALG_ED25519: u8 = 1;
+/// İmzalanan başlık uzunluğu (bayt).
+const HEADER_LEN: usize = 12;
+/// Görüntü sonundaki kuyruğun uzunluğu (bayt).
+pub const TRAILER_LEN: usize = HEADER_LEN + SIGNATURE_LEN;
+
+/// İmzalı görüntü türü.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ImageKind {
+    Module = 1,
+    UserBinary = 2,
+    Firmware = 3,
+}
+
+impl ImageKind {
+    fn name(self) -> &'static str {
+        match self {
+            ImageKind::Module => "modül",
+            ImageKind::UserBinary => "kullanıcı ikilisi",
+            ImageKind::Firmware => "ürün yazılımı",
+        }
+    }
+}
+
+crate::kernel_counter!(SIG_VERIFIED, "signature.verified");
+crate::kernel_counter!(SIG_REJECTED, "signature.rejected");
+crate::kernel_counter!(SIG_UNSIGNED, "signature.unsigned");
+
+// -----------------------------------------------------------------------------
+// DOĞRULAMA
+// -----------------------------------------------------------------------------
+
+/// Görüntüden `offset` konumundan okuyan işlev (okunan bayt sayısı döner).
+type ReadAt<'a> = &'a mut dyn FnMut(usize, &mut [u8]) -> Result<usize, KernelError>;
+
+fn read_exact(read: ReadAt, mut offset: usize, mut buf: &mut [u8]) -> Result<(), KernelError> {
+    while !buf.is_empty() {
+        let n = read(offset, buf)?;
+        if n == 0 {
+            return Err(KernelError::InvalidArgument);
+        }
+        offset += n;
+        buf = &mut buf[n..];
+    }
+    Ok(())
+}
+
+/// İmzasız görüntü için politika kararı.
+fn unsigned(kind: ImageKind, len: usize) -> Result<usize, KernelError> {
+    if REQUIRE_SIGNATURES {
+        serial_println!("[SIG] İmzasız {} reddedildi", kind.name());
+        SIG_REJECTED.inc();
+        return Err(KernelError::InvalidArgument);
+    }
+    serial_println!("[SIG] UYARI: imzasız {} kabul edildi", kind.name());
+    SIG_UNSIGNED.inc();
+    Ok(len)
+}
+
+fn reject(kind: ImageKind, reason: &str) -> Result<usize, KernelError> {
+    serial_println!("[SIG] {} reddedildi: {}", kind.name(), reason);
+    SIG_REJECTED.inc();
+    Err(KernelError::InvalidArgument)
+}
+
+/// `len` baytlık görüntüyü doğrular, kullanılacak yük uzunluğunu döndürür
+/// (imzasız görüntü kabul edilirse tüm uzunluk).
+fn check(kind: ImageKind, len: usize, read: ReadAt) -> Result<usize, KernelError> {
+    if len < TRAILER_LEN {
+        return unsigned(kind, len);
+    }
+    let payload_len = len - TRAILER_LEN;
+    let mut trailer = [0u8; TRAILER_LEN];
+    read_exact(read, payload_len, &mut trailer)?;
+    if trailer[..4] != MAGIC {
+        return unsigned(kind, len);
+    }
+
+    let (header, signature) = trailer.split_at(HEADER_LEN);
+    if header[4] != VERSION || header[5] != ALG_ED25519 {
+        return reject(kind, "desteklenmeyen imza biçimi");
+    }
+    if header[6] != kind as u8 {
+        return reject(kind, "görüntü türü uyuşmuyor");
+    }
+    if u32::from_le_bytes(header[8..12].try_into().unwrap()) as usize != payload_len {
+        return reject(kind, "yük uzunluğu uyuşmuyor");
+    }
+    let Some(key) = BUILTIN_KEY else {
+        // Anahtarsız derlemede imza doğrulanamaz; imzasız gibi davranılır
+        return unsigned(kind, len).map(|_| payload_len);
+    };
+    let Some(mut verifier) = Verifier::new(&key, signature.try_into().unwrap()) else {
+        return reject(kind, "geçersiz imza");
+    };
+
+    let mut buf = [0u8; 512];
+    let mut offset = 0;
+    while offset < payload_len {
+        let want = (payload_len - offset).min(buf.len());
+        read_exact(read, offset, &mut buf[..want])?;
+        verifier.update(&buf[..want]);
+        offset += want;
+    }
+    verifier.update(header);
+    if !verifier.finish() {
+        return reject(kind, "imza doğrulanamadı");
+    }
+    SIG_VERIFIED.inc();
+    Ok(payload_len)
+}
+
+/// Bellekteki görüntüyü doğrular ve yükü döndürür. Modül ve kullanıcı ELF
+/// yükleyicilerinin giriş noktasıdır; dönen dilim dışında bir şey eşlenmemelidir.
+///
+/// # Hatalar
+/// * `InvalidArgument`: İmza geçersiz, kuyruk bozuk veya politika gereği
+///   imzasız görüntü reddedildi.
+pub fn check_image(kind: ImageKind, image: &[u8]) -> Result<&[u8], KernelError> {
+    let mut read = |offset: usize, buf: &mut [u8]| {
+        let n = buf.len().min(image.len().saturating_sub(offset));
+        buf[..n].copy_from_slice(&image[offset..offset + n]);
+        Ok(n)
+    };
+    let payload_len = check(kind, image.len(), &mut read)?;
+    Ok(&image[..payload_len])
+}
+
+/// Hazırlık alanındaki ilk `len` baytı doğrular; yük uzunluğunu döndürür.
+pub fn check_staged(kind: ImageKind, staging: &dyn Staging, len: usize) -> Result<usize, KernelError> {
+    check(kind, len, &mut |offset, buf| staging.read(offset, buf))
+}
+
+/// VFS'teki dosyayı doğrular; yük uzunluğunu döndürür.
+pub fn check_file(kind: ImageKind, path: &str) -> Result<usize, KernelError> {
+    let len = crate::vfs::stat(path)?.size;
+    check(kind, len, &mut |offset, buf| crate::vfs::read(path, offset, buf))
+}
+
+/// Gömülü anahtarla tek bir iletiyi doğrular (kuyruk biçimi dışındaki
+/// kullanımlar için).
+pub fn verify_detached(message: &[u8], signature: &[u8; SIGNATURE_LEN]) -> bool {
+    match BUILTIN_KEY {
+        Some(key) => ed25519::verify(&key, message, signature),
+        None => false,
+    }
+}
+
+// -----------------------------------------------------------------------------
+// KABUK
+// -----------------------------------------------------------------------------
+
+/// `sigcheck <yol> [module|user|firmware]`
+fn sigcheck_command(args: &[&str]) -> Result<(), KernelError> {
+    let path = args.get(1).ok_or(KernelError::InvalidArgument)?;
+    let kind = match args.get(2).copied().unwrap_or("module") {
+        "module" => ImageKind::Module,
+        "user" => ImageKind::UserBinary,
+        "firmware" => ImageKind::Firmware,
+        _ => return Err(KernelError::InvalidArgument),
+    };
+    let payload_len = check_file(kind, path)?;
+    serial_println!("{}: {} ({} bayt yük)", path, kind.name(), payload_len);
+    Ok(())
+}
+
+crate::shell_command!("sigcheck", "Görüntü imzasını doğrular (sigcheck <yol> [module|user|firmware])", sigcheck_command);
+
+fn sigkey_command(_args: &[&str]) -> Result<(), KernelError> {
+    match BUILTIN_KEY {
+        Some(key) => {
+            crate::serial_print!("ed25519 ");
+            for b in key {
+                crate::serial_print!("{:02x}", b);
+            }
+            serial_println!();
+        }
+        None => serial_println!("gömülü anahtar yok"),
+    }
+    serial_println!("imza zorunlu: {}", if REQUIRE_SIGNATURES { "evet" } else { "hayır" });
+    Ok(())
+}
+
+crate::shell_command!("sigkey", "Gömülü imza anahtarı ve politika", sigkey_command);
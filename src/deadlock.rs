@@ -0,0 +1,254 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/deadlock.rs
+// Bloklayan senkronizasyon için bekleme-için (wait-for) grafiği ve
+// kilitlenme (deadlock) algılama.
+//
+// Her görev en fazla bir kaynağı bekler; her kaynağın en fazla bir sahibi
+// vardır (mutex'i tutan görev, kuyruğa bağlı alıcı vb.). Grafik bu iki
+// tablodan türetilir: görev → beklediği kaynak → kaynağın sahibi → ...
+// Bir görev beklemeye girmeden önce `wait_until` zinciri izler; zincir
+// görevin kendisine dönüyorsa bekleme hiç başlamaz ve `Err(Deadlock)`
+// döner. Hata ayıklama derlemelerinde döngü yazdırılıp panik oluşturulur.
+//
+// Sahibi olmayan olaylar (örn. kuyruğa veri gelmesi; gönderen belli değil)
+// grafikte kenar oluşturmaz ve algılanamaz. Kaynak anahtarı nesnenin
+// adresidir; bloklayan nesneler statik olmalı veya bekleyen varken
+// taşınmamalıdır.
+//
+// procfs: `/proc/waitgraph`
+
+use core::cell::UnsafeCell;
+use crate::platformgeneric::spinlock::Spinlock;
+use crate::platformgeneric::KernelError;
+use crate::sched::{self, TaskId, MAX_TASKS};
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// TİPLER
+// -----------------------------------------------------------------------------
+
+/// Beklenebilen kaynak türü.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceKind {
+    /// Uyuyan mutex (`sync::Mutex`); sahibi kilidi tutan görevdir.
+    Mutex,
+    /// IPC kuyruğunda boş yer; sahibi kuyruğa bağlı alıcıdır.
+    QueueSpace,
+    /// IPC kuyruğunda mesaj; sahibi yoktur.
+    QueueData,
+}
+
+/// Beklenen kaynak: tür ve nesne adresi.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Resource {
+    pub kind: ResourceKind,
+    pub key: usize,
+}
+
+impl Resource {
+    pub fn new<T>(kind: ResourceKind, object: &T) -> Self {
+        Resource { kind, key: object as *const T as usize }
+    }
+}
+
+/// Sahibi izlenebilen en fazla kaynak.
+pub const MAX_OWNED: usize = 64;
+
+struct WaitGraph {
+    lock: Spinlock,
+    /// Görevin beklediği kaynak.
+    waiting: UnsafeCell<[Option<Resource>; MAX_TASKS]>,
+    /// Sahibi olan kaynaklar.
+    owners: UnsafeCell<[Option<(Resource, TaskId)>; MAX_OWNED]>,
+}
+
+// Tablolara erişim `lock` ile korunur.
+unsafe impl Sync for WaitGraph {}
+
+static GRAPH: WaitGraph = WaitGraph {
+    lock: Spinlock::new(),
+    waiting: UnsafeCell::new([None; MAX_TASKS]),
+    owners: UnsafeCell::new([None; MAX_OWNED]),
+};
+
+crate::kernel_counter!(DEADLOCKS, "deadlock.detected");
+crate::kernel_counter!(UNTRACKED, "deadlock.untracked");
+
+type Waiting = [Option<Resource>; MAX_TASKS];
+type Owners = [Option<(Resource, TaskId)>; MAX_OWNED];
+
+fn with_graph<R>(f: impl FnOnce(&mut Waiting, &mut Owners) -> R) -> R {
+    GRAPH.lock.lock();
+    // SAFETY: Kilit tutulduğu sürece tablolara tek erişen biziz.
+    let result = unsafe { f(&mut *GRAPH.waiting.get(), &mut *GRAPH.owners.get()) };
+    GRAPH.lock.unlock();
+    result
+}
+
+fn owner_of(owners: &Owners, resource: Resource) -> Option<TaskId> {
+    owners.iter().flatten().find(|(r, _)| *r == resource).map(|&(_, t)| t)
+}
+
+// -----------------------------------------------------------------------------
+// SAHİPLİK
+// -----------------------------------------------------------------------------
+
+/// Kaynağın sahibini ayarlar (`None`: sahipsiz). Tablo doluysa kaynak
+/// izlenmez; o kaynak üzerinden geçen döngüler algılanamaz.
+pub fn set_owner(resource: Resource, owner: Option<TaskId>) {
+    with_graph(|_, owners| {
+        let existing = owners.iter_mut().find(|e| e.is_some_and(|(r, _)| r == resource));
+        match (existing, owner) {
+            (Some(entry), Some(task)) => *entry = Some((resource, task)),
+            (Some(entry), None) => *entry = None,
+            (None, Some(task)) => match owners.iter_mut().find(|e| e.is_none()) {
+                Some(entry) => *entry = Some((resource, task)),
+                None => UNTRACKED.inc(),
+            },
+            (None, None) => {}
+        }
+    });
+}
+
+/// Sonlanan görevin bekleme ve sahiplik kayıtlarını siler (görev kimliği
+/// yeniden kullanılınca yanlış döngü oluşmasın diye).
+pub fn forget(task: TaskId) {
+    with_graph(|waiting, owners| {
+        if let Some(w) = waiting.get_mut(task) {
+            *w = None;
+        }
+        for entry in owners.iter_mut() {
+            if entry.is_some_and(|(_, t)| t == task) {
+                *entry = None;
+            }
+        }
+    });
+}
+
+// -----------------------------------------------------------------------------
+// BEKLEME
+// -----------------------------------------------------------------------------
+
+/// Döngü zinciri: `me` → kaynak → sahip → ... → `me`.
+struct Cycle {
+    links: [(Resource, TaskId); MAX_TASKS],
+    len: usize,
+}
+
+/// `me`, `resource`'u beklemeye başlarsa oluşacak döngüyü arar; yoksa
+/// beklemeyi kaydeder. Döngü varsa `cycle` doldurulur ve `false` döner.
+fn begin_wait(me: TaskId, resource: Resource, cycle: &mut Cycle) -> bool {
+    with_graph(|waiting, owners| {
+        let mut next = resource;
+        // Her görev en fazla bir kaynak beklediği için zincir en çok MAX_TASKS uzunluktadır
+        while cycle.len < MAX_TASKS {
+            let Some(owner) = owner_of(owners, next) else { break };
+            cycle.links[cycle.len] = (next, owner);
+            cycle.len += 1;
+            if owner == me {
+                return false;
+            }
+            match waiting[owner] {
+                Some(r) => next = r,
+                None => break,
+            }
+        }
+        waiting[me] = Some(resource);
+        true
+    })
+}
+
+fn end_wait(me: TaskId) {
+    with_graph(|waiting, _| waiting[me] = None);
+}
+
+fn report(me: TaskId, cycle: &Cycle) {
+    let name = |id: TaskId| sched::task_info(id).map_or("?", |info| info.name);
+    serial_println!("[DEADLOCK] Kilitlenme algılandı:");
+    let mut from = me;
+    for &(resource, owner) in &cycle.links[..cycle.len] {
+        serial_println!(
+            "  görev {} ({}) → {:?} @ {:#x} → sahibi görev {} ({})",
+            from,
+            name(from),
+            resource.kind,
+            resource.key,
+            owner,
+            name(owner)
+        );
+        from = owner;
+    }
+}
+
+/// Çalışan görevi `ready()` doğru olana kadar `resource` üzerinde bekletir.
+///
+/// Görev bağlamı yoksa (açılış) meşgul beklenir ve algılama yapılmaz.
+///
+/// # Hatalar
+/// * `Deadlock`: Bekleme bir döngüyü kapatırdı; görev hiç beklemeden döner.
+pub fn wait_until(resource: Resource, ready: impl Fn() -> bool) -> Result<(), KernelError> {
+    let Some(me) = sched::current() else {
+        while !ready() {
+            core::hint::spin_loop();
+        }
+        return Ok(());
+    };
+    let mut cycle = Cycle { links: [(resource, me); MAX_TASKS], len: 0 };
+    if !begin_wait(me, resource, &mut cycle) {
+        DEADLOCKS.inc();
+        report(me, &cycle);
+        if cfg!(debug_assertions) {
+            panic!("Kilitlenme: görev {} kendi beklediği zinciri bekliyor", me);
+        }
+        return Err(KernelError::Deadlock);
+    }
+
+    // Önce bloklanır, sonra koşula bakılır: uyandırma arada gelmişse görev
+    // hâlâ çalışıyordur ve aşağıdaki `wake` onu yeniden hazır yapar
+    let _ = sched::block(me);
+    while !ready() {
+        core::hint::spin_loop();
+    }
+    let _ = sched::wake(me);
+    end_wait(me);
+    Ok(())
+}
+
+/// `resource`'u bekleyen tüm görevleri uyandırır (kaynak serbest kaldığında).
+pub fn wake_waiters(resource: Resource) {
+    let mut waiters = [false; MAX_TASKS];
+    with_graph(|waiting, _| {
+        for (w, flag) in waiting.iter().zip(waiters.iter_mut()) {
+            *flag = *w == Some(resource);
+        }
+    });
+    for (task, _) in waiters.iter().enumerate().filter(|(_, &w)| w) {
+        let _ = sched::wake(task);
+    }
+}
+
+// -----------------------------------------------------------------------------
+// PROCFS
+// -----------------------------------------------------------------------------
+
+/// Satır başına bekleyen görev: `<görev> <tür> <anahtar> <sahip|->`.
+fn show_waitgraph(out: &mut dyn core::fmt::Write) -> core::fmt::Result {
+    let mut waiting = [None; MAX_TASKS];
+    let mut owners = [None; MAX_OWNED];
+    with_graph(|w, o| {
+        waiting = *w;
+        owners = *o;
+    });
+    for (task, resource) in waiting.iter().enumerate() {
+        let Some(resource) = resource else { continue };
+        write!(out, "{} {:?} {:#x} ", task, resource.kind, resource.key)?;
+        match owner_of(&owners, *resource) {
+            Some(owner) => writeln!(out, "{}", owner)?,
+            None => writeln!(out, "-")?,
+        }
+    }
+    Ok(())
+}
+
+crate::proc_entry!("waitgraph", show_waitgraph);
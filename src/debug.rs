@@ -0,0 +1,404 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/debug.rs
+// Tuzak katmanı üzerinden yazılım kesme noktası (breakpoint), donanım tek
+// adım (single-step) ve donanım gözlem noktası (watchpoint) desteği. GDB stub'ı
+// ve kabuktaki çekirdek hata ayıklayıcı komutu bu arayüzü kullanır.
+
+use crate::arch::debug as arch_debug;
+use crate::platformgeneric::spinlock::Spinlock;
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+use crate::trap::Cause;
+use core::cell::UnsafeCell;
+
+/// Donanım tek adım desteği olmayan mimarilerin döndürdüğü hata kodu.
+pub const ERR_NO_HW_SINGLE_STEP: u32 = 0xDB00_0001;
+
+/// Donanımın desteklemediği gözlem türü (örn. x86'da salt okuma) için hata kodu.
+pub const ERR_WATCH_KIND_UNSUPPORTED: u32 = 0xDB00_0002;
+
+// -----------------------------------------------------------------------------
+// KESME NOKTASI TABLOSU
+// -----------------------------------------------------------------------------
+
+/// Aynı anda kurulabilecek en fazla yazılım kesme noktası sayısı.
+pub const MAX_BREAKPOINTS: usize = 16;
+
+/// Kurulu bir yazılım kesme noktası.
+#[derive(Clone, Copy)]
+struct Breakpoint {
+    /// Kesme noktasının kurulduğu talimat adresi.
+    addr: usize,
+    /// Üzerine yazılan özgün talimat.
+    saved: u32,
+    /// Girişin kullanımda olup olmadığı.
+    active: bool,
+}
+
+impl Breakpoint {
+    const fn empty() -> Self {
+        Breakpoint { addr: 0, saved: 0, active: false }
+    }
+}
+
+struct BreakpointTable {
+    lock: Spinlock,
+    entries: UnsafeCell<[Breakpoint; MAX_BREAKPOINTS]>,
+}
+
+// Tabloya erişim `lock` ile korunur.
+unsafe impl Sync for BreakpointTable {}
+
+static BREAKPOINTS: BreakpointTable = BreakpointTable {
+    lock: Spinlock::new(),
+    entries: UnsafeCell::new([Breakpoint::empty(); MAX_BREAKPOINTS]),
+};
+
+/// Kesme noktası veya tek adım olayında çağrılan istemci (GDB stub, kabuk).
+///
+/// Parametreler: olay nedeni ve tuzak anındaki PC. `true` dönerse yürütme
+/// tek adım modunda devam eder.
+pub type DebugHandler = fn(cause: Cause, pc: usize) -> bool;
+
+static mut DEBUG_HANDLER: Option<DebugHandler> = None;
+
+/// Hata ayıklama olaylarını alacak istemciyi kaydeder.
+pub fn set_handler(handler: Option<DebugHandler>) {
+    BREAKPOINTS.lock.lock();
+    unsafe {
+        DEBUG_HANDLER = handler;
+    }
+    BREAKPOINTS.lock.unlock();
+}
+
+/// Belirtilen adrese boyutuna uygun talimat yazar ve önceki değeri döndürür.
+///
+/// # Güvenlik Notu
+/// Adres yazılabilir ve çalıştırılabilir (veya geçici olarak yazılabilir yapılmış)
+/// bir kod sayfasında olmalıdır.
+unsafe fn patch_insn(addr: usize, insn: u32) -> u32 {
+    let old = if arch_debug::BREAKPOINT_INSN_LEN == 1 {
+        let old = core::ptr::read_volatile(addr as *const u8) as u32;
+        core::ptr::write_volatile(addr as *mut u8, insn as u8);
+        old
+    } else {
+        let old = core::ptr::read_volatile(addr as *const u32);
+        core::ptr::write_volatile(addr as *mut u32, insn);
+        old
+    };
+    arch_debug::sync_icache(addr, arch_debug::BREAKPOINT_INSN_LEN);
+    old
+}
+
+/// Verilen adrese bir yazılım kesme noktası kurar.
+///
+/// # Dönüş Değeri
+/// Başarılıysa tablo indeksi; tablo doluysa `OutOfMemoryStatic`, adres hizasız
+/// veya okunamıyorsa `InvalidArgument`, aynı adreste zaten varsa `ResourceBusy`.
+///
+/// # Güvenlik Notu
+/// Çağıran, adresin geçerli bir talimat sınırı olduğunu garanti etmelidir.
+pub unsafe fn set_breakpoint(addr: usize) -> Result<usize, KernelError> {
+    if addr % arch_debug::BREAKPOINT_INSN_LEN != 0 {
+        return Err(KernelError::InvalidArgument);
+    }
+
+    // Eşlenmemiş bir adrese yama yapmaya çalışıp panik yapmak yerine önce yokla
+    let mut probe = [0u8; 4];
+    crate::fixup::probe_kernel_read(addr, &mut probe[..arch_debug::BREAKPOINT_INSN_LEN])?;
+
+    BREAKPOINTS.lock.lock();
+    let entries = &mut *BREAKPOINTS.entries.get();
+
+    let result = if entries.iter().any(|bp| bp.active && bp.addr == addr) {
+        Err(KernelError::ResourceBusy)
+    } else if let Some((index, slot)) = entries.iter_mut().enumerate().find(|(_, bp)| !bp.active) {
+        slot.saved = patch_insn(addr, arch_debug::BREAKPOINT_INSN);
+        slot.addr = addr;
+        slot.active = true;
+        Ok(index)
+    } else {
+        Err(KernelError::OutOfMemoryStatic)
+    };
+
+    BREAKPOINTS.lock.unlock();
+    result
+}
+
+/// Verilen adresteki kesme noktasını kaldırır ve özgün talimatı geri yükler.
+pub unsafe fn clear_breakpoint(addr: usize) -> Result<(), KernelError> {
+    BREAKPOINTS.lock.lock();
+    let entries = &mut *BREAKPOINTS.entries.get();
+
+    let result = match entries.iter_mut().find(|bp| bp.active && bp.addr == addr) {
+        Some(bp) => {
+            patch_insn(bp.addr, bp.saved);
+            *bp = Breakpoint::empty();
+            Ok(())
+        }
+        None => Err(KernelError::NotFound),
+    };
+
+    BREAKPOINTS.lock.unlock();
+    result
+}
+
+/// Adreste kurulu bir kesme noktası olup olmadığını döndürür.
+pub fn is_breakpoint(addr: usize) -> bool {
+    BREAKPOINTS.lock.lock();
+    let found = unsafe { (*BREAKPOINTS.entries.get()).iter().any(|bp| bp.active && bp.addr == addr) };
+    BREAKPOINTS.lock.unlock();
+    found
+}
+
+/// Kaydedilmiş durum yazmacında (RFLAGS, SPSR vb.) tek adım modunu açar/kapatır.
+pub fn set_single_step(status: &mut u64, enable: bool) -> Result<(), KernelError> {
+    arch_debug::set_single_step(status, enable)
+}
+
+// -----------------------------------------------------------------------------
+// GÖZLEM NOKTASI TABLOSU
+// -----------------------------------------------------------------------------
+
+/// Mimarinin sunduğu donanım gözlem yuvası sayısı (DR0-DR3, DBGWVR<n>, tdata vb.).
+pub const MAX_WATCHPOINTS: usize = arch_debug::MAX_WATCHPOINTS;
+
+/// Gözlem noktasını tetikleyen erişim türü.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchKind {
+    /// Yalnızca okuma (load).
+    Read,
+    /// Yalnızca yazma (store).
+    Write,
+    /// Okuma veya yazma.
+    ReadWrite,
+}
+
+/// Kurulu bir donanım gözlem noktası.
+#[derive(Clone, Copy)]
+struct Watchpoint {
+    addr: usize,
+    len: usize,
+    kind: WatchKind,
+    active: bool,
+}
+
+impl Watchpoint {
+    const fn empty() -> Self {
+        Watchpoint { addr: 0, len: 0, kind: WatchKind::Write, active: false }
+    }
+}
+
+struct WatchpointTable {
+    lock: Spinlock,
+    entries: UnsafeCell<[Watchpoint; MAX_WATCHPOINTS]>,
+    /// Erişimden önce tetiklenen mimarilerde, erişimi tek adımla geçmek için
+    /// geçici olarak devre dışı bırakılan yuva ve istemcinin adımlama isteği.
+    step_over: UnsafeCell<Option<(usize, bool)>>,
+}
+
+// Tabloya erişim `lock` ile korunur.
+unsafe impl Sync for WatchpointTable {}
+
+static WATCHPOINTS: WatchpointTable = WatchpointTable {
+    lock: Spinlock::new(),
+    entries: UnsafeCell::new([Watchpoint::empty(); MAX_WATCHPOINTS]),
+    step_over: UnsafeCell::new(None),
+};
+
+/// `[addr, addr + len)` aralığına erişimleri izleyen bir donanım gözlem noktası kurar.
+///
+/// # Parametreler
+/// * `addr`: Gözlenecek adres; `len` ile hizalı olmalıdır.
+/// * `len`: 1, 2, 4 veya 8 bayt.
+/// * `rw`: Tetikleyen erişim türü.
+///
+/// # Dönüş Değeri
+/// Başarılıysa yuva indeksi; boş yuva yoksa `OutOfMemoryStatic`, uzunluk veya
+/// hizalama geçersizse `InvalidArgument`, aynı aralık zaten izleniyorsa `ResourceBusy`.
+pub fn watch(addr: usize, len: usize, rw: WatchKind) -> Result<usize, KernelError> {
+    if !matches!(len, 1 | 2 | 4 | 8) || addr % len != 0 {
+        return Err(KernelError::InvalidArgument);
+    }
+
+    WATCHPOINTS.lock.lock();
+    let entries = unsafe { &mut *WATCHPOINTS.entries.get() };
+
+    let result = if entries.iter().any(|wp| wp.active && wp.addr == addr && wp.len == len) {
+        Err(KernelError::ResourceBusy)
+    } else if let Some((slot, entry)) = entries.iter_mut().enumerate().find(|(_, wp)| !wp.active) {
+        match unsafe { arch_debug::set_watchpoint(slot, addr, len, rw) } {
+            Ok(()) => {
+                *entry = Watchpoint { addr, len, kind: rw, active: true };
+                Ok(slot)
+            }
+            Err(err) => Err(err),
+        }
+    } else {
+        Err(KernelError::OutOfMemoryStatic)
+    };
+
+    WATCHPOINTS.lock.unlock();
+    result
+}
+
+/// `watch` ile kurulan gözlem noktasını kaldırır.
+pub fn unwatch(slot: usize) -> Result<(), KernelError> {
+    if slot >= MAX_WATCHPOINTS {
+        return Err(KernelError::InvalidArgument);
+    }
+
+    WATCHPOINTS.lock.lock();
+    let entry = unsafe { &mut (*WATCHPOINTS.entries.get())[slot] };
+
+    let result = if entry.active {
+        unsafe { arch_debug::clear_watchpoint(slot) };
+        *entry = Watchpoint::empty();
+        Ok(())
+    } else {
+        Err(KernelError::NotFound)
+    };
+
+    WATCHPOINTS.lock.unlock();
+    result
+}
+
+/// Son tuzağa bir gözlem noktasının yol açıp açmadığını donanım durum yazmacından
+/// (DR6, tdata1.hit, WatchHi vb.) okur ve gözlenen adresi döndürür.
+///
+/// Hatalı veri adresini kendisi bildirmeyen mimarilerin `decode_cause` fonksiyonu kullanır.
+pub fn watchpoint_hit() -> Option<usize> {
+    let slot = arch_debug::watchpoint_hit()?;
+    WATCHPOINTS.lock.lock();
+    let entry = unsafe { (*WATCHPOINTS.entries.get())[slot] };
+    WATCHPOINTS.lock.unlock();
+    if entry.active { Some(entry.addr) } else { None }
+}
+
+/// Adresi kapsayan etkin gözlem yuvasını bulur.
+///
+/// Donanım, izlenen aralıkla örtüşen erişimin başlangıç adresini bildirebilir
+/// (örn. FAR_EL1); bu yüzden en fazla 8 baytlık bir erişimin örtüşmesi aranır.
+fn find_watchpoint(addr: usize) -> Option<(usize, Watchpoint)> {
+    WATCHPOINTS.lock.lock();
+    let found = unsafe {
+        (*WATCHPOINTS.entries.get())
+            .iter()
+            .enumerate()
+            .find(|(_, wp)| wp.active && addr < wp.addr + wp.len && addr.saturating_add(8) > wp.addr)
+            .map(|(slot, wp)| (slot, *wp))
+    };
+    WATCHPOINTS.lock.unlock();
+    found
+}
+
+/// Erişimden önce tetiklenen bir gözlem noktasını, erişimi yeniden tetiklemeden
+/// geçebilmek için devre dışı bırakır ve tek adımla yeniden kurulmasını planlar.
+///
+/// Donanım tek adımı yoksa gözlem noktası tek seferlik olarak kalır.
+fn step_over_watchpoint(slot: usize, client_step: bool, status: &mut u64) {
+    unsafe { arch_debug::clear_watchpoint(slot) };
+
+    if set_single_step(status, true).is_ok() {
+        WATCHPOINTS.lock.lock();
+        unsafe { *WATCHPOINTS.step_over.get() = Some((slot, client_step)) };
+        WATCHPOINTS.lock.unlock();
+    } else {
+        WATCHPOINTS.lock.lock();
+        unsafe { (*WATCHPOINTS.entries.get())[slot] = Watchpoint::empty() };
+        WATCHPOINTS.lock.unlock();
+        serial_println!("[DEBUG] Tek adım yok; gözlem noktası #{} kaldırıldı (tek seferlik).", slot);
+    }
+}
+
+/// Bekleyen bir adım-üzerinden-geçme varsa gözlem noktasını yeniden kurar.
+///
+/// # Dönüş Değeri
+/// Yeniden kurma yapıldıysa istemcinin adımlama isteği; aksi halde `None`.
+fn rearm_watchpoint() -> Option<bool> {
+    WATCHPOINTS.lock.lock();
+    let pending = unsafe { (*WATCHPOINTS.step_over.get()).take() };
+    if let Some((slot, _)) = pending {
+        let wp = unsafe { (*WATCHPOINTS.entries.get())[slot] };
+        if wp.active {
+            let _ = unsafe { arch_debug::set_watchpoint(slot, wp.addr, wp.len, wp.kind) };
+        }
+    }
+    WATCHPOINTS.lock.unlock();
+    pending.map(|(_, client_step)| client_step)
+}
+
+// -----------------------------------------------------------------------------
+// TUZAK KATMANI ENTEGRASYONU
+// -----------------------------------------------------------------------------
+
+/// Mimariye özgü istisna işleyicisinin kesme noktası, tek adım ve gözlem
+/// noktası tuzakları için çağırdığı ortak giriş.
+///
+/// # Parametreler
+/// * `cause`: Çözülmüş tuzak nedeni.
+/// * `pc`: Kaydedilmiş dönüş adresi. Kesme noktasında talimatın kendi adresine çekilir.
+/// * `status`: Kaydedilmiş durum yazmacı (tek adım bayrağı burada tutulur).
+///
+/// # Dönüş Değeri
+/// Tuzak hata ayıklayıcı tarafından işlendiyse `true`; aksi halde çağıran
+/// tuzağı ölümcül olarak ele almalıdır.
+pub fn handle_trap(cause: Cause, pc: &mut u64, status: &mut u64) -> bool {
+    let handler = unsafe { DEBUG_HANDLER };
+
+    match cause {
+        Cause::Breakpoint => {
+            let insn_addr = (*pc as usize).wrapping_sub(arch_debug::BREAKPOINT_PC_ADJUST);
+            if !is_breakpoint(insn_addr) {
+                // Koda gömülü bir kesme noktası talimatı; hata ayıklayıcı yoksa ölümcül.
+                return false;
+            }
+            let Some(handler) = handler else { return false };
+
+            // Özgün talimatı geri koy ve PC'yi ona çek; istemci devam ederken
+            // kesme noktasını tek adım sonrası yeniden kurabilir.
+            unsafe {
+                let _ = clear_breakpoint(insn_addr);
+            }
+            *pc = insn_addr as u64;
+            let step = handler(cause, insn_addr);
+            set_single_step(status, step).is_ok() || !step
+        }
+        Cause::SingleStep => {
+            // Gözlem noktası üzerinden geçmek için kurulan adım istemciye gösterilmez.
+            if rearm_watchpoint() == Some(false) {
+                let _ = set_single_step(status, false);
+                return true;
+            }
+            let Some(handler) = handler else {
+                let _ = set_single_step(status, false);
+                return true;
+            };
+            let step = handler(cause, *pc as usize);
+            set_single_step(status, step).is_ok() || !step
+        }
+        Cause::Watchpoint { addr } => {
+            let Some((slot, wp)) = find_watchpoint(addr) else { return false };
+
+            let step = match handler {
+                Some(handler) => handler(cause, *pc as usize),
+                None => {
+                    serial_println!(
+                        "[DEBUG] Gözlem noktası #{} ({:?}, {:#x}+{}) tetiklendi | PC: {:#x}",
+                        slot, wp.kind, wp.addr, wp.len, *pc
+                    );
+                    false
+                }
+            };
+
+            if arch_debug::WATCH_BEFORE_ACCESS {
+                step_over_watchpoint(slot, step, status);
+                true
+            } else {
+                set_single_step(status, step).is_ok() || !step
+            }
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,565 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/drivers/block/ahci.rs
+// AHCI 1.x SATA denetleyici sürücüsü: bağlı her SATA diski bir blok aygıtı olarak sunar.
+//
+// HBA PCI'dan (sınıf 01.06.01, ABAR = BAR5) bulunur. Her port için komut
+// listesi, FIS alım alanı ve tek bir komut tablosu bir çerçevede; veri için
+// ardışık çerçevelerden bir DMA tamponu ayrılır. Okuma/yazma READ/WRITE DMA
+// EXT (LBA48) ile, komut yuvası 0 üzerinden ve port başına tek komutla yapılır.
+//
+// Tamamlanma kesme ile bildirilir: `handle_irq` port kesme durumunu porta ait
+// atomik alana kaydeder, bekleyen G/Ç bu alanı izler. Kesme hattı yoksa
+// (`irq == u32::MAX`) port kesme durumu doğrudan yoklanır.
+//
+// Kapanışta (`shutdown` zincirinin sürücü aşaması) her portun yazma önbelleği
+// boşaltılır ve komut motoru durdurulur; HBA kesmeleri kapatılır.
+//
+// Varsayım: Çekirdek birebir eşlenmiştir (sanal = fiziksel). Yalnızca 512
+// baytlık mantıksal sektörler desteklenir; ATAPI aygıtları atlanır.
+
+use core::cell::UnsafeCell;
+use core::ptr::{read_volatile, write_volatile};
+use core::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
+use crate::drivers::block::block::{self, BlockDevice};
+use crate::memory::frame;
+use crate::platformgeneric::spinlock::Spinlock;
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// YAZMAÇLAR
+// -----------------------------------------------------------------------------
+
+// HBA genel yazmaçları
+const HBA_CAP: usize = 0x00;
+const HBA_GHC: usize = 0x04;
+const HBA_IS: usize = 0x08;
+const HBA_PI: usize = 0x0C;
+const HBA_CAP2: usize = 0x24;
+const HBA_BOHC: usize = 0x28;
+
+const CAP_SSS: u32 = 1 << 27;
+const GHC_HR: u32 = 1 << 0;
+const GHC_IE: u32 = 1 << 1;
+const GHC_AE: u32 = 1 << 31;
+const CAP2_BOH: u32 = 1 << 0;
+const BOHC_BOS: u32 = 1 << 0;
+const BOHC_OOS: u32 = 1 << 1;
+
+// Port yazmaçları (0x100 + port * 0x80)
+const PORT_BASE: usize = 0x100;
+const PORT_STRIDE: usize = 0x80;
+const PX_CLB: usize = 0x00;
+const PX_FB: usize = 0x08;
+const PX_IS: usize = 0x10;
+const PX_IE: usize = 0x14;
+const PX_CMD: usize = 0x18;
+const PX_TFD: usize = 0x20;
+const PX_SIG: usize = 0x24;
+const PX_SSTS: usize = 0x28;
+const PX_SERR: usize = 0x30;
+const PX_CI: usize = 0x38;
+
+// PxCMD
+const CMD_ST: u32 = 1 << 0;
+const CMD_SUD: u32 = 1 << 1;
+const CMD_POD: u32 = 1 << 2;
+const CMD_FRE: u32 = 1 << 4;
+const CMD_FR: u32 = 1 << 14;
+const CMD_CR: u32 = 1 << 15;
+
+// PxIS / PxIE
+const IS_DHRS: u32 = 1 << 0;
+const IS_TFES: u32 = 1 << 30;
+const IS_ERRORS: u32 = 0x7D80_0000 | IS_TFES;
+
+// PxTFD
+const TFD_ERR: u32 = 1 << 0;
+const TFD_DRQ: u32 = 1 << 3;
+const TFD_BSY: u32 = 1 << 7;
+
+// PxSSTS
+const SSTS_DET_PRESENT: u32 = 3;
+const SSTS_IPM_ACTIVE: u32 = 1;
+
+/// SATA disk imzası (ATAPI: 0xEB14_0101).
+const SIG_ATA: u32 = 0x0000_0101;
+
+// ATA komutları
+const ATA_READ_DMA_EXT: u8 = 0x25;
+const ATA_WRITE_DMA_EXT: u8 = 0x35;
+const ATA_FLUSH_CACHE_EXT: u8 = 0xEA;
+const ATA_IDENTIFY: u8 = 0xEC;
+
+/// Ana makineden aygıta yazmaç FIS'i.
+const FIS_TYPE_REG_H2D: u8 = 0x27;
+
+// Port belleği yerleşimi (tek çerçeve): komut listesi, FIS alanı, komut tablosu
+const CL_OFFSET: usize = 0;
+const FIS_OFFSET: usize = 1024;
+const CT_OFFSET: usize = 2048;
+const PRDT_OFFSET: usize = 0x80;
+
+const SECTOR_SIZE: usize = 512;
+/// DMA tamponu ve tek komutla aktarılan en fazla veri.
+const DMA_BUFFER_SIZE: usize = 64 * 1024;
+/// Tek PRDT girdisinin taşıyabileceği en fazla bayt.
+const PRD_MAX: usize = 4 * 1024 * 1024;
+
+/// Yoklama döngüsü üst sınırı.
+const TIMEOUT_SPINS: u32 = 10_000_000;
+
+// -----------------------------------------------------------------------------
+// PORTLAR
+// -----------------------------------------------------------------------------
+
+/// Bir SATA portunun durumu (`lock` altında).
+struct PortState {
+    /// Port yazmaçlarının adresi.
+    regs: usize,
+    /// Komut listesi / FIS / komut tablosu çerçevesi.
+    mem: usize,
+    /// Veri DMA tamponu.
+    dma: usize,
+    sectors: u64,
+    model: [u8; 40],
+}
+
+/// Blok aygıtı olarak sunulan tek bir SATA portu.
+pub struct AhciPort {
+    name: &'static str,
+    lock: Spinlock,
+    state: UnsafeCell<PortState>,
+    /// Kesme işleyicisinin biriktirdiği PxIS bitleri.
+    irq_status: AtomicU32,
+    /// Tamamlanma kesmeyle mi bildiriliyor?
+    irq_driven: AtomicBool,
+    /// Ait olduğu HBA örneğinin numarası.
+    hba: AtomicUsize,
+    used: AtomicBool,
+}
+
+// Durum `lock` ile korunur.
+unsafe impl Sync for AhciPort {}
+
+impl AhciPort {
+    const fn empty(name: &'static str) -> Self {
+        AhciPort {
+            name,
+            lock: Spinlock::new(),
+            state: UnsafeCell::new(PortState { regs: 0, mem: 0, dma: 0, sectors: 0, model: [b' '; 40] }),
+            irq_status: AtomicU32::new(0),
+            irq_driven: AtomicBool::new(false),
+            hba: AtomicUsize::new(0),
+            used: AtomicBool::new(false),
+        }
+    }
+
+    fn with_state<R>(&self, f: impl FnOnce(&mut PortState) -> R) -> R {
+        self.lock.lock();
+        let result = f(unsafe { &mut *self.state.get() });
+        self.lock.unlock();
+        result
+    }
+
+    /// Komut yuvası 0'da bir ATA komutu yürütür ve tamamlanmasını bekler.
+    ///
+    /// # Parametreler
+    /// * `len`: DMA tamponundaki veri uzunluğu (veri yoksa 0).
+    fn execute(&self, s: &PortState, command: u8, lba: u64, count: u16, len: usize, write: bool) -> Result<(), KernelError> {
+        let header = s.mem + CL_OFFSET;
+        let table = s.mem + CT_OFFSET;
+
+        // Komut tablosu: H2D FIS + PRDT
+        let mut fis = [0u8; 20];
+        fis[0] = FIS_TYPE_REG_H2D;
+        fis[1] = 0x80; // C: komut yazmacı güncellemesi
+        fis[2] = command;
+        fis[4] = lba as u8;
+        fis[5] = (lba >> 8) as u8;
+        fis[6] = (lba >> 16) as u8;
+        fis[7] = 1 << 6; // LBA kipi
+        fis[8] = (lba >> 24) as u8;
+        fis[9] = (lba >> 32) as u8;
+        fis[10] = (lba >> 40) as u8;
+        fis[12] = count as u8;
+        fis[13] = (count >> 8) as u8;
+        for (i, word) in fis.chunks(4).enumerate() {
+            w32(table + i * 4, u32::from_le_bytes([word[0], word[1], word[2], word[3]]));
+        }
+
+        let prds = len.div_ceil(PRD_MAX);
+        for i in 0..prds {
+            let addr = s.dma + i * PRD_MAX;
+            let part = (len - i * PRD_MAX).min(PRD_MAX);
+            let prd = table + PRDT_OFFSET + i * 16;
+            w32(prd, addr as u32);
+            w32(prd + 4, (addr as u64 >> 32) as u32);
+            w32(prd + 8, 0);
+            w32(prd + 12, (part - 1) as u32);
+        }
+
+        // Komut başlığı: CFL = 5 dword, W, PRDTL
+        let mut dw0 = 5 | (prds as u32) << 16;
+        if write {
+            dw0 |= 1 << 6;
+        }
+        w32(header, dw0);
+        w32(header + 4, 0);
+        w32(header + 8, table as u32);
+        w32(header + 12, (table as u64 >> 32) as u32);
+
+        wait_reg(s.regs + PX_TFD, TFD_BSY | TFD_DRQ, 0)?;
+        self.irq_status.store(0, Ordering::Relaxed);
+        w32(s.regs + PX_CI, 1);
+
+        let irq_driven = self.irq_driven.load(Ordering::Relaxed);
+        let mut status = 0;
+        for _ in 0..TIMEOUT_SPINS {
+            // Kesme kipinde durum kesme işleyicisinden gelir; yazmaç yalnızca sonunda okunur
+            status |= if irq_driven {
+                self.irq_status.swap(0, Ordering::Acquire)
+            } else {
+                let is = r32(s.regs + PX_IS);
+                w32(s.regs + PX_IS, is);
+                is
+            };
+            if irq_driven && status & (IS_DHRS | IS_ERRORS) == 0 {
+                core::hint::spin_loop();
+                continue;
+            }
+            if status & IS_ERRORS != 0 || r32(s.regs + PX_TFD) & TFD_ERR != 0 {
+                serial_println!("[AHCI] {}: komut {:#04x} hatası (IS {:#x}, TFD {:#x})", self.name, command, status, r32(s.regs + PX_TFD));
+                recover(s.regs);
+                return Err(KernelError::GenericFailure);
+            }
+            if r32(s.regs + PX_CI) & 1 == 0 {
+                return Ok(());
+            }
+            core::hint::spin_loop();
+        }
+        recover(s.regs);
+        Err(KernelError::GenericFailure)
+    }
+
+    fn transfer(&self, lba: u64, len: usize, mut chunk: impl FnMut(&PortState, u64, usize, usize) -> Result<(), KernelError>) -> Result<(), KernelError> {
+        let count = block::check_range(self, lba, len)?;
+        self.with_state(|s| {
+            let per_cmd = (DMA_BUFFER_SIZE / SECTOR_SIZE) as u64;
+            let mut done = 0;
+            while done < count {
+                let n = (count - done).min(per_cmd);
+                chunk(s, lba + done, (done as usize) * SECTOR_SIZE, n as usize * SECTOR_SIZE)?;
+                done += n;
+            }
+            Ok(())
+        })
+    }
+}
+
+impl BlockDevice for AhciPort {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn block_size(&self) -> usize {
+        SECTOR_SIZE
+    }
+
+    fn block_count(&self) -> u64 {
+        self.with_state(|s| s.sectors)
+    }
+
+    fn read_blocks(&self, lba: u64, buf: &mut [u8]) -> Result<(), KernelError> {
+        let len = buf.len();
+        self.transfer(lba, len, |s, lba, off, bytes| {
+            self.execute(s, ATA_READ_DMA_EXT, lba, (bytes / SECTOR_SIZE) as u16, bytes, false)?;
+            unsafe { core::ptr::copy_nonoverlapping(s.dma as *const u8, buf[off..].as_mut_ptr(), bytes) };
+            Ok(())
+        })
+    }
+
+    fn write_blocks(&self, lba: u64, buf: &[u8]) -> Result<(), KernelError> {
+        self.transfer(lba, buf.len(), |s, lba, off, bytes| {
+            unsafe { core::ptr::copy_nonoverlapping(buf[off..].as_ptr(), s.dma as *mut u8, bytes) };
+            self.execute(s, ATA_WRITE_DMA_EXT, lba, (bytes / SECTOR_SIZE) as u16, bytes, true)
+        })
+    }
+
+    fn flush(&self) -> Result<(), KernelError> {
+        self.with_state(|s| self.execute(s, ATA_FLUSH_CACHE_EXT, 0, 0, 0, false))
+    }
+}
+
+// -----------------------------------------------------------------------------
+// YARDIMCILAR
+// -----------------------------------------------------------------------------
+
+#[inline(always)]
+fn r32(addr: usize) -> u32 {
+    unsafe { read_volatile(addr as *const u32) }
+}
+
+#[inline(always)]
+fn w32(addr: usize, value: u32) {
+    unsafe { write_volatile(addr as *mut u32, value) }
+}
+
+fn wait_reg(addr: usize, mask: u32, want: u32) -> Result<(), KernelError> {
+    for _ in 0..TIMEOUT_SPINS {
+        if r32(addr) & mask == want {
+            return Ok(());
+        }
+    }
+    Err(KernelError::GenericFailure)
+}
+
+/// Komut motorunu durdurur (ST ve FRE kapalı, CR ve FR temizlenene kadar bekler).
+fn stop_port(regs: usize) -> Result<(), KernelError> {
+    w32(regs + PX_CMD, r32(regs + PX_CMD) & !CMD_ST);
+    wait_reg(regs + PX_CMD, CMD_CR, 0)?;
+    w32(regs + PX_CMD, r32(regs + PX_CMD) & !CMD_FRE);
+    wait_reg(regs + PX_CMD, CMD_FR, 0)
+}
+
+fn start_port(regs: usize) -> Result<(), KernelError> {
+    wait_reg(regs + PX_CMD, CMD_CR, 0)?;
+    w32(regs + PX_CMD, r32(regs + PX_CMD) | CMD_FRE);
+    wait_reg(regs + PX_TFD, TFD_BSY | TFD_DRQ, 0)?;
+    w32(regs + PX_CMD, r32(regs + PX_CMD) | CMD_ST);
+    Ok(())
+}
+
+/// Hatalı komuttan sonra portu yeniden başlatır (AHCI 6.2.2.1).
+fn recover(regs: usize) {
+    let _ = stop_port(regs);
+    w32(regs + PX_SERR, u32::MAX);
+    w32(regs + PX_IS, u32::MAX);
+    let _ = start_port(regs);
+}
+
+/// Ürün yazılımından (BIOS) HBA sahipliğini devralır.
+fn bios_handoff(abar: usize) {
+    if r32(abar + HBA_CAP2) & CAP2_BOH == 0 {
+        return;
+    }
+    w32(abar + HBA_BOHC, r32(abar + HBA_BOHC) | BOHC_OOS);
+    if wait_reg(abar + HBA_BOHC, BOHC_BOS, 0).is_err() {
+        serial_println!("[AHCI] BIOS sahipliği bırakmadı; devam ediliyor");
+    }
+}
+
+/// IDENTIFY DEVICE verisinden kapasite ve model bilgisini çözer.
+fn parse_identify(s: &mut PortState) {
+    let id = unsafe { core::slice::from_raw_parts(s.dma as *const u16, 256) };
+    s.sectors = (0..4).fold(0u64, |acc, i| acc | (id[100 + i] as u64) << (16 * i));
+    if s.sectors == 0 {
+        // LBA48 desteklenmiyor: 28 bit kapasite
+        s.sectors = id[60] as u64 | (id[61] as u64) << 16;
+    }
+    // Model dizesi: kelime 27-46, her kelimede baytlar ters sırada
+    for (i, word) in id[27..47].iter().enumerate() {
+        s.model[i * 2] = (word >> 8) as u8;
+        s.model[i * 2 + 1] = *word as u8;
+    }
+    let logical_large = id[106] & 0xC000 == 0x4000 && id[106] & (1 << 12) != 0;
+    if logical_large {
+        serial_println!("[AHCI] Uyarı: 512 bayttan büyük mantıksal sektör desteklenmez");
+        s.sectors = 0;
+    }
+}
+
+// -----------------------------------------------------------------------------
+// KESİLER VE KEŞİF
+// -----------------------------------------------------------------------------
+
+/// Tüm HBA'lardaki en fazla SATA portu.
+const MAX_PORTS: usize = 8;
+
+static PORTS: [AhciPort; MAX_PORTS] = [
+    AhciPort::empty("sata0"),
+    AhciPort::empty("sata1"),
+    AhciPort::empty("sata2"),
+    AhciPort::empty("sata3"),
+    AhciPort::empty("sata4"),
+    AhciPort::empty("sata5"),
+    AhciPort::empty("sata6"),
+    AhciPort::empty("sata7"),
+];
+
+/// En fazla HBA sayısı.
+const MAX_HBAS: usize = 2;
+
+/// Bir HBA'nın ABAR adresi, kesme numarası ve port -> `PORTS` eşlemesi.
+struct Hba {
+    abar: AtomicUsize,
+    irq: AtomicU32,
+    /// Port numarası başına `PORTS` indeksi + 1 (0: yok).
+    ports: [AtomicU32; 32],
+    used: AtomicBool,
+}
+
+#[allow(clippy::declare_interior_mutable_const)]
+const NO_PORT: AtomicU32 = AtomicU32::new(0);
+#[allow(clippy::declare_interior_mutable_const)]
+const EMPTY_HBA: Hba = Hba {
+    abar: AtomicUsize::new(0),
+    irq: AtomicU32::new(u32::MAX),
+    ports: [NO_PORT; 32],
+    used: AtomicBool::new(false),
+};
+static HBAS: [Hba; MAX_HBAS] = [EMPTY_HBA; MAX_HBAS];
+
+/// Mimari kesme dağıtıcısından çağrılır.
+///
+/// # Dönüş Değeri
+/// Kesme bir AHCI HBA'sına aitse `true`.
+pub fn handle_irq(irq: u32) -> bool {
+    let mut handled = false;
+    for hba in HBAS.iter().filter(|h| h.used.load(Ordering::Acquire) && h.irq.load(Ordering::Relaxed) == irq) {
+        let abar = hba.abar.load(Ordering::Relaxed);
+        let pending = r32(abar + HBA_IS);
+        for bit in (0..32).filter(|b| pending & (1 << b) != 0) {
+            let regs = abar + PORT_BASE + bit * PORT_STRIDE;
+            let is = r32(regs + PX_IS);
+            w32(regs + PX_IS, is);
+            if let Some(port) = hba.ports[bit].load(Ordering::Relaxed).checked_sub(1) {
+                PORTS[port as usize].irq_status.fetch_or(is, Ordering::Release);
+            }
+        }
+        w32(abar + HBA_IS, pending);
+        handled = true;
+    }
+    handled
+}
+
+/// Bir portu başlatır, diski tanır ve blok aygıtı olarak kaydeder.
+fn init_port(hba_index: usize, abar: usize, port: usize, irq_driven: bool) -> Result<(), KernelError> {
+    let regs = abar + PORT_BASE + port * PORT_STRIDE;
+    let ssts = r32(regs + PX_SSTS);
+    if ssts & 0xF != SSTS_DET_PRESENT || (ssts >> 8) & 0xF != SSTS_IPM_ACTIVE {
+        return Ok(());
+    }
+    if r32(regs + PX_SIG) != SIG_ATA {
+        return Ok(());
+    }
+    let slot = PORTS
+        .iter()
+        .position(|p| !p.used.swap(true, Ordering::AcqRel))
+        .ok_or(KernelError::OutOfMemoryStatic)?;
+    let dev = &PORTS[slot];
+
+    stop_port(regs)?;
+    let mem = frame::alloc()?;
+    let dma = frame::alloc_contiguous(DMA_BUFFER_SIZE.div_ceil(frame::FRAME_SIZE))?;
+    unsafe { core::ptr::write_bytes(mem as *mut u8, 0, frame::FRAME_SIZE) };
+    w32(regs + PX_CLB, (mem + CL_OFFSET) as u32);
+    w32(regs + PX_CLB + 4, ((mem + CL_OFFSET) as u64 >> 32) as u32);
+    w32(regs + PX_FB, (mem + FIS_OFFSET) as u32);
+    w32(regs + PX_FB + 4, ((mem + FIS_OFFSET) as u64 >> 32) as u32);
+    w32(regs + PX_SERR, u32::MAX);
+    w32(regs + PX_IS, u32::MAX);
+    w32(regs + PX_IE, if irq_driven { IS_DHRS | IS_ERRORS } else { 0 });
+    start_port(regs)?;
+
+    HBAS[hba_index].ports[port].store(slot as u32 + 1, Ordering::Release);
+    dev.hba.store(hba_index, Ordering::Relaxed);
+    dev.irq_driven.store(irq_driven, Ordering::Relaxed);
+
+    dev.with_state(|s| {
+        s.regs = regs;
+        s.mem = mem;
+        s.dma = dma;
+        dev.execute(s, ATA_IDENTIFY, 0, 0, SECTOR_SIZE, false)?;
+        parse_identify(s);
+        let model = core::str::from_utf8(&s.model).unwrap_or("?").trim();
+        serial_println!("[AHCI] {}: port {}, {}", dev.name, port, model);
+        Ok(())
+    })?;
+
+    block::register(dev)?;
+    Ok(())
+}
+
+/// HBA'yı AHCI kipine alır ve uygulanan portları başlatır.
+fn init_hba(abar: usize, irq: u32) -> Result<(), KernelError> {
+    let index = HBAS
+        .iter()
+        .position(|h| !h.used.swap(true, Ordering::AcqRel))
+        .ok_or(KernelError::OutOfMemoryStatic)?;
+    let hba = &HBAS[index];
+    hba.abar.store(abar, Ordering::Relaxed);
+    hba.irq.store(irq, Ordering::Relaxed);
+
+    bios_handoff(abar);
+    w32(abar + HBA_GHC, GHC_AE);
+    w32(abar + HBA_GHC, GHC_AE | GHC_HR);
+    wait_reg(abar + HBA_GHC, GHC_HR, 0)?;
+    w32(abar + HBA_GHC, GHC_AE);
+
+    let cap = r32(abar + HBA_CAP);
+    let implemented = r32(abar + HBA_PI);
+    let irq_driven = irq != u32::MAX;
+    serial_println!("[AHCI] HBA {:#x}: {} port, PI {:#x}", abar, (cap & 0x1F) + 1, implemented);
+
+    // IDENTIFY de kesmeyle tamamlanır; portlar başlatılmadan önce açılır
+    w32(abar + HBA_IS, u32::MAX);
+    if irq_driven {
+        w32(abar + HBA_GHC, GHC_AE | GHC_IE);
+    }
+
+    for port in (0..32).filter(|p| implemented & (1 << p) != 0) {
+        let regs = abar + PORT_BASE + port * PORT_STRIDE;
+        // Kademeli dönme (staggered spin-up) destekleniyorsa aygıtı döndür
+        if cap & CAP_SSS != 0 {
+            w32(regs + PX_CMD, r32(regs + PX_CMD) | CMD_SUD | CMD_POD);
+        }
+        if let Err(e) = init_port(index, abar, port, irq_driven) {
+            serial_println!("[AHCI] Port {}: başlatılamadı: {:?}", port, e);
+        }
+    }
+    Ok(())
+}
+
+/// Kapanış bildirimi: portların önbelleğini boşaltır ve DMA'yı durdurur.
+fn quiesce(_action: crate::shutdown::Action) -> Result<(), KernelError> {
+    let mut result = Ok(());
+    for port in PORTS.iter().filter(|p| p.used.load(Ordering::Acquire)) {
+        // Kilit, süren komut tamamlanana kadar bekletir
+        port.with_state(|s| {
+            if s.regs == 0 {
+                return;
+            }
+            if let Err(e) = port.execute(s, ATA_FLUSH_CACHE_EXT, 0, 0, 0, false).and_then(|_| stop_port(s.regs)) {
+                result = result.and(Err(e));
+            }
+            w32(s.regs + PX_IE, 0);
+        });
+    }
+    for hba in HBAS.iter().filter(|h| h.used.load(Ordering::Acquire)) {
+        w32(hba.abar.load(Ordering::Relaxed) + HBA_GHC, GHC_AE);
+    }
+    result
+}
+
+fn probe() -> Result<(), KernelError> {
+    let mut found = false;
+    for dev in crate::drivers::pci::find_class(0x01, 0x06, 0x01) {
+        let Some(abar) = dev.bar(5) else { continue };
+        dev.enable();
+        dev.set_intx(true);
+
+        // INTx eşlemesi olmayan mimarilerde yoklamayla çalışılır
+        let irq = crate::arch::interrupt::route_pci_intx(dev.irq_line).unwrap_or(u32::MAX);
+
+        init_hba(abar as usize, irq)?;
+        found = true;
+    }
+    if found {
+        crate::shutdown::register("ahci", crate::shutdown::Phase::Drivers, quiesce)?;
+    }
+    Ok(())
+}
+
+crate::initcall!(drivers, "ahci", probe);
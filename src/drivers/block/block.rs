@@ -0,0 +1,171 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/drivers/block/block.rs
+// Blok aygıtı katmanı: depolama sürücülerinin ortak arayüzü ve aygıt kaydı.
+//
+// SD/MMC, SATA ve benzeri sürücüler keşfettikleri her ortamı `register` ile
+// kaydeder; dosya sistemleri ve önbellek katmanları aygıta yalnızca bu arayüz
+// üzerinden, blok numarası (LBA) ile erişir. Arabellek uzunlukları blok
+// boyutunun katı olmalıdır.
+
+use core::cell::UnsafeCell;
+use crate::platformgeneric::spinlock::Spinlock;
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// ARAYÜZ
+// -----------------------------------------------------------------------------
+
+/// Blok aygıtı sürücüsü.
+pub trait BlockDevice: Sync {
+    /// Aygıt adı (örn. "mmc0").
+    fn name(&self) -> &'static str;
+
+    /// Blok boyutu (bayt).
+    fn block_size(&self) -> usize;
+
+    /// Toplam blok sayısı (ortam yoksa 0).
+    fn block_count(&self) -> u64;
+
+    /// Çıkarılabilir ortam takılı mı?
+    fn is_present(&self) -> bool {
+        true
+    }
+
+    /// `lba`'dan başlayarak `buf.len() / block_size()` blok okur.
+    fn read_blocks(&self, lba: u64, buf: &mut [u8]) -> Result<(), KernelError>;
+
+    /// `lba`'dan başlayarak `buf.len() / block_size()` blok yazar.
+    fn write_blocks(&self, lba: u64, buf: &[u8]) -> Result<(), KernelError>;
+
+    /// Aygıtın yazma önbelleğini kalıcı ortama boşaltır.
+    fn flush(&self) -> Result<(), KernelError> {
+        Ok(())
+    }
+}
+
+/// Aktarım parametrelerini aygıtın boyutuna göre doğrular.
+///
+/// # Dönüş Değeri
+/// Aktarılacak blok sayısı.
+pub fn check_range(dev: &dyn BlockDevice, lba: u64, len: usize) -> Result<u64, KernelError> {
+    let bs = dev.block_size();
+    if bs == 0 || len == 0 || len % bs != 0 {
+        return Err(KernelError::InvalidArgument);
+    }
+    if !dev.is_present() {
+        return Err(KernelError::NotFound);
+    }
+    let count = (len / bs) as u64;
+    match lba.checked_add(count) {
+        Some(end) if end <= dev.block_count() => Ok(count),
+        _ => Err(KernelError::InvalidArgument),
+    }
+}
+
+// -----------------------------------------------------------------------------
+// AYGIT KAYDI
+// -----------------------------------------------------------------------------
+
+/// En fazla blok aygıtı sayısı.
+pub const MAX_BLOCK_DEVICES: usize = 8;
+
+struct DeviceTable {
+    lock: Spinlock,
+    devices: UnsafeCell<[Option<&'static dyn BlockDevice>; MAX_BLOCK_DEVICES]>,
+}
+
+// Tabloya erişim `lock` ile korunur.
+unsafe impl Sync for DeviceTable {}
+
+static DEVICES: DeviceTable = DeviceTable {
+    lock: Spinlock::new(),
+    devices: UnsafeCell::new([None; MAX_BLOCK_DEVICES]),
+};
+
+/// Bir blok aygıtını kaydeder.
+///
+/// # Dönüş Değeri
+/// Aygıt numarası; tablo doluysa `OutOfMemoryStatic`.
+pub fn register(dev: &'static dyn BlockDevice) -> Result<usize, KernelError> {
+    DEVICES.lock.lock();
+    let devices = unsafe { &mut *DEVICES.devices.get() };
+    let result = match devices.iter().position(|d| d.is_none()) {
+        Some(id) => {
+            devices[id] = Some(dev);
+            Ok(id)
+        }
+        None => Err(KernelError::OutOfMemoryStatic),
+    };
+    DEVICES.lock.unlock();
+
+    if result.is_ok() {
+        serial_println!(
+            "[BLK] {}: {} blok x {} B ({} MiB)",
+            dev.name(),
+            dev.block_count(),
+            dev.block_size(),
+            dev.block_count() * dev.block_size() as u64 / (1024 * 1024)
+        );
+    }
+    result
+}
+
+/// Numarası verilen aygıt.
+pub fn device(id: usize) -> Option<&'static dyn BlockDevice> {
+    DEVICES.lock.lock();
+    let dev = unsafe { (*DEVICES.devices.get()).get(id).copied().flatten() };
+    DEVICES.lock.unlock();
+    dev
+}
+
+/// Adı verilen aygıt.
+pub fn find(name: &str) -> Option<&'static dyn BlockDevice> {
+    (0..MAX_BLOCK_DEVICES).filter_map(device).find(|d| d.name() == name)
+}
+
+// -----------------------------------------------------------------------------
+// KABUK VE PROCFS
+// -----------------------------------------------------------------------------
+
+fn blk_command(args: &[&str]) -> Result<(), KernelError> {
+    match (args.get(1).copied(), args.get(2), args.get(3)) {
+        (Some("read"), Some(name), lba) => {
+            let dev = find(name).ok_or(KernelError::NotFound)?;
+            let lba = lba.and_then(|s| s.parse().ok()).unwrap_or(0);
+            let mut buf = [0u8; 512];
+            let len = dev.block_size().min(buf.len());
+            dev.read_blocks(lba, &mut buf[..len])?;
+            for (row, chunk) in buf[..len].chunks(16).enumerate() {
+                crate::serial_print!("  {:04x}:", row * 16);
+                chunk.iter().for_each(|b| crate::serial_print!(" {:02x}", b));
+                serial_println!();
+            }
+            Ok(())
+        }
+        _ => {
+            for dev in (0..MAX_BLOCK_DEVICES).filter_map(device) {
+                serial_println!(
+                    "  {:<8} {:>12} blok x {:>4} B {}",
+                    dev.name(),
+                    dev.block_count(),
+                    dev.block_size(),
+                    if dev.is_present() { "" } else { "(ortam yok)" }
+                );
+            }
+            Ok(())
+        }
+    }
+}
+
+crate::shell_command!("blk", "Blok aygıtları (blk [read <aygıt> <lba>])", blk_command);
+
+fn show_devices(out: &mut dyn core::fmt::Write) -> core::fmt::Result {
+    for dev in (0..MAX_BLOCK_DEVICES).filter_map(device) {
+        writeln!(out, "{} {} {} present={}", dev.name(), dev.block_count(), dev.block_size(), dev.is_present())?;
+    }
+    Ok(())
+}
+
+crate::proc_entry!("drivers/block", show_devices);
@@ -0,0 +1,423 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/drivers/block/cache.rs
+// Blok aygıtları için sayfa önbelleği (page cache).
+//
+// Dosya sistemleri aygıta doğrudan `read_blocks`/`write_blocks` yerine bu
+// katmanın `read`/`write` fonksiyonlarıyla erişir. Önbellek sayfası bir
+// çerçevedir (`FRAME_SIZE`) ve aygıtın ardışık bloklarını tutar; anahtar
+// (aygıt numarası, sayfanın ilk bloğu) çiftidir. Küçük, sık erişimler (FAT
+// tablosu, dizin blokları) böylece aygıta her seferinde gitmez.
+//
+// * Okumada eksik sayfa aygıttan bir kerede yüklenir; sayfayı tamamen örten
+//   yazmalar okuma yapmaz.
+// * Yazmalar yalnızca önbelleğe yapılır ve sayfa kirli işaretlenir (write-back).
+//   Geri yazıcı işçi (`pageflush`, bkz. `kthread`) her
+//   `pagecache.flush_interval` saniyede `pagecache.dirty_expire` saniyeden
+//   eski kirli sayfaları aygıta yazar, durdurulurken tümünü yazar; `sync`
+//   hepsini hemen yazar ve aygıt önbelleğini boşaltır. Kapanışta `sync`
+//   `shutdown` zincirinin dosya sistemi aşamasında çağrılır.
+// * Tablo dolduğunda veya çerçeve ayırıcısı bellek baskısı bildirdiğinde
+//   (`frame::set_reclaim_hook`) en uzun süredir kullanılmayan (LRU) sayfalar
+//   çıkarılır; önce temiz sayfalar, gerekirse kirliler geri yazılarak.
+// * Aygıt G/Ç'si `iosched` kuyruğundan geçer; sayfa yüklenirken veya geri
+//   yazılırken yalnızca çağıran görev bloklanır.
+//
+// Kabuk komutu: `pcache [sync|drop]`; procfs: `/proc/pagecache`
+
+use core::cell::UnsafeCell;
+use core::fmt;
+use crate::drivers::block::block::{self, BlockDevice};
+use crate::drivers::block::iosched;
+use crate::kthread::Work;
+use crate::memory::frame::{self, FRAME_SIZE};
+use crate::platformgeneric::spinlock::Spinlock;
+use crate::platformgeneric::{KernelError, SystemConstants};
+use crate::serial_println;
+use crate::sysctl::U32;
+
+// -----------------------------------------------------------------------------
+// SABİTLER VE TİPLER
+// -----------------------------------------------------------------------------
+
+/// Önbellekteki en fazla sayfa (4 KiB sayfalarda 1 MiB).
+pub const MAX_PAGES: usize = 256;
+
+/// Geri yazıcı görevin önceliği.
+const FLUSH_TASK_PRIORITY: u8 = 20;
+
+#[derive(Clone, Copy)]
+struct CachePage {
+    dev: usize,
+    /// Sayfanın ilk bloğu (sayfa başına blok sayısının katı).
+    lba: u64,
+    /// Sayfadaki geçerli blok sayısı (aygıt sonunda tam sayfadan az olabilir).
+    blocks: usize,
+    frame: usize,
+    dirty: bool,
+    /// İlk kirlendiği tik.
+    dirtied_at: u64,
+    /// LRU damgası: son erişimdeki `Cache::clock`.
+    stamp: u64,
+}
+
+/// Önbellek istatistikleri.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CacheStats {
+    pub pages: usize,
+    pub dirty: usize,
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    pub writebacks: u64,
+}
+
+struct Cache {
+    lock: Spinlock,
+    pages: UnsafeCell<[Option<CachePage>; MAX_PAGES]>,
+    clock: UnsafeCell<u64>,
+    stats: UnsafeCell<CacheStats>,
+}
+
+// Tüm alanlara erişim `lock` ile korunur.
+unsafe impl Sync for Cache {}
+
+static CACHE: Cache = Cache {
+    lock: Spinlock::new(),
+    pages: UnsafeCell::new([None; MAX_PAGES]),
+    clock: UnsafeCell::new(0),
+    stats: UnsafeCell::new(CacheStats { pages: 0, dirty: 0, hits: 0, misses: 0, evictions: 0, writebacks: 0 }),
+};
+
+crate::sysctl!(FLUSH_INTERVAL: U32 = U32::new(5, 1, 3600),
+    "pagecache.flush_interval", "Geri yazıcı görevin çalışma aralığı (sn)");
+crate::sysctl!(DIRTY_EXPIRE: U32 = U32::new(30, 0, 3600),
+    "pagecache.dirty_expire", "Kirli sayfanın geri yazılmadan önce bekleyebileceği süre (sn)");
+
+struct State<'a> {
+    pages: &'a mut [Option<CachePage>; MAX_PAGES],
+    clock: &'a mut u64,
+    stats: &'a mut CacheStats,
+}
+
+fn with_cache<R>(f: impl FnOnce(&mut State) -> R) -> R {
+    CACHE.lock.lock();
+    // SAFETY: Kilit tutulduğu sürece duruma tek erişen biziz.
+    let result = unsafe {
+        f(&mut State { pages: &mut *CACHE.pages.get(), clock: &mut *CACHE.clock.get(), stats: &mut *CACHE.stats.get() })
+    };
+    CACHE.lock.unlock();
+    result
+}
+
+fn blocks_per_page(dev: &dyn BlockDevice) -> Result<usize, KernelError> {
+    let bs = dev.block_size();
+    if bs == 0 || bs > FRAME_SIZE || !FRAME_SIZE.is_multiple_of(bs) {
+        return Err(KernelError::InvalidArgument);
+    }
+    Ok(FRAME_SIZE / bs)
+}
+
+// -----------------------------------------------------------------------------
+// SAYFA YÖNETİMİ
+// -----------------------------------------------------------------------------
+
+/// Kirli sayfayı aygıta yazar.
+fn write_back(page: &mut CachePage, stats: &mut CacheStats) -> Result<(), KernelError> {
+    if !page.dirty {
+        return Ok(());
+    }
+    let dev = block::device(page.dev).ok_or(KernelError::NotFound)?;
+    // SAFETY: Çerçeve bu sayfaya aittir ve çekirdekte birebir eşlidir.
+    let data = unsafe { core::slice::from_raw_parts(page.frame as *const u8, page.blocks * dev.block_size()) };
+    iosched::write(page.dev, page.lba, data)?;
+    page.dirty = false;
+    stats.writebacks += 1;
+    Ok(())
+}
+
+/// Çıkarılacak sayfa: en eski temiz sayfa, yoksa en eski kirli.
+fn lru_victim(pages: &[Option<CachePage>; MAX_PAGES]) -> Option<usize> {
+    let oldest = |dirty: bool| {
+        (0..MAX_PAGES)
+            .filter(|&i| pages[i].is_some_and(|p| p.dirty == dirty))
+            .min_by_key(|&i| pages[i].map_or(u64::MAX, |p| p.stamp))
+    };
+    oldest(false).or_else(|| oldest(true))
+}
+
+/// Sayfayı (gerekirse geri yazıp) çıkarır ve çerçevesini döndürür.
+fn evict(state: &mut State, index: usize) -> Result<usize, KernelError> {
+    let page = state.pages[index].as_mut().ok_or(KernelError::NotFound)?;
+    write_back(page, state.stats)?;
+    let frame = page.frame;
+    state.pages[index] = None;
+    state.stats.evictions += 1;
+    Ok(frame)
+}
+
+/// `(dev, lba)` sayfasının indeksini döndürür; yoksa ayırır ve `load` ise
+/// aygıttan okur.
+fn get_page(state: &mut State, id: usize, dev: &dyn BlockDevice, lba: u64, load: bool) -> Result<usize, KernelError> {
+    *state.clock += 1;
+    let stamp = *state.clock;
+    if let Some(index) = state.pages.iter().position(|p| p.is_some_and(|p| p.dev == id && p.lba == lba)) {
+        state.stats.hits += 1;
+        if let Some(page) = state.pages[index].as_mut() {
+            page.stamp = stamp;
+        }
+        return Ok(index);
+    }
+    state.stats.misses += 1;
+
+    // Boş yuva + yeni çerçeve; olmazsa LRU sayfanın yuvası ve çerçevesi
+    let free_slot = state.pages.iter().position(|p| p.is_none());
+    let (index, frame) = match free_slot.map(|slot| (slot, frame::alloc())) {
+        Some((slot, Ok(frame))) => (slot, frame),
+        _ => {
+            let victim = lru_victim(state.pages).ok_or(KernelError::OutOfMemoryStatic)?;
+            (victim, evict(state, victim)?)
+        }
+    };
+
+    let blocks = (dev.block_count().saturating_sub(lba) as usize).min(blocks_per_page(dev)?);
+    if load {
+        // SAFETY: Çerçeve bu sayfaya ayrıldı ve çekirdekte birebir eşlidir.
+        let buf = unsafe { core::slice::from_raw_parts_mut(frame as *mut u8, blocks * dev.block_size()) };
+        if let Err(e) = iosched::read(id, lba, buf) {
+            frame::free(frame, 1).ok();
+            return Err(e);
+        }
+    }
+    state.pages[index] = Some(CachePage { dev: id, lba, blocks, frame, dirty: false, dirtied_at: 0, stamp });
+    Ok(index)
+}
+
+/// `lba`'dan başlayan blok aralığını sayfa sayfa dolaşır:
+/// `f(sayfa indeksi, sayfa içi bayt ofseti, aktarım bayt ofseti, uzunluk)`.
+fn for_each_page(
+    id: usize,
+    lba: u64,
+    len: usize,
+    full_page_skips_load: bool,
+    mut f: impl FnMut(&mut State, usize, usize, usize, usize),
+) -> Result<(), KernelError> {
+    let dev = block::device(id).ok_or(KernelError::NotFound)?;
+    block::check_range(dev, lba, len)?;
+    let bs = dev.block_size();
+    let bpp = blocks_per_page(dev)? as u64;
+
+    with_cache(|state| {
+        let mut done = 0;
+        while done < len {
+            let block = lba + (done / bs) as u64;
+            let page_lba = block - block % bpp;
+            let in_page = (block - page_lba) as usize * bs;
+            let chunk = (FRAME_SIZE - in_page).min(len - done);
+            let load = !(full_page_skips_load && in_page == 0 && chunk == FRAME_SIZE);
+            let index = get_page(state, id, dev, page_lba, load)?;
+            f(state, index, in_page, done, chunk);
+            done += chunk;
+        }
+        Ok(())
+    })
+}
+
+// -----------------------------------------------------------------------------
+// OKUMA / YAZMA
+// -----------------------------------------------------------------------------
+
+/// `id` numaralı aygıtın `lba` bloğundan başlayarak `buf`'ı doldurur.
+///
+/// # Hatalar
+/// * `NotFound`: Aygıt yok veya ortam takılı değil.
+/// * `InvalidArgument`: Uzunluk blok boyutunun katı değil veya aralık aygıt dışında.
+/// * Aygıt okuma hataları olduğu gibi döner.
+pub fn read(id: usize, lba: u64, buf: &mut [u8]) -> Result<(), KernelError> {
+    for_each_page(id, lba, buf.len(), false, |state, index, in_page, at, len| {
+        if let Some(page) = state.pages[index] {
+            // SAFETY: Çerçeve sayfaya aittir; aralık sayfa içindedir.
+            let src = unsafe { core::slice::from_raw_parts((page.frame + in_page) as *const u8, len) };
+            buf[at..at + len].copy_from_slice(src);
+        }
+    })
+}
+
+/// `data`'yı `lba` bloğundan başlayarak önbelleğe yazar; aygıta geri yazıcı
+/// görev veya `sync` yazar.
+///
+/// # Hatalar
+/// `read` ile aynı.
+pub fn write(id: usize, lba: u64, data: &[u8]) -> Result<(), KernelError> {
+    let now = crate::sched::ticks();
+    for_each_page(id, lba, data.len(), true, |state, index, in_page, at, len| {
+        if let Some(page) = state.pages[index].as_mut() {
+            // SAFETY: Çerçeve sayfaya aittir; aralık sayfa içindedir.
+            let dst = unsafe { core::slice::from_raw_parts_mut((page.frame + in_page) as *mut u8, len) };
+            dst.copy_from_slice(&data[at..at + len]);
+            if !page.dirty {
+                page.dirty = true;
+                page.dirtied_at = now;
+            }
+        }
+    })
+}
+
+/// Kirli sayfaları geri yazar: `id` verilirse yalnızca o aygıtınkileri,
+/// `older_than` verilirse yalnızca o tikten önce kirlenenleri.
+fn flush_pages(id: Option<usize>, older_than: Option<u64>) -> Result<usize, KernelError> {
+    with_cache(|state| {
+        let mut written = 0;
+        let mut result = Ok(());
+        for page in state.pages.iter_mut().flatten() {
+            let selected = page.dirty
+                && id.is_none_or(|id| page.dev == id)
+                && older_than.is_none_or(|tick| page.dirtied_at < tick);
+            if !selected {
+                continue;
+            }
+            match write_back(page, state.stats) {
+                Ok(()) => written += 1,
+                // Hata diğer sayfaların yazılmasını engellemez; ilki döndürülür
+                Err(e) => result = result.and(Err(e)),
+            }
+        }
+        result.map(|_| written)
+    })
+}
+
+/// Kirli sayfaları hemen yazar ve aygıtların yazma önbelleğini boşaltır.
+pub fn sync(id: Option<usize>) -> Result<(), KernelError> {
+    flush_pages(id, None)?;
+    for dev in (0..block::MAX_BLOCK_DEVICES).filter(|&d| id.is_none_or(|id| d == id)).filter_map(block::device) {
+        dev.flush()?;
+    }
+    Ok(())
+}
+
+/// Aygıtın sayfalarını geri yazıp önbellekten çıkarır (ortam çıkarılırken).
+pub fn invalidate(id: usize) -> Result<(), KernelError> {
+    flush_pages(Some(id), None)?;
+    with_cache(|state| {
+        for slot in state.pages.iter_mut() {
+            if let Some(page) = slot.take_if(|p| p.dev == id) {
+                frame::free(page.frame, 1).ok();
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Çerçeve ayırıcısının bellek baskısı kancası: en fazla `wanted` sayfayı
+/// LRU sırasıyla çıkarır. Önbellek kilidi zaten tutuluyorsa (tahsis önbelleğin
+/// kendisinden geliyorsa) hiçbir şey yapmaz; önbellek o durumda kendi LRU
+/// sayfasını yeniden kullanır.
+fn reclaim(wanted: usize) -> usize {
+    if !CACHE.lock.try_lock() {
+        return 0;
+    }
+    // SAFETY: Kilit tutuluyor.
+    let mut state = unsafe {
+        State { pages: &mut *CACHE.pages.get(), clock: &mut *CACHE.clock.get(), stats: &mut *CACHE.stats.get() }
+    };
+    let mut freed = 0;
+    while freed < wanted {
+        let Some(victim) = lru_victim(state.pages) else { break };
+        match evict(&mut state, victim) {
+            Ok(frame) => {
+                frame::free(frame, 1).ok();
+                freed += 1;
+            }
+            Err(_) => break,
+        }
+    }
+    CACHE.lock.unlock();
+    freed
+}
+
+/// Güncel istatistikler.
+pub fn stats() -> CacheStats {
+    with_cache(|state| CacheStats {
+        pages: state.pages.iter().flatten().count(),
+        dirty: state.pages.iter().flatten().filter(|p| p.dirty).count(),
+        ..*state.stats
+    })
+}
+
+// -----------------------------------------------------------------------------
+// GERİ YAZICI GÖREV
+// -----------------------------------------------------------------------------
+
+fn flush_interval() -> u64 {
+    u64::from(FLUSH_INTERVAL.get()) * SystemConstants::TIMER_TICK_HZ
+}
+
+/// `pageflush` işçisi: her aralıkta süresi dolan kirli sayfaları, durdurulurken
+/// (kapanış) tüm kirli sayfaları yazar.
+fn flush_work(work: Work) {
+    let cutoff = match work {
+        Work::Tick => {
+            let expire = u64::from(DIRTY_EXPIRE.get()) * SystemConstants::TIMER_TICK_HZ;
+            Some(crate::sched::ticks().saturating_sub(expire).saturating_add(1))
+        }
+        Work::Stop => None,
+        Work::Item(_) => return,
+    };
+    if let Err(e) = flush_pages(None, cutoff) {
+        serial_println!("[PCACHE] Geri yazma hatası: {:?}", e);
+    }
+}
+
+/// Kapanış bildirimi: kalan kirli sayfaları ve aygıt önbelleklerini yazar.
+fn shutdown_sync(_action: crate::shutdown::Action) -> Result<(), KernelError> {
+    sync(None)
+}
+
+fn init() -> Result<(), KernelError> {
+    frame::set_reclaim_hook(reclaim);
+    crate::kthread::spawn_periodic("pageflush", FLUSH_TASK_PRIORITY, flush_interval, flush_work)?;
+    crate::shutdown::register("pagecache", crate::shutdown::Phase::Filesystems, shutdown_sync)?;
+    Ok(())
+}
+
+crate::initcall!(late, "pagecache", init);
+
+// -----------------------------------------------------------------------------
+// KABUK VE PROCFS
+// -----------------------------------------------------------------------------
+
+fn pcache_command(args: &[&str]) -> Result<(), KernelError> {
+    match args.get(1).copied() {
+        Some("sync") => sync(None)?,
+        Some("drop") => {
+            sync(None)?;
+            (0..block::MAX_BLOCK_DEVICES).try_for_each(invalidate)?;
+        }
+        Some(_) => return Err(KernelError::InvalidArgument),
+        None => {}
+    }
+    let s = stats();
+    let lookups = (s.hits + s.misses).max(1);
+    serial_println!(
+        "  {} / {} sayfa ({} KiB), {} kirli | isabet %{} ({} / {}) | çıkarma {} geri yazma {}",
+        s.pages,
+        MAX_PAGES,
+        s.pages * FRAME_SIZE / 1024,
+        s.dirty,
+        s.hits * 100 / lookups,
+        s.hits,
+        lookups,
+        s.evictions,
+        s.writebacks
+    );
+    Ok(())
+}
+
+crate::shell_command!("pcache", "Blok sayfa önbelleği (pcache [sync|drop])", pcache_command);
+
+fn show_pagecache(out: &mut dyn fmt::Write) -> fmt::Result {
+    let s = stats();
+    writeln!(out, "pages {}\ndirty {}\nhits {}\nmisses {}\nevictions {}\nwritebacks {}", s.pages, s.dirty, s.hits, s.misses, s.evictions, s.writebacks)
+}
+
+crate::proc_entry!("pagecache", show_pagecache);
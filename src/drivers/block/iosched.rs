@@ -0,0 +1,436 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/drivers/block/iosched.rs
+// G/Ç zamanlayıcısı: aygıt başına eşzamansız blok istek kuyruğu.
+//
+// İstekler `submit` ile aygıtın kuyruğuna bırakılır ve çağıran hemen döner;
+// aygıtın işçi görevi (`blkio`) istekleri sürücüye iletir ve tamamlanınca
+// çağıranın geri çağırmasını (`CompletionFn`) çalıştırır. Böylece yavaş bir
+// aygıttaki işlem yalnızca o aygıtı bekleyen görevleri durdurur. `read` ve
+// `write`, isteği gönderip görevi tamamlanana kadar bloklayan eşzamanlı
+// sarmalayıcılardır (sayfa önbelleği bunları kullanır).
+//
+// Sıradaki isteğin seçimi (deadline politikası):
+// * Süresi dolmuş istek varsa en eskisi önce (okuma `iosched.read_expire_ms`,
+//   yazma `iosched.write_expire_ms` sonra dolar). Okumalar genelde bir görevi
+//   beklettiği için süreleri kısadır.
+// * Yoksa tek yönlü asansör (C-SCAN): son konumdan sonraki en küçük LBA;
+//   sona gelinince en küçük LBA'ya dönülür.
+// * Seçilen istekle aynı yönde ve bitişik (önünde/arkasında) bekleyen
+//   istekler birleştirilir ve ara arabellek (bounce) üzerinden tek bir sürücü
+//   çağrısıyla aktarılır (en fazla `MERGE_BYTES`).
+//
+// İşçi görev başlamadan önce (açılış) veya görev bağlamı yokken istekler
+// eşzamanlı olarak hemen yürütülür.
+//
+// Kabuk komutu: `iosched`; procfs: `/proc/iosched`
+
+use core::cell::UnsafeCell;
+use core::fmt;
+use core::sync::atomic::{AtomicBool, Ordering};
+use crate::drivers::block::block::{self, MAX_BLOCK_DEVICES};
+use crate::memory::frame::{self, FRAME_SIZE};
+use crate::platformgeneric::spinlock::Spinlock;
+use crate::platformgeneric::KernelError;
+use crate::sched::{self, TaskId};
+use crate::serial_println;
+use crate::sysctl::U32;
+
+// -----------------------------------------------------------------------------
+// SABİTLER VE TİPLER
+// -----------------------------------------------------------------------------
+
+/// Aygıt başına bekleyebilecek en fazla istek.
+pub const QUEUE_DEPTH: usize = 32;
+
+/// Bir birleştirilmiş aktarımın en büyük boyutu (ara arabellek).
+pub const MERGE_PAGES: usize = 8;
+pub const MERGE_BYTES: usize = MERGE_PAGES * FRAME_SIZE;
+
+/// Tek aktarımda birleştirilebilecek en fazla istek.
+const MAX_BATCH: usize = 8;
+
+/// İşçi görevlerin önceliği.
+const WORKER_PRIORITY: u8 = 10;
+
+/// Aktarım yönü.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Read,
+    Write,
+}
+
+/// Tamamlanma geri çağırması; işçi görev bağlamında çalışır.
+pub type CompletionFn = fn(arg: usize, result: Result<(), KernelError>);
+
+#[derive(Clone, Copy)]
+struct Request {
+    op: Op,
+    lba: u64,
+    blocks: u64,
+    buf: usize,
+    len: usize,
+    /// Bu tikten sonra politika isteği öne alır.
+    deadline: u64,
+    done: CompletionFn,
+    arg: usize,
+}
+
+impl Request {
+    fn end(&self) -> u64 {
+        self.lba + self.blocks
+    }
+}
+
+/// Aygıt kuyruğu istatistikleri.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct QueueStats {
+    /// Kuyrukta bekleyen istek.
+    pub pending: usize,
+    pub submitted: u64,
+    pub completed: u64,
+    pub errors: u64,
+    /// Başka bir istekle birleştirilerek aktarılan istek.
+    pub merged: u64,
+    /// Süresi dolduğu için asansör sırası dışında seçilen istek.
+    pub expired: u64,
+    /// Sürücüye yapılan aktarım çağrısı.
+    pub dispatches: u64,
+}
+
+#[derive(Clone, Copy)]
+struct DevQueue {
+    requests: [Option<Request>; QUEUE_DEPTH],
+    /// Son aktarımın bittiği blok (asansör konumu).
+    head: u64,
+    worker: Option<TaskId>,
+    /// Birleştirme ara arabelleği (0: birleştirme yok).
+    bounce: usize,
+    stats: QueueStats,
+}
+
+impl DevQueue {
+    const EMPTY: DevQueue = DevQueue {
+        requests: [None; QUEUE_DEPTH],
+        head: 0,
+        worker: None,
+        bounce: 0,
+        stats: QueueStats { pending: 0, submitted: 0, completed: 0, errors: 0, merged: 0, expired: 0, dispatches: 0 },
+    };
+}
+
+struct Queues {
+    lock: Spinlock,
+    queues: UnsafeCell<[DevQueue; MAX_BLOCK_DEVICES]>,
+}
+
+// Tabloya erişim `lock` ile korunur.
+unsafe impl Sync for Queues {}
+
+static QUEUES: Queues = Queues {
+    lock: Spinlock::new(),
+    queues: UnsafeCell::new([DevQueue::EMPTY; MAX_BLOCK_DEVICES]),
+};
+
+crate::sysctl!(READ_EXPIRE_MS: U32 = U32::new(50, 1, 60_000),
+    "iosched.read_expire_ms", "Okuma isteğinin öne alınmadan önce bekleyebileceği süre (ms)");
+crate::sysctl!(WRITE_EXPIRE_MS: U32 = U32::new(500, 1, 60_000),
+    "iosched.write_expire_ms", "Yazma isteğinin öne alınmadan önce bekleyebileceği süre (ms)");
+
+fn with_queues<R>(f: impl FnOnce(&mut [DevQueue; MAX_BLOCK_DEVICES]) -> R) -> R {
+    QUEUES.lock.lock();
+    // SAFETY: Kilit tutulduğu sürece tabloya tek erişen biziz.
+    let result = f(unsafe { &mut *QUEUES.queues.get() });
+    QUEUES.lock.unlock();
+    result
+}
+
+fn ms_to_ticks(ms: u32) -> u64 {
+    (u64::from(ms) * sched::tick_hz()).div_ceil(1000)
+}
+
+/// İsteği doğrudan sürücüye iletir.
+///
+/// # Safety
+/// `buf`/`len` isteği gönderenin geçerli arabelleği olmalıdır.
+unsafe fn transfer(dev: usize, op: Op, lba: u64, buf: usize, len: usize) -> Result<(), KernelError> {
+    let dev = block::device(dev).ok_or(KernelError::NotFound)?;
+    match op {
+        Op::Read => dev.read_blocks(lba, core::slice::from_raw_parts_mut(buf as *mut u8, len)),
+        Op::Write => dev.write_blocks(lba, core::slice::from_raw_parts(buf as *const u8, len)),
+    }
+}
+
+// -----------------------------------------------------------------------------
+// GÖNDERME
+// -----------------------------------------------------------------------------
+
+/// İsteği aygıtın kuyruğuna bırakır; `done(arg, sonuç)` tamamlanınca çağrılır.
+/// İşçi görev henüz yoksa istek hemen yürütülür ve `done` dönmeden çağrılır.
+///
+/// # Safety
+/// `[buf, buf + len)` istek tamamlanana kadar geçerli kalmalı ve okuma için
+/// başka kimse tarafından kullanılmamalıdır.
+///
+/// # Hatalar
+/// * `NotFound` / `InvalidArgument`: `block::check_range` ile aynı.
+/// * `ResourceBusy`: Kuyruk dolu.
+pub unsafe fn submit(dev: usize, op: Op, lba: u64, buf: *mut u8, len: usize, done: CompletionFn, arg: usize) -> Result<(), KernelError> {
+    let device = block::device(dev).ok_or(KernelError::NotFound)?;
+    let blocks = block::check_range(device, lba, len)?;
+    let expire = match op {
+        Op::Read => READ_EXPIRE_MS.get(),
+        Op::Write => WRITE_EXPIRE_MS.get(),
+    };
+    let request = Request { op, lba, blocks, buf: buf as usize, len, deadline: sched::ticks() + ms_to_ticks(expire), done, arg };
+
+    let worker = with_queues(|queues| {
+        let queue = &mut queues[dev];
+        let worker = queue.worker?;
+        let Some(slot) = queue.requests.iter_mut().find(|r| r.is_none()) else {
+            return Some(Err(KernelError::ResourceBusy));
+        };
+        *slot = Some(request);
+        queue.stats.submitted += 1;
+        // Kuyruk kilidi altında uyandırılır: işçi boş kuyruğu görüp
+        // bloklanmadan önce aynı kilidi tutar, uyandırma kaybolmaz
+        sched::wake(worker).ok();
+        Some(Ok(()))
+    });
+
+    match worker {
+        Some(result) => result,
+        None => {
+            done(arg, transfer(dev, op, lba, request.buf, len));
+            Ok(())
+        }
+    }
+}
+
+/// Eşzamanlı bekleme durumu; `Waiter` adresi geri çağırma argümanıdır.
+struct Waiter {
+    task: TaskId,
+    done: AtomicBool,
+    result: UnsafeCell<Result<(), KernelError>>,
+}
+
+fn complete_waiter(arg: usize, result: Result<(), KernelError>) {
+    // SAFETY: `arg`, `wait_for` yığınındaki ve tamamlanana kadar yaşayan bir `Waiter`'dır.
+    let waiter = unsafe { &*(arg as *const Waiter) };
+    unsafe { *waiter.result.get() = result };
+    waiter.done.store(true, Ordering::Release);
+    sched::wake(waiter.task).ok();
+}
+
+fn wait_for(dev: usize, op: Op, lba: u64, buf: *mut u8, len: usize) -> Result<(), KernelError> {
+    let me = sched::current();
+    // İşçinin kendisi (veya görevsiz bağlam) kendi kuyruğunu bekleyemez
+    let own_worker = with_queues(|queues| me.is_none() || queues.get(dev).is_some_and(|q| q.worker == me));
+    let Some(me) = me.filter(|_| !own_worker) else {
+        // SAFETY: Arabellek çağıranındır ve çağrı boyunca geçerlidir.
+        return unsafe { transfer(dev, op, lba, buf as usize, len) };
+    };
+
+    let waiter = Waiter { task: me, done: AtomicBool::new(false), result: UnsafeCell::new(Ok(())) };
+    // Önce bloklanır: istek hemen tamamlansa bile uyandırma kaybolmaz
+    sched::block(me).ok();
+    // SAFETY: `waiter` ve arabellek, tamamlanma beklenene kadar bu yığında yaşar.
+    if let Err(e) = unsafe { submit(dev, op, lba, buf, len, complete_waiter, &waiter as *const Waiter as usize) } {
+        sched::wake(me).ok();
+        return Err(e);
+    }
+    // Bloklanan görev bir sonraki kesme dönüşünde işlemciden alınır ve
+    // tamamlanınca hazır olarak buradan devam eder
+    while !waiter.done.load(Ordering::Acquire) {
+        core::hint::spin_loop();
+    }
+    // SAFETY: `done` yazıldıktan sonra sonuç bir daha değişmez.
+    unsafe { *waiter.result.get() }
+}
+
+/// `buf`'ı aygıttan okur; görev tamamlanana kadar bloklanır.
+pub fn read(dev: usize, lba: u64, buf: &mut [u8]) -> Result<(), KernelError> {
+    wait_for(dev, Op::Read, lba, buf.as_mut_ptr(), buf.len())
+}
+
+/// `data`'yı aygıta yazar; görev tamamlanana kadar bloklanır.
+pub fn write(dev: usize, lba: u64, data: &[u8]) -> Result<(), KernelError> {
+    wait_for(dev, Op::Write, lba, data.as_ptr() as *mut u8, data.len())
+}
+
+// -----------------------------------------------------------------------------
+// POLİTİKA
+// -----------------------------------------------------------------------------
+
+/// Sıradaki aktarım: seçilen istek ve onunla birleştirilen istekler (LBA
+/// sırasında).
+fn next_batch(queue: &mut DevQueue, now: u64) -> Option<([Request; MAX_BATCH], usize)> {
+    let pending = |r: &Option<Request>| r.is_some();
+    let first = if let Some(expired) = (0..QUEUE_DEPTH)
+        .filter(|&i| queue.requests[i].is_some_and(|r| r.deadline <= now))
+        .min_by_key(|&i| queue.requests[i].map_or(u64::MAX, |r| r.deadline))
+    {
+        queue.stats.expired += 1;
+        expired
+    } else {
+        let by_lba = |i: &usize| queue.requests[*i].map_or(u64::MAX, |r| r.lba);
+        let ahead = (0..QUEUE_DEPTH).filter(|&i| queue.requests[i].is_some_and(|r| r.lba >= queue.head)).min_by_key(by_lba);
+        ahead.or_else(|| (0..QUEUE_DEPTH).filter(|&i| pending(&queue.requests[i])).min_by_key(by_lba))?
+    };
+
+    let seed = queue.requests[first].take()?;
+    let mut batch = [seed; MAX_BATCH];
+    let mut count = 1;
+    let (mut start, mut end, mut bytes) = (seed.lba, seed.end(), seed.len);
+
+    // Bitişik istekleri iki yönde de topla
+    while queue.bounce != 0 && count < MAX_BATCH {
+        let fits = |r: &Request| r.op == seed.op && bytes + r.len <= MERGE_BYTES && (r.lba == end || r.end() == start);
+        let Some(index) = (0..QUEUE_DEPTH).find(|&i| queue.requests[i].as_ref().is_some_and(fits)) else { break };
+        let Some(request) = queue.requests[index].take() else { break };
+        start = start.min(request.lba);
+        end = end.max(request.end());
+        bytes += request.len;
+        batch[count] = request;
+        count += 1;
+    }
+    batch[..count].sort_unstable_by_key(|r| r.lba);
+    if count > 1 {
+        queue.stats.merged += count as u64;
+    }
+    queue.head = end;
+    Some((batch, count))
+}
+
+/// Birleştirilmiş istekleri ara arabellek üzerinden tek aktarımda yürütür.
+///
+/// # Safety
+/// `bounce` en az `MERGE_BYTES` olmalı; istek arabellekleri geçerli olmalıdır.
+unsafe fn dispatch(dev: usize, bounce: usize, batch: &[Request]) -> Result<(), KernelError> {
+    let [single] = batch else {
+        let op = batch[0].op;
+        let total: usize = batch.iter().map(|r| r.len).sum();
+        if op == Op::Write {
+            let mut at = bounce;
+            for r in batch {
+                core::ptr::copy_nonoverlapping(r.buf as *const u8, at as *mut u8, r.len);
+                at += r.len;
+            }
+        }
+        transfer(dev, op, batch[0].lba, bounce, total)?;
+        if op == Op::Read {
+            let mut at = bounce;
+            for r in batch {
+                core::ptr::copy_nonoverlapping(at as *const u8, r.buf as *mut u8, r.len);
+                at += r.len;
+            }
+        }
+        return Ok(());
+    };
+    transfer(dev, single.op, single.lba, single.buf, single.len)
+}
+
+// -----------------------------------------------------------------------------
+// İŞÇİ GÖREVLER
+// -----------------------------------------------------------------------------
+
+fn worker_task(_arg: u64) {
+    // Görev, kuyruğa işçi olarak kaydedilene kadar bekler
+    let me = sched::current();
+    let dev = loop {
+        if let Some(dev) = with_queues(|queues| queues.iter().position(|q| q.worker.is_some() && q.worker == me)) {
+            break dev;
+        }
+        core::hint::spin_loop();
+    };
+
+    loop {
+        let next = with_queues(|queues| {
+            let queue = &mut queues[dev];
+            let next = next_batch(queue, sched::ticks()).map(|(batch, count)| (batch, count, queue.bounce));
+            if next.is_none() {
+                // Kuyruk boş: `submit` aynı kilit altında uyandırır
+                if let Some(me) = me {
+                    sched::block(me).ok();
+                }
+            }
+            next
+        });
+        let Some((requests, count, bounce)) = next else {
+            while with_queues(|queues| queues[dev].requests.iter().all(Option::is_none)) {
+                core::hint::spin_loop();
+            }
+            continue;
+        };
+
+        // SAFETY: Arabellekler gönderenin sözleşmesiyle geçerlidir.
+        let result = unsafe { dispatch(dev, bounce, &requests[..count]) };
+        with_queues(|queues| {
+            let stats = &mut queues[dev].stats;
+            stats.dispatches += 1;
+            stats.completed += count as u64;
+            if result.is_err() {
+                stats.errors += count as u64;
+            }
+        });
+        for request in &requests[..count] {
+            (request.done)(request.arg, result);
+        }
+    }
+}
+
+/// Kayıtlı her blok aygıtı için bir işçi görev başlatır.
+fn init() -> Result<(), KernelError> {
+    for dev in (0..MAX_BLOCK_DEVICES).filter(|&d| block::device(d).is_some()) {
+        let bounce = frame::alloc_contiguous(MERGE_PAGES).unwrap_or(0);
+        let task = sched::spawn("blkio", worker_task as usize, WORKER_PRIORITY)?;
+        with_queues(|queues| {
+            queues[dev].bounce = bounce;
+            queues[dev].head = 0;
+            queues[dev].worker = Some(task);
+        });
+    }
+    Ok(())
+}
+
+crate::initcall!(late, "iosched", init);
+
+/// Aygıt kuyruğunun istatistikleri.
+pub fn stats(dev: usize) -> Option<QueueStats> {
+    with_queues(|queues| {
+        let queue = queues.get(dev).filter(|q| q.worker.is_some())?;
+        Some(QueueStats { pending: queue.requests.iter().flatten().count(), ..queue.stats })
+    })
+}
+
+// -----------------------------------------------------------------------------
+// KABUK VE PROCFS
+// -----------------------------------------------------------------------------
+
+fn iosched_command(_args: &[&str]) -> Result<(), KernelError> {
+    serial_println!("  AYGIT    BEKLEYEN  GÖNDERİLEN  TAMAMLANAN  BİRLEŞEN  SÜRESİ DOLAN  AKTARIM  HATA");
+    for dev in 0..MAX_BLOCK_DEVICES {
+        let (Some(device), Some(s)) = (block::device(dev), stats(dev)) else { continue };
+        serial_println!(
+            "  {:<8} {:>8} {:>11} {:>11} {:>9} {:>13} {:>8} {:>5}",
+            device.name(), s.pending, s.submitted, s.completed, s.merged, s.expired, s.dispatches, s.errors
+        );
+    }
+    Ok(())
+}
+
+crate::shell_command!("iosched", "Blok G/Ç kuyruklarının durumunu gösterir", iosched_command);
+
+fn show_iosched(out: &mut dyn fmt::Write) -> fmt::Result {
+    for dev in 0..MAX_BLOCK_DEVICES {
+        let (Some(device), Some(s)) = (block::device(dev), stats(dev)) else { continue };
+        writeln!(
+            out,
+            "{} {} {} {} {} {} {} {}",
+            device.name(), s.pending, s.submitted, s.completed, s.merged, s.expired, s.dispatches, s.errors
+        )?;
+    }
+    Ok(())
+}
+
+crate::proc_entry!("iosched", show_iosched);
@@ -0,0 +1,676 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/drivers/block/nvme.rs
+// NVMe 1.x denetleyici sürücüsü: her etkin ad alanını (namespace) bir blok
+// aygıtı olarak sunar.
+//
+// Denetleyici PCI'dan (sınıf 01.08.02, yazmaçlar BAR0) bulunur. Yönetim
+// (admin) kuyruğu ve tek bir G/Ç kuyruk çifti (gönderim/tamamlanma) birer
+// çerçevede; veri için ardışık çerçevelerden bir DMA tamponu ve bu tamponun
+// sayfalarını listeleyen sabit bir PRP listesi ayrılır. Denetleyicinin tüm ad
+// alanları G/Ç kuyruğunu paylaşır; kuyrukta aynı anda tek komut bulunur
+// (`lock`).
+//
+// Açılış: denetleyici kapatılır, yönetim kuyrukları AQA/ASQ/ACQ'ya yazılıp
+// yeniden açılır; IDENTIFY ile denetleyici ve etkin ad alanı listesi okunur,
+// G/Ç kuyrukları CREATE IO CQ/SQ ile kurulur.
+//
+// Tamamlanma kesme ile bildirilir: `handle_irq` denetleyicinin kesmesini
+// INTMS ile maskeler ve atomik bir bayrak kaldırır; bekleyen komut
+// tamamlanma kuyruğunu (faz biti) okur, baş kapı zilini (doorbell) yazar ve
+// maskeyi INTMC ile kaldırır. Kesme hattı yoksa (`irq == u32::MAX`)
+// tamamlanma kuyruğu doğrudan yoklanır. Tüm kuyruklar kesme vektörü 0'ı
+// kullanır; çekirdekte henüz MSI ileti adresi sağlayan bir kesme denetleyicisi
+// olmadığından aygıtın MSI-X'i kapalı tutulur ve vektör 0 INTx'e düşer.
+//
+// Kapanışta (`shutdown` zincirinin sürücü aşaması) ad alanlarının yazma
+// önbelleği boşaltılır, G/Ç kuyrukları silinir ve denetleyiciye olağan
+// kapanış (CC.SHN) bildirilir.
+//
+// Varsayım: Çekirdek birebir eşlenmiştir (sanal = fiziksel). Yalnızca 4 KiB
+// bellek sayfası (CC.MPS = 0) ve üst verisiz (metadata), en fazla 4 KiB'lik
+// LBA biçimleri desteklenir.
+
+use core::cell::UnsafeCell;
+use core::ptr::{read_volatile, write_volatile};
+use core::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use crate::drivers::block::block::{self, BlockDevice};
+use crate::memory::frame;
+use crate::platformgeneric::spinlock::Spinlock;
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// YAZMAÇLAR VE KOMUTLAR
+// -----------------------------------------------------------------------------
+
+// Denetleyici yazmaçları
+const REG_CAP: usize = 0x00;
+const REG_VS: usize = 0x08;
+const REG_INTMS: usize = 0x0C;
+const REG_INTMC: usize = 0x10;
+const REG_CC: usize = 0x14;
+const REG_CSTS: usize = 0x1C;
+const REG_AQA: usize = 0x24;
+const REG_ASQ: usize = 0x28;
+const REG_ACQ: usize = 0x30;
+/// Kapı zilleri: kuyruk y için gönderim kuyruğu sonu (tail) `2y`, tamamlanma
+/// kuyruğu başı (head) `2y + 1`. kayıttır; kayıtlar arası `4 << CAP.DSTRD` bayt.
+const DOORBELL_BASE: usize = 0x1000;
+
+// CC
+const CC_EN: u32 = 1 << 0;
+const CC_SHN_NORMAL: u32 = 1 << 14;
+const CC_SHN_MASK: u32 = 3 << 14;
+/// Gönderim girdisi 2^6 = 64, tamamlanma girdisi 2^4 = 16 bayt.
+const CC_IOSQES: u32 = 6 << 16;
+const CC_IOCQES: u32 = 4 << 20;
+
+// CSTS
+const CSTS_RDY: u32 = 1 << 0;
+const CSTS_CFS: u32 = 1 << 1;
+const CSTS_SHST_MASK: u32 = 3 << 2;
+const CSTS_SHST_DONE: u32 = 2 << 2;
+
+// Yönetim komutları
+const ADMIN_DELETE_SQ: u32 = 0x00;
+const ADMIN_CREATE_SQ: u32 = 0x01;
+const ADMIN_DELETE_CQ: u32 = 0x04;
+const ADMIN_CREATE_CQ: u32 = 0x05;
+const ADMIN_IDENTIFY: u32 = 0x06;
+
+// IDENTIFY CNS değerleri
+const CNS_NAMESPACE: u32 = 0x00;
+const CNS_CONTROLLER: u32 = 0x01;
+const CNS_ACTIVE_NAMESPACES: u32 = 0x02;
+
+// NVM komutları
+const NVM_FLUSH: u32 = 0x00;
+const NVM_WRITE: u32 = 0x01;
+const NVM_READ: u32 = 0x02;
+
+const SQ_ENTRY_SIZE: usize = 64;
+const CQ_ENTRY_SIZE: usize = 16;
+
+/// NVMe bellek sayfası (CC.MPS = 0).
+const PAGE_SIZE: usize = 4096;
+/// Kuyruk derinliği (denetleyicinin CAP.MQES sınırıyla kısılır).
+const QUEUE_DEPTH: u16 = 32;
+/// G/Ç kuyruk çiftinin kimliği.
+const IO_QUEUE_ID: u16 = 1;
+/// DMA tamponu ve tek komutla aktarılan en fazla veri.
+const DMA_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Yoklama döngüsü üst sınırı.
+const TIMEOUT_SPINS: u32 = 10_000_000;
+
+// -----------------------------------------------------------------------------
+// KUYRUKLAR
+// -----------------------------------------------------------------------------
+
+/// Bir gönderim/tamamlanma kuyruk çifti.
+#[derive(Clone, Copy)]
+struct Queue {
+    id: u16,
+    sq: usize,
+    cq: usize,
+    depth: u16,
+    sq_tail: u16,
+    cq_head: u16,
+    /// Yeni tamamlanma girdilerinde beklenen faz biti.
+    phase: bool,
+    next_cid: u16,
+}
+
+impl Queue {
+    const fn empty(id: u16) -> Self {
+        Queue { id, sq: 0, cq: 0, depth: 0, sq_tail: 0, cq_head: 0, phase: true, next_cid: 0 }
+    }
+
+    fn doorbell(&self, regs: usize, stride: usize, completion: bool) -> usize {
+        regs + DOORBELL_BASE + (2 * self.id as usize + completion as usize) * stride
+    }
+
+    /// Komutu gönderim kuyruğuna yazar ve kuyruk zilini çalar.
+    ///
+    /// # Dönüş Değeri
+    /// Komuta verilen kimlik (CID).
+    fn push(&mut self, regs: usize, stride: usize, mut cmd: [u32; 16]) -> u16 {
+        let cid = self.next_cid;
+        self.next_cid = self.next_cid.wrapping_add(1);
+        cmd[0] |= (cid as u32) << 16;
+        let entry = self.sq + self.sq_tail as usize * SQ_ENTRY_SIZE;
+        for (i, dw) in cmd.iter().enumerate() {
+            w32(entry + i * 4, *dw);
+        }
+        self.sq_tail = (self.sq_tail + 1) % self.depth;
+        w32(self.doorbell(regs, stride, false), self.sq_tail as u32);
+        cid
+    }
+
+    /// Sıradaki tamamlanma girdisini tüketir.
+    ///
+    /// # Dönüş Değeri
+    /// Yeni girdi varsa (CID, durum kodu, DW0).
+    fn pop(&mut self) -> Option<(u16, u16, u32)> {
+        let entry = self.cq + self.cq_head as usize * CQ_ENTRY_SIZE;
+        let dw3 = r32(entry + 12);
+        if (dw3 & (1 << 16) != 0) != self.phase {
+            return None;
+        }
+        let dw0 = r32(entry);
+        self.cq_head += 1;
+        if self.cq_head == self.depth {
+            self.cq_head = 0;
+            self.phase = !self.phase;
+        }
+        Some((dw3 as u16, ((dw3 >> 17) & 0x7FFF) as u16, dw0))
+    }
+}
+
+// -----------------------------------------------------------------------------
+// DENETLEYİCİLER
+// -----------------------------------------------------------------------------
+
+/// Bir NVMe denetleyicisinin durumu (`lock` altında).
+struct ControllerState {
+    regs: usize,
+    /// Kapı zilleri arası uzaklık (bayt).
+    stride: usize,
+    admin: Queue,
+    io: Queue,
+    /// Veri DMA tamponu.
+    dma: usize,
+    /// DMA tamponunun 2. ve sonraki sayfalarını listeleyen PRP listesi.
+    prp_list: usize,
+    /// Tek komutla aktarılabilecek en fazla bayt (MDTS ile kısılır).
+    max_transfer: usize,
+    model: [u8; 40],
+}
+
+struct Controller {
+    lock: Spinlock,
+    state: UnsafeCell<ControllerState>,
+    regs: AtomicUsize,
+    irq: AtomicU32,
+    /// Kesme geldi; işleyici denetleyici kesmesini maskeledi.
+    irq_pending: AtomicBool,
+    /// Tamamlanma kesmeyle mi bildiriliyor?
+    irq_driven: AtomicBool,
+    /// G/Ç kuyrukları kuruldu mu?
+    io_ready: AtomicBool,
+    used: AtomicBool,
+}
+
+// Durum `lock` ile korunur.
+unsafe impl Sync for Controller {}
+
+impl Controller {
+    const fn empty() -> Self {
+        Controller {
+            lock: Spinlock::new(),
+            state: UnsafeCell::new(ControllerState {
+                regs: 0,
+                stride: 4,
+                admin: Queue::empty(0),
+                io: Queue::empty(IO_QUEUE_ID),
+                dma: 0,
+                prp_list: 0,
+                max_transfer: DMA_BUFFER_SIZE,
+                model: [b' '; 40],
+            }),
+            regs: AtomicUsize::new(0),
+            irq: AtomicU32::new(u32::MAX),
+            irq_pending: AtomicBool::new(false),
+            irq_driven: AtomicBool::new(false),
+            io_ready: AtomicBool::new(false),
+            used: AtomicBool::new(false),
+        }
+    }
+
+    fn with_state<R>(&self, f: impl FnOnce(&mut ControllerState) -> R) -> R {
+        self.lock.lock();
+        let result = f(unsafe { &mut *self.state.get() });
+        self.lock.unlock();
+        result
+    }
+
+    /// Komutu yönetim (`io == false`) veya G/Ç kuyruğunda yürütür ve
+    /// tamamlanmasını bekler.
+    ///
+    /// # Dönüş Değeri
+    /// Tamamlanma girdisinin DW0'ı.
+    fn execute(&self, s: &mut ControllerState, io: bool, cmd: [u32; 16]) -> Result<u32, KernelError> {
+        let (regs, stride) = (s.regs, s.stride);
+        let queue = if io { &mut s.io } else { &mut s.admin };
+        let cid = queue.push(regs, stride, cmd);
+
+        let irq_driven = self.irq_driven.load(Ordering::Relaxed);
+        for _ in 0..TIMEOUT_SPINS {
+            if irq_driven && !self.irq_pending.swap(false, Ordering::Acquire) {
+                core::hint::spin_loop();
+                continue;
+            }
+            let completion = queue.pop();
+            if completion.is_some() {
+                w32(queue.doorbell(regs, stride, true), queue.cq_head as u32);
+            }
+            if irq_driven {
+                w32(regs + REG_INTMC, 1);
+            }
+            match completion {
+                // Önceki (zaman aşımına uğramış) komutun geç gelen girdisi
+                Some((done, _, _)) if done != cid => {}
+                Some((_, 0, dw0)) => return Ok(dw0),
+                Some((_, status, _)) => {
+                    serial_println!("[NVMe] Komut {:#04x} (kuyruk {}) hatası: durum {:#x}", cmd[0] & 0xFF, queue.id, status);
+                    return Err(KernelError::GenericFailure);
+                }
+                None => core::hint::spin_loop(),
+            }
+        }
+        if irq_driven {
+            w32(regs + REG_INTMC, 1);
+        }
+        serial_println!("[NVMe] Komut {:#04x} (kuyruk {}) zaman aşımı", cmd[0] & 0xFF, queue.id);
+        Err(KernelError::GenericFailure)
+    }
+
+    /// IDENTIFY verisini (4 KiB) DMA tamponuna okur.
+    fn identify(&self, s: &mut ControllerState, cns: u32, nsid: u32) -> Result<(), KernelError> {
+        let mut cmd = [0u32; 16];
+        cmd[0] = ADMIN_IDENTIFY;
+        cmd[1] = nsid;
+        set_prp(&mut cmd, s, PAGE_SIZE);
+        cmd[10] = cns;
+        self.execute(s, false, cmd).map(|_| ())
+    }
+}
+
+/// Komutun veri işaretçilerini DMA tamponuna yöneltir.
+fn set_prp(cmd: &mut [u32; 16], s: &ControllerState, len: usize) {
+    let prp2 = match len {
+        0..=PAGE_SIZE => 0,
+        _ if len <= 2 * PAGE_SIZE => s.dma + PAGE_SIZE,
+        _ => s.prp_list,
+    };
+    cmd[6] = s.dma as u32;
+    cmd[7] = (s.dma as u64 >> 32) as u32;
+    cmd[8] = prp2 as u32;
+    cmd[9] = (prp2 as u64 >> 32) as u32;
+}
+
+// -----------------------------------------------------------------------------
+// AD ALANLARI
+// -----------------------------------------------------------------------------
+
+/// Blok aygıtı olarak sunulan tek bir NVMe ad alanı.
+pub struct NvmeNamespace {
+    name: &'static str,
+    nsid: AtomicU32,
+    /// Ait olduğu `CONTROLLERS` indeksi.
+    controller: AtomicUsize,
+    /// Mantıksal blok boyutunun 2 tabanında logaritması.
+    lba_shift: AtomicU32,
+    blocks: AtomicU64,
+    used: AtomicBool,
+}
+
+impl NvmeNamespace {
+    const fn empty(name: &'static str) -> Self {
+        NvmeNamespace {
+            name,
+            nsid: AtomicU32::new(0),
+            controller: AtomicUsize::new(0),
+            lba_shift: AtomicU32::new(9),
+            blocks: AtomicU64::new(0),
+            used: AtomicBool::new(false),
+        }
+    }
+
+    fn controller(&self) -> &'static Controller {
+        &CONTROLLERS[self.controller.load(Ordering::Relaxed)]
+    }
+
+    /// Okuma/yazma komutu kurar.
+    fn rw_command(&self, opcode: u32, s: &ControllerState, lba: u64, bytes: usize) -> [u32; 16] {
+        let mut cmd = [0u32; 16];
+        cmd[0] = opcode;
+        cmd[1] = self.nsid.load(Ordering::Relaxed);
+        set_prp(&mut cmd, s, bytes);
+        cmd[10] = lba as u32;
+        cmd[11] = (lba >> 32) as u32;
+        // Blok sayısı sıfır tabanlıdır
+        cmd[12] = ((bytes >> self.lba_shift.load(Ordering::Relaxed)) - 1) as u32;
+        cmd
+    }
+
+    fn transfer(&self, lba: u64, len: usize, mut chunk: impl FnMut(&mut ControllerState, u64, usize, usize) -> Result<(), KernelError>) -> Result<(), KernelError> {
+        let count = block::check_range(self, lba, len)?;
+        let shift = self.lba_shift.load(Ordering::Relaxed);
+        self.controller().with_state(|s| {
+            let per_cmd = (s.max_transfer >> shift) as u64;
+            let mut done = 0;
+            while done < count {
+                let n = (count - done).min(per_cmd);
+                chunk(s, lba + done, (done as usize) << shift, (n as usize) << shift)?;
+                done += n;
+            }
+            Ok(())
+        })
+    }
+}
+
+impl BlockDevice for NvmeNamespace {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn block_size(&self) -> usize {
+        1 << self.lba_shift.load(Ordering::Relaxed)
+    }
+
+    fn block_count(&self) -> u64 {
+        self.blocks.load(Ordering::Relaxed)
+    }
+
+    fn read_blocks(&self, lba: u64, buf: &mut [u8]) -> Result<(), KernelError> {
+        let ctrl = self.controller();
+        let len = buf.len();
+        self.transfer(lba, len, |s, lba, off, bytes| {
+            let cmd = self.rw_command(NVM_READ, s, lba, bytes);
+            ctrl.execute(s, true, cmd)?;
+            unsafe { core::ptr::copy_nonoverlapping(s.dma as *const u8, buf[off..].as_mut_ptr(), bytes) };
+            Ok(())
+        })
+    }
+
+    fn write_blocks(&self, lba: u64, buf: &[u8]) -> Result<(), KernelError> {
+        let ctrl = self.controller();
+        self.transfer(lba, buf.len(), |s, lba, off, bytes| {
+            unsafe { core::ptr::copy_nonoverlapping(buf[off..].as_ptr(), s.dma as *mut u8, bytes) };
+            let cmd = self.rw_command(NVM_WRITE, s, lba, bytes);
+            ctrl.execute(s, true, cmd).map(|_| ())
+        })
+    }
+
+    fn flush(&self) -> Result<(), KernelError> {
+        let ctrl = self.controller();
+        let mut cmd = [0u32; 16];
+        cmd[0] = NVM_FLUSH;
+        cmd[1] = self.nsid.load(Ordering::Relaxed);
+        ctrl.with_state(|s| ctrl.execute(s, true, cmd).map(|_| ()))
+    }
+}
+
+// -----------------------------------------------------------------------------
+// YARDIMCILAR
+// -----------------------------------------------------------------------------
+
+#[inline(always)]
+fn r32(addr: usize) -> u32 {
+    unsafe { read_volatile(addr as *const u32) }
+}
+
+#[inline(always)]
+fn w32(addr: usize, value: u32) {
+    unsafe { write_volatile(addr as *mut u32, value) }
+}
+
+/// 64 bitlik yazmacı iki 32 bitlik erişimle okur (alt kelime önce).
+fn r64(addr: usize) -> u64 {
+    r32(addr) as u64 | (r32(addr + 4) as u64) << 32
+}
+
+fn w64(addr: usize, value: u64) {
+    w32(addr, value as u32);
+    w32(addr + 4, (value >> 32) as u32);
+}
+
+fn wait_reg(addr: usize, mask: u32, want: u32) -> Result<(), KernelError> {
+    for _ in 0..TIMEOUT_SPINS {
+        if r32(addr) & mask == want {
+            return Ok(());
+        }
+    }
+    Err(KernelError::GenericFailure)
+}
+
+/// Sıfırlanmış, ardışık `bytes` baytlık DMA belleği ayırır.
+fn alloc_zeroed(bytes: usize) -> Result<usize, KernelError> {
+    let frames = bytes.div_ceil(frame::FRAME_SIZE);
+    let addr = frame::alloc_contiguous(frames)?;
+    unsafe { core::ptr::write_bytes(addr as *mut u8, 0, frames * frame::FRAME_SIZE) };
+    Ok(addr)
+}
+
+// -----------------------------------------------------------------------------
+// KESİLER VE KEŞİF
+// -----------------------------------------------------------------------------
+
+/// En fazla denetleyici sayısı.
+const MAX_CONTROLLERS: usize = 2;
+
+static CONTROLLERS: [Controller; MAX_CONTROLLERS] = [Controller::empty(), Controller::empty()];
+
+/// Tüm denetleyicilerdeki en fazla ad alanı.
+const MAX_NAMESPACES: usize = 4;
+
+static NAMESPACES: [NvmeNamespace; MAX_NAMESPACES] = [
+    NvmeNamespace::empty("nvme0"),
+    NvmeNamespace::empty("nvme1"),
+    NvmeNamespace::empty("nvme2"),
+    NvmeNamespace::empty("nvme3"),
+];
+
+/// Mimari kesme dağıtıcısından çağrılır.
+///
+/// # Dönüş Değeri
+/// Kesme bir NVMe denetleyicisine aitse `true`.
+pub fn handle_irq(irq: u32) -> bool {
+    let mut handled = false;
+    for ctrl in CONTROLLERS.iter().filter(|c| c.used.load(Ordering::Acquire) && c.irq.load(Ordering::Relaxed) == irq) {
+        if !ctrl.irq_driven.load(Ordering::Relaxed) {
+            continue;
+        }
+        // Hat seviye tetiklidir: tamamlanmalar tüketilene kadar maskeli kalır
+        w32(ctrl.regs.load(Ordering::Relaxed) + REG_INTMS, 1);
+        ctrl.irq_pending.store(true, Ordering::Release);
+        handled = true;
+    }
+    handled
+}
+
+/// Denetleyiciyi kapatır (CC.EN = 0) ve hazır bitinin düşmesini bekler.
+fn disable(regs: usize) -> Result<(), KernelError> {
+    let cc = r32(regs + REG_CC);
+    if cc & CC_EN != 0 {
+        w32(regs + REG_CC, cc & !CC_EN);
+    }
+    wait_reg(regs + REG_CSTS, CSTS_RDY, 0)
+}
+
+/// Tek bir ad alanını tanır ve blok aygıtı olarak kaydeder.
+fn init_namespace(index: usize, nsid: u32) -> Result<(), KernelError> {
+    let ctrl = &CONTROLLERS[index];
+    let (blocks, lbaf) = ctrl.with_state(|s| {
+        ctrl.identify(s, CNS_NAMESPACE, nsid)?;
+        // NSZE (bayt 0), FLBAS (bayt 26), LBA biçimleri (bayt 128'den, 4'er bayt)
+        let format = unsafe { read_volatile((s.dma + 26) as *const u8) } & 0xF;
+        Ok::<_, KernelError>((r64(s.dma), r32(s.dma + 128 + format as usize * 4)))
+    })?;
+    let lba_shift = (lbaf >> 16) & 0xFF;
+    let metadata = lbaf & 0xFFFF;
+
+    if blocks == 0 || metadata != 0 || !(9..=12).contains(&lba_shift) {
+        serial_println!("[NVMe] Ad alanı {}: desteklenmeyen LBA biçimi, atlandı", nsid);
+        return Ok(());
+    }
+    let slot = NAMESPACES
+        .iter()
+        .position(|n| !n.used.swap(true, Ordering::AcqRel))
+        .ok_or(KernelError::OutOfMemoryStatic)?;
+    let ns = &NAMESPACES[slot];
+    ns.nsid.store(nsid, Ordering::Relaxed);
+    ns.controller.store(index, Ordering::Relaxed);
+    ns.lba_shift.store(lba_shift, Ordering::Relaxed);
+    ns.blocks.store(blocks, Ordering::Relaxed);
+    block::register(ns)?;
+    Ok(())
+}
+
+/// Denetleyiciyi başlatır, G/Ç kuyruklarını kurar ve ad alanlarını kaydeder.
+fn init_controller(regs: usize, irq: u32) -> Result<(), KernelError> {
+    let cap = r64(regs + REG_CAP);
+    if (cap >> 48) & 0xF != 0 {
+        // CAP.MPSMIN > 4 KiB
+        return Err(KernelError::NotFound);
+    }
+    let index = CONTROLLERS
+        .iter()
+        .position(|c| !c.used.swap(true, Ordering::AcqRel))
+        .ok_or(KernelError::OutOfMemoryStatic)?;
+    let ctrl = &CONTROLLERS[index];
+    let depth = QUEUE_DEPTH.min((cap & 0xFFFF) as u16 + 1);
+    let irq_driven = irq != u32::MAX;
+
+    disable(regs)?;
+    let admin_sq = alloc_zeroed(depth as usize * SQ_ENTRY_SIZE)?;
+    let admin_cq = alloc_zeroed(depth as usize * CQ_ENTRY_SIZE)?;
+    let io_sq = alloc_zeroed(depth as usize * SQ_ENTRY_SIZE)?;
+    let io_cq = alloc_zeroed(depth as usize * CQ_ENTRY_SIZE)?;
+    let dma = alloc_zeroed(DMA_BUFFER_SIZE)?;
+    let prp_list = alloc_zeroed(PAGE_SIZE)?;
+    for page in 1..DMA_BUFFER_SIZE / PAGE_SIZE {
+        unsafe { write_volatile((prp_list as *mut u64).add(page - 1), (dma + page * PAGE_SIZE) as u64) };
+    }
+
+    w32(regs + REG_AQA, (depth as u32 - 1) << 16 | (depth as u32 - 1));
+    w64(regs + REG_ASQ, admin_sq as u64);
+    w64(regs + REG_ACQ, admin_cq as u64);
+    w32(regs + REG_INTMS, u32::MAX);
+    w32(regs + REG_CC, CC_EN | CC_IOSQES | CC_IOCQES);
+    wait_reg(regs + REG_CSTS, CSTS_RDY | CSTS_CFS, CSTS_RDY)?;
+
+    ctrl.regs.store(regs, Ordering::Relaxed);
+    ctrl.irq.store(irq, Ordering::Relaxed);
+    ctrl.irq_driven.store(irq_driven, Ordering::Relaxed);
+    // IDENTIFY de kesmeyle tamamlanır; kuyruklar kurulmadan önce açılır
+    if irq_driven {
+        w32(regs + REG_INTMC, 1);
+    }
+
+    let mut nsids = [0u32; MAX_NAMESPACES];
+    ctrl.with_state(|s| {
+        s.regs = regs;
+        s.stride = 4 << ((cap >> 32) & 0xF);
+        s.admin = Queue { sq: admin_sq, cq: admin_cq, depth, ..Queue::empty(0) };
+        s.io = Queue { sq: io_sq, cq: io_cq, depth, ..Queue::empty(IO_QUEUE_ID) };
+        s.dma = dma;
+        s.prp_list = prp_list;
+
+        // Denetleyici: model (bayt 24-63), MDTS (bayt 77), ad alanı sayısı (bayt 516)
+        ctrl.identify(s, CNS_CONTROLLER, 0)?;
+        unsafe { core::ptr::copy_nonoverlapping((s.dma + 24) as *const u8, s.model.as_mut_ptr(), 40) };
+        let mdts = unsafe { read_volatile((s.dma + 77) as *const u8) };
+        if mdts != 0 {
+            s.max_transfer = s.max_transfer.min(PAGE_SIZE << mdts.min(20));
+        }
+        let count = r32(s.dma + 516);
+
+        let mut cmd = [0u32; 16];
+        cmd[0] = ADMIN_CREATE_CQ;
+        cmd[6] = io_cq as u32;
+        cmd[7] = (io_cq as u64 >> 32) as u32;
+        cmd[10] = (depth as u32 - 1) << 16 | IO_QUEUE_ID as u32;
+        // Vektör 0, kesme açık (IEN), fiziksel olarak ardışık (PC)
+        cmd[11] = 1 << 1 | 1;
+        ctrl.execute(s, false, cmd)?;
+        cmd[0] = ADMIN_CREATE_SQ;
+        cmd[6] = io_sq as u32;
+        cmd[7] = (io_sq as u64 >> 32) as u32;
+        // Tamamlanma kuyruğu ve PC
+        cmd[11] = (IO_QUEUE_ID as u32) << 16 | 1;
+        ctrl.execute(s, false, cmd)?;
+        ctrl.io_ready.store(true, Ordering::Release);
+
+        // Etkin ad alanı listesi NVMe 1.1 ile geldi; eski denetleyicilerde 1..=NN
+        if ctrl.identify(s, CNS_ACTIVE_NAMESPACES, 0).is_ok() {
+            for (i, nsid) in nsids.iter_mut().enumerate() {
+                *nsid = r32(s.dma + i * 4);
+            }
+        } else {
+            for (i, nsid) in nsids.iter_mut().enumerate().take(count as usize) {
+                *nsid = i as u32 + 1;
+            }
+        }
+
+        let model = core::str::from_utf8(&s.model).unwrap_or("?").trim();
+        let vs = r32(regs + REG_VS);
+        serial_println!("[NVMe] {:#x}: {} (NVMe {}.{}, {} ad alanı, kuyruk {})", regs, model, vs >> 16, (vs >> 8) & 0xFF, count, depth);
+        Ok::<_, KernelError>(())
+    })?;
+
+    for &nsid in nsids.iter().take_while(|&&n| n != 0) {
+        if let Err(e) = init_namespace(index, nsid) {
+            serial_println!("[NVMe] Ad alanı {}: başlatılamadı: {:?}", nsid, e);
+        }
+    }
+    Ok(())
+}
+
+/// Kapanış bildirimi: ad alanlarının önbelleğini boşaltır, G/Ç kuyruklarını
+/// siler ve denetleyiciye olağan kapanış bildirir.
+fn quiesce(_action: crate::shutdown::Action) -> Result<(), KernelError> {
+    let mut result = Ok(());
+    for ns in NAMESPACES.iter().filter(|n| n.used.load(Ordering::Acquire)) {
+        result = result.and(ns.flush());
+    }
+    for ctrl in CONTROLLERS.iter().filter(|c| c.used.load(Ordering::Acquire) && c.regs.load(Ordering::Relaxed) != 0) {
+        // Kilit, süren komut tamamlanana kadar bekletir
+        ctrl.with_state(|s| {
+            if ctrl.io_ready.swap(false, Ordering::AcqRel) {
+                let mut cmd = [0u32; 16];
+                cmd[0] = ADMIN_DELETE_SQ;
+                cmd[10] = IO_QUEUE_ID as u32;
+                let deleted = ctrl.execute(s, false, cmd);
+                cmd[0] = ADMIN_DELETE_CQ;
+                result = result.and(deleted).and(ctrl.execute(s, false, cmd)).map(|_| ());
+            }
+            w32(s.regs + REG_INTMS, u32::MAX);
+            let cc = r32(s.regs + REG_CC) & !CC_SHN_MASK;
+            w32(s.regs + REG_CC, cc | CC_SHN_NORMAL);
+            if wait_reg(s.regs + REG_CSTS, CSTS_SHST_MASK, CSTS_SHST_DONE).is_err() {
+                result = result.and(Err(KernelError::GenericFailure));
+            }
+        });
+    }
+    result
+}
+
+fn probe() -> Result<(), KernelError> {
+    let mut found = false;
+    for dev in crate::drivers::pci::find_class(0x01, 0x08, 0x02) {
+        let Some(regs) = dev.bar(0) else { continue };
+        dev.enable();
+        // Vektör 0 INTx'e düşsün diye MSI-X kapalı tutulur (bkz. dosya başı)
+        if let Some(msix) = dev.msix() {
+            msix.enable(false);
+        }
+        dev.set_intx(true);
+
+        // INTx eşlemesi olmayan mimarilerde yoklamayla çalışılır
+        let irq = crate::arch::interrupt::route_pci_intx(dev.irq_line).unwrap_or(u32::MAX);
+
+        match init_controller(regs as usize, irq) {
+            Ok(()) => found = true,
+            Err(e) => serial_println!("[NVMe] {}: başlatılamadı: {:?}", dev.bdf, e),
+        }
+    }
+    if found {
+        crate::shutdown::register("nvme", crate::shutdown::Phase::Drivers, quiesce)?;
+    }
+    Ok(())
+}
+
+crate::initcall!(drivers, "nvme", probe);
@@ -0,0 +1,816 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/drivers/block/sdhci.rs
+// SD Host Controller (SDHCI, spesifikasyon v2/v3) sürücüsü: SD kart başlatma,
+// blok okuma/yazma ve kart algılama.
+//
+// Başlatma sırası: CMD0 -> CMD8 -> (CMD55 + ACMD41)* -> CMD2 -> CMD3 -> CMD9
+// -> CMD7 -> CMD16 (yalnızca SDSC) -> ACMD6 (4 bit). Veri aktarımı ADMA2
+// (32 bit tanımlayıcılar) veya PIO (tampon veri portu) ile yapılır; ADMA2
+// tanımlayıcı tablosu her örnek için bir çerçevede tutulur.
+//
+// Kart algılama: PRESENT_STATE.CARD_INSERTED okunur; takma/çıkarma kesmeleri
+// `handle_interrupt` ile yalnızca "ortam değişti" işaretini koyar, kart bir
+// sonraki G/Ç'de veya `mmc rescan` ile yeniden başlatılır.
+//
+// Varsayımlar: Çekirdek arabellekleri birebir eşlenmiştir (sanal = fiziksel)
+// ve DMA önbellek tutarlıdır. Aygıt ağacı: `reg` taban adres, isteğe bağlı
+// `non-removable` / `broken-cd`, `clock-frequency` (yetenek yazmacı temel
+// saati bildirmiyorsa).
+
+use core::cell::UnsafeCell;
+use core::ptr::{read_volatile, write_volatile};
+use core::sync::atomic::{AtomicBool, Ordering};
+use crate::drivers::block::block::{self, BlockDevice};
+use crate::drivers::dma::DmaMode;
+use crate::platformgeneric::spinlock::Spinlock;
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// YAZMAÇLAR
+// -----------------------------------------------------------------------------
+
+const BLOCK_SIZE_REG: usize = 0x04; // 16 bit
+const BLOCK_COUNT: usize = 0x06; // 16 bit
+const ARGUMENT: usize = 0x08;
+const TRANSFER_MODE: usize = 0x0C; // 16 bit
+const COMMAND: usize = 0x0E; // 16 bit
+const RESPONSE: usize = 0x10; // 4 x 32 bit
+const BUFFER_DATA: usize = 0x20;
+const PRESENT_STATE: usize = 0x24;
+const HOST_CONTROL: usize = 0x28; // 8 bit
+const POWER_CONTROL: usize = 0x29; // 8 bit
+const CLOCK_CONTROL: usize = 0x2C; // 16 bit
+const TIMEOUT_CONTROL: usize = 0x2E; // 8 bit
+const SOFTWARE_RESET: usize = 0x2F; // 8 bit
+const INT_STATUS: usize = 0x30; // normal (alt 16) + hata (üst 16)
+const INT_STATUS_ENABLE: usize = 0x34;
+const INT_SIGNAL_ENABLE: usize = 0x38;
+const CAPABILITIES: usize = 0x40;
+const ADMA_ADDRESS: usize = 0x58;
+const HOST_VERSION: usize = 0xFE; // 16 bit
+
+// TRANSFER_MODE
+const TM_DMA_ENABLE: u16 = 1 << 0;
+const TM_BLOCK_COUNT_ENABLE: u16 = 1 << 1;
+const TM_AUTO_CMD12: u16 = 1 << 2;
+const TM_READ: u16 = 1 << 4;
+const TM_MULTI_BLOCK: u16 = 1 << 5;
+
+// COMMAND
+const CMD_RESP_NONE: u16 = 0;
+const CMD_RESP_136: u16 = 1;
+const CMD_RESP_48: u16 = 2;
+const CMD_RESP_48_BUSY: u16 = 3;
+const CMD_CRC_CHECK: u16 = 1 << 3;
+const CMD_INDEX_CHECK: u16 = 1 << 4;
+const CMD_DATA_PRESENT: u16 = 1 << 5;
+
+// PRESENT_STATE
+const PS_CMD_INHIBIT: u32 = 1 << 0;
+const PS_DAT_INHIBIT: u32 = 1 << 1;
+const PS_CARD_INSERTED: u32 = 1 << 16;
+
+// HOST_CONTROL
+const HC_4BIT: u8 = 1 << 1;
+const HC_DMA_ADMA2_32: u8 = 2 << 3;
+const HC_DMA_MASK: u8 = 3 << 3;
+
+// POWER_CONTROL
+const PC_BUS_POWER: u8 = 1 << 0;
+const PC_3V3: u8 = 7 << 1;
+
+// CLOCK_CONTROL
+const CC_INTERNAL_ENABLE: u16 = 1 << 0;
+const CC_INTERNAL_STABLE: u16 = 1 << 1;
+const CC_SD_ENABLE: u16 = 1 << 2;
+
+// SOFTWARE_RESET
+const RESET_ALL: u8 = 1 << 0;
+const RESET_CMD: u8 = 1 << 1;
+const RESET_DATA: u8 = 1 << 2;
+
+// INT_STATUS
+const INT_CMD_COMPLETE: u32 = 1 << 0;
+const INT_XFER_COMPLETE: u32 = 1 << 1;
+const INT_BUF_WRITE_READY: u32 = 1 << 4;
+const INT_BUF_READ_READY: u32 = 1 << 5;
+const INT_CARD_INSERT: u32 = 1 << 6;
+const INT_CARD_REMOVE: u32 = 1 << 7;
+const INT_ERROR: u32 = 1 << 15;
+const INT_ERR_CMD_TIMEOUT: u32 = 1 << 16;
+const INT_ERR_DATA_TIMEOUT: u32 = 1 << 20;
+const INT_ERR_ADMA: u32 = 1 << 25;
+
+// CAPABILITIES
+const CAP_BASE_CLOCK_SHIFT: u32 = 8;
+const CAP_ADMA2: u32 = 1 << 19;
+
+// ADMA2 tanımlayıcı öznitelikleri
+const ADMA_VALID: u16 = 1 << 0;
+const ADMA_END: u16 = 1 << 1;
+const ADMA_ACT_TRAN: u16 = 2 << 4;
+
+/// Tek bir ADMA2 tanımlayıcısının taşıyabileceği en fazla bayt.
+const ADMA_MAX_LEN: usize = 64 * 1024;
+
+/// SD blok boyutu.
+const SECTOR_SIZE: usize = 512;
+
+/// Tek komutla aktarılan en fazla blok (BLOCK_COUNT 16 bit).
+const MAX_BLOCKS_PER_CMD: usize = 0xFFFF;
+
+/// Kart tanıma ve veri aktarımı saatleri.
+const IDENT_CLOCK_HZ: u32 = 400_000;
+const TRANSFER_CLOCK_HZ: u32 = 25_000_000;
+
+/// Temel saat bildirilmemişse ve aygıt ağacı da vermiyorsa kabul edilen değer.
+const DEFAULT_BASE_CLOCK_HZ: u32 = 100_000_000;
+
+/// Yoklama döngüsü üst sınırı.
+const TIMEOUT_SPINS: u32 = 1_000_000;
+
+/// ACMD41 deneme sayısı (kart güç açma süresi ~1 s).
+const ACMD41_RETRIES: u32 = 1000;
+
+// SD komutları
+const CMD_GO_IDLE: u16 = 0;
+const CMD_ALL_SEND_CID: u16 = 2;
+const CMD_SEND_RELATIVE_ADDR: u16 = 3;
+const ACMD_SET_BUS_WIDTH: u16 = 6;
+const CMD_SELECT_CARD: u16 = 7;
+const CMD_SEND_IF_COND: u16 = 8;
+const CMD_SEND_CSD: u16 = 9;
+const CMD_SET_BLOCKLEN: u16 = 16;
+const CMD_READ_SINGLE: u16 = 17;
+const CMD_READ_MULTIPLE: u16 = 18;
+const CMD_WRITE_SINGLE: u16 = 24;
+const CMD_WRITE_MULTIPLE: u16 = 25;
+const ACMD_SD_SEND_OP_COND: u16 = 41;
+const CMD_APP_CMD: u16 = 55;
+
+/// CMD8 argümanı: 2.7-3.6 V, kontrol deseni 0xAA.
+const IF_COND_ARG: u32 = 0x1AA;
+/// ACMD41: 3.2-3.4 V penceresi; HCS (yüksek kapasite desteği) bit 30.
+const OCR_VOLTAGE: u32 = 0x00FF_8000;
+const OCR_HCS: u32 = 1 << 30;
+const OCR_BUSY_DONE: u32 = 1 << 31;
+
+// -----------------------------------------------------------------------------
+// KOMUTLAR
+// -----------------------------------------------------------------------------
+
+/// Komut yanıt türü.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Resp {
+    None,
+    /// R1, R6, R7: 48 bit, CRC ve indeks denetimli.
+    R1,
+    /// R1b: meşgul sinyalli R1.
+    R1b,
+    /// R2 (CID/CSD): 136 bit.
+    R2,
+    /// R3 (OCR): 48 bit, denetimsiz.
+    R3,
+}
+
+impl Resp {
+    fn flags(self) -> u16 {
+        match self {
+            Resp::None => CMD_RESP_NONE,
+            Resp::R1 => CMD_RESP_48 | CMD_CRC_CHECK | CMD_INDEX_CHECK,
+            Resp::R1b => CMD_RESP_48_BUSY | CMD_CRC_CHECK | CMD_INDEX_CHECK,
+            Resp::R2 => CMD_RESP_136 | CMD_CRC_CHECK,
+            Resp::R3 => CMD_RESP_48,
+        }
+    }
+}
+
+/// Veri aşamalı komutun yönü ve arabelleği.
+enum Data<'a> {
+    Read(&'a mut [u8]),
+    Write(&'a [u8]),
+}
+
+impl Data<'_> {
+    fn len(&self) -> usize {
+        match self {
+            Data::Read(buf) => buf.len(),
+            Data::Write(buf) => buf.len(),
+        }
+    }
+
+    fn addr(&self) -> usize {
+        match self {
+            Data::Read(buf) => buf.as_ptr() as usize,
+            Data::Write(buf) => buf.as_ptr() as usize,
+        }
+    }
+}
+
+/// 32 bit ADMA2 tanımlayıcısı.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct AdmaDesc {
+    attr: u16,
+    len: u16,
+    addr: u32,
+}
+
+// -----------------------------------------------------------------------------
+// DENETLEYİCİ
+// -----------------------------------------------------------------------------
+
+/// Başlatılmış kartın bilgileri.
+#[derive(Clone, Copy)]
+struct CardState {
+    base: usize,
+    /// Spesifikasyon sürümü (0: v1, 1: v2, 2: v3).
+    version: u8,
+    base_clock_hz: u32,
+    /// ADMA2 tanımlayıcı tablosu çerçevesi (0: ADMA yok).
+    adma_table: usize,
+    /// Kart algılama hattı yok sayılır (eMMC, `broken-cd`).
+    always_present: bool,
+    /// Aktarımlarda DMA tercihi.
+    dma: DmaMode,
+    /// Kart başlatıldı.
+    ready: bool,
+    rca: u32,
+    /// SDHC/SDXC: blok adresli.
+    high_capacity: bool,
+    blocks: u64,
+}
+
+/// Tek bir SDHCI örneği.
+pub struct Sdhci {
+    name: &'static str,
+    lock: Spinlock,
+    state: UnsafeCell<CardState>,
+    /// Takma/çıkarma kesmesi geldi; kart yeniden başlatılacak.
+    media_changed: AtomicBool,
+    used: AtomicBool,
+}
+
+// Durum `lock` ile korunur.
+unsafe impl Sync for Sdhci {}
+
+impl Sdhci {
+    const fn empty(name: &'static str) -> Self {
+        Sdhci {
+            name,
+            lock: Spinlock::new(),
+            state: UnsafeCell::new(CardState {
+                base: 0,
+                version: 0,
+                base_clock_hz: 0,
+                adma_table: 0,
+                always_present: false,
+                dma: DmaMode::Prefer,
+                ready: false,
+                rca: 0,
+                high_capacity: false,
+                blocks: 0,
+            }),
+            media_changed: AtomicBool::new(false),
+            used: AtomicBool::new(false),
+        }
+    }
+
+    /// Kilit altında durumu verir.
+    fn with_state<R>(&self, f: impl FnOnce(&mut CardState) -> R) -> R {
+        self.lock.lock();
+        let result = f(unsafe { &mut *self.state.get() });
+        self.lock.unlock();
+        result
+    }
+
+    /// Aktarımlarda kullanılacak DMA tercihini ayarlar (varsayılan `Prefer`).
+    pub fn set_dma_mode(&self, mode: DmaMode) {
+        self.with_state(|s| s.dma = mode);
+    }
+
+    /// Kesme işleyicisi: kart takma/çıkarma olaylarını onaylar.
+    pub fn handle_interrupt(&self) {
+        // Taban adres keşiften sonra değişmez; kesme bağlamında kilit alınmaz
+        let base = unsafe { (*self.state.get()).base };
+        if base == 0 {
+            return;
+        }
+        let status = unsafe { read_volatile((base + INT_STATUS) as *const u32) };
+        let events = status & (INT_CARD_INSERT | INT_CARD_REMOVE);
+        if events != 0 {
+            unsafe { write_volatile((base + INT_STATUS) as *mut u32, events) };
+            self.media_changed.store(true, Ordering::Release);
+        }
+    }
+
+    /// Kartı yeniden tanır (kabuk ve kart algılama için).
+    pub fn rescan(&self) -> Result<(), KernelError> {
+        self.media_changed.store(false, Ordering::Release);
+        self.with_state(|s| s.init_card())
+    }
+
+    /// Bekleyen ortam değişikliği varsa kartı yeniden başlatır.
+    fn check_media(&self, s: &mut CardState) -> Result<(), KernelError> {
+        if self.media_changed.swap(false, Ordering::AcqRel) || (!s.ready && s.card_inserted()) {
+            if let Err(e) = s.init_card() {
+                s.ready = false;
+                return Err(e);
+            }
+        }
+        if !s.ready || !s.card_inserted() {
+            s.ready = false;
+            return Err(KernelError::NotFound);
+        }
+        Ok(())
+    }
+
+    fn transfer(&self, lba: u64, mut data: Data) -> Result<(), KernelError> {
+        if data.len() == 0 || data.len() % SECTOR_SIZE != 0 {
+            return Err(KernelError::InvalidArgument);
+        }
+        let count = (data.len() / SECTOR_SIZE) as u64;
+
+        self.lock.lock();
+        let s = unsafe { &mut *self.state.get() };
+        let result = self.check_media(s).and_then(|_| {
+            // Kapasite kart yeniden tanındıktan sonra denetlenir
+            if lba.checked_add(count).is_none_or(|end| end > s.blocks) {
+                return Err(KernelError::InvalidArgument);
+            }
+            let dma = s.dma.resolve(s.adma_table != 0)?;
+            // Komut başına 16 bit blok sayısı sınırı
+            let chunk = MAX_BLOCKS_PER_CMD * SECTOR_SIZE;
+            for i in 0..(count as usize).div_ceil(MAX_BLOCKS_PER_CMD) {
+                let lba = lba + (i * MAX_BLOCKS_PER_CMD) as u64;
+                let part = match &mut data {
+                    Data::Read(buf) => {
+                        let end = ((i + 1) * chunk).min(buf.len());
+                        Data::Read(&mut buf[i * chunk..end])
+                    }
+                    Data::Write(buf) => Data::Write(&buf[i * chunk..((i + 1) * chunk).min(buf.len())]),
+                };
+                s.transfer_blocks(lba, part, dma)?;
+            }
+            Ok(())
+        });
+        self.lock.unlock();
+        result
+    }
+}
+
+impl CardState {
+    #[inline(always)]
+    fn r8(&self, offset: usize) -> u8 {
+        unsafe { read_volatile((self.base + offset) as *const u8) }
+    }
+
+    #[inline(always)]
+    fn w8(&self, offset: usize, value: u8) {
+        unsafe { write_volatile((self.base + offset) as *mut u8, value) }
+    }
+
+    #[inline(always)]
+    fn r16(&self, offset: usize) -> u16 {
+        unsafe { read_volatile((self.base + offset) as *const u16) }
+    }
+
+    #[inline(always)]
+    fn w16(&self, offset: usize, value: u16) {
+        unsafe { write_volatile((self.base + offset) as *mut u16, value) }
+    }
+
+    #[inline(always)]
+    fn r32(&self, offset: usize) -> u32 {
+        unsafe { read_volatile((self.base + offset) as *const u32) }
+    }
+
+    #[inline(always)]
+    fn w32(&self, offset: usize, value: u32) {
+        unsafe { write_volatile((self.base + offset) as *mut u32, value) }
+    }
+
+    fn card_inserted(&self) -> bool {
+        self.always_present || self.r32(PRESENT_STATE) & PS_CARD_INSERTED != 0
+    }
+
+    /// Yazılım sıfırlaması yapar ve bitmesini bekler.
+    fn reset(&self, mask: u8) -> Result<(), KernelError> {
+        self.w8(SOFTWARE_RESET, mask);
+        for _ in 0..TIMEOUT_SPINS {
+            if self.r8(SOFTWARE_RESET) & mask == 0 {
+                return Ok(());
+            }
+        }
+        Err(KernelError::GenericFailure)
+    }
+
+    /// SD saatini `hz`'i aşmayacak şekilde ayarlar.
+    fn set_clock(&self, hz: u32) -> Result<(), KernelError> {
+        self.w16(CLOCK_CONTROL, 0);
+
+        // v3: 10 bit bölen N, SDCLK = taban / 2N. v2: 8 bit, N ikinin kuvveti.
+        let mut div = self.base_clock_hz.div_ceil(2 * hz);
+        if self.version < 2 {
+            div = div.next_power_of_two().min(128);
+        } else {
+            div = div.min(0x3FF);
+        }
+        let freq_sel = (((div & 0xFF) << 8) | (((div >> 8) & 0x3) << 6)) as u16;
+
+        self.w16(CLOCK_CONTROL, freq_sel | CC_INTERNAL_ENABLE);
+        let mut stable = false;
+        for _ in 0..TIMEOUT_SPINS {
+            if self.r16(CLOCK_CONTROL) & CC_INTERNAL_STABLE != 0 {
+                stable = true;
+                break;
+            }
+        }
+        if !stable {
+            return Err(KernelError::GenericFailure);
+        }
+        self.w16(CLOCK_CONTROL, freq_sel | CC_INTERNAL_ENABLE | CC_SD_ENABLE);
+        Ok(())
+    }
+
+    /// Kesme durum bitlerinden birini bekler; hata bitleri `Err` döner.
+    fn wait_int(&self, mask: u32) -> Result<(), KernelError> {
+        for _ in 0..TIMEOUT_SPINS {
+            let status = self.r32(INT_STATUS);
+            if status & INT_ERROR != 0 {
+                self.w32(INT_STATUS, (status & 0xFFFF_0000) | INT_ERROR);
+                let _ = self.reset(RESET_CMD | RESET_DATA);
+                return Err(if status & (INT_ERR_CMD_TIMEOUT | INT_ERR_DATA_TIMEOUT) != 0 {
+                    KernelError::NotFound
+                } else {
+                    KernelError::GenericFailure
+                });
+            }
+            if status & mask != 0 {
+                self.w32(INT_STATUS, status & mask);
+                return Ok(());
+            }
+        }
+        let _ = self.reset(RESET_CMD | RESET_DATA);
+        Err(KernelError::GenericFailure)
+    }
+
+    /// Bir komut gönderir ve yanıtı döner. Veri aşaması (varsa) çağırana aittir.
+    fn command(&self, index: u16, arg: u32, resp: Resp, data: bool) -> Result<[u32; 4], KernelError> {
+        let mut inhibit = PS_CMD_INHIBIT;
+        if data || resp == Resp::R1b {
+            inhibit |= PS_DAT_INHIBIT;
+        }
+        let mut idle = false;
+        for _ in 0..TIMEOUT_SPINS {
+            if self.r32(PRESENT_STATE) & inhibit == 0 {
+                idle = true;
+                break;
+            }
+        }
+        if !idle {
+            return Err(KernelError::ResourceBusy);
+        }
+
+        // Eski durum bitlerini temizle (kart algılama olayları kesme işleyicisine kalır)
+        self.w32(INT_STATUS, !(INT_CARD_INSERT | INT_CARD_REMOVE));
+        self.w32(ARGUMENT, arg);
+        let mut cmd = (index << 8) | resp.flags();
+        if data {
+            cmd |= CMD_DATA_PRESENT;
+        }
+        self.w16(COMMAND, cmd);
+
+        self.wait_int(INT_CMD_COMPLETE)?;
+        let response = [
+            self.r32(RESPONSE),
+            self.r32(RESPONSE + 4),
+            self.r32(RESPONSE + 8),
+            self.r32(RESPONSE + 12),
+        ];
+        if resp == Resp::R1b {
+            self.wait_int(INT_XFER_COMPLETE)?;
+        }
+        Ok(response)
+    }
+
+    /// Uygulamaya özgü komut (CMD55 öneki ile).
+    fn app_command(&self, index: u16, arg: u32, resp: Resp) -> Result<[u32; 4], KernelError> {
+        self.command(CMD_APP_CMD, self.rca << 16, Resp::R1, false)?;
+        self.command(index, arg, resp, false)
+    }
+
+    /// Denetleyiciyi sıfırlar ve takılı kartı tanıma sırasından geçirir.
+    fn init_card(&mut self) -> Result<(), KernelError> {
+        self.ready = false;
+        self.rca = 0;
+        if !self.card_inserted() {
+            return Err(KernelError::NotFound);
+        }
+
+        self.reset(RESET_ALL)?;
+        self.w8(POWER_CONTROL, PC_3V3);
+        self.w8(POWER_CONTROL, PC_3V3 | PC_BUS_POWER);
+        self.w8(TIMEOUT_CONTROL, 0xE);
+        self.w32(INT_STATUS_ENABLE, 0xFFFF_FFFF);
+        self.w32(INT_SIGNAL_ENABLE, INT_CARD_INSERT | INT_CARD_REMOVE);
+        self.w8(HOST_CONTROL, 0);
+        self.set_clock(IDENT_CLOCK_HZ)?;
+
+        self.command(CMD_GO_IDLE, 0, Resp::None, false)?;
+
+        // CMD8'e yanıt vermeyen kart v1.x'tir ve yüksek kapasiteli olamaz
+        let v2 = match self.command(CMD_SEND_IF_COND, IF_COND_ARG, Resp::R1, false) {
+            Ok(r) if r[0] & 0xFFF == IF_COND_ARG => true,
+            Ok(_) => return Err(KernelError::GenericFailure),
+            Err(KernelError::NotFound) => false,
+            Err(e) => return Err(e),
+        };
+
+        let arg = OCR_VOLTAGE | if v2 { OCR_HCS } else { 0 };
+        let mut ocr = 0;
+        for _ in 0..ACMD41_RETRIES {
+            ocr = self.app_command(ACMD_SD_SEND_OP_COND, arg, Resp::R3)?[0];
+            if ocr & OCR_BUSY_DONE != 0 {
+                break;
+            }
+            for _ in 0..1000 {
+                core::hint::spin_loop();
+            }
+        }
+        if ocr & OCR_BUSY_DONE == 0 {
+            return Err(KernelError::GenericFailure);
+        }
+        self.high_capacity = ocr & OCR_HCS != 0;
+
+        self.command(CMD_ALL_SEND_CID, 0, Resp::R2, false)?;
+        self.rca = self.command(CMD_SEND_RELATIVE_ADDR, 0, Resp::R1, false)?[0] >> 16;
+        let csd = self.command(CMD_SEND_CSD, self.rca << 16, Resp::R2, false)?;
+        self.blocks = csd_blocks(csd);
+        self.command(CMD_SELECT_CARD, self.rca << 16, Resp::R1b, false)?;
+
+        if !self.high_capacity {
+            self.command(CMD_SET_BLOCKLEN, SECTOR_SIZE as u32, Resp::R1, false)?;
+        }
+
+        // 4 bit veri yolu ve aktarım saati
+        self.app_command(ACMD_SET_BUS_WIDTH, 2, Resp::R1)?;
+        self.w8(HOST_CONTROL, self.r8(HOST_CONTROL) | HC_4BIT);
+        self.set_clock(TRANSFER_CLOCK_HZ)?;
+
+        self.ready = true;
+        serial_println!(
+            "[SDHCI] Kart hazır: RCA {:#06x}, {}, {} blok",
+            self.rca,
+            if self.high_capacity { "SDHC/SDXC" } else { "SDSC" },
+            self.blocks
+        );
+        Ok(())
+    }
+
+    /// ADMA2 tanımlayıcı tablosunu arabellek için doldurur.
+    /// Arabellek 4 bayt hizalı ve 4 GiB altında olmalıdır.
+    fn build_adma(&self, addr: usize, len: usize) -> bool {
+        let max_descs = crate::memory::frame::FRAME_SIZE / core::mem::size_of::<AdmaDesc>();
+        if addr % 4 != 0 || (addr + len) as u64 > u32::MAX as u64 || len.div_ceil(ADMA_MAX_LEN) > max_descs {
+            return false;
+        }
+        let table = self.adma_table as *mut AdmaDesc;
+        let count = len.div_ceil(ADMA_MAX_LEN);
+        for i in 0..count {
+            let off = i * ADMA_MAX_LEN;
+            let part = (len - off).min(ADMA_MAX_LEN);
+            let mut attr = ADMA_VALID | ADMA_ACT_TRAN;
+            if i + 1 == count {
+                attr |= ADMA_END;
+            }
+            // 64 KiB uzunluk alanında 0 olarak kodlanır
+            let desc = AdmaDesc { attr, len: part as u16, addr: (addr + off) as u32 };
+            unsafe { write_volatile(table.add(i), desc) };
+        }
+        true
+    }
+
+    /// Tek bir CMD17/18/24/25 aktarımı (en fazla `MAX_BLOCKS_PER_CMD` blok).
+    fn transfer_blocks(&self, lba: u64, data: Data, dma: bool) -> Result<(), KernelError> {
+        let count = data.len() / SECTOR_SIZE;
+        let read = matches!(data, Data::Read(_));
+        let arg = if self.high_capacity { lba as u32 } else { (lba * SECTOR_SIZE as u64) as u32 };
+        let index = match (read, count > 1) {
+            (true, false) => CMD_READ_SINGLE,
+            (true, true) => CMD_READ_MULTIPLE,
+            (false, false) => CMD_WRITE_SINGLE,
+            (false, true) => CMD_WRITE_MULTIPLE,
+        };
+
+        // Hizalanmamış arabellekler PIO'ya düşer
+        let use_dma = dma && self.build_adma(data.addr(), data.len());
+
+        let mut mode = TM_BLOCK_COUNT_ENABLE;
+        if read {
+            mode |= TM_READ;
+        }
+        if count > 1 {
+            mode |= TM_MULTI_BLOCK | TM_AUTO_CMD12;
+        }
+        let host = self.r8(HOST_CONTROL) & !HC_DMA_MASK;
+        if use_dma {
+            mode |= TM_DMA_ENABLE;
+            self.w8(HOST_CONTROL, host | HC_DMA_ADMA2_32);
+            self.w32(ADMA_ADDRESS, self.adma_table as u32);
+            self.w32(ADMA_ADDRESS + 4, 0);
+        } else {
+            self.w8(HOST_CONTROL, host);
+        }
+
+        self.w16(BLOCK_SIZE_REG, SECTOR_SIZE as u16);
+        self.w16(BLOCK_COUNT, count as u16);
+        self.w16(TRANSFER_MODE, mode);
+        self.command(index, arg, Resp::R1, true)?;
+
+        if !use_dma {
+            match data {
+                Data::Read(buf) => {
+                    for block in buf.chunks_mut(SECTOR_SIZE) {
+                        self.wait_int(INT_BUF_READ_READY)?;
+                        for word in block.chunks_mut(4) {
+                            word.copy_from_slice(&self.r32(BUFFER_DATA).to_le_bytes());
+                        }
+                    }
+                }
+                Data::Write(buf) => {
+                    for block in buf.chunks(SECTOR_SIZE) {
+                        self.wait_int(INT_BUF_WRITE_READY)?;
+                        for word in block.chunks(4) {
+                            self.w32(BUFFER_DATA, u32::from_le_bytes([word[0], word[1], word[2], word[3]]));
+                        }
+                    }
+                }
+            }
+        }
+
+        self.wait_int(INT_XFER_COMPLETE).inspect_err(|_| {
+            if self.r32(INT_STATUS) & INT_ERR_ADMA != 0 {
+                serial_println!("[SDHCI] ADMA hatası (LBA {})", lba);
+            }
+        })
+    }
+}
+
+/// CSD yazmacından kapasiteyi blok cinsinden hesaplar.
+///
+/// SDHCI R2 yanıtını CRC'siz ve 8 bit sağa kaydırılmış verir: CSD bit `n`,
+/// yanıt yazmaçlarında bit `n - 8`'dedir.
+fn csd_blocks(resp: [u32; 4]) -> u64 {
+    let r = resp[0] as u128 | (resp[1] as u128) << 32 | (resp[2] as u128) << 64 | (resp[3] as u128) << 96;
+    let bits = |hi: u32, lo: u32| ((r >> (lo - 8)) & ((1u128 << (hi - lo + 1)) - 1)) as u64;
+
+    match bits(127, 126) {
+        // CSD v2 (SDHC/SDXC): kapasite = (C_SIZE + 1) * 512 KiB
+        1 => (bits(69, 48) + 1) * 1024,
+        // CSD v1 (SDSC): kapasite = (C_SIZE + 1) * 2^(C_SIZE_MULT + 2) * 2^READ_BL_LEN
+        _ => {
+            let bytes = (bits(73, 62) + 1) << (bits(49, 47) + 2 + bits(83, 80));
+            bytes / SECTOR_SIZE as u64
+        }
+    }
+}
+
+impl BlockDevice for Sdhci {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn block_size(&self) -> usize {
+        SECTOR_SIZE
+    }
+
+    fn block_count(&self) -> u64 {
+        self.with_state(|s| if s.ready { s.blocks } else { 0 })
+    }
+
+    fn is_present(&self) -> bool {
+        self.with_state(|s| s.card_inserted())
+    }
+
+    fn read_blocks(&self, lba: u64, buf: &mut [u8]) -> Result<(), KernelError> {
+        self.transfer(lba, Data::Read(buf))
+    }
+
+    fn write_blocks(&self, lba: u64, buf: &[u8]) -> Result<(), KernelError> {
+        self.transfer(lba, Data::Write(buf))
+    }
+}
+
+// -----------------------------------------------------------------------------
+// AYGIT AĞACI KEŞFİ
+// -----------------------------------------------------------------------------
+
+/// En fazla örnek sayısı.
+const MAX_INSTANCES: usize = 4;
+
+static INSTANCES: [Sdhci; MAX_INSTANCES] = [
+    Sdhci::empty("mmc0"),
+    Sdhci::empty("mmc1"),
+    Sdhci::empty("mmc2"),
+    Sdhci::empty("mmc3"),
+];
+
+/// SDHCI uyumlu denetleyicilerin `compatible` dizeleri.
+const COMPATIBLES: [&str; 4] = [
+    "arasan,sdhci-8.9a",
+    "arasan,sdhci-5.1",
+    "brcm,bcm2711-emmc2",
+    "snps,dwcmshc-sdhci",
+];
+
+/// Tüm örneklerin kesme işleyicisi (paylaşılan hatlar için).
+pub fn handle_interrupts() {
+    INSTANCES.iter().filter(|i| i.used.load(Ordering::Acquire)).for_each(Sdhci::handle_interrupt);
+}
+
+/// Kart algılama durumunu yoklar; değişiklik varsa kartı yeniden tanır.
+pub fn poll_card_detect() {
+    for sd in INSTANCES.iter().filter(|i| i.used.load(Ordering::Acquire)) {
+        sd.handle_interrupt();
+        if sd.media_changed.load(Ordering::Acquire) {
+            let _ = sd.rescan();
+        }
+    }
+}
+
+fn probe() -> Result<(), KernelError> {
+    let Some(fdt) = crate::fdt::get() else { return Ok(()) };
+
+    for node in fdt.nodes().filter(|n| n.is_enabled() && COMPATIBLES.iter().any(|c| n.is_compatible(c))) {
+        let Some((base, _)) = node.reg(0) else { continue };
+        let Some(slot) = INSTANCES.iter().find(|i| !i.used.swap(true, Ordering::AcqRel)) else {
+            return Err(KernelError::OutOfMemoryStatic);
+        };
+
+        let always_present = node.property("non-removable").is_some() || node.property("broken-cd").is_some();
+        slot.with_state(|s| {
+            s.base = base as usize;
+            s.always_present = always_present;
+            s.version = (s.r16(HOST_VERSION) & 0xFF) as u8;
+
+            let caps = s.r32(CAPABILITIES);
+            let mask = if s.version >= 2 { 0xFF } else { 0x3F };
+            s.base_clock_hz = match (caps >> CAP_BASE_CLOCK_SHIFT) & mask {
+                0 => node.property_u32("clock-frequency").unwrap_or(DEFAULT_BASE_CLOCK_HZ),
+                mhz => mhz * 1_000_000,
+            };
+
+            // ADMA2 tablosu için bir çerçeve; yoksa yalnızca PIO
+            if caps & CAP_ADMA2 != 0 {
+                s.adma_table = crate::memory::frame::alloc().unwrap_or(0);
+            }
+
+            if let Err(e) = s.init_card() {
+                serial_println!("[SDHCI] {}: kart başlatılamadı: {:?}", slot.name, e);
+            }
+        });
+
+        block::register(slot)?;
+    }
+    Ok(())
+}
+
+crate::initcall!(drivers, "sdhci", probe);
+
+// -----------------------------------------------------------------------------
+// KABUK
+// -----------------------------------------------------------------------------
+
+fn mmc_command(args: &[&str]) -> Result<(), KernelError> {
+    let instances = INSTANCES.iter().filter(|i| i.used.load(Ordering::Acquire));
+    match args.get(1).copied() {
+        Some("rescan") => {
+            for sd in instances {
+                if let Err(e) = sd.rescan() {
+                    serial_println!("  {}: {:?}", sd.name, e);
+                }
+            }
+            Ok(())
+        }
+        _ => {
+            for sd in instances {
+                let (version, clock, adma, ready) =
+                    sd.with_state(|s| (s.version + 1, s.base_clock_hz, s.adma_table != 0, s.ready));
+                serial_println!(
+                    "  {} v{}.0 temel saat {} MHz, ADMA2: {}, kart: {}",
+                    sd.name,
+                    version,
+                    clock / 1_000_000,
+                    adma,
+                    if ready { "hazır" } else if sd.is_present() { "başlatılmadı" } else { "yok" }
+                );
+            }
+            Ok(())
+        }
+    }
+}
+
+crate::shell_command!("mmc", "SD denetleyicileri (mmc [rescan])", mmc_command);
@@ -0,0 +1,42 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/drivers/dma.rs
+// Veri yolu sürücüleri için ortak DMA kullanım tercihi.
+//
+// Aktarım API'leri çağıranın DMA isteğini `DmaMode` ile alır; denetleyici DMA
+// desteklemiyorsa `Prefer` sessizce PIO'ya düşer, `Required` ise hata verir.
+// DMA ile aktarılacak arabellekler fiziksel olarak ardışık olmalıdır
+// (bkz. `memory::frame::alloc_contiguous`); kullanıcı eşlemesindeki bir
+// arabellek aktarım süresince `mmap::pin_range` ile sabitlenmelidir.
+
+use crate::platformgeneric::KernelError;
+
+/// Bir aktarım için DMA tercihi.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DmaMode {
+    /// Her zaman programlı G/Ç (PIO).
+    #[default]
+    Never,
+    /// Denetleyici destekliyorsa DMA, aksi halde PIO.
+    Prefer,
+    /// DMA zorunlu; desteklenmiyorsa aktarım başlamadan hata döner.
+    Required,
+}
+
+impl DmaMode {
+    /// Tercihi denetleyicinin yeteneğine göre çözer.
+    ///
+    /// # Dönüş Değeri
+    /// DMA kullanılacaksa `true`.
+    ///
+    /// # Hatalar
+    /// * `InvalidArgument`: `Required` istendi ama denetleyici DMA desteklemiyor.
+    pub fn resolve(self, supported: bool) -> Result<bool, KernelError> {
+        match self {
+            DmaMode::Never => Ok(false),
+            DmaMode::Prefer => Ok(supported),
+            DmaMode::Required if supported => Ok(true),
+            DmaMode::Required => Err(KernelError::InvalidArgument),
+        }
+    }
+}
@@ -0,0 +1,296 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/drivers/flash/cfi.rs
+// CFI paralel NOR flash sürücüsü, Intel/Sharp komut kümesi (`cfi-flash`).
+//
+// QEMU `virt` makinelerindeki pflash aygıtları bu modeldir. Flash okuma
+// dizisi (read array) kipindeyken belleğe eşlenmiş gibi doğrudan okunur;
+// programlama ve silme komut dizileriyle yapılır, ardından durum yazmacı
+// hazır olana kadar yoklanır ve aygıt okuma kipine döndürülür. Komutlar
+// veri yolu genişliğindeki (`bank-width`) her bayt şeridine yinelenerek
+// yazılır; böylece aynı hatta paralel bağlı yongalar da desteklenir.
+//
+// Geometri CFI sorgusundan okunur (ilk silme bölgesi kullanılır); boyut
+// aygıt ağacındaki `reg`'den alınır. Yazma tamponu komutu (0xE8)
+// kullanılmaz; her birim tek tek programlanır.
+
+use core::ptr::{read_volatile, write_volatile};
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use crate::drivers::flash::flash::{self, FlashDevice};
+use crate::platformgeneric::spinlock::Spinlock;
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// KOMUTLAR
+// -----------------------------------------------------------------------------
+
+const CMD_READ_ARRAY: u8 = 0xFF;
+const CMD_READ_STATUS: u8 = 0x70;
+const CMD_CLEAR_STATUS: u8 = 0x50;
+const CMD_QUERY: u8 = 0x98;
+const CMD_PROGRAM: u8 = 0x40;
+const CMD_BLOCK_ERASE: u8 = 0x20;
+const CMD_CONFIRM: u8 = 0xD0;
+const CMD_LOCK_SETUP: u8 = 0x60;
+
+// Durum yazmacı
+const SR_READY: u8 = 1 << 7;
+const SR_ERASE_ERROR: u8 = 1 << 5;
+const SR_PROGRAM_ERROR: u8 = 1 << 4;
+const SR_LOCKED: u8 = 1 << 1;
+
+// CFI sorgu tablosu konumları (birim: veri yolu genişliği)
+const CFI_QUERY_ADDR: usize = 0x55;
+const CFI_SIGNATURE: usize = 0x10;
+const CFI_COMMAND_SET: usize = 0x13;
+const CFI_DEVICE_SIZE: usize = 0x27;
+/// İlk silme bölgesi: blok sayısı - 1 (2 bayt), blok boyutu / 256 (2 bayt).
+const CFI_ERASE_REGION1: usize = 0x2D;
+
+/// Intel/Sharp genişletilmiş komut kümesi.
+const COMMAND_SET_INTEL: u16 = 0x0001;
+
+/// Durum yoklaması üst sınırı (silme saniyeler sürebilir).
+const TIMEOUT_SPINS: u32 = 50_000_000;
+
+crate::kernel_counter!(ERASES, "flash.cfi.erases");
+crate::kernel_counter!(ERRORS, "flash.cfi.errors");
+
+// -----------------------------------------------------------------------------
+// AYGIT
+// -----------------------------------------------------------------------------
+
+/// Tek bir CFI flash bankası.
+pub struct CfiFlash {
+    base: AtomicUsize,
+    size: AtomicUsize,
+    erase_size: AtomicUsize,
+    /// Veri yolu genişliği (1, 2 veya 4 bayt).
+    width: AtomicUsize,
+    used: AtomicBool,
+    /// Komut dizileri iç içe geçmesin diye.
+    lock: Spinlock,
+    name: &'static str,
+}
+
+impl CfiFlash {
+    const fn empty(name: &'static str) -> Self {
+        CfiFlash {
+            base: AtomicUsize::new(0),
+            size: AtomicUsize::new(0),
+            erase_size: AtomicUsize::new(0),
+            width: AtomicUsize::new(4),
+            used: AtomicBool::new(false),
+            lock: Spinlock::new(),
+            name,
+        }
+    }
+
+    fn width(&self) -> usize {
+        self.width.load(Ordering::Relaxed)
+    }
+
+    /// Veri yolu genişliğinde değer yazar.
+    fn write_bus(&self, offset: usize, value: u32) {
+        let addr = self.base.load(Ordering::Relaxed) + offset;
+        unsafe {
+            match self.width() {
+                1 => write_volatile(addr as *mut u8, value as u8),
+                2 => write_volatile(addr as *mut u16, value as u16),
+                _ => write_volatile(addr as *mut u32, value),
+            }
+        }
+    }
+
+    /// Veri yolu genişliğinde değer okur.
+    fn read_bus(&self, offset: usize) -> u32 {
+        let addr = self.base.load(Ordering::Relaxed) + offset;
+        unsafe {
+            match self.width() {
+                1 => u32::from(read_volatile(addr as *const u8)),
+                2 => u32::from(read_volatile(addr as *const u16)),
+                _ => read_volatile(addr as *const u32),
+            }
+        }
+    }
+
+    /// Komutu tüm bayt şeritlerine yineleyerek yazar.
+    fn command(&self, offset: usize, cmd: u8) {
+        self.write_bus(offset, u32::from(cmd) * 0x0101_0101);
+    }
+
+    /// CFI sorgu tablosundan bir bayt (en düşük şerit).
+    fn query_byte(&self, index: usize) -> u8 {
+        self.read_bus(index * self.width()) as u8
+    }
+
+    fn query_u16(&self, index: usize) -> u16 {
+        u16::from_le_bytes([self.query_byte(index), self.query_byte(index + 1)])
+    }
+
+    /// İşlem bitene kadar durumu yoklar; hata bitlerini temizleyip okuma
+    /// kipine döner.
+    fn wait_ready(&self, offset: usize) -> Result<(), KernelError> {
+        let mut status = 0;
+        let mut ready = false;
+        for _ in 0..TIMEOUT_SPINS {
+            status = self.read_bus(offset) as u8;
+            if status & SR_READY != 0 {
+                ready = true;
+                break;
+            }
+            core::hint::spin_loop();
+        }
+        let failed = !ready || status & (SR_ERASE_ERROR | SR_PROGRAM_ERROR | SR_LOCKED) != 0;
+        if failed {
+            self.command(offset, CMD_CLEAR_STATUS);
+            ERRORS.inc();
+        }
+        self.command(offset, CMD_READ_ARRAY);
+        match (ready, failed) {
+            (false, _) => Err(DeviceError::Timeout.into()),
+            (true, true) => Err(DeviceError::Status(u32::from(status)).into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// CFI sorgusuyla aygıtı tanır ve geometrisini okur.
+    fn probe(&self, reg_size: usize) -> Result<(), KernelError> {
+        let width = self.width();
+        self.command(0, CMD_READ_ARRAY);
+        self.command(CFI_QUERY_ADDR * width, CMD_QUERY);
+        let signature = [self.query_byte(CFI_SIGNATURE), self.query_byte(CFI_SIGNATURE + 1), self.query_byte(CFI_SIGNATURE + 2)];
+        let command_set = self.query_u16(CFI_COMMAND_SET);
+        let device_size = 1usize << self.query_byte(CFI_DEVICE_SIZE).min(31);
+        let size_field = self.query_u16(CFI_ERASE_REGION1 + 2);
+        self.command(0, CMD_READ_ARRAY);
+
+        if &signature != b"QRY" {
+            return Err(KernelError::NotFound);
+        }
+        if command_set != COMMAND_SET_INTEL {
+            serial_println!("[FLASH] {}: desteklenmeyen komut kümesi {:#06x}", self.name, command_set);
+            return Err(KernelError::NotFound);
+        }
+        // Bölge boyutu 256 baytın katı olarak verilir (0: 128 B)
+        let erase_size = if size_field == 0 { 128 } else { usize::from(size_field) * 256 };
+        let size = if reg_size != 0 { reg_size } else { device_size };
+        if erase_size == 0 || size % erase_size != 0 {
+            return Err(KernelError::InvalidArgument);
+        }
+        self.size.store(size, Ordering::Relaxed);
+        self.erase_size.store(erase_size, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+impl FlashDevice for CfiFlash {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn size(&self) -> usize {
+        self.size.load(Ordering::Relaxed)
+    }
+
+    fn erase_size(&self) -> usize {
+        self.erase_size.load(Ordering::Relaxed)
+    }
+
+    fn write_size(&self) -> usize {
+        self.width()
+    }
+
+    fn read(&self, offset: usize, buf: &mut [u8]) -> Result<(), KernelError> {
+        flash::check_range(self, offset, buf.len(), 1)?;
+        let base = self.base.load(Ordering::Relaxed);
+        self.lock.lock();
+        for (i, b) in buf.iter_mut().enumerate() {
+            *b = unsafe { read_volatile((base + offset + i) as *const u8) };
+        }
+        self.lock.unlock();
+        Ok(())
+    }
+
+    fn program(&self, offset: usize, data: &[u8]) -> Result<(), KernelError> {
+        let width = self.width();
+        flash::check_range(self, offset, data.len(), width)?;
+        self.lock.lock();
+        let mut result = Ok(());
+        for (i, unit) in data.chunks(width).enumerate() {
+            let addr = offset + i * width;
+            let mut bytes = [0u8; 4];
+            bytes[..width].copy_from_slice(unit);
+            // Silinmiş birime tamamen 0xFF yazmak gereksizdir
+            if unit.iter().all(|&b| b == flash::ERASED) {
+                continue;
+            }
+            self.command(addr, CMD_PROGRAM);
+            self.write_bus(addr, u32::from_le_bytes(bytes));
+            result = self.wait_ready(addr);
+            if result.is_err() {
+                break;
+            }
+        }
+        self.lock.unlock();
+        result
+    }
+
+    fn erase(&self, offset: usize) -> Result<(), KernelError> {
+        flash::check_range(self, offset, self.erase_size(), self.erase_size())?;
+        self.lock.lock();
+        // Blok kilidi açılır (açılışta kilitli gelen yongalar için)
+        self.command(offset, CMD_LOCK_SETUP);
+        self.command(offset, CMD_CONFIRM);
+        let mut result = self.wait_ready(offset);
+        if result.is_ok() {
+            self.command(offset, CMD_BLOCK_ERASE);
+            self.command(offset, CMD_CONFIRM);
+            result = self.wait_ready(offset);
+        }
+        self.lock.unlock();
+        if result.is_ok() {
+            ERASES.inc();
+        }
+        result
+    }
+}
+
+// -----------------------------------------------------------------------------
+// AYGIT AĞACI KEŞFİ
+// -----------------------------------------------------------------------------
+
+static INSTANCES: [CfiFlash; 2] = [CfiFlash::empty("flash0"), CfiFlash::empty("flash1")];
+
+fn probe() -> Result<(), KernelError> {
+    let Some(fdt) = crate::fdt::get() else { return Ok(()) };
+
+    for node in fdt.find_compatible("cfi-flash").filter(|n| n.is_enabled()) {
+        let width = node.property_u32("bank-width").unwrap_or(4) as usize;
+        if !matches!(width, 1 | 2 | 4) {
+            continue;
+        }
+        // QEMU `virt` iki bankayı tek düğümde birden çok `reg` girdisiyle verir
+        for index in 0.. {
+            let Some((base, size)) = node.reg(index) else { break };
+            let Some(slot) = INSTANCES.iter().find(|i| !i.used.swap(true, Ordering::AcqRel)) else {
+                return Err(KernelError::OutOfMemoryStatic);
+            };
+            slot.base.store(base as usize, Ordering::Relaxed);
+            slot.width.store(width, Ordering::Relaxed);
+            match slot.probe(size as usize) {
+                Ok(()) => {
+                    flash::register(slot)?;
+                }
+                Err(_) => {
+                    serial_println!("[FLASH] {:#x}: CFI sorgusu başarısız", base);
+                    slot.used.store(false, Ordering::Release);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+crate::initcall!(drivers, "cfi-flash", probe);
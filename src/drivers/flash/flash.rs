@@ -0,0 +1,163 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/drivers/flash/flash.rs
+// Flash katmanı: NOR flash sürücülerinin ortak arayüzü ve aygıt kaydı.
+//
+// Blok aygıtlarından farkı: yazma yalnızca silinmiş (0xFF) alana yapılabilir
+// ve bitleri yalnızca 1'den 0'a çevirir; 1'e döndürmek için tüm silme bloğu
+// silinir. Konumlar bayt cinsindendir; programlama konumu ve uzunluğu
+// `write_size`'ın, silme konumu `erase_size`'ın katı olmalıdır. Üst
+// katmanlar (anahtar-değer deposu, güncelleme alanı) aşınmayı kendileri
+// dağıtır.
+
+use core::cell::UnsafeCell;
+use crate::platformgeneric::spinlock::Spinlock;
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// ARAYÜZ
+// -----------------------------------------------------------------------------
+
+/// Silinmiş flash baytının değeri.
+pub const ERASED: u8 = 0xFF;
+
+/// NOR flash sürücüsü.
+pub trait FlashDevice: Sync {
+    /// Aygıt adı (örn. "flash0").
+    fn name(&self) -> &'static str;
+
+    /// Toplam boyut (bayt).
+    fn size(&self) -> usize;
+
+    /// Silme bloğu boyutu (bayt).
+    fn erase_size(&self) -> usize;
+
+    /// En küçük programlama birimi (bayt).
+    fn write_size(&self) -> usize;
+
+    /// `offset`'ten `buf.len()` bayt okur.
+    fn read(&self, offset: usize, buf: &mut [u8]) -> Result<(), KernelError>;
+
+    /// Silinmiş alana `data`'yı yazar.
+    fn program(&self, offset: usize, data: &[u8]) -> Result<(), KernelError>;
+
+    /// `offset`'teki silme bloğunu siler.
+    fn erase(&self, offset: usize) -> Result<(), KernelError>;
+}
+
+/// Erişim aralığını aygıt boyutuna ve hizalama birimine göre doğrular.
+pub fn check_range(dev: &dyn FlashDevice, offset: usize, len: usize, align: usize) -> Result<(), KernelError> {
+    if align == 0 || offset % align != 0 || len % align != 0 {
+        return Err(KernelError::InvalidArgument);
+    }
+    match offset.checked_add(len) {
+        Some(end) if end <= dev.size() => Ok(()),
+        _ => Err(KernelError::InvalidArgument),
+    }
+}
+
+// -----------------------------------------------------------------------------
+// AYGIT KAYDI
+// -----------------------------------------------------------------------------
+
+/// En fazla flash aygıtı sayısı.
+pub const MAX_FLASH_DEVICES: usize = 4;
+
+struct DeviceTable {
+    lock: Spinlock,
+    devices: UnsafeCell<[Option<&'static dyn FlashDevice>; MAX_FLASH_DEVICES]>,
+}
+
+// Tabloya erişim `lock` ile korunur.
+unsafe impl Sync for DeviceTable {}
+
+static DEVICES: DeviceTable = DeviceTable {
+    lock: Spinlock::new(),
+    devices: UnsafeCell::new([None; MAX_FLASH_DEVICES]),
+};
+
+/// Bir flash aygıtını kaydeder.
+///
+/// # Dönüş Değeri
+/// Aygıt numarası; tablo doluysa `OutOfMemoryStatic`.
+pub fn register(dev: &'static dyn FlashDevice) -> Result<usize, KernelError> {
+    DEVICES.lock.lock();
+    let devices = unsafe { &mut *DEVICES.devices.get() };
+    let result = match devices.iter().position(|d| d.is_none()) {
+        Some(id) => {
+            devices[id] = Some(dev);
+            Ok(id)
+        }
+        None => Err(KernelError::OutOfMemoryStatic),
+    };
+    DEVICES.lock.unlock();
+
+    if result.is_ok() {
+        serial_println!(
+            "[FLASH] {}: {} KiB, silme bloğu {} KiB, yazma birimi {} B",
+            dev.name(),
+            dev.size() / 1024,
+            dev.erase_size() / 1024,
+            dev.write_size()
+        );
+    }
+    result
+}
+
+/// Numarası verilen aygıt.
+pub fn device(id: usize) -> Option<&'static dyn FlashDevice> {
+    DEVICES.lock.lock();
+    let dev = unsafe { (*DEVICES.devices.get()).get(id).copied().flatten() };
+    DEVICES.lock.unlock();
+    dev
+}
+
+/// Adı verilen aygıt.
+pub fn find(name: &str) -> Option<&'static dyn FlashDevice> {
+    (0..MAX_FLASH_DEVICES).filter_map(device).find(|d| d.name() == name)
+}
+
+// -----------------------------------------------------------------------------
+// KABUK VE PROCFS
+// -----------------------------------------------------------------------------
+
+fn flash_command(args: &[&str]) -> Result<(), KernelError> {
+    match (args.get(1).copied(), args.get(2), args.get(3)) {
+        (Some("read"), Some(name), offset) => {
+            let dev = find(name).ok_or(KernelError::NotFound)?;
+            let offset = offset.and_then(|s| s.parse().ok()).unwrap_or(0);
+            let mut buf = [0u8; 64];
+            dev.read(offset, &mut buf)?;
+            for (row, chunk) in buf.chunks(16).enumerate() {
+                crate::serial_print!("  {:08x}:", offset + row * 16);
+                chunk.iter().for_each(|b| crate::serial_print!(" {:02x}", b));
+                serial_println!();
+            }
+            Ok(())
+        }
+        (Some("erase"), Some(name), Some(offset)) => {
+            let dev = find(name).ok_or(KernelError::NotFound)?;
+            let offset = offset.parse().map_err(|_| KernelError::InvalidArgument)?;
+            dev.erase(offset)
+        }
+        (None, ..) => {
+            for dev in (0..MAX_FLASH_DEVICES).filter_map(device) {
+                serial_println!("  {} {} KiB, silme {} KiB", dev.name(), dev.size() / 1024, dev.erase_size() / 1024);
+            }
+            Ok(())
+        }
+        _ => Err(KernelError::InvalidArgument),
+    }
+}
+
+crate::shell_command!("flash", "Flash aygıtları (flash [read <aygıt> <konum> | erase <aygıt> <konum>])", flash_command);
+
+fn show_devices(out: &mut dyn core::fmt::Write) -> core::fmt::Result {
+    for dev in (0..MAX_FLASH_DEVICES).filter_map(device) {
+        writeln!(out, "{} {} {} {}", dev.name(), dev.size(), dev.erase_size(), dev.write_size())?;
+    }
+    Ok(())
+}
+
+crate::proc_entry!("drivers/flash", show_devices);
@@ -0,0 +1,259 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/drivers/i2c/designware.rs
+// Synopsys DesignWare APB I2C denetleyici sürücüsü (`snps,designware-i2c`).
+//
+// Denetleyici yoklamalı (PIO) ana kipte sürülür: her bayt için IC_DATA_CMD'ye
+// komut yazılır, okumalarda RX FIFO'dan karşılık gelen bayt beklenir. Bir
+// işlemdeki tüm mesajlar aynı hedef adrese gitmelidir (IC_TAR yalnızca
+// denetleyici kapalıyken değiştirilebilir).
+//
+// Aygıt ağacı: `reg` taban adres, `clock-frequency` SCL hızı (varsayılan
+// 100 kHz). Giriş saati `clocks` ile çözülmediği için `IC_CLK_HZ` kabul edilir.
+
+use core::ptr::{read_volatile, write_volatile};
+use core::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
+use crate::drivers::i2c::i2c::{self, I2cController, I2cMsg, I2cOp};
+use crate::platformgeneric::KernelError;
+
+// -----------------------------------------------------------------------------
+// YAZMAÇLAR
+// -----------------------------------------------------------------------------
+
+const IC_CON: usize = 0x00;
+const IC_TAR: usize = 0x04;
+const IC_DATA_CMD: usize = 0x10;
+const IC_SS_SCL_HCNT: usize = 0x14;
+const IC_SS_SCL_LCNT: usize = 0x18;
+const IC_FS_SCL_HCNT: usize = 0x1C;
+const IC_FS_SCL_LCNT: usize = 0x20;
+const IC_INTR_MASK: usize = 0x30;
+const IC_RAW_INTR_STAT: usize = 0x34;
+const IC_RX_TL: usize = 0x38;
+const IC_TX_TL: usize = 0x3C;
+const IC_CLR_INTR: usize = 0x40;
+const IC_CLR_TX_ABRT: usize = 0x54;
+const IC_ENABLE: usize = 0x6C;
+const IC_STATUS: usize = 0x70;
+const IC_TX_ABRT_SOURCE: usize = 0x80;
+const IC_ENABLE_STATUS: usize = 0x9C;
+
+// IC_CON
+const CON_MASTER: u32 = 1 << 0;
+const CON_SPEED_STD: u32 = 1 << 1;
+const CON_SPEED_FAST: u32 = 2 << 1;
+const CON_RESTART_EN: u32 = 1 << 5;
+const CON_SLAVE_DISABLE: u32 = 1 << 6;
+
+// IC_TAR
+const TAR_10BIT: u32 = 1 << 12;
+
+// IC_DATA_CMD
+const CMD_READ: u32 = 1 << 8;
+const CMD_STOP: u32 = 1 << 9;
+const CMD_RESTART: u32 = 1 << 10;
+
+// IC_STATUS
+const STATUS_TFNF: u32 = 1 << 1;
+const STATUS_TFE: u32 = 1 << 2;
+const STATUS_RFNE: u32 = 1 << 3;
+const STATUS_MST_ACTIVITY: u32 = 1 << 5;
+
+// IC_RAW_INTR_STAT
+const INTR_TX_ABRT: u32 = 1 << 6;
+
+// IC_TX_ABRT_SOURCE
+const ABRT_ADDR_NOACK: u32 = 0b0111; // 7B_ADDR, 10ADDR1, 10ADDR2
+const ABRT_TXDATA_NOACK: u32 = 1 << 3;
+const ABRT_LOST: u32 = 1 << 12;
+
+/// Varsayılan denetleyici giriş saati.
+const IC_CLK_HZ: u32 = 100_000_000;
+
+/// Yoklama döngüsü üst sınırı.
+const TIMEOUT_SPINS: u32 = 1_000_000;
+
+// -----------------------------------------------------------------------------
+// DENETLEYİCİ
+// -----------------------------------------------------------------------------
+
+/// Tek bir DesignWare I2C örneği.
+pub struct DwI2c {
+    base: AtomicUsize,
+    speed_hz: AtomicU32,
+    used: AtomicBool,
+}
+
+impl DwI2c {
+    const fn empty() -> Self {
+        DwI2c { base: AtomicUsize::new(0), speed_hz: AtomicU32::new(0), used: AtomicBool::new(false) }
+    }
+
+    #[inline(always)]
+    fn read(&self, offset: usize) -> u32 {
+        unsafe { read_volatile((self.base.load(Ordering::Relaxed) + offset) as *const u32) }
+    }
+
+    #[inline(always)]
+    fn write(&self, offset: usize, value: u32) {
+        unsafe { write_volatile((self.base.load(Ordering::Relaxed) + offset) as *mut u32, value) }
+    }
+
+    fn set_enabled(&self, enable: bool) -> Result<(), KernelError> {
+        self.write(IC_ENABLE, enable as u32);
+        for _ in 0..TIMEOUT_SPINS {
+            if (self.read(IC_ENABLE_STATUS) & 1 != 0) == enable {
+                return Ok(());
+            }
+        }
+        Err(KernelError::GenericFailure)
+    }
+
+    /// Ana kip, hız ve SCL sayaçlarını ayarlar.
+    fn init_hw(&self) -> Result<(), KernelError> {
+        self.set_enabled(false)?;
+
+        let speed = self.speed_hz.load(Ordering::Relaxed);
+        let half_period = IC_CLK_HZ / speed / 2;
+        let (con_speed, hcnt_reg, lcnt_reg) = if speed > 100_000 {
+            (CON_SPEED_FAST, IC_FS_SCL_HCNT, IC_FS_SCL_LCNT)
+        } else {
+            (CON_SPEED_STD, IC_SS_SCL_HCNT, IC_SS_SCL_LCNT)
+        };
+        self.write(hcnt_reg, half_period.saturating_sub(8).max(6));
+        self.write(lcnt_reg, half_period.saturating_sub(1).max(8));
+
+        self.write(IC_CON, CON_MASTER | con_speed | CON_RESTART_EN | CON_SLAVE_DISABLE);
+        self.write(IC_TX_TL, 0);
+        self.write(IC_RX_TL, 0);
+        self.write(IC_INTR_MASK, 0); // Yoklamalı kip
+        Ok(())
+    }
+
+    /// İptal kaynağını temizler ve çekirdek hatasına çevirir.
+    fn abort_error(&self) -> KernelError {
+        let source = self.read(IC_TX_ABRT_SOURCE);
+        self.read(IC_CLR_TX_ABRT);
+        if source & ABRT_ADDR_NOACK != 0 {
+            KernelError::NotFound
+        } else if source & ABRT_LOST != 0 {
+            KernelError::ResourceBusy
+        } else {
+            // Veri NACK (ABRT_TXDATA_NOACK) ve diğer iptaller
+            KernelError::GenericFailure
+        }
+    }
+
+    /// `IC_STATUS`'ta `mask` bitinin `set` olmasını bekler; iptal görülürse hata döner.
+    fn wait_status(&self, mask: u32, set: bool) -> Result<(), KernelError> {
+        for _ in 0..TIMEOUT_SPINS {
+            if self.read(IC_RAW_INTR_STAT) & INTR_TX_ABRT != 0 {
+                return Err(self.abort_error());
+            }
+            if (self.read(IC_STATUS) & mask != 0) == set {
+                return Ok(());
+            }
+        }
+        Err(KernelError::GenericFailure)
+    }
+
+    fn run(&self, msgs: &mut [I2cMsg]) -> Result<(), KernelError> {
+        let count = msgs.len();
+        for (index, msg) in msgs.iter_mut().enumerate() {
+            let last_msg = index + 1 == count;
+            let len = match &msg.op {
+                I2cOp::Write(data) => data.len(),
+                I2cOp::Read(buf) => buf.len(),
+            };
+
+            for i in 0..len {
+                let mut cmd = 0;
+                if i == 0 && index > 0 {
+                    cmd |= CMD_RESTART;
+                }
+                if last_msg && i + 1 == len {
+                    cmd |= CMD_STOP;
+                }
+
+                self.wait_status(STATUS_TFNF, true)?;
+                match &mut msg.op {
+                    I2cOp::Write(data) => self.write(IC_DATA_CMD, cmd | data[i] as u32),
+                    I2cOp::Read(buf) => {
+                        self.write(IC_DATA_CMD, cmd | CMD_READ);
+                        self.wait_status(STATUS_RFNE, true)?;
+                        buf[i] = self.read(IC_DATA_CMD) as u8;
+                    }
+                }
+            }
+        }
+
+        // STOP gönderilip veri yolu boşalana kadar bekle
+        self.wait_status(STATUS_TFE, true)?;
+        self.wait_status(STATUS_MST_ACTIVITY, false)
+    }
+}
+
+impl I2cController for DwI2c {
+    fn name(&self) -> &'static str {
+        "designware-i2c"
+    }
+
+    fn bus_speed_hz(&self) -> u32 {
+        self.speed_hz.load(Ordering::Relaxed)
+    }
+
+    fn transfer(&self, msgs: &mut [I2cMsg], _dma: bool) -> Result<(), KernelError> {
+        let Some(first) = msgs.first() else { return Ok(()) };
+        let (addr, ten_bit) = (first.addr, first.ten_bit);
+
+        // Sıfır uzunluklu mesaj DATA_CMD ile ifade edilemez; tüm mesajlar aynı hedefe gider
+        let valid = msgs.iter().all(|m| {
+            m.addr == addr
+                && m.ten_bit == ten_bit
+                && match &m.op {
+                    I2cOp::Write(data) => !data.is_empty(),
+                    I2cOp::Read(buf) => !buf.is_empty(),
+                }
+        });
+        if !valid {
+            return Err(KernelError::InvalidArgument);
+        }
+
+        self.set_enabled(false)?;
+        self.write(IC_TAR, addr as u32 | if ten_bit { TAR_10BIT } else { 0 });
+        self.read(IC_CLR_INTR);
+        self.set_enabled(true)?;
+
+        self.run(msgs)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// AYGIT AĞACI KEŞFİ
+// -----------------------------------------------------------------------------
+
+/// En fazla örnek sayısı.
+const MAX_INSTANCES: usize = 4;
+
+#[allow(clippy::declare_interior_mutable_const)]
+const EMPTY: DwI2c = DwI2c::empty();
+static INSTANCES: [DwI2c; MAX_INSTANCES] = [EMPTY; MAX_INSTANCES];
+
+fn probe() -> Result<(), KernelError> {
+    let Some(fdt) = crate::fdt::get() else { return Ok(()) };
+
+    for node in fdt.find_compatible("snps,designware-i2c").filter(|n| n.is_enabled()) {
+        let Some((base, _)) = node.reg(0) else { continue };
+        let Some(slot) = INSTANCES.iter().find(|i| !i.used.swap(true, Ordering::AcqRel)) else {
+            return Err(KernelError::OutOfMemoryStatic);
+        };
+
+        slot.base.store(base as usize, Ordering::Relaxed);
+        slot.speed_hz.store(node.property_u32("clock-frequency").filter(|&hz| hz > 0).unwrap_or(100_000), Ordering::Relaxed);
+        slot.init_hw()?;
+        i2c::register_bus(slot)?;
+    }
+    Ok(())
+}
+
+crate::initcall!(drivers, "designware-i2c", probe);
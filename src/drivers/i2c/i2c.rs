@@ -0,0 +1,212 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/drivers/i2c/i2c.rs
+// I2C veri yolu çatısı: denetleyici arayüzü, veri yolu kaydı ve aygıt adreslemesi.
+//
+// Denetleyici sürücüleri (örn. `designware.rs`) aygıt ağacından keşfedilen her
+// örneği `register_bus` ile kaydeder ve bir veri yolu numarası alır. Sensör ve
+// EEPROM sürücüleri `I2cDevice { bus, addr }` üzerinden çalışır; bir veri
+// yolundaki aktarımlar veri yolu kilidiyle sıralanır.
+//
+// Hata eşlemesi: adres NACK -> `NotFound`, tahkim kaybı -> `ResourceBusy`,
+// veri NACK / zaman aşımı -> `GenericFailure`.
+
+use core::cell::UnsafeCell;
+use crate::drivers::dma::DmaMode;
+use crate::platformgeneric::spinlock::Spinlock;
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// MESAJLAR VE DENETLEYİCİ ARAYÜZÜ
+// -----------------------------------------------------------------------------
+
+/// Tek bir mesajın yönü ve verisi.
+pub enum I2cOp<'a> {
+    Write(&'a [u8]),
+    Read(&'a mut [u8]),
+}
+
+/// Bir I2C mesajı. Tek `transfer` içindeki mesajlar arasında RESTART, sonda STOP üretilir.
+pub struct I2cMsg<'a> {
+    /// 7 bit (veya `ten_bit` ise 10 bit) hedef adres.
+    pub addr: u16,
+    pub ten_bit: bool,
+    pub op: I2cOp<'a>,
+}
+
+/// I2C denetleyici sürücüsü.
+pub trait I2cController: Sync {
+    /// Denetleyici adı (günlük ve procfs için).
+    fn name(&self) -> &'static str;
+
+    /// Yapılandırılmış SCL frekansı.
+    fn bus_speed_hz(&self) -> u32;
+
+    /// DMA ile aktarım yapabiliyor mu?
+    fn supports_dma(&self) -> bool {
+        false
+    }
+
+    /// Mesajları tek bir işlem olarak yürütür.
+    ///
+    /// # Parametreler
+    /// * `dma`: `true` ise DMA kullanılır (yalnızca `supports_dma` doğruysa istenir).
+    fn transfer(&self, msgs: &mut [I2cMsg], dma: bool) -> Result<(), KernelError>;
+}
+
+// -----------------------------------------------------------------------------
+// VERİ YOLU KAYDI
+// -----------------------------------------------------------------------------
+
+/// En fazla I2C veri yolu sayısı.
+pub const MAX_I2C_BUSES: usize = 4;
+
+struct BusTable {
+    lock: Spinlock,
+    buses: UnsafeCell<[Option<&'static dyn I2cController>; MAX_I2C_BUSES]>,
+}
+
+// Tabloya erişim `lock` ile korunur.
+unsafe impl Sync for BusTable {}
+
+static BUSES: BusTable = BusTable {
+    lock: Spinlock::new(),
+    buses: UnsafeCell::new([None; MAX_I2C_BUSES]),
+};
+
+#[allow(clippy::declare_interior_mutable_const)]
+const UNLOCKED: Spinlock = Spinlock::new();
+/// Her veri yolu için aktarım kilidi.
+static BUS_LOCKS: [Spinlock; MAX_I2C_BUSES] = [UNLOCKED; MAX_I2C_BUSES];
+
+/// Bir denetleyiciyi kaydeder.
+///
+/// # Dönüş Değeri
+/// Veri yolu numarası; tablo doluysa `OutOfMemoryStatic`.
+pub fn register_bus(controller: &'static dyn I2cController) -> Result<usize, KernelError> {
+    BUSES.lock.lock();
+    let buses = unsafe { &mut *BUSES.buses.get() };
+    let result = match buses.iter().position(|b| b.is_none()) {
+        Some(id) => {
+            buses[id] = Some(controller);
+            Ok(id)
+        }
+        None => Err(KernelError::OutOfMemoryStatic),
+    };
+    BUSES.lock.unlock();
+
+    if let Ok(id) = result {
+        serial_println!("[I2C] i2c-{}: {} ({} Hz)", id, controller.name(), controller.bus_speed_hz());
+    }
+    result
+}
+
+/// Veri yolu numarasına karşılık gelen denetleyici.
+pub fn bus(id: usize) -> Option<&'static dyn I2cController> {
+    BUSES.lock.lock();
+    let controller = unsafe { (*BUSES.buses.get()).get(id).copied().flatten() };
+    BUSES.lock.unlock();
+    controller
+}
+
+/// Veri yolunda bir işlem yürütür.
+pub fn transfer(bus_id: usize, msgs: &mut [I2cMsg], mode: DmaMode) -> Result<(), KernelError> {
+    let controller = bus(bus_id).ok_or(KernelError::NotFound)?;
+    let dma = mode.resolve(controller.supports_dma())?;
+
+    BUS_LOCKS[bus_id].lock();
+    let result = controller.transfer(msgs, dma);
+    BUS_LOCKS[bus_id].unlock();
+    result
+}
+
+// -----------------------------------------------------------------------------
+// AYGIT ADRESLEMESİ
+// -----------------------------------------------------------------------------
+
+/// Bir veri yolundaki tek bir I2C aygıtı.
+#[derive(Debug, Clone, Copy)]
+pub struct I2cDevice {
+    pub bus: usize,
+    pub addr: u16,
+    pub ten_bit: bool,
+    pub dma: DmaMode,
+}
+
+impl I2cDevice {
+    pub const fn new(bus: usize, addr: u16) -> Self {
+        I2cDevice { bus, addr, ten_bit: false, dma: DmaMode::Never }
+    }
+
+    fn msg<'a>(&self, op: I2cOp<'a>) -> I2cMsg<'a> {
+        I2cMsg { addr: self.addr, ten_bit: self.ten_bit, op }
+    }
+
+    pub fn write(&self, data: &[u8]) -> Result<(), KernelError> {
+        transfer(self.bus, &mut [self.msg(I2cOp::Write(data))], self.dma)
+    }
+
+    pub fn read(&self, buf: &mut [u8]) -> Result<(), KernelError> {
+        transfer(self.bus, &mut [self.msg(I2cOp::Read(buf))], self.dma)
+    }
+
+    /// Yaz, RESTART, oku (register/EEPROM adresli okuma).
+    pub fn write_read(&self, data: &[u8], buf: &mut [u8]) -> Result<(), KernelError> {
+        transfer(self.bus, &mut [self.msg(I2cOp::Write(data)), self.msg(I2cOp::Read(buf))], self.dma)
+    }
+
+    /// 8 bit adresli bir yazmacı okur.
+    pub fn read_reg(&self, reg: u8) -> Result<u8, KernelError> {
+        let mut value = [0u8];
+        self.write_read(&[reg], &mut value)?;
+        Ok(value[0])
+    }
+
+    /// 8 bit adresli bir yazmaca yazar.
+    pub fn write_reg(&self, reg: u8, value: u8) -> Result<(), KernelError> {
+        self.write(&[reg, value])
+    }
+}
+
+// -----------------------------------------------------------------------------
+// KABUK VE PROCFS
+// -----------------------------------------------------------------------------
+
+fn i2c_command(args: &[&str]) -> Result<(), KernelError> {
+    match args.get(1).copied() {
+        Some("scan") => {
+            let bus_id = args.get(2).and_then(|s| s.parse().ok()).unwrap_or(0);
+            crate::serial_print!("i2c-{}:", bus_id);
+            for addr in 0x08..0x78u16 {
+                let mut probe = [0u8];
+                if I2cDevice::new(bus_id, addr).read(&mut probe).is_ok() {
+                    crate::serial_print!(" {:#04x}", addr);
+                }
+            }
+            serial_println!();
+            Ok(())
+        }
+        _ => {
+            for id in 0..MAX_I2C_BUSES {
+                if let Some(c) = bus(id) {
+                    serial_println!("  i2c-{} {:<20} {} Hz", id, c.name(), c.bus_speed_hz());
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+crate::shell_command!("i2c", "I2C veri yolları (i2c [scan <bus>])", i2c_command);
+
+fn show_buses(out: &mut dyn core::fmt::Write) -> core::fmt::Result {
+    for id in 0..MAX_I2C_BUSES {
+        if let Some(c) = bus(id) {
+            writeln!(out, "i2c-{} {} {} dma={}", id, c.name(), c.bus_speed_hz(), c.supports_dma())?;
+        }
+    }
+    Ok(())
+}
+
+crate::proc_entry!("drivers/i2c", show_buses);
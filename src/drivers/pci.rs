@@ -0,0 +1,378 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/drivers/pci.rs
+// PCI yapılandırma alanı erişimi ve aygıt taraması.
+//
+// amd64'te yapılandırma alanına 0xCF8/0xCFC G/Ç portlarından (mekanizma #1),
+// diğer mimarilerde aygıt ağacındaki `pci-host-ecam-generic` düğümünün
+// ECAM penceresinden erişilir. Sürücüler aygıtları sınıf koduna veya
+// üretici/aygıt kimliğine göre bulur, BAR adresini okur ve `enable` ile
+// bellek erişimini ve veri yolu yöneticiliğini (bus master) açar.
+//
+// Yetenek listesi (`capabilities`) dolaşılabilir; MSI-X yeteneği `msix` ile
+// tablo girdileri programlanarak kullanılır. İleti adresi ve verisi kesme
+// denetleyicisine özgüdür ve sürücüyü çağıran tarafından sağlanır.
+//
+// Kabuk komutu: `lspci`
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+use crate::arch::io;
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// YAPILANDIRMA ALANI
+// -----------------------------------------------------------------------------
+
+// Standart başlık
+const VENDOR_ID: u16 = 0x00;
+const DEVICE_ID: u16 = 0x02;
+const COMMAND: u16 = 0x04;
+const STATUS: u16 = 0x06;
+const CLASS_REVISION: u16 = 0x08;
+const HEADER_TYPE: u16 = 0x0E;
+const BAR0: u16 = 0x10;
+const SUBSYSTEM_ID: u16 = 0x2E;
+const CAPABILITIES_PTR: u16 = 0x34;
+const INTERRUPT_LINE: u16 = 0x3C;
+
+// STATUS
+const STATUS_CAP_LIST: u16 = 1 << 4;
+
+/// Yetenek kimlikleri.
+pub const CAP_VENDOR: u8 = 0x09;
+pub const CAP_MSIX: u8 = 0x11;
+
+// MSI-X ileti denetimi (yetenek kelimesinin üst 16 biti)
+const MSIX_ENABLE: u32 = 1 << 31;
+const MSIX_FUNCTION_MASK: u32 = 1 << 30;
+const MSIX_ENTRY_SIZE: usize = 16;
+const MSIX_VECTOR_MASKED: u32 = 1 << 0;
+
+// COMMAND
+const CMD_MEMORY: u32 = 1 << 1;
+const CMD_BUS_MASTER: u32 = 1 << 2;
+const CMD_INTX_DISABLE: u32 = 1 << 10;
+
+/// Aygıt yok iken okunan üretici kimliği.
+const NO_DEVICE: u16 = 0xFFFF;
+
+/// Veri yolu / aygıt / fonksiyon adresi.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bdf {
+    pub bus: u8,
+    pub dev: u8,
+    pub func: u8,
+}
+
+impl core::fmt::Display for Bdf {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{:02x}:{:02x}.{}", self.bus, self.dev, self.func)
+    }
+}
+
+/// ECAM penceresinin sanal adresi (0: henüz çözülmedi) ve kapsadığı veri yolu sayısı.
+static ECAM_BASE: AtomicUsize = AtomicUsize::new(0);
+static BUS_COUNT: AtomicUsize = AtomicUsize::new(256);
+
+/// ECAM penceresini aygıt ağacından bir kez çözer.
+fn ecam_base() -> Option<usize> {
+    let base = ECAM_BASE.load(Ordering::Acquire);
+    if base != 0 {
+        return Some(base);
+    }
+    let node = crate::fdt::get()?.find_compatible("pci-host-ecam-generic").find(|n| n.is_enabled())?;
+    let (addr, size) = node.reg(0)?;
+    // Her veri yolu 1 MiB (32 aygıt x 8 fonksiyon x 4 KiB)
+    BUS_COUNT.store(((size >> 20) as usize).clamp(1, 256), Ordering::Relaxed);
+    ECAM_BASE.store(addr as usize, Ordering::Release);
+    Some(addr as usize)
+}
+
+/// Hizalı 32 bitlik yapılandırma kelimesini okur.
+pub fn read32(bdf: Bdf, offset: u16) -> u32 {
+    if io::PCI_CONFIG_PORTS {
+        return io::pci_config_read32(config_address(bdf, offset));
+    }
+    match ecam_base() {
+        Some(base) => unsafe { core::ptr::read_volatile((base + ecam_offset(bdf, offset)) as *const u32) },
+        None => u32::MAX,
+    }
+}
+
+/// Hizalı 32 bitlik yapılandırma kelimesini yazar.
+pub fn write32(bdf: Bdf, offset: u16, value: u32) {
+    if io::PCI_CONFIG_PORTS {
+        io::pci_config_write32(config_address(bdf, offset), value);
+    } else if let Some(base) = ecam_base() {
+        unsafe { core::ptr::write_volatile((base + ecam_offset(bdf, offset)) as *mut u32, value) }
+    }
+}
+
+pub fn read16(bdf: Bdf, offset: u16) -> u16 {
+    (read32(bdf, offset & !3) >> ((offset & 2) * 8)) as u16
+}
+
+pub fn read8(bdf: Bdf, offset: u16) -> u8 {
+    (read32(bdf, offset & !3) >> ((offset & 3) * 8)) as u8
+}
+
+/// Port mekanizmasının (0xCF8) adres kelimesi.
+fn config_address(bdf: Bdf, offset: u16) -> u32 {
+    0x8000_0000
+        | (bdf.bus as u32) << 16
+        | (bdf.dev as u32) << 11
+        | (bdf.func as u32) << 8
+        | (offset as u32 & 0xFC)
+}
+
+fn ecam_offset(bdf: Bdf, offset: u16) -> usize {
+    (bdf.bus as usize) << 20 | (bdf.dev as usize) << 15 | (bdf.func as usize) << 12 | (offset as usize & 0xFFC)
+}
+
+// -----------------------------------------------------------------------------
+// AYGITLAR
+// -----------------------------------------------------------------------------
+
+/// Taramada bulunan bir PCI fonksiyonu.
+#[derive(Debug, Clone, Copy)]
+pub struct PciDevice {
+    pub bdf: Bdf,
+    pub vendor: u16,
+    pub device: u16,
+    pub class: u8,
+    pub subclass: u8,
+    pub prog_if: u8,
+    /// Ürün yazılımının atadığı eski tip (INTx) kesme hattı.
+    pub irq_line: u8,
+}
+
+impl PciDevice {
+    fn read(bdf: Bdf) -> Option<PciDevice> {
+        let vendor = read16(bdf, VENDOR_ID);
+        if vendor == NO_DEVICE {
+            return None;
+        }
+        let class = read32(bdf, CLASS_REVISION);
+        Some(PciDevice {
+            bdf,
+            vendor,
+            device: read16(bdf, DEVICE_ID),
+            class: (class >> 24) as u8,
+            subclass: (class >> 16) as u8,
+            prog_if: (class >> 8) as u8,
+            irq_line: read8(bdf, INTERRUPT_LINE),
+        })
+    }
+
+    /// `index`. bellek BAR'ının fiziksel adresi (64 bit BAR'lar iki yuva kaplar).
+    /// G/Ç BAR'ları ve atanmamış BAR'lar için `None`.
+    pub fn bar(&self, index: usize) -> Option<u64> {
+        if index >= 6 {
+            return None;
+        }
+        let offset = BAR0 + index as u16 * 4;
+        let low = read32(self.bdf, offset);
+        if low & 1 != 0 {
+            return None;
+        }
+        let addr = match (low >> 1) & 3 {
+            2 if index < 5 => (read32(self.bdf, offset + 4) as u64) << 32 | (low & !0xF) as u64,
+            _ => (low & !0xF) as u64,
+        };
+        (addr != 0).then_some(addr)
+    }
+
+    /// Bellek erişimini ve veri yolu yöneticiliğini açar.
+    pub fn enable(&self) {
+        let cmd = read32(self.bdf, COMMAND) & 0xFFFF;
+        write32(self.bdf, COMMAND, cmd | CMD_MEMORY | CMD_BUS_MASTER);
+    }
+
+    /// Alt sistem aygıt kimliği (örn. geçiş dönemi virtio aygıtlarında aygıt türü).
+    pub fn subsystem_id(&self) -> u16 {
+        read16(self.bdf, SUBSYSTEM_ID)
+    }
+
+    /// Yetenek listesi: (kimlik, yapılandırma alanı ofseti) çiftleri.
+    pub fn capabilities(&self) -> CapabilityIter {
+        let next = if read16(self.bdf, STATUS) & STATUS_CAP_LIST != 0 {
+            read8(self.bdf, CAPABILITIES_PTR) & !3
+        } else {
+            0
+        };
+        // Bozuk (döngülü) listelere karşı en fazla 48 yetenek dolaşılır
+        CapabilityIter { bdf: self.bdf, next, remaining: 48 }
+    }
+
+    /// Kimliği verilen ilk yeteneğin ofseti.
+    pub fn find_capability(&self, id: u8) -> Option<u16> {
+        self.capabilities().find(|&(cap, _)| cap == id).map(|(_, offset)| offset)
+    }
+
+    /// MSI-X yeteneği (yoksa veya tablo BAR'ı atanmamışsa `None`).
+    pub fn msix(&self) -> Option<Msix> {
+        let cap = self.find_capability(CAP_MSIX)?;
+        let control = read32(self.bdf, cap);
+        let table = read32(self.bdf, cap + 4);
+        let base = self.bar((table & 7) as usize)?;
+        Some(Msix {
+            bdf: self.bdf,
+            cap,
+            table: base as usize + (table & !7) as usize,
+            size: ((control >> 16) & 0x7FF) as u16 + 1,
+        })
+    }
+
+    /// Eski tip INTx kesmesini açar veya kapatır.
+    pub fn set_intx(&self, enable: bool) {
+        let cmd = read32(self.bdf, COMMAND) & 0xFFFF;
+        let cmd = if enable { cmd & !CMD_INTX_DISABLE } else { cmd | CMD_INTX_DISABLE };
+        write32(self.bdf, COMMAND, cmd);
+    }
+}
+
+/// Yetenek listesi yineleyicisi.
+pub struct CapabilityIter {
+    bdf: Bdf,
+    next: u8,
+    remaining: u8,
+}
+
+impl Iterator for CapabilityIter {
+    type Item = (u8, u16);
+
+    fn next(&mut self) -> Option<(u8, u16)> {
+        if self.next < 0x40 || self.remaining == 0 {
+            return None;
+        }
+        let offset = self.next as u16;
+        let header = read16(self.bdf, offset);
+        self.next = (header >> 8) as u8 & !3;
+        self.remaining -= 1;
+        Some((header as u8, offset))
+    }
+}
+
+/// Bir fonksiyonun MSI-X yeteneği. Tablo, BAR'ın birebir eşlendiği
+/// varsayımıyla doğrudan adreslenir.
+#[derive(Debug, Clone, Copy)]
+pub struct Msix {
+    bdf: Bdf,
+    cap: u16,
+    /// Tablonun adresi.
+    table: usize,
+    /// Tablodaki vektör sayısı.
+    pub size: u16,
+}
+
+impl Msix {
+    /// `index`. girdinin ileti adresini ve verisini yazar; girdi maskeli kalır.
+    pub fn set_entry(&self, index: u16, addr: u64, data: u32) -> Result<(), KernelError> {
+        if index >= self.size {
+            return Err(KernelError::InvalidArgument);
+        }
+        let entry = self.table + index as usize * MSIX_ENTRY_SIZE;
+        // SAFETY: Girdi, aygıtın MSI-X tablosunun sınırları içindedir.
+        unsafe {
+            core::ptr::write_volatile((entry + 12) as *mut u32, u32::to_le(MSIX_VECTOR_MASKED));
+            core::ptr::write_volatile(entry as *mut u32, u32::to_le(addr as u32));
+            core::ptr::write_volatile((entry + 4) as *mut u32, u32::to_le((addr >> 32) as u32));
+            core::ptr::write_volatile((entry + 8) as *mut u32, u32::to_le(data));
+        }
+        Ok(())
+    }
+
+    /// `index`. girdiyi maskeler veya açar.
+    pub fn set_masked(&self, index: u16, masked: bool) -> Result<(), KernelError> {
+        if index >= self.size {
+            return Err(KernelError::InvalidArgument);
+        }
+        let control = self.table + index as usize * MSIX_ENTRY_SIZE + 12;
+        // SAFETY: Girdi, aygıtın MSI-X tablosunun sınırları içindedir.
+        unsafe { core::ptr::write_volatile(control as *mut u32, u32::to_le(if masked { MSIX_VECTOR_MASKED } else { 0 })) };
+        Ok(())
+    }
+
+    /// MSI-X'i açar veya kapatır. Açıkken aygıt INTx kullanmaz.
+    pub fn enable(&self, enable: bool) {
+        let control = read32(self.bdf, self.cap) & !MSIX_FUNCTION_MASK;
+        write32(self.bdf, self.cap, if enable { control | MSIX_ENABLE } else { control & !MSIX_ENABLE });
+    }
+}
+
+/// Tüm veri yollarını kaba kuvvetle dolaşan yineleyici.
+pub struct DeviceIter {
+    bus: usize,
+    dev: u8,
+    func: u8,
+}
+
+impl Iterator for DeviceIter {
+    type Item = PciDevice;
+
+    fn next(&mut self) -> Option<PciDevice> {
+        while self.bus < BUS_COUNT.load(Ordering::Relaxed) {
+            let bdf = Bdf { bus: self.bus as u8, dev: self.dev, func: self.func };
+            let found = PciDevice::read(bdf);
+
+            // Çok fonksiyonlu olmayan aygıtlarda 1-7 atlanır
+            let multi = self.func == 0 && found.is_some() && read8(bdf, HEADER_TYPE) & 0x80 != 0;
+            if (self.func == 0 && !multi) || self.func == 7 {
+                self.func = 0;
+                self.dev += 1;
+                if self.dev == 32 {
+                    self.dev = 0;
+                    self.bus += 1;
+                }
+            } else {
+                self.func += 1;
+            }
+
+            if found.is_some() {
+                return found;
+            }
+        }
+        None
+    }
+}
+
+/// Sistemdeki tüm PCI fonksiyonları.
+pub fn devices() -> DeviceIter {
+    if !io::PCI_CONFIG_PORTS && ecam_base().is_none() {
+        return DeviceIter { bus: usize::MAX, dev: 0, func: 0 };
+    }
+    DeviceIter { bus: 0, dev: 0, func: 0 }
+}
+
+/// Sınıf / alt sınıf / programlama arayüzü eşleşen fonksiyonlar.
+pub fn find_class(class: u8, subclass: u8, prog_if: u8) -> impl Iterator<Item = PciDevice> {
+    devices().filter(move |d| d.class == class && d.subclass == subclass && d.prog_if == prog_if)
+}
+
+/// Üretici ve aygıt kimliği eşleşen fonksiyonlar.
+pub fn find_id(vendor: u16, device: u16) -> impl Iterator<Item = PciDevice> {
+    devices().filter(move |d| d.vendor == vendor && d.device == device)
+}
+
+// -----------------------------------------------------------------------------
+// KABUK
+// -----------------------------------------------------------------------------
+
+fn lspci_command(_args: &[&str]) -> Result<(), KernelError> {
+    for d in devices() {
+        serial_println!(
+            "  {} {:04x}:{:04x} sınıf {:02x}.{:02x}.{:02x} IRQ {}",
+            d.bdf,
+            d.vendor,
+            d.device,
+            d.class,
+            d.subclass,
+            d.prog_if,
+            d.irq_line
+        );
+    }
+    Ok(())
+}
+
+crate::shell_command!("lspci", "PCI aygıtlarını listeler", lspci_command);
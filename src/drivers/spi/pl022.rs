@@ -0,0 +1,183 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/drivers/spi/pl022.rs
+// ARM PrimeCell PL022 senkron seri port (SSP) sürücüsü, SPI ana kipi (`arm,pl022`).
+//
+// Her aktarım öncesinde aygıtın kipi ve hızı SSPCR0/SSPCPSR'ye yazılır;
+// baytlar yoklamalı olarak, her gönderilen kelimeye karşılık alınan kelime
+// okunarak aktarılır (RX FIFO taşmaz). PL022 tek bir SSPFSSOUT hattı sürer;
+// bu yüzden yalnızca CS 0 desteklenir. PL080 DMA eşlemesi henüz yoktur.
+//
+// Aygıt ağacı: `reg` taban adres. Giriş saati `clocks` ile çözülmediği için
+// `SSPCLK_HZ` kabul edilir.
+
+use core::ptr::{read_volatile, write_volatile};
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use crate::drivers::spi::spi::{self, SpiConfig, SpiController, SpiTransfer};
+use crate::platformgeneric::KernelError;
+
+// -----------------------------------------------------------------------------
+// YAZMAÇLAR
+// -----------------------------------------------------------------------------
+
+const SSPCR0: usize = 0x00;
+const SSPCR1: usize = 0x04;
+const SSPDR: usize = 0x08;
+const SSPSR: usize = 0x0C;
+const SSPCPSR: usize = 0x10;
+const SSPIMSC: usize = 0x14;
+const SSPDMACR: usize = 0x24;
+
+// SSPCR0
+const CR0_SPO: u32 = 1 << 6;
+const CR0_SPH: u32 = 1 << 7;
+
+// SSPCR1
+const CR1_SSE: u32 = 1 << 1;
+
+// SSPSR
+const SR_TNF: u32 = 1 << 1;
+const SR_RNE: u32 = 1 << 2;
+const SR_BSY: u32 = 1 << 4;
+
+/// Varsayılan SSP giriş saati.
+const SSPCLK_HZ: u32 = 100_000_000;
+
+/// Yoklama döngüsü üst sınırı.
+const TIMEOUT_SPINS: u32 = 1_000_000;
+
+// -----------------------------------------------------------------------------
+// DENETLEYİCİ
+// -----------------------------------------------------------------------------
+
+/// Tek bir PL022 örneği.
+pub struct Pl022 {
+    base: AtomicUsize,
+    used: AtomicBool,
+}
+
+impl Pl022 {
+    const fn empty() -> Self {
+        Pl022 { base: AtomicUsize::new(0), used: AtomicBool::new(false) }
+    }
+
+    #[inline(always)]
+    fn read(&self, offset: usize) -> u32 {
+        unsafe { read_volatile((self.base.load(Ordering::Relaxed) + offset) as *const u32) }
+    }
+
+    #[inline(always)]
+    fn write(&self, offset: usize, value: u32) {
+        unsafe { write_volatile((self.base.load(Ordering::Relaxed) + offset) as *mut u32, value) }
+    }
+
+    fn wait(&self, mask: u32, set: bool) -> Result<(), KernelError> {
+        for _ in 0..TIMEOUT_SPINS {
+            if (self.read(SSPSR) & mask != 0) == set {
+                return Ok(());
+            }
+        }
+        Err(KernelError::GenericFailure)
+    }
+
+    /// `max_hz`'i aşmayan en yüksek hız için (CPSDVSR, SCR) bölenlerini bulur.
+    /// Bit hızı = SSPCLK / (CPSDVSR * (1 + SCR)); CPSDVSR çift, 2..=254.
+    fn dividers(max_hz: u32) -> (u32, u32) {
+        for cpsdvsr in (2..=254u64).step_by(2) {
+            let scr = (SSPCLK_HZ as u64).div_ceil(cpsdvsr * max_hz as u64).saturating_sub(1);
+            if scr <= 255 {
+                return (cpsdvsr as u32, scr as u32);
+            }
+        }
+        (254, 255)
+    }
+
+    /// Aygıtın kipini ve hızını uygular, portu etkinleştirir.
+    fn configure(&self, config: &SpiConfig) -> Result<(), KernelError> {
+        // Arabellekler bayt dizisi olduğu için yalnızca 8 bit kelime desteklenir
+        if config.bits_per_word != 8 {
+            return Err(KernelError::InvalidArgument);
+        }
+        let (cpsdvsr, scr) = Self::dividers(config.max_hz);
+
+        let mut cr0 = (scr << 8) | (config.bits_per_word as u32 - 1);
+        if config.cpol() {
+            cr0 |= CR0_SPO;
+        }
+        if config.cpha() {
+            cr0 |= CR0_SPH;
+        }
+
+        self.write(SSPCR1, 0); // Ayar sırasında port kapalı (ana kip)
+        self.write(SSPCR0, cr0);
+        self.write(SSPCPSR, cpsdvsr);
+        self.write(SSPIMSC, 0); // Yoklamalı kip
+        self.write(SSPDMACR, 0);
+        self.write(SSPCR1, CR1_SSE);
+
+        // Önceki işlemden kalan alım verisini at
+        while self.read(SSPSR) & SR_RNE != 0 {
+            self.read(SSPDR);
+        }
+        Ok(())
+    }
+}
+
+impl SpiController for Pl022 {
+    fn name(&self) -> &'static str {
+        "pl022"
+    }
+
+    fn num_chip_selects(&self) -> u8 {
+        1
+    }
+
+    fn transfer(&self, _cs: u8, config: &SpiConfig, xfers: &mut [SpiTransfer], _dma: bool) -> Result<(), KernelError> {
+        self.configure(config)?;
+
+        for xfer in xfers.iter_mut() {
+            for i in 0..xfer.len {
+                let out = xfer.tx.and_then(|tx| tx.get(i)).copied().unwrap_or(0);
+                self.wait(SR_TNF, true)?;
+                self.write(SSPDR, out as u32);
+
+                self.wait(SR_RNE, true)?;
+                let word = self.read(SSPDR) as u8;
+                if let Some(slot) = xfer.rx.as_deref_mut().and_then(|rx| rx.get_mut(i)) {
+                    *slot = word;
+                }
+            }
+        }
+
+        self.wait(SR_BSY, false)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// AYGIT AĞACI KEŞFİ
+// -----------------------------------------------------------------------------
+
+/// En fazla örnek sayısı.
+const MAX_INSTANCES: usize = 4;
+
+#[allow(clippy::declare_interior_mutable_const)]
+const EMPTY: Pl022 = Pl022::empty();
+static INSTANCES: [Pl022; MAX_INSTANCES] = [EMPTY; MAX_INSTANCES];
+
+fn probe() -> Result<(), KernelError> {
+    let Some(fdt) = crate::fdt::get() else { return Ok(()) };
+
+    for node in fdt.find_compatible("arm,pl022").filter(|n| n.is_enabled()) {
+        let Some((base, _)) = node.reg(0) else { continue };
+        let Some(slot) = INSTANCES.iter().find(|i| !i.used.swap(true, Ordering::AcqRel)) else {
+            return Err(KernelError::OutOfMemoryStatic);
+        };
+
+        slot.base.store(base as usize, Ordering::Relaxed);
+        slot.write(SSPCR1, 0);
+        spi::register_bus(slot)?;
+    }
+    Ok(())
+}
+
+crate::initcall!(drivers, "pl022", probe);
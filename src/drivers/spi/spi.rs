@@ -0,0 +1,223 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/drivers/spi/spi.rs
+// SPI veri yolu çatısı: denetleyici arayüzü, veri yolu kaydı ve aygıt adreslemesi.
+//
+// Denetleyici sürücüleri (örn. `pl022.rs`) aygıt ağacından keşfedilen her
+// örneği `register_bus` ile kaydeder. Aygıt sürücüleri `SpiDevice { bus, cs,
+// config }` üzerinden çalışır; her işlem aygıtın kendi kipi ve hızıyla
+// yürütülür ve veri yolu kilidiyle diğer aygıtlardan ayrılır.
+
+use core::cell::UnsafeCell;
+use crate::drivers::dma::DmaMode;
+use crate::platformgeneric::spinlock::Spinlock;
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// YAPILANDIRMA VE AKTARIMLAR
+// -----------------------------------------------------------------------------
+
+/// Aygıta özgü veri yolu ayarları.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpiConfig {
+    /// SPI kipi 0-3 (bit 1: CPOL, bit 0: CPHA).
+    pub mode: u8,
+    /// Aygıtın desteklediği en yüksek saat frekansı.
+    pub max_hz: u32,
+    /// Kelime uzunluğu (çoğu aygıt için 8).
+    pub bits_per_word: u8,
+}
+
+impl SpiConfig {
+    pub const fn new(mode: u8, max_hz: u32) -> Self {
+        SpiConfig { mode, max_hz, bits_per_word: 8 }
+    }
+
+    pub fn cpol(&self) -> bool {
+        self.mode & 0b10 != 0
+    }
+
+    pub fn cpha(&self) -> bool {
+        self.mode & 0b01 != 0
+    }
+}
+
+/// Tam çift yönlü tek bir aktarım. `tx` yoksa sıfır gönderilir, `rx` yoksa alınan atılır.
+pub struct SpiTransfer<'a> {
+    pub tx: Option<&'a [u8]>,
+    pub rx: Option<&'a mut [u8]>,
+    pub len: usize,
+}
+
+impl<'a> SpiTransfer<'a> {
+    pub fn write(tx: &'a [u8]) -> Self {
+        SpiTransfer { len: tx.len(), tx: Some(tx), rx: None }
+    }
+
+    pub fn read(rx: &'a mut [u8]) -> Self {
+        SpiTransfer { len: rx.len(), tx: None, rx: Some(rx) }
+    }
+
+    /// `tx` ve `rx` aynı uzunlukta olmalıdır.
+    pub fn duplex(tx: &'a [u8], rx: &'a mut [u8]) -> Self {
+        SpiTransfer { len: tx.len().min(rx.len()), tx: Some(tx), rx: Some(rx) }
+    }
+}
+
+/// SPI denetleyici sürücüsü.
+pub trait SpiController: Sync {
+    /// Denetleyici adı (günlük ve procfs için).
+    fn name(&self) -> &'static str;
+
+    /// Denetleyicinin sürdüğü yonga seçimi (chip select) hattı sayısı.
+    fn num_chip_selects(&self) -> u8;
+
+    /// DMA ile aktarım yapabiliyor mu?
+    fn supports_dma(&self) -> bool {
+        false
+    }
+
+    /// Aktarımları `cs` seçili tutularak sırayla yürütür.
+    ///
+    /// # Parametreler
+    /// * `dma`: `true` ise DMA kullanılır (yalnızca `supports_dma` doğruysa istenir).
+    fn transfer(&self, cs: u8, config: &SpiConfig, xfers: &mut [SpiTransfer], dma: bool) -> Result<(), KernelError>;
+}
+
+// -----------------------------------------------------------------------------
+// VERİ YOLU KAYDI
+// -----------------------------------------------------------------------------
+
+/// En fazla SPI veri yolu sayısı.
+pub const MAX_SPI_BUSES: usize = 4;
+
+struct BusTable {
+    lock: Spinlock,
+    buses: UnsafeCell<[Option<&'static dyn SpiController>; MAX_SPI_BUSES]>,
+}
+
+// Tabloya erişim `lock` ile korunur.
+unsafe impl Sync for BusTable {}
+
+static BUSES: BusTable = BusTable {
+    lock: Spinlock::new(),
+    buses: UnsafeCell::new([None; MAX_SPI_BUSES]),
+};
+
+#[allow(clippy::declare_interior_mutable_const)]
+const UNLOCKED: Spinlock = Spinlock::new();
+/// Her veri yolu için aktarım kilidi.
+static BUS_LOCKS: [Spinlock; MAX_SPI_BUSES] = [UNLOCKED; MAX_SPI_BUSES];
+
+/// Bir denetleyiciyi kaydeder.
+///
+/// # Dönüş Değeri
+/// Veri yolu numarası; tablo doluysa `OutOfMemoryStatic`.
+pub fn register_bus(controller: &'static dyn SpiController) -> Result<usize, KernelError> {
+    BUSES.lock.lock();
+    let buses = unsafe { &mut *BUSES.buses.get() };
+    let result = match buses.iter().position(|b| b.is_none()) {
+        Some(id) => {
+            buses[id] = Some(controller);
+            Ok(id)
+        }
+        None => Err(KernelError::OutOfMemoryStatic),
+    };
+    BUSES.lock.unlock();
+
+    if let Ok(id) = result {
+        serial_println!("[SPI] spi-{}: {} ({} CS)", id, controller.name(), controller.num_chip_selects());
+    }
+    result
+}
+
+/// Veri yolu numarasına karşılık gelen denetleyici.
+pub fn bus(id: usize) -> Option<&'static dyn SpiController> {
+    BUSES.lock.lock();
+    let controller = unsafe { (*BUSES.buses.get()).get(id).copied().flatten() };
+    BUSES.lock.unlock();
+    controller
+}
+
+/// Veri yolunda bir işlem yürütür.
+pub fn transfer(
+    bus_id: usize,
+    cs: u8,
+    config: &SpiConfig,
+    xfers: &mut [SpiTransfer],
+    mode: DmaMode,
+) -> Result<(), KernelError> {
+    let controller = bus(bus_id).ok_or(KernelError::NotFound)?;
+    if cs >= controller.num_chip_selects() || config.mode > 3 || config.max_hz == 0 {
+        return Err(KernelError::InvalidArgument);
+    }
+    let dma = mode.resolve(controller.supports_dma())?;
+
+    BUS_LOCKS[bus_id].lock();
+    let result = controller.transfer(cs, config, xfers, dma);
+    BUS_LOCKS[bus_id].unlock();
+    result
+}
+
+// -----------------------------------------------------------------------------
+// AYGIT ADRESLEMESİ
+// -----------------------------------------------------------------------------
+
+/// Bir veri yolundaki tek bir SPI aygıtı.
+#[derive(Debug, Clone, Copy)]
+pub struct SpiDevice {
+    pub bus: usize,
+    pub cs: u8,
+    pub config: SpiConfig,
+    pub dma: DmaMode,
+}
+
+impl SpiDevice {
+    pub const fn new(bus: usize, cs: u8, config: SpiConfig) -> Self {
+        SpiDevice { bus, cs, config, dma: DmaMode::Never }
+    }
+
+    pub fn transfer(&self, xfers: &mut [SpiTransfer]) -> Result<(), KernelError> {
+        transfer(self.bus, self.cs, &self.config, xfers, self.dma)
+    }
+
+    pub fn write(&self, data: &[u8]) -> Result<(), KernelError> {
+        self.transfer(&mut [SpiTransfer::write(data)])
+    }
+
+    pub fn read(&self, buf: &mut [u8]) -> Result<(), KernelError> {
+        self.transfer(&mut [SpiTransfer::read(buf)])
+    }
+
+    /// Komut gönderip yanıt okur (CS arada bırakılmaz).
+    pub fn write_then_read(&self, cmd: &[u8], buf: &mut [u8]) -> Result<(), KernelError> {
+        self.transfer(&mut [SpiTransfer::write(cmd), SpiTransfer::read(buf)])
+    }
+}
+
+// -----------------------------------------------------------------------------
+// KABUK VE PROCFS
+// -----------------------------------------------------------------------------
+
+fn spi_command(_args: &[&str]) -> Result<(), KernelError> {
+    for id in 0..MAX_SPI_BUSES {
+        if let Some(c) = bus(id) {
+            serial_println!("  spi-{} {:<20} {} CS, DMA: {}", id, c.name(), c.num_chip_selects(), c.supports_dma());
+        }
+    }
+    Ok(())
+}
+
+crate::shell_command!("spi", "SPI veri yollarını listeler", spi_command);
+
+fn show_buses(out: &mut dyn core::fmt::Write) -> core::fmt::Result {
+    for id in 0..MAX_SPI_BUSES {
+        if let Some(c) = bus(id) {
+            writeln!(out, "spi-{} {} cs={} dma={}", id, c.name(), c.num_chip_selects(), c.supports_dma())?;
+        }
+    }
+    Ok(())
+}
+
+crate::proc_entry!("drivers/spi", show_buses);
@@ -0,0 +1,165 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/drivers/usb/hid.rs
+// USB HID önyükleme protokolü (boot protocol) klavye sınıf sürücüsü.
+//
+// Arayüz önyükleme protokolüne alınır ve kesme IN uç noktasından 8 baytlık
+// raporlar sürekli okunur: bayt 0 değiştirici tuşlar, bayt 2-7 basılı tuşların
+// kullanım kodları. Her rapor bir öncekiyle karşılaştırılarak basma/bırakma
+// olayları üretilir ve `input` kuyruğuna eklenir. ASCII çevirisi ABD
+// düzenidir; Caps Lock yazılımda izlenir (LED güncellenmez).
+
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicBool, Ordering};
+use crate::drivers::usb::usb::{self, EndpointKind, Interface};
+use crate::input::{self, KeyEvent, MOD_CTRL, MOD_SHIFT};
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// SABİTLER
+// -----------------------------------------------------------------------------
+
+const CLASS_HID: u8 = 0x03;
+const SUBCLASS_BOOT: u8 = 0x01;
+const PROTOCOL_KEYBOARD: u8 = 0x01;
+
+// HID sınıf istekleri
+const HID_SET_IDLE: u8 = 0x0A;
+const HID_SET_PROTOCOL: u8 = 0x0B;
+const PROTOCOL_BOOT: u16 = 0;
+
+/// Önyükleme klavye raporu uzunluğu.
+const REPORT_LEN: usize = 8;
+
+/// Tuş dizisinde "çok fazla tuş" (phantom) durumunu bildiren kod.
+const USAGE_ERROR_ROLLOVER: u8 = 0x01;
+const USAGE_CAPS_LOCK: u8 = 0x39;
+/// Değiştirici tuşların kullanım kodları 0xE0 (Sol Ctrl) ile başlar.
+const USAGE_MODIFIER_BASE: u8 = 0xE0;
+
+/// 0x04 (A) - 0x38 (/) arası kullanım kodlarının ASCII karşılıkları.
+const FIRST_USAGE: u8 = 0x04;
+const NORMAL: &[u8; 53] = b"abcdefghijklmnopqrstuvwxyz1234567890\n\x1b\x08\t -=[]\\#;'`,./";
+const SHIFTED: &[u8; 53] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ!@#$%^&*()\n\x1b\x08\t _+{}|~:\"~<>?";
+
+// -----------------------------------------------------------------------------
+// KLAVYELER
+// -----------------------------------------------------------------------------
+
+/// En fazla klavye sayısı.
+const MAX_KEYBOARDS: usize = 4;
+
+struct Keyboard {
+    used: AtomicBool,
+    caps_lock: AtomicBool,
+    /// Bir önceki rapor (yalnızca rapor işleyicisinden erişilir).
+    last: UnsafeCell<[u8; REPORT_LEN]>,
+}
+
+// `last` yalnızca denetleyicinin olay işleyicisinden, sırayla erişilir.
+unsafe impl Sync for Keyboard {}
+
+#[allow(clippy::declare_interior_mutable_const)]
+const EMPTY: Keyboard = Keyboard {
+    used: AtomicBool::new(false),
+    caps_lock: AtomicBool::new(false),
+    last: UnsafeCell::new([0; REPORT_LEN]),
+};
+static KEYBOARDS: [Keyboard; MAX_KEYBOARDS] = [EMPTY; MAX_KEYBOARDS];
+
+/// Kullanım kodunu ASCII'ye çevirir (karakter üretmeyen tuşlar için 0).
+fn to_ascii(usage: u8, modifiers: u8, caps_lock: bool) -> u8 {
+    let Some(index) = usage.checked_sub(FIRST_USAGE).map(usize::from).filter(|&i| i < NORMAL.len()) else {
+        return 0;
+    };
+    let is_letter = index < 26;
+    let mut shift = modifiers & MOD_SHIFT != 0;
+    if is_letter && caps_lock {
+        shift = !shift;
+    }
+    let c = if shift { SHIFTED[index] } else { NORMAL[index] };
+
+    // Ctrl+harf: kontrol karakteri (Ctrl+C = 0x03)
+    if is_letter && modifiers & MOD_CTRL != 0 {
+        return c.to_ascii_lowercase() - b'a' + 1;
+    }
+    c
+}
+
+/// Rapor işleyicisi: önceki raporla farkı olay olarak kuyruğa ekler.
+fn on_report(cookie: usize, data: &[u8]) {
+    let Some(kbd) = KEYBOARDS.get(cookie) else { return };
+    if data.len() < REPORT_LEN {
+        return;
+    }
+    let last = unsafe { &mut *kbd.last.get() };
+    let (modifiers, keys) = (data[0], &data[2..REPORT_LEN]);
+
+    // Fazla tuş basılı: rapor geçersizdir, durum değiştirilmez
+    if keys.contains(&USAGE_ERROR_ROLLOVER) {
+        return;
+    }
+
+    let changed = modifiers ^ last[0];
+    for bit in (0..8).filter(|b| changed & (1 << b) != 0) {
+        input::push(KeyEvent {
+            usage: USAGE_MODIFIER_BASE + bit,
+            modifiers,
+            pressed: modifiers & (1 << bit) != 0,
+            ascii: 0,
+        });
+    }
+
+    for &usage in last[2..].iter().filter(|&&u| u != 0 && !keys.contains(&u)) {
+        input::push(KeyEvent { usage, modifiers, pressed: false, ascii: 0 });
+    }
+    for &usage in keys.iter().filter(|&&u| u != 0 && !last[2..].contains(&u)) {
+        if usage == USAGE_CAPS_LOCK {
+            kbd.caps_lock.fetch_xor(true, Ordering::Relaxed);
+        }
+        let ascii = to_ascii(usage, modifiers, kbd.caps_lock.load(Ordering::Relaxed));
+        input::push(KeyEvent { usage, modifiers, pressed: true, ascii });
+    }
+
+    last.copy_from_slice(&data[..REPORT_LEN]);
+}
+
+// -----------------------------------------------------------------------------
+// SINIF SÜRÜCÜSÜ
+// -----------------------------------------------------------------------------
+
+/// Önyükleme klavyesi arayüzünü sahiplenir (bkz. `usb::CLASS_DRIVERS`).
+pub fn probe(device: usize, iface: &Interface) -> bool {
+    if iface.class != CLASS_HID || iface.subclass != SUBCLASS_BOOT || iface.protocol != PROTOCOL_KEYBOARD {
+        return false;
+    }
+    let Some(dev) = usb::device(device) else { return false };
+    let Some(ep) = iface.endpoints().iter().find(|e| e.is_in() && e.kind() == EndpointKind::Interrupt) else {
+        return false;
+    };
+    let Some(index) = KEYBOARDS.iter().position(|k| !k.used.swap(true, Ordering::AcqRel)) else {
+        serial_println!("[HID] Klavye tablosu dolu");
+        return false;
+    };
+
+    let result = dev
+        .class_request(iface.number, HID_SET_PROTOCOL, PROTOCOL_BOOT)
+        .and_then(|_| {
+            // SET_IDLE desteklemeyen klavyeler isteği reddedebilir
+            let _ = dev.class_request(iface.number, HID_SET_IDLE, 0);
+            dev.hc.configure_endpoint(dev.slot, ep)
+        })
+        .and_then(|_| dev.hc.start_interrupt_in(dev.slot, ep.address, REPORT_LEN, on_report, index));
+
+    match result {
+        Ok(()) => {
+            serial_println!("[HID] Klavye {} (USB aygıtı {}, uç nokta {:#04x})", index, device, ep.address);
+            true
+        }
+        Err(e) => {
+            serial_println!("[HID] Klavye başlatılamadı: {:?}", e);
+            KEYBOARDS[index].used.store(false, Ordering::Release);
+            false
+        }
+    }
+}
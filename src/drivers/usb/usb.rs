@@ -0,0 +1,415 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/drivers/usb/usb.rs
+// Asgari USB çekirdeği: ana denetleyici arayüzü, aygıt numaralandırma ve
+// sınıf sürücüsü eşleştirme.
+//
+// Ana denetleyici sürücüsü (örn. `xhci.rs`) bir kök porta bağlı aygıtı
+// adresledikten sonra `enumerate` çağırır; çekirdek aygıt ve yapılandırma
+// tanımlayıcılarını okur, ilk yapılandırmayı seçer ve her arayüzü sırayla
+// `CLASS_DRIVERS` listesindeki sınıf sürücülerine sunar.
+//
+// Aktarım türleri: kontrol ve bulk eşzamanlı (çağıran tamamlanmayı bekler),
+// kesme (interrupt) IN ise sürekli yinelenen ve her raporda işleyici çağıran
+// periyodik aktarım olarak sunulur. Hub'lar ve sıcak takma henüz desteklenmez.
+
+use core::cell::UnsafeCell;
+use crate::platformgeneric::spinlock::Spinlock;
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// STANDART TANIMLAR
+// -----------------------------------------------------------------------------
+
+// bmRequestType
+pub const REQ_DIR_IN: u8 = 0x80;
+pub const REQ_TYPE_CLASS: u8 = 0x20;
+pub const REQ_RECIP_INTERFACE: u8 = 0x01;
+
+// Standart istekler
+pub const REQ_GET_DESCRIPTOR: u8 = 0x06;
+pub const REQ_SET_CONFIGURATION: u8 = 0x09;
+
+// Tanımlayıcı türleri
+pub const DESC_DEVICE: u8 = 0x01;
+pub const DESC_CONFIGURATION: u8 = 0x02;
+pub const DESC_INTERFACE: u8 = 0x04;
+pub const DESC_ENDPOINT: u8 = 0x05;
+
+/// Kontrol isteği başlığı (SETUP paketi).
+#[derive(Debug, Clone, Copy)]
+pub struct SetupPacket {
+    pub request_type: u8,
+    pub request: u8,
+    pub value: u16,
+    pub index: u16,
+    pub length: u16,
+}
+
+impl SetupPacket {
+    /// Veri yolundaki 8 baytlık küçük endian düzeni.
+    pub fn to_u64(&self) -> u64 {
+        self.request_type as u64
+            | (self.request as u64) << 8
+            | (self.value as u64) << 16
+            | (self.index as u64) << 32
+            | (self.length as u64) << 48
+    }
+
+    pub fn is_in(&self) -> bool {
+        self.request_type & REQ_DIR_IN != 0
+    }
+}
+
+/// Aygıt hızı.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Speed {
+    Low,
+    Full,
+    High,
+    Super,
+}
+
+/// Uç nokta aktarım türü.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndpointKind {
+    Control,
+    Isochronous,
+    Bulk,
+    Interrupt,
+}
+
+/// Bir uç nokta tanımlayıcısının özü.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Endpoint {
+    /// Bit 7: yön (1 = IN), bit 3:0: uç nokta numarası.
+    pub address: u8,
+    pub attributes: u8,
+    pub max_packet: u16,
+    pub interval: u8,
+}
+
+impl Endpoint {
+    pub fn number(&self) -> u8 {
+        self.address & 0x0F
+    }
+
+    pub fn is_in(&self) -> bool {
+        self.address & 0x80 != 0
+    }
+
+    pub fn kind(&self) -> EndpointKind {
+        match self.attributes & 3 {
+            0 => EndpointKind::Control,
+            1 => EndpointKind::Isochronous,
+            2 => EndpointKind::Bulk,
+            _ => EndpointKind::Interrupt,
+        }
+    }
+}
+
+/// Bir arayüzde izlenen en fazla uç nokta.
+pub const MAX_ENDPOINTS: usize = 4;
+
+/// Yapılandırma tanımlayıcısından çözülen bir arayüz.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Interface {
+    pub number: u8,
+    pub class: u8,
+    pub subclass: u8,
+    pub protocol: u8,
+    pub endpoints: [Endpoint; MAX_ENDPOINTS],
+    pub num_endpoints: usize,
+}
+
+impl Interface {
+    pub fn endpoints(&self) -> &[Endpoint] {
+        &self.endpoints[..self.num_endpoints]
+    }
+}
+
+// -----------------------------------------------------------------------------
+// ANA DENETLEYİCİ ARAYÜZÜ
+// -----------------------------------------------------------------------------
+
+/// Periyodik kesme aktarımında her tamamlanan rapor için çağrılır (kesme bağlamı).
+pub type ReportHandler = fn(cookie: usize, data: &[u8]);
+
+/// USB ana denetleyici sürücüsü. Aygıtlar denetleyicinin verdiği yuva numarasıyla adreslenir.
+pub trait HostController: Sync {
+    fn name(&self) -> &'static str;
+
+    /// Varsayılan kontrol uç noktasında bir istek yürütür.
+    ///
+    /// # Dönüş Değeri
+    /// Veri aşamasında aktarılan bayt sayısı.
+    fn control(&self, slot: u8, setup: SetupPacket, data: &mut [u8]) -> Result<usize, KernelError>;
+
+    /// Bir uç noktayı kullanıma hazırlar (bulk / kesme aktarımlarından önce).
+    fn configure_endpoint(&self, slot: u8, ep: &Endpoint) -> Result<(), KernelError>;
+
+    /// Eşzamanlı bulk aktarımı; yön uç nokta adresinden alınır.
+    fn bulk(&self, slot: u8, ep_addr: u8, data: &mut [u8]) -> Result<usize, KernelError>;
+
+    /// Kesme IN uç noktasında sürekli yinelenen `len` baytlık aktarım başlatır.
+    fn start_interrupt_in(
+        &self,
+        slot: u8,
+        ep_addr: u8,
+        len: usize,
+        handler: ReportHandler,
+        cookie: usize,
+    ) -> Result<(), KernelError>;
+
+    /// Bekleyen tamamlanma olaylarını işler (kesme hattı olmayan sistemler için).
+    fn poll(&self);
+}
+
+// -----------------------------------------------------------------------------
+// AYGIT KAYDI
+// -----------------------------------------------------------------------------
+
+/// Numaralandırılmış bir USB aygıtı.
+#[derive(Clone, Copy)]
+pub struct UsbDevice {
+    pub hc: &'static dyn HostController,
+    pub slot: u8,
+    /// Bağlı olduğu kök port (1'den başlar).
+    pub port: u8,
+    pub speed: Speed,
+    pub vendor: u16,
+    pub product: u16,
+    pub class: u8,
+}
+
+impl UsbDevice {
+    pub fn control(&self, setup: SetupPacket, data: &mut [u8]) -> Result<usize, KernelError> {
+        self.hc.control(self.slot, setup, data)
+    }
+
+    /// GET_DESCRIPTOR isteği.
+    pub fn get_descriptor(&self, kind: u8, index: u8, buf: &mut [u8]) -> Result<usize, KernelError> {
+        let setup = SetupPacket {
+            request_type: REQ_DIR_IN,
+            request: REQ_GET_DESCRIPTOR,
+            value: (kind as u16) << 8 | index as u16,
+            index: 0,
+            length: buf.len() as u16,
+        };
+        self.control(setup, buf)
+    }
+
+    /// Arayüze yönelik sınıf isteği (veri aşamasız).
+    pub fn class_request(&self, interface: u8, request: u8, value: u16) -> Result<(), KernelError> {
+        let setup = SetupPacket {
+            request_type: REQ_TYPE_CLASS | REQ_RECIP_INTERFACE,
+            request,
+            value,
+            index: interface as u16,
+            length: 0,
+        };
+        self.control(setup, &mut []).map(|_| ())
+    }
+}
+
+/// En fazla USB aygıtı ve ana denetleyici sayısı.
+pub const MAX_USB_DEVICES: usize = 16;
+pub const MAX_HOST_CONTROLLERS: usize = 4;
+
+struct Registry {
+    lock: Spinlock,
+    devices: UnsafeCell<[Option<UsbDevice>; MAX_USB_DEVICES]>,
+    controllers: UnsafeCell<[Option<&'static dyn HostController>; MAX_HOST_CONTROLLERS]>,
+}
+
+// Tablolara erişim `lock` ile korunur.
+unsafe impl Sync for Registry {}
+
+static REGISTRY: Registry = Registry {
+    lock: Spinlock::new(),
+    devices: UnsafeCell::new([None; MAX_USB_DEVICES]),
+    controllers: UnsafeCell::new([None; MAX_HOST_CONTROLLERS]),
+};
+
+/// Bir ana denetleyiciyi kaydeder (`poll` ve `lsusb` için).
+pub fn register_controller(hc: &'static dyn HostController) -> Result<usize, KernelError> {
+    REGISTRY.lock.lock();
+    let controllers = unsafe { &mut *REGISTRY.controllers.get() };
+    let result = match controllers.iter().position(|c| c.is_none()) {
+        Some(id) => {
+            controllers[id] = Some(hc);
+            Ok(id)
+        }
+        None => Err(KernelError::OutOfMemoryStatic),
+    };
+    REGISTRY.lock.unlock();
+    result
+}
+
+fn register_device(dev: UsbDevice) -> Result<usize, KernelError> {
+    REGISTRY.lock.lock();
+    let devices = unsafe { &mut *REGISTRY.devices.get() };
+    let result = match devices.iter().position(|d| d.is_none()) {
+        Some(id) => {
+            devices[id] = Some(dev);
+            Ok(id)
+        }
+        None => Err(KernelError::OutOfMemoryStatic),
+    };
+    REGISTRY.lock.unlock();
+    result
+}
+
+/// Numarası verilen aygıt.
+pub fn device(id: usize) -> Option<UsbDevice> {
+    REGISTRY.lock.lock();
+    let dev = unsafe { (*REGISTRY.devices.get()).get(id).copied().flatten() };
+    REGISTRY.lock.unlock();
+    dev
+}
+
+/// Tüm denetleyicilerin bekleyen olaylarını işler.
+pub fn poll() {
+    for id in 0..MAX_HOST_CONTROLLERS {
+        REGISTRY.lock.lock();
+        let hc = unsafe { (*REGISTRY.controllers.get())[id] };
+        REGISTRY.lock.unlock();
+        if let Some(hc) = hc {
+            hc.poll();
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// NUMARALANDIRMA
+// -----------------------------------------------------------------------------
+
+/// Sınıf sürücüleri: arayüzü sahiplenirse `true` döner.
+type ClassProbe = fn(device: usize, iface: &Interface) -> bool;
+
+const CLASS_DRIVERS: &[ClassProbe] = &[crate::drivers::usb::hid::probe];
+
+/// Yapılandırma tanımlayıcısı için okunan en fazla bayt.
+const MAX_CONFIG_LEN: usize = 512;
+
+/// Adreslenmiş bir aygıtı tanır, yapılandırır ve sınıf sürücülerine sunar.
+///
+/// # Dönüş Değeri
+/// Aygıt numarası.
+pub fn enumerate(hc: &'static dyn HostController, slot: u8, port: u8, speed: Speed) -> Result<usize, KernelError> {
+    let mut dev = UsbDevice { hc, slot, port, speed, vendor: 0, product: 0, class: 0 };
+
+    let mut desc = [0u8; 18];
+    dev.get_descriptor(DESC_DEVICE, 0, &mut desc)?;
+    dev.vendor = u16::from_le_bytes([desc[8], desc[9]]);
+    dev.product = u16::from_le_bytes([desc[10], desc[11]]);
+    dev.class = desc[4];
+
+    // Önce başlık, sonra tüm yapılandırma (arayüz ve uç nokta tanımlayıcıları dahil)
+    let mut config = [0u8; MAX_CONFIG_LEN];
+    dev.get_descriptor(DESC_CONFIGURATION, 0, &mut config[..9])?;
+    let total = (u16::from_le_bytes([config[2], config[3]]) as usize).min(MAX_CONFIG_LEN);
+    dev.get_descriptor(DESC_CONFIGURATION, 0, &mut config[..total])?;
+
+    let setup = SetupPacket {
+        request_type: 0,
+        request: REQ_SET_CONFIGURATION,
+        value: config[5] as u16,
+        index: 0,
+        length: 0,
+    };
+    dev.control(setup, &mut [])?;
+
+    let id = register_device(dev)?;
+    serial_println!(
+        "[USB] Aygıt {}: {:04x}:{:04x} sınıf {:#04x}, port {}, {:?}",
+        id,
+        dev.vendor,
+        dev.product,
+        dev.class,
+        port,
+        speed
+    );
+
+    for_each_interface(&config[..total], |iface| {
+        if !CLASS_DRIVERS.iter().any(|probe| probe(id, iface)) {
+            serial_println!("[USB]   Arayüz {}: sınıf {:#04x} için sürücü yok", iface.number, iface.class);
+        }
+    });
+    Ok(id)
+}
+
+/// Yapılandırma tanımlayıcısındaki her arayüzü uç noktalarıyla birlikte verir.
+fn for_each_interface(config: &[u8], mut f: impl FnMut(&Interface)) {
+    let mut current: Option<Interface> = None;
+    let mut off = 0;
+    while off + 2 <= config.len() {
+        let len = config[off] as usize;
+        if len < 2 || off + len > config.len() {
+            break;
+        }
+        let d = &config[off..off + len];
+        match d[1] {
+            DESC_INTERFACE if len >= 9 => {
+                if let Some(iface) = current.take() {
+                    f(&iface);
+                }
+                current = Some(Interface {
+                    number: d[2],
+                    class: d[5],
+                    subclass: d[6],
+                    protocol: d[7],
+                    ..Interface::default()
+                });
+            }
+            DESC_ENDPOINT if len >= 7 => {
+                if let Some(iface) = current.as_mut().filter(|i| i.num_endpoints < MAX_ENDPOINTS) {
+                    iface.endpoints[iface.num_endpoints] = Endpoint {
+                        address: d[2],
+                        attributes: d[3],
+                        max_packet: u16::from_le_bytes([d[4], d[5]]) & 0x7FF,
+                        interval: d[6],
+                    };
+                    iface.num_endpoints += 1;
+                }
+            }
+            _ => {}
+        }
+        off += len;
+    }
+    if let Some(iface) = current {
+        f(&iface);
+    }
+}
+
+// -----------------------------------------------------------------------------
+// KABUK VE PROCFS
+// -----------------------------------------------------------------------------
+
+fn lsusb_command(_args: &[&str]) -> Result<(), KernelError> {
+    for (id, d) in (0..MAX_USB_DEVICES).filter_map(|id| device(id).map(|d| (id, d))) {
+        serial_println!(
+            "  {} {} port {} {:04x}:{:04x} sınıf {:#04x} {:?}",
+            id,
+            d.hc.name(),
+            d.port,
+            d.vendor,
+            d.product,
+            d.class,
+            d.speed
+        );
+    }
+    Ok(())
+}
+
+crate::shell_command!("lsusb", "USB aygıtlarını listeler", lsusb_command);
+
+fn show_devices(out: &mut dyn core::fmt::Write) -> core::fmt::Result {
+    for (id, d) in (0..MAX_USB_DEVICES).filter_map(|id| device(id).map(|d| (id, d))) {
+        writeln!(out, "{} {} {} {:04x}:{:04x} {:#04x} {:?}", id, d.hc.name(), d.port, d.vendor, d.product, d.class, d.speed)?;
+    }
+    Ok(())
+}
+
+crate::proc_entry!("drivers/usb", show_devices);
@@ -0,0 +1,977 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/drivers/usb/xhci.rs
+// eXtensible Host Controller Interface (xHCI 1.x) USB ana denetleyici sürücüsü.
+//
+// Tek bölütlü komut halkası, tek bölütlü olay halkası (kesici 0) ve her uç
+// nokta için bir aktarım halkası kullanılır; halkalar ve bağlamlar çerçeve
+// ayırıcısından alınan sayfalardadır. Başlangıçta bağlı olan kök portlar
+// sıfırlanır, aygıtlar adreslenir ve `usb::enumerate` ile tanıtılır.
+//
+// Eşzamanlı işlemler (komutlar, kontrol ve bulk aktarımları) `lock` altında
+// sıralanır ve tamamlanmayı olay halkasını yoklayarak bekler. Olay halkası
+// bir deneme kilidiyle (`events`) korunur: kesme işleyicisi kilit meşgulse
+// kesmeyi onaylayıp çıkar, olayları kilidi tutan taraf işler. Periyodik kesme
+// aktarımları (HID raporları) olay işlenirken yeniden kuyruğa alınır.
+//
+// Varsayımlar: Çekirdek birebir eşlenmiştir (sanal = fiziksel) ve DMA önbellek
+// tutarlıdır. Veri aşamaları denetleyiciye ait bir sıçrama (bounce) sayfası
+// üzerinden yapılır. Hub'lar ve sıcak takma desteklenmez; port değişiklikleri
+// yalnızca günlüğe yazılır.
+//
+// Keşif: PCI sınıfı 0C.03.30 veya aygıt ağacında `generic-xhci`.
+
+use core::cell::UnsafeCell;
+use core::ptr::{read_volatile, write_volatile};
+use core::sync::atomic::{fence, AtomicBool, AtomicU32, AtomicU64, Ordering};
+use crate::drivers::usb::usb::{self, Endpoint, EndpointKind, HostController, ReportHandler, SetupPacket, Speed};
+use crate::memory::frame;
+use crate::platformgeneric::spinlock::Spinlock;
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// YAZMAÇLAR
+// -----------------------------------------------------------------------------
+
+// Yetenek yazmaçları (taban)
+const CAPLENGTH: usize = 0x00;
+const HCSPARAMS1: usize = 0x04;
+const HCSPARAMS2: usize = 0x08;
+const HCCPARAMS1: usize = 0x10;
+const DBOFF: usize = 0x14;
+const RTSOFF: usize = 0x18;
+
+// İşletim yazmaçları (taban + CAPLENGTH)
+const USBCMD: usize = 0x00;
+const USBSTS: usize = 0x04;
+const CRCR: usize = 0x18;
+const DCBAAP: usize = 0x30;
+const CONFIG: usize = 0x38;
+const PORTSC_BASE: usize = 0x400;
+
+// USBCMD
+const CMD_RUN: u32 = 1 << 0;
+const CMD_RESET: u32 = 1 << 1;
+const CMD_INTE: u32 = 1 << 2;
+
+// USBSTS
+const STS_HALTED: u32 = 1 << 0;
+const STS_EINT: u32 = 1 << 3;
+const STS_CNR: u32 = 1 << 11;
+
+// PORTSC
+const PORT_CCS: u32 = 1 << 0;
+const PORT_PED: u32 = 1 << 1;
+const PORT_PR: u32 = 1 << 4;
+const PORT_SPEED_SHIFT: u32 = 10;
+const PORT_PRC: u32 = 1 << 21;
+/// Yazıldığında temizlenen değişiklik bitleri (CSC..CEC).
+const PORT_CHANGE_MASK: u32 = 0x00FE_0000;
+
+// Çalışma zamanı yazmaçları (taban + RTSOFF), kesici 0
+const IR0: usize = 0x20;
+const IMAN: usize = 0x00;
+const IMOD: usize = 0x04;
+const ERSTSZ: usize = 0x08;
+const ERSTBA: usize = 0x10;
+const ERDP: usize = 0x18;
+
+const IMAN_IP: u32 = 1 << 0;
+const IMAN_IE: u32 = 1 << 1;
+const ERDP_EHB: u64 = 1 << 3;
+
+// Genişletilmiş yetenekler
+const XCAP_LEGACY: u32 = 1;
+const LEGACY_BIOS_OWNED: u32 = 1 << 16;
+const LEGACY_OS_OWNED: u32 = 1 << 24;
+
+// TRB türleri
+const TRB_NORMAL: u32 = 1;
+const TRB_SETUP: u32 = 2;
+const TRB_DATA: u32 = 3;
+const TRB_STATUS: u32 = 4;
+const TRB_LINK: u32 = 6;
+const TRB_ENABLE_SLOT: u32 = 9;
+const TRB_ADDRESS_DEVICE: u32 = 11;
+const TRB_CONFIGURE_EP: u32 = 12;
+const TRB_EVALUATE_CONTEXT: u32 = 13;
+const TRB_TRANSFER_EVENT: u32 = 32;
+const TRB_COMMAND_COMPLETION: u32 = 33;
+const TRB_PORT_STATUS_CHANGE: u32 = 34;
+
+// TRB denetim alanı bitleri
+const TRB_CYCLE: u32 = 1 << 0;
+const TRB_TOGGLE: u32 = 1 << 1;
+const TRB_ISP: u32 = 1 << 2;
+const TRB_IOC: u32 = 1 << 5;
+const TRB_IDT: u32 = 1 << 6;
+const TRB_DIR_IN: u32 = 1 << 16;
+const TRT_OUT: u32 = 2 << 16;
+const TRT_IN: u32 = 3 << 16;
+
+// Tamamlanma kodları
+const CC_SUCCESS: u32 = 1;
+const CC_STALL: u32 = 6;
+const CC_SHORT_PACKET: u32 = 13;
+
+// Uç nokta bağlamı türleri
+const EP_TYPE_BULK_OUT: u32 = 2;
+const EP_TYPE_INTERRUPT_OUT: u32 = 3;
+const EP_TYPE_CONTROL: u32 = 4;
+const EP_TYPE_BULK_IN: u32 = 6;
+const EP_TYPE_INTERRUPT_IN: u32 = 7;
+
+/// Bir halka sayfasındaki TRB sayısı (16 bayt x 256 = 4 KiB; sonuncusu Link TRB).
+const RING_TRBS: usize = 256;
+/// Denetleyici başına desteklenen en fazla aygıt yuvası.
+const MAX_SLOTS: usize = 16;
+/// Aygıt bağlamındaki en fazla uç nokta (DCI 1..=31).
+const MAX_DCI: usize = 32;
+/// En fazla periyodik (kesme IN) aktarım.
+const MAX_PERIODIC: usize = 8;
+/// Desteklenen en fazla karalama (scratchpad) tamponu.
+const MAX_SCRATCHPADS: usize = 64;
+/// xHCI veri yapılarının sayfa boyutu (çerçeve boyutu bundan küçük olamaz).
+const PAGE: usize = 4096;
+
+/// Yoklama döngüsü üst sınırı.
+const TIMEOUT_SPINS: u32 = 10_000_000;
+
+// -----------------------------------------------------------------------------
+// HALKALAR
+// -----------------------------------------------------------------------------
+
+/// Üretici tarafı bir TRB halkası (komut veya aktarım).
+#[derive(Clone, Copy)]
+struct Ring {
+    base: usize,
+    index: usize,
+    cycle: bool,
+}
+
+impl Ring {
+    const NONE: Ring = Ring { base: 0, index: 0, cycle: true };
+
+    /// Sıfırlanmış bir sayfada, sonu başa bağlanmış halka kurar.
+    fn new() -> Result<Ring, KernelError> {
+        let base = alloc_page()?;
+        let link = [base as u32, (base as u64 >> 32) as u32, 0, TRB_LINK << 10 | TRB_TOGGLE];
+        write_trb(base + (RING_TRBS - 1) * 16, link);
+        Ok(Ring { base, index: 0, cycle: true })
+    }
+
+    /// Bir TRB ekler ve fiziksel adresini döndürür.
+    fn push(&mut self, mut trb: [u32; 4]) -> u64 {
+        let addr = self.base + self.index * 16;
+        trb[3] = (trb[3] & !TRB_CYCLE) | self.cycle as u32;
+        write_trb(addr, trb);
+
+        self.index += 1;
+        if self.index == RING_TRBS - 1 {
+            // Link TRB'nin döngü bitini denetleyiciye devret ve döngüyü çevir
+            let link = self.base + self.index * 16;
+            let control = unsafe { read_volatile((link + 12) as *const u32) };
+            unsafe { write_volatile((link + 12) as *mut u32, (control & !TRB_CYCLE) | self.cycle as u32) };
+            self.index = 0;
+            self.cycle = !self.cycle;
+        }
+        addr as u64
+    }
+
+    /// Halkanın ilk TRB adresi ve döngü durumu (bağlam işaretçisi için).
+    fn dequeue_pointer(&self) -> u64 {
+        (self.base + self.index * 16) as u64 | self.cycle as u64
+    }
+}
+
+fn write_trb(addr: usize, trb: [u32; 4]) {
+    unsafe {
+        for (i, word) in trb.iter().take(3).enumerate() {
+            write_volatile((addr + i * 4) as *mut u32, *word);
+        }
+        // Döngü biti en son görünür olmalıdır
+        fence(Ordering::Release);
+        write_volatile((addr + 12) as *mut u32, trb[3]);
+    }
+}
+
+fn read_trb(addr: usize) -> [u32; 4] {
+    unsafe { core::array::from_fn(|i| read_volatile((addr + i * 4) as *const u32)) }
+}
+
+/// Sıfırlanmış bir çerçeve ayırır.
+fn alloc_page() -> Result<usize, KernelError> {
+    let page = frame::alloc()?;
+    unsafe { core::ptr::write_bytes(page as *mut u8, 0, frame::FRAME_SIZE) };
+    Ok(page)
+}
+
+// -----------------------------------------------------------------------------
+// DENETLEYİCİ DURUMU
+// -----------------------------------------------------------------------------
+
+/// Bir aygıt yuvasının bağlamları ve halkaları.
+#[derive(Clone, Copy)]
+struct Slot {
+    output_ctx: usize,
+    input_ctx: usize,
+    port: u8,
+    speed: Speed,
+    rings: [Ring; MAX_DCI],
+}
+
+impl Slot {
+    const NONE: Slot = Slot {
+        output_ctx: 0,
+        input_ctx: 0,
+        port: 0,
+        speed: Speed::Full,
+        rings: [Ring::NONE; MAX_DCI],
+    };
+}
+
+/// `lock` altındaki durum.
+struct State {
+    op: usize,
+    db: usize,
+    ctx_size: usize,
+    max_ports: u8,
+    dcbaa: usize,
+    /// Veri aşamaları için sıçrama sayfası.
+    bounce: usize,
+    cmd_ring: Ring,
+    slots: [Slot; MAX_SLOTS + 1],
+}
+
+/// Periyodik kesme IN aktarımı.
+#[derive(Clone, Copy)]
+struct Periodic {
+    slot: u8,
+    dci: u8,
+    ring: Ring,
+    /// Rapor tamponu (kendi sayfası).
+    buf: usize,
+    len: usize,
+    handler: Option<ReportHandler>,
+    cookie: usize,
+}
+
+impl Periodic {
+    const NONE: Periodic = Periodic { slot: 0, dci: 0, ring: Ring::NONE, buf: 0, len: 0, handler: None, cookie: 0 };
+}
+
+/// `events` deneme kilidi altındaki durum.
+struct EventState {
+    op: usize,
+    ir: usize,
+    db: usize,
+    ring: usize,
+    index: usize,
+    cycle: bool,
+    periodic: [Periodic; MAX_PERIODIC],
+}
+
+/// Tek bir xHCI örneği.
+pub struct Xhci {
+    lock: Spinlock,
+    state: UnsafeCell<State>,
+    events: AtomicBool,
+    ev: UnsafeCell<EventState>,
+    /// Son eşzamanlı işlemin tamamlanma kaydı (TRB adresi, durum, denetim).
+    done_trb: AtomicU64,
+    done_status: AtomicU32,
+    done_control: AtomicU32,
+    /// Kesme numarası (`u32::MAX`: yalnızca yoklama).
+    irq: AtomicU32,
+    base: AtomicU64,
+    used: AtomicBool,
+}
+
+// `state` `lock` ile, `ev` `events` ile korunur.
+unsafe impl Sync for Xhci {}
+
+impl Xhci {
+    const fn empty() -> Self {
+        Xhci {
+            lock: Spinlock::new(),
+            state: UnsafeCell::new(State {
+                op: 0,
+                db: 0,
+                ctx_size: 32,
+                max_ports: 0,
+                dcbaa: 0,
+                bounce: 0,
+                cmd_ring: Ring::NONE,
+                slots: [Slot::NONE; MAX_SLOTS + 1],
+            }),
+            events: AtomicBool::new(false),
+            ev: UnsafeCell::new(EventState {
+                op: 0,
+                ir: 0,
+                db: 0,
+                ring: 0,
+                index: 0,
+                cycle: true,
+                periodic: [Periodic::NONE; MAX_PERIODIC],
+            }),
+            done_trb: AtomicU64::new(0),
+            done_status: AtomicU32::new(0),
+            done_control: AtomicU32::new(0),
+            irq: AtomicU32::new(u32::MAX),
+            base: AtomicU64::new(0),
+            used: AtomicBool::new(false),
+        }
+    }
+
+    /// Kilit altında durumu verir.
+    fn with_state<R>(&self, f: impl FnOnce(&mut State) -> R) -> R {
+        self.lock.lock();
+        let result = f(unsafe { &mut *self.state.get() });
+        self.lock.unlock();
+        result
+    }
+
+    /// Olay kilidini alabilirse olay durumunu verir.
+    fn try_with_events<R>(&self, f: impl FnOnce(&mut EventState) -> R) -> Option<R> {
+        if self.events.swap(true, Ordering::Acquire) {
+            return None;
+        }
+        let result = f(unsafe { &mut *self.ev.get() });
+        self.events.store(false, Ordering::Release);
+        Some(result)
+    }
+
+    /// Olay halkasındaki tüm olayları işler.
+    fn process_events(&self) {
+        self.try_with_events(|ev| {
+            if ev.ring == 0 {
+                return;
+            }
+            loop {
+                let trb = read_trb(ev.ring + ev.index * 16);
+                if (trb[3] & TRB_CYCLE != 0) != ev.cycle {
+                    break;
+                }
+                self.handle_event(ev, trb);
+                ev.index += 1;
+                if ev.index == RING_TRBS {
+                    ev.index = 0;
+                    ev.cycle = !ev.cycle;
+                }
+            }
+            w64(ev.ir + ERDP, (ev.ring + ev.index * 16) as u64 | ERDP_EHB);
+        });
+    }
+
+    fn handle_event(&self, ev: &mut EventState, trb: [u32; 4]) {
+        let kind = (trb[3] >> 10) & 0x3F;
+        match kind {
+            TRB_TRANSFER_EVENT | TRB_COMMAND_COMPLETION => {
+                let slot = (trb[3] >> 24) as u8;
+                let dci = ((trb[3] >> 16) & 0x1F) as u8;
+                if kind == TRB_TRANSFER_EVENT {
+                    if let Some(p) = ev.periodic.iter_mut().find(|p| p.handler.is_some() && p.slot == slot && p.dci == dci) {
+                        periodic_complete(ev.db, p, trb[2]);
+                        return;
+                    }
+                }
+                self.done_status.store(trb[2], Ordering::Relaxed);
+                self.done_control.store(trb[3], Ordering::Relaxed);
+                self.done_trb.store(trb[0] as u64 | (trb[1] as u64) << 32, Ordering::Release);
+            }
+            TRB_PORT_STATUS_CHANGE => {
+                let port = (trb[0] >> 24) as usize;
+                if port == 0 {
+                    return;
+                }
+                let reg = ev.op + PORTSC_BASE + (port - 1) * 0x10;
+                let portsc = r32(reg);
+                w32(reg, port_neutral(portsc) | (portsc & PORT_CHANGE_MASK));
+                serial_println!(
+                    "[XHCI] Port {} {} (sıcak takma desteklenmez)",
+                    port,
+                    if portsc & PORT_CCS != 0 { "bağlandı" } else { "ayrıldı" }
+                );
+            }
+            _ => {}
+        }
+    }
+
+    /// Bir TRB'nin tamamlanmasını bekler.
+    ///
+    /// # Dönüş Değeri
+    /// (tamamlanma kodu, durum alanı, denetim alanı)
+    fn wait(&self, trb: u64) -> Result<(u32, u32), KernelError> {
+        for _ in 0..TIMEOUT_SPINS {
+            self.process_events();
+            if self.done_trb.load(Ordering::Acquire) == trb {
+                let status = self.done_status.load(Ordering::Relaxed);
+                let control = self.done_control.load(Ordering::Relaxed);
+                return match status >> 24 {
+                    CC_SUCCESS | CC_SHORT_PACKET => Ok((status, control)),
+                    CC_STALL => Err(KernelError::InvalidArgument),
+                    code => {
+                        serial_println!("[XHCI] TRB {:#x} hata kodu {}", trb, code);
+                        Err(KernelError::GenericFailure)
+                    }
+                };
+            }
+        }
+        Err(KernelError::GenericFailure)
+    }
+
+    /// Komut halkasına bir komut ekler ve tamamlanmasını bekler.
+    ///
+    /// # Dönüş Değeri
+    /// Tamamlanma olayının denetim alanı (bit 31:24 yuva numarası).
+    fn command(&self, s: &mut State, trb: [u32; 4]) -> Result<u32, KernelError> {
+        self.done_trb.store(0, Ordering::Relaxed);
+        let addr = s.cmd_ring.push(trb);
+        w32(s.db, 0);
+        self.wait(addr).map(|(_, control)| control)
+    }
+
+    /// Bir aktarım halkasındaki son TRB'yi bekler.
+    fn ring_and_wait(&self, s: &State, slot: u8, dci: u8, last: u64) -> Result<u32, KernelError> {
+        w32(s.db + slot as usize * 4, dci as u32);
+        self.wait(last).map(|(status, _)| status & 0xFF_FFFF)
+    }
+
+    // -------------------------------------------------------------------------
+    // BAŞLATMA
+    // -------------------------------------------------------------------------
+
+    /// Denetleyiciyi sıfırlar, veri yapılarını kurar ve çalıştırır.
+    fn start(&self, base: usize) -> Result<(), KernelError> {
+        bios_handoff(base);
+
+        let op = base + r8(base + CAPLENGTH) as usize;
+        let hcs1 = r32(base + HCSPARAMS1);
+        let hcs2 = r32(base + HCSPARAMS2);
+        let hcc1 = r32(base + HCCPARAMS1);
+        let db = base + (r32(base + DBOFF) & !0x3) as usize;
+        let rt = base + (r32(base + RTSOFF) & !0x1F) as usize;
+
+        w32(op + USBCMD, r32(op + USBCMD) & !CMD_RUN);
+        wait_reg(op + USBSTS, STS_HALTED, STS_HALTED)?;
+        w32(op + USBCMD, CMD_RESET);
+        wait_reg(op + USBCMD, CMD_RESET, 0)?;
+        wait_reg(op + USBSTS, STS_CNR, 0)?;
+
+        let max_slots = (hcs1 & 0xFF).min(MAX_SLOTS as u32);
+        w32(op + CONFIG, max_slots);
+
+        // DCBAA ilk yarı, ERST ikinci yarı (64 bayt hizalı)
+        let dcbaa = alloc_page()?;
+        let erst = dcbaa + PAGE / 2;
+
+        let scratchpads = ((((hcs2 >> 21) & 0x1F) << 5) | ((hcs2 >> 27) & 0x1F)) as usize;
+        if scratchpads > 0 {
+            if scratchpads > MAX_SCRATCHPADS {
+                return Err(KernelError::OutOfMemoryStatic);
+            }
+            let array = alloc_page()?;
+            for i in 0..scratchpads {
+                unsafe { write_volatile((array as *mut u64).add(i), alloc_page()? as u64) };
+            }
+            unsafe { write_volatile(dcbaa as *mut u64, array as u64) };
+        }
+        w64(op + DCBAAP, dcbaa as u64);
+
+        let cmd_ring = Ring::new()?;
+        w64(op + CRCR, cmd_ring.dequeue_pointer());
+
+        let event_ring = alloc_page()?;
+        unsafe {
+            write_volatile(erst as *mut u64, event_ring as u64);
+            write_volatile((erst + 8) as *mut u32, RING_TRBS as u32);
+        }
+        let ir = rt + IR0;
+        w32(ir + ERSTSZ, 1);
+        w64(ir + ERDP, event_ring as u64);
+        w64(ir + ERSTBA, erst as u64);
+        w32(ir + IMOD, 4000); // 1 ms kesme birleştirme (250 ns birim)
+        w32(ir + IMAN, IMAN_IP | IMAN_IE);
+
+        self.with_state(|s| {
+            s.op = op;
+            s.db = db;
+            s.ctx_size = if hcc1 & (1 << 2) != 0 { 64 } else { 32 };
+            s.max_ports = (hcs1 >> 24) as u8;
+            s.dcbaa = dcbaa;
+            s.cmd_ring = cmd_ring;
+            alloc_page().map(|page| s.bounce = page)
+        })?;
+        while self.try_with_events(|ev| {
+            ev.op = op;
+            ev.ir = ir;
+            ev.db = db;
+            ev.ring = event_ring;
+            ev.index = 0;
+            ev.cycle = true;
+        })
+        .is_none()
+        {}
+
+        w32(op + USBCMD, CMD_RUN | CMD_INTE);
+        wait_reg(op + USBSTS, STS_HALTED, 0)
+    }
+
+    /// Bağlı kök portları sıfırlar ve aygıtları numaralandırır.
+    fn scan_ports(&'static self) {
+        let (op, ports) = self.with_state(|s| (s.op, s.max_ports));
+        for port in 1..=ports {
+            let reg = op + PORTSC_BASE + (port as usize - 1) * 0x10;
+            if r32(reg) & PORT_CCS == 0 {
+                continue;
+            }
+            let speed = match reset_port(reg) {
+                Ok(speed) => speed,
+                Err(e) => {
+                    serial_println!("[XHCI] Port {} sıfırlanamadı: {:?}", port, e);
+                    continue;
+                }
+            };
+            let result = self
+                .with_state(|s| self.address_device(s, port, speed))
+                .and_then(|slot| usb::enumerate(self, slot, port, speed));
+            if let Err(e) = result {
+                serial_println!("[XHCI] Port {}: aygıt tanınamadı: {:?}", port, e);
+            }
+        }
+    }
+
+    /// Yuva ayırır, varsayılan kontrol uç noktasını kurar ve aygıtı adresler.
+    fn address_device(&self, s: &mut State, port: u8, speed: Speed) -> Result<u8, KernelError> {
+        let slot = (self.command(s, [0, 0, 0, TRB_ENABLE_SLOT << 10])? >> 24) as u8;
+        if slot == 0 || slot as usize > MAX_SLOTS {
+            return Err(KernelError::OutOfMemoryStatic);
+        }
+
+        let output_ctx = alloc_page()?;
+        let input_ctx = alloc_page()?;
+        let ep0 = Ring::new()?;
+        s.slots[slot as usize] = Slot { output_ctx, input_ctx, port, speed, rings: [Ring::NONE; MAX_DCI] };
+        s.slots[slot as usize].rings[1] = ep0;
+        unsafe { write_volatile((s.dcbaa as *mut u64).add(slot as usize), output_ctx as u64) };
+
+        // Giriş kontrol bağlamı: A0 (yuva) + A1 (EP0)
+        let cs = s.ctx_size;
+        ctx_write(input_ctx, 1, 0b11);
+        ctx_write(input_ctx + cs, 0, (1 << 27) | speed_id(speed) << 20);
+        ctx_write(input_ctx + cs, 1, (port as u32) << 16);
+        let mps = default_max_packet(speed);
+        write_ep_ctx(input_ctx + 2 * cs, EP_TYPE_CONTROL, mps, 0, ep0.dequeue_pointer(), 8);
+
+        let trb = [input_ctx as u32, (input_ctx as u64 >> 32) as u32, 0, TRB_ADDRESS_DEVICE << 10 | (slot as u32) << 24];
+        self.command(s, trb)?;
+
+        // Tam hızlı aygıtlarda EP0 paket boyutu tanımlayıcıdan öğrenilir
+        if speed == Speed::Full {
+            let mut head = [0u8; 8];
+            let setup = SetupPacket {
+                request_type: usb::REQ_DIR_IN,
+                request: usb::REQ_GET_DESCRIPTOR,
+                value: (usb::DESC_DEVICE as u16) << 8,
+                index: 0,
+                length: 8,
+            };
+            self.control_locked(s, slot, setup, &mut head)?;
+            if head[7] as u32 != mps && head[7] != 0 {
+                unsafe { core::ptr::write_bytes(input_ctx as *mut u8, 0, PAGE) };
+                ctx_write(input_ctx, 1, 0b10);
+                write_ep_ctx(input_ctx + 2 * cs, EP_TYPE_CONTROL, head[7] as u32, 0, 0, 8);
+                let trb = [input_ctx as u32, (input_ctx as u64 >> 32) as u32, 0, TRB_EVALUATE_CONTEXT << 10 | (slot as u32) << 24];
+                self.command(s, trb)?;
+            }
+        }
+        Ok(slot)
+    }
+
+    /// Kilit tutulurken kontrol aktarımı (Setup, isteğe bağlı Data, Status).
+    fn control_locked(&self, s: &mut State, slot: u8, setup: SetupPacket, data: &mut [u8]) -> Result<usize, KernelError> {
+        let len = (setup.length as usize).min(data.len());
+        if len > PAGE || s.slots.get(slot as usize).is_none_or(|sl| sl.output_ctx == 0) {
+            return Err(KernelError::InvalidArgument);
+        }
+        let is_in = setup.is_in();
+        if !is_in {
+            unsafe { core::ptr::copy_nonoverlapping(data.as_ptr(), s.bounce as *mut u8, len) };
+        }
+
+        let raw = setup.to_u64();
+        let trt = match (len, is_in) {
+            (0, _) => 0,
+            (_, true) => TRT_IN,
+            (_, false) => TRT_OUT,
+        };
+        let bounce = s.bounce;
+        let ring = &mut s.slots[slot as usize].rings[1];
+        self.done_trb.store(0, Ordering::Relaxed);
+        ring.push([raw as u32, (raw >> 32) as u32, 8, TRB_SETUP << 10 | TRB_IDT | trt]);
+        if len > 0 {
+            let dir = if is_in { TRB_DIR_IN } else { 0 };
+            ring.push([bounce as u32, (bounce as u64 >> 32) as u32, len as u32, TRB_DATA << 10 | dir]);
+        }
+        // Durum aşaması veri aşamasının tersi yöndedir (veri yoksa IN)
+        let status_dir = if len == 0 || !is_in { TRB_DIR_IN } else { 0 };
+        let last = ring.push([0, 0, 0, TRB_STATUS << 10 | TRB_IOC | status_dir]);
+        self.ring_and_wait(s, slot, 1, last)?;
+
+        if is_in {
+            unsafe { core::ptr::copy_nonoverlapping(s.bounce as *const u8, data.as_mut_ptr(), len) };
+        }
+        Ok(len)
+    }
+}
+
+/// Periyodik aktarımın tamamlanmasını işler ve aktarımı yeniden kuyruğa alır.
+fn periodic_complete(db: usize, p: &mut Periodic, status: u32) {
+    let code = status >> 24;
+    if code != CC_SUCCESS && code != CC_SHORT_PACKET {
+        serial_println!("[XHCI] Yuva {} DCI {}: periyodik aktarım durdu (kod {})", p.slot, p.dci, code);
+        p.handler = None;
+        return;
+    }
+    let len = p.len.saturating_sub((status & 0xFF_FFFF) as usize);
+    if let Some(handler) = p.handler {
+        handler(p.cookie, unsafe { core::slice::from_raw_parts(p.buf as *const u8, len) });
+    }
+    queue_periodic(db, p);
+}
+
+fn queue_periodic(db: usize, p: &mut Periodic) {
+    p.ring.push([p.buf as u32, (p.buf as u64 >> 32) as u32, p.len as u32, TRB_NORMAL << 10 | TRB_IOC | TRB_ISP]);
+    w32(db + p.slot as usize * 4, p.dci as u32);
+}
+
+impl HostController for Xhci {
+    fn name(&self) -> &'static str {
+        "xhci"
+    }
+
+    fn control(&self, slot: u8, setup: SetupPacket, data: &mut [u8]) -> Result<usize, KernelError> {
+        self.with_state(|s| self.control_locked(s, slot, setup, data))
+    }
+
+    fn configure_endpoint(&self, slot: u8, ep: &Endpoint) -> Result<(), KernelError> {
+        self.with_state(|s| {
+            let sl = *s.slots.get(slot as usize).filter(|sl| sl.output_ctx != 0).ok_or(KernelError::NotFound)?;
+            let dci = endpoint_dci(ep.address);
+            let ep_type = match (ep.kind(), ep.is_in()) {
+                (EndpointKind::Bulk, true) => EP_TYPE_BULK_IN,
+                (EndpointKind::Bulk, false) => EP_TYPE_BULK_OUT,
+                (EndpointKind::Interrupt, true) => EP_TYPE_INTERRUPT_IN,
+                (EndpointKind::Interrupt, false) => EP_TYPE_INTERRUPT_OUT,
+                _ => return Err(KernelError::InvalidArgument),
+            };
+            let ring = Ring::new()?;
+            let cs = s.ctx_size;
+
+            // Yuva bağlamı çıktı bağlamından kopyalanır; bağlam girdisi sayısı büyütülür
+            let input = sl.input_ctx;
+            unsafe {
+                core::ptr::write_bytes(input as *mut u8, 0, PAGE);
+                core::ptr::copy_nonoverlapping(sl.output_ctx as *const u8, (input + cs) as *mut u8, cs);
+            }
+            ctx_write(input, 1, (1 << dci) | 1);
+            let slot0 = ctx_read(input + cs, 0);
+            let entries = ((slot0 >> 27) & 0x1F).max(dci as u32);
+            ctx_write(input + cs, 0, (slot0 & !(0x1F << 27)) | entries << 27);
+
+            let interval = if ep.kind() == EndpointKind::Interrupt { interval_exponent(sl.speed, ep.interval) } else { 0 };
+            let esit = if ep.kind() == EndpointKind::Interrupt { ep.max_packet as u32 } else { 0 };
+            write_ep_ctx(input + (dci + 1) * cs, ep_type, ep.max_packet as u32, interval, ring.dequeue_pointer(), esit.max(8));
+            if esit > 0 {
+                ctx_write(input + (dci + 1) * cs, 4, esit << 16 | esit);
+            }
+
+            let trb = [input as u32, (input as u64 >> 32) as u32, 0, TRB_CONFIGURE_EP << 10 | (slot as u32) << 24];
+            self.command(s, trb)?;
+            s.slots[slot as usize].rings[dci] = ring;
+            Ok(())
+        })
+    }
+
+    fn bulk(&self, slot: u8, ep_addr: u8, data: &mut [u8]) -> Result<usize, KernelError> {
+        let dci = endpoint_dci(ep_addr);
+        let is_in = ep_addr & 0x80 != 0;
+        self.with_state(|s| {
+            let mut done = 0;
+            for chunk in data.chunks_mut(PAGE) {
+                let bounce = s.bounce;
+                let ring = s.slots.get_mut(slot as usize).map(|sl| &mut sl.rings[dci]).filter(|r| r.base != 0);
+                let Some(ring) = ring else { return Err(KernelError::NotFound) };
+                if !is_in {
+                    unsafe { core::ptr::copy_nonoverlapping(chunk.as_ptr(), bounce as *mut u8, chunk.len()) };
+                }
+                self.done_trb.store(0, Ordering::Relaxed);
+                let trb = [bounce as u32, (bounce as u64 >> 32) as u32, chunk.len() as u32, TRB_NORMAL << 10 | TRB_IOC | TRB_ISP];
+                let last = ring.push(trb);
+                let residual = self.ring_and_wait(s, slot, dci as u8, last)? as usize;
+                let len = chunk.len().saturating_sub(residual);
+                if is_in {
+                    unsafe { core::ptr::copy_nonoverlapping(bounce as *const u8, chunk.as_mut_ptr(), len) };
+                }
+                done += len;
+                if len < chunk.len() {
+                    break;
+                }
+            }
+            Ok(done)
+        })
+    }
+
+    fn start_interrupt_in(
+        &self,
+        slot: u8,
+        ep_addr: u8,
+        len: usize,
+        handler: ReportHandler,
+        cookie: usize,
+    ) -> Result<(), KernelError> {
+        let dci = endpoint_dci(ep_addr);
+        if len == 0 || len > PAGE || ep_addr & 0x80 == 0 {
+            return Err(KernelError::InvalidArgument);
+        }
+        // Halka periyodik tabloya taşınır; eşzamanlı aktarımlar artık kullanamaz
+        let ring = self.with_state(|s| {
+            let sl = s.slots.get_mut(slot as usize).ok_or(KernelError::NotFound)?;
+            let ring = sl.rings[dci];
+            if ring.base == 0 {
+                return Err(KernelError::NotFound);
+            }
+            sl.rings[dci] = Ring::NONE;
+            Ok(ring)
+        })?;
+        let buf = alloc_page()?;
+
+        loop {
+            let queued = self.try_with_events(|ev| {
+                let p = ev.periodic.iter_mut().find(|p| p.handler.is_none()).ok_or(KernelError::OutOfMemoryStatic)?;
+                *p = Periodic { slot, dci: dci as u8, ring, buf, len, handler: Some(handler), cookie };
+                queue_periodic(ev.db, p);
+                Ok(())
+            });
+            if let Some(result) = queued {
+                return result;
+            }
+        }
+    }
+
+    fn poll(&self) {
+        self.process_events();
+    }
+}
+
+// -----------------------------------------------------------------------------
+// YARDIMCILAR
+// -----------------------------------------------------------------------------
+
+#[inline(always)]
+fn r8(addr: usize) -> u8 {
+    unsafe { read_volatile(addr as *const u8) }
+}
+
+#[inline(always)]
+fn r32(addr: usize) -> u32 {
+    unsafe { read_volatile(addr as *const u32) }
+}
+
+#[inline(always)]
+fn w32(addr: usize, value: u32) {
+    unsafe { write_volatile(addr as *mut u32, value) }
+}
+
+/// 64 bit yazmaç yazımı (yalnızca 32 bit erişim destekleyen denetleyiciler için iki parça).
+fn w64(addr: usize, value: u64) {
+    w32(addr, value as u32);
+    w32(addr + 4, (value >> 32) as u32);
+}
+
+fn wait_reg(addr: usize, mask: u32, want: u32) -> Result<(), KernelError> {
+    for _ in 0..TIMEOUT_SPINS {
+        if r32(addr) & mask == want {
+            return Ok(());
+        }
+    }
+    Err(KernelError::GenericFailure)
+}
+
+fn ctx_write(ctx: usize, dword: usize, value: u32) {
+    unsafe { write_volatile((ctx + dword * 4) as *mut u32, value) }
+}
+
+fn ctx_read(ctx: usize, dword: usize) -> u32 {
+    unsafe { read_volatile((ctx + dword * 4) as *const u32) }
+}
+
+/// Uç nokta bağlamını yazar (CErr = 3).
+fn write_ep_ctx(ctx: usize, ep_type: u32, max_packet: u32, interval: u32, dequeue: u64, avg_trb_len: u32) {
+    ctx_write(ctx, 0, interval << 16);
+    ctx_write(ctx, 1, (3 << 1) | ep_type << 3 | max_packet << 16);
+    ctx_write(ctx, 2, dequeue as u32);
+    ctx_write(ctx, 3, (dequeue >> 32) as u32);
+    ctx_write(ctx, 4, avg_trb_len);
+}
+
+/// Uç nokta adresinden aygıt bağlamı indeksi (DCI): OUT 2n, IN 2n+1.
+fn endpoint_dci(address: u8) -> usize {
+    (address & 0x0F) as usize * 2 + (address >> 7) as usize
+}
+
+/// PORTSC hız kimliği.
+fn speed_id(speed: Speed) -> u32 {
+    match speed {
+        Speed::Full => 1,
+        Speed::Low => 2,
+        Speed::High => 3,
+        Speed::Super => 4,
+    }
+}
+
+fn default_max_packet(speed: Speed) -> u32 {
+    match speed {
+        Speed::Low | Speed::Full => 8,
+        Speed::High => 64,
+        Speed::Super => 512,
+    }
+}
+
+/// bInterval'ı xHCI aralık üssüne çevirir (periyot = 125 us x 2^üs).
+fn interval_exponent(speed: Speed, b_interval: u8) -> u32 {
+    match speed {
+        // bInterval milisaniye (çerçeve) cinsinden
+        Speed::Low | Speed::Full => {
+            let micro_frames = (b_interval.max(1) as u32) * 8;
+            (31 - micro_frames.leading_zeros()).clamp(3, 10)
+        }
+        _ => (b_interval.clamp(1, 16) - 1) as u32,
+    }
+}
+
+/// Yazıldığında yan etkisi olmayan PORTSC değeri (değişiklik bitleri ve PED sıfır).
+fn port_neutral(portsc: u32) -> u32 {
+    portsc & !(PORT_PED | PORT_CHANGE_MASK)
+}
+
+/// Kök portu sıfırlar ve etkinleştirilmiş portun hızını döndürür.
+fn reset_port(reg: usize) -> Result<Speed, KernelError> {
+    let portsc = r32(reg);
+    // USB3 portları bağlantıda kendiliğinden etkinleşir
+    if portsc & PORT_PED == 0 {
+        w32(reg, port_neutral(portsc) | PORT_PR);
+        wait_reg(reg, PORT_PRC, PORT_PRC)?;
+        w32(reg, port_neutral(r32(reg)) | PORT_PRC);
+    }
+    let portsc = r32(reg);
+    if portsc & PORT_PED == 0 {
+        return Err(KernelError::NotFound);
+    }
+    match (portsc >> PORT_SPEED_SHIFT) & 0xF {
+        1 => Ok(Speed::Full),
+        2 => Ok(Speed::Low),
+        3 => Ok(Speed::High),
+        4.. => Ok(Speed::Super),
+        _ => Err(KernelError::GenericFailure),
+    }
+}
+
+/// Ürün yazılımından (BIOS) denetleyici sahipliğini devralır.
+fn bios_handoff(base: usize) {
+    let mut off = ((r32(base + HCCPARAMS1) >> 16) as usize) << 2;
+    while off != 0 {
+        let cap = r32(base + off);
+        if cap & 0xFF == XCAP_LEGACY {
+            w32(base + off, cap | LEGACY_OS_OWNED);
+            if wait_reg(base + off, LEGACY_BIOS_OWNED, 0).is_err() {
+                serial_println!("[XHCI] BIOS sahipliği bırakmadı; devam ediliyor");
+            }
+            // SMI kaynaklarını kapat
+            w32(base + off + 4, 0);
+            return;
+        }
+        let next = ((cap >> 8) & 0xFF) as usize;
+        if next == 0 {
+            return;
+        }
+        off += next << 2;
+    }
+}
+
+// -----------------------------------------------------------------------------
+// KESİLER VE KEŞİF
+// -----------------------------------------------------------------------------
+
+/// En fazla örnek sayısı.
+const MAX_INSTANCES: usize = 2;
+
+#[allow(clippy::declare_interior_mutable_const)]
+const EMPTY: Xhci = Xhci::empty();
+static INSTANCES: [Xhci; MAX_INSTANCES] = [EMPTY; MAX_INSTANCES];
+
+/// Mimari kesme dağıtıcısından çağrılır.
+///
+/// # Dönüş Değeri
+/// Kesme bir xHCI örneğine aitse `true`.
+pub fn handle_irq(irq: u32) -> bool {
+    let mut handled = false;
+    for hc in INSTANCES.iter().filter(|i| i.used.load(Ordering::Acquire) && i.irq.load(Ordering::Relaxed) == irq) {
+        // Olaylar işlenemese bile kesme onaylanır; bekleyen olayları kilit sahibi işler
+        let (op, ir) = unsafe { ((*hc.ev.get()).op, (*hc.ev.get()).ir) };
+        if op != 0 {
+            w32(op + USBSTS, STS_EINT);
+            w32(ir + IMAN, IMAN_IP | IMAN_IE);
+        }
+        hc.process_events();
+        handled = true;
+    }
+    handled
+}
+
+fn claim(base: u64, irq: u32) -> Result<&'static Xhci, KernelError> {
+    let hc = INSTANCES
+        .iter()
+        .find(|i| !i.used.swap(true, Ordering::AcqRel))
+        .ok_or(KernelError::OutOfMemoryStatic)?;
+    hc.base.store(base, Ordering::Relaxed);
+    hc.irq.store(irq, Ordering::Relaxed);
+    Ok(hc)
+}
+
+fn bring_up(hc: &'static Xhci) {
+    let base = hc.base.load(Ordering::Relaxed) as usize;
+    if let Err(e) = hc.start(base) {
+        serial_println!("[XHCI] {:#x}: başlatılamadı: {:?}", base, e);
+        return;
+    }
+    let ports = hc.with_state(|s| s.max_ports);
+    serial_println!("[XHCI] {:#x}: {} port, IRQ {}", base, ports, hc.irq.load(Ordering::Relaxed) as i32);
+    let _ = usb::register_controller(hc);
+    hc.scan_ports();
+}
+
+fn probe() -> Result<(), KernelError> {
+    for dev in crate::drivers::pci::find_class(0x0C, 0x03, 0x30) {
+        let Some(bar) = dev.bar(0) else { continue };
+        dev.enable();
+        dev.set_intx(true);
+
+        // INTx eşlemesi olmayan mimarilerde yoklamayla çalışılır
+        let irq = crate::arch::interrupt::route_pci_intx(dev.irq_line).unwrap_or(u32::MAX);
+
+        bring_up(claim(bar, irq)?);
+    }
+
+    if let Some(fdt) = crate::fdt::get() {
+        for node in fdt.find_compatible("generic-xhci").filter(|n| n.is_enabled()) {
+            let Some((base, _)) = node.reg(0) else { continue };
+            let irq = crate::arch::interrupt::decode_fdt_irq(&node).unwrap_or(u32::MAX);
+            bring_up(claim(base, irq)?);
+        }
+    }
+    Ok(())
+}
+
+crate::initcall!(drivers, "xhci", probe);
@@ -0,0 +1,357 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/drivers/virtio/pci.rs
+// virtio PCI taşıması (virtio 1.2, 4.1): amd64 QEMU'nun varsayılan
+// makinelerinde virtio aygıtları MMIO değil PCI fonksiyonudur.
+//
+// Aygıtlar üretici 0x1AF4 ile bulunur: 0x1040 + tür (modern) veya
+// 0x1000-0x103F (geçiş dönemi; tür alt sistem kimliğindedir). Yapılar
+// BAR'larda yer alır ve yerleri üreticiye özgü PCI yetenekleriyle bildirilir:
+//
+// * COMMON_CFG: özellik seçimi, aygıt durumu, kuyruk seçimi ve adresleri.
+// * NOTIFY_CFG: kuyruk bildirimi; adres `taban + queue_notify_off *
+//   notify_off_multiplier`.
+// * ISR_CFG: okunduğunda temizlenen kesme durumu baytı (INTx yolu).
+// * DEVICE_CFG: aygıta özgü yapılandırma alanı.
+//
+// MSI-X: aygıtın MSI-X tablosu `Transport::msix` ile sürücüye verilir;
+// sürücü girdileri kesme denetleyicisinin ileti adres/verisiyle programlayıp
+// kuyruklara `set_queue_vector` ile atar. Atanmayan kuyruklar INTx ve ISR
+// kullanır.
+//
+// Varsayım: BAR'lar birebir eşlenmiştir (sanal = fiziksel). Yalnızca bellek
+// BAR'ları desteklenir; eski (legacy) G/Ç arayüzü kullanılmaz.
+
+use core::fmt;
+use core::sync::atomic::{AtomicBool, AtomicU16, AtomicU32, AtomicUsize, Ordering};
+use crate::drivers::pci::{self, Bdf, Msix, PciDevice};
+use crate::drivers::virtio::virtio::{self, DeviceType, Transport};
+use crate::endian;
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// YAPILAR
+// -----------------------------------------------------------------------------
+
+const VIRTIO_VENDOR: u16 = 0x1AF4;
+const TRANSITIONAL_FIRST: u16 = 0x1000;
+const TRANSITIONAL_LAST: u16 = 0x103F;
+const MODERN_BASE: u16 = 0x1040;
+const MODERN_LAST: u16 = 0x107F;
+
+// Üreticiye özgü yetenek: cfg_type (bayt 3), bar (4), offset (8), length (12)
+const CAP_CFG_TYPE: u16 = 3;
+const CAP_BAR: u16 = 4;
+const CAP_OFFSET: u16 = 8;
+const CAP_NOTIFY_MULTIPLIER: u16 = 16;
+
+const CFG_COMMON: u8 = 1;
+const CFG_NOTIFY: u8 = 2;
+const CFG_ISR: u8 = 3;
+const CFG_DEVICE: u8 = 4;
+
+// COMMON_CFG yerleşimi
+const DEVICE_FEATURE_SELECT: usize = 0x00;
+const DEVICE_FEATURE: usize = 0x04;
+const DRIVER_FEATURE_SELECT: usize = 0x08;
+const DRIVER_FEATURE: usize = 0x0C;
+const CONFIG_MSIX_VECTOR: usize = 0x10;
+const NUM_QUEUES: usize = 0x12;
+const DEVICE_STATUS: usize = 0x14;
+const CONFIG_GENERATION: usize = 0x15;
+const QUEUE_SELECT: usize = 0x16;
+const QUEUE_SIZE: usize = 0x18;
+const QUEUE_MSIX_VECTOR: usize = 0x1A;
+const QUEUE_ENABLE: usize = 0x1C;
+const QUEUE_NOTIFY_OFF: usize = 0x1E;
+const QUEUE_DESC: usize = 0x20;
+const QUEUE_DRIVER: usize = 0x28;
+const QUEUE_DEVICE: usize = 0x30;
+
+/// Aynı anda sürülebilecek en fazla virtio PCI fonksiyonu.
+const MAX_PCI_DEVICES: usize = 8;
+
+// -----------------------------------------------------------------------------
+// ERİŞİM YARDIMCILARI (küçük sonlu)
+// -----------------------------------------------------------------------------
+
+// virtio yapıları işlemciden bağımsız olarak küçük sonludur; büyük sonlu
+// yapılarda dönüşümü `endian` yapar.
+
+#[inline(always)]
+fn r8(addr: usize) -> u8 {
+    unsafe { crate::mmio::read(addr) }
+}
+
+#[inline(always)]
+fn w8(addr: usize, value: u8) {
+    unsafe { crate::mmio::write(addr, value) }
+}
+
+#[inline(always)]
+fn r16(addr: usize) -> u16 {
+    unsafe { endian::mmio_read_le16(addr) }
+}
+
+#[inline(always)]
+fn w16(addr: usize, value: u16) {
+    unsafe { endian::mmio_write_le16(addr, value) }
+}
+
+#[inline(always)]
+fn r32(addr: usize) -> u32 {
+    unsafe { endian::mmio_read_le32(addr) }
+}
+
+#[inline(always)]
+fn w32(addr: usize, value: u32) {
+    unsafe { endian::mmio_write_le32(addr, value) }
+}
+
+/// 64 bitlik alanlar iki 32 bitlik yazmayla yazılır (4.1.3.1).
+#[inline(always)]
+fn w64(addr: usize, value: u64) {
+    w32(addr, value as u32);
+    w32(addr + 4, (value >> 32) as u32);
+}
+
+// -----------------------------------------------------------------------------
+// TAŞIMA
+// -----------------------------------------------------------------------------
+
+/// Bir virtio PCI fonksiyonu. Alanlar `probe` sırasında bir kez yazılır.
+pub struct PciTransport {
+    /// Veri yolu << 16 | aygıt << 8 | fonksiyon.
+    bdf: AtomicU32,
+    type_id: AtomicU16,
+    common: AtomicUsize,
+    notify: AtomicUsize,
+    notify_multiplier: AtomicU32,
+    isr: AtomicUsize,
+    /// 0: aygıta özgü yapılandırma alanı yok.
+    device: AtomicUsize,
+    used: AtomicBool,
+}
+
+impl PciTransport {
+    const fn empty() -> Self {
+        PciTransport {
+            bdf: AtomicU32::new(0),
+            type_id: AtomicU16::new(0),
+            common: AtomicUsize::new(0),
+            notify: AtomicUsize::new(0),
+            notify_multiplier: AtomicU32::new(0),
+            isr: AtomicUsize::new(0),
+            device: AtomicUsize::new(0),
+            used: AtomicBool::new(false),
+        }
+    }
+
+    fn bdf(&self) -> Bdf {
+        let raw = self.bdf.load(Ordering::Relaxed);
+        Bdf { bus: (raw >> 16) as u8, dev: (raw >> 8) as u8, func: raw as u8 }
+    }
+
+    fn common(&self) -> usize {
+        self.common.load(Ordering::Relaxed)
+    }
+
+    fn pci_device(&self) -> Option<PciDevice> {
+        let bdf = self.bdf();
+        pci::devices().find(|d| d.bdf == bdf)
+    }
+}
+
+impl Transport for PciTransport {
+    fn location(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        write!(out, "pci {}", self.bdf())
+    }
+
+    fn device_type(&self) -> DeviceType {
+        DeviceType::from_id(self.type_id.load(Ordering::Relaxed))
+    }
+
+    fn device_features(&self) -> u64 {
+        let common = self.common();
+        w32(common + DEVICE_FEATURE_SELECT, 0);
+        let low = r32(common + DEVICE_FEATURE);
+        w32(common + DEVICE_FEATURE_SELECT, 1);
+        let high = r32(common + DEVICE_FEATURE);
+        u64::from(high) << 32 | u64::from(low)
+    }
+
+    fn set_driver_features(&self, features: u64) {
+        let common = self.common();
+        w32(common + DRIVER_FEATURE_SELECT, 0);
+        w32(common + DRIVER_FEATURE, features as u32);
+        w32(common + DRIVER_FEATURE_SELECT, 1);
+        w32(common + DRIVER_FEATURE, (features >> 32) as u32);
+    }
+
+    fn status(&self) -> u8 {
+        r8(self.common() + DEVICE_STATUS)
+    }
+
+    fn set_status(&self, status: u8) {
+        w8(self.common() + DEVICE_STATUS, status);
+    }
+
+    fn queue_count(&self) -> u16 {
+        r16(self.common() + NUM_QUEUES)
+    }
+
+    fn queue_max_size(&self, queue: u16) -> u16 {
+        let common = self.common();
+        w16(common + QUEUE_SELECT, queue);
+        r16(common + QUEUE_SIZE)
+    }
+
+    fn setup_queue(&self, queue: u16, size: u16, desc: u64, driver: u64, device: u64) -> Result<(), KernelError> {
+        let max = self.queue_max_size(queue);
+        if size == 0 || size > max || !size.is_power_of_two() {
+            return Err(KernelError::InvalidArgument);
+        }
+        let common = self.common();
+        w16(common + QUEUE_SELECT, queue);
+        w16(common + QUEUE_SIZE, size);
+        w64(common + QUEUE_DESC, desc);
+        w64(common + QUEUE_DRIVER, driver);
+        w64(common + QUEUE_DEVICE, device);
+        w16(common + QUEUE_ENABLE, 1);
+        Ok(())
+    }
+
+    fn notify(&self, queue: u16) {
+        let common = self.common();
+        w16(common + QUEUE_SELECT, queue);
+        let offset = r16(common + QUEUE_NOTIFY_OFF) as usize * self.notify_multiplier.load(Ordering::Relaxed) as usize;
+        w16(self.notify.load(Ordering::Relaxed) + offset, queue);
+    }
+
+    fn ack_interrupt(&self) -> u8 {
+        r8(self.isr.load(Ordering::Relaxed))
+    }
+
+    fn read_config(&self, offset: usize, buf: &mut [u8]) {
+        let base = self.device.load(Ordering::Relaxed);
+        if base == 0 {
+            buf.fill(0);
+            return;
+        }
+        // Okuma, config_generation değişmeden tamamlanana kadar yinelenir (4.1.4.3.1)
+        let generation = self.common() + CONFIG_GENERATION;
+        loop {
+            let before = r8(generation);
+            for (i, byte) in buf.iter_mut().enumerate() {
+                *byte = r8(base + offset + i);
+            }
+            if r8(generation) == before {
+                break;
+            }
+        }
+    }
+
+    fn set_queue_vector(&self, queue: u16, vector: u16) -> bool {
+        let common = self.common();
+        w16(common + QUEUE_SELECT, queue);
+        w16(common + QUEUE_MSIX_VECTOR, vector);
+        r16(common + QUEUE_MSIX_VECTOR) == vector
+    }
+
+    fn set_config_vector(&self, vector: u16) -> bool {
+        let common = self.common();
+        w16(common + CONFIG_MSIX_VECTOR, vector);
+        r16(common + CONFIG_MSIX_VECTOR) == vector
+    }
+
+    fn msix(&self) -> Option<Msix> {
+        self.pci_device()?.msix()
+    }
+}
+
+#[allow(clippy::declare_interior_mutable_const)]
+const EMPTY: PciTransport = PciTransport::empty();
+static TRANSPORTS: [PciTransport; MAX_PCI_DEVICES] = [EMPTY; MAX_PCI_DEVICES];
+
+// -----------------------------------------------------------------------------
+// ALGILAMA
+// -----------------------------------------------------------------------------
+
+/// Aygıt kimliğinden virtio aygıt türü.
+fn type_id(dev: &PciDevice) -> Option<u16> {
+    match dev.device {
+        TRANSITIONAL_FIRST..=TRANSITIONAL_LAST => Some(dev.subsystem_id()),
+        MODERN_BASE..=MODERN_LAST => Some(dev.device - MODERN_BASE),
+        _ => None,
+    }
+}
+
+/// Üreticiye özgü yeteneği çözer: (tür, yapının adresi, yetenek ofseti).
+fn resolve_cap(dev: &PciDevice, cap: u16) -> Option<(u8, usize, u16)> {
+    let cfg_type = pci::read8(dev.bdf, cap + CAP_CFG_TYPE);
+    let bar = dev.bar(pci::read8(dev.bdf, cap + CAP_BAR) as usize)?;
+    let offset = pci::read32(dev.bdf, cap + CAP_OFFSET);
+    Some((cfg_type, bar as usize + offset as usize, cap))
+}
+
+fn attach(dev: PciDevice, type_id: u16) -> Result<(), KernelError> {
+    let (mut common, mut notify, mut isr, mut device, mut multiplier) = (0, 0, 0, 0, 0);
+    for (_, cap) in dev.capabilities().filter(|&(id, _)| id == pci::CAP_VENDOR) {
+        // İlk uygun yapı tercih edilir (4.1.4)
+        match resolve_cap(&dev, cap) {
+            Some((CFG_COMMON, addr, _)) if common == 0 => common = addr,
+            Some((CFG_NOTIFY, addr, cap)) if notify == 0 => {
+                notify = addr;
+                multiplier = pci::read32(dev.bdf, cap + CAP_NOTIFY_MULTIPLIER);
+            }
+            Some((CFG_ISR, addr, _)) if isr == 0 => isr = addr,
+            Some((CFG_DEVICE, addr, _)) if device == 0 => device = addr,
+            _ => {}
+        }
+    }
+    if common == 0 || notify == 0 || isr == 0 {
+        // Yalnızca eski arayüzü sunan aygıt
+        return Err(KernelError::NotFound);
+    }
+
+    let slot = TRANSPORTS
+        .iter()
+        .position(|t| !t.used.swap(true, Ordering::AcqRel))
+        .ok_or(KernelError::OutOfMemoryStatic)?;
+    let transport = &TRANSPORTS[slot];
+    let bdf = dev.bdf;
+    transport.bdf.store(u32::from(bdf.bus) << 16 | u32::from(bdf.dev) << 8 | u32::from(bdf.func), Ordering::Relaxed);
+    transport.type_id.store(type_id, Ordering::Relaxed);
+    transport.common.store(common, Ordering::Relaxed);
+    transport.notify.store(notify, Ordering::Relaxed);
+    transport.notify_multiplier.store(multiplier, Ordering::Relaxed);
+    transport.isr.store(isr, Ordering::Relaxed);
+    transport.device.store(device, Ordering::Relaxed);
+
+    dev.enable();
+    dev.set_intx(true);
+    virtio::register(transport)?;
+    serial_println!(
+        "[VIRTIO] {} {} (pci{}, {} kuyruk)",
+        bdf,
+        transport.device_type().name(),
+        if dev.msix().is_some() { ", msi-x" } else { "" },
+        transport.queue_count()
+    );
+    Ok(())
+}
+
+fn probe() -> Result<(), KernelError> {
+    for dev in pci::devices().filter(|d| d.vendor == VIRTIO_VENDOR) {
+        let Some(type_id) = type_id(&dev) else { continue };
+        if let Err(e) = attach(dev, type_id) {
+            serial_println!("[VIRTIO] {}: atlandı: {:?}", dev.bdf, e);
+        }
+    }
+    Ok(())
+}
+
+// Aygıt sürücüleri (`drivers` seviyesi) `virtio::claim` ile aygıt alır; taşıma
+// onlardan önce, kesme denetleyicisiyle aynı seviyede taranır.
+crate::initcall!(irq, "virtio-pci", probe);
@@ -0,0 +1,274 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/drivers/virtio/virtio.rs
+// virtio 1.x ortak katmanı: taşıma (transport) arayüzü, durum/özellik
+// anlaşması ve aygıt kaydı.
+//
+// virtio aygıt sürücüleri (blk, net, ...) aygıta yalnızca `Transport`
+// üzerinden erişir; aygıtın PCI fonksiyonu (`pci.rs`) ya da MMIO penceresi
+// olması sürücüyü değiştirmez. Taşımalar buldukları aygıtları `register` ile
+// kaydeder, sürücüler `find(DeviceType)` ile alır.
+//
+// Açılış sırası (virtio 1.2, 3.1.1): `negotiate` aygıtı sıfırlar,
+// ACKNOWLEDGE | DRIVER bildirir, istenen özelliklerle aygıtın sunduklarının
+// kesişimini yazar ve FEATURES_OK'un kabulünü doğrular. Sürücü kuyruklarını
+// `Transport::setup_queue` ile kurduktan sonra `driver_ok` çağırır.
+//
+// Yalnızca "modern" (VERSION_1) arayüz desteklenir; yapılandırma alanı ve
+// kuyruk yapıları küçük sonludur (little-endian).
+//
+// Kabuk komutu: `lsvirtio`
+
+use core::cell::UnsafeCell;
+use crate::platformgeneric::spinlock::Spinlock;
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// SABİTLER
+// -----------------------------------------------------------------------------
+
+// Aygıt durumu bitleri
+pub const STATUS_ACKNOWLEDGE: u8 = 1;
+pub const STATUS_DRIVER: u8 = 2;
+pub const STATUS_DRIVER_OK: u8 = 4;
+pub const STATUS_FEATURES_OK: u8 = 8;
+pub const STATUS_NEEDS_RESET: u8 = 64;
+pub const STATUS_FAILED: u8 = 128;
+
+/// Modern arayüz; taşımalar bu özelliği her zaman ister.
+pub const F_VERSION_1: u64 = 1 << 32;
+
+// ISR durum bitleri
+pub const ISR_QUEUE: u8 = 1 << 0;
+pub const ISR_CONFIG: u8 = 1 << 1;
+
+/// MSI-X vektörü atanmadı (INTx ve ISR kullanılır).
+pub const NO_VECTOR: u16 = 0xFFFF;
+
+/// Kayıt edilebilecek en fazla aygıt.
+pub const MAX_DEVICES: usize = 8;
+
+/// virtio aygıt türü (virtio 1.2, 5).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceType {
+    Net,
+    Block,
+    Console,
+    Entropy,
+    Balloon,
+    Scsi,
+    Gpu,
+    Input,
+    Other(u16),
+}
+
+impl DeviceType {
+    pub fn from_id(id: u16) -> DeviceType {
+        match id {
+            1 => DeviceType::Net,
+            2 => DeviceType::Block,
+            3 => DeviceType::Console,
+            4 => DeviceType::Entropy,
+            5 => DeviceType::Balloon,
+            8 => DeviceType::Scsi,
+            16 => DeviceType::Gpu,
+            18 => DeviceType::Input,
+            other => DeviceType::Other(other),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            DeviceType::Net => "net",
+            DeviceType::Block => "blk",
+            DeviceType::Console => "console",
+            DeviceType::Entropy => "rng",
+            DeviceType::Balloon => "balloon",
+            DeviceType::Scsi => "scsi",
+            DeviceType::Gpu => "gpu",
+            DeviceType::Input => "input",
+            DeviceType::Other(_) => "?",
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// TAŞIMA ARAYÜZÜ
+// -----------------------------------------------------------------------------
+
+/// Bir virtio aygıtına erişim yolu (PCI veya MMIO).
+pub trait Transport: Sync {
+    /// Taşıma adı ve konumu (örn. "pci 00:03.0").
+    fn location(&self, out: &mut dyn core::fmt::Write) -> core::fmt::Result;
+
+    fn device_type(&self) -> DeviceType;
+
+    /// Aygıtın sunduğu özellikler (64 bit).
+    fn device_features(&self) -> u64;
+
+    /// Sürücünün kabul ettiği özellikleri yazar.
+    fn set_driver_features(&self, features: u64);
+
+    fn status(&self) -> u8;
+
+    /// Durum yazmacını yazar (0: aygıtı sıfırlar).
+    fn set_status(&self, status: u8);
+
+    /// Kuyruk sayısı.
+    fn queue_count(&self) -> u16;
+
+    /// Kuyruğun aygıtça desteklenen en büyük boyu (0: kuyruk yok).
+    fn queue_max_size(&self, queue: u16) -> u16;
+
+    /// Kuyruğun boyunu ve tanımlayıcı / sürücü (avail) / aygıt (used)
+    /// alanlarının fiziksel adreslerini yazar ve kuyruğu etkinleştirir.
+    fn setup_queue(&self, queue: u16, size: u16, desc: u64, driver: u64, device: u64) -> Result<(), KernelError>;
+
+    /// Aygıta kuyrukta yeni iş olduğunu bildirir.
+    fn notify(&self, queue: u16);
+
+    /// Kesme durumunu okur ve onaylar (`ISR_QUEUE`, `ISR_CONFIG`).
+    fn ack_interrupt(&self) -> u8;
+
+    /// Aygıta özgü yapılandırma alanından okur.
+    fn read_config(&self, offset: usize, buf: &mut [u8]);
+
+    /// Kuyruğa MSI-X vektörü atar (`NO_VECTOR`: kaldırır). Taşıma MSI-X
+    /// desteklemiyorsa veya aygıt reddettiyse `false`.
+    fn set_queue_vector(&self, _queue: u16, _vector: u16) -> bool {
+        false
+    }
+
+    /// Yapılandırma değişikliği kesmesine MSI-X vektörü atar.
+    fn set_config_vector(&self, _vector: u16) -> bool {
+        false
+    }
+
+    /// Aygıtın MSI-X tablosu (yalnızca PCI).
+    fn msix(&self) -> Option<crate::drivers::pci::Msix> {
+        None
+    }
+}
+
+// -----------------------------------------------------------------------------
+// AÇILIŞ YARDIMCILARI
+// -----------------------------------------------------------------------------
+
+/// Aygıtı sıfırlar ve özellikleri anlaşır (bkz. dosya başı).
+///
+/// # Dönüş Değeri
+/// Anlaşılan özellikler (`wanted`, aygıtın sundukları ve `F_VERSION_1`
+/// kesişimi).
+///
+/// # Hatalar
+/// * `Device(Unsupported)`: Aygıt modern arayüzü sunmuyor.
+/// * `Device(Rejected)`: Aygıt özellik kümesini reddetti.
+pub fn negotiate(transport: &dyn Transport, wanted: u64) -> Result<u64, KernelError> {
+    transport.set_status(0);
+    while transport.status() != 0 {
+        core::hint::spin_loop();
+    }
+    transport.set_status(STATUS_ACKNOWLEDGE);
+    transport.set_status(STATUS_ACKNOWLEDGE | STATUS_DRIVER);
+
+    let offered = transport.device_features();
+    if offered & F_VERSION_1 == 0 {
+        transport.set_status(STATUS_FAILED);
+        return Err(DeviceError::Unsupported.into());
+    }
+    let features = offered & (wanted | F_VERSION_1);
+    transport.set_driver_features(features);
+    transport.set_status(STATUS_ACKNOWLEDGE | STATUS_DRIVER | STATUS_FEATURES_OK);
+    if transport.status() & STATUS_FEATURES_OK == 0 {
+        transport.set_status(STATUS_FAILED);
+        return Err(DeviceError::Rejected.into());
+    }
+    Ok(features)
+}
+
+/// Kuyruklar kurulduktan sonra aygıtı çalışır duruma alır.
+pub fn driver_ok(transport: &dyn Transport) {
+    transport.set_status(transport.status() | STATUS_DRIVER_OK);
+}
+
+/// Yapılandırma alanından küçük sonlu 16 bitlik alan okur.
+pub fn config_u16(transport: &dyn Transport, offset: usize) -> u16 {
+    let mut buf = [0u8; 2];
+    transport.read_config(offset, &mut buf);
+    crate::endian::read_le16(&buf, 0).unwrap_or(0)
+}
+
+/// Yapılandırma alanından küçük sonlu 32 bitlik alan okur.
+pub fn config_u32(transport: &dyn Transport, offset: usize) -> u32 {
+    let mut buf = [0u8; 4];
+    transport.read_config(offset, &mut buf);
+    crate::endian::read_le32(&buf, 0).unwrap_or(0)
+}
+
+/// Yapılandırma alanından küçük sonlu 64 bitlik alan okur (örn. blk `capacity`).
+pub fn config_u64(transport: &dyn Transport, offset: usize) -> u64 {
+    let mut buf = [0u8; 8];
+    transport.read_config(offset, &mut buf);
+    crate::endian::read_le64(&buf, 0).unwrap_or(0)
+}
+
+// -----------------------------------------------------------------------------
+// AYGIT KAYDI
+// -----------------------------------------------------------------------------
+
+struct DeviceTable {
+    lock: Spinlock,
+    devices: UnsafeCell<[Option<(&'static dyn Transport, bool)>; MAX_DEVICES]>,
+}
+
+// Tabloya erişim `lock` ile korunur.
+unsafe impl Sync for DeviceTable {}
+
+static DEVICES: DeviceTable = DeviceTable {
+    lock: Spinlock::new(),
+    devices: UnsafeCell::new([None; MAX_DEVICES]),
+};
+
+fn with_devices<R>(f: impl FnOnce(&mut [Option<(&'static dyn Transport, bool)>; MAX_DEVICES]) -> R) -> R {
+    DEVICES.lock.lock();
+    // SAFETY: Kilit tutulduğu sürece tabloya tek erişen biziz.
+    let result = unsafe { f(&mut *DEVICES.devices.get()) };
+    DEVICES.lock.unlock();
+    result
+}
+
+/// Taşımanın bulduğu bir aygıtı kaydeder.
+pub fn register(transport: &'static dyn Transport) -> Result<usize, KernelError> {
+    with_devices(|devices| {
+        let id = devices.iter().position(Option::is_none).ok_or(KernelError::OutOfMemoryStatic)?;
+        devices[id] = Some((transport, false));
+        Ok(id)
+    })
+}
+
+/// Türü verilen, henüz bir sürücünün almadığı ilk aygıtı sürücüye verir.
+pub fn claim(kind: DeviceType) -> Option<&'static dyn Transport> {
+    with_devices(|devices| {
+        let (transport, claimed) = devices.iter_mut().flatten().find(|(t, claimed)| !*claimed && t.device_type() == kind)?;
+        *claimed = true;
+        Some(*transport)
+    })
+}
+
+// -----------------------------------------------------------------------------
+// KABUK
+// -----------------------------------------------------------------------------
+
+fn lsvirtio_command(_args: &[&str]) -> Result<(), KernelError> {
+    let devices = with_devices(|devices| *devices);
+    let mut console = crate::shell::Console;
+    for (id, (transport, claimed)) in devices.iter().enumerate().filter_map(|(i, d)| Some((i, (*d)?))) {
+        crate::serial_print!("  {:>2} {:<8} ", id, transport.device_type().name());
+        let _ = transport.location(&mut console);
+        serial_println!(" kuyruk {} {}", transport.queue_count(), if claimed { "[sürücülü]" } else { "" });
+    }
+    Ok(())
+}
+
+crate::shell_command!("lsvirtio", "virtio aygıtlarını listeler", lsvirtio_command);
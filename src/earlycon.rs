@@ -0,0 +1,372 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/earlycon.rs
+// Konsol UART'ının çalışma zamanında seçilmesi.
+//
+// Mimarilerin konsol sürücüleri UART'ı eskiden sabit bir adresten
+// kullanıyordu (örn. Raspberry Pi 4 PL011, QEMU virt 16550); farklı kartlarda
+// aynı çekirdek görüntüsü sessiz kalıyordu. `boot::init` sonunda `select`
+// konsolu şu sırayla belirler:
+//
+// 1. Aygıt ağacında `/chosen/stdout-path` (veya eski `linux,stdout-path`):
+//    yol ya da `/aliases` takma adı, isteğe bağlı `:<baud>[parite][bit]`
+//    son ekiyle. Düğümün `compatible`, `reg`, `reg-shift`,
+//    `clock-frequency` ve `current-speed` özellikleri okunur. `reg` adresi
+//    fiziksel kabul edilir (ara veri yolu `ranges` çevirisi yapılmaz).
+// 2. ACPI sistemlerinde SPCR tablosu (arayüz türü, genel adres yapısı,
+//    baud kodu).
+// 3. Komut satırında `earlycon=<tür>[,mmio|mmio32|io],<adres>[,<baud>]`
+//    (örn. `earlycon=uart8250,mmio32,0x10000000,115200n8`,
+//    `earlycon=pl011,0x9000000`).
+// 4. Hiçbiri yoksa mimarinin sabit varsayılanı (`Source::Default`).
+//
+// Sürücüler adresi her erişimde `mmio_base` / `io_base` ile alır; seçilen
+// UART sürücünün türünden değilse kendi varsayılanına döner. Böylece tür
+// uyuşmazlığında yanlış yazmaç düzenine yazılmaz.
+//
+// Kabuk komutu: `earlycon`
+
+use core::sync::atomic::{AtomicBool, AtomicU32, AtomicU8, AtomicUsize, Ordering};
+use crate::boot::BootInfo;
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// TİPLER VE DURUM
+// -----------------------------------------------------------------------------
+
+/// Varsayılan baud hızı.
+pub const DEFAULT_BAUD: u32 = 115_200;
+
+/// UART yazmaç düzeni.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum UartKind {
+    /// 8250/16550 uyumlu (DesignWare APB UART dahil).
+    Ns16550 = 1,
+    /// ARM PrimeCell PL011 (SBSA genel UART dahil).
+    Pl011 = 2,
+    /// SiFive UART.
+    SiFive = 3,
+}
+
+impl UartKind {
+    fn from_u8(value: u8) -> Option<UartKind> {
+        match value {
+            1 => Some(UartKind::Ns16550),
+            2 => Some(UartKind::Pl011),
+            3 => Some(UartKind::SiFive),
+            _ => None,
+        }
+    }
+
+    fn from_compatible(node: &crate::fdt::Node) -> Option<UartKind> {
+        const TABLE: [(&str, UartKind); 8] = [
+            ("ns16550a", UartKind::Ns16550),
+            ("ns16550", UartKind::Ns16550),
+            ("ns16450", UartKind::Ns16550),
+            ("ns8250", UartKind::Ns16550),
+            ("snps,dw-apb-uart", UartKind::Ns16550),
+            ("arm,pl011", UartKind::Pl011),
+            ("arm,sbsa-uart", UartKind::Pl011),
+            ("sifive,uart0", UartKind::SiFive),
+        ];
+        TABLE.iter().find(|(compat, _)| node.is_compatible(compat)).map(|&(_, kind)| kind)
+    }
+
+    fn from_earlycon(name: &str) -> Option<UartKind> {
+        match name {
+            "uart" | "uart8250" | "ns16550" | "ns16550a" => Some(UartKind::Ns16550),
+            "pl011" => Some(UartKind::Pl011),
+            "sifive" => Some(UartKind::SiFive),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            UartKind::Ns16550 => "ns16550",
+            UartKind::Pl011 => "pl011",
+            UartKind::SiFive => "sifive",
+        }
+    }
+}
+
+/// Seçimin kaynağı.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Source {
+    Default = 0,
+    StdoutPath = 1,
+    Spcr = 2,
+    Earlycon = 3,
+}
+
+impl Source {
+    fn from_u8(value: u8) -> Source {
+        match value {
+            1 => Source::StdoutPath,
+            2 => Source::Spcr,
+            3 => Source::Earlycon,
+            _ => Source::Default,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Source::Default => "varsayılan",
+            Source::StdoutPath => "stdout-path",
+            Source::Spcr => "spcr",
+            Source::Earlycon => "earlycon",
+        }
+    }
+}
+
+/// Seçilen konsol UART'ı.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConsoleDesc {
+    pub kind: UartKind,
+    /// MMIO adresi ya da G/Ç portu.
+    pub base: usize,
+    /// G/Ç portu mu (x86)?
+    pub port_io: bool,
+    /// Yazmaç aralığı = 1 << `reg_shift` bayt.
+    pub reg_shift: u8,
+    pub baud: u32,
+    /// UART giriş saati (bilinmiyorsa `None`).
+    pub clock_hz: Option<u32>,
+}
+
+static KIND: AtomicU8 = AtomicU8::new(0);
+static BASE: AtomicUsize = AtomicUsize::new(0);
+static PORT_IO: AtomicBool = AtomicBool::new(false);
+static REG_SHIFT: AtomicU8 = AtomicU8::new(0);
+static BAUD: AtomicU32 = AtomicU32::new(DEFAULT_BAUD);
+static CLOCK: AtomicU32 = AtomicU32::new(0);
+static SOURCE: AtomicU8 = AtomicU8::new(Source::Default as u8);
+
+fn store(desc: ConsoleDesc, source: Source) {
+    BASE.store(desc.base, Ordering::Relaxed);
+    PORT_IO.store(desc.port_io, Ordering::Relaxed);
+    REG_SHIFT.store(desc.reg_shift, Ordering::Relaxed);
+    BAUD.store(desc.baud, Ordering::Relaxed);
+    CLOCK.store(desc.clock_hz.unwrap_or(0), Ordering::Relaxed);
+    SOURCE.store(source as u8, Ordering::Relaxed);
+    // Tür en son yazılır: okuyucu onu gördüğünde adres hazırdır
+    KIND.store(desc.kind as u8, Ordering::Release);
+}
+
+/// Seçilen konsol (varsayılan kullanılıyorsa `None`).
+pub fn get() -> Option<ConsoleDesc> {
+    let kind = UartKind::from_u8(KIND.load(Ordering::Acquire))?;
+    Some(ConsoleDesc {
+        kind,
+        base: BASE.load(Ordering::Relaxed),
+        port_io: PORT_IO.load(Ordering::Relaxed),
+        reg_shift: REG_SHIFT.load(Ordering::Relaxed),
+        baud: BAUD.load(Ordering::Relaxed),
+        clock_hz: match CLOCK.load(Ordering::Relaxed) {
+            0 => None,
+            hz => Some(hz),
+        },
+    })
+}
+
+/// Seçilen UART `kind` türünde ve MMIO ise adresi.
+#[inline]
+pub fn mmio_base(kind: UartKind) -> Option<usize> {
+    (KIND.load(Ordering::Acquire) == kind as u8 && !PORT_IO.load(Ordering::Relaxed))
+        .then(|| BASE.load(Ordering::Relaxed))
+}
+
+/// Seçilen UART `kind` türünde ve G/Ç portundaysa port tabanı.
+#[inline]
+pub fn io_base(kind: UartKind) -> Option<usize> {
+    (KIND.load(Ordering::Acquire) == kind as u8 && PORT_IO.load(Ordering::Relaxed))
+        .then(|| BASE.load(Ordering::Relaxed))
+}
+
+/// Seçilen UART'ın yazmaç aralığı kaydırması (varsayılanda 0).
+#[inline]
+pub fn reg_shift() -> u8 {
+    REG_SHIFT.load(Ordering::Relaxed)
+}
+
+/// İstenen baud hızı.
+pub fn baud() -> u32 {
+    BAUD.load(Ordering::Relaxed)
+}
+
+/// Ürün yazılımının bildirdiği UART saati.
+pub fn clock_hz() -> Option<u32> {
+    get().and_then(|desc| desc.clock_hz)
+}
+
+/// Seçimin kaynağı.
+pub fn source() -> Source {
+    Source::from_u8(SOURCE.load(Ordering::Relaxed))
+}
+
+// -----------------------------------------------------------------------------
+// AYGIT AĞACI (stdout-path)
+// -----------------------------------------------------------------------------
+
+/// `115200n8` biçimindeki son ekten baud hızı.
+fn parse_baud(options: &str) -> Option<u32> {
+    let digits = options.find(|c: char| !c.is_ascii_digit()).unwrap_or(options.len());
+    options[..digits].parse().ok().filter(|&baud| baud != 0)
+}
+
+fn from_stdout_path(_info: &BootInfo) -> Option<ConsoleDesc> {
+    let fdt = crate::fdt::get()?;
+    let chosen = fdt.find_path("/chosen")?;
+    let value = chosen.property_str("stdout-path").or_else(|| chosen.property_str("linux,stdout-path"))?;
+    let (path, options) = value.split_once(':').unwrap_or((value, ""));
+    // Takma ad `/` ile başlamaz
+    let path = if path.starts_with('/') { path } else { fdt.find_path("/aliases")?.property_str(path)? };
+    let node = fdt.find_path(path)?;
+    let kind = UartKind::from_compatible(&node)?;
+    let (base, _) = node.reg(0)?;
+    let baud = parse_baud(options).or_else(|| node.property_u32("current-speed")).unwrap_or(DEFAULT_BAUD);
+    Some(ConsoleDesc {
+        kind,
+        base: base as usize,
+        port_io: false,
+        reg_shift: node.property_u32("reg-shift").unwrap_or(0) as u8,
+        baud,
+        clock_hz: node.property_u32("clock-frequency").filter(|&hz| hz != 0),
+    })
+}
+
+// -----------------------------------------------------------------------------
+// ACPI (SPCR)
+// -----------------------------------------------------------------------------
+
+/// ACPI tablo başlığı boyutu.
+const SDT_HEADER_SIZE: usize = 36;
+
+unsafe fn read_u32(addr: usize) -> u32 {
+    core::ptr::read_unaligned(addr as *const u32)
+}
+
+unsafe fn read_u64(addr: usize) -> u64 {
+    core::ptr::read_unaligned(addr as *const u64)
+}
+
+/// XSDT (ACPI 2.0+) veya RSDT'de imzası verilen tabloyu arar.
+///
+/// # Safety
+/// `rsdp` geçerli bir RSDP'yi göstermeli, tablolar eşlenmiş olmalıdır.
+unsafe fn find_acpi_table(rsdp: usize, signature: &[u8; 4]) -> Option<usize> {
+    let revision = *((rsdp + 15) as *const u8);
+    let (root, entry_size) = if revision >= 2 && read_u64(rsdp + 24) != 0 {
+        (read_u64(rsdp + 24) as usize, 8)
+    } else {
+        (read_u32(rsdp + 16) as usize, 4)
+    };
+    if root == 0 {
+        return None;
+    }
+    let len = read_u32(root + 4) as usize;
+    (root + SDT_HEADER_SIZE..root + len).step_by(entry_size).find_map(|entry| {
+        let table = if entry_size == 8 { read_u64(entry) as usize } else { read_u32(entry) as usize };
+        (table != 0 && core::slice::from_raw_parts(table as *const u8, 4) == signature).then_some(table)
+    })
+}
+
+fn from_spcr(info: &BootInfo) -> Option<ConsoleDesc> {
+    let rsdp = info.acpi_rsdp?;
+    // SAFETY: RSDP önyükleyiciden geldi; ACPI tabloları kimlik eşlemeli alanda.
+    unsafe {
+        let spcr = find_acpi_table(rsdp, b"SPCR")?;
+        let kind = match *((spcr + 36) as *const u8) {
+            0 | 1 | 0x12 => UartKind::Ns16550,
+            3 | 0x0E => UartKind::Pl011,
+            _ => return None,
+        };
+        // Genel adres yapısı (GAS): alan kimliği, bit genişliği, ..., adres
+        let space = *((spcr + 40) as *const u8);
+        let base = read_u64(spcr + 44) as usize;
+        if base == 0 || space > 1 {
+            return None;
+        }
+        let baud = match *((spcr + 58) as *const u8) {
+            3 => 9_600,
+            4 => 19_200,
+            6 => 57_600,
+            7 => 115_200,
+            _ => DEFAULT_BAUD,
+        };
+        // Erişim genişliği (GAS bayt 3): 3 = 32 bit yazmaçlar
+        let reg_shift = if kind == UartKind::Ns16550 && *((spcr + 43) as *const u8) == 3 { 2 } else { 0 };
+        Some(ConsoleDesc { kind, base, port_io: space == 1, reg_shift, baud, clock_hz: None })
+    }
+}
+
+// -----------------------------------------------------------------------------
+// KOMUT SATIRI (earlycon=)
+// -----------------------------------------------------------------------------
+
+fn parse_hex(value: &str) -> Option<usize> {
+    let value = value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")).unwrap_or(value);
+    usize::from_str_radix(value, 16).ok()
+}
+
+fn from_earlycon(info: &BootInfo) -> Option<ConsoleDesc> {
+    let value = info.args().find_map(|arg| arg.strip_prefix("earlycon="))?;
+    let mut fields = value.split(',');
+    let kind = UartKind::from_earlycon(fields.next()?)?;
+    let (port_io, reg_shift, address) = match fields.next()? {
+        "io" => (true, 0, fields.next()?),
+        "mmio" => (false, 0, fields.next()?),
+        "mmio32" => (false, 2, fields.next()?),
+        address => (false, 0, address),
+    };
+    let base = parse_hex(address)?;
+    let baud = fields.next().and_then(parse_baud).unwrap_or(DEFAULT_BAUD);
+    Some(ConsoleDesc { kind, base, port_io, reg_shift, baud, clock_hz: None })
+}
+
+// -----------------------------------------------------------------------------
+// SEÇİM
+// -----------------------------------------------------------------------------
+
+/// Konsolu seçer (bkz. dosya başı). `boot::init` tarafından, ilk çıktıdan
+/// önce çağrılır.
+pub fn select(info: &BootInfo) {
+    let candidates = [
+        (Source::StdoutPath, from_stdout_path as fn(&BootInfo) -> Option<ConsoleDesc>),
+        (Source::Spcr, from_spcr),
+        (Source::Earlycon, from_earlycon),
+    ];
+    for (source, find) in candidates {
+        if let Some(desc) = find(info) {
+            store(desc, source);
+            return;
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// KABUK
+// -----------------------------------------------------------------------------
+
+fn earlycon_command(_args: &[&str]) -> Result<(), KernelError> {
+    let Some(desc) = get() else {
+        serial_println!("  kaynak varsayılan (mimarinin sabit UART'ı), {} baud", baud());
+        return Ok(());
+    };
+    serial_println!(
+        "  kaynak {}, {} {} {:#x}, aralık {} bayt, {} baud, saat {}",
+        source().name(),
+        desc.kind.name(),
+        if desc.port_io { "io" } else { "mmio" },
+        desc.base,
+        1u32 << desc.reg_shift,
+        desc.baud,
+        desc.clock_hz.unwrap_or(0)
+    );
+    Ok(())
+}
+
+crate::shell_command!("earlycon", "Seçilen konsol UART'ını gösterir", earlycon_command);
@@ -0,0 +1,152 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/endian.rs
+// Bayt sırası belirli aygıt verisi için türler ve erişim yardımcıları.
+//
+// Çekirdek hem küçük sonlu (x86, ARM, RISC-V, LoongArch) hem büyük sonlu
+// (SPARC, PowerPC ve MIPS'in BE yapılandırmaları) işlemcilerde derlenir.
+// Aygıt verisinin sırası ise işlemciden bağımsızdır:
+//
+// * FDT her zaman büyük sonludur.
+// * virtio 1.x yapıları ve yapılandırma alanı her zaman küçük sonludur;
+//   PCI yapılandırma alanı ve PCI BAR'larındaki yazmaçlar da öyle.
+// * Ağ başlıkları büyük sonludur (`net` kendi `from_be_bytes` okumasını yapar).
+//
+// Sürücüler ham `read_volatile` + `u32::from_le` yazmak yerine aşağıdaki
+// üç biçimden birini kullanır; dönüşüm işlemci zaten aynı sıradaysa boşa
+// iner, aynı sürücü kodu iki tür yapıda da doğru çalışır:
+//
+// * `Le32`/`Be32` (16/64 bit eşleri): bellekteki yapıların alanları
+//   (virtqueue tanımlayıcıları, FDT başlığı). Değer aygıt sırasında saklanır;
+//   `get`/`set` yerel sıraya çevirir. `#[repr(transparent)]` oldukları için
+//   `#[repr(C)]` yapılarda ham tamsayının yerine geçerler.
+// * `read_le32`/`read_be32`/`write_*`: bayt dizilerinden hizasız okuma ve
+//   yazma; aralık dışıysa `None`.
+// * `mmio_read_le32`/`mmio_write_be32` ...: `crate::mmio` üzerinden yazmaç
+//   erişimi (yazmadan sonra mimarinin G/Ç bariyeri).
+//
+// Kabuk komutu: yok; procfs: yok
+
+use core::fmt;
+
+// -----------------------------------------------------------------------------
+// TÜRLER
+// -----------------------------------------------------------------------------
+
+macro_rules! endian_type {
+    ($name:ident, $ty:ty, $to:ident, $from:ident, $doc:literal) => {
+        #[doc = $doc]
+        #[repr(transparent)]
+        #[derive(Clone, Copy, Default, PartialEq, Eq)]
+        pub struct $name($ty);
+
+        impl $name {
+            /// Yerel sıradaki değerden oluşturur.
+            pub const fn new(value: $ty) -> Self {
+                $name(value.$to())
+            }
+
+            /// Yerel sıradaki değer.
+            pub const fn get(self) -> $ty {
+                <$ty>::$from(self.0)
+            }
+
+            pub fn set(&mut self, value: $ty) {
+                self.0 = value.$to();
+            }
+
+            /// Bellekte duran (aygıt sırasındaki) ham değer.
+            pub const fn raw(self) -> $ty {
+                self.0
+            }
+        }
+
+        impl From<$ty> for $name {
+            fn from(value: $ty) -> Self {
+                $name::new(value)
+            }
+        }
+
+        impl From<$name> for $ty {
+            fn from(value: $name) -> Self {
+                value.get()
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{:#x}", self.get())
+            }
+        }
+    };
+}
+
+endian_type!(Le16, u16, to_le, from_le, "Küçük sonlu saklanan 16 bitlik alan.");
+endian_type!(Le32, u32, to_le, from_le, "Küçük sonlu saklanan 32 bitlik alan.");
+endian_type!(Le64, u64, to_le, from_le, "Küçük sonlu saklanan 64 bitlik alan.");
+endian_type!(Be16, u16, to_be, from_be, "Büyük sonlu saklanan 16 bitlik alan.");
+endian_type!(Be32, u32, to_be, from_be, "Büyük sonlu saklanan 32 bitlik alan.");
+endian_type!(Be64, u64, to_be, from_be, "Büyük sonlu saklanan 64 bitlik alan.");
+
+// -----------------------------------------------------------------------------
+// BAYT DİZİLERİ
+// -----------------------------------------------------------------------------
+
+macro_rules! buffer_access {
+    ($read:ident, $write:ident, $ty:ty, $from_bytes:ident, $to_bytes:ident) => {
+        /// `off`'taki değeri okur; aralık dışıysa `None`.
+        #[inline]
+        pub fn $read(buf: &[u8], off: usize) -> Option<$ty> {
+            let bytes = buf.get(off..off.checked_add(core::mem::size_of::<$ty>())?)?;
+            Some(<$ty>::$from_bytes(bytes.try_into().ok()?))
+        }
+
+        /// `off`'a değeri yazar; aralık dışıysa `None`.
+        #[inline]
+        pub fn $write(buf: &mut [u8], off: usize, value: $ty) -> Option<()> {
+            let bytes = buf.get_mut(off..off.checked_add(core::mem::size_of::<$ty>())?)?;
+            bytes.copy_from_slice(&value.$to_bytes());
+            Some(())
+        }
+    };
+}
+
+buffer_access!(read_le16, write_le16, u16, from_le_bytes, to_le_bytes);
+buffer_access!(read_le32, write_le32, u32, from_le_bytes, to_le_bytes);
+buffer_access!(read_le64, write_le64, u64, from_le_bytes, to_le_bytes);
+buffer_access!(read_be16, write_be16, u16, from_be_bytes, to_be_bytes);
+buffer_access!(read_be32, write_be32, u32, from_be_bytes, to_be_bytes);
+buffer_access!(read_be64, write_be64, u64, from_be_bytes, to_be_bytes);
+
+// -----------------------------------------------------------------------------
+// MMIO
+// -----------------------------------------------------------------------------
+
+macro_rules! mmio_access {
+    ($read:ident, $write:ident, $ty:ty, $to:ident, $from:ident) => {
+        /// Yazmacı okur ve yerel sıraya çevirir.
+        ///
+        /// # Safety
+        /// `addr` eşlenmiş ve hizalı bir aygıt yazmacı olmalıdır.
+        #[inline(always)]
+        pub unsafe fn $read(addr: usize) -> $ty {
+            <$ty>::$from(crate::mmio::read::<$ty>(addr))
+        }
+
+        /// Yerel sıradaki değeri aygıt sırasına çevirip yazar.
+        ///
+        /// # Safety
+        /// `addr` eşlenmiş ve hizalı bir aygıt yazmacı olmalıdır.
+        #[inline(always)]
+        pub unsafe fn $write(addr: usize, value: $ty) {
+            crate::mmio::write::<$ty>(addr, value.$to())
+        }
+    };
+}
+
+mmio_access!(mmio_read_le16, mmio_write_le16, u16, to_le, from_le);
+mmio_access!(mmio_read_le32, mmio_write_le32, u32, to_le, from_le);
+mmio_access!(mmio_read_le64, mmio_write_le64, u64, to_le, from_le);
+mmio_access!(mmio_read_be16, mmio_write_be16, u16, to_be, from_be);
+mmio_access!(mmio_read_be32, mmio_write_be32, u32, to_be, from_be);
+mmio_access!(mmio_read_be64, mmio_write_be64, u64, to_be, from_be);
@@ -0,0 +1,121 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/entropy.rs
+// Çekirdek içi rastgelelik havuzu.
+//
+// Havuz 32 baytlık bir SHA-256 durumudur; her kaynak `add` ile
+// `durum = SHA-256(durum || veri)` biçiminde karıştırılır. İlk okumada havuz
+// kendiliğinden tohumlanır:
+// * aygıt ağacının `/chosen` düğümündeki `rng-seed` ve `kaslr-seed`
+//   (önyükleyici dolduruyorsa),
+// * serbest çalışan sayaçtan (`clock::cycles`) zamanlama titreşimi: kısa bir
+//   bellek döngüsünün süre farkları.
+// Donanım üreteci olan sürücüler ve portlar çıktılarını `add` ile ekler.
+// QEMU TCG'de titreşim zayıftır; orada `rng-seed` verilmelidir.
+//
+// Çıktı `random_u64` ile alınır: durum ve artan sayaçtan türetilir, ardından
+// durum ileri karıştırılır; verilen bir değerden önceki çıktılar geri
+// hesaplanamaz. Kriptografik anahtarlar için değil, çekirdek sertleştirmesi
+// (yığın kanaryası, adres rastgeleleştirme) için tasarlanmıştır.
+//
+// Kabuk komutu: yok; procfs: yok
+
+use core::cell::UnsafeCell;
+use crate::clock;
+use crate::crypto::sha256::{Sha256, DIGEST_LEN};
+use crate::platformgeneric::spinlock::Spinlock;
+
+/// Tohumlamada alınan titreşim örneği sayısı.
+const JITTER_SAMPLES: usize = 256;
+
+struct Pool {
+    state: [u8; DIGEST_LEN],
+    counter: u64,
+    seeded: bool,
+}
+
+struct PoolCell {
+    lock: Spinlock,
+    pool: UnsafeCell<Pool>,
+}
+
+// Havuza erişim `lock` ile korunur.
+unsafe impl Sync for PoolCell {}
+
+static POOL: PoolCell = PoolCell {
+    lock: Spinlock::new(),
+    pool: UnsafeCell::new(Pool { state: [0; DIGEST_LEN], counter: 0, seeded: false }),
+};
+
+fn with_pool<R>(f: impl FnOnce(&mut Pool) -> R) -> R {
+    POOL.lock.lock();
+    // SAFETY: Kilit tutulduğu sürece havuza tek erişen biziz.
+    let result = f(unsafe { &mut *POOL.pool.get() });
+    POOL.lock.unlock();
+    result
+}
+
+impl Pool {
+    fn mix(&mut self, data: &[u8]) {
+        let mut ctx = Sha256::new();
+        ctx.update(&self.state);
+        ctx.update(data);
+        self.state = ctx.finalize();
+    }
+
+    fn seed(&mut self) {
+        if let Some(chosen) = crate::fdt::get().and_then(|fdt| fdt.find_path("/chosen")) {
+            for name in ["rng-seed", "kaslr-seed"] {
+                if let Some(seed) = chosen.property(name) {
+                    self.mix(seed);
+                }
+            }
+        }
+        self.mix(&jitter());
+        self.seeded = true;
+    }
+}
+
+/// Bellek döngüsünün süre farklarından titreşim özeti.
+fn jitter() -> [u8; DIGEST_LEN] {
+    let mut ctx = Sha256::new();
+    let mut scratch = [0u8; 64];
+    let mut last = clock::cycles();
+    for i in 0..JITTER_SAMPLES {
+        for (j, byte) in scratch.iter_mut().enumerate() {
+            // SAFETY: Yerel dizi; uçucu yazma döngünün atılmasını önler.
+            unsafe { core::ptr::write_volatile(byte, (last as u8).wrapping_add((i ^ j) as u8)) };
+        }
+        let now = clock::cycles();
+        ctx.update(&now.wrapping_sub(last).to_ne_bytes());
+        last = now;
+    }
+    ctx.finalize()
+}
+
+// -----------------------------------------------------------------------------
+// ARAYÜZ
+// -----------------------------------------------------------------------------
+
+/// Havuza entropi ekler (donanım üreteci, aygıt kimlikleri, zaman damgaları).
+pub fn add(data: &[u8]) {
+    with_pool(|pool| pool.mix(data));
+}
+
+/// Havuzdan 64 bit rastgele değer; havuz henüz tohumlanmadıysa önce tohumlar.
+pub fn random_u64() -> u64 {
+    with_pool(|pool| {
+        if !pool.seeded {
+            pool.seed();
+        }
+        pool.counter += 1;
+        let mut ctx = Sha256::new();
+        ctx.update(&pool.state);
+        ctx.update(&pool.counter.to_ne_bytes());
+        ctx.update(&clock::cycles().to_ne_bytes());
+        let out = ctx.finalize();
+        // İleri karıştırma: çıktı durumdan, durum çıktıdan türetilemez
+        pool.mix(b"ileri");
+        u64::from_ne_bytes(out[..8].try_into().unwrap_or_default())
+    })
+}
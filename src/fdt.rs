@@ -0,0 +1,323 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/fdt.rs
+// Düzleştirilmiş Aygıt Ağacı (Flattened Device Tree, FDT) okuyucusu.
+//
+// Önyükleyiciden gelen blob adresini `boot::init` (ve mimarilerin `dtb.rs`
+// dosyalarındaki `DtbParser::set_dtb_address`) kaydeder; bu modül aynı blob üzerinde
+// sürücülerin ihtiyaç duyduğu genel sorguları sağlar: düğümleri dolaşma,
+// `compatible` ile arama, yol ile arama ve `reg` / `interrupts` / sayısal
+// özellik okuma. Blob salt okunurdur ve hiçbir şey kopyalanmaz.
+//
+// Biçim: https://devicetree-specification.readthedocs.io (v17, büyük endian;
+// alanlar işlemcinin bayt sırasından bağımsız olarak `endian::read_be32` ile okunur)
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+use crate::endian::read_be32;
+use crate::platformgeneric::KernelError;
+
+// -----------------------------------------------------------------------------
+// SABİTLER
+// -----------------------------------------------------------------------------
+
+const FDT_MAGIC: u32 = 0xD00D_FEED;
+
+const FDT_BEGIN_NODE: u32 = 0x1;
+const FDT_END_NODE: u32 = 0x2;
+const FDT_PROP: u32 = 0x3;
+const FDT_NOP: u32 = 0x4;
+const FDT_END: u32 = 0x9;
+
+/// Desteklenen en derin düğüm iç içeliği.
+const MAX_DEPTH: usize = 16;
+
+/// Kayıtlı blob adresi (0: yok).
+static BLOB: AtomicUsize = AtomicUsize::new(0);
+
+// -----------------------------------------------------------------------------
+// BLOB
+// -----------------------------------------------------------------------------
+
+/// Doğrulanmış bir FDT blob'u.
+#[derive(Clone, Copy)]
+pub struct Fdt {
+    data: &'static [u8],
+    struct_off: usize,
+    struct_len: usize,
+    strings_off: usize,
+}
+
+/// Sıfır sonlu dizeyi okur.
+fn cstr(data: &'static [u8], off: usize) -> Option<&'static str> {
+    let bytes = data.get(off..)?;
+    let len = bytes.iter().position(|&b| b == 0)?;
+    core::str::from_utf8(&bytes[..len]).ok()
+}
+
+/// Hücre dizisinden `cells` adet 32 bitlik hücreyi tek bir sayıya birleştirir.
+fn read_cells(data: &[u8], off: usize, cells: u32) -> Option<u64> {
+    (0..cells as usize).try_fold(0u64, |acc, i| Some((acc << 32) | read_be32(data, off + i * 4)? as u64))
+}
+
+impl Fdt {
+    /// Bellekteki blob'u doğrular.
+    ///
+    /// # Safety
+    /// `addr` geçerli, eşlenmiş ve çekirdek ömrü boyunca değişmeyecek bir FDT'yi göstermelidir.
+    pub unsafe fn from_addr(addr: usize) -> Result<Fdt, KernelError> {
+        if addr == 0 {
+            return Err(KernelError::NotFound);
+        }
+        let header = core::slice::from_raw_parts(addr as *const u8, 40);
+        if read_be32(header, 0) != Some(FDT_MAGIC) {
+            return Err(KernelError::InvalidArgument);
+        }
+        let total = read_be32(header, 4).unwrap_or(0) as usize;
+        let data = core::slice::from_raw_parts(addr as *const u8, total);
+
+        let struct_off = read_be32(header, 8).unwrap_or(0) as usize;
+        let strings_off = read_be32(header, 12).unwrap_or(0) as usize;
+        let struct_len = read_be32(header, 36).unwrap_or(0) as usize;
+        if struct_off + struct_len > total || strings_off > total {
+            return Err(KernelError::InvalidArgument);
+        }
+        Ok(Fdt { data, struct_off, struct_len, strings_off })
+    }
+
+    /// Blob'un kendisi (başlıktaki `totalsize` kadar).
+    pub fn blob(&self) -> &'static [u8] {
+        self.data
+    }
+
+    /// Tüm düğümleri belge sırasıyla dolaşır (kök dahil).
+    pub fn nodes(&self) -> NodeIter {
+        NodeIter {
+            fdt: *self,
+            pos: 0,
+            depth: 0,
+            cells: [(2, 1); MAX_DEPTH],
+        }
+    }
+
+    /// `compatible` listesinde `compat` geçen düğümler.
+    pub fn find_compatible<'a>(&self, compat: &'a str) -> impl Iterator<Item = Node> + 'a {
+        self.nodes().filter(move |n| n.is_compatible(compat))
+    }
+
+    /// Mutlak yol ile düğüm arar (örn. "/chosen", "/soc/serial@fe201000").
+    /// Birim adresi verilmeyen bileşen, `@` öncesi adı eşleşen ilk düğüme uyar.
+    pub fn find_path(&self, path: &str) -> Option<Node> {
+        let mut components = [""; MAX_DEPTH];
+        let mut count = 0;
+        for part in path.split('/').filter(|p| !p.is_empty()) {
+            *components.get_mut(count)? = part;
+            count += 1;
+        }
+
+        let mut matched = 0;
+        for node in self.nodes() {
+            if node.depth == 0 {
+                if count == 0 {
+                    return Some(node);
+                }
+                continue;
+            }
+            if node.depth > matched + 1 {
+                continue;
+            }
+            if node.depth <= matched {
+                // Eşleşen alt ağaçtan çıkıldı; yol bulunamadı
+                return None;
+            }
+            let want = components[matched];
+            let name_matches = node.name == want
+                || (!want.contains('@') && node.name.split('@').next() == Some(want));
+            if name_matches {
+                matched += 1;
+                if matched == count {
+                    return Some(node);
+                }
+            }
+        }
+        None
+    }
+
+    /// `/aliases` veya `/chosen` gibi bir düğümdeki dize özelliği.
+    pub fn property_str(&self, path: &str, name: &str) -> Option<&'static str> {
+        self.find_path(path)?.property_str(name)
+    }
+
+    fn token(&self, pos: usize) -> Option<u32> {
+        if pos >= self.struct_len {
+            return None;
+        }
+        read_be32(self.data, self.struct_off + pos)
+    }
+
+    fn string(&self, off: usize) -> Option<&'static str> {
+        cstr(self.data, self.strings_off + off)
+    }
+}
+
+/// Kayıtlı blob'u doğrular ve genel erişim için saklar.
+pub fn init(addr: usize) -> Result<(), KernelError> {
+    // SAFETY: Adres önyükleyiciden gelir; blob çekirdek ömrü boyunca korunur.
+    unsafe { Fdt::from_addr(addr)? };
+    BLOB.store(addr, Ordering::Release);
+    Ok(())
+}
+
+/// Kayıtlı FDT (önyükleyici vermediyse `None`).
+pub fn get() -> Option<Fdt> {
+    // SAFETY: Yalnızca `init` tarafından doğrulanmış adresler saklanır.
+    unsafe { Fdt::from_addr(BLOB.load(Ordering::Acquire)).ok() }
+}
+
+// -----------------------------------------------------------------------------
+// DÜĞÜMLER
+// -----------------------------------------------------------------------------
+
+/// Ağaçtaki bir düğüm.
+#[derive(Clone, Copy)]
+pub struct Node {
+    fdt: Fdt,
+    /// Birim adresi dahil düğüm adı (`serial@fe201000`); kök için boş.
+    pub name: &'static str,
+    /// Kök 0.
+    pub depth: usize,
+    /// Özelliklerin başladığı yapı bloğu konumu.
+    props: usize,
+    /// Üst düğümün `#address-cells` / `#size-cells` değerleri (`reg` çözümü için).
+    address_cells: u32,
+    size_cells: u32,
+}
+
+/// Belge sırasıyla düğüm yineleyicisi.
+pub struct NodeIter {
+    fdt: Fdt,
+    pos: usize,
+    depth: usize,
+    /// Her seviyedeki düğümün çocuklarına uygulanacak hücre sayıları.
+    cells: [(u32, u32); MAX_DEPTH],
+}
+
+impl Iterator for NodeIter {
+    type Item = Node;
+
+    fn next(&mut self) -> Option<Node> {
+        loop {
+            match self.fdt.token(self.pos)? {
+                FDT_BEGIN_NODE => {
+                    let name = cstr(self.fdt.data, self.fdt.struct_off + self.pos + 4)?;
+                    let props = (self.pos + 4 + name.len() + 1).next_multiple_of(4);
+                    let depth = self.depth;
+                    if depth >= MAX_DEPTH {
+                        return None;
+                    }
+                    let (address_cells, size_cells) = if depth == 0 { (2, 1) } else { self.cells[depth - 1] };
+                    let node = Node { fdt: self.fdt, name, depth, props, address_cells, size_cells };
+
+                    // Bu düğümün çocukları için hücre sayıları
+                    self.cells[depth] = (
+                        node.property_u32("#address-cells").unwrap_or(2),
+                        node.property_u32("#size-cells").unwrap_or(1),
+                    );
+                    self.depth += 1;
+                    self.pos = props;
+                    return Some(node);
+                }
+                FDT_END_NODE => {
+                    self.depth = self.depth.checked_sub(1)?;
+                    self.pos += 4;
+                }
+                FDT_PROP => {
+                    let len = self.fdt.token(self.pos + 4)? as usize;
+                    self.pos = (self.pos + 12 + len).next_multiple_of(4);
+                }
+                FDT_NOP => self.pos += 4,
+                FDT_END => return None,
+                _ => return None,
+            }
+        }
+    }
+}
+
+impl Node {
+    /// Özelliğin ham değeri.
+    pub fn property(&self, name: &str) -> Option<&'static [u8]> {
+        let fdt = &self.fdt;
+        let mut pos = self.props;
+        loop {
+            match fdt.token(pos)? {
+                FDT_PROP => {
+                    let len = fdt.token(pos + 4)? as usize;
+                    let name_off = fdt.token(pos + 8)? as usize;
+                    let value_off = fdt.struct_off + pos + 12;
+                    if fdt.string(name_off)? == name {
+                        return fdt.data.get(value_off..value_off + len);
+                    }
+                    pos = (pos + 12 + len).next_multiple_of(4);
+                }
+                FDT_NOP => pos += 4,
+                // Özellikler çocuk düğümlerden önce gelir
+                _ => return None,
+            }
+        }
+    }
+
+    /// Tek hücreli sayısal özellik.
+    pub fn property_u32(&self, name: &str) -> Option<u32> {
+        read_be32(self.property(name)?, 0)
+    }
+
+    /// Bir veya iki hücrelik sayısal özellik (örn. `linux,initrd-start`).
+    pub fn property_u64(&self, name: &str) -> Option<u64> {
+        let value = self.property(name).filter(|v| v.len() >= 4)?;
+        read_cells(value, 0, (value.len() / 4).min(2) as u32)
+    }
+
+    /// Dize özelliği (listeyse ilk eleman).
+    pub fn property_str(&self, name: &str) -> Option<&'static str> {
+        let value = self.property(name)?;
+        let len = value.iter().position(|&b| b == 0).unwrap_or(value.len());
+        core::str::from_utf8(&value[..len]).ok()
+    }
+
+    /// `compatible` listesinde `compat` var mı?
+    pub fn is_compatible(&self, compat: &str) -> bool {
+        self.property("compatible").is_some_and(|value| {
+            value.split(|&b| b == 0).any(|entry| entry == compat.as_bytes())
+        })
+    }
+
+    /// `status` "okay" veya yok mu? (Devre dışı düğümler sürücülere verilmez.)
+    pub fn is_enabled(&self) -> bool {
+        matches!(self.property_str("status"), None | Some("okay") | Some("ok"))
+    }
+
+    /// `reg` özelliğinin `index`. (adres, boyut) çifti.
+    pub fn reg(&self, index: usize) -> Option<(u64, u64)> {
+        let value = self.property("reg")?;
+        let entry = (self.address_cells + self.size_cells) as usize * 4;
+        let off = index * entry;
+        if entry == 0 || off + entry > value.len() {
+            return None;
+        }
+        let addr = read_cells(value, off, self.address_cells)?;
+        let size = read_cells(value, off + self.address_cells as usize * 4, self.size_cells)?;
+        Some((addr, size))
+    }
+
+    /// `interrupts` özelliğinin ham hücreleri (`index`. hücre).
+    /// GIC için kesme üç hücrelidir: (tür, numara, bayraklar); bkz. `gic_irq`.
+    pub fn interrupt_cell(&self, index: usize) -> Option<u32> {
+        read_be32(self.property("interrupts")?, index * 4)
+    }
+
+    /// GIC biçimli ilk kesmenin GIC kesme kimliği (SPI: +32, PPI: +16).
+    pub fn gic_irq(&self) -> Option<u32> {
+        let kind = self.interrupt_cell(0)?;
+        let number = self.interrupt_cell(1)?;
+        Some(if kind == 0 { number + 32 } else { number + 16 })
+    }
+}
@@ -0,0 +1,277 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/fixup.rs
+// Çekirdek kodunda kurtarılabilir hatalar için istisna düzeltme (fixup) tablosu.
+//
+// Hata verebilecek her talimat (kullanıcı belleği erişimi, hata ayıklayıcının
+// bellek okuması, aygıt yoklaması), assembly içinde `.ex_table` bölümüne
+// (talimat adresi, kurtarma adresi) çifti olarak kaydedilir. Mimarinin sayfa
+// hatası, hizalama ve veri yolu hatası işleyicileri panik yapmadan önce
+// `fixup_exception` çağırır; kayıt bulunursa PC kurtarma adresine yönlendirilir
+// ve kurtarma kodu çağırana bir hata kodu döndürür.
+//
+// Linker betiği: __ex_table_start = .; KEEP(*(.ex_table)) __ex_table_end = .;
+// Kurtarma kodu `.text.fixup` bölümüne yerleştirilir (normal `.text` içinde kalmalıdır).
+//
+// Tablo tek talimatlık yoklamalar içindir. Birden çok adımlık bir işin
+// (öz-sınama, olmayabilecek bir aygıtın sürücü kodu) herhangi bir yerinde
+// oluşan hata için `catch` kullanılır: setjmp/longjmp gibi, girişte
+// callee-saved yazmaçlar ve yığın işaretçisi kaydedilir (`ArchContext::catch`);
+// gövdede kurtarılabilir bir istisna olursa işleyici PC'yi `catch_resume`'a
+// yönlendirir, o da kaydedilen bağlamı yükler ve `catch` `Err` döner.
+// Gövdenin yığın çerçeveleri atılır: yıkıcılar çalışmaz, gövde hata anında
+// kilit tutmamalı ve zamanlayıcıya bırakmamalıdır (çerçeve zinciri işlemci
+// başınadır).
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+use crate::arch::fixup as arch_fixup;
+use crate::context::{ArchContext, Context};
+use crate::platformgeneric::KernelError;
+use crate::trap::Cause;
+
+// -----------------------------------------------------------------------------
+// DÜZELTME TABLOSU
+// -----------------------------------------------------------------------------
+
+/// Tek bir düzeltme kaydı. Assembly tarafında iki adet 64 bit kelime olarak üretilir.
+#[repr(C)]
+pub struct ExTableEntry {
+    /// Hata verebilecek talimatın adresi.
+    pub insn: u64,
+    /// Hata durumunda devam edilecek adres.
+    pub fixup: u64,
+}
+
+extern "C" {
+    static __ex_table_start: ExTableEntry;
+    static __ex_table_end: ExTableEntry;
+}
+
+/// Tüm düzeltme kayıtlarını dilim olarak döndürür.
+fn table() -> &'static [ExTableEntry] {
+    // SAFETY: Semboller linker betiği tarafından `.ex_table` bölümünün iki ucuna yerleştirilir.
+    unsafe {
+        let start = &__ex_table_start as *const ExTableEntry;
+        let end = &__ex_table_end as *const ExTableEntry;
+        let count = (end as usize - start as usize) / core::mem::size_of::<ExTableEntry>();
+        core::slice::from_raw_parts(start, count)
+    }
+}
+
+/// Hata veren talimat için kurtarma adresini arar.
+pub fn search(pc: u64) -> Option<u64> {
+    table().iter().find(|entry| entry.insn == pc).map(|entry| entry.fixup)
+}
+
+crate::kernel_counter!(FIXUPS, "fixup.recovered");
+
+/// Kurtarılabilir bir hata için düzeltme kaydı varsa PC'yi kurtarma adresine taşır.
+///
+/// Tablo yalnızca bellek erişimi kaynaklı nedenleri (sayfa hatası, hizalama,
+/// veri yolu hatası) düzeltir. Kayıt yoksa ve hata etkin bir `catch`
+/// gövdesinde oluştuysa PC `catch_resume`'a yönlendirilir.
+///
+/// # Dönüş Değeri
+/// PC değiştirildiyse `true`; işleyici istisnadan doğrudan dönmelidir.
+pub fn fixup_exception(cause: Cause, pc: &mut u64) -> bool {
+    if matches!(cause, Cause::PageFault { .. } | Cause::Misaligned { .. } | Cause::MachineError) {
+        if let Some(fixup) = search(*pc) {
+            *pc = fixup;
+            FIXUPS.inc();
+            return true;
+        }
+    }
+    catch_fault(cause, pc)
+}
+
+// -----------------------------------------------------------------------------
+// DENETİMLİ KURTARMA (catch)
+// -----------------------------------------------------------------------------
+
+/// `catch` gövdesinde yakalanan istisna.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fault {
+    pub cause: Cause,
+    /// Hata veren talimatın adresi.
+    pub pc: usize,
+}
+
+/// Etkin `catch` çağrısı; iç içe çağrılar `prev` ile zincirlenir.
+struct CatchFrame {
+    context: Context,
+    prev: usize,
+    /// `catch` kesme bağlamında mı çağrıldı? Gövdeyi bölen bir kesme
+    /// işleyicisindeki hata bu çerçeveye yönlendirilmez.
+    in_irq: bool,
+    fault: Option<Fault>,
+}
+
+/// İşlemcinin en içteki `CatchFrame`'i (0: yok).
+crate::percpu!(CATCH_FRAME: AtomicUsize = AtomicUsize::new(0));
+
+crate::kernel_counter!(CAUGHT, "fixup.caught");
+
+/// Gövdeyi çalıştırır; gövdede kurtarılabilir bir istisna (sayfa hatası,
+/// geçersiz talimat, veri yolu hatası, ...) olursa sistemi paniğe sokmadan
+/// `Err` döner.
+///
+/// Gövde hatanın oluştuğu noktada bırakılır: yerel değerlerin yıkıcıları
+/// çalışmaz (bellek sızar ama bozulmaz). Gövde hata verebilecek kısımda
+/// kilit tutmamalı ve görev değiştirmemelidir.
+///
+/// ```ignore
+/// match fixup::catch(|| unsafe { probe_registers(base) }) {
+///     Ok(id) => serial_println!("aygıt {:#x}", id),
+///     Err(fault) => serial_println!("aygıt yok: {}", fault.cause),
+/// }
+/// ```
+pub fn catch<R, F: FnOnce() -> R>(body: F) -> Result<R, Fault> {
+    struct Call<F, R> {
+        body: Option<F>,
+        result: Option<R>,
+    }
+
+    extern "C" fn run<F: FnOnce() -> R, R>(data: *mut u8) {
+        // SAFETY: `data`, aşağıdaki `catch_call`'a verilen `Call<F, R>`'dir.
+        let call = unsafe { &mut *(data as *mut Call<F, R>) };
+        if let Some(body) = call.body.take() {
+            call.result = Some(body());
+        }
+    }
+
+    let mut call = Call { body: Some(body), result: None };
+    let slot = CATCH_FRAME.get();
+    let mut frame = CatchFrame {
+        context: Context::new(0, 0, 0),
+        prev: slot.load(Ordering::Relaxed),
+        in_irq: crate::irqstack::in_interrupt(),
+        fault: None,
+    };
+    slot.store(&mut frame as *mut CatchFrame as usize, Ordering::Relaxed);
+    // SAFETY: `frame` ve `call` bu çerçevede, geri dönüşten sonrasına kadar
+    // yaşar; bağlam yalnızca gövde çalışırken (`catch_resume`) yüklenir.
+    let resumed = unsafe { frame.context.catch(run::<F, R>, &mut call as *mut Call<F, R> as *mut u8) };
+    slot.store(frame.prev, Ordering::Relaxed);
+
+    match (resumed, frame.fault, call.result) {
+        (0, _, Some(result)) => Ok(result),
+        (_, Some(fault), _) => Err(fault),
+        // Gövde dönmeden bağlam başka bir yoldan yüklenemez
+        _ => unreachable!("catch: gövde sonucu yok"),
+    }
+}
+
+/// Hata etkin bir `catch` gövdesindeyse çerçeveyi işaretler ve PC'yi
+/// `catch_resume`'a yönlendirir.
+fn catch_fault(cause: Cause, pc: &mut u64) -> bool {
+    if !cause.is_fatal() {
+        return false;
+    }
+    let frame = CATCH_FRAME.get().load(Ordering::Relaxed) as *mut CatchFrame;
+    if frame.is_null() {
+        return false;
+    }
+    // SAFETY: Çerçeve, sahibi `catch` dönene kadar zincirde kalır; zincir
+    // yalnızca bu işlemcide değiştirilir.
+    let frame = unsafe { &mut *frame };
+    if frame.in_irq != crate::irqstack::in_interrupt() {
+        return false;
+    }
+    frame.fault = Some(Fault { cause, pc: *pc as usize });
+    frame.context.set_return_value(1);
+    *pc = entry_addr(catch_resume);
+    CAUGHT.inc();
+    true
+}
+
+/// İstisna dönüşünün PC'sinin gösterdiği yer: en içteki `catch`'in kaydettiği
+/// bağlamı yükler. Hata anındaki yığın üzerinde çalışır ve dönmez.
+extern "C" fn catch_resume() -> ! {
+    let frame = CATCH_FRAME.get().load(Ordering::Relaxed) as *const CatchFrame;
+    let mut discarded = Context::new(0, 0, 0);
+    // SAFETY: `catch_fault` çerçeveyi az önce doğruladı; çerçevenin sahibi
+    // `catch` henüz dönmedi.
+    unsafe { discarded.switch(&(*frame).context) };
+    unreachable!("catch_resume: bağlam yüklenemedi")
+}
+
+/// İşlevin ilk talimatının adresi. PowerPC ELFv1'de işlev işaretçisi bir
+/// tanımlayıcıyı gösterir; giriş adresi onun ilk kelimesidir.
+fn entry_addr(f: extern "C" fn() -> !) -> u64 {
+    let addr = f as usize;
+    #[cfg(target_arch = "powerpc64")]
+    // SAFETY: İşlev tanımlayıcıları `.opd` bölümündedir ve değişmez.
+    let addr = unsafe { *(addr as *const usize) };
+    addr as u64
+}
+
+// -----------------------------------------------------------------------------
+// GÜVENLİ BELLEK YOKLAMA
+// -----------------------------------------------------------------------------
+
+/// Çekirdek belleğini hata vermeden okur (hata ayıklayıcı, yığın dökümü).
+///
+/// # Hatalar
+/// * `InvalidArgument`: Aralıktaki bir bayt okunamadı (eşlenmemiş veya erişim hatası).
+pub fn probe_kernel_read(addr: usize, buf: &mut [u8]) -> Result<(), KernelError> {
+    for (offset, byte) in buf.iter_mut().enumerate() {
+        // SAFETY: Okuma talimatı düzeltme tablosunda kayıtlıdır; hata panik yerine `None` döndürür.
+        *byte = unsafe { arch_fixup::probe_read_u8(addr.wrapping_add(offset)) }
+            .ok_or(KernelError::InvalidArgument)?;
+    }
+    Ok(())
+}
+
+/// Belleğe hata vermeden yazar (sistem çağrısı sonuçlarının kullanıcı
+/// tamponuna kopyalanması).
+///
+/// # Hatalar
+/// * `InvalidArgument`: Aralıktaki bir bayt yazılamadı; önceki baytlar yazılmış olabilir.
+pub fn probe_kernel_write(addr: usize, buf: &[u8]) -> Result<(), KernelError> {
+    for (offset, byte) in buf.iter().enumerate() {
+        // SAFETY: Yazma talimatı düzeltme tablosunda kayıtlıdır; hata panik yerine `false` döndürür.
+        if !unsafe { arch_fixup::probe_write_u8(addr.wrapping_add(offset), *byte) } {
+            return Err(KernelError::InvalidArgument);
+        }
+    }
+    Ok(())
+}
+
+/// Bir aygıt yazmacını (32 bit MMIO) yoklar. Aygıt yoksa veri yolu/eşleme hatası
+/// panik yerine `NotFound` olarak döner.
+///
+/// # Not
+/// ARM'da eşzamansız SError olarak bildirilen harici iptaller tabloyla
+/// düzeltilemez; aygıt keşfi için bunları da yakalayan `mmio::try_read32`
+/// kullanılmalıdır.
+pub fn probe_mmio_read32(addr: usize) -> Result<u32, KernelError> {
+    if addr % 4 != 0 {
+        return Err(KernelError::InvalidArgument);
+    }
+    // SAFETY: Okuma talimatı düzeltme tablosunda kayıtlıdır.
+    unsafe { arch_fixup::probe_read_u32(addr) }.ok_or(KernelError::NotFound)
+}
+
+// -----------------------------------------------------------------------------
+// TESTLER
+// -----------------------------------------------------------------------------
+
+crate::ktest! {
+    fn catch_recovers_fault() {
+        crate::ktest_assert_eq!(catch(|| 41 + 1), Ok(42));
+        use crate::memory::frame::FRAME_SIZE;
+        // Alanın ardındaki koruma sayfası eşlenmemiştir
+        let Ok(base) = crate::memory::vmalloc::vmalloc(FRAME_SIZE) else {
+            // vmalloc penceresi olmayan mimariler (MIPS, OpenRISC)
+            return Ok(());
+        };
+        let guard = (base + FRAME_SIZE) as *const u8;
+        // SAFETY: Okuma hata verir ve `catch`'e döner.
+        let fault = catch(|| unsafe { core::ptr::read_volatile(guard) });
+        // İç içe: içteki hata dıştaki çerçeveyi etkilemez
+        let nested = catch(|| catch(|| unsafe { core::ptr::read_volatile(guard) }).is_err());
+        crate::memory::vmalloc::vfree(base)?;
+        crate::ktest_assert!(matches!(fault, Err(Fault { cause: Cause::PageFault { .. }, .. })));
+        crate::ktest_assert_eq!(nested, Ok(true));
+    }
+}
@@ -0,0 +1,353 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/hrtimer.rs
+// Yüksek çözünürlüklü tek seferlik zamanlayıcılar ve ayarlanabilir tik.
+//
+// Sayaç frekansı biliniyorsa (`clock::cycles_hz() != 0`) zamanlayıcı
+// kesmesi mimarinin karşılaştırıcısıyla (`time::set_timer_deadline`) tek
+// seferlik kurulur: bir sonraki hedef, sıradaki zamanlayıcı tikinden ve en
+// erken hrtimer'dan hangisi önce geliyorsa odur. Böylece tik hızı
+// (`sched.tick_hz`: 100/250/1000 Hz) çalışma zamanında değişebilir ve
+// hrtimer'lar tik periyodundan bağımsız olarak nanosaniye hedefiyle dolar.
+//
+// Frekans bilinmiyorsa (örn. TSC kalibre edilmemiş x86) mimarinin periyodik
+// tiki kullanılmaya devam eder; hrtimer'lar her tikte kontrol edilir ve
+// çözünürlük tik periyoduna düşer.
+//
+// Geri çağırmalar `timer.rs`'deki gibi kesme bağlamında, kilit bırakıldıktan
+// sonra çalışır. Kimlikler `HRTIMER_IDS` havuzundan O(1) alınır.
+
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use crate::platformgeneric::spinlock::IrqSafeSpinlock;
+use crate::platformgeneric::KernelError;
+use crate::{clock, serial_println};
+
+use crate::arch::time as arch_time;
+
+// -----------------------------------------------------------------------------
+// SABİTLER VE TİPLER
+// -----------------------------------------------------------------------------
+
+/// Aynı anda kurulabilecek en fazla hrtimer.
+pub const MAX_HRTIMERS: usize = 16;
+/// Tek kesmede telafi edilecek en fazla kaçırılmış tik; fazlası atılır ve
+/// tik zamanı yeniden hizalanır.
+const MAX_CATCHUP_TICKS: u32 = 8;
+/// Karşılaştırıcı kurulduktan sonra hedef geçmişte kalırsa kesme içinde
+/// yapılacak en fazla tekrar.
+const MAX_PASSES: u32 = 4;
+/// Karşılaştırıcıya yazılacak en yakın hedef (şimdiden itibaren, ns).
+/// Eşitlik karşılaştırmalı sayaçlarda (Compare, TICK_CMPR) geçmiş hedef
+/// bir sayaç turu boyunca kaybolur.
+const MIN_PROGRAM_NS: u64 = 1_000;
+
+const NS_PER_SEC: u64 = 1_000_000_000;
+
+/// hrtimer dolduğunda çağrılan fonksiyon (kesme bağlamı).
+pub type HrTimerFn = fn(arg: usize);
+
+/// hrtimer tablosundaki indeks; zamanlayıcı dolduktan sonra geçersizdir.
+pub type HrTimerId = usize;
+
+#[derive(Clone, Copy)]
+struct HrTimer {
+    active: bool,
+    /// Dolma zamanı (mutlak, `now_ns` cinsinden).
+    deadline: u64,
+    callback: Option<HrTimerFn>,
+    arg: usize,
+}
+
+impl HrTimer {
+    const fn empty() -> Self {
+        HrTimer { active: false, deadline: 0, callback: None, arg: 0 }
+    }
+}
+
+struct Queue {
+    lock: IrqSafeSpinlock,
+    timers: UnsafeCell<[HrTimer; MAX_HRTIMERS]>,
+}
+
+// Tüm alanlara erişim `lock` ile korunur; tik kesmesi de aldığından kesmeler kapalı tutulur.
+unsafe impl Sync for Queue {}
+
+static QUEUE: Queue = Queue {
+    lock: IrqSafeSpinlock::new(),
+    timers: UnsafeCell::new([HrTimer::empty(); MAX_HRTIMERS]),
+};
+
+/// Karşılaştırıcı kipi etkin mi? Değilse mimarinin periyodik tiki kullanılır.
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+/// Tik periyodu (ns).
+static TICK_PERIOD_NS: AtomicU64 = AtomicU64::new(0);
+/// Bir sonraki zamanlayıcı tikinin zamanı (ns).
+static NEXT_TICK_NS: AtomicU64 = AtomicU64::new(0);
+/// Karşılaştırıcıya en son yazılan hedef (ns).
+static PROGRAMMED_NS: AtomicU64 = AtomicU64::new(u64::MAX);
+
+crate::mempool!(HRTIMER_IDS: (), MAX_HRTIMERS, "hrtimer");
+
+crate::kernel_counter!(FIRED, "hrtimer.fired");
+crate::kernel_counter!(TICKS_DROPPED, "hrtimer.ticks_dropped");
+
+fn with_queue<R>(f: impl FnOnce(&mut [HrTimer; MAX_HRTIMERS]) -> R) -> R {
+    let flags = QUEUE.lock.lock();
+    // SAFETY: Kilit tutulduğu sürece tabloya tek erişen biziz.
+    let result = unsafe { f(&mut *QUEUE.timers.get()) };
+    QUEUE.lock.unlock(flags);
+    result
+}
+
+/// Dolan veya iptal edilen hrtimer'ın kimliğini havuza geri verir.
+fn release(timer: &mut HrTimer, id: HrTimerId) {
+    timer.active = false;
+    // SAFETY: Kimlik `start_at_ns` ile alındı; `active` bayrağı onu bir kez geri vermemizi sağlar.
+    unsafe { HRTIMER_IDS.free_index(id) };
+}
+
+fn earliest() -> u64 {
+    with_queue(|timers| timers.iter().filter(|t| t.active).map(|t| t.deadline).min().unwrap_or(u64::MAX))
+}
+
+// -----------------------------------------------------------------------------
+// ZAMAN
+// -----------------------------------------------------------------------------
+
+#[cfg(feature = "cooperative")]
+fn tick_count() -> u64 {
+    crate::coop::ticks()
+}
+#[cfg(not(feature = "cooperative"))]
+fn tick_count() -> u64 {
+    crate::sched::ticks()
+}
+
+/// Açılıştan bu yana geçen süre (ns). Sayaç frekansı bilinmiyorsa tik
+/// sayısından hesaplanır ve çözünürlüğü tik periyodudur.
+pub fn now_ns() -> u64 {
+    clock::cycles_to_ns(clock::cycles())
+        .unwrap_or_else(|| tick_count() * (NS_PER_SEC / crate::sched::tick_hz()))
+}
+
+/// hrtimer'ların zamanlama çözünürlüğü (ns).
+pub fn resolution_ns() -> u64 {
+    match clock::cycles_hz() {
+        hz if is_active() => (NS_PER_SEC / hz).max(1),
+        _ => NS_PER_SEC / crate::sched::tick_hz(),
+    }
+}
+
+/// Karşılaştırıcı kipi etkin mi?
+pub fn is_active() -> bool {
+    ACTIVE.load(Ordering::Acquire)
+}
+
+/// Karşılaştırıcıyı `deadline_ns`'e (en erken `MIN_PROGRAM_NS` sonrasına) kurar.
+fn program(deadline_ns: u64) {
+    let deadline_ns = deadline_ns.max(now_ns() + MIN_PROGRAM_NS);
+    PROGRAMMED_NS.store(deadline_ns, Ordering::Release);
+    let hz = u128::from(clock::cycles_hz());
+    // Yukarı yuvarlanır: kesme hedeften önce gelmemeli
+    let cycles = (u128::from(deadline_ns) * hz).div_ceil(u128::from(NS_PER_SEC)) as u64;
+    // SAFETY: Karşılaştırıcıya yalnızca bu modül yazar.
+    unsafe { arch_time::set_timer_deadline(arch_time::Cycles(cycles)) };
+}
+
+// -----------------------------------------------------------------------------
+// ZAMANLAYICI KURMA
+// -----------------------------------------------------------------------------
+
+/// Mutlak `deadline` (ns, `now_ns` cinsinden) anında `callback(arg)`
+/// çağıracak tek seferlik bir hrtimer kurar. Geçmiş bir hedef bir sonraki
+/// zamanlayıcı kesmesinde dolar.
+///
+/// # Dönüş Değeri
+/// Zamanlayıcı kimliği; tablo doluysa `OutOfMemoryStatic`.
+pub fn start_at_ns(deadline: u64, callback: HrTimerFn, arg: usize) -> Result<HrTimerId, KernelError> {
+    let id = HRTIMER_IDS.alloc_index().ok_or(KernelError::OutOfMemoryStatic)?;
+    with_queue(|timers| timers[id] = HrTimer { active: true, deadline, callback: Some(callback), arg });
+    // Kurulu hedeften önceyse karşılaştırıcı öne çekilir
+    if is_active() && deadline < PROGRAMMED_NS.load(Ordering::Acquire) {
+        program(deadline);
+    }
+    Ok(id)
+}
+
+/// `delay` ns sonra dolacak tek seferlik bir hrtimer kurar.
+pub fn start_ns(delay: u64, callback: HrTimerFn, arg: usize) -> Result<HrTimerId, KernelError> {
+    start_at_ns(now_ns().saturating_add(delay), callback, arg)
+}
+
+/// Kurulu bir hrtimer'ı iptal eder. Karşılaştırıcı yeniden kurulmaz; boşa
+/// gelen kesme yalnızca tikleri işler.
+pub fn cancel(id: HrTimerId) -> Result<(), KernelError> {
+    with_queue(|timers| match timers.get_mut(id) {
+        Some(t) if t.active => {
+            release(t, id);
+            Ok(())
+        }
+        _ => Err(KernelError::NotFound),
+    })
+}
+
+// -----------------------------------------------------------------------------
+// KESME İŞLEME
+// -----------------------------------------------------------------------------
+
+fn run_tick() {
+    #[cfg(feature = "cooperative")]
+    crate::coop::tick();
+    #[cfg(not(feature = "cooperative"))]
+    crate::sched::tick();
+}
+
+/// `now`'a kadar gelmiş tikleri işler; `MAX_CATCHUP_TICKS`'ten fazlası atılır.
+fn catch_up_ticks(now: u64) {
+    let period = TICK_PERIOD_NS.load(Ordering::Acquire);
+    let mut next = NEXT_TICK_NS.load(Ordering::Acquire);
+    let mut delivered = 0;
+    while next <= now {
+        if delivered == MAX_CATCHUP_TICKS {
+            TICKS_DROPPED.add((now - next) / period + 1);
+            next = now + period;
+            break;
+        }
+        run_tick();
+        next += period;
+        delivered += 1;
+    }
+    NEXT_TICK_NS.store(next, Ordering::Release);
+}
+
+/// Süresi `now`'a kadar dolan hrtimer'ları çalıştırır.
+fn run_expired(now: u64) {
+    let mut fire: [(Option<HrTimerFn>, usize); MAX_HRTIMERS] = [(None, 0); MAX_HRTIMERS];
+    let mut count = 0;
+    with_queue(|timers| {
+        for (id, t) in timers.iter_mut().enumerate().filter(|(_, t)| t.active && t.deadline <= now) {
+            release(t, id);
+            fire[count] = (t.callback, t.arg);
+            count += 1;
+        }
+    });
+    for (callback, arg) in fire[..count].iter().copied() {
+        if let Some(callback) = callback {
+            FIRED.inc();
+            callback(arg);
+        }
+    }
+}
+
+/// Mimarinin zamanlayıcı kesmesinden çağrılır: dolan tikleri ve
+/// hrtimer'ları işler ve karşılaştırıcıyı bir sonraki hedefe kurar.
+/// Karşılaştırıcı kipi kapalıysa tek bir tik işler.
+pub fn timer_interrupt() {
+    if !is_active() {
+        run_tick();
+        run_expired(now_ns());
+        return;
+    }
+    for _ in 0..MAX_PASSES {
+        let now = now_ns();
+        catch_up_ticks(now);
+        run_expired(now);
+        let next = NEXT_TICK_NS.load(Ordering::Acquire).min(earliest());
+        program(next);
+        // Geri çağırmalar uzun sürdüyse hedef kaçmış olabilir
+        if now_ns() < PROGRAMMED_NS.load(Ordering::Acquire) {
+            break;
+        }
+    }
+}
+
+/// `sched.tick_hz` kancası: tik periyodunu değiştirir ve karşılaştırıcıyı
+/// yeni periyoda göre kurar.
+fn set_tick_rate(hz: u32) -> Result<(), KernelError> {
+    if hz == 0 {
+        return Err(KernelError::InvalidArgument);
+    }
+    let period = NS_PER_SEC / u64::from(hz);
+    let next = now_ns() + period;
+    TICK_PERIOD_NS.store(period, Ordering::Release);
+    NEXT_TICK_NS.store(next, Ordering::Release);
+    program(next.min(earliest()));
+    serial_println!("[HRTIMER] Tik hızı {} Hz ({} ns)", hz, period);
+    Ok(())
+}
+
+// -----------------------------------------------------------------------------
+// UYKU
+// -----------------------------------------------------------------------------
+
+fn wake_task(arg: usize) {
+    let _ = crate::sched::wake(arg);
+}
+
+/// Çalışan görevi `ns` nanosaniye boyunca bekletir (`timer::sleep`'in
+/// nanosaniye karşılığı).
+pub fn sleep_ns(ns: u64) {
+    let deadline = now_ns().saturating_add(ns);
+    if let Some(me) = crate::sched::current() {
+        // Önce bloklanır: zamanlayıcı kurulur kurulmaz dolsa bile uyandırma kaybolmaz
+        let _ = crate::sched::block(me);
+        if start_at_ns(deadline, wake_task, me).is_err() {
+            let _ = crate::sched::wake(me);
+        }
+    }
+    while now_ns() < deadline {
+        core::hint::spin_loop();
+    }
+}
+
+// -----------------------------------------------------------------------------
+// BAŞLATMA
+// -----------------------------------------------------------------------------
+
+fn init() -> Result<(), KernelError> {
+    if clock::cycles_hz() == 0 {
+        serial_println!("[HRTIMER] Sayaç frekansı bilinmiyor; periyodik tik kullanılıyor");
+        return Ok(());
+    }
+    let hz = crate::sched::tick_hz();
+    let period = NS_PER_SEC / hz;
+    let next = now_ns() + period;
+    TICK_PERIOD_NS.store(period, Ordering::Release);
+    NEXT_TICK_NS.store(next, Ordering::Release);
+    ACTIVE.store(true, Ordering::Release);
+    crate::sched::set_tick_rate_hook(set_tick_rate);
+    program(next);
+    serial_println!("[HRTIMER] Karşılaştırıcı kipi: {} Hz tik, {} ns çözünürlük", hz, resolution_ns());
+    Ok(())
+}
+
+crate::initcall!(timer, "hrtimer", init);
+
+// -----------------------------------------------------------------------------
+// PROCFS
+// -----------------------------------------------------------------------------
+
+fn show_hrtimers(out: &mut dyn core::fmt::Write) -> core::fmt::Result {
+    let now = now_ns();
+    let mut active = [HrTimer::empty(); MAX_HRTIMERS];
+    with_queue(|timers| active = *timers);
+    for (id, t) in active.iter().enumerate().filter(|(_, t)| t.active) {
+        writeln!(out, "{} {}", id, t.deadline.saturating_sub(now))?;
+    }
+    Ok(())
+}
+
+crate::proc_entry!("hrtimers", show_hrtimers);
+
+// -----------------------------------------------------------------------------
+// TESTLER
+// -----------------------------------------------------------------------------
+
+crate::ktest! {
+    fn hrtimer_sleep_at_least() {
+        let start = now_ns();
+        sleep_ns(1_000_000);
+        crate::ktest_assert!(now_ns() - start >= 1_000_000);
+    }
+}
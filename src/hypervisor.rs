@@ -0,0 +1,175 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/hypervisor.rs
+// Sanallaştırma algılama ve paravirt (hypervisor destekli) hizmet seçimi.
+//
+// Çekirdek KVM/QEMU altında çalışırken öykünülmüş eski aygıtlara (PIT, 8250
+// UART, SMC tabanlı firmware) dokunmak yerine hypervisor'ın sunduğu daha
+// güvenilir arayüzleri tercih eder: kvmclock, SBI/hvterm konsolu, HVC üzerinden
+// PSCI. Algılama mimariye özgüdür (CPUID hypervisor biti, SBI uygulama kimliği,
+// PSCI iletim yöntemi, MSR.HV, CPUCFG imzası).
+
+use core::sync::atomic::{AtomicU8, Ordering};
+use crate::arch::hypervisor as arch_hv;
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// HYPERVISOR TÜRLERİ
+// -----------------------------------------------------------------------------
+
+/// Algılanan hypervisor.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HypervisorKind {
+    /// Çıplak donanım (veya algılanamayan sanallaştırma).
+    None = 0,
+    /// Linux KVM.
+    Kvm = 1,
+    /// Donanım hızlandırmasız QEMU (TCG).
+    Qemu = 2,
+    /// Xen.
+    Xen = 3,
+    /// Microsoft Hyper-V.
+    HyperV = 4,
+    /// Varlığı bilinen ama türü tanınmayan hypervisor (PowerVM, Xvisor vb.).
+    Other = 5,
+}
+
+impl HypervisorKind {
+    fn from_u8(val: u8) -> Self {
+        match val {
+            1 => HypervisorKind::Kvm,
+            2 => HypervisorKind::Qemu,
+            3 => HypervisorKind::Xen,
+            4 => HypervisorKind::HyperV,
+            5 => HypervisorKind::Other,
+            _ => HypervisorKind::None,
+        }
+    }
+
+    /// Log çıktısında kullanılan ad.
+    pub fn name(self) -> &'static str {
+        match self {
+            HypervisorKind::None => "yok",
+            HypervisorKind::Kvm => "KVM",
+            HypervisorKind::Qemu => "QEMU (TCG)",
+            HypervisorKind::Xen => "Xen",
+            HypervisorKind::HyperV => "Hyper-V",
+            HypervisorKind::Other => "bilinmeyen",
+        }
+    }
+}
+
+/// Etkinleştirilen paravirt olanakları.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Paravirt {
+    /// Paravirt saat kaynağı (kvmclock vb.) kullanılıyor.
+    pub clock: bool,
+    /// Paravirt konsol (SBI DBCN, hvterm vb.) kullanılıyor.
+    pub console: bool,
+    /// Zamanlayıcı için TSC deadline modu kullanılabilir.
+    pub timer_deadline: bool,
+    /// Firmware çağrıları (PSCI) SMC yerine HVC ile yapılmalı.
+    pub hvc_conduit: bool,
+}
+
+impl Paravirt {
+    const CLOCK: u8 = 1 << 0;
+    const CONSOLE: u8 = 1 << 1;
+    const TIMER_DEADLINE: u8 = 1 << 2;
+    const HVC_CONDUIT: u8 = 1 << 3;
+
+    fn to_bits(self) -> u8 {
+        (self.clock as u8 * Self::CLOCK)
+            | (self.console as u8 * Self::CONSOLE)
+            | (self.timer_deadline as u8 * Self::TIMER_DEADLINE)
+            | (self.hvc_conduit as u8 * Self::HVC_CONDUIT)
+    }
+
+    fn from_bits(bits: u8) -> Self {
+        Paravirt {
+            clock: bits & Self::CLOCK != 0,
+            console: bits & Self::CONSOLE != 0,
+            timer_deadline: bits & Self::TIMER_DEADLINE != 0,
+            hvc_conduit: bits & Self::HVC_CONDUIT != 0,
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// ALGILAMA
+// -----------------------------------------------------------------------------
+
+/// Algılama henüz yapılmadı.
+const KIND_UNKNOWN: u8 = 0xFF;
+
+static KIND: AtomicU8 = AtomicU8::new(KIND_UNKNOWN);
+static PARAVIRT: AtomicU8 = AtomicU8::new(0);
+
+/// Hypervisor'ı algılar (ilk çağrıda) ve sonucu döndürür.
+pub fn detect() -> HypervisorKind {
+    let cached = KIND.load(Ordering::Acquire);
+    if cached != KIND_UNKNOWN {
+        return HypervisorKind::from_u8(cached);
+    }
+
+    let kind = arch_hv::detect();
+    KIND.store(kind as u8, Ordering::Release);
+    kind
+}
+
+/// Çekirdek bir hypervisor altında mı çalışıyor?
+pub fn is_virtualized() -> bool {
+    detect() != HypervisorKind::None
+}
+
+/// Etkin paravirt olanakları.
+pub fn paravirt() -> Paravirt {
+    Paravirt::from_bits(PARAVIRT.load(Ordering::Acquire))
+}
+
+// -----------------------------------------------------------------------------
+// PARAVIRT HİZMETLERİ
+// -----------------------------------------------------------------------------
+
+/// Paravirt konsol etkinse baytı ona yazar.
+///
+/// # Dönüş Değeri
+/// Bayt paravirt konsola yazıldıysa `true`; çağıran aksi halde kendi UART'ını kullanır.
+#[inline]
+pub fn console_write_byte(byte: u8) -> bool {
+    if PARAVIRT.load(Ordering::Relaxed) & Paravirt::CONSOLE == 0 {
+        return false;
+    }
+    arch_hv::pv_console_write_byte(byte);
+    true
+}
+
+/// Paravirt saat kaynağından monoton zamanı (nanosaniye) okur.
+pub fn clock_ns() -> Option<u64> {
+    if PARAVIRT.load(Ordering::Relaxed) & Paravirt::CLOCK == 0 {
+        return None;
+    }
+    Some(arch_hv::pv_clock_ns())
+}
+
+/// Hypervisor'ı algılar ve mimarinin sunduğu paravirt olanakları etkinleştirir.
+fn init() -> Result<(), KernelError> {
+    let kind = detect();
+    if kind == HypervisorKind::None {
+        return Ok(());
+    }
+
+    // SAFETY: Tek çekirdekli erken başlatmada, konsol ve saat kullanılmadan önce çağrılır.
+    let pv = unsafe { arch_hv::enable_paravirt(kind) };
+    PARAVIRT.store(pv.to_bits(), Ordering::Release);
+
+    serial_println!(
+        "[HV] Hypervisor: {} | saat: {} konsol: {} tsc-deadline: {} hvc: {}",
+        kind.name(), pv.clock, pv.console, pv.timer_deadline, pv.hvc_conduit
+    );
+    Ok(())
+}
+
+crate::initcall!(earlycon, "hypervisor", init);
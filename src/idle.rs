@@ -0,0 +1,299 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/idle.rs
+// Boşta döngüsü ve işlemci kullanım muhasebesi.
+//
+// Hazır görev yokken işlemci `wait_for_interrupt` ile mimarinin düşük güç
+// beklemesine (`power::system_idle`: HLT, WFI, wait vb.) girer. Beklemeye
+// giriş ve çıkış monotonik saatle (`clock::cycles`) damgalanır; aradaki
+// süre çekirdeğin boşta döngüsüne eklenir. Kesme dönüşünde doğrudan bir
+// göreve geçilirse boşta dönem `sched::schedule` içinden `leave` ile
+// kapatılır. Boşta beklerken çalışan kesme işleyicileri boşta sayılır.
+//
+// Her işlemcinin bir boşta görevi vardır (`sched::spawn_idle`); zamanlayıcı
+// başka seçilebilir görev bulamazsa ona geçer. Boşta görevi kesmeleri açık
+// tutarak `wait_for_interrupt` ile bekler, uyanınca `sched::schedule` ile
+// hazır göreve geçer. Böylece işlemci hiçbir zaman kesme durumu belirsiz bir
+// görevin içinde park edilmez. Bağlam geçişi açılmadan önce açılış akışı
+// `idle_loop` ile aynı işi yapar.
+//
+// Her saniye bir zamanlayıcı geri çağırması çekirdek başına son saniyedeki
+// kullanımı (binde) halka tampona yazar; 1, 10 ve 60 saniyelik kayan
+// pencereler bu örneklerin ortalamasıdır. Sayaçlar atomiktir; kesme
+// bağlamından güvenle okunur ve yazılır.
+//
+// Muhasebe işlemci başına değişkendir (`percpu!`); her çekirdek yalnızca
+// kendi kopyasını yazar, raporlar çevrimiçi çekirdeklerin kopyalarını okur.
+//
+// Kabuk komutu: `cpuload`; procfs: `/proc/cpuload`
+
+use core::fmt;
+use core::sync::atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use crate::clock;
+use crate::platformgeneric::{KernelError, SystemConstants};
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// SABİTLER VE TİPLER
+// -----------------------------------------------------------------------------
+
+/// Muhasebesi tutulan en fazla çekirdek.
+pub const MAX_CPUS: usize = crate::percpu::MAX_CPUS;
+
+/// Saklanan saniyelik örnek sayısı (en uzun pencere).
+pub const SAMPLE_SLOTS: usize = 60;
+
+/// Raporlanan kayan pencereler (saniye).
+pub const WINDOWS: [usize; 3] = [1, 10, 60];
+
+/// Bir çekirdeğin kullanım özeti.
+#[derive(Debug, Clone, Copy)]
+pub struct CpuLoad {
+    /// Açılıştan beri boşta geçen sayaç birimi.
+    pub idle_cycles: u64,
+    /// Açılıştan beri meşgul geçen sayaç birimi.
+    pub busy_cycles: u64,
+    /// Boşta beklemeye giriş sayısı.
+    pub idle_entries: u64,
+    /// `WINDOWS` pencerelerindeki kullanım (binde); örnek yoksa 0.
+    pub permille: [u32; WINDOWS.len()],
+}
+
+struct CpuAccount {
+    /// Tamamlanmış boşta dönemlerin toplamı.
+    idle_cycles: AtomicU64,
+    /// Süren boşta dönemin başlangıcı (0: boşta değil).
+    idle_since: AtomicU64,
+    idle_entries: AtomicU64,
+    /// Son örneklemedeki saat ve boşta toplamı.
+    last_stamp: AtomicU64,
+    last_idle: AtomicU64,
+    /// Saniyelik kullanım örnekleri (binde).
+    samples: [AtomicU32; SAMPLE_SLOTS],
+}
+
+#[allow(clippy::declare_interior_mutable_const)]
+const ZERO32: AtomicU32 = AtomicU32::new(0);
+
+impl CpuAccount {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const EMPTY: CpuAccount = CpuAccount {
+        idle_cycles: AtomicU64::new(0),
+        idle_since: AtomicU64::new(0),
+        idle_entries: AtomicU64::new(0),
+        last_stamp: AtomicU64::new(0),
+        last_idle: AtomicU64::new(0),
+        samples: [ZERO32; SAMPLE_SLOTS],
+    };
+
+    /// Süren dönem dahil boşta toplamı.
+    fn idle_at(&self, now: u64) -> u64 {
+        let since = self.idle_since.load(Ordering::Acquire);
+        let running = if since == 0 { 0 } else { now.wrapping_sub(since) };
+        self.idle_cycles.load(Ordering::Relaxed) + running
+    }
+}
+
+crate::percpu!(ACCOUNT: CpuAccount = CpuAccount::EMPTY);
+
+/// Muhasebenin başladığı saat değeri.
+static BOOT_STAMP: AtomicU64 = AtomicU64::new(0);
+/// Yazılan örnek sayısı (halka tamponda bir sonraki yuva `% SAMPLE_SLOTS`).
+static SAMPLES_TAKEN: AtomicUsize = AtomicUsize::new(0);
+
+crate::kernel_counter!(IDLE_ENTRIES, "idle.entries");
+
+/// Çevrimiçi çekirdek sayısı.
+pub fn online_cpus() -> usize {
+    crate::percpu::online_cpus()
+}
+
+// -----------------------------------------------------------------------------
+// BOŞTA DÖNGÜSÜ
+// -----------------------------------------------------------------------------
+
+fn arch_idle() {
+    crate::arch::power::system_idle();
+}
+
+/// Yerel kesmeleri açar; boşta beklemesinden kesmeyle çıkılabilmesi için.
+/// Boşta görevi hiçbir kilit tutmaz; kesme açıkken bölünebilir.
+fn arch_irq_enable() {
+    crate::irqoff::enable();
+}
+
+/// Boşta dönemi başlatır.
+pub fn enter() {
+    let cpu = ACCOUNT.get();
+    // 0 "boşta değil" anlamına geldiğinden damga en az 1'dir
+    cpu.idle_since.store(clock::cycles().max(1), Ordering::Release);
+    cpu.idle_entries.fetch_add(1, Ordering::Relaxed);
+    IDLE_ENTRIES.inc();
+}
+
+/// Süren boşta dönemi kapatır; boşta değilse etkisizdir. Hem boşta
+/// döngüsünden hem de göreve geçişte (`sched::schedule`) çağrılır.
+pub fn leave() {
+    let cpu = ACCOUNT.get();
+    let since = cpu.idle_since.swap(0, Ordering::AcqRel);
+    if since != 0 {
+        cpu.idle_cycles.fetch_add(clock::cycles().wrapping_sub(since), Ordering::Relaxed);
+    }
+}
+
+/// İşlemciyi bir sonraki kesmeye kadar bekletir ve süreyi boşta sayar.
+pub fn wait_for_interrupt() {
+    enter();
+    arch_idle();
+    leave();
+}
+
+/// Çekirdeğin boşta döngüsü: hazır görev varsa ona geçer, yoksa bekler.
+/// Boşta görevi varsa `schedule` ona geçer ve bu döngüye bir daha dönülmez.
+pub fn idle_loop() -> ! {
+    loop {
+        if crate::sched::schedule().is_none() {
+            arch_irq_enable();
+            wait_for_interrupt();
+        }
+    }
+}
+
+/// İşlemcinin boşta görevi; argüman işlemci numarasıdır. Yalnızca bu
+/// işlemcide ve başka seçilebilir görev yokken çalışır.
+fn idle_task(_cpu: u64) {
+    loop {
+        arch_irq_enable();
+        wait_for_interrupt();
+        // Uyandıran kesme bir görevi hazır yaptıysa ona geçilir
+        crate::sched::schedule();
+    }
+}
+
+/// Her işlemci için boşta görevini oluşturur. Henüz açılmamış işlemcilerin
+/// görevleri de şimdi oluşturulur; işlemci açılınca hazırdırlar.
+fn init_tasks() -> Result<(), KernelError> {
+    for cpu in 0..MAX_CPUS {
+        crate::sched::spawn_idle(cpu, idle_task as usize)?;
+    }
+    Ok(())
+}
+
+crate::initcall!(timer, "idle", init_tasks);
+
+// -----------------------------------------------------------------------------
+// ÖRNEKLEME
+// -----------------------------------------------------------------------------
+
+/// Saniyelik zamanlayıcı geri çağırması: her çekirdeğin son örnekten bu
+/// yana kullanımını halka tampona yazar.
+fn sample(_arg: usize) {
+    let now = clock::cycles();
+    let slot = SAMPLES_TAKEN.load(Ordering::Relaxed) % SAMPLE_SLOTS;
+    for (_, cpu) in ACCOUNT.iter() {
+        let idle = cpu.idle_at(now);
+        let elapsed = now.wrapping_sub(cpu.last_stamp.swap(now, Ordering::Relaxed));
+        let idle_delta = idle.wrapping_sub(cpu.last_idle.swap(idle, Ordering::Relaxed)).min(elapsed);
+        let permille = if elapsed == 0 {
+            0
+        } else {
+            ((u128::from(elapsed - idle_delta) * 1000) / u128::from(elapsed)) as u32
+        };
+        cpu.samples[slot].store(permille, Ordering::Relaxed);
+    }
+    SAMPLES_TAKEN.fetch_add(1, Ordering::Release);
+}
+
+/// Çekirdeğin son `seconds` saniyedeki ortalama kullanımı (binde).
+/// Henüz örnek yoksa `None`; daha az örnek varsa mevcutların ortalaması.
+pub fn utilization(cpu: usize, seconds: usize) -> Option<u32> {
+    let account = ACCOUNT.get_for(cpu)?;
+    let taken = SAMPLES_TAKEN.load(Ordering::Acquire);
+    let count = seconds.min(SAMPLE_SLOTS).min(taken);
+    if count == 0 {
+        return None;
+    }
+    let sum: u32 = (1..=count)
+        .map(|back| account.samples[(taken - back) % SAMPLE_SLOTS].load(Ordering::Relaxed))
+        .sum();
+    Some(sum / count as u32)
+}
+
+/// Çekirdeğin kullanım özeti; çekirdek çevrimdışıysa `None`.
+pub fn cpu_load(cpu: usize) -> Option<CpuLoad> {
+    let account = ACCOUNT.get_for(cpu)?;
+    let now = clock::cycles();
+    let total = now.wrapping_sub(BOOT_STAMP.load(Ordering::Relaxed));
+    let idle = account.idle_at(now).min(total);
+    let mut permille = [0; WINDOWS.len()];
+    for (value, &window) in permille.iter_mut().zip(WINDOWS.iter()) {
+        *value = utilization(cpu, window).unwrap_or(0);
+    }
+    Some(CpuLoad {
+        idle_cycles: idle,
+        busy_cycles: total - idle,
+        idle_entries: account.idle_entries.load(Ordering::Relaxed),
+        permille,
+    })
+}
+
+fn init() -> Result<(), KernelError> {
+    let now = clock::cycles();
+    BOOT_STAMP.store(now, Ordering::Relaxed);
+    for (_, cpu) in ACCOUNT.iter() {
+        cpu.last_stamp.store(now, Ordering::Relaxed);
+    }
+    let second = SystemConstants::TIMER_TICK_HZ;
+    crate::timer::start(second, second, sample, 0)?;
+    Ok(())
+}
+
+crate::initcall!(timer, "cpuload", init);
+
+// -----------------------------------------------------------------------------
+// KABUK VE PROCFS
+// -----------------------------------------------------------------------------
+
+/// Binde değeri yüzde olarak yazar (örn. 123 → "12.3%").
+struct Percent(u32);
+
+impl fmt::Display for Percent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}%", self.0 / 10, self.0 % 10)
+    }
+}
+
+fn cpuload_command(_args: &[&str]) -> Result<(), KernelError> {
+    serial_println!("  CPU      1s     10s     60s      BOŞTA GİRİŞ");
+    for cpu in 0..MAX_CPUS {
+        let Some(load) = cpu_load(cpu) else { continue };
+        serial_println!(
+            "  {:<3} {:>7} {:>7} {:>7} {:>16}",
+            cpu,
+            Percent(load.permille[0]),
+            Percent(load.permille[1]),
+            Percent(load.permille[2]),
+            load.idle_entries
+        );
+    }
+    Ok(())
+}
+
+crate::shell_command!("cpuload", "Çekirdek başına işlemci kullanımını gösterir", cpuload_command);
+
+/// İlk satır `hz <frekans>`; ardından çekirdek başına
+/// `cpu<n> <boşta> <meşgul> <1s> <10s> <60s>` (sayaç birimi ve binde).
+fn show_cpuload(out: &mut dyn fmt::Write) -> fmt::Result {
+    writeln!(out, "hz {}", clock::cycles_hz())?;
+    for cpu in 0..MAX_CPUS {
+        let Some(load) = cpu_load(cpu) else { continue };
+        writeln!(
+            out,
+            "cpu{} {} {} {} {} {}",
+            cpu, load.idle_cycles, load.busy_cycles, load.permille[0], load.permille[1], load.permille[2]
+        )?;
+    }
+    Ok(())
+}
+
+crate::proc_entry!("cpuload", show_cpuload);
@@ -0,0 +1,180 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/initcall.rs
+// Bağımlılık sırasına göre çalışan başlatma çağrıları (initcall) çerçevesi.
+//
+// Alt sistemler kendilerini `initcall!` makrosu ile kaydeder. Makro, kaydı
+// seviyeye özgü bir linker bölümüne (`.initcall.<seviye>`) yerleştirir;
+// `run_initcalls` bu bölümleri sabit seviye sırasıyla dolaşır. Böylece tüm
+// mimariler aynı, iyi tanımlı başlatma sırasını paylaşır.
+//
+// Linker betiği her seviye için aşağıdaki sembolleri tanımlamalıdır:
+//   __initcall_<seviye>_start = .; KEEP(*(.initcall.<seviye>)) __initcall_<seviye>_end = .;
+
+use core::sync::atomic::{AtomicU8, Ordering};
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// BAŞLATMA SEVİYELERİ
+// -----------------------------------------------------------------------------
+
+/// Başlatma seviyeleri. Sıralama, başlatma sırasıdır.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum InitLevel {
+    /// Erken konsol (seri port vb.). Hata ayıklama çıktısı için ilk çalışır.
+    EarlyCon = 0,
+    /// Bellek yönetimi (MMU, çerçeve ayırıcı, yığın).
+    Mm = 1,
+    /// İstisna vektörleri ve kesme denetleyicisi.
+    Irq = 2,
+    /// Zamanlayıcı ve saat kaynakları.
+    Timer = 3,
+    /// Aygıt sürücüleri.
+    Drivers = 4,
+    /// Dosya sistemleri (initrd vb.).
+    Fs = 5,
+    /// Diğer her şeyden sonra çalışması gereken işler.
+    Late = 6,
+}
+
+impl InitLevel {
+    /// Tüm seviyeler, çalıştırılma sırasıyla.
+    pub const ALL: [InitLevel; 7] = [
+        InitLevel::EarlyCon,
+        InitLevel::Mm,
+        InitLevel::Irq,
+        InitLevel::Timer,
+        InitLevel::Drivers,
+        InitLevel::Fs,
+        InitLevel::Late,
+    ];
+
+    /// Seviyenin log çıktısında kullanılan kısa adı.
+    pub fn name(self) -> &'static str {
+        match self {
+            InitLevel::EarlyCon => "earlycon",
+            InitLevel::Mm => "mm",
+            InitLevel::Irq => "irq",
+            InitLevel::Timer => "timer",
+            InitLevel::Drivers => "drivers",
+            InitLevel::Fs => "fs",
+            InitLevel::Late => "late",
+        }
+    }
+}
+
+/// Linker bölümüne yerleştirilen tek bir başlatma kaydı.
+#[repr(C)]
+pub struct InitCall {
+    /// Alt sistemin adı (log çıktısı için).
+    pub name: &'static str,
+    /// Çalıştırılacak başlatma fonksiyonu.
+    pub func: fn() -> Result<(), KernelError>,
+}
+
+/// Bir başlatma fonksiyonunu verilen seviyeye kaydeder.
+///
+/// # Örnek
+/// `initcall!(timer, "rv64i-clint", clint_init);`
+#[macro_export]
+macro_rules! initcall {
+    ($level:ident, $name:expr, $func:path) => {
+        const _: () = {
+            #[used]
+            #[link_section = concat!(".initcall.", stringify!($level))]
+            static __INITCALL: $crate::initcall::InitCall = $crate::initcall::InitCall {
+                name: $name,
+                func: $func,
+            };
+        };
+    };
+}
+
+// -----------------------------------------------------------------------------
+// LİNKER SEMBOLLERİ
+// -----------------------------------------------------------------------------
+
+extern "C" {
+    static __initcall_earlycon_start: InitCall;
+    static __initcall_earlycon_end: InitCall;
+    static __initcall_mm_start: InitCall;
+    static __initcall_mm_end: InitCall;
+    static __initcall_irq_start: InitCall;
+    static __initcall_irq_end: InitCall;
+    static __initcall_timer_start: InitCall;
+    static __initcall_timer_end: InitCall;
+    static __initcall_drivers_start: InitCall;
+    static __initcall_drivers_end: InitCall;
+    static __initcall_fs_start: InitCall;
+    static __initcall_fs_end: InitCall;
+    static __initcall_late_start: InitCall;
+    static __initcall_late_end: InitCall;
+}
+
+/// Verilen seviyenin linker bölümünü dilim olarak döndürür.
+fn section(level: InitLevel) -> &'static [InitCall] {
+    // SAFETY: Başlangıç/bitiş sembolleri linker betiği tarafından aynı bölümün
+    // iki ucuna yerleştirilir ve arada yalnızca `InitCall` kayıtları bulunur.
+    unsafe {
+        let (start, end): (*const InitCall, *const InitCall) = match level {
+            InitLevel::EarlyCon => (&__initcall_earlycon_start, &__initcall_earlycon_end),
+            InitLevel::Mm => (&__initcall_mm_start, &__initcall_mm_end),
+            InitLevel::Irq => (&__initcall_irq_start, &__initcall_irq_end),
+            InitLevel::Timer => (&__initcall_timer_start, &__initcall_timer_end),
+            InitLevel::Drivers => (&__initcall_drivers_start, &__initcall_drivers_end),
+            InitLevel::Fs => (&__initcall_fs_start, &__initcall_fs_end),
+            InitLevel::Late => (&__initcall_late_start, &__initcall_late_end),
+        };
+        let count = (end as usize - start as usize) / core::mem::size_of::<InitCall>();
+        core::slice::from_raw_parts(start, count)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// ÇALIŞTIRMA
+// -----------------------------------------------------------------------------
+
+/// Son tamamlanan seviyenin bir fazlası (0: hiçbir seviye çalışmadı).
+static COMPLETED_LEVELS: AtomicU8 = AtomicU8::new(0);
+
+/// Verilen seviyenin tamamlanıp tamamlanmadığını döndürür.
+/// Geç kaydolan kodun, bağımlı olduğu alt sistemin hazır olduğunu doğrulaması için.
+pub fn level_done(level: InitLevel) -> bool {
+    COMPLETED_LEVELS.load(Ordering::Acquire) > level as u8
+}
+
+/// Tek bir seviyedeki tüm kayıtlı başlatma fonksiyonlarını çalıştırır.
+///
+/// Başarısız bir başlatma diğerlerini durdurmaz; hata loglanır ve sayılır.
+///
+/// # Dönüş Değeri
+/// Başarısız olan başlatma fonksiyonu sayısı.
+pub fn run_level(level: InitLevel) -> usize {
+    let mut failures = 0;
+
+    for call in section(level) {
+        match (call.func)() {
+            Ok(()) => {}
+            Err(err) => {
+                serial_println!("[INIT] {}/{} başarısız: {:?}", level.name(), call.name, err);
+                failures += 1;
+            }
+        }
+    }
+
+    COMPLETED_LEVELS.fetch_max(level as u8 + 1, Ordering::AcqRel);
+    failures
+}
+
+/// Tüm seviyeleri sırasıyla çalıştırır.
+///
+/// # Güvenlik Notu
+/// Yalnızca tek çekirdekli ilk başlangıçta, `platform_init` içinden bir kez çağrılmalıdır.
+pub fn run_initcalls() {
+    for level in InitLevel::ALL {
+        let failures = run_level(level);
+        serial_println!("[INIT] Seviye '{}' tamamlandı ({} hata).", level.name(), failures);
+    }
+}
@@ -1,5 +1,27 @@
 #![allow(dead_code)] // Geliştirmenin ilk aşaması için uyarıları gizler
 
+// src/initrd.rs
+// Initrd görüntüsü ve initramfs (newc cpio arşivi) açıcısı.
+//
+// Önyükleyici arşivi belleğe yükleyip adresini bildirir (aygıt ağacının
+// `/chosen` düğümü veya Multiboot2 modülü; bkz. `boot::BootInfo::initramfs`);
+// bildirilmezse AMD64'te çekirdeğe gömülü görüntü kullanılır. Arşiv açılışta
+// ramfs'e (`/`) açılır; böylece erken kullanıcı görevleri, yapılandırma ve
+// test programları blok sürücüsü olmayan kartlarda da tek bir önyükleme
+// yükünde gelebilir.
+//
+// * Arşiv alanı `frame::init` içinde çerçeve ayırıcısından ayrılır ve
+//   açıldıktan sonra geri verilir (gömülü görüntü hariç).
+// * Yalnızca dizinler ve normal dosyalar açılır; sembolik bağlar ve aygıt
+//   düğümleri atlanır.
+//
+// Kabuk komutu: `initrd`
+
+use core::sync::atomic::{AtomicBool, Ordering};
+use crate::memory::frame::{self, FRAME_SIZE};
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+
 /// Statik olarak gömülecek Initrd görüntüsünün ham verilerini tutar.
 ///
 /// Not: Gerçek bir çekirdekte, bu veri ya bootloader tarafından
@@ -21,12 +43,24 @@ impl InitRd {
     /// # Güvenlik Notu
     /// Bu fonksiyon, veri dizisinin var olduğunu varsayar. Eğer `INITRD_DATA`
     /// tanımlı değilse, derleme hatası oluşacaktır.
+    #[cfg(target_arch = "x86_64")]
     pub fn new() -> Self {
         InitRd {
             data: INITRD_DATA,
         }
     }
 
+    /// Önyükleyicinin belleğe yüklediği görüntüyü sarar.
+    ///
+    /// # Safety
+    /// `[addr, addr + len)` eşlenmiş olmalı ve sarmalayıcı kullanıldığı sürece
+    /// değişmemelidir.
+    pub unsafe fn from_raw(addr: usize, len: usize) -> Self {
+        InitRd {
+            data: core::slice::from_raw_parts(addr as *const u8, len),
+        }
+    }
+
     /// Initrd görüntüsünün ham bayt dizisine salt okunur erişim sağlar.
     ///
     /// # Dönüş Değeri
@@ -59,6 +93,203 @@ impl InitRd {
         Some(&self.data[offset..offset + length])
     }
 
-    // İleride buraya dosya arama, metadata okuma gibi fonksiyonlar eklenebilir.
+    /// Görüntüyü newc cpio arşivi olarak dolaşır.
+    pub fn entries(&self) -> CpioIter<'static> {
+        CpioIter { data: self.data, pos: 0, done: false }
+    }
 
+    /// Arşivi ramfs'e açar ve (dizin, dosya) sayısını döndürür.
+    ///
+    /// # Hatalar
+    /// * `InvalidArgument`: Arşiv bozuk (başlık, ad veya boyut geçersiz).
+    /// * ramfs hataları (tablo dolu vb.) olduğu gibi döner.
+    pub fn unpack(&self) -> Result<(usize, usize), KernelError> {
+        let (mut dirs, mut files) = (0, 0);
+        for entry in self.entries() {
+            let entry = entry?;
+            match entry.mode & S_IFMT {
+                S_IFDIR => {
+                    crate::ramfs::mkdir(entry.name)?;
+                    dirs += 1;
+                }
+                S_IFREG => {
+                    if let Some(slash) = entry.name.rfind('/') {
+                        crate::ramfs::mkdir(&entry.name[..slash])?;
+                    }
+                    crate::ramfs::truncate(entry.name)?;
+                    crate::ramfs::write(entry.name, 0, entry.data)?;
+                    files += 1;
+                }
+                _ => serial_println!("[INITRD] {} atlandı (mod {:o})", entry.name, entry.mode),
+            }
+        }
+        Ok((dirs, files))
+    }
 }
+
+// -----------------------------------------------------------------------------
+// CPIO (NEWC) AYRIŞTIRICI
+// -----------------------------------------------------------------------------
+
+/// newc başlığı: 6 baytlık sihirli dize ve 13 adet 8 haneli onaltılık alan.
+const CPIO_HEADER_LEN: usize = 110;
+const CPIO_MAGIC: &[u8] = b"070701";
+/// Sağlama toplamlı çeşit; sağlama toplamı denetlenmez.
+const CPIO_MAGIC_CRC: &[u8] = b"070702";
+const CPIO_TRAILER: &str = "TRAILER!!!";
+
+const S_IFMT: u32 = 0o170000;
+const S_IFDIR: u32 = 0o040000;
+const S_IFREG: u32 = 0o100000;
+
+// Başlıktaki alanların sırası
+const FIELD_MODE: usize = 1;
+const FIELD_FILESIZE: usize = 6;
+const FIELD_NAMESIZE: usize = 11;
+
+/// Arşivdeki bir girdi. Ad, baştaki `./` olmadan verilir.
+#[derive(Debug, Clone, Copy)]
+pub struct CpioEntry<'a> {
+    pub name: &'a str,
+    pub mode: u32,
+    pub data: &'a [u8],
+}
+
+/// `InitRd::entries` yineleyicisi; bozuk arşivde bir hata verip durur.
+pub struct CpioIter<'a> {
+    data: &'a [u8],
+    pos: usize,
+    done: bool,
+}
+
+fn cpio_field(header: &[u8], index: usize) -> Option<usize> {
+    let text = core::str::from_utf8(header.get(6 + index * 8..6 + (index + 1) * 8)?).ok()?;
+    usize::from_str_radix(text, 16).ok()
+}
+
+impl<'a> CpioIter<'a> {
+    fn parse(&mut self) -> Result<Option<CpioEntry<'a>>, KernelError> {
+        let header = self.data.get(self.pos..self.pos + CPIO_HEADER_LEN).ok_or(KernelError::InvalidArgument)?;
+        if &header[..6] != CPIO_MAGIC && &header[..6] != CPIO_MAGIC_CRC {
+            return Err(KernelError::InvalidArgument);
+        }
+        let field = |index| cpio_field(header, index).ok_or(KernelError::InvalidArgument);
+        let (mode, size, name_size) = (field(FIELD_MODE)?, field(FIELD_FILESIZE)?, field(FIELD_NAMESIZE)?);
+
+        // Ad sıfırla biter; başlık + ad ve veri 4 bayta hizalanır
+        let name_start = self.pos + CPIO_HEADER_LEN;
+        let name = self
+            .data
+            .get(name_start..name_start + name_size.saturating_sub(1))
+            .and_then(|n| core::str::from_utf8(n).ok())
+            .ok_or(KernelError::InvalidArgument)?;
+        let data_start = (name_start + name_size).next_multiple_of(4);
+        let data = self.data.get(data_start..data_start + size).ok_or(KernelError::InvalidArgument)?;
+        self.pos = (data_start + size).next_multiple_of(4);
+
+        if name == CPIO_TRAILER {
+            return Ok(None);
+        }
+        let name = name.trim_start_matches("./").trim_start_matches('/');
+        Ok(Some(CpioEntry { name, mode: mode as u32, data }))
+    }
+}
+
+impl<'a> Iterator for CpioIter<'a> {
+    type Item = Result<CpioEntry<'a>, KernelError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            match self.parse() {
+                // Kök dizin girdisi (`.`) atlanır
+                Ok(Some(entry)) if entry.name.is_empty() || entry.name == "." => continue,
+                Ok(Some(entry)) => return Some(Ok(entry)),
+                Ok(None) => self.done = true,
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+        None
+    }
+}
+
+// -----------------------------------------------------------------------------
+// AÇILIŞ
+// -----------------------------------------------------------------------------
+
+/// Önyükleyici alanı açıldıktan sonra ayırıcıya geri verildi mi?
+static RELEASED: AtomicBool = AtomicBool::new(false);
+
+/// Önyükleyicinin bildirdiği görüntü alanı.
+fn boot_range() -> Option<(usize, usize)> {
+    crate::boot::info().initramfs
+}
+
+/// Önyükleme görüntüsü: önce önyükleyicinin yüklediği, yoksa (AMD64) gömülü görüntü.
+/// Önyükleyici alanı açılıştan sonra bırakıldığı için artık `None` döner.
+pub fn boot_initrd() -> Option<InitRd> {
+    if let Some((addr, len)) = boot_range() {
+        if RELEASED.load(Ordering::Acquire) {
+            return None;
+        }
+        // SAFETY: Alan önyükleyiciden gelir ve `reserve_initrd` ile ayrılmıştır.
+        return Some(unsafe { InitRd::from_raw(addr, len) });
+    }
+    #[cfg(target_arch = "x86_64")]
+    if !INITRD_DATA.is_empty() {
+        return Some(InitRd::new());
+    }
+    None
+}
+
+/// Çerçeve ayırıcısı görüntünün üzerine yazmasın diye alanı ayırır
+/// (`frame::init` içinden).
+pub(crate) fn reserve_initrd() {
+    if let Some((addr, len)) = boot_range() {
+        frame::reserve(addr, len);
+    }
+}
+
+/// Görüntüyü ramfs'e açar ve önyükleyici alanını ayırıcıya geri verir.
+fn unpack_initramfs() -> Result<(), KernelError> {
+    let Some(initrd) = boot_initrd() else { return Ok(()) };
+    let result = initrd.unpack();
+    match result {
+        Ok((dirs, files)) => serial_println!("[INITRD] {} KiB: {} dizin, {} dosya açıldı", initrd.get_size() / 1024, dirs, files),
+        Err(e) => serial_println!("[INITRD] Arşiv açılamadı: {:?}", e),
+    }
+
+    // Yalnızca tamamen görüntüye ait çerçeveler bırakılır
+    if let Some((addr, len)) = boot_range() {
+        let first = addr.next_multiple_of(FRAME_SIZE);
+        let last = (addr + len) / FRAME_SIZE * FRAME_SIZE;
+        RELEASED.store(true, Ordering::Release);
+        if last > first {
+            frame::free(first, (last - first) / FRAME_SIZE).ok();
+        }
+    }
+    result.map(|_| ())
+}
+
+crate::initcall!(fs, "initramfs", unpack_initramfs);
+
+// -----------------------------------------------------------------------------
+// KABUK KOMUTU
+// -----------------------------------------------------------------------------
+
+fn initrd_command(_args: &[&str]) -> Result<(), KernelError> {
+    let Some(initrd) = boot_initrd() else {
+        serial_println!("Initrd yok veya açıldıktan sonra belleği bırakıldı.");
+        return Ok(());
+    };
+    serial_println!("  MOD      BOYUT  AD");
+    for entry in initrd.entries() {
+        let entry = entry?;
+        serial_println!("  {:06o} {:>7}  {}", entry.mode, entry.data.len(), entry.name);
+    }
+    Ok(())
+}
+
+crate::shell_command!("initrd", "Initramfs arşivinin içeriğini listeler", initrd_command);
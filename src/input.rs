@@ -0,0 +1,119 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/input.rs
+// Girdi olay kuyruğu: klavye sürücülerinden kabuğa ve uygulamalara tuş olayları.
+//
+// Üreticiler (USB HID, PS/2 vb.) olayları kesme bağlamından `push` ile ekler;
+// tüketici `pop` veya yalnızca karakter üreten basışlar için `read_char`
+// kullanır. Kuyruk tek üretici / tek tüketici halkasıdır: tüm üreticiler
+// kesme bağlamında (iç içe geçmeden) çalışmalıdır. Kuyruk doluysa yeni olay
+// atılır ve `input.dropped` sayacı artar.
+//
+// Seri konsolun alınan baytları da `push_byte` ile aynı kuyruğa girer; kabuk
+// tek bir kaynaktan okur (bkz. `shell::console_task`).
+
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+// -----------------------------------------------------------------------------
+// OLAYLAR
+// -----------------------------------------------------------------------------
+
+// Değiştirici tuş bitleri (USB HID önyükleme raporu ile aynı düzen)
+pub const MOD_LCTRL: u8 = 1 << 0;
+pub const MOD_LSHIFT: u8 = 1 << 1;
+pub const MOD_LALT: u8 = 1 << 2;
+pub const MOD_LGUI: u8 = 1 << 3;
+pub const MOD_RCTRL: u8 = 1 << 4;
+pub const MOD_RSHIFT: u8 = 1 << 5;
+pub const MOD_RALT: u8 = 1 << 6;
+pub const MOD_RGUI: u8 = 1 << 7;
+
+pub const MOD_CTRL: u8 = MOD_LCTRL | MOD_RCTRL;
+pub const MOD_SHIFT: u8 = MOD_LSHIFT | MOD_RSHIFT;
+
+/// Tek bir tuş olayı.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyEvent {
+    /// USB HID kullanım kodu (Keyboard/Keypad sayfası).
+    pub usage: u8,
+    /// Olay anındaki değiştirici tuşlar (`MOD_*`).
+    pub modifiers: u8,
+    pub pressed: bool,
+    /// Tuşun ürettiği ASCII karakter (yoksa 0).
+    pub ascii: u8,
+}
+
+impl KeyEvent {
+    const EMPTY: KeyEvent = KeyEvent { usage: 0, modifiers: 0, pressed: false, ascii: 0 };
+}
+
+// -----------------------------------------------------------------------------
+// KUYRUK
+// -----------------------------------------------------------------------------
+
+/// Kuyruk kapasitesi (ikinin kuvveti).
+pub const QUEUE_SIZE: usize = 128;
+
+struct EventQueue {
+    events: UnsafeCell<[KeyEvent; QUEUE_SIZE]>,
+    /// Üreticinin yazacağı sıradaki konum (serbest artan).
+    head: AtomicUsize,
+    /// Tüketicinin okuyacağı sıradaki konum (serbest artan).
+    tail: AtomicUsize,
+}
+
+// Tek üretici / tek tüketici: yuvaya yazma `head` yayımlanmadan önce biter.
+unsafe impl Sync for EventQueue {}
+
+static QUEUE: EventQueue = EventQueue {
+    events: UnsafeCell::new([KeyEvent::EMPTY; QUEUE_SIZE]),
+    head: AtomicUsize::new(0),
+    tail: AtomicUsize::new(0),
+};
+
+crate::kernel_counter!(DROPPED, "input.dropped");
+
+/// Kuyruğa bir olay ekler (kesme bağlamından).
+pub fn push(event: KeyEvent) {
+    let head = QUEUE.head.load(Ordering::Relaxed);
+    if head.wrapping_sub(QUEUE.tail.load(Ordering::Acquire)) >= QUEUE_SIZE {
+        DROPPED.inc();
+        return;
+    }
+    unsafe { (*QUEUE.events.get())[head % QUEUE_SIZE] = event };
+    QUEUE.head.store(head.wrapping_add(1), Ordering::Release);
+}
+
+/// Seri konsoldan gelen ham baytı kuyruğa ekler (UART RX kesmesinden).
+/// Kullanım kodu 0'dır; kaçış dizileri satır düzenleyicide çözülür.
+pub fn push_byte(byte: u8) {
+    crate::replay::uart_byte(byte);
+    push(KeyEvent { usage: 0, modifiers: 0, pressed: true, ascii: byte });
+}
+
+/// Sıradaki olayı alır.
+pub fn pop() -> Option<KeyEvent> {
+    let tail = QUEUE.tail.load(Ordering::Relaxed);
+    if tail == QUEUE.head.load(Ordering::Acquire) {
+        return None;
+    }
+    let event = unsafe { (*QUEUE.events.get())[tail % QUEUE_SIZE] };
+    QUEUE.tail.store(tail.wrapping_add(1), Ordering::Release);
+    Some(event)
+}
+
+/// Karakter üreten sıradaki tuş basışını döndürür; diğer olaylar atılır.
+pub fn read_char() -> Option<u8> {
+    while let Some(event) = pop() {
+        if event.pressed && event.ascii != 0 {
+            return Some(event.ascii);
+        }
+    }
+    None
+}
+
+/// Bekleyen olay sayısı.
+pub fn pending() -> usize {
+    QUEUE.head.load(Ordering::Acquire).wrapping_sub(QUEUE.tail.load(Ordering::Acquire))
+}
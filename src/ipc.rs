@@ -1,5 +1,8 @@
 use core::sync::atomic::{AtomicUsize, Ordering};
 use core::cell::UnsafeCell;
+use crate::deadlock::{self, Resource, ResourceKind};
+use crate::platformgeneric::KernelError;
+use crate::sched::TaskId;
 
 /// Her mesaj için maksimum sabit boyutu tanımlar.
 ///
@@ -101,6 +104,7 @@ impl IpcQueue {
 
         let next_tail = (tail + 1) % QUEUE_DEPTH;
         self.tail.store(next_tail, Ordering::Release);
+        deadlock::wake_waiters(Resource::new(ResourceKind::QueueData, self));
         
         Ok(())
     }
@@ -127,7 +131,42 @@ impl IpcQueue {
 
         let next_head = (head + 1) % QUEUE_DEPTH;
         self.head.store(next_head, Ordering::Release);
+        deadlock::wake_waiters(Resource::new(ResourceKind::QueueSpace, self));
 
         Some(message)
     }
+
+    /// Kuyruğu okuyacak görevi bağlar. Dolu kuyrukta bloklanan göndericiler
+    /// bu görevi bekliyor sayılır; böylece iki görevin birbirinin dolu
+    /// kuyruğuna yazarken kilitlenmesi algılanır.
+    pub fn bind_receiver(&self, task: TaskId) {
+        deadlock::set_owner(Resource::new(ResourceKind::QueueSpace, self), Some(task));
+    }
+
+    /// Mesajı gönderir; kuyruk doluysa yer açılana kadar bloklanır.
+    ///
+    /// # Hatalar
+    /// * `Deadlock`: Beklemek bir bekleme döngüsünü kapatırdı.
+    pub fn send_blocking(&self, mut message: IpcMessage) -> Result<(), KernelError> {
+        let resource = Resource::new(ResourceKind::QueueSpace, self);
+        loop {
+            match self.send(message) {
+                Ok(()) => return Ok(()),
+                Err(returned) => message = returned,
+            }
+            deadlock::wait_until(resource, || !self.is_full())?;
+        }
+    }
+
+    /// Mesaj alır; kuyruk boşsa mesaj gelene kadar bloklanır. Göndericiler
+    /// belli olmadığından bu bekleme kilitlenme grafiğinde kenar oluşturmaz.
+    pub fn receive_blocking(&self) -> Result<IpcMessage, KernelError> {
+        let resource = Resource::new(ResourceKind::QueueData, self);
+        loop {
+            if let Some(message) = self.receive() {
+                return Ok(message);
+            }
+            deadlock::wait_until(resource, || !self.is_empty())?;
+        }
+    }
 }
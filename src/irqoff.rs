@@ -0,0 +1,225 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/irqoff.rs
+// Yerel kesmeleri kapatan bölgeler ve kesme kapalı süre bütçesi.
+//
+// Kesmelerin kapalı kaldığı en uzun süre, sistemin en kötü kesme
+// gecikmesinin alt sınırıdır. Kesmeleri kapatan çekirdek kodu bunu bu
+// modülün sarmalayıcılarıyla yapar: `save`/`restore` çifti ve onun
+// üzerindeki `IrqSafeSpinlock` (`platformgeneric::spinlock`). Maskenin
+// kendisi her portun `interrupt` modülündedir (`local_irq_save` ve
+// arkadaşları, bkz. `arch/srcarch.rs` cephe sözleşmesi); buradaki kod
+// mimariden bağımsızdır.
+//
+// `irqlatency` özelliğiyle derlenince (hata ayıklama kipi) her dış bölge
+// ölçülür: kesmeleri kapatan `save` başlangıç damgasını ve çağrı yerini
+// (`#[track_caller]`) işlemci başına kaydeder, kesmeleri yeniden açan
+// `restore` süreyi hesaplar. İç içe bölgeler (kesmeler zaten kapalıyken
+// alınan `save`) dıştaki bölgenin parçasıdır. Süre `irqoff.budget_us`
+// bütçesini aşarsa iki çağrı yeri de yazdırılır ve `irqoff.panic` açıksa
+// (varsayılan) çekirdek durdurulur; QEMU altındaki CI koşuları gecikme
+// disiplinini böylece doğrudan denetler. TCG'de zaman ölçümü gürültülü
+// olduğundan bütçe konak hızına göre ayarlanmalıdır.
+//
+// Donanımın kesmeleri kapatarak girdiği işleyiciler ölçülmez. Boşta
+// bekleme öncesi kesmeleri koşulsuz açan `enable` açık bölgeyi kapatır.
+//
+// Kabuk komutu: `irqoff [reset]`; procfs: `/proc/irqoff` (`irqlatency` ile)
+
+use crate::arch::interrupt;
+
+// -----------------------------------------------------------------------------
+// KESME MASKESİ
+// -----------------------------------------------------------------------------
+
+/// Yerel kesmeleri kapatır; önceki durumu döndürür.
+#[inline(always)]
+#[track_caller]
+pub fn save() -> usize {
+    let flags = interrupt::local_irq_save();
+    #[cfg(feature = "irqlatency")]
+    if was_enabled(flags) {
+        check::begin(core::panic::Location::caller());
+    }
+    flags
+}
+
+/// `save` öncesindeki kesme durumunu geri yükler.
+#[inline(always)]
+#[track_caller]
+pub fn restore(flags: usize) {
+    #[cfg(feature = "irqlatency")]
+    if was_enabled(flags) {
+        check::end(core::panic::Location::caller());
+    }
+    interrupt::local_irq_restore(flags);
+}
+
+/// `save` çağrılmadan önce kesmeler açık mıydı?
+#[inline(always)]
+fn was_enabled(flags: usize) -> bool {
+    interrupt::irqs_were_enabled(flags)
+}
+
+/// Yerel kesmeleri koşulsuz açar (boşta bekleme, görevin ilk girişi).
+/// Kesmeler kapalıysa açık ölçüm bölgesi burada biter.
+#[inline(always)]
+#[track_caller]
+pub fn enable() {
+    #[cfg(feature = "irqlatency")]
+    if !was_enabled(interrupt::local_irq_save()) {
+        check::end(core::panic::Location::caller());
+    }
+    interrupt::local_irq_enable();
+}
+
+/// `f`'yi yerel kesmeler kapalıyken çalıştırır.
+#[inline]
+#[track_caller]
+pub fn without_interrupts<R>(f: impl FnOnce() -> R) -> R {
+    let flags = save();
+    let result = f();
+    restore(flags);
+    result
+}
+
+// -----------------------------------------------------------------------------
+// SÜRE DENETİMİ (`irqlatency`)
+// -----------------------------------------------------------------------------
+
+#[cfg(feature = "irqlatency")]
+mod check {
+    use core::fmt::{self, Write};
+    use core::panic::Location;
+    use core::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+    use crate::clock;
+    use crate::platformgeneric::KernelError;
+    use crate::serial_println;
+    use crate::sysctl::{Flag, U32};
+
+    crate::sysctl!(BUDGET_US: U32 = U32::new(100, 0, 1_000_000),
+        "irqoff.budget_us", "Kesmelerin kapalı kalabileceği en uzun süre (µs, 0: denetim yok)");
+    crate::sysctl!(PANIC: Flag = Flag::new(true),
+        "irqoff.panic", "Bütçe aşılınca çekirdeği durdurur");
+
+    crate::kernel_counter!(OVERRUNS, "irqoff.overruns");
+
+    /// İşlemcinin süren ve en uzun kesme kapalı bölgesi.
+    struct Region {
+        /// Süren bölgenin başlangıç damgası (0: kesmeler açık).
+        start: AtomicU64,
+        start_site: AtomicUsize,
+        worst: AtomicU64,
+        worst_start: AtomicUsize,
+        worst_end: AtomicUsize,
+    }
+
+    crate::percpu!(REGION: Region = Region {
+        start: AtomicU64::new(0),
+        start_site: AtomicUsize::new(0),
+        worst: AtomicU64::new(0),
+        worst_start: AtomicUsize::new(0),
+        worst_end: AtomicUsize::new(0),
+    });
+
+    fn site(location: &'static Location<'static>) -> usize {
+        location as *const Location<'static> as usize
+    }
+
+    /// `site` ile saklanan çağrı yeri.
+    struct Site(usize);
+
+    impl fmt::Display for Site {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            if self.0 == 0 {
+                return f.write_str("?");
+            }
+            // SAFETY: Değer `site` ile `&'static Location`'dan üretildi.
+            let location = unsafe { &*(self.0 as *const Location<'static>) };
+            write!(f, "{}:{}", location.file(), location.line())
+        }
+    }
+
+    pub(super) fn begin(location: &'static Location<'static>) {
+        let region = REGION.get();
+        region.start_site.store(site(location), Ordering::Relaxed);
+        region.start.store(clock::cycles().max(1), Ordering::Relaxed);
+    }
+
+    pub(super) fn end(location: &'static Location<'static>) {
+        let region = REGION.get();
+        let start = region.start.swap(0, Ordering::Relaxed);
+        // Ölçüm açılmadan önce başlamış bölge
+        if start == 0 {
+            return;
+        }
+        let Some(ns) = clock::cycles_to_ns(clock::cycles().wrapping_sub(start)) else { return };
+        let start_site = region.start_site.load(Ordering::Relaxed);
+        if ns > region.worst.load(Ordering::Relaxed) {
+            region.worst.store(ns, Ordering::Relaxed);
+            region.worst_start.store(start_site, Ordering::Relaxed);
+            region.worst_end.store(site(location), Ordering::Relaxed);
+        }
+        let budget = u64::from(BUDGET_US.get());
+        if budget == 0 || ns <= budget * 1000 {
+            return;
+        }
+        OVERRUNS.inc();
+        serial_println!(
+            "[IRQOFF] Kesmeler {} µs kapalı kaldı (bütçe {} µs): {} -> {}",
+            ns / 1000,
+            budget,
+            Site(start_site),
+            location
+        );
+        if PANIC.get() {
+            panic!("kesme kapalı süre bütçesi aşıldı ({} -> {})", Site(start_site), location);
+        }
+    }
+
+    fn show_irqoff(out: &mut dyn Write) -> fmt::Result {
+        for (cpu, region) in REGION.iter() {
+            writeln!(
+                out,
+                "cpu{} worst_ns {} start {} end {}",
+                cpu,
+                region.worst.load(Ordering::Relaxed),
+                Site(region.worst_start.load(Ordering::Relaxed)),
+                Site(region.worst_end.load(Ordering::Relaxed))
+            )?;
+        }
+        writeln!(out, "budget_us {}", BUDGET_US.get())?;
+        writeln!(out, "overruns {}", OVERRUNS.get())
+    }
+
+    crate::proc_entry!("irqoff", show_irqoff);
+
+    /// `irqoff [reset]`
+    fn irqoff_command(args: &[&str]) -> Result<(), KernelError> {
+        match args.get(1).copied() {
+            None => {
+                serial_println!("  CPU  EN UZUN (µs)  BAŞLANGIÇ -> BİTİŞ");
+                for (cpu, region) in REGION.iter() {
+                    serial_println!(
+                        "  {:<4} {:>12}  {} -> {}",
+                        cpu,
+                        region.worst.load(Ordering::Relaxed) / 1000,
+                        Site(region.worst_start.load(Ordering::Relaxed)),
+                        Site(region.worst_end.load(Ordering::Relaxed))
+                    );
+                }
+                serial_println!("Bütçe: {} µs, aşım: {}", BUDGET_US.get(), OVERRUNS.get());
+                Ok(())
+            }
+            Some("reset") => {
+                for (_, region) in REGION.iter() {
+                    region.worst.store(0, Ordering::Relaxed);
+                }
+                Ok(())
+            }
+            _ => Err(KernelError::InvalidArgument),
+        }
+    }
+
+    crate::shell_command!("irqoff", "Kesme kapalı bölgelerin en uzun süresi (irqoff [reset])", irqoff_command);
+}
@@ -0,0 +1,158 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/irqquarantine.rs
+// Bilinmeyen veya işleyicisi olmayan kesmelerin karantinası.
+//
+// Sürücüsü olmayan bir kesme sessizce yok sayılırsa (ya da her seferinde tek
+// satır yazdırılırsa) seviye tetiklemeli kaynak durumu temizlenmediği için
+// hemen yeniden gelir; EOI gönderilmezse denetleyici daha düşük öncelikli
+// kesmeleri de tutar. Mimarinin dağıtıcısı, hiçbir sürücünün sahiplenmediği
+// kesmeyi `quarantine`'e verir:
+//
+// * Kesme başına ilk gelişte vektör, denetleyici durumu (çağıranın okuduğu
+//   bekleyen/hizmetteki/maske yazmaçları) ve kaydedilen bağlam bir kez
+//   yazdırılır; sonraki gelişler yalnızca sayılır.
+// * Kaynak denetleyicide maskelenir (`irqstorm` ile aynı mimari bağlantı).
+//   Maskelenemiyorsa kesme sayılmaya devam eder ve durum "maskelenemedi"
+//   olarak görünür.
+// * EOI'yi çağıran gönderir; bu modül denetleyici protokolünü bilmez.
+// * Maskeli kesme kendiliğinden açılmaz (fırtına maskesinden farkı):
+//   sürücü yüklendikten sonra `irqquarantine release <irq>` ile açılır.
+//
+// Kayıt kilitsizdir (atomik) ve kesme bağlamında yapılır.
+//
+// Kabuk komutu: `irqquarantine`; procfs: `/proc/irqquarantine`
+
+use core::fmt::{self, Write};
+use core::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+use crate::stats::MAX_IRQS;
+
+// -----------------------------------------------------------------------------
+// DURUM
+// -----------------------------------------------------------------------------
+
+/// Kesme başına karantina durumu.
+struct Slot {
+    /// İşleyicisiz geliş sayısı.
+    count: AtomicU32,
+    /// Ayrıntılı rapor yazıldı mı?
+    reported: AtomicBool,
+    masked: AtomicBool,
+}
+
+impl Slot {
+    const fn new() -> Self {
+        Slot { count: AtomicU32::new(0), reported: AtomicBool::new(false), masked: AtomicBool::new(false) }
+    }
+}
+
+#[allow(clippy::declare_interior_mutable_const)]
+const EMPTY: Slot = Slot::new();
+static SLOTS: [Slot; MAX_IRQS] = [EMPTY; MAX_IRQS];
+
+crate::kernel_counter!(UNHANDLED, "irq.unhandled");
+crate::kernel_counter!(QUARANTINED, "irq.quarantined");
+
+// -----------------------------------------------------------------------------
+// KARANTİNA
+// -----------------------------------------------------------------------------
+
+/// Hiçbir sürücünün sahiplenmediği kesmeyi kaydeder ve kaynağını maskeler.
+///
+/// # Parametreler
+/// * `controller`: Denetleyicinin o anki durumu (örn. 8259 IRR/ISR/IMR).
+/// * `context`: Kesilen bağlamın kaydedilen yazmaçları.
+///
+/// # Dönüş Değeri
+/// Kaynak bu çağrıda veya daha önce maskelendiyse `true`.
+pub fn quarantine(irq: u32, controller: fmt::Arguments, context: fmt::Arguments) -> bool {
+    UNHANDLED.inc();
+    let Some(slot) = SLOTS.get(irq as usize) else {
+        serial_println!("[IRQ] İşleyicisiz kesme {} (tablo dışı)", irq);
+        return false;
+    };
+    slot.count.fetch_add(1, Ordering::Relaxed);
+    if slot.masked.load(Ordering::Relaxed) {
+        return true;
+    }
+
+    let masked = crate::irqstorm::arch_mask(irq, true);
+    slot.masked.store(masked, Ordering::Relaxed);
+    if masked {
+        QUARANTINED.inc();
+    }
+    if !slot.reported.swap(true, Ordering::Relaxed) {
+        serial_println!("[IRQ] İşleyicisiz kesme {}: karantinaya alındı ({})", irq, if masked { "maskelendi" } else { "maskelenemedi" });
+        serial_println!("      Denetleyici: {}", controller);
+        serial_println!("      Bağlam: {}", context);
+    }
+    masked
+}
+
+/// Kesme karantinada maskeli mi?
+pub fn is_quarantined(irq: u32) -> bool {
+    SLOTS.get(irq as usize).is_some_and(|s| s.masked.load(Ordering::Relaxed))
+}
+
+/// Karantinadaki kesmenin maskesini kaldırır ve kaydını sıfırlar; kesme
+/// yeniden işleyicisiz gelirse tekrar raporlanır.
+///
+/// # Hatalar
+/// * `InvalidArgument`: Kesme numarası tablo dışında.
+/// * `NotFound`: Kesme karantinada değil.
+pub fn release(irq: u32) -> Result<(), KernelError> {
+    let slot = SLOTS.get(irq as usize).ok_or(KernelError::InvalidArgument)?;
+    if !slot.masked.swap(false, Ordering::Relaxed) {
+        return Err(KernelError::NotFound);
+    }
+    slot.reported.store(false, Ordering::Relaxed);
+    crate::irqstorm::arch_mask(irq, false);
+    Ok(())
+}
+
+// -----------------------------------------------------------------------------
+// KABUK VE PROCFS
+// -----------------------------------------------------------------------------
+
+fn active() -> impl Iterator<Item = (usize, &'static Slot)> {
+    SLOTS.iter().enumerate().filter(|(_, s)| s.count.load(Ordering::Relaxed) != 0)
+}
+
+fn show_irqquarantine(out: &mut dyn Write) -> fmt::Result {
+    for (irq, slot) in active() {
+        writeln!(
+            out,
+            "irq{} unhandled {} masked {}",
+            irq,
+            slot.count.load(Ordering::Relaxed),
+            slot.masked.load(Ordering::Relaxed) as u8
+        )?;
+    }
+    Ok(())
+}
+
+crate::proc_entry!("irqquarantine", show_irqquarantine);
+
+/// `irqquarantine [release <irq>]`
+fn irqquarantine_command(args: &[&str]) -> Result<(), KernelError> {
+    match (args.get(1).copied(), args.get(2)) {
+        (None, _) => {
+            serial_println!("  IRQ  İŞLEYİCİSİZ  DURUM");
+            for (irq, slot) in active() {
+                serial_println!(
+                    "  {:>3}  {:>11}  {}",
+                    irq,
+                    slot.count.load(Ordering::Relaxed),
+                    if slot.masked.load(Ordering::Relaxed) { "maskeli" } else { "açık" }
+                );
+            }
+            Ok(())
+        }
+        (Some("release"), Some(irq)) => release(irq.parse().map_err(|_| KernelError::InvalidArgument)?),
+        _ => Err(KernelError::InvalidArgument),
+    }
+}
+
+crate::shell_command!("irqquarantine", "İşleyicisiz kesme karantinası (irqquarantine [release <irq>])", irqquarantine_command);
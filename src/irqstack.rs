@@ -0,0 +1,173 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/irqstack.rs
+// İşlemci başına kesme yığınları ve iç içe kesme denetimi.
+//
+// Kesmeler kesilen görevin yığınında çalışırsa küçük görev yığınları taşar.
+// Her işlemciye `IRQ_STACK_PAGES` sayfalık ayrı bir kesme yığını ayrılır ve
+// mimarinin giriş kodu kesme gelince bu yığına geçer:
+//
+// * AMD64: TSS'nin IST1 girdisi; 32 ve üstü vektörler IST1'i kullanır.
+//   Çift hata ayrı bir acil durum yığınına (IST2) alınır.
+// * ARMv9: Görevler `SP_EL0` ile çalışır (`SPSel = 0`); istisna girişinde
+//   donanım `SP_EL1`'e, yani kesme yığınına geçer.
+// * RISC-V: Tuzak girişi `sscratch`'teki işlemci başına `TrapScratch`
+//   yapısından kesme yığınının üstünü okur ve en dış seviyede ona geçer.
+//
+// Diğer mimarilerde kesmeler henüz görev yığınında çalışır; iç içe geçme
+// denetimi yine de uygulanır. Mimari işleyiciler ilk iş olarak `enter` ile
+// bir `IrqGuard` alır: iç içe geçme derinliği `MAX_NESTING`'i aşarsa veya
+// yığın dibine `RED_ZONE`'dan fazla yaklaşılırsa çekirdek paniğe girer.
+// Kesme yığınındayken görev değiştirilmez: dönüş yolu çerçeveyi önce görev
+// yığınına taşır (bkz. `sched::preempt_if_needed`).
+//
+// Kesme yığını ayrılırken görev yığınları gibi boyanır; `high_water_mark`
+// işleyicilerin gerçekten ulaştığı derinliği verir (girişte örneklenen
+// `LOW_WATER` yalnızca işleyici başlarken kalan yeri gösterir). Ölçümlerin
+// toplu görünümü ve statik çözümlemeyle karşılaştırma: `stackusage`.
+//
+// Kabuk komutu: `irqstack`
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+use crate::arch::exception;
+use crate::memory::frame::{self, FRAME_SIZE};
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// SABİTLER VE İŞLEMCİ BAŞINA DURUM
+// -----------------------------------------------------------------------------
+
+/// Kesme yığınının boyutu (sayfa).
+pub const IRQ_STACK_PAGES: usize = 4;
+pub const IRQ_STACK_SIZE: usize = IRQ_STACK_PAGES * FRAME_SIZE;
+
+/// İzin verilen en derin iç içe kesme/istisna.
+pub const MAX_NESTING: usize = 4;
+
+/// Yığın dibine bu kadar yaklaşan işleyici taşma sayılır (bayt).
+pub const RED_ZONE: usize = 512;
+
+/// Kesme yığınının üstü (0: ayrılmadı).
+crate::percpu!(STACK_TOP: AtomicUsize = AtomicUsize::new(0));
+/// Süren kesme/istisna işleyicisi sayısı.
+crate::percpu!(DEPTH: AtomicUsize = AtomicUsize::new(0));
+/// Görülen en derin iç içe geçme.
+crate::percpu!(MAX_DEPTH: AtomicUsize = AtomicUsize::new(0));
+/// Kesme yığınında görülen en düşük adres (kullanım ölçüsü).
+crate::percpu!(LOW_WATER: AtomicUsize = AtomicUsize::new(usize::MAX));
+
+crate::kernel_counter!(IRQ_NESTED, "irq.nested");
+
+// -----------------------------------------------------------------------------
+// KURULUM
+// -----------------------------------------------------------------------------
+
+/// Çalışılan işlemcinin kesme yığınını ayırır ve giriş koduna tanıtır.
+/// Açılış işlemcisi için `irq` seviyesinde çağrılır; ikincil işlemciler
+/// `percpu::enter` sonrasında, kesmeler açılmadan önce çağırmalıdır.
+///
+/// # Hatalar
+/// * `ResourceBusy`: Bu işlemcinin kesme yığını zaten var.
+/// * `OutOfMemoryStatic`: Yığın için çerçeve yok.
+pub fn setup_cpu() -> Result<(), KernelError> {
+    if !exception::SWITCHES_IRQ_STACK {
+        return Ok(());
+    }
+    if STACK_TOP.get().load(Ordering::Acquire) != 0 {
+        return Err(KernelError::ResourceBusy);
+    }
+    let bottom = frame::alloc_contiguous(IRQ_STACK_PAGES)?;
+    let top = bottom + IRQ_STACK_SIZE;
+    // SAFETY: Çerçeveler yeni ayrıldı; kullanım ölçümü için boyanır.
+    unsafe { core::ptr::write_bytes(bottom as *mut u8, crate::memory::memory::STACK_PAINT, IRQ_STACK_SIZE) };
+    // SAFETY: Yığın yeni ayrıldı ve yalnızca bu işlemcinin giriş koduna verilir.
+    if let Err(e) = unsafe { exception::install_irq_stack(top) } {
+        let _ = frame::free(bottom, IRQ_STACK_PAGES);
+        return Err(e);
+    }
+    STACK_TOP.get().store(top, Ordering::Release);
+    Ok(())
+}
+
+fn init() -> Result<(), KernelError> {
+    setup_cpu()
+}
+
+crate::initcall!(irq, "irqstack", init);
+
+// -----------------------------------------------------------------------------
+// İŞLEYİCİ GİRİŞ/ÇIKIŞ
+// -----------------------------------------------------------------------------
+
+/// Kesme/istisna işleyicisi süresince tutulur; bırakılınca derinlik azalır.
+pub struct IrqGuard(());
+
+impl Drop for IrqGuard {
+    fn drop(&mut self) {
+        DEPTH.get().fetch_sub(1, Ordering::Release);
+    }
+}
+
+/// Mimari işleyicinin ilk işi. `sp`, işleyicinin yığınındaki bir adrestir
+/// (genelde kaydedilen bağlamın adresi).
+///
+/// # Panik
+/// İç içe geçme `MAX_NESTING`'i aşarsa veya kesme yığınında `RED_ZONE`'dan
+/// az yer kalmışsa.
+#[inline]
+pub fn enter(sp: usize) -> IrqGuard {
+    let depth = DEPTH.get().fetch_add(1, Ordering::Acquire) + 1;
+    if depth > 1 {
+        IRQ_NESTED.inc();
+        MAX_DEPTH.get().fetch_max(depth, Ordering::Relaxed);
+    }
+    if depth > MAX_NESTING {
+        panic!("iç içe kesme sınırı aşıldı (derinlik {})", depth);
+    }
+    let top = STACK_TOP.get().load(Ordering::Relaxed);
+    if top != 0 && (top - IRQ_STACK_SIZE..top).contains(&sp) {
+        LOW_WATER.get().fetch_min(sp, Ordering::Relaxed);
+        if sp - (top - IRQ_STACK_SIZE) < RED_ZONE {
+            panic!("kesme yığını taşıyor (sp {:#x}, derinlik {})", sp, depth);
+        }
+    }
+    IrqGuard(())
+}
+
+/// `cpu`'nun kesme yığınının açılıştan bu yana en yüksek kullanımı (bayt);
+/// kesme yığını yoksa `None`.
+pub fn high_water_mark(cpu: usize) -> Option<usize> {
+    let top = STACK_TOP.get_for(cpu)?.load(Ordering::Acquire);
+    if top == 0 {
+        return None;
+    }
+    // SAFETY: Yığın `setup_cpu`'da ayrıldı, boyandı ve hiç bırakılmaz.
+    Some(unsafe { crate::stackusage::painted_usage(top - IRQ_STACK_SIZE, IRQ_STACK_SIZE) })
+}
+
+/// Bir kesme veya istisna işleyicisinin içinde miyiz?
+#[inline]
+pub fn in_interrupt() -> bool {
+    DEPTH.get().load(Ordering::Relaxed) != 0
+}
+
+// -----------------------------------------------------------------------------
+// KABUK KOMUTU
+// -----------------------------------------------------------------------------
+
+fn irqstack_command(_args: &[&str]) -> Result<(), KernelError> {
+    serial_println!("  CPU  YIĞIN ÜSTÜ          EN ÇOK KULLANIM  ÖLÇÜLEN    EN DERİN");
+    for (cpu, top) in STACK_TOP.iter() {
+        let top = top.load(Ordering::Acquire);
+        let low = LOW_WATER.get_for(cpu).map_or(usize::MAX, |l| l.load(Ordering::Relaxed));
+        let used = if top == 0 || low == usize::MAX { 0 } else { top - low };
+        let deepest = MAX_DEPTH.get_for(cpu).map_or(0, |d| d.load(Ordering::Relaxed));
+        let measured = high_water_mark(cpu).unwrap_or(0);
+        serial_println!("  {:<4} {:#018x} {:>9} bayt  {:>9}  {:>8}", cpu, top, used, measured, deepest);
+    }
+    serial_println!("Yığın: {} bayt, iç içe sınır: {}, iç içe kesme: {}", IRQ_STACK_SIZE, MAX_NESTING, IRQ_NESTED.get());
+    Ok(())
+}
+
+crate::shell_command!("irqstack", "Kesme yığınlarının kullanımını gösterir", irqstack_command);
@@ -0,0 +1,202 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/irqstorm.rs
+// Kesme fırtınası algılama ve otomatik maskeleme.
+//
+// Sürekli kesme üreten (durumu temizlenmeyen, yanlış yapılandırılmış) bir
+// aygıt işlemciyi kesme işleyicisinde kilitleyebilir; görevler hiç
+// çalışamaz. Mimarinin kesme dağıtıcısı her kesmede `irq_fired` çağırır:
+//
+// * Kesme başına `irq.storm_window_ms` uzunluğunda pencerelerde sayım yapılır.
+// * Pencere içindeki sayı `irq.storm_threshold` değerini aşar ve pencere
+//   başından beri hiç bağlam geçişi olmamışsa (`sched.switches` değişmedi,
+//   görevler ilerlemiyor) kesme denetleyicide maskelenir, işleyicinin adıyla
+//   uyarı yazdırılır ve `irq_fired` `false` döndürür (dağıtıcı sürücüyü
+//   çağırmaz, yalnızca EOI gönderir).
+// * Periyodik zamanlayıcı her `irq.storm_retry_ms` milisaniyede (açılıştaki
+//   değer) maskeli kesmelerin maskesini kaldırır; fırtına sürüyorsa kesme
+//   yeniden maskelenir.
+//
+// Kayıt kilitsizdir (atomik) ve kesme bağlamında yapılır. Maskeleme yalnızca
+// denetleyicisi desteklenen mimarilerde yapılır (amd64 8259 PIC hatları,
+// armv9 GIC, rv64i PLIC, mips64 CP0 Status.IM); diğerlerinde fırtına sayılır
+// ve yazdırılır. Aynı bağlantıyı `irqquarantine` de kullanır.
+//
+// Kabuk komutu: `irqstorm`; procfs: `/proc/irqstorm`
+
+use core::fmt::{self, Write};
+use core::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+use crate::stats::MAX_IRQS;
+use crate::sysctl::U32;
+
+// -----------------------------------------------------------------------------
+// AYARLAR VE DURUM
+// -----------------------------------------------------------------------------
+
+crate::sysctl!(THRESHOLD: U32 = U32::new(20_000, 100, 10_000_000),
+    "irq.storm_threshold", "Fırtına sayılan pencere başına kesme sayısı");
+crate::sysctl!(WINDOW_MS: U32 = U32::new(100, 10, 10_000),
+    "irq.storm_window_ms", "Fırtına sayım penceresi (ms)");
+crate::sysctl!(RETRY_MS: U32 = U32::new(1000, 10, 60_000),
+    "irq.storm_retry_ms", "Maskelenen kesmelerin yeniden açılma denemesi aralığı (ms)");
+
+/// Kesme başına pencere durumu.
+struct IrqWindow {
+    /// Pencerenin başladığı tik.
+    start: AtomicU64,
+    /// Penceredeki kesme sayısı.
+    count: AtomicU32,
+    /// Pencere başındaki bağlam geçişi sayısı.
+    switches: AtomicU64,
+    masked: AtomicBool,
+    /// Bu kesme için algılanan fırtına sayısı.
+    storms: AtomicU32,
+}
+
+impl IrqWindow {
+    const fn new() -> Self {
+        IrqWindow {
+            start: AtomicU64::new(0),
+            count: AtomicU32::new(0),
+            switches: AtomicU64::new(0),
+            masked: AtomicBool::new(false),
+            storms: AtomicU32::new(0),
+        }
+    }
+
+    fn restart(&self, now: u64) {
+        self.start.store(now, Ordering::Relaxed);
+        self.count.store(0, Ordering::Relaxed);
+        self.switches.store(crate::sched::CONTEXT_SWITCHES.get(), Ordering::Relaxed);
+    }
+}
+
+#[allow(clippy::declare_interior_mutable_const)]
+const EMPTY: IrqWindow = IrqWindow::new();
+static WINDOWS: [IrqWindow; MAX_IRQS] = [EMPTY; MAX_IRQS];
+
+crate::kernel_counter!(STORMS, "irq.storms");
+crate::kernel_counter!(REENABLES, "irq.storm_reenables");
+
+fn ms_to_ticks(ms: u32) -> u64 {
+    (u64::from(ms) * crate::sched::tick_hz() / 1000).max(1)
+}
+
+// -----------------------------------------------------------------------------
+// MİMARİ BAĞLANTISI
+// -----------------------------------------------------------------------------
+
+/// Kesmeyi denetleyicide maskeler veya açar; desteklenmiyorsa `false`.
+pub(crate) fn arch_mask(irq: u32, masked: bool) -> bool {
+    crate::arch::interrupt::set_irq_masked(irq, masked)
+}
+
+// -----------------------------------------------------------------------------
+// ALGILAMA
+// -----------------------------------------------------------------------------
+
+/// Mimarinin kesme dağıtıcısından, sürücü çağrılmadan önce çağrılır.
+/// `handler_name` yalnızca fırtına bildirilirken çağrılır.
+///
+/// # Dönüş Değeri
+/// Kesme sürücüye dağıtılmalıysa `true`; fırtına algılanıp kesme
+/// maskelendiyse `false`.
+pub fn irq_fired(irq: u32, handler_name: fn(u32) -> &'static str) -> bool {
+    let Some(window) = WINDOWS.get(irq as usize) else { return true };
+    let now = crate::sched::ticks();
+    if now.wrapping_sub(window.start.load(Ordering::Relaxed)) >= ms_to_ticks(WINDOW_MS.get()) {
+        window.restart(now);
+        return true;
+    }
+    let count = window.count.fetch_add(1, Ordering::Relaxed) + 1;
+    if count <= THRESHOLD.get() || window.masked.load(Ordering::Relaxed) {
+        return true;
+    }
+    if crate::sched::CONTEXT_SWITCHES.get() != window.switches.load(Ordering::Relaxed) {
+        // Görevler ilerliyor: yoğun ama sağlıklı kesme; yeni pencereye geç
+        window.restart(now);
+        return true;
+    }
+
+    STORMS.inc();
+    window.storms.fetch_add(1, Ordering::Relaxed);
+    let masked = arch_mask(irq, true);
+    window.masked.store(masked, Ordering::Relaxed);
+    window.restart(now);
+    serial_println!(
+        "[IRQ] Fırtına: IRQ {} ({}) {} ms içinde {} kez, görev ilerlemiyor; {}",
+        irq,
+        handler_name(irq),
+        WINDOW_MS.get(),
+        count,
+        if masked { "maskelendi" } else { "maskelenemedi" }
+    );
+    !masked
+}
+
+/// Kesme fırtına nedeniyle maskeli mi?
+pub fn is_masked(irq: u32) -> bool {
+    WINDOWS.get(irq as usize).is_some_and(|w| w.masked.load(Ordering::Relaxed))
+}
+
+/// Periyodik zamanlayıcı: maskeli kesmeleri yeniden açar.
+fn retry(_arg: usize) {
+    let now = crate::sched::ticks();
+    for (irq, window) in WINDOWS.iter().enumerate().filter(|(_, w)| w.masked.load(Ordering::Relaxed)) {
+        window.restart(now);
+        window.masked.store(false, Ordering::Relaxed);
+        arch_mask(irq as u32, false);
+        REENABLES.inc();
+    }
+}
+
+fn init() -> Result<(), KernelError> {
+    let period = ms_to_ticks(RETRY_MS.get());
+    crate::timer::start(period, period, retry, 0)?;
+    Ok(())
+}
+
+crate::initcall!(late, "irqstorm", init);
+
+// -----------------------------------------------------------------------------
+// KABUK VE PROCFS
+// -----------------------------------------------------------------------------
+
+fn show_irqstorm(out: &mut dyn Write) -> fmt::Result {
+    writeln!(out, "threshold {} window_ms {} retry_ms {}", THRESHOLD.get(), WINDOW_MS.get(), RETRY_MS.get())?;
+    for (irq, window) in WINDOWS.iter().enumerate().filter(|(_, w)| w.storms.load(Ordering::Relaxed) != 0) {
+        writeln!(
+            out,
+            "irq{} storms {} masked {}",
+            irq,
+            window.storms.load(Ordering::Relaxed),
+            window.masked.load(Ordering::Relaxed) as u8
+        )?;
+    }
+    Ok(())
+}
+
+crate::proc_entry!("irqstorm", show_irqstorm);
+
+fn irqstorm_command(_args: &[&str]) -> Result<(), KernelError> {
+    serial_println!(
+        "  eşik {} kesme / {} ms; yeniden açma {} ms",
+        THRESHOLD.get(),
+        WINDOW_MS.get(),
+        RETRY_MS.get()
+    );
+    serial_println!("  IRQ  FIRTINA  DURUM");
+    for (irq, window) in WINDOWS.iter().enumerate().filter(|(_, w)| w.storms.load(Ordering::Relaxed) != 0) {
+        serial_println!(
+            "  {:>3}  {:>7}  {}",
+            irq,
+            window.storms.load(Ordering::Relaxed),
+            if window.masked.load(Ordering::Relaxed) { "maskeli" } else { "açık" }
+        );
+    }
+    Ok(())
+}
+
+crate::shell_command!("irqstorm", "Kesme fırtınası algılama durumunu gösterir", irqstorm_command);
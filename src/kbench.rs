@@ -0,0 +1,351 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/kbench.rs
+// Çekirdek temel işlemleri için ölçüm (benchmark) takımı.
+//
+// Bağlam değişimi, kesmeden göreve gecikme, sistem çağrısı gidiş-dönüşü,
+// mutex (çekişmesiz ve çekişmeli) ve memcpy verimi döngü cinsinden ölçülür.
+// Mimarilerde ortak bir PMU soyutlaması olmadığından süre kaynağı
+// `clock::cycles()` serbest çalışan sayacıdır (TSC, CNTPCT, time, TB, ...);
+// çözünürlüğü mimariye göre değişir ve satırlarda `hz` ile birlikte verilir.
+//
+// Çıktı biçimi sabittir; sürümler arası gerilemeler satırların
+// karşılaştırılmasıyla bulunur (alan sırası ve adları değiştirilmemelidir):
+//
+//   KBENCH begin arch=amd64 hz=2400000000
+//   KBENCH arch=amd64 bench=ctx_switch iters=1000 min=412 mean=455 max=1893 ns_mean=189
+//   KBENCH arch=amd64 bench=memcpy iters=1000 min=... mean=... max=... ns_mean=...
+//   KBENCH arch=amd64 bench=memcpy bytes=16384 mb_s=...
+//   KBENCH end
+//
+// Sayaç frekansı bilinmiyorsa `ns_mean=-` ve `mb_s=-` yazılır.
+//
+// Ölçümler görev bağlamı gerektirir (kabuktan `kbench [ad]`); ortak görevler
+// çağıranın önceliğiyle oluşturulur ve ölçüm sonunda sonlanır.
+
+use core::sync::atomic::{AtomicBool, AtomicU64, AtomicU8, AtomicUsize, Ordering};
+use crate::clock;
+use crate::platformgeneric::KernelError;
+use crate::sched::{self, TaskId};
+use crate::serial_println;
+use crate::sync::Mutex;
+use crate::version::ARCH;
+
+/// Ölçüm başına varsayılan tekrar sayısı.
+pub const DEFAULT_ITERS: u32 = 1000;
+
+/// memcpy ölçümünün tampon boyutu.
+const COPY_BYTES: usize = 16 * 1024;
+
+/// Kesme ve çekişme ölçümlerinde tekrar, tik gecikmesine bağlı olduğundan azaltılır.
+const SLOW_DIVISOR: u32 = 16;
+
+/// Kesmeden göreve ölçümünde zamanlayıcı gecikmesi.
+const IRQ_DELAY_NS: u64 = 50_000;
+
+// -----------------------------------------------------------------------------
+// ÖRNEKLER VE RAPOR
+// -----------------------------------------------------------------------------
+
+/// Bir ölçümün döngü istatistikleri.
+#[derive(Debug, Clone, Copy)]
+pub struct Sample {
+    pub iters: u32,
+    pub min: u64,
+    pub max: u64,
+    pub sum: u64,
+}
+
+impl Sample {
+    const fn new() -> Self {
+        Sample { iters: 0, min: u64::MAX, max: 0, sum: 0 }
+    }
+
+    fn record(&mut self, cycles: u64) {
+        self.iters += 1;
+        self.min = self.min.min(cycles);
+        self.max = self.max.max(cycles);
+        self.sum = self.sum.saturating_add(cycles);
+    }
+
+    pub fn mean(&self) -> u64 {
+        self.sum / u64::from(self.iters.max(1))
+    }
+}
+
+/// Tek bir sonuç satırı yazar.
+fn report(name: &str, sample: &Sample) {
+    let min = if sample.iters == 0 { 0 } else { sample.min };
+    match clock::cycles_to_ns(sample.mean()) {
+        Some(ns) => serial_println!(
+            "KBENCH arch={} bench={} iters={} min={} mean={} max={} ns_mean={}",
+            ARCH, name, sample.iters, min, sample.mean(), sample.max, ns
+        ),
+        None => serial_println!(
+            "KBENCH arch={} bench={} iters={} min={} mean={} max={} ns_mean=-",
+            ARCH, name, sample.iters, min, sample.mean(), sample.max
+        ),
+    }
+}
+
+// -----------------------------------------------------------------------------
+// ORTAK GÖREV
+// -----------------------------------------------------------------------------
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+enum Mode {
+    PingPong = 0,
+    Mutex = 1,
+}
+
+/// Ölçümü yürüten görev.
+static DRIVER: AtomicUsize = AtomicUsize::new(usize::MAX);
+static MODE: AtomicU8 = AtomicU8::new(Mode::PingPong as u8);
+static STOP: AtomicBool = AtomicBool::new(false);
+/// Kilidin bırakıldığı (veya kesmenin geldiği) an.
+static STAMP: AtomicU64 = AtomicU64::new(0);
+/// Ortak görevin ölçtüğü son süre.
+static LAST: AtomicU64 = AtomicU64::new(0);
+
+static BENCH_LOCK: Mutex<u64> = Mutex::new(0);
+
+/// Ortak görev: her turda sürücüyü uyandırıp kendini bloklar. Mutex
+/// kipinde önce sürücünün tuttuğu kilidi bekler ve devir süresini ölçer.
+fn partner(_arg: u64) {
+    let Some(me) = sched::current() else {
+        return;
+    };
+    let driver = DRIVER.load(Ordering::Acquire);
+    while !STOP.load(Ordering::Acquire) {
+        if MODE.load(Ordering::Acquire) == Mode::Mutex as u8 {
+            if let Ok(mut guard) = BENCH_LOCK.lock() {
+                LAST.store(clock::cycles().wrapping_sub(STAMP.load(Ordering::Acquire)), Ordering::Release);
+                *guard += 1;
+            }
+        }
+        let _ = sched::wake(driver);
+        let _ = sched::block(me);
+        sched::schedule();
+    }
+    sched::exit(me).ok();
+    sched::schedule();
+}
+
+/// Çağıranın önceliğiyle ortak görevi başlatır.
+fn spawn_partner(me: TaskId, mode: Mode) -> Result<TaskId, KernelError> {
+    let priority = sched::task_info(me).ok_or(KernelError::NotFound)?.priority;
+    DRIVER.store(me, Ordering::Release);
+    MODE.store(mode as u8, Ordering::Release);
+    STOP.store(false, Ordering::Release);
+    sched::spawn("kbench", partner as usize, priority)
+}
+
+/// Ortak görevi durdurur ve sonlanması için işlemciyi bırakır.
+fn stop_partner(peer: TaskId) {
+    STOP.store(true, Ordering::Release);
+    let _ = sched::wake(peer);
+    sched::schedule();
+}
+
+// -----------------------------------------------------------------------------
+// ÖLÇÜMLER
+// -----------------------------------------------------------------------------
+
+/// İki görev arasında ping-pong; bir tur iki bağlam değişimidir.
+pub fn ctx_switch(iters: u32) -> Result<Sample, KernelError> {
+    let me = sched::current().ok_or(KernelError::NotFound)?;
+    let peer = spawn_partner(me, Mode::PingPong)?;
+    let mut sample = Sample::new();
+    for _ in 0..iters {
+        let start = clock::cycles();
+        let _ = sched::wake(peer);
+        let _ = sched::block(me);
+        sched::schedule();
+        sample.record(clock::cycles().wrapping_sub(start) / 2);
+    }
+    stop_partner(peer);
+    Ok(sample)
+}
+
+fn irq_fired(task: usize) {
+    STAMP.store(clock::cycles(), Ordering::Release);
+    let _ = sched::wake(task);
+}
+
+/// hrtimer kesmesinin işleyicisinden, uyandırdığı görevin çalışmasına kadar geçen süre.
+pub fn irq_to_task(iters: u32) -> Result<Sample, KernelError> {
+    let me = sched::current().ok_or(KernelError::NotFound)?;
+    // Tek seferlik kesme kurulamıyor (periyodik tik kipi)
+    if !crate::hrtimer::is_active() {
+        return Err(KernelError::ResourceBusy);
+    }
+    let mut sample = Sample::new();
+    for _ in 0..iters {
+        STAMP.store(0, Ordering::Release);
+        // Önce bloklanır: zamanlayıcı hemen dolsa bile uyandırma kaybolmaz
+        let _ = sched::block(me);
+        if let Err(e) = crate::hrtimer::start_ns(IRQ_DELAY_NS, irq_fired, me) {
+            let _ = sched::wake(me);
+            return Err(e);
+        }
+        sched::schedule();
+        // Başka hazır görev yoksa geçiş olmadan buraya dönülmüş olabilir
+        while STAMP.load(Ordering::Acquire) == 0 {
+            core::hint::spin_loop();
+        }
+        sample.record(clock::cycles().wrapping_sub(STAMP.load(Ordering::Acquire)));
+    }
+    Ok(sample)
+}
+
+/// `syscall::dispatch` üzerinden `clock_getres` gidiş-dönüşü. Çekirdek
+/// görevleri tuzak komutunu taşınabilir biçimde kullanamadığından tuzak
+/// giriş/çıkış maliyeti dahil değildir.
+pub fn syscall(iters: u32) -> Result<Sample, KernelError> {
+    let mut sample = Sample::new();
+    for _ in 0..iters {
+        let start = clock::cycles();
+        let ret = crate::syscall::dispatch(crate::syscall::SYS_CLOCK_GETRES, [0; 6]);
+        sample.record(clock::cycles().wrapping_sub(start));
+        if ret < 0 {
+            return Err(KernelError::GenericFailure);
+        }
+    }
+    Ok(sample)
+}
+
+/// Çekişmesiz kilitle + bırak.
+pub fn mutex_uncontended(iters: u32) -> Result<Sample, KernelError> {
+    let mut sample = Sample::new();
+    for _ in 0..iters {
+        let start = clock::cycles();
+        let guard = BENCH_LOCK.lock()?;
+        drop(guard);
+        sample.record(clock::cycles().wrapping_sub(start));
+    }
+    Ok(sample)
+}
+
+/// Çekişmeli devir: kilidin bırakılmasından bekleyen görevin kilidi
+/// almasına kadar geçen süre (bekleyenin uyandırılması ve zamanlanması dahil).
+pub fn mutex_contended(iters: u32) -> Result<Sample, KernelError> {
+    let me = sched::current().ok_or(KernelError::NotFound)?;
+    let mut guard = Some(BENCH_LOCK.lock()?);
+    let peer = spawn_partner(me, Mode::Mutex)?;
+    let mut sample = Sample::new();
+    for _ in 0..iters {
+        // Ortak görev uyanıp kilidi beklemeye başlasın
+        let _ = sched::wake(peer);
+        sched::schedule();
+        let _ = sched::block(me);
+        STAMP.store(clock::cycles(), Ordering::Release);
+        drop(guard.take());
+        sched::schedule();
+        sample.record(LAST.load(Ordering::Acquire));
+        guard = Some(BENCH_LOCK.lock()?);
+    }
+    STOP.store(true, Ordering::Release);
+    drop(guard);
+    stop_partner(peer);
+    Ok(sample)
+}
+
+static mut COPY_SRC: [u8; COPY_BYTES] = [0x5a; COPY_BYTES];
+static mut COPY_DST: [u8; COPY_BYTES] = [0; COPY_BYTES];
+static COPY_BUSY: AtomicBool = AtomicBool::new(false);
+
+/// `COPY_BYTES` baytlık kopyalama; satıra verim (MB/s) eklenir.
+pub fn memcpy(iters: u32) -> Result<Sample, KernelError> {
+    if COPY_BUSY.swap(true, Ordering::Acquire) {
+        return Err(KernelError::ResourceBusy);
+    }
+    let mut sample = Sample::new();
+    for _ in 0..iters {
+        let start = clock::cycles();
+        // SAFETY: Tamponlara `COPY_BUSY` tutulurken yalnızca bu döngü erişir.
+        unsafe {
+            let src = &*core::ptr::addr_of!(COPY_SRC);
+            let dst = &mut *core::ptr::addr_of_mut!(COPY_DST);
+            dst.copy_from_slice(core::hint::black_box(src));
+            core::hint::black_box(dst);
+        }
+        sample.record(clock::cycles().wrapping_sub(start));
+    }
+    COPY_BUSY.store(false, Ordering::Release);
+    Ok(sample)
+}
+
+// -----------------------------------------------------------------------------
+// TAKIM
+// -----------------------------------------------------------------------------
+
+/// Ölçüm kaydı.
+pub struct Bench {
+    pub name: &'static str,
+    pub run: fn(u32) -> Result<Sample, KernelError>,
+    /// Tik gecikmesine bağlı, daha az tekrarla çalışan ölçüm.
+    pub slow: bool,
+}
+
+/// Takımdaki ölçümler; sıra çıktı sırasıdır.
+pub static BENCHES: &[Bench] = &[
+    Bench { name: "ctx_switch", run: ctx_switch, slow: false },
+    Bench { name: "irq_to_task", run: irq_to_task, slow: true },
+    Bench { name: "syscall", run: syscall, slow: false },
+    Bench { name: "mutex", run: mutex_uncontended, slow: false },
+    Bench { name: "mutex_contended", run: mutex_contended, slow: true },
+    Bench { name: "memcpy", run: memcpy, slow: false },
+];
+
+/// Adı `filter`'ı içeren ölçümleri `iters` tekrarla çalıştırır (boş filtre: hepsi).
+///
+/// # Dönüş Değeri
+/// Hata veren ölçüm sayısı; hatalı ölçümler `KBENCH ... error=<hata>` satırıyla bildirilir.
+pub fn run(filter: &str, iters: u32) -> usize {
+    serial_println!("KBENCH begin arch={} hz={}", ARCH, clock::cycles_hz());
+    let mut failed = 0;
+    for bench in BENCHES.iter().filter(|b| filter.is_empty() || b.name.contains(filter)) {
+        let count = if bench.slow { (iters / SLOW_DIVISOR).max(1) } else { iters };
+        match (bench.run)(count) {
+            Ok(sample) => {
+                report(bench.name, &sample);
+                if bench.name == "memcpy" {
+                    report_throughput(&sample);
+                }
+            }
+            Err(error) => {
+                failed += 1;
+                serial_println!("KBENCH arch={} bench={} error={:?}", ARCH, bench.name, error);
+            }
+        }
+    }
+    serial_println!("KBENCH end");
+    failed
+}
+
+fn report_throughput(sample: &Sample) {
+    match clock::cycles_to_ns(sample.mean()).filter(|&ns| ns != 0) {
+        // bayt/ns * 1000 = MB/s
+        Some(ns) => serial_println!("KBENCH arch={} bench=memcpy bytes={} mb_s={}", ARCH, COPY_BYTES, COPY_BYTES as u64 * 1000 / ns),
+        None => serial_println!("KBENCH arch={} bench=memcpy bytes={} mb_s=-", ARCH, COPY_BYTES),
+    }
+}
+
+/// `kbench [ad] [tekrar]`
+fn kbench_command(args: &[&str]) -> Result<(), KernelError> {
+    let filter = args.get(1).copied().filter(|&a| a != "all").unwrap_or("");
+    let iters = match args.get(2) {
+        Some(text) => text.parse().map_err(|_| KernelError::InvalidArgument)?,
+        None => DEFAULT_ITERS,
+    };
+    if iters == 0 {
+        return Err(KernelError::InvalidArgument);
+    }
+    match run(filter, iters) {
+        0 => Ok(()),
+        _ => Err(KernelError::GenericFailure),
+    }
+}
+
+crate::shell_command!("kbench", "Çekirdek ölçümlerini çalıştırır (kbench [ad|all] [tekrar])", kbench_command);
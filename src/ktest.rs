@@ -0,0 +1,247 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/ktest.rs
+// Çekirdek içi birim testleri (`ktest`): hedef donanımda veya QEMU'da çalışır.
+//
+// Ana makinede sınanamayan kod (CSR/sistem yazmacı erişimleri, bağlam
+// değişimi, istisna düzeltmeleri, havuzlar) için testler `ktest!` ile
+// `.ktests` linker bölümüne kaydedilir ve çekirdeğin içinde çalıştırılır.
+// Testler yalnızca `ktest` özelliğiyle derlenir; özellik kapalıyken makro
+// hiçbir şey üretmez ve üretim görüntüsü büyümez.
+//
+// Çalıştırma:
+// * Kabuk: `ktest [filtre]`, `ktest list`.
+// * Açılış: açılış komut satırında (`boot::info().cmdline()`) `ktest` veya
+//   `ktest=<filtre>` varsa testler `late` seviyesinde kendiliğinden çalışır.
+//
+// Çıktı KTAP (Linux kselftest/KUnit) biçimindedir; satırlar seri porta
+// yazılır ve CI tarafından ayrıştırılabilir:
+//
+//   KTAP version 1
+//   1..2
+//   ok 1 memory::pool::pool_exhaustion
+//   # src/net/netbuf.rs:312: assert_eq: flat.data() == b"eh2veri"
+//   not ok 2 net::netbuf::netbuf_headroom_and_chain
+//   # Totals: pass:1 fail:1 skip:0 total:2
+//
+// Çekirdekte panik yakalanamadığından testler başarısızlığı `Err` ile
+// bildirir: `ktest_assert!`/`ktest_assert_eq!` ve `KernelError` döndüren
+// çağrılarda `?` kullanılır. Testte oluşan işlemci istisnası (sayfa hatası,
+// geçersiz talimat) `fixup::catch` ile yakalanır; test başarısız sayılır ve
+// çalıştırıcı sıradaki teste geçer.
+//
+// Linker betiği: __ktests_start = .; KEEP(*(.ktests)) __ktests_end = .;
+
+use core::panic::Location;
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// KAYIT
+// -----------------------------------------------------------------------------
+
+/// Test kaydı.
+#[repr(C)]
+pub struct KTest {
+    /// Testin modül yolu (`module_path!()`).
+    pub module: &'static str,
+    pub name: &'static str,
+    pub func: fn() -> Result<(), Failure>,
+}
+
+impl KTest {
+    /// Kasa adı olmadan modül yolu (örn. "memory::pool").
+    pub fn module(&self) -> &'static str {
+        self.module.split_once("::").map_or("", |(_, rest)| rest)
+    }
+}
+
+/// Başarısız testin nedeni.
+#[derive(Debug, Clone, Copy)]
+pub enum Reason {
+    /// Doğrulanamayan koşul (makro metni).
+    Assertion(&'static str),
+    /// `?` ile yayılan çekirdek hatası.
+    Error(KernelError),
+}
+
+/// Başarısızlık ve oluştuğu yer.
+#[derive(Debug, Clone, Copy)]
+pub struct Failure {
+    pub location: &'static Location<'static>,
+    pub reason: Reason,
+}
+
+impl Failure {
+    #[track_caller]
+    pub fn assertion(message: &'static str) -> Self {
+        Failure { location: Location::caller(), reason: Reason::Assertion(message) }
+    }
+}
+
+impl From<KernelError> for Failure {
+    /// Konum, hatayı yayan `?`'dir.
+    #[track_caller]
+    fn from(error: KernelError) -> Self {
+        Failure { location: Location::caller(), reason: Reason::Error(error) }
+    }
+}
+
+/// Çekirdek içi test tanımlar ve kaydeder (yalnızca `ktest` özelliğiyle).
+///
+/// Gövde `()` üretir; `?` ve `ktest_assert!` ile erken dönebilir.
+///
+/// ```ignore
+/// crate::ktest! {
+///     fn pool_exhaustion() {
+///         let a = POOL.alloc(1)?;
+///         crate::ktest_assert_eq!(POOL.available(), 0);
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! ktest {
+    ($(#[$meta:meta])* fn $name:ident() $body:block) => {
+        #[cfg(feature = "ktest")]
+        $(#[$meta])*
+        fn $name() -> Result<(), $crate::ktest::Failure> {
+            let () = $body;
+            Ok(())
+        }
+
+        #[cfg(feature = "ktest")]
+        const _: () = {
+            #[used]
+            #[link_section = ".ktests"]
+            static __KTEST: $crate::ktest::KTest = $crate::ktest::KTest {
+                module: module_path!(),
+                name: stringify!($name),
+                func: $name,
+            };
+        };
+    };
+}
+
+/// Koşul yanlışsa testi başarısız sayar.
+#[macro_export]
+macro_rules! ktest_assert {
+    ($cond:expr) => {
+        if !$cond {
+            return Err($crate::ktest::Failure::assertion(concat!("assert: ", stringify!($cond))));
+        }
+    };
+}
+
+/// İki değer eşit değilse ikisini de yazdırır ve testi başarısız sayar.
+#[macro_export]
+macro_rules! ktest_assert_eq {
+    ($left:expr, $right:expr) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if left != right {
+                    $crate::serial_println!("# sol: {:?}", left);
+                    $crate::serial_println!("# sağ: {:?}", right);
+                    return Err($crate::ktest::Failure::assertion(concat!(
+                        "assert_eq: ",
+                        stringify!($left),
+                        " == ",
+                        stringify!($right)
+                    )));
+                }
+            }
+        }
+    };
+}
+
+extern "C" {
+    static __ktests_start: KTest;
+    static __ktests_end: KTest;
+}
+
+/// Kayıtlı tüm testler.
+pub fn tests() -> &'static [KTest] {
+    // SAFETY: Semboller linker betiği tarafından `.ktests` bölümünün iki ucuna yerleştirilir.
+    unsafe {
+        let start = &__ktests_start as *const KTest;
+        let end = &__ktests_end as *const KTest;
+        let count = (end as usize - start as usize) / core::mem::size_of::<KTest>();
+        core::slice::from_raw_parts(start, count)
+    }
+}
+
+fn matches(test: &KTest, filter: &str) -> bool {
+    filter.is_empty() || test.module().contains(filter) || test.name.contains(filter)
+}
+
+// -----------------------------------------------------------------------------
+// ÇALIŞTIRICI
+// -----------------------------------------------------------------------------
+
+/// Adı veya modülü `filter`'ı içeren testleri sırayla çalıştırır ve KTAP
+/// çıktısı üretir (boş filtre: hepsi).
+///
+/// # Dönüş Değeri
+/// (geçen, kalan) test sayıları.
+pub fn run(filter: &str) -> (usize, usize) {
+    let total = tests().iter().filter(|t| matches(t, filter)).count();
+    serial_println!("KTAP version 1");
+    serial_println!("1..{}", total);
+    let (mut passed, mut failed) = (0, 0);
+    for (number, test) in tests().iter().filter(|t| matches(t, filter)).enumerate() {
+        match crate::fixup::catch(test.func) {
+            Ok(Ok(())) => {
+                passed += 1;
+                serial_println!("ok {} {}::{}", number + 1, test.module(), test.name);
+            }
+            Ok(Err(failure)) => {
+                failed += 1;
+                let location = failure.location;
+                match failure.reason {
+                    Reason::Assertion(text) => serial_println!("# {}:{}: {}", location.file(), location.line(), text),
+                    Reason::Error(error) => serial_println!("# {}:{}: hata {:?}", location.file(), location.line(), error),
+                }
+                serial_println!("not ok {} {}::{}", number + 1, test.module(), test.name);
+            }
+            Err(fault) => {
+                failed += 1;
+                serial_println!("# istisna: {} (pc {:#x})", fault.cause, fault.pc);
+                serial_println!("not ok {} {}::{}", number + 1, test.module(), test.name);
+            }
+        }
+    }
+    serial_println!("# Totals: pass:{} fail:{} skip:0 total:{}", passed, failed, total);
+    (passed, failed)
+}
+
+/// `ktest [list | filtre]`
+fn ktest_command(args: &[&str]) -> Result<(), KernelError> {
+    if args.get(1) == Some(&"list") {
+        for test in tests() {
+            serial_println!("  {}::{}", test.module(), test.name);
+        }
+        return Ok(());
+    }
+    match run(args.get(1).copied().unwrap_or("")) {
+        (_, 0) => Ok(()),
+        _ => Err(KernelError::GenericFailure),
+    }
+}
+
+#[cfg(feature = "ktest")]
+crate::shell_command!("ktest", "Çekirdek içi testleri çalıştırır (ktest [list | filtre])", ktest_command);
+
+/// Açılış argümanlarında `ktest[=filtre]` varsa testleri çalıştırır.
+fn boot_run() -> Result<(), KernelError> {
+    for arg in crate::boot::info().args() {
+        let filter = match arg.split_once('=') {
+            Some(("ktest", filter)) => filter,
+            None if arg == "ktest" => "",
+            _ => continue,
+        };
+        run(filter);
+    }
+    Ok(())
+}
+
+#[cfg(feature = "ktest")]
+crate::initcall!(late, "ktest", boot_run);
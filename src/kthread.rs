@@ -0,0 +1,354 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/kthread.rs
+// Çekirdek işçi görevleri (kthreadd karşılığı): adlandırılmış arka plan
+// görevleri ve sınırlı iş kuyrukları.
+//
+// Alt sistemler kendi görev döngülerini yazmak yerine `spawn` ile bir işçi
+// başlatır ve işleri `Worker::submit` ile kuyruğa ekler; işçi her iş için
+// işleyiciyi `Work::Item(değer)` ile çağırır. `spawn_periodic` ile başlatılan
+// işçiler ayrıca her aralıkta `Work::Tick` alır (aralık her turda yeniden
+// okunur, sysctl ile değişebilir).
+//
+// * Kuyruk `QUEUE_LEN` işle sınırlıdır; dolu kuyruğa ekleme `ResourceBusy`
+//   döndürür (geri basınç çağırandadır).
+// * Boş kuyrukta işçi, `submit` ile aynı kilit altında bloklanır; uyandırma
+//   kaybolmaz (bkz. `iosched`).
+// * Yaşam döngüsü: `Worker::stop` (veya kapanışta `stop_all`) işçiyi
+//   durdurur. İşçi kuyruktaki işleri bitirir, işleyiciyi son kez `Work::Stop`
+//   ile çağırır (örn. kirli sayfaları yazmak için) ve görevini sonlandırır.
+//
+// İşleyiciler görev bağlamında çalışır; bloklayabilir ve uyuyabilir.
+// `submit` kısa bir kilit alır ve `sched::wake` çağırır; kilit kesmelere
+// karşı korumalı olmadığından yalnızca görev bağlamından çağrılmalıdır.
+//
+// Kabuk komutu: `kthreads`; procfs: `/proc/kthreads`
+
+use core::cell::UnsafeCell;
+use core::fmt::{self, Write};
+use crate::platformgeneric::spinlock::Spinlock;
+use crate::platformgeneric::KernelError;
+use crate::sched::{self, TaskId};
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// SABİTLER VE TİPLER
+// -----------------------------------------------------------------------------
+
+/// Aynı anda var olabilecek en fazla işçi.
+pub const MAX_WORKERS: usize = 16;
+
+/// İşçi başına kuyruk kapasitesi.
+pub const QUEUE_LEN: usize = 32;
+
+/// İşleyiciye verilen iş.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Work {
+    /// `submit` ile kuyruğa eklenen değer.
+    Item(usize),
+    /// Periyodik işçinin aralığı doldu.
+    Tick,
+    /// İşçi durduruluyor; kuyruk boşaltıldı, son çağrı.
+    Stop,
+}
+
+/// İş işleyicisi.
+pub type Handler = fn(Work);
+
+/// Periyodik aralık (tik); her beklemede yeniden çağrılır.
+pub type Interval = fn() -> u64;
+
+/// İşçi tanıtıcısı.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Worker(usize);
+
+#[derive(Clone, Copy)]
+struct Slot {
+    name: &'static str,
+    handler: Option<Handler>,
+    interval: Option<Interval>,
+    /// Görev oluşturulana kadar `None`.
+    task: Option<TaskId>,
+    queue: [usize; QUEUE_LEN],
+    head: usize,
+    count: usize,
+    stopping: bool,
+    processed: u64,
+    rejected: u64,
+}
+
+impl Slot {
+    const fn empty() -> Self {
+        Slot {
+            name: "",
+            handler: None,
+            interval: None,
+            task: None,
+            queue: [0; QUEUE_LEN],
+            head: 0,
+            count: 0,
+            stopping: false,
+            processed: 0,
+            rejected: 0,
+        }
+    }
+
+    fn in_use(&self) -> bool {
+        self.handler.is_some()
+    }
+}
+
+struct Workers {
+    lock: Spinlock,
+    slots: UnsafeCell<[Slot; MAX_WORKERS]>,
+}
+
+// Tüm alanlara erişim `lock` ile korunur.
+unsafe impl Sync for Workers {}
+
+static WORKERS: Workers = Workers {
+    lock: Spinlock::new(),
+    slots: UnsafeCell::new([Slot::empty(); MAX_WORKERS]),
+};
+
+fn with_workers<R>(f: impl FnOnce(&mut [Slot; MAX_WORKERS]) -> R) -> R {
+    WORKERS.lock.lock();
+    // SAFETY: Kilit tutulduğu sürece duruma tek erişen biziz.
+    let result = unsafe { f(&mut *WORKERS.slots.get()) };
+    WORKERS.lock.unlock();
+    result
+}
+
+// -----------------------------------------------------------------------------
+// OLUŞTURMA
+// -----------------------------------------------------------------------------
+
+/// İstek üzerine çalışan bir işçi başlatır.
+///
+/// # Hatalar
+/// * `OutOfMemoryStatic`: İşçi veya görev tablosu dolu.
+/// * `InvalidArgument`: Öncelik geçersiz (bkz. `sched::spawn`).
+pub fn spawn(name: &'static str, priority: u8, handler: Handler) -> Result<Worker, KernelError> {
+    start(name, priority, handler, None)
+}
+
+/// İstekleri işleyen ve ayrıca her `interval()` tikte `Work::Tick` alan bir
+/// işçi başlatır (hatalar için bkz. `spawn`).
+pub fn spawn_periodic(name: &'static str, priority: u8, interval: Interval, handler: Handler) -> Result<Worker, KernelError> {
+    start(name, priority, handler, Some(interval))
+}
+
+fn start(name: &'static str, priority: u8, handler: Handler, interval: Option<Interval>) -> Result<Worker, KernelError> {
+    let index = with_workers(|slots| {
+        let index = slots.iter().position(|s| !s.in_use()).ok_or(KernelError::OutOfMemoryStatic)?;
+        slots[index] = Slot { name, handler: Some(handler), interval, ..Slot::empty() };
+        Ok(index)
+    })?;
+    // İşçi kendi yuvasını argüman olarak alır
+    match sched::spawn_with_arg(name, worker_task as usize, index, priority) {
+        Ok(task) => {
+            with_workers(|slots| slots[index].task = Some(task));
+            Ok(Worker(index))
+        }
+        Err(e) => {
+            with_workers(|slots| slots[index] = Slot::empty());
+            Err(e)
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// İŞ KUYRUĞU
+// -----------------------------------------------------------------------------
+
+impl Worker {
+    /// İşi kuyruğa ekler ve işçiyi uyandırır.
+    ///
+    /// # Hatalar
+    /// * `ResourceBusy`: Kuyruk dolu.
+    /// * `NotFound`: İşçi durduruldu veya durduruluyor.
+    pub fn submit(self, item: usize) -> Result<(), KernelError> {
+        with_workers(|slots| {
+            let slot = &mut slots[self.0];
+            if !slot.in_use() || slot.stopping {
+                return Err(KernelError::NotFound);
+            }
+            if slot.count == QUEUE_LEN {
+                slot.rejected += 1;
+                return Err(KernelError::ResourceBusy);
+            }
+            slot.queue[(slot.head + slot.count) % QUEUE_LEN] = item;
+            slot.count += 1;
+            // İşçi aynı kilit altında bloklandığından uyandırma kaybolmaz
+            if let Some(task) = slot.task {
+                sched::wake(task).ok();
+            }
+            Ok(())
+        })
+    }
+
+    /// İşçiyi durdurur: kuyruk boşaltılır, işleyici `Work::Stop` alır ve görev
+    /// sonlanır. Dönüşte işçi henüz çalışıyor olabilir.
+    pub fn stop(self) -> Result<(), KernelError> {
+        with_workers(|slots| {
+            let slot = &mut slots[self.0];
+            if !slot.in_use() {
+                return Err(KernelError::NotFound);
+            }
+            slot.stopping = true;
+            if let Some(task) = slot.task {
+                sched::wake(task).ok();
+            }
+            Ok(())
+        })
+    }
+
+    /// İşçinin görev kimliği (görev oluşturulmadıysa veya işçi sonlandıysa `None`).
+    pub fn task(self) -> Option<TaskId> {
+        with_workers(|slots| slots[self.0].task.filter(|_| slots[self.0].in_use()))
+    }
+}
+
+/// Tüm işçileri durdurur (kapanış yolu).
+pub fn stop_all() {
+    for index in 0..MAX_WORKERS {
+        Worker(index).stop().ok();
+    }
+}
+
+/// Çalışan işçi sayısı.
+pub fn running() -> usize {
+    with_workers(|slots| slots.iter().filter(|s| s.in_use()).count())
+}
+
+/// Görev bir işçinin görevi mi?
+pub fn is_worker(task: TaskId) -> bool {
+    with_workers(|slots| slots.iter().any(|s| s.in_use() && s.task == Some(task)))
+}
+
+// -----------------------------------------------------------------------------
+// İŞÇİ GÖREVİ
+// -----------------------------------------------------------------------------
+
+/// Bekleme sonucu.
+enum Next {
+    Item(usize),
+    Tick,
+    Stop,
+}
+
+fn wake_worker(task: usize) {
+    sched::wake(task).ok();
+}
+
+fn worker_task(arg: u64) {
+    let me = sched::current();
+    let index = arg as usize;
+    // `start` görev kimliğini `spawn` döndükten sonra yazar; o zamana kadar
+    // bloklanırsak `submit` bizi uyandıramaz
+    while with_workers(|slots| slots[index].task.is_none()) {
+        core::hint::spin_loop();
+    }
+    let (handler, interval) = with_workers(|slots| (slots[index].handler, slots[index].interval));
+    let Some(handler) = handler else { return };
+
+    let mut deadline = interval.map(|interval| sched::ticks() + interval());
+    loop {
+        match wait(index, me, deadline) {
+            Next::Item(item) => handler(Work::Item(item)),
+            Next::Tick => {
+                handler(Work::Tick);
+                deadline = interval.map(|interval| sched::ticks() + interval());
+            }
+            Next::Stop => break,
+        }
+        with_workers(|slots| slots[index].processed += 1);
+    }
+
+    handler(Work::Stop);
+    with_workers(|slots| slots[index] = Slot::empty());
+    if let Some(me) = me {
+        sched::exit(me).ok();
+    }
+    sched::schedule();
+}
+
+/// Sıradaki işi bekler. Kuyruk boşken görev kilit altında bloklanır; periyodik
+/// işçide bir zamanlayıcı görevi aralık sonunda uyandırır.
+fn wait(index: usize, me: Option<TaskId>, deadline: Option<u64>) -> Next {
+    loop {
+        let due = deadline.is_some_and(|d| sched::ticks() >= d);
+        let next = with_workers(|slots| {
+            let slot = &mut slots[index];
+            if slot.count > 0 {
+                let item = slot.queue[slot.head];
+                slot.head = (slot.head + 1) % QUEUE_LEN;
+                slot.count -= 1;
+                return Some(Next::Item(item));
+            }
+            if slot.stopping {
+                return Some(Next::Stop);
+            }
+            if due {
+                return Some(Next::Tick);
+            }
+            if let Some(me) = me {
+                sched::block(me).ok();
+            }
+            None
+        });
+        if let Some(next) = next {
+            return next;
+        }
+
+        let timer = match (deadline, me) {
+            (Some(d), Some(me)) => crate::timer::start(d.saturating_sub(sched::ticks()), 0, wake_worker, me).ok(),
+            _ => None,
+        };
+        // Bloklanan görev bir sonraki kesme dönüşünde işlemciden alınır;
+        // uyandırıldığında koşul yeniden değerlendirilir
+        while with_workers(|slots| slots[index].count == 0 && !slots[index].stopping)
+            && deadline.is_none_or(|d| sched::ticks() < d)
+        {
+            core::hint::spin_loop();
+        }
+        if let Some(timer) = timer {
+            crate::timer::cancel(timer).ok();
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// PROCFS VE KABUK
+// -----------------------------------------------------------------------------
+
+fn show_kthreads(out: &mut dyn Write) -> fmt::Result {
+    let slots = with_workers(|slots| *slots);
+    for slot in slots.iter().filter(|s| s.in_use()) {
+        let task = slot.task.map_or(-1, |t| t as isize);
+        writeln!(out, "{} {} {} {} {}", slot.name, task, slot.count, slot.processed, slot.rejected)?;
+    }
+    Ok(())
+}
+
+crate::proc_entry!("kthreads", show_kthreads);
+
+fn kthreads_command(_args: &[&str]) -> Result<(), KernelError> {
+    let slots = with_workers(|slots| *slots);
+    serial_println!("  AD           GÖREV  KUYRUK  İŞLENEN  REDDEDİLEN  DURUM");
+    for slot in slots.iter().filter(|s| s.in_use()) {
+        serial_println!(
+            "  {:<12} {:>5} {:>4}/{:<2} {:>8} {:>11}  {}",
+            slot.name,
+            slot.task.map_or(-1, |t| t as isize),
+            slot.count,
+            QUEUE_LEN,
+            slot.processed,
+            slot.rejected,
+            if slot.stopping { "duruyor" } else if slot.interval.is_some() { "periyodik" } else { "istek" }
+        );
+    }
+    Ok(())
+}
+
+crate::shell_command!("kthreads", "Çekirdek işçi görevlerini gösterir", kthreads_command);
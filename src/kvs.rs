@@ -0,0 +1,467 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/kvs.rs
+// Flash üzerinde kalıcı anahtar-değer deposu (ağ ayarları, log düzeyleri vb.).
+//
+// Depo, flash aygıtının son iki silme bloğunu (sektör) kullanır ve günlük
+// (log) yapısındadır: her `set`/`remove` etkin sektörün sonuna yeni bir kayıt
+// ekler, bir anahtarın geçerli değeri ona ait son kayıttır. Sektör dolunca
+// canlı kayıtlar diğer sektöre kopyalanır (sıkıştırma) ve etkinlik oraya
+// geçer; böylece silmeler iki sektöre eşit dağılır ve her değişiklikte
+// silme gerekmez. Değeri değişmeyen `set` hiç yazma yapmaz.
+//
+// Sektör başlığı (16 B): sihirli sayı, sıra numarası, silme sayacı, CRC-32.
+// Sıkıştırmada başlık en son yazılır; yarıda kalan sıkıştırma başlıksız
+// kalır ve açılışta eski sektör kullanılır. Kayıt: anahtar uzunluğu (1 B),
+// bayraklar (1 B), değer uzunluğu (2 B), CRC-32 (4 B), anahtar, değer;
+// yazma birimine hizalanır. CRC'si tutmayan (yarıda kalmış) kayıt yok
+// sayılır.
+
+use core::cell::UnsafeCell;
+use crate::drivers::flash::flash::{self, FlashDevice};
+use crate::platformgeneric::spinlock::Spinlock;
+use crate::platformgeneric::{DeviceError, KernelError};
+use crate::serial_println;
+use crate::update::crc32_update;
+
+// -----------------------------------------------------------------------------
+// SABİTLER
+// -----------------------------------------------------------------------------
+
+/// En uzun anahtar ve değer (bayt).
+pub const MAX_KEY: usize = 32;
+pub const MAX_VALUE: usize = 256;
+
+const SECTOR_MAGIC: u32 = 0x3153_564B; // "KVS1"
+const SECTOR_HEADER_LEN: usize = 16;
+const RECORD_HEADER_LEN: usize = 8;
+const MAX_RECORD: usize = RECORD_HEADER_LEN + MAX_KEY + MAX_VALUE + 8;
+
+const FLAG_VALUE: u8 = 0xFF;
+const FLAG_TOMBSTONE: u8 = 0x00;
+
+/// Tercih edilen aygıt (QEMU `virt`'te `flash0` ürün yazılımını tutar).
+const PREFERRED_DEVICE: &str = "flash1";
+
+crate::kernel_counter!(COMPACTIONS, "kvs.compactions");
+crate::kernel_counter!(WRITES, "kvs.writes");
+
+// -----------------------------------------------------------------------------
+// DEPO DURUMU
+// -----------------------------------------------------------------------------
+
+struct Store {
+    dev: Option<&'static dyn FlashDevice>,
+    /// Bölgenin aygıttaki başlangıcı ve sektör boyutu.
+    base: usize,
+    sector_size: usize,
+    /// Kayıt hizalaması (en az 8, yazma biriminin katı).
+    align: usize,
+    active: usize,
+    seq: u32,
+    erase_counts: [u32; 2],
+    /// Etkin sektörde ilk boş konum.
+    tail: usize,
+}
+
+struct StoreCell {
+    lock: Spinlock,
+    store: UnsafeCell<Store>,
+}
+
+// Erişim `lock` ile korunur.
+unsafe impl Sync for StoreCell {}
+
+static STORE: StoreCell = StoreCell {
+    lock: Spinlock::new(),
+    store: UnsafeCell::new(Store {
+        dev: None,
+        base: 0,
+        sector_size: 0,
+        align: 8,
+        active: 0,
+        seq: 0,
+        erase_counts: [0; 2],
+        tail: 0,
+    }),
+};
+
+fn with_store<R>(f: impl FnOnce(&mut Store) -> Result<R, KernelError>) -> Result<R, KernelError> {
+    STORE.lock.lock();
+    let store = unsafe { &mut *STORE.store.get() };
+    let result = if store.dev.is_some() { f(store) } else { Err(KernelError::NotFound) };
+    STORE.lock.unlock();
+    result
+}
+
+/// Kayıt başlığı ve konumu.
+#[derive(Clone, Copy)]
+struct Record {
+    offset: usize,
+    key_len: usize,
+    value_len: usize,
+    flags: u8,
+    valid: bool,
+}
+
+impl Record {
+    fn value_offset(&self) -> usize {
+        self.offset + RECORD_HEADER_LEN + self.key_len
+    }
+}
+
+impl Store {
+    fn dev(&self) -> Result<&'static dyn FlashDevice, KernelError> {
+        self.dev.ok_or(DeviceError::NotPresent.into())
+    }
+
+    fn sector_offset(&self, sector: usize) -> usize {
+        self.base + sector * self.sector_size
+    }
+
+    fn read(&self, offset: usize, buf: &mut [u8]) -> Result<(), KernelError> {
+        self.dev()?.read(self.sector_offset(self.active) + offset, buf)
+    }
+
+    fn record_len(&self, key_len: usize, value_len: usize) -> usize {
+        (RECORD_HEADER_LEN + key_len + value_len).next_multiple_of(self.align)
+    }
+
+    /// `offset`'teki kaydı okur; günlüğün sonunda veya bozuk başlıkta `None`.
+    fn record_at(&self, offset: usize) -> Result<Option<Record>, KernelError> {
+        if offset + RECORD_HEADER_LEN > self.sector_size {
+            return Ok(None);
+        }
+        let mut header = [0u8; RECORD_HEADER_LEN];
+        self.read(offset, &mut header)?;
+        let key_len = usize::from(header[0]);
+        let value_len = usize::from(u16::from_le_bytes([header[2], header[3]]));
+        if header[0] == flash::ERASED || key_len == 0 || key_len > MAX_KEY || value_len > MAX_VALUE {
+            return Ok(None);
+        }
+        if offset + self.record_len(key_len, value_len) > self.sector_size {
+            return Ok(None);
+        }
+
+        let mut body = [0u8; MAX_KEY + MAX_VALUE];
+        let body = &mut body[..key_len + value_len];
+        self.read(offset + RECORD_HEADER_LEN, body)?;
+        let crc = crc32_update(crc32_update(0, &header[..4]), body);
+        let valid = crc == u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+        Ok(Some(Record { offset, key_len, value_len, flags: header[1], valid }))
+    }
+
+    /// Kaydın anahtarı `key` mi?
+    fn key_matches(&self, record: &Record, key: &[u8]) -> Result<bool, KernelError> {
+        if record.key_len != key.len() {
+            return Ok(false);
+        }
+        let mut stored = [0u8; MAX_KEY];
+        self.read(record.offset + RECORD_HEADER_LEN, &mut stored[..key.len()])?;
+        Ok(&stored[..key.len()] == key)
+    }
+
+    /// `key`'e ait son geçerli kayıt.
+    fn lookup(&self, key: &[u8]) -> Result<Option<Record>, KernelError> {
+        let mut found = None;
+        let mut offset = SECTOR_HEADER_LEN;
+        while let Some(record) = self.record_at(offset)? {
+            if record.valid && self.key_matches(&record, key)? {
+                found = Some(record);
+            }
+            offset += self.record_len(record.key_len, record.value_len);
+        }
+        Ok(found)
+    }
+
+    /// Kayıt, anahtarının son geçerli kaydı mı? (Sıkıştırma ve listeleme için.)
+    fn is_latest(&self, record: &Record) -> Result<bool, KernelError> {
+        let mut key = [0u8; MAX_KEY];
+        self.read(record.offset + RECORD_HEADER_LEN, &mut key[..record.key_len])?;
+        let mut offset = record.offset + self.record_len(record.key_len, record.value_len);
+        while let Some(later) = self.record_at(offset)? {
+            if later.valid && self.key_matches(&later, &key[..record.key_len])? {
+                return Ok(false);
+            }
+            offset += self.record_len(later.key_len, later.value_len);
+        }
+        Ok(true)
+    }
+
+    /// Etkin sektörü tarar ve ilk boş konumu bulur.
+    fn scan_tail(&mut self) -> Result<(), KernelError> {
+        let mut offset = SECTOR_HEADER_LEN;
+        while let Some(record) = self.record_at(offset)? {
+            offset += self.record_len(record.key_len, record.value_len);
+        }
+        // Bozuk başlıktan sonrasına güvenilmez: boş değilse sektör dolu sayılır
+        let mut next = [0u8; 1];
+        if offset < self.sector_size {
+            self.read(offset, &mut next)?;
+        }
+        self.tail = if offset < self.sector_size && next[0] == flash::ERASED { offset } else { self.sector_size };
+        Ok(())
+    }
+
+    /// Sektör başlığını okur: (sıra numarası, silme sayacı); geçersizse `None`.
+    fn read_sector_header(&self, sector: usize) -> Result<Option<(u32, u32)>, KernelError> {
+        let mut header = [0u8; SECTOR_HEADER_LEN];
+        self.dev()?.read(self.sector_offset(sector), &mut header)?;
+        let word = |i: usize| u32::from_le_bytes([header[i], header[i + 1], header[i + 2], header[i + 3]]);
+        let valid = word(0) == SECTOR_MAGIC && word(12) == crc32_update(0, &header[..12]);
+        Ok(valid.then(|| (word(4), word(8))))
+    }
+
+    fn write_sector_header(&self, sector: usize, seq: u32, erase_count: u32) -> Result<(), KernelError> {
+        let mut header = [0u8; SECTOR_HEADER_LEN];
+        header[0..4].copy_from_slice(&SECTOR_MAGIC.to_le_bytes());
+        header[4..8].copy_from_slice(&seq.to_le_bytes());
+        header[8..12].copy_from_slice(&erase_count.to_le_bytes());
+        let crc = crc32_update(0, &header[..12]);
+        header[12..16].copy_from_slice(&crc.to_le_bytes());
+        self.dev()?.program(self.sector_offset(sector), &header)
+    }
+
+    fn erase_sector(&mut self, sector: usize) -> Result<(), KernelError> {
+        let erase_size = self.dev()?.erase_size();
+        for block in (0..self.sector_size).step_by(erase_size) {
+            self.dev()?.erase(self.sector_offset(sector) + block)?;
+        }
+        self.erase_counts[sector] = self.erase_counts[sector].wrapping_add(1);
+        Ok(())
+    }
+
+    /// Bölgeyi açar; geçerli sektör yoksa biçimlendirir.
+    fn mount(&mut self) -> Result<(), KernelError> {
+        let headers = [self.read_sector_header(0)?, self.read_sector_header(1)?];
+        for (count, header) in self.erase_counts.iter_mut().zip(headers) {
+            *count = header.map_or(0, |(_, erases)| erases);
+        }
+        let active = match headers {
+            [Some((a, _)), Some((b, _))] => usize::from((b.wrapping_sub(a) as i32) > 0),
+            [Some(_), None] => 0,
+            [None, Some(_)] => 1,
+            [None, None] => {
+                serial_println!("[KVS] Geçerli sektör yok, biçimlendiriliyor");
+                self.erase_sector(0)?;
+                self.write_sector_header(0, 1, self.erase_counts[0])?;
+                self.active = 0;
+                self.seq = 1;
+                self.tail = SECTOR_HEADER_LEN;
+                return Ok(());
+            }
+        };
+        self.active = active;
+        self.seq = headers[active].map_or(1, |(seq, _)| seq);
+        self.scan_tail()
+    }
+
+    /// Canlı kayıtları diğer sektöre taşır ve etkin sektörü değiştirir.
+    fn compact(&mut self) -> Result<(), KernelError> {
+        let from = self.active;
+        let to = 1 - from;
+        self.erase_sector(to)?;
+
+        let mut buf = [0u8; MAX_RECORD];
+        let mut dst = SECTOR_HEADER_LEN;
+        let mut offset = SECTOR_HEADER_LEN;
+        while let Some(record) = self.record_at(offset)? {
+            let len = self.record_len(record.key_len, record.value_len);
+            if record.valid && record.flags == FLAG_VALUE && self.is_latest(&record)? {
+                self.read(offset, &mut buf[..len])?;
+                self.dev()?.program(self.sector_offset(to) + dst, &buf[..len])?;
+                dst += len;
+            }
+            offset += len;
+        }
+
+        // Başlık en son: yarıda kalan sıkıştırmada eski sektör geçerli kalır
+        let seq = self.seq.wrapping_add(1);
+        self.write_sector_header(to, seq, self.erase_counts[to])?;
+        self.active = to;
+        self.seq = seq;
+        self.tail = dst;
+        COMPACTIONS.inc();
+        Ok(())
+    }
+
+    /// Kaydı günlüğe ekler; yer yoksa önce sıkıştırır.
+    fn append(&mut self, key: &[u8], flags: u8, value: &[u8]) -> Result<(), KernelError> {
+        let len = self.record_len(key.len(), value.len());
+        if self.tail + len > self.sector_size {
+            self.compact()?;
+            if self.tail + len > self.sector_size {
+                return Err(KernelError::OutOfMemoryStatic);
+            }
+        }
+
+        let mut buf = [flash::ERASED; MAX_RECORD];
+        buf[0] = key.len() as u8;
+        buf[1] = flags;
+        buf[2..4].copy_from_slice(&(value.len() as u16).to_le_bytes());
+        let body = RECORD_HEADER_LEN..RECORD_HEADER_LEN + key.len() + value.len();
+        buf[body.start..body.start + key.len()].copy_from_slice(key);
+        buf[body.start + key.len()..body.end].copy_from_slice(value);
+        let crc = crc32_update(crc32_update(0, &buf[..4]), &buf[body]);
+        buf[4..8].copy_from_slice(&crc.to_le_bytes());
+
+        let at = self.tail;
+        // Yazma başarısız olsa da alan kirlenmiş olabilir: kuyruk yine ilerler
+        self.tail += len;
+        self.dev()?.program(self.sector_offset(self.active) + at, &buf[..len])?;
+        WRITES.inc();
+        Ok(())
+    }
+}
+
+// -----------------------------------------------------------------------------
+// ARAYÜZ
+// -----------------------------------------------------------------------------
+
+fn check_key(key: &str) -> Result<&[u8], KernelError> {
+    if key.is_empty() || key.len() > MAX_KEY {
+        return Err(KernelError::InvalidArgument);
+    }
+    Ok(key.as_bytes())
+}
+
+/// `key`'in değerini `buf`'a okur.
+///
+/// # Dönüş Değeri
+/// Değerin uzunluğu (`buf` kısaysa yalnızca sığan kısım kopyalanır);
+/// anahtar yoksa `NotFound`.
+pub fn get(key: &str, buf: &mut [u8]) -> Result<usize, KernelError> {
+    let key = check_key(key)?;
+    with_store(|store| match store.lookup(key)? {
+        Some(record) if record.flags == FLAG_VALUE => {
+            let n = record.value_len.min(buf.len());
+            store.read(record.value_offset(), &mut buf[..n])?;
+            Ok(record.value_len)
+        }
+        _ => Err(KernelError::NotFound),
+    })
+}
+
+/// `key`'e `value`'yu atar. Değer aynıysa flash'a yazılmaz.
+pub fn set(key: &str, value: &[u8]) -> Result<(), KernelError> {
+    let key = check_key(key)?;
+    if value.len() > MAX_VALUE {
+        return Err(KernelError::InvalidArgument);
+    }
+    with_store(|store| {
+        if let Some(record) = store.lookup(key)?.filter(|r| r.flags == FLAG_VALUE && r.value_len == value.len()) {
+            let mut current = [0u8; MAX_VALUE];
+            store.read(record.value_offset(), &mut current[..value.len()])?;
+            if &current[..value.len()] == value {
+                return Ok(());
+            }
+        }
+        store.append(key, FLAG_VALUE, value)
+    })
+}
+
+/// `key`'i siler.
+pub fn remove(key: &str) -> Result<(), KernelError> {
+    let key = check_key(key)?;
+    with_store(|store| match store.lookup(key)? {
+        Some(record) if record.flags == FLAG_VALUE => store.append(key, FLAG_TOMBSTONE, &[]),
+        _ => Err(KernelError::NotFound),
+    })
+}
+
+/// Tüm canlı anahtar-değer çiftleri için `f`'yi çağırır (depo kilidi tutulurken).
+pub fn for_each(mut f: impl FnMut(&str, &[u8])) -> Result<(), KernelError> {
+    with_store(|store| {
+        let mut buf = [0u8; MAX_KEY + MAX_VALUE];
+        let mut offset = SECTOR_HEADER_LEN;
+        while let Some(record) = store.record_at(offset)? {
+            if record.valid && record.flags == FLAG_VALUE && store.is_latest(&record)? {
+                let body = &mut buf[..record.key_len + record.value_len];
+                store.read(offset + RECORD_HEADER_LEN, body)?;
+                let (key, value) = body.split_at(record.key_len);
+                f(core::str::from_utf8(key).unwrap_or("?"), value);
+            }
+            offset += store.record_len(record.key_len, record.value_len);
+        }
+        Ok(())
+    })
+}
+
+/// Depo kullanımı: (etkin sektörde dolu bayt, sektör boyutu, silme sayaçları).
+pub fn usage() -> Result<(usize, usize, [u32; 2]), KernelError> {
+    with_store(|store| Ok((store.tail, store.sector_size, store.erase_counts)))
+}
+
+/// Depoyu tercih edilen flash aygıtının son iki silme bloğunda açar.
+fn init() -> Result<(), KernelError> {
+    let dev = flash::find(PREFERRED_DEVICE)
+        .or_else(|| (0..flash::MAX_FLASH_DEVICES).rev().find_map(flash::device));
+    let Some(dev) = dev else { return Ok(()) };
+    let sector_size = dev.erase_size();
+    if sector_size < SECTOR_HEADER_LEN + MAX_RECORD || dev.size() < 2 * sector_size {
+        return Err(KernelError::InvalidArgument);
+    }
+
+    STORE.lock.lock();
+    let store = unsafe { &mut *STORE.store.get() };
+    store.dev = Some(dev);
+    store.base = dev.size() - 2 * sector_size;
+    store.sector_size = sector_size;
+    store.align = dev.write_size().max(8);
+    let result = store.mount();
+    if result.is_err() {
+        store.dev = None;
+    }
+    let (tail, seq) = (store.tail, store.seq);
+    STORE.lock.unlock();
+
+    result?;
+    serial_println!("[KVS] {} üzerinde açıldı ({} / {} B, sıra {})", dev.name(), tail, sector_size, seq);
+    Ok(())
+}
+
+crate::initcall!(fs, "kvs", init);
+
+// -----------------------------------------------------------------------------
+// KABUK VE PROCFS
+// -----------------------------------------------------------------------------
+
+fn kv_command(args: &[&str]) -> Result<(), KernelError> {
+    match (args.get(1).copied(), args.get(2)) {
+        (Some("get"), Some(key)) => {
+            let mut buf = [0u8; MAX_VALUE];
+            let len = get(key, &mut buf)?;
+            match core::str::from_utf8(&buf[..len]) {
+                Ok(text) => serial_println!("{}", text),
+                Err(_) => serial_println!("{:02x?}", &buf[..len]),
+            }
+            Ok(())
+        }
+        (Some("set"), Some(key)) => {
+            let value = args.get(3).ok_or(KernelError::InvalidArgument)?;
+            set(key, value.as_bytes())
+        }
+        (Some("rm"), Some(key)) => remove(key),
+        (None, _) => {
+            for_each(|key, value| match core::str::from_utf8(value) {
+                Ok(text) => serial_println!("  {} = {}", key, text),
+                Err(_) => serial_println!("  {} = ({} bayt)", key, value.len()),
+            })?;
+            let (used, size, erases) = usage()?;
+            serial_println!("  {} / {} B kullanımda, silmeler {} / {}", used, size, erases[0], erases[1]);
+            Ok(())
+        }
+        _ => Err(KernelError::InvalidArgument),
+    }
+}
+
+crate::shell_command!("kv", "Kalıcı anahtar-değer deposu (kv [get <k> | set <k> <v> | rm <k>])", kv_command);
+
+fn show_kvs(out: &mut dyn core::fmt::Write) -> core::fmt::Result {
+    if let Ok((used, size, erases)) = usage() {
+        writeln!(out, "used {}\nsize {}\nerases {} {}", used, size, erases[0], erases[1])?;
+    }
+    Ok(())
+}
+
+crate::proc_entry!("kvs", show_kvs);
@@ -0,0 +1,288 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/latency.rs
+// Gecikme ölçümü: kesme girişinden işleyiciye ve uyanmadan çalışmaya kadar
+// geçen sürenin log2 histogramları.
+//
+// Zaman damgaları monotonik saatten (`clock::cycles`; TSC, CNTPCT, time,
+// TB vb.) okunur. Kova `i`, [2^i, 2^(i+1)) sayaç birimindeki örnekleri tutar;
+// sayaç frekansı biliniyorsa raporlar nanosaniyeye çevrilir. Kayıt kilitsizdir
+// (atomik) ve kesme bağlamından güvenle yapılır.
+//
+// * Kesme: mimarinin kesme girişi `irq_enter` ile damga alır, sürücüye
+//   dağıtmadan hemen önce `irq_dispatch` çağırır. Histogramlar ilk gelişte
+//   `MAX_IRQ_HISTOGRAMS` yuvadan birine bağlanır.
+// * Görev: `sched::wake` uyanma anını, `sched::schedule` göreve geçişi
+//   bildirir; kesintiye uğrayıp yeniden seçilen görevler sayılmaz.
+//
+// Kabuk komutu: `latency [irq|task|reset]`; procfs: `/proc/latency`
+
+use core::fmt;
+use core::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use crate::clock;
+use crate::platformgeneric::KernelError;
+use crate::sched::{TaskId, MAX_TASKS};
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// ZAMAN DAMGASI
+// -----------------------------------------------------------------------------
+
+/// Sayaç değerini raporlama birimine (ns veya döngü) çevirir.
+fn to_unit(value: u64, hz: u64) -> u64 {
+    if hz == 0 {
+        return value;
+    }
+    (u128::from(value) * 1_000_000_000 / u128::from(hz)) as u64
+}
+
+fn unit_name(hz: u64) -> &'static str {
+    if hz == 0 { "döngü" } else { "ns" }
+}
+
+// -----------------------------------------------------------------------------
+// HİSTOGRAM
+// -----------------------------------------------------------------------------
+
+/// Kova sayısı: 2^40 sayaç birimine kadar (GHz sayaçta ~18 dk).
+pub const BUCKETS: usize = 40;
+
+#[allow(clippy::declare_interior_mutable_const)]
+const ZERO32: AtomicU32 = AtomicU32::new(0);
+#[allow(clippy::declare_interior_mutable_const)]
+const ZERO64: AtomicU64 = AtomicU64::new(0);
+#[allow(clippy::declare_interior_mutable_const)]
+const EMPTY: Histogram = Histogram::new();
+
+/// Kilitsiz log2 gecikme histogramı.
+pub struct Histogram {
+    buckets: [AtomicU32; BUCKETS],
+    count: AtomicU64,
+    sum: AtomicU64,
+    max: AtomicU64,
+}
+
+impl Histogram {
+    pub const fn new() -> Self {
+        Histogram { buckets: [ZERO32; BUCKETS], count: ZERO64, sum: ZERO64, max: ZERO64 }
+    }
+
+    /// Bir örneği (sayaç birimi) kaydeder.
+    pub fn record(&self, value: u64) {
+        let bucket = (u64::BITS - value.leading_zeros()).saturating_sub(1) as usize;
+        self.buckets[bucket.min(BUCKETS - 1)].fetch_add(1, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.sum.fetch_add(value, Ordering::Relaxed);
+        self.max.fetch_max(value, Ordering::Relaxed);
+    }
+
+    pub fn reset(&self) {
+        self.buckets.iter().for_each(|b| b.store(0, Ordering::Relaxed));
+        self.count.store(0, Ordering::Relaxed);
+        self.sum.store(0, Ordering::Relaxed);
+        self.max.store(0, Ordering::Relaxed);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    /// En kötü durum (sayaç birimi).
+    pub fn max(&self) -> u64 {
+        self.max.load(Ordering::Relaxed)
+    }
+
+    /// Ortalama (sayaç birimi).
+    pub fn mean(&self) -> u64 {
+        self.sum.load(Ordering::Relaxed).checked_div(self.count()).unwrap_or(0)
+    }
+
+    /// Boş olmayan kovaları `[alt, üst)` aralıklarıyla yazar.
+    fn dump(&self, hz: u64) {
+        for (i, bucket) in self.buckets.iter().enumerate() {
+            let n = bucket.load(Ordering::Relaxed);
+            if n == 0 {
+                continue;
+            }
+            let low = if i == 0 { 0 } else { 1u64 << i };
+            let high = 1u64 << (i + 1);
+            serial_println!("      {:>12} - {:<12} {:>10}", to_unit(low, hz), to_unit(high, hz), n);
+        }
+    }
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// -----------------------------------------------------------------------------
+// KESME GECİKMESİ
+// -----------------------------------------------------------------------------
+
+/// Histogramı tutulan en fazla farklı kesme.
+pub const MAX_IRQ_HISTOGRAMS: usize = 16;
+
+/// Yuvaya bağlı kesme numarası + 1 (0 = boş).
+static IRQ_SLOTS: [AtomicU32; MAX_IRQ_HISTOGRAMS] = [ZERO32; MAX_IRQ_HISTOGRAMS];
+static IRQ_HIST: [Histogram; MAX_IRQ_HISTOGRAMS] = [EMPTY; MAX_IRQ_HISTOGRAMS];
+
+crate::kernel_counter!(IRQ_UNTRACKED, "latency.irq_untracked");
+
+/// Kesmenin yuvasını bulur; yoksa ilk boş yuvayı kilitsiz olarak alır.
+fn irq_slot(irq: u32) -> Option<usize> {
+    let tag = irq.wrapping_add(1);
+    for (i, slot) in IRQ_SLOTS.iter().enumerate() {
+        match slot.compare_exchange(0, tag, Ordering::AcqRel, Ordering::Acquire) {
+            Ok(_) => return Some(i),
+            Err(current) if current == tag => return Some(i),
+            Err(_) => {}
+        }
+    }
+    None
+}
+
+/// Kesme girişinde, mimari işleyicinin ilk işi olarak çağrılır.
+#[inline(always)]
+pub fn irq_enter() -> u64 {
+    clock::cycles()
+}
+
+/// Sürücüye dağıtmadan hemen önce çağrılır; `entry`, `irq_enter` damgasıdır.
+#[inline]
+pub fn irq_dispatch(irq: u32, entry: u64) {
+    let delta = clock::cycles().wrapping_sub(entry);
+    match irq_slot(irq) {
+        Some(i) => IRQ_HIST[i].record(delta),
+        None => IRQ_UNTRACKED.inc(),
+    }
+}
+
+// -----------------------------------------------------------------------------
+// GÖREV UYANMA GECİKMESİ
+// -----------------------------------------------------------------------------
+
+/// Uyanma damgası (0 = bekleyen ölçüm yok).
+static WAKE_STAMP: [AtomicU64; MAX_TASKS] = [ZERO64; MAX_TASKS];
+static TASK_HIST: [Histogram; MAX_TASKS] = [EMPTY; MAX_TASKS];
+
+/// Görev yeni oluşturulduğunda önceki sahibin ölçümlerini siler.
+pub fn task_reset(id: TaskId) {
+    if let (Some(stamp), Some(hist)) = (WAKE_STAMP.get(id), TASK_HIST.get(id)) {
+        stamp.store(0, Ordering::Relaxed);
+        hist.reset();
+    }
+}
+
+/// Bekleyen görev hazır duruma geçtiğinde çağrılır.
+#[inline]
+pub fn task_woken(id: TaskId) {
+    if let Some(stamp) = WAKE_STAMP.get(id) {
+        // 0 "ölçüm yok" anlamına geldiği için en az 1
+        stamp.store(clock::cycles().max(1), Ordering::Relaxed);
+    }
+}
+
+/// Göreve geçilirken çağrılır; uyanma damgası varsa gecikmeyi kaydeder.
+#[inline]
+pub fn task_dispatched(id: TaskId) {
+    let Some(stamp) = WAKE_STAMP.get(id) else { return };
+    let woken = stamp.swap(0, Ordering::Relaxed);
+    if woken != 0 {
+        TASK_HIST[id].record(clock::cycles().wrapping_sub(woken));
+    }
+}
+
+/// Görevin uyanma gecikmesi histogramı.
+pub fn task_histogram(id: TaskId) -> Option<&'static Histogram> {
+    TASK_HIST.get(id)
+}
+
+/// Kesmenin giriş gecikmesi histogramı (ölçülmüşse).
+pub fn irq_histogram(irq: u32) -> Option<&'static Histogram> {
+    let tag = irq.wrapping_add(1);
+    IRQ_SLOTS.iter().position(|s| s.load(Ordering::Acquire) == tag).map(|i| &IRQ_HIST[i])
+}
+
+// -----------------------------------------------------------------------------
+// KABUK VE PROCFS
+// -----------------------------------------------------------------------------
+
+fn dump_irqs(hz: u64) {
+    serial_println!("  IRQ         SAYI   ORT({0})   EN KÖTÜ({0})", unit_name(hz));
+    for (slot, hist) in IRQ_SLOTS.iter().zip(IRQ_HIST.iter()) {
+        let tag = slot.load(Ordering::Acquire);
+        if tag == 0 || hist.count() == 0 {
+            continue;
+        }
+        serial_println!(
+            "  {:<6} {:>10} {:>10} {:>12}",
+            tag - 1,
+            hist.count(),
+            to_unit(hist.mean(), hz),
+            to_unit(hist.max(), hz)
+        );
+        hist.dump(hz);
+    }
+}
+
+fn dump_tasks(hz: u64) {
+    serial_println!("  GÖREV              SAYI   ORT({0})   EN KÖTÜ({0})", unit_name(hz));
+    for (id, hist) in TASK_HIST.iter().enumerate() {
+        if hist.count() == 0 {
+            continue;
+        }
+        let name = crate::sched::task_info(id).map_or("?", |info| info.name);
+        serial_println!(
+            "  {:>2} {:<12} {:>10} {:>10} {:>12}",
+            id,
+            name,
+            hist.count(),
+            to_unit(hist.mean(), hz),
+            to_unit(hist.max(), hz)
+        );
+        hist.dump(hz);
+    }
+}
+
+/// `latency [irq|task|reset]`
+fn latency_command(args: &[&str]) -> Result<(), KernelError> {
+    let hz = clock::cycles_hz();
+    match args.get(1).copied() {
+        None => {
+            dump_irqs(hz);
+            dump_tasks(hz);
+        }
+        Some("irq") => dump_irqs(hz),
+        Some("task") => dump_tasks(hz),
+        Some("reset") => {
+            IRQ_HIST.iter().for_each(Histogram::reset);
+            TASK_HIST.iter().for_each(Histogram::reset);
+        }
+        Some(_) => return Err(KernelError::InvalidArgument),
+    }
+    Ok(())
+}
+
+crate::shell_command!("latency", "Gecikme histogramları (latency [irq|task|reset])", latency_command);
+
+/// Satır başına özet: `irq <no> <sayı> <ort> <en kötü>` / `task <id> ...`
+/// (değerler ham sayaç birimindedir).
+fn show_latency(out: &mut dyn fmt::Write) -> fmt::Result {
+    writeln!(out, "hz {}", clock::cycles_hz())?;
+    for (slot, hist) in IRQ_SLOTS.iter().zip(IRQ_HIST.iter()) {
+        let tag = slot.load(Ordering::Acquire);
+        if tag != 0 && hist.count() != 0 {
+            writeln!(out, "irq {} {} {} {}", tag - 1, hist.count(), hist.mean(), hist.max())?;
+        }
+    }
+    for (id, hist) in TASK_HIST.iter().enumerate() {
+        if hist.count() != 0 {
+            writeln!(out, "task {} {} {} {}", id, hist.count(), hist.mean(), hist.max())?;
+        }
+    }
+    Ok(())
+}
+
+crate::proc_entry!("latency", show_latency);
@@ -0,0 +1,175 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/layout.rs
+// Çekirdek imajının bellek yerleşimi (linker betiğinin sınır sembolleri).
+//
+// Her mimarinin `src/arch/<mimari>/linker.ld` betiği aynı sembolleri tanımlar;
+// bu modül onları adres aralıkları olarak dışa açar. MMU bölüm koruması
+// (`protection`), çerçeve ayırıcısının imajı ayırması ve yığın koruma sayfası
+// bu sınırları kullanır. Bölümler sayfa hizalıdır.
+//
+// Kabuk komutu: `layout`
+
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+
+extern "C" {
+    static __kernel_start: u8;
+    static __kernel_end: u8;
+    static __text_start: u8;
+    static __text_end: u8;
+    static __rodata_start: u8;
+    static __rodata_end: u8;
+    static __data_start: u8;
+    static __data_end: u8;
+    static __percpu_start: u8;
+    static __percpu_end: u8;
+    static __bss_start: u8;
+    static __bss_end: u8;
+    static __noinit_start: u8;
+    static __noinit_end: u8;
+    static __stack_guard_start: u8;
+    static __stack_guard_end: u8;
+    static __boot_stack_bottom: u8;
+    static __boot_stack_top: u8;
+}
+
+/// Yarı açık adres aralığı `[start, end)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Region {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Region {
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    pub fn contains(&self, addr: usize) -> bool {
+        (self.start..self.end).contains(&addr)
+    }
+}
+
+macro_rules! region {
+    ($start:ident, $end:ident) => {
+        // SAFETY: Semboller linker betiğinde tanımlıdır; yalnızca adresleri alınır.
+        unsafe {
+            Region {
+                start: core::ptr::addr_of!($start) as usize,
+                end: core::ptr::addr_of!($end) as usize,
+            }
+        }
+    };
+}
+
+/// İmajın tamamı (koruma sayfası ve önyükleme yığını dahil).
+pub fn kernel() -> Region {
+    region!(__kernel_start, __kernel_end)
+}
+
+/// Kod.
+pub fn text() -> Region {
+    region!(__text_start, __text_end)
+}
+
+/// Salt okunur veri ve kayıt tabloları (initcall, kabuk komutları, procfs,
+/// sysctl, istisna tablosu).
+pub fn rodata() -> Region {
+    region!(__rodata_start, __rodata_end)
+}
+
+/// İlk değerli yazılabilir veri.
+pub fn data() -> Region {
+    region!(__data_start, __data_end)
+}
+
+/// İşlemci başına değişkenlerin şablonu.
+pub fn percpu() -> Region {
+    region!(__percpu_start, __percpu_end)
+}
+
+/// Sıfırlanan veri.
+pub fn bss() -> Region {
+    region!(__bss_start, __bss_end)
+}
+
+/// Açılışta sıfırlanmayan, sıcak yeniden başlatmada korunan veri (bkz. `reboot`).
+pub fn noinit() -> Region {
+    region!(__noinit_start, __noinit_end)
+}
+
+/// Önyükleme yığınının altındaki koruma sayfası.
+pub fn stack_guard() -> Region {
+    region!(__stack_guard_start, __stack_guard_end)
+}
+
+/// Önyükleme yığını (`end` yığının üstüdür).
+pub fn boot_stack() -> Region {
+    region!(__boot_stack_bottom, __boot_stack_top)
+}
+
+/// Çekirdek sayfasının olması gereken izinleri.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protection {
+    /// Okunur ve yürütülür (`.text`).
+    ReadExec,
+    /// Yalnızca okunur (`.rodata`).
+    ReadOnly,
+    /// Okunur ve yazılır (`.data`, `.percpu`, `.bss`, `.noinit`, yığın).
+    ReadWrite,
+    /// Eşlenmemeli (yığın koruma sayfası).
+    Guard,
+}
+
+/// `addr` çekirdek imajındaysa bulunduğu bölümün izinlerini döndürür.
+/// MMU kurulumu imajı bu izinlerle sayfa sayfa eşler.
+pub fn protection(addr: usize) -> Option<Protection> {
+    if text().contains(addr) {
+        Some(Protection::ReadExec)
+    } else if rodata().contains(addr) {
+        Some(Protection::ReadOnly)
+    } else if stack_guard().contains(addr) {
+        Some(Protection::Guard)
+    } else if kernel().contains(addr) {
+        Some(Protection::ReadWrite)
+    } else {
+        None
+    }
+}
+
+/// Çerçeve ayırıcısı imajın üzerine yazmasın diye imajı ayırır
+/// (`frame::init` içinden).
+pub(crate) fn reserve_image() {
+    let image = kernel();
+    crate::memory::frame::reserve(image.start, image.len());
+}
+
+// -----------------------------------------------------------------------------
+// KABUK KOMUTU
+// -----------------------------------------------------------------------------
+
+fn layout_command(_args: &[&str]) -> Result<(), KernelError> {
+    let sections: [(&str, Region, &str); 9] = [
+        ("kernel", kernel(), ""),
+        (".text", text(), "r-x"),
+        (".rodata", rodata(), "r--"),
+        (".data", data(), "rw-"),
+        (".percpu", percpu(), "rw-"),
+        (".bss", bss(), "rw-"),
+        (".noinit", noinit(), "rw-"),
+        ("guard", stack_guard(), "---"),
+        ("stack", boot_stack(), "rw-"),
+    ];
+    serial_println!("  BÖLÜM     BAŞLANGIÇ          BİTİŞ              BOYUT      İZİN");
+    for (name, region, prot) in sections {
+        serial_println!("  {:<9} {:#018x} {:#018x} {:>7} KiB {}", name, region.start, region.end, region.len() / 1024, prot);
+    }
+    Ok(())
+}
+
+crate::shell_command!("layout", "Çekirdek imajının bölüm yerleşimini gösterir", layout_command);
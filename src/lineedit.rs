@@ -0,0 +1,532 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/lineedit.rs
+// Kabuk için ANSI satır düzenleyici: imleç hareketi, silme, geçmiş ve sekme tamamlama.
+//
+// Düzenleyici oturum başına bir `LineEditor`'dır ve çıktısını verilen
+// `fmt::Write` hedefine yazar; böylece seri konsol ve ağ oturumları aynı
+// kodu kullanır. Girdi iki biçimde gelebilir:
+// * Ham baytlar (`feed_byte`): seri terminalin gönderdiği ANSI kaçış
+//   dizileri (ESC [ A, ESC [ 3 ~, ESC O H, ...) burada çözülür.
+// * Klavye olayları (`feed_event`): USB HID ok/Home/End/Delete tuşları
+//   kullanım kodundan, diğerleri ürettikleri ASCII bayttan çevrilir.
+//
+// Tuşlar (Emacs/readline karşılıklarıyla):
+//   ←/→ Ctrl-B/F   imleç        Home/End Ctrl-A/E   satır başı/sonu
+//   ↑/↓ Ctrl-P/N   geçmiş       Backspace, Delete/Ctrl-D
+//   Ctrl-K         imleçten sona kadar siler
+//   Ctrl-U         satır başından imlece kadar siler
+//   Ctrl-C         satırı iptal eder      Ctrl-L  ekranı temizler
+//   Tab            ilk kelimede komut adı, `/` ile başlayan argümanlarda
+//                  VFS yolu (örn. `/proc/drivers/`) tamamlar
+//
+// Yavaş seri hatlar için ekran yalnızca değişen kısımdan itibaren yeniden
+// çizilir. Satırlar yazdırılabilir ASCII ile sınırlıdır.
+//
+// Geçmiş tüm oturumlarca paylaşılan, RAM'de tutulan bir halkadır ve çekirdek
+// çalıştığı sürece korunur; ardışık tekrarlar ve boş satırlar kaydedilmez.
+
+use core::fmt::{self, Write};
+use crate::input::KeyEvent;
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+use crate::sync::Mutex;
+
+/// Bir satırın en fazla uzunluğu (bayt).
+pub const LINE_MAX: usize = 128;
+
+/// Geçmişte tutulan satır sayısı.
+pub const HISTORY_LEN: usize = 32;
+
+// USB HID kullanım kodları (karakter üretmeyen düzenleme tuşları)
+const USAGE_HOME: u8 = 0x4A;
+const USAGE_DELETE: u8 = 0x4C;
+const USAGE_END: u8 = 0x4D;
+const USAGE_RIGHT: u8 = 0x4F;
+const USAGE_LEFT: u8 = 0x50;
+const USAGE_DOWN: u8 = 0x51;
+const USAGE_UP: u8 = 0x52;
+
+// -----------------------------------------------------------------------------
+// SATIR VE GEÇMİŞ
+// -----------------------------------------------------------------------------
+
+/// Sabit boyutlu satır arabelleği.
+#[derive(Clone, Copy)]
+pub struct Line {
+    buf: [u8; LINE_MAX],
+    len: usize,
+}
+
+impl Line {
+    pub const EMPTY: Line = Line { buf: [0; LINE_MAX], len: 0 };
+
+    pub fn as_str(&self) -> &str {
+        // Yalnızca yazdırılabilir ASCII eklenir
+        core::str::from_utf8(&self.buf[..self.len]).unwrap_or("")
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn insert(&mut self, at: usize, bytes: &[u8]) -> usize {
+        let n = bytes.len().min(LINE_MAX - self.len);
+        self.buf.copy_within(at..self.len, at + n);
+        self.buf[at..at + n].copy_from_slice(&bytes[..n]);
+        self.len += n;
+        n
+    }
+
+    fn remove(&mut self, from: usize, to: usize) {
+        self.buf.copy_within(to..self.len, from);
+        self.len -= to - from;
+    }
+}
+
+struct History {
+    lines: [Line; HISTORY_LEN],
+    /// Şimdiye kadar eklenen satır sayısı (serbest artan).
+    count: usize,
+}
+
+static HISTORY: Mutex<History> = Mutex::new(History { lines: [Line::EMPTY; HISTORY_LEN], count: 0 });
+
+/// Satırı geçmişe ekler (boşsa veya son satırla aynıysa eklenmez).
+pub fn history_push(line: &Line) {
+    if line.as_str().trim().is_empty() {
+        return;
+    }
+    let Ok(mut history) = HISTORY.lock() else { return };
+    let count = history.count;
+    if count > 0 && history.lines[(count - 1) % HISTORY_LEN].as_str() == line.as_str() {
+        return;
+    }
+    history.lines[count % HISTORY_LEN] = *line;
+    history.count = count + 1;
+}
+
+/// Geçmişten `age`. en yeni satır (1: son girilen).
+pub fn history_get(age: usize) -> Option<Line> {
+    let history = HISTORY.lock().ok()?;
+    if age == 0 || age > history.count.min(HISTORY_LEN) {
+        return None;
+    }
+    Some(history.lines[(history.count - age) % HISTORY_LEN])
+}
+
+/// Geçmişteki satır sayısı.
+pub fn history_len() -> usize {
+    HISTORY.lock().map_or(0, |h| h.count.min(HISTORY_LEN))
+}
+
+// -----------------------------------------------------------------------------
+// TUŞ ÇÖZÜMLEME
+// -----------------------------------------------------------------------------
+
+/// Düzenleme tuşu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Char(u8),
+    Enter,
+    Backspace,
+    Delete,
+    Left,
+    Right,
+    Home,
+    End,
+    Up,
+    Down,
+    Tab,
+    KillToEnd,
+    KillToStart,
+    Cancel,
+    Clear,
+}
+
+/// Kaçış dizisi çözücüsünün durumu.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Escape {
+    None,
+    /// ESC alındı.
+    Esc,
+    /// ESC [ ve sayısal parametre.
+    Csi(u8),
+    /// ESC O (uygulama imleç kipi).
+    Ss3,
+}
+
+/// Kontrol karakteri veya yazdırılabilir bayt.
+fn control_key(byte: u8) -> Option<Key> {
+    Some(match byte {
+        b'\r' | b'\n' => Key::Enter,
+        0x7f | 0x08 => Key::Backspace,
+        b'\t' => Key::Tab,
+        0x01 => Key::Home,
+        0x02 => Key::Left,
+        0x03 => Key::Cancel,
+        0x04 => Key::Delete,
+        0x05 => Key::End,
+        0x06 => Key::Right,
+        0x0b => Key::KillToEnd,
+        0x0c => Key::Clear,
+        0x0e => Key::Down,
+        0x10 => Key::Up,
+        0x15 => Key::KillToStart,
+        0x20..=0x7e => Key::Char(byte),
+        _ => return None,
+    })
+}
+
+/// CSI/SS3 dizisinin son harfi.
+fn final_key(byte: u8) -> Option<Key> {
+    Some(match byte {
+        b'A' => Key::Up,
+        b'B' => Key::Down,
+        b'C' => Key::Right,
+        b'D' => Key::Left,
+        b'H' => Key::Home,
+        b'F' => Key::End,
+        _ => return None,
+    })
+}
+
+// -----------------------------------------------------------------------------
+// DÜZENLEYİCİ
+// -----------------------------------------------------------------------------
+
+/// Tek bir oturumun satır düzenleyicisi.
+pub struct LineEditor {
+    prompt: &'static str,
+    line: Line,
+    cursor: usize,
+    escape: Escape,
+    /// Son bayt CR'ydi: ardından gelen LF ikinci bir Enter sayılmaz.
+    after_cr: bool,
+    /// Gezilen geçmiş satırı (0: düzenlenen satır).
+    browse: usize,
+    /// Geçmişte gezinmeye başlamadan önce yazılmakta olan satır.
+    draft: Line,
+}
+
+impl LineEditor {
+    pub const fn new(prompt: &'static str) -> Self {
+        LineEditor {
+            prompt,
+            line: Line::EMPTY,
+            cursor: 0,
+            escape: Escape::None,
+            after_cr: false,
+            browse: 0,
+            draft: Line::EMPTY,
+        }
+    }
+
+    /// İstemi yazar ve yeni bir satıra başlar.
+    pub fn prompt(&mut self, out: &mut dyn Write) {
+        self.line = Line::EMPTY;
+        self.cursor = 0;
+        self.browse = 0;
+        let _ = out.write_str(self.prompt);
+    }
+
+    /// Terminalden gelen bir baytı işler.
+    ///
+    /// # Dönüş Değeri
+    /// Enter ile tamamlanan satır (geçmişe eklenmiş olarak); istem yeniden
+    /// yazılmaz, çağıran komutu çalıştırdıktan sonra `prompt` çağırır.
+    pub fn feed_byte(&mut self, byte: u8, out: &mut dyn Write) -> Option<Line> {
+        let after_cr = core::mem::replace(&mut self.after_cr, byte == b'\r');
+        let key = match self.escape {
+            Escape::None if byte == 0x1b => {
+                self.escape = Escape::Esc;
+                return None;
+            }
+            Escape::None if byte == b'\n' && after_cr => return None,
+            Escape::None => control_key(byte),
+            Escape::Esc => {
+                self.escape = match byte {
+                    b'[' => Escape::Csi(0),
+                    b'O' => Escape::Ss3,
+                    _ => Escape::None,
+                };
+                return None;
+            }
+            Escape::Csi(param) if byte.is_ascii_digit() => {
+                self.escape = Escape::Csi(param.saturating_mul(10).saturating_add(byte - b'0'));
+                return None;
+            }
+            // Değiştirici parametresi (örn. ESC [ 1 ; 5 C) yok sayılır
+            Escape::Csi(_) if byte == b';' => {
+                self.escape = Escape::Csi(0);
+                return None;
+            }
+            Escape::Csi(param) => {
+                self.escape = Escape::None;
+                match (byte, param) {
+                    (b'~', 1 | 7) => Some(Key::Home),
+                    (b'~', 3) => Some(Key::Delete),
+                    (b'~', 4 | 8) => Some(Key::End),
+                    _ => final_key(byte),
+                }
+            }
+            Escape::Ss3 => {
+                self.escape = Escape::None;
+                final_key(byte)
+            }
+        };
+        self.feed_key(key?, out)
+    }
+
+    /// Klavye olayını işler (bkz. `feed_byte`).
+    pub fn feed_event(&mut self, event: &KeyEvent, out: &mut dyn Write) -> Option<Line> {
+        if !event.pressed {
+            return None;
+        }
+        if event.ascii != 0 {
+            return self.feed_byte(event.ascii, out);
+        }
+        let key = match event.usage {
+            USAGE_HOME => Key::Home,
+            USAGE_DELETE => Key::Delete,
+            USAGE_END => Key::End,
+            USAGE_RIGHT => Key::Right,
+            USAGE_LEFT => Key::Left,
+            USAGE_DOWN => Key::Down,
+            USAGE_UP => Key::Up,
+            _ => return None,
+        };
+        self.feed_key(key, out)
+    }
+
+    /// Çözülmüş bir tuşu uygular.
+    pub fn feed_key(&mut self, key: Key, out: &mut dyn Write) -> Option<Line> {
+        let cursor = self.cursor;
+        match key {
+            Key::Char(byte) => {
+                if self.line.insert(cursor, &[byte]) == 0 {
+                    let _ = out.write_str("\x07");
+                } else if cursor + 1 == self.line.len {
+                    // Sona ekleme: yalnızca yankı
+                    self.cursor += 1;
+                    let _ = out.write_char(byte as char);
+                } else {
+                    self.cursor += 1;
+                    self.redraw_from(cursor, out);
+                }
+            }
+            Key::Enter => {
+                let _ = out.write_str("\n");
+                let line = self.line;
+                history_push(&line);
+                self.line = Line::EMPTY;
+                self.cursor = 0;
+                self.browse = 0;
+                return Some(line);
+            }
+            Key::Backspace if cursor > 0 => {
+                self.line.remove(cursor - 1, cursor);
+                self.cursor -= 1;
+                move_left(out, 1);
+                self.redraw_from(cursor - 1, out);
+            }
+            Key::Delete if cursor < self.line.len => {
+                self.line.remove(cursor, cursor + 1);
+                self.redraw_from(cursor, out);
+            }
+            Key::Left if cursor > 0 => {
+                self.cursor -= 1;
+                move_left(out, 1);
+            }
+            Key::Right if cursor < self.line.len => {
+                self.cursor += 1;
+                move_right(out, 1);
+            }
+            Key::Home => {
+                self.cursor = 0;
+                move_left(out, cursor);
+            }
+            Key::End => {
+                self.cursor = self.line.len;
+                move_right(out, self.line.len - cursor);
+            }
+            Key::KillToEnd => {
+                self.line.len = cursor;
+                let _ = out.write_str("\x1b[K");
+            }
+            Key::KillToStart => {
+                self.line.remove(0, cursor);
+                self.cursor = 0;
+                move_left(out, cursor);
+                self.redraw_from(0, out);
+            }
+            Key::Up => self.browse_history(self.browse + 1, out),
+            Key::Down if self.browse > 0 => self.browse_history(self.browse - 1, out),
+            Key::Tab => self.complete(out),
+            Key::Cancel => {
+                let _ = out.write_str("^C\n");
+                self.prompt(out);
+            }
+            Key::Clear => {
+                let _ = write!(out, "\x1b[2J\x1b[H{}", self.prompt);
+                self.redraw_from(0, out);
+            }
+            Key::Backspace | Key::Delete | Key::Left | Key::Right | Key::Down => {}
+        }
+        None
+    }
+
+    /// Terminal imleci `from` sütunundayken satırı oradan itibaren yeniden
+    /// çizer, satır sonunu temizler ve imleci `self.cursor`'a geri getirir.
+    fn redraw_from(&self, from: usize, out: &mut dyn Write) {
+        let _ = out.write_str(&self.line.as_str()[from..]);
+        let _ = out.write_str("\x1b[K");
+        move_left(out, self.line.len - self.cursor);
+    }
+
+    /// Satırı geçmişin `age`. girdisiyle değiştirir (0: taslak).
+    fn browse_history(&mut self, age: usize, out: &mut dyn Write) {
+        let line = if age == 0 {
+            self.draft
+        } else {
+            match history_get(age) {
+                Some(line) => line,
+                None => return,
+            }
+        };
+        if self.browse == 0 {
+            self.draft = self.line;
+        }
+        self.browse = age;
+        move_left(out, self.cursor);
+        self.line = line;
+        self.cursor = line.len;
+        self.redraw_from(0, out);
+    }
+
+    // -------------------------------------------------------------------------
+    // TAMAMLAMA
+    // -------------------------------------------------------------------------
+
+    /// İmlecin solundaki kelimeyi tamamlar: adaylar tek ise tamamen, birden
+    /// fazla ise ortak öneke kadar; ortak önek uzamıyorsa adaylar listelenir.
+    fn complete(&mut self, out: &mut dyn Write) {
+        let text = &self.line.as_str()[..self.cursor];
+        let start = text.rfind(' ').map_or(0, |i| i + 1);
+        let first_word = text[..start].trim().is_empty();
+        let word = &text[start..];
+        if !first_word && !word.starts_with('/') {
+            let _ = out.write_str("\x07");
+            return;
+        }
+        let (dir, partial) = match word.rfind('/') {
+            Some(slash) if !first_word => word.split_at(slash + 1),
+            _ => ("", word),
+        };
+
+        // İlk aday ve tüm adaylarla ortak önekinin uzunluğu
+        let mut matches = 0;
+        let mut first: (&'static str, bool) = ("", false);
+        let mut common = 0;
+        candidates(first_word, dir, &mut |name, is_dir| {
+            if !name.starts_with(partial) {
+                return;
+            }
+            if matches == 0 {
+                first = (name, is_dir);
+                common = name.len();
+            } else {
+                common = first.0.bytes().zip(name.bytes()).take(common).take_while(|(a, b)| a == b).count();
+            }
+            matches += 1;
+        });
+
+        let extension = &first.0[partial.len().min(common)..common];
+        match matches {
+            0 => {
+                let _ = out.write_str("\x07");
+            }
+            1 => {
+                let suffix: &[u8] = if first.1 { b"/" } else { b" " };
+                self.insert_completion(extension.as_bytes(), suffix, out);
+            }
+            _ if !extension.is_empty() => self.insert_completion(extension.as_bytes(), b"", out),
+            _ => self.list_candidates(first_word, out),
+        }
+    }
+
+    fn insert_completion(&mut self, extension: &[u8], suffix: &[u8], out: &mut dyn Write) {
+        let cursor = self.cursor;
+        let mut added = self.line.insert(cursor, extension);
+        added += self.line.insert(cursor + added, suffix);
+        self.cursor += added;
+        self.redraw_from(cursor, out);
+    }
+
+    fn list_candidates(&mut self, first_word: bool, out: &mut dyn Write) {
+        let text = &self.line.as_str()[..self.cursor];
+        let word = &text[text.rfind(' ').map_or(0, |i| i + 1)..];
+        let (dir, partial) = match word.rfind('/') {
+            Some(slash) if !first_word => word.split_at(slash + 1),
+            _ => ("", word),
+        };
+        let _ = out.write_str("\n");
+        candidates(first_word, dir, &mut |name, is_dir| {
+            if name.starts_with(partial) {
+                let _ = write!(out, "{}{}  ", name, if is_dir { "/" } else { "" });
+            }
+        });
+        let _ = write!(out, "\n{}", self.prompt);
+        self.redraw_from(0, out);
+    }
+}
+
+/// Tamamlama adaylarını sırayla `f(ad, dizin_mi)`'ye verir: ilk kelimede
+/// kayıtlı komutlar, aksi halde `dir` dizininin girdileri.
+fn candidates(first_word: bool, dir: &str, f: &mut dyn FnMut(&'static str, bool)) {
+    if first_word {
+        for cmd in crate::shell::commands() {
+            f(cmd.name, false);
+        }
+        return;
+    }
+    let path = if dir.len() > 1 { dir.trim_end_matches('/') } else { dir };
+    let mut index = 0;
+    while let Ok(Some(entry)) = crate::vfs::readdir(path, index) {
+        f(entry.name, entry.kind == crate::vfs::FileType::Directory);
+        index += 1;
+    }
+}
+
+fn move_left(out: &mut dyn Write, columns: usize) {
+    if columns > 0 {
+        let _ = write!(out, "\x1b[{}D", columns);
+    }
+}
+
+fn move_right(out: &mut dyn Write, columns: usize) {
+    if columns > 0 {
+        let _ = write!(out, "\x1b[{}C", columns);
+    }
+}
+
+// -----------------------------------------------------------------------------
+// KABUK KOMUTU
+// -----------------------------------------------------------------------------
+
+/// `history`: geçmişi eskiden yeniye numaralı listeler.
+fn history_command(_args: &[&str]) -> Result<(), KernelError> {
+    let len = history_len();
+    for age in (1..=len).rev() {
+        if let Some(line) = history_get(age) {
+            serial_println!("  {:>3}  {}", len - age + 1, line.as_str());
+        }
+    }
+    Ok(())
+}
+
+crate::shell_command!("history", "Komut geçmişini listeler", history_command);
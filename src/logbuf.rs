@@ -0,0 +1,476 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/logbuf.rs
+// Sıkıştırılmış çekirdek günlük halkası (`dmesg`).
+//
+// Tüm `klog!` iletileri (görev, kesme ve açılış akışı) bağlamadan ve konsol
+// hedefinden bağımsız olarak bellekte saklanır; `serial_println!` çıktısı
+// (kabuk yanıtları) halkaya girmez. Her satır zaman damgası ve seviye
+// harfiyle yazılır:
+//
+//   [   12.345678] W [NET] eth0: bağlantı koptu
+//
+// Satırlar önce `BLOCK_SIZE`'lık düz metin bloğunda birikir. Blok dolunca
+// mühürlenir (`sealed` kopyasına alınır), `lz4` ile sıkıştırılır ve
+// `STORE_SIZE`'lık depoya eklenir; depo dolunca en eski bloklar silinir.
+// Depodaki her blok 4 baytlık başlıkla saklanır: saklanan uzunluk (u16; üst
+// bit: sıkıştırma kazandırmadı, düz saklandı) ve açılmış uzunluk (u16).
+// Tipik günlük metni ~3 kat küçüldüğünden 16 KiB'lık depo ~48 KiB geçmiş
+// tutar; küçük bellekli kartlarda düz metin halkası bunun üçte biri olurdu.
+//
+// Okuma (`dmesg`, `/proc/dmesg`) blokları sırayla tek tek açar: kilit yalnızca
+// bir bloğun açılması süresince tutulur, konsola yazarken bırakılır. Okuma
+// sırasında silinen bloklar atlanır, eklenen satırlar gösterilmeyebilir.
+// Silinmiş bir bloğun devamı olan yarım ilk satır gösterilmez.
+//
+// Bloklar `&str` karakter sınırlarında bölünür; her blok kendi başına geçerli
+// UTF-8'dir. Halka `IrqSafeSpinlock` ile korunur, ama sıkıştırma kilidin
+// dışında yapılır: `pack` mühürlü bloğu kilit altında kendi tamponuna
+// kopyalar, kilidi bırakıp sıkıştırır ve sonucu yeniden kilit alarak ekler.
+// Kesmeler kapalıyken yalnızca kopyalar ve en eski bloğun silinmesi (depo
+// kaydırma, en fazla `STORE_SIZE`) yapılır. Sıkıştırıcı tek kopyadır; onu
+// başka bir çağıran kullanıyorsa veya kayıt kesme bağlamındaysa sıkıştırma
+// bir sonraki `record`'a ertelenir. Mühürlü blok hâlâ beklerken yeni blok
+// dolarsa bekleyen blok sıkıştırılmadan (düz) depoya alınır; böylece sıra
+// korunur ve yazma hiç beklemez. Panik yolu (`tail`) kilidi beklemez; kilit
+// tutuluyorsa günlük kuyruğu çökme kaydına eklenmez.
+//
+// Kabuk komutu: `dmesg [clear | stats]`; procfs: `/proc/dmesg`
+
+use core::cell::UnsafeCell;
+use core::fmt::{self, Write};
+use core::sync::atomic::{AtomicBool, Ordering};
+use crate::lz4::{self, Compressor};
+use crate::platformgeneric::spinlock::IrqSafeSpinlock;
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+use crate::sysctl::LogLevel;
+
+/// Düz metin bloğu; dolunca sıkıştırılıp depoya eklenir.
+pub const BLOCK_SIZE: usize = 1024;
+/// Sıkıştırılmış blok deposu.
+pub const STORE_SIZE: usize = 16 * 1024;
+
+/// Blok başlığı: saklanan uzunluk ve açılmış uzunluk (u16, küçük sonlu).
+const HEADER: usize = 4;
+/// Saklanan uzunluğun üst biti: blok düz saklandı.
+const STORED_RAW: u16 = 0x8000;
+
+crate::kernel_counter!(LINES, "logbuf.lines");
+crate::kernel_counter!(EVICTED, "logbuf.evicted");
+
+// -----------------------------------------------------------------------------
+// HALKA
+// -----------------------------------------------------------------------------
+
+struct Ring {
+    /// Yazılmakta olan son blok.
+    block: [u8; BLOCK_SIZE],
+    block_len: usize,
+    /// Dolmuş, sıkıştırılmayı bekleyen blok (`sealed_len == 0`: yok).
+    sealed: [u8; BLOCK_SIZE],
+    sealed_len: usize,
+    /// Her mühürlemede artar; `pack` eklerken bloğun değişmediğini bununla
+    /// anlar (`clear` sıfırlamaz).
+    sealed_id: u64,
+    /// Sıkıştırılmış bloklar, en eskisi başta.
+    store: [u8; STORE_SIZE],
+    used: usize,
+    blocks: usize,
+    /// En eski bloğun sıra numarası (şimdiye dek silinen blok sayısı).
+    first: u64,
+    /// Depodaki blokların açılmış toplam boyutu.
+    raw_bytes: usize,
+    /// Silinen blokların açılmış toplam boyutu.
+    evicted_bytes: u64,
+}
+
+struct RingCell {
+    lock: IrqSafeSpinlock,
+    ring: UnsafeCell<Ring>,
+}
+
+// Halkaya erişim `lock` ile korunur.
+unsafe impl Sync for RingCell {}
+
+static RING: RingCell = RingCell {
+    lock: IrqSafeSpinlock::new(),
+    ring: UnsafeCell::new(Ring {
+        block: [0; BLOCK_SIZE],
+        block_len: 0,
+        sealed: [0; BLOCK_SIZE],
+        sealed_len: 0,
+        sealed_id: 0,
+        store: [0; STORE_SIZE],
+        used: 0,
+        blocks: 0,
+        first: 0,
+        raw_bytes: 0,
+        evicted_bytes: 0,
+    }),
+};
+
+/// Sıkıştırıcı ve tamponları; `busy` bayrağını alan tek çağıran kullanır.
+struct Packer {
+    busy: AtomicBool,
+    state: UnsafeCell<PackState>,
+}
+
+struct PackState {
+    input: [u8; BLOCK_SIZE],
+    output: [u8; lz4::bound(BLOCK_SIZE)],
+    compressor: Compressor,
+}
+
+// Duruma yalnızca `busy` bayrağını alan erişir.
+unsafe impl Sync for Packer {}
+
+static PACKER: Packer = Packer {
+    busy: AtomicBool::new(false),
+    state: UnsafeCell::new(PackState {
+        input: [0; BLOCK_SIZE],
+        output: [0; lz4::bound(BLOCK_SIZE)],
+        compressor: Compressor::new(),
+    }),
+};
+
+fn with_ring<R>(f: impl FnOnce(&mut Ring) -> R) -> R {
+    let flags = RING.lock.lock();
+    // SAFETY: Kilit tutulduğu sürece halkaya tek erişen biziz.
+    let result = f(unsafe { &mut *RING.ring.get() });
+    RING.lock.unlock(flags);
+    result
+}
+
+/// Kilit alınamazsa `None` (panik yolu).
+fn try_with_ring<R>(f: impl FnOnce(&mut Ring) -> R) -> Option<R> {
+    let flags = RING.lock.try_lock()?;
+    // SAFETY: `with_ring` ile aynı.
+    let result = f(unsafe { &mut *RING.ring.get() });
+    RING.lock.unlock(flags);
+    Some(result)
+}
+
+impl Ring {
+    /// `at` konumundaki bloğun (saklanan uzunluk, düz mü, açılmış uzunluk).
+    fn header(&self, at: usize) -> (usize, bool, usize) {
+        let stored = u16::from_le_bytes([self.store[at], self.store[at + 1]]);
+        let raw = u16::from_le_bytes([self.store[at + 2], self.store[at + 3]]);
+        (usize::from(stored & !STORED_RAW), stored & STORED_RAW != 0, usize::from(raw))
+    }
+
+    fn evict_oldest(&mut self) {
+        let (len, _, raw) = self.header(0);
+        let size = HEADER + len;
+        self.store.copy_within(size..self.used, 0);
+        self.used -= size;
+        self.blocks -= 1;
+        self.first += 1;
+        self.raw_bytes -= raw;
+        self.evicted_bytes += raw as u64;
+        EVICTED.inc();
+    }
+
+    /// Depoya `len` baytlık bir blok yeri açar, başlığını yazar ve verinin
+    /// yazılacağı konumu döndürür; gerekirse en eski bloklar silinir.
+    fn append_slot(&mut self, len: usize, flag: u16, raw: usize) -> usize {
+        while self.used + HEADER + len > STORE_SIZE {
+            self.evict_oldest();
+        }
+        let at = self.used;
+        self.store[at..at + 2].copy_from_slice(&(len as u16 | flag).to_le_bytes());
+        self.store[at + 2..at + HEADER].copy_from_slice(&(raw as u16).to_le_bytes());
+        self.used += HEADER + len;
+        self.blocks += 1;
+        self.raw_bytes += raw;
+        at + HEADER
+    }
+
+    /// Dolan bloğu mühürler. Önceki mühürlü blok hâlâ bekliyorsa sıkıştırılmadan
+    /// depoya alınır.
+    fn seal(&mut self) {
+        if self.block_len == 0 {
+            return;
+        }
+        if self.sealed_len != 0 {
+            let len = self.sealed_len;
+            let at = self.append_slot(len, STORED_RAW, len);
+            self.store[at..at + len].copy_from_slice(&self.sealed[..len]);
+        }
+        self.sealed[..self.block_len].copy_from_slice(&self.block[..self.block_len]);
+        self.sealed_len = self.block_len;
+        self.sealed_id += 1;
+        self.block_len = 0;
+    }
+
+    /// Mühürlü bloğun numarası (varsa).
+    fn sealed_seq(&self) -> u64 {
+        self.first + self.blocks as u64
+    }
+
+    /// Yazılmakta olan bloğun numarası.
+    fn open_seq(&self) -> u64 {
+        self.sealed_seq() + u64::from(self.sealed_len != 0)
+    }
+
+    /// `seq` numaralı bloğu (silindiyse en eskisini) `text`'e açar; okunan
+    /// bloğun numarasını ve uzunluğunu döndürür. Bloklar bittiyse `None`.
+    fn read(&self, seq: u64, text: &mut [u8; BLOCK_SIZE]) -> Option<(u64, Result<usize, KernelError>)> {
+        let seq = seq.max(self.first);
+        if seq == self.open_seq() {
+            text[..self.block_len].copy_from_slice(&self.block[..self.block_len]);
+            return Some((seq, Ok(self.block_len)));
+        }
+        if seq > self.open_seq() {
+            return None;
+        }
+        if seq == self.sealed_seq() {
+            text[..self.sealed_len].copy_from_slice(&self.sealed[..self.sealed_len]);
+            return Some((seq, Ok(self.sealed_len)));
+        }
+        let mut at = 0;
+        for _ in self.first..seq {
+            at += HEADER + self.header(at).0;
+        }
+        let (len, stored_raw, raw) = self.header(at);
+        let data = &self.store[at + HEADER..at + HEADER + len];
+        let result = if stored_raw {
+            text[..len].copy_from_slice(data);
+            Ok(len)
+        } else {
+            lz4::decompress(data, text).and_then(|n| if n == raw { Ok(n) } else { Err(KernelError::InvalidArgument) })
+        };
+        Some((seq, result))
+    }
+
+    /// Halkayı boşaltır; numaralar ve silinme sayımı da sıfırlanır, böylece
+    /// sonraki ilk satır yarım sayılmaz.
+    fn clear(&mut self) {
+        self.block_len = 0;
+        self.sealed_len = 0;
+        self.used = 0;
+        self.blocks = 0;
+        self.first = 0;
+        self.raw_bytes = 0;
+        self.evicted_bytes = 0;
+    }
+}
+
+impl Write for Ring {
+    /// Bloğa ekler; blok dolunca karakter sınırında böler ve mühürler.
+    fn write_str(&mut self, mut s: &str) -> fmt::Result {
+        while !s.is_empty() {
+            if self.block_len == BLOCK_SIZE {
+                self.seal();
+            }
+            let mut n = s.len().min(BLOCK_SIZE - self.block_len);
+            while !s.is_char_boundary(n) {
+                n -= 1;
+            }
+            if n == 0 {
+                // Karakter sığmıyor; blok erken kapatılır
+                self.seal();
+                continue;
+            }
+            self.block[self.block_len..self.block_len + n].copy_from_slice(&s.as_bytes()[..n]);
+            self.block_len += n;
+            s = &s[n..];
+        }
+        Ok(())
+    }
+}
+
+// -----------------------------------------------------------------------------
+// YAZMA
+// -----------------------------------------------------------------------------
+
+fn level_letter(level: LogLevel) -> char {
+    match level {
+        LogLevel::Off => '-',
+        LogLevel::Error => 'E',
+        LogLevel::Warn => 'W',
+        LogLevel::Info => 'I',
+        LogLevel::Debug => 'D',
+        LogLevel::Trace => 'T',
+    }
+}
+
+/// `console::log` tarafından her `klog!` iletisi için çağrılır.
+pub fn record(level: LogLevel, args: fmt::Arguments) {
+    let now_us = crate::hrtimer::now_ns() / 1_000;
+    let sealed = with_ring(|ring| {
+        let _ = writeln!(ring, "[{:>5}.{:06}] {} {}", now_us / 1_000_000, now_us % 1_000_000, level_letter(level), args);
+        ring.sealed_len != 0
+    });
+    LINES.inc();
+    // Kesme işleyicisinde sıkıştırılmaz; blok bir sonraki kayıtta eklenir
+    if sealed && !crate::irqstack::in_interrupt() {
+        pack();
+    }
+}
+
+/// Mühürlü bloğu halka kilidinin dışında sıkıştırıp depoya ekler. Sıkıştırıcı
+/// başka bir çağırandaysa hemen döner; o çağıran yeni mühürlü bloğu da alır.
+fn pack() {
+    while !PACKER.busy.swap(true, Ordering::Acquire) {
+        // SAFETY: `busy` bayrağı bizde; durum başka kimse tarafından kullanılmaz.
+        let state = unsafe { &mut *PACKER.state.get() };
+        loop {
+            // Kilit altında yalnızca kopyalanır
+            let taken = with_ring(|ring| {
+                let len = ring.sealed_len;
+                state.input[..len].copy_from_slice(&ring.sealed[..len]);
+                (len != 0).then_some((ring.sealed_id, len))
+            });
+            let Some((id, raw)) = taken else { break };
+            let text = &state.input[..raw];
+            let (len, flag) = match state.compressor.compress(text, &mut state.output) {
+                Some(n) if n < raw => (n, 0),
+                _ => {
+                    state.output[..raw].copy_from_slice(text);
+                    (raw, STORED_RAW)
+                }
+            };
+            with_ring(|ring| {
+                // Bu arada düz saklandıysa veya halka temizlendiyse sonuç atılır
+                if ring.sealed_len != 0 && ring.sealed_id == id {
+                    let at = ring.append_slot(len, flag, raw);
+                    ring.store[at..at + len].copy_from_slice(&state.output[..len]);
+                    ring.sealed_len = 0;
+                }
+            });
+        }
+        PACKER.busy.store(false, Ordering::Release);
+        // Bayrak bırakılırken mühürlenen blok kaçırılmasın
+        if with_ring(|ring| ring.sealed_len == 0) {
+            break;
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// OKUMA
+// -----------------------------------------------------------------------------
+
+/// Blok metnini yazar. Önce `skip` bayt atlanır; atlanan bir şey olduysa
+/// veya `partial` ise ilk satır sonuna kadar da atlanır.
+fn emit(text: &[u8], skip: &mut usize, partial: &mut bool, out: &mut dyn Write) -> fmt::Result {
+    let n = (*skip).min(text.len());
+    *skip -= n;
+    let mut text = &text[n..];
+    *partial |= n > 0;
+    if *partial {
+        match text.iter().position(|&b| b == b'\n') {
+            Some(end) => {
+                text = &text[end + 1..];
+                *partial = false;
+            }
+            None => return Ok(()),
+        }
+    }
+    match core::str::from_utf8(text) {
+        Ok(s) => out.write_str(s),
+        Err(e) => out.write_str(core::str::from_utf8(&text[..e.valid_up_to()]).unwrap_or("")),
+    }
+}
+
+/// Halkanın tamamını en eskiden en yeniye yazar; kilit blok başına alınır.
+pub fn dump(out: &mut dyn Write) -> fmt::Result {
+    let mut text = [0u8; BLOCK_SIZE];
+    let (mut seq, mut skip) = (0, 0);
+    let mut partial = false;
+    while let Some((read, result)) = with_ring(|ring| ring.read(seq, &mut text)) {
+        // Silinmiş bloğun devamı yarım satırla başlar
+        partial |= read != seq;
+        match result {
+            Ok(len) => emit(&text[..len], &mut skip, &mut partial, out)?,
+            Err(_) => {
+                writeln!(out, "<blok {} bozuk>", read)?;
+                partial = true;
+            }
+        }
+        seq = read + 1;
+    }
+    Ok(())
+}
+
+/// Halkanın son `max` baytını yazar (ilk yarım satır atlanır). Kilidi
+/// beklemez; kilit tutuluyorsa hiçbir şey yazmadan `false` döner.
+pub fn tail(max: usize, out: &mut dyn Write) -> bool {
+    try_with_ring(|ring| {
+        let mut text = [0u8; BLOCK_SIZE];
+        let mut skip = (ring.raw_bytes + ring.sealed_len + ring.block_len).saturating_sub(max);
+        let mut partial = ring.first > 0;
+        let mut seq = ring.first;
+        while let Some((read, Ok(len))) = ring.read(seq, &mut text) {
+            if emit(&text[..len], &mut skip, &mut partial, out).is_err() {
+                break;
+            }
+            seq = read + 1;
+        }
+    })
+    .is_some()
+}
+
+/// Halka özeti.
+#[derive(Debug, Clone, Copy)]
+pub struct Stats {
+    /// Depodaki blokların açılmış ve sıkıştırılmış boyutu.
+    pub raw_bytes: usize,
+    pub stored_bytes: usize,
+    pub blocks: usize,
+    /// Henüz sıkıştırılmamış (yazılan ve mühürlü) bloklardaki bayt.
+    pub pending: usize,
+    pub evicted_bytes: u64,
+}
+
+pub fn stats() -> Stats {
+    with_ring(|ring| Stats {
+        raw_bytes: ring.raw_bytes,
+        stored_bytes: ring.used,
+        blocks: ring.blocks,
+        pending: ring.sealed_len + ring.block_len,
+        evicted_bytes: ring.evicted_bytes,
+    })
+}
+
+// -----------------------------------------------------------------------------
+// KABUK VE PROCFS
+// -----------------------------------------------------------------------------
+
+fn show_dmesg(out: &mut dyn Write) -> fmt::Result {
+    dump(out)
+}
+
+crate::proc_entry!("dmesg", show_dmesg);
+
+/// `dmesg [clear | stats]`
+fn dmesg_command(args: &[&str]) -> Result<(), KernelError> {
+    match args.get(1).copied() {
+        None => dump(&mut crate::shell::Console).map_err(|_| KernelError::GenericFailure),
+        Some("clear") => {
+            with_ring(Ring::clear);
+            Ok(())
+        }
+        Some("stats") => {
+            let stats = stats();
+            // Oran onda bir hassasiyetle
+            let ratio = (stats.raw_bytes * 10).checked_div(stats.stored_bytes).unwrap_or(0);
+            serial_println!(
+                "Depo: {} / {} bayt, {} blok ({} bayt açık), oran {}.{}x",
+                stats.stored_bytes,
+                STORE_SIZE,
+                stats.blocks,
+                stats.raw_bytes,
+                ratio / 10,
+                ratio % 10
+            );
+            serial_println!("Sıkıştırılmamış blok: {} / {} bayt", stats.pending, BLOCK_SIZE);
+            serial_println!("Satır: {}, silinen: {} blok ({} bayt)", LINES.get(), EVICTED.get(), stats.evicted_bytes);
+            Ok(())
+        }
+        Some(_) => Err(KernelError::InvalidArgument),
+    }
+}
+
+crate::shell_command!("dmesg", "Sıkıştırılmış çekirdek günlüğü (dmesg [clear | stats])", dmesg_command);
@@ -0,0 +1,220 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/lz4.rs
+// LZ4 blok biçimiyle hafif sıkıştırma (çerçevesiz, tek blok).
+//
+// Günlük halkası (`logbuf`) ve çökme kaydı (`reboot`) metni bununla
+// sıkıştırır; aynı satır başları ve tekrarlayan ileti gövdeleri olan çekirdek
+// günlüğü tipik olarak 2-4 kat küçülür. Biçim LZ4 blok biçimidir, böylece
+// dökülen ham bloklar konakta `lz4` araçlarıyla da açılabilir:
+//
+//   dizi   = belirteç, [ek değişmez uzunluğu], değişmezler,
+//            ofset (2 bayt, küçük sonlu), [ek eşleşme uzunluğu]
+//   belirteç = (değişmez uzunluğu << 4) | (eşleşme uzunluğu - 4); 15 ise
+//            uzunluk 255'lik ek baytlarla sürer
+//
+// Son dizi yalnızca değişmez içerir; son 5 bayt hep değişmezdir ve son
+// eşleşme bloğun bitiminden en az 12 bayt önce başlar (LZ4 kuralları).
+//
+// Sıkıştırıcı tek geçişlidir; 2 KiB'lık karma tablosu `Compressor`'ün
+// içindedir ve çağıranın durağan durumunda tutulur, böylece 8 KiB'lık görev
+// yığınlarında ve panik yolunda güvenle çağrılabilir. Sıkıştırma oranı için
+// değil, hız ve sabit bellek için seçilmiştir. Blok en fazla 64 KiB olabilir.
+//
+// Kabuk komutu: yok; procfs: yok
+
+use crate::platformgeneric::KernelError;
+
+/// Sıkıştırılabilecek en büyük blok.
+pub const MAX_INPUT: usize = u16::MAX as usize;
+
+const MIN_MATCH: usize = 4;
+const MAX_OFFSET: usize = u16::MAX as usize;
+/// Bloğun son bu kadar baytı değişmezdir.
+const LAST_LITERALS: usize = 5;
+/// Son eşleşme en geç bloğun sonundan bu kadar önce başlar.
+const MF_LIMIT: usize = 12;
+const HASH_BITS: u32 = 10;
+
+/// En kötü durumda (sıkıştırılamayan veri) çıktının boyutu.
+pub const fn bound(len: usize) -> usize {
+    len + len / 255 + 16
+}
+
+fn read_u32(data: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes([data[at], data[at + 1], data[at + 2], data[at + 3]])
+}
+
+fn hash(seq: u32) -> usize {
+    (seq.wrapping_mul(2_654_435_761) >> (32 - HASH_BITS)) as usize
+}
+
+// -----------------------------------------------------------------------------
+// SIKIŞTIRMA
+// -----------------------------------------------------------------------------
+
+struct Writer<'a> {
+    out: &'a mut [u8],
+    pos: usize,
+}
+
+impl Writer<'_> {
+    fn byte(&mut self, value: u8) -> Option<()> {
+        *self.out.get_mut(self.pos)? = value;
+        self.pos += 1;
+        Some(())
+    }
+
+    fn bytes(&mut self, data: &[u8]) -> Option<()> {
+        self.out.get_mut(self.pos..self.pos + data.len())?.copy_from_slice(data);
+        self.pos += data.len();
+        Some(())
+    }
+
+    /// 15 ve üzeri uzunluğun ek baytları.
+    fn length(&mut self, mut rest: usize) -> Option<()> {
+        while rest >= 255 {
+            self.byte(255)?;
+            rest -= 255;
+        }
+        self.byte(rest as u8)
+    }
+
+    /// Değişmezler ve (varsa) ardından gelen eşleşme.
+    fn sequence(&mut self, literals: &[u8], matched: Option<(usize, usize)>) -> Option<()> {
+        let lit = literals.len();
+        let extra = matched.map_or(0, |(_, len)| len - MIN_MATCH);
+        self.byte(((lit.min(15) as u8) << 4) | extra.min(15) as u8)?;
+        if lit >= 15 {
+            self.length(lit - 15)?;
+        }
+        self.bytes(literals)?;
+        if let Some((offset, _)) = matched {
+            self.bytes(&(offset as u16).to_le_bytes())?;
+            if extra >= 15 {
+                self.length(extra - 15)?;
+            }
+        }
+        Some(())
+    }
+}
+
+/// Sıkıştırıcı durumu (karma tablosu).
+pub struct Compressor {
+    table: [u16; 1 << HASH_BITS],
+}
+
+impl Compressor {
+    pub const fn new() -> Self {
+        Compressor { table: [0; 1 << HASH_BITS] }
+    }
+
+    /// `input`'u `out`'a sıkıştırır ve yazılan bayt sayısını döndürür. `out`
+    /// yetmezse veya girdi `MAX_INPUT`'tan büyükse `None`; `bound(len)`
+    /// baytlık çıktı her zaman yeter.
+    pub fn compress(&mut self, input: &[u8], out: &mut [u8]) -> Option<usize> {
+        if input.len() > MAX_INPUT {
+            return None;
+        }
+        // Önceki bloğun konumları bu blokta geçersizdir
+        self.table.fill(0);
+        let mut writer = Writer { out, pos: 0 };
+        let (mut anchor, mut i) = (0, 0);
+        if input.len() > MF_LIMIT {
+            let end = input.len() - LAST_LITERALS;
+            while i <= input.len() - MF_LIMIT {
+                let seq = read_u32(input, i);
+                let slot = &mut self.table[hash(seq)];
+                let candidate = usize::from(*slot);
+                *slot = i as u16;
+                if candidate < i && i - candidate <= MAX_OFFSET && read_u32(input, candidate) == seq {
+                    let mut len = MIN_MATCH;
+                    while i + len < end && input[candidate + len] == input[i + len] {
+                        len += 1;
+                    }
+                    writer.sequence(&input[anchor..i], Some((i - candidate, len)))?;
+                    i += len;
+                    anchor = i;
+                } else {
+                    i += 1;
+                }
+            }
+        }
+        writer.sequence(&input[anchor..], None)?;
+        Some(writer.pos)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// AÇMA
+// -----------------------------------------------------------------------------
+
+/// 15 ve üzeri uzunluğun ek baytlarını okur.
+fn read_length(input: &[u8], pos: &mut usize) -> Result<usize, KernelError> {
+    let mut total = 0;
+    loop {
+        let byte = *input.get(*pos).ok_or(KernelError::InvalidArgument)?;
+        *pos += 1;
+        total += usize::from(byte);
+        if byte != 255 {
+            return Ok(total);
+        }
+    }
+}
+
+/// Sıkıştırılmış bloğu `out`'a açar ve açılan bayt sayısını döndürür.
+///
+/// # Hatalar
+/// * `InvalidArgument`: Blok bozuk (kesik, geçersiz ofset).
+/// * `OutOfMemoryStatic`: `out` açılan veriye yetmiyor.
+pub fn decompress(input: &[u8], out: &mut [u8]) -> Result<usize, KernelError> {
+    let (mut ip, mut op) = (0, 0);
+    loop {
+        let token = *input.get(ip).ok_or(KernelError::InvalidArgument)?;
+        ip += 1;
+        let mut lit = usize::from(token >> 4);
+        if lit == 15 {
+            lit += read_length(input, &mut ip)?;
+        }
+        let literals = input.get(ip..ip + lit).ok_or(KernelError::InvalidArgument)?;
+        out.get_mut(op..op + lit).ok_or(KernelError::OutOfMemoryStatic)?.copy_from_slice(literals);
+        ip += lit;
+        op += lit;
+        if ip == input.len() {
+            return Ok(op);
+        }
+
+        let offset = match input.get(ip..ip + 2) {
+            Some(&[lo, hi]) => usize::from(u16::from_le_bytes([lo, hi])),
+            _ => return Err(KernelError::InvalidArgument),
+        };
+        ip += 2;
+        if offset == 0 || offset > op {
+            return Err(KernelError::InvalidArgument);
+        }
+        let mut len = usize::from(token & 15) + MIN_MATCH;
+        if token & 15 == 15 {
+            len += read_length(input, &mut ip)?;
+        }
+        if op + len > out.len() {
+            return Err(KernelError::OutOfMemoryStatic);
+        }
+        // Ofset uzunluktan kısa olabilir (yinelenen desen); bayt bayt kopyalanır
+        for k in op..op + len {
+            out[k] = out[k - offset];
+        }
+        op += len;
+    }
+}
+
+crate::ktest! {
+    fn lz4_round_trip() {
+        let text = b"[    1.000] <I> [NET] eth0: bagli\n[    2.000] <I> [NET] eth0: bagli\naaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+        let mut packed = [0u8; bound(128)];
+        let n = Compressor::new().compress(text, &mut packed).unwrap_or(usize::MAX);
+        crate::ktest_assert!(n < text.len());
+        let mut unpacked = [0u8; 128];
+        let m = decompress(&packed[..n], &mut unpacked)?;
+        crate::ktest_assert_eq!(&unpacked[..m], &text[..]);
+    }
+}
@@ -0,0 +1,358 @@
+#![allow(dead_code)]
+
+// src/memory/frame.rs
+// Fiziksel sayfa çerçevesi (frame) ayırıcısı: tek bir ardışık fiziksel bölge
+// üzerinde bit eşlemli (bitmap) tahsis.
+//
+// Platform başlatma kodu kullanılabilir RAM bölgesini `init` ile verir. `init`
+// çekirdek imajını ve initrd'yi kendisi ayırır, komut satırında `memtest`
+// varsa boş çerçeveleri ilk tahsisten önce sınatır; diğer dolu alanlar (MMIO
+// delikleri vb.) `reserve` ile işaretlenir. Ayırıcı
+// kullanım, en yüksek kullanım (high-water mark), başarısız istek sayısı ve
+// parçalanma (en uzun boş ardışık çerçeve dizisi) istatistiklerini tutar.
+//
+// Bellek baskısı: tahsis başarısız olursa kayıtlı geri kazanım kancası
+// (örn. sayfa önbelleği) çağrılır ve istek bir kez daha denenir.
+//
+// Karantina: bozuk bulunan çerçeveler (`memtest`) `quarantine` ile ayrı bir
+// bitmap'e yazılır ve kullanımda işaretlenir; bir daha tahsis edilmez ve
+// `free` ile bırakılamaz.
+//
+// Kabuk komutu: `meminfo` (bkz. `heap.rs`); procfs: `/proc/meminfo`
+
+use core::cell::UnsafeCell;
+use crate::platformgeneric::spinlock::IrqSafeSpinlock;
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+
+use crate::arch::mmu as arch_mmu;
+
+// -----------------------------------------------------------------------------
+// SABİTLER VE TİPLER
+// -----------------------------------------------------------------------------
+
+/// Çerçeve boyutu (mimarinin sayfa boyutu).
+pub const FRAME_SIZE: usize = arch_mmu::PAGE_SIZE;
+
+/// Yönetilebilecek en fazla çerçeve sayısı (4 KiB sayfalarda 128 MiB).
+pub const MAX_FRAMES: usize = 32 * 1024;
+
+const WORDS: usize = MAX_FRAMES / 64;
+
+/// Çerçeve ayırıcısı istatistikleri.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FrameStats {
+    /// Yönetilen toplam çerçeve.
+    pub total: usize,
+    /// Şu an kullanılan (tahsisli veya ayrılmış) çerçeve.
+    pub used: usize,
+    /// Açılıştan (veya son `reset_peak`'ten) bu yana en yüksek `used`.
+    pub peak: usize,
+    /// Karşılanamayan tahsis isteği sayısı.
+    pub failures: u64,
+    /// En uzun boş ardışık çerçeve dizisi.
+    pub largest_free_run: usize,
+    /// Boş çerçeve dizilerinin sayısı (1: hiç parçalanma yok).
+    pub free_runs: usize,
+    /// Karantinadaki (bozuk) çerçeve; `used`'a dahildir.
+    pub quarantined: usize,
+}
+
+/// Bellek baskısı kancası: en az `wanted` çerçeve serbest bırakmaya çalışır
+/// ve bıraktığı çerçeve sayısını döndürür. Ayırıcı kilidi tutulmadan çağrılır.
+pub type ReclaimHook = fn(wanted: usize) -> usize;
+
+struct FrameAllocator {
+    lock: IrqSafeSpinlock,
+    /// 1: çerçeve kullanımda.
+    bitmap: UnsafeCell<[u64; WORDS]>,
+    /// 1: çerçeve karantinada.
+    bad: UnsafeCell<[u64; WORDS]>,
+    base: UnsafeCell<usize>,
+    stats: UnsafeCell<FrameStats>,
+    reclaim_hook: UnsafeCell<Option<ReclaimHook>>,
+}
+
+// Tüm alanlara erişim `lock` ile korunur.
+unsafe impl Sync for FrameAllocator {}
+
+static FRAMES: FrameAllocator = FrameAllocator {
+    lock: IrqSafeSpinlock::new(),
+    bitmap: UnsafeCell::new([u64::MAX; WORDS]),
+    bad: UnsafeCell::new([0; WORDS]),
+    base: UnsafeCell::new(0),
+    stats: UnsafeCell::new(FrameStats {
+        total: 0,
+        used: 0,
+        peak: 0,
+        failures: 0,
+        largest_free_run: 0,
+        free_runs: 0,
+        quarantined: 0,
+    }),
+    reclaim_hook: UnsafeCell::new(None),
+};
+
+crate::kernel_counter!(FRAME_ALLOC_FAILURES, "frame.alloc_failures");
+crate::kernel_counter!(FRAME_RECLAIMED, "frame.reclaimed");
+
+/// Kilidi tutarak ayırıcı durumu üzerinde `f`'yi çalıştırır.
+fn with_frames<R>(f: impl FnOnce(&mut [u64; WORDS], &mut usize, &mut FrameStats) -> R) -> R {
+    let flags = FRAMES.lock.lock();
+    // SAFETY: Kilit tutulduğu sürece duruma tek erişen biziz.
+    let result = unsafe { f(&mut *FRAMES.bitmap.get(), &mut *FRAMES.base.get(), &mut *FRAMES.stats.get()) };
+    FRAMES.lock.unlock(flags);
+    result
+}
+
+/// Kilidi tutarak karantina bitmap'i üzerinde `f`'yi çalıştırır.
+fn with_bad<R>(f: impl FnOnce(&mut [u64; WORDS], &mut [u64; WORDS], usize, &mut FrameStats) -> R) -> R {
+    let flags = FRAMES.lock.lock();
+    // SAFETY: Kilit tutulduğu sürece duruma tek erişen biziz.
+    let result = unsafe {
+        f(&mut *FRAMES.bitmap.get(), &mut *FRAMES.bad.get(), *FRAMES.base.get(), &mut *FRAMES.stats.get())
+    };
+    FRAMES.lock.unlock(flags);
+    result
+}
+
+fn is_used(bitmap: &[u64; WORDS], frame: usize) -> bool {
+    bitmap[frame / 64] & (1 << (frame % 64)) != 0
+}
+
+fn set_used(bitmap: &mut [u64; WORDS], frame: usize, used: bool) {
+    if used {
+        bitmap[frame / 64] |= 1 << (frame % 64);
+    } else {
+        bitmap[frame / 64] &= !(1 << (frame % 64));
+    }
+}
+
+// -----------------------------------------------------------------------------
+// BAŞLATMA
+// -----------------------------------------------------------------------------
+
+/// Ayırıcıyı `[base, base + len)` fiziksel bölgesiyle başlatır. `MAX_FRAMES`'i
+/// aşan kısım kullanılmaz. `boot::init`'ten sonra ve ilk tahsisten önce
+/// çağrılmalıdır: böylece bellek sınaması hiçbir çerçeve dağıtılmadan yapılır.
+///
+/// # Hatalar
+/// * `InvalidArgument`: `base` çerçeve hizalı değil veya bölge bir çerçeveden küçük.
+pub fn init(base: usize, len: usize) -> Result<(), KernelError> {
+    if base % FRAME_SIZE != 0 || len < FRAME_SIZE {
+        return Err(KernelError::InvalidArgument);
+    }
+    let total = (len / FRAME_SIZE).min(MAX_FRAMES);
+
+    with_bad(|bitmap, bad, _, _| {
+        bitmap.fill(u64::MAX);
+        bad.fill(0);
+    });
+    with_frames(|bitmap, region_base, stats| {
+        (0..total).for_each(|frame| set_used(bitmap, frame, false));
+        *region_base = base;
+        *stats = FrameStats { total, ..FrameStats::default() };
+    });
+
+    serial_println!("[FRAME] {:#x}: {} çerçeve ({} KiB)", base, total, total * FRAME_SIZE / 1024);
+
+    // Sınama yalnızca boş çerçevelere dokunur; imaj ve initrd önce ayrılmalıdır
+    crate::layout::reserve_image();
+    crate::initrd::reserve_initrd();
+    if crate::boot::info().has_arg("memtest") {
+        crate::memory::memtest::run();
+    }
+    Ok(())
+}
+
+/// Bellek baskısında çağrılacak geri kazanım kancasını kaydeder.
+pub fn set_reclaim_hook(hook: ReclaimHook) {
+    let flags = FRAMES.lock.lock();
+    // SAFETY: Kilit tutuluyor.
+    unsafe { *FRAMES.reclaim_hook.get() = Some(hook) };
+    FRAMES.lock.unlock(flags);
+}
+
+/// Fiziksel aralığı kullanımda olarak işaretler (çekirdek imajı, initrd, MMIO delikleri).
+pub fn reserve(addr: usize, len: usize) {
+    with_frames(|bitmap, base, stats| {
+        let first = addr.saturating_sub(*base) / FRAME_SIZE;
+        let last = (addr + len).saturating_sub(*base).div_ceil(FRAME_SIZE).min(stats.total);
+        for frame in first..last {
+            if !is_used(bitmap, frame) {
+                set_used(bitmap, frame, true);
+                stats.used += 1;
+            }
+        }
+        stats.peak = stats.peak.max(stats.used);
+    });
+}
+
+// -----------------------------------------------------------------------------
+// TAHSİS
+// -----------------------------------------------------------------------------
+
+/// Tek bir çerçeve tahsis eder ve fiziksel adresini döndürür.
+pub fn alloc() -> Result<usize, KernelError> {
+    alloc_contiguous(1)
+}
+
+/// `count` ardışık çerçeve tahsis eder (DMA arabellekleri için). İlk uyan dizi
+/// seçilir; yer yoksa geri kazanım kancası çağrılıp bir kez daha denenir.
+///
+/// # Hatalar
+/// * `InvalidArgument`: `count` sıfır.
+/// * `OutOfMemoryStatic`: Yeterince uzun boş dizi yok.
+pub fn alloc_contiguous(count: usize) -> Result<usize, KernelError> {
+    if count == 0 {
+        return Err(KernelError::InvalidArgument);
+    }
+
+    let mut result = try_alloc(count);
+    if result.is_err() {
+        let flags = FRAMES.lock.lock();
+        // SAFETY: Kilit tutuluyor.
+        let hook = unsafe { *FRAMES.reclaim_hook.get() };
+        FRAMES.lock.unlock(flags);
+        if let Some(reclaimed) = hook.map(|hook| hook(count)).filter(|&n| n > 0) {
+            FRAME_RECLAIMED.add(reclaimed as u64);
+            result = try_alloc(count);
+        }
+    }
+
+    if result.is_err() {
+        with_frames(|_, _, stats| stats.failures += 1);
+        FRAME_ALLOC_FAILURES.inc();
+    }
+    result
+}
+
+fn try_alloc(count: usize) -> Result<usize, KernelError> {
+    with_frames(|bitmap, base, stats| {
+        let mut run_start = 0;
+        let mut run_len = 0;
+        for frame in 0..stats.total {
+            if is_used(bitmap, frame) {
+                run_len = 0;
+                continue;
+            }
+            if run_len == 0 {
+                run_start = frame;
+            }
+            run_len += 1;
+            if run_len == count {
+                (run_start..run_start + count).for_each(|f| set_used(bitmap, f, true));
+                stats.used += count;
+                stats.peak = stats.peak.max(stats.used);
+                return Ok(*base + run_start * FRAME_SIZE);
+            }
+        }
+        Err(KernelError::OutOfMemoryStatic)
+    })
+}
+
+/// `alloc_contiguous` ile alınan `count` çerçeveyi serbest bırakır.
+///
+/// # Hatalar
+/// * `InvalidArgument`: Adres hizasız, bölge dışında, çerçevelerden biri
+///   zaten boş ya da karantinada.
+pub fn free(addr: usize, count: usize) -> Result<(), KernelError> {
+    with_bad(|bitmap, bad, base, stats| {
+        if addr < base || (addr - base) % FRAME_SIZE != 0 {
+            return Err(KernelError::InvalidArgument);
+        }
+        let first = (addr - base) / FRAME_SIZE;
+        if first + count > stats.total || !(first..first + count).all(|f| is_used(bitmap, f) && !is_used(bad, f)) {
+            return Err(KernelError::InvalidArgument);
+        }
+        (first..first + count).for_each(|f| set_used(bitmap, f, false));
+        stats.used -= count;
+        Ok(())
+    })
+}
+
+// -----------------------------------------------------------------------------
+// KARANTİNA
+// -----------------------------------------------------------------------------
+
+/// Yönetilen bölgenin başlangıcı ve çerçeve sayısı.
+pub fn region() -> (usize, usize) {
+    with_frames(|_, base, stats| (*base, stats.total))
+}
+
+/// Çerçeve boşsa kullanımda işaretler ve `true` döner (`memtest` çerçeveyi
+/// sınarken başkasına verilmesin diye). `free` ile bırakılır.
+pub fn claim(addr: usize) -> bool {
+    with_frames(|bitmap, base, stats| {
+        let Some(frame) = addr.checked_sub(*base).map(|off| off / FRAME_SIZE).filter(|&f| f < stats.total) else {
+            return false;
+        };
+        if is_used(bitmap, frame) {
+            return false;
+        }
+        set_used(bitmap, frame, true);
+        stats.used += 1;
+        stats.peak = stats.peak.max(stats.used);
+        true
+    })
+}
+
+/// Çerçeveyi kalıcı olarak ayırıcıdan çıkarır. Boş veya `claim` ile alınmış
+/// olabilir; ikinci çağrı bir şey yapmaz.
+///
+/// # Hatalar
+/// * `InvalidArgument`: Adres bölge dışında.
+pub fn quarantine(addr: usize) -> Result<(), KernelError> {
+    with_bad(|bitmap, bad, base, stats| {
+        let frame = addr.checked_sub(base).map(|off| off / FRAME_SIZE).filter(|&f| f < stats.total);
+        let frame = frame.ok_or(KernelError::InvalidArgument)?;
+        if is_used(bad, frame) {
+            return Ok(());
+        }
+        set_used(bad, frame, true);
+        if !is_used(bitmap, frame) {
+            set_used(bitmap, frame, true);
+            stats.used += 1;
+        }
+        stats.quarantined += 1;
+        Ok(())
+    })
+}
+
+/// Karantinadaki çerçevelerin adresleri (artan sırada, en fazla `out.len()`);
+/// yazılan sayıyı döndürür.
+pub fn quarantined(out: &mut [usize]) -> usize {
+    with_bad(|_, bad, base, stats| {
+        let frames = (0..stats.total).filter(|&f| is_used(bad, f));
+        out.iter_mut().zip(frames).map(|(slot, f)| *slot = base + f * FRAME_SIZE).count()
+    })
+}
+
+// -----------------------------------------------------------------------------
+// İSTATİSTİKLER
+// -----------------------------------------------------------------------------
+
+/// Güncel istatistikler. Parçalanma ölçüleri bitmap taranarak hesaplanır.
+pub fn stats() -> FrameStats {
+    with_frames(|bitmap, _, stats| {
+        let mut snapshot = *stats;
+        let mut run = 0;
+        for frame in 0..stats.total {
+            if is_used(bitmap, frame) {
+                run = 0;
+            } else {
+                if run == 0 {
+                    snapshot.free_runs += 1;
+                }
+                run += 1;
+                snapshot.largest_free_run = snapshot.largest_free_run.max(run);
+            }
+        }
+        snapshot
+    })
+}
+
+/// En yüksek kullanım işaretini güncel kullanıma çeker.
+pub fn reset_peak() {
+    with_frames(|_, _, stats| stats.peak = stats.used);
+}
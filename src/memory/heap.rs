@@ -0,0 +1,307 @@
+#![allow(dead_code)]
+
+// src/memory/heap.rs
+// Çekirdek yığın (heap) ayırıcısı: derleme zamanında boyutu sabit bir arena
+// üzerinde adrese göre sıralı, birleştirmeli (coalescing) ilk uyan boş liste.
+//
+// Sabit bellekli gömülü kurulumlarda kapasite planlaması için ayırıcı anlık ve
+// en yüksek kullanımı, başarısız istekleri ve parçalanmayı (en büyük boş blok /
+// toplam boş alan) raporlar. `alloc` crate'i kullanılmaz; çağıranlar
+// `allocate` / `deallocate` fonksiyonlarını aynı `Layout` ile çağırır.
+//
+// `kasan` özelliğiyle derlenince tahsisler koruma bölgesiyle büyütülür ve
+// serbest bırakılan bloklar gölge bellek denetiminden geçer (`kasan.rs`).
+//
+// Kabuk komutu: `meminfo [reset]`; procfs: `/proc/meminfo`
+
+use core::alloc::Layout;
+use core::cell::UnsafeCell;
+use core::ptr::{self, NonNull};
+use crate::platformgeneric::spinlock::Spinlock;
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// SABİTLER VE TİPLER
+// -----------------------------------------------------------------------------
+
+/// Arena boyutu (bayt).
+pub const HEAP_SIZE: usize = 256 * 1024;
+
+/// Tüm blokların hizalaması ve boyut birimi.
+const MIN_ALIGN: usize = 16;
+
+/// Boş blok başlığı; boş alanın ilk baytlarında tutulur.
+struct FreeBlock {
+    size: usize,
+    next: *mut FreeBlock,
+}
+
+const MIN_BLOCK: usize = core::mem::size_of::<FreeBlock>();
+const _: () = assert!(MIN_BLOCK <= MIN_ALIGN);
+
+/// Yığın ayırıcısı istatistikleri.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HeapStats {
+    /// Arena boyutu.
+    pub total: usize,
+    /// Tahsisli bayt (hizalama yuvarlaması dahil).
+    pub used: usize,
+    /// Açılıştan (veya son `reset_peak`'ten) bu yana en yüksek `used`.
+    pub peak: usize,
+    /// Başarılı tahsis sayısı.
+    pub allocs: u64,
+    /// Serbest bırakma sayısı.
+    pub frees: u64,
+    /// Karşılanamayan tahsis isteği sayısı.
+    pub failures: u64,
+    /// En büyük boş blok (tek seferde tahsis edilebilecek en büyük alan).
+    pub largest_free: usize,
+    /// Boş blok sayısı.
+    pub free_blocks: usize,
+}
+
+impl HeapStats {
+    /// Dış parçalanma yüzdesi: 0 = tüm boş alan tek blokta.
+    pub fn fragmentation_percent(&self) -> usize {
+        let free = self.total - self.used;
+        if free == 0 { 0 } else { 100 - self.largest_free * 100 / free }
+    }
+}
+
+#[repr(C, align(16))]
+struct Arena([u8; HEAP_SIZE]);
+
+struct Heap {
+    lock: Spinlock,
+    arena: UnsafeCell<Arena>,
+    head: UnsafeCell<*mut FreeBlock>,
+    initialized: UnsafeCell<bool>,
+    stats: UnsafeCell<HeapStats>,
+}
+
+// Tüm alanlara erişim `lock` ile korunur.
+unsafe impl Sync for Heap {}
+
+static HEAP: Heap = Heap {
+    lock: Spinlock::new(),
+    arena: UnsafeCell::new(Arena([0; HEAP_SIZE])),
+    head: UnsafeCell::new(ptr::null_mut()),
+    initialized: UnsafeCell::new(false),
+    stats: UnsafeCell::new(HeapStats {
+        total: HEAP_SIZE,
+        used: 0,
+        peak: 0,
+        allocs: 0,
+        frees: 0,
+        failures: 0,
+        largest_free: 0,
+        free_blocks: 0,
+    }),
+};
+
+crate::kernel_counter!(HEAP_ALLOC_FAILURES, "heap.alloc_failures");
+
+/// Kilidi tutarak `f`'yi çalıştırır; ilk çağrıda arenayı tek boş blok olarak kurar.
+fn with_heap<R>(f: impl FnOnce(&mut *mut FreeBlock, &mut HeapStats) -> R) -> R {
+    HEAP.lock.lock();
+    // SAFETY: Kilit tutulduğu sürece duruma tek erişen biziz.
+    let result = unsafe {
+        let head = &mut *HEAP.head.get();
+        if !*HEAP.initialized.get() {
+            let block = HEAP.arena.get() as *mut FreeBlock;
+            block.write(FreeBlock { size: HEAP_SIZE, next: ptr::null_mut() });
+            *head = block;
+            #[cfg(feature = "kasan")]
+            crate::memory::kasan::init(block as usize);
+            *HEAP.initialized.get() = true;
+        }
+        f(head, &mut *HEAP.stats.get())
+    };
+    HEAP.lock.unlock();
+    result
+}
+
+fn block_size(layout: Layout) -> usize {
+    #[cfg(feature = "kasan")]
+    let size = crate::memory::kasan::inflate(layout.size());
+    #[cfg(not(feature = "kasan"))]
+    let size = layout.size();
+    size.max(MIN_BLOCK).next_multiple_of(MIN_ALIGN)
+}
+
+/// `[addr, addr + size)` alanını adres sıralı listeye ekler ve komşularla birleştirir.
+///
+/// # Safety
+/// Alan arena içinde, `MIN_ALIGN` hizalı ve hiçbir boş blokla örtüşmemelidir.
+unsafe fn insert_free(head: &mut *mut FreeBlock, addr: usize, size: usize) {
+    let mut prev: *mut FreeBlock = ptr::null_mut();
+    let mut cur = *head;
+    while !cur.is_null() && (cur as usize) < addr {
+        prev = cur;
+        cur = (*cur).next;
+    }
+
+    let block = addr as *mut FreeBlock;
+    block.write(FreeBlock { size, next: cur });
+
+    // Sonraki blokla birleştir
+    if !cur.is_null() && addr + size == cur as usize {
+        (*block).size += (*cur).size;
+        (*block).next = (*cur).next;
+    }
+
+    // Önceki blokla birleştir
+    if prev.is_null() {
+        *head = block;
+    } else if prev as usize + (*prev).size == addr {
+        (*prev).size += (*block).size;
+        (*prev).next = (*block).next;
+    } else {
+        (*prev).next = block;
+    }
+}
+
+// -----------------------------------------------------------------------------
+// TAHSİS
+// -----------------------------------------------------------------------------
+
+/// `layout` için bellek tahsis eder.
+///
+/// # Hatalar
+/// * `InvalidArgument`: Boyut sıfır.
+/// * `OutOfMemoryStatic`: Yeterince büyük boş blok yok.
+pub fn allocate(layout: Layout) -> Result<NonNull<u8>, KernelError> {
+    if layout.size() == 0 {
+        return Err(KernelError::InvalidArgument);
+    }
+    let size = block_size(layout);
+    let align = layout.align().max(MIN_ALIGN);
+
+    let result = with_heap(|head, stats| unsafe {
+        let mut prev: *mut FreeBlock = ptr::null_mut();
+        let mut cur = *head;
+        while !cur.is_null() {
+            let start = cur as usize;
+            let end = start + (*cur).size;
+            let alloc_start = start.next_multiple_of(align);
+            let alloc_end = alloc_start + size;
+
+            if alloc_end <= end {
+                // Bloğu listeden çıkar; ön ve arka artıkları geri ekle
+                let next = (*cur).next;
+                if prev.is_null() { *head = next } else { (*prev).next = next }
+                if alloc_start > start {
+                    insert_free(head, start, alloc_start - start);
+                }
+                if end > alloc_end {
+                    insert_free(head, alloc_end, end - alloc_end);
+                }
+
+                stats.used += size;
+                stats.peak = stats.peak.max(stats.used);
+                stats.allocs += 1;
+                #[cfg(feature = "kasan")]
+                crate::memory::kasan::on_alloc(alloc_start, layout.size(), size);
+                return Ok(NonNull::new_unchecked(alloc_start as *mut u8));
+            }
+            prev = cur;
+            cur = (*cur).next;
+        }
+        stats.failures += 1;
+        Err(KernelError::OutOfMemoryStatic)
+    });
+
+    if result.is_err() {
+        HEAP_ALLOC_FAILURES.inc();
+    }
+    result
+}
+
+/// `allocate` ile alınan belleği serbest bırakır.
+///
+/// # Safety
+/// `ptr` bu ayırıcıdan aynı `layout` ile alınmış olmalı ve bir kez serbest bırakılmalıdır.
+pub unsafe fn deallocate(ptr: NonNull<u8>, layout: Layout) {
+    let size = block_size(layout);
+    with_heap(|head, stats| {
+        stats.frees += 1;
+        // KASAN: blok karantinaya girer; boş listeye en eski karantina bloğu döner
+        #[cfg(feature = "kasan")]
+        let Some((addr, size)) = crate::memory::kasan::on_free(ptr.as_ptr() as usize, layout.size(), size) else {
+            return;
+        };
+        #[cfg(not(feature = "kasan"))]
+        let addr = ptr.as_ptr() as usize;
+        insert_free(head, addr, size);
+        stats.used -= size;
+    });
+}
+
+// -----------------------------------------------------------------------------
+// İSTATİSTİKLER
+// -----------------------------------------------------------------------------
+
+/// Güncel istatistikler. Parçalanma ölçüleri boş liste dolaşılarak hesaplanır.
+pub fn stats() -> HeapStats {
+    with_heap(|head, stats| {
+        let mut snapshot = *stats;
+        let mut cur = *head;
+        while !cur.is_null() {
+            // SAFETY: Liste yalnızca arena içindeki geçerli boş blokları içerir.
+            unsafe {
+                snapshot.largest_free = snapshot.largest_free.max((*cur).size);
+                snapshot.free_blocks += 1;
+                cur = (*cur).next;
+            }
+        }
+        snapshot
+    })
+}
+
+/// En yüksek kullanım işaretini güncel kullanıma çeker.
+pub fn reset_peak() {
+    with_heap(|_, stats| stats.peak = stats.used);
+}
+
+// -----------------------------------------------------------------------------
+// KABUK KOMUTU
+// -----------------------------------------------------------------------------
+
+fn meminfo_command(args: &[&str]) -> Result<(), KernelError> {
+    if args.get(1) == Some(&"reset") {
+        reset_peak();
+        crate::memory::frame::reset_peak();
+        crate::memory::memory::TaskStackAllocator::reset_peak();
+        return Ok(());
+    }
+
+    let heap = stats();
+    serial_println!(
+        "Heap:   {} / {} B kullanımda (tepe {} B) | tahsis {} serbest {} başarısız {}",
+        heap.used, heap.total, heap.peak, heap.allocs, heap.frees, heap.failures
+    );
+    serial_println!(
+        "        {} boş blok, en büyük {} B, parçalanma %{}",
+        heap.free_blocks, heap.largest_free, heap.fragmentation_percent()
+    );
+
+    let frames = crate::memory::frame::stats();
+    serial_println!(
+        "Frame:  {} / {} çerçeve kullanımda (tepe {}) | başarısız {}",
+        frames.used, frames.total, frames.peak, frames.failures
+    );
+    serial_println!(
+        "        {} boş dizi, en uzun {} çerçeve, karantinada {}",
+        frames.free_runs, frames.largest_free_run, frames.quarantined
+    );
+
+    let stacks = crate::memory::memory::TaskStackAllocator::stats();
+    serial_println!(
+        "Yığın:  {} / {} görev yığını kullanımda (tepe {}) | başarısız {}",
+        stacks.used, stacks.total, stacks.peak, stacks.failures
+    );
+    Ok(())
+}
+
+crate::shell_command!("meminfo", "Bellek ayırıcı istatistikleri (meminfo [reset])", meminfo_command);
@@ -0,0 +1,264 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+#![cfg(feature = "kasan")]
+
+// src/memory/kasan.rs
+// KASAN-lite: çekirdek yığını (heap) için gölge bellekli hata ayıklama kipi.
+//
+// `kasan` özelliğiyle derlenince etkinleşir; normal derlemede `heap.rs` bu
+// modülü hiç çağırmaz. Arenanın her 8 baytı için bir gölge baytı tutulur:
+//
+//   0        : 8 baytın tamamı erişilebilir
+//   1..=7    : yalnızca ilk N bayt erişilebilir (tahsisin son parçası)
+//   REDZONE  : tahsisin arkasındaki koruma bölgesi
+//   FREED    : serbest bırakılmış, karantinada
+//   UNMAPPED : hiç tahsis edilmemiş veya karantinadan çıkmış boş alan
+//
+// Derleyici araçlaması olmadığından her erişim denetlenemez; bunun yerine:
+//
+// * Tahsis, isteğe `REDZONE_SIZE` bayt ekler ve koruma bölgesini bilinen bir
+//   desenle doldurur. Serbest bırakmada desen bozulmuşsa taşma (overflow)
+//   raporlanır.
+// * Serbest bırakılan blok desenle doldurulup `QUARANTINE_SLOTS` girişlik
+//   karantinaya alınır; boş listeye ancak karantinadan çıkınca döner. Çıkışta
+//   desen bozulmuşsa serbest bırakma sonrası yazma (use-after-free)
+//   raporlanır. Karantinadaki bloklar `meminfo`da kullanımda görünür.
+// * Çift ve geçersiz serbest bırakma gölgeden anında yakalanır.
+// * Sürücüler ve kopyalama yardımcıları `check` ile erişimi açıkça
+//   denetleyebilir.
+//
+// Tüm değiştiren çağrılar yığın kilidi altında yapılır. Rapor sonrası
+// `kasan.panic` açıksa çekirdek durdurulur.
+//
+// Kabuk komutu: `kasan`
+
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use crate::memory::heap::HEAP_SIZE;
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+use crate::sysctl::Flag;
+
+// -----------------------------------------------------------------------------
+// SABİTLER VE GÖLGE BELLEK
+// -----------------------------------------------------------------------------
+
+/// Gölge baytı başına arena baytı.
+const GRANULE: usize = 8;
+const SHADOW_LEN: usize = HEAP_SIZE / GRANULE;
+
+const REDZONE: u8 = 0xFA;
+const FREED: u8 = 0xFB;
+const UNMAPPED: u8 = 0xFC;
+
+/// Koruma bölgesi ve serbest blok doldurma desenleri.
+const REDZONE_FILL: u8 = 0xCC;
+const FREE_FILL: u8 = 0xDD;
+
+/// Her tahsise eklenen koruma bölgesi (bayt).
+pub const REDZONE_SIZE: usize = 16;
+
+/// Karantinada bekletilen en fazla blok.
+pub const QUARANTINE_SLOTS: usize = 64;
+
+struct Shadow {
+    bytes: UnsafeCell<[u8; SHADOW_LEN]>,
+    /// Karantinadaki bloklar (adres, blok boyu) ve bir sonraki yazma yuvası.
+    quarantine: UnsafeCell<[Option<(usize, usize)>; QUARANTINE_SLOTS]>,
+    next_slot: UnsafeCell<usize>,
+}
+
+// Değiştiren erişimler yığın kilidi altında yapılır; `check` yalnızca okur.
+unsafe impl Sync for Shadow {}
+
+static SHADOW: Shadow = Shadow {
+    bytes: UnsafeCell::new([UNMAPPED; SHADOW_LEN]),
+    quarantine: UnsafeCell::new([None; QUARANTINE_SLOTS]),
+    next_slot: UnsafeCell::new(0),
+};
+
+crate::sysctl!(PANIC_ON_REPORT: Flag = Flag::new(true),
+    "kasan.panic", "KASAN raporundan sonra çekirdeği durdurur");
+
+crate::kernel_counter!(REPORTS, "kasan.reports");
+
+/// Arena başlangıcı; `heap` ilk kurulumda bildirir.
+static ARENA_BASE: AtomicUsize = AtomicUsize::new(0);
+
+/// Hata türü.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bug {
+    /// Koruma bölgesine yazılmış (tahsis sonrası taşma).
+    HeapOverflow,
+    /// Serbest bırakılmış bloğa yazılmış.
+    UseAfterFree,
+    /// Aynı blok iki kez serbest bırakılmış.
+    DoubleFree,
+    /// Tahsis başlangıcı olmayan adres serbest bırakılmış.
+    InvalidFree,
+    /// `check` ile denetlenen erişim geçersiz bölgeye değiyor.
+    InvalidAccess,
+}
+
+// -----------------------------------------------------------------------------
+// YARDIMCILAR
+// -----------------------------------------------------------------------------
+
+/// # Safety
+/// Çağıran yığın kilidini tutmalıdır (veya yalnızca okumalıdır).
+unsafe fn shadow() -> &'static mut [u8; SHADOW_LEN] {
+    &mut *SHADOW.bytes.get()
+}
+
+fn granule_of(addr: usize) -> Option<usize> {
+    let base = ARENA_BASE.load(Ordering::Relaxed);
+    addr.checked_sub(base).filter(|&off| base != 0 && off < HEAP_SIZE).map(|off| off / GRANULE)
+}
+
+/// `[addr, addr + len)` aralığının gölgesini `value` yapar (8 bayt hizalı).
+unsafe fn poison(addr: usize, len: usize, value: u8) {
+    let (Some(first), Some(last)) = (granule_of(addr), granule_of(addr + len - 1)) else { return };
+    shadow()[first..=last].fill(value);
+}
+
+/// `[addr, addr + size)` erişilebilir yapar; son parçada kısmi değer yazılır.
+unsafe fn unpoison(addr: usize, size: usize) {
+    let Some(first) = granule_of(addr) else { return };
+    let full = size / GRANULE;
+    shadow()[first..first + full].fill(0);
+    if !size.is_multiple_of(GRANULE) {
+        shadow()[first + full] = (size % GRANULE) as u8;
+    }
+}
+
+fn report(bug: Bug, addr: usize, size: usize, culprit: usize) {
+    REPORTS.inc();
+    serial_println!("[KASAN] ==================================================");
+    serial_println!("[KASAN] {:?}: blok {:#x} ({} bayt), bozulan adres {:#x}", bug, addr, size, culprit);
+    if let Some(g) = granule_of(culprit) {
+        let lo = g.saturating_sub(4);
+        let hi = (g + 4).min(SHADOW_LEN - 1);
+        crate::serial_print!("[KASAN] gölge:");
+        // SAFETY: Yalnızca okuma.
+        for (i, value) in unsafe { shadow() }[lo..=hi].iter().enumerate() {
+            let mark = if lo + i == g { '>' } else { ' ' };
+            crate::serial_print!("{}{:02x}", mark, value);
+        }
+        serial_println!();
+    }
+    if PANIC_ON_REPORT.get() {
+        panic!("KASAN: {:?} @ {:#x}", bug, culprit);
+    }
+}
+
+/// Desenden sapan ilk baytın adresi.
+unsafe fn first_mismatch(addr: usize, len: usize, fill: u8) -> Option<usize> {
+    (0..len).map(|i| addr + i).find(|&a| (a as *const u8).read_volatile() != fill)
+}
+
+// -----------------------------------------------------------------------------
+// AYIRICI KANCALARI (`heap.rs`, yığın kilidi altında)
+// -----------------------------------------------------------------------------
+
+/// Arenayı bildirir; tamamı tahsis edilmemiş olarak işaretlenir.
+pub unsafe fn init(arena: usize) {
+    ARENA_BASE.store(arena, Ordering::Relaxed);
+    shadow().fill(UNMAPPED);
+}
+
+/// Tahsis isteğine koruma bölgesini ekler.
+pub fn inflate(size: usize) -> usize {
+    size + REDZONE_SIZE
+}
+
+/// Yeni tahsis: istenen bölüm açılır, arkası koruma bölgesi yapılır.
+///
+/// # Safety
+/// `[addr, addr + block)` ayırıcının az önce verdiği blok olmalıdır.
+pub unsafe fn on_alloc(addr: usize, size: usize, block: usize) {
+    poison(addr, block, REDZONE);
+    unpoison(addr, size);
+    core::ptr::write_bytes((addr + size) as *mut u8, REDZONE_FILL, block - size);
+}
+
+/// Serbest bırakma: blok denetlenir, zehirlenir ve karantinaya alınır.
+/// Boş listeye gerçekten dönecek (karantinadan çıkan) blok döndürülür;
+/// hata bulunursa blok hiç serbest bırakılmaz (`None`).
+///
+/// # Safety
+/// `addr` arena içinde olmalıdır; `size` istenen, `block` gerçek boyuttur.
+pub unsafe fn on_free(addr: usize, size: usize, block: usize) -> Option<(usize, usize)> {
+    let Some(first) = granule_of(addr) else {
+        report(Bug::InvalidFree, addr, size, addr);
+        return None;
+    };
+    match shadow()[first] {
+        FREED => {
+            report(Bug::DoubleFree, addr, size, addr);
+            return None;
+        }
+        REDZONE | UNMAPPED => {
+            report(Bug::InvalidFree, addr, size, addr);
+            return None;
+        }
+        _ => {}
+    }
+    if let Some(bad) = first_mismatch(addr + size, block - size, REDZONE_FILL) {
+        report(Bug::HeapOverflow, addr, size, bad);
+    }
+
+    poison(addr, block, FREED);
+    core::ptr::write_bytes(addr as *mut u8, FREE_FILL, block);
+
+    let slots = &mut *SHADOW.quarantine.get();
+    let next = &mut *SHADOW.next_slot.get();
+    let evicted = slots[*next].replace((addr, block));
+    *next = (*next + 1) % QUARANTINE_SLOTS;
+    if let Some((old, len)) = evicted {
+        if let Some(bad) = first_mismatch(old, len, FREE_FILL) {
+            report(Bug::UseAfterFree, old, len, bad);
+        }
+        poison(old, len, UNMAPPED);
+    }
+    evicted
+}
+
+// -----------------------------------------------------------------------------
+// AÇIK DENETİM
+// -----------------------------------------------------------------------------
+
+/// `[addr, addr + len)` erişimini gölgeye göre denetler. Arena dışındaki
+/// adresler denetlenmez.
+///
+/// # Hatalar
+/// * `InvalidArgument`: Aralık koruma bölgesine, serbest veya tahsis
+///   edilmemiş alana değiyor (rapor da yazılır).
+pub fn check(addr: usize, len: usize) -> Result<(), KernelError> {
+    for a in addr..addr.saturating_add(len) {
+        let Some(g) = granule_of(a) else { continue };
+        // SAFETY: Yalnızca okuma.
+        let value = unsafe { shadow()[g] };
+        let ok = value == 0 || (value < GRANULE as u8 && a % GRANULE < usize::from(value));
+        if !ok {
+            report(Bug::InvalidAccess, addr, len, a);
+            return Err(KernelError::InvalidArgument);
+        }
+    }
+    Ok(())
+}
+
+// -----------------------------------------------------------------------------
+// KABUK KOMUTU
+// -----------------------------------------------------------------------------
+
+fn kasan_command(_args: &[&str]) -> Result<(), KernelError> {
+    // SAFETY: Yalnızca okuma; sayımlar anlık görüntüdür.
+    let (shadow, quarantine) = unsafe { (shadow(), &*SHADOW.quarantine.get()) };
+    let count = |v: u8| shadow.iter().filter(|&&s| s == v).count() * GRANULE;
+    serial_println!("  gölge: {} B ({} B arena)", SHADOW_LEN, HEAP_SIZE);
+    serial_println!("  koruma bölgesi: {} B, serbest (karantina): {} B, boş: {} B", count(REDZONE), count(FREED), count(UNMAPPED));
+    serial_println!("  karantina: {}/{} blok", quarantine.iter().flatten().count(), QUARANTINE_SLOTS);
+    serial_println!("  rapor: {}", REPORTS.get());
+    Ok(())
+}
+
+crate::shell_command!("kasan", "KASAN gölge bellek durumunu gösterir", kasan_command);
@@ -4,6 +4,7 @@
 use crate::platform::{Platform, PlatformManager};
 use crate::platformgeneric::{KernelError, SystemConstants};
 use core::ops::RangeInclusive;
+use core::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 
 /// Bellek Yönetimi için Ortak Arayüz (Trait).
 ///
@@ -65,6 +66,29 @@ static mut GLOBAL_TASK_STACK_ALLOCATOR: TaskStackAllocator = TaskStackAllocator
     is_allocated: [false; SystemConstants::MAX_TASKS],
 };
 
+// Kapasite planlaması için yığın ayırıcı istatistikleri
+static STACK_PEAK: AtomicUsize = AtomicUsize::new(0);
+static STACK_FAILURES: AtomicU64 = AtomicU64::new(0);
+
+crate::kernel_counter!(STACK_ALLOC_FAILURES, "stack.alloc_failures");
+
+/// Tahsiste yığına doldurulan desen; en yüksek kullanım işareti (high-water
+/// mark), tabandan itibaren bozulmamış desen uzunluğundan hesaplanır.
+pub const STACK_PAINT: u8 = 0xA5;
+
+/// Görev yığını ayırıcısı istatistikleri.
+#[derive(Debug, Clone, Copy)]
+pub struct StackStats {
+    /// Toplam yığın yuvası.
+    pub total: usize,
+    /// Tahsisli yığın sayısı.
+    pub used: usize,
+    /// Açılıştan (veya son `reset_peak`'ten) bu yana en yüksek `used`.
+    pub peak: usize,
+    /// Reddedilen tahsis isteği sayısı.
+    pub failures: u64,
+}
+
 impl TaskStackAllocator {
     /// Belirtilen görev ID'si için yığının başlangıç sanal adresini hesaplar.
     pub fn get_stack_base_address(task_id: usize) -> Option<usize> {
@@ -87,17 +111,25 @@ impl TaskStackAllocator {
         // --- Gerçek bir çekirdekte burada bir kilit (Spinlock) olmalıdır! ---
         unsafe {
             if GLOBAL_TASK_STACK_ALLOCATOR.is_allocated[task_id] {
+                STACK_FAILURES.fetch_add(1, Ordering::Relaxed);
+                STACK_ALLOC_FAILURES.inc();
                 return Err(KernelError::ResourceBusy);
             }
             
             // Yığını tahsis edildi olarak işaretle
             GLOBAL_TASK_STACK_ALLOCATOR.is_allocated[task_id] = true;
         }
+        STACK_PEAK.fetch_max(Self::allocated_count(), Ordering::Relaxed);
 
         // Yığın taban adresini hesapla (üst adrese yakın)
         let base_addr = Self::get_stack_base_address(task_id)
             .ok_or(KernelError::OutOfMemoryStatic)?;
 
+        // Kullanım ölçümü için yığını desenle boya
+        unsafe {
+            core::ptr::write_bytes(base_addr as *mut u8, STACK_PAINT, MemoryRegions::TASK_STACK_SIZE);
+        }
+
         // Yığın pointerı genellikle en yüksek adresten başlar ve aşağı doğru büyür.
         Ok(base_addr + MemoryRegions::TASK_STACK_SIZE)
     }
@@ -122,4 +154,49 @@ impl TaskStackAllocator {
         // kaldırılması mimariye özgü PlatformManager üzerinden yapılmalıdır.
         Ok(())
     }
+
+    /// Görevin tahsisli yığınının adres aralığı; tahsisli değilse `None`.
+    pub fn stack_range(task_id: usize) -> Option<core::ops::Range<usize>> {
+        if task_id >= SystemConstants::MAX_TASKS || unsafe { !GLOBAL_TASK_STACK_ALLOCATOR.is_allocated[task_id] } {
+            return None;
+        }
+        let base = Self::get_stack_base_address(task_id)?;
+        Some(base..base + MemoryRegions::TASK_STACK_SIZE)
+    }
+
+    /// Görev yığınının açılıştan bu yana en yüksek kullanımı (bayt).
+    /// Yığın aşağı doğru büyüdüğünden tabandaki bozulmamış desen hiç
+    /// kullanılmamış bölgedir. Yığın tahsisli değilse `None`.
+    pub fn high_water_mark(task_id: usize) -> Option<usize> {
+        if task_id >= SystemConstants::MAX_TASKS || unsafe { !GLOBAL_TASK_STACK_ALLOCATOR.is_allocated[task_id] } {
+            return None;
+        }
+        let base = Self::get_stack_base_address(task_id)? as *const u8;
+        let untouched = (0..MemoryRegions::TASK_STACK_SIZE)
+            .take_while(|&i| unsafe { core::ptr::read_volatile(base.add(i)) } == STACK_PAINT)
+            .count();
+        Some(MemoryRegions::TASK_STACK_SIZE - untouched)
+    }
+
+    /// Şu an tahsis edilmiş yığın sayısı.
+    pub fn allocated_count() -> usize {
+        unsafe {
+            GLOBAL_TASK_STACK_ALLOCATOR.is_allocated.iter().filter(|&&used| used).count()
+        }
+    }
+
+    /// Kullanım, en yüksek kullanım ve başarısız istek istatistikleri.
+    pub fn stats() -> StackStats {
+        StackStats {
+            total: SystemConstants::MAX_TASKS,
+            used: Self::allocated_count(),
+            peak: STACK_PEAK.load(Ordering::Relaxed),
+            failures: STACK_FAILURES.load(Ordering::Relaxed),
+        }
+    }
+
+    /// En yüksek kullanım işaretini güncel kullanıma çeker.
+    pub fn reset_peak() {
+        STACK_PEAK.store(Self::allocated_count(), Ordering::Relaxed);
+    }
 }
@@ -0,0 +1,165 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/memory/memtest.rs
+// Açılışta hızlı bellek sınaması ve bozuk çerçeve karantinası.
+//
+// Ucuz kartlardaki sınırda DRAM'in hataları çoğunlukla belirli hücrelerde
+// ya da adres hatlarındadır ve rastgele çökmeler olarak görünür. Komut
+// satırında `memtest` verilirse çerçeve ayırıcısının tüm boş çerçeveleri
+// `frame::init` içinde sınanır: çekirdek imajı ve initrd ayrıldıktan sonra,
+// ilk tahsisten önce. Böylece bozuk bir çerçeve hiçbir yapıya verilmeden
+// karantinaya alınır:
+//
+// 1. Adres-adreste: her sözcüğe kendi adresi yazılır, sonra okunur (adres
+//    hattı ve kod çözücü hataları).
+// 2. Yürüyen bir: `i`. sözcüğe `1 << (i % 64)` yazılır (takılı sıfır
+//    bitler ve komşu hat etkileşimi).
+// 3. Yürüyen sıfır: ikinci desenin tersi (takılı bir bitler).
+//
+// Sınanan çerçeve önce `frame::claim` ile alınır, böylece sınama sırasında
+// başkasına verilmez. Hatalı çerçeve `frame::quarantine` ile kalıcı olarak
+// ayırıcıdan çıkarılır; sağlam çerçeve sıfırlanıp bırakılır. Sonunda
+// karantinadaki çerçeve sayısı yazdırılır. Çerçeveler fiziksel adresleriyle
+// (birebir eşleme) erişilir; aygıt ağacı blob'u ayırıcıda ayrılmadığından
+// atlanır.
+//
+// Sınama hızlıdır ve önbellek üzerinden yapılır: önbellekte kalan yazmalar
+// DRAM'e hiç ulaşmayabilir. Kapsamlı sınama için önyükleyicinin sınayıcısı
+// (örn. memtest86+, U-Boot `mtest`) kullanılmalıdır.
+//
+// Kabuk komutu: `memtest [run]`; procfs: yok (`/proc/meminfo`'da
+// `FramesQuarantined`)
+
+use core::sync::atomic::{AtomicU64, Ordering};
+use crate::memory::frame::{self, FRAME_SIZE};
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+
+/// Çerçevedeki sözcük sayısı.
+const WORDS: usize = FRAME_SIZE / core::mem::size_of::<usize>();
+
+/// Raporda adresleri listelenen en fazla çerçeve.
+const REPORT_MAX: usize = 16;
+
+crate::kernel_counter!(TESTED, "memtest.tested");
+crate::kernel_counter!(BAD, "memtest.bad");
+
+/// Son sınamanın süresi (ns).
+static LAST_NS: AtomicU64 = AtomicU64::new(0);
+
+// -----------------------------------------------------------------------------
+// DESENLER
+// -----------------------------------------------------------------------------
+
+/// Çerçeveyi `pattern(i)` ile doldurur ve geri okur; ilk hatalı sözcüğün
+/// sırasını döndürür.
+///
+/// # Safety
+/// Çerçeve eşlenmiş ve çağıranın olmalıdır.
+unsafe fn pass(addr: usize, pattern: impl Fn(usize) -> usize) -> Option<usize> {
+    let words = addr as *mut usize;
+    for i in 0..WORDS {
+        core::ptr::write_volatile(words.add(i), pattern(i));
+    }
+    (0..WORDS).find(|&i| core::ptr::read_volatile(words.add(i)) != pattern(i))
+}
+
+/// Üç deseni sırayla uygular; ilk hatanın adresini döndürür.
+///
+/// # Safety
+/// Çerçeve eşlenmiş ve çağıranın olmalıdır.
+unsafe fn test_frame(addr: usize) -> Option<usize> {
+    let word = |i: usize| addr + i * core::mem::size_of::<usize>();
+    let patterns: [&dyn Fn(usize) -> usize; 3] = [
+        &|i| word(i),
+        &|i| 1 << (i % usize::BITS as usize),
+        &|i| !(1 << (i % usize::BITS as usize)),
+    ];
+    patterns.iter().find_map(|pattern| pass(addr, pattern).map(word))
+}
+
+// -----------------------------------------------------------------------------
+// SINAMA
+// -----------------------------------------------------------------------------
+
+/// Aygıt ağacı blob'u bu çerçeveyle çakışıyor mu?
+fn overlaps_fdt(addr: usize) -> bool {
+    crate::fdt::get().is_some_and(|fdt| {
+        let blob = fdt.blob();
+        let start = blob.as_ptr() as usize;
+        addr < start + blob.len() && start < addr + FRAME_SIZE
+    })
+}
+
+/// Tüm boş çerçeveleri sınar; bu sınamada karantinaya alınan çerçeve
+/// sayısını döndürür.
+pub fn run() -> usize {
+    let (base, total) = frame::region();
+    let started = crate::hrtimer::now_ns();
+    let (mut tested, mut bad) = (0, 0);
+    for addr in (0..total).map(|f| base + f * FRAME_SIZE) {
+        if overlaps_fdt(addr) || !frame::claim(addr) {
+            continue;
+        }
+        tested += 1;
+        // SAFETY: Çerçeve `claim` ile alındı; ayırıcının bölgesi birebir eşlenmiştir.
+        match unsafe { test_frame(addr) } {
+            Some(fault) => {
+                serial_println!("[MEMTEST] {:#x} bozuk (ilk hata {:#x}); karantinaya alındı", addr, fault);
+                let _ = frame::quarantine(addr);
+                bad += 1;
+            }
+            None => {
+                // SAFETY: Aynı çerçeve; bırakılmadan önce sıfırlanır.
+                unsafe { core::ptr::write_bytes(addr as *mut u8, 0, FRAME_SIZE) };
+                let _ = frame::free(addr, 1);
+            }
+        }
+    }
+    let elapsed = crate::hrtimer::now_ns().saturating_sub(started);
+    LAST_NS.store(elapsed, Ordering::Relaxed);
+    TESTED.add(tested as u64);
+    BAD.add(bad as u64);
+    serial_println!(
+        "[MEMTEST] {} çerçeve sınandı ({} ms): {} bozuk, karantinada toplam {}",
+        tested,
+        elapsed / 1_000_000,
+        bad,
+        frame::stats().quarantined
+    );
+    bad
+}
+
+// -----------------------------------------------------------------------------
+// KABUK KOMUTU
+// -----------------------------------------------------------------------------
+
+/// `memtest [run]`
+fn memtest_command(args: &[&str]) -> Result<(), KernelError> {
+    match args.get(1).copied() {
+        None => {}
+        Some("run") => {
+            run();
+        }
+        _ => return Err(KernelError::InvalidArgument),
+    }
+    let mut frames = [0usize; REPORT_MAX];
+    let listed = frame::quarantined(&mut frames);
+    let total = frame::stats().quarantined;
+    serial_println!(
+        "Sınanan: {}, bozuk: {}, son sınama {} ms",
+        TESTED.get(),
+        BAD.get(),
+        LAST_NS.load(Ordering::Relaxed) / 1_000_000
+    );
+    serial_println!("Karantinada {} çerçeve ({} KiB)", total, total * FRAME_SIZE / 1024);
+    for addr in &frames[..listed] {
+        serial_println!("  {:#x}", addr);
+    }
+    if total > listed {
+        serial_println!("  ... ve {} çerçeve daha", total - listed);
+    }
+    Ok(())
+}
+
+crate::shell_command!("memtest", "Bellek sınaması ve bozuk çerçeve karantinası (memtest [run])", memtest_command);
@@ -0,0 +1,338 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/memory/pool.rs
+// Sabit boyutlu nesne havuzu (mempool): deterministik tahsis.
+//
+// `Pool<T, N>` N adet `T` yuvasını statik olarak ayırır; tahsis ve serbest
+// bırakma kilitsiz ve O(1)'dir, genel yığına (`heap`) hiç dokunmaz. Bu
+// yüzden kesme işleyicilerinde ve gerçek zamanlı yollarda güvenle
+// kullanılabilir. Boş yuvalar etiketli (ABA korumalı) bir Treiber yığınında
+// tutulur; hiç kullanılmamış yuvalar ayrı bir sayaçla sırayla verilir, böylece
+// havuz `const` olarak kurulabilir.
+//
+// Havuzlar `mempool!` ile tanımlanır ve `.mempools` bölümüne kaydedilir;
+// doluluk, tepe kullanım ve tükenme (isteğin karşılanamaması) sayıları
+// `mempools` kabuk komutu ve `/proc/mempools` ile raporlanır.
+
+use core::cell::UnsafeCell;
+use core::mem::MaybeUninit;
+use core::ops::{Deref, DerefMut};
+use core::sync::atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// SABİTLER VE TİPLER
+// -----------------------------------------------------------------------------
+
+/// Boş listenin sonu.
+const NIL: u32 = u32::MAX;
+
+/// Havuz istatistikleri.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PoolStats {
+    /// Yuva sayısı (N).
+    pub capacity: usize,
+    /// Kullanımdaki yuva sayısı.
+    pub in_use: usize,
+    /// Açılıştan bu yana en yüksek `in_use`.
+    pub peak: usize,
+    /// Başarılı tahsis sayısı.
+    pub allocs: u64,
+    /// Serbest bırakma sayısı.
+    pub frees: u64,
+    /// Havuz boşken gelen (karşılanamayan) istek sayısı.
+    pub exhausted: u64,
+}
+
+/// Kayıt tablosundan havuzlara tipten bağımsız erişim.
+pub trait PoolInfo: Sync {
+    fn name(&self) -> &'static str;
+    fn stats(&self) -> PoolStats;
+}
+
+/// N adet `T` için sabit boyutlu, kilitsiz nesne havuzu.
+pub struct Pool<T, const N: usize> {
+    name: &'static str,
+    slots: [UnsafeCell<MaybeUninit<T>>; N],
+    /// Boş listedeki sonraki yuva.
+    next: [AtomicU32; N],
+    /// Boş listenin başı: üst 32 bit sürüm etiketi, alt 32 bit yuva (veya `NIL`).
+    head: AtomicU64,
+    /// Hiç verilmemiş ilk yuva.
+    fresh: AtomicUsize,
+    in_use: AtomicUsize,
+    peak: AtomicUsize,
+    allocs: AtomicU64,
+    frees: AtomicU64,
+    exhausted: AtomicU64,
+}
+
+// Bir yuvaya yalnızca onu tahsis eden erişir; `T` başka bağlama taşınabilmeli.
+unsafe impl<T: Send, const N: usize> Sync for Pool<T, N> {}
+
+// -----------------------------------------------------------------------------
+// HAVUZ
+// -----------------------------------------------------------------------------
+
+impl<T, const N: usize> Pool<T, N> {
+    /// Boş bir havuz (derleme zamanı sabiti; `mempool!` ile kullanılır).
+    pub const fn new(name: &'static str) -> Self {
+        assert!(N < NIL as usize, "havuz çok büyük");
+        Pool {
+            name,
+            slots: [const { UnsafeCell::new(MaybeUninit::uninit()) }; N],
+            next: [const { AtomicU32::new(NIL) }; N],
+            head: AtomicU64::new(NIL as u64),
+            fresh: AtomicUsize::new(0),
+            in_use: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+            allocs: AtomicU64::new(0),
+            frees: AtomicU64::new(0),
+            exhausted: AtomicU64::new(0),
+        }
+    }
+
+    /// Boş bir yuva ayırır (değer yazılmaz). O(1), kilitsiz.
+    ///
+    /// # Dönüş Değeri
+    /// Yuva indeksi (0..N); havuz boşsa `None` ve `exhausted` artar.
+    pub fn alloc_index(&self) -> Option<usize> {
+        let index = self.pop_free().or_else(|| self.take_fresh());
+        match index {
+            Some(_) => {
+                self.allocs.fetch_add(1, Ordering::Relaxed);
+                let in_use = self.in_use.fetch_add(1, Ordering::Relaxed) + 1;
+                self.peak.fetch_max(in_use, Ordering::Relaxed);
+            }
+            None => {
+                self.exhausted.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        index
+    }
+
+    /// `alloc_index` ile alınmış yuvayı havuza geri verir. Yuvadaki değer
+    /// düşürülmez (bkz. `PoolBox`).
+    ///
+    /// # Safety
+    /// `index` bu havuzdan alınmış ve henüz geri verilmemiş olmalıdır.
+    pub unsafe fn free_index(&self, index: usize) {
+        debug_assert!(index < N);
+        let mut head = self.head.load(Ordering::Acquire);
+        loop {
+            self.next[index].store(head as u32, Ordering::Relaxed);
+            let new = Self::tagged(head, index as u32);
+            match self.head.compare_exchange_weak(head, new, Ordering::Release, Ordering::Acquire) {
+                Ok(_) => break,
+                Err(current) => head = current,
+            }
+        }
+        self.frees.fetch_add(1, Ordering::Relaxed);
+        self.in_use.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    /// Yuvanın ham göstericisi.
+    ///
+    /// # Safety
+    /// Gösterici yalnızca yuvayı tahsis eden tarafından ve yuva geri
+    /// verilene kadar kullanılmalıdır.
+    pub unsafe fn slot(&self, index: usize) -> *mut T {
+        (*self.slots[index].get()).as_mut_ptr()
+    }
+
+    /// `value`'yu bir yuvaya yerleştirir; tutamaç düşürülünce değer
+    /// düşürülür ve yuva havuza döner.
+    ///
+    /// # Hatalar
+    /// * `OutOfMemoryStatic`: Havuz boş.
+    pub fn alloc(&self, value: T) -> Result<PoolBox<'_, T, N>, KernelError> {
+        let index = self.alloc_index().ok_or(KernelError::OutOfMemoryStatic)?;
+        // SAFETY: Yuva yeni tahsis edildi; ona yalnızca biz erişiyoruz.
+        unsafe { self.slot(index).write(value) };
+        Ok(PoolBox { pool: self, index })
+    }
+
+    /// Boş yuva sayısı (anlık).
+    pub fn available(&self) -> usize {
+        N - self.in_use.load(Ordering::Relaxed)
+    }
+
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    fn tagged(old: u64, index: u32) -> u64 {
+        (((old >> 32) + 1) << 32) | u64::from(index)
+    }
+
+    fn pop_free(&self) -> Option<usize> {
+        let mut head = self.head.load(Ordering::Acquire);
+        loop {
+            let index = head as u32;
+            if index == NIL {
+                return None;
+            }
+            // Etiket, araya giren pop/push çiftinin (ABA) eski `next`'i yazmasını engeller
+            let next = self.next[index as usize].load(Ordering::Relaxed);
+            match self.head.compare_exchange_weak(head, Self::tagged(head, next), Ordering::Acquire, Ordering::Acquire) {
+                Ok(_) => return Some(index as usize),
+                Err(current) => head = current,
+            }
+        }
+    }
+
+    fn take_fresh(&self) -> Option<usize> {
+        self.fresh
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |f| (f < N).then_some(f + 1))
+            .ok()
+    }
+}
+
+impl<T, const N: usize> PoolInfo for Pool<T, N>
+where
+    Pool<T, N>: Sync,
+{
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn stats(&self) -> PoolStats {
+        PoolStats {
+            capacity: N,
+            in_use: self.in_use.load(Ordering::Relaxed),
+            peak: self.peak.load(Ordering::Relaxed),
+            allocs: self.allocs.load(Ordering::Relaxed),
+            frees: self.frees.load(Ordering::Relaxed),
+            exhausted: self.exhausted.load(Ordering::Relaxed),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// TUTAMAÇ
+// -----------------------------------------------------------------------------
+
+/// Havuzdan alınmış bir nesne; düşürülünce havuza döner.
+pub struct PoolBox<'a, T, const N: usize> {
+    pool: &'a Pool<T, N>,
+    index: usize,
+}
+
+impl<T, const N: usize> PoolBox<'_, T, N> {
+    /// Nesnenin havuzdaki yuvası.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl<T, const N: usize> Deref for PoolBox<'_, T, N> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: Yuva bu tutamaca aittir ve `alloc` içinde yazılmıştır.
+        unsafe { &*self.pool.slot(self.index) }
+    }
+}
+
+impl<T, const N: usize> DerefMut for PoolBox<'_, T, N> {
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: Yuva bu tutamaca aittir ve `alloc` içinde yazılmıştır.
+        unsafe { &mut *self.pool.slot(self.index) }
+    }
+}
+
+impl<T, const N: usize> Drop for PoolBox<'_, T, N> {
+    fn drop(&mut self) {
+        // SAFETY: Yuva bu tutamaca aittir; değer bir kez düşürülür ve yuva bir kez geri verilir.
+        unsafe {
+            self.pool.slot(self.index).drop_in_place();
+            self.pool.free_index(self.index);
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// KAYIT
+// -----------------------------------------------------------------------------
+
+/// Statik bir nesne havuzu tanımlar ve istatistikler için kaydeder.
+///
+/// ```ignore
+/// crate::mempool!(pub(crate) NETBUFS: NetBuf, 32, "netbuf");
+/// let buf = NETBUFS.alloc(NetBuf::new())?;
+/// ```
+#[macro_export]
+macro_rules! mempool {
+    ($vis:vis $ident:ident: $ty:ty, $n:expr, $name:expr) => {
+        $vis static $ident: $crate::memory::pool::Pool<$ty, { $n }> = $crate::memory::pool::Pool::new($name);
+        const _: () = {
+            #[used]
+            #[link_section = ".mempools"]
+            static __MEMPOOL: &'static dyn $crate::memory::pool::PoolInfo = &$ident;
+        };
+    };
+}
+
+extern "C" {
+    static __mempools_start: &'static dyn PoolInfo;
+    static __mempools_end: &'static dyn PoolInfo;
+}
+
+/// Kayıtlı tüm havuzlar.
+pub fn pools() -> &'static [&'static dyn PoolInfo] {
+    // SAFETY: Semboller linker betiği tarafından `.mempools` bölümünün iki ucuna yerleştirilir.
+    unsafe {
+        let start = &__mempools_start as *const &'static dyn PoolInfo;
+        let end = &__mempools_end as *const &'static dyn PoolInfo;
+        let count = (end as usize - start as usize) / core::mem::size_of::<&'static dyn PoolInfo>();
+        core::slice::from_raw_parts(start, count)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// KABUK VE PROCFS
+// -----------------------------------------------------------------------------
+
+fn mempools_command(_args: &[&str]) -> Result<(), KernelError> {
+    for pool in pools() {
+        let s = pool.stats();
+        serial_println!(
+            "{:<12} {:>4} / {:<4} kullanımda (tepe {}) | tahsis {} serbest {} tükenme {}",
+            pool.name(), s.in_use, s.capacity, s.peak, s.allocs, s.frees, s.exhausted
+        );
+    }
+    Ok(())
+}
+
+crate::shell_command!("mempools", "Nesne havuzu istatistikleri", mempools_command);
+
+fn show_mempools(out: &mut dyn core::fmt::Write) -> core::fmt::Result {
+    for pool in pools() {
+        let s = pool.stats();
+        writeln!(out, "{} {} {} {} {} {} {}", pool.name(), s.capacity, s.in_use, s.peak, s.allocs, s.frees, s.exhausted)?;
+    }
+    Ok(())
+}
+
+crate::proc_entry!("mempools", show_mempools);
+
+// -----------------------------------------------------------------------------
+// TESTLER
+// -----------------------------------------------------------------------------
+
+crate::ktest! {
+    fn pool_exhaustion() {
+        static POOL: Pool<u32, 2> = Pool::new("ktest");
+        let exhausted = POOL.stats().exhausted;
+        let a = POOL.alloc(1)?;
+        let b = POOL.alloc(2)?;
+        crate::ktest_assert!(POOL.alloc(3).is_err());
+        crate::ktest_assert_eq!(POOL.stats().exhausted, exhausted + 1);
+        // Serbest bırakılan yuva hemen yeniden kullanılır
+        drop(a);
+        let c = POOL.alloc(4)?;
+        crate::ktest_assert_eq!((*b, *c), (2, 4));
+        crate::ktest_assert_eq!(POOL.available(), 0);
+    }
+}
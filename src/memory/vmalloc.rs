@@ -0,0 +1,248 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/memory/vmalloc.rs
+// Çekirdek sanal alan ayırıcısı (vmalloc): dağınık fiziksel çerçeveleri
+// ayrılmış bir çekirdek sanal adres penceresinde ardışık olarak eşler.
+//
+// Büyük tamponlar (örn. modül imajları, büyük tablolar) için fiziksel olarak
+// ardışık bellek (`frame::alloc_contiguous`) parçalanma arttıkça bulunamaz.
+// `vmalloc(len)` çerçeveleri tek tek ayırır ve mimarinin `VMALLOC_BASE`
+// penceresinde sanal olarak ardışık eşler; `vfree` eşlemeleri kaldırır,
+// çerçeveleri geri verir ve sanal aralığı serbest bırakır.
+//
+// * Pencere ilk uyan (first-fit) yöntemiyle dağıtılır. Alanlar arasında
+//   eşlenmemiş bir koruma sayfası bırakılır; alan sonunu aşan erişim sayfa
+//   hatasına düşer.
+// * Eşlemeler `arch_mmu::map_kernel_page` ile yapılır (çekirdek R/W,
+//   yürütülemez). Sayfalama kurulmadan önce çağrılırsa hata döner.
+// * Bellek sıfırlanmış olarak döndürülür.
+// * MIPS64 ve OpenRISC'te sayfa tablosu yoktur (yalnızca TLB); bu
+//   mimarilerde `vmalloc` `PlatformSpecificError` döndürür.
+//
+// Kabuk komutu: `vmallocinfo`; procfs: `/proc/vmallocinfo`
+
+use core::cell::UnsafeCell;
+use core::fmt::{self, Write};
+use crate::memory::frame::{self, FRAME_SIZE};
+use crate::platformgeneric::spinlock::Spinlock;
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+
+#[cfg(not(any(target_arch = "mips64", target_arch = "or1k")))]
+use crate::arch::mmu as arch_mmu;
+
+// -----------------------------------------------------------------------------
+// SABİTLER VE TİPLER
+// -----------------------------------------------------------------------------
+
+/// Aynı anda var olabilecek en fazla alan.
+const MAX_AREAS: usize = 64;
+
+/// Alanlar arasında bırakılan eşlenmemiş koruma sayfası sayısı.
+const GUARD_PAGES: usize = 1;
+
+/// `vmalloc` ile ayrılmış sanal alan.
+#[derive(Debug, Clone, Copy)]
+pub struct Area {
+    /// Sanal başlangıç adresi.
+    pub start: usize,
+    /// Eşlenen sayfa sayısı (koruma sayfası hariç).
+    pub pages: usize,
+}
+
+impl Area {
+    /// Koruma sayfası dahil alanın sonu.
+    fn reserved_end(&self) -> usize {
+        self.start + (self.pages + GUARD_PAGES) * FRAME_SIZE
+    }
+}
+
+struct Areas {
+    lock: Spinlock,
+    slots: UnsafeCell<[Option<Area>; MAX_AREAS]>,
+}
+
+// Tüm alanlara erişim `lock` ile korunur.
+unsafe impl Sync for Areas {}
+
+static AREAS: Areas = Areas {
+    lock: Spinlock::new(),
+    slots: UnsafeCell::new([None; MAX_AREAS]),
+};
+
+fn with_areas<R>(f: impl FnOnce(&mut [Option<Area>; MAX_AREAS]) -> R) -> R {
+    AREAS.lock.lock();
+    // SAFETY: Kilit tutulduğu sürece duruma tek erişen biziz.
+    let result = unsafe { f(&mut *AREAS.slots.get()) };
+    AREAS.lock.unlock();
+    result
+}
+
+crate::kernel_counter!(ALLOCS, "vmalloc.allocs");
+crate::kernel_counter!(FAILS, "vmalloc.fails");
+
+// -----------------------------------------------------------------------------
+// MİMARİ BAĞLANTISI
+// -----------------------------------------------------------------------------
+
+#[cfg(not(any(target_arch = "mips64", target_arch = "or1k")))]
+const WINDOW: (usize, usize) = (arch_mmu::VMALLOC_BASE, arch_mmu::VMALLOC_SIZE);
+
+#[cfg(any(target_arch = "mips64", target_arch = "or1k"))]
+const WINDOW: (usize, usize) = (0, 0);
+
+#[cfg(not(any(target_arch = "mips64", target_arch = "or1k")))]
+fn arch_map(virt: usize, phys: usize) -> Result<(), KernelError> {
+    arch_mmu::map_kernel_page(virt, phys)
+}
+
+#[cfg(any(target_arch = "mips64", target_arch = "or1k"))]
+fn arch_map(_virt: usize, _phys: usize) -> Result<(), KernelError> {
+    Err(KernelError::PlatformSpecificError(0))
+}
+
+#[cfg(not(any(target_arch = "mips64", target_arch = "or1k")))]
+fn arch_unmap(virt: usize) -> Option<usize> {
+    // SAFETY: Adres bu modülün eşlediği bir alandadır.
+    unsafe { arch_mmu::unmap_kernel_page(virt) }
+}
+
+#[cfg(any(target_arch = "mips64", target_arch = "or1k"))]
+fn arch_unmap(_virt: usize) -> Option<usize> {
+    None
+}
+
+// -----------------------------------------------------------------------------
+// SANAL ARALIK YÖNETİMİ
+// -----------------------------------------------------------------------------
+
+/// Pencerede `pages` sayfalık (koruma sayfası dahil sığan) ilk boş aralığı
+/// bulur ve kaydeder.
+fn reserve(pages: usize) -> Option<usize> {
+    let (base, size) = WINDOW;
+    let need = (pages + GUARD_PAGES).checked_mul(FRAME_SIZE)?;
+    with_areas(|areas| {
+        let slot = areas.iter().position(Option::is_none)?;
+        let mut candidate = base;
+        // Adayla çakışan alan kalmayana kadar adayı çakışan alanın sonuna taşı.
+        while let Some(blocking) =
+            areas.iter().flatten().find(|a| a.start < candidate + need && candidate < a.reserved_end())
+        {
+            candidate = blocking.reserved_end();
+        }
+        if candidate + need > base + size {
+            return None;
+        }
+        areas[slot] = Some(Area { start: candidate, pages });
+        Some(candidate)
+    })
+}
+
+/// `start` ile başlayan alanı kayıttan çıkarır.
+fn release(start: usize) -> Option<Area> {
+    with_areas(|areas| areas.iter_mut().find(|a| a.is_some_and(|a| a.start == start))?.take())
+}
+
+/// Alanın ilk `mapped` sayfasının eşlemesini kaldırır ve çerçevelerini geri verir.
+fn unmap_pages(start: usize, mapped: usize) {
+    for page in 0..mapped {
+        if let Some(phys) = arch_unmap(start + page * FRAME_SIZE) {
+            let _ = frame::free(phys, 1);
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// GENEL ARAYÜZ
+// -----------------------------------------------------------------------------
+
+/// `len` baytlık (sayfaya yuvarlanır), sanal olarak ardışık ve sıfırlanmış
+/// çekirdek belleği ayırır; başlangıç adresini döndürür.
+///
+/// # Hatalar
+/// * `InvalidArgument`: `len` sıfır.
+/// * `OutOfMemoryStatic`: Pencerede yer, alan yuvası veya çerçeve kalmadı.
+/// * `PlatformSpecificError`: Mimaride pencere yok veya sayfalama kurulmadı.
+pub fn vmalloc(len: usize) -> Result<usize, KernelError> {
+    if len == 0 {
+        return Err(KernelError::InvalidArgument);
+    }
+    if WINDOW.1 == 0 {
+        return Err(KernelError::PlatformSpecificError(0));
+    }
+    let pages = len.div_ceil(FRAME_SIZE);
+    let Some(start) = reserve(pages) else {
+        FAILS.inc();
+        return Err(KernelError::OutOfMemoryStatic);
+    };
+
+    // Çerçeveler kilit dışında ayrılır: `frame::alloc` geri kazanım kancasını
+    // çağırabilir.
+    for page in 0..pages {
+        let virt = start + page * FRAME_SIZE;
+        let mapped = frame::alloc().and_then(|phys| {
+            arch_map(virt, phys).inspect_err(|_| {
+                let _ = frame::free(phys, 1);
+            })
+        });
+        if let Err(e) = mapped {
+            unmap_pages(start, page);
+            release(start);
+            FAILS.inc();
+            return Err(e);
+        }
+    }
+
+    // SAFETY: Aralık az önce bu çağrı için eşlendi.
+    unsafe { core::ptr::write_bytes(start as *mut u8, 0, pages * FRAME_SIZE) };
+    ALLOCS.inc();
+    Ok(start)
+}
+
+/// `vmalloc` ile ayrılan belleği geri verir.
+///
+/// # Hatalar
+/// * `NotFound`: `addr` bir `vmalloc` alanının başlangıcı değil.
+pub fn vfree(addr: usize) -> Result<(), KernelError> {
+    let area = with_areas(|areas| areas.iter().flatten().find(|a| a.start == addr).copied())
+        .ok_or(KernelError::NotFound)?;
+    // Aralık, eşlemeler kaldırılana kadar kayıtta kalır; aksi halde eşzamanlı
+    // bir `vmalloc` aynı adresleri alıp `AlreadyMapped` ile başarısız olabilir.
+    unmap_pages(area.start, area.pages);
+    release(area.start);
+    Ok(())
+}
+
+/// Kayıtlı alanlar (sırasız).
+pub fn areas() -> [Option<Area>; MAX_AREAS] {
+    with_areas(|areas| *areas)
+}
+
+// -----------------------------------------------------------------------------
+// PROCFS VE KABUK
+// -----------------------------------------------------------------------------
+
+fn show_vmallocinfo(out: &mut dyn Write) -> fmt::Result {
+    for area in areas().iter().flatten() {
+        let size = area.pages * FRAME_SIZE;
+        writeln!(out, "{:#018x}-{:#018x} {:>10} pages={}", area.start, area.start + size, size, area.pages)?;
+    }
+    Ok(())
+}
+
+crate::proc_entry!("vmallocinfo", show_vmallocinfo);
+
+fn vmallocinfo_command(_args: &[&str]) -> Result<(), KernelError> {
+    let (base, size) = WINDOW;
+    let areas = areas();
+    let used: usize = areas.iter().flatten().map(|a| a.pages).sum();
+    serial_println!("  pencere {:#018x}-{:#018x}", base, base + size);
+    serial_println!("  BAŞLANGIÇ          BOYUT");
+    for area in areas.iter().flatten() {
+        serial_println!("  {:#018x} {:>8} KiB", area.start, area.pages * FRAME_SIZE / 1024);
+    }
+    serial_println!("  {} alan, {} KiB", areas.iter().flatten().count(), used * FRAME_SIZE / 1024);
+    Ok(())
+}
+
+crate::shell_command!("vmallocinfo", "vmalloc alanlarını gösterir", vmallocinfo_command);
@@ -0,0 +1,228 @@
+#![allow(dead_code)]
+
+// src/memory/vmaudit.rs
+// Sanal bellek düzeni denetimi: etkin sayfa tablolarını dolaşır, ardışık
+// eşlemeleri bölgelere birleştirir ve izin/önbellek özniteliklerini yazdırır.
+// Yazılabilir+yürütülebilir (W^X ihlali) bölgeler işaretlenir.
+//
+// Mimarilerin eşleme fonksiyonlarının ortak hata tipi (`MapError`) ve adres
+// denetimi (`validate_mapping`) de buradadır.
+//
+// Kabuk komutu: `vm dump`
+
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+
+// Mimariye özgü sayfa tablosu yürüyücüsü (`walk_mappings`) her mimarinin `mmu.rs` dosyasındadır.
+use crate::arch::mmu as arch_mmu;
+
+// -----------------------------------------------------------------------------
+// ORTAK EŞLEME TİPLERİ
+// -----------------------------------------------------------------------------
+
+/// Bir eşlemenin erişim izinleri.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Perms {
+    pub read: bool,
+    pub write: bool,
+    pub exec: bool,
+    /// Kullanıcı modundan erişilebilir mi?
+    pub user: bool,
+}
+
+impl Perms {
+    /// W^X ihlali: aynı anda yazılabilir ve yürütülebilir.
+    pub fn is_wx(&self) -> bool {
+        self.write && self.exec
+    }
+}
+
+/// Bir eşlemenin önbellek özniteliği.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheAttr {
+    /// Normal bellek, write-back önbellekli.
+    WriteBack,
+    /// Normal bellek, write-through önbellekli.
+    WriteThrough,
+    /// Önbelleksiz normal bellek.
+    Uncached,
+    /// Aygıt belleği (MMIO; sıralı, birleştirilmeyen erişim).
+    Device,
+}
+
+impl CacheAttr {
+    fn short(self) -> &'static str {
+        match self {
+            CacheAttr::WriteBack => "WB",
+            CacheAttr::WriteThrough => "WT",
+            CacheAttr::Uncached => "UC",
+            CacheAttr::Device => "DEV",
+        }
+    }
+}
+
+/// Sayfa tablosu yürüyücüsünün bildirdiği tek bir yaprak eşleme (sayfa veya blok).
+#[derive(Debug, Clone, Copy)]
+pub struct Mapping {
+    pub vaddr: usize,
+    pub paddr: usize,
+    pub size: usize,
+    pub perms: Perms,
+    pub cache: CacheAttr,
+}
+
+impl Mapping {
+    /// `next` bu eşlemenin sanal ve fiziksel olarak bitişik, aynı öznitelikli devamı mı?
+    fn continues_with(&self, next: &Mapping) -> bool {
+        self.vaddr.wrapping_add(self.size) == next.vaddr
+            && self.paddr.wrapping_add(self.size) == next.paddr
+            && self.perms == next.perms
+            && self.cache == next.cache
+    }
+}
+
+/// Mimarilerin `map_page`/`map_tlb_entry` fonksiyonlarının hata türü.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MapError {
+    /// Sanal veya fiziksel adres sayfa (büyük sayfada blok) sınırında değil.
+    Unaligned,
+    /// Sanal adres kanonik değil, fiziksel adres mimarinin genişliğini aşıyor
+    /// ya da TLB indeksi sınır dışında.
+    OutOfRange,
+    /// Adres farklı bir fiziksel adrese veya bayraklarla zaten eşli (değiştirmek
+    /// için mimarinin `remap_page` fonksiyonu kullanılır), daha büyük bir
+    /// eşlemenin (blok) içinde ya da yerine yazılacak giriş bir alt tabloyu
+    /// gösteriyor.
+    AlreadyMapped,
+    /// Kaldırılacak adres eşli değil.
+    NotMapped,
+    /// Ara sayfa tablosu ayrılamadı.
+    OutOfMemory,
+    /// Bayraklar adres alanına taşıyor veya mimaride ayrılmış bir birleşim.
+    InvalidFlags,
+    /// Sayfalama (kök tablo) henüz kurulmadı.
+    NotReady,
+}
+
+/// Ayrıntı korunur; genel sınıf `KernelError::general` ile alınır.
+impl From<MapError> for KernelError {
+    fn from(error: MapError) -> Self {
+        KernelError::Map(error)
+    }
+}
+
+/// Eşleme öncesi ortak denetim: iki adres `size` hizalı, sanal adres
+/// `va_bits` genişliğinde kanonik (üst bitler işaret genişletmesi), fiziksel
+/// adres `pa_bits` içinde ve bayraklar `addr_mask` ile çakışmıyor. Genişlik
+/// 64 verilirse o adres için aralık denetimi yapılmaz.
+pub fn validate_mapping(
+    vaddr: usize,
+    paddr: usize,
+    size: usize,
+    va_bits: u32,
+    pa_bits: u32,
+    flags: u64,
+    addr_mask: u64,
+) -> Result<(), MapError> {
+    if !vaddr.is_multiple_of(size) || !paddr.is_multiple_of(size) {
+        return Err(MapError::Unaligned);
+    }
+    let shift = usize::BITS - va_bits;
+    if (((vaddr << shift) as isize) >> shift) as usize != vaddr || (paddr as u64).checked_shr(pa_bits).unwrap_or(0) != 0 {
+        return Err(MapError::OutOfRange);
+    }
+    if flags & addr_mask != 0 {
+        return Err(MapError::InvalidFlags);
+    }
+    Ok(())
+}
+
+// -----------------------------------------------------------------------------
+// BÖLGE BİRLEŞTİRME VE ÇIKTI
+// -----------------------------------------------------------------------------
+
+/// Denetim sonucu özet bilgileri.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AuditSummary {
+    /// Birleştirilmiş bölge sayısı.
+    pub regions: usize,
+    /// Eşlenmiş toplam bayt.
+    pub mapped_bytes: usize,
+    /// W^X ihlali olan bölge sayısı.
+    pub wx_regions: usize,
+}
+
+fn print_region(region: &Mapping, summary: &mut AuditSummary) {
+    let p = region.perms;
+    let wx = p.is_wx();
+
+    serial_println!(
+        "{:#018x}-{:#018x} -> {:#014x} {:>8}K {}{}{}{} {:<3}{}",
+        region.vaddr,
+        region.vaddr.wrapping_add(region.size),
+        region.paddr,
+        region.size / 1024,
+        if p.read { 'r' } else { '-' },
+        if p.write { 'w' } else { '-' },
+        if p.exec { 'x' } else { '-' },
+        if p.user { 'u' } else { 'k' },
+        region.cache.short(),
+        if wx { "  !!! W+X" } else { "" }
+    );
+
+    summary.regions += 1;
+    summary.mapped_bytes += region.size;
+    if wx {
+        summary.wx_regions += 1;
+    }
+}
+
+/// Etkin sayfa tablolarını dolaşır ve birleştirilmiş bölge listesini yazdırır.
+pub fn dump() -> AuditSummary {
+    let mut summary = AuditSummary::default();
+    let mut current: Option<Mapping> = None;
+
+    serial_println!("SANAL ADRES ARALIĞI                       FİZİKSEL        BOYUT İZİN ÖNB");
+
+    arch_mmu::walk_mappings(&mut |m: Mapping| {
+        match current {
+            Some(ref mut region) if region.continues_with(&m) => region.size += m.size,
+            Some(region) => {
+                print_region(&region, &mut summary);
+                current = Some(m);
+            }
+            None => current = Some(m),
+        }
+    });
+
+    if let Some(region) = current {
+        print_region(&region, &mut summary);
+    }
+
+    serial_println!(
+        "{} bölge, {} KiB eşli, {} W+X bölge.",
+        summary.regions,
+        summary.mapped_bytes / 1024,
+        summary.wx_regions
+    );
+    summary
+}
+
+// -----------------------------------------------------------------------------
+// KABUK KOMUTU
+// -----------------------------------------------------------------------------
+
+fn vm_command(args: &[&str]) -> Result<(), KernelError> {
+    match args.get(1) {
+        Some(&"dump") => {
+            dump();
+            Ok(())
+        }
+        _ => {
+            serial_println!("Kullanım: vm dump");
+            Err(KernelError::InvalidArgument)
+        }
+    }
+}
+
+crate::shell_command!("vm", "Sanal bellek tanıları (vm dump)", vm_command);
\ No newline at end of file
@@ -0,0 +1,603 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/mmap.rs
+// VFS dosyalarının kullanıcı görevlerine bellek eşlemesi (mmap).
+//
+// `sys_mmap` yalnızca bir sanal adres aralığı (VMA) ayırır; sayfalar ilk
+// erişimde sayfa hatası işleyicisinden (`handle_fault`) dosya sisteminden
+// okunarak yüklenir. Böylece büyük salt okunur veriler yalnızca dokunulan
+// kadarıyla belleğe gelir.
+//
+// * Aynı dosya sayfasını eşleyen VMA'lar aynı çerçeveyi paylaşır. Sayfa önce
+//   salt okunur eşlenir; yazma hatası işlenirken yazılabilir yapılır.
+// * `MAP_PRIVATE`: yazma hatasında çerçeve başka bir eşlemeyle paylaşılıyorsa
+//   kopyalanır (copy-on-write); kopya dosyaya hiç yazılmaz.
+// * `MAP_SHARED`: yazılan sayfa kirli işaretlenir; `sys_msync`, `sys_munmap`
+//   ve görev çıkışı kirli sayfaları VFS üzerinden dosyaya geri yazar ve
+//   sayfayı yeniden salt okunur eşler.
+//
+// Henüz dosya tanıtıcısı tablosu olmadığından dosya yol ile verilir:
+// `mmap(yol_ptr, yol_uzunluğu, uzunluk, prot, flags, ofset)`. Tüm görevler
+// aynı sayfa tablosunu kullandığından VMA'lar `MMAP_BASE` penceresinde
+// çakışmayacak biçimde yerleştirilir. `sys_munmap` yalnızca bütün bir
+// eşlemeyi kaldırır. Sayfa tablosu işlemleri şimdilik yalnızca RISC-V'de
+// vardır; diğer mimarilerde eşleme `PlatformSpecificError` ile reddedilir.
+//
+// Sayfa sabitleme (`pin_range`, `sys_mlock`): DMA arabellekleri ve sayfa
+// hatası gecikmesine dayanamayan gerçek zamanlı görevler için aralığın
+// sayfaları hemen yüklenip eşlenir ve sabitlenir:
+// * Yazılabilir eşlemelerde sayfa yazılabilir eşlenir; `MAP_PRIVATE` için
+//   kopya o anda alınır, sonradan copy-on-write hatası olmaz.
+// * Geri yazma sabit sayfayı salt okunur yeniden eşlemez ve kirli bırakır.
+// * Geri kazanım yapan kod sabit çerçevelere dokunmamalıdır (`frame_pinned`).
+// Sabitlemeler sayfa başına sayılır; eşleme kaldırılınca hepsi düşer. Görev
+// başına değil sistem genelinde `mmap.pin_limit` sayfa sabitlenebilir.
+//
+// Kabuk komutu: `maps`
+
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use crate::memory::frame::{self, FRAME_SIZE};
+use crate::platformgeneric::spinlock::Spinlock;
+use crate::platformgeneric::KernelError;
+use crate::sched::{self, TaskId};
+use crate::serial_println;
+use crate::sysctl::U32;
+use crate::trap::Access;
+use crate::uaccess::UserSlice;
+use crate::vfs::{self, FileType};
+
+// -----------------------------------------------------------------------------
+// SABİTLER VE TİPLER
+// -----------------------------------------------------------------------------
+
+/// En fazla eşleme (tüm görevler).
+pub const MAX_VMAS: usize = 16;
+/// Eşleme başına en fazla sayfa (4 KiB sayfalarda 1 MiB).
+pub const MAX_VMA_PAGES: usize = 256;
+/// Dosya yolunun en fazla uzunluğu.
+pub const MAX_PATH: usize = 64;
+
+/// Eşlemelerin yerleştirildiği kullanıcı sanal adres penceresi.
+pub const MMAP_BASE: usize = 0x10_0000_0000;
+pub const MMAP_END: usize = MMAP_BASE + 0x4000_0000;
+
+pub const PROT_READ: usize = 1 << 0;
+pub const PROT_WRITE: usize = 1 << 1;
+pub const PROT_EXEC: usize = 1 << 2;
+
+pub const MAP_SHARED: usize = 0x01;
+pub const MAP_PRIVATE: usize = 0x02;
+
+const BITMAP_WORDS: usize = MAX_VMA_PAGES / 64;
+
+crate::sysctl!(PIN_LIMIT: U32 = U32::new(1024, 0, 32 * 1024),
+    "mmap.pin_limit", "Sabitlenebilecek en fazla eşleme sayfası (tüm görevler)");
+
+#[derive(Clone, Copy)]
+struct Vma {
+    task: TaskId,
+    start: usize,
+    pages: usize,
+    path: [u8; MAX_PATH],
+    path_len: usize,
+    /// Dosyadaki başlangıç (sayfa hizalı).
+    offset: usize,
+    prot: usize,
+    shared: bool,
+    /// Sayfa başına çerçeve (0: henüz yüklenmedi).
+    frames: [usize; MAX_VMA_PAGES],
+    /// Yazılabilir eşlenmiş sayfalar: `MAP_SHARED` için kirli, `MAP_PRIVATE`
+    /// için göreve özel (dosyayla artık ilgisi olmayan) çerçeve.
+    written: [u64; BITMAP_WORDS],
+    /// Sayfa başına sabitleme sayısı (`pin_range`).
+    pins: [u8; MAX_VMA_PAGES],
+}
+
+impl Vma {
+    fn path(&self) -> &str {
+        core::str::from_utf8(&self.path[..self.path_len]).unwrap_or("?")
+    }
+
+    fn end(&self) -> usize {
+        self.start + self.pages * FRAME_SIZE
+    }
+
+    fn contains(&self, addr: usize) -> bool {
+        (self.start..self.end()).contains(&addr)
+    }
+
+    fn file_offset(&self, page: usize) -> usize {
+        self.offset + page * FRAME_SIZE
+    }
+
+    fn is_written(&self, page: usize) -> bool {
+        self.written[page / 64] & (1 << (page % 64)) != 0
+    }
+
+    fn set_written(&mut self, page: usize, value: bool) {
+        if value {
+            self.written[page / 64] |= 1 << (page % 64);
+        } else {
+            self.written[page / 64] &= !(1 << (page % 64));
+        }
+    }
+
+    /// Çerçeve dosya sayfasını mı tutuyor (diğer eşlemelerle paylaşılabilir mi)?
+    fn holds_file_page(&self, page: usize) -> bool {
+        self.frames[page] != 0 && (self.shared || !self.is_written(page))
+    }
+
+    fn is_pinned(&self, page: usize) -> bool {
+        self.pins[page] != 0
+    }
+
+    fn pinned_pages(&self) -> usize {
+        self.pins[..self.pages].iter().filter(|&&n| n != 0).count()
+    }
+}
+
+struct VmaTable {
+    lock: Spinlock,
+    vmas: UnsafeCell<[Option<Vma>; MAX_VMAS]>,
+}
+
+// Tabloya erişim `lock` ile korunur.
+unsafe impl Sync for VmaTable {}
+
+static VMAS: VmaTable = VmaTable {
+    lock: Spinlock::new(),
+    vmas: UnsafeCell::new([None; MAX_VMAS]),
+};
+
+crate::kernel_counter!(MMAP_FAULTS, "mmap.faults");
+crate::kernel_counter!(MMAP_COW, "mmap.cow");
+crate::kernel_counter!(MMAP_WRITEBACK, "mmap.writeback");
+
+/// Sabit sayfa sayısı (en az bir sabitlemesi olan sayfalar, tüm eşlemeler).
+static PINNED_PAGES: AtomicUsize = AtomicUsize::new(0);
+
+fn with_vmas<R>(f: impl FnOnce(&mut [Option<Vma>; MAX_VMAS]) -> R) -> R {
+    VMAS.lock.lock();
+    // SAFETY: Kilit tutulduğu sürece tabloya tek erişen biziz.
+    let result = f(unsafe { &mut *VMAS.vmas.get() });
+    VMAS.lock.unlock();
+    result
+}
+
+// -----------------------------------------------------------------------------
+// MİMARİ SAYFA TABLOSU İŞLEMLERİ
+// -----------------------------------------------------------------------------
+
+unsafe fn arch_map(vaddr: usize, paddr: usize, write: bool, exec: bool) -> Result<(), KernelError> {
+    crate::arch::mmu::map_user_page(vaddr, paddr, write, exec)
+}
+
+unsafe fn arch_unmap(vaddr: usize) {
+    crate::arch::mmu::unmap_user_page(vaddr);
+}
+
+// -----------------------------------------------------------------------------
+// SAYFA YÖNETİMİ
+// -----------------------------------------------------------------------------
+
+/// Dosya sayfasını tutan, başka bir eşlemenin zaten yüklediği çerçeve.
+fn cached_frame(vmas: &[Option<Vma>; MAX_VMAS], path: &str, file_offset: usize) -> Option<usize> {
+    vmas.iter().flatten().filter(|v| v.path() == path).find_map(|v| {
+        let rel = file_offset.checked_sub(v.offset)?;
+        let page = rel / FRAME_SIZE;
+        (page < v.pages && v.holds_file_page(page)).then(|| v.frames[page])
+    })
+}
+
+/// Çerçeveyi kullanan sayfa sayısı (tüm eşlemeler).
+fn frame_users(vmas: &[Option<Vma>; MAX_VMAS], frame: usize) -> usize {
+    vmas.iter().flatten().map(|v| v.frames[..v.pages].iter().filter(|&&f| f == frame).count()).sum()
+}
+
+/// Yeni bir çerçeve ayırır ve dosyanın `offset` konumundaki sayfayla
+/// doldurur; dosya sonundan sonrası sıfırdır.
+fn load_page(path: &str, offset: usize) -> Result<usize, KernelError> {
+    let frame = frame::alloc()?;
+    // SAFETY: Çerçeve yeni ayrıldı ve çekirdekte birebir eşlidir.
+    let page = unsafe { core::slice::from_raw_parts_mut(frame as *mut u8, FRAME_SIZE) };
+    page.fill(0);
+    let mut filled = 0;
+    while filled < FRAME_SIZE {
+        match vfs::read(path, offset + filled, &mut page[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) => {
+                frame::free(frame, 1).ok();
+                return Err(e);
+            }
+        }
+    }
+    Ok(frame)
+}
+
+/// Eşlemenin tek bir sayfasına hata işler.
+fn fault_page(vmas: &mut [Option<Vma>; MAX_VMAS], index: usize, page: usize, write: bool) -> Result<(), KernelError> {
+    let vma = vmas[index].as_ref().ok_or(KernelError::NotFound)?;
+    let (path, path_len, file_offset, shared) = (vma.path, vma.path_len, vma.file_offset(page), vma.shared);
+    let path = core::str::from_utf8(&path[..path_len]).map_err(|_| KernelError::InvalidArgument)?;
+
+    if vma.frames[page] == 0 {
+        let frame = match cached_frame(vmas, path, file_offset) {
+            Some(frame) => frame,
+            None => load_page(path, file_offset)?,
+        };
+        vmas[index].as_mut().ok_or(KernelError::NotFound)?.frames[page] = frame;
+    }
+
+    let users = frame_users(vmas, vmas[index].as_ref().map_or(0, |v| v.frames[page]));
+    let vma = vmas[index].as_mut().ok_or(KernelError::NotFound)?;
+    if write {
+        if !shared && !vma.is_written(page) && users > 1 {
+            // Copy-on-write: diğer eşlemeler dosya sayfasını görmeye devam eder
+            let copy = frame::alloc()?;
+            // SAFETY: İki çerçeve de birebir eşli ve ayrıktır.
+            unsafe { core::ptr::copy_nonoverlapping(vma.frames[page] as *const u8, copy as *mut u8, FRAME_SIZE) };
+            vma.frames[page] = copy;
+            MMAP_COW.inc();
+        }
+        vma.set_written(page, true);
+    }
+
+    let vaddr = vma.start + page * FRAME_SIZE;
+    // SAFETY: Adres bu eşlemenin penceresindedir; çerçeve eşlemeye aittir.
+    unsafe { arch_map(vaddr, vma.frames[page], vma.is_written(page), vma.prot & PROT_EXEC != 0) }
+}
+
+/// Sayfa hatası işleyicisinden çağrılır. Adres çalışan görevin bir
+/// eşlemesindeyse ve erişim izinliyse sayfayı yükler/eşler ve `true` döner;
+/// aksi halde hata normal yoldan (çekirdek dökümü, sonlandırma) işlenir.
+pub fn handle_fault(addr: usize, access: Access) -> bool {
+    let Some(task) = sched::current() else { return false };
+    with_vmas(|vmas| {
+        let Some(index) = vmas.iter().position(|v| v.is_some_and(|v| v.task == task && v.contains(addr))) else {
+            return false;
+        };
+        let (prot, page) = vmas[index].as_ref().map_or((0, 0), |v| (v.prot, (addr - v.start) / FRAME_SIZE));
+        if !permits(prot, access) {
+            return false;
+        }
+        match fault_page(vmas, index, page, access == Access::Write) {
+            Ok(()) => {
+                MMAP_FAULTS.inc();
+                true
+            }
+            Err(e) => {
+                serial_println!("[MMAP] {:#x} yüklenemedi: {:?}", addr, e);
+                false
+            }
+        }
+    })
+}
+
+/// `prot` koruması `access` erişimine izin veriyor mu?
+fn permits(prot: usize, access: Access) -> bool {
+    match access {
+        Access::Read => prot & (PROT_READ | PROT_WRITE | PROT_EXEC) != 0,
+        Access::Write => prot & PROT_WRITE != 0,
+        Access::Execute => prot & PROT_EXEC != 0,
+    }
+}
+
+/// `[addr, addr + len)` aralığı `task`'ın tek bir eşlemesinin içinde ve
+/// eşlemenin koruması `access`'e izin veriyor mu? (`uaccess` denetimi)
+pub fn range_allows(task: TaskId, addr: usize, len: usize, access: Access) -> bool {
+    let Some(end) = addr.checked_add(len) else { return false };
+    with_vmas(|vmas| {
+        vmas.iter().flatten().any(|v| v.task == task && v.start <= addr && end <= v.end() && permits(v.prot, access))
+    })
+}
+
+/// Paylaşımlı eşlemenin kirli sayfalarını dosyaya yazar ve salt okunur
+/// yeniden eşler. `pages` aralığı dışındaki sayfalara dokunulmaz.
+fn write_back(vma: &mut Vma, pages: core::ops::Range<usize>) -> Result<(), KernelError> {
+    if !vma.shared {
+        return Ok(());
+    }
+    let (path, path_len) = (vma.path, vma.path_len);
+    let path = core::str::from_utf8(&path[..path_len]).map_err(|_| KernelError::InvalidArgument)?;
+    let size = vfs::stat(path)?.size;
+    for page in pages.start..pages.end.min(vma.pages) {
+        if !vma.is_written(page) {
+            continue;
+        }
+        let offset = vma.file_offset(page);
+        let len = size.saturating_sub(offset).min(FRAME_SIZE);
+        if len > 0 {
+            // SAFETY: Çerçeve bu eşlemeye ait ve birebir eşlidir.
+            let data = unsafe { core::slice::from_raw_parts(vma.frames[page] as *const u8, len) };
+            vfs::write(path, offset, data)?;
+            MMAP_WRITEBACK.inc();
+        }
+        if vma.is_pinned(page) {
+            // Sabit sayfa yazılabilir kalır; yazmalar izlenemediğinden kirli sayılır
+            continue;
+        }
+        vma.set_written(page, false);
+        // SAFETY: Sayfa bu eşlemeye ait; bir sonraki yazma yeniden kirletir.
+        unsafe { arch_map(vma.start + page * FRAME_SIZE, vma.frames[page], false, vma.prot & PROT_EXEC != 0)? };
+    }
+    Ok(())
+}
+
+/// Eşlemeyi kaldırır ve başka eşlemenin kullanmadığı çerçeveleri bırakır.
+fn release(vmas: &mut [Option<Vma>; MAX_VMAS], index: usize) {
+    let Some(vma) = vmas[index].take() else { return };
+    PINNED_PAGES.fetch_sub(vma.pinned_pages(), Ordering::Relaxed);
+    for page in (0..vma.pages).filter(|&p| vma.frames[p] != 0) {
+        // SAFETY: Sayfa bu eşlemeye aitti.
+        unsafe { arch_unmap(vma.start + page * FRAME_SIZE) };
+        if frame_users(vmas, vma.frames[page]) == 0 {
+            frame::free(vma.frames[page], 1).ok();
+        }
+    }
+}
+
+/// `sched::exit` tarafından çağrılır: görevin eşlemeleri geri yazılıp kaldırılır.
+pub fn task_exited(task: TaskId) {
+    with_vmas(|vmas| {
+        for index in 0..MAX_VMAS {
+            let Some(vma) = vmas[index].as_mut().filter(|v| v.task == task) else { continue };
+            if let Err(e) = write_back(vma, 0..MAX_VMA_PAGES) {
+                serial_println!("[MMAP] {} geri yazılamadı: {:?}", vma.path(), e);
+            }
+            release(vmas, index);
+        }
+    });
+}
+
+// -----------------------------------------------------------------------------
+// SAYFA SABİTLEME
+// -----------------------------------------------------------------------------
+
+/// `[start, start + len)` aralığının görevin eşlemelerine düşen sayfaları:
+/// `(eşleme indisi, ilk sayfa, son sayfa + 1)`. `MMAP_BASE` penceresinde
+/// eşlemeyle örtülmeyen bir sayfa varsa `InvalidArgument`. Pencere dışı
+/// bellek (çekirdek, görev imajı, DMA çerçeveleri) talep üzerine yüklenmez;
+/// zaten yerleşik ve eşli olduğundan atlanır.
+fn pin_span(
+    vmas: &[Option<Vma>; MAX_VMAS],
+    task: TaskId,
+    start: usize,
+    len: usize,
+) -> Result<([(usize, usize, usize); MAX_VMAS], usize), KernelError> {
+    let first = start & !(FRAME_SIZE - 1);
+    let end = start.checked_add(len).ok_or(KernelError::InvalidArgument)?.next_multiple_of(FRAME_SIZE);
+    let (lo, hi) = (first.max(MMAP_BASE), end.min(MMAP_END));
+
+    let mut spans = [(0, 0, 0); MAX_VMAS];
+    let mut count = 0;
+    let mut covered = 0;
+    for (index, vma) in vmas.iter().enumerate() {
+        let Some(vma) = vma.as_ref().filter(|v| v.task == task && v.start < hi && lo < v.end()) else { continue };
+        let from = (lo.max(vma.start) - vma.start) / FRAME_SIZE;
+        let to = (hi.min(vma.end()) - vma.start) / FRAME_SIZE;
+        spans[count] = (index, from, to);
+        count += 1;
+        covered += to - from;
+    }
+    if lo < hi && covered != (hi - lo) / FRAME_SIZE {
+        return Err(KernelError::InvalidArgument);
+    }
+    Ok((spans, count))
+}
+
+/// Görevin `[start, start + len)` aralığını yerleşik ve eşli tutar: sayfalar
+/// hemen yüklenir, yazılabilir eşlemelerde yazılabilir eşlenir (özel
+/// eşlemelerde kopya şimdi alınır) ve sabitlenir. Her çağrı `unpin_range`
+/// ile dengelenmelidir; eşleme kaldırılınca sabitlemeler de düşer.
+///
+/// # Hatalar
+/// * `InvalidArgument`: Aralık eşleme penceresinde eşlenmemiş sayfa içeriyor,
+///   bir eşleme hiç erişilemez (`PROT_NONE`) ya da bir sayfa çok kez sabitlenmiş.
+/// * `OutOfMemoryStatic`: Çerçeve kalmadı veya `mmap.pin_limit` aşılıyor.
+/// * Yükleme hataları VFS'ten olduğu gibi döner; hiçbir sayfa sabitlenmez.
+pub fn pin_range(task: TaskId, start: usize, len: usize) -> Result<(), KernelError> {
+    with_vmas(|vmas| {
+        let (spans, count) = pin_span(vmas, task, start, len)?;
+        let mut new_pins = 0;
+        for &(index, from, to) in &spans[..count] {
+            let vma = vmas[index].as_ref().ok_or(KernelError::NotFound)?;
+            if vma.prot & (PROT_READ | PROT_WRITE | PROT_EXEC) == 0 || vma.pins[from..to].contains(&u8::MAX) {
+                return Err(KernelError::InvalidArgument);
+            }
+            new_pins += vma.pins[from..to].iter().filter(|&&n| n == 0).count();
+        }
+        if PINNED_PAGES.load(Ordering::Relaxed) + new_pins > PIN_LIMIT.get() as usize {
+            return Err(KernelError::OutOfMemoryStatic);
+        }
+
+        // Önce tüm sayfalar yüklenir; biri başarısız olursa hiçbiri sabitlenmez
+        for &(index, from, to) in &spans[..count] {
+            let write = vmas[index].as_ref().is_some_and(|v| v.prot & PROT_WRITE != 0);
+            for page in from..to {
+                fault_page(vmas, index, page, write)?;
+            }
+        }
+        for &(index, from, to) in &spans[..count] {
+            let vma = vmas[index].as_mut().ok_or(KernelError::NotFound)?;
+            vma.pins[from..to].iter_mut().for_each(|n| *n += 1);
+        }
+        PINNED_PAGES.fetch_add(new_pins, Ordering::Relaxed);
+        Ok(())
+    })
+}
+
+/// `pin_range` ile alınan sabitlemeleri bırakır. Sabit olmayan sayfalar yok
+/// sayılır; son sabitlemesi kalkan sayfa bir sonraki geri yazmada yeniden
+/// salt okunur eşlenir.
+///
+/// # Hatalar
+/// * `InvalidArgument`: Aralık eşleme penceresinde eşlenmemiş sayfa içeriyor.
+pub fn unpin_range(task: TaskId, start: usize, len: usize) -> Result<(), KernelError> {
+    with_vmas(|vmas| {
+        let (spans, count) = pin_span(vmas, task, start, len)?;
+        for &(index, from, to) in &spans[..count] {
+            let vma = vmas[index].as_mut().ok_or(KernelError::NotFound)?;
+            for pins in vma.pins[from..to].iter_mut().filter(|n| **n != 0) {
+                *pins -= 1;
+                if *pins == 0 {
+                    PINNED_PAGES.fetch_sub(1, Ordering::Relaxed);
+                }
+            }
+        }
+        Ok(())
+    })
+}
+
+/// Çerçeve sabit bir sayfa tarafından kullanılıyor mu? Çerçeveleri geri
+/// kazanan veya taşıyan kod bunu denetlemelidir.
+pub fn frame_pinned(frame: usize) -> bool {
+    with_vmas(|vmas| vmas.iter().flatten().any(|v| (0..v.pages).any(|p| v.frames[p] == frame && v.is_pinned(p))))
+}
+
+/// Sabit sayfa sayısı (tüm eşlemeler).
+pub fn pinned_pages() -> usize {
+    PINNED_PAGES.load(Ordering::Relaxed)
+}
+
+// -----------------------------------------------------------------------------
+// SİSTEM ÇAĞRILARI
+// -----------------------------------------------------------------------------
+
+/// Dosyayı çalışan görevin adres alanına eşler ve başlangıç adresini döndürür.
+///
+/// # Hatalar
+/// * `InvalidArgument`: Uzunluk 0 veya çok büyük, ofset hizasız, bayraklar
+///   geçersiz, yol okunamadı ya da bir dizin.
+/// * `BadAddress`: Yol tamponu görevin adres alanında değil.
+/// * `NotFound`: Dosya yok veya çağıran bir görev değil.
+/// * `OutOfMemoryStatic`: Eşleme tablosu veya adres penceresi dolu.
+pub fn sys_mmap(path: UserSlice, len: usize, prot: usize, flags: usize, offset: usize) -> Result<usize, KernelError> {
+    let task = sched::current().ok_or(KernelError::NotFound)?;
+    let pages = len.div_ceil(FRAME_SIZE);
+    let shared = match flags & (MAP_SHARED | MAP_PRIVATE) {
+        MAP_SHARED => true,
+        MAP_PRIVATE => false,
+        _ => return Err(KernelError::InvalidArgument),
+    };
+    if pages == 0 || pages > MAX_VMA_PAGES || !offset.is_multiple_of(FRAME_SIZE) || path.is_empty() || path.len() > MAX_PATH {
+        return Err(KernelError::InvalidArgument);
+    }
+
+    let mut path_buf = [0u8; MAX_PATH];
+    let path_len = path.read(&mut path_buf)?;
+    let name = core::str::from_utf8(&path_buf[..path_len]).map_err(|_| KernelError::InvalidArgument)?;
+    if vfs::stat(name)?.kind != FileType::File {
+        return Err(KernelError::InvalidArgument);
+    }
+
+    with_vmas(|vmas| {
+        // Pencerede ilk uyan boşluk
+        let size = pages * FRAME_SIZE;
+        let mut start = MMAP_BASE;
+        while let Some(end) = vmas.iter().flatten().filter(|v| v.start < start + size && start < v.end()).map(Vma::end).max() {
+            start = end;
+        }
+        if start + size > MMAP_END {
+            return Err(KernelError::OutOfMemoryStatic);
+        }
+        let slot = vmas.iter_mut().find(|v| v.is_none()).ok_or(KernelError::OutOfMemoryStatic)?;
+        *slot = Some(Vma {
+            task,
+            start,
+            pages,
+            path: path_buf,
+            path_len,
+            offset,
+            prot,
+            shared,
+            frames: [0; MAX_VMA_PAGES],
+            written: [0; BITMAP_WORDS],
+            pins: [0; MAX_VMA_PAGES],
+        });
+        Ok(start)
+    })
+}
+
+/// Çalışan görevin `addr` adresindeki eşlemesini kaldırır (paylaşımlıysa
+/// önce geri yazar).
+///
+/// # Hatalar
+/// * `InvalidArgument`: `addr`/`len` bütün bir eşlemeye karşılık gelmiyor.
+/// * Geri yazma hataları VFS'ten olduğu gibi döner; eşleme yine kaldırılır.
+pub fn sys_munmap(addr: usize, len: usize) -> Result<(), KernelError> {
+    let task = sched::current().ok_or(KernelError::NotFound)?;
+    with_vmas(|vmas| {
+        let index = vmas
+            .iter()
+            .position(|v| v.is_some_and(|v| v.task == task && v.start == addr && v.pages == len.div_ceil(FRAME_SIZE)))
+            .ok_or(KernelError::InvalidArgument)?;
+        let result = vmas[index].as_mut().map_or(Ok(()), |v| write_back(v, 0..MAX_VMA_PAGES));
+        release(vmas, index);
+        result
+    })
+}
+
+/// `[addr, addr + len)` aralığına düşen paylaşımlı eşleme sayfalarını
+/// dosyaya geri yazar.
+///
+/// # Hatalar
+/// * `InvalidArgument`: Aralık görevin hiçbir eşlemesine değmiyor.
+pub fn sys_msync(addr: usize, len: usize) -> Result<(), KernelError> {
+    let task = sched::current().ok_or(KernelError::NotFound)?;
+    let end = addr.saturating_add(len);
+    with_vmas(|vmas| {
+        let mut found = false;
+        for vma in vmas.iter_mut().flatten().filter(|v| v.task == task && v.start < end && addr < v.end()) {
+            found = true;
+            let first = addr.saturating_sub(vma.start) / FRAME_SIZE;
+            let last = (end - vma.start).div_ceil(FRAME_SIZE);
+            write_back(vma, first..last)?;
+        }
+        if found { Ok(()) } else { Err(KernelError::InvalidArgument) }
+    })
+}
+
+/// `mlock(addr, len)`: çalışan görevin aralığını sabitler (bkz. `pin_range`).
+pub fn sys_mlock(addr: usize, len: usize) -> Result<(), KernelError> {
+    pin_range(sched::current().ok_or(KernelError::NotFound)?, addr, len)
+}
+
+/// `munlock(addr, len)`: `sys_mlock` sabitlemesini bırakır.
+pub fn sys_munlock(addr: usize, len: usize) -> Result<(), KernelError> {
+    unpin_range(sched::current().ok_or(KernelError::NotFound)?, addr, len)
+}
+
+// -----------------------------------------------------------------------------
+// KABUK KOMUTU
+// -----------------------------------------------------------------------------
+
+fn maps_command(_args: &[&str]) -> Result<(), KernelError> {
+    serial_println!("  GÖREV  BAŞLANGIÇ          BİTİŞ              İZİN  YÜKLÜ  YAZILI  SABİT  DOSYA");
+    with_vmas(|vmas| {
+        for vma in vmas.iter().flatten() {
+            let loaded = vma.frames[..vma.pages].iter().filter(|&&f| f != 0).count();
+            let written = vma.written.iter().map(|w| w.count_ones()).sum::<u32>();
+            serial_println!(
+                "  {:>5}  {:#018x} {:#018x} {}{}{}{}  {:>5}  {:>6}  {:>5}  {}+{:#x}",
+                vma.task,
+                vma.start,
+                vma.end(),
+                if vma.prot & PROT_READ != 0 { 'r' } else { '-' },
+                if vma.prot & PROT_WRITE != 0 { 'w' } else { '-' },
+                if vma.prot & PROT_EXEC != 0 { 'x' } else { '-' },
+                if vma.shared { 's' } else { 'p' },
+                loaded,
+                written,
+                vma.pinned_pages(),
+                vma.path(),
+                vma.offset
+            );
+        }
+    });
+    serial_println!("  sabit sayfa: {} / {}", pinned_pages(), PIN_LIMIT.get());
+    Ok(())
+}
+
+crate::shell_command!("maps", "Görevlerin dosya eşlemelerini (mmap) listeler", maps_command);
@@ -0,0 +1,262 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/mmio.rs
+// Yazmaç bitlerini kesmeye ve diğer işlemcilere karşı güvenli değiştiren
+// MMIO yardımcıları (set/clear/toggle).
+//
+// GIC, PLIC ve UART sürücüleri aynı kalıbı tekrar tekrar açık yazıyordu:
+// yazmacı oku, biti değiştir, geri yaz. Bu oku-değiştir-yaz (RMW) dizisi
+// iki yerden bölünebilir: araya giren bir kesme işleyicisi aynı yazmacın
+// başka bir bitini değiştirirse ya da başka bir işlemci aynı anda aynı
+// yazmaca yazarsa değişikliklerden biri kaybolur (PLIC etkinleştirme
+// kelimesi 32 kesmeyi paylaşır, PL011 IMSC yazmacı TX/RX bitlerini).
+//
+// * Erişim, mimarinin `io::mmio_read_*`/`mmio_write_*` işlevleriyle yapılır;
+//   yazmanın ardından mimarinin G/Ç bariyeri çalışır (`dsb`, `fence`, `sync`,
+//   `dbar`, `msync`, `membar`; x86'da MMIO zaten sıralıdır, derleyici çiti
+//   yeterlidir).
+// * RMW, adrese göre seçilen 16 `IrqSafeSpinlock`'tan biri altında, yani
+//   yerel kesmeler kapalıyken yapılır. Kesme kapatma AMD64, ARMv9 ve
+//   RV64I'de uygulanır (`irqoff`);
+//   diğer mimarilerde yalnızca kilit vardır, bu yüzden oralarda aynı
+//   yazmaç kesme işleyicisinden değiştirilmemelidir.
+// * Donanım ayrı set/clear yazmaçları sunuyorsa (GIC ISENABLER/ICENABLER,
+//   PL011 ICR) RMW gerekmez; bu yardımcılar yalnızca paylaşılan yazmaçlar
+//   içindir.
+// * `try_read32`, isteğe bağlı çevre birimlerinin keşfi içindir: aygıt
+//   yoksa oluşan veri yolu hatası/veri iptali düzeltme tablosuyla (eşzamanlı
+//   hata) ya da `fixup::catch` ile (ARM SError gibi gecikmeli hata) yakalanır
+//   ve `None` döner.
+//
+// Kabuk komutu: yok; procfs: yok (sayaçlar: `mmio.rmw`, `mmio.probe_faults`)
+
+use core::ops::{BitAnd, BitOr, BitXor, Not};
+use crate::platformgeneric::spinlock::IrqSafeSpinlock;
+
+use crate::arch::io as arch_io;
+
+crate::kernel_counter!(RMW, "mmio.rmw");
+crate::kernel_counter!(PROBE_FAULTS, "mmio.probe_faults");
+
+// -----------------------------------------------------------------------------
+// YAZMAÇ GENİŞLİKLERİ
+// -----------------------------------------------------------------------------
+
+/// MMIO yazmaç genişliği (u8, u16, u32, u64).
+pub trait Register:
+    Copy + BitAnd<Output = Self> + BitOr<Output = Self> + BitXor<Output = Self> + Not<Output = Self>
+{
+    /// # Safety
+    /// `addr` eşlenmiş ve hizalı bir aygıt yazmacı olmalıdır.
+    unsafe fn load(addr: usize) -> Self;
+
+    /// # Safety
+    /// `addr` eşlenmiş ve hizalı bir aygıt yazmacı olmalıdır.
+    unsafe fn store(addr: usize, value: Self);
+}
+
+macro_rules! register {
+    ($ty:ty, $read:ident, $write:ident) => {
+        impl Register for $ty {
+            #[inline(always)]
+            unsafe fn load(addr: usize) -> Self {
+                arch_io::$read(addr)
+            }
+
+            #[inline(always)]
+            unsafe fn store(addr: usize, value: Self) {
+                arch_io::$write(addr, value)
+            }
+        }
+    };
+}
+
+register!(u8, mmio_read_u8, mmio_write_u8);
+register!(u16, mmio_read_u16, mmio_write_u16);
+register!(u32, mmio_read_u32, mmio_write_u32);
+register!(u64, mmio_read_u64, mmio_write_u64);
+
+// -----------------------------------------------------------------------------
+// BARİYER
+// -----------------------------------------------------------------------------
+
+/// Önceki MMIO yazmalarının aygıta ulaşmasını bekler.
+#[inline(always)]
+fn barrier() {
+    #[cfg(target_arch = "x86_64")]
+    core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+    #[cfg(target_arch = "aarch64")]
+    arch_io::dsb();
+    #[cfg(target_arch = "riscv64")]
+    arch_io::fence();
+    #[cfg(any(target_arch = "powerpc64", target_arch = "mips64"))]
+    arch_io::sync();
+    #[cfg(target_arch = "loongarch64")]
+    arch_io::dbar();
+    #[cfg(target_arch = "or1k")]
+    arch_io::msync();
+    #[cfg(target_arch = "sparc64")]
+    arch_io::membar_all();
+}
+
+// -----------------------------------------------------------------------------
+// ERİŞİM
+// -----------------------------------------------------------------------------
+
+const LOCK_COUNT: usize = 16;
+
+#[allow(clippy::declare_interior_mutable_const)]
+const UNLOCKED: IrqSafeSpinlock = IrqSafeSpinlock::new();
+static LOCKS: [IrqSafeSpinlock; LOCK_COUNT] = [UNLOCKED; LOCK_COUNT];
+
+/// Aynı 8 baytlık kelimedeki yazmaçlar aynı kilidi paylaşır.
+fn lock_for(addr: usize) -> &'static IrqSafeSpinlock {
+    &LOCKS[(addr >> 3) % LOCK_COUNT]
+}
+
+/// Yazmacı okur.
+///
+/// # Safety
+/// `addr` eşlenmiş ve hizalı bir aygıt yazmacı olmalıdır.
+#[inline(always)]
+pub unsafe fn read<T: Register>(addr: usize) -> T {
+    T::load(addr)
+}
+
+/// Yazmaca yazar ve yazmanın aygıta ulaşmasını bekler.
+///
+/// # Safety
+/// `addr` eşlenmiş ve hizalı bir aygıt yazmacı olmalıdır.
+#[inline(always)]
+pub unsafe fn write<T: Register>(addr: usize, value: T) {
+    T::store(addr, value);
+    barrier();
+}
+
+/// Yazmacı kilit altında ve yerel kesmeler kapalıyken `f` ile değiştirir;
+/// yazılan değeri döndürür.
+///
+/// # Safety
+/// `addr` eşlenmiş ve hizalı bir aygıt yazmacı olmalıdır; `f` kilidi
+/// yeniden almaya çalışmamalıdır.
+#[track_caller]
+pub unsafe fn modify<T: Register>(addr: usize, f: impl FnOnce(T) -> T) -> T {
+    let lock = lock_for(addr);
+    let flags = lock.lock();
+    let value = f(T::load(addr));
+    T::store(addr, value);
+    barrier();
+    lock.unlock(flags);
+    RMW.inc();
+    value
+}
+
+/// `mask` bitlerini 1 yapar.
+///
+/// # Safety
+/// Bkz. [`modify`].
+#[track_caller]
+pub unsafe fn set_bits<T: Register>(addr: usize, mask: T) {
+    modify(addr, |value: T| value | mask);
+}
+
+/// `mask` bitlerini 0 yapar.
+///
+/// # Safety
+/// Bkz. [`modify`].
+#[track_caller]
+pub unsafe fn clear_bits<T: Register>(addr: usize, mask: T) {
+    modify(addr, |value: T| value & !mask);
+}
+
+/// `mask` bitlerini tersine çevirir.
+///
+/// # Safety
+/// Bkz. [`modify`].
+#[track_caller]
+pub unsafe fn toggle_bits<T: Register>(addr: usize, mask: T) {
+    modify(addr, |value: T| value ^ mask);
+}
+
+/// `mask` alanını `value` ile değiştirir; alan dışındaki bitler korunur.
+///
+/// # Safety
+/// Bkz. [`modify`].
+#[track_caller]
+pub unsafe fn update_bits<T: Register>(addr: usize, mask: T, value: T) {
+    modify(addr, |old: T| (old & !mask) | (value & mask));
+}
+
+/// `enable`'a göre `mask` bitlerini 1 ya da 0 yapar.
+///
+/// # Safety
+/// Bkz. [`modify`].
+#[track_caller]
+pub unsafe fn assign_bits<T: Register>(addr: usize, mask: T, enable: bool) {
+    if enable {
+        set_bits(addr, mask)
+    } else {
+        clear_bits(addr, mask)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// HATA GÜVENLİ YOKLAMA
+// -----------------------------------------------------------------------------
+
+/// Gecikmeli veri yolu hatalarını (ARM SError) açar; önceki maskeyi döndürür.
+#[inline(always)]
+fn async_abort_unmask() -> usize {
+    #[cfg(target_arch = "aarch64")]
+    {
+        let flags: usize;
+        // SAFETY: Yalnızca DAIF.A okunur ve temizlenir.
+        unsafe { core::arch::asm!("mrs {}, daif", "msr daifclr, #4", out(reg) flags, options(nomem, nostack)) };
+        flags
+    }
+    #[cfg(not(target_arch = "aarch64"))]
+    0
+}
+
+#[inline(always)]
+fn async_abort_restore(flags: usize) {
+    // SAFETY: Yalnızca `async_abort_unmask`'in okuduğu durum geri yazılır.
+    #[cfg(target_arch = "aarch64")]
+    unsafe {
+        core::arch::asm!("msr daif, {}", in(reg) flags, options(nomem, nostack));
+    }
+    #[cfg(not(target_arch = "aarch64"))]
+    let _ = flags;
+}
+
+/// 32 bitlik bir aygıt yazmacını çekirdeği düşürmeden okur; aygıt yoksa
+/// (veri yolu hatası, veri iptali, eşlenmemiş adres) `None` döner.
+///
+/// Okuma düzeltme tablosundaki talimatla yapılır (eşzamanlı hatalar orada
+/// düzeltilir); ardından bariyer, gecikmeli hatanın (ARM SError) bu çağrı
+/// dönmeden ve `catch` gövdesi içindeyken alınmasını sağlar.
+///
+/// # Not
+/// x86'da olmayan aygıt çoğu zaman hata vermez, tüm bitleri 1 okur; çağıran
+/// kimlik yazmacını ayrıca doğrulamalıdır. Kesme bağlamında yapılan yoklama
+/// yalnızca eşzamanlı hatalardan korunur.
+pub fn try_read32(addr: usize) -> Option<u32> {
+    if addr % 4 != 0 {
+        return None;
+    }
+    // Maske gövdenin dışında geri yüklenir: hata gövdeyi yarıda bırakır
+    let flags = async_abort_unmask();
+    let value = crate::fixup::catch(|| {
+        let value = crate::fixup::probe_mmio_read32(addr).ok();
+        barrier();
+        #[cfg(target_arch = "aarch64")]
+        arch_io::isb();
+        value
+    });
+    async_abort_restore(flags);
+    let value = value.ok().flatten();
+    if value.is_none() {
+        PROBE_FAULTS.inc();
+    }
+    value
+}
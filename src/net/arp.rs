@@ -0,0 +1,210 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/net/arp.rs
+// ARP (RFC 826): IPv4 adreslerinin Ethernet adreslerine çözümlenmesi.
+//
+// Çözümlenen adresler küçük bir önbellekte `ENTRY_TTL_TICKS` boyunca tutulur.
+// Önbellekte olmayan bir adres için istek yayınlanır ve `resolve`
+// `ResourceBusy` döner; paket atılır ve üst katman (DNS, DHCP yenileme vb.)
+// yeniden dener. Bize yönelik isteklere yanıt verilir ve isteği gönderenin
+// adresi önbelleğe alınır.
+
+use core::cell::UnsafeCell;
+use crate::net::net::{self, Ipv4Addr, MacAddr};
+use crate::platformgeneric::spinlock::Spinlock;
+use crate::platformgeneric::{KernelError, SystemConstants};
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// SABİTLER
+// -----------------------------------------------------------------------------
+
+const PACKET_LEN: usize = 28;
+const HTYPE_ETHERNET: u16 = 1;
+const OP_REQUEST: u16 = 1;
+const OP_REPLY: u16 = 2;
+
+/// Önbellek kapasitesi.
+const CACHE_SIZE: usize = 16;
+/// Önbellek girdisinin geçerlilik süresi (5 dakika).
+const ENTRY_TTL_TICKS: u64 = 300 * SystemConstants::TIMER_TICK_HZ;
+/// Aynı adres için art arda istekler arasındaki en kısa süre (100 ms).
+const REQUEST_INTERVAL_TICKS: u64 = SystemConstants::TIMER_TICK_HZ / 10;
+
+crate::kernel_counter!(REQUESTS_SENT, "net.arp.requests");
+crate::kernel_counter!(REPLIES_SENT, "net.arp.replies");
+
+// -----------------------------------------------------------------------------
+// ÖNBELLEK
+// -----------------------------------------------------------------------------
+
+#[derive(Clone, Copy)]
+struct Entry {
+    iface: usize,
+    ip: Ipv4Addr,
+    /// `None`: istek gönderildi, yanıt bekleniyor.
+    mac: Option<MacAddr>,
+    /// Çözümlenmiş girdide geçerlilik sonu, bekleyende son istek tiki.
+    stamp: u64,
+}
+
+struct Cache {
+    lock: Spinlock,
+    entries: UnsafeCell<[Option<Entry>; CACHE_SIZE]>,
+}
+
+// Tabloya erişim `lock` ile korunur.
+unsafe impl Sync for Cache {}
+
+static CACHE: Cache = Cache {
+    lock: Spinlock::new(),
+    entries: UnsafeCell::new([None; CACHE_SIZE]),
+};
+
+fn with_cache<R>(f: impl FnOnce(&mut [Option<Entry>; CACHE_SIZE]) -> R) -> R {
+    CACHE.lock.lock();
+    let result = f(unsafe { &mut *CACHE.entries.get() });
+    CACHE.lock.unlock();
+    result
+}
+
+/// Girdiyi ekler veya günceller; tablo doluysa en eski girdinin yerine yazar.
+fn store(entries: &mut [Option<Entry>; CACHE_SIZE], entry: Entry) {
+    let index = entries
+        .iter()
+        .position(|e| e.is_some_and(|e| e.iface == entry.iface && e.ip == entry.ip))
+        .or_else(|| entries.iter().position(|e| e.is_none()))
+        .unwrap_or_else(|| {
+            (0..CACHE_SIZE).min_by_key(|&i| entries[i].map_or(0, |e| e.stamp)).unwrap_or(0)
+        });
+    entries[index] = Some(entry);
+}
+
+/// Adresi önbellekten çözümler; yoksa istek yayınlar.
+///
+/// # Dönüş Değeri
+/// Donanım adresi; istek gönderildiyse (veya bekliyorsa) `ResourceBusy`.
+pub fn resolve(iface: usize, ip: Ipv4Addr) -> Result<MacAddr, KernelError> {
+    enum Lookup {
+        Hit(MacAddr),
+        Pending,
+        Request,
+    }
+
+    let now = crate::sched::ticks();
+    let lookup = with_cache(|entries| {
+        match entries.iter().flatten().find(|e| e.iface == iface && e.ip == ip) {
+            Some(Entry { mac: Some(mac), stamp, .. }) if *stamp > now => Lookup::Hit(*mac),
+            Some(Entry { mac: None, stamp, .. }) if now - *stamp < REQUEST_INTERVAL_TICKS => Lookup::Pending,
+            _ => {
+                store(entries, Entry { iface, ip, mac: None, stamp: now });
+                Lookup::Request
+            }
+        }
+    });
+
+    match lookup {
+        Lookup::Hit(mac) => Ok(mac),
+        Lookup::Pending => Err(KernelError::ResourceBusy),
+        Lookup::Request => {
+            send(iface, OP_REQUEST, MacAddr::BROADCAST, MacAddr::ZERO, ip)?;
+            REQUESTS_SENT.inc();
+            Err(KernelError::ResourceBusy)
+        }
+    }
+}
+
+/// Arayüzün önbellek girdilerini siler (adres değiştiğinde).
+pub fn flush(iface: usize) {
+    with_cache(|entries| {
+        for entry in entries.iter_mut().filter(|e| e.is_some_and(|e| e.iface == iface)) {
+            *entry = None;
+        }
+    });
+}
+
+// -----------------------------------------------------------------------------
+// PAKETLER
+// -----------------------------------------------------------------------------
+
+fn send(iface: usize, op: u16, eth_dst: MacAddr, target_mac: MacAddr, target_ip: Ipv4Addr) -> Result<(), KernelError> {
+    let info = net::interface(iface).ok_or(KernelError::NotFound)?;
+    let mut packet = [0u8; PACKET_LEN];
+    packet[0..2].copy_from_slice(&HTYPE_ETHERNET.to_be_bytes());
+    packet[2..4].copy_from_slice(&net::ETHERTYPE_IPV4.to_be_bytes());
+    packet[4] = 6;
+    packet[5] = 4;
+    packet[6..8].copy_from_slice(&op.to_be_bytes());
+    packet[8..14].copy_from_slice(&info.dev.mac().0);
+    packet[14..18].copy_from_slice(&info.addr.0);
+    packet[18..24].copy_from_slice(&target_mac.0);
+    packet[24..28].copy_from_slice(&target_ip.0);
+    net::transmit(iface, eth_dst, net::ETHERTYPE_ARP, &packet)
+}
+
+/// Gelen ARP paketini işler (`net::poll` içinden).
+pub fn receive(iface: usize, packet: &[u8]) {
+    if packet.len() < PACKET_LEN
+        || u16::from_be_bytes([packet[0], packet[1]]) != HTYPE_ETHERNET
+        || u16::from_be_bytes([packet[2], packet[3]]) != net::ETHERTYPE_IPV4
+        || packet[4] != 6
+        || packet[5] != 4
+    {
+        return;
+    }
+    let Some(info) = net::interface(iface) else { return };
+    let op = u16::from_be_bytes([packet[6], packet[7]]);
+    let mut sender_mac = MacAddr::ZERO;
+    sender_mac.0.copy_from_slice(&packet[8..14]);
+    let sender_ip = Ipv4Addr([packet[14], packet[15], packet[16], packet[17]]);
+    let target_ip = Ipv4Addr([packet[24], packet[25], packet[26], packet[27]]);
+    let for_us = !info.addr.is_unspecified() && target_ip == info.addr;
+
+    // Bilinen girdiler her pakette tazelenir; yeni girdi yalnızca bize
+    // yönelik paketlerden veya beklenen yanıtlardan oluşturulur
+    let expires = crate::sched::ticks() + ENTRY_TTL_TICKS;
+    with_cache(|entries| {
+        let known = entries.iter().flatten().any(|e| e.iface == iface && e.ip == sender_ip);
+        if (known || for_us) && !sender_ip.is_unspecified() {
+            store(entries, Entry { iface, ip: sender_ip, mac: Some(sender_mac), stamp: expires });
+        }
+    });
+
+    if op == OP_REQUEST && for_us && send(iface, OP_REPLY, sender_mac, sender_mac, sender_ip).is_ok() {
+        REPLIES_SENT.inc();
+    }
+}
+
+// -----------------------------------------------------------------------------
+// KABUK VE PROCFS
+// -----------------------------------------------------------------------------
+
+fn entries() -> [Option<Entry>; CACHE_SIZE] {
+    with_cache(|entries| *entries)
+}
+
+fn arp_command(_args: &[&str]) -> Result<(), KernelError> {
+    let now = crate::sched::ticks();
+    for entry in entries().iter().flatten() {
+        let name = net::interface(entry.iface).map_or("?", |i| i.dev.name());
+        match entry.mac {
+            Some(mac) if entry.stamp > now => serial_println!("  {} {} {}", entry.ip, mac, name),
+            Some(_) => serial_println!("  {} (süresi doldu) {}", entry.ip, name),
+            None => serial_println!("  {} (çözümleniyor) {}", entry.ip, name),
+        }
+    }
+    Ok(())
+}
+
+crate::shell_command!("arp", "ARP önbelleğini gösterir", arp_command);
+
+fn show_arp(out: &mut dyn core::fmt::Write) -> core::fmt::Result {
+    for entry in entries().iter().flatten() {
+        if let Some(mac) = entry.mac {
+            writeln!(out, "{} {} {}", entry.ip, mac, entry.iface)?;
+        }
+    }
+    Ok(())
+}
+
+crate::proc_entry!("net/arp", show_arp);
@@ -0,0 +1,647 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/net/dhcp.rs
+// DHCP istemcisi (RFC 2131): ilk Ethernet arayüzü için adres kiralama.
+//
+// İstemci ayrı bir görevde çalışır: DISCOVER → OFFER → REQUEST → ACK ile
+// adres alınır, arayüz (adres, maske, ağ geçidi) ve DNS sunucuları
+// yapılandırılır. Kira süresinin yarısında (T1) zamanlama çarkındaki bir
+// zamanlayıcı görevi uyandırır ve kira sunucuya tek yönlü (unicast) istekle
+// yenilenir; T2'de (süre %87.5) yayınla yeniden bağlanılır; kira dolarsa
+// adres bırakılıp baştan başlanır. Sunucudan yanıtları yayınla istemek için
+// BROADCAST bayrağı her zaman ayarlanır.
+//
+// Kalıcı `NetConfig` (`cfg.net`) DHCP'yi kapatıp sabit adres verebilir; bu
+// durumda görev başlatılmaz ve arayüz açılışta doğrudan yapılandırılır.
+
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use crate::config::{self, Config, TlvReader, TlvWriter};
+use crate::net::net::{self, Ipv4Addr};
+use crate::net::socket::{Protocol, Socket};
+use crate::platformgeneric::spinlock::Spinlock;
+use crate::platformgeneric::{KernelError, SystemConstants};
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// SABİTLER
+// -----------------------------------------------------------------------------
+
+const CLIENT_PORT: u16 = 68;
+const SERVER_PORT: u16 = 67;
+
+const OP_REQUEST: u8 = 1;
+const OP_REPLY: u8 = 2;
+const HTYPE_ETHERNET: u8 = 1;
+const FLAG_BROADCAST: u16 = 0x8000;
+const MAGIC_COOKIE: [u8; 4] = [99, 130, 83, 99];
+/// Sabit alanlar ve sihirli çerez; seçenekler bu konumda başlar.
+const OPTIONS_OFFSET: usize = 240;
+const MAX_MESSAGE: usize = 576;
+
+// Seçenekler
+const OPT_PAD: u8 = 0;
+const OPT_SUBNET_MASK: u8 = 1;
+const OPT_ROUTER: u8 = 3;
+const OPT_DNS: u8 = 6;
+const OPT_REQUESTED_IP: u8 = 50;
+const OPT_LEASE_TIME: u8 = 51;
+const OPT_MESSAGE_TYPE: u8 = 53;
+const OPT_SERVER_ID: u8 = 54;
+const OPT_PARAM_LIST: u8 = 55;
+const OPT_RENEWAL_TIME: u8 = 58;
+const OPT_REBINDING_TIME: u8 = 59;
+const OPT_END: u8 = 255;
+
+// İleti türleri
+const DHCPDISCOVER: u8 = 1;
+const DHCPOFFER: u8 = 2;
+const DHCPREQUEST: u8 = 3;
+const DHCPACK: u8 = 5;
+const DHCPNAK: u8 = 6;
+const DHCPRELEASE: u8 = 7;
+
+/// İlk yeniden gönderim süresi (4 s); her denemede ikiye katlanır.
+const INITIAL_TIMEOUT_SECS: u64 = 4;
+const MAX_TIMEOUT_SECS: u64 = 64;
+/// Yenileme/yeniden bağlanma durumunda istekler arası en kısa süre.
+const MIN_RETRY_SECS: u64 = 60;
+/// DHCP görevinin önceliği.
+const TASK_PRIORITY: u8 = 20;
+
+const TICK_HZ: u64 = SystemConstants::TIMER_TICK_HZ;
+
+// -----------------------------------------------------------------------------
+// KİRA DURUMU
+// -----------------------------------------------------------------------------
+
+/// İstemci durumu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum State {
+    Init,
+    Selecting,
+    Requesting,
+    Bound,
+    Renewing,
+    Rebinding,
+}
+
+/// Alınan kira.
+#[derive(Debug, Clone, Copy)]
+pub struct Lease {
+    pub addr: Ipv4Addr,
+    pub netmask: Ipv4Addr,
+    pub gateway: Ipv4Addr,
+    pub server: Ipv4Addr,
+    pub dns: [Ipv4Addr; 2],
+    /// Kira süresi, T1 ve T2 (saniye).
+    pub lease_secs: u32,
+    pub t1_secs: u32,
+    pub t2_secs: u32,
+    /// Kiranın alındığı / son yenilendiği tik.
+    pub acquired_at: u64,
+}
+
+impl Lease {
+    const EMPTY: Lease = Lease {
+        addr: Ipv4Addr::UNSPECIFIED,
+        netmask: Ipv4Addr::UNSPECIFIED,
+        gateway: Ipv4Addr::UNSPECIFIED,
+        server: Ipv4Addr::UNSPECIFIED,
+        dns: [Ipv4Addr::UNSPECIFIED; 2],
+        lease_secs: 0,
+        t1_secs: 0,
+        t2_secs: 0,
+        acquired_at: 0,
+    };
+
+    fn deadline(&self, secs: u32) -> u64 {
+        self.acquired_at.saturating_add(u64::from(secs) * TICK_HZ)
+    }
+}
+
+struct Client {
+    lock: Spinlock,
+    state: UnsafeCell<State>,
+    lease: UnsafeCell<Lease>,
+}
+
+// Tüm alanlara erişim `lock` ile korunur.
+unsafe impl Sync for Client {}
+
+static CLIENT: Client = Client {
+    lock: Spinlock::new(),
+    state: UnsafeCell::new(State::Init),
+    lease: UnsafeCell::new(Lease::EMPTY),
+};
+
+/// İstemcinin yönettiği arayüz ve görev.
+static IFACE: AtomicUsize = AtomicUsize::new(usize::MAX);
+static TASK: AtomicUsize = AtomicUsize::new(usize::MAX);
+/// Yenileme zamanlayıcısı doldu (veya `dhcp renew` istendi).
+static RENEW_DUE: AtomicBool = AtomicBool::new(false);
+
+fn with_client<R>(f: impl FnOnce(&mut State, &mut Lease) -> R) -> R {
+    CLIENT.lock.lock();
+    let result = unsafe { f(&mut *CLIENT.state.get(), &mut *CLIENT.lease.get()) };
+    CLIENT.lock.unlock();
+    result
+}
+
+/// İstemcinin durumu ve geçerli kira.
+pub fn status() -> (State, Lease) {
+    with_client(|state, lease| (*state, *lease))
+}
+
+fn set_state(new: State) {
+    with_client(|state, _| *state = new);
+}
+
+// -----------------------------------------------------------------------------
+// İLETİLER
+// -----------------------------------------------------------------------------
+
+/// Yanıttan çıkarılan bilgiler.
+#[derive(Clone, Copy)]
+struct Reply {
+    kind: u8,
+    yiaddr: Ipv4Addr,
+    server: Ipv4Addr,
+    netmask: Ipv4Addr,
+    router: Ipv4Addr,
+    dns: [Ipv4Addr; 2],
+    lease_secs: u32,
+    t1_secs: u32,
+    t2_secs: u32,
+}
+
+struct Message {
+    buf: [u8; MAX_MESSAGE],
+    len: usize,
+}
+
+impl Message {
+    /// İstek iletisinin sabit alanlarını ve ileti türü seçeneğini yazar.
+    fn new(kind: u8, xid: u32, ciaddr: Ipv4Addr, mac: &[u8; 6]) -> Self {
+        let mut msg = Message { buf: [0; MAX_MESSAGE], len: OPTIONS_OFFSET };
+        msg.buf[0] = OP_REQUEST;
+        msg.buf[1] = HTYPE_ETHERNET;
+        msg.buf[2] = 6;
+        msg.buf[4..8].copy_from_slice(&xid.to_be_bytes());
+        msg.buf[10..12].copy_from_slice(&FLAG_BROADCAST.to_be_bytes());
+        msg.buf[12..16].copy_from_slice(&ciaddr.0);
+        msg.buf[28..34].copy_from_slice(mac);
+        msg.buf[236..240].copy_from_slice(&MAGIC_COOKIE);
+        msg.option(OPT_MESSAGE_TYPE, &[kind]);
+        msg
+    }
+
+    fn option(&mut self, code: u8, data: &[u8]) {
+        self.buf[self.len] = code;
+        self.buf[self.len + 1] = data.len() as u8;
+        self.buf[self.len + 2..self.len + 2 + data.len()].copy_from_slice(data);
+        self.len += 2 + data.len();
+    }
+
+    fn finish(&mut self) -> &[u8] {
+        self.option(OPT_PARAM_LIST, &[OPT_SUBNET_MASK, OPT_ROUTER, OPT_DNS, OPT_LEASE_TIME, OPT_RENEWAL_TIME, OPT_REBINDING_TIME]);
+        self.buf[self.len] = OPT_END;
+        self.len += 1;
+        &self.buf[..self.len]
+    }
+}
+
+fn addr_at(data: &[u8]) -> Ipv4Addr {
+    Ipv4Addr([data[0], data[1], data[2], data[3]])
+}
+
+/// Sunucu yanıtını doğrular ve seçeneklerini çözümler.
+fn parse_reply(msg: &[u8], xid: u32, mac: &[u8; 6]) -> Option<Reply> {
+    if msg.len() < OPTIONS_OFFSET
+        || msg[0] != OP_REPLY
+        || msg[4..8] != xid.to_be_bytes()
+        || msg[28..34] != mac[..]
+        || msg[236..240] != MAGIC_COOKIE
+    {
+        return None;
+    }
+    let mut reply = Reply {
+        kind: 0,
+        yiaddr: addr_at(&msg[16..20]),
+        server: Ipv4Addr::UNSPECIFIED,
+        netmask: Ipv4Addr::UNSPECIFIED,
+        router: Ipv4Addr::UNSPECIFIED,
+        dns: [Ipv4Addr::UNSPECIFIED; 2],
+        lease_secs: 0,
+        t1_secs: 0,
+        t2_secs: 0,
+    };
+
+    let mut pos = OPTIONS_OFFSET;
+    while let Some(&code) = msg.get(pos) {
+        if code == OPT_END {
+            break;
+        }
+        if code == OPT_PAD {
+            pos += 1;
+            continue;
+        }
+        let len = usize::from(*msg.get(pos + 1)?);
+        let data = msg.get(pos + 2..pos + 2 + len)?;
+        let u32_at = |d: &[u8]| u32::from_be_bytes([d[0], d[1], d[2], d[3]]);
+        match (code, len) {
+            (OPT_MESSAGE_TYPE, 1) => reply.kind = data[0],
+            (OPT_SUBNET_MASK, 4) => reply.netmask = addr_at(data),
+            (OPT_ROUTER, l) if l >= 4 => reply.router = addr_at(data),
+            (OPT_SERVER_ID, 4) => reply.server = addr_at(data),
+            (OPT_LEASE_TIME, 4) => reply.lease_secs = u32_at(data),
+            (OPT_RENEWAL_TIME, 4) => reply.t1_secs = u32_at(data),
+            (OPT_REBINDING_TIME, 4) => reply.t2_secs = u32_at(data),
+            (OPT_DNS, l) if l >= 4 => {
+                for (slot, chunk) in reply.dns.iter_mut().zip(data.chunks_exact(4)) {
+                    *slot = addr_at(chunk);
+                }
+            }
+            _ => {}
+        }
+        pos += 2 + len;
+    }
+    (reply.kind != 0).then_some(reply)
+}
+
+// -----------------------------------------------------------------------------
+// İSTEMCİ
+// -----------------------------------------------------------------------------
+
+/// İstemcinin bir çalışması boyunca sabit kalan bilgiler.
+struct Session {
+    sock: Socket,
+    iface: usize,
+    mac: [u8; 6],
+    xid: u32,
+}
+
+impl Session {
+    /// Yeni bir işlem kimliği (transaction id) seçer.
+    fn next_xid(&mut self) {
+        let seed = u32::from_be_bytes([self.mac[2], self.mac[3], self.mac[4], self.mac[5]]);
+        self.xid = seed ^ (crate::sched::ticks() as u32).wrapping_mul(0x9E37_79B9);
+    }
+
+    /// İletiyi gönderir; `server` belirtilmemişse yayınlar.
+    fn send(&self, data: &[u8], server: Option<Ipv4Addr>) -> Result<(), KernelError> {
+        match server {
+            Some(server) => self.sock.send_to(server, SERVER_PORT, data),
+            None => self.sock.send_broadcast(self.iface, SERVER_PORT, data),
+        }
+    }
+
+    /// `timeout_ticks` boyunca `kinds` türlerinden biriyle gelen yanıtı bekler.
+    fn wait_reply(&self, kinds: &[u8], timeout_ticks: u64) -> Option<Reply> {
+        let deadline = crate::sched::ticks() + timeout_ticks;
+        let mut buf = [0u8; MAX_MESSAGE];
+        loop {
+            let remaining = deadline.checked_sub(crate::sched::ticks()).filter(|&t| t > 0)?;
+            let Ok((len, _, port)) = self.sock.recv_timeout(&mut buf, remaining) else { return None };
+            if port != SERVER_PORT {
+                continue;
+            }
+            if let Some(reply) = parse_reply(&buf[..len], self.xid, &self.mac) {
+                if kinds.contains(&reply.kind) {
+                    return Some(reply);
+                }
+            }
+        }
+    }
+
+    /// Seçme aşaması: teklif gelene kadar geri çekilmeli DISCOVER.
+    fn discover(&mut self) -> Reply {
+        set_state(State::Selecting);
+        let mut timeout = INITIAL_TIMEOUT_SECS;
+        loop {
+            self.next_xid();
+            let mut msg = Message::new(DHCPDISCOVER, self.xid, Ipv4Addr::UNSPECIFIED, &self.mac);
+            if self.send(msg.finish(), None).is_ok() {
+                if let Some(offer) = self.wait_reply(&[DHCPOFFER], timeout * TICK_HZ) {
+                    return offer;
+                }
+            }
+            timeout = (timeout * 2).min(MAX_TIMEOUT_SECS);
+        }
+    }
+
+    /// Teklif edilen adresi ister (SELECTING → REQUESTING).
+    fn request(&mut self, offer: &Reply) -> Option<Reply> {
+        set_state(State::Requesting);
+        for _ in 0..4 {
+            let mut msg = Message::new(DHCPREQUEST, self.xid, Ipv4Addr::UNSPECIFIED, &self.mac);
+            msg.option(OPT_REQUESTED_IP, &offer.yiaddr.0);
+            msg.option(OPT_SERVER_ID, &offer.server.0);
+            if self.send(msg.finish(), None).is_ok() {
+                if let Some(reply) = self.wait_reply(&[DHCPACK, DHCPNAK], INITIAL_TIMEOUT_SECS * TICK_HZ) {
+                    return (reply.kind == DHCPACK).then_some(reply);
+                }
+            }
+        }
+        None
+    }
+
+    /// Kirayı yeniler: `unicast` ise sunucuya (RENEWING), değilse yayınla
+    /// (REBINDING). `until` tikine kadar aralıklarla denenir.
+    fn renew(&mut self, lease: &Lease, unicast: bool, until: u64) -> Option<Reply> {
+        set_state(if unicast { State::Renewing } else { State::Rebinding });
+        self.next_xid();
+        while crate::sched::ticks() < until {
+            let mut msg = Message::new(DHCPREQUEST, self.xid, lease.addr, &self.mac);
+            let data = msg.finish();
+            let server = unicast.then_some(lease.server);
+            // Sunucu adresinin ARP çözümlemesi ilk gönderimi geciktirebilir
+            let mut sent = self.send(data, server);
+            if sent == Err(KernelError::ResourceBusy) {
+                net::poll_for(TICK_HZ / 10);
+                sent = self.send(data, server);
+            }
+            // RFC 2131 4.4.5: kalan sürenin yarısı kadar (en az 60 s) beklenir
+            let wait = (until.saturating_sub(crate::sched::ticks()) / 2).max(MIN_RETRY_SECS * TICK_HZ);
+            if sent.is_ok() {
+                if let Some(reply) = self.wait_reply(&[DHCPACK, DHCPNAK], INITIAL_TIMEOUT_SECS * TICK_HZ) {
+                    return (reply.kind == DHCPACK).then_some(reply);
+                }
+            }
+            sleep_until(crate::sched::ticks() + wait.min(until.saturating_sub(crate::sched::ticks())));
+        }
+        None
+    }
+
+    /// ACK ile gelen kirayı uygular.
+    fn bind(&self, ack: &Reply) -> Lease {
+        let lease_secs = if ack.lease_secs == 0 { u32::MAX } else { ack.lease_secs };
+        let lease = Lease {
+            addr: ack.yiaddr,
+            netmask: if ack.netmask.is_unspecified() { Ipv4Addr([255, 255, 255, 0]) } else { ack.netmask },
+            gateway: ack.router,
+            server: ack.server,
+            dns: ack.dns,
+            lease_secs,
+            t1_secs: if ack.t1_secs == 0 { lease_secs / 2 } else { ack.t1_secs },
+            t2_secs: if ack.t2_secs == 0 { (u64::from(lease_secs) * 7 / 8) as u32 } else { ack.t2_secs },
+            acquired_at: crate::sched::ticks(),
+        };
+        let previous = status().1;
+        if previous.addr != lease.addr || previous.gateway != lease.gateway || previous.netmask != lease.netmask {
+            let _ = net::configure(self.iface, lease.addr, lease.netmask, lease.gateway);
+            crate::klog!(net::LOG, Info, "[DHCP] {}/{} ağ geçidi {} ({} s kira)", lease.addr, lease.netmask, lease.gateway, lease.lease_secs);
+        }
+        let servers = lease.dns.iter().filter(|d| !d.is_unspecified()).count();
+        crate::net::dns::set_servers(&lease.dns[..servers]);
+        with_client(|state, stored| {
+            *state = State::Bound;
+            *stored = lease;
+        });
+        lease
+    }
+
+    /// Kirayı bırakır ve arayüzün adresini siler.
+    fn unbind(&self) {
+        let _ = net::configure(self.iface, Ipv4Addr::UNSPECIFIED, Ipv4Addr::UNSPECIFIED, Ipv4Addr::UNSPECIFIED);
+        with_client(|state, lease| {
+            *state = State::Init;
+            *lease = Lease::EMPTY;
+        });
+        crate::klog!(net::LOG, Warn, "[DHCP] Kira sona erdi, adres bırakıldı");
+    }
+}
+
+fn on_renew_timer(task: usize) {
+    RENEW_DUE.store(true, Ordering::Release);
+    let _ = crate::sched::wake(task);
+}
+
+/// `deadline` tikine kadar veya `dhcp renew` istenene kadar bekler.
+///
+/// Bekleme, zamanlama çarkına kurulan bir zamanlayıcıyla uyandırılan
+/// görev bloklamasıdır; görev bağlamı yoksa meşgul beklenir.
+fn sleep_until(deadline: u64) {
+    RENEW_DUE.store(false, Ordering::Release);
+    let delay = deadline.saturating_sub(crate::sched::ticks());
+    if delay == 0 {
+        return;
+    }
+    let me = crate::sched::current();
+    let timer = me.and_then(|id| crate::timer::start(delay, 0, on_renew_timer, id).ok());
+    while !RENEW_DUE.load(Ordering::Acquire) && crate::sched::ticks() < deadline {
+        match me {
+            Some(id) if timer.is_some() => {
+                let _ = crate::sched::block(id);
+                // Uyandırma bloklamadan önce geldiyse görev hazır kalır
+                if RENEW_DUE.load(Ordering::Acquire) {
+                    let _ = crate::sched::wake(id);
+                }
+                while !crate::sched::is_runnable(id) {
+                    core::hint::spin_loop();
+                }
+            }
+            _ => core::hint::spin_loop(),
+        }
+    }
+    if let Some(timer) = timer {
+        let _ = crate::timer::cancel(timer);
+    }
+}
+
+/// DHCP görevinin giriş noktası.
+fn dhcp_task(_arg: u64) {
+    let iface = IFACE.load(Ordering::Acquire);
+    let Some(info) = net::interface(iface) else { return };
+    let mut session = match Socket::open(Protocol::Udp).and_then(|sock| sock.bind(CLIENT_PORT).map(|_| sock)) {
+        Ok(sock) => Session { sock, iface, mac: info.dev.mac().0, xid: 0 },
+        Err(e) => {
+            crate::klog!(net::LOG, Error, "[DHCP] Soket açılamadı: {:?}", e);
+            return;
+        }
+    };
+
+    loop {
+        let offer = session.discover();
+        let Some(ack) = session.request(&offer) else { continue };
+        let mut lease = session.bind(&ack);
+
+        // Bağlı: T1'de yenileme, T2'de yeniden bağlanma, süre dolunca baştan
+        loop {
+            sleep_until(lease.deadline(lease.t1_secs));
+            let renewed = session
+                .renew(&lease, true, lease.deadline(lease.t2_secs))
+                .or_else(|| session.renew(&lease, false, lease.deadline(lease.lease_secs)));
+            match renewed {
+                Some(ack) => lease = session.bind(&ack),
+                None => {
+                    session.unbind();
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// İlk Ethernet arayüzü için DHCP görevini başlatır; kalıcı ayarlar sabit
+/// adres istiyorsa arayüzü doğrudan yapılandırır.
+fn init() -> Result<(), KernelError> {
+    let Some((iface, info)) = net::interfaces().find(|(_, i)| !i.dev.is_loopback()) else {
+        return Ok(());
+    };
+    let cfg = config::get::<NetConfig>();
+    if !cfg.dhcp {
+        net::configure(iface, cfg.addr, cfg.netmask, cfg.gateway)?;
+        if !cfg.dns.is_unspecified() {
+            crate::net::dns::set_servers(&[cfg.dns]);
+        }
+        crate::klog!(net::LOG, Info, "[DHCP] {}: sabit adres {}/{} ağ geçidi {}", info.dev.name(), cfg.addr, cfg.netmask, cfg.gateway);
+        return Ok(());
+    }
+    IFACE.store(iface, Ordering::Release);
+    let task = crate::sched::spawn("dhcp", dhcp_task as usize, TASK_PRIORITY)?;
+    TASK.store(task, Ordering::Release);
+    crate::klog!(net::LOG, Info, "[DHCP] {} için istemci başlatıldı", info.dev.name());
+    Ok(())
+}
+
+crate::initcall!(late, "dhcp", init);
+
+// -----------------------------------------------------------------------------
+// KALICI AYARLAR
+// -----------------------------------------------------------------------------
+
+/// İlk Ethernet arayüzünün kalıcı adres ayarları.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NetConfig {
+    /// Adres DHCP ile mi alınır? `false` ise aşağıdaki sabit adres kullanılır.
+    pub dhcp: bool,
+    pub addr: Ipv4Addr,
+    pub netmask: Ipv4Addr,
+    pub gateway: Ipv4Addr,
+    /// DNS sunucusu (0.0.0.0: yok).
+    pub dns: Ipv4Addr,
+}
+
+impl Default for NetConfig {
+    fn default() -> Self {
+        NetConfig {
+            dhcp: true,
+            addr: Ipv4Addr::UNSPECIFIED,
+            netmask: Ipv4Addr([255, 255, 255, 0]),
+            gateway: Ipv4Addr::UNSPECIFIED,
+            dns: Ipv4Addr::UNSPECIFIED,
+        }
+    }
+}
+
+// `cfg.net` TLV etiketleri; silinen etiketlerin numaraları yeniden kullanılmaz
+const TAG_DHCP: u8 = 1;
+const TAG_ADDR: u8 = 2;
+const TAG_NETMASK: u8 = 3;
+const TAG_GATEWAY: u8 = 4;
+const TAG_DNS: u8 = 5;
+
+impl Config for NetConfig {
+    const KEY: &'static str = "cfg.net";
+    const VERSION: u16 = 1;
+
+    fn encode(&self, out: &mut TlvWriter) -> Result<(), KernelError> {
+        out.bool(TAG_DHCP, self.dhcp)?;
+        out.bytes(TAG_ADDR, &self.addr.0)?;
+        out.bytes(TAG_NETMASK, &self.netmask.0)?;
+        out.bytes(TAG_GATEWAY, &self.gateway.0)?;
+        out.bytes(TAG_DNS, &self.dns.0)
+    }
+
+    fn decode(&mut self, fields: &TlvReader) {
+        self.dhcp = fields.bool(TAG_DHCP).unwrap_or(self.dhcp);
+        self.addr = fields.array(TAG_ADDR).map_or(self.addr, Ipv4Addr);
+        self.netmask = fields.array(TAG_NETMASK).map_or(self.netmask, Ipv4Addr);
+        self.gateway = fields.array(TAG_GATEWAY).map_or(self.gateway, Ipv4Addr);
+        self.dns = fields.array(TAG_DNS).map_or(self.dns, Ipv4Addr);
+    }
+}
+
+/// `netcfg [dhcp | static <adres> [maske] [ağ geçidi] [dns] | reset]`:
+/// kalıcı ayarları gösterir veya değiştirir (sonraki açılışta geçerli).
+fn netcfg_command(args: &[&str]) -> Result<(), KernelError> {
+    let parse = |i: usize, default: Ipv4Addr| args.get(i).map_or(Some(default), |s| Ipv4Addr::parse(s)).ok_or(KernelError::InvalidArgument);
+    match args.get(1).copied() {
+        None => {
+            let cfg = config::get::<NetConfig>();
+            if cfg.dhcp {
+                serial_println!("  dhcp");
+            } else {
+                serial_println!("  sabit {}/{} ağ geçidi {} dns {}", cfg.addr, cfg.netmask, cfg.gateway, cfg.dns);
+            }
+            return Ok(());
+        }
+        Some("dhcp") => config::set(&NetConfig { dhcp: true, ..config::get() })?,
+        Some("static") => {
+            let defaults = NetConfig::default();
+            let cfg = NetConfig {
+                dhcp: false,
+                addr: Ipv4Addr::parse(args.get(2).ok_or(KernelError::InvalidArgument)?).ok_or(KernelError::InvalidArgument)?,
+                netmask: parse(3, defaults.netmask)?,
+                gateway: parse(4, defaults.gateway)?,
+                dns: parse(5, defaults.dns)?,
+            };
+            config::set(&cfg)?;
+        }
+        Some("reset") => config::reset::<NetConfig>()?,
+        Some(_) => return Err(KernelError::InvalidArgument),
+    }
+    serial_println!("  Kaydedildi; sonraki açılışta geçerli");
+    Ok(())
+}
+
+crate::shell_command!("netcfg", "Kalıcı ağ ayarları (netcfg [dhcp | static <adres> [maske] [ağ geçidi] [dns] | reset])", netcfg_command);
+
+// -----------------------------------------------------------------------------
+// KABUK VE PROCFS
+// -----------------------------------------------------------------------------
+
+fn state_name(state: State) -> &'static str {
+    match state {
+        State::Init => "init",
+        State::Selecting => "selecting",
+        State::Requesting => "requesting",
+        State::Bound => "bound",
+        State::Renewing => "renewing",
+        State::Rebinding => "rebinding",
+    }
+}
+
+fn dhcp_command(args: &[&str]) -> Result<(), KernelError> {
+    if args.get(1) == Some(&"renew") {
+        let task = TASK.load(Ordering::Acquire);
+        if task == usize::MAX {
+            return Err(KernelError::NotFound);
+        }
+        on_renew_timer(task);
+        return Ok(());
+    }
+    let (state, lease) = status();
+    serial_println!("  durum: {}", state_name(state));
+    if state != State::Init && !lease.addr.is_unspecified() {
+        let elapsed = (crate::sched::ticks() - lease.acquired_at) / TICK_HZ;
+        serial_println!("  adres: {}/{} ağ geçidi {} sunucu {}", lease.addr, lease.netmask, lease.gateway, lease.server);
+        serial_println!("  kira: {} s (T1 {} s, T2 {} s), geçen {} s", lease.lease_secs, lease.t1_secs, lease.t2_secs, elapsed);
+    }
+    Ok(())
+}
+
+crate::shell_command!("dhcp", "DHCP kira durumu (dhcp [renew])", dhcp_command);
+
+fn show_lease(out: &mut dyn core::fmt::Write) -> core::fmt::Result {
+    let (state, lease) = status();
+    writeln!(out, "state {}", state_name(state))?;
+    writeln!(out, "addr {}", lease.addr)?;
+    writeln!(out, "netmask {}", lease.netmask)?;
+    writeln!(out, "gateway {}", lease.gateway)?;
+    writeln!(out, "server {}", lease.server)?;
+    writeln!(out, "lease {} {} {}", lease.lease_secs, lease.t1_secs, lease.t2_secs)
+}
+
+crate::proc_entry!("net/dhcp", show_lease);
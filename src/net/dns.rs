@@ -0,0 +1,317 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/net/dns.rs
+// En küçük DNS saplama çözümleyicisi (stub resolver): A kayıtları, UDP.
+//
+// Sunucular DHCP'den (seçenek 6) veya `nameserver` komutuyla ayarlanır.
+// Sorgular özyinelemeli (RD) gönderilir ve yanıttaki ilk A kaydı kullanılır;
+// CNAME zincirini sunucunun yanıta eklediği varsayılır. Başarılı yanıtlar
+// kaydın TTL'i kadar (en fazla `MAX_CACHE_TTL_SECS`) önbellekte tutulur.
+
+use core::cell::UnsafeCell;
+use crate::net::net::{self, Ipv4Addr};
+use crate::net::socket::{Protocol, Socket};
+use crate::platformgeneric::spinlock::Spinlock;
+use crate::platformgeneric::{KernelError, SystemConstants};
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// SABİTLER
+// -----------------------------------------------------------------------------
+
+const DNS_PORT: u16 = 53;
+const HEADER_LEN: usize = 12;
+/// Sorgu adının en büyük uzunluğu (RFC 1035).
+const MAX_NAME: usize = 253;
+/// UDP üzerinden DNS iletisinin en büyük boyutu.
+const MAX_MESSAGE: usize = 512;
+
+const TYPE_A: u16 = 1;
+const CLASS_IN: u16 = 1;
+const FLAG_RESPONSE: u16 = 0x8000;
+const FLAG_RECURSION_DESIRED: u16 = 0x0100;
+const RCODE_MASK: u16 = 0x000F;
+const RCODE_NXDOMAIN: u16 = 3;
+
+/// En fazla sunucu sayısı.
+pub const MAX_SERVERS: usize = 2;
+/// Sunucu başına deneme sayısı.
+const ATTEMPTS: usize = 3;
+/// Yanıt için beklenen süre (2 s).
+const TIMEOUT_TICKS: u64 = 2 * SystemConstants::TIMER_TICK_HZ;
+/// Gönderim ARP çözümlemesini beklerken yeniden deneme aralığı (50 ms).
+const ARP_RETRY_TICKS: u64 = SystemConstants::TIMER_TICK_HZ / 20;
+
+const CACHE_SIZE: usize = 8;
+const MAX_CACHE_TTL_SECS: u32 = 3600;
+
+crate::kernel_counter!(QUERIES, "net.dns.queries");
+crate::kernel_counter!(CACHE_HITS, "net.dns.cache_hits");
+
+// -----------------------------------------------------------------------------
+// SUNUCULAR VE ÖNBELLEK
+// -----------------------------------------------------------------------------
+
+#[derive(Clone, Copy)]
+struct CacheEntry {
+    name: [u8; MAX_NAME],
+    name_len: usize,
+    addr: Ipv4Addr,
+    expires: u64,
+}
+
+struct Resolver {
+    lock: Spinlock,
+    servers: UnsafeCell<[Ipv4Addr; MAX_SERVERS]>,
+    cache: UnsafeCell<[Option<CacheEntry>; CACHE_SIZE]>,
+    /// Önbellekte sıradaki üzerine yazılacak yuva.
+    next_slot: UnsafeCell<usize>,
+}
+
+// Tüm alanlara erişim `lock` ile korunur.
+unsafe impl Sync for Resolver {}
+
+static RESOLVER: Resolver = Resolver {
+    lock: Spinlock::new(),
+    servers: UnsafeCell::new([Ipv4Addr::UNSPECIFIED; MAX_SERVERS]),
+    cache: UnsafeCell::new([None; CACHE_SIZE]),
+    next_slot: UnsafeCell::new(0),
+};
+
+fn with_resolver<R>(f: impl FnOnce(&mut [Ipv4Addr; MAX_SERVERS], &mut [Option<CacheEntry>; CACHE_SIZE], &mut usize) -> R) -> R {
+    RESOLVER.lock.lock();
+    let result = unsafe { f(&mut *RESOLVER.servers.get(), &mut *RESOLVER.cache.get(), &mut *RESOLVER.next_slot.get()) };
+    RESOLVER.lock.unlock();
+    result
+}
+
+/// Sunucu listesini değiştirir ve önbelleği temizler.
+pub fn set_servers(list: &[Ipv4Addr]) {
+    with_resolver(|servers, cache, _| {
+        *servers = [Ipv4Addr::UNSPECIFIED; MAX_SERVERS];
+        for (slot, addr) in servers.iter_mut().zip(list) {
+            *slot = *addr;
+        }
+        *cache = [None; CACHE_SIZE];
+    });
+}
+
+/// Ayarlı sunucular (boş yuvalar 0.0.0.0).
+pub fn servers() -> [Ipv4Addr; MAX_SERVERS] {
+    with_resolver(|servers, _, _| *servers)
+}
+
+fn cache_lookup(name: &str) -> Option<Ipv4Addr> {
+    let now = crate::sched::ticks();
+    with_resolver(|_, cache, _| {
+        cache
+            .iter()
+            .flatten()
+            .find(|e| e.expires > now && e.name[..e.name_len].eq_ignore_ascii_case(name.as_bytes()))
+            .map(|e| e.addr)
+    })
+}
+
+fn cache_store(name: &str, addr: Ipv4Addr, ttl_secs: u32) {
+    let mut entry = CacheEntry {
+        name: [0; MAX_NAME],
+        name_len: name.len(),
+        addr,
+        expires: crate::sched::ticks() + u64::from(ttl_secs.min(MAX_CACHE_TTL_SECS)) * SystemConstants::TIMER_TICK_HZ,
+    };
+    entry.name[..name.len()].copy_from_slice(name.as_bytes());
+    with_resolver(|_, cache, next| {
+        cache[*next] = Some(entry);
+        *next = (*next + 1) % CACHE_SIZE;
+    });
+}
+
+// -----------------------------------------------------------------------------
+// İLETİLER
+// -----------------------------------------------------------------------------
+
+/// A kaydı sorgusu oluşturur.
+///
+/// # Dönüş Değeri
+/// İleti uzunluğu; ad geçersizse `InvalidArgument`.
+fn build_query(buf: &mut [u8; MAX_MESSAGE], id: u16, name: &str) -> Result<usize, KernelError> {
+    buf[..HEADER_LEN].fill(0);
+    buf[0..2].copy_from_slice(&id.to_be_bytes());
+    buf[2..4].copy_from_slice(&FLAG_RECURSION_DESIRED.to_be_bytes());
+    buf[4..6].copy_from_slice(&1u16.to_be_bytes()); // QDCOUNT
+
+    let mut pos = HEADER_LEN;
+    for label in name.trim_end_matches('.').split('.') {
+        if label.is_empty() || label.len() > 63 {
+            return Err(KernelError::InvalidArgument);
+        }
+        buf[pos] = label.len() as u8;
+        buf[pos + 1..pos + 1 + label.len()].copy_from_slice(label.as_bytes());
+        pos += 1 + label.len();
+    }
+    buf[pos] = 0;
+    buf[pos + 1..pos + 3].copy_from_slice(&TYPE_A.to_be_bytes());
+    buf[pos + 3..pos + 5].copy_from_slice(&CLASS_IN.to_be_bytes());
+    Ok(pos + 5)
+}
+
+/// İletideki (sıkıştırılmış olabilecek) adı atlar.
+fn skip_name(msg: &[u8], mut pos: usize) -> Option<usize> {
+    loop {
+        let len = *msg.get(pos)?;
+        match len {
+            0 => return Some(pos + 1),
+            // Sıkıştırma işaretçisi: ad burada biter
+            l if l & 0xC0 == 0xC0 => return Some(pos + 2),
+            l => pos += 1 + usize::from(l),
+        }
+    }
+}
+
+fn read_u16(msg: &[u8], pos: usize) -> Option<u16> {
+    Some(u16::from_be_bytes([*msg.get(pos)?, *msg.get(pos + 1)?]))
+}
+
+/// Yanıttan ilk A kaydını ve TTL'ini çıkarır.
+fn parse_response(msg: &[u8], id: u16) -> Result<(Ipv4Addr, u32), KernelError> {
+    let malformed = KernelError::InvalidArgument;
+    let flags = read_u16(msg, 2).ok_or(malformed)?;
+    if read_u16(msg, 0) != Some(id) || flags & FLAG_RESPONSE == 0 {
+        return Err(malformed);
+    }
+    match flags & RCODE_MASK {
+        0 => {}
+        RCODE_NXDOMAIN => return Err(KernelError::NotFound),
+        rcode => return Err(KernelError::PlatformSpecificError(u32::from(rcode))),
+    }
+    let questions = read_u16(msg, 4).ok_or(malformed)?;
+    let answers = read_u16(msg, 6).ok_or(malformed)?;
+
+    let mut pos = HEADER_LEN;
+    for _ in 0..questions {
+        pos = skip_name(msg, pos).ok_or(malformed)? + 4;
+    }
+    for _ in 0..answers {
+        pos = skip_name(msg, pos).ok_or(malformed)?;
+        let rtype = read_u16(msg, pos).ok_or(malformed)?;
+        let class = read_u16(msg, pos + 2).ok_or(malformed)?;
+        let ttl = u32::from_be_bytes(msg.get(pos + 4..pos + 8).ok_or(malformed)?.try_into().map_err(|_| malformed)?);
+        let rdlen = usize::from(read_u16(msg, pos + 8).ok_or(malformed)?);
+        let rdata = msg.get(pos + 10..pos + 10 + rdlen).ok_or(malformed)?;
+        if rtype == TYPE_A && class == CLASS_IN && rdlen == 4 {
+            return Ok((Ipv4Addr([rdata[0], rdata[1], rdata[2], rdata[3]]), ttl));
+        }
+        pos += 10 + rdlen;
+    }
+    Err(KernelError::NotFound)
+}
+
+// -----------------------------------------------------------------------------
+// ÇÖZÜMLEME
+// -----------------------------------------------------------------------------
+
+/// Tek bir sunucuya sorgu gönderip yanıtı bekler.
+fn query(sock: &Socket, server: Ipv4Addr, name: &str) -> Result<(Ipv4Addr, u32), KernelError> {
+    let id = (crate::sched::ticks() as u16) ^ sock.port();
+    let mut buf = [0u8; MAX_MESSAGE];
+    let len = build_query(&mut buf, id, name)?;
+
+    // İlk gönderim ağ geçidinin ARP çözümlemesini başlatabilir
+    let deadline = crate::sched::ticks() + TIMEOUT_TICKS;
+    loop {
+        match sock.send_to(server, DNS_PORT, &buf[..len]) {
+            Ok(()) => break,
+            Err(KernelError::ResourceBusy) if crate::sched::ticks() < deadline => net::poll_for(ARP_RETRY_TICKS),
+            Err(e) => return Err(e),
+        }
+    }
+    QUERIES.inc();
+
+    loop {
+        let remaining = deadline.checked_sub(crate::sched::ticks()).filter(|&t| t > 0).ok_or(KernelError::ResourceBusy)?;
+        let (len, src, port) = sock.recv_timeout(&mut buf, remaining)?;
+        if src != server || port != DNS_PORT {
+            continue;
+        }
+        match parse_response(&buf[..len], id) {
+            // Kimliği tutmayan (eski sorgulara ait) yanıtlar atlanır
+            Err(KernelError::InvalidArgument) => continue,
+            result => return result,
+        }
+    }
+}
+
+/// Ana makine adını IPv4 adresine çözümler.
+///
+/// "a.b.c.d" biçimindeki metin ve "localhost" sorgusuz çözülür.
+pub fn resolve(name: &str) -> Result<Ipv4Addr, KernelError> {
+    if let Some(addr) = Ipv4Addr::parse(name) {
+        return Ok(addr);
+    }
+    if name.eq_ignore_ascii_case("localhost") {
+        return Ok(Ipv4Addr::LOCALHOST);
+    }
+    if name.is_empty() || name.len() > MAX_NAME {
+        return Err(KernelError::InvalidArgument);
+    }
+    if let Some(addr) = cache_lookup(name) {
+        CACHE_HITS.inc();
+        return Ok(addr);
+    }
+
+    let sock = Socket::open(Protocol::Udp)?;
+    let mut result = Err(KernelError::NotFound);
+    for server in servers().into_iter().filter(|s| !s.is_unspecified()) {
+        for _ in 0..ATTEMPTS {
+            result = query(&sock, server, name);
+            match result {
+                Ok((addr, ttl)) => {
+                    cache_store(name, addr, ttl);
+                    return Ok(addr);
+                }
+                // Ad yok: diğer sunuculara sormaya gerek yok
+                Err(KernelError::NotFound) => return Err(KernelError::NotFound),
+                Err(_) => {}
+            }
+        }
+    }
+    result.map(|(addr, _)| addr)
+}
+
+// -----------------------------------------------------------------------------
+// KABUK VE PROCFS
+// -----------------------------------------------------------------------------
+
+fn nslookup_command(args: &[&str]) -> Result<(), KernelError> {
+    let name = args.get(1).ok_or(KernelError::InvalidArgument)?;
+    let addr = resolve(name)?;
+    serial_println!("{}: {}", name, addr);
+    Ok(())
+}
+
+crate::shell_command!("nslookup", "Ad çözümler (nslookup <ad>)", nslookup_command);
+
+fn nameserver_command(args: &[&str]) -> Result<(), KernelError> {
+    if args.len() > 1 {
+        let mut list = [Ipv4Addr::UNSPECIFIED; MAX_SERVERS];
+        for (slot, arg) in list.iter_mut().zip(&args[1..]) {
+            *slot = Ipv4Addr::parse(arg).ok_or(KernelError::InvalidArgument)?;
+        }
+        set_servers(&list[..(args.len() - 1).min(MAX_SERVERS)]);
+    }
+    for server in servers().iter().filter(|s| !s.is_unspecified()) {
+        serial_println!("  {}", server);
+    }
+    Ok(())
+}
+
+crate::shell_command!("nameserver", "DNS sunucuları (nameserver [<adres> ...])", nameserver_command);
+
+fn show_resolv(out: &mut dyn core::fmt::Write) -> core::fmt::Result {
+    for server in servers().iter().filter(|s| !s.is_unspecified()) {
+        writeln!(out, "nameserver {}", server)?;
+    }
+    Ok(())
+}
+
+crate::proc_entry!("net/resolv", show_resolv);
@@ -0,0 +1,144 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/net/icmp.rs
+// ICMP: yankı (echo) isteklerine yanıt ve `ping` istemcisi.
+//
+// Gelen yankı istekleri aynı yükle yanıtlanır. Yankı yanıtları, tanımlayıcı
+// alanı soketin portuna eşit olan ICMP soketine teslim edilir (bkz.
+// `socket`). `ping` komutu bu yolu uçtan uca kullanır; 127.0.0.1'e ping,
+// geri döngü aygıtı, alım kuyruğu, IPv4 ve soket katmanlarını donanımsız
+// ve deterministik olarak sınar.
+
+use crate::net::ipv4::{self, Header};
+use crate::net::net::{self, Ipv4Addr};
+use crate::net::netbuf::{self, NetBuf};
+use crate::net::socket::{self, Protocol, Socket};
+use crate::platformgeneric::{ticks_to_ms, KernelError};
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// SABİTLER
+// -----------------------------------------------------------------------------
+
+pub const TYPE_ECHO_REPLY: u8 = 0;
+pub const TYPE_DEST_UNREACHABLE: u8 = 3;
+pub const TYPE_ECHO_REQUEST: u8 = 8;
+
+/// Tür, kod, sağlama, tanımlayıcı ve sıra numarası.
+pub const ECHO_HEADER_LEN: usize = 8;
+
+crate::kernel_counter!(ECHO_REQUESTS, "net.icmp.echo_requests");
+crate::kernel_counter!(BAD_CHECKSUM, "net.icmp.bad_checksum");
+
+// -----------------------------------------------------------------------------
+// ALMA VE GÖNDERME
+// -----------------------------------------------------------------------------
+
+/// Gelen ICMP iletisini işler (`ipv4::receive` içinden).
+pub fn receive(iface: usize, header: &Header, msg: &[u8]) {
+    let _ = iface;
+    if msg.len() < ECHO_HEADER_LEN {
+        return;
+    }
+    if net::checksum(msg) != 0 {
+        BAD_CHECKSUM.inc();
+        return;
+    }
+    match msg[0] {
+        TYPE_ECHO_REQUEST if header.dst != Ipv4Addr::BROADCAST => {
+            ECHO_REQUESTS.inc();
+            let Ok(mut buf) = NetBuf::from_slice(netbuf::HEADROOM, msg) else { return };
+            let reply = buf.data_mut();
+            reply[0] = TYPE_ECHO_REPLY;
+            finish(reply);
+            let _ = ipv4::send_buf(header.src, ipv4::PROTO_ICMP, buf);
+        }
+        TYPE_ECHO_REPLY => {
+            let id = u16::from_be_bytes([msg[4], msg[5]]);
+            socket::deliver(Protocol::Icmp, id, header.src, 0, msg);
+        }
+        _ => {}
+    }
+}
+
+/// Sağlama alanını sıfırlayıp yeniden hesaplar.
+fn finish(msg: &mut [u8]) {
+    msg[2..4].fill(0);
+    let sum = net::checksum(msg);
+    msg[2..4].copy_from_slice(&sum.to_be_bytes());
+}
+
+/// Bir yankı isteği gönderir; `msg`'nin tür ve tanımlayıcı alanları ile
+/// sağlama toplamı yeniden yazılır (bkz. `Socket::send_to`).
+pub fn send_echo(dst: Ipv4Addr, id: u16, msg: &[u8]) -> Result<(), KernelError> {
+    if msg.len() < ECHO_HEADER_LEN || msg.len() > socket::MAX_DATAGRAM {
+        return Err(KernelError::InvalidArgument);
+    }
+    let mut buf = NetBuf::from_slice(netbuf::HEADROOM, msg)?;
+    let request = buf.data_mut();
+    request[0] = TYPE_ECHO_REQUEST;
+    request[1] = 0;
+    request[4..6].copy_from_slice(&id.to_be_bytes());
+    finish(request);
+    ipv4::send_buf(dst, ipv4::PROTO_ICMP, buf)
+}
+
+// -----------------------------------------------------------------------------
+// PING
+// -----------------------------------------------------------------------------
+
+/// Yanıt için beklenen en uzun süre (tik).
+const PING_TIMEOUT_TICKS: u64 = 1000;
+/// Yankı isteklerine eklenen desen yükünün uzunluğu.
+const PING_PAYLOAD_LEN: usize = 56;
+
+/// Tek bir yankı isteği gönderip yanıtını bekler.
+///
+/// # Dönüş Değeri
+/// Gidiş-dönüş süresi (tik); yanıt gelmezse `ResourceBusy`.
+pub fn ping(sock: &Socket, dst: Ipv4Addr, seq: u16) -> Result<u64, KernelError> {
+    let mut msg = [0u8; ECHO_HEADER_LEN + PING_PAYLOAD_LEN];
+    msg[6..8].copy_from_slice(&seq.to_be_bytes());
+    for (i, b) in msg[ECHO_HEADER_LEN..].iter_mut().enumerate() {
+        *b = i as u8;
+    }
+
+    let start = crate::sched::ticks();
+    sock.send_to(dst, 0, &msg)?;
+    let mut reply = [0u8; ECHO_HEADER_LEN + PING_PAYLOAD_LEN];
+    loop {
+        let elapsed = crate::sched::ticks() - start;
+        let remaining = PING_TIMEOUT_TICKS.checked_sub(elapsed).ok_or(KernelError::ResourceBusy)?;
+        let (len, src, _) = sock.recv_timeout(&mut reply, remaining)?;
+        // Eski isteklerin geç gelen yanıtları atlanır
+        if src == dst && len == msg.len() && reply[6..8] == msg[6..8] && reply[ECHO_HEADER_LEN..] == msg[ECHO_HEADER_LEN..] {
+            return Ok(crate::sched::ticks() - start);
+        }
+    }
+}
+
+fn ping_command(args: &[&str]) -> Result<(), KernelError> {
+    let dst = net::lookup(args.get(1).ok_or(KernelError::InvalidArgument)?)?;
+    let count: u16 = args.get(2).and_then(|s| s.parse().ok()).unwrap_or(4);
+    let sock = Socket::open(Protocol::Icmp)?;
+
+    serial_println!("PING {}: {} bayt veri", dst, PING_PAYLOAD_LEN);
+    let mut received = 0;
+    for seq in 1..=count {
+        match ping(&sock, dst, seq) {
+            Ok(rtt) => {
+                received += 1;
+                serial_println!("{} bayt {}: icmp_seq={} süre={} ms", ECHO_HEADER_LEN + PING_PAYLOAD_LEN, dst, seq, ticks_to_ms(rtt));
+            }
+            Err(KernelError::ResourceBusy) => serial_println!("icmp_seq={}: zaman aşımı", seq),
+            Err(e) => {
+                serial_println!("icmp_seq={}: gönderilemedi ({:?})", seq, e);
+                break;
+            }
+        }
+    }
+    serial_println!("--- {}: {} gönderildi, {} alındı ---", dst, count, received);
+    Ok(())
+}
+
+crate::shell_command!("ping", "ICMP yankı isteği gönderir (ping <adres|ad> [sayı])", ping_command);
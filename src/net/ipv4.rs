@@ -0,0 +1,178 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/net/ipv4.rs
+// IPv4 katmanı: başlık doğrulama, yerel teslim ve gönderme.
+//
+// Parçalanmış (fragmented) paketler desteklenmez ve atılır; gönderilen
+// paketlerde "Parçalama" (DF) biti ayarlanır. IP seçenekleri okunurken
+// atlanır, gönderilen paketlerde hiç bulunmaz.
+
+use core::sync::atomic::{AtomicU16, Ordering};
+use crate::net::net::{self, Ipv4Addr, MacAddr};
+use crate::net::netbuf::{self, NetBuf};
+use crate::platformgeneric::KernelError;
+
+// -----------------------------------------------------------------------------
+// SABİTLER
+// -----------------------------------------------------------------------------
+
+/// Seçeneksiz başlık uzunluğu.
+pub const HEADER_LEN: usize = 20;
+/// Gönderilen paketlerin varsayılan yaşam süresi.
+pub const DEFAULT_TTL: u8 = 64;
+
+pub const PROTO_ICMP: u8 = 1;
+pub const PROTO_TCP: u8 = 6;
+pub const PROTO_UDP: u8 = 17;
+
+const FLAG_DF: u16 = 0x4000;
+const FLAG_MF: u16 = 0x2000;
+const FRAGMENT_OFFSET_MASK: u16 = 0x1FFF;
+
+static NEXT_ID: AtomicU16 = AtomicU16::new(1);
+
+crate::kernel_counter!(RX_BAD_HEADER, "net.ip.bad_header");
+crate::kernel_counter!(RX_FRAGMENTS, "net.ip.fragments");
+crate::kernel_counter!(RX_NOT_LOCAL, "net.ip.not_local");
+crate::kernel_counter!(RX_UNKNOWN_PROTO, "net.ip.unknown_proto");
+
+// -----------------------------------------------------------------------------
+// BAŞLIK
+// -----------------------------------------------------------------------------
+
+/// Çözümlenmiş IPv4 başlığı.
+#[derive(Debug, Clone, Copy)]
+pub struct Header {
+    pub src: Ipv4Addr,
+    pub dst: Ipv4Addr,
+    pub protocol: u8,
+    pub ttl: u8,
+}
+
+/// Paketi doğrular; başlığı ve yükü (dolgu baytları hariç) döndürür.
+pub fn parse(packet: &[u8]) -> Option<(Header, &[u8])> {
+    if packet.len() < HEADER_LEN || packet[0] >> 4 != 4 {
+        return None;
+    }
+    let ihl = usize::from(packet[0] & 0x0F) * 4;
+    let total = usize::from(u16::from_be_bytes([packet[2], packet[3]]));
+    if ihl < HEADER_LEN || total < ihl || total > packet.len() || net::checksum(&packet[..ihl]) != 0 {
+        return None;
+    }
+    let header = Header {
+        src: Ipv4Addr([packet[12], packet[13], packet[14], packet[15]]),
+        dst: Ipv4Addr([packet[16], packet[17], packet[18], packet[19]]),
+        protocol: packet[9],
+        ttl: packet[8],
+    };
+    Some((header, &packet[ihl..total]))
+}
+
+/// `buf`'ın başına (en az `HEADER_LEN` bayt) seçeneksiz bir başlık yazar.
+fn write_header(buf: &mut [u8], src: Ipv4Addr, dst: Ipv4Addr, protocol: u8, payload_len: usize) {
+    let total = (HEADER_LEN + payload_len) as u16;
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    buf[0] = 0x45; // Sürüm 4, IHL 5
+    buf[1] = 0;
+    buf[2..4].copy_from_slice(&total.to_be_bytes());
+    buf[4..6].copy_from_slice(&id.to_be_bytes());
+    buf[6..8].copy_from_slice(&FLAG_DF.to_be_bytes());
+    buf[8] = DEFAULT_TTL;
+    buf[9] = protocol;
+    buf[10..12].fill(0);
+    buf[12..16].copy_from_slice(&src.0);
+    buf[16..20].copy_from_slice(&dst.0);
+    let sum = net::checksum(&buf[..HEADER_LEN]);
+    buf[10..12].copy_from_slice(&sum.to_be_bytes());
+}
+
+// -----------------------------------------------------------------------------
+// ALMA
+// -----------------------------------------------------------------------------
+
+/// Arayüzden gelen IPv4 paketini işler (`net::poll` içinden).
+pub fn receive(iface: usize, packet: &[u8]) {
+    let Some((header, payload)) = parse(packet) else {
+        RX_BAD_HEADER.inc();
+        return;
+    };
+    let flags = u16::from_be_bytes([packet[6], packet[7]]);
+    if flags & FLAG_MF != 0 || flags & FRAGMENT_OFFSET_MASK != 0 {
+        RX_FRAGMENTS.inc();
+        return;
+    }
+    let accepted = net::interface(iface).is_some_and(|i| i.dev.is_loopback() || header.dst == i.broadcast());
+    if !accepted && !net::is_local(header.dst) && header.dst != Ipv4Addr::BROADCAST {
+        RX_NOT_LOCAL.inc();
+        return;
+    }
+
+    match header.protocol {
+        PROTO_ICMP => crate::net::icmp::receive(iface, &header, payload),
+        PROTO_TCP => crate::net::tcp::receive(iface, &header, payload),
+        PROTO_UDP => crate::net::udp::receive(iface, &header, payload),
+        _ => RX_UNKNOWN_PROTO.inc(),
+    }
+}
+
+// -----------------------------------------------------------------------------
+// GÖNDERME
+// -----------------------------------------------------------------------------
+
+/// Bir sonraki sekmenin donanım adresini bulur.
+fn next_hop_mac(iface: usize, next_hop: Ipv4Addr) -> Result<MacAddr, KernelError> {
+    let info = net::interface(iface).ok_or(KernelError::NotFound)?;
+    if info.dev.is_loopback() {
+        return Ok(MacAddr::ZERO);
+    }
+    if next_hop == Ipv4Addr::BROADCAST || next_hop == info.broadcast() {
+        return Ok(MacAddr::BROADCAST);
+    }
+    crate::net::arp::resolve(iface, next_hop)
+}
+
+/// Hedef için çıkış arayüzü, kaynak adres ve bir sonraki sekme.
+///
+/// Kaynak adres, hedef yerelse hedefin kendisi, değilse çıkış arayüzünün
+/// adresidir.
+pub fn select_route(dst: Ipv4Addr) -> Result<(usize, Ipv4Addr, Ipv4Addr), KernelError> {
+    let (iface, next_hop) = net::route(dst).ok_or(KernelError::NotFound)?;
+    let src = if net::is_local(dst) { dst } else { net::interface(iface).ok_or(KernelError::NotFound)?.addr };
+    Ok((iface, src, next_hop))
+}
+
+/// `payload`'ı `dst`'ye yönlendirerek gönderir.
+///
+/// Bir sonraki sekmenin adresi henüz çözümlenmemişse ARP isteği gönderilir
+/// ve `ResourceBusy` döner; çağıran daha sonra yeniden dener.
+pub fn send(dst: Ipv4Addr, protocol: u8, payload: &[u8]) -> Result<(), KernelError> {
+    let (iface, src, next_hop) = select_route(dst)?;
+    send_from(iface, src, dst, next_hop, protocol, payload)
+}
+
+/// Kaynak adresi ve arayüzü açıkça verilen gönderim (örn. adres almadan
+/// önce 0.0.0.0 kaynağıyla yayın).
+pub fn send_from(iface: usize, src: Ipv4Addr, dst: Ipv4Addr, next_hop: Ipv4Addr, protocol: u8, payload: &[u8]) -> Result<(), KernelError> {
+    if HEADER_LEN + payload.len() > net::MAX_MTU {
+        return Err(KernelError::InvalidArgument);
+    }
+    send_buf_from(iface, src, dst, next_hop, protocol, NetBuf::from_slice(netbuf::HEADROOM, payload)?)
+}
+
+/// `send` ile aynı; yük, önüne başlık eklenecek bir ağ tamponundadır.
+pub fn send_buf(dst: Ipv4Addr, protocol: u8, payload: NetBuf) -> Result<(), KernelError> {
+    let (iface, src, next_hop) = select_route(dst)?;
+    send_buf_from(iface, src, dst, next_hop, protocol, payload)
+}
+
+/// `send_from` ile aynı; başlık tamponun önüne yazılır, yük kopyalanmaz.
+pub fn send_buf_from(iface: usize, src: Ipv4Addr, dst: Ipv4Addr, next_hop: Ipv4Addr, protocol: u8, payload: NetBuf) -> Result<(), KernelError> {
+    let payload_len = payload.total_len();
+    if HEADER_LEN + payload_len > net::MAX_MTU {
+        return Err(KernelError::InvalidArgument);
+    }
+    let mac = next_hop_mac(iface, next_hop)?;
+    let mut packet = payload.ensure_headroom(HEADER_LEN)?;
+    write_header(packet.push_header(HEADER_LEN)?, src, dst, protocol, payload_len);
+    net::transmit_buf(iface, mac, net::ETHERTYPE_IPV4, packet)
+}
@@ -0,0 +1,94 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/net/loopback.rs
+// Geri döngü (loopback) ağ aygıtı: "lo", 127.0.0.1/8.
+//
+// Gönderilen her çerçeve (ağ tamponu, kopyalanmadan) doğrudan yığının alım
+// kuyruğuna geri eklenir ve sıradaki `net::poll` çağrısında işlenir.
+// Donanım gerektirmediği için
+// yığının, tamponların ve soket katmanının QEMU altında deterministik
+// sınanmasında kullanılır; açılışta 127.0.0.1'e tek bir ping ile öz sınama
+// yapılır.
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+use crate::net::net::{self, Ipv4Addr, MacAddr, NetDevice};
+use crate::net::netbuf::NetBuf;
+use crate::net::socket::{Protocol, Socket};
+use crate::platformgeneric::KernelError;
+
+/// Kayıt öncesi arayüz numarası için işaret değeri.
+const UNREGISTERED: usize = usize::MAX;
+
+struct Loopback {
+    iface: AtomicUsize,
+}
+
+impl NetDevice for Loopback {
+    fn name(&self) -> &'static str {
+        "lo"
+    }
+
+    fn mac(&self) -> MacAddr {
+        MacAddr::ZERO
+    }
+
+    fn is_loopback(&self) -> bool {
+        true
+    }
+
+    fn transmit(&self, frame: &[u8]) -> Result<(), KernelError> {
+        match self.iface.load(Ordering::Acquire) {
+            UNREGISTERED => Err(KernelError::NotFound),
+            id => {
+                net::receive(id, frame);
+                Ok(())
+            }
+        }
+    }
+
+    fn transmit_buf(&self, buf: NetBuf) -> Result<(), KernelError> {
+        match self.iface.load(Ordering::Acquire) {
+            UNREGISTERED => Err(KernelError::NotFound),
+            id => {
+                net::receive_buf(id, buf, None);
+                Ok(())
+            }
+        }
+    }
+}
+
+static LOOPBACK: Loopback = Loopback { iface: AtomicUsize::new(UNREGISTERED) };
+
+fn init() -> Result<(), KernelError> {
+    let id = net::register_device(&LOOPBACK, Ipv4Addr::LOCALHOST, Ipv4Addr([255, 0, 0, 0]))?;
+    LOOPBACK.iface.store(id, Ordering::Release);
+    Ok(())
+}
+
+crate::initcall!(drivers, "loopback", init);
+
+/// Açılış öz sınaması: 127.0.0.1'e tek bir yankı isteği.
+///
+/// Zamanlayıcıya bağlı değildir: istek ve yanıt tek bir `net::poll`
+/// çağrısında işlenir, yanıt hemen ardından sokette olmalıdır.
+fn selftest() -> Result<(), KernelError> {
+    let sock = Socket::open(Protocol::Icmp)?;
+    let msg = *b"\0\0\0\0\0\0\0\x01lo-selftest";
+    sock.send_to(Ipv4Addr::LOCALHOST, 0, &msg)?;
+    net::poll();
+
+    let mut reply = [0u8; 32];
+    match sock.recv_from(&mut reply) {
+        // Sıra numarası ve yük aynen geri dönmeli
+        Some((len, src, _)) if src == Ipv4Addr::LOCALHOST && len == msg.len() && reply[6..len] == msg[6..] => {
+            crate::klog!(net::LOG, Info, "[NET] Geri döngü öz sınaması başarılı");
+            Ok(())
+        }
+        _ => {
+            crate::klog!(net::LOG, Error, "[NET] Geri döngü öz sınaması başarısız");
+            Err(KernelError::GenericFailure)
+        }
+    }
+}
+
+crate::initcall!(late, "net-selftest", selftest);
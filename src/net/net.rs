@@ -0,0 +1,699 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/net/net.rs
+// Ağ yığınının çekirdeği: adres türleri, ağ aygıtı arayüzü, arayüz tablosu,
+// alım kuyruğu ve Ethernet çerçeveleme.
+//
+// Sürücüler aldıkları çerçeveleri `receive` ile kuyruğa ekler (kesme
+// bağlamından da çağrılabilir); çerçeveler `poll` çağrıldığında görev
+// bağlamında işlenir. Böylece protokol katmanları hiçbir zaman kesme
+// bağlamında çalışmaz ve geri döngü (loopback) aygıtı kendi gönderdiği
+// paketi aynı çağrı zinciri içinde özyinelemeli olarak işlemez.
+//
+// Tüm aygıtlar Ethernet çerçevesi taşır; geri döngü aygıtında MAC adresleri
+// sıfırdır.
+//
+// Çerçeveler yığın içinde `NetBuf` olarak taşınır (bkz. `netbuf`): alım
+// kuyruğu tamponları tutar, protokoller başlıkları yerinde geçer ve gönderim
+// yolunda her katman başlığını tamponun önüne ekler. Tampon alan sürücüler
+// `receive_buf` ve `NetDevice::transmit_buf` ile kopyasız çalışır; dilim
+// tabanlı `receive`/`transmit` yalnızca sınırda bir kez kopyalar.
+//
+// Zaman damgaları (PTP/IEEE 1588 için): her çerçeve alındığında (`receive`)
+// ve aygıta verilmeden hemen önce (`transmit`) monotonik saatten yazılım
+// damgası alınır. Donanım damgası üreten sürücüler alımda `receive_timestamped`
+// kullanır ve gönderimde `NetDevice::tx_timestamp` sağlar. Damgalar
+// `socket` katmanında soket başına sunulur.
+
+use core::cell::UnsafeCell;
+use core::fmt;
+use core::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use crate::platformgeneric::spinlock::Spinlock;
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+use crate::hrtimer;
+use crate::net::netbuf::{self, NetBuf};
+use crate::sysctl::{Level, LogLevel};
+
+crate::sysctl!(pub LOG: Level = Level::new(LogLevel::Info), "log.net", "Ağ yığını günlük seviyesi");
+
+// -----------------------------------------------------------------------------
+// ADRESLER
+// -----------------------------------------------------------------------------
+
+/// IPv4 adresi (ağ bayt sırasında).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Ipv4Addr(pub [u8; 4]);
+
+impl Ipv4Addr {
+    pub const UNSPECIFIED: Ipv4Addr = Ipv4Addr([0, 0, 0, 0]);
+    pub const BROADCAST: Ipv4Addr = Ipv4Addr([255, 255, 255, 255]);
+    pub const LOCALHOST: Ipv4Addr = Ipv4Addr([127, 0, 0, 1]);
+
+    pub const fn from_u32(value: u32) -> Self {
+        Ipv4Addr(value.to_be_bytes())
+    }
+
+    pub const fn to_u32(self) -> u32 {
+        u32::from_be_bytes(self.0)
+    }
+
+    /// 127.0.0.0/8 aralığında mı?
+    pub const fn is_loopback(self) -> bool {
+        self.0[0] == 127
+    }
+
+    pub fn is_unspecified(self) -> bool {
+        self == Self::UNSPECIFIED
+    }
+
+    /// `other` ile aynı alt ağda mı?
+    pub fn same_subnet(self, other: Ipv4Addr, netmask: Ipv4Addr) -> bool {
+        (self.to_u32() ^ other.to_u32()) & netmask.to_u32() == 0
+    }
+
+    /// "a.b.c.d" biçimindeki metni çözümler.
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut octets = [0u8; 4];
+        let mut parts = s.split('.');
+        for octet in octets.iter_mut() {
+            *octet = parts.next()?.parse().ok()?;
+        }
+        parts.next().is_none().then_some(Ipv4Addr(octets))
+    }
+}
+
+impl fmt::Display for Ipv4Addr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [a, b, c, d] = self.0;
+        write!(f, "{}.{}.{}.{}", a, b, c, d)
+    }
+}
+
+/// Ethernet MAC adresi.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MacAddr(pub [u8; 6]);
+
+impl MacAddr {
+    pub const ZERO: MacAddr = MacAddr([0; 6]);
+    pub const BROADCAST: MacAddr = MacAddr([0xFF; 6]);
+}
+
+impl fmt::Display for MacAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [a, b, c, d, e, g] = self.0;
+        write!(f, "{:02x}:{:02x}:{:02x}:{:02x}:{:02x}:{:02x}", a, b, c, d, e, g)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// İNTERNET SAĞLAMA TOPLAMI
+// -----------------------------------------------------------------------------
+
+/// `data`'yı 16 bitlik kelimeler halinde `sum`'a ekler (katlanmamış toplam).
+/// Sözde başlık (pseudo header) gerektiren protokoller parçaları sırayla ekler.
+pub fn checksum_add(mut sum: u32, data: &[u8]) -> u32 {
+    let mut chunks = data.chunks_exact(2);
+    for word in &mut chunks {
+        sum = sum.wrapping_add(u32::from(u16::from_be_bytes([word[0], word[1]])));
+    }
+    if let [last] = chunks.remainder() {
+        sum = sum.wrapping_add(u32::from(*last) << 8);
+    }
+    sum
+}
+
+/// Katlanmamış toplamı 16 bite indirip tümleyenini alır.
+pub fn checksum_finish(mut sum: u32) -> u16 {
+    while sum >> 16 != 0 {
+        sum = (sum & 0xFFFF) + (sum >> 16);
+    }
+    !(sum as u16)
+}
+
+/// RFC 1071 internet sağlama toplamı. Sağlama alanı dahil hesaplandığında
+/// geçerli bir başlık için 0 döner.
+pub fn checksum(data: &[u8]) -> u16 {
+    checksum_finish(checksum_add(0, data))
+}
+
+// -----------------------------------------------------------------------------
+// AĞ AYGITI ARAYÜZÜ
+// -----------------------------------------------------------------------------
+
+/// Ethernet başlığı uzunluğu.
+pub const ETH_HEADER_LEN: usize = 14;
+/// Desteklenen en büyük çerçeve (başlık dahil, FCS hariç).
+pub const MAX_FRAME: usize = 1518;
+/// Desteklenen en büyük yük (MTU).
+pub const MAX_MTU: usize = MAX_FRAME - ETH_HEADER_LEN;
+
+pub const ETHERTYPE_IPV4: u16 = 0x0800;
+pub const ETHERTYPE_ARP: u16 = 0x0806;
+
+/// Ağ aygıtı sürücüsü.
+pub trait NetDevice: Sync {
+    /// Aygıt adı (örn. "lo", "eth0").
+    fn name(&self) -> &'static str;
+
+    /// Donanım adresi.
+    fn mac(&self) -> MacAddr;
+
+    /// En büyük yük boyutu (Ethernet başlığı hariç).
+    fn mtu(&self) -> usize {
+        MAX_MTU
+    }
+
+    /// Geri döngü aygıtı mı? (ARP gerektirmez, 127.0.0.0/8 buraya yönlenir)
+    fn is_loopback(&self) -> bool {
+        false
+    }
+
+    /// Tam bir Ethernet çerçevesi gönderir.
+    fn transmit(&self, frame: &[u8]) -> Result<(), KernelError>;
+
+    /// Ağ tamponundaki çerçeveyi gönderir. Varsayılan gerçekleme tek bölütlü
+    /// tamponu kopyalamadan, zincirli olanı düzleştirerek `transmit`'e verir;
+    /// dağıt-topla (scatter-gather) DMA yapabilen sürücüler bölütleri
+    /// doğrudan kullanmak için bunu gerçekler.
+    fn transmit_buf(&self, buf: NetBuf) -> Result<(), KernelError> {
+        if buf.next().is_none() {
+            return self.transmit(buf.data());
+        }
+        let mut frame = [0u8; MAX_FRAME];
+        let len = buf.copy_to(&mut frame);
+        self.transmit(&frame[..len])
+    }
+
+    /// Son `transmit` edilen çerçevenin donanım zaman damgası (ns, aygıt
+    /// saatine göre). Donanım damgası desteklemeyen aygıtlar `None` döner.
+    fn tx_timestamp(&self) -> Option<u64> {
+        None
+    }
+}
+
+// -----------------------------------------------------------------------------
+// ZAMAN DAMGALARI
+// -----------------------------------------------------------------------------
+
+/// Bir çerçevenin alım veya gönderim anı.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Timestamp {
+    /// Yazılım damgası: `hrtimer::now_ns` (monotonik, ns).
+    pub software: u64,
+    /// Donanım damgası (ns, aygıt saatine göre); yoksa `None`.
+    pub hardware: Option<u64>,
+}
+
+impl Timestamp {
+    pub const NONE: Timestamp = Timestamp { software: 0, hardware: None };
+}
+
+/// `poll` ile dağıtılmakta olan çerçevenin alım damgası. `poll` tek bir
+/// çağrıda çalıştığından protokol katmanları bunu argümansız okuyabilir.
+static RX_STAMP_SW: AtomicU64 = AtomicU64::new(0);
+static RX_STAMP_HW: AtomicU64 = AtomicU64::new(NO_HW_STAMP);
+const NO_HW_STAMP: u64 = u64::MAX;
+
+// İşlemci başına son gönderim damgası: gönderim çağrı zinciri (soket ->
+// UDP -> IPv4 -> `transmit`) çağıranın işlemcisinde eşzamanlı tamamlanır.
+crate::percpu!(TX_STAMP_SW: AtomicU64 = AtomicU64::new(0));
+crate::percpu!(TX_STAMP_HW: AtomicU64 = AtomicU64::new(NO_HW_STAMP));
+
+fn pack_hw(hw: Option<u64>) -> u64 {
+    hw.unwrap_or(NO_HW_STAMP)
+}
+
+fn unpack_hw(raw: u64) -> Option<u64> {
+    (raw != NO_HW_STAMP).then_some(raw)
+}
+
+/// Şu anda protokol katmanlarına dağıtılan çerçevenin alım damgası.
+/// Yalnızca `poll` içinden (protokol alım yollarında) anlamlıdır.
+pub fn rx_timestamp() -> Timestamp {
+    Timestamp {
+        software: RX_STAMP_SW.load(Ordering::Relaxed),
+        hardware: unpack_hw(RX_STAMP_HW.load(Ordering::Relaxed)),
+    }
+}
+
+/// Bu işlemcide son başarıyla gönderilen çerçevenin damgası.
+pub fn last_tx_timestamp() -> Timestamp {
+    Timestamp {
+        software: TX_STAMP_SW.get().load(Ordering::Relaxed),
+        hardware: unpack_hw(TX_STAMP_HW.get().load(Ordering::Relaxed)),
+    }
+}
+
+// -----------------------------------------------------------------------------
+// ARAYÜZ TABLOSU
+// -----------------------------------------------------------------------------
+
+/// En fazla ağ arayüzü sayısı.
+pub const MAX_INTERFACES: usize = 4;
+
+/// Bir ağ arayüzünün yapılandırması.
+#[derive(Clone, Copy)]
+pub struct Interface {
+    pub dev: &'static dyn NetDevice,
+    pub addr: Ipv4Addr,
+    pub netmask: Ipv4Addr,
+    /// Varsayılan ağ geçidi (yoksa 0.0.0.0).
+    pub gateway: Ipv4Addr,
+}
+
+impl Interface {
+    /// Alt ağın yayın adresi (örn. 192.168.1.255).
+    pub fn broadcast(&self) -> Ipv4Addr {
+        Ipv4Addr::from_u32(self.addr.to_u32() | !self.netmask.to_u32())
+    }
+}
+
+/// Arayüz başına trafik sayaçları.
+pub struct InterfaceStats {
+    pub rx_packets: AtomicU64,
+    pub rx_bytes: AtomicU64,
+    pub tx_packets: AtomicU64,
+    pub tx_bytes: AtomicU64,
+    pub rx_dropped: AtomicU64,
+    pub tx_errors: AtomicU64,
+}
+
+#[allow(clippy::declare_interior_mutable_const)]
+const EMPTY_STATS: InterfaceStats = InterfaceStats {
+    rx_packets: AtomicU64::new(0),
+    rx_bytes: AtomicU64::new(0),
+    tx_packets: AtomicU64::new(0),
+    tx_bytes: AtomicU64::new(0),
+    rx_dropped: AtomicU64::new(0),
+    tx_errors: AtomicU64::new(0),
+};
+static STATS: [InterfaceStats; MAX_INTERFACES] = [EMPTY_STATS; MAX_INTERFACES];
+
+struct InterfaceTable {
+    lock: Spinlock,
+    ifaces: UnsafeCell<[Option<Interface>; MAX_INTERFACES]>,
+}
+
+// Tabloya erişim `lock` ile korunur.
+unsafe impl Sync for InterfaceTable {}
+
+static INTERFACES: InterfaceTable = InterfaceTable {
+    lock: Spinlock::new(),
+    ifaces: UnsafeCell::new([None; MAX_INTERFACES]),
+};
+
+/// Bir ağ aygıtını arayüz olarak kaydeder.
+///
+/// # Dönüş Değeri
+/// Arayüz numarası; tablo doluysa `OutOfMemoryStatic`.
+pub fn register_device(dev: &'static dyn NetDevice, addr: Ipv4Addr, netmask: Ipv4Addr) -> Result<usize, KernelError> {
+    if dev.mtu() > MAX_MTU {
+        return Err(KernelError::InvalidArgument);
+    }
+    INTERFACES.lock.lock();
+    let ifaces = unsafe { &mut *INTERFACES.ifaces.get() };
+    let result = match ifaces.iter().position(|i| i.is_none()) {
+        Some(id) => {
+            ifaces[id] = Some(Interface { dev, addr, netmask, gateway: Ipv4Addr::UNSPECIFIED });
+            Ok(id)
+        }
+        None => Err(KernelError::OutOfMemoryStatic),
+    };
+    INTERFACES.lock.unlock();
+
+    if result.is_ok() {
+        crate::klog!(LOG, Info, "[NET] {}: {} {}/{} mtu {}", dev.name(), dev.mac(), addr, netmask, dev.mtu());
+    }
+    result
+}
+
+/// Numarası verilen arayüz.
+pub fn interface(id: usize) -> Option<Interface> {
+    INTERFACES.lock.lock();
+    let iface = unsafe { (*INTERFACES.ifaces.get()).get(id).copied().flatten() };
+    INTERFACES.lock.unlock();
+    iface
+}
+
+/// Kayıtlı arayüzler (numarasıyla birlikte).
+pub fn interfaces() -> impl Iterator<Item = (usize, Interface)> {
+    (0..MAX_INTERFACES).filter_map(|id| interface(id).map(|i| (id, i)))
+}
+
+/// Adı verilen arayüzün numarası.
+pub fn find(name: &str) -> Option<usize> {
+    interfaces().find(|(_, i)| i.dev.name() == name).map(|(id, _)| id)
+}
+
+/// Arayüz trafik sayaçları.
+pub fn stats(id: usize) -> Option<&'static InterfaceStats> {
+    STATS.get(id)
+}
+
+/// Arayüzün adres yapılandırmasını değiştirir.
+pub fn configure(id: usize, addr: Ipv4Addr, netmask: Ipv4Addr, gateway: Ipv4Addr) -> Result<(), KernelError> {
+    INTERFACES.lock.lock();
+    let result = match unsafe { (*INTERFACES.ifaces.get()).get_mut(id) } {
+        Some(Some(iface)) => {
+            iface.addr = addr;
+            iface.netmask = netmask;
+            iface.gateway = gateway;
+            Ok(())
+        }
+        _ => Err(KernelError::NotFound),
+    };
+    INTERFACES.lock.unlock();
+    if result.is_ok() {
+        crate::net::arp::flush(id);
+    }
+    result
+}
+
+/// Adres bu sisteme mi ait? (herhangi bir arayüzün adresi veya 127.0.0.0/8)
+pub fn is_local(addr: Ipv4Addr) -> bool {
+    addr.is_loopback() || interfaces().any(|(_, i)| !i.addr.is_unspecified() && i.addr == addr)
+}
+
+/// Hedef için çıkış arayüzünü ve bir sonraki sekmeyi (next hop) seçer.
+///
+/// Yerel adresler geri döngü arayüzüne, doğrudan bağlı alt ağlar ilgili
+/// arayüze, geri kalan her şey ağ geçidi tanımlı ilk arayüze yönlenir.
+pub fn route(dst: Ipv4Addr) -> Option<(usize, Ipv4Addr)> {
+    if is_local(dst) {
+        return interfaces().find(|(_, i)| i.dev.is_loopback()).map(|(id, _)| (id, dst));
+    }
+    let mut ifaces = interfaces().filter(|(_, i)| !i.dev.is_loopback());
+    if dst == Ipv4Addr::BROADCAST {
+        return ifaces.next().map(|(id, _)| (id, dst));
+    }
+    let mut gateway = None;
+    for (id, iface) in ifaces {
+        if !iface.addr.is_unspecified() && dst.same_subnet(iface.addr, iface.netmask) {
+            return Some((id, dst));
+        }
+        if gateway.is_none() && !iface.gateway.is_unspecified() {
+            gateway = Some((id, iface.gateway));
+        }
+    }
+    gateway
+}
+
+/// Ana makine adını (veya "a.b.c.d" metnini) IPv4 adresine çözümler
+/// (bkz. `dns`).
+pub fn lookup(name: &str) -> Result<Ipv4Addr, KernelError> {
+    crate::net::dns::resolve(name)
+}
+
+// -----------------------------------------------------------------------------
+// ÇERÇEVE GÖNDERME
+// -----------------------------------------------------------------------------
+
+crate::kernel_counter!(TX_ERRORS, "net.tx_errors");
+
+/// `payload`'ı Ethernet çerçevesine sarıp arayüzden gönderir.
+pub fn transmit(id: usize, dst: MacAddr, ethertype: u16, payload: &[u8]) -> Result<(), KernelError> {
+    transmit_buf(id, dst, ethertype, NetBuf::from_slice(netbuf::HEADROOM, payload)?)
+}
+
+/// Tampondaki yükün önüne Ethernet başlığını ekleyip arayüzden gönderir.
+pub fn transmit_buf(id: usize, dst: MacAddr, ethertype: u16, buf: NetBuf) -> Result<(), KernelError> {
+    let iface = interface(id).ok_or(KernelError::NotFound)?;
+    if buf.total_len() > iface.dev.mtu() {
+        return Err(KernelError::InvalidArgument);
+    }
+
+    let mut buf = buf.ensure_headroom(ETH_HEADER_LEN)?;
+    let header = buf.push_header(ETH_HEADER_LEN)?;
+    header[0..6].copy_from_slice(&dst.0);
+    header[6..12].copy_from_slice(&iface.dev.mac().0);
+    header[12..14].copy_from_slice(&ethertype.to_be_bytes());
+    let len = buf.total_len();
+
+    let stats = &STATS[id];
+    // Yazılım damgası çerçeve aygıta verilmeden hemen önce alınır
+    let software = hrtimer::now_ns();
+    match iface.dev.transmit_buf(buf) {
+        Ok(()) => {
+            TX_STAMP_SW.get().store(software, Ordering::Relaxed);
+            TX_STAMP_HW.get().store(pack_hw(iface.dev.tx_timestamp()), Ordering::Relaxed);
+            stats.tx_packets.fetch_add(1, Ordering::Relaxed);
+            stats.tx_bytes.fetch_add(len as u64, Ordering::Relaxed);
+            Ok(())
+        }
+        Err(e) => {
+            stats.tx_errors.fetch_add(1, Ordering::Relaxed);
+            TX_ERRORS.inc();
+            Err(e)
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// ALIM KUYRUĞU
+// -----------------------------------------------------------------------------
+
+/// Kuyruktaki en fazla çerçeve sayısı.
+const RX_QUEUE_LEN: usize = 16;
+/// Üreticinin kuyruk kilidi için deneme sayısı; kilit alınamazsa çerçeve atılır.
+const RX_LOCK_SPINS: usize = 1000;
+/// `net` görevinin yoklama aralığı (10 ms) ve önceliği.
+const NET_TASK_INTERVAL_TICKS: u64 = 10;
+const NET_TASK_PRIORITY: u8 = 20;
+
+struct RxSlot {
+    iface: usize,
+    stamp: Timestamp,
+    buf: Option<NetBuf>,
+}
+
+struct RxQueue {
+    /// Kesme bağlamındaki üreticiler kilidi beklemez (bkz. `receive`).
+    busy: AtomicBool,
+    slots: UnsafeCell<[RxSlot; RX_QUEUE_LEN]>,
+    /// Sıradaki okunacak yuva ve dolu yuva sayısı.
+    head: UnsafeCell<usize>,
+    count: UnsafeCell<usize>,
+}
+
+// Tüm alanlara `busy` bayrağı alınarak erişilir.
+unsafe impl Sync for RxQueue {}
+
+const EMPTY_SLOT: RxSlot = RxSlot { iface: 0, stamp: Timestamp::NONE, buf: None };
+
+static RX_QUEUE: RxQueue = RxQueue {
+    busy: AtomicBool::new(false),
+    slots: UnsafeCell::new([EMPTY_SLOT; RX_QUEUE_LEN]),
+    head: UnsafeCell::new(0),
+    count: UnsafeCell::new(0),
+};
+
+/// Aynı anda tek bir `poll` çalışır.
+static POLLING: AtomicBool = AtomicBool::new(false);
+
+crate::kernel_counter!(RX_DROPPED, "net.rx_dropped");
+crate::kernel_counter!(RX_FRAMES, "net.rx_frames");
+
+fn try_acquire_rx(spins: usize) -> bool {
+    for _ in 0..spins {
+        if RX_QUEUE.busy.compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed).is_ok() {
+            return true;
+        }
+        core::hint::spin_loop();
+    }
+    false
+}
+
+fn release_rx() {
+    RX_QUEUE.busy.store(false, Ordering::Release);
+}
+
+/// Sürücüden gelen çerçeveyi bir ağ tamponuna kopyalayıp işlenmek üzere
+/// kuyruğa ekler.
+///
+/// Kesme bağlamından çağrılabilir. Kuyruk doluysa, tampon havuzu boşsa veya
+/// kilit kısa sürede alınamazsa çerçeve atılır ve `net.rx_dropped` artar.
+pub fn receive(id: usize, frame: &[u8]) {
+    receive_timestamped(id, frame, None);
+}
+
+/// `receive` ile aynı; sürücü çerçevenin donanım alım damgasını (ns, aygıt
+/// saatine göre) da verir.
+pub fn receive_timestamped(id: usize, frame: &[u8], hardware: Option<u64>) {
+    // Yazılım damgası kopyalama ve kuyruk beklemesinden önce, ilk fırsatta alınır
+    let stamp = Timestamp { software: hrtimer::now_ns(), hardware };
+    match NetBuf::from_slice(0, frame) {
+        Ok(buf) => enqueue(id, buf, stamp),
+        Err(_) => drop_rx(id),
+    }
+}
+
+/// Sürücünün doldurduğu tamponu kopyalamadan kuyruğa ekler; tampon
+/// zincirli olabilir. Kesme bağlamından çağrılabilir.
+pub fn receive_buf(id: usize, buf: NetBuf, hardware: Option<u64>) {
+    enqueue(id, buf, Timestamp { software: hrtimer::now_ns(), hardware });
+}
+
+fn drop_rx(id: usize) {
+    if let Some(stats) = STATS.get(id) {
+        stats.rx_dropped.fetch_add(1, Ordering::Relaxed);
+    }
+    RX_DROPPED.inc();
+}
+
+fn enqueue(id: usize, buf: NetBuf, stamp: Timestamp) {
+    let Some(stats) = STATS.get(id) else { return };
+    let len = buf.total_len();
+    if len > MAX_FRAME || len < ETH_HEADER_LEN || !try_acquire_rx(RX_LOCK_SPINS) {
+        drop_rx(id);
+        return;
+    }
+    let (head, count) = unsafe { (*RX_QUEUE.head.get(), &mut *RX_QUEUE.count.get()) };
+    if *count == RX_QUEUE_LEN {
+        release_rx();
+        drop_rx(id);
+        return;
+    }
+    let slot = unsafe { &mut (*RX_QUEUE.slots.get())[(head + *count) % RX_QUEUE_LEN] };
+    slot.iface = id;
+    slot.stamp = stamp;
+    slot.buf = Some(buf);
+    *count += 1;
+    release_rx();
+
+    stats.rx_packets.fetch_add(1, Ordering::Relaxed);
+    stats.rx_bytes.fetch_add(len as u64, Ordering::Relaxed);
+}
+
+/// Kuyruktan sıradaki çerçeveyi alır.
+fn dequeue() -> Option<(usize, NetBuf, Timestamp)> {
+    while !try_acquire_rx(RX_LOCK_SPINS) {}
+    let (head, count) = unsafe { (&mut *RX_QUEUE.head.get(), &mut *RX_QUEUE.count.get()) };
+    let result = (*count > 0).then(|| {
+        let slot = unsafe { &mut (*RX_QUEUE.slots.get())[*head] };
+        *head = (*head + 1) % RX_QUEUE_LEN;
+        *count -= 1;
+        (slot.iface, slot.buf.take(), slot.stamp)
+    });
+    release_rx();
+    result.and_then(|(id, buf, stamp)| Some((id, buf?, stamp)))
+}
+
+/// Bekleyen tüm çerçeveleri protokol katmanlarına dağıtır.
+///
+/// Görev bağlamından çağrılmalıdır. İşleme sırasında üretilen yanıtlar
+/// (örn. geri döngüye gönderilen ICMP yankısı) da aynı çağrıda işlenir.
+///
+/// # Dönüş Değeri
+/// İşlenen çerçeve sayısı.
+pub fn poll() -> usize {
+    if POLLING.swap(true, Ordering::Acquire) {
+        return 0;
+    }
+    let mut processed = 0;
+    while let Some((id, buf, stamp)) = dequeue() {
+        RX_FRAMES.inc();
+        RX_STAMP_SW.store(stamp.software, Ordering::Relaxed);
+        RX_STAMP_HW.store(pack_hw(stamp.hardware), Ordering::Relaxed);
+        dispatch(id, buf);
+        processed += 1;
+    }
+    // TCP yeniden gönderim ve TIME-WAIT süreleri
+    crate::net::tcp::on_poll();
+    POLLING.store(false, Ordering::Release);
+    processed
+}
+
+/// `ticks` tik boyunca yığını ilerletir (örn. ARP yanıtı beklerken).
+pub fn poll_for(ticks: u64) {
+    let deadline = crate::sched::ticks() + ticks;
+    while crate::sched::ticks() < deadline {
+        poll();
+        core::hint::spin_loop();
+    }
+}
+
+/// Yığını düzenli aralıklarla ilerleten `net` işçisi: uygulamalar beklemese
+/// de gelen çerçeveler işlenir ve TCP zamanlayıcıları çalışır.
+fn net_work(work: crate::kthread::Work) {
+    if work == crate::kthread::Work::Tick {
+        poll();
+    }
+}
+
+fn start_net_task() -> Result<(), KernelError> {
+    crate::kthread::spawn_periodic("net", NET_TASK_PRIORITY, || NET_TASK_INTERVAL_TICKS, net_work)?;
+    Ok(())
+}
+
+crate::initcall!(late, "net-task", start_net_task);
+
+/// Ethernet yükünü protokole verir. Protokoller tamponun içini dilim olarak
+/// okur; yalnızca zincirli çerçeveler düzleştirilirken kopyalanır.
+fn dispatch(id: usize, buf: NetBuf) {
+    let Ok(buf) = buf.linearize() else {
+        drop_rx(id);
+        return;
+    };
+    let frame = buf.data();
+    let ethertype = u16::from_be_bytes([frame[12], frame[13]]);
+    let payload = &frame[ETH_HEADER_LEN..];
+    match ethertype {
+        ETHERTYPE_IPV4 => crate::net::ipv4::receive(id, payload),
+        ETHERTYPE_ARP => crate::net::arp::receive(id, payload),
+        _ => {
+            STATS[id].rx_dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// KABUK VE PROCFS
+// -----------------------------------------------------------------------------
+
+fn ifconfig_command(args: &[&str]) -> Result<(), KernelError> {
+    if let (Some(name), Some(addr)) = (args.get(1), args.get(2)) {
+        let id = find(name).ok_or(KernelError::NotFound)?;
+        let addr = Ipv4Addr::parse(addr).ok_or(KernelError::InvalidArgument)?;
+        let parse_or = |i: usize, default| args.get(i).map_or(Some(default), |s| Ipv4Addr::parse(s));
+        let netmask = parse_or(3, Ipv4Addr([255, 255, 255, 0])).ok_or(KernelError::InvalidArgument)?;
+        let gateway = parse_or(4, Ipv4Addr::UNSPECIFIED).ok_or(KernelError::InvalidArgument)?;
+        return configure(id, addr, netmask, gateway);
+    }
+    for (id, iface) in interfaces() {
+        let stats = &STATS[id];
+        serial_println!("{}: {} {}/{} gw {} mtu {}", iface.dev.name(), iface.dev.mac(), iface.addr, iface.netmask, iface.gateway, iface.dev.mtu());
+        serial_println!(
+            "    RX {} paket {} B ({} atıldı)  TX {} paket {} B ({} hata)",
+            stats.rx_packets.load(Ordering::Relaxed),
+            stats.rx_bytes.load(Ordering::Relaxed),
+            stats.rx_dropped.load(Ordering::Relaxed),
+            stats.tx_packets.load(Ordering::Relaxed),
+            stats.tx_bytes.load(Ordering::Relaxed),
+            stats.tx_errors.load(Ordering::Relaxed)
+        );
+    }
+    Ok(())
+}
+
+crate::shell_command!("ifconfig", "Ağ arayüzleri (ifconfig [<arayüz> <adres> [maske] [ağ geçidi]])", ifconfig_command);
+
+fn show_dev(out: &mut dyn core::fmt::Write) -> core::fmt::Result {
+    for (id, iface) in interfaces() {
+        let stats = &STATS[id];
+        writeln!(
+            out,
+            "{} {} {} {} {} {} {}",
+            iface.dev.name(),
+            stats.rx_packets.load(Ordering::Relaxed),
+            stats.rx_bytes.load(Ordering::Relaxed),
+            stats.rx_dropped.load(Ordering::Relaxed),
+            stats.tx_packets.load(Ordering::Relaxed),
+            stats.tx_bytes.load(Ordering::Relaxed),
+            stats.tx_errors.load(Ordering::Relaxed)
+        )?;
+    }
+    Ok(())
+}
+
+crate::proc_entry!("net/dev", show_dev);
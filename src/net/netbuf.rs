@@ -0,0 +1,322 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/net/netbuf.rs
+// Ağ tamponları (pbuf/skb benzeri): başlık payı, referans sayımı ve zincirleme.
+//
+// Her `NetBuf` `NETBUF_SIZE` baytlık bir bölütü gösterir; geçerli veri
+// `[head, tail)` aralığıdır. Gönderimde veri tampona bir kez yazılır ve her
+// katman kendi başlığını önündeki paya (`push_header`) ekler; alımda
+// katmanlar başlıkları `pull_header` ile geçer. Böylece çerçeve katmanlar
+// arasında kopyalanmaz.
+//
+// * Bölütler `netbuf` nesne havuzundan alınır; tahsis kilitsiz ve O(1)
+//   olduğundan sürücüler kesme bağlamında da tampon alabilir. Havuz boşsa
+//   `OutOfMemoryStatic` döner.
+// * `clone` bölütü kopyalamaz, referans sayısını artırır; son tutamaç
+//   düşürülünce bölüt (ve zincirin geri kalanı) havuza döner. Paylaşılan
+//   tampon değiştirilemez: değiştiren yöntemler tek tutamaç gerektirir.
+// * Bölütler `append` ile zincirlenebilir (dağıt-topla). Önünde yer olmayan
+//   veya paylaşılan bir tampona başlık eklemek için `ensure_headroom` yeni
+//   bir baş bölütü zincirin önüne koyar.
+
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicU32, Ordering};
+use crate::net::net::MAX_FRAME;
+use crate::platformgeneric::KernelError;
+
+// -----------------------------------------------------------------------------
+// SABİTLER VE TİPLER
+// -----------------------------------------------------------------------------
+
+/// Gönderim tamponlarında başlıklar için ayrılan pay (Ethernet + IPv4 + TCP
+/// ve seçenekler).
+pub const HEADROOM: usize = 64;
+/// Bölüt boyutu: pay ve en büyük çerçeve.
+pub const NETBUF_SIZE: usize = HEADROOM + MAX_FRAME;
+/// Havuzdaki bölüt sayısı (alım kuyruğu ve uçuştaki gönderimler).
+pub const NETBUF_COUNT: usize = 32;
+
+/// Bölüt üst verisi; veri baytları `STORAGE`'da aynı indekstedir.
+struct Meta {
+    refs: AtomicU32,
+    head: usize,
+    tail: usize,
+    next: Option<NetBuf>,
+}
+
+crate::mempool!(NETBUFS: Meta, NETBUF_COUNT, "netbuf");
+
+struct Storage([UnsafeCell<[u8; NETBUF_SIZE]>; NETBUF_COUNT]);
+
+// Bir bölütün baytlarına yalnızca onu tutan `NetBuf` erişir; yazma tek tutamaç gerektirir.
+unsafe impl Sync for Storage {}
+
+static STORAGE: Storage = Storage([const { UnsafeCell::new([0; NETBUF_SIZE]) }; NETBUF_COUNT]);
+
+/// Referans sayımlı ağ tamponu tutamacı.
+pub struct NetBuf {
+    index: usize,
+}
+
+// -----------------------------------------------------------------------------
+// TAHSİS
+// -----------------------------------------------------------------------------
+
+impl NetBuf {
+    /// Boş bir tampon alır; veri `headroom` ofsetinden başlar.
+    ///
+    /// # Hatalar
+    /// * `InvalidArgument`: `headroom` bölütten büyük.
+    /// * `OutOfMemoryStatic`: Havuz boş.
+    pub fn alloc(headroom: usize) -> Result<NetBuf, KernelError> {
+        if headroom > NETBUF_SIZE {
+            return Err(KernelError::InvalidArgument);
+        }
+        let index = NETBUFS.alloc_index().ok_or(KernelError::OutOfMemoryStatic)?;
+        // SAFETY: Yuva yeni tahsis edildi; ona yalnızca biz erişiyoruz.
+        unsafe { NETBUFS.slot(index).write(Meta { refs: AtomicU32::new(1), head: headroom, tail: headroom, next: None }) };
+        Ok(NetBuf { index })
+    }
+
+    /// `data`'yı `headroom` payından sonra içeren bir tampon.
+    pub fn from_slice(headroom: usize, data: &[u8]) -> Result<NetBuf, KernelError> {
+        let mut buf = NetBuf::alloc(headroom)?;
+        buf.put(data.len())?.copy_from_slice(data);
+        Ok(buf)
+    }
+
+    fn meta(&self) -> &Meta {
+        // SAFETY: Yuva en az bu tutamaç yaşadıkça tahsislidir.
+        unsafe { &*NETBUFS.slot(self.index) }
+    }
+
+    fn meta_mut(&mut self) -> &mut Meta {
+        assert!(self.is_unique(), "netbuf: paylaşılan tampon değiştirilemez");
+        // SAFETY: Tek tutamaç biziz ve `&mut self` ödünç almayı dışlar.
+        unsafe { &mut *NETBUFS.slot(self.index) }
+    }
+
+    fn bytes(&self) -> &[u8; NETBUF_SIZE] {
+        // SAFETY: Bölüt tahsisli; yazma yalnızca tek tutamaçla yapılır.
+        unsafe { &*STORAGE.0[self.index].get() }
+    }
+
+    /// Üst veri ve baytlar birlikte (tek tutamaç).
+    fn parts_mut(&mut self) -> (&mut Meta, &mut [u8; NETBUF_SIZE]) {
+        let index = self.index;
+        let meta = self.meta_mut();
+        // SAFETY: `meta_mut` tek tutamaç olduğumuzu doğruladı.
+        (meta, unsafe { &mut *STORAGE.0[index].get() })
+    }
+
+    /// Bu bölüte başka tutamaç yok mu?
+    pub fn is_unique(&self) -> bool {
+        self.meta().refs.load(Ordering::Acquire) == 1
+    }
+
+    /// Bu bölütün tutamaç sayısı.
+    pub fn ref_count(&self) -> u32 {
+        self.meta().refs.load(Ordering::Relaxed)
+    }
+}
+
+impl Clone for NetBuf {
+    fn clone(&self) -> Self {
+        self.meta().refs.fetch_add(1, Ordering::Relaxed);
+        NetBuf { index: self.index }
+    }
+}
+
+impl Drop for NetBuf {
+    fn drop(&mut self) {
+        if self.meta().refs.fetch_sub(1, Ordering::AcqRel) == 1 {
+            // SAFETY: Son tutamaç bizdik; üst veri (zincirin geri kalanı dahil) bir kez düşürülür.
+            unsafe {
+                NETBUFS.slot(self.index).drop_in_place();
+                NETBUFS.free_index(self.index);
+            }
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// VERİ
+// -----------------------------------------------------------------------------
+
+impl NetBuf {
+    /// Bu bölütteki veri.
+    pub fn data(&self) -> &[u8] {
+        let meta = self.meta();
+        &self.bytes()[meta.head..meta.tail]
+    }
+
+    /// Bu bölütteki veri (yazılabilir; tek tutamaç gerektirir).
+    pub fn data_mut(&mut self) -> &mut [u8] {
+        let (meta, bytes) = self.parts_mut();
+        &mut bytes[meta.head..meta.tail]
+    }
+
+    /// Bu bölütteki veri uzunluğu.
+    pub fn len(&self) -> usize {
+        let meta = self.meta();
+        meta.tail - meta.head
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Zincirdeki toplam veri uzunluğu.
+    pub fn total_len(&self) -> usize {
+        self.segments().map(<[u8]>::len).sum()
+    }
+
+    /// Verinin önündeki boş alan.
+    pub fn headroom(&self) -> usize {
+        self.meta().head
+    }
+
+    /// Verinin arkasındaki boş alan.
+    pub fn tailroom(&self) -> usize {
+        NETBUF_SIZE - self.meta().tail
+    }
+
+    /// Verinin önüne `len` baytlık başlık alanı açar ve onu döndürür.
+    ///
+    /// # Hatalar
+    /// * `InvalidArgument`: Önde yeterli pay yok (bkz. `ensure_headroom`).
+    pub fn push_header(&mut self, len: usize) -> Result<&mut [u8], KernelError> {
+        let (meta, bytes) = self.parts_mut();
+        if len > meta.head {
+            return Err(KernelError::InvalidArgument);
+        }
+        meta.head -= len;
+        Ok(&mut bytes[meta.head..meta.head + len])
+    }
+
+    /// Verinin başından `len` baytı (işlenmiş başlığı) atlar.
+    ///
+    /// # Hatalar
+    /// * `InvalidArgument`: Bölütte `len` bayt veri yok.
+    pub fn pull_header(&mut self, len: usize) -> Result<(), KernelError> {
+        let meta = self.meta_mut();
+        if len > meta.tail - meta.head {
+            return Err(KernelError::InvalidArgument);
+        }
+        meta.head += len;
+        Ok(())
+    }
+
+    /// Verinin sonuna `len` bayt ekler ve eklenen alanı döndürür.
+    ///
+    /// # Hatalar
+    /// * `InvalidArgument`: Arkada yeterli yer yok.
+    pub fn put(&mut self, len: usize) -> Result<&mut [u8], KernelError> {
+        let (meta, bytes) = self.parts_mut();
+        if len > NETBUF_SIZE - meta.tail {
+            return Err(KernelError::InvalidArgument);
+        }
+        meta.tail += len;
+        Ok(&mut bytes[meta.tail - len..meta.tail])
+    }
+
+    /// Bu bölütün verisini ilk `len` bayta kısaltır (örn. Ethernet dolgusu).
+    pub fn trim(&mut self, len: usize) {
+        let meta = self.meta_mut();
+        meta.tail = meta.tail.min(meta.head + len);
+    }
+}
+
+// -----------------------------------------------------------------------------
+// ZİNCİRLEME
+// -----------------------------------------------------------------------------
+
+impl NetBuf {
+    /// Zincirdeki sonraki bölüt.
+    pub fn next(&self) -> Option<&NetBuf> {
+        self.meta().next.as_ref()
+    }
+
+    /// Zincirdeki tüm bölütlerin verisi, sırayla.
+    pub fn segments(&self) -> impl Iterator<Item = &[u8]> {
+        core::iter::successors(Some(self), |buf| buf.next()).map(NetBuf::data)
+    }
+
+    /// `tail`'i zincirin sonuna ekler. Zincir boyunca bölütler paylaşılmamış
+    /// olmalıdır.
+    pub fn append(&mut self, tail: NetBuf) {
+        let meta = self.meta_mut();
+        match &mut meta.next {
+            Some(next) => next.append(tail),
+            None => meta.next = Some(tail),
+        }
+    }
+
+    /// Önünde en az `len` bayt pay bulunan, değiştirilebilir bir baş
+    /// bölütü garanti eder. Gerekirse yeni bir bölüt alınır ve tampon onun
+    /// arkasına zincirlenir; veri kopyalanmaz.
+    pub fn ensure_headroom(self, len: usize) -> Result<NetBuf, KernelError> {
+        if self.is_unique() && self.headroom() >= len {
+            return Ok(self);
+        }
+        let mut head = NetBuf::alloc(NETBUF_SIZE)?;
+        head.append(self);
+        Ok(head)
+    }
+
+    /// Zincirin verisini `out`'a kopyalar.
+    ///
+    /// # Dönüş Değeri
+    /// Kopyalanan bayt sayısı (`out` kısaysa kesilir).
+    pub fn copy_to(&self, out: &mut [u8]) -> usize {
+        let mut copied = 0;
+        for segment in self.segments() {
+            let n = segment.len().min(out.len() - copied);
+            out[copied..copied + n].copy_from_slice(&segment[..n]);
+            copied += n;
+        }
+        copied
+    }
+
+    /// Zincirli tamponu tek bölüte düzleştirir; tek bölütlü tampon olduğu
+    /// gibi döner.
+    ///
+    /// # Hatalar
+    /// * `InvalidArgument`: Toplam veri bir bölüte sığmıyor.
+    /// * `OutOfMemoryStatic`: Havuz boş.
+    pub fn linearize(self) -> Result<NetBuf, KernelError> {
+        if self.next().is_none() {
+            return Ok(self);
+        }
+        let mut flat = NetBuf::alloc(HEADROOM)?;
+        let dst = flat.put(self.total_len())?;
+        self.copy_to(dst);
+        Ok(flat)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// TESTLER
+// -----------------------------------------------------------------------------
+
+crate::ktest! {
+    fn netbuf_headroom_and_chain() {
+        let mut buf = NetBuf::from_slice(HEADROOM, b"veri")?;
+        buf.push_header(2)?.copy_from_slice(b"h2");
+        crate::ktest_assert_eq!(buf.data(), b"h2veri");
+
+        // Paylaşılan tampona başlık, önüne zincirlenen yeni bölütle eklenir
+        let shared = buf.clone();
+        crate::ktest_assert!(!buf.is_unique());
+        let mut buf = buf.ensure_headroom(1)?;
+        buf.push_header(1)?.copy_from_slice(b"e");
+        crate::ktest_assert!(buf.next().is_some());
+        let mut out = [0u8; 16];
+        let len = buf.copy_to(&mut out);
+        crate::ktest_assert_eq!(&out[..len], b"eh2veri");
+
+        let flat = buf.linearize()?;
+        crate::ktest_assert_eq!(flat.data(), b"eh2veri");
+        crate::ktest_assert_eq!(shared.ref_count(), 1);
+    }
+}
@@ -0,0 +1,328 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/net/socket.rs
+// Datagram soket katmanı: protokol katmanlarından gelen yükleri bağlı
+// sokete teslim eder ve uygulamalara gönderme/alma arayüzü sunar.
+//
+// Her soket bir protokole ve bir yerel "porta" bağlanır. UDP soketlerinde
+// bu UDP portudur; ICMP soketlerinde yankı isteklerinin tanımlayıcı
+// (identifier) alanıdır: gönderilen isteklere soketin tanımlayıcısı yazılır
+// ve aynı tanımlayıcıyla gelen yankı yanıtları sokete teslim edilir.
+// Alma tarafı engellemesizdir;
+// `recv_timeout` beklerken `net::poll` çağırarak yığını ilerletir.
+//
+// Zaman damgası açık soketlerde (`set_timestamping`) her datagram alım
+// damgasıyla kuyruğa girer ve son gönderimin damgası saklanır (bkz.
+// `net::Timestamp`); PTP istemcileri bunları olay iletileri için kullanır.
+
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicBool, AtomicU16, Ordering};
+use crate::net::net::{self, Ipv4Addr, Timestamp};
+use crate::platformgeneric::spinlock::Spinlock;
+use crate::platformgeneric::KernelError;
+
+// -----------------------------------------------------------------------------
+// SABİTLER
+// -----------------------------------------------------------------------------
+
+/// En fazla soket sayısı.
+pub const MAX_SOCKETS: usize = 8;
+/// Soket başına kuyruktaki en fazla datagram sayısı.
+const SOCKET_QUEUE_LEN: usize = 4;
+/// En büyük datagram yükü (IPv4 başlığı hariç).
+pub const MAX_DATAGRAM: usize = net::MAX_MTU - crate::net::ipv4::HEADER_LEN;
+
+/// Otomatik atanan portların aralığı (IANA geçici port aralığı).
+const EPHEMERAL_FIRST: u16 = 49152;
+
+/// Soket protokolü.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    Icmp,
+    Udp,
+}
+
+// -----------------------------------------------------------------------------
+// SOKET TABLOSU
+// -----------------------------------------------------------------------------
+
+#[derive(Clone, Copy)]
+struct Datagram {
+    src: Ipv4Addr,
+    src_port: u16,
+    len: usize,
+    stamp: Timestamp,
+    data: [u8; MAX_DATAGRAM],
+}
+
+struct SocketState {
+    protocol: Protocol,
+    port: u16,
+    queue: [Datagram; SOCKET_QUEUE_LEN],
+    head: usize,
+    count: usize,
+    dropped: u64,
+    timestamping: bool,
+    last_tx: Option<Timestamp>,
+}
+
+struct SocketSlot {
+    used: AtomicBool,
+    lock: Spinlock,
+    state: UnsafeCell<SocketState>,
+}
+
+// `state` yalnızca `lock` tutulurken değiştirilir.
+unsafe impl Sync for SocketSlot {}
+
+const EMPTY_DATAGRAM: Datagram =
+    Datagram { src: Ipv4Addr::UNSPECIFIED, src_port: 0, len: 0, stamp: Timestamp::NONE, data: [0; MAX_DATAGRAM] };
+
+#[allow(clippy::declare_interior_mutable_const)]
+const EMPTY: SocketSlot = SocketSlot {
+    used: AtomicBool::new(false),
+    lock: Spinlock::new(),
+    state: UnsafeCell::new(SocketState {
+        protocol: Protocol::Icmp,
+        port: 0,
+        queue: [EMPTY_DATAGRAM; SOCKET_QUEUE_LEN],
+        head: 0,
+        count: 0,
+        dropped: 0,
+        timestamping: false,
+        last_tx: None,
+    }),
+};
+static SOCKETS: [SocketSlot; MAX_SOCKETS] = [EMPTY; MAX_SOCKETS];
+
+/// Sıradaki geçici portun aralık içindeki konumu.
+static NEXT_EPHEMERAL: AtomicU16 = AtomicU16::new(0);
+
+crate::kernel_counter!(SOCKET_DROPPED, "net.socket_dropped");
+
+impl SocketSlot {
+    fn with<R>(&self, f: impl FnOnce(&mut SocketState) -> R) -> R {
+        self.lock.lock();
+        let result = f(unsafe { &mut *self.state.get() });
+        self.lock.unlock();
+        result
+    }
+}
+
+/// Protokol ve porta bağlı açık soket var mı?
+fn port_in_use(protocol: Protocol, port: u16) -> bool {
+    SOCKETS
+        .iter()
+        .any(|s| s.used.load(Ordering::Acquire) && s.with(|st| st.protocol == protocol && st.port == port))
+}
+
+/// Protokol katmanından gelen yükü bağlı sokete teslim eder.
+///
+/// # Dönüş Değeri
+/// Yükü alan bir soket varsa `true` (kuyruğu dolu olsa bile).
+pub fn deliver(protocol: Protocol, port: u16, src: Ipv4Addr, src_port: u16, data: &[u8]) -> bool {
+    for slot in SOCKETS.iter().filter(|s| s.used.load(Ordering::Acquire)) {
+        let matched = slot.with(|st| {
+            if st.protocol != protocol || st.port != port {
+                return false;
+            }
+            if st.count == SOCKET_QUEUE_LEN || data.len() > MAX_DATAGRAM {
+                st.dropped += 1;
+                SOCKET_DROPPED.inc();
+                return true;
+            }
+            let dgram = &mut st.queue[(st.head + st.count) % SOCKET_QUEUE_LEN];
+            dgram.src = src;
+            dgram.src_port = src_port;
+            dgram.len = data.len();
+            dgram.stamp = if st.timestamping { net::rx_timestamp() } else { Timestamp::NONE };
+            dgram.data[..data.len()].copy_from_slice(data);
+            st.count += 1;
+            true
+        });
+        if matched {
+            return true;
+        }
+    }
+    false
+}
+
+// -----------------------------------------------------------------------------
+// SOKET TANITICISI
+// -----------------------------------------------------------------------------
+
+/// Açık bir soket; düşürüldüğünde kapanır.
+pub struct Socket {
+    index: usize,
+}
+
+impl Socket {
+    /// Yeni bir soket açar ve geçici bir porta bağlar.
+    pub fn open(protocol: Protocol) -> Result<Socket, KernelError> {
+        let index = SOCKETS
+            .iter()
+            .position(|s| !s.used.swap(true, Ordering::AcqRel))
+            .ok_or(KernelError::OutOfMemoryStatic)?;
+        let socket = Socket { index };
+        SOCKETS[index].with(|st| {
+            st.protocol = protocol;
+            st.port = 0;
+            st.head = 0;
+            st.count = 0;
+            st.dropped = 0;
+            st.timestamping = false;
+            st.last_tx = None;
+        });
+        socket.bind(0)?;
+        Ok(socket)
+    }
+
+    fn slot(&self) -> &'static SocketSlot {
+        &SOCKETS[self.index]
+    }
+
+    pub fn protocol(&self) -> Protocol {
+        self.slot().with(|st| st.protocol)
+    }
+
+    /// Soketin bağlı olduğu yerel port.
+    pub fn port(&self) -> u16 {
+        self.slot().with(|st| st.port)
+    }
+
+    /// Soketi yerel bir porta bağlar; 0 verilirse boş bir geçici port seçilir.
+    pub fn bind(&self, port: u16) -> Result<(), KernelError> {
+        let protocol = self.protocol();
+        let port = if port != 0 {
+            if port_in_use(protocol, port) {
+                return Err(KernelError::ResourceBusy);
+            }
+            port
+        } else {
+            let range = u16::MAX - EPHEMERAL_FIRST;
+            (0..range)
+                .map(|_| EPHEMERAL_FIRST + NEXT_EPHEMERAL.fetch_add(1, Ordering::Relaxed) % range)
+                .find(|&p| !port_in_use(protocol, p))
+                .ok_or(KernelError::ResourceBusy)?
+        };
+        self.slot().with(|st| st.port = port);
+        Ok(())
+    }
+
+    /// Bir datagram gönderir. ICMP soketlerinde `data` tam ICMP iletisidir;
+    /// tanımlayıcı ve sağlama toplamı çekirdek tarafından yazılır, `port`
+    /// yok sayılır.
+    pub fn send_to(&self, dst: Ipv4Addr, port: u16, data: &[u8]) -> Result<(), KernelError> {
+        match self.protocol() {
+            Protocol::Icmp => crate::net::icmp::send_echo(dst, self.port(), data),
+            Protocol::Udp => crate::net::udp::send(self.port(), dst, port, data),
+        }?;
+        self.record_tx_timestamp();
+        Ok(())
+    }
+
+    /// Zaman damgası açıksa son gönderimin damgasını saklar.
+    fn record_tx_timestamp(&self) {
+        let stamp = net::last_tx_timestamp();
+        self.slot().with(|st| {
+            if st.timestamping {
+                st.last_tx = Some(stamp);
+            }
+        });
+    }
+
+    /// Alım/gönderim zaman damgalarını açar veya kapatır (SO_TIMESTAMPING).
+    pub fn set_timestamping(&self, enabled: bool) {
+        self.slot().with(|st| {
+            st.timestamping = enabled;
+            st.last_tx = None;
+        });
+    }
+
+    /// Son gönderilen datagramın damgası; damga kapalıysa veya henüz
+    /// gönderim yoksa `None`.
+    pub fn tx_timestamp(&self) -> Option<Timestamp> {
+        self.slot().with(|st| st.last_tx)
+    }
+
+    /// UDP datagramını arayüzden yayınlar (örn. adres almadan önce DHCP).
+    pub fn send_broadcast(&self, iface: usize, port: u16, data: &[u8]) -> Result<(), KernelError> {
+        match self.protocol() {
+            Protocol::Udp => crate::net::udp::send_broadcast(iface, self.port(), port, data),
+            Protocol::Icmp => Err(KernelError::InvalidArgument),
+        }?;
+        self.record_tx_timestamp();
+        Ok(())
+    }
+
+    /// Bekleyen bir datagramı `buf`'a kopyalar (engellemesiz).
+    ///
+    /// # Dönüş Değeri
+    /// `(uzunluk, kaynak adres, kaynak port)`; `buf`'a sığmayan kısım atılır.
+    pub fn recv_from(&self, buf: &mut [u8]) -> Option<(usize, Ipv4Addr, u16)> {
+        self.recv_from_timestamped(buf).map(|(len, src, port, _)| (len, src, port))
+    }
+
+    /// `recv_from` ile aynı; datagramın alım damgasını da döndürür (damga
+    /// kapalıyken alınmış datagramlarda `Timestamp::NONE`).
+    pub fn recv_from_timestamped(&self, buf: &mut [u8]) -> Option<(usize, Ipv4Addr, u16, Timestamp)> {
+        self.slot().with(|st| {
+            if st.count == 0 {
+                return None;
+            }
+            let dgram = &st.queue[st.head];
+            let len = dgram.len.min(buf.len());
+            buf[..len].copy_from_slice(&dgram.data[..len]);
+            let result = (len, dgram.src, dgram.src_port, dgram.stamp);
+            st.head = (st.head + 1) % SOCKET_QUEUE_LEN;
+            st.count -= 1;
+            Some(result)
+        })
+    }
+
+    /// En fazla `timeout_ticks` tik boyunca datagram bekler; beklerken
+    /// yığını `net::poll` ile ilerletir.
+    pub fn recv_timeout(&self, buf: &mut [u8], timeout_ticks: u64) -> Result<(usize, Ipv4Addr, u16), KernelError> {
+        let deadline = crate::sched::ticks() + timeout_ticks;
+        loop {
+            net::poll();
+            if let Some(result) = self.recv_from(buf) {
+                return Ok(result);
+            }
+            if crate::sched::ticks() >= deadline {
+                return Err(KernelError::ResourceBusy);
+            }
+            core::hint::spin_loop();
+        }
+    }
+
+    /// Kuyruk dolu olduğu için atılan datagram sayısı.
+    pub fn dropped(&self) -> u64 {
+        self.slot().with(|st| st.dropped)
+    }
+}
+
+impl Drop for Socket {
+    fn drop(&mut self) {
+        let slot = self.slot();
+        slot.with(|st| {
+            st.port = 0;
+            st.count = 0;
+        });
+        slot.used.store(false, Ordering::Release);
+    }
+}
+
+// -----------------------------------------------------------------------------
+// PROCFS
+// -----------------------------------------------------------------------------
+
+fn show_sockets(out: &mut dyn core::fmt::Write) -> core::fmt::Result {
+    for (i, slot) in SOCKETS.iter().enumerate().filter(|(_, s)| s.used.load(Ordering::Acquire)) {
+        let (protocol, port, count, dropped) = slot.with(|st| (st.protocol, st.port, st.count, st.dropped));
+        writeln!(out, "{} {:?} {} {} {}", i, protocol, port, count, dropped)?;
+    }
+    Ok(())
+}
+
+crate::proc_entry!("net/sockets", show_sockets);
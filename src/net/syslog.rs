@@ -0,0 +1,506 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/net/syslog.rs
+// Çekirdek günlüklerinin UDP üzerinden syslog toplayıcısına iletilmesi.
+//
+// Kayıtlar iki yoldan toplanır:
+// * `console::set_log_tap`: `syslog.level` seviyesine kadar tüm `klog!`
+//   iletileri (kesme bağlamı dahil) önem seviyesiyle birlikte.
+// * `console` çoğullayıcısının `netlog` hedefi: bu hedefe bağlanan
+//   görevlerin çıktısı (`console bind <görev> stdio netlog`). `log` akışı
+//   `netlog`'a bağlanan görevin iletileri seviyeden bağımsız iletilir.
+//
+// Kayıtlar sabit boyutlu bir halkada biriktirilir; `syslogd` görevi halkayı
+// `FLUSH_TICKS` aralıklarla boşaltır ve kayıtları RFC 5424 satırları olarak
+// paketler. Bir datagram birden fazla satır taşıyabilir (satırlar `\n` ile
+// ayrılır; toplayıcı tarafında satır bölme gerekir):
+//
+//   <6>1 2026-10-16T09:30:12.345678Z sahne dhcp 7 - - [DHCP] Kira sona erdi
+//
+// Geri basınç: günlük yazan bağlam asla beklemez. Halka doluysa yeni kayıt
+// atılır (`syslog.dropped`); gönderim başarısız olursa (ARP çözülmedi, ağ
+// yok) kayıtlar halkada kalır ve `RETRY_TICKS` sonra yeniden denenir. Atılan
+// kayıt sayısı yer açıldığında toplayıcıya ayrı bir satırla bildirilir.
+// `syslogd` görevinin kendi ürettiği iletiler geri besleme döngüsü
+// oluşmasın diye toplanmaz.
+//
+// Duvar saati ayarlanmamışsa zaman damgası NILVALUE (`-`) olarak gönderilir.
+//
+// Ayar: kalıcı `SyslogConfig` (`cfg.syslog`); kabuk
+// `syslog [status | server <adres> [port] | disable]`.
+
+use core::cell::UnsafeCell;
+use core::fmt::{self, Write};
+use core::sync::atomic::{AtomicBool, AtomicU16, AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use crate::config::{self, Config, TlvReader, TlvWriter};
+use crate::console::{self, Sink, Stream};
+use crate::net::net::Ipv4Addr;
+use crate::net::socket::{Protocol, Socket};
+use crate::platformgeneric::{KernelError, SystemConstants};
+use crate::sched::{self, TaskId};
+use crate::serial_println;
+use crate::sysctl::{Level, LogLevel};
+
+// -----------------------------------------------------------------------------
+// SABİTLER
+// -----------------------------------------------------------------------------
+
+/// Varsayılan toplayıcı portu (RFC 5426).
+pub const DEFAULT_PORT: u16 = 514;
+
+/// Halkadaki kayıt sayısı.
+const MAX_RECORDS: usize = 64;
+/// Kayıt başına ileti uzunluğu; uzun iletiler kesilir.
+const RECORD_TEXT: usize = 192;
+/// Datagram boyutu (parçalanmasız Ethernet MTU'su altında).
+const MAX_DATAGRAM: usize = 1200;
+/// Tek satırın (başlık + ileti) en fazla uzunluğu.
+const MAX_LINE: usize = RECORD_TEXT + 96;
+
+const TICK_HZ: u64 = SystemConstants::TIMER_TICK_HZ;
+/// Boşaltma aralığı (100 ms).
+const FLUSH_TICKS: u64 = TICK_HZ / 10;
+/// Başarısız gönderimden sonra bekleme (1 s).
+const RETRY_TICKS: u64 = TICK_HZ;
+
+const TASK_PRIORITY: u8 = 26;
+
+/// Çekirdek iletileri için syslog tesisi (facility) `kern`.
+const FACILITY_KERN: u8 = 0;
+/// `netlog` hedefine yazılan görev çıktısının önemi (`notice`).
+const SEVERITY_STDIO: u8 = 5;
+
+/// Duvar saati bu değerden (2000-01-01) önceyse ayarlanmamış sayılır.
+const WALL_VALID_SECS: u64 = 946_684_800;
+
+const NO_TASK: usize = usize::MAX;
+
+crate::sysctl!(pub LEVEL: Level = Level::new(LogLevel::Info), "syslog.level", "Toplayıcıya iletilen en düşük günlük seviyesi");
+
+crate::kernel_counter!(RECORDS, "syslog.records");
+crate::kernel_counter!(DATAGRAMS, "syslog.datagrams");
+crate::kernel_counter!(DROPPED, "syslog.dropped");
+crate::kernel_counter!(SEND_ERRORS, "syslog.send_errors");
+
+// -----------------------------------------------------------------------------
+// KALICI AYARLAR
+// -----------------------------------------------------------------------------
+
+/// Günlük iletimi ayarları.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyslogConfig {
+    /// Toplayıcı adresi; belirtilmemişse iletim kapalıdır.
+    pub server: Ipv4Addr,
+    pub port: u16,
+}
+
+impl Default for SyslogConfig {
+    fn default() -> Self {
+        SyslogConfig { server: Ipv4Addr::UNSPECIFIED, port: DEFAULT_PORT }
+    }
+}
+
+impl SyslogConfig {
+    pub fn enabled(&self) -> bool {
+        self.server != Ipv4Addr::UNSPECIFIED
+    }
+}
+
+// `cfg.syslog` TLV etiketleri; silinen etiketlerin numaraları yeniden kullanılmaz
+const TAG_SERVER: u8 = 1;
+const TAG_PORT: u8 = 2;
+
+impl Config for SyslogConfig {
+    const KEY: &'static str = "cfg.syslog";
+    const VERSION: u16 = 1;
+
+    fn encode(&self, out: &mut TlvWriter) -> Result<(), KernelError> {
+        out.bytes(TAG_SERVER, &self.server.0)?;
+        out.u16(TAG_PORT, self.port)
+    }
+
+    fn decode(&mut self, fields: &TlvReader) {
+        self.server = fields.array(TAG_SERVER).map_or(self.server, Ipv4Addr);
+        self.port = fields.u16(TAG_PORT).unwrap_or(self.port);
+    }
+}
+
+/// Etkin toplayıcı (görev çalışırken kabuktan değiştirilebilir).
+static SERVER: AtomicU32 = AtomicU32::new(0);
+static PORT: AtomicU16 = AtomicU16::new(DEFAULT_PORT);
+
+fn server() -> Ipv4Addr {
+    Ipv4Addr(SERVER.load(Ordering::Relaxed).to_be_bytes())
+}
+
+fn apply(cfg: &SyslogConfig) {
+    SERVER.store(u32::from_be_bytes(cfg.server.0), Ordering::Relaxed);
+    PORT.store(cfg.port, Ordering::Relaxed);
+}
+
+// -----------------------------------------------------------------------------
+// KAYIT HALKASI
+// -----------------------------------------------------------------------------
+
+#[derive(Clone, Copy)]
+struct Record {
+    /// Kaydın alındığı monoton zaman (`hrtimer::now_ns`).
+    mono_ns: u64,
+    task: TaskId,
+    severity: u8,
+    len: usize,
+    text: [u8; RECORD_TEXT],
+}
+
+const EMPTY_RECORD: Record = Record { mono_ns: 0, task: NO_TASK, severity: 0, len: 0, text: [0; RECORD_TEXT] };
+
+impl Record {
+    fn text(&self) -> &str {
+        match core::str::from_utf8(&self.text[..self.len]) {
+            Ok(text) => text,
+            // Kesme bir karakterin ortasına denk gelmiş olabilir
+            Err(e) => core::str::from_utf8(&self.text[..e.valid_up_to()]).unwrap_or(""),
+        }
+    }
+}
+
+impl Write for Record {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let n = s.len().min(RECORD_TEXT - self.len);
+        self.text[self.len..self.len + n].copy_from_slice(&s.as_bytes()[..n]);
+        self.len += n;
+        Ok(())
+    }
+}
+
+struct Ring {
+    records: UnsafeCell<[Record; MAX_RECORDS]>,
+    /// En eski kaydın konumu ve kayıt sayısı; yalnızca `busy` tutulurken değişir.
+    head: AtomicUsize,
+    count: AtomicUsize,
+    /// Deneme kilidi: tutulurken gelen kayıt (örn. kesmeden) atılır.
+    busy: AtomicBool,
+}
+
+// Kayıtlara yalnızca `busy` kilidini alan bağlam erişir.
+unsafe impl Sync for Ring {}
+
+static RING: Ring = Ring {
+    records: UnsafeCell::new([EMPTY_RECORD; MAX_RECORDS]),
+    head: AtomicUsize::new(0),
+    count: AtomicUsize::new(0),
+    busy: AtomicBool::new(false),
+};
+
+/// Son bildirimden bu yana atılan kayıt sayısı.
+static UNREPORTED_DROPS: AtomicU64 = AtomicU64::new(0);
+
+/// `syslogd` görevinin kimliği (`NO_TASK`: çalışmıyor).
+static LOGGER: AtomicUsize = AtomicUsize::new(NO_TASK);
+
+impl Ring {
+    fn with<R>(&self, f: impl FnOnce(&mut [Record; MAX_RECORDS], &mut usize, &mut usize) -> R) -> Option<R> {
+        if self.busy.swap(true, Ordering::Acquire) {
+            return None;
+        }
+        let (mut head, mut count) = (self.head.load(Ordering::Relaxed), self.count.load(Ordering::Relaxed));
+        // SAFETY: Kilit tutulduğu sürece kayıtlara tek erişen biziz.
+        let result = f(unsafe { &mut *self.records.get() }, &mut head, &mut count);
+        self.head.store(head, Ordering::Relaxed);
+        self.count.store(count, Ordering::Relaxed);
+        self.busy.store(false, Ordering::Release);
+        Some(result)
+    }
+}
+
+fn drop_record() {
+    DROPPED.inc();
+    UNREPORTED_DROPS.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Kaydı halkaya ekler; halka doluysa veya kilit tutuluyorsa kayıt atılır.
+fn capture(task: Option<TaskId>, severity: u8, args: fmt::Arguments) {
+    let task = task.unwrap_or(NO_TASK);
+    if task != NO_TASK && task == LOGGER.load(Ordering::Relaxed) {
+        return;
+    }
+    let stored = RING.with(|records, head, count| {
+        if *count == MAX_RECORDS {
+            return false;
+        }
+        let record = &mut records[(*head + *count) % MAX_RECORDS];
+        *record = Record { mono_ns: crate::hrtimer::now_ns(), task, severity, ..EMPTY_RECORD };
+        let _ = record.write_fmt(args);
+        // `serial_println!` satır sonu satır ayırıcısıyla karışmasın
+        while record.len > 0 && matches!(record.text[record.len - 1], b'\n' | b'\r') {
+            record.len -= 1;
+        }
+        if record.len == 0 {
+            return true;
+        }
+        *count += 1;
+        true
+    });
+    match stored {
+        Some(true) => RECORDS.inc(),
+        _ => drop_record(),
+    }
+}
+
+/// Çekirdek günlük seviyesini syslog önemine çevirir.
+fn severity(level: LogLevel) -> u8 {
+    match level {
+        LogLevel::Off | LogLevel::Error => 3,
+        LogLevel::Warn => 4,
+        LogLevel::Info => 6,
+        LogLevel::Debug | LogLevel::Trace => 7,
+    }
+}
+
+/// `klog!` dinleyicisi.
+fn log_tap(task: Option<TaskId>, level: LogLevel, args: fmt::Arguments) {
+    // `log` akışı açıkça bu hedefe bağlanan görevin tüm iletileri alınır
+    let bound = task.is_some_and(|t| console::binding(t, Stream::Log) == Some(Sink::Netlog));
+    if bound || LEVEL.enabled(level) {
+        capture(task, severity(level), args);
+    }
+}
+
+/// `netlog` hedefinin yazma fonksiyonu.
+fn netlog_write(args: fmt::Arguments) {
+    let task = sched::current();
+    // `klog!` iletileri dinleyici tarafından önem seviyesiyle zaten alındı
+    if task.is_some_and(console::in_log) {
+        return;
+    }
+    capture(task, SEVERITY_STDIO, args);
+}
+
+// -----------------------------------------------------------------------------
+// BİÇİMLENDİRME
+// -----------------------------------------------------------------------------
+
+/// Sabit tampona yazan biçimlendirici; sığmayan kısım kesilir.
+struct LineBuf {
+    buf: [u8; MAX_LINE],
+    len: usize,
+}
+
+impl Write for LineBuf {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let n = s.len().min(MAX_LINE - self.len);
+        self.buf[self.len..self.len + n].copy_from_slice(&s.as_bytes()[..n]);
+        self.len += n;
+        Ok(())
+    }
+}
+
+/// RFC 5424 zaman damgası (UTC, µs çözünürlük) veya NILVALUE.
+fn write_timestamp(out: &mut dyn Write, real_ns: u64) -> fmt::Result {
+    let secs = real_ns / 1_000_000_000;
+    if secs < WALL_VALID_SECS {
+        return out.write_str("-");
+    }
+    let (year, month, day) = crate::walltime::civil_from_days(secs / 86_400);
+    let of_day = secs % 86_400;
+    write!(
+        out,
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:06}Z",
+        year,
+        month,
+        day,
+        of_day / 3600,
+        of_day / 60 % 60,
+        of_day % 60,
+        real_ns % 1_000_000_000 / 1000
+    )
+}
+
+/// Kaydı tek RFC 5424 satırı olarak biçimlendirir.
+fn format_record(record: &Record, wall_offset: i128) -> LineBuf {
+    let mut line = LineBuf { buf: [0; MAX_LINE], len: 0 };
+    let real_ns = (i128::from(record.mono_ns) + wall_offset).max(0) as u64;
+    let app = match record.task {
+        NO_TASK => "kernel",
+        task => sched::task_info(task).map_or("-", |info| info.name),
+    };
+    let _ = write!(line, "<{}>1 ", FACILITY_KERN * 8 + record.severity);
+    let _ = write_timestamp(&mut line, real_ns);
+    let _ = write!(line, " {} {} ", crate::version::NODENAME, app);
+    let _ = match record.task {
+        NO_TASK => line.write_str("-"),
+        task => write!(line, "{}", task),
+    };
+    let _ = write!(line, " - - {}", record.text());
+    line
+}
+
+// -----------------------------------------------------------------------------
+// GÖNDERİM GÖREVİ
+// -----------------------------------------------------------------------------
+
+/// Bir datagram gönderir; hata sayacı artırılır.
+fn send(socket: &Socket, datagram: &[u8]) -> bool {
+    match socket.send_to(server(), PORT.load(Ordering::Relaxed), datagram) {
+        Ok(()) => {
+            DATAGRAMS.inc();
+            true
+        }
+        Err(_) => {
+            SEND_ERRORS.inc();
+            false
+        }
+    }
+}
+
+/// Halkadaki kayıtları datagramlar halinde gönderir. Gönderilemeyen kayıtlar
+/// halkada kalır.
+///
+/// # Dönüş Değeri
+/// Gönderim başarısız olduysa `false`.
+fn flush(socket: &Socket) -> bool {
+    let mut datagram = [0u8; MAX_DATAGRAM];
+    let wall_offset = i128::from(crate::walltime::now_ns()) - i128::from(crate::hrtimer::now_ns());
+
+    let drops = UNREPORTED_DROPS.swap(0, Ordering::Relaxed);
+    if drops != 0 {
+        let mut notice = Record { mono_ns: crate::hrtimer::now_ns(), severity: 4, ..EMPTY_RECORD };
+        let _ = write!(notice, "[SYSLOG] {} kayıt atıldı (halka dolu)", drops);
+        let line = format_record(&notice, wall_offset);
+        if !send(socket, &line.buf[..line.len]) {
+            UNREPORTED_DROPS.fetch_add(drops, Ordering::Relaxed);
+            return false;
+        }
+    }
+
+    loop {
+        // Datagrama sığan kayıtları sırayla kopyalar (halkadan henüz çıkarmaz)
+        let mut len = 0;
+        let mut taken = 0;
+        loop {
+            let record = RING.with(|records, head, count| (taken < *count).then(|| records[(*head + taken) % MAX_RECORDS]));
+            let Some(Some(record)) = record else { break };
+            let line = format_record(&record, wall_offset);
+            let needed = line.len + usize::from(len != 0);
+            if len + needed > MAX_DATAGRAM {
+                break;
+            }
+            if len != 0 {
+                datagram[len] = b'\n';
+                len += 1;
+            }
+            datagram[len..len + line.len].copy_from_slice(&line.buf[..line.len]);
+            len += line.len;
+            taken += 1;
+        }
+        if taken == 0 {
+            return true;
+        }
+        if !send(socket, &datagram[..len]) {
+            return false;
+        }
+        // Gönderilen kayıtları çıkarır; kilit tutuluyorsa kısa süre beklenir
+        while RING
+            .with(|_, head, count| {
+                *head = (*head + taken) % MAX_RECORDS;
+                *count -= taken;
+            })
+            .is_none()
+        {
+            core::hint::spin_loop();
+        }
+    }
+}
+
+fn logger_task(_arg: u64) {
+    let socket = match Socket::open(Protocol::Udp) {
+        Ok(socket) => socket,
+        Err(e) => {
+            serial_println!("[SYSLOG] Soket açılamadı: {:?}", e);
+            LOGGER.store(NO_TASK, Ordering::Release);
+            if let Some(me) = sched::current() {
+                sched::exit(me).ok();
+            }
+            sched::schedule();
+            return;
+        }
+    };
+    loop {
+        let delay = if server() == Ipv4Addr::UNSPECIFIED || flush(&socket) { FLUSH_TICKS } else { RETRY_TICKS };
+        crate::timer::sleep(delay);
+    }
+}
+
+/// Yakalamayı açar ve gönderim görevini (çalışmıyorsa) başlatır.
+fn start() -> Result<(), KernelError> {
+    if LOGGER.load(Ordering::Acquire) == NO_TASK {
+        let task = sched::spawn("syslogd", logger_task as usize, TASK_PRIORITY)?;
+        LOGGER.store(task, Ordering::Release);
+    }
+    console::register(Sink::Netlog, Some(netlog_write))?;
+    console::set_log_tap(Some(log_tap));
+    Ok(())
+}
+
+fn init() -> Result<(), KernelError> {
+    let cfg = config::get::<SyslogConfig>();
+    apply(&cfg);
+    if !cfg.enabled() {
+        return Ok(());
+    }
+    start()?;
+    serial_println!("[SYSLOG] Günlükler {}:{} adresine iletiliyor", cfg.server, cfg.port);
+    Ok(())
+}
+
+crate::initcall!(late, "syslog", init);
+
+// -----------------------------------------------------------------------------
+// KABUK KOMUTU
+// -----------------------------------------------------------------------------
+
+/// `syslog [status | server <adres> [port] | disable]`
+fn syslog_command(args: &[&str]) -> Result<(), KernelError> {
+    match args.get(1).copied() {
+        None | Some("status") => {
+            let cfg = config::get::<SyslogConfig>();
+            match server() {
+                Ipv4Addr::UNSPECIFIED => serial_println!("  kapalı"),
+                addr => serial_println!("  toplayıcı {}:{}", addr, PORT.load(Ordering::Relaxed)),
+            }
+            if cfg.enabled() {
+                serial_println!("  kayıtlı ayar {}:{}", cfg.server, cfg.port);
+            }
+            serial_println!(
+                "  halkada {}/{} kayıt, gönderilen datagram {}, atılan {}, gönderim hatası {}",
+                RING.count.load(Ordering::Relaxed),
+                MAX_RECORDS,
+                DATAGRAMS.get(),
+                DROPPED.get(),
+                SEND_ERRORS.get()
+            );
+            Ok(())
+        }
+        Some("server") => {
+            let server = args.get(2).and_then(|s| Ipv4Addr::parse(s)).ok_or(KernelError::InvalidArgument)?;
+            let port = match args.get(3) {
+                Some(s) => s.parse().ok().filter(|&p| p != 0).ok_or(KernelError::InvalidArgument)?,
+                None => DEFAULT_PORT,
+            };
+            let cfg = SyslogConfig { server, port };
+            config::set(&cfg)?;
+            apply(&cfg);
+            start()
+        }
+        Some("disable") => {
+            config::reset::<SyslogConfig>()?;
+            // Görev çalışmaya devam eder ama toplayıcı olmadan göndermez
+            apply(&SyslogConfig::default());
+            console::set_log_tap(None);
+            console::register(Sink::Netlog, None)
+        }
+        Some(_) => Err(KernelError::InvalidArgument),
+    }
+}
+
+crate::shell_command!("syslog", "Günlük iletimi (syslog [status | server <adres> [port] | disable])", syslog_command);
@@ -0,0 +1,984 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/net/tcp.rs
+// TCP (RFC 793, sadeleştirilmiş) ve akış soketi arayüzü.
+//
+// Bağlantılar statik bir tabloda tutulur (TCB); tabloya tek bir kilitle
+// erişilir. Gelen segmentler `net::poll` içinden işlenir; yeniden gönderim
+// ve TIME-WAIT süreleri de her `poll` çağrısında denetlenir (bkz. `on_poll`).
+// Arka planda `net` görevi yığını düzenli aralıklarla ilerlettiği için
+// zamanlayıcılar uygulama beklemese de işler.
+//
+// Sadeleştirmeler:
+// * Sıra dışı (out-of-order) segmentler tutulmaz; atılır ve beklenen sıra
+//   numarası yeniden onaylanır (karşı taraf yeniden gönderir).
+// * Alma penceresi sabit boyutlu alma tamponunun boş alanıdır; tıkanıklık
+//   denetimi (slow start vb.) yoktur, gönderim karşı tarafın penceresiyle
+//   sınırlıdır.
+// * Seçeneklerden yalnızca MSS desteklenir.
+//
+// Soket arayüzü (`TcpListener`, `TcpStream`) çekirdek görevlerinden
+// kullanılır; engelleyen çağrılar beklerken `net::poll` çağırır.
+
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicU16, Ordering};
+use crate::net::ipv4::{self, Header};
+use crate::net::net::{self, Ipv4Addr};
+use crate::net::netbuf::{self, NetBuf};
+use crate::platformgeneric::spinlock::Spinlock;
+use crate::platformgeneric::{KernelError, SystemConstants};
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// SABİTLER
+// -----------------------------------------------------------------------------
+
+/// En fazla eşzamanlı TCB (dinleyiciler dahil).
+pub const MAX_CONNECTIONS: usize = 8;
+/// Bağlantı başına gönderme ve alma tamponu boyutu; alma penceresi en fazla budur.
+pub const BUFFER_SIZE: usize = 4096;
+/// Dinleyici başına kabul edilmeyi bekleyen en fazla bağlantı.
+const BACKLOG: usize = 2;
+
+const HEADER_LEN: usize = 20;
+const OPT_MSS_LEN: usize = 4;
+/// MSS seçeneği gelmezse varsayılan (RFC 879).
+const DEFAULT_MSS: usize = 536;
+/// Tek segmentteki en büyük yük.
+const MAX_SEGMENT: usize = net::MAX_MTU - ipv4::HEADER_LEN - HEADER_LEN;
+
+const FIN: u8 = 0x01;
+const SYN: u8 = 0x02;
+const RST: u8 = 0x04;
+const PSH: u8 = 0x08;
+const ACK: u8 = 0x10;
+
+const TICK_HZ: u64 = SystemConstants::TIMER_TICK_HZ;
+/// İlk yeniden gönderim süresi (1 s, RFC 6298) ve üst sınırı.
+const RTO_INITIAL: u64 = TICK_HZ;
+const RTO_MAX: u64 = 30 * TICK_HZ;
+/// Bu kadar yeniden gönderimden sonra bağlantı koparılır.
+const MAX_RETRIES: u32 = 8;
+/// TIME-WAIT süresi (2 x MSL; küçük sistem için kısaltılmış).
+const TIME_WAIT_TICKS: u64 = 2 * TICK_HZ;
+
+/// Engelleyen çağrılar için sınırsız bekleme.
+pub const WAIT_FOREVER: u64 = u64::MAX;
+
+const EPHEMERAL_FIRST: u16 = 49152;
+static NEXT_EPHEMERAL: AtomicU16 = AtomicU16::new(0);
+
+crate::kernel_counter!(SEGMENTS_IN, "net.tcp.segments_in");
+crate::kernel_counter!(SEGMENTS_OUT, "net.tcp.segments_out");
+crate::kernel_counter!(RETRANSMITS, "net.tcp.retransmits");
+crate::kernel_counter!(RESETS_SENT, "net.tcp.resets_sent");
+crate::kernel_counter!(BAD_SEGMENTS, "net.tcp.bad_segments");
+
+/// Bağlantı durumu (RFC 793 3.2).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TcpState {
+    Closed,
+    Listen,
+    SynSent,
+    SynReceived,
+    Established,
+    FinWait1,
+    FinWait2,
+    CloseWait,
+    Closing,
+    LastAck,
+    TimeWait,
+}
+
+// Sıra numarası karşılaştırmaları (32 bit sarmalı aritmetik)
+fn seq_lt(a: u32, b: u32) -> bool {
+    (a.wrapping_sub(b) as i32) < 0
+}
+
+fn seq_le(a: u32, b: u32) -> bool {
+    !seq_lt(b, a)
+}
+
+// -----------------------------------------------------------------------------
+// TAMPONLAR
+// -----------------------------------------------------------------------------
+
+/// Sabit boyutlu bayt halkası.
+struct Ring {
+    buf: [u8; BUFFER_SIZE],
+    start: usize,
+    len: usize,
+}
+
+impl Ring {
+    const EMPTY: Ring = Ring { buf: [0; BUFFER_SIZE], start: 0, len: 0 };
+
+    fn free(&self) -> usize {
+        BUFFER_SIZE - self.len
+    }
+
+    /// Sığdığı kadarını ekler.
+    fn push(&mut self, data: &[u8]) -> usize {
+        let n = data.len().min(self.free());
+        for (i, &b) in data[..n].iter().enumerate() {
+            self.buf[(self.start + self.len + i) % BUFFER_SIZE] = b;
+        }
+        self.len += n;
+        n
+    }
+
+    /// `offset`'ten başlayarak `out`'a kopyalar (tüketmeden).
+    fn peek(&self, offset: usize, out: &mut [u8]) -> usize {
+        let n = out.len().min(self.len.saturating_sub(offset));
+        for (i, b) in out[..n].iter_mut().enumerate() {
+            *b = self.buf[(self.start + offset + i) % BUFFER_SIZE];
+        }
+        n
+    }
+
+    fn consume(&mut self, n: usize) {
+        let n = n.min(self.len);
+        self.start = (self.start + n) % BUFFER_SIZE;
+        self.len -= n;
+    }
+
+    fn clear(&mut self) {
+        self.start = 0;
+        self.len = 0;
+    }
+}
+
+// -----------------------------------------------------------------------------
+// BAĞLANTI TABLOSU
+// -----------------------------------------------------------------------------
+
+/// İletim Kontrol Bloğu.
+struct Tcb {
+    state: TcpState,
+    /// Bir soket tanıtıcısı bu yuvayı tutuyor mu? Tutulmayan ve kapanmış
+    /// yuva boştur.
+    owned: bool,
+    /// Henüz kabul edilmemiş bağlantının dinleyicisi.
+    parent: Option<usize>,
+    /// Bağlantı karşı taraftan sıfırlandı veya zaman aşımıyla koptu.
+    reset: bool,
+
+    local_addr: Ipv4Addr,
+    local_port: u16,
+    remote_addr: Ipv4Addr,
+    remote_port: u16,
+
+    iss: u32,
+    /// Onaylanmamış ilk bayt, sıradaki gönderilecek bayt, karşı tarafın penceresi.
+    snd_una: u32,
+    snd_nxt: u32,
+    snd_wnd: u32,
+    snd_mss: usize,
+    irs: u32,
+    rcv_nxt: u32,
+
+    /// `snd_una`'dan başlayan gönderilmiş ve gönderilmemiş veri.
+    tx: Ring,
+    rx: Ring,
+
+    /// Uygulama kapattı; veri bitince FIN gönderilecek.
+    fin_pending: bool,
+    fin_sent: bool,
+    /// Karşı taraf FIN gönderdi (akış sonu).
+    peer_fin: bool,
+    /// Gönderilecek bir onay (ACK) bekliyor.
+    ack_pending: bool,
+
+    rto: u64,
+    /// Yeniden gönderim (veya pencere yoklama) zamanı.
+    rto_deadline: Option<u64>,
+    retries: u32,
+    time_wait_deadline: u64,
+}
+
+impl Tcb {
+    const EMPTY: Tcb = Tcb {
+        state: TcpState::Closed,
+        owned: false,
+        parent: None,
+        reset: false,
+        local_addr: Ipv4Addr::UNSPECIFIED,
+        local_port: 0,
+        remote_addr: Ipv4Addr::UNSPECIFIED,
+        remote_port: 0,
+        iss: 0,
+        snd_una: 0,
+        snd_nxt: 0,
+        snd_wnd: 0,
+        snd_mss: DEFAULT_MSS,
+        irs: 0,
+        rcv_nxt: 0,
+        tx: Ring::EMPTY,
+        rx: Ring::EMPTY,
+        fin_pending: false,
+        fin_sent: false,
+        peer_fin: false,
+        ack_pending: false,
+        rto: RTO_INITIAL,
+        rto_deadline: None,
+        retries: 0,
+        time_wait_deadline: 0,
+    };
+
+    fn is_free(&self) -> bool {
+        self.state == TcpState::Closed && !self.owned
+    }
+
+    /// Yuvayı yeni bir bağlantı için sıfırlar.
+    fn reinit(&mut self) {
+        *self = Tcb::EMPTY;
+    }
+
+    fn is_synchronized(&self) -> bool {
+        !matches!(self.state, TcpState::Closed | TcpState::Listen | TcpState::SynSent)
+    }
+
+    /// Reklam edilen alma penceresi.
+    fn window(&self) -> u16 {
+        self.rx.free().min(u16::MAX as usize) as u16
+    }
+
+    fn arm_timer(&mut self, now: u64) {
+        if self.rto_deadline.is_none() {
+            self.rto_deadline = Some(now + self.rto);
+        }
+    }
+
+    fn enter_time_wait(&mut self, now: u64) {
+        self.state = TcpState::TimeWait;
+        self.rto_deadline = None;
+        self.time_wait_deadline = now + TIME_WAIT_TICKS;
+    }
+
+    /// Bağlantıyı hemen kapatır (tamponlar uygulama okuyana kadar kalır).
+    fn abort(&mut self) {
+        self.state = TcpState::Closed;
+        self.reset = true;
+        self.rto_deadline = None;
+        self.tx.clear();
+    }
+}
+
+struct TcpTable {
+    lock: Spinlock,
+    conns: UnsafeCell<[Tcb; MAX_CONNECTIONS]>,
+}
+
+// Tabloya erişim `lock` ile korunur.
+unsafe impl Sync for TcpTable {}
+
+static TCP: TcpTable = TcpTable {
+    lock: Spinlock::new(),
+    conns: UnsafeCell::new([Tcb::EMPTY; MAX_CONNECTIONS]),
+};
+
+/// Kilidi tutarak bağlantı tablosu üzerinde `f`'yi çalıştırır.
+fn with_tcp<R>(f: impl FnOnce(&mut [Tcb; MAX_CONNECTIONS]) -> R) -> R {
+    TCP.lock.lock();
+    // SAFETY: Kilit tutulduğu sürece tabloya tek erişen biziz.
+    let result = f(unsafe { &mut *TCP.conns.get() });
+    TCP.lock.unlock();
+    result
+}
+
+/// Başlangıç sıra numarası: tik sayacı ve bağlantı dörtlüsünden türetilir.
+fn new_iss(tcb: &Tcb) -> u32 {
+    let ports = (u32::from(tcb.local_port) << 16) | u32::from(tcb.remote_port);
+    let mix = (crate::sched::ticks() as u32).wrapping_mul(250_000) ^ ports ^ tcb.remote_addr.to_u32();
+    mix.wrapping_mul(0x9E37_79B9)
+}
+
+fn port_in_use(conns: &[Tcb; MAX_CONNECTIONS], port: u16) -> bool {
+    conns.iter().any(|t| !t.is_free() && t.local_port == port)
+}
+
+// -----------------------------------------------------------------------------
+// SEGMENTLER
+// -----------------------------------------------------------------------------
+
+/// Çözümlenmiş gelen segment.
+struct Segment<'a> {
+    src_port: u16,
+    dst_port: u16,
+    seq: u32,
+    ack: u32,
+    flags: u8,
+    window: u32,
+    mss: Option<usize>,
+    data: &'a [u8],
+}
+
+impl Segment<'_> {
+    /// Sıra numarası alanında kapladığı uzunluk (SYN ve FIN birer sayılır).
+    fn seq_len(&self) -> u32 {
+        self.data.len() as u32 + u32::from(self.flags & SYN != 0) + u32::from(self.flags & FIN != 0)
+    }
+}
+
+/// Sözde başlık dahil sağlama toplamı.
+fn checksum(src: Ipv4Addr, dst: Ipv4Addr, segment: &[u8]) -> u16 {
+    let mut sum = net::checksum_add(0, &src.0);
+    sum = net::checksum_add(sum, &dst.0);
+    sum = net::checksum_add(sum, &[0, ipv4::PROTO_TCP]);
+    sum = net::checksum_add(sum, &(segment.len() as u16).to_be_bytes());
+    net::checksum_finish(net::checksum_add(sum, segment))
+}
+
+fn parse<'a>(header: &Header, segment: &'a [u8]) -> Option<Segment<'a>> {
+    if segment.len() < HEADER_LEN || checksum(header.src, header.dst, segment) != 0 {
+        return None;
+    }
+    let offset = usize::from(segment[12] >> 4) * 4;
+    if offset < HEADER_LEN || offset > segment.len() {
+        return None;
+    }
+
+    let mut mss = None;
+    let mut options = &segment[HEADER_LEN..offset];
+    while let Some(&kind) = options.first() {
+        match kind {
+            0 => break,
+            1 => options = &options[1..],
+            _ => {
+                let len = usize::from(*options.get(1)?);
+                if len < 2 || len > options.len() {
+                    return None;
+                }
+                if kind == 2 && len == OPT_MSS_LEN {
+                    mss = Some(usize::from(u16::from_be_bytes([options[2], options[3]])));
+                }
+                options = &options[len..];
+            }
+        }
+    }
+
+    Some(Segment {
+        src_port: u16::from_be_bytes([segment[0], segment[1]]),
+        dst_port: u16::from_be_bytes([segment[2], segment[3]]),
+        seq: u32::from_be_bytes([segment[4], segment[5], segment[6], segment[7]]),
+        ack: u32::from_be_bytes([segment[8], segment[9], segment[10], segment[11]]),
+        flags: segment[13],
+        window: u32::from(u16::from_be_bytes([segment[14], segment[15]])),
+        mss,
+        data: &segment[offset..],
+    })
+}
+
+/// Bir segment oluşturup gönderir. SYN segmentlerine MSS seçeneği eklenir.
+#[allow(clippy::too_many_arguments)]
+fn emit(src: Ipv4Addr, src_port: u16, dst: Ipv4Addr, dst_port: u16, seq: u32, ack: u32, flags: u8, window: u16, data: &[u8]) {
+    let options = if flags & SYN != 0 { OPT_MSS_LEN } else { 0 };
+    let header_len = HEADER_LEN + options;
+    // Havuz boşsa segment kaybolmuş sayılır (aşağıya bakın)
+    let Ok(mut segment) = NetBuf::from_slice(netbuf::HEADROOM, data) else { return };
+    let Ok(buf) = segment.push_header(header_len) else { return };
+
+    // Havuzdan gelen bölüt önceki çerçevenin baytlarını taşır
+    buf.fill(0);
+    buf[0..2].copy_from_slice(&src_port.to_be_bytes());
+    buf[2..4].copy_from_slice(&dst_port.to_be_bytes());
+    buf[4..8].copy_from_slice(&seq.to_be_bytes());
+    buf[8..12].copy_from_slice(&ack.to_be_bytes());
+    buf[12] = ((header_len / 4) as u8) << 4;
+    buf[13] = flags;
+    buf[14..16].copy_from_slice(&window.to_be_bytes());
+    if options != 0 {
+        buf[20] = 2;
+        buf[21] = OPT_MSS_LEN as u8;
+        buf[22..24].copy_from_slice(&(MAX_SEGMENT as u16).to_be_bytes());
+    }
+    let sum = checksum(src, dst, segment.data());
+    segment.data_mut()[16..18].copy_from_slice(&sum.to_be_bytes());
+
+    // Gönderilemeyen segment (örn. ARP çözümlemesi sürüyor) kaybolmuş sayılır;
+    // yeniden gönderim zamanlayıcısı telafi eder
+    if let Ok((iface, _, next_hop)) = ipv4::select_route(dst) {
+        if ipv4::send_buf_from(iface, src, dst, next_hop, ipv4::PROTO_TCP, segment).is_ok() {
+            SEGMENTS_OUT.inc();
+        }
+    }
+}
+
+/// Bağlantı üzerinden segment gönderir (onay ve pencere alanlarıyla).
+fn send_segment(tcb: &mut Tcb, seq: u32, flags: u8, data: &[u8]) {
+    let ack = if flags & ACK != 0 { tcb.rcv_nxt } else { 0 };
+    emit(tcb.local_addr, tcb.local_port, tcb.remote_addr, tcb.remote_port, seq, ack, flags, tcb.window(), data);
+    if flags & ACK != 0 {
+        tcb.ack_pending = false;
+    }
+}
+
+/// Bağlantısı olmayan segmente RST ile yanıt verir (RFC 793 3.4).
+fn send_reset(header: &Header, seg: &Segment) {
+    if seg.flags & RST != 0 {
+        return;
+    }
+    RESETS_SENT.inc();
+    if seg.flags & ACK != 0 {
+        emit(header.dst, seg.dst_port, header.src, seg.src_port, seg.ack, 0, RST, 0, &[]);
+    } else {
+        let ack = seg.seq.wrapping_add(seg.seq_len());
+        emit(header.dst, seg.dst_port, header.src, seg.src_port, 0, ack, RST | ACK, 0, &[]);
+    }
+}
+
+// -----------------------------------------------------------------------------
+// GİRDİ İŞLEME
+// -----------------------------------------------------------------------------
+
+/// Gelen TCP segmentini işler (`ipv4::receive` içinden).
+pub fn receive(iface: usize, header: &Header, segment: &[u8]) {
+    let _ = iface;
+    let Some(seg) = parse(header, segment) else {
+        BAD_SEGMENTS.inc();
+        return;
+    };
+    SEGMENTS_IN.inc();
+    let now = crate::sched::ticks();
+
+    with_tcp(|conns| {
+        let connected = conns.iter().position(|t| {
+            !matches!(t.state, TcpState::Closed | TcpState::Listen)
+                && t.local_port == seg.dst_port
+                && t.remote_port == seg.src_port
+                && t.remote_addr == header.src
+        });
+        if let Some(i) = connected {
+            process(&mut conns[i], &seg, now);
+            output(&mut conns[i], now);
+            return;
+        }
+        match conns.iter().position(|t| t.state == TcpState::Listen && t.local_port == seg.dst_port) {
+            Some(listener) => listen_input(conns, listener, header, &seg, now),
+            None => send_reset(header, &seg),
+        }
+    });
+}
+
+/// Dinleyen sokete gelen segment: SYN için yeni bağlantı oluşturulur.
+fn listen_input(conns: &mut [Tcb; MAX_CONNECTIONS], listener: usize, header: &Header, seg: &Segment, now: u64) {
+    if seg.flags & RST != 0 {
+        return;
+    }
+    if seg.flags & ACK != 0 || seg.flags & SYN == 0 {
+        send_reset(header, seg);
+        return;
+    }
+    let waiting = conns.iter().filter(|t| t.parent == Some(listener)).count();
+    let Some(child) = conns.iter().position(Tcb::is_free).filter(|_| waiting < BACKLOG) else {
+        // Kuyruk dolu: SYN yanıtsız bırakılır, karşı taraf yeniden dener
+        return;
+    };
+
+    let tcb = &mut conns[child];
+    tcb.reinit();
+    tcb.state = TcpState::SynReceived;
+    tcb.parent = Some(listener);
+    tcb.local_addr = header.dst;
+    tcb.local_port = seg.dst_port;
+    tcb.remote_addr = header.src;
+    tcb.remote_port = seg.src_port;
+    tcb.irs = seg.seq;
+    tcb.rcv_nxt = seg.seq.wrapping_add(1);
+    tcb.iss = new_iss(tcb);
+    tcb.snd_una = tcb.iss;
+    tcb.snd_nxt = tcb.iss.wrapping_add(1);
+    tcb.snd_wnd = seg.window;
+    tcb.snd_mss = seg.mss.unwrap_or(DEFAULT_MSS).min(MAX_SEGMENT);
+    let iss = tcb.iss;
+    send_segment(tcb, iss, SYN | ACK, &[]);
+    tcb.arm_timer(now);
+}
+
+/// Bağlantıya ait segmenti durum makinesine uygular.
+fn process(tcb: &mut Tcb, seg: &Segment, now: u64) {
+    if tcb.state == TcpState::SynSent {
+        let ack_ok = seg.ack == tcb.iss.wrapping_add(1);
+        if seg.flags & ACK != 0 && !ack_ok {
+            if seg.flags & RST == 0 {
+                emit(tcb.local_addr, tcb.local_port, tcb.remote_addr, tcb.remote_port, seg.ack, 0, RST, 0, &[]);
+            }
+            return;
+        }
+        if seg.flags & RST != 0 {
+            // Bağlantı reddedildi
+            if seg.flags & ACK != 0 {
+                tcb.abort();
+            }
+            return;
+        }
+        if seg.flags & SYN != 0 && seg.flags & ACK != 0 {
+            tcb.irs = seg.seq;
+            tcb.rcv_nxt = seg.seq.wrapping_add(1);
+            tcb.snd_una = seg.ack;
+            tcb.snd_wnd = seg.window;
+            tcb.snd_mss = seg.mss.unwrap_or(DEFAULT_MSS).min(MAX_SEGMENT);
+            tcb.state = TcpState::Established;
+            tcb.rto_deadline = None;
+            tcb.retries = 0;
+            tcb.ack_pending = true;
+        }
+        return;
+    }
+
+    // Yalnızca tam beklenen sıra numarasıyla gelen segment kabul edilir
+    let in_order = seg.seq == tcb.rcv_nxt;
+    if seg.flags & RST != 0 {
+        if in_order {
+            tcb.abort();
+        }
+        return;
+    }
+    if seg.flags & SYN != 0 {
+        // Yinelenen SYN (SYN-RECEIVED'da SYN+ACK kaybolmuş olabilir): yeniden onayla
+        if tcb.state == TcpState::SynReceived {
+            tcb.rto_deadline = Some(now);
+        } else {
+            tcb.ack_pending = true;
+        }
+        return;
+    }
+    if seg.flags & ACK == 0 {
+        return;
+    }
+
+    // ACK işleme
+    if tcb.state == TcpState::SynReceived {
+        if seg.ack != tcb.snd_nxt {
+            emit(tcb.local_addr, tcb.local_port, tcb.remote_addr, tcb.remote_port, seg.ack, 0, RST, 0, &[]);
+            return;
+        }
+        tcb.state = TcpState::Established;
+        tcb.snd_una = seg.ack;
+        tcb.rto_deadline = None;
+        tcb.retries = 0;
+    } else if seq_lt(tcb.snd_una, seg.ack) && seq_le(seg.ack, tcb.snd_nxt) {
+        let acked = seg.ack.wrapping_sub(tcb.snd_una) as usize;
+        tcb.tx.consume(acked);
+        tcb.snd_una = seg.ack;
+        tcb.rto = RTO_INITIAL;
+        tcb.retries = 0;
+        tcb.rto_deadline = (tcb.snd_una != tcb.snd_nxt).then_some(now + tcb.rto);
+    } else if seq_lt(tcb.snd_nxt, seg.ack) {
+        // Henüz gönderilmemiş veriyi onaylıyor: yok sayılır, doğru değer bildirilir
+        tcb.ack_pending = true;
+        return;
+    }
+    tcb.snd_wnd = seg.window;
+
+    let fin_acked = tcb.fin_sent && tcb.snd_una == tcb.snd_nxt;
+    match tcb.state {
+        TcpState::FinWait1 if fin_acked => tcb.state = TcpState::FinWait2,
+        TcpState::Closing if fin_acked => tcb.enter_time_wait(now),
+        TcpState::LastAck if fin_acked => {
+            tcb.state = TcpState::Closed;
+            tcb.rto_deadline = None;
+            return;
+        }
+        _ => {}
+    }
+
+    // Veri
+    if !seg.data.is_empty() {
+        if in_order && matches!(tcb.state, TcpState::Established | TcpState::FinWait1 | TcpState::FinWait2) {
+            let accepted = tcb.rx.push(seg.data);
+            tcb.rcv_nxt = tcb.rcv_nxt.wrapping_add(accepted as u32);
+        }
+        tcb.ack_pending = true;
+    }
+
+    // FIN: ancak önceki tüm veri alındıysa
+    if seg.flags & FIN != 0 && in_order && seg.seq.wrapping_add(seg.data.len() as u32) == tcb.rcv_nxt {
+        tcb.rcv_nxt = tcb.rcv_nxt.wrapping_add(1);
+        tcb.peer_fin = true;
+        tcb.ack_pending = true;
+        match tcb.state {
+            TcpState::Established => tcb.state = TcpState::CloseWait,
+            TcpState::FinWait1 => tcb.state = TcpState::Closing,
+            TcpState::FinWait2 => tcb.enter_time_wait(now),
+            _ => {}
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// ÇIKTI VE ZAMANLAYICILAR
+// -----------------------------------------------------------------------------
+
+/// Pencerenin izin verdiği kadar veri, gerekiyorsa FIN ve bekleyen onayı gönderir.
+fn output(tcb: &mut Tcb, now: u64) {
+    if matches!(tcb.state, TcpState::Established | TcpState::CloseWait) && !tcb.fin_sent {
+        let mut chunk = [0u8; MAX_SEGMENT];
+        loop {
+            let in_flight = tcb.snd_nxt.wrapping_sub(tcb.snd_una) as usize;
+            let unsent = tcb.tx.len - in_flight;
+            let window = tcb.snd_wnd as usize;
+            if unsent == 0 {
+                break;
+            }
+            if in_flight >= window {
+                // Sıfır pencere: yoklama için zamanlayıcı kurulur
+                tcb.arm_timer(now);
+                break;
+            }
+            let n = tcb.tx.peek(in_flight, &mut chunk[..unsent.min(window - in_flight).min(tcb.snd_mss)]);
+            let seq = tcb.snd_nxt;
+            send_segment(tcb, seq, PSH | ACK, &chunk[..n]);
+            tcb.snd_nxt = tcb.snd_nxt.wrapping_add(n as u32);
+            tcb.arm_timer(now);
+        }
+
+        let all_sent = tcb.snd_nxt.wrapping_sub(tcb.snd_una) as usize == tcb.tx.len;
+        if tcb.fin_pending && all_sent {
+            let seq = tcb.snd_nxt;
+            send_segment(tcb, seq, FIN | ACK, &[]);
+            tcb.fin_sent = true;
+            tcb.snd_nxt = tcb.snd_nxt.wrapping_add(1);
+            tcb.state = if tcb.state == TcpState::Established { TcpState::FinWait1 } else { TcpState::LastAck };
+            tcb.arm_timer(now);
+        }
+    }
+
+    if tcb.ack_pending && tcb.is_synchronized() {
+        let seq = tcb.snd_nxt;
+        send_segment(tcb, seq, ACK, &[]);
+    }
+}
+
+/// Süresi dolan yeniden gönderimi yapar.
+fn retransmit(tcb: &mut Tcb, now: u64) {
+    tcb.retries += 1;
+    if tcb.retries > MAX_RETRIES {
+        if tcb.is_synchronized() {
+            let seq = tcb.snd_nxt;
+            send_segment(tcb, seq, RST, &[]);
+        }
+        tcb.abort();
+        return;
+    }
+    RETRANSMITS.inc();
+    tcb.rto = (tcb.rto * 2).min(RTO_MAX);
+    tcb.rto_deadline = Some(now + tcb.rto);
+
+    let iss = tcb.iss;
+    match tcb.state {
+        TcpState::SynSent => send_segment(tcb, iss, SYN, &[]),
+        TcpState::SynReceived => send_segment(tcb, iss, SYN | ACK, &[]),
+        _ => {
+            let in_flight = tcb.snd_nxt.wrapping_sub(tcb.snd_una) as usize;
+            let mut chunk = [0u8; MAX_SEGMENT];
+            let una = tcb.snd_una;
+            if in_flight == 0 && tcb.tx.len > 0 {
+                // Pencere yoklaması: pencerenin ötesinde tek bayt
+                let n = tcb.tx.peek(0, &mut chunk[..1]);
+                send_segment(tcb, una, PSH | ACK, &chunk[..n]);
+                tcb.snd_nxt = tcb.snd_nxt.wrapping_add(n as u32);
+            } else if tcb.tx.len > 0 {
+                let n = tcb.tx.peek(0, &mut chunk[..in_flight.min(tcb.tx.len).min(tcb.snd_mss)]);
+                send_segment(tcb, una, PSH | ACK, &chunk[..n]);
+            } else if tcb.fin_sent && in_flight > 0 {
+                send_segment(tcb, una, FIN | ACK, &[]);
+            } else {
+                tcb.rto_deadline = None;
+            }
+        }
+    }
+}
+
+/// `net::poll` sonunda çağrılır: yeniden gönderim ve TIME-WAIT süreleri.
+pub fn on_poll() {
+    let now = crate::sched::ticks();
+    with_tcp(|conns| {
+        for tcb in conns.iter_mut() {
+            if tcb.state == TcpState::TimeWait && now >= tcb.time_wait_deadline {
+                tcb.state = TcpState::Closed;
+            } else if tcb.state != TcpState::Closed && tcb.rto_deadline.is_some_and(|d| now >= d) {
+                retransmit(tcb, now);
+            }
+            // Kabul edilmeden kapanan bağlantının yuvası bırakılır
+            if tcb.state == TcpState::Closed && tcb.parent.is_some() {
+                tcb.parent = None;
+            }
+        }
+    });
+}
+
+// -----------------------------------------------------------------------------
+// SOKET ARAYÜZÜ
+// -----------------------------------------------------------------------------
+
+/// `f` bir değer döndürene kadar yığını ilerletir; en fazla `timeout` tik bekler.
+fn wait_for<R>(timeout: u64, mut f: impl FnMut(&mut [Tcb; MAX_CONNECTIONS]) -> Option<R>) -> Result<R, KernelError> {
+    let deadline = crate::sched::ticks().saturating_add(timeout);
+    loop {
+        net::poll();
+        if let Some(result) = with_tcp(&mut f) {
+            return Ok(result);
+        }
+        if crate::sched::ticks() >= deadline {
+            return Err(KernelError::ResourceBusy);
+        }
+        core::hint::spin_loop();
+    }
+}
+
+/// Boş bir yuvayı soket için ayırır.
+fn allocate(conns: &mut [Tcb; MAX_CONNECTIONS]) -> Result<usize, KernelError> {
+    let index = conns.iter().position(Tcb::is_free).ok_or(KernelError::OutOfMemoryStatic)?;
+    conns[index].reinit();
+    conns[index].owned = true;
+    Ok(index)
+}
+
+/// Dinleyen TCP soketi.
+pub struct TcpListener {
+    index: usize,
+}
+
+impl TcpListener {
+    /// Yerel `port`'ta bağlantı kabul etmeye başlar.
+    pub fn bind(port: u16) -> Result<TcpListener, KernelError> {
+        if port == 0 {
+            return Err(KernelError::InvalidArgument);
+        }
+        with_tcp(|conns| {
+            if conns.iter().any(|t| t.state == TcpState::Listen && t.local_port == port) {
+                return Err(KernelError::ResourceBusy);
+            }
+            let index = allocate(conns)?;
+            conns[index].state = TcpState::Listen;
+            conns[index].local_port = port;
+            Ok(TcpListener { index })
+        })
+    }
+
+    pub fn port(&self) -> u16 {
+        with_tcp(|conns| conns[self.index].local_port)
+    }
+
+    /// Kurulmuş bir bağlantıyı kabul eder; en fazla `timeout` tik bekler.
+    pub fn accept(&self, timeout: u64) -> Result<TcpStream, KernelError> {
+        let listener = self.index;
+        let index = wait_for(timeout, |conns| {
+            let child = conns.iter().position(|t| {
+                t.parent == Some(listener) && matches!(t.state, TcpState::Established | TcpState::CloseWait)
+            })?;
+            conns[child].parent = None;
+            conns[child].owned = true;
+            Some(child)
+        })?;
+        Ok(TcpStream { index })
+    }
+}
+
+impl Drop for TcpListener {
+    fn drop(&mut self) {
+        let listener = self.index;
+        with_tcp(|conns| {
+            // Kabul edilmemiş bağlantılar sıfırlanır
+            for tcb in conns.iter_mut().filter(|t| t.parent == Some(listener)) {
+                let seq = tcb.snd_nxt;
+                send_segment(tcb, seq, RST, &[]);
+                tcb.abort();
+                tcb.parent = None;
+            }
+            conns[listener].state = TcpState::Closed;
+            conns[listener].owned = false;
+        });
+    }
+}
+
+/// Kurulmuş bir TCP bağlantısı.
+pub struct TcpStream {
+    index: usize,
+}
+
+impl TcpStream {
+    /// `addr:port`'a bağlanır; en fazla `timeout` tik bekler.
+    pub fn connect(addr: Ipv4Addr, port: u16, timeout: u64) -> Result<TcpStream, KernelError> {
+        let (_, local_addr, _) = ipv4::select_route(addr)?;
+        let now = crate::sched::ticks();
+        let index = with_tcp(|conns| {
+            let range = u16::MAX - EPHEMERAL_FIRST;
+            let local_port = (0..range)
+                .map(|_| EPHEMERAL_FIRST + NEXT_EPHEMERAL.fetch_add(1, Ordering::Relaxed) % range)
+                .find(|&p| !port_in_use(conns, p))
+                .ok_or(KernelError::ResourceBusy)?;
+            let index = allocate(conns)?;
+            let tcb = &mut conns[index];
+            tcb.state = TcpState::SynSent;
+            tcb.local_addr = local_addr;
+            tcb.local_port = local_port;
+            tcb.remote_addr = addr;
+            tcb.remote_port = port;
+            tcb.iss = new_iss(tcb);
+            tcb.snd_una = tcb.iss;
+            tcb.snd_nxt = tcb.iss.wrapping_add(1);
+            let iss = tcb.iss;
+            send_segment(tcb, iss, SYN, &[]);
+            tcb.arm_timer(now);
+            Ok(index)
+        })?;
+
+        // Hata durumunda düşürülen tanıtıcı yuvayı bırakır
+        let stream = TcpStream { index };
+        wait_for(timeout, |conns| match conns[index].state {
+            TcpState::SynSent => None,
+            TcpState::Closed => Some(Err(KernelError::NotFound)),
+            _ => Some(Ok(())),
+        })??;
+        Ok(stream)
+    }
+
+    pub fn state(&self) -> TcpState {
+        with_tcp(|conns| conns[self.index].state)
+    }
+
+    pub fn peer(&self) -> (Ipv4Addr, u16) {
+        with_tcp(|conns| (conns[self.index].remote_addr, conns[self.index].remote_port))
+    }
+
+    /// `data`'nın tamamını gönderme tamponuna yazar; tampon doluysa
+    /// yer açılmasını en fazla `timeout` tik bekler.
+    ///
+    /// # Dönüş Değeri
+    /// Yazılan bayt sayısı (zaman aşımında eksik olabilir); bağlantı
+    /// koptuysa `NotFound`.
+    pub fn send(&self, data: &[u8], timeout: u64) -> Result<usize, KernelError> {
+        let mut written = 0;
+        while written < data.len() {
+            let result = wait_for(timeout, |conns| {
+                let tcb = &mut conns[self.index];
+                if tcb.reset || !matches!(tcb.state, TcpState::Established | TcpState::CloseWait) || tcb.fin_pending {
+                    return Some(Err(KernelError::NotFound));
+                }
+                if tcb.tx.free() == 0 {
+                    return None;
+                }
+                let n = tcb.tx.push(&data[written..]);
+                output(tcb, crate::sched::ticks());
+                Some(Ok(n))
+            });
+            match result {
+                Ok(Ok(n)) => written += n,
+                Ok(Err(e)) => return Err(e),
+                Err(e) if written == 0 => return Err(e),
+                Err(_) => break,
+            }
+        }
+        Ok(written)
+    }
+
+    /// Gelen veriyi `buf`'a okur; veri yoksa en fazla `timeout` tik bekler.
+    ///
+    /// # Dönüş Değeri
+    /// Okunan bayt sayısı; karşı taraf akışı kapattıysa 0. Bağlantı
+    /// sıfırlandıysa `NotFound`, zaman aşımında `ResourceBusy`.
+    pub fn recv(&self, buf: &mut [u8], timeout: u64) -> Result<usize, KernelError> {
+        wait_for(timeout, |conns| {
+            let tcb = &mut conns[self.index];
+            if tcb.rx.len > 0 {
+                let window_was_closed = tcb.rx.free() < tcb.snd_mss;
+                let n = tcb.rx.peek(0, buf);
+                tcb.rx.consume(n);
+                // Kapanmak üzere olan pencere yeniden açıldı: karşı tarafa bildir
+                if window_was_closed && tcb.is_synchronized() {
+                    tcb.ack_pending = true;
+                    output(tcb, crate::sched::ticks());
+                }
+                return Some(Ok(n));
+            }
+            if tcb.reset {
+                return Some(Err(KernelError::NotFound));
+            }
+            if tcb.peer_fin || tcb.state == TcpState::Closed {
+                return Some(Ok(0));
+            }
+            None
+        })?
+    }
+
+    /// Gönderme yönünü kapatır: tampondaki veri bitince FIN gönderilir.
+    pub fn shutdown(&self) {
+        with_tcp(|conns| {
+            let tcb = &mut conns[self.index];
+            tcb.fin_pending = true;
+            output(tcb, crate::sched::ticks());
+        });
+    }
+}
+
+impl Drop for TcpStream {
+    fn drop(&mut self) {
+        // Bağlantı arka planda kapanmaya devam eder; yuva kapanınca boşalır
+        with_tcp(|conns| {
+            let tcb = &mut conns[self.index];
+            match tcb.state {
+                TcpState::SynSent => tcb.state = TcpState::Closed,
+                TcpState::SynReceived | TcpState::Established | TcpState::CloseWait => {
+                    tcb.fin_pending = true;
+                    output(tcb, crate::sched::ticks());
+                }
+                _ => {}
+            }
+            tcb.owned = false;
+        });
+    }
+}
+
+// -----------------------------------------------------------------------------
+// KABUK VE PROCFS
+// -----------------------------------------------------------------------------
+
+fn state_name(state: TcpState) -> &'static str {
+    match state {
+        TcpState::Closed => "CLOSED",
+        TcpState::Listen => "LISTEN",
+        TcpState::SynSent => "SYN_SENT",
+        TcpState::SynReceived => "SYN_RECV",
+        TcpState::Established => "ESTABLISHED",
+        TcpState::FinWait1 => "FIN_WAIT1",
+        TcpState::FinWait2 => "FIN_WAIT2",
+        TcpState::CloseWait => "CLOSE_WAIT",
+        TcpState::Closing => "CLOSING",
+        TcpState::LastAck => "LAST_ACK",
+        TcpState::TimeWait => "TIME_WAIT",
+    }
+}
+
+/// Dolu yuvaların özeti: (yerel port, uzak adres, uzak port, durum, rx, tx).
+fn snapshot() -> [Option<(u16, Ipv4Addr, u16, TcpState, usize, usize)>; MAX_CONNECTIONS] {
+    with_tcp(|conns| {
+        let mut out = [None; MAX_CONNECTIONS];
+        for (slot, t) in out.iter_mut().zip(conns.iter()) {
+            if !t.is_free() {
+                *slot = Some((t.local_port, t.remote_addr, t.remote_port, t.state, t.rx.len, t.tx.len));
+            }
+        }
+        out
+    })
+}
+
+fn netstat_command(_args: &[&str]) -> Result<(), KernelError> {
+    serial_println!("  YEREL   UZAK                  DURUM        RX    TX");
+    for (port, addr, rport, state, rx, tx) in snapshot().iter().flatten() {
+        serial_println!("  {:<7} {}:{} {} {} {}", port, addr, rport, state_name(*state), rx, tx);
+    }
+    Ok(())
+}
+
+crate::shell_command!("netstat", "TCP bağlantılarını listeler", netstat_command);
+
+fn show_tcp(out: &mut dyn core::fmt::Write) -> core::fmt::Result {
+    for (port, addr, rport, state, rx, tx) in snapshot().iter().flatten() {
+        writeln!(out, "{} {}:{} {} {} {}", port, addr, rport, state_name(*state), rx, tx)?;
+    }
+    Ok(())
+}
+
+crate::proc_entry!("net/tcp", show_tcp);
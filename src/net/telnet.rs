@@ -0,0 +1,518 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/net/telnet.rs
+// TCP üzerinden uzak çekirdek kabuğu (telnet benzeri, RFC 854 alt kümesi).
+//
+// `telnetd` görevi yapılandırılan portu dinler; kabul edilen her bağlantı
+// için bir oturum görevi başlatılır. Oturum önce paylaşılan parolayı sorar,
+// doğrulanırsa seri konsoldakiyle aynı satır düzenleyiciyi
+// (`lineedit::LineEditor`) çalıştırır. Oturum görevinin `serial_print!`
+// çıktısı `shell::set_redirect` ile bağlantıya yönlendirilir; böylece
+// komutlar değişmeden uzak oturumda çalışır.
+//
+// Telnet: bağlantı başında IAC WILL ECHO ve IAC WILL SUPPRESS-GO-AHEAD
+// gönderilir (karakter kipi; yankıyı sunucu yapar). İstemciden gelen
+// seçenek anlaşmaları ve alt anlaşmalar (SB ... SE) yanıtlanmadan atlanır.
+//
+// Kimlik doğrulama: parola kalıcı `TelnetConfig`'te (`cfg.telnet`) SHA-256
+// özeti olarak saklanır ve sabit zamanlı karşılaştırılır. Parola
+// ayarlanmamışsa sunucu hiç başlatılmaz. `MAX_AUTH_ATTEMPTS` hatalı
+// denemeden sonra bağlantı kapatılır; her hatadan sonra bekletilir.
+//
+// Oturumlar `IDLE_TIMEOUT_TICKS` boyunca girdi gelmezse veya `telnet kick`
+// ile kapatılır; en fazla `MAX_SESSIONS` oturum açık olabilir.
+
+use core::cell::UnsafeCell;
+use core::fmt::{self, Write};
+use core::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use crate::config::{self, Config, TlvReader, TlvWriter};
+use crate::crypto::{crypto, sha256};
+use crate::lineedit::{LineEditor, LINE_MAX};
+use crate::net::tcp::{TcpListener, TcpStream};
+use crate::platformgeneric::{KernelError, SystemConstants};
+use crate::sched::{self, TaskId};
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// SABİTLER
+// -----------------------------------------------------------------------------
+
+/// Varsayılan dinleme portu.
+pub const DEFAULT_PORT: u16 = 23;
+/// Eşzamanlı oturum sayısı.
+pub const MAX_SESSIONS: usize = 2;
+
+const MAX_AUTH_ATTEMPTS: u32 = 3;
+/// Hatalı parola sonrası bekleme (1 s).
+const AUTH_DELAY_TICKS: u64 = TICK_HZ;
+/// Girdisiz geçen bu süreden sonra oturum kapatılır (10 dk).
+const IDLE_TIMEOUT_TICKS: u64 = 600 * TICK_HZ;
+/// Girdi/bağlantı yoklama aralığı (20 ms).
+const POLL_TICKS: u64 = TICK_HZ / 50;
+/// Çıktı gönderiminde bekleme sınırı (2 s).
+const SEND_TIMEOUT_TICKS: u64 = 2 * TICK_HZ;
+/// Oturum başına çıktı tamponu.
+const OUT_BUF: usize = 1024;
+
+const TASK_PRIORITY: u8 = 22;
+const TICK_HZ: u64 = SystemConstants::TIMER_TICK_HZ;
+
+pub const PROMPT: &str = "sahne# ";
+
+// Telnet komutları ve seçenekleri
+const IAC: u8 = 255;
+const DONT: u8 = 254;
+const WILL: u8 = 251;
+const SB: u8 = 250;
+const SE: u8 = 240;
+const OPT_ECHO: u8 = 1;
+const OPT_SGA: u8 = 3;
+
+crate::kernel_counter!(SESSIONS_OPENED, "telnet.sessions");
+crate::kernel_counter!(AUTH_FAILED, "telnet.auth_failed");
+crate::kernel_counter!(REJECTED, "telnet.rejected");
+
+// -----------------------------------------------------------------------------
+// KALICI AYARLAR
+// -----------------------------------------------------------------------------
+
+/// Uzak kabuk ayarları.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TelnetConfig {
+    pub port: u16,
+    /// Parolanın SHA-256 özeti; tamamı sıfırsa parola yoktur ve sunucu kapalıdır.
+    pub secret_hash: [u8; sha256::DIGEST_LEN],
+}
+
+impl Default for TelnetConfig {
+    fn default() -> Self {
+        TelnetConfig { port: DEFAULT_PORT, secret_hash: [0; sha256::DIGEST_LEN] }
+    }
+}
+
+impl TelnetConfig {
+    pub fn enabled(&self) -> bool {
+        self.secret_hash.iter().any(|&b| b != 0)
+    }
+}
+
+// `cfg.telnet` TLV etiketleri; silinen etiketlerin numaraları yeniden kullanılmaz
+const TAG_PORT: u8 = 1;
+const TAG_SECRET_HASH: u8 = 2;
+
+impl Config for TelnetConfig {
+    const KEY: &'static str = "cfg.telnet";
+    const VERSION: u16 = 1;
+
+    fn encode(&self, out: &mut TlvWriter) -> Result<(), KernelError> {
+        out.u16(TAG_PORT, self.port)?;
+        out.bytes(TAG_SECRET_HASH, &self.secret_hash)
+    }
+
+    fn decode(&mut self, fields: &TlvReader) {
+        self.port = fields.u16(TAG_PORT).unwrap_or(self.port);
+        self.secret_hash = fields.array(TAG_SECRET_HASH).unwrap_or(self.secret_hash);
+    }
+}
+
+// -----------------------------------------------------------------------------
+// OTURUM TABLOSU
+// -----------------------------------------------------------------------------
+
+/// Boş oturum yuvası.
+const FREE: usize = usize::MAX;
+/// Yuva ayrıldı; oturum görevi henüz başlatılıyor.
+const STARTING: usize = usize::MAX - 1;
+
+/// Çıktı tamponu: yönlendirilen `serial_print!` çıktısı burada biriktirilir.
+struct OutBuf {
+    buf: [u8; OUT_BUF],
+    len: usize,
+}
+
+struct Session {
+    /// Oturum görevinin kimliği, `FREE` veya `STARTING`.
+    task: AtomicUsize,
+    /// `telnet kick` isteği.
+    kick: AtomicBool,
+    /// Son girdinin geldiği tik.
+    last_input: AtomicU64,
+    opened_at: AtomicU64,
+    /// Bağlantı ve çıktı tamponu yalnızca oturum görevi tarafından kullanılır
+    /// (dinleyici `STARTING` durumunda bağlantıyı bir kez yazar).
+    stream: UnsafeCell<Option<TcpStream>>,
+    out: UnsafeCell<OutBuf>,
+    /// Tampon boşaltılırken gelen çıktı (örn. TCP hata günlüğü) seri porta gider.
+    flushing: AtomicBool,
+}
+
+// Yuva içeriğine `task` alanının sahipliği kuralıyla tek görev erişir.
+unsafe impl Sync for Session {}
+
+#[allow(clippy::declare_interior_mutable_const)]
+const EMPTY: Session = Session {
+    task: AtomicUsize::new(FREE),
+    kick: AtomicBool::new(false),
+    last_input: AtomicU64::new(0),
+    opened_at: AtomicU64::new(0),
+    stream: UnsafeCell::new(None),
+    out: UnsafeCell::new(OutBuf { buf: [0; OUT_BUF], len: 0 }),
+    flushing: AtomicBool::new(false),
+};
+static SESSIONS: [Session; MAX_SESSIONS] = [EMPTY; MAX_SESSIONS];
+
+fn session_of(task: TaskId) -> Option<&'static Session> {
+    SESSIONS.iter().find(|s| s.task.load(Ordering::Acquire) == task)
+}
+
+impl Session {
+    /// Bağlantı (yalnızca oturum görevinden).
+    fn stream(&self) -> Option<&TcpStream> {
+        // SAFETY: Yuvanın sahibi çağıran görevdir.
+        unsafe { (*self.stream.get()).as_ref() }
+    }
+
+    /// Çıktıyı tampona ekler; `\n` telnet satır sonu `\r\n`'e çevrilir.
+    fn push(&self, bytes: &[u8]) {
+        for &byte in bytes {
+            if byte == b'\n' {
+                self.push_raw(b'\r');
+            }
+            self.push_raw(byte);
+        }
+    }
+
+    fn push_raw(&self, byte: u8) {
+        // SAFETY: Yuvanın sahibi çağıran görevdir.
+        let full = unsafe { (*self.out.get()).len == OUT_BUF };
+        if full {
+            self.flush();
+        }
+        let out = unsafe { &mut *self.out.get() };
+        if out.len < OUT_BUF {
+            out.buf[out.len] = byte;
+            out.len += 1;
+        }
+    }
+
+    /// Tampondakileri bağlantıya gönderir; gönderilemeyen çıktı atılır.
+    fn flush(&self) {
+        if self.flushing.swap(true, Ordering::Acquire) {
+            return;
+        }
+        // SAFETY: Yuvanın sahibi çağıran görevdir.
+        let out = unsafe { &mut *self.out.get() };
+        if let Some(stream) = self.stream() {
+            let _ = stream.send(&out.buf[..out.len], SEND_TIMEOUT_TICKS);
+        }
+        out.len = 0;
+        self.flushing.store(false, Ordering::Release);
+    }
+}
+
+/// Oturum yazıcısı (`fmt::Write` olarak satır düzenleyiciye verilir).
+struct SessionWriter(&'static Session);
+
+impl Write for SessionWriter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.push(s.as_bytes());
+        Ok(())
+    }
+}
+
+/// `shell::OutputSink`: oturum görevinin konsol çıktısını bağlantıya yazar.
+fn session_sink(task: TaskId, args: fmt::Arguments) -> bool {
+    match session_of(task) {
+        Some(session) if !session.flushing.load(Ordering::Acquire) => SessionWriter(session).write_fmt(args).is_ok(),
+        _ => false,
+    }
+}
+
+// -----------------------------------------------------------------------------
+// TELNET GİRDİSİ
+// -----------------------------------------------------------------------------
+
+/// IAC dizilerini ayıklayan girdi çözücüsü.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Input {
+    Data,
+    /// IAC alındı.
+    Command,
+    /// WILL/WONT/DO/DONT'un seçenek baytı bekleniyor.
+    Option,
+    /// Alt anlaşma gövdesi (IAC SE'ye kadar).
+    Subnegotiation,
+    SubnegotiationIac,
+}
+
+impl Input {
+    /// Baytı işler; uygulamaya geçen veri baytını döndürür.
+    fn feed(&mut self, byte: u8) -> Option<u8> {
+        let (next, data) = match (*self, byte) {
+            (Input::Data, IAC) => (Input::Command, None),
+            (Input::Data, _) => (Input::Data, Some(byte)),
+            (Input::Command, IAC) => (Input::Data, Some(IAC)),
+            (Input::Command, WILL..=DONT) => (Input::Option, None),
+            (Input::Command, SB) => (Input::Subnegotiation, None),
+            (Input::Command, _) | (Input::Option, _) => (Input::Data, None),
+            (Input::Subnegotiation, IAC) => (Input::SubnegotiationIac, None),
+            (Input::Subnegotiation, _) => (Input::Subnegotiation, None),
+            (Input::SubnegotiationIac, SE) => (Input::Data, None),
+            (Input::SubnegotiationIac, _) => (Input::Subnegotiation, None),
+        };
+        *self = next;
+        data
+    }
+}
+
+/// Oturumun sonraki girdi parçasını bekler.
+///
+/// # Dönüş Değeri
+/// Okunan bayt sayısı; bağlantı kapandıysa, oturum atıldıysa veya boşta
+/// kalma süresi dolduysa `None`.
+fn read_input(session: &Session, buf: &mut [u8]) -> Option<usize> {
+    loop {
+        if session.kick.load(Ordering::Acquire) {
+            return None;
+        }
+        match session.stream()?.recv(buf, 0) {
+            Ok(0) | Err(KernelError::NotFound) => return None,
+            Ok(n) => {
+                session.last_input.store(sched::ticks(), Ordering::Relaxed);
+                return Some(n);
+            }
+            Err(_) => {}
+        }
+        if sched::ticks().saturating_sub(session.last_input.load(Ordering::Relaxed)) > IDLE_TIMEOUT_TICKS {
+            session.push("\nZaman aşımı\n".as_bytes());
+            return None;
+        }
+        crate::timer::sleep(POLL_TICKS);
+    }
+}
+
+/// Parolayı yankısız okur ve doğrular.
+fn authenticate(session: &'static Session, input: &mut Input) -> bool {
+    let expected = config::get::<TelnetConfig>().secret_hash;
+    for _ in 0..MAX_AUTH_ATTEMPTS {
+        session.push(b"Parola: ");
+        session.flush();
+        let mut password = [0u8; LINE_MAX];
+        let mut len = 0;
+        let mut chunk = [0u8; 64];
+        'line: loop {
+            let Some(n) = read_input(session, &mut chunk) else {
+                crypto::zeroize(&mut password);
+                return false;
+            };
+            for &byte in &chunk[..n] {
+                match input.feed(byte) {
+                    Some(b'\r' | b'\n') => break 'line,
+                    Some(0x7f | 0x08) => len = len.saturating_sub(1),
+                    Some(byte) if len < LINE_MAX && byte >= 0x20 => {
+                        password[len] = byte;
+                        len += 1;
+                    }
+                    _ => {}
+                }
+            }
+        }
+        crypto::zeroize(&mut chunk);
+        session.push(b"\n");
+        let ok = crypto::ct_eq(&sha256::digest(&password[..len]), &expected);
+        crypto::zeroize(&mut password);
+        if ok {
+            return true;
+        }
+        AUTH_FAILED.inc();
+        session.push("Hatalı parola\n".as_bytes());
+        session.flush();
+        crate::timer::sleep(AUTH_DELAY_TICKS);
+    }
+    false
+}
+
+// -----------------------------------------------------------------------------
+// OTURUM GÖREVİ
+// -----------------------------------------------------------------------------
+
+fn session_task(_arg: u64) {
+    let Some(me) = sched::current() else { return };
+    // Dinleyici görev kimliğini `spawn` döndükten sonra yazar
+    let session = loop {
+        if let Some(session) = session_of(me) {
+            break session;
+        }
+        crate::timer::sleep(1);
+    };
+    let peer = session.stream().map(TcpStream::peer);
+    if let Some((addr, port)) = peer {
+        crate::klog!(crate::net::net::LOG, Info, "[TELNET] {}:{} bağlandı", addr, port);
+    }
+
+    session.push(&[IAC, WILL, OPT_ECHO, IAC, WILL, OPT_SGA]);
+    let mut input = Input::Data;
+    if authenticate(session, &mut input) {
+        run_shell(session, me, &mut input);
+    }
+
+    session.flush();
+    let _ = crate::shell::set_redirect(me, None);
+    // SAFETY: Yuvanın sahibi bu görevdir; bırakılan bağlantı arka planda kapanır.
+    unsafe { *session.stream.get() = None };
+    if let Some((addr, port)) = peer {
+        crate::klog!(crate::net::net::LOG, Info, "[TELNET] {}:{} oturumu kapandı", addr, port);
+    }
+    session.task.store(FREE, Ordering::Release);
+    sched::exit(me).ok();
+    sched::schedule();
+}
+
+fn run_shell(session: &'static Session, me: TaskId, input: &mut Input) {
+    if crate::shell::set_redirect(me, Some(session_sink)).is_err() {
+        return;
+    }
+    let mut editor = LineEditor::new(PROMPT);
+    editor.prompt(&mut SessionWriter(session));
+    session.flush();
+    let mut chunk = [0u8; 64];
+    while let Some(n) = read_input(session, &mut chunk) {
+        for &byte in &chunk[..n] {
+            let Some(byte) = input.feed(byte) else { continue };
+            let Some(line) = editor.feed_byte(byte, &mut SessionWriter(session)) else { continue };
+            let line = line.as_str();
+            if matches!(line.trim(), "exit" | "logout") {
+                return;
+            }
+            if let Err(e) = crate::shell::execute(line) {
+                if line.split_whitespace().next().and_then(crate::shell::find).is_some() {
+                    serial_println!("Hata: {:?}", e);
+                }
+            }
+            editor.prompt(&mut SessionWriter(session));
+        }
+        session.flush();
+    }
+}
+
+// -----------------------------------------------------------------------------
+// DİNLEYİCİ
+// -----------------------------------------------------------------------------
+
+/// Yeni bağlantıya bir yuva ve görev verir; yer yoksa bağlantı kapatılır.
+fn start_session(stream: TcpStream) -> Result<(), KernelError> {
+    let Some(session) = SESSIONS
+        .iter()
+        .find(|s| s.task.compare_exchange(FREE, STARTING, Ordering::AcqRel, Ordering::Relaxed).is_ok())
+    else {
+        REJECTED.inc();
+        let _ = stream.send("Oturum sınırı doldu\r\n".as_bytes(), SEND_TIMEOUT_TICKS);
+        return Err(KernelError::ResourceBusy);
+    };
+    let now = sched::ticks();
+    session.kick.store(false, Ordering::Relaxed);
+    session.last_input.store(now, Ordering::Relaxed);
+    session.opened_at.store(now, Ordering::Relaxed);
+    // SAFETY: `STARTING` yuvasına yalnızca dinleyici erişir.
+    unsafe {
+        *session.stream.get() = Some(stream);
+        (*session.out.get()).len = 0;
+    }
+    match sched::spawn("telnet", session_task as usize, TASK_PRIORITY) {
+        Ok(task) => {
+            session.task.store(task, Ordering::Release);
+            SESSIONS_OPENED.inc();
+            Ok(())
+        }
+        Err(e) => {
+            unsafe { *session.stream.get() = None };
+            session.task.store(FREE, Ordering::Release);
+            Err(e)
+        }
+    }
+}
+
+fn listener_task(_arg: u64) {
+    let port = config::get::<TelnetConfig>().port;
+    let listener = match TcpListener::bind(port) {
+        Ok(listener) => listener,
+        Err(e) => {
+            serial_println!("[TELNET] {} portu dinlenemedi: {:?}", port, e);
+            return;
+        }
+    };
+    loop {
+        match listener.accept(0) {
+            Ok(stream) => {
+                if let Err(e) = start_session(stream) {
+                    crate::klog!(crate::net::net::LOG, Warn, "[TELNET] Oturum başlatılamadı: {:?}", e);
+                }
+            }
+            Err(_) => crate::timer::sleep(POLL_TICKS),
+        }
+    }
+}
+
+fn init() -> Result<(), KernelError> {
+    let cfg = config::get::<TelnetConfig>();
+    if !cfg.enabled() {
+        return Ok(());
+    }
+    sched::spawn("telnetd", listener_task as usize, TASK_PRIORITY)?;
+    serial_println!("[TELNET] Uzak kabuk {} portunda", cfg.port);
+    Ok(())
+}
+
+crate::initcall!(late, "telnet", init);
+
+// -----------------------------------------------------------------------------
+// KABUK KOMUTU
+// -----------------------------------------------------------------------------
+
+/// `telnet [list | kick <n> | passwd <parola> | port <n> | disable]`
+fn telnet_command(args: &[&str]) -> Result<(), KernelError> {
+    match args.get(1).copied() {
+        None | Some("list") => {
+            let cfg = config::get::<TelnetConfig>();
+            serial_println!("  port {} ({})", cfg.port, if cfg.enabled() { "açık" } else { "kapalı" });
+            let now = sched::ticks();
+            for (i, session) in SESSIONS.iter().enumerate() {
+                match session.task.load(Ordering::Acquire) {
+                    FREE => {}
+                    STARTING => serial_println!("  {}: başlatılıyor", i),
+                    task => serial_println!(
+                        "  {}: görev {} süre {} s boşta {} s",
+                        i,
+                        task,
+                        now.saturating_sub(session.opened_at.load(Ordering::Relaxed)) / TICK_HZ,
+                        now.saturating_sub(session.last_input.load(Ordering::Relaxed)) / TICK_HZ
+                    ),
+                }
+            }
+            return Ok(());
+        }
+        Some("kick") => {
+            let index: usize = args.get(2).and_then(|s| s.parse().ok()).ok_or(KernelError::InvalidArgument)?;
+            let session = SESSIONS.get(index).ok_or(KernelError::NotFound)?;
+            if session.task.load(Ordering::Acquire) == FREE {
+                return Err(KernelError::NotFound);
+            }
+            session.kick.store(true, Ordering::Release);
+            return Ok(());
+        }
+        Some("passwd") => {
+            let secret = args.get(2).ok_or(KernelError::InvalidArgument)?;
+            config::set(&TelnetConfig { secret_hash: sha256::digest(secret.as_bytes()), ..config::get() })?;
+        }
+        Some("port") => {
+            let port = args.get(2).and_then(|s| s.parse().ok()).filter(|&p| p != 0).ok_or(KernelError::InvalidArgument)?;
+            config::set(&TelnetConfig { port, ..config::get() })?;
+        }
+        Some("disable") => config::reset::<TelnetConfig>()?,
+        Some(_) => return Err(KernelError::InvalidArgument),
+    }
+    serial_println!("  Kaydedildi; sonraki açılışta geçerli");
+    Ok(())
+}
+
+crate::shell_command!("telnet", "Uzak kabuk (telnet [list | kick <n> | passwd <parola> | port <n> | disable])", telnet_command);
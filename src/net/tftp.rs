@@ -0,0 +1,204 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/net/tftp.rs
+// TFTP istemcisi (RFC 1350): sunucudan dosyayı "octet" kipinde okur.
+//
+// İndirilen veri bir güncelleme hazırlık alanına (`update::Staging`) sırayla
+// yazılır ve aktarım sırasında CRC-32'si hesaplanır. Her DATA bloğu
+// onaylanır; yanıt gelmezse son paket (RRQ veya ACK) yeniden gönderilir.
+// Sunucunun ilk yanıtı geldiği port aktarım kimliği (TID) olarak kilitlenir;
+// başka porttan gelen paketlere "bilinmeyen TID" hatası döndürülür.
+// Blok numarası 65535'ten sonra 0'a sararak devam eder (büyük görüntüler).
+
+use crate::net::net::{self, Ipv4Addr};
+use crate::net::socket::{Protocol, Socket};
+use crate::platformgeneric::{KernelError, SystemConstants};
+use crate::serial_println;
+use crate::update::{self, RamStaging, Staging};
+
+// -----------------------------------------------------------------------------
+// SABİTLER
+// -----------------------------------------------------------------------------
+
+/// Sunucunun iyi bilinen portu.
+pub const SERVER_PORT: u16 = 69;
+/// Veri bloğu boyutu.
+pub const BLOCK_SIZE: usize = 512;
+
+const OP_RRQ: u16 = 1;
+const OP_DATA: u16 = 3;
+const OP_ACK: u16 = 4;
+const OP_ERROR: u16 = 5;
+
+const ERR_NOT_FOUND: u16 = 1;
+const ERR_DISK_FULL: u16 = 3;
+const ERR_UNKNOWN_TID: u16 = 5;
+
+/// Yanıt bekleme süresi (1 s) ve yeniden gönderim sayısı.
+const TIMEOUT_TICKS: u64 = SystemConstants::TIMER_TICK_HZ;
+const MAX_RETRIES: u32 = 5;
+/// Gönderim ARP çözümlemesini beklerken yeniden deneme aralığı (50 ms).
+const ARP_RETRY_TICKS: u64 = SystemConstants::TIMER_TICK_HZ / 20;
+
+crate::kernel_counter!(BLOCKS_RECEIVED, "net.tftp.blocks");
+crate::kernel_counter!(RETRANSMITS, "net.tftp.retransmits");
+
+/// Tamamlanan aktarımın özeti.
+#[derive(Debug, Clone, Copy)]
+pub struct Transfer {
+    /// Görüntü uzunluğu (bayt).
+    pub len: usize,
+    /// Alınan verinin CRC-32'si.
+    pub crc32: u32,
+}
+
+// -----------------------------------------------------------------------------
+// PAKETLER
+// -----------------------------------------------------------------------------
+
+/// RRQ paketini oluşturur; uzunluğunu döndürür.
+fn build_rrq(buf: &mut [u8], file: &str) -> Result<usize, KernelError> {
+    const MODE: &[u8] = b"octet\0";
+    let len = 2 + file.len() + 1 + MODE.len();
+    if file.is_empty() || len > buf.len() || file.bytes().any(|b| b == 0) {
+        return Err(KernelError::InvalidArgument);
+    }
+    buf[0..2].copy_from_slice(&OP_RRQ.to_be_bytes());
+    buf[2..2 + file.len()].copy_from_slice(file.as_bytes());
+    buf[2 + file.len()] = 0;
+    buf[3 + file.len()..len].copy_from_slice(MODE);
+    Ok(len)
+}
+
+fn build_ack(block: u16) -> [u8; 4] {
+    let mut ack = [0u8; 4];
+    ack[0..2].copy_from_slice(&OP_ACK.to_be_bytes());
+    ack[2..4].copy_from_slice(&block.to_be_bytes());
+    ack
+}
+
+fn send_error(sock: &Socket, dst: Ipv4Addr, port: u16, code: u16, msg: &str) {
+    let mut buf = [0u8; 64];
+    let msg = &msg.as_bytes()[..msg.len().min(buf.len() - 5)];
+    buf[0..2].copy_from_slice(&OP_ERROR.to_be_bytes());
+    buf[2..4].copy_from_slice(&code.to_be_bytes());
+    buf[4..4 + msg.len()].copy_from_slice(msg);
+    let _ = sock.send_to(dst, port, &buf[..5 + msg.len()]);
+}
+
+/// ARP çözümlemesi sürüyorsa kısa aralıklarla yeniden dener.
+fn send(sock: &Socket, dst: Ipv4Addr, port: u16, data: &[u8]) -> Result<(), KernelError> {
+    let deadline = crate::sched::ticks() + TIMEOUT_TICKS;
+    loop {
+        match sock.send_to(dst, port, data) {
+            Err(KernelError::ResourceBusy) if crate::sched::ticks() < deadline => net::poll_for(ARP_RETRY_TICKS),
+            result => return result,
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// İNDİRME
+// -----------------------------------------------------------------------------
+
+/// `server`'dan `file`'ı indirip `staging`'e yazar.
+///
+/// # Hatalar
+/// * `NotFound`: Sunucu dosyayı bulamadı.
+/// * `ResourceBusy`: Sunucu yanıt vermiyor.
+/// * `OutOfMemoryStatic`: Görüntü hazırlık alanına sığmıyor.
+/// * `GenericFailure`: Sunucu başka bir hata bildirdi.
+pub fn download(server: Ipv4Addr, file: &str, staging: &mut dyn Staging) -> Result<Transfer, KernelError> {
+    let sock = Socket::open(Protocol::Udp)?;
+    let mut request = [0u8; 128];
+    let request_len = build_rrq(&mut request, file)?;
+    send(&sock, server, SERVER_PORT, &request[..request_len])?;
+
+    let mut buf = [0u8; 4 + BLOCK_SIZE];
+    let mut tid: Option<u16> = None;
+    let mut expected: u16 = 1;
+    let mut len = 0usize;
+    let mut crc = 0u32;
+    let mut retries = 0;
+
+    loop {
+        let (n, src, port) = match sock.recv_timeout(&mut buf, TIMEOUT_TICKS) {
+            Ok(packet) => packet,
+            Err(KernelError::ResourceBusy) => {
+                retries += 1;
+                if retries > MAX_RETRIES {
+                    return Err(KernelError::ResourceBusy);
+                }
+                RETRANSMITS.inc();
+                // Son paket yeniden gönderilir: ilk blok gelmediyse RRQ, yoksa son ACK
+                match tid {
+                    None => send(&sock, server, SERVER_PORT, &request[..request_len])?,
+                    Some(tid) => send(&sock, server, tid, &build_ack(expected.wrapping_sub(1)))?,
+                }
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+        if src != server || n < 4 {
+            continue;
+        }
+        if tid.is_some_and(|tid| tid != port) {
+            send_error(&sock, src, port, ERR_UNKNOWN_TID, "unknown transfer id");
+            continue;
+        }
+
+        let opcode = u16::from_be_bytes([buf[0], buf[1]]);
+        let block = u16::from_be_bytes([buf[2], buf[3]]);
+        match opcode {
+            OP_DATA if block == expected => {
+                tid = Some(port);
+                retries = 0;
+                let data = &buf[4..n];
+                if let Err(e) = staging.write(len, data) {
+                    send_error(&sock, src, port, ERR_DISK_FULL, "staging area full");
+                    return Err(e);
+                }
+                len += data.len();
+                crc = update::crc32_update(crc, data);
+                BLOCKS_RECEIVED.inc();
+                send(&sock, src, port, &build_ack(block))?;
+                expected = expected.wrapping_add(1);
+                if data.len() < BLOCK_SIZE {
+                    staging.finish()?;
+                    return Ok(Transfer { len, crc32: crc });
+                }
+            }
+            // Önceki bloğun tekrarı: ACK'imiz kaybolmuş, yeniden onaylanır
+            OP_DATA if tid.is_some() && block == expected.wrapping_sub(1) => {
+                send(&sock, src, port, &build_ack(block))?;
+            }
+            OP_ERROR => {
+                let end = buf[4..n].iter().position(|&b| b == 0).map_or(n, |p| p + 4);
+                let msg = core::str::from_utf8(&buf[4..end]).unwrap_or("?");
+                serial_println!("[TFTP] Sunucu hatası {}: {}", block, msg);
+                return Err(if block == ERR_NOT_FOUND { KernelError::NotFound } else { KernelError::GenericFailure });
+            }
+            _ => {}
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// KABUK
+// -----------------------------------------------------------------------------
+
+/// `tftp <sunucu> <dosya>`: dosyayı RAM'e indirir, boyutunu ve CRC-32'sini yazar.
+fn tftp_command(args: &[&str]) -> Result<(), KernelError> {
+    if args.len() < 3 {
+        return Err(KernelError::InvalidArgument);
+    }
+    let server = net::lookup(args[1])?;
+    let mut staging = RamStaging::new(update::DEFAULT_RAM_STAGING)?;
+    let start = crate::sched::ticks();
+    let transfer = download(server, args[2], &mut staging)?;
+    let ms = crate::platformgeneric::ticks_to_ms(crate::sched::ticks() - start);
+    serial_println!("{}: {} bayt, crc32 {:08x}, {} ms", args[2], transfer.len, transfer.crc32, ms);
+    Ok(())
+}
+
+crate::shell_command!("tftp", "TFTP ile dosya indirir (tftp <sunucu> <dosya>)", tftp_command);
@@ -0,0 +1,90 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/net/udp.rs
+// UDP: datagramları hedef porta bağlı sokete teslim eder ve gönderir.
+//
+// Gelen datagramların sağlama toplamı (sıfır değilse) sözde başlıkla
+// doğrulanır; gönderilenlerde her zaman hesaplanır. Bağlı soketi olmayan
+// porta gelen datagramlar sessizce atılır (ICMP "port ulaşılamaz" yanıtı
+// gönderilmez).
+
+use crate::net::ipv4::{self, Header};
+use crate::net::net::{self, Ipv4Addr};
+use crate::net::netbuf::{self, NetBuf};
+use crate::net::socket::{self, Protocol};
+use crate::platformgeneric::KernelError;
+
+/// UDP başlığı uzunluğu.
+pub const HEADER_LEN: usize = 8;
+/// En büyük UDP yükü.
+pub const MAX_PAYLOAD: usize = socket::MAX_DATAGRAM - HEADER_LEN;
+
+crate::kernel_counter!(BAD_CHECKSUM, "net.udp.bad_checksum");
+crate::kernel_counter!(NO_PORT, "net.udp.no_port");
+
+/// Sözde başlık dahil UDP sağlama toplamı.
+fn checksum(src: Ipv4Addr, dst: Ipv4Addr, datagram: &[u8]) -> u16 {
+    let mut sum = net::checksum_add(0, &src.0);
+    sum = net::checksum_add(sum, &dst.0);
+    sum = net::checksum_add(sum, &[0, ipv4::PROTO_UDP]);
+    sum = net::checksum_add(sum, &(datagram.len() as u16).to_be_bytes());
+    net::checksum_finish(net::checksum_add(sum, datagram))
+}
+
+/// Gelen UDP datagramını işler (`ipv4::receive` içinden).
+pub fn receive(iface: usize, header: &Header, datagram: &[u8]) {
+    let _ = iface;
+    if datagram.len() < HEADER_LEN {
+        return;
+    }
+    let src_port = u16::from_be_bytes([datagram[0], datagram[1]]);
+    let dst_port = u16::from_be_bytes([datagram[2], datagram[3]]);
+    let len = usize::from(u16::from_be_bytes([datagram[4], datagram[5]]));
+    if len < HEADER_LEN || len > datagram.len() {
+        return;
+    }
+    let datagram = &datagram[..len];
+    if datagram[6..8] != [0, 0] && checksum(header.src, header.dst, datagram) != 0 {
+        BAD_CHECKSUM.inc();
+        return;
+    }
+    if !socket::deliver(Protocol::Udp, dst_port, header.src, src_port, &datagram[HEADER_LEN..]) {
+        NO_PORT.inc();
+    }
+}
+
+/// Datagramı verilen arayüz ve kaynak adresle gönderir.
+fn send_via(iface: usize, src: Ipv4Addr, next_hop: Ipv4Addr, src_port: u16, dst: Ipv4Addr, dst_port: u16, data: &[u8]) -> Result<(), KernelError> {
+    if data.len() > MAX_PAYLOAD {
+        return Err(KernelError::InvalidArgument);
+    }
+    // Yük tampona bir kez yazılır; UDP, IPv4 ve Ethernet başlıkları önüne eklenir
+    let mut buf = NetBuf::from_slice(netbuf::HEADROOM, data)?;
+    let len = HEADER_LEN + data.len();
+    let header = buf.push_header(HEADER_LEN)?;
+    header[0..2].copy_from_slice(&src_port.to_be_bytes());
+    header[2..4].copy_from_slice(&dst_port.to_be_bytes());
+    header[4..6].copy_from_slice(&(len as u16).to_be_bytes());
+    // Sağlama alanı hesaplamadan önce sıfırlanır (bölüt eski bayt taşır)
+    header[6..8].fill(0);
+    // Hesaplanan 0, "sağlama yok" ile karışmaması için 0xFFFF olarak gönderilir
+    let sum = match checksum(src, dst, buf.data()) {
+        0 => 0xFFFF,
+        sum => sum,
+    };
+    buf.data_mut()[6..8].copy_from_slice(&sum.to_be_bytes());
+    ipv4::send_buf_from(iface, src, dst, next_hop, ipv4::PROTO_UDP, buf)
+}
+
+/// Datagramı `dst:dst_port`'a yönlendirerek gönderir.
+pub fn send(src_port: u16, dst: Ipv4Addr, dst_port: u16, data: &[u8]) -> Result<(), KernelError> {
+    let (iface, src, next_hop) = ipv4::select_route(dst)?;
+    send_via(iface, src, next_hop, src_port, dst, dst_port, data)
+}
+
+/// Datagramı arayüzden 255.255.255.255'e yayınlar. Kaynak adres arayüzün
+/// adresidir (adres almadan önce 0.0.0.0).
+pub fn send_broadcast(iface: usize, src_port: u16, dst_port: u16, data: &[u8]) -> Result<(), KernelError> {
+    let src = net::interface(iface).ok_or(KernelError::NotFound)?.addr;
+    send_via(iface, src, Ipv4Addr::BROADCAST, src_port, Ipv4Addr::BROADCAST, dst_port, data)
+}
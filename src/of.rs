@@ -0,0 +1,472 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/of.rs
+// OpenFirmware (IEEE 1275) istemci arayüzü: bellek haritası, konsol ve
+// aygıt ağacının düzleştirilmesi.
+//
+// SPARC OBP ve PowerPC SLOF/OpenBIOS çekirdeğe düzleştirilmiş aygıt ağacı
+// yerine bir istemci arayüzü giriş noktası verir (`boot::BootRegs::aux`).
+// Her çağrı bir hücre dizisidir: servis adı, argüman sayısı, dönüş sayısı,
+// argümanlar ve dönüşler. Hücre SPARC V9'da 64, PowerPC'de 32 bittir;
+// PowerPC'de ürün yazılımı 32-bit kipte çağrılır (`of_enter`), bu yüzden
+// çağrı dizisi ve tamponlar ilk 4 GiB'de (çekirdek görüntüsünde) olmalıdır.
+//
+// `populate` açılışta `boot::init` içinden çağrılır:
+// * `/memory` düğümünün `available` özelliği (yoksa `reg`) kullanılabilir
+//   RAM olarak bellek haritasına yazılır; ürün yazılımının kendi kullandığı
+//   alanlar `available`'da yoktur.
+// * Ağaç `child`/`peer`/`nextprop`/`getprop` ile dolaşılıp statik bir
+//   tampona FDT (v17) olarak yazılır ve `fdt::init` ile kaydedilir. Böylece
+//   sürücüler, komut satırı ve initramfs FDT kullanan mimarilerle aynı yoldan
+//   okunur.
+// * `/chosen` `stdout` örneği erken konsol için saklanır (`write`).
+//
+// Çekirdek kendi tuzak tablosunu kurduktan sonra ürün yazılımı çağrılmamalıdır;
+// `write` yalnızca erken açılışta kullanılır.
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+use crate::boot::{BootInfo, MemoryKind};
+use crate::platformgeneric::spinlock::Spinlock;
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// İSTEMCİ ARAYÜZÜ
+// -----------------------------------------------------------------------------
+
+/// Çağrı dizisinin hücresi.
+#[cfg(target_arch = "powerpc64")]
+pub type Cell = u32;
+#[cfg(not(target_arch = "powerpc64"))]
+pub type Cell = u64;
+
+/// Geçersiz tanıtıcı / başarısızlık dönüşü.
+const FAILURE: Cell = Cell::MAX;
+
+/// Bir çağrıdaki en fazla argüman + dönüş.
+const MAX_CELLS: usize = 12;
+
+/// Giriş noktası (0: yok).
+static ENTRY: AtomicUsize = AtomicUsize::new(0);
+/// `/chosen` `stdout` örneği (0: yok).
+static STDOUT: AtomicUsize = AtomicUsize::new(0);
+/// Ürün yazılımı yeniden girişli değildir.
+static LOCK: Spinlock = Spinlock::new();
+
+#[cfg(target_arch = "powerpc64")]
+core::arch::global_asm!(
+    // of_enter(args: r3, entry: r4): kalıcı yazmaçları saklar, MSR[SF]'i
+    // temizleyip 32-bit kipte ürün yazılımını çağırır ve MSR'yi geri yükler.
+    ".global of_enter",
+    "of_enter:",
+    "mflr 0",
+    "std 0, 16(1)",
+    "stdu 1, -256(1)",
+    "std 2, 40(1)",
+    "std 13, 56(1)",
+    "std 14, 64(1)", "std 15, 72(1)", "std 16, 80(1)", "std 17, 88(1)",
+    "std 18, 96(1)", "std 19, 104(1)", "std 20, 112(1)", "std 21, 120(1)",
+    "std 22, 128(1)", "std 23, 136(1)", "std 24, 144(1)", "std 25, 152(1)",
+    "std 26, 160(1)", "std 27, 168(1)", "std 28, 176(1)", "std 29, 184(1)",
+    "std 30, 192(1)", "std 31, 200(1)",
+    "mfmsr 11",
+    "std 11, 208(1)",
+    "mtctr 4",
+    "rldicl 11, 11, 0, 1",
+    "mtmsrd 11",
+    "isync",
+    "bctrl",
+    "ld 11, 208(1)",
+    "mtmsrd 11",
+    "isync",
+    "ld 2, 40(1)",
+    "ld 13, 56(1)",
+    "ld 14, 64(1)", "ld 15, 72(1)", "ld 16, 80(1)", "ld 17, 88(1)",
+    "ld 18, 96(1)", "ld 19, 104(1)", "ld 20, 112(1)", "ld 21, 120(1)",
+    "ld 22, 128(1)", "ld 23, 136(1)", "ld 24, 144(1)", "ld 25, 152(1)",
+    "ld 26, 160(1)", "ld 27, 168(1)", "ld 28, 176(1)", "ld 29, 184(1)",
+    "ld 30, 192(1)", "ld 31, 200(1)",
+    "addi 1, 1, 256",
+    "ld 0, 16(1)",
+    "mtlr 0",
+    "blr",
+);
+
+#[cfg(target_arch = "powerpc64")]
+extern "C" {
+    fn of_enter(args: *mut Cell, entry: usize) -> i32;
+}
+
+/// Ürün yazılımına geçer.
+///
+/// # Safety
+/// `entry` geçerli bir istemci arayüzü giriş noktası olmalıdır.
+#[cfg(target_arch = "powerpc64")]
+unsafe fn enter(args: *mut Cell, entry: usize) -> i32 {
+    of_enter(args, entry)
+}
+
+/// Ürün yazılımına geçer (OBP giriş noktası V9 C ABI'sine uyar).
+///
+/// # Safety
+/// `entry` geçerli bir istemci arayüzü giriş noktası olmalıdır.
+#[cfg(target_arch = "sparc64")]
+unsafe fn enter(args: *mut Cell, entry: usize) -> i32 {
+    let cif: extern "C" fn(*mut Cell) -> i32 = core::mem::transmute(entry);
+    cif(args)
+}
+
+#[cfg(not(any(target_arch = "powerpc64", target_arch = "sparc64")))]
+unsafe fn enter(_args: *mut Cell, _entry: usize) -> i32 {
+    -1
+}
+
+/// İşaretçiyi hücreye çevirir (PowerPC'de 4 GiB altı varsayılır).
+fn ptr<T>(p: *const T) -> Cell {
+    p as usize as Cell
+}
+
+/// `service` servisini çağırır; dönüşler `rets`'e yazılır.
+///
+/// # Hatalar
+/// * `NotFound`: İstemci arayüzü yok.
+/// * `InvalidArgument`: Çok fazla argüman.
+/// * `Device(Unsupported)`: Ürün yazılımı servisi tanımadı.
+fn call(service: &'static [u8], args: &[Cell], rets: &mut [Cell]) -> Result<(), KernelError> {
+    let entry = ENTRY.load(Ordering::Acquire);
+    if entry == 0 {
+        return Err(KernelError::NotFound);
+    }
+    if args.len() + rets.len() > MAX_CELLS {
+        return Err(KernelError::InvalidArgument);
+    }
+    debug_assert!(service.last() == Some(&0));
+    let mut cells = [0 as Cell; 3 + MAX_CELLS];
+    cells[0] = ptr(service.as_ptr());
+    cells[1] = args.len() as Cell;
+    cells[2] = rets.len() as Cell;
+    cells[3..3 + args.len()].copy_from_slice(args);
+
+    LOCK.lock();
+    // SAFETY: Giriş noktası önyükleyiciden geldi; dizi çağrı boyunca yaşar.
+    let status = unsafe { enter(cells.as_mut_ptr(), entry) };
+    LOCK.unlock();
+
+    if status != 0 {
+        return Err(DeviceError::Unsupported.into());
+    }
+    rets.copy_from_slice(&cells[3 + args.len()..3 + args.len() + rets.len()]);
+    Ok(())
+}
+
+/// Tek dönüşlü çağrı.
+fn call1(service: &'static [u8], args: &[Cell]) -> Result<Cell, KernelError> {
+    let mut ret = [0];
+    call(service, args, &mut ret)?;
+    Ok(ret[0])
+}
+
+// -----------------------------------------------------------------------------
+// SERVİSLER
+// -----------------------------------------------------------------------------
+
+/// Paket (düğüm) tanıtıcısı.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Phandle(Cell);
+
+/// Sıfır sonlu yolu (en fazla 127 bayt) yığında hazırlar.
+fn cpath(path: &str, buf: &mut [u8; 128]) -> Result<(), KernelError> {
+    if path.len() >= buf.len() {
+        return Err(KernelError::InvalidArgument);
+    }
+    buf[..path.len()].copy_from_slice(path.as_bytes());
+    buf[path.len()] = 0;
+    Ok(())
+}
+
+/// Yol ile düğüm arar.
+pub fn finddevice(path: &str) -> Option<Phandle> {
+    let mut buf = [0u8; 128];
+    cpath(path, &mut buf).ok()?;
+    match call1(b"finddevice\0", &[ptr(buf.as_ptr())]).ok()? {
+        0 | FAILURE => None,
+        handle => Some(Phandle(handle)),
+    }
+}
+
+/// İlk alt düğüm.
+pub fn child(node: Phandle) -> Option<Phandle> {
+    match call1(b"child\0", &[node.0]).ok()? {
+        0 | FAILURE => None,
+        handle => Some(Phandle(handle)),
+    }
+}
+
+/// Sonraki kardeş düğüm (`None` ile kök).
+pub fn peer(node: Option<Phandle>) -> Option<Phandle> {
+    match call1(b"peer\0", &[node.map_or(0, |n| n.0)]).ok()? {
+        0 | FAILURE => None,
+        handle => Some(Phandle(handle)),
+    }
+}
+
+/// Özelliğin uzunluğu (yoksa `None`).
+pub fn getproplen(node: Phandle, name: &[u8]) -> Option<usize> {
+    match call1(b"getproplen\0", &[node.0, ptr(name.as_ptr())]).ok()? {
+        FAILURE => None,
+        len => Some(len as usize),
+    }
+}
+
+/// Özelliği `buf`'a okur; okunan bayt sayısını döndürür. `name` sıfır sonlu olmalıdır.
+pub fn getprop(node: Phandle, name: &[u8], buf: &mut [u8]) -> Option<usize> {
+    let args = [node.0, ptr(name.as_ptr()), ptr(buf.as_ptr()), buf.len() as Cell];
+    match call1(b"getprop\0", &args).ok()? {
+        FAILURE => None,
+        len => Some((len as usize).min(buf.len())),
+    }
+}
+
+/// `prev`'den (sıfır sonlu; boşsa ilk) sonraki özelliğin adını `buf`'a yazar.
+fn nextprop(node: Phandle, prev: &[u8], buf: &mut [u8; 32]) -> bool {
+    let args = [node.0, ptr(prev.as_ptr()), ptr(buf.as_ptr())];
+    matches!(call1(b"nextprop\0", &args), Ok(1))
+}
+
+/// Düğümün tam yolunu `buf`'a yazar; uzunluğu döndürür.
+fn package_to_path(node: Phandle, buf: &mut [u8]) -> Option<usize> {
+    let args = [node.0, ptr(buf.as_ptr()), buf.len() as Cell];
+    match call1(b"package-to-path\0", &args).ok()? {
+        FAILURE => None,
+        len => Some((len as usize).min(buf.len())),
+    }
+}
+
+/// Ürün yazılımının konsoluna yazar (erken açılış).
+pub fn write(bytes: &[u8]) -> Result<(), KernelError> {
+    let stdout = STDOUT.load(Ordering::Relaxed) as Cell;
+    if stdout == 0 {
+        return Err(KernelError::NotFound);
+    }
+    call1(b"write\0", &[stdout, ptr(bytes.as_ptr()), bytes.len() as Cell]).map(|_| ())
+}
+
+// -----------------------------------------------------------------------------
+// BELLEK HARİTASI
+// -----------------------------------------------------------------------------
+
+/// Kökün `#address-cells` / `#size-cells` değerleri (varsayılan 2 / 1).
+fn root_cells() -> (usize, usize) {
+    let Some(root) = peer(None) else { return (2, 1) };
+    let mut cell = [0u8; 4];
+    let mut read = |name: &[u8], default: usize| match getprop(root, name, &mut cell) {
+        Some(4) => u32::from_be_bytes(cell) as usize,
+        _ => default,
+    };
+    (read(b"#address-cells\0", 2), read(b"#size-cells\0", 1))
+}
+
+fn read_cells(data: &[u8], cells: usize) -> u64 {
+    (0..cells).map_while(|i| crate::endian::read_be32(data, i * 4)).fold(0, |acc, c| acc << 32 | u64::from(c))
+}
+
+/// `/memory` düğümündeki kullanılabilir bölgeleri haritaya yazar.
+fn memory_map(info: &mut BootInfo) {
+    let Some(memory) = finddevice("/memory") else { return };
+    let (address_cells, size_cells) = root_cells();
+    let mut buf = [0u8; 512];
+    let len = getprop(memory, b"available\0", &mut buf).or_else(|| getprop(memory, b"reg\0", &mut buf)).unwrap_or(0);
+    let entry = (address_cells + size_cells) * 4;
+    for chunk in buf[..len].chunks_exact(entry) {
+        let base = read_cells(chunk, address_cells);
+        let size = read_cells(&chunk[address_cells * 4..], size_cells);
+        if info.add_memory(base, size, MemoryKind::Usable).is_err() {
+            serial_println!("[OF] UYARI: bellek haritası kesildi");
+            break;
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// AĞACIN DÜZLEŞTİRİLMESİ
+// -----------------------------------------------------------------------------
+
+/// Düzleştirilmiş ağaç tamponu.
+const FDT_SIZE: usize = 64 * 1024;
+/// Özellik adları tamponu (sonradan ağacın sonuna eklenir).
+const STRINGS_SIZE: usize = 8 * 1024;
+/// Desteklenen en derin düğüm iç içeliği (`fdt::MAX_DEPTH` ile aynı).
+const MAX_DEPTH: usize = 16;
+
+const FDT_MAGIC: u32 = 0xD00D_FEED;
+const FDT_BEGIN_NODE: u32 = 0x1;
+const FDT_END_NODE: u32 = 0x2;
+const FDT_PROP: u32 = 0x3;
+const FDT_END: u32 = 0x9;
+const HEADER_SIZE: usize = 40;
+/// Boş bellek ayırma tablosu (tek sonlandırıcı girdi).
+const RSVMAP_SIZE: usize = 16;
+
+#[repr(C, align(8))]
+struct Blob([u8; FDT_SIZE]);
+
+static mut FDT_BLOB: Blob = Blob([0; FDT_SIZE]);
+static mut STRINGS: [u8; STRINGS_SIZE] = [0; STRINGS_SIZE];
+
+struct Flattener<'a> {
+    out: &'a mut [u8],
+    len: usize,
+    strings: &'a mut [u8],
+    strings_len: usize,
+}
+
+impl Flattener<'_> {
+    fn push(&mut self, bytes: &[u8]) -> Result<(), KernelError> {
+        let end = self.len + bytes.len();
+        let padded = (end + 3) & !3;
+        if padded > self.out.len() {
+            return Err(KernelError::OutOfMemoryStatic);
+        }
+        self.out[self.len..end].copy_from_slice(bytes);
+        self.out[end..padded].fill(0);
+        self.len = padded;
+        Ok(())
+    }
+
+    fn push_u32(&mut self, value: u32) -> Result<(), KernelError> {
+        self.push(&value.to_be_bytes())
+    }
+
+    /// Özellik adının dize tablosundaki ofseti (varsa yeniden kullanılır).
+    fn string(&mut self, name: &[u8]) -> Result<u32, KernelError> {
+        let mut off = 0;
+        while off < self.strings_len {
+            let end = off + self.strings[off..self.strings_len].iter().position(|&b| b == 0).unwrap_or(0);
+            if &self.strings[off..end] == name {
+                return Ok(off as u32);
+            }
+            off = end + 1;
+        }
+        let end = self.strings_len + name.len() + 1;
+        if end > self.strings.len() {
+            return Err(KernelError::OutOfMemoryStatic);
+        }
+        self.strings[self.strings_len..end - 1].copy_from_slice(name);
+        self.strings[end - 1] = 0;
+        self.strings_len = end;
+        Ok(off as u32)
+    }
+
+    fn node(&mut self, node: Phandle, depth: usize) -> Result<(), KernelError> {
+        if depth >= MAX_DEPTH {
+            return Err(KernelError::InvalidArgument);
+        }
+        // Düğüm adı: tam yolun son bileşeni (kökte boş)
+        let mut path = [0u8; 256];
+        let len = package_to_path(node, &mut path).unwrap_or(0);
+        let path = &path[..path[..len].iter().position(|&b| b == 0).unwrap_or(len)];
+        let name = path.rsplit(|&b| b == b'/').next().unwrap_or(&[]);
+        self.push_u32(FDT_BEGIN_NODE)?;
+        let mut name_z = [0u8; 64];
+        let name = &name[..name.len().min(name_z.len() - 1)];
+        name_z[..name.len()].copy_from_slice(name);
+        self.push(&name_z[..name.len() + 1])?;
+
+        let mut prev = [0u8; 32];
+        let mut next = [0u8; 32];
+        while nextprop(node, &prev, &mut next) {
+            let name_len = next.iter().position(|&b| b == 0).unwrap_or(next.len() - 1);
+            let value_len = getproplen(node, &next).unwrap_or(0);
+            let nameoff = self.string(&next[..name_len])?;
+            self.push_u32(FDT_PROP)?;
+            self.push_u32(value_len as u32)?;
+            self.push_u32(nameoff)?;
+            // Değer doğrudan çıktı tamponuna okunur
+            let start = self.len;
+            if start + value_len + 3 > self.out.len() {
+                return Err(KernelError::OutOfMemoryStatic);
+            }
+            let read = getprop(node, &next, &mut self.out[start..start + value_len]).unwrap_or(0);
+            self.out[start + read..start + value_len].fill(0);
+            let end = (start + value_len + 3) & !3;
+            self.out[start + value_len..end].fill(0);
+            self.len = end;
+            prev = next;
+        }
+
+        let mut current = child(node);
+        while let Some(sub) = current {
+            self.node(sub, depth + 1)?;
+            current = peer(Some(sub));
+        }
+        self.push_u32(FDT_END_NODE)
+    }
+}
+
+/// Ürün yazılımının ağacını `FDT_BLOB`'a FDT olarak yazar; blob adresini döndürür.
+fn flatten(boot_cpu: u32) -> Result<usize, KernelError> {
+    let root = peer(None).ok_or(KernelError::NotFound)?;
+    // SAFETY: Yalnızca açılışta, tek işlemcide bir kez çağrılır.
+    let (out, strings) = unsafe {
+        (&mut (*core::ptr::addr_of_mut!(FDT_BLOB)).0, &mut *core::ptr::addr_of_mut!(STRINGS))
+    };
+    let struct_off = HEADER_SIZE + RSVMAP_SIZE;
+    out[..struct_off].fill(0);
+    let mut flat = Flattener { out: &mut out[..], len: struct_off, strings: &mut strings[..], strings_len: 0 };
+    flat.node(root, 0)?;
+    flat.push_u32(FDT_END)?;
+
+    let struct_len = flat.len - struct_off;
+    let strings_off = flat.len;
+    let strings_len = flat.strings_len;
+    let total = strings_off + strings_len;
+    if total > flat.out.len() {
+        return Err(KernelError::OutOfMemoryStatic);
+    }
+    flat.out[strings_off..total].copy_from_slice(&flat.strings[..strings_len]);
+
+    let header = [
+        FDT_MAGIC,
+        total as u32,
+        struct_off as u32,
+        strings_off as u32,
+        HEADER_SIZE as u32,
+        17, // sürüm
+        16, // geriye uyumlu en eski sürüm
+        boot_cpu,
+        strings_len as u32,
+        struct_len as u32,
+    ];
+    for (i, word) in header.iter().enumerate() {
+        crate::endian::write_be32(&mut flat.out, i * 4, *word);
+    }
+    Ok(flat.out.as_ptr() as usize)
+}
+
+// -----------------------------------------------------------------------------
+// AÇILIŞ
+// -----------------------------------------------------------------------------
+
+/// İstemci arayüzünden bellek haritasını ve aygıt ağacını okuyup `info`'yu
+/// doldurur (bkz. dosya başı). `boot::init` tarafından çağrılır.
+pub fn populate(info: &mut BootInfo, entry: usize) {
+    ENTRY.store(entry, Ordering::Release);
+
+    if let Some(chosen) = finddevice("/chosen") {
+        let mut cell = [0u8; 4];
+        if getprop(chosen, b"stdout\0", &mut cell) == Some(4) {
+            STDOUT.store(u32::from_be_bytes(cell) as usize, Ordering::Relaxed);
+        }
+    }
+
+    memory_map(info);
+
+    match flatten(info.boot_cpu as u32) {
+        Ok(blob) if crate::fdt::init(blob).is_ok() => {
+            info.dtb = Some(blob);
+            crate::boot::from_fdt(info);
+        }
+        Ok(_) => serial_println!("[OF] UYARI: düzleştirilen ağaç doğrulanamadı"),
+        Err(err) => serial_println!("[OF] UYARI: aygıt ağacı düzleştirilemedi: {:?}", err),
+    }
+}
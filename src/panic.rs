@@ -0,0 +1,240 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/panic.rs
+// Mimariden bağımsız, yeniden girişe dayanıklı panik yolu.
+//
+// Mimarilerin `#[panic_handler]`'ı yalnızca `handle`'ı çağırır. Panik en
+// kötü bağlamda gelebilir: bir kesme işleyicisinin içinde, zamanlayıcı ya da
+// konsol kilidi tutulurken veya panik yolunun kendisinde (ikinci panik). Bu
+// yüzden yol hiçbir kilidi beklemez ve teşhis çıktısını her durumda üretir:
+//
+// * Önce yerel kesmeler kapatılır ve işlemci başına `DEPTH` sayacı artırılır.
+//   1: tam rapor; 2: panik yolunda ikinci panik, yalnızca konum ve ileti
+//   (çökme kaydı atlanır); 3 ve üstü: biçimlendirme de güvenilmez sayılır,
+//   sabit bir dizge yazılıp işlemci durdurulur.
+// * Çıktı `PanicConsole` ile yazılır: kabuk yönlendirmesi (`shell::redirect`)
+//   atlanır, bayt erken konsolun (`earlycon`) seçtiği UART'a yoklamalı
+//   gönderilir. AMD64 ve ARMv9'da TX kuyruğu deneme kilidiyle boşaltılır;
+//   kilit tutuluyorsa kuyruk beklenmeden doğrudan yazılır.
+// * Paniği ilk yakalayan işlemci `OWNER` olur ve diğerlerini durdurur:
+//   kayıtlı durdurma IPI'si (`set_halt_ipi_hook`) gönderilir, IPI yoksa
+//   işlemciler bir sonraki tikte `stop_if_halted` ile durur. Sahip raporu
+//   yazmadan önce kısa bir süre onları bekler; böylece çıktı karışmaz.
+//   Aynı anda paniğe düşen diğer işlemciler tek satır yazıp durur.
+// * Görev kimliği zamanlayıcı kilidi denenerek okunur (`sched::try_current`);
+//   kilit bu işlemcide tutuluyken panik gelmişse görev "?" yazılır.
+//
+// Kabuk komutu: yok; procfs: yok (sayaçlar: `panic.nested`, `panic.halted`)
+
+use core::fmt::{self, Write};
+use core::panic::PanicInfo;
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use crate::percpu;
+
+// -----------------------------------------------------------------------------
+// DURUM
+// -----------------------------------------------------------------------------
+
+/// Paniği yakalayan işlemci (`NO_OWNER`: panik yok).
+const NO_OWNER: usize = usize::MAX;
+static OWNER: AtomicUsize = AtomicUsize::new(NO_OWNER);
+
+/// Diğer işlemcilerden durmaları istendi mi?
+static HALT_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// Durma isteğine uyan işlemci sayısı.
+static HALTED_CPUS: AtomicUsize = AtomicUsize::new(0);
+
+/// Durdurma IPI'si gönderen mimari işlevi (`fn(cpu)`; 0: kayıt yok).
+/// Kilitsiz okunabilmesi için işaretçi olarak saklanır.
+static HALT_IPI_HOOK: AtomicUsize = AtomicUsize::new(0);
+
+/// Sahibin diğer işlemcileri bekleyeceği en fazla döngü.
+const HALT_WAIT_SPINS: usize = 10_000_000;
+
+crate::percpu!(DEPTH: AtomicUsize = AtomicUsize::new(0));
+
+crate::kernel_counter!(NESTED, "panic.nested");
+crate::kernel_counter!(HALTED, "panic.halted");
+
+/// Mimarinin durdurma IPI'sini kaydeder. İşlev kesme bağlamından ve kilit
+/// almadan çalışabilmelidir; alıcıda `stop_if_halted` çağrılmalıdır.
+pub fn set_halt_ipi_hook(hook: fn(usize)) {
+    HALT_IPI_HOOK.store(hook as usize, Ordering::Release);
+}
+
+/// Bir işlemci panikte mi?
+pub fn in_progress() -> bool {
+    OWNER.load(Ordering::Acquire) != NO_OWNER
+}
+
+/// Durma istendiyse bu işlemciyi durdurur. Zamanlayıcı tikinden ve durdurma
+/// IPI'sinin işleyicisinden çağrılır.
+pub fn stop_if_halted() {
+    if HALT_REQUESTED.load(Ordering::Acquire) && OWNER.load(Ordering::Acquire) != percpu::cpu_id() {
+        irq_disable();
+        HALTED_CPUS.fetch_add(1, Ordering::AcqRel);
+        HALTED.inc();
+        halt();
+    }
+}
+
+// -----------------------------------------------------------------------------
+// KONSOL
+// -----------------------------------------------------------------------------
+
+/// Kilitsiz, yoklamalı panik konsolu.
+pub struct PanicConsole;
+
+impl PanicConsole {
+    fn put(byte: u8) {
+        crate::arch::console::panic_write_byte(byte);
+    }
+}
+
+impl Write for PanicConsole {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for byte in s.bytes() {
+            if byte == b'\n' {
+                Self::put(b'\r');
+            }
+            Self::put(byte);
+        }
+        Ok(())
+    }
+}
+
+/// Panik konsoluna biçimlendirilmiş satır yazar.
+macro_rules! panic_println {
+    ($($arg:tt)*) => {{
+        let _ = writeln!(PanicConsole, $($arg)*);
+    }};
+}
+
+// -----------------------------------------------------------------------------
+// MİMARİ
+// -----------------------------------------------------------------------------
+
+/// Yerel kesmeleri kapatır; panik yolu geri dönmediği için durum atılır.
+fn irq_disable() {
+    let _ = crate::irqoff::save();
+}
+
+/// Kesme güdümlü konsol gönderimini kapatır; kuyruk deneme kilidiyle boşaltılır.
+fn console_quiesce() {
+    crate::arch::console::panic_quiesce();
+}
+
+fn halt() -> ! {
+    crate::arch::panic::halt_loop()
+}
+
+// -----------------------------------------------------------------------------
+// PANİK YOLU
+// -----------------------------------------------------------------------------
+
+/// Diğer çevrimiçi işlemcilerden durmalarını ister ve kısa süre bekler.
+fn halt_others(cpu: usize) {
+    HALT_REQUESTED.store(true, Ordering::Release);
+    let others = percpu::online_cpus().saturating_sub(1);
+    if others == 0 {
+        return;
+    }
+    let hook = HALT_IPI_HOOK.load(Ordering::Acquire);
+    if hook != 0 {
+        // SAFETY: Değer yalnızca `set_halt_ipi_hook` ile bir `fn(usize)`'dan yazılır.
+        let hook: fn(usize) = unsafe { core::mem::transmute::<usize, fn(usize)>(hook) };
+        for (other, _) in DEPTH.iter().filter(|&(other, _)| other != cpu) {
+            hook(other);
+        }
+    }
+    let mut spins = 0;
+    while HALTED_CPUS.load(Ordering::Acquire) < others && spins < HALT_WAIT_SPINS {
+        core::hint::spin_loop();
+        spins += 1;
+    }
+}
+
+/// Mimarilerin panik işleyicisi buraya devreder; `arch` başlıkta görünür.
+pub fn handle(info: &PanicInfo, arch: &str) -> ! {
+    irq_disable();
+    let depth = DEPTH.get().fetch_add(1, Ordering::AcqRel) + 1;
+    if depth >= 3 {
+        // Biçimlendirme bile paniğe yol açmış olabilir
+        let _ = PanicConsole.write_str("\n!!! PANİK YOLUNDA TEKRARLANAN PANİK: işlemci durduruluyor !!!\n");
+        halt();
+    }
+
+    let cpu = percpu::cpu_id();
+    if depth == 2 {
+        NESTED.inc();
+        panic_println!("\n!!! İÇ İÇE PANİK (işlemci {}, panik yolunda) !!!", cpu);
+        report_location(info);
+        panic_println!("... İşlemci Durduruluyor.");
+        halt();
+    }
+
+    if let Err(owner) = OWNER.compare_exchange(NO_OWNER, cpu, Ordering::AcqRel, Ordering::Acquire) {
+        // Başka bir işlemci raporu yazıyor; ona tek satırla eşlik edip dur
+        HALTED_CPUS.fetch_add(1, Ordering::AcqRel);
+        HALTED.inc();
+        match info.location() {
+            Some(location) => panic_println!(
+                "[PANIC] işlemci {}: {}:{} (işlemci {} panikte, duruluyor)",
+                cpu,
+                location.file(),
+                location.line(),
+                owner
+            ),
+            None => panic_println!("[PANIC] işlemci {}: (işlemci {} panikte, duruluyor)", cpu, owner),
+        }
+        halt();
+    }
+
+    // Kuyruktaki logları gönder ve konsolu yoklamalı kipe al; kesmelere artık güvenilmez
+    console_quiesce();
+    halt_others(cpu);
+
+    panic_println!("\n========================================");
+    panic_println!("!!! KERNEL PANIC ({}) !!!", arch);
+    panic_println!(
+        "İşlemci: {}, bağlam: {}, görev: {}",
+        cpu,
+        if crate::irqstack::in_interrupt() { "kesme işleyicisi" } else { "görev" },
+        TaskLabel(crate::sched::try_current())
+    );
+    report_location(info);
+    panic_println!("========================================");
+
+    // Çökme kaydı: sıcak yeniden başlatmadan sonra `lastboot` ile okunur
+    crate::reboot::record_panic(info);
+
+    panic_println!("... İşlemci Durduruluyor.");
+    halt();
+}
+
+fn report_location(info: &PanicInfo) {
+    match info.location() {
+        Some(location) => {
+            panic_println!("Hata Konumu: {}:{}:{}", location.file(), location.line(), location.column())
+        }
+        None => panic_println!("Hata Konumu: Bilinmiyor"),
+    }
+    match info.message() {
+        Some(message) => panic_println!("Hata Mesajı: {}", message),
+        None => panic_println!("Hata Mesajı: Yok"),
+    }
+}
+
+/// `try_current` sonucunu yazar: kilit alınamadıysa "?".
+struct TaskLabel(Option<Option<crate::sched::TaskId>>);
+
+impl fmt::Display for TaskLabel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(Some(id)) => write!(f, "{}", id),
+            Some(None) => f.write_str("yok"),
+            None => f.write_str("?"),
+        }
+    }
+}
@@ -0,0 +1,302 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/partition.rs
+// ARINC-653 tarzı zaman bölümlemesi (time partitioning).
+//
+// Zaman çizelgesi sabit uzunlukta, tekrarlanan bir ana çerçeveye (major frame)
+// bölünür. Ana çerçeve, her biri tek bir bölüme (partition) ayrılmış zaman
+// pencerelerinden (minor frame / window) oluşur. Bir pencere süresince yalnızca
+// o bölüme atanmış görevler çalışabilir; pencere sınırı zamanlayıcı kesmesinde
+// uygulanır ve bir bölüm, başka bir bölümün süresini hiçbir koşulda kullanamaz.
+//
+// Hiçbir bölüme atanmamış görevler (çekirdek hizmetleri, boşta görevi) her
+// pencerede çalışmaya uygundur. Bölümleme `start` çağrılana kadar etkin değildir.
+
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use crate::platformgeneric::spinlock::IrqSafeSpinlock;
+use crate::platformgeneric::KernelError;
+use crate::sched::{TaskId, MAX_TASKS};
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// SABİTLER VE TİPLER
+// -----------------------------------------------------------------------------
+
+/// En fazla bölüm sayısı.
+pub const MAX_PARTITIONS: usize = 8;
+
+/// Ana çerçevedeki en fazla pencere sayısı.
+pub const MAX_WINDOWS: usize = 16;
+
+/// "Bölüm yok" işareti (atomik alanlarda).
+const NO_PARTITION: u8 = 0xFF;
+
+/// Bölüm kimliği.
+pub type PartitionId = u8;
+
+/// Ana çerçevedeki tek bir zaman penceresi.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Window {
+    /// Pencerenin ait olduğu bölüm.
+    pub partition: PartitionId,
+    /// Pencere süresi (tik).
+    pub duration: u32,
+}
+
+#[derive(Clone, Copy)]
+struct Partition {
+    name: &'static str,
+    used: bool,
+    /// Bölüme ayrılan toplam pencere süresi (tik).
+    granted_ticks: u64,
+    /// Bölüm görevlerinin fiilen çalıştığı süre (tik).
+    busy_ticks: u64,
+}
+
+impl Partition {
+    const fn empty() -> Self {
+        Partition { name: "", used: false, granted_ticks: 0, busy_ticks: 0 }
+    }
+}
+
+struct PartitionTable {
+    lock: IrqSafeSpinlock,
+    partitions: UnsafeCell<[Partition; MAX_PARTITIONS]>,
+    windows: UnsafeCell<[Window; MAX_WINDOWS]>,
+    window_count: UnsafeCell<usize>,
+    /// Geçerli pencere indeksi ve pencerede kalan tik.
+    cursor: UnsafeCell<(usize, u32)>,
+    /// Tamamlanan ana çerçeve sayısı.
+    frames: UnsafeCell<u64>,
+}
+
+// Tüm alanlara erişim `lock` ile korunur. Tik kesmesi (`tick`) de kilidi
+// aldığından kilit kesmeler kapalıyken tutulur.
+unsafe impl Sync for PartitionTable {}
+
+static TABLE: PartitionTable = PartitionTable {
+    lock: IrqSafeSpinlock::new(),
+    partitions: UnsafeCell::new([Partition::empty(); MAX_PARTITIONS]),
+    windows: UnsafeCell::new([Window { partition: 0, duration: 0 }; MAX_WINDOWS]),
+    window_count: UnsafeCell::new(0),
+    cursor: UnsafeCell::new((0, 0)),
+    frames: UnsafeCell::new(0),
+};
+
+// Zamanlayıcının seçim yolunda kilitsiz okunan durum.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static ACTIVE: AtomicU8 = AtomicU8::new(NO_PARTITION);
+
+#[allow(clippy::declare_interior_mutable_const)]
+const UNASSIGNED: AtomicU8 = AtomicU8::new(NO_PARTITION);
+static MEMBERSHIP: [AtomicU8; MAX_TASKS] = [UNASSIGNED; MAX_TASKS];
+
+// -----------------------------------------------------------------------------
+// YAPILANDIRMA
+// -----------------------------------------------------------------------------
+
+/// Yeni bir bölüm oluşturur.
+///
+/// # Dönüş Değeri
+/// Bölüm kimliği; tablo doluysa `OutOfMemoryStatic`.
+pub fn create(name: &'static str) -> Result<PartitionId, KernelError> {
+    let flags = TABLE.lock.lock();
+    let partitions = unsafe { &mut *TABLE.partitions.get() };
+
+    let result = match partitions.iter_mut().enumerate().find(|(_, p)| !p.used) {
+        Some((id, slot)) => {
+            *slot = Partition { name, used: true, ..Partition::empty() };
+            Ok(id as PartitionId)
+        }
+        None => Err(KernelError::OutOfMemoryStatic),
+    };
+
+    TABLE.lock.unlock(flags);
+    result
+}
+
+fn partition_exists(id: PartitionId) -> bool {
+    let flags = TABLE.lock.lock();
+    let exists = unsafe { (*TABLE.partitions.get()).get(id as usize).is_some_and(|p| p.used) };
+    TABLE.lock.unlock(flags);
+    exists
+}
+
+/// Görevi bir bölüme atar (`None`: bölümsüz, her pencerede çalışabilir).
+pub fn assign(task: TaskId, partition: Option<PartitionId>) -> Result<(), KernelError> {
+    if task >= MAX_TASKS {
+        return Err(KernelError::InvalidArgument);
+    }
+    let value = match partition {
+        Some(id) if partition_exists(id) => id,
+        Some(_) => return Err(KernelError::NotFound),
+        None => NO_PARTITION,
+    };
+    MEMBERSHIP[task].store(value, Ordering::Release);
+    Ok(())
+}
+
+/// Ana çerçeveyi tanımlar. Bölümleme etkinse yeni çizelge bir sonraki tikte
+/// ilk pencereden başlar.
+///
+/// # Hatalar
+/// * `InvalidArgument`: Pencere listesi boş, çok uzun veya sıfır süreli pencere içeriyor.
+/// * `NotFound`: Pencere tanımsız bir bölüme ait.
+pub fn set_schedule(windows: &[Window]) -> Result<(), KernelError> {
+    if windows.is_empty() || windows.len() > MAX_WINDOWS || windows.iter().any(|w| w.duration == 0) {
+        return Err(KernelError::InvalidArgument);
+    }
+    if !windows.iter().all(|w| partition_exists(w.partition)) {
+        return Err(KernelError::NotFound);
+    }
+
+    let flags = TABLE.lock.lock();
+    unsafe {
+        (*TABLE.windows.get())[..windows.len()].copy_from_slice(windows);
+        *TABLE.window_count.get() = windows.len();
+        *TABLE.cursor.get() = (windows.len() - 1, 0);
+    }
+    TABLE.lock.unlock(flags);
+    Ok(())
+}
+
+/// Ana çerçevenin toplam süresi (tik).
+pub fn major_frame_ticks() -> u64 {
+    let flags = TABLE.lock.lock();
+    let total = unsafe {
+        (*TABLE.windows.get())[..*TABLE.window_count.get()]
+            .iter()
+            .map(|w| w.duration as u64)
+            .sum()
+    };
+    TABLE.lock.unlock(flags);
+    total
+}
+
+/// Bölümlemeyi başlatır. Bir çizelge tanımlanmış olmalıdır.
+pub fn start() -> Result<(), KernelError> {
+    let flags = TABLE.lock.lock();
+    let ready = unsafe { *TABLE.window_count.get() } > 0;
+    TABLE.lock.unlock(flags);
+
+    if !ready {
+        return Err(KernelError::InvalidArgument);
+    }
+    ENABLED.store(true, Ordering::Release);
+    serial_println!("[PART] Zaman bölümlemesi başladı. Ana çerçeve: {} tik", major_frame_ticks());
+    Ok(())
+}
+
+/// Bölümlemeyi durdurur; tüm görevler yeniden her zaman çalışabilir.
+pub fn stop() {
+    ENABLED.store(false, Ordering::Release);
+    ACTIVE.store(NO_PARTITION, Ordering::Release);
+}
+
+// -----------------------------------------------------------------------------
+// ZAMANLAYICI ARAYÜZÜ
+// -----------------------------------------------------------------------------
+
+/// Geçerli pencerenin bölümü (bölümleme kapalıysa `None`).
+pub fn active() -> Option<PartitionId> {
+    match ACTIVE.load(Ordering::Acquire) {
+        NO_PARTITION => None,
+        id => Some(id),
+    }
+}
+
+/// Görevin şu anki pencerede çalışmasına izin var mı? Zamanlayıcının seçim
+/// yolunda kilitsiz çağrılır.
+#[inline]
+pub fn allows(task: TaskId) -> bool {
+    if !ENABLED.load(Ordering::Acquire) {
+        return true;
+    }
+    match MEMBERSHIP.get(task).map(|m| m.load(Ordering::Acquire)) {
+        Some(NO_PARTITION) | None => true,
+        Some(id) => id == ACTIVE.load(Ordering::Acquire),
+    }
+}
+
+/// `sched::tick` tarafından her tikte çağrılır: pencere süresini düşer ve sınırda
+/// sonraki pencereye geçer.
+///
+/// # Parametreler
+/// * `running`: Bu tikte çalışan görev (bölüm kullanım muhasebesi için).
+///
+/// # Dönüş Değeri
+/// Etkin bölüm değiştiyse `true` (yeniden zamanlama gerekir).
+pub(crate) fn tick(running: Option<TaskId>) -> bool {
+    if !ENABLED.load(Ordering::Acquire) {
+        return false;
+    }
+
+    let flags = TABLE.lock.lock();
+    // SAFETY: Kilit tutuluyor.
+    let (partitions, windows, count, cursor, frames) = unsafe {
+        (
+            &mut *TABLE.partitions.get(),
+            &*TABLE.windows.get(),
+            *TABLE.window_count.get(),
+            &mut *TABLE.cursor.get(),
+            &mut *TABLE.frames.get(),
+        )
+    };
+
+    let previous = ACTIVE.load(Ordering::Relaxed);
+
+    // Biten tikin muhasebesi
+    if previous != NO_PARTITION {
+        let part = &mut partitions[previous as usize];
+        part.granted_ticks += 1;
+        if running.is_some_and(|id| MEMBERSHIP[id].load(Ordering::Relaxed) == previous) {
+            part.busy_ticks += 1;
+        }
+    }
+
+    // Pencere sınırı
+    cursor.1 = cursor.1.saturating_sub(1);
+    if cursor.1 == 0 && count > 0 {
+        cursor.0 = (cursor.0 + 1) % count;
+        if cursor.0 == 0 && previous != NO_PARTITION {
+            *frames += 1;
+        }
+        cursor.1 = windows[cursor.0].duration;
+        ACTIVE.store(windows[cursor.0].partition, Ordering::Release);
+    }
+
+    let switched = ACTIVE.load(Ordering::Relaxed) != previous;
+    TABLE.lock.unlock(flags);
+    switched
+}
+
+// -----------------------------------------------------------------------------
+// KABUK KOMUTU
+// -----------------------------------------------------------------------------
+
+fn part_command(_args: &[&str]) -> Result<(), KernelError> {
+    let flags = TABLE.lock.lock();
+    let (partitions, windows, count, frames) = unsafe {
+        (*TABLE.partitions.get(), *TABLE.windows.get(), *TABLE.window_count.get(), *TABLE.frames.get())
+    };
+    TABLE.lock.unlock(flags);
+
+    serial_println!(
+        "Bölümleme: {} | Etkin: {:?} | Tamamlanan ana çerçeve: {}",
+        if ENABLED.load(Ordering::Relaxed) { "açık" } else { "kapalı" },
+        active(),
+        frames
+    );
+    serial_println!("Pencereler:");
+    for (i, w) in windows[..count].iter().enumerate() {
+        serial_println!("  #{:<2} bölüm {} ({}) {} tik", i, w.partition, partitions[w.partition as usize].name, w.duration);
+    }
+    serial_println!("  ID AD               AYRILAN     KULLANILAN");
+    for (id, p) in partitions.iter().enumerate().filter(|(_, p)| p.used) {
+        serial_println!("  {:>2} {:<16} {:>10} {:>10}", id, p.name, p.granted_ticks, p.busy_ticks);
+    }
+    Ok(())
+}
+
+crate::shell_command!("part", "Zaman bölümleme durumu", part_command);
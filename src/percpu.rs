@@ -0,0 +1,196 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/percpu.rs
+// İşlemci başına (per-CPU) değişkenler.
+//
+// `percpu!` ile tanımlanan değişkenler `.percpu` linker bölümüne yerleşir; bu
+// bölüm bir şablondur. Her işlemciye açılışta şablonun bir kopyası ayrılır ve
+// kopyanın adresi mimarinin işlemci yerel taban yazmacına yazılır (AMD64 GS
+// tabanı, ARMv9 `TPIDR_EL1`, RISC-V `tp`, PowerPC64 `r13`, SPARC `%g7`,
+// LoongArch `$r21`, MIPS `KScratch1`, OpenRISC `r10`). Bir değişkene erişim,
+// taban + değişkenin şablondaki ofsetidir; kilit gerekmez ve kesme
+// bağlamından güvenle yapılır. Değişkenler `Sync` olmalıdır (genelde atomik).
+//
+// * Açılış işlemcisi (0) `mm` seviyesine kadar doğrudan şablonu kullanır,
+//   sonra kendi kopyasına geçer. Şablon, o ana kadar yazılan değerlerle
+//   birlikte sonradan açılan işlemcilere kopyalanır.
+// * SMP açılışı: açık işlemci `prepare(cpu)` ile alanı ayırır, yeni işlemci
+//   ilk iş olarak `enter(cpu)` çağırır.
+//
+// Kullananlar: zamanlayıcının yeniden zamanlama bayrağı (`sched`), boşta
+// muhasebesi (`idle`). RCU eklendiğinde sükûnet durumu da burada tutulur.
+//
+// Kabuk komutu: `percpu`
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+use crate::layout;
+use crate::memory::frame::{self, FRAME_SIZE};
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// SABİTLER VE TİPLER
+// -----------------------------------------------------------------------------
+
+/// Desteklenen en fazla işlemci.
+pub const MAX_CPUS: usize = 4;
+
+/// İşlemci başına değişken; `percpu!` ile tanımlanır.
+#[repr(transparent)]
+pub struct PerCpu<T>(T);
+
+impl<T: Sync> PerCpu<T> {
+    pub const fn new(value: T) -> Self {
+        PerCpu(value)
+    }
+
+    /// Değişkenin şablondaki ofseti.
+    fn offset(&'static self) -> usize {
+        self as *const Self as usize - layout::percpu().start
+    }
+
+    /// Çalışılan işlemcinin kopyası.
+    #[inline]
+    pub fn get(&'static self) -> &'static T {
+        // SAFETY: Taban, şablonun bu işlemciye ait kopyasını (veya şablonu)
+        // gösterir; ofset şablonun içindedir.
+        unsafe { &*((base() + self.offset()) as *const T) }
+    }
+
+    /// `cpu` işlemcisinin kopyası (çevrimdışıysa `None`).
+    pub fn get_for(&'static self, cpu: usize) -> Option<&'static T> {
+        let base = BASES.get(cpu)?.load(Ordering::Acquire);
+        // SAFETY: `get` ile aynı; alan hiç bırakılmaz.
+        (base != 0).then(|| unsafe { &*((base + self.offset()) as *const T) })
+    }
+
+    /// Çevrimiçi işlemcilerin kopyaları (istatistik toplamak için).
+    pub fn iter(&'static self) -> impl Iterator<Item = (usize, &'static T)> {
+        (0..MAX_CPUS).filter_map(move |cpu| Some((cpu, self.get_for(cpu)?)))
+    }
+}
+
+/// İşlemci başına bir değişken tanımlar ve `.percpu` bölümüne yerleştirir.
+///
+/// ```ignore
+/// crate::percpu!(pub(crate) SWITCHES: AtomicU64 = AtomicU64::new(0));
+/// SWITCHES.get().fetch_add(1, Ordering::Relaxed);
+/// ```
+#[macro_export]
+macro_rules! percpu {
+    ($vis:vis $ident:ident: $ty:ty = $init:expr) => {
+        #[used]
+        #[link_section = ".percpu"]
+        $vis static $ident: $crate::percpu::PerCpu<$ty> = $crate::percpu::PerCpu::new($init);
+    };
+}
+
+/// İşlemcilerin alan tabanları (0: çevrimdışı).
+#[allow(clippy::declare_interior_mutable_const)]
+const OFFLINE: AtomicUsize = AtomicUsize::new(0);
+static BASES: [AtomicUsize; MAX_CPUS] = [OFFLINE; MAX_CPUS];
+
+crate::percpu!(CPU_NUMBER: AtomicUsize = AtomicUsize::new(0));
+
+// -----------------------------------------------------------------------------
+// MİMARİ TABAN YAZMACI
+// -----------------------------------------------------------------------------
+
+use crate::arch::task as arch_task;
+
+/// Çalışılan işlemcinin alan tabanı; yazmaç henüz yazılmadıysa şablon.
+#[inline]
+fn base() -> usize {
+    match arch_task::cpu_local_base() {
+        0 => layout::percpu().start,
+        base => base,
+    }
+}
+
+// -----------------------------------------------------------------------------
+// İŞLEMCİ AÇILIŞI
+// -----------------------------------------------------------------------------
+
+/// Şablonun bir kopyasını ayırır; şablon boşsa şablonun kendisi kullanılır.
+fn alloc_area() -> Result<usize, KernelError> {
+    let template = layout::percpu();
+    if template.is_empty() {
+        return Ok(template.start);
+    }
+    let area = frame::alloc_contiguous(template.len().div_ceil(FRAME_SIZE))?;
+    // SAFETY: Alan yeni ayrıldı; şablon linker betiğindeki `.percpu` bölümüdür.
+    unsafe { core::ptr::copy_nonoverlapping(template.start as *const u8, area as *mut u8, template.len()) };
+    Ok(area)
+}
+
+/// `cpu` için alan ayırır (açık bir işlemciden, yeni işlemci başlatılmadan önce).
+///
+/// # Hatalar
+/// * `InvalidArgument`: `cpu >= MAX_CPUS`.
+/// * `ResourceBusy`: İşlemci zaten çevrimiçi ya da görev başına yığın
+///   kanaryası almış görevler var (`canary::smp_prepare`).
+/// * `OutOfMemoryStatic`: Alan için çerçeve yok.
+pub fn prepare(cpu: usize) -> Result<(), KernelError> {
+    let slot = BASES.get(cpu).ok_or(KernelError::InvalidArgument)?;
+    if slot.load(Ordering::Acquire) != 0 {
+        return Err(KernelError::ResourceBusy);
+    }
+    // Tek küresel yığın kanaryası: görev başına değerler kapanır
+    crate::canary::smp_prepare()?;
+    let area = alloc_area()?;
+    // SAFETY: Alan `CPU_NUMBER`'ı içeren şablonun kopyasıdır.
+    unsafe { (*((area + CPU_NUMBER.offset()) as *const AtomicUsize)).store(cpu, Ordering::Relaxed) };
+    slot.store(area, Ordering::Release);
+    Ok(())
+}
+
+/// Yeni işlemcinin ilk işi: `prepare` ile ayrılan alanı taban yazmacına yazar.
+///
+/// # Safety
+/// `cpu` çağıran işlemcinin numarası olmalı ve `prepare(cpu)` başarılı olmalıdır.
+pub unsafe fn enter(cpu: usize) {
+    arch_task::set_cpu_local_base(BASES[cpu].load(Ordering::Acquire));
+}
+
+/// Açılış işlemcisini şablondan kendi kopyasına geçirir.
+fn init_boot_cpu() -> Result<(), KernelError> {
+    let area = alloc_area().unwrap_or_else(|e| {
+        serial_println!("[PERCPU] Açılış işlemcisine alan ayrılamadı ({:?}); şablon kullanılıyor", e);
+        layout::percpu().start
+    });
+    BASES[0].store(area, Ordering::Release);
+    // SAFETY: Alan şablonun kopyası (veya kendisi) ve bu işlemcinindir.
+    unsafe { arch_task::set_cpu_local_base(area) };
+    Ok(())
+}
+
+crate::initcall!(mm, "percpu", init_boot_cpu);
+
+/// Çalışılan işlemcinin numarası.
+#[inline]
+pub fn cpu_id() -> usize {
+    CPU_NUMBER.get().load(Ordering::Relaxed)
+}
+
+/// Çevrimiçi işlemci sayısı (açılış işlemcisi her zaman sayılır).
+pub fn online_cpus() -> usize {
+    BASES.iter().skip(1).filter(|b| b.load(Ordering::Acquire) != 0).count() + 1
+}
+
+// -----------------------------------------------------------------------------
+// KABUK KOMUTU
+// -----------------------------------------------------------------------------
+
+fn percpu_command(_args: &[&str]) -> Result<(), KernelError> {
+    let template = layout::percpu();
+    serial_println!("Şablon: {:#x} ({} bayt), bu işlemci: {}", template.start, template.len(), cpu_id());
+    for (cpu, base) in BASES.iter().enumerate() {
+        match base.load(Ordering::Acquire) {
+            0 => serial_println!("  cpu{}: çevrimdışı", cpu),
+            base => serial_println!("  cpu{}: {:#x}", cpu, base),
+        }
+    }
+    Ok(())
+}
+
+crate::shell_command!("percpu", "İşlemci başına alanları listeler", percpu_command);
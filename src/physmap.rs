@@ -0,0 +1,411 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/physmap.rs
+// Fiziksel bellek haritası (physmap): tüm RAM'in çekirdek adres alanında
+// büyük sayfalarla eşlenmesi ve aygıt bölgelerinin isteğe bağlı eşlenmesi.
+//
+// Eskiden MMU kurulumu ilk 16 MiB'ı sabit olarak birebir eşliyordu; bu
+// aralığın üstündeki RAM'e ve aygıtlara (örn. RISC-V PLIC 0x0C00_0000)
+// erişim sessizce sayfa hatasına düşüyordu. Artık:
+// * RAM bölgeleri önyükleme bilgisinin (`boot::info`) bellek haritasından
+//   okunur; harita boşsa eski 16 MiB varsayımı kullanılır. Mimari MMU
+//   kurulumu bölgeleri `map_ram` ile eşler: hizalı kısımlar büyük sayfalarla
+//   (`LARGE_PAGE_SIZE`), kenarlar 4 KiB sayfalarla.
+// * Aygıt bölgeleri RAM eşlemesinde yoktur. Sürücüler MMIO'ya erişmeden önce
+//   `map_mmio` çağırır; bölge aygıt (önbelleksiz) özniteliğiyle eşlenip
+//   kaydedilir, aynı bölge için sonraki çağrılar kayıtlı pencereyi döndürür.
+// * Sayfa tabloları statik bir havuzdan (`alloc_table`), havuz bitince
+//   çerçeve ayırıcısından alınır. Her tablonun geçerli giriş sayısı tutulur
+//   (`table_ref`/`table_unref`); mimarilerin `unmap_page` fonksiyonları boşalan
+//   ara tabloları `free_table` ile geri verir. Kök tablolar statiktir ve
+//   izlenmez.
+//
+// Sanal adres mimariye göre değişir: amd64 ve rv64i'de birebir (sanal =
+// fiziksel), armv9'da yüksek yarıda doğrusal (`KERNEL_START_VADDR` +
+// fiziksel). Büyük sayfalı eşleme şimdilik bu üç mimaride vardır; diğerlerinde
+// portun `mmu::map_device`'ı, dolayısıyla `map_mmio`, `PlatformSpecificError`
+// döndürür.
+//
+// Kabuk komutu: `physmap`
+
+use core::cell::UnsafeCell;
+use crate::memory::vmaudit::MapError;
+use crate::platformgeneric::spinlock::Spinlock;
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// SABİTLER VE TİPLER
+// -----------------------------------------------------------------------------
+
+/// Küçük sayfa ve sayfa tablosu boyutu.
+pub const PAGE_SIZE: usize = 4096;
+/// Büyük sayfa boyutu (ikinci seviye yaprak).
+pub const LARGE_PAGE_SIZE: usize = 2 * 1024 * 1024;
+
+/// Kaydedilen en fazla RAM bölgesi.
+pub const MAX_RAM_REGIONS: usize = 8;
+/// En fazla aygıt penceresi.
+pub const MAX_MMIO_WINDOWS: usize = 32;
+
+/// Statik sayfa tablosu havuzu (tablo sayısı). 2 MiB sayfalarla 1 GiB RAM
+/// tek bir son seviye tablosuna sığar; havuz açılış için yeterlidir.
+const TABLE_POOL_SIZE: usize = 64;
+/// İzlenen en fazla sayfa tablosu (havuz ve çerçeve ayırıcısından alınanlar).
+const MAX_TABLES: usize = 512;
+
+/// Bellek haritası yoksa varsayılan RAM (eski sabit eşleme).
+const FALLBACK_RAM: Region = Region { start: 0, len: 16 * 1024 * 1024 };
+
+/// Fiziksel adres aralığı.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Region {
+    pub start: usize,
+    pub len: usize,
+}
+
+impl Region {
+    pub fn end(&self) -> usize {
+        self.start + self.len
+    }
+
+    fn overlaps(&self, start: usize, end: usize) -> bool {
+        self.start < end && start < self.end()
+    }
+}
+
+/// `map_mmio` ile eşlenmiş aygıt penceresi.
+#[derive(Debug, Clone, Copy)]
+pub struct MmioWindow {
+    pub phys: usize,
+    pub virt: usize,
+    pub len: usize,
+}
+
+/// Bir eşlemede kullanılan sayfa sayıları.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MapStats {
+    pub large: usize,
+    pub small: usize,
+}
+
+struct Physmap {
+    lock: Spinlock,
+    ram: UnsafeCell<[Option<Region>; MAX_RAM_REGIONS]>,
+    ram_stats: UnsafeCell<MapStats>,
+    mmio: UnsafeCell<[Option<MmioWindow>; MAX_MMIO_WINDOWS]>,
+}
+
+// Tüm alanlara erişim `lock` ile korunur.
+unsafe impl Sync for Physmap {}
+
+static PHYSMAP: Physmap = Physmap {
+    lock: Spinlock::new(),
+    ram: UnsafeCell::new([None; MAX_RAM_REGIONS]),
+    ram_stats: UnsafeCell::new(MapStats { large: 0, small: 0 }),
+    mmio: UnsafeCell::new([None; MAX_MMIO_WINDOWS]),
+};
+
+#[repr(C, align(4096))]
+struct TablePool(UnsafeCell<[[u8; PAGE_SIZE]; TABLE_POOL_SIZE]>);
+
+// Havuz girişleri `TABLES.pool_used` ile yalnızca bir sahibe dağıtılır.
+unsafe impl Sync for TablePool {}
+
+static TABLE_POOL: TablePool = TablePool(UnsafeCell::new([[0; PAGE_SIZE]; TABLE_POOL_SIZE]));
+
+/// Ayrılmış bir sayfa tablosu ve içindeki geçerli giriş sayısı.
+#[derive(Clone, Copy)]
+struct TableSlot {
+    /// Tablonun fiziksel adresi; 0: boş yuva.
+    addr: usize,
+    entries: u16,
+}
+
+struct Tables {
+    lock: Spinlock,
+    slots: UnsafeCell<[TableSlot; MAX_TABLES]>,
+    pool_used: UnsafeCell<[bool; TABLE_POOL_SIZE]>,
+}
+
+// Tüm alanlara erişim `lock` ile korunur.
+unsafe impl Sync for Tables {}
+
+static TABLES: Tables = Tables {
+    lock: Spinlock::new(),
+    slots: UnsafeCell::new([TableSlot { addr: 0, entries: 0 }; MAX_TABLES]),
+    pool_used: UnsafeCell::new([false; TABLE_POOL_SIZE]),
+};
+
+fn with_tables<R>(f: impl FnOnce(&mut [TableSlot; MAX_TABLES], &mut [bool; TABLE_POOL_SIZE]) -> R) -> R {
+    TABLES.lock.lock();
+    // SAFETY: Kilit tutulduğu sürece duruma tek erişen biziz.
+    let result = unsafe { f(&mut *TABLES.slots.get(), &mut *TABLES.pool_used.get()) };
+    TABLES.lock.unlock();
+    result
+}
+
+fn with_physmap<R>(
+    f: impl FnOnce(&mut [Option<Region>; MAX_RAM_REGIONS], &mut MapStats, &mut [Option<MmioWindow>; MAX_MMIO_WINDOWS]) -> R,
+) -> R {
+    PHYSMAP.lock.lock();
+    // SAFETY: Kilit tutulduğu sürece duruma tek erişen biziz.
+    let result = unsafe { f(&mut *PHYSMAP.ram.get(), &mut *PHYSMAP.ram_stats.get(), &mut *PHYSMAP.mmio.get()) };
+    PHYSMAP.lock.unlock();
+    result
+}
+
+// -----------------------------------------------------------------------------
+// SAYFA TABLOLARI
+// -----------------------------------------------------------------------------
+
+/// Havuzdaki tablonun indeksi; adres havuzda değilse `None`.
+fn pool_index(addr: usize) -> Option<usize> {
+    let base = TABLE_POOL.0.get() as usize;
+    (base..base + TABLE_POOL_SIZE * PAGE_SIZE).contains(&addr).then(|| (addr - base) / PAGE_SIZE)
+}
+
+/// Sıfırlanmış, `PAGE_SIZE` hizalı bir sayfa tablosu ayırır ve fiziksel
+/// adresini döndürür; tablo sıfır girişle izlenmeye başlar. Havuz bitince
+/// çerçeve ayırıcısına düşer; o da henüz başlatılmadıysa veya doluysa ya da
+/// izleme tablosu doluysa `None`.
+pub fn alloc_table() -> Option<usize> {
+    let pooled = with_tables(|slots, pool_used| {
+        let slot = slots.iter_mut().find(|slot| slot.addr == 0)?;
+        let index = pool_used.iter().position(|used| !used)?;
+        pool_used[index] = true;
+        // SAFETY: Havuz girişi yalnızca bu çağrıya verildi.
+        slot.addr = unsafe { (*TABLE_POOL.0.get())[index].as_mut_ptr() as usize };
+        slot.entries = 0;
+        Some(slot.addr)
+    });
+    let addr = match pooled {
+        Some(addr) => addr,
+        None => {
+            // Çerçeve ayırıcısının geri kazanım kancası eşleme kaldırabilir
+            // (`free_table`); bu yüzden kilit dışında ayrılır.
+            let addr = crate::memory::frame::alloc().ok()?;
+            let tracked = with_tables(|slots, _| {
+                let slot = slots.iter_mut().find(|slot| slot.addr == 0)?;
+                *slot = TableSlot { addr, entries: 0 };
+                Some(())
+            });
+            if tracked.is_none() {
+                let _ = crate::memory::frame::free(addr, 1);
+                return None;
+            }
+            addr
+        }
+    };
+    // SAFETY: Sayfa bu çağrıya aittir ve çekirdekte erişilebilirdir.
+    unsafe { core::ptr::write_bytes(addr as *mut u8, 0, PAGE_SIZE) };
+    Some(addr)
+}
+
+/// `alloc_table` ile ayrılmış tabloyu geri verir. Çağıran, tabloyu gösteren
+/// üst girişi temizlemiş ve TLB'yi boşaltmış olmalıdır. İzlenmeyen adres
+/// (statik kök tablo) yok sayılır.
+pub fn free_table(addr: usize) {
+    let pooled = with_tables(|slots, pool_used| {
+        let slot = slots.iter_mut().find(|slot| slot.addr == addr)?;
+        slot.addr = 0;
+        match pool_index(addr) {
+            Some(index) => {
+                pool_used[index] = false;
+                Some(true)
+            }
+            None => Some(false),
+        }
+    });
+    if pooled == Some(false) {
+        let _ = crate::memory::frame::free(addr, 1);
+    }
+}
+
+/// Tabloda bir giriş geçerli hale geldi. İzlenmeyen tablolar (kök) için etkisizdir.
+pub fn table_ref(addr: usize) {
+    with_tables(|slots, _| {
+        if let Some(slot) = slots.iter_mut().find(|slot| slot.addr == addr) {
+            slot.entries += 1;
+        }
+    });
+}
+
+/// Tablodaki bir giriş temizlendi; kalan geçerli giriş sayısını döndürür.
+/// İzlenmeyen tablolar (kök) için `None`: bu tablolar hiçbir zaman serbest
+/// bırakılmaz.
+pub fn table_unref(addr: usize) -> Option<usize> {
+    with_tables(|slots, _| {
+        let slot = slots.iter_mut().find(|slot| slot.addr == addr)?;
+        slot.entries = slot.entries.saturating_sub(1);
+        Some(slot.entries as usize)
+    })
+}
+
+/// Ayrılmış sayfa tablosu sayısı.
+pub fn tables_used() -> usize {
+    with_tables(|slots, _| slots.iter().filter(|slot| slot.addr != 0).count())
+}
+
+// -----------------------------------------------------------------------------
+// EŞLEME
+// -----------------------------------------------------------------------------
+
+/// `[paddr, paddr + len)` aralığını `vaddr`'dan başlayarak eşler. Sanal ve
+/// fiziksel adresin ikisi de hizalı olan 2 MiB parçalar `map_large`, kalanlar
+/// `map_small` ile eşlenir. Adres ve uzunluk sayfa hizalı olmalıdır.
+///
+/// İlk eşleme hatasında durur; o ana kadar kurulan eşlemeler geri alınmaz.
+pub fn map_range(
+    vaddr: usize,
+    paddr: usize,
+    len: usize,
+    map_large: &mut dyn FnMut(usize, usize) -> Result<(), MapError>,
+    map_small: &mut dyn FnMut(usize, usize) -> Result<(), MapError>,
+) -> Result<MapStats, MapError> {
+    let mut stats = MapStats::default();
+    let mut offset = 0;
+    while offset < len {
+        let (v, p) = (vaddr + offset, paddr + offset);
+        if v.is_multiple_of(LARGE_PAGE_SIZE) && p.is_multiple_of(LARGE_PAGE_SIZE) && len - offset >= LARGE_PAGE_SIZE {
+            map_large(v, p)?;
+            stats.large += 1;
+            offset += LARGE_PAGE_SIZE;
+        } else {
+            map_small(v, p)?;
+            stats.small += 1;
+            offset += PAGE_SIZE;
+        }
+    }
+    Ok(stats)
+}
+
+/// Önyükleme bilgisindeki kullanılabilir RAM bölgeleri; yoksa `FALLBACK_RAM`.
+fn discover_ram(out: &mut [Option<Region>; MAX_RAM_REGIONS]) {
+    let mut count = 0;
+    for region in crate::boot::info().usable_memory() {
+        let Some(slot) = out.get_mut(count) else {
+            serial_println!("[PHYSMAP] UYARI: {} bölgeden fazlası yok sayıldı", MAX_RAM_REGIONS);
+            return;
+        };
+        *slot = Some(Region { start: region.base as usize, len: region.len as usize });
+        count += 1;
+    }
+    if count == 0 {
+        out[0] = Some(FALLBACK_RAM);
+    }
+}
+
+/// Mimarinin MMU kurulumundan çağrılır: tüm RAM'i `virt = fiziksel + offset`
+/// olacak biçimde eşler (bkz. `map_range`). Bölgeler sayfa sınırına genişletilir.
+/// Bir bölge eşlenemezse hata döner; mimari sayfalamayı etkinleştirmemelidir.
+pub fn map_ram(
+    offset: usize,
+    map_large: &mut dyn FnMut(usize, usize) -> Result<(), MapError>,
+    map_small: &mut dyn FnMut(usize, usize) -> Result<(), MapError>,
+) -> Result<MapStats, MapError> {
+    let mut regions = [None; MAX_RAM_REGIONS];
+    discover_ram(&mut regions);
+
+    let mut total = MapStats::default();
+    for region in regions.iter_mut().flatten() {
+        let start = region.start & !(PAGE_SIZE - 1);
+        let end = region.end().next_multiple_of(PAGE_SIZE);
+        *region = Region { start, len: end - start };
+        let stats = map_range(start + offset, start, end - start, map_large, map_small).inspect_err(|e| {
+            serial_println!("[PHYSMAP] HATA: RAM {:#x}-{:#x} eşlenemedi: {:?}", start, end, e);
+        })?;
+        serial_println!(
+            "[PHYSMAP] RAM {:#x}-{:#x} eşlendi ({} büyük, {} küçük sayfa)",
+            start,
+            end,
+            stats.large,
+            stats.small
+        );
+        total.large += stats.large;
+        total.small += stats.small;
+    }
+    with_physmap(|ram, ram_stats, _| {
+        *ram = regions;
+        *ram_stats = total;
+    });
+    Ok(total)
+}
+
+fn arch_map_device(phys: usize, len: usize) -> Result<usize, KernelError> {
+    crate::arch::mmu::map_device(phys, len)
+}
+
+/// Aygıt bölgesini çekirdek adres alanında aygıt özniteliğiyle eşler ve
+/// `phys`'e karşılık gelen sanal adresi döndürür. Bölge zaten bir pencerenin
+/// içindeyse yeniden eşlenmez; kısmen örtüşen pencereler birleştirilir.
+///
+/// # Hatalar
+/// * `InvalidArgument`: Uzunluk 0, aralık taşıyor veya RAM ile örtüşüyor
+///   (aynı belleğin farklı önbellek öznitelikleriyle eşlenmesi tanımsızdır).
+/// * `OutOfMemoryStatic`: Pencere tablosu dolu.
+/// * `Map(NotReady)`: MMU henüz kurulmadı.
+/// * `PlatformSpecificError`: Mimaride desteklenmiyor.
+pub fn map_mmio(phys: usize, len: usize) -> Result<usize, KernelError> {
+    let end = phys.checked_add(len).filter(|_| len != 0).ok_or(KernelError::InvalidArgument)?;
+    let mut start = phys & !(PAGE_SIZE - 1);
+    let mut end = end.checked_next_multiple_of(PAGE_SIZE).ok_or(KernelError::InvalidArgument)?;
+
+    with_physmap(|ram, _, mmio| {
+        if ram.iter().flatten().any(|r| r.overlaps(start, end)) {
+            return Err(KernelError::InvalidArgument);
+        }
+        if let Some(window) = mmio.iter().flatten().find(|w| w.phys <= start && end <= w.phys + w.len) {
+            return Ok(window.virt + (phys - window.phys));
+        }
+
+        // Örtüşen (veya bitişik) pencereler tek pencerede birleştirilir
+        for window in mmio.iter().flatten().filter(|w| w.phys <= end && start <= w.phys + w.len) {
+            start = start.min(window.phys);
+            end = end.max(window.phys + window.len);
+        }
+        let free = mmio.iter().filter(|w| w.is_none()).count();
+        let merged = mmio.iter().flatten().filter(|w| w.phys <= end && start <= w.phys + w.len).count();
+        if free + merged == 0 {
+            return Err(KernelError::OutOfMemoryStatic);
+        }
+
+        let virt = arch_map_device(start, end - start)?;
+        for slot in mmio.iter_mut().filter(|w| w.is_some_and(|w| w.phys <= end && start <= w.phys + w.len)) {
+            *slot = None;
+        }
+        let slot = mmio.iter_mut().find(|w| w.is_none()).ok_or(KernelError::OutOfMemoryStatic)?;
+        *slot = Some(MmioWindow { phys: start, virt, len: end - start });
+        Ok(virt + (phys - start))
+    })
+}
+
+/// Fiziksel adres `map_ram` ile eşlenmiş RAM'de mi?
+pub fn is_ram(phys: usize) -> bool {
+    with_physmap(|ram, _, _| ram.iter().flatten().any(|r| r.overlaps(phys, phys + 1)))
+}
+
+// -----------------------------------------------------------------------------
+// KABUK KOMUTU
+// -----------------------------------------------------------------------------
+
+fn physmap_command(_args: &[&str]) -> Result<(), KernelError> {
+    let (ram, ram_stats, mmio) = with_physmap(|ram, stats, mmio| (*ram, *stats, *mmio));
+    serial_println!("  TÜR   FİZİKSEL           SANAL              BOYUT");
+    for region in ram.iter().flatten() {
+        serial_println!("  ram   {:#018x} {:>18} {:>8} KiB", region.start, "-", region.len / 1024);
+    }
+    for window in mmio.iter().flatten() {
+        serial_println!("  mmio  {:#018x} {:#018x} {:>8} KiB", window.phys, window.virt, window.len / 1024);
+    }
+    serial_println!(
+        "  RAM: {} büyük, {} küçük sayfa; sayfa tablosu: {} (havuz {})",
+        ram_stats.large,
+        ram_stats.small,
+        tables_used(),
+        TABLE_POOL_SIZE
+    );
+    Ok(())
+}
+
+crate::shell_command!("physmap", "RAM eşlemesini ve aygıt pencerelerini gösterir", physmap_command);
@@ -1,5 +1,21 @@
 #![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
 
+use crate::memory::vmaudit::MapError;
+
+// -----------------------------------------------------------------------------
+// HATA TÜRLERİ
+// -----------------------------------------------------------------------------
+//
+// Hatalar iki katmanlıdır: genel varyantlar (`InvalidArgument`, `NotFound` ...)
+// her modülün kullandığı sınıflardır; alt sistemler kendi hata türlerini
+// (`MapError`, `DeviceError`) `From` ile `KernelError` içine sarar, böylece `?`
+// ile yukarı taşınırken ayrıntı kaybolmaz. Sınıfa göre karar veren çağıranlar
+// (errno çevirisi, yeniden deneme) `general` ile genel varyanta iner.
+//
+// `panic!` yalnızca bozulmuş değişmezler içindir (kesme yığını taşması,
+// çekirdek kipinde düzeltmesi olmayan tuzak, KASAN); donanımın veya
+// çağıranın yol açabileceği her durum `Result` ile döner.
+
 /// İşletim Sistemi Seviyesi Hata Kodları için genel bir Enum.
 ///
 /// Statik tabanlı sistemler için önemli olan deterministik hata yönetimi sağlar.
@@ -17,8 +33,60 @@ pub enum KernelError {
     OutOfMemoryStatic,
     /// Donanım veya mimariye özgü bir hata oluştu.
     PlatformSpecificError(u32),
+    /// Bloklayan işlem bir bekleme döngüsünü kapatırdı (kilitlenme).
+    Deadlock,
+    /// Kullanıcı adresi görevin adres alanında değil veya erişilemedi (bkz. `uaccess`).
+    BadAddress,
     /// Genel, tanımlanmamış hata.
     GenericFailure,
+    /// Sayfa tablosu veya TLB eşleme hatası (bkz. `vmaudit`).
+    Map(MapError),
+    /// Aygıt veya ürün yazılımı hatası.
+    Device(DeviceError),
+}
+
+impl KernelError {
+    /// Alt sistem hatasını genel sınıfına indirger; genel varyantlar aynen döner.
+    pub fn general(self) -> KernelError {
+        match self {
+            KernelError::Map(error) => match error {
+                MapError::Unaligned | MapError::OutOfRange | MapError::InvalidFlags => KernelError::InvalidArgument,
+                MapError::AlreadyMapped => KernelError::ResourceBusy,
+                MapError::NotMapped => KernelError::NotFound,
+                MapError::OutOfMemory => KernelError::OutOfMemoryStatic,
+                MapError::NotReady => KernelError::PlatformSpecificError(0),
+            },
+            KernelError::Device(error) => match error {
+                DeviceError::NotPresent => KernelError::NotFound,
+                DeviceError::Timeout => KernelError::ResourceBusy,
+                DeviceError::Unsupported | DeviceError::Rejected => KernelError::PlatformSpecificError(0),
+                DeviceError::Status(status) => KernelError::PlatformSpecificError(status),
+            },
+            other => other,
+        }
+    }
+}
+
+/// Aygıt sürücülerinin ve ürün yazılımı arayüzlerinin hata türü.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceError {
+    /// Aygıt yok veya henüz bağlanmadı.
+    NotPresent,
+    /// Aygıt istenen arayüzü sunmuyor (örn. virtio modern arayüzü) ya da
+    /// ürün yazılımı servisi tanımıyor.
+    Unsupported,
+    /// Aygıt sürücünün yapılandırmasını reddetti (örn. virtio özellik kümesi).
+    Rejected,
+    /// Aygıt süresinde yanıt vermedi.
+    Timeout,
+    /// Aygıt hata bildirdi; değer aygıtın ham durum yazmacıdır.
+    Status(u32),
+}
+
+impl From<DeviceError> for KernelError {
+    fn from(error: DeviceError) -> Self {
+        KernelError::Device(error)
+    }
 }
 
 /// Tüm sistem sabitlerini içeren bir yapı.
@@ -30,7 +98,8 @@ impl SystemConstants {
     pub const MAX_TASKS: usize = 32;
 
     /// Temel zamanlayıcı tik periyodu (saniyenin kaçta biri).
-    /// Sert Gerçek Zamanlı sistemler için kritik bir ayardır.
+    /// Sert Gerçek Zamanlı sistemler için kritik bir ayardır. Açılış
+    /// varsayılanıdır; çalışma zamanında `sched.tick_hz` ile değişir.
     pub const TIMER_TICK_HZ: u64 = 1000; // 1000 Hz = 1 ms periyot
     
     /// IPC mesaj kuyruğu için varsayılan derinlik.
@@ -101,6 +170,14 @@ pub mod spinlock {
             }
         }
 
+        /// Kilidi beklemeden almayı dener; kilit zaten tutuluyorsa `false`.
+        ///
+        /// Aynı kilidi tutan bir yoldan geri çağrılabilecek kodda (örn. bellek
+        /// geri kazanımı) kendi kendine kilitlenmeyi önlemek için kullanılır.
+        pub fn try_lock(&self) -> bool {
+            self.locked.compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed).is_ok()
+        }
+
         /// Kilidi serbest bırakır.
         ///
         /// # Güvenlik Notu
@@ -109,4 +186,48 @@ pub mod spinlock {
             self.locked.store(false, Ordering::Release);
         }
     }
+
+    /// Yerel kesmeler kapalıyken tutulan kilit; kesme işleyicisiyle
+    /// paylaşılan veriler içindir. Kesme kapalı süre `irqoff` ile ölçülür
+    /// (çağrı yeri `lock`'u çağıran yerdir).
+    pub struct IrqSafeSpinlock {
+        lock: Spinlock,
+    }
+
+    impl IrqSafeSpinlock {
+        pub const fn new() -> Self {
+            IrqSafeSpinlock { lock: Spinlock::new() }
+        }
+
+        /// Kesmeleri kapatıp kilidi alır; önceki kesme durumunu döndürür.
+        #[inline]
+        #[track_caller]
+        pub fn lock(&self) -> usize {
+            let flags = crate::irqoff::save();
+            self.lock.lock();
+            flags
+        }
+
+        /// Kesmeleri kapatıp kilidi beklemeden almayı dener; kilit tutuluyorsa
+        /// kesme durumu geri yüklenir ve `None` döner (panik yolları için).
+        #[inline]
+        #[track_caller]
+        pub fn try_lock(&self) -> Option<usize> {
+            let flags = crate::irqoff::save();
+            if self.lock.try_lock() {
+                Some(flags)
+            } else {
+                crate::irqoff::restore(flags);
+                None
+            }
+        }
+
+        /// Kilidi bırakır ve `lock`'un döndürdüğü kesme durumunu geri yükler.
+        #[inline]
+        #[track_caller]
+        pub fn unlock(&self, flags: usize) {
+            self.lock.unlock();
+            crate::irqoff::restore(flags);
+        }
+    }
 }
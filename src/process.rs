@@ -0,0 +1,416 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/process.rs
+// Görev grupları: hafif süreç (process) modeli.
+//
+// Bir grup, birlikte yaşayıp birlikte ölen görevleri toplar (örn. yüklenen
+// bir ELF ve iş parçacıkları). Grup tek birim olarak sinyallenir ve
+// sonlandırılır:
+//
+// * Sinyaller grubun tüm üyelerine bekleyen bit olarak bırakılır; bloklu
+//   üyeler uyandırılır, görevler `take_signals` ile okur. `Kill` ise
+//   doğrudan sonlandırmadır.
+// * Grubun sahip olduğu kaynaklar (açık tanıtıcılar, adres alanı vb.)
+//   `attach_resource` ile bırakma işlevleriyle kaydedilir ve sonlandırmada
+//   kayıt sırasının tersine bırakılır.
+// * Sonlanan grup, ebeveyni `wait` ile çıkış kodunu alana kadar zombi
+//   kalır; ebeveyni yoksa hemen toplanır. Canlı çocuk gruplar ebeveynin
+//   ebeveynine aktarılır, zombi çocuklar toplanır.
+// * Son üyesi `sched::exit` ile çıkan grup 0 koduyla sonlanır.
+//
+// Kabuk komutları: `groups`, `gkill <grup> [sinyal]`; procfs: `/proc/groups`
+
+use core::cell::UnsafeCell;
+use crate::platformgeneric::spinlock::Spinlock;
+use crate::platformgeneric::KernelError;
+use crate::sched::{self, TaskId, MAX_TASKS};
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// SABİTLER VE TİPLER
+// -----------------------------------------------------------------------------
+
+/// Grup tablosunun boyutu.
+pub const MAX_GROUPS: usize = 16;
+/// Grup başına kaydedilebilecek en fazla kaynak.
+pub const MAX_RESOURCES: usize = 8;
+
+// Üyelik bir `u64` bit maskesinde tutulur
+const _: () = assert!(MAX_TASKS <= 64);
+
+/// Grup tablosundaki indeks.
+pub type GroupId = usize;
+
+/// Grup sinyalleri. Değer, bekleyen sinyal maskesindeki bit numarasıdır.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Signal {
+    /// Kesme isteği (kabuktan Ctrl-C benzeri).
+    Interrupt = 2,
+    /// Koşulsuz sonlandırma; bekleyen bit bırakılmaz, grup hemen yıkılır.
+    Kill = 9,
+    User1 = 10,
+    User2 = 12,
+    /// Düzenli sonlanma isteği; görev temizlik yapıp çıkmalıdır.
+    Terminate = 15,
+}
+
+impl Signal {
+    pub fn from_number(n: u8) -> Option<Signal> {
+        match n {
+            2 => Some(Signal::Interrupt),
+            9 => Some(Signal::Kill),
+            10 => Some(Signal::User1),
+            12 => Some(Signal::User2),
+            15 => Some(Signal::Terminate),
+            _ => None,
+        }
+    }
+
+    pub const fn mask(self) -> u32 {
+        1 << self as u8
+    }
+}
+
+/// Grup kaynağını bırakan işlev; argüman kayıt sırasında verilir.
+pub type ReleaseFn = fn(arg: usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupState {
+    Free,
+    Alive,
+    /// Sonlandı; ebeveyn çıkış kodunu henüz almadı.
+    Zombie,
+}
+
+#[derive(Clone, Copy)]
+struct Group {
+    name: &'static str,
+    state: GroupState,
+    parent: Option<GroupId>,
+    members: u64,
+    exit_code: i32,
+    resources: [Option<(ReleaseFn, usize)>; MAX_RESOURCES],
+}
+
+impl Group {
+    const fn empty() -> Self {
+        Group { name: "", state: GroupState::Free, parent: None, members: 0, exit_code: 0, resources: [None; MAX_RESOURCES] }
+    }
+}
+
+/// Dışarıya verilen salt okunur grup bilgisi.
+#[derive(Debug, Clone, Copy)]
+pub struct GroupInfo {
+    pub name: &'static str,
+    pub state: GroupState,
+    pub parent: Option<GroupId>,
+    pub members: u64,
+    pub exit_code: i32,
+}
+
+struct GroupTable {
+    lock: Spinlock,
+    groups: UnsafeCell<[Group; MAX_GROUPS]>,
+    /// Görev başına bekleyen sinyal maskesi.
+    pending: UnsafeCell<[u32; MAX_TASKS]>,
+}
+
+// Tabloya erişim `lock` ile korunur.
+unsafe impl Sync for GroupTable {}
+
+static GROUPS: GroupTable = GroupTable {
+    lock: Spinlock::new(),
+    groups: UnsafeCell::new([Group::empty(); MAX_GROUPS]),
+    pending: UnsafeCell::new([0; MAX_TASKS]),
+};
+
+crate::kernel_counter!(GROUPS_CREATED, "process.created");
+crate::kernel_counter!(GROUPS_KILLED, "process.killed");
+
+/// Kilidi tutarak grup tablosu üzerinde `f`'yi çalıştırır. Kilit tutulurken
+/// zamanlayıcı çağrılmaz (`sched::exit` bu modüle geri döner).
+fn with_groups<R>(f: impl FnOnce(&mut [Group; MAX_GROUPS], &mut [u32; MAX_TASKS]) -> R) -> R {
+    GROUPS.lock.lock();
+    // SAFETY: Kilit tutulduğu sürece tabloya tek erişen biziz.
+    let result = unsafe { f(&mut *GROUPS.groups.get(), &mut *GROUPS.pending.get()) };
+    GROUPS.lock.unlock();
+    result
+}
+
+fn alive(groups: &[Group; MAX_GROUPS], id: GroupId) -> Result<(), KernelError> {
+    match groups.get(id) {
+        Some(g) if g.state == GroupState::Alive => Ok(()),
+        _ => Err(KernelError::NotFound),
+    }
+}
+
+fn members_of(mask: u64) -> impl Iterator<Item = TaskId> {
+    (0..MAX_TASKS).filter(move |t| mask & (1 << t) != 0)
+}
+
+// -----------------------------------------------------------------------------
+// OLUŞTURMA VE ÜYELİK
+// -----------------------------------------------------------------------------
+
+/// Boş bir grup oluşturur.
+///
+/// # Hatalar
+/// * `NotFound`: Ebeveyn grup yaşamıyor.
+/// * `OutOfMemoryStatic`: Grup tablosu dolu.
+pub fn create(name: &'static str, parent: Option<GroupId>) -> Result<GroupId, KernelError> {
+    with_groups(|groups, _| {
+        if let Some(p) = parent {
+            alive(groups, p)?;
+        }
+        let id = groups.iter().position(|g| g.state == GroupState::Free).ok_or(KernelError::OutOfMemoryStatic)?;
+        groups[id] = Group { name, state: GroupState::Alive, parent, ..Group::empty() };
+        GROUPS_CREATED.inc();
+        Ok(id)
+    })
+}
+
+/// Grupta yeni bir görev oluşturur (bkz. `sched::spawn`).
+pub fn spawn(group: GroupId, name: &'static str, entry: usize, priority: u8) -> Result<TaskId, KernelError> {
+    with_groups(|groups, _| alive(groups, group))?;
+    let task = sched::spawn(name, entry, priority)?;
+    if let Err(e) = join(group, task) {
+        // Grup arada yıkıldıysa sahipsiz görev bırakılmaz
+        sched::exit(task).ok();
+        return Err(e);
+    }
+    Ok(task)
+}
+
+/// Var olan görevi gruba ekler (önceki grubundan çıkarılır).
+pub fn join(group: GroupId, task: TaskId) -> Result<(), KernelError> {
+    if task >= MAX_TASKS {
+        return Err(KernelError::InvalidArgument);
+    }
+    with_groups(|groups, pending| {
+        alive(groups, group)?;
+        groups.iter_mut().for_each(|g| g.members &= !(1 << task));
+        groups[group].members |= 1 << task;
+        pending[task] = 0;
+        Ok(())
+    })
+}
+
+/// Görevin grubu.
+pub fn group_of(task: TaskId) -> Option<GroupId> {
+    if task >= MAX_TASKS {
+        return None;
+    }
+    with_groups(|groups, _| groups.iter().position(|g| g.state == GroupState::Alive && g.members & (1 << task) != 0))
+}
+
+/// Gruba, sonlandırmada bırakılacak bir kaynak (tanıtıcı, adres alanı vb.)
+/// kaydeder.
+pub fn attach_resource(group: GroupId, release: ReleaseFn, arg: usize) -> Result<(), KernelError> {
+    with_groups(|groups, _| {
+        alive(groups, group)?;
+        let slot = groups[group].resources.iter_mut().find(|r| r.is_none()).ok_or(KernelError::OutOfMemoryStatic)?;
+        *slot = Some((release, arg));
+        Ok(())
+    })
+}
+
+/// Kaydı, kaynağı bırakmadan siler (kaynak grup ölmeden kapatıldığında).
+pub fn detach_resource(group: GroupId, release: ReleaseFn, arg: usize) -> Result<(), KernelError> {
+    with_groups(|groups, _| {
+        alive(groups, group)?;
+        let slot = groups[group]
+            .resources
+            .iter_mut()
+            .find(|r| r.is_some_and(|(f, a)| f as usize == release as usize && a == arg))
+            .ok_or(KernelError::NotFound)?;
+        *slot = None;
+        Ok(())
+    })
+}
+
+// -----------------------------------------------------------------------------
+// SİNYALLER
+// -----------------------------------------------------------------------------
+
+/// Grubun tüm üyelerine sinyal gönderir. `Kill` grubu hemen yıkar.
+pub fn signal(group: GroupId, sig: Signal) -> Result<(), KernelError> {
+    if sig == Signal::Kill {
+        return kill(group, -(Signal::Kill as i32));
+    }
+    let members = with_groups(|groups, pending| {
+        alive(groups, group)?;
+        let members = groups[group].members;
+        for task in members_of(members) {
+            pending[task] |= sig.mask();
+        }
+        Ok(members)
+    })?;
+    // Bloklu üyeler sinyali görebilsin diye uyandırılır
+    for task in members_of(members) {
+        let _ = sched::wake(task);
+    }
+    Ok(())
+}
+
+/// Çalışan görevin bekleyen sinyallerini alır ve temizler.
+pub fn take_signals() -> u32 {
+    let Some(me) = sched::current() else { return 0 };
+    with_groups(|_, pending| core::mem::take(&mut pending[me]))
+}
+
+// -----------------------------------------------------------------------------
+// SONLANDIRMA
+// -----------------------------------------------------------------------------
+
+/// Grubu tek birim olarak sonlandırır: üyeler çıkarılır, kaynaklar
+/// bırakılır, çocuklar aktarılır veya toplanır.
+pub fn kill(group: GroupId, exit_code: i32) -> Result<(), KernelError> {
+    // Tablo kilit altında güncellenir; görev sonlandırma ve kaynak bırakma
+    // kilit bırakıldıktan sonra yapılır
+    let (members, resources) = with_groups(|groups, pending| {
+        alive(groups, group)?;
+        let parent = groups[group].parent;
+        let has_parent = parent.is_some_and(|p| groups[p].state == GroupState::Alive);
+        let g = &mut groups[group];
+        let members = core::mem::take(&mut g.members);
+        let resources = core::mem::replace(&mut g.resources, [None; MAX_RESOURCES]);
+        g.exit_code = exit_code;
+        g.state = if has_parent { GroupState::Zombie } else { GroupState::Free };
+        for task in members_of(members) {
+            pending[task] = 0;
+        }
+
+        for child in groups.iter_mut().filter(|c| c.parent == Some(group)) {
+            match child.state {
+                GroupState::Alive => child.parent = parent,
+                GroupState::Zombie => *child = Group::empty(),
+                GroupState::Free => {}
+            }
+        }
+        Ok((members, resources))
+    })?;
+
+    // Çalışan görev kendi grubunu öldürüyorsa en son çıkarılır
+    let me = sched::current();
+    for task in members_of(members).filter(|&t| Some(t) != me) {
+        sched::exit(task).ok();
+    }
+    for (release, arg) in resources.iter().rev().flatten() {
+        release(*arg);
+    }
+    GROUPS_KILLED.inc();
+    serial_println!("[PROC] Grup {} sonlandı (kod {})", group, exit_code);
+    if let Some(me) = me.filter(|&t| members & (1 << t) != 0) {
+        sched::exit(me).ok();
+    }
+    Ok(())
+}
+
+/// `sched::exit` tarafından çağrılır: görevi grubundan çıkarır; son üye
+/// çıktıysa grup 0 koduyla sonlanır.
+pub fn task_exited(task: TaskId) {
+    if task >= MAX_TASKS {
+        return;
+    }
+    let emptied = with_groups(|groups, pending| {
+        pending[task] = 0;
+        let id = groups.iter().position(|g| g.state == GroupState::Alive && g.members & (1 << task) != 0)?;
+        groups[id].members &= !(1 << task);
+        (groups[id].members == 0).then_some(id)
+    });
+    if let Some(group) = emptied {
+        kill(group, 0).ok();
+    }
+}
+
+/// Zombi çocuk grubun çıkış kodunu alır ve girişi serbest bırakır.
+///
+/// # Hatalar
+/// * `ResourceBusy`: Grup hâlâ yaşıyor.
+/// * `NotFound`: Grup yok veya `parent`'ın çocuğu değil.
+pub fn wait(parent: GroupId, child: GroupId) -> Result<i32, KernelError> {
+    with_groups(|groups, _| {
+        let g = groups.get_mut(child).ok_or(KernelError::NotFound)?;
+        if g.parent != Some(parent) {
+            return Err(KernelError::NotFound);
+        }
+        match g.state {
+            GroupState::Alive => Err(KernelError::ResourceBusy),
+            GroupState::Zombie => {
+                let code = g.exit_code;
+                *g = Group::empty();
+                Ok(code)
+            }
+            GroupState::Free => Err(KernelError::NotFound),
+        }
+    })
+}
+
+/// Grup hakkında salt okunur bilgi.
+pub fn group_info(id: GroupId) -> Option<GroupInfo> {
+    with_groups(|groups, _| {
+        groups.get(id).filter(|g| g.state != GroupState::Free).map(|g| GroupInfo {
+            name: g.name,
+            state: g.state,
+            parent: g.parent,
+            members: g.members,
+            exit_code: g.exit_code,
+        })
+    })
+}
+
+// -----------------------------------------------------------------------------
+// KABUK VE PROCFS
+// -----------------------------------------------------------------------------
+
+fn groups_command(_args: &[&str]) -> Result<(), KernelError> {
+    serial_println!("  ID AD               DURUM   EBEVEYN  ÜYELER");
+    for id in 0..MAX_GROUPS {
+        let Some(info) = group_info(id) else { continue };
+        crate::serial_print!(
+            "  {:>2} {:<16} {:<7} {:>7} ",
+            id,
+            info.name,
+            match info.state {
+                GroupState::Alive => "canlı",
+                GroupState::Zombie => "zombi",
+                GroupState::Free => "-",
+            },
+            info.parent.map_or(-1, |p| p as i64)
+        );
+        for task in members_of(info.members) {
+            crate::serial_print!(" {}", task);
+        }
+        serial_println!();
+    }
+    Ok(())
+}
+
+crate::shell_command!("groups", "Görev gruplarını listeler", groups_command);
+
+/// `gkill <grup> [sinyal]` (varsayılan 15)
+fn gkill_command(args: &[&str]) -> Result<(), KernelError> {
+    let group = args.get(1).and_then(|s| s.parse().ok()).ok_or(KernelError::InvalidArgument)?;
+    let number = args.get(2).map_or(Some(Signal::Terminate as u8), |s| s.parse().ok());
+    let sig = number.and_then(Signal::from_number).ok_or(KernelError::InvalidArgument)?;
+    signal(group, sig)
+}
+
+crate::shell_command!("gkill", "Gruba sinyal gönderir (gkill <grup> [2|9|10|12|15])", gkill_command);
+
+/// Satır başına grup: `<id> <ad> <durum> <ebeveyn|-> <üye maskesi> <kod>`.
+fn show_groups(out: &mut dyn core::fmt::Write) -> core::fmt::Result {
+    for id in 0..MAX_GROUPS {
+        let Some(info) = group_info(id) else { continue };
+        write!(out, "{} {} {:?} ", id, info.name, info.state)?;
+        match info.parent {
+            Some(p) => write!(out, "{}", p)?,
+            None => write!(out, "-")?,
+        }
+        writeln!(out, " {:#x} {}", info.members, info.exit_code)?;
+    }
+    Ok(())
+}
+
+crate::proc_entry!("groups", show_groups);
@@ -0,0 +1,323 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/procfs.rs
+// Çekirdek iç durumunu metin dosyaları olarak sunan sentetik dosya sistemi (`/proc`).
+//
+// Dosyaların diskte karşılığı yoktur: her okumada ilgili üreteç fonksiyonu
+// çalıştırılır ve çıktının istenen `offset` penceresi çağıranın arabelleğine
+// kopyalanır. Bu yüzden parça parça okunan bir dosya, okumalar arasında değişen
+// sayaçlar nedeniyle tutarsız görünebilir; tek seferde okumak için yeterli
+// büyüklükte arabellek kullanılmalıdır.
+//
+// Alt sistemler ve sürücüler kendi dosyalarını `proc_entry!` ile `.proc_entries`
+// linker bölümüne kaydeder (sürücüler `drivers/<ad>` yolunu kullanır). Yoldaki
+// `/` ayırıcıları dizin olarak gösterilir.
+//
+// `sys/` dizini `sysctl` parametrelerinden türetilir: her parametre
+// `/proc/sys/<ad>` dosyasıdır ve bu dosyalara yazmak parametreyi ayarlar.
+//
+// Linker betiği: __proc_entries_start = .; KEEP(*(.proc_entries)) __proc_entries_end = .;
+
+use core::fmt::{self, Write};
+use crate::memory::memory::{MemoryRegions, TaskStackAllocator};
+use crate::memory::{frame, heap};
+use crate::platformgeneric::KernelError;
+use crate::sched::{self, SchedClass, TaskState, MAX_TASKS};
+use crate::stats;
+use crate::sysctl::{self, Sysctl};
+use crate::vfs::{DirEntry, FileSystem, FileType, Stat};
+
+// -----------------------------------------------------------------------------
+// GİRDİ KAYDI
+// -----------------------------------------------------------------------------
+
+/// `/proc` altındaki tek bir dosya.
+#[repr(C)]
+pub struct ProcEntry {
+    /// `/proc`'a göreli yol (örn. "tasks", "drivers/uart").
+    pub path: &'static str,
+    /// Dosya içeriğini üreten fonksiyon.
+    pub show: fn(out: &mut dyn Write) -> fmt::Result,
+}
+
+/// Bir procfs dosyası kaydeder.
+///
+/// ```ignore
+/// fn uart_status(out: &mut dyn core::fmt::Write) -> core::fmt::Result {
+///     writeln!(out, "baud: 115200")
+/// }
+/// crate::proc_entry!("drivers/uart", uart_status);
+/// ```
+#[macro_export]
+macro_rules! proc_entry {
+    ($path:expr, $show:path) => {
+        const _: () = {
+            #[used]
+            #[link_section = ".proc_entries"]
+            static __PROC_ENTRY: $crate::procfs::ProcEntry = $crate::procfs::ProcEntry {
+                path: $path,
+                show: $show,
+            };
+        };
+    };
+}
+
+extern "C" {
+    static __proc_entries_start: ProcEntry;
+    static __proc_entries_end: ProcEntry;
+}
+
+/// Kayıtlı tüm procfs dosyaları.
+pub fn entries() -> &'static [ProcEntry] {
+    // SAFETY: Semboller linker betiği tarafından `.proc_entries` bölümünün iki ucuna yerleştirilir.
+    unsafe {
+        let start = &__proc_entries_start as *const ProcEntry;
+        let end = &__proc_entries_end as *const ProcEntry;
+        let count = (end as usize - start as usize) / core::mem::size_of::<ProcEntry>();
+        core::slice::from_raw_parts(start, count)
+    }
+}
+
+fn find(path: &str) -> Option<&'static ProcEntry> {
+    entries().iter().find(|e| e.path == path)
+}
+
+/// `sysctl` parametrelerinin gösterildiği dizin.
+const SYS_DIR: &str = "sys";
+
+fn find_tunable(path: &str) -> Option<&'static Sysctl> {
+    sysctl::find(path.strip_prefix(SYS_DIR)?.strip_prefix('/')?)
+}
+
+/// Dosyanın içeriğini üretir: kayıtlı üreteç veya parametre değeri.
+fn generate(path: &str, out: &mut dyn Write) -> Option<fmt::Result> {
+    if let Some(entry) = find(path) {
+        return Some((entry.show)(out));
+    }
+    let tunable = find_tunable(path)?;
+    Some(tunable.value.show(out).and_then(|_| writeln!(out)))
+}
+
+// -----------------------------------------------------------------------------
+// ÇIKTI PENCERELEME
+// -----------------------------------------------------------------------------
+
+/// Üretilen metnin yalnızca `[skip, skip + buf.len())` aralığını arabelleğe yazar.
+/// Arabellek dolduğunda hata döndürerek üretimi erken keser.
+struct WindowWriter<'a> {
+    skip: usize,
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl Write for WindowWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut bytes = s.as_bytes();
+        let skipped = self.skip.min(bytes.len());
+        self.skip -= skipped;
+        bytes = &bytes[skipped..];
+
+        let room = self.buf.len() - self.len;
+        let n = room.min(bytes.len());
+        self.buf[self.len..self.len + n].copy_from_slice(&bytes[..n]);
+        self.len += n;
+
+        if n < bytes.len() { Err(fmt::Error) } else { Ok(()) }
+    }
+}
+
+/// Yalnızca üretilen metnin uzunluğunu sayar (`stat` boyutu için).
+struct CountingWriter(usize);
+
+impl Write for CountingWriter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.len();
+        Ok(())
+    }
+}
+
+// -----------------------------------------------------------------------------
+// DOSYA SİSTEMİ
+// -----------------------------------------------------------------------------
+
+pub struct ProcFs;
+
+/// `/proc` örneği.
+pub static PROCFS: ProcFs = ProcFs;
+
+/// `dir` dizininin doğrudan çocuklarını (tekrarsız) sırayla döndürür.
+fn children(dir: &str) -> impl Iterator<Item = DirEntry> + '_ {
+    let all = entries();
+    let files = all.iter().enumerate().filter_map(move |(i, entry)| {
+        let child = child_of(dir, entry.path)?;
+        // Aynı alt dizin birden fazla girdiden türeyebilir; yalnızca ilki gösterilir
+        if all[..i].iter().any(|e| child_of(dir, e.path).is_some_and(|c| c.name == child.name)) {
+            return None;
+        }
+        Some(child)
+    });
+
+    let tunables = sysctl::tunables();
+    let sys_dir = (dir.is_empty() && !tunables.is_empty())
+        .then_some(DirEntry { name: SYS_DIR, kind: FileType::Directory });
+    let sys_files = tunables
+        .iter()
+        .filter(move |_| dir == SYS_DIR)
+        .map(|t| DirEntry { name: t.name, kind: FileType::File });
+    files.chain(sys_dir).chain(sys_files)
+}
+
+/// `path` `dir` altındaysa, `dir`'in doğrudan çocuğu olan bileşeni döndürür.
+fn child_of(dir: &str, path: &'static str) -> Option<DirEntry> {
+    let rest = if dir.is_empty() {
+        path
+    } else {
+        path.strip_prefix(dir)?.strip_prefix('/')?
+    };
+    Some(match rest.find('/') {
+        Some(slash) => DirEntry { name: &rest[..slash], kind: FileType::Directory },
+        None => DirEntry { name: rest, kind: FileType::File },
+    })
+}
+
+impl FileSystem for ProcFs {
+    fn name(&self) -> &'static str {
+        "procfs"
+    }
+
+    fn stat(&self, path: &str) -> Result<Stat, KernelError> {
+        let mut counter = CountingWriter(0);
+        if let Some(result) = generate(path, &mut counter) {
+            result.map_err(|_| KernelError::GenericFailure)?;
+            return Ok(Stat { kind: FileType::File, size: counter.0 });
+        }
+        if path.is_empty() || children(path).next().is_some() {
+            return Ok(Stat { kind: FileType::Directory, size: 0 });
+        }
+        Err(KernelError::NotFound)
+    }
+
+    fn read(&self, path: &str, offset: usize, buf: &mut [u8]) -> Result<usize, KernelError> {
+        let mut window = WindowWriter { skip: offset, buf, len: 0 };
+        // Arabellek dolduğunda üreteç `fmt::Error` ile kesilir; bu beklenen durumdur
+        let _ = generate(path, &mut window).ok_or(KernelError::NotFound)?;
+        Ok(window.len)
+    }
+
+    /// Yalnızca `sys/` altındaki parametre dosyaları yazılabilir; yazılan
+    /// metnin tamamı yeni değer olarak ayrıştırılır.
+    fn write(&self, path: &str, offset: usize, data: &[u8]) -> Result<usize, KernelError> {
+        let tunable = find_tunable(path).ok_or(KernelError::InvalidArgument)?;
+        if offset != 0 {
+            return Err(KernelError::InvalidArgument);
+        }
+        let text = core::str::from_utf8(data).map_err(|_| KernelError::InvalidArgument)?;
+        tunable.value.set_str(text.trim())?;
+        Ok(data.len())
+    }
+
+    fn readdir(&self, path: &str, index: usize) -> Result<Option<DirEntry>, KernelError> {
+        if find(path).is_some() || find_tunable(path).is_some() {
+            return Err(KernelError::InvalidArgument);
+        }
+        let mut iter = children(path).peekable();
+        if !path.is_empty() && iter.peek().is_none() {
+            return Err(KernelError::NotFound);
+        }
+        Ok(iter.nth(index))
+    }
+}
+
+fn mount_procfs() -> Result<(), KernelError> {
+    crate::vfs::mount("/proc", &PROCFS)
+}
+
+crate::initcall!(fs, "procfs", mount_procfs);
+
+// -----------------------------------------------------------------------------
+// ÇEKİRDEK DOSYALARI
+// -----------------------------------------------------------------------------
+
+fn show_tasks(out: &mut dyn Write) -> fmt::Result {
+    writeln!(out, "id name state class prio runtime_ticks switches")?;
+    for id in 0..MAX_TASKS {
+        let Some(info) = sched::task_info(id) else { continue };
+        let state = match info.state {
+            TaskState::Ready => "ready",
+            TaskState::Running => "running",
+            TaskState::Blocked => "blocked",
+            TaskState::Exited => "exited",
+            TaskState::Free => continue,
+        };
+        let class = match info.class {
+            SchedClass::Normal => "normal",
+            SchedClass::RealTime => "rt",
+            SchedClass::Idle => "idle",
+        };
+        writeln!(
+            out,
+            "{} {} {} {} {} {} {}",
+            id, info.name, state, class, info.priority, info.runtime_ticks, info.switches
+        )?;
+    }
+    Ok(())
+}
+
+fn show_interrupts(out: &mut dyn Write) -> fmt::Result {
+    for irq in 0..stats::MAX_IRQS {
+        let count = stats::irq_count(irq);
+        if count > 0 {
+            writeln!(out, "{:>4}: {}", irq, count)?;
+        }
+    }
+    Ok(())
+}
+
+fn show_counters(out: &mut dyn Write) -> fmt::Result {
+    for counter in stats::counters() {
+        writeln!(out, "{} {}", counter.name, counter.get())?;
+    }
+    Ok(())
+}
+
+fn show_meminfo(out: &mut dyn Write) -> fmt::Result {
+    let heap = heap::stats();
+    writeln!(out, "HeapTotal:        {} B", heap.total)?;
+    writeln!(out, "HeapUsed:         {} B", heap.used)?;
+    writeln!(out, "HeapPeak:         {} B", heap.peak)?;
+    writeln!(out, "HeapAllocs:       {}", heap.allocs)?;
+    writeln!(out, "HeapFrees:        {}", heap.frees)?;
+    writeln!(out, "HeapFailures:     {}", heap.failures)?;
+    writeln!(out, "HeapFreeBlocks:   {}", heap.free_blocks)?;
+    writeln!(out, "HeapLargestFree:  {} B", heap.largest_free)?;
+    writeln!(out, "HeapFragPercent:  {}", heap.fragmentation_percent())?;
+
+    let frames = frame::stats();
+    writeln!(out, "FrameSize:        {} B", frame::FRAME_SIZE)?;
+    writeln!(out, "FramesTotal:      {}", frames.total)?;
+    writeln!(out, "FramesUsed:       {}", frames.used)?;
+    writeln!(out, "FramesPeak:       {}", frames.peak)?;
+    writeln!(out, "FrameFailures:    {}", frames.failures)?;
+    writeln!(out, "FrameFreeRuns:    {}", frames.free_runs)?;
+    writeln!(out, "FrameLargestRun:  {}", frames.largest_free_run)?;
+    writeln!(out, "FramesQuarantined: {}", frames.quarantined)?;
+
+    let stacks = TaskStackAllocator::stats();
+    writeln!(out, "TaskStackSize:    {} B", MemoryRegions::TASK_STACK_SIZE)?;
+    writeln!(out, "TaskStacksTotal:  {}", stacks.total)?;
+    writeln!(out, "TaskStacksUsed:   {}", stacks.used)?;
+    writeln!(out, "TaskStacksPeak:   {}", stacks.peak)?;
+    writeln!(out, "TaskStackFailures: {}", stacks.failures)
+}
+
+fn show_uptime(out: &mut dyn Write) -> fmt::Result {
+    // Tik hızı çalışma zamanında değişebildiğinden süre tiklerden hesaplanmaz
+    let ns = crate::hrtimer::now_ns();
+    writeln!(out, "{}.{:03} s ({} ticks)", ns / 1_000_000_000, ns % 1_000_000_000 / 1_000_000, sched::ticks())
+}
+
+crate::proc_entry!("tasks", show_tasks);
+crate::proc_entry!("interrupts", show_interrupts);
+crate::proc_entry!("counters", show_counters);
+crate::proc_entry!("meminfo", show_meminfo);
+crate::proc_entry!("uptime", show_uptime);
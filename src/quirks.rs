@@ -0,0 +1,170 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/quirks.rs
+// İşlemci errata ve kart tuhaflıkları (quirk) tablosu.
+//
+// Belirli bir çekirdek revizyonuna veya karta özgü davranış değişiklikleri
+// genel kod yollarına dağılmak yerine burada toplanır. Tablo girdileri
+// işlemci kimliğine (`cpu::features().id`, maske/değer eşleşmesi) ve/veya
+// aygıt ağacı kök düğümünün `compatible` dizesine göre eşleşir. `apply`,
+// `platform_init` içinde initcall'lardan önce bir kez çağrılır; eşleşen
+// girdilerin bayrakları birleştirilir.
+//
+// Tüketiciler yalnızca bayrağı sorgular (`has`, `uart_clock_hz`):
+// * `NO_WFE`: Boşta beklemede WFE yerine WFI (armv9 `power::system_idle`).
+// * `TLB_FLUSH_ALL`: Tek adres yerine tüm TLB temizliği (armv9 `flush_page`).
+// * UART saati: 16550 bölücüsü varsayılan 1.8432 MHz yerine kartın saatinden
+//   hesaplanır (rv64i konsolu).
+//
+// Kabuk komutu: `quirks`
+
+use core::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// BAYRAKLAR VE TABLO
+// -----------------------------------------------------------------------------
+
+/// Boşta beklemede WFE kullanma.
+pub const NO_WFE: u32 = 1 << 0;
+/// Sayfa eşlemesi kaldırılırken tüm TLB'yi temizle.
+pub const TLB_FLUSH_ALL: u32 = 1 << 1;
+
+/// 16550 UART'ın varsayılan giriş saati (115200 baud için bölücü 1).
+pub const DEFAULT_UART_CLOCK_HZ: u32 = 1_843_200;
+
+/// İşlemci kimliği eşleşmesi: mimari `version::ARCH` ve `id & mask == value`.
+#[derive(Debug, Clone, Copy)]
+pub struct CpuMatch {
+    pub arch: &'static str,
+    pub mask: u64,
+    pub value: u64,
+}
+
+/// Tablo girdisi. Verilen tüm koşullar sağlanmalıdır; hiç koşulu olmayan
+/// girdi eşleşmez.
+#[derive(Debug, Clone, Copy)]
+pub struct Quirk {
+    pub name: &'static str,
+    pub cpu: Option<CpuMatch>,
+    /// Kök düğümün `compatible` listesinde aranan dize.
+    pub board: Option<&'static str>,
+    pub flags: u32,
+    /// 0: değiştirme.
+    pub uart_clock_hz: u32,
+}
+
+impl Quirk {
+    fn matches(&self, cpu_id: u64, is_board: &dyn Fn(&str) -> bool) -> bool {
+        if self.cpu.is_none() && self.board.is_none() {
+            return false;
+        }
+        self.cpu.is_none_or(|m| m.arch == crate::version::ARCH && cpu_id & m.mask == m.value) && self.board.is_none_or(is_board)
+    }
+}
+
+/// MIDR_EL1: uygulayıcı [31:24], varyant [23:20], parça [15:4] (revizyon hariç).
+const MIDR_PART_VARIANT: u64 = 0xFFF0_FFF0;
+
+static QUIRKS: [Quirk; 3] = [
+    // Cortex-A53 r0 (erken revizyonlar): WFE uyanması ve VA tabanlı TLBI
+    // errata'larına karşı WFI ve tam TLB temizliği kullanılır.
+    Quirk {
+        name: "cortex-a53-r0",
+        cpu: Some(CpuMatch { arch: "armv9", mask: MIDR_PART_VARIANT, value: 0x4100_D030 }),
+        board: None,
+        flags: NO_WFE | TLB_FLUSH_ALL,
+        uart_clock_hz: 0,
+    },
+    // StarFive JH7110 (VisionFive 2): DesignWare 8250 UART 24 MHz ile beslenir.
+    Quirk {
+        name: "starfive-jh7110-uart",
+        cpu: None,
+        board: Some("starfive,jh7110"),
+        flags: 0,
+        uart_clock_hz: 24_000_000,
+    },
+    // Allwinner D1: 16550 uyumlu UART 24 MHz APB saatinden beslenir.
+    Quirk {
+        name: "allwinner-d1-uart",
+        cpu: None,
+        board: Some("allwinner,sun20i-d1"),
+        flags: 0,
+        uart_clock_hz: 24_000_000,
+    },
+];
+
+// -----------------------------------------------------------------------------
+// UYGULAMA VE SORGULAMA
+// -----------------------------------------------------------------------------
+
+/// Etkin bayraklar.
+static ACTIVE: AtomicU32 = AtomicU32::new(0);
+/// Eşleşen tablo girdileri (bit i = `QUIRKS[i]`).
+static MATCHED: AtomicU64 = AtomicU64::new(0);
+/// 0: varsayılan saat.
+static UART_CLOCK: AtomicU32 = AtomicU32::new(0);
+
+/// Tabloyu algılanan işlemci ve karta göre uygular; eşleşen girdileri
+/// yazdırır. `platform_init` içinde, initcall'lardan önce çağrılır.
+pub fn apply() {
+    let cpu_id = crate::cpu::features().id;
+    let root = crate::fdt::get().and_then(|fdt| fdt.find_path("/"));
+    let is_board = |compat: &str| root.as_ref().is_some_and(|node| node.is_compatible(compat));
+
+    let (mut flags, mut matched, mut uart_clock) = (0, 0u64, 0);
+    for (i, quirk) in QUIRKS.iter().enumerate().filter(|(_, q)| q.matches(cpu_id, &is_board)) {
+        serial_println!("[QUIRK] {} uygulandı (bayraklar {:#x})", quirk.name, quirk.flags);
+        flags |= quirk.flags;
+        matched |= 1 << i;
+        if quirk.uart_clock_hz != 0 {
+            uart_clock = quirk.uart_clock_hz;
+        }
+    }
+    ACTIVE.store(flags, Ordering::Relaxed);
+    MATCHED.store(matched, Ordering::Relaxed);
+    UART_CLOCK.store(uart_clock, Ordering::Relaxed);
+}
+
+/// Bayrak etkin mi?
+#[inline]
+pub fn has(flag: u32) -> bool {
+    ACTIVE.load(Ordering::Relaxed) & flag != 0
+}
+
+/// Kartın UART saati bir girdiyle değiştirildiyse değeri.
+pub fn uart_clock_hz() -> Option<u32> {
+    match UART_CLOCK.load(Ordering::Relaxed) {
+        0 => None,
+        hz => Some(hz),
+    }
+}
+
+/// 16550 bölücüsü: saat / (16 × baud), en az 1. Saat önce kart
+/// düzeltmesinden, sonra ürün yazılımının bildirdiğinden (`earlycon`) alınır.
+pub fn uart_divisor(baud: u32) -> u16 {
+    let clock = uart_clock_hz().or_else(crate::earlycon::clock_hz).unwrap_or(DEFAULT_UART_CLOCK_HZ);
+    (clock / (16 * baud)).clamp(1, u16::MAX as u32) as u16
+}
+
+// -----------------------------------------------------------------------------
+// KABUK KOMUTU
+// -----------------------------------------------------------------------------
+
+fn quirks_command(_args: &[&str]) -> Result<(), KernelError> {
+    let matched = MATCHED.load(Ordering::Relaxed);
+    serial_println!("  AD                     EŞLEŞME  BAYRAK");
+    for (i, quirk) in QUIRKS.iter().enumerate() {
+        let on = matched & (1 << i) != 0;
+        serial_println!("  {:<22} {:<8} {:#x}", quirk.name, if on { "evet" } else { "hayır" }, quirk.flags);
+    }
+    serial_println!(
+        "  etkin: {:#x}; UART saati: {} Hz",
+        ACTIVE.load(Ordering::Relaxed),
+        uart_clock_hz().unwrap_or(DEFAULT_UART_CLOCK_HZ)
+    );
+    Ok(())
+}
+
+crate::shell_command!("quirks", "Uygulanan errata ve kart tuhaflıklarını gösterir", quirks_command);
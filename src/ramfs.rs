@@ -0,0 +1,329 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/ramfs.rs
+// Bellekte tutulan, yazılabilir kök dosya sistemi (ramfs).
+//
+// Açılışta `/`'a bağlanır; içeriği önyükleyicinin verdiği initramfs'ten
+// (`initrd::unpack`) gelir ve çalışma sırasında yazılabilir. Dosya verisi
+// çerçeve ayırıcısından alınan ardışık çerçevelerde tutulur; dosya büyüdükçe
+// daha büyük bir alana taşınır. Düğüm tablosu statiktir ve düğümler hiç
+// silinmez; bu yüzden `readdir`'in döndürdüğü adlar çekirdek ömrü boyunca
+// geçerlidir.
+//
+// VFS'te oluşturma çağrısı olmadığından var olmayan bir dosyaya yazmak,
+// üst dizini varsa dosyayı oluşturur. Dizinler `mkdir` ile oluşturulur.
+
+use core::cell::UnsafeCell;
+use crate::memory::frame::{self, FRAME_SIZE};
+use crate::platformgeneric::spinlock::Spinlock;
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+use crate::vfs::{DirEntry, FileSystem, FileType, Stat};
+
+// -----------------------------------------------------------------------------
+// SABİTLER VE TİPLER
+// -----------------------------------------------------------------------------
+
+/// En fazla düğüm (dosya + dizin).
+pub const MAX_NODES: usize = 128;
+/// Kök dizine göreli yolun en fazla uzunluğu.
+pub const MAX_PATH: usize = 96;
+/// Bir dosyanın en fazla boyutu (çerçeve).
+pub const MAX_FILE_PAGES: usize = 256;
+
+#[derive(Clone, Copy)]
+struct Node {
+    /// Kök dizine göreli yol (baştaki `/` olmadan).
+    path: [u8; MAX_PATH],
+    path_len: usize,
+    kind: FileType,
+    /// Verinin ilk çerçevesi (0: veri yok).
+    data: usize,
+    /// Ayrılmış çerçeve sayısı.
+    pages: usize,
+    size: usize,
+}
+
+impl Node {
+    fn path(&self) -> &str {
+        core::str::from_utf8(&self.path[..self.path_len]).unwrap_or("")
+    }
+
+    fn name(&self) -> &str {
+        let path = self.path();
+        path.rsplit('/').next().unwrap_or(path)
+    }
+
+    fn parent(&self) -> &str {
+        parent_of(self.path())
+    }
+}
+
+struct NodeTable {
+    lock: Spinlock,
+    nodes: UnsafeCell<[Option<Node>; MAX_NODES]>,
+}
+
+// Tabloya erişim `lock` ile korunur.
+unsafe impl Sync for NodeTable {}
+
+static NODES: NodeTable = NodeTable {
+    lock: Spinlock::new(),
+    nodes: UnsafeCell::new([None; MAX_NODES]),
+};
+
+fn with_nodes<R>(f: impl FnOnce(&'static mut [Option<Node>; MAX_NODES]) -> R) -> R {
+    NODES.lock.lock();
+    // SAFETY: Kilit tutulduğu sürece tabloya tek erişen biziz.
+    let result = f(unsafe { &mut *NODES.nodes.get() });
+    NODES.lock.unlock();
+    result
+}
+
+fn parent_of(path: &str) -> &str {
+    path.rfind('/').map_or("", |slash| &path[..slash])
+}
+
+/// Yolu düğüm tablosundaki biçime getirir: baştaki/sondaki `/` ve `.`
+/// bileşenleri atılır; `..` ve boş bileşenler reddedilir.
+fn normalize<'a>(path: &'a str, buf: &'a mut [u8; MAX_PATH]) -> Result<&'a str, KernelError> {
+    let mut len = 0;
+    for part in path.split('/').filter(|p| !p.is_empty() && *p != ".") {
+        if part == ".." {
+            return Err(KernelError::InvalidArgument);
+        }
+        let sep = usize::from(len > 0);
+        if len + sep + part.len() > MAX_PATH {
+            return Err(KernelError::InvalidArgument);
+        }
+        if sep == 1 {
+            buf[len] = b'/';
+        }
+        buf[len + sep..len + sep + part.len()].copy_from_slice(part.as_bytes());
+        len += sep + part.len();
+    }
+    core::str::from_utf8(&buf[..len]).map_err(|_| KernelError::InvalidArgument)
+}
+
+fn find(nodes: &[Option<Node>; MAX_NODES], path: &str) -> Option<usize> {
+    nodes.iter().position(|n| n.as_ref().is_some_and(|n| n.path() == path))
+}
+
+/// Yolun türü; kök her zaman dizindir.
+fn kind_of(nodes: &[Option<Node>; MAX_NODES], path: &str) -> Option<FileType> {
+    if path.is_empty() {
+        return Some(FileType::Directory);
+    }
+    find(nodes, path).and_then(|i| nodes[i].map(|n| n.kind))
+}
+
+/// Üst dizini var olan yeni bir düğüm ekler.
+fn insert(nodes: &mut [Option<Node>; MAX_NODES], path: &str, kind: FileType) -> Result<usize, KernelError> {
+    if path.is_empty() || find(nodes, path).is_some() {
+        return Err(KernelError::ResourceBusy);
+    }
+    if kind_of(nodes, parent_of(path)) != Some(FileType::Directory) {
+        return Err(KernelError::NotFound);
+    }
+    let index = nodes.iter().position(|n| n.is_none()).ok_or(KernelError::OutOfMemoryStatic)?;
+    let mut node = Node { path: [0; MAX_PATH], path_len: path.len(), kind, data: 0, pages: 0, size: 0 };
+    node.path[..path.len()].copy_from_slice(path.as_bytes());
+    nodes[index] = Some(node);
+    Ok(index)
+}
+
+// -----------------------------------------------------------------------------
+// DIŞ ARAYÜZ
+// -----------------------------------------------------------------------------
+
+/// Dizin oluşturur; eksik üst dizinler de oluşturulur. Dizin zaten varsa
+/// başarılı döner.
+///
+/// # Hatalar
+/// * `InvalidArgument`: Yol geçersiz veya çok uzun.
+/// * `ResourceBusy`: Yolun bir bileşeni dosya.
+/// * `OutOfMemoryStatic`: Düğüm tablosu dolu.
+pub fn mkdir(path: &str) -> Result<(), KernelError> {
+    let mut buf = [0u8; MAX_PATH];
+    let path = normalize(path, &mut buf)?;
+    with_nodes(|nodes| {
+        let mut end = 0;
+        while end < path.len() {
+            end = path[end + 1..].find('/').map_or(path.len(), |i| end + 1 + i);
+            match kind_of(nodes, &path[..end]) {
+                Some(FileType::Directory) => {}
+                Some(FileType::File) => return Err(KernelError::ResourceBusy),
+                None => {
+                    insert(nodes, &path[..end], FileType::Directory)?;
+                }
+            }
+        }
+        Ok(())
+    })
+}
+
+/// Dosyayı boyutu sıfır olacak şekilde oluşturur veya kırpar.
+pub fn truncate(path: &str) -> Result<(), KernelError> {
+    let mut buf = [0u8; MAX_PATH];
+    let path = normalize(path, &mut buf)?;
+    with_nodes(|nodes| {
+        let index = match find(nodes, path) {
+            Some(index) => index,
+            None => insert(nodes, path, FileType::File)?,
+        };
+        let node = nodes[index].as_mut().ok_or(KernelError::NotFound)?;
+        if node.kind != FileType::File {
+            return Err(KernelError::InvalidArgument);
+        }
+        node.size = 0;
+        Ok(())
+    })
+}
+
+/// Dosyanın verisini en az `size` bayt tutacak şekilde büyütür.
+fn reserve(node: &mut Node, size: usize) -> Result<(), KernelError> {
+    let needed = size.div_ceil(FRAME_SIZE);
+    if needed <= node.pages {
+        return Ok(());
+    }
+    if needed > MAX_FILE_PAGES {
+        return Err(KernelError::OutOfMemoryStatic);
+    }
+    // Art arda küçük yazmalarda her seferinde taşımamak için iki katına büyür
+    let pages = needed.max(node.pages * 2).min(MAX_FILE_PAGES);
+    let data = frame::alloc_contiguous(pages)?;
+    // SAFETY: Çerçeveler yeni ayrıldı; eski alan bu düğüme aittir ve çekirdekte
+    // birebir eşlidir.
+    unsafe {
+        if node.data != 0 {
+            core::ptr::copy_nonoverlapping(node.data as *const u8, data as *mut u8, node.size);
+            frame::free(node.data, node.pages).ok();
+        }
+    }
+    node.data = data;
+    node.pages = pages;
+    Ok(())
+}
+
+/// Kullanılan düğüm sayısı ve dosya verisi için ayrılmış bayt.
+pub fn usage() -> (usize, usize) {
+    with_nodes(|nodes| {
+        let used = nodes.iter().flatten().count();
+        let bytes = nodes.iter().flatten().map(|n| n.pages * FRAME_SIZE).sum();
+        (used, bytes)
+    })
+}
+
+// -----------------------------------------------------------------------------
+// VFS ARAYÜZÜ
+// -----------------------------------------------------------------------------
+
+struct RamFs;
+
+static RAMFS: RamFs = RamFs;
+
+impl FileSystem for RamFs {
+    fn name(&self) -> &'static str {
+        "ramfs"
+    }
+
+    fn stat(&self, path: &str) -> Result<Stat, KernelError> {
+        let mut buf = [0u8; MAX_PATH];
+        let path = normalize(path, &mut buf)?;
+        with_nodes(|nodes| {
+            if path.is_empty() {
+                return Ok(Stat { kind: FileType::Directory, size: 0 });
+            }
+            let node = find(nodes, path).and_then(|i| nodes[i]).ok_or(KernelError::NotFound)?;
+            Ok(Stat { kind: node.kind, size: if node.kind == FileType::File { node.size } else { 0 } })
+        })
+    }
+
+    fn read(&self, path: &str, offset: usize, buf: &mut [u8]) -> Result<usize, KernelError> {
+        let mut name = [0u8; MAX_PATH];
+        let path = normalize(path, &mut name)?;
+        with_nodes(|nodes| {
+            let node = find(nodes, path).and_then(|i| nodes[i]).ok_or(KernelError::NotFound)?;
+            if node.kind != FileType::File {
+                return Err(KernelError::InvalidArgument);
+            }
+            let len = node.size.saturating_sub(offset).min(buf.len());
+            if len > 0 {
+                // SAFETY: `offset + len <= size <= pages * FRAME_SIZE`.
+                let data = unsafe { core::slice::from_raw_parts((node.data + offset) as *const u8, len) };
+                buf[..len].copy_from_slice(data);
+            }
+            Ok(len)
+        })
+    }
+
+    fn readdir(&self, path: &str, index: usize) -> Result<Option<DirEntry>, KernelError> {
+        let mut buf = [0u8; MAX_PATH];
+        let path = normalize(path, &mut buf)?;
+        with_nodes(|nodes| {
+            match kind_of(nodes, path) {
+                Some(FileType::Directory) => {}
+                Some(FileType::File) => return Err(KernelError::InvalidArgument),
+                None => return Err(KernelError::NotFound),
+            }
+            // Düğümler hiç silinmediğinden adlar statik tabloda sabit kalır
+            let nodes: &'static [Option<Node>; MAX_NODES] = nodes;
+            Ok(nodes
+                .iter()
+                .flatten()
+                .filter(|n| n.parent() == path)
+                .nth(index)
+                .map(|n| DirEntry { name: n.name(), kind: n.kind }))
+        })
+    }
+
+    fn write(&self, path: &str, offset: usize, data: &[u8]) -> Result<usize, KernelError> {
+        let mut buf = [0u8; MAX_PATH];
+        let path = normalize(path, &mut buf)?;
+        with_nodes(|nodes| {
+            let index = match find(nodes, path) {
+                Some(index) => index,
+                None => insert(nodes, path, FileType::File)?,
+            };
+            let node = nodes[index].as_mut().ok_or(KernelError::NotFound)?;
+            if node.kind != FileType::File {
+                return Err(KernelError::InvalidArgument);
+            }
+            let end = offset.checked_add(data.len()).ok_or(KernelError::InvalidArgument)?;
+            reserve(node, end)?;
+            // SAFETY: `reserve` en az `end` baytlık alan ayırdı; dosyadaki boşluk
+            // (eski sondan `offset`'e) sıfırlanır.
+            unsafe {
+                if offset > node.size {
+                    core::ptr::write_bytes((node.data + node.size) as *mut u8, 0, offset - node.size);
+                }
+                core::ptr::copy_nonoverlapping(data.as_ptr(), (node.data + offset) as *mut u8, data.len());
+            }
+            node.size = node.size.max(end);
+            Ok(data.len())
+        })
+    }
+}
+
+/// Dosyaya `offset` konumundan yazar (dosya yoksa oluşturulur).
+pub fn write(path: &str, offset: usize, data: &[u8]) -> Result<usize, KernelError> {
+    RAMFS.write(path, offset, data)
+}
+
+fn mount_root() -> Result<(), KernelError> {
+    crate::vfs::mount("/", &RAMFS)
+}
+
+crate::initcall!(fs, "ramfs", mount_root);
+
+// -----------------------------------------------------------------------------
+// KABUK KOMUTU
+// -----------------------------------------------------------------------------
+
+fn df_command(_args: &[&str]) -> Result<(), KernelError> {
+    let (used, bytes) = usage();
+    serial_println!("ramfs: {}/{} düğüm, {} KiB veri", used, MAX_NODES, bytes / 1024);
+    Ok(())
+}
+
+crate::shell_command!("df", "ramfs kullanımını gösterir", df_command);
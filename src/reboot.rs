@@ -0,0 +1,434 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/reboot.rs
+// Sıcak/soğuk yeniden başlatma isteği ve açılışlar arası korunan kayıt.
+//
+// Linker betiğindeki `.noinit` bölümü açılışta sıfırlanmaz. Burada tutulan
+// `Persistent` alanı yeniden başlatma türünü, neden kodunu, açılış sayısını
+// ve çökme kaydını (panik iletileri) içerir; sihirli sayı ve CRC-32 ile
+// doğrulanır. Sıcak yeniden başlatma RAM'i korur, böylece bir sonraki
+// açılışta önceki oturumun neden bittiği okunabilir (`lastboot`).
+//
+// Mimarilerin sıcak sıfırlama yolları (`arch::<mimari>::shutdown::system_reset`):
+// * amd64: BIOS sıcak başlatma bayrağı + klavye denetleyicisi; soğukta 0xCF9.
+// * armv9: PSCI SYSTEM_RESET2 (neden `cookie` olarak iletilir).
+// * rv64i: SBI SRST sıcak/soğuk türü, hata nedenleri SYSTEM_FAILURE ile.
+// * Diğerleri: yalnızca genel yeniden başlatma; RAM'in korunması platforma bağlıdır.
+//
+// Açılışta kayıt okunur ve alan yeni oturum için "beklenmeyen sıfırlama"
+// nedeniyle hazırlanır: oturum `request` ile bitmezse (watchdog, güç
+// kesintisi, donanım sıfırlaması) sonraki açılış bunu görür. Kayıt geçersizse
+// önceki açılış güç verme (soğuk) sayılır.
+//
+// Çökme kaydı alanda `lz4` ile sıkıştırılmış saklanır: oturumun düz metni
+// `CRASH_TEXT_SIZE`'a kadar bellekte biriktirilir ve her eklemede
+// `CRASH_LOG_SIZE`'lık alana yeniden sıkıştırılır; sığmayan ekleme atılır.
+// Panikte ileti ve konumun ardından günlük halkasının (`logbuf`) son
+// `CRASH_TAIL` baytı da eklenir, böylece `lastboot` çökmeden önceki
+// günlüğü de gösterir. Kayıt açılışta açılır.
+//
+// Not: Veri önbelleği olan mimarilerde sıfırlama öncesi önbelleğin belleğe
+// yazılması ürün yazılımının sorumluluğundadır (PSCI ve SBI bunu garanti eder).
+
+use core::cell::UnsafeCell;
+use core::fmt::{self, Write};
+use core::mem::MaybeUninit;
+use core::panic::PanicInfo;
+use core::sync::atomic::{AtomicBool, Ordering};
+use crate::lz4::{self, Compressor};
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+use crate::update::crc32_update;
+
+// -----------------------------------------------------------------------------
+// TÜRLER
+// -----------------------------------------------------------------------------
+
+/// Korunan alanda sıkıştırılmış çökme kaydının boyutu (bayt).
+pub const CRASH_LOG_SIZE: usize = 2048;
+/// Çökme kaydının en fazla düz metin boyutu.
+pub const CRASH_TEXT_SIZE: usize = 8192;
+/// Panikte çökme kaydına eklenen günlük kuyruğu (düz bayt).
+const CRASH_TAIL: usize = 3072;
+
+const MAGIC: u32 = 0x5342_4f54; // "SBOT"
+/// Alanın düzeni değiştiğinde artırılır; eski kayıt geçersiz sayılır.
+const LAYOUT_VERSION: u16 = 2;
+
+/// Yeniden başlatma türü.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// Tam sıfırlama; RAM korunmaz.
+    Cold,
+    /// İşlemci/sistem sıfırlaması; RAM (ve `.noinit`) korunur.
+    Warm,
+}
+
+/// Yeniden başlatma nedeni. Standart kodlar aşağıdadır; kartlar kendi
+/// kodlarını `BOARD_BASE` ve üzerinde tanımlayabilir.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reason(pub u32);
+
+impl Reason {
+    /// Geçerli kayıt yok: ilk güç verme veya RAM korunmadı.
+    pub const POWER_ON: Reason = Reason(0);
+    /// Oturum istek olmadan bitti (watchdog, güç kesintisi, donanım).
+    pub const UNEXPECTED: Reason = Reason(1);
+    /// Kabuk veya uygulama isteği.
+    pub const USER: Reason = Reason(2);
+    pub const PANIC: Reason = Reason(3);
+    pub const WATCHDOG: Reason = Reason(4);
+    /// Ürün yazılımı güncellemesini uygulamak için.
+    pub const UPDATE: Reason = Reason(5);
+    pub const BOARD_BASE: Reason = Reason(0x100);
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Reason::POWER_ON => "power-on",
+            Reason::UNEXPECTED => "unexpected",
+            Reason::USER => "user",
+            Reason::PANIC => "panic",
+            Reason::WATCHDOG => "watchdog",
+            Reason::UPDATE => "update",
+            _ => "board",
+        }
+    }
+
+    /// Hata kaynaklı sıfırlama mı? (ürün yazılımına bildirilir)
+    pub fn is_failure(self) -> bool {
+        matches!(self, Reason::PANIC | Reason::WATCHDOG)
+    }
+
+    fn parse(s: &str) -> Option<Reason> {
+        Some(match s {
+            "user" => Reason::USER,
+            "panic" => Reason::PANIC,
+            "watchdog" => Reason::WATCHDOG,
+            "update" => Reason::UPDATE,
+            _ => Reason(s.parse().ok()?),
+        })
+    }
+}
+
+/// Önceki açılışın kaydı.
+#[derive(Clone, Copy)]
+pub struct BootRecord {
+    pub kind: Kind,
+    pub reason: Reason,
+    /// Son soğuk açılıştan beri açılış sayısı (bu açılış dahil).
+    pub boot_count: u32,
+    /// Açılmış çökme kaydı.
+    crash: [u8; CRASH_TEXT_SIZE],
+    crash_len: usize,
+}
+
+impl BootRecord {
+    /// Önceki oturumun çökme kaydı (boş olabilir).
+    pub fn crash_log(&self) -> &str {
+        match core::str::from_utf8(&self.crash[..self.crash_len]) {
+            Ok(text) => text,
+            // Kayıt bir karakterin ortasında kesilmiş olabilir
+            Err(e) => core::str::from_utf8(&self.crash[..e.valid_up_to()]).unwrap_or(""),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// KORUNAN ALAN
+// -----------------------------------------------------------------------------
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct Persistent {
+    magic: u32,
+    version: u16,
+    /// 0: soğuk, 1: sıcak.
+    kind: u16,
+    reason: u32,
+    boot_count: u32,
+    /// Sıkıştırılmış ve açılmış çökme kaydı uzunluğu.
+    crash_len: u32,
+    crash_text_len: u32,
+    crc: u32,
+    crash: [u8; CRASH_LOG_SIZE],
+}
+
+impl Persistent {
+    fn checksum(&self) -> u32 {
+        let mut crc = crc32_update(0, &self.magic.to_le_bytes());
+        crc = crc32_update(crc, &self.version.to_le_bytes());
+        crc = crc32_update(crc, &self.kind.to_le_bytes());
+        crc = crc32_update(crc, &self.reason.to_le_bytes());
+        crc = crc32_update(crc, &self.boot_count.to_le_bytes());
+        crc = crc32_update(crc, &self.crash_len.to_le_bytes());
+        crc = crc32_update(crc, &self.crash_text_len.to_le_bytes());
+        crc32_update(crc, &self.crash[..(self.crash_len as usize).min(CRASH_LOG_SIZE)])
+    }
+
+    fn is_valid(&self) -> bool {
+        self.magic == MAGIC
+            && self.version == LAYOUT_VERSION
+            && self.crash_len as usize <= CRASH_LOG_SIZE
+            && self.crash_text_len as usize <= CRASH_TEXT_SIZE
+            && self.crc == self.checksum()
+    }
+
+    fn seal(&mut self) {
+        self.crc = self.checksum();
+    }
+
+    /// Çökme kaydını açar; bozuksa (doğru CRC'ye rağmen) boş kayıt.
+    fn unpack_crash(&self, out: &mut [u8; CRASH_TEXT_SIZE]) -> usize {
+        let packed = &self.crash[..self.crash_len as usize];
+        match lz4::decompress(packed, out) {
+            Ok(n) if n == self.crash_text_len as usize => n,
+            _ => 0,
+        }
+    }
+}
+
+struct PersistentArea(UnsafeCell<MaybeUninit<Persistent>>);
+
+// Alana `BUSY` bayrağını alan tek bağlam erişir.
+unsafe impl Sync for PersistentArea {}
+
+/// Bu oturumun çökme kaydının düz metni.
+struct CrashText {
+    text: [u8; CRASH_TEXT_SIZE],
+    len: usize,
+    compressor: Compressor,
+}
+
+struct CrashTextCell(UnsafeCell<CrashText>);
+
+// `AREA` ile aynı: yalnızca `BUSY` bayrağını alan bağlam erişir.
+unsafe impl Sync for CrashTextCell {}
+
+static TEXT: CrashTextCell =
+    CrashTextCell(UnsafeCell::new(CrashText { text: [0; CRASH_TEXT_SIZE], len: 0, compressor: Compressor::new() }));
+
+#[link_section = ".noinit"]
+static AREA: PersistentArea = PersistentArea(UnsafeCell::new(MaybeUninit::uninit()));
+
+/// Alana erişim bayrağı; panik yolunda beklemek yerine kayıt atlanır.
+static BUSY: AtomicBool = AtomicBool::new(false);
+
+fn with_area<R>(f: impl FnOnce(&mut Persistent, &mut CrashText) -> R) -> Option<R> {
+    if BUSY.swap(true, Ordering::Acquire) {
+        return None;
+    }
+    // SAFETY: Alan her bit deseninin geçerli olduğu düz tamsayılardan oluşur;
+    // önceki açılıştan kalan içerik `is_valid` ile doğrulanır. Düz metne de
+    // yalnızca bayrağı alan bağlam erişir.
+    let result = f(unsafe { (*AREA.0.get()).assume_init_mut() }, unsafe { &mut *TEXT.0.get() });
+    BUSY.store(false, Ordering::Release);
+    Some(result)
+}
+
+struct LastBoot(UnsafeCell<Option<BootRecord>>);
+
+// Yalnızca açılışta (`init`) bir kez yazılır.
+unsafe impl Sync for LastBoot {}
+
+static LAST: LastBoot = LastBoot(UnsafeCell::new(None));
+
+/// Önceki açılışın kaydı; kayıt geçersizse (güç verme) `None`.
+pub fn last() -> Option<&'static BootRecord> {
+    // SAFETY: `init` sonrası değişmez.
+    unsafe { (*LAST.0.get()).as_ref() }
+}
+
+/// Önceki kaydı okur ve alanı bu oturum için hazırlar.
+fn init() -> Result<(), KernelError> {
+    // SAFETY: Açılışta, başka okuyucu yokken tek kez yazılır.
+    let last = unsafe { &mut *LAST.0.get() };
+    with_area(|area, text| {
+        *last = None;
+        if area.is_valid() {
+            let record = last.insert(BootRecord {
+                kind: if area.kind == 1 { Kind::Warm } else { Kind::Cold },
+                reason: Reason(area.reason),
+                boot_count: area.boot_count,
+                crash: [0; CRASH_TEXT_SIZE],
+                crash_len: 0,
+            });
+            record.crash_len = area.unpack_crash(&mut record.crash);
+        }
+        area.magic = MAGIC;
+        area.version = LAYOUT_VERSION;
+        area.kind = 0;
+        area.reason = Reason::UNEXPECTED.0;
+        area.boot_count = last.as_ref().map_or(1, |p| p.boot_count.wrapping_add(1));
+        area.crash_len = 0;
+        area.crash_text_len = 0;
+        text.len = 0;
+        area.seal();
+    })
+    .ok_or(KernelError::ResourceBusy)?;
+
+    match last {
+        Some(record) => serial_println!(
+            "[REBOOT] Önceki oturum: {} yeniden başlatma, neden {} ({}), açılış #{}",
+            if record.kind == Kind::Warm { "sıcak" } else { "soğuk" },
+            record.reason.name(),
+            record.reason.0,
+            record.boot_count
+        ),
+        None => serial_println!("[REBOOT] Korunan kayıt yok (güç verme)"),
+    }
+    Ok(())
+}
+
+crate::initcall!(earlycon, "reboot", init);
+
+// -----------------------------------------------------------------------------
+// ÇÖKME KAYDI
+// -----------------------------------------------------------------------------
+
+impl Write for CrashText {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let n = s.len().min(CRASH_TEXT_SIZE - self.len);
+        self.text[self.len..self.len + n].copy_from_slice(&s.as_bytes()[..n]);
+        self.len += n;
+        if n < s.len() { Err(fmt::Error) } else { Ok(()) }
+    }
+}
+
+impl CrashText {
+    /// Düz metni alana sıkıştırır; sığmazsa `false` (alanın kaydı bozulur).
+    fn pack(&mut self, area: &mut Persistent) -> bool {
+        match self.compressor.compress(&self.text[..self.len], &mut area.crash) {
+            Some(n) => {
+                area.crash_len = n as u32;
+                area.crash_text_len = self.len as u32;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Çökme kaydına metin ekler; sıkıştırılmış kayıt alana sığmazsa veya düz
+/// metin sınırı aşılırsa ekleme kesilir ya da atılır. Alan o an başka bir
+/// bağlamda yazılıyorsa (iç içe panik) kayıt atlanır.
+pub fn crash_record(args: fmt::Arguments) {
+    with_area(|area, text| {
+        if area.magic == MAGIC {
+            let before = text.len;
+            let _ = text.write_fmt(args);
+            if !text.pack(area) {
+                // Önceki metin sığıyordu; sıkıştırma belirlenimcidir
+                text.len = before;
+                text.pack(area);
+            }
+            area.seal();
+        }
+    });
+}
+
+/// Günlük kuyruğunu parça parça çökme kaydına ekler.
+struct TailWriter;
+
+impl Write for TailWriter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        crash_record(format_args!("{}", s));
+        Ok(())
+    }
+}
+
+/// Panik işleyicileri tarafından çağrılır: konumu, iletiyi ve günlük
+/// kuyruğunu kaydeder.
+pub fn record_panic(info: &PanicInfo) {
+    // Panik zamanlayıcı kilidi tutulurken gelmiş olabilir; beklenmez
+    let task = crate::sched::try_current().flatten();
+    match info.location() {
+        Some(location) => crash_record(format_args!(
+            "panic görev={:?} {}:{}:{}\n",
+            task,
+            location.file(),
+            location.line(),
+            location.column()
+        )),
+        None => crash_record(format_args!("panic görev={:?}\n", task)),
+    }
+    if let Some(message) = info.message() {
+        crash_record(format_args!("{}\n", message));
+    }
+    crash_record(format_args!("--- dmesg ---\n"));
+    // Günlük halkası kilitliyse (panik günlük yazarken geldi) kuyruk eklenmez
+    if !crate::logbuf::tail(CRASH_TAIL, &mut TailWriter) {
+        crash_record(format_args!("<günlük halkası kilitli>\n"));
+    }
+    with_area(|area, _| {
+        area.reason = Reason::PANIC.0;
+        area.seal();
+    });
+}
+
+// -----------------------------------------------------------------------------
+// YENİDEN BAŞLATMA
+// -----------------------------------------------------------------------------
+
+fn arch_reset(kind: Kind, reason: Reason) -> ! {
+    crate::arch::shutdown::system_reset(kind == Kind::Warm, reason.0, reason.is_failure())
+}
+
+/// Türü ve nedeni korunan kayda yazar ve sistemi yeniden başlatır. Çökme
+/// kaydı korunur; soğuk istekte kayıt ancak RAM korunursa okunabilir.
+///
+/// Hata kaynaklı olmayan nedenlerde önce düzenli kapanış sırası
+/// (`shutdown::run`) uygulanır; panik ve watchdog yolları doğrudan sıfırlar.
+pub fn request(kind: Kind, reason: Reason) -> ! {
+    if !reason.is_failure() {
+        crate::shutdown::run(crate::shutdown::Action::Reboot);
+    }
+    with_area(|area, _| {
+        area.kind = if kind == Kind::Warm { 1 } else { 0 };
+        area.reason = reason.0;
+        area.seal();
+    });
+    serial_println!("[REBOOT] {:?} yeniden başlatma, neden {} ({})", kind, reason.name(), reason.0);
+    arch_reset(kind, reason)
+}
+
+// -----------------------------------------------------------------------------
+// KABUK VE PROCFS
+// -----------------------------------------------------------------------------
+
+/// `reboot [warm | cold] [neden]`
+fn reboot_command(args: &[&str]) -> Result<(), KernelError> {
+    let kind = match args.get(1).copied() {
+        None | Some("warm") => Kind::Warm,
+        Some("cold") => Kind::Cold,
+        Some(_) => return Err(KernelError::InvalidArgument),
+    };
+    let reason = match args.get(2) {
+        Some(text) => Reason::parse(text).ok_or(KernelError::InvalidArgument)?,
+        None => Reason::USER,
+    };
+    request(kind, reason)
+}
+
+crate::shell_command!("reboot", "Sistemi yeniden başlatır (reboot [warm | cold] [neden])", reboot_command);
+
+fn show_last_boot(out: &mut dyn Write) -> fmt::Result {
+    let Some(record) = last() else {
+        return writeln!(out, "reason power-on");
+    };
+    writeln!(out, "kind {}", if record.kind == Kind::Warm { "warm" } else { "cold" })?;
+    writeln!(out, "reason {} {}", record.reason.name(), record.reason.0)?;
+    writeln!(out, "boot_count {}", record.boot_count)?;
+    if !record.crash_log().is_empty() {
+        writeln!(out, "crash:")?;
+        out.write_str(record.crash_log())?;
+    }
+    Ok(())
+}
+
+crate::proc_entry!("lastboot", show_last_boot);
+
+fn lastboot_command(_args: &[&str]) -> Result<(), KernelError> {
+    show_last_boot(&mut crate::shell::Console).map_err(|_| KernelError::GenericFailure)
+}
+
+crate::shell_command!("lastboot", "Önceki oturumun bitiş nedeni ve çökme kaydı", lastboot_command);
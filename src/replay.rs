@@ -0,0 +1,246 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/replay.rs
+// Deterministik yeniden oynatma için dış girdi kaydı (QEMU icount).
+//
+// QEMU `-icount shift=N,rr=record|replay` altında komut sayacı sanal saati
+// sürer; aynı girdilerle çalışan iki koşu aynı sırayla ilerler. Nadir
+// zamanlayıcı/IPC yarışlarını ayıklamak için hatalı koşunun dış girdileri
+// iz halkasına yazılır ve yeniden oynatılan koşununkilerle karşılaştırılır:
+//
+// * `Irq`: kesme gelişi (mimarinin dağıtıcısı, kesme istatistiğiyle birlikte).
+// * `Uart`: seri konsoldan alınan bayt (`input::push_byte`).
+// * `Timer`: zamanlayıcı dolması (`timer` tik işleyicisi; geri çağırma
+//   adresi ve argümanı).
+//
+// Her kayıt sıra numarası, döngü sayacı (`clock::cycles`), tik ve işlemci
+// numarasıyla damgalanır. Kayıtlar ayrıca bir FNV-1a özetine katılır; iki
+// koşunun özetleri eşitse girdi sırası aynıdır, değilse `/proc/replay`
+// dökümleri satır satır karşılaştırılarak ilk ayrılan olay bulunur.
+//
+// Kip isteğe bağlıdır: `trace.replay` kapalıyken kayıt noktalarının maliyeti
+// tek bir yüklemedir. Açılış argümanlarında `replay` varsa kayıt ilk
+// seviyede açılır. Kayıt kilitsizdir (atomik) ve kesme bağlamında yapılır;
+// halka dolunca en eski kayıtların üzerine yazılır (`replay.overwritten`).
+// QEMU'nun kayıt/oynatma kipi tek işlemcilidir; çok işlemcide özet sırası
+// işlemciler arası yarışa bağlıdır.
+//
+// Kabuk komutu: `replay [on|off|clear]`; procfs: `/proc/replay`
+
+use core::fmt::{self, Write};
+use core::sync::atomic::{AtomicU64, Ordering};
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+use crate::sysctl::Flag;
+
+// -----------------------------------------------------------------------------
+// OLAYLAR VE HALKA
+// -----------------------------------------------------------------------------
+
+crate::sysctl!(ENABLED: Flag = Flag::new(false),
+    "trace.replay", "Dış girdileri (kesme, UART, zamanlayıcı) yeniden oynatma için kaydeder");
+
+/// Halkadaki kayıt sayısı.
+const RING_SIZE: usize = 512;
+
+/// Kaydedilen dış girdi türü.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Event {
+    Irq = 1,
+    Uart = 2,
+    Timer = 3,
+}
+
+impl Event {
+    fn from_u8(value: u8) -> Option<Event> {
+        match value {
+            1 => Some(Event::Irq),
+            2 => Some(Event::Uart),
+            3 => Some(Event::Timer),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Event::Irq => "irq",
+            Event::Uart => "uart",
+            Event::Timer => "timer",
+        }
+    }
+}
+
+/// Halkadaki bir kayıt. `seq` en son yazılır: okuyucu yazımı süren ya da
+/// üzerine yazılmış yuvayı sıra numarasından tanır.
+struct Slot {
+    /// Sıra numarası + 1 (0: boş).
+    seq: AtomicU64,
+    cycles: AtomicU64,
+    tick: AtomicU64,
+    /// Olay türü (bit 0-7) ve işlemci (bit 8-15).
+    kind: AtomicU64,
+    /// Olay verisi (kesme numarası, bayt, geri çağırma adresi).
+    data: AtomicU64,
+    /// İkinci veri kelimesi (zamanlayıcı argümanı).
+    arg: AtomicU64,
+}
+
+impl Slot {
+    const fn new() -> Self {
+        Slot {
+            seq: AtomicU64::new(0),
+            cycles: AtomicU64::new(0),
+            tick: AtomicU64::new(0),
+            kind: AtomicU64::new(0),
+            data: AtomicU64::new(0),
+            arg: AtomicU64::new(0),
+        }
+    }
+}
+
+#[allow(clippy::declare_interior_mutable_const)]
+const EMPTY: Slot = Slot::new();
+static RING: [Slot; RING_SIZE] = [EMPTY; RING_SIZE];
+
+/// Sıradaki kaydın numarası.
+static NEXT: AtomicU64 = AtomicU64::new(0);
+
+const FNV_OFFSET: u64 = 0xCBF2_9CE4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01B3;
+
+/// Kaydedilen olayların sıra duyarlı özeti.
+static DIGEST: AtomicU64 = AtomicU64::new(FNV_OFFSET);
+
+crate::kernel_counter!(OVERWRITTEN, "replay.overwritten");
+
+fn fnv(mut hash: u64, value: u64) -> u64 {
+    for byte in value.to_le_bytes() {
+        hash = (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+// -----------------------------------------------------------------------------
+// KAYIT NOKTALARI
+// -----------------------------------------------------------------------------
+
+fn record(event: Event, data: u64, arg: u64) {
+    let seq = NEXT.fetch_add(1, Ordering::Relaxed);
+    let slot = &RING[(seq % RING_SIZE as u64) as usize];
+    if slot.seq.swap(0, Ordering::Acquire) != 0 {
+        OVERWRITTEN.inc();
+    }
+    let tick = crate::sched::ticks();
+    slot.cycles.store(crate::clock::cycles(), Ordering::Relaxed);
+    slot.tick.store(tick, Ordering::Relaxed);
+    slot.kind.store(event as u64 | (crate::percpu::cpu_id() as u64) << 8, Ordering::Relaxed);
+    slot.data.store(data, Ordering::Relaxed);
+    slot.arg.store(arg, Ordering::Relaxed);
+    slot.seq.store(seq + 1, Ordering::Release);
+
+    // Döngü sayacı özete katılmaz: icount dışındaki koşularda da aynı girdi
+    // sırası aynı özeti verir
+    let _ = DIGEST.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |hash| {
+        Some(fnv(fnv(fnv(fnv(hash, event as u64), tick), data), arg))
+    });
+}
+
+/// Mimarinin kesme dağıtıcısından her kesmede çağrılır.
+#[inline]
+pub fn irq(irq: u32) {
+    if ENABLED.get() {
+        record(Event::Irq, u64::from(irq), 0);
+    }
+}
+
+/// Seri konsoldan alınan bayt.
+#[inline]
+pub fn uart_byte(byte: u8) {
+    if ENABLED.get() {
+        record(Event::Uart, u64::from(byte), 0);
+    }
+}
+
+/// Dolan zamanlayıcı (geri çağırma adresi ve argümanı).
+#[inline]
+pub fn timer(callback: usize, arg: usize) {
+    if ENABLED.get() {
+        record(Event::Timer, callback as u64, arg as u64);
+    }
+}
+
+/// Halkayı ve özeti sıfırlar.
+pub fn clear() {
+    for slot in RING.iter() {
+        slot.seq.store(0, Ordering::Relaxed);
+    }
+    NEXT.store(0, Ordering::Relaxed);
+    DIGEST.store(FNV_OFFSET, Ordering::Relaxed);
+}
+
+/// Açılış argümanlarında `replay` varsa kaydı açar.
+fn boot_enable() -> Result<(), KernelError> {
+    if crate::boot::info().has_arg("replay") {
+        ENABLED.set(true);
+        serial_println!("[REPLAY] Dış girdi kaydı açık");
+    }
+    Ok(())
+}
+
+crate::initcall!(earlycon, "replay", boot_enable);
+
+// -----------------------------------------------------------------------------
+// KABUK VE PROCFS
+// -----------------------------------------------------------------------------
+
+/// Halkadaki kayıtları eskiden yeniye yazar: `sıra tik döngü işlemci olay veri [arg]`.
+fn show_replay(out: &mut dyn Write) -> fmt::Result {
+    let next = NEXT.load(Ordering::Acquire);
+    writeln!(out, "enabled {} events {} digest {:016x}", ENABLED.get() as u8, next, DIGEST.load(Ordering::Relaxed))?;
+    for seq in next.saturating_sub(RING_SIZE as u64)..next {
+        let slot = &RING[(seq % RING_SIZE as u64) as usize];
+        let kind = slot.kind.load(Ordering::Relaxed);
+        let (cycles, tick, data, arg) = (
+            slot.cycles.load(Ordering::Relaxed),
+            slot.tick.load(Ordering::Relaxed),
+            slot.data.load(Ordering::Relaxed),
+            slot.arg.load(Ordering::Relaxed),
+        );
+        // Okurken yazılan ya da üzerine yazılan yuva atlanır
+        if slot.seq.load(Ordering::Acquire) != seq + 1 {
+            continue;
+        }
+        let Some(event) = Event::from_u8(kind as u8) else { continue };
+        write!(out, "{} {} {} {} {} ", seq, tick, cycles, (kind >> 8) as u8, event.name())?;
+        match event {
+            Event::Irq => writeln!(out, "{}", data)?,
+            Event::Uart => writeln!(out, "{:#04x}", data)?,
+            Event::Timer => writeln!(out, "{:#x} {}", data, arg)?,
+        }
+    }
+    Ok(())
+}
+
+crate::proc_entry!("replay", show_replay);
+
+fn replay_command(args: &[&str]) -> Result<(), KernelError> {
+    match args.get(1).copied() {
+        Some("on") => ENABLED.set(true),
+        Some("off") => ENABLED.set(false),
+        Some("clear") => clear(),
+        Some(_) => return Err(KernelError::InvalidArgument),
+        None => {}
+    }
+    serial_println!(
+        "  kayıt {}, {} olay (halka {}), üzerine yazılan {}, özet {:016x}",
+        if ENABLED.get() { "açık" } else { "kapalı" },
+        NEXT.load(Ordering::Relaxed),
+        RING_SIZE,
+        OVERWRITTEN.get(),
+        DIGEST.load(Ordering::Relaxed)
+    );
+    Ok(())
+}
+
+crate::shell_command!("replay", "Yeniden oynatma için dış girdi kaydı (replay [on|off|clear])", replay_command);
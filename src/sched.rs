@@ -0,0 +1,998 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/sched.rs
+// Temel görev zamanlayıcısı: statik görev tablosu, sabit öncelikli kesintili
+// (preemptive) seçim ve eşit öncelikte zaman dilimli sıralı (round-robin) paylaşım.
+//
+// Zamanlayıcı "sırada hangi görev çalışmalı" kararını verir; bağlamın
+// değiştirilmesi `context::switch` ile mimarinin `ArchContext` uygulamasına
+// bırakılır. Zamanlayıcı mimarinin `TaskContext` yapısını tanımaz. Mimari
+// `initialize_tasking` içinde `context::enable` çağırana kadar geçiş yapılmaz.
+//
+// Her işlemcinin bir boşta görevi vardır (`spawn_idle`, bkz. `idle`); başka
+// seçilebilir görev yokken o çalışır. Görev işlevi döndüğünde mimarinin
+// `task_entry`'si `exit_current` ile görevi sonlandırır ve işlemciyi bırakır.
+//
+// Gerçek zamanlı sınıflar (RM/EDF) `sched_rt.rs` içindedir ve normal sınıftaki
+// tüm görevlerden önce değerlendirilir. Zaman bölümlemesi (`partition.rs`)
+// etkinse yalnızca geçerli pencerenin bölümündeki görevler seçilebilir.
+//
+// SMP: görev tablosu paylaşılır, çalışan görev (`current`) işlemci
+// başınadır. Normal sınıftaki her görev bir işlemcinin çalışma kuyruğundadır
+// (`Task::cpu`) ve işlemci yalnızca kendi kuyruğundan seçer. Görev
+// oluşturulurken, ve uyandırıldığında önceki kuyruğu artık izinli değilse,
+// izinli en kısa kuyruğa yerleştirilir. Gerçek zamanlı görevler kuyruğa
+// bağlı değildir; izinli herhangi bir işlemcide çalışabilir.
+//
+// Yük dengeleme çekme (pull) temellidir: yalnızca hazır (çalışmayan) ve
+// yakınlığı hedef işlemciyi içeren görevler taşınır, adaylardan en yüksek
+// öncelikli olanı seçilir.
+// * Periyodik: her işlemci `sched.balance_interval` tikte bir, en az iki
+//   görev daha uzun bir kuyruktan bir görev çeker.
+// * Boşta: kuyruğu boşalan işlemci başka kuyrukta bekleyen bir görevi çalar.
+//
+// Her görevin bir işlemci yakınlık maskesi (`set_affinity`) vardır. Çalışan
+// bir görevin maskesi o işlemciyi dışlarsa işlemciye yeniden zamanlama
+// IPI'si (`kick_cpu`) gönderilir; görev bırakılırken izinli bir kuyruğa
+// taşınır. IPI'yi mimari `set_resched_ipi_hook` ile kaydeder; işleyicisi
+// yalnızca kesmeyi onaylar, geçişi kesme dönüşündeki `preempt_if_needed` yapar.
+//
+// Kesinti (preemption): tik, uyandırma ve IPI yalnızca `NEED_RESCHED`'i
+// kurar. Her mimarinin tuzak dönüş yolu, kesme işleyicisi bittikten sonra
+// ve kesilen bağlama dönmeden önce `preempt_if_needed`'i çağırır (montaj
+// dönüş yolu olan portlarda `sched_preempt_from_trap` üzerinden). Kesme
+// yığınına geçen portlar (AMD64, ARMv9, RV64I) çerçeveyi önce kesilen
+// görevin yığınına taşır; kesme yığınında görev değiştirilmez. Kullanıcı
+// kipine dönüşte bu portlarda görevin çekirdek yığını olmadığından
+// kesinti bir sonraki çekirdek içi kesmeye kalır.
+//
+// Çalışma zamanı ayarları (`sysctl`): `sched.time_slice`, `sched.tick_hz`,
+// `sched.balance_interval`; ısıl kısma (`thermal`) normal sınıf zaman
+// dilimini `set_slice_scale` ile yüzde olarak küçültür.
+// izleme noktaları: `trace.sched_switch`, `trace.sched_wakeup`.
+
+use core::cell::UnsafeCell;
+use core::cmp::Reverse;
+use core::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use crate::platformgeneric::spinlock::IrqSafeSpinlock;
+use crate::percpu::{self, MAX_CPUS};
+use crate::platformgeneric::{KernelError, SystemConstants};
+use crate::sysctl::{Flag, U32};
+use crate::serial_println;
+use crate::uaccess::UserSlice;
+
+// -----------------------------------------------------------------------------
+// SABİTLER VE TİPLER
+// -----------------------------------------------------------------------------
+
+/// Görev tablosunun boyutu.
+pub const MAX_TASKS: usize = SystemConstants::MAX_TASKS;
+
+/// Normal sınıf için öncelik seviyesi sayısı. 0 en yüksek önceliktir.
+pub const PRIORITY_LEVELS: u8 = 32;
+
+/// Normal sınıfta bir görevin kesintiye uğramadan çalışabileceği tik sayısı
+/// (varsayılan; çalışma zamanında `sched.time_slice` ile değişir).
+pub const TIME_SLICE_TICKS: u32 = 10;
+
+crate::sysctl!(TIME_SLICE: U32 = U32::new(TIME_SLICE_TICKS, 1, 1000),
+    "sched.time_slice", "Normal sınıf zaman dilimi (tik)");
+
+/// Zaman dilimi ölçeği (yüzde, 1-100); ısıl kısma sırasında düşürülür.
+static SLICE_SCALE: AtomicU32 = AtomicU32::new(100);
+
+/// Normal sınıf zaman dilimini `sched.time_slice` değerinin yüzdesi olarak
+/// ölçekler (1-100; 100: kısma yok). Yeni dilimler bir sonraki yenilemede
+/// geçerli olur.
+pub fn set_slice_scale(percent: u32) -> Result<(), KernelError> {
+    if !(1..=100).contains(&percent) {
+        return Err(KernelError::InvalidArgument);
+    }
+    SLICE_SCALE.store(percent, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Geçerli zaman dilimi ölçeği (yüzde).
+pub fn slice_scale() -> u32 {
+    SLICE_SCALE.load(Ordering::Relaxed)
+}
+
+/// Ölçeklenmiş zaman dilimi (en az bir tik).
+fn time_slice() -> u32 {
+    (TIME_SLICE.get() * slice_scale() / 100).max(1)
+}
+
+/// Varsayılan periyodik yük dengeleme aralığı (tik).
+pub const BALANCE_INTERVAL_TICKS: u32 = 100;
+
+crate::sysctl!(BALANCE_INTERVAL: U32 = U32::new(BALANCE_INTERVAL_TICKS, 1, 10_000),
+    "sched.balance_interval", "Periyodik yük dengeleme aralığı (tik)");
+
+/// `sched.tick_hz` için desteklenen tik hızları (Hz).
+pub const TICK_RATES: [u32; 3] = [100, 250, 1000];
+
+crate::sysctl!(TICK_HZ: U32 = U32::new(SystemConstants::TIMER_TICK_HZ as u32, 100, 1000).with_apply(apply_tick_hz),
+    "sched.tick_hz", "Zamanlayıcı kesme hızı (Hz): 100, 250 veya 1000");
+
+crate::sysctl!(TRACE_SWITCH: Flag = Flag::new(false),
+    "trace.sched_switch", "Bağlam geçişlerini yazdırır");
+
+crate::sysctl!(TRACE_WAKEUP: Flag = Flag::new(false),
+    "trace.sched_wakeup", "Görev uyandırmalarını yazdırır");
+
+/// Görev tablosundaki indeks.
+pub type TaskId = usize;
+
+/// İşlemci kümesi: bit `n` işlemci `n`'dir.
+pub type CpuMask = u32;
+
+/// Tüm işlemciler (varsayılan yakınlık).
+pub const ALL_CPUS: CpuMask = (1 << MAX_CPUS) - 1;
+
+/// Görevin yaşam döngüsü durumu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskState {
+    /// Tablo girişi boş.
+    Free,
+    /// Çalışmaya hazır, işlemci bekliyor.
+    Ready,
+    /// Şu an işlemcide çalışıyor.
+    Running,
+    /// Bir olay (IPC, zamanlayıcı, periyot başlangıcı) bekliyor.
+    Blocked,
+    /// Sonlandı; kaynakları henüz toplanmadı.
+    Exited,
+}
+
+/// Görevin zamanlama sınıfı.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchedClass {
+    /// Sabit öncelikli, zaman dilimli.
+    Normal,
+    /// Periyodik gerçek zamanlı (bkz. `sched_rt`). Normal sınıftan her zaman önce seçilir.
+    RealTime,
+    /// İşlemcinin boşta görevi; yalnızca başka seçilebilir görev yokken çalışır.
+    Idle,
+}
+
+/// Bir görev kontrol bloğu.
+#[derive(Clone, Copy)]
+struct Task {
+    name: &'static str,
+    state: TaskState,
+    class: SchedClass,
+    priority: u8,
+    /// Görevin giriş noktası (mimari bağlamı oluştururken kullanır).
+    entry: usize,
+    /// Giriş noktasına ilk argüman olarak verilen değer.
+    arg: usize,
+    /// Geçerli zaman diliminde kalan tik.
+    slice_left: u32,
+    /// Toplam çalışma süresi (tik).
+    runtime_ticks: u64,
+    /// Göreve geçiş sayısı.
+    switches: u64,
+    /// Görevin çalışabileceği işlemciler.
+    affinity: CpuMask,
+    /// Görevin çalışma kuyruğu: çalıştığı veya sırasını beklediği işlemci.
+    cpu: u8,
+    /// Yazmaçları bir işlemcide: çalışıyor ya da bırakıldı ama bağlamı henüz
+    /// kaydedilmedi (bkz. `finish_switch`). Başka işlemci onu seçemez.
+    on_cpu: bool,
+    /// `exit` temizliği sürüyor; giriş `Exited` kalır, `finish_switch` onu boşaltmaz.
+    reaping: bool,
+}
+
+impl Task {
+    const fn empty() -> Self {
+        Task {
+            name: "",
+            state: TaskState::Free,
+            class: SchedClass::Normal,
+            priority: 0,
+            entry: 0,
+            arg: 0,
+            slice_left: 0,
+            runtime_ticks: 0,
+            switches: 0,
+            affinity: ALL_CPUS,
+            cpu: 0,
+            on_cpu: false,
+            reaping: false,
+        }
+    }
+
+    fn allows_cpu(&self, cpu: usize) -> bool {
+        self.affinity & (1 << cpu) != 0
+    }
+}
+
+/// Dışarıya verilen salt okunur görev bilgisi.
+#[derive(Debug, Clone, Copy)]
+pub struct TaskInfo {
+    pub name: &'static str,
+    pub state: TaskState,
+    pub class: SchedClass,
+    pub priority: u8,
+    pub entry: usize,
+    pub arg: usize,
+    pub runtime_ticks: u64,
+    pub switches: u64,
+    pub affinity: CpuMask,
+    /// Çalışma kuyruğunun işlemcisi.
+    pub cpu: usize,
+}
+
+/// Mimarinin zamanlayıcı kesmesini verilen hıza (Hz) yeniden programlayan kanca.
+pub type TickRateHook = fn(hz: u32) -> Result<(), KernelError>;
+
+/// Mimarinin `cpu` işlemcisine yeniden zamanlama IPI'si gönderen kanca.
+pub type ReschedIpiHook = fn(cpu: usize);
+
+// -----------------------------------------------------------------------------
+// GÖREV TABLOSU
+// -----------------------------------------------------------------------------
+
+struct Scheduler {
+    lock: IrqSafeSpinlock,
+    tasks: UnsafeCell<[Task; MAX_TASKS]>,
+    /// İşlemci başına çalışan görev.
+    current: UnsafeCell<[Option<TaskId>; MAX_CPUS]>,
+    /// İşlemci başına boşta görevi.
+    idle: UnsafeCell<[Option<TaskId>; MAX_CPUS]>,
+    tick_rate_hook: UnsafeCell<Option<TickRateHook>>,
+    resched_ipi_hook: UnsafeCell<Option<ReschedIpiHook>>,
+}
+
+// Tüm alanlara erişim `lock` ile korunur. Tik kesmesi ve kesmeden çağrılan
+// `wake` de kilidi aldığından kilit kesmeler kapalıyken tutulur.
+unsafe impl Sync for Scheduler {}
+
+static SCHED: Scheduler = Scheduler {
+    lock: IrqSafeSpinlock::new(),
+    tasks: UnsafeCell::new([Task::empty(); MAX_TASKS]),
+    current: UnsafeCell::new([None; MAX_CPUS]),
+    idle: UnsafeCell::new([None; MAX_CPUS]),
+    tick_rate_hook: UnsafeCell::new(None),
+    resched_ipi_hook: UnsafeCell::new(None),
+};
+
+/// Tik sayacı (açılıştan bu yana).
+static TICKS: AtomicU64 = AtomicU64::new(0);
+
+/// Bu işlemcide kesme dönüşünde yeniden zamanlama gerekiyor mu?
+crate::percpu!(NEED_RESCHED: AtomicBool = AtomicBool::new(false));
+
+/// `SWITCHED_FROM` boşken tutulan değer.
+const NO_TASK: usize = usize::MAX;
+
+/// Bu işlemcinin son geçişte bıraktığı, `on_cpu`'su henüz temizlenmemiş görev.
+crate::percpu!(SWITCHED_FROM: AtomicUsize = AtomicUsize::new(NO_TASK));
+
+crate::kernel_counter!(pub CONTEXT_SWITCHES, "sched.switches");
+crate::kernel_counter!(RESCHED_IPIS, "sched.resched_ipis");
+crate::kernel_counter!(MIGRATIONS, "sched.migrations");
+crate::kernel_counter!(BALANCE_PERIODIC, "sched.balance_periodic");
+crate::kernel_counter!(BALANCE_IDLE, "sched.balance_idle");
+
+/// Bu işlemcinin kuyruğuna yük dengelemeyle çekilen görev sayısı.
+crate::percpu!(PULLED: AtomicU64 = AtomicU64::new(0));
+
+/// Kilidi tutarak görev tablosu ve bu işlemcinin çalışan görevi üzerinde `f`'yi çalıştırır.
+fn with_tasks<R>(f: impl FnOnce(&mut [Task; MAX_TASKS], &mut Option<TaskId>) -> R) -> R {
+    let flags = SCHED.lock.lock();
+    // Kesmeler kapalı: işlemci numarası kilit bırakılana kadar değişmez
+    let cpu = percpu::cpu_id();
+    // SAFETY: Kilit tutulduğu sürece tabloya tek erişen biziz.
+    let result = unsafe { f(&mut *SCHED.tasks.get(), &mut (*SCHED.current.get())[cpu]) };
+    SCHED.lock.unlock(flags);
+    result
+}
+
+/// Mimarinin tik hızı değiştirme fonksiyonunu kaydeder. Kayıt yoksa
+/// `sched.tick_hz` ayarı `NotFound` ile reddedilir.
+pub fn set_tick_rate_hook(hook: TickRateHook) {
+    let flags = SCHED.lock.lock();
+    unsafe {
+        *SCHED.tick_rate_hook.get() = Some(hook);
+    }
+    SCHED.lock.unlock(flags);
+}
+
+/// Mimarinin yeniden zamanlama IPI'si gönderme fonksiyonunu kaydeder. Kayıt
+/// yoksa diğer işlemciler isteği bir sonraki tiklerinde görür.
+pub fn set_resched_ipi_hook(hook: ReschedIpiHook) {
+    let flags = SCHED.lock.lock();
+    unsafe {
+        *SCHED.resched_ipi_hook.get() = Some(hook);
+    }
+    SCHED.lock.unlock(flags);
+}
+
+/// `cpu` işlemcisinden yeniden zamanlama ister; başka bir işlemciyse IPI
+/// gönderilir. Çevrimdışı işlemci yok sayılır. Kesme bağlamından çağrılabilir.
+pub fn kick_cpu(cpu: usize) {
+    if cpu == percpu::cpu_id() {
+        NEED_RESCHED.get().store(true, Ordering::Release);
+        return;
+    }
+    let Some(flag) = NEED_RESCHED.get_for(cpu) else { return };
+    flag.store(true, Ordering::Release);
+    let flags = SCHED.lock.lock();
+    let hook = unsafe { *SCHED.resched_ipi_hook.get() };
+    SCHED.lock.unlock(flags);
+    if let Some(hook) = hook {
+        RESCHED_IPIS.inc();
+        hook(cpu);
+    }
+}
+
+/// Çevrimiçi işlemcilerin maskesi.
+fn online_mask() -> CpuMask {
+    (0..MAX_CPUS).filter(|&cpu| NEED_RESCHED.get_for(cpu).is_some()).fold(1, |mask, cpu| mask | 1 << cpu)
+}
+
+fn apply_tick_hz(hz: u32) -> Result<(), KernelError> {
+    if !TICK_RATES.contains(&hz) {
+        return Err(KernelError::InvalidArgument);
+    }
+    let flags = SCHED.lock.lock();
+    let hook = unsafe { *SCHED.tick_rate_hook.get() };
+    SCHED.lock.unlock(flags);
+    hook.ok_or(KernelError::NotFound)?(hz)
+}
+
+/// Geçerli tik hızı (Hz). Tik cinsinden verilmiş süreler (zamanlayıcılar,
+/// RT periyotları) hız değişince yeniden ölçeklenmez.
+pub fn tick_hz() -> u64 {
+    u64::from(TICK_HZ.get())
+}
+
+/// Yeni bir normal sınıf görevi oluşturur ve hazır kuyruğuna ekler. Giriş
+/// noktası `fn(u64)` imzalıdır ve argüman olarak 0 alır.
+///
+/// # Dönüş Değeri
+/// Görev kimliği; öncelik geçersizse `InvalidArgument`, tablo doluysa `OutOfMemoryStatic`.
+pub fn spawn(name: &'static str, entry: usize, priority: u8) -> Result<TaskId, KernelError> {
+    spawn_with_arg(name, entry, 0, priority)
+}
+
+/// `spawn` gibi; giriş noktası ilk çalıştığında `arg`'ı alır. Argüman görev
+/// bağlamındaki kalıcı yazmaçlarda taşınır ve mimarinin `task_start`
+/// basamağında ABI'nin ilk argüman yazmacına (rdi, x0, a0, r3, %o0) konur.
+pub fn spawn_with_arg(name: &'static str, entry: usize, arg: usize, priority: u8) -> Result<TaskId, KernelError> {
+    if priority >= PRIORITY_LEVELS {
+        return Err(KernelError::InvalidArgument);
+    }
+
+    let (id, cpu) = with_tasks(|tasks, _| {
+        let id = tasks.iter().position(|t| t.state == TaskState::Free).ok_or(KernelError::OutOfMemoryStatic)?;
+        let cpu = select_cpu(tasks, ALL_CPUS, percpu::cpu_id());
+        tasks[id] = Task {
+            name,
+            state: TaskState::Ready,
+            entry,
+            arg,
+            priority,
+            slice_left: time_slice(),
+            cpu: cpu as u8,
+            ..Task::empty()
+        };
+        Ok((id, cpu))
+    })?;
+
+    crate::latency::task_reset(id);
+    NEED_RESCHED.get().store(true, Ordering::Release);
+    kick_cpu(cpu);
+    Ok(id)
+}
+
+/// `cpu` işlemcisinin boşta görevini oluşturur. Boşta görev yalnızca o
+/// işlemcide çalışır, kuyruk uzunluğuna ve yük dengelemeye katılmaz; giriş
+/// noktası argüman olarak işlemci numarasını alır.
+///
+/// # Dönüş Değeri
+/// Görev kimliği; işlemci geçersizse `InvalidArgument`, işlemcinin boşta
+/// görevi zaten varsa `ResourceBusy`, tablo doluysa `OutOfMemoryStatic`.
+pub fn spawn_idle(cpu: usize, entry: usize) -> Result<TaskId, KernelError> {
+    if cpu >= MAX_CPUS {
+        return Err(KernelError::InvalidArgument);
+    }
+    with_tasks(|tasks, _| {
+        // SAFETY: Kilit `with_tasks` tarafından tutuluyor.
+        let idle = unsafe { &mut (*SCHED.idle.get())[cpu] };
+        if idle.is_some() {
+            return Err(KernelError::ResourceBusy);
+        }
+        let id = tasks.iter().position(|t| t.state == TaskState::Free).ok_or(KernelError::OutOfMemoryStatic)?;
+        tasks[id] = Task {
+            name: "idle",
+            state: TaskState::Ready,
+            class: SchedClass::Idle,
+            entry,
+            arg: cpu,
+            priority: PRIORITY_LEVELS - 1,
+            affinity: 1 << cpu,
+            cpu: cpu as u8,
+            ..Task::empty()
+        };
+        *idle = Some(id);
+        Ok(id)
+    })
+}
+
+/// Görevin zamanlama sınıfını değiştirir (`sched_rt` kaydı tarafından kullanılır).
+pub fn set_class(id: TaskId, class: SchedClass) -> Result<(), KernelError> {
+    with_tasks(|tasks, _| match tasks.get_mut(id) {
+        Some(task) if task.class == SchedClass::Idle => Err(KernelError::InvalidArgument),
+        Some(task) if task.state != TaskState::Free => {
+            task.class = class;
+            Ok(())
+        }
+        _ => Err(KernelError::NotFound),
+    })
+}
+
+/// Görevi bekleme durumuna alır. Çalışan görev kendini bloklarsa yeniden zamanlama istenir.
+pub fn block(id: TaskId) -> Result<(), KernelError> {
+    let result = with_tasks(|tasks, _| match tasks.get_mut(id) {
+        Some(task) if task.class == SchedClass::Idle => Err(KernelError::InvalidArgument),
+        Some(task) if matches!(task.state, TaskState::Ready | TaskState::Running) => {
+            task.state = TaskState::Blocked;
+            Ok(())
+        }
+        Some(task) if task.state == TaskState::Blocked => Ok(()),
+        _ => Err(KernelError::NotFound),
+    });
+    NEED_RESCHED.get().store(true, Ordering::Release);
+    result
+}
+
+/// Bekleyen bir görevi hazır duruma getirir. Kesme bağlamından çağrılabilir.
+pub fn wake(id: TaskId) -> Result<(), KernelError> {
+    let cpu = percpu::cpu_id();
+    let result = with_tasks(|tasks, _| match tasks.get(id).map(|t| t.state) {
+        Some(TaskState::Blocked) => {
+            let task = &mut tasks[id];
+            task.state = TaskState::Ready;
+            crate::latency::task_woken(id);
+            if TRACE_WAKEUP.get() {
+                serial_println!("[TRACE] sched_wakeup görev={} tik={}", id, TICKS.load(Ordering::Relaxed));
+            }
+            // Önbelleği sıcak olan önceki kuyruk, izinli ve çevrimiçiyse korunur
+            let previous = usize::from(task.cpu);
+            if task.allows_cpu(previous) && online_mask() & (1 << previous) != 0 {
+                return Ok(Some(previous));
+            }
+            let target = select_cpu(tasks, tasks[id].affinity, cpu);
+            tasks[id].cpu = target as u8;
+            MIGRATIONS.inc();
+            Ok(Some(target))
+        }
+        Some(TaskState::Ready | TaskState::Running) => Ok(None),
+        _ => Err(KernelError::NotFound),
+    });
+    NEED_RESCHED.get().store(true, Ordering::Release);
+    if let Ok(Some(target)) = result {
+        kick_cpu(target);
+    }
+    result.map(|_| ())
+}
+
+/// Görevi sonlandırır ve tablo girişini serbest bırakır.
+///
+/// Giriş temizlik bitene kadar `Exited` kalır; böylece başka bir işlemcideki
+/// `spawn` onu temizlik kancaları çalışırken yeniden kullanamaz. Yığını
+/// kullanılan görev (çalışan veya bağlamı henüz kaydedilmemiş, bkz.
+/// `Task::on_cpu`) zombi kalır: bağlamı ve yığını, işlemci ondan geçtikten
+/// sonra `finish_switch` bırakır. Girişi `exit` ile `finish_switch`'ten
+/// hangisi sonra biterse o boşaltır. Görev kendini sonlandırabilir; ardından
+/// `schedule` çağrılmalıdır (bkz. `exit_current`).
+pub fn exit(id: TaskId) -> Result<(), KernelError> {
+    let result = with_tasks(|tasks, current| match tasks.get_mut(id) {
+        // Boşta görevi sonlandırılamaz
+        Some(task) if task.class == SchedClass::Idle => Err(KernelError::InvalidArgument),
+        Some(task) if !matches!(task.state, TaskState::Free | TaskState::Exited) => {
+            if task.class == SchedClass::RealTime {
+                crate::sched_rt::forget(id);
+            }
+            // Başka bir işlemcide çalışıyorsa o işlemci görevi bırakmalıdır
+            let elsewhere = (task.state == TaskState::Running && *current != Some(id)).then_some(usize::from(task.cpu));
+            task.state = TaskState::Exited;
+            task.reaping = true;
+            Ok(elsewhere)
+        }
+        _ => Err(KernelError::NotFound),
+    });
+    if let Ok(Some(cpu)) = result {
+        kick_cpu(cpu);
+    }
+    let result = result.map(|_| ());
+    if result.is_ok() {
+        // Tablo girişi yeniden kullanılacağı için bölüm üyeliği ve bekleme
+        // grafiği kayıtları da sıfırlanır
+        crate::partition::assign(id, None).ok();
+        crate::deadlock::forget(id);
+        crate::process::task_exited(id);
+        crate::mmap::task_exited(id);
+        crate::console::task_exited(id);
+        crate::syscall::task_exited(id);
+        // Yığın hâlâ kullanılıyorsa girişi `finish_switch` boşaltır
+        let zombie = with_tasks(|tasks, _| {
+            tasks[id].reaping = false;
+            tasks[id].on_cpu
+        });
+        if !zombie {
+            crate::context::release(id);
+            with_tasks(|tasks, _| tasks[id].state = TaskState::Free);
+        }
+    }
+    NEED_RESCHED.get().store(true, Ordering::Release);
+    result
+}
+
+/// Çalışan görevi sonlandırır ve işlemciyi bırakır; dönmez. Görev işlevi
+/// döndüğünde mimarinin `task_entry`'si çağırır. Görev zombi olarak
+/// bırakılır; yığını bu çağrı hâlâ kullandığından geçişten sonra toplanır.
+pub fn exit_current() -> ! {
+    if let Some(id) = current() {
+        exit(id).ok();
+    }
+    schedule();
+    // Bağlam geçişi kapalıysa (açılış) görev dışı akış gibi boşta beklenir
+    crate::idle::idle_loop()
+}
+
+/// Çalışan görevin kimliği.
+pub fn current() -> Option<TaskId> {
+    with_tasks(|_, current| *current)
+}
+
+/// Kilidi beklemeden geçerli görevi döndürür; kilit tutuluyorsa `None`.
+/// Panik yolu içindir: kilidi tutan bu işlemcinin kendisi olabilir.
+pub fn try_current() -> Option<Option<TaskId>> {
+    let flags = SCHED.lock.try_lock()?;
+    // SAFETY: Kilit tutuluyor.
+    let current = unsafe { (*SCHED.current.get())[percpu::cpu_id()] };
+    SCHED.lock.unlock(flags);
+    Some(current)
+}
+
+/// Görev hakkında salt okunur bilgi.
+pub fn task_info(id: TaskId) -> Option<TaskInfo> {
+    with_tasks(|tasks, _| {
+        tasks.get(id).filter(|t| t.state != TaskState::Free).map(|t| TaskInfo {
+            name: t.name,
+            state: t.state,
+            class: t.class,
+            priority: t.priority,
+            entry: t.entry,
+            arg: t.arg,
+            runtime_ticks: t.runtime_ticks,
+            switches: t.switches,
+            affinity: t.affinity,
+            cpu: usize::from(t.cpu),
+        })
+    })
+}
+
+/// Görevin çalışabileceği işlemcileri belirler. Hazır görevin kuyruğu artık
+/// izinli değilse izinli en kısa kuyruğa taşınır. Görev izin verilmeyen bir
+/// işlemcide çalışıyorsa o işlemci yeniden zamanlar (IPI) ve görev izinli
+/// bir işlemciye geçer; çağıran görev kendini taşıyorsa dönüşte izinli bir
+/// işlemcidedir.
+///
+/// # Hatalar
+/// * `InvalidArgument`: Maske hiçbir çevrimiçi işlemciyi içermiyor.
+/// * `NotFound`: Görev yok.
+pub fn set_affinity(id: TaskId, mask: CpuMask) -> Result<(), KernelError> {
+    let mask = mask & ALL_CPUS;
+    if mask & online_mask() == 0 {
+        return Err(KernelError::InvalidArgument);
+    }
+    let running_on = with_tasks(|tasks, _| {
+        let task = tasks.get_mut(id).filter(|t| t.state != TaskState::Free).ok_or(KernelError::NotFound)?;
+        task.affinity = mask;
+        let cpu = usize::from(task.cpu);
+        if task.allows_cpu(cpu) {
+            return Ok(None);
+        }
+        // Çalışan görevi `schedule` bırakırken izinli bir kuyruğa taşır
+        if task.state == TaskState::Running {
+            return Ok(Some(cpu));
+        }
+        tasks[id].cpu = select_cpu(tasks, mask, cpu) as u8;
+        MIGRATIONS.inc();
+        Ok(None)
+    })?;
+    let Some(cpu) = running_on else { return Ok(()) };
+
+    kick_cpu(cpu);
+    if cpu == percpu::cpu_id() && current() == Some(id) && !crate::irqstack::in_interrupt() {
+        schedule();
+    }
+    Ok(())
+}
+
+/// Görevin çalışmaya hazır (veya çalışıyor) olup olmadığı.
+pub fn is_runnable(id: TaskId) -> bool {
+    with_tasks(|tasks, _| tasks.get(id).is_some_and(|t| matches!(t.state, TaskState::Ready | TaskState::Running)))
+}
+
+// -----------------------------------------------------------------------------
+// TİK VE SEÇİM
+// -----------------------------------------------------------------------------
+
+/// Açılıştan bu yana geçen tik sayısı.
+pub fn ticks() -> u64 {
+    TICKS.load(Ordering::Acquire)
+}
+
+/// Zamanlayıcı kesmesinden her tikte çağrılır: süre muhasebesi, zaman dilimi
+/// ve gerçek zamanlı iş salımları. Gerekirse `NEED_RESCHED` ayarlanır.
+pub fn tick() {
+    // Başka bir işlemci panikteyse burada durulur (durdurma IPI'si yoksa)
+    crate::panic::stop_if_halted();
+    let now = TICKS.fetch_add(1, Ordering::AcqRel) + 1;
+
+    let (running, running_rt) = with_tasks(|tasks, current| {
+        let Some(id) = *current else { return (None, None) };
+        let task = &mut tasks[id];
+        task.runtime_ticks += 1;
+
+        if task.class == SchedClass::RealTime {
+            return (Some(id), Some(id));
+        }
+        task.slice_left = task.slice_left.saturating_sub(1);
+        if task.slice_left == 0 {
+            task.slice_left = time_slice();
+            NEED_RESCHED.get().store(true, Ordering::Release);
+        }
+        (Some(id), None)
+    });
+
+    // Bölüm penceresi değiştiyse çalışan görev bir sonraki fırsatta kesilir
+    if crate::partition::tick(running) {
+        NEED_RESCHED.get().store(true, Ordering::Release);
+    }
+    if crate::sched_rt::tick(now, running_rt) {
+        NEED_RESCHED.get().store(true, Ordering::Release);
+    }
+
+    // İşlemciler aynı tikte dengelemesin diye aralık işlemci numarasıyla kaydırılır
+    let cpu = percpu::cpu_id();
+    if (now + cpu as u64) % u64::from(BALANCE_INTERVAL.get()) == 0 && with_tasks(|tasks, _| pull(tasks, cpu, false)).is_some() {
+        BALANCE_PERIODIC.inc();
+        NEED_RESCHED.get().store(true, Ordering::Release);
+    }
+    crate::timer::tick(now);
+}
+
+/// `cpu` kuyruğundaki hazır veya çalışan normal sınıf görev sayısı.
+fn queue_len(tasks: &[Task; MAX_TASKS], cpu: usize) -> usize {
+    tasks
+        .iter()
+        .filter(|t| t.class == SchedClass::Normal && usize::from(t.cpu) == cpu)
+        .filter(|t| matches!(t.state, TaskState::Ready | TaskState::Running))
+        .count()
+}
+
+/// `affinity` içindeki çevrimiçi işlemcilerden kuyruğu en kısa olanı seçer;
+/// eşitlikte `prefer`, sonra en küçük numara.
+fn select_cpu(tasks: &[Task; MAX_TASKS], affinity: CpuMask, prefer: usize) -> usize {
+    let allowed = match affinity & online_mask() {
+        // İzinli işlemciler henüz açılmadı: görev ilkinin kuyruğunda bekler
+        0 => affinity,
+        mask => mask,
+    };
+    (0..MAX_CPUS)
+        .filter(|&cpu| allowed & (1 << cpu) != 0)
+        .min_by_key(|&cpu| (queue_len(tasks, cpu), cpu != prefer, cpu))
+        .unwrap_or(prefer)
+}
+
+/// Başka bir kuyruktan `cpu` kuyruğuna bir görev çeker. Adaylar hazır
+/// (çalışmayan), normal sınıf ve yakınlığı `cpu`'yu içeren görevlerdir; en
+/// yüksek öncelikli, eşitlikte en uzun kuyruktaki aday seçilir. `idle`
+/// değilse yalnızca bu işlemcininkinden en az iki uzun kuyruklardan çekilir.
+fn pull(tasks: &mut [Task; MAX_TASKS], cpu: usize, idle: bool) -> Option<TaskId> {
+    let mut lens = [0; MAX_CPUS];
+    for (other, len) in lens.iter_mut().enumerate() {
+        *len = queue_len(tasks, other);
+    }
+    let mine = lens[cpu];
+    let id = (0..MAX_TASKS)
+        .filter(|&id| {
+            let task = &tasks[id];
+            let source = usize::from(task.cpu);
+            task.class == SchedClass::Normal
+                && task.state == TaskState::Ready
+                && !task.on_cpu
+                && source != cpu
+                && task.allows_cpu(cpu)
+                && (idle || lens[source] >= mine + 2)
+        })
+        .min_by_key(|&id| (tasks[id].priority, Reverse(lens[usize::from(tasks[id].cpu)])))?;
+    tasks[id].cpu = cpu as u8;
+    MIGRATIONS.inc();
+    PULLED.get().fetch_add(1, Ordering::Relaxed);
+    Some(id)
+}
+
+/// Görev bu işlemcide seçilebilir mi? Hazır (bağlamı kaydedilmiş) veya bu
+/// işlemcide çalışan, yakınlığı bu işlemciyi içeren ve bölüm penceresinin
+/// izin verdiği görevler.
+fn eligible(tasks: &[Task; MAX_TASKS], id: TaskId, current: Option<TaskId>, cpu: usize) -> bool {
+    let task = &tasks[id];
+    let runnable =
+        (task.state == TaskState::Ready && !task.on_cpu) || (task.state == TaskState::Running && current == Some(id));
+    runnable && task.allows_cpu(cpu) && crate::partition::allows(id)
+}
+
+/// Bu işlemcinin kuyruğundan bir sonraki normal sınıf görevini seçer: en yüksek öncelik, eşitlikte
+/// geçerli görevden sonraki ilk görev (round-robin).
+fn pick_normal(tasks: &[Task; MAX_TASKS], current: Option<TaskId>, cpu: usize) -> Option<TaskId> {
+    let start = current.map_or(0, |id| id + 1);
+    let mut best: Option<TaskId> = None;
+
+    for offset in 0..MAX_TASKS {
+        let id = (start + offset) % MAX_TASKS;
+        let task = &tasks[id];
+        if task.class != SchedClass::Normal || usize::from(task.cpu) != cpu || !eligible(tasks, id, current, cpu) {
+            continue;
+        }
+        if best.is_none_or(|b| task.priority < tasks[b].priority) {
+            best = Some(id);
+        }
+    }
+    best
+}
+
+/// Bu işlemcinin boşta görevi; yoksa veya seçilebilir durumda değilse `None`.
+fn pick_idle(tasks: &[Task; MAX_TASKS], current: Option<TaskId>, cpu: usize) -> Option<TaskId> {
+    // SAFETY: Kilit çağıran (`schedule`) tarafından tutuluyor.
+    let id = unsafe { (*SCHED.idle.get())[cpu] }?;
+    let state = tasks[id].state;
+    ((state == TaskState::Ready && !tasks[id].on_cpu) || (state == TaskState::Running && current == Some(id))).then_some(id)
+}
+
+/// Sıradaki görevi seçer ve gerekiyorsa ona geçer (`context::switch`).
+/// Seçilebilir görev yoksa işlemcinin boşta görevine geçilir. Kesmeler
+/// seçimden geçişin sonuna kadar kapalıdır: aradaki bir kesmenin dönüşünde
+/// çağrılan `preempt_if_needed`, henüz geçilmemiş görevi çalışıyor sanırdı.
+///
+/// Bırakılan görev hazır işaretlenir ama `on_cpu` kalır: bağlamı
+/// `context::switch` içinde kaydedilene kadar başka bir işlemci onu seçip
+/// aynı yığında çalıştıramaz. İşaret geçişten sonra yeni görevin ilk işi
+/// olan `finish_switch` ile temizlenir.
+///
+/// # Dönüş Değeri
+/// Çalışacak görev; hazır görev yoksa (boşta görevi seçildiyse de) `None`
+/// (çağıran boşta bekler, bkz. `idle::idle_loop`).
+pub fn schedule() -> Option<TaskId> {
+    let flags = crate::irqoff::save();
+    NEED_RESCHED.get().store(false, Ordering::Release);
+
+    // Gerçek zamanlı sınıf önce: en erken son tarih (EDF) veya en kısa periyot (RM)
+    let rt_next = crate::sched_rt::pick();
+    let cpu = percpu::cpu_id();
+
+    let (prev, next, start, moved, idle) = with_tasks(|tasks, current| {
+        let prev = *current;
+        let next = rt_next.filter(|&id| eligible(tasks, id, prev, cpu)).or_else(|| {
+            pick_normal(tasks, prev, cpu).or_else(|| {
+                // Kuyruk boş: başka bir kuyrukta bekleyen görevi çal
+                pull(tasks, cpu, true)?;
+                BALANCE_IDLE.inc();
+                pick_normal(tasks, prev, cpu)
+            })
+        })
+        .or_else(|| pick_idle(tasks, prev, cpu));
+
+        let mut moved = None;
+        if let Some(p) = prev.filter(|&p| Some(p) != next) {
+            SWITCHED_FROM.get().store(p, Ordering::Relaxed);
+            if tasks[p].state == TaskState::Running {
+                tasks[p].state = TaskState::Ready;
+                // Yakınlığı bu işlemciyi artık içermiyorsa izinli bir kuyruğa taşınır
+                if !tasks[p].allows_cpu(cpu) {
+                    let target = select_cpu(tasks, tasks[p].affinity, cpu);
+                    tasks[p].cpu = target as u8;
+                    MIGRATIONS.inc();
+                    moved = Some(target);
+                }
+            }
+        }
+        if let Some(n) = next {
+            if Some(n) != prev {
+                tasks[n].switches += 1;
+                CONTEXT_SWITCHES.inc();
+                crate::latency::task_dispatched(n);
+                if TRACE_SWITCH.get() {
+                    serial_println!("[TRACE] sched_switch {:?} -> {} tik={}", prev, n, TICKS.load(Ordering::Relaxed));
+                }
+            }
+            tasks[n].state = TaskState::Running;
+            tasks[n].cpu = cpu as u8;
+            tasks[n].on_cpu = true;
+        }
+        *current = next;
+        let start = next.map_or((0, 0), |n| (tasks[n].entry, tasks[n].arg));
+        let idle = next.is_some_and(|n| tasks[n].class == SchedClass::Idle);
+        (prev, next, start, moved, idle)
+    });
+
+    if let Some(target) = moved {
+        kick_cpu(target);
+    }
+
+    if next.is_some() && !idle {
+        // Kesme dönüşünde boşta beklemeden doğrudan göreve geçiliyor olabilir
+        crate::idle::leave();
+    }
+    if let Some(n) = next {
+        crate::context::switch(prev, n, start.0, start.1);
+    }
+    // Buraya başka bir görevden (ve belki başka bir işlemcide) dönülmüş olabilir
+    finish_switch();
+    crate::irqoff::restore(flags);
+    next.filter(|_| !idle)
+}
+
+/// Geçişin ikinci yarısı: bu işlemcinin son bıraktığı görevin bağlamı artık
+/// kaydedildi, `on_cpu` temizlenir. `schedule`'da geçiş döndükten sonra ve
+/// ilk kez çalışan görevde (`context::task_started`) kesmeler kapalıyken
+/// çağrılır. Görev bu arada başka bir kuyruk için hazır olduysa o işlemci
+/// onu atlamış olabilir; yeniden zamanlama istenir. Sonlanmış (zombi) görevin
+/// bağlamı ve yığını burada bırakılır, ardından tablo girişi boşalır; `exit`
+/// temizliği sürüyorsa (`reaping`) bunu `exit` yapar.
+pub fn finish_switch() {
+    let prev = SWITCHED_FROM.get().swap(NO_TASK, Ordering::Relaxed);
+    if prev == NO_TASK {
+        return;
+    }
+    let cpu = percpu::cpu_id();
+    let (waiting, zombie) = with_tasks(|tasks, _| {
+        let task = &mut tasks[prev];
+        task.on_cpu = false;
+        let waiting = (task.state == TaskState::Ready && usize::from(task.cpu) != cpu).then_some(usize::from(task.cpu));
+        (waiting, task.state == TaskState::Exited && !task.reaping)
+    });
+    if let Some(target) = waiting {
+        kick_cpu(target);
+    }
+    if zombie {
+        // Giriş boşalmadan bağlam bırakılır: yeniden kullanan görev eski bağlamı görmez
+        crate::context::release(prev);
+        with_tasks(|tasks, _| tasks[prev].state = TaskState::Free);
+    }
+}
+
+/// Kesme dönüşünde, kesme yığınından çıktıktan sonra çağrılır: bekleyen
+/// yeniden zamanlama isteği varsa uygular. Bir işleyicinin içindeyken
+/// görev değiştirilmez; aksi halde görev kesme yığınında kalırdı. Kesmeler
+/// kapalı çağrılır; seçilen görev kendi kesme durumuyla devam eder, bu görev
+/// yeniden seçildiğinde buradan döner ve tuzak dönüşü sürer.
+pub fn preempt_if_needed() {
+    if crate::irqstack::in_interrupt() {
+        return;
+    }
+    if NEED_RESCHED.get().load(Ordering::Acquire) {
+        schedule();
+    }
+}
+
+/// Montaj tuzak dönüş yollarının çağırdığı `preempt_if_needed`.
+#[no_mangle]
+pub extern "C" fn sched_preempt_from_trap() {
+    preempt_if_needed();
+}
+
+// -----------------------------------------------------------------------------
+// SİSTEM ÇAĞRILARI
+// -----------------------------------------------------------------------------
+
+/// Kullanıcıya görünen `cpu_set_t` boyutu (bir `unsigned long`).
+const CPU_SET_BYTES: usize = 8;
+
+/// `sched_setaffinity(pid, len, *mask)`: `pid` 0 ise çağıran görev.
+/// `len` baytından ilk `CPU_SET_BYTES` kadarı okunur.
+pub fn sys_sched_setaffinity(pid: usize, mask: UserSlice) -> Result<usize, KernelError> {
+    let mut raw = [0u8; CPU_SET_BYTES];
+    mask.read(&mut raw)?;
+    let mask = u64::from_ne_bytes(raw);
+    let id = if pid == 0 { current().ok_or(KernelError::NotFound)? } else { pid };
+    set_affinity(id, (mask & u64::from(ALL_CPUS)) as CpuMask)?;
+    Ok(0)
+}
+
+/// `sched_getaffinity(pid, len, *mask)`: yazılan bayt sayısını döndürür.
+pub fn sys_sched_getaffinity(pid: usize, mask: UserSlice) -> Result<usize, KernelError> {
+    if mask.len() < CPU_SET_BYTES {
+        return Err(KernelError::InvalidArgument);
+    }
+    let id = if pid == 0 { current().ok_or(KernelError::NotFound)? } else { pid };
+    let affinity = task_info(id).ok_or(KernelError::NotFound)?.affinity;
+    mask.write(&u64::from(affinity).to_ne_bytes())?;
+    Ok(CPU_SET_BYTES)
+}
+
+// -----------------------------------------------------------------------------
+// KABUK KOMUTU
+// -----------------------------------------------------------------------------
+
+fn ps_command(_args: &[&str]) -> Result<(), KernelError> {
+    serial_println!("  ID AD               DURUM    SINIF    ÖNC  SÜRE(tik)  GEÇİŞ");
+    for id in 0..MAX_TASKS {
+        if let Some(info) = task_info(id) {
+            serial_println!(
+                "  {:>2} {:<16} {:<8} {:<8} {:>3} {:>10} {:>6}",
+                id,
+                info.name,
+                match info.state {
+                    TaskState::Ready => "hazır",
+                    TaskState::Running => "çalışıyor",
+                    TaskState::Blocked => "bekliyor",
+                    TaskState::Exited => "bitti",
+                    TaskState::Free => "-",
+                },
+                match info.class {
+                    SchedClass::Normal => "normal",
+                    SchedClass::RealTime => "rt",
+                    SchedClass::Idle => "idle",
+                },
+                info.priority,
+                info.runtime_ticks,
+                info.switches
+            );
+        }
+    }
+    Ok(())
+}
+
+crate::shell_command!("ps", "Görev listesini gösterir", ps_command);
+
+/// `taskset <görev> [maske]`: yakınlığı gösterir veya ayarlar (maske onaltılık, örn. `0x3`).
+fn taskset_command(args: &[&str]) -> Result<(), KernelError> {
+    let id: TaskId = args.get(1).and_then(|s| s.parse().ok()).ok_or(KernelError::InvalidArgument)?;
+    if let Some(text) = args.get(2) {
+        let digits = text.strip_prefix("0x").unwrap_or(text);
+        let mask = CpuMask::from_str_radix(digits, 16).map_err(|_| KernelError::InvalidArgument)?;
+        set_affinity(id, mask)?;
+    }
+    let info = task_info(id).ok_or(KernelError::NotFound)?;
+    serial_println!("  görev {} ({}): yakınlık {:#x}, işlemci {}", id, info.name, info.affinity, info.cpu);
+    Ok(())
+}
+
+/// `runq`: işlemci başına kuyruk uzunluğu, çalışan görev ve çekilen görev sayısı.
+fn runq_command(_args: &[&str]) -> Result<(), KernelError> {
+    let online = online_mask();
+    let (lens, running) = with_tasks(|tasks, _| {
+        let mut lens = [0; MAX_CPUS];
+        for (cpu, len) in lens.iter_mut().enumerate() {
+            *len = queue_len(tasks, cpu);
+        }
+        // SAFETY: Kilit `with_tasks` tarafından tutuluyor.
+        (lens, unsafe { *SCHED.current.get() })
+    });
+    serial_println!("  İŞL KUYRUK ÇALIŞAN  ÇEKİLEN");
+    for cpu in (0..MAX_CPUS).filter(|&cpu| online & (1 << cpu) != 0) {
+        let pulled = PULLED.get_for(cpu).map_or(0, |p| p.load(Ordering::Relaxed));
+        match running[cpu] {
+            Some(id) => serial_println!("  {:>3} {:>6} {:>7} {:>8}", cpu, lens[cpu], id, pulled),
+            None => serial_println!("  {:>3} {:>6} {:>7} {:>8}", cpu, lens[cpu], "-", pulled),
+        }
+    }
+    serial_println!(
+        "  taşıma {}, periyodik {}, boşta {}",
+        MIGRATIONS.get(),
+        BALANCE_PERIODIC.get(),
+        BALANCE_IDLE.get()
+    );
+    Ok(())
+}
+
+crate::shell_command!("runq", "İşlemci çalışma kuyruklarını ve yük dengelemeyi gösterir", runq_command);
+
+crate::shell_command!("taskset", "Görevin işlemci yakınlığı (taskset <görev> [maske])", taskset_command);
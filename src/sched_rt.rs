@@ -0,0 +1,462 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/sched_rt.rs
+// Periyodik gerçek zamanlı zamanlama sınıfları: Rate-Monotonic (RM) ve
+// Earliest-Deadline-First (EDF).
+//
+// Her gerçek zamanlı görev (periyot, en kötü çalışma süresi (WCET), göreli son
+// tarih) üçlüsüyle kaydedilir. Kayıt sırasında kabul denetimi yapılır: görev
+// kümesinin çizelgelenebilirliği bozuluyorsa kayıt reddedilir. Her tikte
+// periyodu gelen işler salınır (görev uyandırılır), son tarih kaçırmaları ve
+// WCET aşımları sayılır. Görev her işin sonunda `wait_next_period` çağırır.
+//
+// Tüm süreler tik cinsindendir (bkz. `SystemConstants::TIMER_TICK_HZ`).
+
+use core::cell::UnsafeCell;
+use crate::platformgeneric::spinlock::IrqSafeSpinlock;
+use crate::platformgeneric::KernelError;
+use crate::sched::{self, SchedClass, TaskId, MAX_TASKS};
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// TİPLER
+// -----------------------------------------------------------------------------
+
+/// Gerçek zamanlı görevler arasında seçim politikası.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RtPolicy {
+    /// Sabit öncelik: periyodu kısa olan önce (Liu & Layland).
+    RateMonotonic,
+    /// Dinamik öncelik: mutlak son tarihi en erken olan önce.
+    EarliestDeadlineFirst,
+}
+
+/// Periyodik görev parametreleri (tik).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RtParams {
+    /// İş salım aralığı.
+    pub period: u64,
+    /// En kötü durum çalışma süresi.
+    pub wcet: u64,
+    /// Salımdan itibaren göreli son tarih (`wcet <= deadline <= period`).
+    pub deadline: u64,
+}
+
+impl RtParams {
+    /// Son tarihi periyoda eşit (örtük son tarihli) parametreler.
+    pub const fn implicit(period: u64, wcet: u64) -> Self {
+        RtParams { period, wcet, deadline: period }
+    }
+}
+
+/// Kayıtlı bir gerçek zamanlı görevin iş durumu.
+#[derive(Clone, Copy)]
+struct RtTask {
+    active: bool,
+    params: RtParams,
+    /// Bir sonraki işin salım anı.
+    next_release: u64,
+    /// Geçerli işin mutlak son tarihi.
+    abs_deadline: u64,
+    /// Geçerli işte kalan WCET bütçesi.
+    budget_left: u64,
+    /// Salınmış ve henüz tamamlanmamış iş sayısı.
+    backlog: u32,
+    jobs: u64,
+    deadline_misses: u64,
+    overruns: u64,
+}
+
+impl RtTask {
+    const fn empty() -> Self {
+        RtTask {
+            active: false,
+            params: RtParams { period: 0, wcet: 0, deadline: 0 },
+            next_release: 0,
+            abs_deadline: 0,
+            budget_left: 0,
+            backlog: 0,
+            jobs: 0,
+            deadline_misses: 0,
+            overruns: 0,
+        }
+    }
+}
+
+/// Çizelgelenebilirlik analizi ve çalışma zamanı sayaçları.
+#[derive(Debug, Clone, Copy)]
+pub struct RtStats {
+    pub policy: RtPolicy,
+    /// Kayıtlı gerçek zamanlı görev sayısı.
+    pub tasks: usize,
+    /// Toplam kullanım Σ C/T (milyonda).
+    pub utilization_ppm: u64,
+    /// Toplam yoğunluk Σ C/min(D, T) (milyonda).
+    pub density_ppm: u64,
+    /// Politikanın yeterli kullanım sınırı (RM: n(2^(1/n) - 1), EDF: 1).
+    pub bound_ppm: u64,
+    /// Görev kümesi kabul denetimini geçiyor mu?
+    pub schedulable: bool,
+    pub deadline_misses: u64,
+    pub overruns: u64,
+}
+
+// -----------------------------------------------------------------------------
+// TABLO
+// -----------------------------------------------------------------------------
+
+struct RtTable {
+    lock: IrqSafeSpinlock,
+    policy: UnsafeCell<RtPolicy>,
+    tasks: UnsafeCell<[RtTask; MAX_TASKS]>,
+}
+
+// Tüm alanlara erişim `lock` ile korunur. Tik kesmesi (`tick`) de kilidi
+// aldığından kilit kesmeler kapalıyken tutulur.
+unsafe impl Sync for RtTable {}
+
+static RT: RtTable = RtTable {
+    lock: IrqSafeSpinlock::new(),
+    policy: UnsafeCell::new(RtPolicy::EarliestDeadlineFirst),
+    tasks: UnsafeCell::new([RtTask::empty(); MAX_TASKS]),
+};
+
+// Kilit sırası: `sched` kilidi tutulurken bu kilit alınabilir, tersi yasaktır.
+// Bu yüzden aşağıdaki fonksiyonlar `sched` çağrılarını kilidi bıraktıktan sonra yapar.
+fn with_rt<R>(f: impl FnOnce(&mut RtPolicy, &mut [RtTask; MAX_TASKS]) -> R) -> R {
+    let flags = RT.lock.lock();
+    // SAFETY: Kilit tutulduğu sürece tabloya tek erişen biziz.
+    let result = unsafe { f(&mut *RT.policy.get(), &mut *RT.tasks.get()) };
+    RT.lock.unlock(flags);
+    result
+}
+
+// -----------------------------------------------------------------------------
+// KABUL DENETİMİ
+// -----------------------------------------------------------------------------
+
+const PPM: u64 = 1_000_000;
+
+/// n(2^(1/n) - 1) Liu & Layland sınırı (milyonda), n = 1..=32.
+const RM_BOUND_PPM: [u64; 32] = [
+    1000000, 828427, 779763, 756828, 743492, 734772, 728627, 724062, 720538, 717735, 715452,
+    713557, 711959, 710593, 709412, 708381, 707472, 706666, 705946, 705298, 704713, 704182,
+    703698, 703254, 702846, 702469, 702121, 701798, 701497, 701217, 700955, 700709,
+];
+
+/// ln 2: n sonsuza giderken RM sınırı.
+const RM_BOUND_LIMIT_PPM: u64 = 693147;
+
+fn rm_bound_ppm(n: usize) -> u64 {
+    match n {
+        0 => PPM,
+        1..=32 => RM_BOUND_PPM[n - 1],
+        _ => RM_BOUND_LIMIT_PPM,
+    }
+}
+
+/// Görev kümesinin kullanımını ve yoğunluğunu (milyonda) hesaplar. Her terim yukarı yuvarlanır.
+fn load_ppm<'a>(set: impl Iterator<Item = &'a RtParams>) -> (usize, u64, u64) {
+    set.fold((0, 0, 0), |(n, u, d), p| {
+        let util = (p.wcet * PPM).div_ceil(p.period);
+        let dens = (p.wcet * PPM).div_ceil(p.deadline.min(p.period));
+        (n + 1, u + util, d + dens)
+    })
+}
+
+/// RM için kesin yanıt süresi analizi (Joseph & Pandya):
+/// R = C_i + Σ_{j: T_j < T_i} ⌈R / T_j⌉ · C_j, her görev için R <= D_i olmalı.
+fn rm_response_time_ok(set: &[RtParams]) -> bool {
+    set.iter().enumerate().all(|(i, task)| {
+        let higher = |j: usize, other: &RtParams| other.period < task.period || (other.period == task.period && j < i);
+        let mut response = task.wcet;
+        loop {
+            let next = task.wcet
+                + set.iter().enumerate()
+                    .filter(|&(j, other)| j != i && higher(j, other))
+                    .map(|(_, other)| response.div_ceil(other.period) * other.wcet)
+                    .sum::<u64>();
+            if next > task.deadline {
+                return false;
+            }
+            if next == response {
+                return true;
+            }
+            response = next;
+        }
+    })
+}
+
+/// Görev kümesinin politika altında çizelgelenebilir olup olmadığını denetler.
+///
+/// * EDF: Σ C/min(D, T) <= 1 (örtük son tarihte kesin, aksi halde yeterli koşul).
+/// * RM: önce Liu & Layland kullanım sınırı; aşılırsa kesin yanıt süresi analizi.
+fn admissible(policy: RtPolicy, set: &[RtParams]) -> bool {
+    let (n, util, density) = load_ppm(set.iter());
+    match policy {
+        RtPolicy::EarliestDeadlineFirst => density <= PPM,
+        RtPolicy::RateMonotonic => {
+            let implicit = set.iter().all(|p| p.deadline >= p.period);
+            (implicit && util <= rm_bound_ppm(n)) || (util <= PPM && rm_response_time_ok(set))
+        }
+    }
+}
+
+/// Kayıtlı görevlerin parametrelerini (ve isteğe bağlı bir adayı) diziye toplar.
+fn collect(tasks: &[RtTask; MAX_TASKS], extra: Option<(TaskId, RtParams)>) -> ([RtParams; MAX_TASKS], usize) {
+    let mut set = [RtParams { period: 1, wcet: 0, deadline: 1 }; MAX_TASKS];
+    let mut n = 0;
+    for (id, task) in tasks.iter().enumerate() {
+        let params = match extra {
+            Some((eid, p)) if eid == id => Some(p),
+            _ if task.active => Some(task.params),
+            _ => None,
+        };
+        if let Some(p) = params {
+            set[n] = p;
+            n += 1;
+        }
+    }
+    (set, n)
+}
+
+// -----------------------------------------------------------------------------
+// ARAYÜZ
+// -----------------------------------------------------------------------------
+
+/// Gerçek zamanlı politikayı değiştirir. Mevcut görev kümesi yeni politika
+/// altında çizelgelenemiyorsa `ResourceBusy` döner ve politika değişmez.
+pub fn set_policy(policy: RtPolicy) -> Result<(), KernelError> {
+    with_rt(|current, tasks| {
+        let (set, n) = collect(tasks, None);
+        if !admissible(policy, &set[..n]) {
+            return Err(KernelError::ResourceBusy);
+        }
+        *current = policy;
+        Ok(())
+    })
+}
+
+/// Var olan bir görevi periyodik gerçek zamanlı göreve dönüştürür. İlk iş hemen salınır.
+///
+/// # Hatalar
+/// * `InvalidArgument`: `0 < wcet <= deadline <= period` sağlanmıyor.
+/// * `ResourceBusy`: Kabul denetimi başarısız (kullanım sınırı aşılıyor).
+/// * `NotFound`: Görev yok.
+pub fn register_periodic(task: TaskId, params: RtParams) -> Result<(), KernelError> {
+    if task >= MAX_TASKS || params.wcet == 0 || params.wcet > params.deadline || params.deadline > params.period {
+        return Err(KernelError::InvalidArgument);
+    }
+    if sched::task_info(task).is_none() {
+        return Err(KernelError::NotFound);
+    }
+
+    let now = sched::ticks();
+    with_rt(|policy, tasks| {
+        let (set, n) = collect(tasks, Some((task, params)));
+        if !admissible(*policy, &set[..n]) {
+            serial_println!("[RT] Görev {} reddedildi: küme çizelgelenemez ({:?}).", task, policy);
+            return Err(KernelError::ResourceBusy);
+        }
+        tasks[task] = RtTask {
+            active: true,
+            params,
+            next_release: now + params.period,
+            abs_deadline: now + params.deadline,
+            budget_left: params.wcet,
+            backlog: 1,
+            jobs: 1,
+            ..RtTask::empty()
+        };
+        Ok(())
+    })?;
+
+    sched::set_class(task, SchedClass::RealTime)?;
+    sched::wake(task).ok();
+    Ok(())
+}
+
+/// Görevi gerçek zamanlı sınıftan çıkarır ve normal sınıfa döndürür.
+pub fn unregister(task: TaskId) -> Result<(), KernelError> {
+    forget(task);
+    sched::set_class(task, SchedClass::Normal)
+}
+
+/// Görev tablosu girişini temizler (`sched::exit` tarafından, sched kilidi altında çağrılır).
+pub(crate) fn forget(task: TaskId) {
+    with_rt(|_, tasks| {
+        if let Some(entry) = tasks.get_mut(task) {
+            *entry = RtTask::empty();
+        }
+    });
+}
+
+/// Çalışan gerçek zamanlı görev geçerli işini bitirdiğini bildirir ve bir
+/// sonraki salıma kadar bloklanır.
+pub fn wait_next_period(task: TaskId) -> Result<(), KernelError> {
+    // İş gecikmişse bir sonraki iş zaten salınmış olabilir; o zaman bloklanmadan devam edilir
+    let idle = with_rt(|_, tasks| match tasks.get_mut(task) {
+        Some(entry) if entry.active => {
+            entry.backlog = entry.backlog.saturating_sub(1);
+            Ok(entry.backlog == 0)
+        }
+        _ => Err(KernelError::NotFound),
+    })?;
+
+    if idle {
+        sched::block(task)?;
+    }
+    sched::schedule();
+    Ok(())
+}
+
+/// `sched::tick` tarafından çağrılır: işleri salar, son tarihleri ve bütçeyi denetler.
+///
+/// # Dönüş Değeri
+/// Yeniden zamanlama gerekiyorsa `true`.
+pub(crate) fn tick(now: u64, running: Option<TaskId>) -> bool {
+    let mut release = [false; MAX_TASKS];
+    let mut throttle: Option<TaskId> = None;
+
+    let resched = with_rt(|_, tasks| {
+        let mut resched = false;
+
+        // Çalışan işin bütçesini düş; biterse iş WCET'i aşmıştır ve iptal edilir
+        if let Some(id) = running {
+            let entry = &mut tasks[id];
+            if entry.active && entry.backlog > 0 {
+                entry.budget_left = entry.budget_left.saturating_sub(1);
+                if entry.budget_left == 0 {
+                    entry.overruns += 1;
+                    entry.backlog -= 1;
+                    entry.budget_left = entry.params.wcet;
+                    if entry.backlog == 0 {
+                        throttle = Some(id);
+                    }
+                    resched = true;
+                }
+            }
+        }
+
+        for (id, entry) in tasks.iter_mut().enumerate().filter(|(_, e)| e.active) {
+            if entry.backlog > 0 && now > entry.abs_deadline {
+                entry.deadline_misses += 1;
+                // Aynı iş için tekrar sayılmaması için son tarih bir sonraki salıma ertelenir
+                entry.abs_deadline = entry.next_release + entry.params.deadline;
+            }
+            if now >= entry.next_release {
+                entry.backlog += 1;
+                entry.jobs += 1;
+                entry.budget_left = entry.params.wcet;
+                entry.abs_deadline = entry.next_release + entry.params.deadline;
+                entry.next_release += entry.params.period;
+                release[id] = true;
+                resched = true;
+            }
+        }
+        resched
+    });
+
+    // Kilit bırakıldıktan sonra zamanlayıcı durumunu güncelle
+    if let Some(id) = throttle {
+        if !release[id] {
+            sched::block(id).ok();
+        }
+    }
+    for (id, _) in release.iter().enumerate().filter(|(_, r)| **r) {
+        sched::wake(id).ok();
+    }
+    resched
+}
+
+/// Bekleyen işi olan görevler arasından politikaya göre sıradakini seçer.
+pub(crate) fn pick() -> Option<TaskId> {
+    let candidates = with_rt(|policy, tasks| {
+        let mut ids = [None; MAX_TASKS];
+        for (id, entry) in tasks.iter().enumerate() {
+            if entry.active && entry.backlog > 0 {
+                ids[id] = Some((
+                    match policy {
+                        RtPolicy::EarliestDeadlineFirst => entry.abs_deadline,
+                        RtPolicy::RateMonotonic => entry.params.period,
+                    },
+                    id,
+                ));
+            }
+        }
+        ids
+    });
+
+    // En küçük anahtar kazanır; eşitlikte küçük görev kimliği
+    candidates
+        .iter()
+        .flatten()
+        .filter(|(_, id)| sched::is_runnable(*id))
+        .min()
+        .map(|&(_, id)| id)
+}
+
+/// Çizelgelenebilirlik analizi ve gerçek zamanlı sayaçlar.
+pub fn stats() -> RtStats {
+    with_rt(|policy, tasks| {
+        let (set, n) = collect(tasks, None);
+        let (_, util, density) = load_ppm(set[..n].iter());
+        let (misses, overruns) = tasks
+            .iter()
+            .filter(|e| e.active)
+            .fold((0, 0), |(m, o), e| (m + e.deadline_misses, o + e.overruns));
+
+        RtStats {
+            policy: *policy,
+            tasks: n,
+            utilization_ppm: util,
+            density_ppm: density,
+            bound_ppm: match policy {
+                RtPolicy::EarliestDeadlineFirst => PPM,
+                RtPolicy::RateMonotonic => rm_bound_ppm(n),
+            },
+            schedulable: admissible(*policy, &set[..n]),
+            deadline_misses: misses,
+            overruns,
+        }
+    })
+}
+
+// -----------------------------------------------------------------------------
+// KABUK KOMUTU
+// -----------------------------------------------------------------------------
+
+fn rt_command(args: &[&str]) -> Result<(), KernelError> {
+    match args.get(1) {
+        Some(&"edf") => return set_policy(RtPolicy::EarliestDeadlineFirst),
+        Some(&"rm") => return set_policy(RtPolicy::RateMonotonic),
+        Some(_) => {
+            serial_println!("Kullanım: rt [edf|rm]");
+            return Err(KernelError::InvalidArgument);
+        }
+        None => {}
+    }
+
+    let s = stats();
+    serial_println!(
+        "Politika: {:?} | Görev: {} | U = {}.{:06} | Yoğunluk = {}.{:06} | Sınır = {}.{:06} | {}",
+        s.policy,
+        s.tasks,
+        s.utilization_ppm / PPM, s.utilization_ppm % PPM,
+        s.density_ppm / PPM, s.density_ppm % PPM,
+        s.bound_ppm / PPM, s.bound_ppm % PPM,
+        if s.schedulable { "çizelgelenebilir" } else { "ÇİZELGELENEMEZ" }
+    );
+
+    with_rt(|_, tasks| {
+        for (id, e) in tasks.iter().enumerate().filter(|(_, e)| e.active) {
+            serial_println!(
+                "  {:>2} T={:<6} C={:<5} D={:<6} iş={:<8} kaçırma={:<5} aşım={}",
+                id, e.params.period, e.params.wcet, e.params.deadline, e.jobs, e.deadline_misses, e.overruns
+            );
+        }
+    });
+    Ok(())
+}
+
+crate::shell_command!("rt", "Gerçek zamanlı görev analizi (rt [edf|rm])", rt_command);
@@ -0,0 +1,217 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/shell.rs
+// Çekirdek hata ayıklama kabuğu: komut kaydı ve komut satırı yürütücüsü.
+//
+// Komutlar `shell_command!` makrosu ile `.shell_cmds` linker bölümüne kaydedilir
+// (bkz. `initcall.rs`); böylece her alt sistem kendi tanı komutunu kabuğa
+// dokunmadan ekleyebilir.
+//
+// Konsol oturumu `shell` görevinde çalışır: girdi kuyruğundaki (klavye ve
+// seri RX) tuşlar `lineedit::LineEditor`'a verilir, tamamlanan satırlar
+// `execute` ile yürütülür.
+//
+// Komutlar çıktılarını `serial_println!` ile yazar. Uzak oturumlar (bkz.
+// `net::telnet`) kendi görevleri için `set_redirect` ile bir çıktı hedefi
+// kaydeder; o görevin `serial_print!` çıktısı seri port yerine hedefe gider.
+// Görevin `console` ile açıkça bağlanmış akışı bu yönlendirmeden önce gelir.
+//
+// Linker betiği: __shell_cmds_start = .; KEEP(*(.shell_cmds)) __shell_cmds_end = .;
+
+use core::fmt;
+use core::sync::atomic::{AtomicPtr, Ordering};
+use crate::lineedit::LineEditor;
+use crate::platformgeneric::KernelError;
+use crate::sched::{TaskId, MAX_TASKS};
+use crate::serial_println;
+
+/// Bir komut satırında ayrıştırılacak en fazla argüman sayısı (komut adı dahil).
+pub const MAX_ARGS: usize = 8;
+
+/// Konsol istemi.
+pub const PROMPT: &str = "sahne> ";
+
+/// Kabuk görevinin önceliği.
+const SHELL_PRIORITY: u8 = 24;
+
+/// Girdi kuyruğu boşken yoklama aralığı (tik).
+const POLL_TICKS: u64 = 10;
+
+/// Kabuk komutu kaydı.
+#[repr(C)]
+pub struct ShellCommand {
+    /// Komut adı (ilk kelime).
+    pub name: &'static str,
+    /// `help` çıktısında gösterilen tek satırlık açıklama.
+    pub help: &'static str,
+    /// Komut fonksiyonu. `args[0]` komut adıdır.
+    pub func: fn(args: &[&str]) -> Result<(), KernelError>,
+}
+
+/// Bir kabuk komutunu kaydeder.
+///
+/// # Örnek
+/// `shell_command!("vm", "Sanal bellek tanıları (vm dump)", vm_command);`
+#[macro_export]
+macro_rules! shell_command {
+    ($name:expr, $help:expr, $func:path) => {
+        const _: () = {
+            #[used]
+            #[link_section = ".shell_cmds"]
+            static __SHELL_CMD: $crate::shell::ShellCommand = $crate::shell::ShellCommand {
+                name: $name,
+                help: $help,
+                func: $func,
+            };
+        };
+    };
+}
+
+extern "C" {
+    static __shell_cmds_start: ShellCommand;
+    static __shell_cmds_end: ShellCommand;
+}
+
+/// Kayıtlı tüm komutları dilim olarak döndürür.
+pub fn commands() -> &'static [ShellCommand] {
+    // SAFETY: Semboller linker betiği tarafından `.shell_cmds` bölümünün iki ucuna yerleştirilir.
+    unsafe {
+        let start = &__shell_cmds_start as *const ShellCommand;
+        let end = &__shell_cmds_end as *const ShellCommand;
+        let count = (end as usize - start as usize) / core::mem::size_of::<ShellCommand>();
+        core::slice::from_raw_parts(start, count)
+    }
+}
+
+/// Ada göre komut arar.
+pub fn find(name: &str) -> Option<&'static ShellCommand> {
+    commands().iter().find(|cmd| cmd.name == name)
+}
+
+/// Bir komut satırını boşluklara göre ayırır ve ilgili komutu çalıştırır.
+///
+/// # Dönüş Değeri
+/// Boş satır için `Ok(())`; bilinmeyen komut için `NotFound`; fazla argüman
+/// için `InvalidArgument`; aksi halde komutun kendi sonucu.
+pub fn execute(line: &str) -> Result<(), KernelError> {
+    let mut args: [&str; MAX_ARGS] = [""; MAX_ARGS];
+    let mut argc = 0;
+
+    for word in line.split_whitespace() {
+        if argc == MAX_ARGS {
+            serial_println!("Çok fazla argüman (en fazla {}).", MAX_ARGS);
+            return Err(KernelError::InvalidArgument);
+        }
+        args[argc] = word;
+        argc += 1;
+    }
+
+    if argc == 0 {
+        return Ok(());
+    }
+
+    match find(args[0]) {
+        Some(cmd) => (cmd.func)(&args[..argc]),
+        None => {
+            serial_println!("Bilinmeyen komut: '{}'. Komut listesi için 'help' yazın.", args[0]);
+            Err(KernelError::NotFound)
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// ÇIKTI YÖNLENDİRME
+// -----------------------------------------------------------------------------
+
+/// Görevin konsol çıktısını alan hedef. `false` dönerse çıktı seri porta yazılır.
+pub type OutputSink = fn(task: TaskId, args: fmt::Arguments) -> bool;
+
+/// Görev başına çıktı hedefi (`OutputSink` işaretçisi; boşsa seri port).
+static REDIRECT: [AtomicPtr<()>; MAX_TASKS] = [const { AtomicPtr::new(core::ptr::null_mut()) }; MAX_TASKS];
+
+/// `task` görevinin çıktısını `sink`'e yönlendirir (`None`: seri porta geri döner).
+pub fn set_redirect(task: TaskId, sink: Option<OutputSink>) -> Result<(), KernelError> {
+    let slot = REDIRECT.get(task).ok_or(KernelError::InvalidArgument)?;
+    slot.store(sink.map_or(core::ptr::null_mut(), |f| f as *mut ()), Ordering::Release);
+    Ok(())
+}
+
+/// `serial_print!` tarafından çağrılır: çalışan görevin çıktısı bir konsol
+/// hedefine bağlandıysa (`console::bind`) veya yönlendirildiyse hedefe verir.
+/// Kesme bağlamındaki çıktı yönlendirilmez.
+///
+/// # Dönüş Değeri
+/// Çıktı hedef tarafından alındıysa `true`.
+#[doc(hidden)]
+pub fn redirect(args: fmt::Arguments) -> bool {
+    if crate::irqstack::in_interrupt() {
+        return false;
+    }
+    let Some(task) = crate::sched::current() else {
+        return false;
+    };
+    if let Some(routed) = crate::console::route(task, args) {
+        return routed;
+    }
+    let sink = REDIRECT[task].load(Ordering::Acquire);
+    if sink.is_null() {
+        return false;
+    }
+    // SAFETY: Tabloya yalnızca `set_redirect` bir `OutputSink` işaretçisi yazar.
+    let sink: OutputSink = unsafe { core::mem::transmute(sink) };
+    sink(task, args)
+}
+
+// -----------------------------------------------------------------------------
+// KONSOL OTURUMU
+// -----------------------------------------------------------------------------
+
+/// Seri konsola yazan `fmt::Write` hedefi.
+pub struct Console;
+
+impl fmt::Write for Console {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        crate::serial_print!("{}", s);
+        Ok(())
+    }
+}
+
+/// Konsol kabuğu: girdi olaylarını düzenleyiciye verir ve satırları yürütür.
+fn console_task(_arg: u64) {
+    let mut editor = LineEditor::new(PROMPT);
+    editor.prompt(&mut Console);
+    loop {
+        while let Some(event) = crate::input::pop() {
+            if let Some(line) = editor.feed_event(&event, &mut Console) {
+                if let Err(e) = execute(line.as_str()) {
+                    // Bilinmeyen komut `execute` içinde bildirildi
+                    if line.as_str().split_whitespace().next().and_then(find).is_some() {
+                        serial_println!("Hata: {:?}", e);
+                    }
+                }
+                editor.prompt(&mut Console);
+            }
+        }
+        crate::timer::sleep(POLL_TICKS);
+    }
+}
+
+fn start_console() -> Result<(), KernelError> {
+    crate::sched::spawn("shell", console_task as usize, SHELL_PRIORITY)?;
+    Ok(())
+}
+
+crate::initcall!(late, "shell", start_console);
+
+// -----------------------------------------------------------------------------
+// YERLEŞİK KOMUTLAR
+// -----------------------------------------------------------------------------
+
+fn help_command(_args: &[&str]) -> Result<(), KernelError> {
+    for cmd in commands() {
+        serial_println!("  {:<12} {}", cmd.name, cmd.help);
+    }
+    Ok(())
+}
+
+crate::shell_command!("help", "Kayıtlı komutları listeler", help_command);
\ No newline at end of file
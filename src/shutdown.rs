@@ -0,0 +1,244 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/shutdown.rs
+// Düzenli sistem kapatma: alt sistem bildirim zinciri ve görev sonlandırma.
+//
+// Mimarilerin `system_shutdown`/`system_reset` yolları yalnızca kesmeleri
+// kapatıp donanımı sıfırlar; açık dosyalar, önbellekteki kirli sayfalar ve
+// süren DMA aktarımları yarıda kalır. `run`, güç kesilmeden önce şu sırayı
+// uygular:
+//
+// 1. Görevler: çalışan görev dışındaki tüm canlı gruplara `Terminate`
+//    gönderilir ve `shutdown.task_timeout_ms` boyunca çıkmaları beklenir;
+//    süre dolduğunda kalan gruplar `kill` ile sonlandırılır.
+// 2. Çekirdek işçileri (`kthread::stop_all`) durdurulur ve aynı süre boyunca
+//    bitmeleri beklenir; işçiler kuyruklarını boşaltıp `Work::Stop` ile son
+//    işlerini yapar (örn. `pageflush`). Sırayı bir işçi başlattıysa (örn.
+//    `thermal` acil kapanışı) kendisi beklenmez.
+// 3. `Phase::Filesystems` bildirimleri: dosya sistemleri ve blok önbelleği
+//    kirli verilerini yazar.
+// 4. `Phase::Drivers` bildirimleri: sürücüler aygıt önbelleklerini boşaltır
+//    ve DMA motorlarını durdurur. Bu aşamadan sonra aygıt G/Ç'si yapılmaz.
+//
+// Bildirimler `register` ile çalışma zamanında kaydedilir; aynı aşamadakiler
+// kayıt sırasının tersine çağrılır (sonra başlayan önce durur). Hata dönen
+// bildirim yazdırılır, zincir devam eder. Sıra yalnızca bir kez çalışır;
+// ikinci çağrı doğrudan dönüş yapar. Panik yolu bu sırayı kullanmaz.
+//
+// Kabuk komutu: `poweroff`, `shutdown`; procfs: `/proc/shutdown`
+
+use core::cell::UnsafeCell;
+use core::fmt::{self, Write};
+use core::sync::atomic::{AtomicBool, Ordering};
+use crate::platformgeneric::spinlock::Spinlock;
+use crate::platformgeneric::KernelError;
+use crate::process::{self, GroupState, Signal, MAX_GROUPS};
+use crate::serial_println;
+use crate::sysctl::U32;
+
+// -----------------------------------------------------------------------------
+// TÜRLER
+// -----------------------------------------------------------------------------
+
+/// Kayıtlı bildirimlerin en fazla sayısı.
+const MAX_NOTIFIERS: usize = 16;
+
+/// Kapatmanın sonunda yapılacak işlem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    PowerOff,
+    Reboot,
+}
+
+/// Bildirimin çağrıldığı aşama.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// Dosya sistemleri ve blok önbelleği (görevler sonlandıktan sonra).
+    Filesystems,
+    /// Aygıt sürücüleri (DMA durdurma, aygıt önbelleği boşaltma).
+    Drivers,
+}
+
+impl Phase {
+    fn name(self) -> &'static str {
+        match self {
+            Phase::Filesystems => "fs",
+            Phase::Drivers => "drivers",
+        }
+    }
+}
+
+/// Bildirim fonksiyonu. Görev bağlamında, kesmeler açıkken çağrılır.
+pub type NotifierFn = fn(Action) -> Result<(), KernelError>;
+
+#[derive(Clone, Copy)]
+struct Notifier {
+    name: &'static str,
+    phase: Phase,
+    func: NotifierFn,
+}
+
+struct Chain {
+    lock: Spinlock,
+    slots: UnsafeCell<[Option<Notifier>; MAX_NOTIFIERS]>,
+}
+
+// Tabloya erişim `lock` ile korunur.
+unsafe impl Sync for Chain {}
+
+static CHAIN: Chain = Chain {
+    lock: Spinlock::new(),
+    slots: UnsafeCell::new([None; MAX_NOTIFIERS]),
+};
+
+fn with_chain<R>(f: impl FnOnce(&mut [Option<Notifier>; MAX_NOTIFIERS]) -> R) -> R {
+    CHAIN.lock.lock();
+    // SAFETY: Kilit tutulduğu sürece tabloya tek erişen biziz.
+    let result = unsafe { f(&mut *CHAIN.slots.get()) };
+    CHAIN.lock.unlock();
+    result
+}
+
+/// Sıra başladı mı?
+static STARTED: AtomicBool = AtomicBool::new(false);
+
+crate::sysctl!(TASK_TIMEOUT_MS: U32 = U32::new(2000, 0, 60_000),
+    "shutdown.task_timeout_ms", "Kapanışta grupların Terminate sonrası çıkması için beklenen süre (ms)");
+
+// -----------------------------------------------------------------------------
+// KAYIT
+// -----------------------------------------------------------------------------
+
+/// Kapanış bildirimi kaydeder.
+///
+/// # Hatalar
+/// * `OutOfMemoryStatic`: Tablo dolu.
+/// * `ResourceBusy`: Kapanış sırası başladı.
+pub fn register(name: &'static str, phase: Phase, func: NotifierFn) -> Result<(), KernelError> {
+    if STARTED.load(Ordering::Acquire) {
+        return Err(KernelError::ResourceBusy);
+    }
+    with_chain(|slots| {
+        let slot = slots.iter_mut().find(|s| s.is_none()).ok_or(KernelError::OutOfMemoryStatic)?;
+        *slot = Some(Notifier { name, phase, func });
+        Ok(())
+    })
+}
+
+/// Kaydı siler (sürücü kaldırıldığında).
+pub fn unregister(func: NotifierFn) -> Result<(), KernelError> {
+    with_chain(|slots| {
+        let slot = slots
+            .iter_mut()
+            .find(|s| s.is_some_and(|n| n.func as usize == func as usize))
+            .ok_or(KernelError::NotFound)?;
+        *slot = None;
+        Ok(())
+    })
+}
+
+// -----------------------------------------------------------------------------
+// KAPANIŞ SIRASI
+// -----------------------------------------------------------------------------
+
+/// Çalışan görevin grubu dışındaki canlı gruplar (bit i = grup i).
+fn live_groups(me: Option<process::GroupId>) -> u32 {
+    (0..MAX_GROUPS)
+        .filter(|&id| Some(id) != me)
+        .filter(|&id| process::group_info(id).is_some_and(|g| g.state == GroupState::Alive))
+        .fold(0, |mask, id| mask | 1 << id)
+}
+
+/// `done` doğru olana veya `shutdown.task_timeout_ms` dolana kadar bekler.
+fn wait_until(done: impl Fn() -> bool) -> bool {
+    let timeout = u64::from(TASK_TIMEOUT_MS.get()) * crate::sched::tick_hz() / 1000;
+    let deadline = crate::sched::ticks() + timeout;
+    while !done() {
+        if crate::sched::ticks() >= deadline {
+            return false;
+        }
+        crate::timer::sleep(1);
+    }
+    true
+}
+
+/// Gruplara `Terminate` gönderir, süre boyunca bekler ve kalanları öldürür.
+fn terminate_groups() {
+    let me = crate::sched::current().and_then(process::group_of);
+    let live = live_groups(me);
+    if live == 0 {
+        return;
+    }
+    for id in (0..MAX_GROUPS).filter(|id| live & (1 << id) != 0) {
+        let _ = process::signal(id, Signal::Terminate);
+    }
+
+    wait_until(|| live_groups(me) == 0);
+
+    let remaining = live_groups(me);
+    for id in (0..MAX_GROUPS).filter(|id| remaining & (1 << id) != 0) {
+        serial_println!("[SHUTDOWN] Grup {} süresinde çıkmadı, sonlandırılıyor", id);
+        let _ = process::kill(id, -(Signal::Kill as i32));
+    }
+}
+
+/// Bir aşamanın bildirimlerini kayıt sırasının tersine çağırır.
+fn notify(phase: Phase, action: Action) {
+    // Bildirimler kilit dışında çağrılır; bildirim başka kilitler alabilir
+    let slots = with_chain(|slots| *slots);
+    for notifier in slots.iter().rev().flatten().filter(|n| n.phase == phase) {
+        if let Err(e) = (notifier.func)(action) {
+            serial_println!("[SHUTDOWN] {} ({}): {:?}", notifier.name, phase.name(), e);
+        }
+    }
+}
+
+/// Kapanış sırasını uygular (bkz. dosya başı). Sıra zaten başladıysa
+/// hemen döner. Görev bağlamından çağrılmalıdır.
+pub fn run(action: Action) {
+    if STARTED.swap(true, Ordering::AcqRel) {
+        return;
+    }
+    serial_println!("[SHUTDOWN] {:?} için sistem kapatılıyor", action);
+    terminate_groups();
+    crate::kthread::stop_all();
+    let own = crate::sched::current().is_some_and(crate::kthread::is_worker) as usize;
+    if !wait_until(|| crate::kthread::running() <= own) {
+        serial_println!("[SHUTDOWN] {} çekirdek işçisi süresinde bitmedi", crate::kthread::running() - own);
+    }
+    notify(Phase::Filesystems, action);
+    notify(Phase::Drivers, action);
+    serial_println!("[SHUTDOWN] Alt sistemler durduruldu");
+}
+
+/// Sırayı uygular ve sistemin gücünü keser.
+pub fn poweroff() -> ! {
+    run(Action::PowerOff);
+    arch_poweroff()
+}
+
+fn arch_poweroff() -> ! {
+    crate::arch::shutdown::system_shutdown()
+}
+
+// -----------------------------------------------------------------------------
+// KABUK VE PROCFS
+// -----------------------------------------------------------------------------
+
+fn show_shutdown(out: &mut dyn Write) -> fmt::Result {
+    writeln!(out, "started {}", STARTED.load(Ordering::Relaxed) as u8)?;
+    writeln!(out, "task_timeout_ms {}", TASK_TIMEOUT_MS.get())?;
+    for notifier in with_chain(|slots| *slots).iter().flatten() {
+        writeln!(out, "{} {}", notifier.phase.name(), notifier.name)?;
+    }
+    Ok(())
+}
+
+crate::proc_entry!("shutdown", show_shutdown);
+
+fn poweroff_command(_args: &[&str]) -> Result<(), KernelError> {
+    poweroff()
+}
+
+crate::shell_command!("poweroff", "Alt sistemleri durdurup sistemi kapatır", poweroff_command);
+crate::shell_command!("shutdown", "Alt sistemleri durdurup sistemi kapatır (poweroff)", poweroff_command);
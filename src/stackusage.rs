@@ -0,0 +1,120 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/stackusage.rs
+// Görev ve kesme yığınlarının ölçülen en yüksek kullanımı.
+//
+// Yığınlar ayrılırken desenle boyanır (`STACK_PAINT`); en yüksek kullanım
+// (high-water mark), yığın dibinden itibaren bozulmamış desen uzunluğundan
+// hesaplanır. Görev yığınları `TaskStackAllocator`'da, kesme yığınları
+// `irqstack::setup_cpu`'da boyanır. Ayrı kesme yığını olmayan mimarilerde
+// (bkz. `irqstack`) işleyiciler görev yığınında çalışır ve kullanımları
+// görev satırlarına yansır.
+//
+// Ölçüm yalnızca çalışılan yolları görür; derleme tarafındaki statik
+// çözümleme (`cargo xtask stack <mimari>`) ise çağrı zincirinin en kötü
+// durumunu verir. `/proc/stackusage` çıktısı kaydedilip
+// `cargo xtask stack <mimari> --measured DOSYA` ile verilirse iki değer
+// yan yana raporlanır; yığın boyutları (`MemoryRegions::TASK_STACK_SIZE`,
+// `irqstack::IRQ_STACK_PAGES`) bu ikisinin büyüğüne pay eklenerek seçilir.
+//
+// Kabuk komutu: `stackusage`; procfs: `/proc/stackusage`
+
+use core::fmt::{self, Write};
+use crate::irqstack::{self, IRQ_STACK_SIZE};
+use crate::memory::memory::{MemoryRegions, TaskStackAllocator, STACK_PAINT};
+use crate::percpu::MAX_CPUS;
+use crate::platformgeneric::KernelError;
+use crate::sched::{self, MAX_TASKS};
+use crate::serial_println;
+
+/// Bu orandan (%) fazla kullanılan yığın uyarıyla gösterilir.
+pub const WARN_PERCENT: usize = 75;
+
+// -----------------------------------------------------------------------------
+// ÖLÇÜM
+// -----------------------------------------------------------------------------
+
+/// `bottom`'dan başlayan `size` baytlık boyalı yığının en yüksek kullanımı.
+///
+/// # Safety
+/// Aralık eşlenmiş ve okunabilir olmalıdır.
+pub unsafe fn painted_usage(bottom: usize, size: usize) -> usize {
+    let base = bottom as *const u8;
+    let untouched = (0..size).take_while(|&i| core::ptr::read_volatile(base.add(i)) == STACK_PAINT).count();
+    size - untouched
+}
+
+/// Ölçülen bir yığın.
+struct Usage {
+    used: usize,
+    size: usize,
+}
+
+impl Usage {
+    fn percent(&self) -> usize {
+        self.used * 100 / self.size.max(1)
+    }
+}
+
+fn tasks() -> impl Iterator<Item = (usize, sched::TaskInfo, Usage)> {
+    (0..MAX_TASKS).filter_map(|id| {
+        let info = sched::task_info(id)?;
+        let used = TaskStackAllocator::high_water_mark(id)?;
+        Some((id, info, Usage { used, size: MemoryRegions::TASK_STACK_SIZE }))
+    })
+}
+
+fn irq_stacks() -> impl Iterator<Item = (usize, Usage)> {
+    (0..MAX_CPUS).filter_map(|cpu| Some((cpu, Usage { used: irqstack::high_water_mark(cpu)?, size: IRQ_STACK_SIZE })))
+}
+
+// -----------------------------------------------------------------------------
+// KABUK VE PROCFS
+// -----------------------------------------------------------------------------
+
+/// `task <id> <ad> <giriş> <kullanılan> <boyut>` ve `irq <cpu> <kullanılan>
+/// <boyut>` satırları; `cargo xtask stack --measured` bu biçimi okur.
+fn show_stackusage(out: &mut dyn Write) -> fmt::Result {
+    for (id, info, usage) in tasks() {
+        writeln!(out, "task {} {} {:#x} {} {}", id, info.name, info.entry, usage.used, usage.size)?;
+    }
+    for (cpu, usage) in irq_stacks() {
+        writeln!(out, "irq {} {} {}", cpu, usage.used, usage.size)?;
+    }
+    Ok(())
+}
+
+crate::proc_entry!("stackusage", show_stackusage);
+
+fn stackusage_command(_args: &[&str]) -> Result<(), KernelError> {
+    let row = |kind: &str, number: usize, name: &str, usage: &Usage| {
+        let warn = if usage.percent() > WARN_PERCENT { " !" } else { "" };
+        serial_println!(
+            "  {:<6} {:>3} {:<16} {:>10} {:>7} {:>3}%{}",
+            kind,
+            number,
+            name,
+            usage.used,
+            usage.size,
+            usage.percent(),
+            warn
+        );
+    };
+    serial_println!("Mimari: {}", crate::arch::PORT);
+    serial_println!("  TÜR     NO AD               KULLANILAN   BOYUT    %");
+    for (id, info, usage) in tasks() {
+        row("görev", id, info.name, &usage);
+    }
+    // Kesme yığınlarında NO işlemci numarasıdır
+    for (cpu, usage) in irq_stacks() {
+        row("kesme", cpu, "-", &usage);
+    }
+    serial_println!(
+        "!: %{}'ten fazla. Statik en kötü durum: cargo xtask stack {} --measured <proc/stackusage>",
+        WARN_PERCENT,
+        crate::arch::PORT
+    );
+    Ok(())
+}
+
+crate::shell_command!("stackusage", "Yığınların ölçülen en yüksek kullanımı", stackusage_command);
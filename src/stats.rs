@@ -0,0 +1,137 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/stats.rs
+// Çekirdek olay sayaçları ve kesme sayımları.
+//
+// Alt sistemler `kernel_counter!` ile adlandırılmış sayaçlarını `.kcounters`
+// linker bölümüne kaydeder; böylece yeni bir istatistik eklemek için merkezi bir
+// listeye dokunmak gerekmez. Sayaçlar kilitsizdir (atomik) ve kesme
+// bağlamından güvenle artırılabilir. Değerler procfs (`/proc/counters`,
+// `/proc/interrupts`) ve `stats` kabuk komutu ile okunur.
+//
+// Linker betiği (yazılabilir veri bölümü içinde):
+//   __kcounters_start = .; KEEP(*(.kcounters)) __kcounters_end = .;
+
+use core::sync::atomic::{AtomicU64, Ordering};
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// OLAY SAYAÇLARI
+// -----------------------------------------------------------------------------
+
+/// Adlandırılmış, tek değerli olay sayacı.
+#[repr(C)]
+pub struct EventCounter {
+    /// Sayacın adı (`alt_sistem.olay` biçiminde, örn. "sched.switches").
+    pub name: &'static str,
+    value: AtomicU64,
+}
+
+impl EventCounter {
+    pub const fn new(name: &'static str) -> Self {
+        EventCounter { name, value: AtomicU64::new(0) }
+    }
+
+    #[inline]
+    pub fn inc(&self) {
+        self.value.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub fn add(&self, n: u64) {
+        self.value.fetch_add(n, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub fn get(&self) -> u64 {
+        self.value.load(Ordering::Relaxed)
+    }
+
+    pub fn reset(&self) {
+        self.value.store(0, Ordering::Relaxed);
+    }
+}
+
+/// Bir olay sayacı tanımlar ve `.kcounters` bölümüne kaydeder.
+///
+/// ```ignore
+/// crate::kernel_counter!(pub(crate) CONTEXT_SWITCHES, "sched.switches");
+/// CONTEXT_SWITCHES.inc();
+/// ```
+#[macro_export]
+macro_rules! kernel_counter {
+    ($vis:vis $ident:ident, $name:expr) => {
+        #[used]
+        #[link_section = ".kcounters"]
+        $vis static $ident: $crate::stats::EventCounter = $crate::stats::EventCounter::new($name);
+    };
+}
+
+extern "C" {
+    static __kcounters_start: EventCounter;
+    static __kcounters_end: EventCounter;
+}
+
+/// Kayıtlı tüm olay sayaçları.
+pub fn counters() -> &'static [EventCounter] {
+    // SAFETY: Semboller linker betiği tarafından `.kcounters` bölümünün iki ucuna yerleştirilir.
+    unsafe {
+        let start = &__kcounters_start as *const EventCounter;
+        let end = &__kcounters_end as *const EventCounter;
+        let count = (end as usize - start as usize) / core::mem::size_of::<EventCounter>();
+        core::slice::from_raw_parts(start, count)
+    }
+}
+
+/// Ada göre sayaç arar.
+pub fn find(name: &str) -> Option<&'static EventCounter> {
+    counters().iter().find(|c| c.name == name)
+}
+
+// -----------------------------------------------------------------------------
+// KESME SAYIMLARI
+// -----------------------------------------------------------------------------
+
+/// Sayımı tutulan en yüksek kesme numarası + 1.
+pub const MAX_IRQS: usize = 256;
+
+#[allow(clippy::declare_interior_mutable_const)]
+const ZERO: AtomicU64 = AtomicU64::new(0);
+static IRQ_COUNTS: [AtomicU64; MAX_IRQS] = [ZERO; MAX_IRQS];
+
+kernel_counter!(IRQ_OUT_OF_RANGE, "irq.out_of_range");
+
+/// Mimarinin kesme giriş yolundan her kesmede çağrılır.
+#[inline]
+pub fn record_irq(irq: usize) {
+    match IRQ_COUNTS.get(irq) {
+        Some(count) => {
+            count.fetch_add(1, Ordering::Relaxed);
+        }
+        None => IRQ_OUT_OF_RANGE.inc(),
+    }
+}
+
+/// Kesmenin açılıştan bu yana kaç kez geldiği.
+pub fn irq_count(irq: usize) -> u64 {
+    IRQ_COUNTS.get(irq).map_or(0, |c| c.load(Ordering::Relaxed))
+}
+
+// -----------------------------------------------------------------------------
+// KABUK KOMUTU
+// -----------------------------------------------------------------------------
+
+fn stats_command(args: &[&str]) -> Result<(), KernelError> {
+    if args.get(1) == Some(&"reset") {
+        counters().iter().for_each(EventCounter::reset);
+        IRQ_COUNTS.iter().for_each(|c| c.store(0, Ordering::Relaxed));
+        return Ok(());
+    }
+    for counter in counters() {
+        serial_println!("  {:<28} {:>12}", counter.name, counter.get());
+    }
+    Ok(())
+}
+
+crate::shell_command!("stats", "Olay sayaçlarını gösterir (stats [reset])", stats_command);
@@ -0,0 +1,111 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/sync.rs
+// Görev düzeyinde uyuyan mutex.
+//
+// `Spinlock`'un aksine çekişmede görev bloklanır ve kilidi bırakan görev
+// bekleyenleri uyandırır. Sahip görev izlenir ve bekleme-için grafiğine
+// (`deadlock`) bildirilir; kilitlenmeye yol açacak bir `lock` çağrısı
+// beklemek yerine `Err(Deadlock)` döndürür (aynı görevin kilidi ikinci kez
+// alması da dahil). Kesme bağlamından kullanılmamalıdır.
+
+use core::cell::UnsafeCell;
+use core::ops::{Deref, DerefMut};
+use core::sync::atomic::{AtomicUsize, Ordering};
+use crate::deadlock::{self, Resource, ResourceKind};
+use crate::platformgeneric::KernelError;
+
+/// Kilit boş.
+const UNLOCKED: usize = 0;
+/// Görev bağlamı olmadan (açılışta) alınmış kilit.
+const BOOT_OWNER: usize = usize::MAX;
+
+/// Uyuyan mutex. Statik olarak tanımlanmalıdır (adresi kaynak anahtarıdır).
+pub struct Mutex<T> {
+    /// Sahip görev + 1, `UNLOCKED` veya `BOOT_OWNER`.
+    owner: AtomicUsize,
+    data: UnsafeCell<T>,
+}
+
+// Veriye erişim yalnızca kilidi tutan görevin `MutexGuard`'ı üzerinden olur.
+unsafe impl<T: Send> Sync for Mutex<T> {}
+
+impl<T> Mutex<T> {
+    pub const fn new(data: T) -> Self {
+        Mutex { owner: AtomicUsize::new(UNLOCKED), data: UnsafeCell::new(data) }
+    }
+
+    fn resource(&self) -> Resource {
+        Resource::new(ResourceKind::Mutex, self)
+    }
+
+    fn try_acquire(&self, tag: usize) -> bool {
+        self.owner.compare_exchange(UNLOCKED, tag, Ordering::Acquire, Ordering::Relaxed).is_ok()
+    }
+
+    /// Kilidi alır; gerekirse serbest kalana kadar bekler.
+    ///
+    /// # Hatalar
+    /// * `Deadlock`: Beklemek bir bekleme döngüsünü kapatırdı.
+    pub fn lock(&self) -> Result<MutexGuard<'_, T>, KernelError> {
+        let me = crate::sched::current();
+        let tag = me.map_or(BOOT_OWNER, |id| id + 1);
+        while !self.try_acquire(tag) {
+            deadlock::wait_until(self.resource(), || self.owner.load(Ordering::Acquire) == UNLOCKED)?;
+        }
+        if me.is_some() {
+            deadlock::set_owner(self.resource(), me);
+        }
+        Ok(MutexGuard { mutex: self })
+    }
+
+    /// Beklemeden dener; kilit tutuluyorsa `ResourceBusy`.
+    pub fn try_lock(&self) -> Result<MutexGuard<'_, T>, KernelError> {
+        let me = crate::sched::current();
+        if !self.try_acquire(me.map_or(BOOT_OWNER, |id| id + 1)) {
+            return Err(KernelError::ResourceBusy);
+        }
+        if me.is_some() {
+            deadlock::set_owner(self.resource(), me);
+        }
+        Ok(MutexGuard { mutex: self })
+    }
+
+    /// Kilidi tutan görev (açılışta alınmışsa veya boşsa `None`).
+    pub fn owner(&self) -> Option<crate::sched::TaskId> {
+        match self.owner.load(Ordering::Relaxed) {
+            UNLOCKED | BOOT_OWNER => None,
+            tag => Some(tag - 1),
+        }
+    }
+}
+
+/// Kilit tutucu; bırakıldığında kilit açılır ve bekleyenler uyandırılır.
+pub struct MutexGuard<'a, T> {
+    mutex: &'a Mutex<T>,
+}
+
+impl<T> Deref for MutexGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: Kilit bu tutucuya aittir.
+        unsafe { &*self.mutex.data.get() }
+    }
+}
+
+impl<T> DerefMut for MutexGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: Kilit bu tutucuya aittir.
+        unsafe { &mut *self.mutex.data.get() }
+    }
+}
+
+impl<T> Drop for MutexGuard<'_, T> {
+    fn drop(&mut self) {
+        let resource = self.mutex.resource();
+        deadlock::set_owner(resource, None);
+        self.mutex.owner.store(UNLOCKED, Ordering::Release);
+        deadlock::wake_waiters(resource);
+    }
+}
@@ -0,0 +1,315 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/syscall.rs
+// Kullanıcı görevlerinin sistem çağrısı dağıtıcısı.
+//
+// Mimarinin tuzak işleyicisi çağrı numarasını ve en fazla altı argümanı
+// kendi ABI'sindeki yazmaçlardan okuyup `dispatch`'e verir ve dönüş değerini
+// sonuç yazmacına yazar (RISC-V: numara a7, argümanlar a0..a5, sonuç a0).
+// Numaralar Linux'un genel (asm-generic) tablosuyla aynıdır; böylece hazır
+// kullanıcı kitaplıkları değiştirilmeden kullanılabilir.
+//
+// Hata durumunda negatif bir errno döner (`errno`).
+//
+// İşleyiciler kullanıcı adreslerini ham tamsayı olarak almaz: `invoke`
+// argümanları `Args` üzerinden `uaccess::UserPtr`/`UserSlice` olarak verir;
+// her erişim görevin adres alanıyla denetlenir ve geçersiz adres EFAULT
+// döner (bkz. `uaccess`).
+//
+// İzleme (strace benzeri): `strace <görev> on` ile işaretlenen görevlerin her
+// çağrısı dönüşte çağrı adı, türüne göre çözülmüş argümanları ve sonucuyla
+// birlikte `[TRACE] syscall` satırı olarak yazdırılır:
+//
+//   [TRACE] syscall görev=5 clock_gettime(CLOCK_MONOTONIC, 0x80001f40) = 0
+//
+// İşaret görev başına atomiktir; izlenmeyen görevlerde maliyet tek bir
+// yüklemedir. Görev sonlandığında işaret temizlenir.
+//
+// Kabuk komutu: `strace [<görev> on|off]`
+
+use core::fmt;
+use core::sync::atomic::{AtomicBool, Ordering};
+use crate::platformgeneric::KernelError;
+use crate::sched::{TaskId, MAX_TASKS};
+use crate::serial_println;
+use crate::uaccess::{Pod, UserPtr, UserSlice};
+
+// -----------------------------------------------------------------------------
+// ÇAĞRI NUMARALARI
+// -----------------------------------------------------------------------------
+
+pub const SYS_CLOCK_SETTIME: usize = 112;
+pub const SYS_CLOCK_GETTIME: usize = 113;
+pub const SYS_CLOCK_GETRES: usize = 114;
+pub const SYS_CLOCK_NANOSLEEP: usize = 115;
+pub const SYS_SCHED_SETAFFINITY: usize = 122;
+pub const SYS_SCHED_GETAFFINITY: usize = 123;
+pub const SYS_UNAME: usize = 160;
+pub const SYS_MUNMAP: usize = 215;
+pub const SYS_MMAP: usize = 222;
+pub const SYS_MSYNC: usize = 227;
+pub const SYS_MLOCK: usize = 228;
+pub const SYS_MUNLOCK: usize = 229;
+pub const SYS_CLOCK_ADJTIME: usize = 266;
+
+// Sahne'ye özgü çağrılar Linux tablosuyla çakışmasın diye 1024'ten başlar.
+pub const SYS_CONSOLE_BIND: usize = 1024;
+
+// -----------------------------------------------------------------------------
+// HATA KODLARI
+// -----------------------------------------------------------------------------
+
+const EIO: isize = 5;
+const EFAULT: isize = 14;
+const EAGAIN: isize = 11;
+const ENOMEM: isize = 12;
+const EINVAL: isize = 22;
+const ENOENT: isize = 2;
+const ENOSYS: isize = 38;
+const EDEADLK: isize = 35;
+
+/// Çekirdek hatasını kullanıcıya dönen negatif errno değerine çevirir.
+pub fn errno(error: KernelError) -> isize {
+    -match error.general() {
+        KernelError::Success => 0,
+        KernelError::ResourceBusy => EAGAIN,
+        KernelError::InvalidArgument => EINVAL,
+        KernelError::NotFound => ENOENT,
+        KernelError::OutOfMemoryStatic => ENOMEM,
+        KernelError::Deadlock => EDEADLK,
+        KernelError::BadAddress => EFAULT,
+        KernelError::PlatformSpecificError(_) | KernelError::GenericFailure => EIO,
+        // `general` alt sistem hatalarını genel varyanta indirger
+        KernelError::Map(_) | KernelError::Device(_) => EIO,
+    }
+}
+
+fn ret(result: Result<usize, KernelError>) -> isize {
+    match result {
+        Ok(value) => value as isize,
+        Err(e) => errno(e),
+    }
+}
+
+crate::kernel_counter!(SYSCALLS, "syscall.calls");
+crate::kernel_counter!(SYSCALLS_UNKNOWN, "syscall.unknown");
+
+// -----------------------------------------------------------------------------
+// DAĞITICI
+// -----------------------------------------------------------------------------
+
+/// Çalışan görev adına `nr` numaralı çağrıyı yürütür.
+pub fn dispatch(nr: usize, args: [usize; 6]) -> isize {
+    SYSCALLS.inc();
+    let traced = crate::sched::current().filter(|&task| is_traced(task));
+    let result = invoke(nr, args);
+    if let Some(task) = traced {
+        serial_println!("[TRACE] syscall görev={} {} = {}", task, Call { nr, args }, Ret(result));
+    }
+    result
+}
+
+/// Tuzak çerçevesinden okunan argümanlar. Alanı bu modüle özeldir: değer
+/// yalnızca dağıtıcıda kurulur, böylece kullanıcı işaretçileri
+/// (`UserPtr::from_trap`, `UserSlice::from_trap`) yalnızca buradan üretilir.
+pub struct Args([usize; 6]);
+
+impl Args {
+    /// `index` numaralı ham argüman.
+    pub fn raw(&self, index: usize) -> usize {
+        self.0[index]
+    }
+
+    fn ptr<T: Pod>(&self, index: usize) -> UserPtr<T> {
+        UserPtr::from_trap(self, index)
+    }
+
+    /// `addr` ve `len` numaralı argümanlardan tampon.
+    fn slice(&self, addr: usize, len: usize) -> UserSlice {
+        UserSlice::from_trap(self, addr, len)
+    }
+}
+
+fn invoke(nr: usize, args: [usize; 6]) -> isize {
+    let user = Args(args);
+    match nr {
+        SYS_CLOCK_SETTIME => ret(crate::walltime::sys_clock_settime(args[0], user.ptr(1))),
+        SYS_CLOCK_GETTIME => ret(crate::walltime::sys_clock_gettime(args[0], user.ptr(1))),
+        SYS_CLOCK_GETRES => ret(crate::walltime::sys_clock_getres(args[0], user.ptr(1))),
+        SYS_CLOCK_NANOSLEEP => ret(crate::walltime::sys_clock_nanosleep(args[0], args[1], user.ptr(2), user.ptr(3))),
+        SYS_CLOCK_ADJTIME => ret(crate::walltime::sys_clock_adjtime(args[0], user.ptr(1))),
+        SYS_SCHED_SETAFFINITY => ret(crate::sched::sys_sched_setaffinity(args[0], user.slice(2, 1))),
+        SYS_SCHED_GETAFFINITY => ret(crate::sched::sys_sched_getaffinity(args[0], user.slice(2, 1))),
+        SYS_UNAME => ret(crate::version::sys_uname(user.ptr(0))),
+        SYS_MMAP => ret(crate::mmap::sys_mmap(user.slice(0, 1), args[2], args[3], args[4], args[5])),
+        SYS_MUNMAP => ret(crate::mmap::sys_munmap(args[0], args[1]).map(|_| 0)),
+        SYS_MSYNC => ret(crate::mmap::sys_msync(args[0], args[1]).map(|_| 0)),
+        SYS_MLOCK => ret(crate::mmap::sys_mlock(args[0], args[1]).map(|_| 0)),
+        SYS_MUNLOCK => ret(crate::mmap::sys_munlock(args[0], args[1]).map(|_| 0)),
+        SYS_CONSOLE_BIND => ret(crate::console::sys_console_bind(args[0], args[1])),
+        _ => {
+            SYSCALLS_UNKNOWN.inc();
+            -ENOSYS
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// İZLEME
+// -----------------------------------------------------------------------------
+
+static TRACED: [AtomicBool; MAX_TASKS] = [const { AtomicBool::new(false) }; MAX_TASKS];
+
+/// Görevin çağrıları izleniyor mu?
+pub fn is_traced(task: TaskId) -> bool {
+    TRACED.get(task).is_some_and(|t| t.load(Ordering::Relaxed))
+}
+
+/// Görevin çağrı izlemesini açar veya kapatır.
+pub fn set_traced(task: TaskId, on: bool) -> Result<(), KernelError> {
+    crate::sched::task_info(task).ok_or(KernelError::NotFound)?;
+    TRACED[task].store(on, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Görev tablosu girişi yeniden kullanılmadan önce `sched::exit` tarafından çağrılır.
+pub fn task_exited(task: TaskId) {
+    if let Some(traced) = TRACED.get(task) {
+        traced.store(false, Ordering::Relaxed);
+    }
+}
+
+/// Argümanın gösterim biçimi.
+#[derive(Clone, Copy)]
+enum Arg {
+    /// İşaretli tamsayı.
+    Int,
+    /// Boyut / uzunluk (onluk).
+    Size,
+    /// Kullanıcı adresi (onaltılık).
+    Ptr,
+    /// Saat kimliği (`CLOCK_*`).
+    Clock,
+    /// Bellek koruma bitleri (`PROT_*`).
+    Prot,
+}
+
+/// Çağrının adı ve argüman biçimleri.
+fn signature(nr: usize) -> Option<(&'static str, &'static [Arg])> {
+    use Arg::*;
+    Some(match nr {
+        SYS_CLOCK_SETTIME => ("clock_settime", &[Clock, Ptr]),
+        SYS_CLOCK_GETTIME => ("clock_gettime", &[Clock, Ptr]),
+        SYS_CLOCK_GETRES => ("clock_getres", &[Clock, Ptr]),
+        SYS_CLOCK_NANOSLEEP => ("clock_nanosleep", &[Clock, Int, Ptr, Ptr]),
+        SYS_CLOCK_ADJTIME => ("clock_adjtime", &[Clock, Ptr]),
+        SYS_SCHED_SETAFFINITY => ("sched_setaffinity", &[Int, Size, Ptr]),
+        SYS_SCHED_GETAFFINITY => ("sched_getaffinity", &[Int, Size, Ptr]),
+        SYS_UNAME => ("uname", &[Ptr]),
+        SYS_MMAP => ("mmap", &[Ptr, Size, Prot, Int, Int, Int]),
+        SYS_MUNMAP => ("munmap", &[Ptr, Size]),
+        SYS_MSYNC => ("msync", &[Ptr, Size]),
+        SYS_MLOCK => ("mlock", &[Ptr, Size]),
+        SYS_MUNLOCK => ("munlock", &[Ptr, Size]),
+        SYS_CONSOLE_BIND => ("console_bind", &[Int, Int]),
+        _ => return None,
+    })
+}
+
+/// Çözülmüş çağrı: `ad(arg, ...)`.
+struct Call {
+    nr: usize,
+    args: [usize; 6],
+}
+
+impl fmt::Display for Call {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Some((name, kinds)) = signature(self.nr) else {
+            // Bilinmeyen çağrı: numara ve ham argümanlar
+            write!(f, "syscall_{}(", self.nr)?;
+            for (i, arg) in self.args.iter().enumerate() {
+                write!(f, "{}{:#x}", if i == 0 { "" } else { ", " }, arg)?;
+            }
+            return f.write_str(")");
+        };
+        write!(f, "{}(", name)?;
+        for (i, (kind, &arg)) in kinds.iter().zip(self.args.iter()).enumerate() {
+            if i != 0 {
+                f.write_str(", ")?;
+            }
+            match kind {
+                Arg::Int => write!(f, "{}", arg as isize)?,
+                Arg::Size => write!(f, "{}", arg)?,
+                Arg::Ptr if arg == 0 => f.write_str("NULL")?,
+                Arg::Ptr => write!(f, "{:#x}", arg)?,
+                Arg::Clock => match arg {
+                    crate::walltime::CLOCK_REALTIME => f.write_str("CLOCK_REALTIME")?,
+                    crate::walltime::CLOCK_MONOTONIC => f.write_str("CLOCK_MONOTONIC")?,
+                    crate::walltime::CLOCK_MONOTONIC_RAW => f.write_str("CLOCK_MONOTONIC_RAW")?,
+                    crate::walltime::CLOCK_BOOTTIME => f.write_str("CLOCK_BOOTTIME")?,
+                    other => write!(f, "{}", other)?,
+                },
+                Arg::Prot => write_prot(f, arg)?,
+            }
+        }
+        f.write_str(")")
+    }
+}
+
+fn write_prot(f: &mut fmt::Formatter, prot: usize) -> fmt::Result {
+    use crate::mmap::{PROT_EXEC, PROT_READ, PROT_WRITE};
+    if prot == 0 {
+        return f.write_str("PROT_NONE");
+    }
+    let mut first = true;
+    for (bit, name) in [(PROT_READ, "PROT_READ"), (PROT_WRITE, "PROT_WRITE"), (PROT_EXEC, "PROT_EXEC")] {
+        if prot & bit != 0 {
+            write!(f, "{}{}", if first { "" } else { "|" }, name)?;
+            first = false;
+        }
+    }
+    let rest = prot & !(PROT_READ | PROT_WRITE | PROT_EXEC);
+    if rest != 0 {
+        write!(f, "{}{:#x}", if first { "" } else { "|" }, rest)?;
+    }
+    Ok(())
+}
+
+/// Dönüş değeri; hatalar errno adıyla.
+struct Ret(isize);
+
+impl fmt::Display for Ret {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self.0.wrapping_neg() {
+            EIO => "EIO",
+            EFAULT => "EFAULT",
+            EAGAIN => "EAGAIN",
+            ENOMEM => "ENOMEM",
+            EINVAL => "EINVAL",
+            ENOENT => "ENOENT",
+            ENOSYS => "ENOSYS",
+            EDEADLK => "EDEADLK",
+            _ => return write!(f, "{}", self.0),
+        };
+        write!(f, "{} ({})", self.0, name)
+    }
+}
+
+fn strace_command(args: &[&str]) -> Result<(), KernelError> {
+    match (args.get(1), args.get(2).copied()) {
+        (None, _) => {
+            for task in (0..MAX_TASKS).filter(|&t| is_traced(t)) {
+                let name = crate::sched::task_info(task).map_or("?", |info| info.name);
+                serial_println!("  {:>3} {}", task, name);
+            }
+            Ok(())
+        }
+        (Some(task), Some(state @ ("on" | "off"))) => {
+            let task = task.parse().map_err(|_| KernelError::InvalidArgument)?;
+            set_traced(task, state == "on")
+        }
+        _ => Err(KernelError::InvalidArgument),
+    }
+}
+
+crate::shell_command!("strace", "Görevin sistem çağrılarını izler (strace [<görev> on|off])", strace_command);
@@ -0,0 +1,306 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/sysctl.rs
+// Çalışma zamanında ayarlanabilen çekirdek parametreleri (tunable).
+//
+// Alt sistemler parametrelerini `sysctl!` ile `.sysctls` linker bölümüne
+// kaydeder; parametre tipli bir statiktir (`U32`, `Flag`, `Level`) ve kod
+// içinde doğrudan `get`/`enabled` ile okunur. Kayıt makrosu yalnızca
+// `Tunable` uygulayan tipleri kabul eder; metinden gelen değerler tipe göre
+// ayrıştırılır ve aralık dışı değerler `InvalidArgument` ile reddedilir.
+// `U32` için değer yazılmadan önce çağrılan bir uygulama fonksiyonu
+// (örn. donanımı yeniden programlamak) verilebilir; hata dönerse değer
+// değişmez. Okuma ve yazma atomiktir, kesme bağlamından okunabilir.
+//
+// Adlar `alt_sistem.ad` biçimindedir: günlük seviyeleri `log.<modül>`,
+// izleme noktaları `trace.<olay>` altında toplanır.
+//
+// Kabuk komutu: `sysctl [ad [değer]]`; procfs: `/proc/sys/<ad>` (okunur ve
+// yazılır).
+//
+// Linker betiği: __sysctls_start = .; KEEP(*(.sysctls)) __sysctls_end = .;
+
+use core::fmt;
+use core::sync::atomic::{AtomicBool, AtomicU32, AtomicU8, Ordering};
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// PARAMETRE TİPLERİ
+// -----------------------------------------------------------------------------
+
+/// Metin olarak okunup yazılabilen parametre değeri.
+pub trait Tunable: Sync {
+    /// Tip adı (`sysctl` listesinde gösterilir).
+    fn kind(&self) -> &'static str;
+    /// Geçerli değeri yazar.
+    fn show(&self, out: &mut dyn fmt::Write) -> fmt::Result;
+    /// Metinden ayrıştırıp ayarlar.
+    fn set_str(&self, value: &str) -> Result<(), KernelError>;
+}
+
+/// Aralık denetimli 32 bit tamsayı.
+pub struct U32 {
+    value: AtomicU32,
+    min: u32,
+    max: u32,
+    apply: Option<fn(u32) -> Result<(), KernelError>>,
+}
+
+impl U32 {
+    pub const fn new(default: u32, min: u32, max: u32) -> Self {
+        U32 { value: AtomicU32::new(default), min, max, apply: None }
+    }
+
+    /// Yeni değer saklanmadan önce çağrılacak fonksiyonu ekler.
+    pub const fn with_apply(self, apply: fn(u32) -> Result<(), KernelError>) -> Self {
+        U32 { apply: Some(apply), ..self }
+    }
+
+    #[inline]
+    pub fn get(&self) -> u32 {
+        self.value.load(Ordering::Relaxed)
+    }
+
+    pub fn set(&self, value: u32) -> Result<(), KernelError> {
+        if value < self.min || value > self.max {
+            return Err(KernelError::InvalidArgument);
+        }
+        if let Some(apply) = self.apply {
+            apply(value)?;
+        }
+        self.value.store(value, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+impl Tunable for U32 {
+    fn kind(&self) -> &'static str {
+        "u32"
+    }
+
+    fn show(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        write!(out, "{}", self.get())
+    }
+
+    fn set_str(&self, value: &str) -> Result<(), KernelError> {
+        self.set(value.parse().map_err(|_| KernelError::InvalidArgument)?)
+    }
+}
+
+/// Açık/kapalı bayrağı (izleme noktaları vb.).
+pub struct Flag {
+    value: AtomicBool,
+}
+
+impl Flag {
+    pub const fn new(default: bool) -> Self {
+        Flag { value: AtomicBool::new(default) }
+    }
+
+    #[inline]
+    pub fn get(&self) -> bool {
+        self.value.load(Ordering::Relaxed)
+    }
+
+    pub fn set(&self, value: bool) {
+        self.value.store(value, Ordering::Relaxed);
+    }
+}
+
+impl Tunable for Flag {
+    fn kind(&self) -> &'static str {
+        "bool"
+    }
+
+    fn show(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        write!(out, "{}", u8::from(self.get()))
+    }
+
+    fn set_str(&self, value: &str) -> Result<(), KernelError> {
+        match value {
+            "1" | "on" | "true" => self.set(true),
+            "0" | "off" | "false" => self.set(false),
+            _ => return Err(KernelError::InvalidArgument),
+        }
+        Ok(())
+    }
+}
+
+/// Günlük seviyesi; seçilen seviye ve daha önemlileri yazdırılır.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(u8)]
+pub enum LogLevel {
+    Off = 0,
+    Error = 1,
+    Warn = 2,
+    Info = 3,
+    Debug = 4,
+    Trace = 5,
+}
+
+const LEVEL_NAMES: [&str; 6] = ["off", "error", "warn", "info", "debug", "trace"];
+
+/// Modül başına günlük seviyesi.
+pub struct Level {
+    value: AtomicU8,
+}
+
+impl Level {
+    pub const fn new(default: LogLevel) -> Self {
+        Level { value: AtomicU8::new(default as u8) }
+    }
+
+    /// `level` önemindeki iletiler yazdırılmalı mı?
+    #[inline]
+    pub fn enabled(&self, level: LogLevel) -> bool {
+        level != LogLevel::Off && level as u8 <= self.value.load(Ordering::Relaxed)
+    }
+
+    pub fn set(&self, level: LogLevel) {
+        self.value.store(level as u8, Ordering::Relaxed);
+    }
+}
+
+impl Tunable for Level {
+    fn kind(&self) -> &'static str {
+        "level"
+    }
+
+    fn show(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        let value = usize::from(self.value.load(Ordering::Relaxed));
+        write!(out, "{}", LEVEL_NAMES.get(value).copied().unwrap_or("?"))
+    }
+
+    fn set_str(&self, value: &str) -> Result<(), KernelError> {
+        let index = LEVEL_NAMES
+            .iter()
+            .position(|&name| name == value)
+            .or_else(|| value.parse::<usize>().ok().filter(|&n| n < LEVEL_NAMES.len()))
+            .ok_or(KernelError::InvalidArgument)?;
+        self.value.store(index as u8, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+/// Modülün günlük seviyesine göre koşullu yazdırır.
+///
+/// ```ignore
+/// crate::sysctl!(pub LOG: crate::sysctl::Level = crate::sysctl::Level::new(LogLevel::Info), "log.net", "Ağ günlük seviyesi");
+/// crate::klog!(LOG, Warn, "[NET] {}: bağlantı koptu", name);
+/// ```
+#[macro_export]
+macro_rules! klog {
+    ($level:expr, $severity:ident, $($arg:tt)*) => {
+        if $level.enabled($crate::sysctl::LogLevel::$severity) {
+            // Görevin `log` akışı ayrı bir konsol hedefine bağlanmış olabilir
+            $crate::console::log($crate::sysctl::LogLevel::$severity, format_args!($($arg)*));
+        }
+    };
+}
+
+// -----------------------------------------------------------------------------
+// KAYIT
+// -----------------------------------------------------------------------------
+
+/// Kayıtlı parametre.
+#[repr(C)]
+pub struct Sysctl {
+    /// `alt_sistem.ad` biçiminde ad.
+    pub name: &'static str,
+    /// `sysctl` listesinde gösterilen açıklama.
+    pub help: &'static str,
+    pub value: &'static dyn Tunable,
+}
+
+/// Tipli bir parametre statiği tanımlar ve `.sysctls` bölümüne kaydeder.
+///
+/// ```ignore
+/// crate::sysctl!(pub(crate) TIME_SLICE: crate::sysctl::U32 = crate::sysctl::U32::new(10, 1, 1000),
+///     "sched.time_slice", "Zaman dilimi (tik)");
+/// let slice = TIME_SLICE.get();
+/// ```
+#[macro_export]
+macro_rules! sysctl {
+    ($vis:vis $ident:ident: $ty:ty = $init:expr, $name:expr, $help:expr) => {
+        $vis static $ident: $ty = $init;
+        const _: () = {
+            #[used]
+            #[link_section = ".sysctls"]
+            static __SYSCTL: $crate::sysctl::Sysctl = $crate::sysctl::Sysctl {
+                name: $name,
+                help: $help,
+                value: &$ident,
+            };
+        };
+    };
+}
+
+extern "C" {
+    static __sysctls_start: Sysctl;
+    static __sysctls_end: Sysctl;
+}
+
+/// Kayıtlı tüm parametreler.
+pub fn tunables() -> &'static [Sysctl] {
+    // SAFETY: Semboller linker betiği tarafından `.sysctls` bölümünün iki ucuna yerleştirilir.
+    unsafe {
+        let start = &__sysctls_start as *const Sysctl;
+        let end = &__sysctls_end as *const Sysctl;
+        let count = (end as usize - start as usize) / core::mem::size_of::<Sysctl>();
+        core::slice::from_raw_parts(start, count)
+    }
+}
+
+/// Ada göre parametre arar.
+pub fn find(name: &str) -> Option<&'static Sysctl> {
+    tunables().iter().find(|t| t.name == name)
+}
+
+/// Parametreyi metinden ayarlar.
+///
+/// # Hatalar
+/// * `NotFound`: Bu adla parametre yok.
+/// * `InvalidArgument`: Değer ayrıştırılamadı veya aralık dışında.
+pub fn set(name: &str, value: &str) -> Result<(), KernelError> {
+    find(name).ok_or(KernelError::NotFound)?.value.set_str(value.trim())
+}
+
+// -----------------------------------------------------------------------------
+// KABUK KOMUTU
+// -----------------------------------------------------------------------------
+
+/// Parametre değerini konsola yazmak için `fmt::Write` köprüsü.
+struct Console;
+
+impl fmt::Write for Console {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        crate::serial_print!("{}", s);
+        Ok(())
+    }
+}
+
+fn print(tunable: &Sysctl, verbose: bool) {
+    crate::serial_print!("  {:<24} = ", tunable.name);
+    let _ = tunable.value.show(&mut Console);
+    if verbose {
+        serial_println!("  ({}) {}", tunable.value.kind(), tunable.help);
+    } else {
+        serial_println!();
+    }
+}
+
+fn sysctl_command(args: &[&str]) -> Result<(), KernelError> {
+    match args {
+        [_] => tunables().iter().for_each(|t| print(t, true)),
+        [_, name] => print(find(name).ok_or(KernelError::NotFound)?, true),
+        [_, name, value] => {
+            set(name, value)?;
+            print(find(name).ok_or(KernelError::NotFound)?, false);
+        }
+        _ => return Err(KernelError::InvalidArgument),
+    }
+    Ok(())
+}
+
+crate::shell_command!("sysctl", "Çekirdek parametrelerini gösterir/ayarlar (sysctl [ad [değer]])", sysctl_command);
@@ -0,0 +1,318 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/thermal.rs
+// Isıl izleme: sıcaklık algılayıcıları, eşik noktaları ve acil kısma.
+//
+// Algılayıcı sürücüleri `ThermalSensor` arayüzünü uygular ve `register` ile
+// kaydedilir (sanal makinede `virtual` algılayıcı, ileride SoC'lerin ısıl
+// MMIO blokları). `thermal` işçisi (`kthread::spawn_periodic`, zamanlayıcı
+// tabanlı) her `thermal.poll_ms` milisaniyede tüm algılayıcıları okur ve en
+// yüksek sıcaklığı eşiklerle karşılaştırır:
+//
+// * `thermal.warn_mc`: uyarı yazdırılır.
+// * `thermal.throttle_mc`: normal sınıf zaman dilimi `thermal.throttle_pct`
+//   yüzdesine düşürülür (`sched::set_slice_scale`); sıcaklık eşiğin
+//   `thermal.hysteresis_mc` altına inince eski haline döner.
+// * `thermal.critical_mc`: düzenli kapanış ve güç kesme (`shutdown::poweroff`).
+//
+// Sıcaklıklar milidere Celsius (m°C) cinsindendir. Okunamayan algılayıcı
+// atlanır; hiçbir algılayıcı okunamazsa seviye değişmez.
+//
+// Sanal algılayıcı, işlemci kullanımından türetilen bir sıcaklık üretir;
+// eşikleri denemek için değeri `thermal emulate <m°C>` ile sabitlenebilir.
+//
+// Kabuk komutu: `thermal [emulate <m°C> | emulate off]`; procfs: `/proc/thermal`
+
+use core::cell::UnsafeCell;
+use core::fmt::{self, Write};
+use core::sync::atomic::{AtomicI32, AtomicU8, Ordering};
+use crate::platformgeneric::spinlock::Spinlock;
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+use crate::sysctl::U32;
+
+// -----------------------------------------------------------------------------
+// ALGILAYICILAR
+// -----------------------------------------------------------------------------
+
+/// Kaydedilebilecek en fazla algılayıcı.
+pub const MAX_SENSORS: usize = 8;
+
+/// Örnekleme işçisinin önceliği; yük altında da zamanında çalışmalıdır.
+const THERMAL_TASK_PRIORITY: u8 = 4;
+
+/// Sıcaklık algılayıcısı sürücüsü.
+pub trait ThermalSensor: Sync {
+    fn name(&self) -> &'static str;
+    /// Anlık sıcaklık (m°C). Görev bağlamında çağrılır.
+    fn read_millicelsius(&self) -> Result<i32, KernelError>;
+}
+
+struct SensorTable {
+    lock: Spinlock,
+    sensors: UnsafeCell<[Option<&'static dyn ThermalSensor>; MAX_SENSORS]>,
+}
+
+// Tabloya erişim `lock` ile korunur.
+unsafe impl Sync for SensorTable {}
+
+static SENSORS: SensorTable = SensorTable {
+    lock: Spinlock::new(),
+    sensors: UnsafeCell::new([None; MAX_SENSORS]),
+};
+
+fn with_sensors<R>(f: impl FnOnce(&mut [Option<&'static dyn ThermalSensor>; MAX_SENSORS]) -> R) -> R {
+    SENSORS.lock.lock();
+    // SAFETY: Kilit tutulduğu sürece tabloya tek erişen biziz.
+    let result = unsafe { f(&mut *SENSORS.sensors.get()) };
+    SENSORS.lock.unlock();
+    result
+}
+
+/// Bir algılayıcı kaydeder.
+///
+/// # Dönüş Değeri
+/// Algılayıcı numarası; tablo doluysa `OutOfMemoryStatic`.
+pub fn register(sensor: &'static dyn ThermalSensor) -> Result<usize, KernelError> {
+    let id = with_sensors(|sensors| {
+        let id = sensors.iter().position(Option::is_none).ok_or(KernelError::OutOfMemoryStatic)?;
+        sensors[id] = Some(sensor);
+        Ok(id)
+    })?;
+    serial_println!("[THERMAL] {}: algılayıcı {}", sensor.name(), id);
+    Ok(id)
+}
+
+/// Kayıtlı algılayıcılar.
+fn sensors() -> [Option<&'static dyn ThermalSensor>; MAX_SENSORS] {
+    with_sensors(|sensors| *sensors)
+}
+
+// -----------------------------------------------------------------------------
+// EŞİKLER
+// -----------------------------------------------------------------------------
+
+crate::sysctl!(POLL_MS: U32 = U32::new(1000, 10, 60_000),
+    "thermal.poll_ms", "Algılayıcı örnekleme aralığı (ms)");
+crate::sysctl!(WARN_MC: U32 = U32::new(70_000, 0, 200_000),
+    "thermal.warn_mc", "Uyarı eşiği (m°C)");
+crate::sysctl!(THROTTLE_MC: U32 = U32::new(85_000, 0, 200_000),
+    "thermal.throttle_mc", "Kısma eşiği (m°C)");
+crate::sysctl!(CRITICAL_MC: U32 = U32::new(100_000, 0, 200_000),
+    "thermal.critical_mc", "Acil kapanış eşiği (m°C)");
+crate::sysctl!(HYSTERESIS_MC: U32 = U32::new(2_000, 0, 20_000),
+    "thermal.hysteresis_mc", "Seviyeden çıkmak için eşiğin altına inilmesi gereken miktar (m°C)");
+crate::sysctl!(THROTTLE_PCT: U32 = U32::new(50, 1, 100),
+    "thermal.throttle_pct", "Kısmada normal sınıf zaman diliminin yüzdesi");
+
+/// Isıl seviye; eşikler aşıldıkça yükselir.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(u8)]
+pub enum Level {
+    Normal = 0,
+    Warm = 1,
+    Throttled = 2,
+    Critical = 3,
+}
+
+impl Level {
+    fn from_u8(value: u8) -> Level {
+        match value {
+            1 => Level::Warm,
+            2 => Level::Throttled,
+            3 => Level::Critical,
+            _ => Level::Normal,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Level::Normal => "normal",
+            Level::Warm => "warm",
+            Level::Throttled => "throttled",
+            Level::Critical => "critical",
+        }
+    }
+
+    /// Seviyeye giriş eşiği (m°C).
+    fn threshold(self) -> i64 {
+        i64::from(match self {
+            Level::Normal => 0,
+            Level::Warm => WARN_MC.get(),
+            Level::Throttled => THROTTLE_MC.get(),
+            Level::Critical => CRITICAL_MC.get(),
+        })
+    }
+}
+
+const LEVELS: [Level; 3] = [Level::Warm, Level::Throttled, Level::Critical];
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Normal as u8);
+/// Son örnekteki en yüksek sıcaklık (m°C); `i32::MIN`: örnek yok.
+static LAST_MC: AtomicI32 = AtomicI32::new(i32::MIN);
+
+crate::kernel_counter!(TRIPS, "thermal.trips");
+crate::kernel_counter!(READ_ERRORS, "thermal.read_errors");
+
+/// Geçerli ısıl seviye.
+pub fn level() -> Level {
+    Level::from_u8(LEVEL.load(Ordering::Relaxed))
+}
+
+/// Sıcaklığa göre yeni seviye. Yükselme eşikte, düşme eşiğin histerezis
+/// kadar altında olur.
+fn next_level(current: Level, temp: i32) -> Level {
+    let temp = i64::from(temp);
+    let hysteresis = i64::from(HYSTERESIS_MC.get());
+    LEVELS.iter().rev().copied().find(|&level| {
+        let threshold = level.threshold();
+        if level <= current { temp >= threshold - hysteresis } else { temp >= threshold }
+    })
+    .unwrap_or(Level::Normal)
+}
+
+/// Seviye değişiminin eylemlerini uygular.
+fn enter_level(old: Level, new: Level, temp: i32) {
+    if new > old {
+        TRIPS.inc();
+    }
+    serial_println!("[THERMAL] {} m°C: {} -> {}", temp, old.name(), new.name());
+    let scale = if new >= Level::Throttled { THROTTLE_PCT.get() } else { 100 };
+    let _ = crate::sched::set_slice_scale(scale);
+    if new == Level::Critical {
+        serial_println!("[THERMAL] Kritik sıcaklık, sistem kapatılıyor");
+        crate::shutdown::poweroff();
+    }
+}
+
+/// Tüm algılayıcıları okur; en yüksek sıcaklık.
+fn sample() -> Option<i32> {
+    let mut hottest = None;
+    for sensor in sensors().iter().flatten() {
+        match sensor.read_millicelsius() {
+            Ok(temp) => hottest = hottest.max(Some(temp)),
+            Err(_) => READ_ERRORS.inc(),
+        }
+    }
+    hottest
+}
+
+fn poll_interval() -> u64 {
+    (u64::from(POLL_MS.get()) * crate::sched::tick_hz() / 1000).max(1)
+}
+
+fn thermal_work(work: crate::kthread::Work) {
+    if work != crate::kthread::Work::Tick {
+        return;
+    }
+    let Some(temp) = sample() else { return };
+    LAST_MC.store(temp, Ordering::Relaxed);
+    let old = level();
+    let new = next_level(old, temp);
+    if new != old {
+        LEVEL.store(new as u8, Ordering::Relaxed);
+        enter_level(old, new, temp);
+    }
+}
+
+// -----------------------------------------------------------------------------
+// SANAL ALGILAYICI
+// -----------------------------------------------------------------------------
+
+/// Boştaki sıcaklık (m°C).
+const VIRTUAL_IDLE_MC: i32 = 40_000;
+/// Tam yükte boştakine eklenen sıcaklık (m°C).
+const VIRTUAL_LOAD_MC: i32 = 30_000;
+
+/// Sanal makine algılayıcısı: işlemci 0'ın son 5 saniyelik kullanımından
+/// türetilir. `EMULATED` ayarlıysa o değeri döndürür.
+struct VirtualSensor;
+
+/// `i32::MIN`: öykünme kapalı.
+static EMULATED: AtomicI32 = AtomicI32::new(i32::MIN);
+
+impl ThermalSensor for VirtualSensor {
+    fn name(&self) -> &'static str {
+        "virtual"
+    }
+
+    fn read_millicelsius(&self) -> Result<i32, KernelError> {
+        match EMULATED.load(Ordering::Relaxed) {
+            i32::MIN => {
+                let permille = crate::idle::utilization(0, 5).unwrap_or(0) as i32;
+                Ok(VIRTUAL_IDLE_MC + VIRTUAL_LOAD_MC * permille / 1000)
+            }
+            temp => Ok(temp),
+        }
+    }
+}
+
+static VIRTUAL: VirtualSensor = VirtualSensor;
+
+/// Örnekleme işçisini başlatır; sanal makinede sanal algılayıcıyı kaydeder.
+fn init() -> Result<(), KernelError> {
+    if crate::hypervisor::is_virtualized() {
+        register(&VIRTUAL)?;
+    }
+    crate::kthread::spawn_periodic("thermal", THERMAL_TASK_PRIORITY, poll_interval, thermal_work)?;
+    Ok(())
+}
+
+crate::initcall!(late, "thermal", init);
+
+// -----------------------------------------------------------------------------
+// KABUK VE PROCFS
+// -----------------------------------------------------------------------------
+
+fn show_thermal(out: &mut dyn Write) -> fmt::Result {
+    writeln!(out, "level {}", level().name())?;
+    match LAST_MC.load(Ordering::Relaxed) {
+        i32::MIN => writeln!(out, "temp -")?,
+        temp => writeln!(out, "temp {}", temp)?,
+    }
+    writeln!(out, "slice_scale {}", crate::sched::slice_scale())?;
+    for (id, sensor) in sensors().iter().enumerate() {
+        let Some(sensor) = sensor else { continue };
+        match sensor.read_millicelsius() {
+            Ok(temp) => writeln!(out, "sensor{} {} {}", id, sensor.name(), temp)?,
+            Err(_) => writeln!(out, "sensor{} {} -", id, sensor.name())?,
+        }
+    }
+    Ok(())
+}
+
+crate::proc_entry!("thermal", show_thermal);
+
+/// `thermal [emulate <m°C> | emulate off]`
+fn thermal_command(args: &[&str]) -> Result<(), KernelError> {
+    match args.get(1..) {
+        Some(["emulate", "off"]) => EMULATED.store(i32::MIN, Ordering::Relaxed),
+        Some(["emulate", temp]) => {
+            let temp: i32 = temp.parse().map_err(|_| KernelError::InvalidArgument)?;
+            if temp == i32::MIN {
+                return Err(KernelError::InvalidArgument);
+            }
+            EMULATED.store(temp, Ordering::Relaxed);
+        }
+        Some([]) | None => {}
+        Some(_) => return Err(KernelError::InvalidArgument),
+    }
+    serial_println!(
+        "  seviye {} | eşikler: uyarı {} kısma {} kritik {} m°C | dilim %{}",
+        level().name(),
+        WARN_MC.get(),
+        THROTTLE_MC.get(),
+        CRITICAL_MC.get(),
+        crate::sched::slice_scale()
+    );
+    for (id, sensor) in sensors().iter().enumerate() {
+        let Some(sensor) = sensor else { continue };
+        match sensor.read_millicelsius() {
+            Ok(temp) => serial_println!("  {:>2} {:<12} {:>7} m°C", id, sensor.name(), temp),
+            Err(e) => serial_println!("  {:>2} {:<12} {:?}", id, sensor.name(), e),
+        }
+    }
+    Ok(())
+}
+
+crate::shell_command!("thermal", "Isıl durumu gösterir (thermal [emulate <m°C> | emulate off])", thermal_command);
@@ -0,0 +1,246 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/timer.rs
+// Çekirdek yazılım zamanlayıcıları: karma zamanlama çarkı (hashed timing wheel).
+//
+// Zamanlayıcılar statik bir tabloda tutulur ve dolma tiklerine göre
+// `WHEEL_SLOTS` kovadan birine zincirlenir. Her tikte yalnızca o tikin
+// kovası taranır; çark turundan daha uzak zamanlayıcılar kovada kalır ve
+// dolma tikleri gelene kadar atlanır. Böylece tik başına iş, kurulu
+// zamanlayıcı sayısından bağımsızdır. Kimlikler (tablo yuvaları) `TIMER_IDS`
+// havuzundan kilitsiz ve O(1) alınır; kurma yolu tabloyu taramaz.
+//
+// Geri çağırmalar zamanlayıcı kesmesi bağlamında, tablo kilidi bırakıldıktan
+// sonra çalışır; kısa olmalı ve yalnızca kesme bağlamında güvenli
+// fonksiyonları (örn. `sched::wake`) çağırmalıdır.
+
+use core::cell::UnsafeCell;
+use crate::platformgeneric::spinlock::IrqSafeSpinlock;
+use crate::platformgeneric::KernelError;
+
+// -----------------------------------------------------------------------------
+// SABİTLER VE TİPLER
+// -----------------------------------------------------------------------------
+
+/// Aynı anda kurulabilecek en fazla zamanlayıcı.
+pub const MAX_TIMERS: usize = 32;
+/// Çarktaki kova sayısı (ikinin kuvveti).
+const WHEEL_SLOTS: usize = 64;
+/// Tek bir tikte çalıştırılabilecek en fazla geri çağırma; fazlası bir
+/// sonraki tike kalır.
+const MAX_FIRE_PER_TICK: usize = 8;
+
+/// Zamanlayıcı dolduğunda çağrılan fonksiyon (kesme bağlamı).
+pub type TimerFn = fn(arg: usize);
+
+/// Zamanlayıcı tablosundaki indeks. Tek seferlik zamanlayıcının kimliği
+/// dolduktan sonra geçersizdir.
+pub type TimerId = usize;
+
+#[derive(Clone, Copy)]
+struct Timer {
+    active: bool,
+    /// Dolma tiki (mutlak).
+    expires: u64,
+    /// 0 değilse zamanlayıcı bu aralıkla yeniden kurulur.
+    period: u64,
+    callback: Option<TimerFn>,
+    arg: usize,
+    /// Aynı kovadaki sıradaki zamanlayıcı.
+    next: Option<usize>,
+}
+
+impl Timer {
+    const fn empty() -> Self {
+        Timer { active: false, expires: 0, period: 0, callback: None, arg: 0, next: None }
+    }
+}
+
+struct Wheel {
+    lock: IrqSafeSpinlock,
+    timers: UnsafeCell<[Timer; MAX_TIMERS]>,
+    slots: UnsafeCell<[Option<usize>; WHEEL_SLOTS]>,
+}
+
+// Tüm alanlara erişim `lock` ile korunur; tik kesmesi de aldığından kesmeler kapalı tutulur.
+unsafe impl Sync for Wheel {}
+
+static WHEEL: Wheel = Wheel {
+    lock: IrqSafeSpinlock::new(),
+    timers: UnsafeCell::new([Timer::empty(); MAX_TIMERS]),
+    slots: UnsafeCell::new([None; WHEEL_SLOTS]),
+};
+
+crate::mempool!(TIMER_IDS: (), MAX_TIMERS, "timer");
+
+crate::kernel_counter!(FIRED, "timer.fired");
+crate::kernel_counter!(DEFERRED, "timer.deferred");
+
+fn with_wheel<R>(f: impl FnOnce(&mut [Timer; MAX_TIMERS], &mut [Option<usize>; WHEEL_SLOTS]) -> R) -> R {
+    let flags = WHEEL.lock.lock();
+    // SAFETY: Kilit tutulduğu sürece tabloya tek erişen biziz.
+    let result = unsafe { f(&mut *WHEEL.timers.get(), &mut *WHEEL.slots.get()) };
+    WHEEL.lock.unlock(flags);
+    result
+}
+
+fn slot_of(expires: u64) -> usize {
+    (expires % WHEEL_SLOTS as u64) as usize
+}
+
+fn link(timers: &mut [Timer; MAX_TIMERS], slots: &mut [Option<usize>; WHEEL_SLOTS], id: TimerId) {
+    let slot = slot_of(timers[id].expires);
+    timers[id].next = slots[slot];
+    slots[slot] = Some(id);
+}
+
+fn unlink(timers: &mut [Timer; MAX_TIMERS], slots: &mut [Option<usize>; WHEEL_SLOTS], id: TimerId) {
+    let slot = slot_of(timers[id].expires);
+    let next = timers[id].next;
+    let mut cursor = &mut slots[slot];
+    while let Some(current) = *cursor {
+        if current == id {
+            *cursor = next;
+            break;
+        }
+        cursor = &mut timers[current].next;
+    }
+    timers[id].next = None;
+}
+
+// -----------------------------------------------------------------------------
+// ZAMANLAYICI KURMA
+// -----------------------------------------------------------------------------
+
+/// `delay` tik sonra `callback(arg)` çağıracak bir zamanlayıcı kurar.
+/// `period` 0 değilse zamanlayıcı her `period` tikte yeniden dolar.
+///
+/// # Dönüş Değeri
+/// Zamanlayıcı kimliği; tablo doluysa `OutOfMemoryStatic`.
+pub fn start(delay: u64, period: u64, callback: TimerFn, arg: usize) -> Result<TimerId, KernelError> {
+    let expires = crate::sched::ticks() + delay.max(1);
+    let id = TIMER_IDS.alloc_index().ok_or(KernelError::OutOfMemoryStatic)?;
+    with_wheel(|timers, slots| {
+        timers[id] = Timer { active: true, expires, period, callback: Some(callback), arg, next: None };
+        link(timers, slots, id);
+    });
+    Ok(id)
+}
+
+/// Etkin olmayan zamanlayıcının kimliğini havuza geri verir.
+fn release(timers: &mut [Timer; MAX_TIMERS], id: TimerId) {
+    timers[id].active = false;
+    // SAFETY: Kimlik `start` ile alındı; `active` bayrağı onu bir kez geri vermemizi sağlar.
+    unsafe { TIMER_IDS.free_index(id) };
+}
+
+/// Kurulu bir zamanlayıcıyı iptal eder.
+pub fn cancel(id: TimerId) -> Result<(), KernelError> {
+    with_wheel(|timers, slots| match timers.get(id) {
+        Some(t) if t.active => {
+            unlink(timers, slots, id);
+            release(timers, id);
+            Ok(())
+        }
+        _ => Err(KernelError::NotFound),
+    })
+}
+
+/// Zamanlayıcıyı `delay` tik sonra dolacak şekilde yeniden kurar.
+pub fn modify(id: TimerId, delay: u64) -> Result<(), KernelError> {
+    let expires = crate::sched::ticks() + delay.max(1);
+    with_wheel(|timers, slots| match timers.get(id) {
+        Some(t) if t.active => {
+            unlink(timers, slots, id);
+            timers[id].expires = expires;
+            link(timers, slots, id);
+            Ok(())
+        }
+        _ => Err(KernelError::NotFound),
+    })
+}
+
+/// Zamanlayıcı kesmesinden her tikte çağrılır (`sched::tick` içinden).
+pub fn tick(now: u64) {
+    let mut fire: [(Option<TimerFn>, usize); MAX_FIRE_PER_TICK] = [(None, 0); MAX_FIRE_PER_TICK];
+    let mut count = 0;
+
+    with_wheel(|timers, slots| {
+        let slot = slot_of(now);
+        let mut cursor = slots[slot];
+        while let Some(id) = cursor {
+            cursor = timers[id].next;
+            if timers[id].expires > now {
+                continue;
+            }
+            unlink(timers, slots, id);
+            if count == MAX_FIRE_PER_TICK {
+                // Sıradaki tikin kovasına ertelenir
+                DEFERRED.inc();
+                timers[id].expires = now + 1;
+                link(timers, slots, id);
+                continue;
+            }
+            fire[count] = (timers[id].callback, timers[id].arg);
+            count += 1;
+            if timers[id].period != 0 {
+                timers[id].expires = now + timers[id].period;
+                link(timers, slots, id);
+            } else {
+                release(timers, id);
+            }
+        }
+    });
+
+    for (callback, arg) in fire[..count].iter().copied() {
+        if let Some(callback) = callback {
+            FIRED.inc();
+            crate::replay::timer(callback as usize, arg);
+            callback(arg);
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// UYKU
+// -----------------------------------------------------------------------------
+
+fn wake_task(arg: usize) {
+    let _ = crate::sched::wake(arg);
+}
+
+/// Çalışan görevi `ticks` tik boyunca bekletir.
+///
+/// Görev bağlamı yoksa (açılış) ya da zamanlayıcı tablosu doluysa tik
+/// sayacı meşgul beklemeyle izlenir.
+pub fn sleep(ticks: u64) {
+    let deadline = crate::sched::ticks() + ticks;
+    if let Some(me) = crate::sched::current() {
+        // Önce bloklanır: zamanlayıcı kurulur kurulmaz dolsa bile uyandırma kaybolmaz
+        let _ = crate::sched::block(me);
+        if start(ticks, 0, wake_task, me).is_err() {
+            let _ = crate::sched::wake(me);
+        }
+    }
+    // Bloklanan görev bir sonraki kesme dönüşünde işlemciden alınır ve
+    // zamanlayıcı dolunca hazır olarak buradan devam eder
+    while crate::sched::ticks() < deadline {
+        core::hint::spin_loop();
+    }
+}
+
+// -----------------------------------------------------------------------------
+// PROCFS
+// -----------------------------------------------------------------------------
+
+fn show_timers(out: &mut dyn core::fmt::Write) -> core::fmt::Result {
+    let now = crate::sched::ticks();
+    let mut active = [Timer::empty(); MAX_TIMERS];
+    with_wheel(|timers, _| active = *timers);
+    for (id, t) in active.iter().enumerate().filter(|(_, t)| t.active) {
+        writeln!(out, "{} {} {}", id, t.expires.saturating_sub(now), t.period)?;
+    }
+    Ok(())
+}
+
+crate::proc_entry!("timers", show_timers);
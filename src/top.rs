@@ -0,0 +1,198 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/top.rs
+// Kabuk `top` komutu: görev ve kesme istatistiklerinin canlı görünümü.
+//
+// Ekran her aralıkta ANSI kaçış dizileriyle (imleci başa al, ekranı temizle)
+// yeniden çizilir. Görev CPU yüzdesi, aralık boyunca artan `runtime_ticks`'in
+// geçen tike oranıdır; kesmeler aynı aralıktaki sayım artışına göre sıralanır.
+// Çekirdek kullanımı `idle::cpu_load`, yığın kullanımı
+// `TaskStackAllocator::high_water_mark` üzerinden okunur.
+//
+// Kullanım: `top [aralık_sn] [tur]` — `tur` 0 ise (varsayılan) `q` tuşuna
+// basılana kadar sürer.
+
+use core::fmt;
+use crate::idle;
+use crate::memory::memory::{MemoryRegions, TaskStackAllocator};
+use crate::platformgeneric::{KernelError, SystemConstants};
+use crate::sched::{self, SchedClass, TaskState, MAX_TASKS};
+use crate::serial_println;
+use crate::stats;
+
+// -----------------------------------------------------------------------------
+// SABİTLER
+// -----------------------------------------------------------------------------
+
+/// Gösterilen en yoğun kesme sayısı.
+const TOP_IRQS: usize = 5;
+
+/// Tuş denetimi arasında beklenen tik (~50 ms).
+const POLL_TICKS: u64 = SystemConstants::TIMER_TICK_HZ / 20;
+
+/// İmleci sol üste al ve ekranı temizle.
+const ANSI_HOME_CLEAR: &str = "\x1b[H\x1b[2J";
+/// Ters video (başlık satırı) aç / kapat.
+const ANSI_REVERSE: &str = "\x1b[7m";
+const ANSI_RESET: &str = "\x1b[0m";
+
+/// Bir önceki çizimdeki değerler; artışlar bunlara göre hesaplanır.
+struct Snapshot {
+    ticks: u64,
+    runtime: [u64; MAX_TASKS],
+    irqs: [u64; stats::MAX_IRQS],
+}
+
+impl Snapshot {
+    fn take() -> Self {
+        let mut snapshot = Snapshot { ticks: sched::ticks(), runtime: [0; MAX_TASKS], irqs: [0; stats::MAX_IRQS] };
+        for (id, runtime) in snapshot.runtime.iter_mut().enumerate() {
+            *runtime = sched::task_info(id).map_or(0, |info| info.runtime_ticks);
+        }
+        for (irq, count) in snapshot.irqs.iter_mut().enumerate() {
+            *count = stats::irq_count(irq);
+        }
+        snapshot
+    }
+}
+
+// -----------------------------------------------------------------------------
+// ÇİZİM
+// -----------------------------------------------------------------------------
+
+/// Binde değeri yüzdenin tam ve ondalık kısmına ayırır (örn. 123 → (12, 3)).
+fn percent(permille: u64) -> (u64, u64) {
+    (permille / 10, permille % 10)
+}
+
+/// Değer yoksa "-" yazar (hizalama korunur).
+struct OrDash(Option<usize>);
+
+impl fmt::Display for OrDash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(value) => fmt::Display::fmt(&value, f),
+            None => f.pad("-"),
+        }
+    }
+}
+
+fn state_name(state: TaskState) -> &'static str {
+    match state {
+        TaskState::Ready => "hazır",
+        TaskState::Running => "çalışıyor",
+        TaskState::Blocked => "bekliyor",
+        TaskState::Exited => "bitti",
+        TaskState::Free => "-",
+    }
+}
+
+fn draw(prev: &Snapshot, now: &Snapshot) {
+    let elapsed = now.ticks.saturating_sub(prev.ticks).max(1);
+
+    crate::serial_print!("{}", ANSI_HOME_CLEAR);
+    serial_println!("top - tik {}  aralık {} tik", now.ticks, elapsed);
+    for cpu in 0..idle::online_cpus() {
+        let Some(load) = idle::cpu_load(cpu) else { continue };
+        let [one, ten, sixty] = load.permille.map(|p| percent(u64::from(p)));
+        serial_println!(
+            "cpu{}: {}.{}% (1s) {}.{}% (10s) {}.{}% (60s)",
+            cpu, one.0, one.1, ten.0, ten.1, sixty.0, sixty.1
+        );
+    }
+    serial_println!();
+
+    serial_println!(
+        "{}  ID GÖREV            DURUM     SINIF  ÖNC   CPU%   YIĞIN/{:<5} GEÇİŞ{}",
+        ANSI_REVERSE,
+        MemoryRegions::TASK_STACK_SIZE,
+        ANSI_RESET
+    );
+    for id in 0..MAX_TASKS {
+        let Some(info) = sched::task_info(id) else { continue };
+        let used = info.runtime_ticks.saturating_sub(prev.runtime[id]);
+        let (whole, tenth) = percent((used * 1000 / elapsed).min(1000));
+        let class = match info.class {
+            SchedClass::Normal => "normal",
+            SchedClass::RealTime => "rt",
+            SchedClass::Idle => "idle",
+        };
+        serial_println!(
+            "  {:>2} {:<16} {:<9} {:<6} {:>3} {:>4}.{} {:>11} {:>6}",
+            id,
+            info.name,
+            state_name(info.state),
+            class,
+            info.priority,
+            whole,
+            tenth,
+            OrDash(TaskStackAllocator::high_water_mark(id)),
+            info.switches
+        );
+    }
+    serial_println!();
+
+    // Aralıktaki artışa göre en yoğun kesmeler (sıralı ekleme)
+    let mut busiest: [(usize, u64); TOP_IRQS] = [(0, 0); TOP_IRQS];
+    for irq in 0..stats::MAX_IRQS {
+        let delta = now.irqs[irq].saturating_sub(prev.irqs[irq]);
+        if delta == 0 || delta <= busiest[TOP_IRQS - 1].1 {
+            continue;
+        }
+        let pos = busiest.iter().position(|&(_, d)| delta > d).unwrap_or(TOP_IRQS - 1);
+        busiest.copy_within(pos..TOP_IRQS - 1, pos + 1);
+        busiest[pos] = (irq, delta);
+    }
+    serial_println!("{}  IRQ   ARALIK/sn       TOPLAM{}", ANSI_REVERSE, ANSI_RESET);
+    for &(irq, delta) in busiest.iter().filter(|&&(_, d)| d > 0) {
+        serial_println!(
+            "  {:>3} {:>11} {:>12}",
+            irq,
+            delta * SystemConstants::TIMER_TICK_HZ / elapsed,
+            now.irqs[irq]
+        );
+    }
+}
+
+/// `ticks` boyunca bekler; bu sürede `q` basılırsa `true`.
+fn wait_or_quit(ticks: u64) -> bool {
+    let deadline = sched::ticks() + ticks;
+    while sched::ticks() < deadline {
+        if matches!(crate::input::read_char(), Some(b'q' | b'Q')) {
+            return true;
+        }
+        crate::timer::sleep(POLL_TICKS.min(deadline.saturating_sub(sched::ticks())).max(1));
+    }
+    false
+}
+
+// -----------------------------------------------------------------------------
+// KABUK KOMUTU
+// -----------------------------------------------------------------------------
+
+fn top_command(args: &[&str]) -> Result<(), KernelError> {
+    let parse = |index: usize, default: u64| match args.get(index) {
+        Some(arg) => arg.parse::<u64>().map_err(|_| KernelError::InvalidArgument),
+        None => Ok(default),
+    };
+    let interval = parse(1, 1)?.max(1) * SystemConstants::TIMER_TICK_HZ;
+    let rounds = parse(2, 0)?;
+
+    let mut prev = Snapshot::take();
+    let mut round = 0;
+    loop {
+        if wait_or_quit(interval) {
+            break;
+        }
+        let now = Snapshot::take();
+        draw(&prev, &now);
+        prev = now;
+        round += 1;
+        if rounds != 0 && round >= rounds {
+            break;
+        }
+    }
+    Ok(())
+}
+
+crate::shell_command!("top", "Görev ve kesme istatistiklerini canlı gösterir (top [aralık_sn] [tur], çıkış: q)", top_command);
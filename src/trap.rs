@@ -0,0 +1,124 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/trap.rs
+// Mimariden bağımsız tuzak (trap) nedeni soyutlaması.
+//
+// Her mimarinin `exception.rs` dosyası kendi ham neden kodunu (SCAUSE, ESR_EL1,
+// vektör numarası, TT vb.) `decode_cause` ile bu ortak `Cause` tipine çevirir.
+// Genel hata işleyicisi, sinyal teslimi ve gdbstub yalnızca bu tipi görür.
+
+use core::fmt;
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// ORTAK NEDEN TİPLERİ
+// -----------------------------------------------------------------------------
+
+/// Sayfa hatasına yol açan erişim türü.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    /// Veri okuma (load).
+    Read,
+    /// Veri yazma (store).
+    Write,
+    /// Talimat getirme (fetch).
+    Execute,
+}
+
+/// Tüm mimarilerde aynı anlama gelen tuzak nedeni.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cause {
+    /// Sayfa/TLB hatası. `addr`, hataya yol açan sanal adrestir.
+    PageFault { access: Access, addr: usize },
+    /// Hizalanmamış bellek erişimi.
+    Misaligned { addr: usize },
+    /// Sistem çağrısı talimatı (syscall, ecall, svc, sc, ta).
+    Syscall,
+    /// Yazılım kesme noktası talimatı (int3, ebreak, brk, break, tw).
+    Breakpoint,
+    /// Donanım tek adım (single-step) tuzağı (RFLAGS.TF, MDSCR_EL1.SS vb.).
+    SingleStep,
+    /// Donanım gözlem noktası (watchpoint) eşleşmesi. `addr`, gözlenen veri adresidir.
+    Watchpoint { addr: usize },
+    /// Geçersiz veya ayrıcalıklı talimat.
+    IllegalInstruction,
+    /// Donanım kesmesi. Değer, kesme denetleyicisine göre IRQ numarasıdır.
+    Interrupt(u32),
+    /// Makine denetimi, veri yolu hatası veya SError gibi donanım hataları.
+    MachineError,
+    /// Çözümlenemeyen ham neden kodu.
+    Unknown(u64),
+}
+
+impl Cause {
+    /// Nedenin çekirdek tarafından kurtarılamaz olup olmadığını döndürür.
+    ///
+    /// Kesmeler, sistem çağrıları ve kesme noktaları normal akışın parçasıdır;
+    /// diğerleri bir düzeltme (fixup) veya sinyal yoksa ölümcüldür.
+    pub fn is_fatal(&self) -> bool {
+        !matches!(
+            self,
+            Cause::Interrupt(_) | Cause::Syscall | Cause::Breakpoint | Cause::SingleStep | Cause::Watchpoint { .. }
+        )
+    }
+
+    /// Hataya yol açan adres (varsa).
+    pub fn fault_addr(&self) -> Option<usize> {
+        match *self {
+            Cause::PageFault { addr, .. } | Cause::Misaligned { addr } | Cause::Watchpoint { addr } => Some(addr),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Cause {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Cause::PageFault { access, addr } => write!(f, "Sayfa Hatası ({:?} @ {:#x})", access, addr),
+            Cause::Misaligned { addr } => write!(f, "Hizalama Hatası (@ {:#x})", addr),
+            Cause::Syscall => write!(f, "Sistem Çağrısı"),
+            Cause::Breakpoint => write!(f, "Kesme Noktası"),
+            Cause::SingleStep => write!(f, "Tek Adım"),
+            Cause::Watchpoint { addr } => write!(f, "Gözlem Noktası (@ {:#x})", addr),
+            Cause::IllegalInstruction => write!(f, "Geçersiz Talimat"),
+            Cause::Interrupt(irq) => write!(f, "Kesme (IRQ {})", irq),
+            Cause::MachineError => write!(f, "Makine Hatası"),
+            Cause::Unknown(raw) => write!(f, "Bilinmeyen Neden ({:#x})", raw),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// GENEL HATA İŞLEYİCİSİ
+// -----------------------------------------------------------------------------
+
+/// Kurtarılamaz bir tuzağı tüm mimarilerde aynı biçimde raporlar ve çekirdeği durdurur.
+///
+/// # Parametreler
+/// * `cause`: Mimariye özgü çözücüden gelen ortak neden.
+/// * `pc`: Tuzağın oluştuğu talimat adresi.
+pub fn handle_fatal(cause: Cause, pc: usize) -> ! {
+    serial_println!("[TRAP] {} | PC: {:#x}", cause, pc);
+    panic!("Kurtarılamaz Tuzak: {}", cause);
+}
+
+/// Kullanıcı kipinde oluşan ölümcül tuzak: çekirdek durmaz, yalnızca hata
+/// veren görev (ve varsa grubu) çekirdek dökümü alınarak sonlandırılır.
+/// Dönüşte çağıran mimari, `sched::schedule` ile seçilen göreve geçmelidir.
+///
+/// # Parametreler
+/// * `regs`: Mimarinin ELF `elf_gregset_t` sırasındaki yazmaçları (bkz. `coredump`).
+pub fn handle_user_fatal(cause: Cause, pc: usize, regs: &[u64]) {
+    let Some(task) = crate::sched::current() else { handle_fatal(cause, pc) };
+    serial_println!("[TRAP] Görev {}: {} | PC: {:#x}", task, cause, pc);
+    if let Err(e) = crate::coredump::dump(task, cause, pc, regs) {
+        serial_println!("[TRAP] Çekirdek dökümü alınamadı: {:?}", e);
+    }
+    // Sinyalle ölen görevin çıkış kodu 128 + sinyal (POSIX kabuk geleneği)
+    let code = 128 + crate::coredump::signal_for(cause) as i32;
+    match crate::process::group_of(task) {
+        Some(group) => crate::process::kill(group, code).ok(),
+        None => crate::sched::exit(task).ok(),
+    };
+    crate::sched::schedule();
+}
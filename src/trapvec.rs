@@ -0,0 +1,88 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/trapvec.rs
+// Bildirimsel tuzak vektör tablosu üreteci.
+//
+// Vektör tabloları elle yazılınca boş bırakılan girdiler (ARM FIQ/SError),
+// yanlış hizalama ve bir sonraki yuvaya taşan taslaklar kolayca gözden
+// kaçar. `trap_vectors!` bir girdi listesinden tabloyu `global_asm!` ile
+// üretir:
+//
+// * Tablo `align` baytlık sınıra hizalanır ve `slots * slot` bayt yer kaplar;
+//   her yuva `.org` ile tam ofsetine konur.
+// * Liste aralıklarla (`ilk ..= son`) tablonun tamamını boşluksuz ve sırayla
+//   kapsamalıdır; boşluk, çakışma veya eksik yuva derleme hatasıdır.
+// * Bir yuvanın taslağı yuva boyunu aşarsa bir sonraki `.org` geri gitmeye
+//   çalışır ve derleme yine hata verir.
+//
+// Her mimari iki yerel makro sağlar:
+// * Girdi makrosu `(tablo, işleyici)`: yuvaya sığan kısa taslak. Yuva
+//   numarası derleyici sembolü `<tablo>_slot`'tadır. Genelde kaydedici
+//   yazmaçları boşaltır, işleyiciyi ve yuva numarasını yükler ve ortak koda
+//   sıçrar. Aynı tabloda farklı girdi makroları kullanılabilir (örn. SPARC
+//   pencere taşma/dolum tuzakları).
+// * Ortak kod makrosu `(tablo)`: tablonun hemen arkasına bir kez yerleşen
+//   kaydet/çağır/geri yükle gövdesi (`<tablo>_common`).
+//
+// Kullananlar: `arch/{armv9,sparcv9,mips64,powerpc64}/vectors.rs`.
+
+/// Bildirimsel listeden tuzak vektör tablosu üretir.
+///
+/// ```ignore
+/// crate::trap_vectors! {
+///     name: "armv9_vectors",
+///     align: 2048,
+///     slot: 0x80,
+///     slots: 16,
+///     common: armv9_common,
+///     vectors: [
+///         0 ..= 0 => armv9_entry("generic_sync_handler"),
+///         1 ..= 1 => armv9_entry("generic_irq_handler"),
+///         2 ..= 15 => armv9_entry("generic_unhandled_vector"),
+///     ]
+/// }
+/// ```
+#[macro_export]
+macro_rules! trap_vectors {
+    (
+        name: $name:literal,
+        align: $align:literal,
+        slot: $slot:literal,
+        slots: $slots:literal,
+        common: $common:ident,
+        vectors: [ $( $first:literal ..= $last:literal => $entry:ident ( $handler:literal ) ),* $(,)? ]
+    ) => {
+        core::arch::global_asm!($crate::trap_vectors!(
+            @asm $name, $align, $slot, $slots, $common, [ $( $first, $last, $entry, $handler; )* ]
+        ));
+    };
+
+    // Üretilen montaj metni. Karşılaştırmalar `!=` yerine fark ile yazılır:
+    // LLVM'in SPARC ayrıştırıcısı `.if` içinde `!=`'yi yanlış değerlendirir.
+    (@asm $name:literal, $align:literal, $slot:literal, $slots:literal, $common:ident,
+        [ $( $first:literal, $last:literal, $entry:ident, $handler:literal; )* ]) => {
+        concat!(
+            ".pushsection .text.trapvec, \"ax\"\n",
+            ".balign ", stringify!($align), "\n",
+            ".global ", $name, "\n",
+            $name, ":\n",
+            ".set ", $name, "_slot, 0\n",
+            $(
+                ".if ", $name, "_slot - ", stringify!($first), "\n",
+                ".error \"", $name, ": vektör listesinde boşluk veya çakışma (", stringify!($first), ")\"\n",
+                ".endif\n",
+                ".rept ", stringify!($last), " - ", stringify!($first), " + 1\n",
+                ".org ", $name, " + ", $name, "_slot * ", stringify!($slot), "\n",
+                $entry!($name, $handler),
+                ".set ", $name, "_slot, ", $name, "_slot + 1\n",
+                ".endr\n",
+            )*
+            ".if ", $name, "_slot - ", stringify!($slots), "\n",
+            ".error \"", $name, ": vektör listesi tabloyu kapsamıyor\"\n",
+            ".endif\n",
+            ".org ", $name, " + ", stringify!($slots), " * ", stringify!($slot), "\n",
+            $common!($name),
+            ".popsection\n",
+        )
+    };
+}
@@ -0,0 +1,235 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/uaccess.rs
+// Sistem çağrılarının kullanıcı belleğine denetimli erişimi.
+//
+// Sistem çağrısı işleyicileri kullanıcı adreslerini hiçbir zaman doğrudan
+// okumaz ya da yazmaz; argümanları `UserPtr<T>` (tek değer) ve `UserSlice`
+// (adres + uzunluk) olarak alır. Bu türler yalnızca dağıtıcının tuzak
+// çerçevesinden okuduğu değerlerden (`syscall::Args`) üretilir: kurucular
+// `&Args` ister ve `Args` yalnızca `syscall` içinde kurulabilir; işleyicinin
+// elindeki bir tamsayıdan kullanıcı işaretçisi yapılamaz.
+//
+// Her kopyalama önce aralığı çalışan görevin adres alanıyla (`AddressSpace`)
+// denetler: aralık görevin tek bir `mmap` eşlemesinin içinde olmalı ve
+// eşlemenin `PROT_*` koruması erişime izin vermelidir. Görevin çekirdek
+// yığını kullanıcı belleği değildir ve kabul edilmez. NULL, taşan ve eşleme
+// dışındaki aralıklar (çekirdek görüntüsü, başka görevin belleği)
+// `BadAddress` ile reddedilir. Denetimden geçen kopya mimarinin
+// `security::copy_from_user`/`copy_to_user`'ı ile yapılır: erişim SMAP
+// (`stac`/`clac`), PAN veya SUM kısıtı yalnızca kopya süresince kaldırılarak
+// düzeltme tablolu talimatlarla yapılır; eşleme kopya sırasında kaldırılmış
+// olsa bile hata panik yerine yine `BadAddress` olur. Dağıtıcı
+// `BadAddress`'i EFAULT olarak döndürür.
+//
+// Kopyalanabilen türler `Pod` ile işaretlenir: her bit deseni geçerli olan,
+// işaretçi içermeyen düz veriler (tamsayılar ve dizileri). Yapı alanlarına
+// `UserPtr::field` ile bayt ofsetinden erişilir.
+//
+// Kabuk komutu: yok; procfs: yok (sayaç: `uaccess.faults`)
+
+use core::marker::PhantomData;
+use core::mem::{size_of, MaybeUninit};
+use crate::arch::security;
+use crate::platformgeneric::KernelError;
+use crate::sched::{self, TaskId};
+use crate::syscall::Args;
+use crate::trap::Access;
+
+crate::kernel_counter!(FAULTS, "uaccess.faults");
+
+// -----------------------------------------------------------------------------
+// KOPYALANABİLEN TÜRLER
+// -----------------------------------------------------------------------------
+
+/// Kullanıcı belleğiyle bayt bayt kopyalanabilen düz veri.
+///
+/// # Safety
+/// Her bit deseni türün geçerli bir değeri olmalı; tür işaretçi, referans
+/// veya dolgu baytı içermemelidir.
+pub unsafe trait Pod: Copy {}
+
+macro_rules! impl_pod {
+    ($($t:ty),*) => { $(unsafe impl Pod for $t {})* };
+}
+
+impl_pod!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+
+// -----------------------------------------------------------------------------
+// ADRES ALANI
+// -----------------------------------------------------------------------------
+
+/// Bir görevin kullanıcı koduna açık bellek bölgeleri.
+#[derive(Clone, Copy)]
+pub struct AddressSpace {
+    task: TaskId,
+}
+
+impl AddressSpace {
+    /// Çalışan görevin adres alanı.
+    ///
+    /// # Hatalar
+    /// * `BadAddress`: Çağıran bir görev değil (açılış akışı, boşta döngü).
+    pub fn current() -> Result<Self, KernelError> {
+        sched::current().map(Self::of).ok_or(KernelError::BadAddress)
+    }
+
+    pub fn of(task: TaskId) -> Self {
+        AddressSpace { task }
+    }
+
+    /// `[addr, addr + len)` aralığı görevin tek bir kullanıcı eşlemesinin
+    /// içinde ve `access` erişimine açık mı? Boş aralık her zaman geçerlidir.
+    ///
+    /// # Hatalar
+    /// * `BadAddress`: Aralık NULL, taşıyor ya da görevin eşlemelerinde değil.
+    pub fn check(&self, addr: usize, len: usize, access: Access) -> Result<(), KernelError> {
+        if len == 0 {
+            return Ok(());
+        }
+        addr.checked_add(len).ok_or(KernelError::BadAddress)?;
+        if addr == 0 || access == Access::Execute {
+            return Err(KernelError::BadAddress);
+        }
+        if crate::mmap::range_allows(self.task, addr, len, access) {
+            Ok(())
+        } else {
+            Err(KernelError::BadAddress)
+        }
+    }
+}
+
+/// Denetimden geçen aralıktan kopyalar; kopya hatasını `BadAddress`'e çevirir.
+fn copy_in(addr: usize, buf: &mut [u8]) -> Result<(), KernelError> {
+    AddressSpace::current()?.check(addr, buf.len(), Access::Read)?;
+    security::copy_from_user(buf, addr).map_err(|_| {
+        FAULTS.inc();
+        KernelError::BadAddress
+    })
+}
+
+fn copy_out(addr: usize, buf: &[u8]) -> Result<(), KernelError> {
+    AddressSpace::current()?.check(addr, buf.len(), Access::Write)?;
+    security::copy_to_user(addr, buf).map_err(|_| {
+        FAULTS.inc();
+        KernelError::BadAddress
+    })
+}
+
+// -----------------------------------------------------------------------------
+// KULLANICI İŞARETÇİLERİ
+// -----------------------------------------------------------------------------
+
+/// Kullanıcı belleğindeki bir `T` değerinin adresi.
+pub struct UserPtr<T: Pod> {
+    addr: usize,
+    _type: PhantomData<fn() -> T>,
+}
+
+impl<T: Pod> Clone for UserPtr<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Pod> Copy for UserPtr<T> {}
+
+impl<T: Pod> UserPtr<T> {
+    /// Tuzak çerçevesinin `index`. argümanından.
+    pub fn from_trap(args: &Args, index: usize) -> Self {
+        Self::new(args.raw(index))
+    }
+
+    fn new(addr: usize) -> Self {
+        UserPtr { addr, _type: PhantomData }
+    }
+
+    pub fn addr(&self) -> usize {
+        self.addr
+    }
+
+    /// İsteğe bağlı argümanlar (örn. `clock_getres`'in `ts`'i) için.
+    pub fn is_null(&self) -> bool {
+        self.addr == 0
+    }
+
+    /// `offset` baytındaki `U` alanı; sınır denetimi erişimde yapılır.
+    pub fn field<U: Pod>(&self, offset: usize) -> UserPtr<U> {
+        // Taşma denetimde reddedilen bir adrese dönüşür
+        UserPtr::new(self.addr.checked_add(offset).unwrap_or(usize::MAX))
+    }
+
+    /// Değeri kullanıcı belleğinden okur.
+    ///
+    /// # Hatalar
+    /// * `BadAddress`: Aralık okunabilir bir bölgede değil ya da okunamadı.
+    pub fn read(&self) -> Result<T, KernelError> {
+        let mut value = MaybeUninit::<T>::uninit();
+        // SAFETY: Tampon `T`'nin kendi deposudur; `Pod` her bit desenini kabul eder.
+        let bytes = unsafe { core::slice::from_raw_parts_mut(value.as_mut_ptr() as *mut u8, size_of::<T>()) };
+        copy_in(self.addr, bytes)?;
+        // SAFETY: Bütün baytlar yazıldı.
+        Ok(unsafe { value.assume_init() })
+    }
+
+    /// Değeri kullanıcı belleğine yazar.
+    ///
+    /// # Hatalar
+    /// * `BadAddress`: Aralık yazılabilir bir bölgede değil ya da yazılamadı;
+    ///   hata yazma sırasındaysa önceki baytlar yazılmış olabilir.
+    pub fn write(&self, value: &T) -> Result<(), KernelError> {
+        // SAFETY: `Pod` türlerinde dolgu baytı yoktur; tüm baytlar ilklenmiştir.
+        let bytes = unsafe { core::slice::from_raw_parts(value as *const T as *const u8, size_of::<T>()) };
+        copy_out(self.addr, bytes)
+    }
+}
+
+/// Kullanıcı belleğinde `len` baytlık tampon.
+#[derive(Clone, Copy)]
+pub struct UserSlice {
+    addr: usize,
+    len: usize,
+}
+
+impl UserSlice {
+    /// Tuzak çerçevesinin `addr`. ve `len`. argümanlarından.
+    pub fn from_trap(args: &Args, addr: usize, len: usize) -> Self {
+        UserSlice { addr: args.raw(addr), len: args.raw(len) }
+    }
+
+    pub fn addr(&self) -> usize {
+        self.addr
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Tamponun başından `buf`'a en fazla `buf.len()` bayt okur; okunan bayt
+    /// sayısını döndürür.
+    ///
+    /// # Hatalar
+    /// * `BadAddress`: Okunacak aralık okunabilir bir bölgede değil ya da okunamadı.
+    pub fn read(&self, buf: &mut [u8]) -> Result<usize, KernelError> {
+        let n = self.len.min(buf.len());
+        copy_in(self.addr, &mut buf[..n])?;
+        Ok(n)
+    }
+
+    /// `buf`'tan tamponun başına en fazla `len()` bayt yazar; yazılan bayt
+    /// sayısını döndürür.
+    ///
+    /// # Hatalar
+    /// * `BadAddress`: Yazılacak aralık yazılabilir bir bölgede değil ya da yazılamadı.
+    pub fn write(&self, buf: &[u8]) -> Result<usize, KernelError> {
+        let n = self.len.min(buf.len());
+        copy_out(self.addr, &buf[..n])?;
+        Ok(n)
+    }
+}
@@ -0,0 +1,274 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/uart.rs
+// UART sürücüleri için ortak kesme güdümlü gönderim (TX) kuyruğu ve akış denetimi.
+//
+// Kesme kipinde konsola yazılan baytlar önce bir halka arabelleğe alınır;
+// donanım FIFO'su, TX kesmesi geldiğinde (16550: THR boş, PL011: FIFO eşik
+// seviyesinin altına indi) arabellekten yeniden doldurulur. Böylece büyük log
+// patlamaları kesmeler kapalıyken meşgul beklemede işlemciyi tutmaz.
+//
+// Su seviyeleri (watermark): kuyruk `HIGH_WATERMARK`'a ulaşırsa yazan görev
+// kuyruğu `LOW_WATERMARK`'a inene kadar eşzamanlı (yoklamalı) boşaltır. Kayıp
+// yerine geri basınç (backpressure) tercih edilir.
+//
+// Üretici ve tüketici tarafları ayrı deneme kilitleriyle (try-lock) korunur;
+// kilidi tutan bağlam aynı işlemcide kesildiyse yeni yazma beklemez, doğrudan
+// yoklamalı yazılır (iç içe bağlamların çıktısı araya girebilir ama kilitlenme olmaz).
+// Panik ve GDB yolu `write_polled` ile önce kuyruğu boşaltır, sonra yoklamalı yazar.
+
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering};
+use crate::platformgeneric::KernelError;
+
+// -----------------------------------------------------------------------------
+// SABİTLER VE TİPLER
+// -----------------------------------------------------------------------------
+
+/// Gönderim kuyruğu boyutu (2'nin kuvveti olmalı).
+pub const TX_QUEUE_SIZE: usize = 4096;
+
+/// Bu seviyede yazan görev kuyruğu eşzamanlı boşaltmaya başlar.
+pub const HIGH_WATERMARK: usize = TX_QUEUE_SIZE * 3 / 4;
+
+/// Eşzamanlı boşaltma bu seviyede durur.
+pub const LOW_WATERMARK: usize = TX_QUEUE_SIZE / 4;
+
+const _: () = assert!(TX_QUEUE_SIZE.is_power_of_two());
+
+/// Donanım akış denetimi.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlowControl {
+    /// Akış denetimi yok.
+    None,
+    /// RTS/CTS donanım el sıkışması.
+    RtsCts,
+}
+
+/// Sürücünün gönderim yolunun donanım işlemleri.
+pub trait UartTx {
+    /// Şu an beklemeden FIFO'ya yazılabilecek bayt sayısı. Akış denetimi
+    /// etkinse ve karşı taraf CTS'yi düşürdüyse 0 döner.
+    fn tx_room() -> usize;
+
+    /// Veri yazmacına bir bayt yazar (yer olduğu varsayılır).
+    fn put(byte: u8);
+
+    /// TX kesmesini açar veya kapatır.
+    fn set_tx_interrupt(enable: bool);
+}
+
+// -----------------------------------------------------------------------------
+// GÖNDERİM KUYRUĞU
+// -----------------------------------------------------------------------------
+
+/// Tek üretici / tek tüketici halka arabellek. Üreticiler ve tüketiciler
+/// kendi aralarında deneme kilitleriyle sıralanır.
+pub struct TxQueue {
+    buf: UnsafeCell<[u8; TX_QUEUE_SIZE]>,
+    /// Tüketicinin okuyacağı sonraki konum (sarmalanmaz, maske ile indekslenir).
+    head: AtomicUsize,
+    /// Üreticinin yazacağı sonraki konum.
+    tail: AtomicUsize,
+    producer: AtomicBool,
+    consumer: AtomicBool,
+    irq_mode: AtomicBool,
+    flow: AtomicU8,
+}
+
+// `buf`'ın her bölgesine aynı anda yalnızca bir taraf erişir (head/tail sözleşmesi).
+unsafe impl Sync for TxQueue {}
+
+impl TxQueue {
+    pub const fn new() -> Self {
+        TxQueue {
+            buf: UnsafeCell::new([0; TX_QUEUE_SIZE]),
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+            producer: AtomicBool::new(false),
+            consumer: AtomicBool::new(false),
+            irq_mode: AtomicBool::new(false),
+            flow: AtomicU8::new(FlowControl::None as u8),
+        }
+    }
+
+    /// Kuyruktaki bayt sayısı.
+    pub fn len(&self) -> usize {
+        self.tail.load(Ordering::Acquire).wrapping_sub(self.head.load(Ordering::Acquire))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Kesme kipini açar veya kapatır. Kapatmadan önce kuyruk `flush` ile boşaltılmalıdır.
+    pub fn set_irq_mode(&self, enable: bool, flow: FlowControl) {
+        self.flow.store(flow as u8, Ordering::Relaxed);
+        self.irq_mode.store(enable, Ordering::Release);
+    }
+
+    pub fn irq_mode(&self) -> bool {
+        self.irq_mode.load(Ordering::Acquire)
+    }
+
+    pub fn flow_control(&self) -> FlowControl {
+        if self.flow.load(Ordering::Relaxed) == FlowControl::RtsCts as u8 {
+            FlowControl::RtsCts
+        } else {
+            FlowControl::None
+        }
+    }
+
+    /// Kuyruğa bir bayt ekler.
+    ///
+    /// # Dönüş Değeri
+    /// Ekledikten sonraki kuyruk uzunluğu.
+    ///
+    /// # Hatalar
+    /// * `ResourceBusy`: Başka bir üretici (muhtemelen kesilen bağlam) kuyruğa yazıyor.
+    /// * `OutOfMemoryStatic`: Kuyruk dolu.
+    pub fn push(&self, byte: u8) -> Result<usize, KernelError> {
+        if self.producer.swap(true, Ordering::Acquire) {
+            return Err(KernelError::ResourceBusy);
+        }
+        let tail = self.tail.load(Ordering::Relaxed);
+        let len = tail.wrapping_sub(self.head.load(Ordering::Acquire));
+        let result = if len == TX_QUEUE_SIZE {
+            Err(KernelError::OutOfMemoryStatic)
+        } else {
+            // SAFETY: `tail` konumu tüketiciye henüz yayımlanmadı.
+            unsafe { (*self.buf.get())[tail % TX_QUEUE_SIZE] = byte };
+            self.tail.store(tail.wrapping_add(1), Ordering::Release);
+            Ok(len + 1)
+        };
+        self.producer.store(false, Ordering::Release);
+        result
+    }
+
+    /// Tüketici kilidi tutulurken sıradaki baytı okur ve kuyruktan çıkarır.
+    fn pop(&self) -> Option<u8> {
+        let head = self.head.load(Ordering::Relaxed);
+        if head == self.tail.load(Ordering::Acquire) {
+            return None;
+        }
+        // SAFETY: `head` konumu üretici tarafından yayımlandı ve henüz geri verilmedi.
+        let byte = unsafe { (*self.buf.get())[head % TX_QUEUE_SIZE] };
+        self.head.store(head.wrapping_add(1), Ordering::Release);
+        Some(byte)
+    }
+}
+
+impl Default for TxQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+crate::kernel_counter!(TX_IRQ_REFILLS, "uart.tx_irq_refills");
+crate::kernel_counter!(TX_THROTTLED, "uart.tx_throttled");
+crate::kernel_counter!(TX_POLLED_FALLBACK, "uart.tx_polled_fallback");
+
+// -----------------------------------------------------------------------------
+// SÜRÜCÜ YARDIMCILARI
+// -----------------------------------------------------------------------------
+
+/// FIFO'yu kuyruktan donanımın kabul ettiği kadar doldurur ve TX kesmesini
+/// kuyrukta bayt kalıp kalmamasına göre ayarlar. Başka bir bağlam zaten
+/// dolduruyorsa hemen döner.
+pub fn refill<U: UartTx>(queue: &TxQueue) {
+    loop {
+        if queue.consumer.swap(true, Ordering::Acquire) {
+            return;
+        }
+
+        let mut room = U::tx_room();
+        while room > 0 {
+            let Some(byte) = queue.pop() else { break };
+            U::put(byte);
+            room -= 1;
+            if room == 0 {
+                room = U::tx_room();
+            }
+        }
+
+        let pending = !queue.is_empty();
+        U::set_tx_interrupt(pending);
+        queue.consumer.store(false, Ordering::Release);
+
+        // Kilidi bırakmadan hemen önce eklenen bayt, üreticinin `refill`
+        // denemesi başarısız olduğu için kesme kapalıyken kuyrukta kalmasın
+        if pending || queue.is_empty() {
+            return;
+        }
+    }
+}
+
+/// Kuyruk `target` uzunluğuna inene kadar yoklamalı olarak boşaltır.
+fn drain_to<U: UartTx>(queue: &TxQueue, target: usize) {
+    while queue.len() > target {
+        if queue.consumer.swap(true, Ordering::Acquire) {
+            // Tüketici bu işlemcide kesilmiş olabilir; sonsuz beklemeyelim
+            return;
+        }
+        while queue.len() > target && U::tx_room() > 0 {
+            if let Some(byte) = queue.pop() {
+                U::put(byte);
+            }
+        }
+        queue.consumer.store(false, Ordering::Release);
+    }
+}
+
+/// Tek bir baytı sürücünün kipine göre gönderir: kesme kipinde kuyruğa ekler,
+/// aksi halde FIFO'da yer açılmasını bekleyip doğrudan yazar.
+pub fn write<U: UartTx>(queue: &TxQueue, byte: u8) {
+    if !queue.irq_mode() {
+        put_polled::<U>(byte);
+        return;
+    }
+
+    let mut result = queue.push(byte);
+    if result == Err(KernelError::OutOfMemoryStatic) {
+        drain_to::<U>(queue, LOW_WATERMARK);
+        result = queue.push(byte);
+    }
+
+    match result {
+        Ok(len) => {
+            if len >= HIGH_WATERMARK {
+                TX_THROTTLED.inc();
+                drain_to::<U>(queue, LOW_WATERMARK);
+            }
+            refill::<U>(queue);
+        }
+        Err(_) => {
+            TX_POLLED_FALLBACK.inc();
+            put_polled::<U>(byte);
+        }
+    }
+}
+
+/// Sürücünün TX kesmesi işleyicisinden çağrılır.
+pub fn handle_tx_interrupt<U: UartTx>(queue: &TxQueue) {
+    TX_IRQ_REFILLS.inc();
+    refill::<U>(queue);
+}
+
+/// Kuyruğu tamamen boşaltır (kesme kipini kapatmadan veya panikten önce).
+pub fn flush<U: UartTx>(queue: &TxQueue) {
+    drain_to::<U>(queue, 0);
+}
+
+/// Bekleyen kuyruğu boşaltıp baytı yoklamalı yazar. Panik işleyicisi ve GDB
+/// stub'ı gibi kesmelere güvenemeyen yollar içindir; sıralama korunur.
+pub fn write_polled<U: UartTx>(queue: &TxQueue, byte: u8) {
+    flush::<U>(queue);
+    put_polled::<U>(byte);
+}
+
+fn put_polled<U: UartTx>(byte: u8) {
+    while U::tx_room() == 0 {
+        core::hint::spin_loop();
+    }
+    U::put(byte);
+}
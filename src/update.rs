@@ -0,0 +1,347 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/update.rs
+// Ürün yazılımı güncelleme çekirdeği: hazırlık (staging) alanları, görüntü
+// doğrulama ve güncelleme rutinine devir.
+//
+// Yeni görüntü önce bir hazırlık alanına yazılır (RAM veya bir blok
+// aygıtındaki bölge), ardından alandan geri okunarak CRC-32 ve imza
+// kuyruğu (`crypto::signature`) ile doğrulanır ve kayıtlı güncelleme
+// işleyicisine verilir. İşleyici platforma özgüdür
+// (görüntüyü etkin bölüme kopyalamak, önyükleyiciye işaret bırakmak vb.);
+// kayıtlı işleyici yoksa güncelleme reddedilir. Görüntüler ağdan
+// `net::tftp` ile indirilir.
+
+use core::cell::UnsafeCell;
+use crate::crypto::signature::{self, ImageKind};
+use crate::drivers::block::block::{self, BlockDevice};
+use crate::memory::frame;
+use crate::platformgeneric::spinlock::Spinlock;
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// HAZIRLIK ALANLARI
+// -----------------------------------------------------------------------------
+
+/// Görüntünün doğrulanmadan önce yazıldığı alan. Yazmalar baştan sona
+/// sıralı yapılır.
+pub trait Staging {
+    /// Alanın adı (örn. "ram", "mmc0").
+    fn name(&self) -> &'static str;
+
+    /// Alabileceği en büyük görüntü (bayt).
+    fn capacity(&self) -> usize;
+
+    /// `offset`'e `data`'yı yazar.
+    fn write(&mut self, offset: usize, data: &[u8]) -> Result<(), KernelError>;
+
+    /// `offset`'ten `buf`'a okur; okunan bayt sayısını döndürür.
+    fn read(&self, offset: usize, buf: &mut [u8]) -> Result<usize, KernelError>;
+
+    /// Bekleyen yazmaları kalıcı hale getirir (son yazmadan sonra çağrılır).
+    fn finish(&mut self) -> Result<(), KernelError> {
+        Ok(())
+    }
+}
+
+/// Varsayılan RAM hazırlık alanı boyutu (4 MiB).
+pub const DEFAULT_RAM_STAGING: usize = 4 * 1024 * 1024;
+
+/// Ardışık fiziksel çerçevelerden oluşan RAM alanı.
+pub struct RamStaging {
+    base: usize,
+    frames: usize,
+    len: usize,
+}
+
+impl RamStaging {
+    /// `capacity` baytlık alan ayırır.
+    pub fn new(capacity: usize) -> Result<RamStaging, KernelError> {
+        let frames = capacity.div_ceil(frame::FRAME_SIZE);
+        let base = frame::alloc_contiguous(frames)?;
+        Ok(RamStaging { base, frames, len: 0 })
+    }
+
+    /// Yazılmış bölümün görünümü.
+    pub fn as_slice(&self) -> &[u8] {
+        // SAFETY: Çerçeveler bu nesneye aittir ve `len` kapasiteyi aşmaz.
+        unsafe { core::slice::from_raw_parts(self.base as *const u8, self.len) }
+    }
+}
+
+impl Staging for RamStaging {
+    fn name(&self) -> &'static str {
+        "ram"
+    }
+
+    fn capacity(&self) -> usize {
+        self.frames * frame::FRAME_SIZE
+    }
+
+    fn write(&mut self, offset: usize, data: &[u8]) -> Result<(), KernelError> {
+        if offset + data.len() > self.capacity() {
+            return Err(KernelError::OutOfMemoryStatic);
+        }
+        // SAFETY: Aralık yukarıda kapasiteyle sınırlandı.
+        unsafe { core::ptr::copy_nonoverlapping(data.as_ptr(), (self.base + offset) as *mut u8, data.len()) };
+        self.len = self.len.max(offset + data.len());
+        Ok(())
+    }
+
+    fn read(&self, offset: usize, buf: &mut [u8]) -> Result<usize, KernelError> {
+        let n = buf.len().min(self.len.saturating_sub(offset));
+        buf[..n].copy_from_slice(&self.as_slice()[offset..offset + n]);
+        Ok(n)
+    }
+}
+
+impl Drop for RamStaging {
+    fn drop(&mut self) {
+        let _ = frame::free(self.base, self.frames);
+    }
+}
+
+/// En büyük desteklenen blok boyutu.
+const MAX_BLOCK_SIZE: usize = 4096;
+
+/// Blok aygıtında `lba`'dan başlayan bölge. Veriler blok boyutunda
+/// biriktirilip yazılır; son kısmi blok `finish` ile sıfırla doldurulur.
+pub struct BlockStaging {
+    dev: &'static dyn BlockDevice,
+    lba: u64,
+    blocks: u64,
+    pending: [u8; MAX_BLOCK_SIZE],
+    /// `pending`'in başlangıç konumu ve doluluğu.
+    pending_offset: usize,
+    pending_len: usize,
+    len: usize,
+}
+
+impl BlockStaging {
+    /// `dev` üzerinde `lba`'dan başlayan `blocks` bloğu kullanır (0: aygıtın sonuna kadar).
+    pub fn new(dev: &'static dyn BlockDevice, lba: u64, blocks: u64) -> Result<BlockStaging, KernelError> {
+        let bs = dev.block_size();
+        if bs == 0 || bs > MAX_BLOCK_SIZE || lba >= dev.block_count() {
+            return Err(KernelError::InvalidArgument);
+        }
+        let available = dev.block_count() - lba;
+        let blocks = if blocks == 0 { available } else { blocks.min(available) };
+        Ok(BlockStaging { dev, lba, blocks, pending: [0; MAX_BLOCK_SIZE], pending_offset: 0, pending_len: 0, len: 0 })
+    }
+
+    fn flush_pending(&mut self) -> Result<(), KernelError> {
+        let bs = self.dev.block_size();
+        if self.pending_len == 0 {
+            return Ok(());
+        }
+        self.pending[self.pending_len..bs].fill(0);
+        let lba = self.lba + (self.pending_offset / bs) as u64;
+        self.dev.write_blocks(lba, &self.pending[..bs])?;
+        self.pending_offset += bs;
+        self.pending_len = 0;
+        Ok(())
+    }
+}
+
+impl Staging for BlockStaging {
+    fn name(&self) -> &'static str {
+        self.dev.name()
+    }
+
+    fn capacity(&self) -> usize {
+        (self.blocks as usize).saturating_mul(self.dev.block_size())
+    }
+
+    fn write(&mut self, offset: usize, mut data: &[u8]) -> Result<(), KernelError> {
+        let bs = self.dev.block_size();
+        if offset != self.pending_offset + self.pending_len {
+            return Err(KernelError::InvalidArgument);
+        }
+        let end = offset + data.len();
+        if end > self.capacity() {
+            return Err(KernelError::OutOfMemoryStatic);
+        }
+        while !data.is_empty() {
+            let n = data.len().min(bs - self.pending_len);
+            self.pending[self.pending_len..self.pending_len + n].copy_from_slice(&data[..n]);
+            self.pending_len += n;
+            data = &data[n..];
+            if self.pending_len == bs {
+                self.flush_pending()?;
+            }
+        }
+        self.len = end;
+        Ok(())
+    }
+
+    fn read(&self, offset: usize, buf: &mut [u8]) -> Result<usize, KernelError> {
+        let bs = self.dev.block_size();
+        let mut block = [0u8; MAX_BLOCK_SIZE];
+        let end = self.len.min(offset + buf.len());
+        let mut pos = offset;
+        while pos < end {
+            let lba = self.lba + (pos / bs) as u64;
+            block::check_range(self.dev, lba, bs)?;
+            self.dev.read_blocks(lba, &mut block[..bs])?;
+            let start = pos % bs;
+            let n = (bs - start).min(end - pos);
+            buf[pos - offset..pos - offset + n].copy_from_slice(&block[start..start + n]);
+            pos += n;
+        }
+        Ok(end.saturating_sub(offset))
+    }
+
+    fn finish(&mut self) -> Result<(), KernelError> {
+        self.flush_pending()?;
+        self.dev.flush()
+    }
+}
+
+// -----------------------------------------------------------------------------
+// DOĞRULAMA
+// -----------------------------------------------------------------------------
+
+/// CRC-32 (IEEE 802.3, yansıtılmış 0xEDB88320) tablosu.
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// Akan veri için CRC-32 günceller. İlk çağrıda `crc` 0 verilir.
+pub fn crc32_update(crc: u32, data: &[u8]) -> u32 {
+    let mut crc = !crc;
+    for &b in data {
+        crc = CRC32_TABLE[((crc ^ u32::from(b)) & 0xFF) as usize] ^ (crc >> 8);
+    }
+    !crc
+}
+
+/// Hazırlık alanındaki ilk `len` baytın CRC-32'sini geri okuyarak hesaplar.
+pub fn staged_crc32(staging: &dyn Staging, len: usize) -> Result<u32, KernelError> {
+    let mut buf = [0u8; 512];
+    let mut crc = 0;
+    let mut offset = 0;
+    while offset < len {
+        let want = (len - offset).min(buf.len());
+        let n = staging.read(offset, &mut buf[..want])?;
+        if n == 0 {
+            return Err(KernelError::InvalidArgument);
+        }
+        crc = crc32_update(crc, &buf[..n]);
+        offset += n;
+    }
+    Ok(crc)
+}
+
+// -----------------------------------------------------------------------------
+// GÜNCELLEME İŞLEYİCİSİ
+// -----------------------------------------------------------------------------
+
+/// Doğrulanmış görüntüyü (alan, uzunluk) kuran platform rutini.
+pub type UpdateHandler = fn(&dyn Staging, usize) -> Result<(), KernelError>;
+
+struct HandlerSlot {
+    lock: Spinlock,
+    handler: UnsafeCell<Option<UpdateHandler>>,
+}
+
+// Erişim `lock` ile korunur.
+unsafe impl Sync for HandlerSlot {}
+
+static HANDLER: HandlerSlot = HandlerSlot { lock: Spinlock::new(), handler: UnsafeCell::new(None) };
+
+/// Güncelleme işleyicisini kaydeder (öncekinin yerine geçer).
+pub fn set_handler(handler: UpdateHandler) {
+    HANDLER.lock.lock();
+    unsafe { *HANDLER.handler.get() = Some(handler) };
+    HANDLER.lock.unlock();
+}
+
+fn handler() -> Option<UpdateHandler> {
+    HANDLER.lock.lock();
+    let handler = unsafe { *HANDLER.handler.get() };
+    HANDLER.lock.unlock();
+    handler
+}
+
+crate::kernel_counter!(UPDATES_APPLIED, "update.applied");
+crate::kernel_counter!(UPDATES_REJECTED, "update.rejected");
+
+/// Hazırlık alanındaki görüntüyü doğrular ve işleyiciye devreder.
+///
+/// # Hatalar
+/// * `InvalidArgument`: Görüntü boş, CRC-32 tutmuyor veya imza geçersiz.
+/// * `NotFound`: Kayıtlı güncelleme işleyicisi yok.
+pub fn apply(staging: &dyn Staging, len: usize, expected_crc: u32) -> Result<(), KernelError> {
+    let result = verify_and_apply(staging, len, expected_crc);
+    match result {
+        Ok(()) => UPDATES_APPLIED.inc(),
+        Err(_) => UPDATES_REJECTED.inc(),
+    }
+    result
+}
+
+fn verify_and_apply(staging: &dyn Staging, len: usize, expected_crc: u32) -> Result<(), KernelError> {
+    if len == 0 {
+        return Err(KernelError::InvalidArgument);
+    }
+    let crc = staged_crc32(staging, len)?;
+    if crc != expected_crc {
+        serial_println!("[UPDATE] CRC-32 uyuşmazlığı: {:08x} (beklenen {:08x})", crc, expected_crc);
+        return Err(KernelError::InvalidArgument);
+    }
+    // İşleyici imza kuyruğu olmadan yalnızca yükü görür
+    let len = signature::check_staged(ImageKind::Firmware, staging, len)?;
+    let handler = handler().ok_or(KernelError::NotFound)?;
+    serial_println!("[UPDATE] {} bayt görüntü ({}) doğrulandı, kuruluyor", len, staging.name());
+    handler(staging, len)
+}
+
+// -----------------------------------------------------------------------------
+// KABUK
+// -----------------------------------------------------------------------------
+
+/// `fwupdate <sunucu> <dosya> <crc32> [<blok aygıtı> [lba]]`
+fn fwupdate_command(args: &[&str]) -> Result<(), KernelError> {
+    if args.len() < 4 {
+        return Err(KernelError::InvalidArgument);
+    }
+    let server = crate::net::net::lookup(args[1])?;
+    let expected = u32::from_str_radix(args[3].trim_start_matches("0x"), 16).map_err(|_| KernelError::InvalidArgument)?;
+
+    match args.get(4) {
+        Some(name) => {
+            let dev = block::find(name).ok_or(KernelError::NotFound)?;
+            let lba = match args.get(5) {
+                Some(lba) => lba.parse().map_err(|_| KernelError::InvalidArgument)?,
+                None => 0,
+            };
+            let mut staging = BlockStaging::new(dev, lba, 0)?;
+            let transfer = crate::net::tftp::download(server, args[2], &mut staging)?;
+            apply(&staging, transfer.len, expected)
+        }
+        None => {
+            let mut staging = RamStaging::new(DEFAULT_RAM_STAGING)?;
+            let transfer = crate::net::tftp::download(server, args[2], &mut staging)?;
+            apply(&staging, transfer.len, expected)
+        }
+    }
+}
+
+crate::shell_command!(
+    "fwupdate",
+    "Ağdan ürün yazılımı günceller (fwupdate <sunucu> <dosya> <crc32> [<aygıt> [lba]])",
+    fwupdate_command
+);
@@ -0,0 +1,217 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/version.rs
+// Derleme kimliği: sürüm, git özeti, derleme zamanı, özellikler ve mimari.
+//
+// Sahadan gelen raporların hangi görüntüye ait olduğu bu bilgilerle
+// belirlenir. Değerler derleme sırasında gömülür; `cargo xtask build` şu
+// ortam değişkenlerini ayarlar (doğrudan `cargo build` ile derlenirse
+// "unknown"/0 kalır):
+// * `SAHNE_GIT_HASH`: kısa commit özeti; çalışma ağacı temiz değilse `-dirty` eki.
+// * `SAHNE_BUILD_TIME`: Unix zamanı (s). Yeniden üretilebilir derlemeler için
+//   `SOURCE_DATE_EPOCH` verilmişse o kullanılır.
+// Etkin Cargo özellikleri `cfg!` ile doğrudan derleyiciden okunur.
+//
+// Açılışta tek satırlık kimlik yazdırılır. Erişim: `sys_uname` (Linux
+// `struct utsname`), `/proc/version` ve kabukta `version`.
+
+use core::fmt::{self, Write};
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+use crate::uaccess::UserPtr;
+
+// -----------------------------------------------------------------------------
+// DERLEME BİLGİLERİ
+// -----------------------------------------------------------------------------
+
+pub const NAME: &str = "Sahne Karnal";
+
+/// Sistem adı (`uname -s`).
+pub const SYSNAME: &str = "Sahne";
+
+/// Düğüm adı (`uname -n`, syslog HOSTNAME).
+pub const NODENAME: &str = "sahne";
+
+/// Paket sürümü.
+pub const VERSION: &str = match option_env!("CARGO_PKG_VERSION") {
+    Some(version) => version,
+    None => "0.0.0",
+};
+
+/// Kısa git özeti.
+pub const GIT_HASH: &str = match option_env!("SAHNE_GIT_HASH") {
+    Some(hash) => hash,
+    None => "unknown",
+};
+
+/// Derleme zamanı (Unix zamanı, s); bilinmiyorsa 0.
+pub const BUILD_TIME: u64 = match option_env!("SAHNE_BUILD_TIME") {
+    Some(time) => parse_u64(time),
+    None => 0,
+};
+
+/// Derleme profili.
+pub const PROFILE: &str = if cfg!(debug_assertions) { "debug" } else { "release" };
+
+#[cfg(target_arch = "x86_64")]
+pub const ARCH: &str = "amd64";
+#[cfg(target_arch = "aarch64")]
+pub const ARCH: &str = "armv9";
+#[cfg(target_arch = "riscv64")]
+pub const ARCH: &str = "rv64i";
+#[cfg(target_arch = "powerpc64")]
+pub const ARCH: &str = "powerpc64";
+#[cfg(target_arch = "sparc64")]
+pub const ARCH: &str = "sparcv9";
+#[cfg(target_arch = "loongarch64")]
+pub const ARCH: &str = "loongarch64";
+#[cfg(target_arch = "mips64")]
+pub const ARCH: &str = "mips64";
+#[cfg(target_arch = "or1k")]
+pub const ARCH: &str = "openrisc64";
+
+/// Cargo özellikleri ve etkin olup olmadıkları.
+pub const FEATURES: [(&str, bool); 4] = [
+    ("cooperative", cfg!(feature = "cooperative")),
+    ("kasan", cfg!(feature = "kasan")),
+    ("ktest", cfg!(feature = "ktest")),
+    ("require-signatures", cfg!(feature = "require-signatures")),
+];
+
+/// Ondalık sayıyı derleme zamanında ayrıştırır; geçersiz metin 0 verir.
+const fn parse_u64(text: &str) -> u64 {
+    let bytes = text.as_bytes();
+    let mut value: u64 = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            return 0;
+        }
+        value = value * 10 + (bytes[i] - b'0') as u64;
+        i += 1;
+    }
+    value
+}
+
+/// Etkin özellikleri `+a+b` biçiminde yazar (hiçbiri yoksa `-`).
+pub fn write_features(out: &mut dyn Write) -> fmt::Result {
+    let mut any = false;
+    for (name, _) in FEATURES.iter().filter(|(_, enabled)| *enabled) {
+        write!(out, "+{}", name)?;
+        any = true;
+    }
+    if !any {
+        out.write_str("-")?;
+    }
+    Ok(())
+}
+
+/// Derleme zamanını UTC olarak yazar.
+pub fn write_build_time(out: &mut dyn Write) -> fmt::Result {
+    if BUILD_TIME == 0 {
+        return out.write_str("unknown");
+    }
+    let (year, month, day) = crate::walltime::civil_from_days(BUILD_TIME / 86_400);
+    let of_day = BUILD_TIME % 86_400;
+    write!(
+        out,
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        of_day / 3600,
+        of_day / 60 % 60,
+        of_day % 60
+    )
+}
+
+/// Tek satırlık kimlik: `Sahne Karnal 0.1.0 (git 1a2b3c4d5e6f) amd64 release +ktest, 2026-10-16 09:30:12 UTC`
+pub fn write_banner(out: &mut dyn Write) -> fmt::Result {
+    write!(out, "{} {} (git {}) {} {} ", NAME, VERSION, GIT_HASH, ARCH, PROFILE)?;
+    write_features(out)?;
+    out.write_str(", ")?;
+    write_build_time(out)
+}
+
+fn print_banner() -> Result<(), KernelError> {
+    let _ = write_banner(&mut crate::shell::Console);
+    serial_println!();
+    Ok(())
+}
+
+crate::initcall!(earlycon, "version", print_banner);
+
+// -----------------------------------------------------------------------------
+// UNAME SİSTEM ÇAĞRISI
+// -----------------------------------------------------------------------------
+
+/// `struct utsname` alan uzunluğu (Linux `__NEW_UTS_LEN + 1`).
+const UTS_FIELD: usize = 65;
+/// Alanlar: sysname, nodename, release, version, machine, domainname.
+const UTS_FIELDS: usize = 6;
+
+/// Sabit uzunluklu, NUL ile biten alana yazan biçimlendirici; taşan kısım kesilir.
+struct Field<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl Write for Field<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        // Son bayt NUL için ayrılır
+        let n = s.len().min(self.buf.len().saturating_sub(self.len + 1));
+        self.buf[self.len..self.len + n].copy_from_slice(&s.as_bytes()[..n]);
+        self.len += n;
+        Ok(())
+    }
+}
+
+/// `uname(*buf)`: Linux `struct utsname` (6 × 65 bayt) doldurur.
+/// `version` alanı git özeti, özellikler ve derleme zamanını içerir.
+pub fn sys_uname(buf: UserPtr<[u8; UTS_FIELD * UTS_FIELDS]>) -> Result<usize, KernelError> {
+    let mut raw = [0u8; UTS_FIELD * UTS_FIELDS];
+    let mut fields = raw.chunks_exact_mut(UTS_FIELD);
+    let mut field = || Field { buf: fields.next().unwrap_or_default(), len: 0 };
+
+    let _ = field().write_str(SYSNAME);
+    let _ = field().write_str(NODENAME);
+    let _ = field().write_str(VERSION);
+    let mut version = field();
+    let _ = write!(version, "#{} ", GIT_HASH);
+    let _ = write_features(&mut version);
+    let _ = version.write_str(" ");
+    let _ = write_build_time(&mut version);
+    let _ = field().write_str(ARCH);
+    let _ = field().write_str("(none)");
+
+    buf.write(&raw)?;
+    Ok(0)
+}
+
+// -----------------------------------------------------------------------------
+// PROCFS VE KABUK
+// -----------------------------------------------------------------------------
+
+fn show_version(out: &mut dyn Write) -> fmt::Result {
+    write_banner(out)?;
+    out.write_str("\n")
+}
+
+crate::proc_entry!("version", show_version);
+
+fn version_command(_args: &[&str]) -> Result<(), KernelError> {
+    serial_println!("  {} {}", NAME, VERSION);
+    serial_println!("  git       {}", GIT_HASH);
+    serial_println!("  mimari    {}", ARCH);
+    serial_println!("  profil    {}", PROFILE);
+    let mut console = crate::shell::Console;
+    let _ = console.write_str("  derleme   ");
+    let _ = write_build_time(&mut console);
+    serial_println!();
+    for (name, enabled) in FEATURES {
+        serial_println!("  özellik   {:<20} {}", name, if enabled { "açık" } else { "kapalı" });
+    }
+    Ok(())
+}
+
+crate::shell_command!("version", "Derleme kimliğini gösterir", version_command);
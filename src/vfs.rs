@@ -0,0 +1,238 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/vfs.rs
+// Sanal dosya sistemi (VFS) katmanı: bağlama (mount) tablosu ve yol çözümleme.
+//
+// Her dosya sistemi `FileSystem` trait'ini uygular ve bir bağlama noktasına
+// (`/proc`, `/` vb.) bağlanır. Yol, en uzun eşleşen bağlama noktasına göre
+// çözülür ve dosya sistemine bağlama noktasına göreli olarak iletilir
+// (örn. `/proc/tasks` -> procfs içinde `tasks`, kök dizin için boş dize).
+//
+// Dinamik bellek kullanılmaz: okumalar çağıranın arabelleğine yapılır, dizin
+// içerikleri indeks ile tek tek sorgulanır.
+
+use core::cell::UnsafeCell;
+use crate::platformgeneric::spinlock::Spinlock;
+use crate::platformgeneric::KernelError;
+use crate::serial_println;
+
+// -----------------------------------------------------------------------------
+// DOSYA SİSTEMİ ARAYÜZÜ
+// -----------------------------------------------------------------------------
+
+/// Dosya türü.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileType {
+    File,
+    Directory,
+}
+
+/// Dosya meta verisi.
+#[derive(Debug, Clone, Copy)]
+pub struct Stat {
+    pub kind: FileType,
+    /// Bayt cinsinden boyut (dizinler için 0).
+    pub size: usize,
+}
+
+/// Dizin girdisi.
+#[derive(Debug, Clone, Copy)]
+pub struct DirEntry {
+    pub name: &'static str,
+    pub kind: FileType,
+}
+
+/// Tüm dosya sistemlerinin uyguladığı arayüz. Yollar bağlama noktasına
+/// görelidir ve baştaki `/` içermez.
+pub trait FileSystem: Sync {
+    /// Dosya sisteminin türü (örn. "procfs").
+    fn name(&self) -> &'static str;
+
+    /// Yolun meta verisini döndürür; yoksa `NotFound`.
+    fn stat(&self, path: &str) -> Result<Stat, KernelError>;
+
+    /// `offset` konumundan `buf`'a okur ve okunan bayt sayısını döndürür (dosya sonunda 0).
+    fn read(&self, path: &str, offset: usize, buf: &mut [u8]) -> Result<usize, KernelError>;
+
+    /// Dizinin `index`. girdisini döndürür; girdiler bittiğinde `None`.
+    fn readdir(&self, path: &str, index: usize) -> Result<Option<DirEntry>, KernelError>;
+
+    /// `offset` konumuna yazar. Salt okunur dosya sistemleri için varsayılan olarak reddedilir.
+    fn write(&self, _path: &str, _offset: usize, _data: &[u8]) -> Result<usize, KernelError> {
+        Err(KernelError::InvalidArgument)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// BAĞLAMA TABLOSU
+// -----------------------------------------------------------------------------
+
+/// En fazla bağlama noktası sayısı.
+pub const MAX_MOUNTS: usize = 8;
+
+#[derive(Clone, Copy)]
+struct Mount {
+    /// Mutlak bağlama noktası (`/` veya `/proc` gibi; sonda `/` yok).
+    path: &'static str,
+    fs: &'static dyn FileSystem,
+}
+
+struct MountTable {
+    lock: Spinlock,
+    mounts: UnsafeCell<[Option<Mount>; MAX_MOUNTS]>,
+}
+
+// Tabloya erişim `lock` ile korunur.
+unsafe impl Sync for MountTable {}
+
+static MOUNTS: MountTable = MountTable {
+    lock: Spinlock::new(),
+    mounts: UnsafeCell::new([None; MAX_MOUNTS]),
+};
+
+/// Bir dosya sistemini verilen mutlak yola bağlar.
+///
+/// # Hatalar
+/// * `InvalidArgument`: Yol `/` ile başlamıyor veya `/` ile bitiyor (kök hariç).
+/// * `ResourceBusy`: Bu noktada zaten bir bağlama var.
+/// * `OutOfMemoryStatic`: Bağlama tablosu dolu.
+pub fn mount(path: &'static str, fs: &'static dyn FileSystem) -> Result<(), KernelError> {
+    if !path.starts_with('/') || (path.len() > 1 && path.ends_with('/')) {
+        return Err(KernelError::InvalidArgument);
+    }
+
+    MOUNTS.lock.lock();
+    let mounts = unsafe { &mut *MOUNTS.mounts.get() };
+
+    let result = if mounts.iter().flatten().any(|m| m.path == path) {
+        Err(KernelError::ResourceBusy)
+    } else if let Some(slot) = mounts.iter_mut().find(|m| m.is_none()) {
+        *slot = Some(Mount { path, fs });
+        Ok(())
+    } else {
+        Err(KernelError::OutOfMemoryStatic)
+    };
+
+    MOUNTS.lock.unlock();
+
+    if result.is_ok() {
+        serial_println!("[VFS] {} -> {}", fs.name(), path);
+    }
+    result
+}
+
+/// Bir bağlama noktasını kaldırır.
+pub fn umount(path: &str) -> Result<(), KernelError> {
+    MOUNTS.lock.lock();
+    let mounts = unsafe { &mut *MOUNTS.mounts.get() };
+    let result = match mounts.iter_mut().find(|m| m.is_some_and(|m| m.path == path)) {
+        Some(slot) => {
+            *slot = None;
+            Ok(())
+        }
+        None => Err(KernelError::NotFound),
+    };
+    MOUNTS.lock.unlock();
+    result
+}
+
+/// `path`'in `mount` altında olup olmadığını denetler ve göreli kısmı döndürür.
+fn relative<'a>(mount: &str, path: &'a str) -> Option<&'a str> {
+    if mount == "/" {
+        return Some(path.trim_start_matches('/'));
+    }
+    let rest = path.strip_prefix(mount)?;
+    if rest.is_empty() {
+        Some("")
+    } else {
+        rest.strip_prefix('/')
+    }
+}
+
+/// Mutlak yolu en uzun eşleşen bağlamaya göre çözer.
+fn resolve(path: &str) -> Result<(&'static dyn FileSystem, &str), KernelError> {
+    if !path.starts_with('/') {
+        return Err(KernelError::InvalidArgument);
+    }
+    let path = if path.len() > 1 { path.trim_end_matches('/') } else { path };
+
+    MOUNTS.lock.lock();
+    let mounts = unsafe { &*MOUNTS.mounts.get() };
+    let best = mounts
+        .iter()
+        .flatten()
+        .filter_map(|m| relative(m.path, path).map(|rel| (m.path.len(), m.fs, rel)))
+        .max_by_key(|(len, _, _)| *len);
+    MOUNTS.lock.unlock();
+
+    best.map(|(_, fs, rel)| (fs, rel)).ok_or(KernelError::NotFound)
+}
+
+// -----------------------------------------------------------------------------
+// DOSYA İŞLEMLERİ
+// -----------------------------------------------------------------------------
+
+/// Mutlak yolun meta verisi.
+pub fn stat(path: &str) -> Result<Stat, KernelError> {
+    let (fs, rel) = resolve(path)?;
+    fs.stat(rel)
+}
+
+/// Dosyadan okur.
+pub fn read(path: &str, offset: usize, buf: &mut [u8]) -> Result<usize, KernelError> {
+    let (fs, rel) = resolve(path)?;
+    fs.read(rel, offset, buf)
+}
+
+/// Dosyaya yazar.
+pub fn write(path: &str, offset: usize, data: &[u8]) -> Result<usize, KernelError> {
+    let (fs, rel) = resolve(path)?;
+    fs.write(rel, offset, data)
+}
+
+/// Dizinin `index`. girdisi.
+pub fn readdir(path: &str, index: usize) -> Result<Option<DirEntry>, KernelError> {
+    let (fs, rel) = resolve(path)?;
+    fs.readdir(rel, index)
+}
+
+// -----------------------------------------------------------------------------
+// KABUK KOMUTLARI
+// -----------------------------------------------------------------------------
+
+fn ls_command(args: &[&str]) -> Result<(), KernelError> {
+    let path = args.get(1).copied().unwrap_or("/");
+    let mut index = 0;
+    while let Some(entry) = readdir(path, index)? {
+        match entry.kind {
+            FileType::Directory => serial_println!("  {}/", entry.name),
+            FileType::File => serial_println!("  {}", entry.name),
+        }
+        index += 1;
+    }
+    Ok(())
+}
+
+fn cat_command(args: &[&str]) -> Result<(), KernelError> {
+    let Some(path) = args.get(1) else {
+        serial_println!("Kullanım: cat <yol>");
+        return Err(KernelError::InvalidArgument);
+    };
+
+    let mut buf = [0u8; 128];
+    let mut offset = 0;
+    loop {
+        let n = read(path, offset, &mut buf)?;
+        if n == 0 {
+            break;
+        }
+        for &byte in &buf[..n] {
+            crate::serial_print!("{}", byte as char);
+        }
+        offset += n;
+    }
+    Ok(())
+}
+
+crate::shell_command!("ls", "Dizin içeriğini listeler (ls [yol])", ls_command);
+crate::shell_command!("cat", "Dosya içeriğini yazdırır (cat <yol>)", cat_command);
@@ -0,0 +1,246 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// src/walltime.rs
+// Duvar saati (CLOCK_REALTIME) ve saat sistem çağrıları.
+//
+// Duvar saati monotonik saatin (`hrtimer::now_ns`) üzerine kurulu bir
+// dönüşümdür: bir çapa noktası (monotonik zaman, Unix zamanı) ve kalan
+// kaydırma (slew) miktarı tutulur. `settime` çapayı doğrudan taşır; `adjtime`
+// ise farkı `SLEW_PPM` hızında yavaş yavaş uygular, böylece duvar saati
+// sıçramaz ve geri gitmez. Zamanlayıcılar ve zamanlayıcı tiki yalnızca
+// monotonik saati kullandığından hiçbir ayar onları etkilemez.
+//
+// Sistem çağrıları Linux'un `clock_gettime`/`clock_settime`/`clock_getres`/
+// `clock_nanosleep` ve `clock_adjtime` (yalnızca ADJ_OFFSET_SINGLESHOT)
+// arayüzünü izler; `struct timespec` 16 bayttır (tv_sec, tv_nsec: i64).
+
+use core::cell::UnsafeCell;
+use crate::hrtimer;
+use crate::platformgeneric::spinlock::Spinlock;
+use crate::platformgeneric::KernelError;
+use crate::uaccess::UserPtr;
+
+// -----------------------------------------------------------------------------
+// SABİTLER VE TİPLER
+// -----------------------------------------------------------------------------
+
+pub const CLOCK_REALTIME: usize = 0;
+pub const CLOCK_MONOTONIC: usize = 1;
+pub const CLOCK_MONOTONIC_RAW: usize = 4;
+pub const CLOCK_BOOTTIME: usize = 7;
+
+/// `clock_nanosleep`: hedef mutlak zamandır.
+pub const TIMER_ABSTIME: usize = 1;
+
+/// `struct timex.modes`: tek seferlik kaydırma (adjtime).
+const ADJ_OFFSET_SINGLESHOT: u32 = 0x0001 | 0x8000;
+/// `struct timex.modes`: kalan kaydırmayı yalnızca oku.
+const ADJ_OFFSET_SS_READ: u32 = 0x2000 | ADJ_OFFSET_SINGLESHOT;
+/// `struct timex` içinde `offset` alanının ofseti (modes: u32 + hizalama).
+const TIMEX_OFFSET_FIELD: usize = 8;
+/// `clock_adjtime` dönüşü: saat eşzamanlı.
+const TIME_OK: usize = 0;
+
+/// Kaydırma hızı: saniyede en fazla 500 µs (adjtime ile aynı).
+pub const SLEW_PPM: u64 = 500;
+/// Kabul edilen en büyük tek seferlik kaydırma (Linux: ±0,5 s).
+pub const MAX_SLEW_NS: i64 = 500_000_000;
+
+const NS_PER_SEC: u64 = 1_000_000_000;
+
+struct WallClock {
+    lock: Spinlock,
+    /// Çapa anındaki monotonik zaman (ns).
+    anchor_mono: UnsafeCell<u64>,
+    /// Çapa anındaki Unix zamanı (ns).
+    anchor_real: UnsafeCell<u64>,
+    /// Çapa anında uygulanmayı bekleyen kaydırma (ns, işaretli).
+    slew: UnsafeCell<i64>,
+}
+
+// Tüm alanlara erişim `lock` ile korunur.
+unsafe impl Sync for WallClock {}
+
+static WALL: WallClock = WallClock {
+    lock: Spinlock::new(),
+    anchor_mono: UnsafeCell::new(0),
+    anchor_real: UnsafeCell::new(0),
+    slew: UnsafeCell::new(0),
+};
+
+crate::kernel_counter!(SETTIME, "walltime.settime");
+crate::kernel_counter!(ADJTIME, "walltime.adjtime");
+
+fn with_wall<R>(f: impl FnOnce(&mut u64, &mut u64, &mut i64) -> R) -> R {
+    WALL.lock.lock();
+    // SAFETY: Kilit tutulduğu sürece alanlara tek erişen biziz.
+    let result = unsafe { f(&mut *WALL.anchor_mono.get(), &mut *WALL.anchor_real.get(), &mut *WALL.slew.get()) };
+    WALL.lock.unlock();
+    result
+}
+
+/// Çapadan `mono`'ya kadar uygulanmış kaydırma ve kalan miktar.
+fn applied_slew(anchor_mono: u64, slew: i64, mono: u64) -> (i64, i64) {
+    let budget = (mono.saturating_sub(anchor_mono) * SLEW_PPM / 1_000_000).min(i64::MAX as u64) as i64;
+    let applied = slew.clamp(-budget, budget);
+    (applied, slew - applied)
+}
+
+fn real_at(anchor_mono: u64, anchor_real: u64, slew: i64, mono: u64) -> u64 {
+    let (applied, _) = applied_slew(anchor_mono, slew, mono);
+    (anchor_real + mono.saturating_sub(anchor_mono)).saturating_add_signed(applied)
+}
+
+// -----------------------------------------------------------------------------
+// DUVAR SAATİ
+// -----------------------------------------------------------------------------
+
+/// Unix zamanı (1970'ten bu yana ns). Hiç ayarlanmadıysa açılıştan bu yana
+/// geçen süredir.
+pub fn now_ns() -> u64 {
+    let mono = hrtimer::now_ns();
+    with_wall(|anchor_mono, anchor_real, slew| real_at(*anchor_mono, *anchor_real, *slew, mono))
+}
+
+/// Duvar saatini `real_ns`'e ayarlar; bekleyen kaydırma iptal edilir.
+pub fn settime(real_ns: u64) {
+    let mono = hrtimer::now_ns();
+    with_wall(|anchor_mono, anchor_real, slew| {
+        *anchor_mono = mono;
+        *anchor_real = real_ns;
+        *slew = 0;
+    });
+    SETTIME.inc();
+}
+
+/// Duvar saatini `delta_ns` kadar yavaşça kaydırır (`SLEW_PPM` hızında).
+/// Önceki kaydırmanın kalanı atılır ve döndürülür.
+///
+/// # Hatalar
+/// * `InvalidArgument`: |delta| `MAX_SLEW_NS`'den büyük; büyük farklar `settime` ile düzeltilmelidir.
+pub fn adjtime(delta_ns: i64) -> Result<i64, KernelError> {
+    if !(-MAX_SLEW_NS..=MAX_SLEW_NS).contains(&delta_ns) {
+        return Err(KernelError::InvalidArgument);
+    }
+    let mono = hrtimer::now_ns();
+    let remaining = with_wall(|anchor_mono, anchor_real, slew| {
+        // Yeni çapa: şimdiye kadar uygulanan kaydırma sabitlenir
+        let (_, remaining) = applied_slew(*anchor_mono, *slew, mono);
+        *anchor_real = real_at(*anchor_mono, *anchor_real, *slew, mono);
+        *anchor_mono = mono;
+        *slew = delta_ns;
+        remaining
+    });
+    ADJTIME.inc();
+    Ok(remaining)
+}
+
+/// Uygulanmayı bekleyen kaydırma (ns).
+pub fn pending_slew() -> i64 {
+    let mono = hrtimer::now_ns();
+    with_wall(|anchor_mono, _, slew| applied_slew(*anchor_mono, *slew, mono).1)
+}
+
+/// `clock` saatinin şimdiki değeri (ns).
+///
+/// # Hatalar
+/// * `InvalidArgument`: Bilinmeyen saat kimliği.
+pub fn clock_now(clock: usize) -> Result<u64, KernelError> {
+    match clock {
+        CLOCK_REALTIME => Ok(now_ns()),
+        // Askıya alma desteklenmediğinden BOOTTIME monotonik saatle aynıdır
+        CLOCK_MONOTONIC | CLOCK_MONOTONIC_RAW | CLOCK_BOOTTIME => Ok(hrtimer::now_ns()),
+        _ => Err(KernelError::InvalidArgument),
+    }
+}
+
+/// Unix gününü (1970-01-01'den beri) takvim tarihine çevirir.
+pub fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    // H. Hinnant, "chrono-Compatible Low-Level Date Algorithms"
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z % 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
+// -----------------------------------------------------------------------------
+// SİSTEM ÇAĞRILARI
+// -----------------------------------------------------------------------------
+
+/// Kullanıcının `struct timespec`'i: saniye ve nanosaniye.
+type Timespec = [i64; 2];
+
+fn read_timespec(ptr: UserPtr<Timespec>) -> Result<u64, KernelError> {
+    let [sec, nsec] = ptr.read()?;
+    if sec < 0 || !(0..NS_PER_SEC as i64).contains(&nsec) {
+        return Err(KernelError::InvalidArgument);
+    }
+    (sec as u64).checked_mul(NS_PER_SEC).and_then(|ns| ns.checked_add(nsec as u64)).ok_or(KernelError::InvalidArgument)
+}
+
+fn write_timespec(ptr: UserPtr<Timespec>, ns: u64) -> Result<(), KernelError> {
+    ptr.write(&[(ns / NS_PER_SEC) as i64, (ns % NS_PER_SEC) as i64])
+}
+
+/// `clock_gettime(clock, *ts)`.
+pub fn sys_clock_gettime(clock: usize, ts: UserPtr<Timespec>) -> Result<usize, KernelError> {
+    write_timespec(ts, clock_now(clock)?)?;
+    Ok(0)
+}
+
+/// `clock_settime(clock, *ts)`: yalnızca CLOCK_REALTIME ayarlanabilir.
+pub fn sys_clock_settime(clock: usize, ts: UserPtr<Timespec>) -> Result<usize, KernelError> {
+    if clock != CLOCK_REALTIME {
+        return Err(KernelError::InvalidArgument);
+    }
+    settime(read_timespec(ts)?);
+    Ok(0)
+}
+
+/// `clock_getres(clock, *ts)`; `ts` NULL olabilir.
+pub fn sys_clock_getres(clock: usize, ts: UserPtr<Timespec>) -> Result<usize, KernelError> {
+    clock_now(clock)?;
+    if !ts.is_null() {
+        write_timespec(ts, hrtimer::resolution_ns())?;
+    }
+    Ok(0)
+}
+
+/// `clock_nanosleep(clock, flags, *req, *rem)`. Sinyal olmadığından uyku
+/// kesilmez ve `rem` yazılmaz. Mutlak CLOCK_REALTIME hedefi çağrı anındaki
+/// farka çevrilir; uyku sırasındaki `settime` hedefi kaydırmaz.
+pub fn sys_clock_nanosleep(
+    clock: usize,
+    flags: usize,
+    req: UserPtr<Timespec>,
+    _rem: UserPtr<Timespec>,
+) -> Result<usize, KernelError> {
+    let target = read_timespec(req)?;
+    let now = clock_now(clock)?;
+    let delay = if flags & TIMER_ABSTIME != 0 { target.saturating_sub(now) } else { target };
+    hrtimer::sleep_ns(delay);
+    Ok(0)
+}
+
+/// `clock_adjtime(clock, *timex)`: yalnızca CLOCK_REALTIME ve
+/// ADJ_OFFSET_SINGLESHOT / ADJ_OFFSET_SS_READ (µs cinsinden `offset`)
+/// desteklenir. Kalan kaydırma `offset`'e geri yazılır.
+pub fn sys_clock_adjtime(clock: usize, timex: UserPtr<u32>) -> Result<usize, KernelError> {
+    if clock != CLOCK_REALTIME {
+        return Err(KernelError::InvalidArgument);
+    }
+    let offset = timex.field::<i64>(TIMEX_OFFSET_FIELD);
+    let remaining = match timex.read()? {
+        ADJ_OFFSET_SS_READ => pending_slew(),
+        ADJ_OFFSET_SINGLESHOT => adjtime(offset.read()?.checked_mul(1000).ok_or(KernelError::InvalidArgument)?)?,
+        _ => return Err(KernelError::InvalidArgument),
+    };
+    offset.write(&(remaining / 1000))?;
+    Ok(TIME_OK)
+}
@@ -0,0 +1,85 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// xtask/src/arch.rs
+// Desteklenen mimariler: derleme hedefi, linker betiği ve görüntü türü.
+//
+// Rust'ın yerleşik çıplak donanım (`*-none`) hedefi olmayan mimariler
+// `targets/` altındaki özel hedef tanımlarını (JSON) kullanır; bunlar için
+// `core`/`alloc` kaynaktan derlenir (`-Z build-std`, nightly gerekir).
+// Tanımların kaynağı `targets.rs`'dir.
+
+use std::path::{Path, PathBuf};
+
+use crate::targets::TargetSpec;
+
+/// Mimarinin önyükleme yüküne dönüştürülme biçimi.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageKind {
+    /// GRUB ile önyüklenen Multiboot2 ISO (`grub-mkrescue`).
+    Multiboot2Iso,
+    /// Düz ikili (`Image`) ve yanında aygıt ağacı (DTB); U-Boot / QEMU `-kernel`.
+    FlatWithDtb,
+    /// OpenSBI `fw_payload` (düz ikili OpenSBI'ın arkasına eklenir).
+    FwPayload,
+    /// OpenBIOS'un doğrudan yüklediği ELF.
+    OpenBiosElf,
+    /// Önyükleyicinin (QEMU `-kernel`, UEFI) doğrudan yüklediği ELF.
+    Elf,
+}
+
+pub struct Arch {
+    /// `src/arch/` altındaki dizin adı; komut satırında da bu ad kullanılır.
+    pub name: &'static str,
+    /// Komut satırında kabul edilen diğer adlar (`target_arch` değeri vb.).
+    pub aliases: &'static [&'static str],
+    /// Yerleşik hedef üçlüsü veya depo köküne göreli hedef JSON'u.
+    pub target: &'static str,
+    pub image: ImageKind,
+}
+
+pub const ARCHES: &[Arch] = &[
+    Arch { name: "amd64", aliases: &["x86_64"], target: "x86_64-unknown-none", image: ImageKind::Multiboot2Iso },
+    Arch { name: "armv9", aliases: &["aarch64", "arm64"], target: "aarch64-unknown-none", image: ImageKind::FlatWithDtb },
+    Arch { name: "rv64i", aliases: &["riscv64"], target: "riscv64gc-unknown-none-elf", image: ImageKind::FwPayload },
+    Arch { name: "sparcv9", aliases: &["sparc64"], target: "targets/sparc64-sahne-none.json", image: ImageKind::OpenBiosElf },
+    Arch { name: "powerpc64", aliases: &["ppc64"], target: "targets/powerpc64-sahne-none.json", image: ImageKind::OpenBiosElf },
+    Arch { name: "loongarch64", aliases: &[], target: "loongarch64-unknown-none-softfloat", image: ImageKind::Elf },
+    Arch { name: "mips64", aliases: &[], target: "targets/mips64-sahne-none.json", image: ImageKind::Elf },
+    Arch { name: "openrisc64", aliases: &["or1k"], target: "targets/or1k-sahne-none.json", image: ImageKind::Elf },
+];
+
+impl Arch {
+    /// Mimariyi adından veya takma adından bulur.
+    pub fn find(name: &str) -> Option<&'static Arch> {
+        ARCHES.iter().find(|a| a.name == name || a.aliases.contains(&name))
+    }
+
+    /// Hedef özel bir JSON tanımı mı?
+    pub fn custom_target(&self) -> bool {
+        self.target.ends_with(".json")
+    }
+
+    /// `cargo build --target` argümanı.
+    pub fn target_arg(&self, root: &Path) -> PathBuf {
+        if self.custom_target() {
+            root.join(self.target)
+        } else {
+            PathBuf::from(self.target)
+        }
+    }
+
+    /// Özel hedefin `targets.rs` kaydı.
+    pub fn spec(&self) -> Option<&'static TargetSpec> {
+        TargetSpec::find(self.target_dir_name()).filter(|_| self.custom_target())
+    }
+
+    /// Cargo'nun çıktı dizininde kullandığı hedef adı (JSON için dosya adı kökü).
+    pub fn target_dir_name(&self) -> &'static str {
+        let file = self.target.rsplit('/').next().unwrap_or(self.target);
+        file.strip_suffix(".json").unwrap_or(file)
+    }
+
+    pub fn linker_script(&self, root: &Path) -> PathBuf {
+        root.join("src/arch").join(self.name).join("linker.ld")
+    }
+}
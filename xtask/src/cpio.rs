@@ -0,0 +1,72 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// xtask/src/cpio.rs
+// Dizinden newc cpio arşivi (initramfs) üretir.
+//
+// Çekirdeğin açıcısı (`src/initrd.rs`) ile aynı biçim: 110 baytlık ASCII
+// başlık, sıfır sonlu ad, 4 bayta hizalı veri ve `TRAILER!!!` girdisi.
+// Çıktı tekrarlanabilir olsun diye girdiler ada göre sıralanır, sahip 0:0 ve
+// değiştirilme zamanı 0 yazılır.
+
+use std::fs;
+use std::io;
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+
+const TRAILER: &str = "TRAILER!!!";
+
+struct Writer {
+    out: Vec<u8>,
+    ino: u32,
+}
+
+impl Writer {
+    fn entry(&mut self, name: &str, mode: u32, data: &[u8]) {
+        self.ino += 1;
+        let nlink = if mode & 0o170000 == 0o040000 { 2 } else { 1 };
+        let fields = [self.ino, mode, 0, 0, nlink, 0, data.len() as u32, 0, 0, 0, 0, name.len() as u32 + 1, 0];
+        self.out.extend_from_slice(b"070701");
+        for field in fields {
+            self.out.extend_from_slice(format!("{:08X}", field).as_bytes());
+        }
+        self.out.extend_from_slice(name.as_bytes());
+        self.out.push(0);
+        self.pad();
+        self.out.extend_from_slice(data);
+        self.pad();
+    }
+
+    fn pad(&mut self) {
+        while !self.out.len().is_multiple_of(4) {
+            self.out.push(0);
+        }
+    }
+
+    fn walk(&mut self, root: &Path, dir: &Path) -> io::Result<()> {
+        let mut entries = fs::read_dir(dir)?.collect::<io::Result<Vec<_>>>()?;
+        entries.sort_by_key(|e| e.file_name());
+        for entry in entries {
+            let path = entry.path();
+            let meta = fs::symlink_metadata(&path)?;
+            let name = path.strip_prefix(root).unwrap_or(&path).to_string_lossy().into_owned();
+            if meta.is_dir() {
+                self.entry(&name, meta.mode(), &[]);
+                self.walk(root, &path)?;
+            } else if meta.file_type().is_symlink() {
+                let target = fs::read_link(&path)?;
+                self.entry(&name, meta.mode(), target.to_string_lossy().as_bytes());
+            } else if meta.is_file() {
+                self.entry(&name, meta.mode(), &fs::read(&path)?);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `dir` içeriğini arşivler; arşiv kökü `dir`'in kendisidir.
+pub fn pack(dir: &Path) -> io::Result<Vec<u8>> {
+    let mut writer = Writer { out: Vec::new(), ino: 0 };
+    writer.walk(dir, dir)?;
+    writer.entry(TRAILER, 0, &[]);
+    Ok(writer.out)
+}
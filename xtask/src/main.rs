@@ -0,0 +1,451 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// xtask/src/main.rs
+// Önyükleme görüntüsü oluşturucu: `cargo xtask <komut>`.
+//
+// Çekirdeği mimarinin hedefi ve linker betiğiyle (`src/arch/<mimari>/linker.ld`)
+// derler ve önyüklenebilir yüke dönüştürür. Sonuçlar `target/images/<mimari>/`
+// altına yazılır.
+//
+//   cargo xtask list
+//   cargo xtask build <mimari> [--release] [--features F] [--bin AD] [--toolchain AD]
+//   cargo xtask image <mimari> [--release] [--features F] [--bin AD] [--toolchain AD]
+//                     [--initramfs DİZİN|DOSYA] [--dtb DOSYA] [--opensbi DİZİN]
+//   cargo xtask cpio <dizin> <çıktı>
+//   cargo xtask targets [--check]
+//   cargo xtask stack <mimari> [derleme seçenekleri] [--top N] [--root AD]...
+//                     [--measured DOSYA]
+//
+// Mimari konumsal ya da `--arch <mimari>` ile verilebilir. Özel hedefli
+// mimarilerde (`targets.rs`) derlemeden önce `targets/*.json` dosyasının
+// tabloyla aynı olduğu denetlenir. `--toolchain`, rustup araç zincirini
+// seçer (`RUSTUP_TOOLCHAIN`); yukarı akışta arka ucu olmayan hedefler
+// (OpenRISC) için zorunludur.
+//
+// `stack`, çekirdeği `-Z emit-stack-sizes` ile (nightly) derleyip işlev
+// çerçevelerini ve kesme işleyicileriyle görevlerin en kötü yığın
+// kullanımını raporlar; ayrıntılar `stack.rs`'de.
+//
+// Görüntü türleri (bkz. `arch::ImageKind`):
+// * amd64: GRUB Multiboot2 ISO (`grub-mkrescue`, `xorriso` gerekir).
+// * armv9: düz `Image` + verilen DTB.
+// * rv64i: `--opensbi` verilirse OpenSBI `fw_payload`, yoksa düz `Image`.
+// * sparcv9, powerpc64: OpenBIOS'un yüklediği ELF.
+// * loongarch64, mips64, openrisc64: ELF.
+//
+// Initramfs: AMD64'te çekirdeğe gömülür (depo kökündeki `initrd.img`,
+// bkz. `src/initrd.rs`); diğer mimarilerde `initramfs.cpio` olarak görüntünün
+// yanına koyulur ve önyükleyici tarafından yüklenir (QEMU `-initrd`, U-Boot).
+// Dizin verilirse önce newc cpio arşivine paketlenir.
+//
+// Araçlar ortam değişkenleriyle değiştirilebilir: `OBJCOPY` (varsayılan
+// `rust-objcopy`, yoksa `llvm-objcopy`), `OBJDUMP` (`rust-objdump` /
+// `llvm-objdump`), `GRUB_MKRESCUE`, `CROSS_COMPILE`
+// (OpenSBI derlemesi için).
+
+mod arch;
+mod cpio;
+mod stack;
+mod targets;
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode};
+
+use arch::{Arch, ImageKind, ARCHES};
+use targets::{Backend, TARGETS};
+
+/// Çekirdek ikilisinin varsayılan adı (`--bin` ile değiştirilebilir).
+const KERNEL_BIN: &str = "sahne-karnal";
+
+type Result<T> = std::result::Result<T, String>;
+
+// -----------------------------------------------------------------------------
+// KOMUT SATIRI
+// -----------------------------------------------------------------------------
+
+#[derive(Default)]
+struct Options {
+    release: bool,
+    features: Option<String>,
+    bin: Option<String>,
+    initramfs: Option<PathBuf>,
+    dtb: Option<PathBuf>,
+    opensbi: Option<PathBuf>,
+    toolchain: Option<String>,
+    /// `-Z emit-stack-sizes` (yalnızca `stack`).
+    stack_sizes: bool,
+    top: Option<usize>,
+    roots: Vec<String>,
+    measured: Option<PathBuf>,
+}
+
+fn parse_options(args: &[String]) -> Result<Options> {
+    let mut options = Options::default();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let mut value = || iter.next().cloned().ok_or_else(|| format!("{} bir değer bekler", arg));
+        match arg.as_str() {
+            "--release" => options.release = true,
+            "--features" => options.features = Some(value()?),
+            "--bin" => options.bin = Some(value()?),
+            "--initramfs" => options.initramfs = Some(value()?.into()),
+            "--dtb" => options.dtb = Some(value()?.into()),
+            "--opensbi" => options.opensbi = Some(value()?.into()),
+            "--toolchain" => options.toolchain = Some(value()?),
+            "--top" => options.top = Some(value()?.parse().map_err(|_| "--top bir sayı bekler".to_string())?),
+            "--root" => options.roots.push(value()?),
+            "--measured" => options.measured = Some(value()?.into()),
+            _ => return Err(format!("bilinmeyen seçenek: {}", arg)),
+        }
+    }
+    Ok(options)
+}
+
+fn usage() {
+    eprintln!("Kullanım:");
+    eprintln!("  cargo xtask list");
+    eprintln!("  cargo xtask build <mimari>|--arch <mimari> [--release] [--features F] [--bin AD] [--toolchain AD]");
+    eprintln!("  cargo xtask image <mimari>|--arch <mimari> [--release] [--features F] [--bin AD] [--toolchain AD]");
+    eprintln!("                    [--initramfs DİZİN|DOSYA] [--dtb DOSYA] [--opensbi DİZİN]");
+    eprintln!("  cargo xtask cpio <dizin> <çıktı>");
+    eprintln!("  cargo xtask targets [--check]");
+    eprintln!("  cargo xtask stack <mimari>|--arch <mimari> [derleme seçenekleri] [--top N] [--root AD]... [--measured DOSYA]");
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("list") => {
+            list();
+            Ok(())
+        }
+        Some(command @ ("build" | "image" | "stack")) => {
+            // `build sparcv9` veya `build --arch sparcv9`
+            let rest = match args.get(1).map(String::as_str) {
+                Some("--arch") => &args[2..],
+                _ => &args[1..],
+            };
+            let Some(arch) = rest.first() else {
+                usage();
+                return ExitCode::FAILURE;
+            };
+            Arch::find(arch)
+                .ok_or_else(|| format!("bilinmeyen mimari: {} (`cargo xtask list`)", arch))
+                .and_then(|arch| parse_options(&rest[1..]).map(|options| (arch, options)))
+                .and_then(|(arch, options)| match command {
+                    "build" => build(arch, &options).map(|_| ()),
+                    "stack" => stack_report(arch, options),
+                    _ => image(arch, &options),
+                })
+        }
+        Some("targets") if args.len() == 1 => write_targets(),
+        Some("targets") if args.len() == 2 && args[1] == "--check" => check_targets(),
+        Some("cpio") if args.len() == 3 => {
+            cpio::pack(Path::new(&args[1]))
+                .and_then(|data| fs::write(&args[2], data))
+                .map_err(|e| format!("{}: {}", args[1], e))
+        }
+        _ => {
+            usage();
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("hata: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn list() {
+    println!("  MİMARİ       HEDEF                               GÖRÜNTÜ");
+    for arch in ARCHES {
+        println!("  {:<12} {:<35} {:?}", arch.name, arch.target, arch.image);
+    }
+}
+
+// -----------------------------------------------------------------------------
+// HEDEF TANIMLARI
+// -----------------------------------------------------------------------------
+
+/// `targets/*.json` dosyalarını `targets.rs` tablosundan yeniden yazar.
+fn write_targets() -> Result<()> {
+    let dir = root().join("targets");
+    fs::create_dir_all(&dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    for spec in TARGETS {
+        let path = root().join(spec.path());
+        fs::write(&path, spec.to_json()).map_err(|e| format!("{}: {}", path.display(), e))?;
+        println!("[xtask] {}", spec.path());
+    }
+    Ok(())
+}
+
+/// Dosya tablodan üretilenle aynı mı?
+fn target_is_current(spec: &targets::TargetSpec) -> bool {
+    fs::read_to_string(root().join(spec.path())).is_ok_and(|json| json == spec.to_json())
+}
+
+fn check_targets() -> Result<()> {
+    let stale: Vec<String> = TARGETS.iter().filter(|s| !target_is_current(s)).map(|s| s.path()).collect();
+    if stale.is_empty() {
+        println!("[xtask] {} hedef tanımı güncel", TARGETS.len());
+        Ok(())
+    } else {
+        Err(format!("güncel olmayan hedef tanımları: {} (`cargo xtask targets`)", stale.join(", ")))
+    }
+}
+
+// -----------------------------------------------------------------------------
+// YARDIMCILAR
+// -----------------------------------------------------------------------------
+
+/// Depo kökü (`xtask/` dizininin üstü).
+fn root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().map_or_else(|| PathBuf::from("."), Path::to_path_buf)
+}
+
+fn run(command: &mut Command) -> Result<()> {
+    let program = command.get_program().to_string_lossy().into_owned();
+    let status = command.status().map_err(|e| format!("{} çalıştırılamadı: {}", program, e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("{} başarısız oldu ({})", program, status))
+    }
+}
+
+/// Ortam değişkeniyle verilen veya PATH'te bulunan ilk araç.
+fn tool(var: &str, candidates: &[&str]) -> Result<String> {
+    if let Ok(tool) = env::var(var) {
+        return Ok(tool);
+    }
+    let path = env::var_os("PATH").unwrap_or_default();
+    candidates
+        .iter()
+        .find(|name| env::split_paths(&path).any(|dir| dir.join(name).is_file()))
+        .map(|name| name.to_string())
+        .ok_or_else(|| format!("{} bulunamadı ({} ile belirtilebilir)", candidates.join(" / "), var))
+}
+
+fn copy(from: &Path, to: &Path) -> Result<()> {
+    fs::copy(from, to).map(|_| ()).map_err(|e| format!("{} -> {}: {}", from.display(), to.display(), e))
+}
+
+fn objcopy(args: &[&str], input: &Path, output: &Path) -> Result<()> {
+    let objcopy = tool("OBJCOPY", &["rust-objcopy", "llvm-objcopy"])?;
+    run(Command::new(objcopy).args(args).arg(input).arg(output))
+}
+
+/// `--initramfs` değerini cpio verisine çevirir (dizinse paketler).
+fn initramfs_data(path: &Path) -> Result<Vec<u8>> {
+    let data = if path.is_dir() { cpio::pack(path) } else { fs::read(path) };
+    data.map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+// -----------------------------------------------------------------------------
+// DERLEME
+// -----------------------------------------------------------------------------
+
+/// Kısa commit özeti; çalışma ağacında değişiklik varsa `-dirty` eklenir.
+/// Git yoksa veya depo değilse "unknown".
+fn git_hash(root: &Path) -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .current_dir(root)
+            .args(args)
+            .output()
+            .ok()
+            .filter(|out| out.status.success())
+            .map(|out| String::from_utf8_lossy(&out.stdout).trim().to_string())
+    };
+    match git(&["rev-parse", "--short=12", "HEAD"]) {
+        Some(hash) if git(&["status", "--porcelain"]).is_some_and(|s| !s.is_empty()) => format!("{}-dirty", hash),
+        Some(hash) => hash,
+        None => "unknown".into(),
+    }
+}
+
+/// Derleme zamanı (Unix zamanı, s); `SOURCE_DATE_EPOCH` verilmişse o kullanılır.
+fn build_time() -> u64 {
+    env::var("SOURCE_DATE_EPOCH").ok().and_then(|s| s.parse().ok()).unwrap_or_else(|| {
+        std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map_or(0, |d| d.as_secs())
+    })
+}
+
+/// Çekirdeği derler ve ELF'in yolunu döndürür.
+fn build(arch: &Arch, options: &Options) -> Result<PathBuf> {
+    let root = root();
+    let script = arch.linker_script(&root);
+    if !script.is_file() {
+        return Err(format!("linker betiği yok: {}", script.display()));
+    }
+    let target = arch.target_arg(&root);
+    if arch.custom_target() && !target.is_file() {
+        return Err(format!("hedef tanımı yok: {} (`cargo xtask targets`)", target.display()));
+    }
+    if let Some(spec) = arch.spec() {
+        if !target_is_current(spec) {
+            return Err(format!("{} targets.rs ile uyuşmuyor (`cargo xtask targets`)", spec.path()));
+        }
+        if spec.backend == Backend::OutOfTree && options.toolchain.is_none() {
+            return Err(format!(
+                "{}: yukarı akış LLVM'de {} arka ucu yok; --toolchain ile uygun araç zincirini verin",
+                arch.name, spec.arch
+            ));
+        }
+    }
+
+    // AMD64 initramfs'i `include_bytes!` ile gömer; dosya her zaman var olmalıdır
+    let embedded = root.join("initrd.img");
+    if arch.image == ImageKind::Multiboot2Iso {
+        let data = match &options.initramfs {
+            Some(path) => initramfs_data(path)?,
+            None => Vec::new(),
+        };
+        if options.initramfs.is_some() || !embedded.exists() {
+            fs::write(&embedded, data).map_err(|e| format!("{}: {}", embedded.display(), e))?;
+        }
+    }
+
+    let bin = options.bin.as_deref().unwrap_or(KERNEL_BIN);
+    let mut cargo = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".into()));
+    cargo.current_dir(&root).args(["build", "--bin", bin, "--target"]).arg(&target);
+    if let Some(toolchain) = &options.toolchain {
+        cargo.env("RUSTUP_TOOLCHAIN", toolchain);
+    }
+    if options.release {
+        cargo.arg("--release");
+    }
+    if let Some(features) = &options.features {
+        cargo.args(["--features", features]);
+    }
+    if arch.custom_target() {
+        cargo.args(["-Z", "build-std=core,alloc", "-Z", "build-std-features=compiler-builtins-mem"]);
+    }
+    // Boşluk içeren yollar için ayraçlı (0x1f) biçim
+    let mut flags = vec![format!("-Clink-arg=-T{}", script.display()), "-Crelocation-model=static".into()];
+    if arch.custom_target() {
+        // Yeni rustc sürümleri JSON hedef tanımını kararsız kabul eder
+        flags.push("-Zunstable-options".into());
+    }
+    if options.stack_sizes {
+        flags.push("-Zemit-stack-sizes".into());
+    }
+    // Yığın kanaryası (`src/canary.rs`)
+    if options.features.as_deref().is_some_and(|f| f.split([',', ' ']).any(|f| f == "stackprotector")) {
+        flags.push("-Zstack-protector=strong".into());
+    }
+    cargo.env("CARGO_ENCODED_RUSTFLAGS", flags.join("\x1f")).env_remove("RUSTFLAGS");
+    // Derleme kimliği (bkz. `src/version.rs`)
+    cargo.env("SAHNE_GIT_HASH", git_hash(&root)).env("SAHNE_BUILD_TIME", build_time().to_string());
+    run(&mut cargo)?;
+
+    let profile = if options.release { "release" } else { "debug" };
+    let elf = root.join("target").join(arch.target_dir_name()).join(profile).join(bin);
+    if !elf.is_file() {
+        return Err(format!("derleme çıktısı bulunamadı: {}", elf.display()));
+    }
+    println!("[xtask] {}: {}", arch.name, elf.display());
+    Ok(elf)
+}
+
+// -----------------------------------------------------------------------------
+// YIĞIN ÇÖZÜMLEMESİ
+// -----------------------------------------------------------------------------
+
+fn stack_report(arch: &Arch, mut options: Options) -> Result<()> {
+    options.stack_sizes = true;
+    let elf = build(arch, &options)?;
+    let objdump = tool("OBJDUMP", &["rust-objdump", "llvm-objdump"])?;
+    let report = stack::Options { top: options.top.unwrap_or(20), roots: &options.roots, measured: options.measured.as_deref() };
+    stack::report(arch.name, &elf, &objdump, &report)
+}
+
+// -----------------------------------------------------------------------------
+// GÖRÜNTÜ
+// -----------------------------------------------------------------------------
+
+fn image(arch: &Arch, options: &Options) -> Result<()> {
+    let elf = build(arch, options)?;
+    let out = root().join("target/images").join(arch.name);
+    fs::create_dir_all(&out).map_err(|e| format!("{}: {}", out.display(), e))?;
+    let name = format!("sahne-{}", arch.name);
+
+    let mut artifacts = Vec::new();
+    match arch.image {
+        ImageKind::Multiboot2Iso => {
+            let staging = out.join("iso");
+            let grub = staging.join("boot/grub");
+            fs::create_dir_all(&grub).map_err(|e| format!("{}: {}", grub.display(), e))?;
+            copy(&elf, &staging.join("boot/kernel.elf"))?;
+            let config = format!("set timeout=0\nset default=0\n\nmenuentry \"{}\" {{\n    multiboot2 /boot/kernel.elf\n    boot\n}}\n", name);
+            fs::write(grub.join("grub.cfg"), config).map_err(|e| e.to_string())?;
+            let iso = out.join(format!("{}.iso", name));
+            let mkrescue = tool("GRUB_MKRESCUE", &["grub-mkrescue", "grub2-mkrescue"])?;
+            run(Command::new(mkrescue).arg("-o").arg(&iso).arg(&staging))?;
+            artifacts.push(iso);
+        }
+        ImageKind::FlatWithDtb | ImageKind::FwPayload => {
+            let flat = out.join("Image");
+            objcopy(&["-O", "binary"], &elf, &flat)?;
+            artifacts.push(flat.clone());
+            if arch.image == ImageKind::FwPayload {
+                if let Some(opensbi) = &options.opensbi {
+                    artifacts.extend(fw_payload(opensbi, &flat, &out)?);
+                } else {
+                    println!("[xtask] --opensbi verilmedi; yalnızca düz Image üretildi (QEMU: -bios default -kernel Image)");
+                }
+            }
+        }
+        ImageKind::OpenBiosElf | ImageKind::Elf => {
+            let stripped = out.join(format!("{}.elf", name));
+            objcopy(&["--strip-debug"], &elf, &stripped)?;
+            artifacts.push(stripped);
+        }
+    }
+
+    if let Some(dtb) = &options.dtb {
+        let target = out.join(dtb.file_name().unwrap_or_else(|| "board.dtb".as_ref()));
+        copy(dtb, &target)?;
+        artifacts.push(target);
+    } else if arch.image == ImageKind::FlatWithDtb {
+        println!("[xtask] --dtb verilmedi; önyükleyicinin aygıt ağacı kullanılacak");
+    }
+
+    if let (Some(path), false) = (&options.initramfs, arch.image == ImageKind::Multiboot2Iso) {
+        let cpio = out.join("initramfs.cpio");
+        fs::write(&cpio, initramfs_data(path)?).map_err(|e| format!("{}: {}", cpio.display(), e))?;
+        artifacts.push(cpio);
+    }
+
+    for artifact in &artifacts {
+        println!("[xtask] -> {}", artifact.display());
+    }
+    Ok(())
+}
+
+/// OpenSBI'ı çekirdeği yük olarak gömerek derler ve `fw_payload` dosyalarını
+/// çıktı dizinine kopyalar.
+fn fw_payload(opensbi: &Path, payload: &Path, out: &Path) -> Result<Vec<PathBuf>> {
+    let cross = env::var("CROSS_COMPILE").unwrap_or_else(|_| "riscv64-unknown-elf-".into());
+    run(Command::new("make")
+        .arg("-C")
+        .arg(opensbi)
+        .arg("PLATFORM=generic")
+        .arg(format!("CROSS_COMPILE={}", cross))
+        .arg(format!("FW_PAYLOAD_PATH={}", payload.display())))?;
+
+    let firmware = opensbi.join("build/platform/generic/firmware");
+    ["fw_payload.elf", "fw_payload.bin"]
+        .iter()
+        .map(|file| {
+            let target = out.join(file);
+            copy(&firmware.join(file), &target).map(|_| target)
+        })
+        .collect()
+}
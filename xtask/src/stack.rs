@@ -0,0 +1,412 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// xtask/src/stack.rs
+// Yığın kullanımı çözümlemesi: `cargo xtask stack <mimari>`.
+//
+// Çekirdek `-Z emit-stack-sizes` ile derlenir; rustc (LLVM) her işlevin
+// çerçeve boyunu `.stack_sizes` bölümüne yazar: işlev adresi (hedefin uç
+// düzeniyle 8 bayt) ve ardından ULEB128 boyut. Bölüm bellek dışı (INFO)
+// olduğundan linker betikleri onu atmaz.
+//
+// Çerçeveler tek başına en kötü durumu vermez; çağrı zinciri
+// `llvm-objdump -d` çıktısından kurulur. Yönerge satırında
+// `<hedef-adresi> <işlev>` açıklaması olan ve başka bir işlevin başına
+// giden her dal çağrı sayılır (mimariden bağımsız: `call`, `bl`, `jal`,
+// `l.jal`, kuyruk çağrısı `jmp`/`b`). Kuyruk çağrısı çağıranın çerçevesini
+// bırakır; toplamak sonucu yalnızca büyütür.
+//
+// Sınırlar (rapor bunları işaretler):
+// * Dolaylı çağrılar (işlev işaretçisi, `dyn` yöntemleri) izlenemez; böyle
+//   bir işlevin toplamı alt sınırdır (`+`).
+// * Özyineleme zincirin sonsuz olabileceği anlamına gelir (`özyineleme`).
+// * Montaj işlevlerinin (`.S`, `global_asm!`) çerçevesi bilinmez, 0 sayılır.
+// * Donanımın istisna girişinde yığına yazdığı çerçeve ve giriş kodunun
+//   kaydettiği bağlam köke dahil değildir.
+//
+// AMD64'te `call`'un yığına yazdığı dönüş adresi çerçeveye dahil değildir;
+// zincirdeki her çağrıya 8 bayt eklenir.
+//
+// Kökler: mimari istisna/kesme işleyicileri (`generic_*handler`,
+// `generic_*vector`) ve görev girişi (`task_entry`); `--root AD` ile ek kök
+// verilir. Görev gövdeleri `task_entry`'den dolaylı çağrılır; çalışan
+// çekirdeğin `/proc/stackusage` çıktısı `--measured DOSYA` ile verilirse
+// oradaki görev giriş adresleri de kök olur ve ölçülen en yüksek kullanım
+// statik tahminin yanında gösterilir.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use crate::Result;
+
+/// `.stack_sizes` kaydı olmayan işlev.
+const UNKNOWN: u64 = 0;
+
+// -----------------------------------------------------------------------------
+// ELF
+// -----------------------------------------------------------------------------
+
+struct Elf<'a> {
+    data: &'a [u8],
+    big_endian: bool,
+}
+
+impl<'a> Elf<'a> {
+    fn parse(data: &'a [u8]) -> Result<Self> {
+        if data.len() < 64 || &data[..4] != b"\x7fELF" {
+            return Err("ELF dosyası değil".into());
+        }
+        if data[4] != 2 {
+            return Err("yalnızca 64 bitlik ELF desteklenir".into());
+        }
+        Ok(Elf { data, big_endian: data[5] == 2 })
+    }
+
+    fn bytes(&self, offset: usize, len: usize) -> Result<&'a [u8]> {
+        offset
+            .checked_add(len)
+            .and_then(|end| self.data.get(offset..end))
+            .ok_or_else(|| format!("ELF kesik ({:#x})", offset))
+    }
+
+    fn uint(&self, offset: usize, len: usize) -> Result<u64> {
+        let bytes = self.bytes(offset, len)?;
+        let fold = |acc: u64, &b: &u8| (acc << 8) | u64::from(b);
+        Ok(if self.big_endian { bytes.iter().fold(0, fold) } else { bytes.iter().rev().fold(0, fold) })
+    }
+
+    /// Adı verilen bölümün içeriği.
+    fn section(&self, wanted: &str) -> Result<Option<&'a [u8]>> {
+        let shoff = self.uint(0x28, 8)? as usize;
+        let shentsize = self.uint(0x3a, 2)? as usize;
+        let shnum = self.uint(0x3c, 2)? as usize;
+        let shstrndx = self.uint(0x3e, 2)? as usize;
+        let header = |index: usize| shoff + index * shentsize;
+        let names = self.uint(header(shstrndx) + 0x18, 8)? as usize;
+        for index in 0..shnum {
+            let name = names + self.uint(header(index), 4)? as usize;
+            let name = self.data.get(name..).and_then(|s| s.split(|&b| b == 0).next());
+            if name == Some(wanted.as_bytes()) {
+                let offset = self.uint(header(index) + 0x18, 8)? as usize;
+                let size = self.uint(header(index) + 0x20, 8)? as usize;
+                return self.bytes(offset, size).map(Some);
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// `.stack_sizes` bölümünü okur: işlev adresi -> çerçeve boyu.
+fn stack_sizes(elf: &Elf) -> Result<HashMap<u64, u64>> {
+    let section = elf
+        .section(".stack_sizes")?
+        .ok_or("ELF'te .stack_sizes yok (derleme -Z emit-stack-sizes ile yapılmadı mı?)")?;
+    let reader = Elf { data: section, big_endian: elf.big_endian };
+    let mut sizes = HashMap::new();
+    let mut pos = 0;
+    while pos < section.len() {
+        let addr = reader.uint(pos, 8)?;
+        pos += 8;
+        let (mut size, mut shift) = (0u64, 0);
+        loop {
+            let byte = *section.get(pos).ok_or(".stack_sizes kesik")?;
+            pos += 1;
+            size |= u64::from(byte & 0x7f) << shift;
+            shift += 7;
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+        sizes.insert(addr, size);
+    }
+    Ok(sizes)
+}
+
+// -----------------------------------------------------------------------------
+// ÇAĞRI ÇİZGESİ
+// -----------------------------------------------------------------------------
+
+#[derive(Default)]
+struct Function {
+    name: String,
+    frame: u64,
+    callees: Vec<u64>,
+    /// İşlev dolaylı çağrı yapıyor.
+    indirect: bool,
+}
+
+/// Dolaylı çağrı anımsatıcıları (hedef açıklaması yoksa).
+const INDIRECT_CALLS: &[&str] = &["call", "callq", "blr", "blraa", "jalr", "bctrl", "l.jalr", "jirl"];
+
+/// `hex` (isteğe bağlı `0x` önekli) onaltılık sayı mı?
+fn hex(token: &str) -> Option<u64> {
+    u64::from_str_radix(token.trim_start_matches("0x"), 16).ok()
+}
+
+/// Çağrı hedefi: açıklamadan önceki adres ya da (RISC-V `jalr 0x10(ra) <f>`
+/// gibi adressiz biçimde) ofsetsiz açıklamadaki ad.
+enum Target {
+    Addr(u64),
+    Name(String),
+}
+
+/// `llvm-objdump -d` çıktısından işlevleri ve doğrudan çağrıları çıkarır.
+fn call_graph(disassembly: &str, sizes: &HashMap<u64, u64>) -> HashMap<u64, Function> {
+    let mut functions: HashMap<u64, Function> = HashMap::new();
+    let mut targets: Vec<(u64, Target)> = Vec::new();
+    let mut current = None;
+    for line in disassembly.lines() {
+        // İşlev başlığı: `ffffffff80001000 <ad>:`
+        if let Some(head) = line.strip_suffix(">:") {
+            let (addr, name) = head.split_once(" <").unwrap_or(("", ""));
+            current = hex(addr);
+            if let Some(addr) = current {
+                let frame = sizes.get(&addr).copied().unwrap_or(UNKNOWN);
+                functions.insert(addr, Function { name: name.to_string(), frame, ..Default::default() });
+            }
+            continue;
+        }
+        let Some(caller) = current else { continue };
+        // Yönerge: `adres: anımsatıcı işlenenler [<hedef>]`; x86'nın
+        // `# 0x... <veri>` açıklamaları çağrı değildir
+        let Some((_, insn)) = line.split_once(':') else { continue };
+        let insn = insn.split('#').next().unwrap_or("").trim();
+        let Some(mnemonic) = insn.split_whitespace().next() else { continue };
+        match (insn.find(" <"), insn.strip_suffix('>')) {
+            (Some(open), Some(_)) => {
+                let before = insn[..open].rsplit([' ', '\t', ',']).next().unwrap_or("");
+                let name = &insn[open + 2..insn.len() - 1];
+                if let Some(addr) = hex(before) {
+                    targets.push((caller, Target::Addr(addr)));
+                } else if !name.contains("+0x") {
+                    targets.push((caller, Target::Name(name.to_string())));
+                }
+            }
+            _ if INDIRECT_CALLS.contains(&mnemonic) => {
+                if let Some(function) = functions.get_mut(&caller) {
+                    function.indirect = true;
+                }
+            }
+            _ => {}
+        }
+    }
+    // Yalnızca bir işlevin başına giden dallar çağrıdır (kendi başına dal özyinelemedir)
+    let by_name: HashMap<String, u64> = functions.iter().map(|(&addr, f)| (f.name.clone(), addr)).collect();
+    for (caller, target) in targets {
+        let target = match target {
+            Target::Addr(addr) => addr,
+            Target::Name(name) => match by_name.get(&name) {
+                Some(&addr) => addr,
+                None => continue,
+            },
+        };
+        if functions.contains_key(&target) {
+            let callees = &mut functions.get_mut(&caller).expect("çağıran listede").callees;
+            if !callees.contains(&target) {
+                callees.push(target);
+            }
+        }
+    }
+    functions
+}
+
+/// Kökten başlayan en derin zincir.
+#[derive(Clone, Copy, Default)]
+struct Depth {
+    bytes: u64,
+    /// Zincirde dolaylı çağrı var: değer alt sınırdır.
+    partial: bool,
+    recursive: bool,
+}
+
+/// Çağrı başına çerçeve dışında kalan bayt (dönüş adresi).
+fn return_slot(arch: &str) -> u64 {
+    if arch == "amd64" {
+        8
+    } else {
+        0
+    }
+}
+
+struct Walk<'a> {
+    functions: &'a HashMap<u64, Function>,
+    return_slot: u64,
+    memo: HashMap<u64, Depth>,
+}
+
+impl Walk<'_> {
+    fn depth(&mut self, addr: u64) -> Depth {
+        self.visit(addr, &mut Vec::new())
+    }
+
+    fn visit(&mut self, addr: u64, active: &mut Vec<u64>) -> Depth {
+        if let Some(depth) = self.memo.get(&addr) {
+            return *depth;
+        }
+        if active.contains(&addr) {
+            return Depth { recursive: true, ..Default::default() };
+        }
+        let Some(function) = self.functions.get(&addr) else { return Depth::default() };
+        active.push(addr);
+        let mut worst = Depth::default();
+        for &callee in &function.callees {
+            let d = self.visit(callee, active);
+            worst.partial |= d.partial;
+            worst.recursive |= d.recursive;
+            worst.bytes = worst.bytes.max(d.bytes);
+        }
+        active.pop();
+        let result = Depth {
+            bytes: function.frame + self.return_slot + worst.bytes,
+            partial: worst.partial || function.indirect,
+            recursive: worst.recursive,
+        };
+        // Özyineleme içindeki ara sonuçlar eksiktir; saklanmaz
+        if !result.recursive {
+            self.memo.insert(addr, result);
+        }
+        result
+    }
+}
+
+// -----------------------------------------------------------------------------
+// ÖLÇÜLEN KULLANIM (`/proc/stackusage`)
+// -----------------------------------------------------------------------------
+
+/// `/proc/stackusage` satırı.
+struct Measured {
+    kind: String,
+    label: String,
+    entry: Option<u64>,
+    used: u64,
+    size: u64,
+}
+
+/// `task <id> <ad> <giriş> <kullanılan> <boyut>` ve
+/// `irq <cpu> <kullanılan> <boyut>` satırlarını okur.
+fn parse_measured(text: &str) -> Vec<Measured> {
+    text.lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let number = |i: usize| fields.get(i).and_then(|f| f.parse::<u64>().ok());
+            match fields.first().copied() {
+                Some("task") if fields.len() == 6 => Some(Measured {
+                    kind: "görev".into(),
+                    label: format!("{} {}", fields[1], fields[2]),
+                    entry: hex(fields[3]),
+                    used: number(4)?,
+                    size: number(5)?,
+                }),
+                Some("irq") if fields.len() == 4 => Some(Measured {
+                    kind: "kesme".into(),
+                    label: format!("cpu{}", fields[1]),
+                    entry: None,
+                    used: number(2)?,
+                    size: number(3)?,
+                }),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+// -----------------------------------------------------------------------------
+// RAPOR
+// -----------------------------------------------------------------------------
+
+pub struct Options<'a> {
+    /// En büyük çerçeveli kaç işlev listelensin.
+    pub top: usize,
+    pub roots: &'a [String],
+    pub measured: Option<&'a Path>,
+}
+
+/// Mimari istisna/kesme işleyicisi mi?
+fn is_handler(name: &str) -> bool {
+    let last = name.rsplit("::").next().unwrap_or(name);
+    last.starts_with("generic_") && (last.contains("handler") || last.contains("vector"))
+}
+
+fn is_default_root(name: &str) -> bool {
+    is_handler(name) || name.rsplit("::").next() == Some("task_entry")
+}
+
+fn mark(depth: &Depth) -> &'static str {
+    match (depth.recursive, depth.partial) {
+        (true, _) => " özyineleme",
+        (false, true) => "+",
+        _ => "",
+    }
+}
+
+/// ELF'i çözümleyip raporu yazdırır.
+pub fn report(arch: &str, elf_path: &Path, objdump: &str, options: &Options) -> Result<()> {
+    let data = fs::read(elf_path).map_err(|e| format!("{}: {}", elf_path.display(), e))?;
+    let sizes = stack_sizes(&Elf::parse(&data)?)?;
+
+    let output = Command::new(objdump)
+        .args(["-d", "--demangle", "--no-show-raw-insn"])
+        .arg(elf_path)
+        .output()
+        .map_err(|e| format!("{} çalıştırılamadı: {}", objdump, e))?;
+    if !output.status.success() {
+        return Err(format!("{} başarısız oldu ({})", objdump, output.status));
+    }
+    let functions = call_graph(&String::from_utf8_lossy(&output.stdout), &sizes);
+    let measured = match options.measured {
+        Some(path) => parse_measured(&fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?),
+        None => Vec::new(),
+    };
+
+    println!("[xtask] {}: {} işlev, {} çerçeve kaydı", arch, functions.len(), sizes.len());
+
+    let mut frames: Vec<&Function> = functions.values().filter(|f| f.frame != UNKNOWN).collect();
+    frames.sort_by(|a, b| b.frame.cmp(&a.frame).then_with(|| a.name.cmp(&b.name)));
+    println!();
+    println!("  ÇERÇEVE  İŞLEV");
+    for function in frames.iter().take(options.top) {
+        println!("  {:>7}  {}", function.frame, function.name);
+    }
+
+    // Kökler: varsayılan adlar, `--root` ve ölçümdeki görev girişleri
+    let mut roots: Vec<u64> = functions
+        .iter()
+        .filter(|(_, f)| is_default_root(&f.name) || options.roots.iter().any(|r| f.name == *r || f.name.ends_with(&format!("::{}", r))))
+        .map(|(&addr, _)| addr)
+        .collect();
+    roots.extend(measured.iter().filter_map(|m| m.entry).filter(|e| functions.contains_key(e)));
+    roots.sort_by(|a, b| functions[a].name.cmp(&functions[b].name));
+    roots.dedup();
+
+    let mut walk = Walk { functions: &functions, return_slot: return_slot(arch), memo: HashMap::new() };
+    println!();
+    println!("  EN KÖTÜ   KÖK");
+    let mut handlers = Depth::default();
+    for root in &roots {
+        let d = walk.depth(*root);
+        println!("  {:>7}{:<2} {}", d.bytes, mark(&d), functions[root].name);
+        if is_handler(&functions[root].name) {
+            handlers.bytes = handlers.bytes.max(d.bytes);
+            handlers.partial |= d.partial;
+            handlers.recursive |= d.recursive;
+        }
+    }
+
+    if !measured.is_empty() {
+        println!();
+        println!("  TÜR    YIĞIN                  ÖLÇÜLEN   BOYUT  STATİK EN KÖTÜ");
+        for m in &measured {
+            // Kesme yığınının tahmini en derin işleyicidir
+            let estimate = match m.entry {
+                None => Some(handlers).filter(|d| d.bytes != 0),
+                Some(entry) => functions.contains_key(&entry).then(|| walk.depth(entry)),
+            };
+            let estimate = estimate.map_or_else(|| "-".to_string(), |d| format!("{}{}", d.bytes, mark(&d)));
+            println!("  {:<6} {:<20} {:>8} {:>7}  {}", m.kind, m.label, m.used, m.size, estimate);
+        }
+    }
+    println!();
+    println!("  +: zincirde dolaylı çağrı var, değer alt sınırdır; özyineleme: zincir sınırsız olabilir");
+    Ok(())
+}
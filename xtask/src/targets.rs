@@ -0,0 +1,166 @@
+#![allow(dead_code)] // Geliştirme aşaması için uyarıları gizler
+
+// xtask/src/targets.rs
+// Özel hedef tanımlarının (target spec JSON) kaynağı.
+//
+// Rust'ın yerleşik çıplak donanım hedefi olmayan mimariler için hedef
+// tanımları burada tutulur; `targets/*.json` dosyaları bu tablodan üretilir
+// ve elle düzenlenmez:
+//
+//   cargo xtask targets           # dosyaları yeniden yazar
+//   cargo xtask targets --check   # dosyalar tabloyla aynı mı? (CI)
+//
+// `build` özel hedefli bir mimaride önce dosyanın güncel olduğunu denetler;
+// böylece derleme her makinede aynı tanımla yapılır.
+//
+// Ortak seçimler (tüm çekirdek hedefleri):
+// * `os: none`, `panic-strategy: abort`, `relocation-model: static`.
+// * Kayan nokta kapalıdır (`+soft-float` / `-fpu`): bağlam geçişi FP
+//   yazmaçlarını kaydetmez, derleyici de onları kullanmamalıdır.
+// * Kırmızı bölge (red zone) kapalıdır: kesmeler aynı yığında çalışır.
+// * Bağlayıcı `rust-lld`'dir; dış araç zinciri gerekmez.
+//
+// OpenRISC için yukarı akış LLVM'de arka uç yoktur; tanım, OpenRISC arka
+// uçlu bir araç zinciriyle (`--toolchain`) kullanılmak üzere tutulur.
+
+/// Hedefin derleyici desteği.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    /// Yukarı akış rustc/LLVM bu mimariyi derler (`-Z build-std` ile).
+    Upstream,
+    /// Arka uç yalnızca ağaç dışı bir LLVM çatalında var.
+    OutOfTree,
+}
+
+/// Bir özel hedef tanımı; alanlar rustc hedef JSON'undaki anahtarlarla aynıdır.
+pub struct TargetSpec {
+    /// Dosya adı kökü (`targets/<name>.json`) ve Cargo'nun hedef dizini.
+    pub name: &'static str,
+    pub llvm_target: &'static str,
+    pub arch: &'static str,
+    pub cpu: &'static str,
+    pub features: &'static str,
+    pub data_layout: &'static str,
+    pub endian: &'static str,
+    pub pointer_width: u32,
+    pub max_atomic_width: u32,
+    /// `llvm-abiname` ve `abi` (boşsa yazılmaz).
+    pub abi: &'static str,
+    pub llvm_abiname: &'static str,
+    /// `code-model` (boşsa LLVM varsayılanı).
+    pub code_model: &'static str,
+    pub backend: Backend,
+}
+
+pub const TARGETS: &[TargetSpec] = &[
+    TargetSpec {
+        name: "sparc64-sahne-none",
+        llvm_target: "sparc64-unknown-none-elf",
+        arch: "sparc64",
+        cpu: "v9",
+        features: "+soft-float",
+        data_layout: "E-m:e-i64:64-i128:128-n32:64-S128",
+        endian: "big",
+        pointer_width: 64,
+        max_atomic_width: 64,
+        abi: "",
+        llvm_abiname: "",
+        // Çekirdek 4 MiB'a bağlanır (`linker.ld`): 32 bitlik mutlak adresler yeter
+        code_model: "small",
+        backend: Backend::Upstream,
+    },
+    TargetSpec {
+        name: "powerpc64-sahne-none",
+        llvm_target: "powerpc64-unknown-none-elf",
+        arch: "powerpc64",
+        cpu: "ppc64",
+        features: "-altivec,-vsx,-hard-float",
+        // Çıplak donanım üçlüsünün LLVM varsayılanı (Linux üçlüsü ayrıca S128 ve
+        // vektör hizası ekler)
+        data_layout: "E-m:e-Fi64-i64:64-i128:128-n32:64",
+        endian: "big",
+        pointer_width: 64,
+        max_atomic_width: 64,
+        // Bağlam kodu TOC (r2) ve işlev tanımlayıcılarıyla çalışır: ELFv1
+        abi: "elfv1",
+        llvm_abiname: "elfv1",
+        code_model: "",
+        backend: Backend::Upstream,
+    },
+    TargetSpec {
+        name: "mips64-sahne-none",
+        llvm_target: "mips64-unknown-none",
+        arch: "mips64",
+        cpu: "mips64r2",
+        features: "+mips64r2,+soft-float,+noabicalls",
+        data_layout: "E-m:e-i8:8:32-i16:16:32-i64:64-i128:128-n32:64-S128",
+        endian: "big",
+        pointer_width: 64,
+        max_atomic_width: 64,
+        abi: "abi64",
+        llvm_abiname: "n64",
+        code_model: "",
+        backend: Backend::Upstream,
+    },
+    TargetSpec {
+        name: "or1k-sahne-none",
+        llvm_target: "or1k-unknown-elf",
+        arch: "or1k",
+        cpu: "",
+        features: "",
+        data_layout: "E-m:e-p:64:64-i64:64-n32:64-S64",
+        endian: "big",
+        pointer_width: 64,
+        max_atomic_width: 64,
+        abi: "",
+        llvm_abiname: "",
+        code_model: "",
+        backend: Backend::OutOfTree,
+    },
+];
+
+impl TargetSpec {
+    pub fn find(name: &str) -> Option<&'static TargetSpec> {
+        TARGETS.iter().find(|t| t.name == name)
+    }
+
+    /// Depo köküne göreli dosya yolu.
+    pub fn path(&self) -> String {
+        format!("targets/{}.json", self.name)
+    }
+
+    /// rustc hedef JSON'u; anahtarlar alfabetik sıradadır (`--print
+    /// target-spec-json` çıktısıyla karşılaştırılabilsin).
+    pub fn to_json(&self) -> String {
+        let mut fields: Vec<(&str, String)> = vec![
+            ("arch", quote(self.arch)),
+            ("data-layout", quote(self.data_layout)),
+            ("disable-redzone", "true".into()),
+            ("linker", quote("rust-lld")),
+            ("linker-flavor", quote("gnu-lld")),
+            ("llvm-target", quote(self.llvm_target)),
+            ("max-atomic-width", self.max_atomic_width.to_string()),
+            ("os", quote("none")),
+            ("panic-strategy", quote("abort")),
+            ("relocation-model", quote("static")),
+            ("target-endian", quote(self.endian)),
+            ("target-pointer-width", self.pointer_width.to_string()),
+        ];
+        let optional = [
+            ("abi", self.abi),
+            ("code-model", self.code_model),
+            ("cpu", self.cpu),
+            ("features", self.features),
+            ("llvm-abiname", self.llvm_abiname),
+        ];
+        fields.extend(optional.iter().filter(|(_, v)| !v.is_empty()).map(|&(k, v)| (k, quote(v))));
+        fields.sort_by_key(|&(k, _)| k);
+
+        let body: Vec<String> = fields.iter().map(|(k, v)| format!("  \"{}\": {}", k, v)).collect();
+        format!("{{\n{}\n}}\n", body.join(",\n"))
+    }
+}
+
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value)
+}